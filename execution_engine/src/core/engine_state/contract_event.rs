@@ -0,0 +1,97 @@
+//! Events emitted by contracts while executing a deploy.
+use datasize::DataSize;
+use schemars::JsonSchema;
+use serde::{de::Error as SerdeError, Deserialize, Deserializer, Serialize, Serializer};
+
+use casper_types::{CLValue, Key};
+
+/// The maximum length in bytes of the name of a contract event.
+pub const CONTRACT_EVENT_NAME_MAX_LENGTH: usize = 64;
+
+/// An event emitted by a contract via the `casper_emit_event` host function.
+///
+/// Events are recorded in the order in which they were emitted, and only successful session
+/// executions yield any.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug, DataSize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ContractEvent {
+    /// The contract or account whose code emitted the event, as a formatted `Key`.
+    #[serde(with = "key_as_formatted_string")]
+    #[schemars(with = "String")]
+    emitter: Key,
+    /// The name of the event.
+    name: String,
+    /// The event's data.
+    value: CLValue,
+}
+
+impl ContractEvent {
+    /// Creates a new contract event.
+    pub fn new(emitter: Key, name: String, value: CLValue) -> Self {
+        ContractEvent {
+            emitter,
+            name,
+            value,
+        }
+    }
+
+    /// Returns the contract or account whose code emitted the event.
+    pub fn emitter(&self) -> Key {
+        self.emitter
+    }
+
+    /// Returns the name of the event.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the event's data.
+    pub fn value(&self) -> &CLValue {
+        &self.value
+    }
+}
+
+/// Human-readable formats represent the key as its formatted string, e.g. "hash-0202..02".
+mod key_as_formatted_string {
+    use super::*;
+
+    pub(super) fn serialize<S: Serializer>(key: &Key, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            key.to_formatted_string().serialize(serializer)
+        } else {
+            key.serialize(serializer)
+        }
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Key, D::Error> {
+        if deserializer.is_human_readable() {
+            let formatted_key = String::deserialize(deserializer)?;
+            Key::from_formatted_str(&formatted_key).map_err(SerdeError::custom)
+        } else {
+            Key::deserialize(deserializer)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_serialize_emitter_as_formatted_key() {
+        let event = ContractEvent::new(
+            Key::Hash([2; 32]),
+            "transfer".to_string(),
+            CLValue::from_t(1u64).unwrap(),
+        );
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["emitter"], Key::Hash([2; 32]).to_formatted_string());
+        let decoded: ContractEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, event);
+
+        let bytes = bincode::serialize(&event).unwrap();
+        let decoded: ContractEvent = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(decoded, event);
+    }
+}
@@ -1,6 +1,10 @@
 //! Support for runtime configuration of the execution engine - as an integral property of the
 //! `EngineState` instance.
-use crate::shared::{system_config::SystemConfig, wasm_config::WasmConfig};
+use crate::shared::{
+    fee_handling::{FeeHandling, RefundHandling, DEFAULT_FEE_HANDLING, DEFAULT_REFUND_HANDLING},
+    system_config::SystemConfig,
+    wasm_config::WasmConfig,
+};
 
 /// Default value for a maximum query depth configuration option.
 pub const DEFAULT_MAX_QUERY_DEPTH: u64 = 5;
@@ -19,6 +23,8 @@ const DAY_MILLIS: usize = 24 * 60 * 60 * 1000;
 /// Default length of total vesting schedule period expressed in days.
 pub const DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS: u64 =
     VESTING_SCHEDULE_LENGTH_DAYS as u64 * DAY_MILLIS as u64;
+/// Default value for allowing validators to receive rewards into a separate purse.
+pub const DEFAULT_ALLOW_SEPARATE_REWARD_PURSES: bool = false;
 
 /// The runtime configuration of the execution engine
 #[derive(Debug, Copy, Clone)]
@@ -35,6 +41,13 @@ pub struct EngineConfig {
     strict_argument_checking: bool,
     /// Vesting schedule period in milliseconds.
     vesting_schedule_period_millis: u64,
+    /// This flag indicates if validators may have their rewards paid into a purse other than their
+    /// bonding purse.
+    allow_separate_reward_purses: bool,
+    /// How the unspent part of a deploy's payment is handled.
+    refund_handling: RefundHandling,
+    /// How the fees paid for executing a deploy are handled.
+    fee_handling: FeeHandling,
     wasm_config: WasmConfig,
     system_config: SystemConfig,
 }
@@ -48,6 +61,9 @@ impl Default for EngineConfig {
             minimum_delegation_amount: DEFAULT_MINIMUM_DELEGATION_AMOUNT,
             strict_argument_checking: DEFAULT_STRICT_ARGUMENT_CHECKING,
             vesting_schedule_period_millis: DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS,
+            allow_separate_reward_purses: DEFAULT_ALLOW_SEPARATE_REWARD_PURSES,
+            refund_handling: DEFAULT_REFUND_HANDLING,
+            fee_handling: DEFAULT_FEE_HANDLING,
             wasm_config: WasmConfig::default(),
             system_config: SystemConfig::default(),
         }
@@ -64,6 +80,9 @@ impl EngineConfig {
         minimum_delegation_amount: u64,
         strict_argument_checking: bool,
         vesting_schedule_period_millis: u64,
+        allow_separate_reward_purses: bool,
+        refund_handling: RefundHandling,
+        fee_handling: FeeHandling,
         wasm_config: WasmConfig,
         system_config: SystemConfig,
     ) -> EngineConfig {
@@ -74,6 +93,9 @@ impl EngineConfig {
             minimum_delegation_amount,
             strict_argument_checking,
             vesting_schedule_period_millis,
+            allow_separate_reward_purses,
+            refund_handling,
+            fee_handling,
             wasm_config,
             system_config,
         }
@@ -113,4 +135,19 @@ impl EngineConfig {
     pub fn vesting_schedule_period_millis(&self) -> u64 {
         self.vesting_schedule_period_millis
     }
+
+    /// Get the engine config's flag allowing validators to designate a separate reward purse.
+    pub fn allow_separate_reward_purses(&self) -> bool {
+        self.allow_separate_reward_purses
+    }
+
+    /// Returns how the unspent part of a deploy's payment is handled.
+    pub fn refund_handling(&self) -> RefundHandling {
+        self.refund_handling
+    }
+
+    /// Returns how the fees paid for executing a deploy are handled.
+    pub fn fee_handling(&self) -> FeeHandling {
+        self.fee_handling
+    }
 }
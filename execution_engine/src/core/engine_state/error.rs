@@ -98,6 +98,9 @@ pub enum Error {
     /// Failed to retrieve the current EraId from the auction state.
     #[error("Failed to retrieve the era_id from the auction state")]
     FailedToRetrieveEraId,
+    /// The effects of session code failed the engine's [`super::SessionEffectsCheck`].
+    #[error("Session effects rejected: {0}")]
+    SessionEffectsRejected(String),
 }
 
 impl Error {
@@ -6,7 +6,7 @@ use casper_types::{
     bytesrepr::FromBytes, CLTyped, CLValue, Gas, Key, Motes, StoredValue, TransferAddr,
 };
 
-use super::error;
+use super::{error, ContractEvent};
 use crate::{
     core::execution::Error as ExecError,
     shared::{execution_journal::ExecutionJournal, transform::Transform},
@@ -58,6 +58,8 @@ pub enum ExecutionResult {
         cost: Gas,
         /// Journal of execution.
         execution_journal: ExecutionJournal,
+        /// Events emitted by contracts, in the order in which they were emitted.
+        events: Vec<ContractEvent>,
     },
 }
 
@@ -67,6 +69,7 @@ impl Default for ExecutionResult {
             execution_journal: Default::default(),
             transfers: Default::default(),
             cost: Default::default(),
+            events: Default::default(),
         }
     }
 }
@@ -144,6 +147,14 @@ impl ExecutionResult {
         }
     }
 
+    /// Returns the events emitted by contracts, which is empty for the failure variant.
+    pub fn events(&self) -> &[ContractEvent] {
+        match self {
+            ExecutionResult::Failure { .. } => &[],
+            ExecutionResult::Success { events, .. } => events,
+        }
+    }
+
     /// The journal of transforms regardless of variant.
     pub fn execution_journal(&self) -> &ExecutionJournal {
         match self {
@@ -176,11 +187,13 @@ impl ExecutionResult {
             ExecutionResult::Success {
                 transfers,
                 execution_journal,
+                events,
                 ..
             } => ExecutionResult::Success {
                 transfers,
                 cost,
                 execution_journal,
+                events,
             },
         }
     }
@@ -205,11 +218,13 @@ impl ExecutionResult {
             ExecutionResult::Success {
                 cost,
                 execution_journal,
+                events,
                 ..
             } => ExecutionResult::Success {
                 transfers,
                 cost,
                 execution_journal,
+                events,
             },
         }
     }
@@ -235,10 +250,12 @@ impl ExecutionResult {
                 transfers,
                 cost,
                 execution_journal: _,
+                events,
             } => ExecutionResult::Success {
                 transfers,
                 cost,
                 execution_journal,
+                events,
             },
         }
     }
@@ -349,6 +366,7 @@ impl From<&ExecutionResult> for casper_types::ExecutionResult {
                 transfers,
                 cost,
                 execution_journal,
+                ..
             } => casper_types::ExecutionResult::Success {
                 effect: execution_journal.into(),
                 transfers: transfers.clone(),
@@ -376,6 +394,7 @@ impl From<ExecutionResult> for casper_types::ExecutionResult {
                 transfers,
                 cost,
                 execution_journal,
+                ..
             } => casper_types::ExecutionResult::Success {
                 effect: execution_journal.into(),
                 transfers,
@@ -484,6 +503,7 @@ impl ExecutionResultBuilder {
     pub fn build(self) -> Result<ExecutionResult, ExecutionResultBuilderError> {
         let mut error: Option<error::Error> = None;
         let mut transfers = self.transfers();
+        let mut events = Vec::new();
         let cost = self.total_cost();
 
         let mut journal = match self.payment_execution_result {
@@ -507,8 +527,13 @@ impl ExecutionResultBuilder {
                 transfers = session_transfers;
             }
             Some(ExecutionResult::Success {
-                execution_journal, ..
-            }) => journal.extend(execution_journal.into_iter()),
+                execution_journal,
+                events: session_events,
+                ..
+            }) => {
+                journal.extend(execution_journal.into_iter());
+                events = session_events;
+            }
             None => return Err(ExecutionResultBuilderError::MissingSessionExecutionResult),
         };
 
@@ -530,6 +555,7 @@ impl ExecutionResultBuilder {
                 transfers,
                 cost,
                 execution_journal: journal,
+                events,
             }),
             Some(error) => Ok(ExecutionResult::Failure {
                 error,
@@ -0,0 +1,140 @@
+//! Support for profiling the gas consumed while executing deploys.
+use std::{collections::BTreeMap, fmt};
+
+use casper_types::Gas;
+
+/// A class of Wasm opcodes sharing a single cost in the chainspec's opcode cost table.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum OpcodeClass {
+    /// Bit operations.
+    Bit,
+    /// Arithmetic add operations.
+    Add,
+    /// Mul operations.
+    Mul,
+    /// Div operations.
+    Div,
+    /// Memory load operations.
+    Load,
+    /// Memory store operations.
+    Store,
+    /// Const operations.
+    Const,
+    /// Local operations.
+    Local,
+    /// Global operations.
+    Global,
+    /// Control flow operations.
+    ControlFlow,
+    /// Integer comparison operations.
+    IntegerComparison,
+    /// Conversion operations.
+    Conversion,
+    /// The unreachable operation.
+    Unreachable,
+    /// The nop operation.
+    Nop,
+    /// The current memory operation.
+    CurrentMemory,
+    /// The grow memory operation, including the per-page cost of the memory grown.
+    GrowMemory,
+    /// Operations charged at the regular opcode cost.
+    Regular,
+}
+
+impl fmt::Display for OpcodeClass {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            OpcodeClass::Bit => "bit",
+            OpcodeClass::Add => "add",
+            OpcodeClass::Mul => "mul",
+            OpcodeClass::Div => "div",
+            OpcodeClass::Load => "load",
+            OpcodeClass::Store => "store",
+            OpcodeClass::Const => "const",
+            OpcodeClass::Local => "local",
+            OpcodeClass::Global => "global",
+            OpcodeClass::ControlFlow => "control_flow",
+            OpcodeClass::IntegerComparison => "integer_comparison",
+            OpcodeClass::Conversion => "conversion",
+            OpcodeClass::Unreachable => "unreachable",
+            OpcodeClass::Nop => "nop",
+            OpcodeClass::CurrentMemory => "current_memory",
+            OpcodeClass::GrowMemory => "grow_memory",
+            OpcodeClass::Regular => "regular",
+        };
+        write!(formatter, "{}", name)
+    }
+}
+
+/// The gas consumed by calls to a single host function.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct HostFunctionGas {
+    /// The number of times the host function was called.
+    pub calls: u64,
+    /// The total gas charged while executing the host function, including any storage and system
+    /// contract costs it incurred.
+    pub cost: Gas,
+}
+
+/// A breakdown of the gas consumed while executing deploys.
+///
+/// Opcode costs are charged by the engine per metered block of Wasm rather than per opcode.  The
+/// cost of each block is attributed to the opcode classes it contains in proportion to their costs
+/// in the opcode cost table, so the totals per class are exact to within rounding, but the
+/// attribution within a block is an estimate.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct GasProfile {
+    host_functions: BTreeMap<String, HostFunctionGas>,
+    opcode_classes: BTreeMap<OpcodeClass, Gas>,
+    other: Gas,
+}
+
+impl GasProfile {
+    /// Returns the gas consumed per host function, keyed by the host function's import name.
+    pub fn host_functions(&self) -> &BTreeMap<String, HostFunctionGas> {
+        &self.host_functions
+    }
+
+    /// Returns the gas consumed per class of Wasm opcodes.
+    pub fn opcode_classes(&self) -> &BTreeMap<OpcodeClass, Gas> {
+        &self.opcode_classes
+    }
+
+    /// Returns the gas charged outside of any host function or Wasm opcode, e.g. by system
+    /// contracts called directly by the engine.
+    pub fn other(&self) -> Gas {
+        self.other
+    }
+
+    /// Returns the total gas recorded in the profile.
+    pub fn total(&self) -> Gas {
+        let host_functions = self.host_functions.values().map(|usage| usage.cost);
+        self.opcode_classes
+            .values()
+            .copied()
+            .chain(host_functions)
+            .fold(self.other, |total, cost| total + cost)
+    }
+
+    pub(crate) fn record_host_function_call(&mut self, name: &str) {
+        self.host_functions
+            .entry(name.to_string())
+            .or_default()
+            .calls += 1;
+    }
+
+    pub(crate) fn record_host_function_cost(&mut self, name: &str, cost: Gas) {
+        let usage = self.host_functions.entry(name.to_string()).or_default();
+        usage.cost += cost;
+    }
+
+    pub(crate) fn record_opcode_cost(&mut self, class: OpcodeClass, cost: Gas) {
+        let total = self.opcode_classes.entry(class).or_default();
+        *total += cost;
+    }
+
+    pub(crate) fn record_other_cost(&mut self, cost: Gas) {
+        self.other += cost;
+    }
+}
@@ -0,0 +1,51 @@
+//! Support for querying the balances of many purses at once.
+use casper_hashing::Digest;
+use casper_types::{account::AccountHash, URef, U512};
+
+use crate::core::engine_state::Error;
+
+/// Identifies a purse whose balance is requested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BalanceIdentifier {
+    /// The purse identified by this [`URef`].
+    Purse(URef),
+    /// The main purse of the account identified by this account hash.
+    MainPurseUnderAccountHash(AccountHash),
+}
+
+/// Represents a request to obtain the balances of a number of purses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetBalancesRequest {
+    state_hash: Digest,
+    purses: Vec<BalanceIdentifier>,
+}
+
+impl GetBalancesRequest {
+    /// Creates new request.
+    pub fn new(state_hash: Digest, purses: Vec<BalanceIdentifier>) -> Self {
+        GetBalancesRequest { state_hash, purses }
+    }
+
+    /// Returns state root hash.
+    pub fn state_hash(&self) -> Digest {
+        self.state_hash
+    }
+
+    /// Returns the identifiers of the purses whose balances are requested.
+    pub fn purses(&self) -> &[BalanceIdentifier] {
+        &self.purses
+    }
+}
+
+/// Represents a result of a `get_balances` request.
+#[derive(Debug)]
+pub enum GetBalancesResult {
+    /// Invalid state root hash.
+    RootNotFound,
+    /// Contains the balance of each requested purse, in the order requested, or the error
+    /// encountered while reading it.
+    Success {
+        /// The balances of the requested purses, in motes.
+        balances: Vec<Result<U512, Error>>,
+    },
+}
@@ -0,0 +1,120 @@
+//! Support for enumerating the items of a dictionary.
+use casper_hashing::Digest;
+use casper_types::{bytesrepr::Bytes, CLValue, DictionaryAddr, Key, URef};
+
+/// The number of leading bytes of a dictionary key shared by the keys scanned together.
+pub const DICTIONARY_KEY_BUCKET_PREFIX_LENGTH: usize = 2;
+
+/// The maximum number of dictionary keys inspected by a single request.
+pub const MAX_DICTIONARY_KEYS_INSPECTED: usize = 1_000;
+
+/// The maximum number of buckets of dictionary keys scanned by a single request.
+pub const MAX_DICTIONARY_KEY_BUCKETS_SCANNED: usize = 256;
+
+/// Represents a request to obtain the items of a dictionary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetDictionaryItemsRequest {
+    state_hash: Digest,
+    seed_uref: URef,
+    after: Option<DictionaryAddr>,
+    limit: usize,
+}
+
+impl GetDictionaryItemsRequest {
+    /// Creates new request.
+    ///
+    /// At most `limit` items are returned, and if `after` is provided, only items stored under a
+    /// dictionary key ordered after it are returned.
+    pub fn new(
+        state_hash: Digest,
+        seed_uref: URef,
+        after: Option<DictionaryAddr>,
+        limit: usize,
+    ) -> Self {
+        GetDictionaryItemsRequest {
+            state_hash,
+            seed_uref,
+            after,
+            limit,
+        }
+    }
+
+    /// Returns state root hash.
+    pub fn state_hash(&self) -> Digest {
+        self.state_hash
+    }
+
+    /// Returns the seed [`URef`] of the dictionary.
+    pub fn seed_uref(&self) -> URef {
+        self.seed_uref
+    }
+
+    /// Returns the dictionary key after which items are returned, if any.
+    pub fn after(&self) -> Option<DictionaryAddr> {
+        self.after
+    }
+
+    /// Returns the maximum number of items to return.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+}
+
+/// An item of a dictionary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DictionaryItem {
+    /// The `Key::Dictionary` under which the item is stored.
+    pub key: Key,
+    /// The bytes of the key the item was stored with by the contract.
+    pub dictionary_item_key: Bytes,
+    /// The value of the item.
+    pub value: CLValue,
+}
+
+/// Represents a result of a `get_dictionary_items` request.
+#[derive(Debug)]
+pub enum GetDictionaryItemsResult {
+    /// Invalid state root hash.
+    RootNotFound,
+    /// Contains the requested items of the dictionary.
+    Success {
+        /// The items, ordered by their dictionary key.
+        items: Vec<DictionaryItem>,
+        /// The dictionary key up to which the dictionary keyspace was scanned, if it was not
+        /// scanned to its end, to be passed as `after` to continue the scan.
+        next_page_after: Option<DictionaryAddr>,
+    },
+}
+
+/// Returns the bucket of dictionary keys the given one belongs to.
+pub(crate) fn dictionary_key_bucket(addr: &DictionaryAddr) -> u16 {
+    let mut bucket = [0; DICTIONARY_KEY_BUCKET_PREFIX_LENGTH];
+    bucket.copy_from_slice(&addr[..DICTIONARY_KEY_BUCKET_PREFIX_LENGTH]);
+    u16::from_be_bytes(bucket)
+}
+
+/// Returns the greatest dictionary key in the given bucket.
+pub(crate) fn last_dictionary_addr_in_bucket(bucket: u16) -> DictionaryAddr {
+    let mut addr = [u8::MAX; 32];
+    addr[..DICTIONARY_KEY_BUCKET_PREFIX_LENGTH].copy_from_slice(&bucket.to_be_bytes());
+    addr
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_map_dictionary_keys_to_buckets() {
+        let mut addr = [7; 32];
+        addr[0] = 1;
+        addr[1] = 2;
+        assert_eq!(dictionary_key_bucket(&addr), 0x0102);
+        let last = last_dictionary_addr_in_bucket(0x0102);
+        assert_eq!(dictionary_key_bucket(&last), 0x0102);
+        assert!(addr < last);
+        assert!(last < last_dictionary_addr_in_bucket(0x0103));
+        assert_eq!(dictionary_key_bucket(&[0; 32]), 0);
+        assert_eq!(last_dictionary_addr_in_bucket(u16::MAX), [u8::MAX; 32]);
+    }
+}
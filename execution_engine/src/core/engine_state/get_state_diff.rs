@@ -0,0 +1,80 @@
+//! Support for finding the keys changed between two global states.
+use casper_hashing::Digest;
+use casper_types::{Key, StoredValue};
+
+/// Represents a request to obtain the keys whose values differ between two global states.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetStateDiffRequest {
+    old_state_hash: Digest,
+    new_state_hash: Digest,
+    after: Option<Key>,
+    limit: usize,
+}
+
+impl GetStateDiffRequest {
+    /// Creates new request.
+    ///
+    /// At most `limit` changes are returned, and if `after` is provided, only changes to keys
+    /// ordered after it are returned.
+    pub fn new(
+        old_state_hash: Digest,
+        new_state_hash: Digest,
+        after: Option<Key>,
+        limit: usize,
+    ) -> Self {
+        GetStateDiffRequest {
+            old_state_hash,
+            new_state_hash,
+            after,
+            limit,
+        }
+    }
+
+    /// Returns the state root hash of the state compared from.
+    pub fn old_state_hash(&self) -> Digest {
+        self.old_state_hash
+    }
+
+    /// Returns the state root hash of the state compared to.
+    pub fn new_state_hash(&self) -> Digest {
+        self.new_state_hash
+    }
+
+    /// Returns the key after which changes are returned, if any.
+    pub fn after(&self) -> Option<Key> {
+        self.after
+    }
+
+    /// Returns the maximum number of changes to return.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+}
+
+/// A change to the value stored under a key between two global states.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateChange {
+    /// The key whose value changed.
+    pub key: Key,
+    /// The value stored under the key in the state compared from, if any.
+    pub old_value: Option<StoredValue>,
+    /// The value stored under the key in the state compared to, if any.
+    pub new_value: Option<StoredValue>,
+}
+
+/// Represents a result of a `get_state_diff` request.
+#[derive(Debug)]
+pub enum GetStateDiffResult {
+    /// One of the state root hashes is invalid.
+    RootNotFound(Digest),
+    /// A trie below one of the state roots is missing, e.g. as global state is still being
+    /// synchronized.
+    MissingTrie(Digest),
+    /// Contains the requested changes.
+    Success {
+        /// The changes, ordered by the serialized form of their keys.
+        changes: Vec<StateChange>,
+        /// The key of the last returned change if more changes are available.
+        next_page_after: Option<Key>,
+    },
+}
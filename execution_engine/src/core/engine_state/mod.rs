@@ -1,6 +1,7 @@
 //!  This module contains all the execution related code.
 pub mod balance;
 pub mod chainspec_registry;
+pub mod contract_event;
 pub mod deploy_item;
 pub mod engine_config;
 pub mod era_validators;
@@ -9,11 +10,17 @@ pub mod executable_deploy_item;
 pub mod execute_request;
 pub mod execution_effect;
 pub mod execution_result;
+pub mod gas_profile;
 pub mod genesis;
+pub mod get_balances;
 pub mod get_bids;
+pub mod get_dictionary_items;
+pub mod get_state_diff;
+pub mod module_cache;
 pub mod op;
 pub mod query;
 pub mod run_genesis_request;
+pub mod session_effects_check;
 pub mod step;
 pub mod system_contract_registry;
 mod transfer;
@@ -24,6 +31,7 @@ use std::{
     collections::{BTreeMap, BTreeSet},
     convert::TryFrom,
     rc::Rc,
+    sync::Arc,
 };
 
 use num::Zero;
@@ -47,13 +55,15 @@ use casper_types::{
         mint::{self, ROUND_SEIGNIORAGE_RATE_KEY},
         AUCTION, HANDLE_PAYMENT, MINT, STANDARD_PAYMENT,
     },
-    AccessRights, ApiError, BlockTime, CLValue, ContractHash, DeployHash, DeployInfo, Gas, Key,
-    KeyTag, Motes, Phase, ProtocolVersion, PublicKey, RuntimeArgs, StoredValue, URef, U512,
+    AccessRights, ApiError, BlockTime, CLValue, ContractHash, DeployHash, DeployInfo,
+    DictionaryAddr, Gas, Key, KeyTag, Motes, Phase, ProtocolVersion, PublicKey, RuntimeArgs,
+    StoredValue, URef, U512,
 };
 
 pub use self::{
     balance::{BalanceRequest, BalanceResult},
     chainspec_registry::ChainspecRegistry,
+    contract_event::{ContractEvent, CONTRACT_EVENT_NAME_MAX_LENGTH},
     deploy_item::DeployItem,
     engine_config::{EngineConfig, DEFAULT_MAX_QUERY_DEPTH, DEFAULT_MAX_RUNTIME_CALL_STACK_HEIGHT},
     era_validators::{GetEraValidatorsError, GetEraValidatorsRequest},
@@ -62,10 +72,20 @@ pub use self::{
     execute_request::ExecuteRequest,
     execution::Error as ExecError,
     execution_result::{ExecutionResult, ForcedTransferResult},
+    gas_profile::{GasProfile, HostFunctionGas, OpcodeClass},
     genesis::{ExecConfig, GenesisAccount, GenesisConfig, GenesisSuccess},
+    get_balances::{BalanceIdentifier, GetBalancesRequest, GetBalancesResult},
     get_bids::{GetBidsRequest, GetBidsResult},
+    get_dictionary_items::{
+        DictionaryItem, GetDictionaryItemsRequest, GetDictionaryItemsResult,
+        DICTIONARY_KEY_BUCKET_PREFIX_LENGTH, MAX_DICTIONARY_KEYS_INSPECTED,
+        MAX_DICTIONARY_KEY_BUCKETS_SCANNED,
+    },
+    get_state_diff::{GetStateDiffRequest, GetStateDiffResult, StateChange},
+    module_cache::{ModuleCache, ModuleCacheStats},
     query::{QueryRequest, QueryResult},
     run_genesis_request::RunGenesisRequest,
+    session_effects_check::SessionEffectsCheck,
     step::{RewardItem, SlashItem, StepError, StepRequest, StepSuccess},
     system_contract_registry::SystemContractRegistry,
     transfer::{TransferArgs, TransferRuntimeArgsBuilder, TransferTargetMode},
@@ -80,15 +100,18 @@ use crate::{
             upgrade::{ProtocolUpgradeError, SystemUpgrader},
         },
         execution::{self, DirectSystemContractCall, Executor},
-        runtime::RuntimeStack,
+        runtime::{GasProfiler, RuntimeStack},
+        runtime_context::dictionary::DictionaryValue,
         tracking_copy::{TrackingCopy, TrackingCopyExt},
     },
     shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
     storage::{
         global_state::{
             lmdb::LmdbGlobalState, scratch::ScratchGlobalState, CommitProvider, StateProvider,
+            StateReader,
         },
         trie::{TrieOrChunk, TrieOrChunkId},
+        trie_store::operations::DiffResult,
     },
     system::auction,
 };
@@ -115,6 +138,8 @@ pub const WASMLESS_TRANSFER_FIXED_GAS_PRICE: u64 = 1;
 pub struct EngineState<S> {
     config: EngineConfig,
     state: S,
+    module_cache: Option<Arc<ModuleCache>>,
+    session_effects_check: Option<Arc<dyn SessionEffectsCheck>>,
 }
 
 impl EngineState<ScratchGlobalState> {
@@ -143,6 +168,8 @@ impl EngineState<LmdbGlobalState> {
         EngineState {
             config: self.config,
             state: self.state.create_scratch(),
+            module_cache: self.module_cache.clone(),
+            session_effects_check: self.session_effects_check.clone(),
         }
     }
 
@@ -166,7 +193,38 @@ where
 {
     /// Creates new engine state.
     pub fn new(state: S, config: EngineConfig) -> EngineState<S> {
-        EngineState { config, state }
+        EngineState {
+            config,
+            state,
+            module_cache: None,
+            session_effects_check: None,
+        }
+    }
+
+    /// Creates new engine state which caches the Wasm modules it prepares for execution in
+    /// `module_cache`, so that deploys executing the same code skip preparing it again.
+    pub fn with_module_cache(
+        state: S,
+        config: EngineConfig,
+        module_cache: Arc<ModuleCache>,
+    ) -> EngineState<S> {
+        EngineState {
+            config,
+            state,
+            module_cache: Some(module_cache),
+            session_effects_check: None,
+        }
+    }
+
+    /// Sets a check which the effects of each deploy's session code must pass to be committed.
+    ///
+    /// See [`SessionEffectsCheck`].
+    pub fn with_session_effects_check(
+        mut self,
+        session_effects_check: Arc<dyn SessionEffectsCheck>,
+    ) -> Self {
+        self.session_effects_check = Some(session_effects_check);
+        self
     }
 
     /// Returns engine config.
@@ -174,9 +232,28 @@ where
         &self.config
     }
 
+    /// Returns the cache of prepared Wasm modules, if enabled.
+    pub fn module_cache(&self) -> Option<&Arc<ModuleCache>> {
+        self.module_cache.as_ref()
+    }
+
     /// Updates current engine config with a new instance.
+    ///
+    /// As cached modules were prepared according to the previous config, the module cache is
+    /// cleared.
     pub fn update_config(&mut self, new_config: EngineConfig) {
-        self.config = new_config
+        self.config = new_config;
+        if let Some(module_cache) = &self.module_cache {
+            module_cache.clear();
+        }
+    }
+
+    fn new_executor(&self) -> Executor {
+        let mut executor = Executor::new(*self.config());
+        if let Some(module_cache) = &self.module_cache {
+            executor.set_module_cache(Arc::clone(module_cache));
+        }
+        executor
     }
 
     /// Commits genesis process.
@@ -493,10 +570,38 @@ where
     pub fn run_execute(
         &self,
         correlation_id: CorrelationId,
-        mut exec_request: ExecuteRequest,
+        exec_request: ExecuteRequest,
     ) -> Result<ExecutionResults, Error> {
-        let executor = Executor::new(*self.config());
+        let executor = self.new_executor();
+        self.execute_deploys(correlation_id, &executor, exec_request)
+    }
+
+    /// Runs a deploy execution request, recording where the gas consumed by its deploys is spent.
+    ///
+    /// Returns the execution results as per [`EngineState::run_execute`], along with a breakdown
+    /// of the gas consumed by all the deploys in the request per host function and per class of
+    /// Wasm opcodes.
+    pub fn run_execute_with_gas_profile(
+        &self,
+        correlation_id: CorrelationId,
+        exec_request: ExecuteRequest,
+    ) -> Result<(ExecutionResults, GasProfile), Error> {
+        let gas_profiler = Rc::new(RefCell::new(GasProfiler::new(
+            self.config().wasm_config().opcode_costs(),
+        )));
+        let mut executor = self.new_executor();
+        executor.set_gas_profiler(Rc::clone(&gas_profiler));
+        let results = self.execute_deploys(correlation_id, &executor, exec_request)?;
+        let gas_profile = gas_profiler.borrow().profile().clone();
+        Ok((results, gas_profile))
+    }
 
+    fn execute_deploys(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        mut exec_request: ExecuteRequest,
+    ) -> Result<ExecutionResults, Error> {
         let deploys = exec_request.take_deploys();
         let mut results = ExecutionResults::with_capacity(deploys.len());
 
@@ -504,7 +609,7 @@ where
             let result = match deploy_item.session {
                 ExecutableDeployItem::Transfer { .. } => self.transfer(
                     correlation_id,
-                    &executor,
+                    executor,
                     exec_request.protocol_version,
                     exec_request.parent_state_hash,
                     BlockTime::new(exec_request.block_time),
@@ -513,7 +618,7 @@ where
                 ),
                 _ => self.deploy(
                     correlation_id,
-                    &executor,
+                    executor,
                     exec_request.protocol_version,
                     exec_request.parent_state_hash,
                     BlockTime::new(exec_request.block_time),
@@ -582,6 +687,37 @@ where
         Ok(BalanceResult::Success { motes, proof })
     }
 
+    /// Gets the balances of a number of purses, all read through a single view of global state.
+    ///
+    /// Unlike [`EngineState::get_purse_balance`], no Merkle proofs are provided.
+    pub fn get_balances(
+        &self,
+        correlation_id: CorrelationId,
+        get_balances_request: GetBalancesRequest,
+    ) -> Result<GetBalancesResult, Error> {
+        let mut tracking_copy = match self.tracking_copy(get_balances_request.state_hash())? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(GetBalancesResult::RootNotFound),
+        };
+        let balances = get_balances_request
+            .purses()
+            .iter()
+            .map(|balance_identifier| -> Result<U512, Error> {
+                let purse_uref = match balance_identifier {
+                    BalanceIdentifier::Purse(purse_uref) => *purse_uref,
+                    BalanceIdentifier::MainPurseUnderAccountHash(account_hash) => tracking_copy
+                        .get_account(correlation_id, *account_hash)?
+                        .main_purse(),
+                };
+                let purse_balance_key =
+                    tracking_copy.get_purse_balance_key(correlation_id, purse_uref.into())?;
+                let balance = tracking_copy.get_purse_balance(correlation_id, purse_balance_key)?;
+                Ok(balance.value())
+            })
+            .collect();
+        Ok(GetBalancesResult::Success { balances })
+    }
+
     /// Executes a native transfer.
     ///
     /// Native transfers do not involve WASM at all, and also skip executing payment code.
@@ -1477,6 +1613,25 @@ where
         };
         debug!("Session result: {:?}", session_result);
 
+        // Session effects failing the session effects check are discarded as if session code had
+        // failed, while payment and finalization effects are still committed.
+        if let Some(session_effects_check) = self
+            .session_effects_check
+            .as_ref()
+            .filter(|_| session_result.is_success())
+        {
+            let session_effects = session_tracking_copy.borrow().effect().transforms;
+            if let Err(violation) = session_effects_check.check(&session_effects) {
+                debug!(%violation, "session effects rejected");
+                session_result = ExecutionResult::Failure {
+                    error: Error::SessionEffectsRejected(violation),
+                    transfers: Vec::default(),
+                    cost: session_result.cost(),
+                    execution_journal: Default::default(),
+                };
+            }
+        }
+
         // Create + persist deploy info.
         {
             let transfers = session_result.transfers();
@@ -1804,6 +1959,159 @@ where
         Ok(GetBidsResult::Success { bids })
     }
 
+    /// Obtains the items of a dictionary.
+    ///
+    /// As dictionary keys are not derived from their seed URef alone, the items of a dictionary can
+    /// only be found by inspecting the keys of all dictionaries. To bound the work done per request,
+    /// the dictionary keyspace is scanned in buckets of keys sharing their first
+    /// [`DICTIONARY_KEY_BUCKET_PREFIX_LENGTH`] bytes, starting at the bucket of the requested
+    /// starting point, and the scan stops once `limit` items were found, or
+    /// [`MAX_DICTIONARY_KEYS_INSPECTED`] keys were inspected, or
+    /// [`MAX_DICTIONARY_KEY_BUCKETS_SCANNED`] buckets were scanned. A page can hence hold fewer
+    /// than `limit` items even though more items follow.
+    pub fn get_dictionary_items(
+        &self,
+        correlation_id: CorrelationId,
+        get_dictionary_items_request: GetDictionaryItemsRequest,
+    ) -> Result<GetDictionaryItemsResult, Error> {
+        let tracking_copy = match self.tracking_copy(get_dictionary_items_request.state_hash())? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(GetDictionaryItemsResult::RootNotFound),
+        };
+        let reader = tracking_copy.reader();
+
+        let seed_uref_addr = get_dictionary_items_request.seed_uref().addr();
+        let limit = get_dictionary_items_request.limit();
+        let after = get_dictionary_items_request.after();
+
+        let mut items = Vec::new();
+        let mut keys_inspected = 0;
+        // The key up to which the keyspace has been scanned.
+        let mut scanned_up_to = after;
+        let mut bucket = after
+            .as_ref()
+            .map_or(0, get_dictionary_items::dictionary_key_bucket);
+
+        for _ in 0..MAX_DICTIONARY_KEY_BUCKETS_SCANNED {
+            let mut prefix = vec![KeyTag::Dictionary as u8];
+            prefix.extend_from_slice(&bucket.to_be_bytes());
+            let mut dictionary_addrs: Vec<DictionaryAddr> = reader
+                .keys_with_prefix(correlation_id, &prefix)
+                .map_err(|err| Error::Exec(err.into()))?
+                .into_iter()
+                .filter_map(|key| match key {
+                    Key::Dictionary(addr) => Some(addr),
+                    _ => None,
+                })
+                .filter(|addr| after.map_or(true, |after| *addr > after))
+                .collect();
+            dictionary_addrs.sort_unstable();
+
+            for addr in dictionary_addrs {
+                if items.len() == limit || keys_inspected == MAX_DICTIONARY_KEYS_INSPECTED {
+                    return Ok(GetDictionaryItemsResult::Success {
+                        items,
+                        next_page_after: scanned_up_to,
+                    });
+                }
+                keys_inspected += 1;
+                scanned_up_to = Some(addr);
+
+                let key = Key::Dictionary(addr);
+                let cl_value = match reader.read(correlation_id, &key).map_err(Into::into)? {
+                    Some(StoredValue::CLValue(cl_value)) => cl_value,
+                    _ => continue,
+                };
+                let dictionary_value: DictionaryValue = match cl_value.into_t() {
+                    Ok(dictionary_value) => dictionary_value,
+                    Err(_) => continue,
+                };
+                if dictionary_value.seed_uref_addr().as_slice() != seed_uref_addr {
+                    continue;
+                }
+                items.push(DictionaryItem {
+                    key,
+                    dictionary_item_key: dictionary_value.dictionary_item_key_bytes().clone(),
+                    value: dictionary_value.into_cl_value(),
+                });
+            }
+
+            // Resume after this bucket next time, even if it held no keys.
+            scanned_up_to = Some(get_dictionary_items::last_dictionary_addr_in_bucket(bucket));
+            bucket = match bucket.checked_add(1) {
+                Some(next_bucket) => next_bucket,
+                // The whole keyspace has been scanned.
+                None => {
+                    return Ok(GetDictionaryItemsResult::Success {
+                        items,
+                        next_page_after: None,
+                    })
+                }
+            };
+        }
+
+        Ok(GetDictionaryItemsResult::Success {
+            items,
+            next_page_after: scanned_up_to,
+        })
+    }
+
+    /// Obtains the keys whose values differ between two global states.
+    ///
+    /// Subtries of global state shared by both states are skipped, so the cost of the comparison
+    /// depends on the number of changes rather than the size of global state.
+    pub fn get_state_diff(
+        &self,
+        correlation_id: CorrelationId,
+        get_state_diff_request: GetStateDiffRequest,
+    ) -> Result<GetStateDiffResult, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let old_state_hash = get_state_diff_request.old_state_hash();
+        let new_state_hash = get_state_diff_request.new_state_hash();
+        for state_hash in [old_state_hash, new_state_hash] {
+            if self.state.checkout(state_hash)?.is_none() {
+                return Ok(GetStateDiffResult::RootNotFound(state_hash));
+            }
+        }
+
+        let start_after = get_state_diff_request
+            .after()
+            .map(|key| key.to_bytes())
+            .transpose()?;
+        let limit = get_state_diff_request.limit();
+
+        match self.state.diff(
+            correlation_id,
+            old_state_hash,
+            new_state_hash,
+            start_after.as_deref(),
+            limit,
+        )? {
+            DiffResult::Found { changes, has_more } => {
+                let changes: Vec<StateChange> = changes
+                    .into_iter()
+                    .map(|change| StateChange {
+                        key: change.key,
+                        old_value: change.old_value,
+                        new_value: change.new_value,
+                    })
+                    .collect();
+                let next_page_after = if has_more {
+                    changes.last().map(|change| change.key)
+                } else {
+                    None
+                };
+                Ok(GetStateDiffResult::Success {
+                    changes,
+                    next_page_after,
+                })
+            }
+            DiffResult::TrieNotFound(trie_key) => Ok(GetStateDiffResult::MissingTrie(trie_key)),
+        }
+    }
+
     /// Executes a step request.
     pub fn commit_step(
         &self,
@@ -1816,7 +2124,7 @@ where
             Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
         };
 
-        let executor = Executor::new(*self.config());
+        let executor = self.new_executor();
 
         let system_account_addr = PublicKey::System.to_account_hash();
 
@@ -2180,7 +2488,8 @@ fn should_charge_for_errors_in_wasm(execution_result: &ExecutionResult) -> bool
             | Error::FailedToGetStoredWithdraws
             | Error::FailedToGetWithdrawPurses
             | Error::FailedToRetrieveUnbondingDelay
-            | Error::FailedToRetrieveEraId => false,
+            | Error::FailedToRetrieveEraId
+            | Error::SessionEffectsRejected(_) => false,
         },
         ExecutionResult::Success { .. } => false,
     }
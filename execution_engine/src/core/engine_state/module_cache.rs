@@ -0,0 +1,222 @@
+//! Support for caching Wasm modules prepared for execution across deploys.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{self, Debug, Formatter},
+    sync::Mutex,
+};
+
+use parity_wasm::elements::Module;
+
+use casper_hashing::Digest;
+use casper_types::ContractWasmHash;
+
+/// Identifies a cached module.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub(crate) enum ModuleCacheKey {
+    /// A stored contract's Wasm, which is deserialized but not preprocessed before execution.
+    ContractWasm(ContractWasmHash),
+    /// Session or payment module bytes, keyed by their hash, which are preprocessed before
+    /// execution.
+    ModuleBytes(Digest),
+}
+
+/// Statistics describing the use of a [`ModuleCache`].
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct ModuleCacheStats {
+    /// The number of lookups which found a cached module.
+    pub hits: u64,
+    /// The number of lookups which didn't find a cached module.
+    pub misses: u64,
+    /// The number of modules currently cached.
+    pub entries: usize,
+    /// The approximate size in bytes of the modules currently cached.
+    pub size_bytes: usize,
+}
+
+struct CachedModule {
+    module: Module,
+    size_bytes: usize,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct Inner {
+    modules: HashMap<ModuleCacheKey, CachedModule>,
+    /// The keys of the cached modules, ordered from least to most recently used.
+    usage_order: BTreeMap<u64, ModuleCacheKey>,
+    next_use: u64,
+    stats: ModuleCacheStats,
+}
+
+impl Inner {
+    fn next_use(&mut self) -> u64 {
+        let next_use = self.next_use;
+        self.next_use += 1;
+        next_use
+    }
+
+    fn evict_least_recently_used(&mut self) {
+        let oldest = match self.usage_order.keys().next() {
+            Some(last_used) => *last_used,
+            None => return,
+        };
+        if let Some(key) = self.usage_order.remove(&oldest) {
+            if let Some(cached) = self.modules.remove(&key) {
+                self.stats.entries -= 1;
+                self.stats.size_bytes -= cached.size_bytes;
+            }
+        }
+    }
+}
+
+/// A cache of Wasm modules prepared for execution, shared between deploys so that executing the
+/// same code repeatedly doesn't repeat its deserialization and preprocessing.
+///
+/// The size of each module is approximated by the length of the Wasm bytes it was prepared from.
+/// When inserting a module would exceed the cache's budget, the least recently used modules are
+/// evicted.
+///
+/// Modules are prepared according to the engine's Wasm config, so the cache must be cleared if
+/// the config changes.
+pub struct ModuleCache {
+    max_size_bytes: usize,
+    inner: Mutex<Inner>,
+}
+
+impl ModuleCache {
+    /// Creates a new, empty cache holding at most `max_size_bytes` of modules.
+    pub fn new(max_size_bytes: usize) -> Self {
+        ModuleCache {
+            max_size_bytes,
+            inner: Mutex::new(Inner::default()),
+        }
+    }
+
+    /// Returns the maximum size in bytes of the modules held by the cache.
+    pub fn max_size_bytes(&self) -> usize {
+        self.max_size_bytes
+    }
+
+    /// Returns statistics describing the use of the cache.
+    pub fn stats(&self) -> ModuleCacheStats {
+        self.inner.lock().unwrap().stats
+    }
+
+    /// Removes all cached modules, leaving the hit and miss counts untouched.
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.modules.clear();
+        inner.usage_order.clear();
+        inner.stats.entries = 0;
+        inner.stats.size_bytes = 0;
+    }
+
+    /// Returns a copy of the cached module under `key`, if any.
+    pub(crate) fn get(&self, key: &ModuleCacheKey) -> Option<Module> {
+        let mut inner = self.inner.lock().unwrap();
+        let next_use = inner.next_use();
+        let inner = &mut *inner;
+        match inner.modules.get_mut(key) {
+            Some(cached) => {
+                inner.usage_order.remove(&cached.last_used);
+                inner.usage_order.insert(next_use, *key);
+                cached.last_used = next_use;
+                inner.stats.hits += 1;
+                Some(cached.module.clone())
+            }
+            None => {
+                inner.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Caches `module` under `key`, evicting the least recently used modules as required to keep
+    /// within the cache's budget.
+    ///
+    /// Modules larger than the whole budget are not cached.
+    pub(crate) fn insert(&self, key: ModuleCacheKey, module: Module, size_bytes: usize) {
+        if size_bytes > self.max_size_bytes {
+            return;
+        }
+        let mut inner = self.inner.lock().unwrap();
+        if inner.modules.contains_key(&key) {
+            return;
+        }
+        while inner.stats.size_bytes + size_bytes > self.max_size_bytes {
+            inner.evict_least_recently_used();
+        }
+        let last_used = inner.next_use();
+        inner.usage_order.insert(last_used, key);
+        inner.modules.insert(
+            key,
+            CachedModule {
+                module,
+                size_bytes,
+                last_used,
+            },
+        );
+        inner.stats.entries += 1;
+        inner.stats.size_bytes += size_bytes;
+    }
+}
+
+impl Debug for ModuleCache {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("ModuleCache")
+            .field("max_size_bytes", &self.max_size_bytes)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(byte: u8) -> ModuleCacheKey {
+        ModuleCacheKey::ModuleBytes(Digest::hash([byte]))
+    }
+
+    fn module() -> Module {
+        Module::default()
+    }
+
+    #[test]
+    fn should_evict_least_recently_used_modules() {
+        let cache = ModuleCache::new(30);
+        cache.insert(key(1), module(), 10);
+        cache.insert(key(2), module(), 10);
+        cache.insert(key(3), module(), 10);
+        assert!(cache.get(&key(1)).is_some());
+
+        cache.insert(key(4), module(), 10);
+        assert!(cache.get(&key(2)).is_none());
+        assert!(cache.get(&key(1)).is_some());
+        assert!(cache.get(&key(3)).is_some());
+        assert!(cache.get(&key(4)).is_some());
+
+        cache.insert(key(5), module(), 25);
+        assert!(cache.get(&key(1)).is_none());
+        assert!(cache.get(&key(3)).is_none());
+        assert!(cache.get(&key(4)).is_none());
+        assert!(cache.get(&key(5)).is_some());
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 5);
+        assert_eq!(stats.misses, 4);
+        assert_eq!(stats.entries, 1);
+        assert_eq!(stats.size_bytes, 25);
+    }
+
+    #[test]
+    fn should_not_cache_modules_larger_than_budget() {
+        let cache = ModuleCache::new(10);
+        cache.insert(key(1), module(), 10);
+        cache.insert(key(2), module(), 11);
+        assert!(cache.get(&key(1)).is_some());
+        assert!(cache.get(&key(2)).is_none());
+        assert_eq!(cache.stats().size_bytes, 10);
+    }
+}
@@ -0,0 +1,18 @@
+//! Support for checking the effects of session code before they are committed.
+use std::fmt::Debug;
+
+use casper_types::Key;
+
+use crate::shared::{additive_map::AdditiveMap, transform::Transform};
+
+/// A check which the effects of a deploy's session code must pass for them to be committed.
+///
+/// The effects of payment code, and of finalizing the payment of a deploy, are never checked.  A
+/// session whose effects fail the check is treated like one which failed during execution: its
+/// effects are discarded, while the deploy is still charged for the gas it consumed.
+///
+/// Checks must be deterministic, as every node executing a block must agree on their outcome.
+pub trait SessionEffectsCheck: Send + Sync + Debug {
+    /// Returns a description of the violation if `effects` fail the check.
+    fn check(&self, effects: &AdditiveMap<Key, Transform>) -> Result<(), String>;
+}
@@ -1,4 +1,4 @@
-use std::{cell::RefCell, collections::BTreeSet, rc::Rc};
+use std::{cell::RefCell, collections::BTreeSet, rc::Rc, sync::Arc};
 
 use casper_types::{
     account::{Account, AccountHash},
@@ -12,11 +12,11 @@ use casper_types::{
 use crate::{
     core::{
         engine_state::{
-            executable_deploy_item::ExecutionKind, execution_result::ExecutionResult, EngineConfig,
-            ExecError,
+            executable_deploy_item::ExecutionKind, execution_result::ExecutionResult,
+            module_cache::ModuleCache, EngineConfig, ExecError,
         },
         execution::{address_generator::AddressGenerator, Error},
-        runtime::{Runtime, RuntimeStack},
+        runtime::{GasProfiler, Runtime, RuntimeStack},
         runtime_context::RuntimeContext,
         tracking_copy::{TrackingCopy, TrackingCopyExt},
     },
@@ -35,12 +35,28 @@ fn try_get_amount(runtime_args: &RuntimeArgs) -> Result<U512, ExecError> {
 /// Executor object deals with execution of WASM modules.
 pub struct Executor {
     config: EngineConfig,
+    gas_profiler: Option<Rc<RefCell<GasProfiler>>>,
+    module_cache: Option<Arc<ModuleCache>>,
 }
 
 impl Executor {
     /// Creates new executor object.
     pub fn new(config: EngineConfig) -> Self {
-        Executor { config }
+        Executor {
+            config,
+            gas_profiler: None,
+            module_cache: None,
+        }
+    }
+
+    /// Records where gas is consumed with `gas_profiler`.
+    pub(crate) fn set_gas_profiler(&mut self, gas_profiler: Rc<RefCell<GasProfiler>>) {
+        self.gas_profiler = Some(gas_profiler);
+    }
+
+    /// Caches the Wasm modules prepared for execution in `module_cache`.
+    pub(crate) fn set_module_cache(&mut self, module_cache: Arc<ModuleCache>) {
+        self.module_cache = Some(module_cache);
     }
 
     /// Executes a WASM module.
@@ -122,6 +138,7 @@ impl Executor {
                 execution_journal: runtime.context().execution_journal(),
                 transfers: runtime.context().transfers().to_owned(),
                 cost: runtime.context().gas_counter(),
+                events: runtime.context().events().to_owned(),
             },
             Err(error) => ExecutionResult::Failure {
                 error: error.into(),
@@ -197,6 +214,7 @@ impl Executor {
                 execution_journal: runtime.context().execution_journal(),
                 transfers: runtime.context().transfers().to_owned(),
                 cost: runtime.context().gas_counter(),
+                events: runtime.context().events().to_owned(),
             },
             Err(error) => ExecutionResult::Failure {
                 execution_journal,
@@ -321,6 +339,7 @@ impl Executor {
                     execution_journal: runtime.context().execution_journal(),
                     transfers: runtime.context().transfers().to_owned(),
                     cost: runtime.context().gas_counter(),
+                    events: runtime.context().events().to_owned(),
                 }
                 .take_with_ret(ret),
                 Err(error) => ExecutionResult::Failure {
@@ -369,7 +388,7 @@ impl Executor {
         let gas_counter = Gas::default();
         let transfers = Vec::default();
 
-        RuntimeContext::new(
+        let mut runtime_context = RuntimeContext::new(
             tracking_copy,
             entry_point_type,
             named_keys,
@@ -389,7 +408,14 @@ impl Executor {
             self.config,
             transfers,
             remaining_spending_limit,
-        )
+        );
+        if let Some(gas_profiler) = &self.gas_profiler {
+            runtime_context.set_gas_profiler(Rc::clone(gas_profiler));
+        }
+        if let Some(module_cache) = &self.module_cache {
+            runtime_context.set_module_cache(Arc::clone(module_cache));
+        }
+        runtime_context
     }
 }
 
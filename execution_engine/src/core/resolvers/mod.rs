@@ -11,6 +11,12 @@ use casper_types::ProtocolVersion;
 use self::error::ResolverError;
 use crate::{core::resolvers::memory_resolver::MemoryResolver, shared::wasm_config::WasmConfig};
 
+/// The first protocol version in which Wasm may import the `casper_emit_event` host function.
+///
+/// Nodes running an earlier protocol version reject Wasm importing it, so all nodes agree on the
+/// outcome of executing such Wasm until the protocol upgrade activates.
+pub const EMIT_EVENT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::from_parts(1, 5, 0);
+
 /// Creates a module resolver for given protocol version.
 ///
 /// * `protocol_version` Version of the protocol. Can't be lower than 1.
@@ -22,6 +28,7 @@ pub(crate) fn create_module_resolver(
     if protocol_version >= ProtocolVersion::V1_0_0 {
         return Ok(v1_resolver::RuntimeModuleImportResolver::new(
             wasm_config.max_memory,
+            protocol_version,
         ));
     }
     Err(ResolverError::UnknownProtocolVersion(protocol_version))
@@ -29,6 +36,8 @@ pub(crate) fn create_module_resolver(
 
 #[cfg(test)]
 mod tests {
+    use wasmi::{Signature, ValueType};
+
     use casper_types::ProtocolVersion;
 
     use super::*;
@@ -45,4 +54,19 @@ mod tests {
     fn protocol_version_1_always_resolves() {
         assert!(create_module_resolver(ProtocolVersion::V1_0_0, &WasmConfig::default()).is_ok());
     }
+
+    #[test]
+    fn emit_event_resolves_only_from_its_protocol_version() {
+        let signature = Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32));
+        let resolves = |protocol_version| {
+            create_module_resolver(protocol_version, &WasmConfig::default())
+                .unwrap()
+                .resolve_func("casper_emit_event", &signature)
+                .is_ok()
+        };
+        assert!(!resolves(ProtocolVersion::V1_0_0));
+        assert!(!resolves(ProtocolVersion::from_parts(1, 4, 8)));
+        assert!(resolves(EMIT_EVENT_PROTOCOL_VERSION));
+        assert!(resolves(ProtocolVersion::from_parts(2, 0, 0)));
+    }
 }
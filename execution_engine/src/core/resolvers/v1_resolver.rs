@@ -5,20 +5,25 @@ use wasmi::{
     MemoryInstance, MemoryRef, ModuleImportResolver, Signature, ValueType,
 };
 
+use casper_types::ProtocolVersion;
+
 use super::{
     error::ResolverError, memory_resolver::MemoryResolver, v1_function_index::FunctionIndex,
+    EMIT_EVENT_PROTOCOL_VERSION,
 };
 
 pub(crate) struct RuntimeModuleImportResolver {
     memory: RefCell<Option<MemoryRef>>,
     max_memory: u32,
+    protocol_version: ProtocolVersion,
 }
 
 impl RuntimeModuleImportResolver {
-    pub(crate) fn new(max_memory: u32) -> Self {
+    pub(crate) fn new(max_memory: u32, protocol_version: ProtocolVersion) -> Self {
         Self {
             memory: RefCell::new(None),
             max_memory,
+            protocol_version,
         }
     }
 }
@@ -241,6 +246,12 @@ impl ModuleImportResolver for RuntimeModuleImportResolver {
                 Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
                 FunctionIndex::RandomBytes.into(),
             ),
+            "casper_emit_event" if self.protocol_version >= EMIT_EVENT_PROTOCOL_VERSION => {
+                FuncInstance::alloc_host(
+                    Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
+                    FunctionIndex::EmitEventFuncIndex.into(),
+                )
+            }
             _ => {
                 return Err(InterpreterError::Function(format!(
                     "host module doesn't export function with name {}",
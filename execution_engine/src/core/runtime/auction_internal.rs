@@ -5,7 +5,7 @@ use casper_types::{
     bytesrepr::{FromBytes, ToBytes},
     crypto,
     system::{
-        auction::{Bid, EraInfo, Error, UnbondingPurse},
+        auction::{Bid, EraInfo, Error, UnbondingPurse, REWARD_PURSE_KEY},
         mint,
     },
     CLTyped, CLValue, EraId, Key, KeyTag, PublicKey, RuntimeArgs, StoredValue, URef,
@@ -106,6 +106,23 @@ where
             .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))
     }
 
+    fn read_reward_purse(&mut self, account_hash: &AccountHash) -> Result<Option<URef>, Error> {
+        match self.context.read_gs_direct(&Key::Account(*account_hash)) {
+            Ok(Some(StoredValue::Account(account))) => Ok(account
+                .named_keys()
+                .get(REWARD_PURSE_KEY)
+                .and_then(Key::as_uref)
+                .copied()),
+            Ok(Some(_)) => Err(Error::Storage),
+            Ok(None) => Ok(None),
+            Err(execution::Error::BytesRepr(_)) => Err(Error::Serialization),
+            // NOTE: This extra condition is needed to correctly propagate GasLimit to the user. See
+            // also [`Runtime::reverter`] and [`to_auction_error`]
+            Err(execution::Error::GasLimit) => Err(Error::GasLimit),
+            Err(_) => Err(Error::Storage),
+        }
+    }
+
     fn record_era_info(&mut self, era_id: EraId, era_info: EraInfo) -> Result<(), Error> {
         Runtime::record_era_info(self, era_id, era_info)
             .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::RecordEraInfo))
@@ -140,6 +157,10 @@ where
     fn vesting_schedule_period_millis(&self) -> u64 {
         self.config.vesting_schedule_period_millis()
     }
+
+    fn allow_separate_reward_purses(&self) -> bool {
+        self.config.allow_separate_reward_purses()
+    }
 }
 
 impl<'a, R> MintProvider for Runtime<'a, R>
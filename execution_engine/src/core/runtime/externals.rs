@@ -32,6 +32,32 @@ where
     ) -> Result<Option<RuntimeValue>, Trap> {
         let func = FunctionIndex::try_from(index).expect("unknown function index");
 
+        // Calls to the injected gas function are attributed to opcodes rather than host functions.
+        let gas_profiler = match func {
+            FunctionIndex::GasFuncIndex => None,
+            _ => self.context.gas_profiler(),
+        };
+        if let Some(gas_profiler) = &gas_profiler {
+            gas_profiler.borrow_mut().enter_host_function(func.name());
+        }
+        let result = self.invoke_host_function(func, args);
+        if let Some(gas_profiler) = &gas_profiler {
+            gas_profiler.borrow_mut().exit_host_function();
+        }
+        result
+    }
+}
+
+impl<'a, R> Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<Error>,
+{
+    fn invoke_host_function(
+        &mut self,
+        func: FunctionIndex,
+        args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
         let host_function_costs = self.config.wasm_config().take_host_function_costs();
 
         match func {
@@ -189,7 +215,11 @@ where
                 let (gas_arg,): (u32,) = Args::parse(args)?;
                 // Gas is special cased internal host function and for accounting purposes it isn't
                 // represented in protocol data.
-                self.gas(Gas::new(gas_arg.into()))?;
+                let amount = match self.context.gas_profiler() {
+                    Some(gas_profiler) => gas_profiler.borrow_mut().gas_function_charge(gas_arg),
+                    None => Gas::new(gas_arg.into()),
+                };
+                self.gas(amount)?;
                 Ok(None)
             }
 
@@ -1060,6 +1090,20 @@ where
 
                 Ok(Some(RuntimeValue::I32(0)))
             }
+
+            FunctionIndex::EmitEventFuncIndex => {
+                // args(0) = pointer to event name in Wasm memory
+                // args(1) = size of event name in Wasm memory
+                // args(2) = pointer to serialized `CLValue` in Wasm memory
+                // args(3) = size of serialized `CLValue` in Wasm memory
+                let (name_ptr, name_size, value_ptr, value_size) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    &host_function_costs.emit_event,
+                    [name_ptr, name_size, value_ptr, value_size],
+                )?;
+                let ret = self.emit_event(name_ptr, name_size, value_ptr, value_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
         }
     }
 }
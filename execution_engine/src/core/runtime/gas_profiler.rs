@@ -0,0 +1,366 @@
+//! Attribution of gas charges to host functions and Wasm opcode classes.
+use parity_wasm::elements::{External, Instruction, Module};
+use pwasm_utils::rules::InstructionType;
+
+use casper_types::Gas;
+
+use crate::{
+    core::engine_state::gas_profile::{GasProfile, OpcodeClass},
+    shared::opcode_costs::OpcodeCosts,
+};
+
+const GAS_MODULE_NAME: &str = "env";
+const GAS_FUNCTION_NAME: &str = "gas";
+
+/// The reason for the charge made by a single call to the `gas` function of an instrumented module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum GasSite {
+    /// Charges the cost of a metered block, split by the opcode classes it contains.
+    Opcodes(Vec<(OpcodeClass, u64)>),
+    /// Precedes a charge whose argument is the raw cost of growing memory rather than a site id.
+    GrowMemory,
+}
+
+/// Records where the gas consumed while executing deploys is spent.
+///
+/// Modules executed while profiling are instrumented by [`GasProfiler::instrument`], which replaces
+/// the constant cost passed to the injected `gas` function by the id of a [`GasSite`] describing
+/// that cost.  All other charges are attributed to the innermost host function being executed.
+#[derive(Debug)]
+pub(crate) struct GasProfiler {
+    opcode_costs: OpcodeCosts,
+    sites: Vec<GasSite>,
+    host_function_stack: Vec<&'static str>,
+    pending_site: Option<GasSite>,
+    profile: GasProfile,
+}
+
+impl GasProfiler {
+    /// Creates a new profiler attributing opcode costs according to `opcode_costs`.
+    pub(crate) fn new(opcode_costs: OpcodeCosts) -> Self {
+        GasProfiler {
+            opcode_costs,
+            sites: Vec::new(),
+            host_function_stack: Vec::new(),
+            pending_site: None,
+            profile: GasProfile::default(),
+        }
+    }
+
+    /// Returns the profile recorded so far.
+    pub(crate) fn profile(&self) -> &GasProfile {
+        &self.profile
+    }
+
+    /// Rewrites the calls to the `gas` function injected into a preprocessed module so that each
+    /// passes the id of a site registered with this profiler rather than a raw cost.
+    pub(crate) fn instrument(&mut self, mut module: Module) -> Module {
+        let gas_function_index = match gas_function_index(&module) {
+            Some(index) => index,
+            None => return module,
+        };
+        let code_section = match module.code_section_mut() {
+            Some(code_section) => code_section,
+            None => return module,
+        };
+        for body in code_section.bodies_mut() {
+            let instructions = body.code_mut().elements_mut();
+            let mut instrumented = Vec::with_capacity(instructions.len());
+            for (index, instruction) in instructions.iter().enumerate() {
+                if *instruction != Instruction::Call(gas_function_index) {
+                    instrumented.push(instruction.clone());
+                    continue;
+                }
+                let site = match instrumented.last() {
+                    Some(Instruction::I32Const(cost)) => {
+                        let block = instructions[index + 1..]
+                            .iter()
+                            .take_while(|next| **next != Instruction::Call(gas_function_index));
+                        GasSite::Opcodes(self.split_block_cost(*cost as u32 as u64, block))
+                    }
+                    _ => GasSite::GrowMemory,
+                };
+                let site_id = self.register_site(site.clone());
+                match site {
+                    GasSite::Opcodes(_) => {
+                        instrumented.pop();
+                        instrumented.push(Instruction::I32Const(site_id));
+                    }
+                    GasSite::GrowMemory => {
+                        instrumented.push(Instruction::I32Const(site_id));
+                        instrumented.push(Instruction::Call(gas_function_index));
+                    }
+                }
+                instrumented.push(instruction.clone());
+            }
+            *instructions = instrumented;
+        }
+        module
+    }
+
+    /// Returns the gas to charge for a call to the `gas` function with the given argument, noting
+    /// the site it was made from so that the charge can be attributed by [`Self::record_charge`].
+    pub(crate) fn gas_function_charge(&mut self, gas_arg: u32) -> Gas {
+        if let Some(GasSite::GrowMemory) = self.pending_site {
+            self.pending_site = Some(GasSite::Opcodes(vec![(
+                OpcodeClass::GrowMemory,
+                gas_arg.into(),
+            )]));
+            return Gas::from(gas_arg);
+        }
+        match self.sites.get(gas_arg as usize).cloned() {
+            Some(GasSite::Opcodes(costs)) => {
+                let total: u64 = costs.iter().map(|(_, cost)| cost).sum();
+                self.pending_site = Some(GasSite::Opcodes(costs));
+                Gas::from(total)
+            }
+            Some(GasSite::GrowMemory) => {
+                self.pending_site = Some(GasSite::GrowMemory);
+                Gas::default()
+            }
+            // Not a call made by the injected code: charge the argument as-is.
+            None => Gas::from(gas_arg),
+        }
+    }
+
+    /// Notes that execution of the named host function has started.
+    pub(crate) fn enter_host_function(&mut self, name: &'static str) {
+        self.profile.record_host_function_call(name);
+        self.host_function_stack.push(name);
+    }
+
+    /// Notes that execution of the innermost host function has finished.
+    pub(crate) fn exit_host_function(&mut self) {
+        self.host_function_stack.pop();
+    }
+
+    /// Attributes `amount` of consumed gas to the pending opcode site, the innermost host function
+    /// or, failing those, to other costs.
+    pub(crate) fn record_charge(&mut self, amount: Gas) {
+        match self.pending_site.take() {
+            Some(GasSite::Opcodes(costs)) => {
+                let mut remaining = amount;
+                for (class, cost) in costs {
+                    let cost = Gas::from(cost).min(remaining);
+                    self.profile.record_opcode_cost(class, cost);
+                    remaining = remaining - cost;
+                }
+            }
+            // The marker preceding a grow memory charge is free.
+            Some(GasSite::GrowMemory) => self.pending_site = Some(GasSite::GrowMemory),
+            None => match self.host_function_stack.last() {
+                Some(name) => self.profile.record_host_function_cost(name, amount),
+                None => self.profile.record_other_cost(amount),
+            },
+        }
+    }
+
+    fn register_site(&mut self, site: GasSite) -> i32 {
+        self.sites.push(site);
+        (self.sites.len() - 1) as i32
+    }
+
+    /// Splits the cost charged for a metered block across the opcode classes of the instructions
+    /// it contains, in proportion to their costs in the opcode cost table.
+    ///
+    /// The instructions passed may include ones added after gas metering, such as those enforcing
+    /// the stack height limit, so the weights are scaled to add up to the cost actually charged.
+    fn split_block_cost<'a>(
+        &self,
+        cost: u64,
+        block: impl Iterator<Item = &'a Instruction>,
+    ) -> Vec<(OpcodeClass, u64)> {
+        let mut weights: Vec<(OpcodeClass, u64)> = Vec::new();
+        for instruction in block {
+            if matches!(instruction, Instruction::End | Instruction::Else) {
+                continue;
+            }
+            let class = opcode_class(instruction);
+            let weight = u64::from(self.class_cost(class));
+            match weights.iter_mut().find(|(existing, _)| *existing == class) {
+                Some((_, total)) => *total += weight,
+                None => weights.push((class, weight)),
+            }
+        }
+
+        let total_weight: u64 = weights.iter().map(|(_, weight)| weight).sum();
+        if total_weight == 0 {
+            return vec![(OpcodeClass::Regular, cost)];
+        }
+        let mut split: Vec<(OpcodeClass, u64)> = weights
+            .iter()
+            .map(|(class, weight)| {
+                let share = u128::from(cost) * u128::from(*weight) / u128::from(total_weight);
+                (*class, share as u64)
+            })
+            .collect();
+        // Give any rounding remainder to the first class so the split adds up to the cost.
+        let allocated: u64 = split.iter().map(|(_, share)| share).sum();
+        split[0].1 += cost - allocated;
+        split
+    }
+
+    fn class_cost(&self, class: OpcodeClass) -> u32 {
+        let costs = &self.opcode_costs;
+        match class {
+            OpcodeClass::Bit => costs.bit,
+            OpcodeClass::Add => costs.add,
+            OpcodeClass::Mul => costs.mul,
+            OpcodeClass::Div => costs.div,
+            OpcodeClass::Load => costs.load,
+            OpcodeClass::Store => costs.store,
+            OpcodeClass::Const => costs.op_const,
+            OpcodeClass::Local => costs.local,
+            OpcodeClass::Global => costs.global,
+            OpcodeClass::ControlFlow => costs.control_flow,
+            OpcodeClass::IntegerComparison => costs.integer_comparison,
+            OpcodeClass::Conversion => costs.conversion,
+            OpcodeClass::Unreachable => costs.unreachable,
+            OpcodeClass::Nop => costs.nop,
+            OpcodeClass::CurrentMemory => costs.current_memory,
+            OpcodeClass::GrowMemory => costs.grow_memory,
+            OpcodeClass::Regular => costs.regular,
+        }
+    }
+}
+
+/// Returns the index of the last function imported as `env.gas`, i.e. the one added by
+/// [`pwasm_utils::inject_gas_counter`].
+fn gas_function_index(module: &Module) -> Option<u32> {
+    let imports = module.import_section()?;
+    imports
+        .entries()
+        .iter()
+        .filter(|entry| matches!(entry.external(), External::Function(_)))
+        .enumerate()
+        .filter(|(_, entry)| {
+            entry.module() == GAS_MODULE_NAME && entry.field() == GAS_FUNCTION_NAME
+        })
+        .map(|(index, _)| index as u32)
+        .last()
+}
+
+fn opcode_class(instruction: &Instruction) -> OpcodeClass {
+    match InstructionType::op(instruction) {
+        InstructionType::Bit => OpcodeClass::Bit,
+        InstructionType::Add => OpcodeClass::Add,
+        InstructionType::Mul => OpcodeClass::Mul,
+        InstructionType::Div => OpcodeClass::Div,
+        InstructionType::Load => OpcodeClass::Load,
+        InstructionType::Store => OpcodeClass::Store,
+        InstructionType::Const => OpcodeClass::Const,
+        InstructionType::Local => OpcodeClass::Local,
+        InstructionType::Global => OpcodeClass::Global,
+        InstructionType::ControlFlow => OpcodeClass::ControlFlow,
+        InstructionType::IntegerComparison => OpcodeClass::IntegerComparison,
+        InstructionType::Conversion => OpcodeClass::Conversion,
+        InstructionType::Unreachable => OpcodeClass::Unreachable,
+        InstructionType::Nop => OpcodeClass::Nop,
+        InstructionType::CurrentMemory => OpcodeClass::CurrentMemory,
+        InstructionType::GrowMemory => OpcodeClass::GrowMemory,
+        // Float instructions are forbidden by the gas rules, and any others are charged at the
+        // regular cost.
+        _ => OpcodeClass::Regular,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::{builder, elements::Instructions};
+
+    use super::*;
+    use crate::shared::wasm_config::WasmConfig;
+
+    /// Returns the body of a single function module after gas metering, before and after being
+    /// instrumented by `profiler`.
+    fn metered_body(
+        profiler: &mut GasProfiler,
+        instructions: Vec<Instruction>,
+    ) -> (Vec<Instruction>, Vec<Instruction>) {
+        let module = builder::module()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .with_instructions(Instructions::new(instructions))
+            .build()
+            .build()
+            .memory()
+            .build()
+            .build();
+        let module = pwasm_utils::inject_gas_counter(
+            module,
+            &WasmConfig::default().opcode_costs().to_set(),
+            GAS_MODULE_NAME,
+        )
+        .expect("should inject gas counter");
+        let body = |module: &Module| {
+            module.code_section().expect("should have code").bodies()[0]
+                .code()
+                .elements()
+                .to_vec()
+        };
+        let metered = body(&module);
+        let instrumented = body(&profiler.instrument(module));
+        (metered, instrumented)
+    }
+
+    #[test]
+    fn should_attribute_block_cost_to_opcode_classes() {
+        let opcode_costs = WasmConfig::default().opcode_costs();
+        let mut profiler = GasProfiler::new(opcode_costs);
+        let (metered, instrumented) = metered_body(
+            &mut profiler,
+            vec![
+                Instruction::I32Const(1),
+                Instruction::I32Const(2),
+                Instruction::I32Add,
+                Instruction::Drop,
+                Instruction::End,
+            ],
+        );
+
+        let block_cost = match metered[0] {
+            Instruction::I32Const(cost) => cost as u32,
+            ref other => panic!("expected block cost, got {:?}", other),
+        };
+        let site_id = match instrumented[0] {
+            Instruction::I32Const(site_id) => site_id as u32,
+            ref other => panic!("expected site id, got {:?}", other),
+        };
+        assert_eq!(metered[1..], instrumented[1..]);
+
+        let charge = profiler.gas_function_charge(site_id);
+        assert_eq!(charge, Gas::from(block_cost));
+        profiler.record_charge(charge);
+
+        let profile = profiler.profile();
+        assert!(profile.opcode_classes().contains_key(&OpcodeClass::Const));
+        assert!(profile.opcode_classes().contains_key(&OpcodeClass::Add));
+        assert!(profile.host_functions().is_empty());
+        assert_eq!(profile.total(), Gas::from(block_cost));
+    }
+
+    #[test]
+    fn should_attribute_other_charges_to_innermost_host_function() {
+        let mut profiler = GasProfiler::new(WasmConfig::default().opcode_costs());
+        profiler.record_charge(Gas::from(5u32));
+        profiler.enter_host_function("casper_call_contract");
+        profiler.enter_host_function("casper_write");
+        profiler.record_charge(Gas::from(7u32));
+        profiler.exit_host_function();
+        profiler.record_charge(Gas::from(11u32));
+        profiler.exit_host_function();
+
+        let profile = profiler.profile();
+        assert_eq!(profile.other(), Gas::from(5u32));
+        let call_contract = profile.host_functions()["casper_call_contract"];
+        assert_eq!(call_contract.calls, 1);
+        assert_eq!(call_contract.cost, Gas::from(11u32));
+        assert_eq!(
+            profile.host_functions()["casper_write"].cost,
+            Gas::from(7u32)
+        );
+        assert_eq!(profile.total(), Gas::from(23u32));
+    }
+}
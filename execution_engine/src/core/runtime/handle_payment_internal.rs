@@ -1,10 +1,11 @@
 use casper_types::{
-    account::AccountHash, system::handle_payment::Error, BlockTime, Key, Phase, StoredValue,
-    TransferredTo, URef, U512,
+    account::AccountHash, system::handle_payment::Error, BlockTime, CLValue, Key, Phase,
+    StoredValue, TransferredTo, URef, U512,
 };
 
 use crate::{
     core::{execution, runtime::Runtime},
+    shared::fee_handling::{FeeHandling, RefundHandling},
     storage::global_state::StateReader,
     system::handle_payment::{
         mint_provider::MintProvider, runtime_provider::RuntimeProvider, HandlePayment,
@@ -66,6 +67,23 @@ where
         self.get_balance(purse)
             .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::GetBalance))
     }
+
+    fn burn_from_purse(&mut self, source: URef, amount: U512) -> Result<(), Error> {
+        let balance = self.balance(source)?.ok_or(Error::GetBalance)?;
+        let reduced_balance = balance
+            .checked_sub(amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+        let cl_value = CLValue::from_t(reduced_balance).map_err(|_| Error::Transfer)?;
+        self.context
+            .metered_write_gs_unsafe(Key::Balance(source.addr()), StoredValue::CLValue(cl_value))
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Transfer))?;
+
+        let mint_contract_hash = self
+            .get_mint_contract()
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Transfer))?;
+        self.mint_reduce_total_supply(mint_contract_hash, amount)
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Transfer))
+    }
 }
 
 // TODO: Update RuntimeProvider to better handle errors
@@ -101,6 +119,14 @@ where
     fn get_caller(&self) -> AccountHash {
         self.context.get_caller()
     }
+
+    fn refund_handling(&self) -> RefundHandling {
+        self.config.refund_handling()
+    }
+
+    fn fee_handling(&self) -> FeeHandling {
+        self.config.fee_handling()
+    }
 }
 
 impl<'a, R> HandlePayment for Runtime<'a, R>
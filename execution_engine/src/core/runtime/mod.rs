@@ -2,6 +2,7 @@
 mod args;
 mod auction_internal;
 mod externals;
+mod gas_profiler;
 mod handle_payment_internal;
 mod host_function_flag;
 mod mint_internal;
@@ -20,6 +21,7 @@ use parity_wasm::elements::Module;
 use tracing::error;
 use wasmi::{MemoryRef, Trap, TrapKind};
 
+use casper_hashing::Digest;
 use casper_types::{
     account::{Account, AccountHash, ActionType, Weight},
     bytesrepr::{self, Bytes, FromBytes, ToBytes},
@@ -35,14 +37,18 @@ use casper_types::{
         HANDLE_PAYMENT, MINT, STANDARD_PAYMENT,
     },
     AccessRights, ApiError, CLTyped, CLValue, ContextAccessRights, ContractHash,
-    ContractPackageHash, ContractVersionKey, ContractWasm, DeployHash, EntryPointType, EraId, Gas,
-    GrantedAccess, Key, NamedArg, Parameter, Phase, PublicKey, RuntimeArgs, StoredValue, Transfer,
-    TransferResult, TransferredTo, URef, DICTIONARY_ITEM_KEY_MAX_LENGTH, U512,
+    ContractPackageHash, ContractVersionKey, ContractWasm, ContractWasmHash, DeployHash,
+    EntryPointType, EraId, Gas, GrantedAccess, Key, NamedArg, Parameter, Phase, PublicKey,
+    RuntimeArgs, StoredValue, Transfer, TransferResult, TransferredTo, URef,
+    DICTIONARY_ITEM_KEY_MAX_LENGTH, U512,
 };
 
 use crate::{
     core::{
-        engine_state::EngineConfig,
+        engine_state::{
+            module_cache::ModuleCacheKey, ContractEvent, EngineConfig,
+            CONTRACT_EVENT_NAME_MAX_LENGTH,
+        },
         execution::{self, Error},
         runtime::host_function_flag::HostFunctionFlag,
         runtime_context::{self, RuntimeContext},
@@ -58,6 +64,7 @@ use crate::{
         standard_payment::StandardPayment,
     },
 };
+pub(crate) use gas_profiler::GasProfiler;
 pub use stack::{RuntimeStack, RuntimeStackFrame, RuntimeStackOverflow};
 
 enum CallContractIdentifier {
@@ -160,6 +167,57 @@ where
         self.context.charge_gas(amount)
     }
 
+    /// Instruments `module` to attribute its opcode costs if gas profiling is enabled.
+    ///
+    /// Only the instantiated copy of a module is instrumented; `self.module` is left untouched as
+    /// it may be stored in global state as a new contract version.
+    fn instrument_for_gas_profiling(&self, module: Module) -> Module {
+        match self.context.gas_profiler() {
+            Some(gas_profiler) => gas_profiler.borrow_mut().instrument(module),
+            None => module,
+        }
+    }
+
+    /// Preprocesses session or payment `module_bytes` for execution, reusing the module prepared
+    /// for an earlier execution of the same bytes if the module cache is enabled.
+    fn preprocess_module_bytes(&self, module_bytes: &Bytes) -> Result<Module, Error> {
+        let wasm_config = *self.config.wasm_config();
+        let module_cache = match self.context.module_cache() {
+            Some(module_cache) => module_cache,
+            None => return Ok(wasm_prep::preprocess(wasm_config, module_bytes)?),
+        };
+        let key = ModuleCacheKey::ModuleBytes(Digest::hash(module_bytes));
+        if let Some(module) = module_cache.get(&key) {
+            return Ok(module);
+        }
+        let module = wasm_prep::preprocess(wasm_config, module_bytes)?;
+        module_cache.insert(key, module.clone(), module_bytes.len());
+        Ok(module)
+    }
+
+    /// Deserializes a stored contract's Wasm for execution, reusing the module deserialized for an
+    /// earlier execution of the same contract Wasm if the module cache is enabled.
+    ///
+    /// The contract Wasm must still be read from global state by the caller, so that the read is
+    /// recorded in the execution effects regardless of whether the module is cached.
+    fn deserialize_contract_wasm(
+        &self,
+        contract_wasm_hash: ContractWasmHash,
+        contract_wasm: &ContractWasm,
+    ) -> Result<Module, Error> {
+        let module_cache = match self.context.module_cache() {
+            Some(module_cache) => module_cache,
+            None => return Ok(parity_wasm::deserialize_buffer(contract_wasm.bytes())?),
+        };
+        let key = ModuleCacheKey::ContractWasm(contract_wasm_hash);
+        if let Some(module) = module_cache.get(&key) {
+            return Ok(module);
+        }
+        let module: Module = parity_wasm::deserialize_buffer(contract_wasm.bytes())?;
+        module_cache.insert(key, module.clone(), contract_wasm.bytes().len());
+        Ok(module)
+    }
+
     /// Returns current gas counter.
     fn gas_counter(&self) -> Gas {
         self.context.gas_counter()
@@ -1027,9 +1085,12 @@ where
     ) -> Result<CLValue, Error> {
         let protocol_version = self.context.protocol_version();
         let wasm_config = self.config.wasm_config();
-        let module = wasm_prep::preprocess(*wasm_config, module_bytes)?;
-        let (instance, memory) =
-            utils::instance_and_memory(module.clone(), protocol_version, wasm_config)?;
+        let module = self.preprocess_module_bytes(module_bytes)?;
+        let (instance, memory) = utils::instance_and_memory(
+            self.instrument_for_gas_profiling(module.clone()),
+            protocol_version,
+            wasm_config,
+        )?;
         self.memory = Some(memory);
         self.module = Some(module);
         self.stack = Some(stack);
@@ -1339,7 +1400,7 @@ where
                 None => return Err(Error::KeyNotFound(context_key)),
             };
 
-            parity_wasm::deserialize_buffer(contract_wasm.bytes())?
+            self.deserialize_contract_wasm(contract.contract_wasm_hash(), &contract_wasm)?
         };
 
         let context = self.context.new_from_self(
@@ -1351,7 +1412,7 @@ where
         );
         let protocol_version = self.context.protocol_version();
         let (instance, memory) = utils::instance_and_memory(
-            module.clone(),
+            self.instrument_for_gas_profiling(module.clone()),
             protocol_version,
             self.config.wasm_config(),
         )?;
@@ -1368,6 +1429,10 @@ where
             let transfers = self.context.transfers_mut();
             *transfers = runtime.context.transfers().to_owned();
         }
+        {
+            let events = self.context.events_mut();
+            *events = runtime.context.events().to_owned();
+        }
 
         let error = match result {
             Err(error) => error,
@@ -2851,6 +2916,33 @@ where
         Ok(Ok(()))
     }
 
+    /// Records an event emitted by the code currently executing.
+    ///
+    /// Events are only recorded during the session phase, in the order in which they are emitted.
+    fn emit_event(
+        &mut self,
+        name_ptr: u32,
+        name_size: u32,
+        value_ptr: u32,
+        value_size: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let name_bytes = self.bytes_from_mem(name_ptr, name_size as usize)?;
+        if name_bytes.is_empty() || name_bytes.len() > CONTRACT_EVENT_NAME_MAX_LENGTH {
+            return Ok(Err(ApiError::InvalidArgument));
+        }
+        let name = match String::from_utf8(name_bytes) {
+            Ok(name) => name,
+            Err(_) => return Ok(Err(ApiError::InvalidArgument)),
+        };
+        let value = self.cl_value_from_mem(value_ptr, value_size)?;
+        if self.context.phase() != Phase::Session {
+            return Ok(Ok(()));
+        }
+        let event = ContractEvent::new(self.context.base_key(), name, value);
+        self.context.events_mut().push(event);
+        Ok(Ok(()))
+    }
+
     /// Checks if immediate caller is a system contract or account.
     ///
     /// For cases where call stack is only the session code, then this method returns `true` if the
@@ -1,3 +1,4 @@
+//! Types and functions for values stored in dictionaries.
 use casper_types::{
     bytesrepr::{self, Bytes, FromBytes, ToBytes},
     CLType, CLTyped, CLValue, CLValueError, Key, StoredValue,
@@ -19,6 +20,7 @@ pub struct DictionaryValue {
 }
 
 impl DictionaryValue {
+    /// Constructs a new [`DictionaryValue`].
     pub fn new(
         cl_value: CLValue,
         seed_uref_addr: Vec<u8>,
@@ -35,6 +37,16 @@ impl DictionaryValue {
     pub fn into_cl_value(self) -> CLValue {
         self.cl_value
     }
+
+    /// Returns the address of the dictionary's seed [`casper_types::URef`].
+    pub fn seed_uref_addr(&self) -> &Bytes {
+        &self.seed_uref_addr
+    }
+
+    /// Returns the bytes of the key used to create the `Key::Dictionary`.
+    pub fn dictionary_item_key_bytes(&self) -> &Bytes {
+        &self.dictionary_item_key_bytes
+    }
 }
 
 impl CLTyped for DictionaryValue {
@@ -5,6 +5,7 @@ use std::{
     convert::{TryFrom, TryInto},
     fmt::Debug,
     rc::Rc,
+    sync::Arc,
 };
 
 use tracing::error;
@@ -25,8 +26,12 @@ use casper_types::{
 
 use crate::{
     core::{
-        engine_state::{execution_effect::ExecutionEffect, EngineConfig, SystemContractRegistry},
+        engine_state::{
+            execution_effect::ExecutionEffect, module_cache::ModuleCache, ContractEvent,
+            EngineConfig, SystemContractRegistry,
+        },
         execution::{AddressGenerator, Error},
+        runtime::GasProfiler,
         runtime_context::dictionary::DictionaryValue,
         tracking_copy::{AddResult, TrackingCopy, TrackingCopyExt},
     },
@@ -34,7 +39,7 @@ use crate::{
     storage::global_state::StateReader,
 };
 
-pub(crate) mod dictionary;
+pub mod dictionary;
 #[cfg(test)]
 mod tests;
 
@@ -103,7 +108,10 @@ pub struct RuntimeContext<'a, R> {
     engine_config: EngineConfig,
     entry_point_type: EntryPointType,
     transfers: Vec<TransferAddr>,
+    events: Vec<ContractEvent>,
     remaining_spending_limit: U512,
+    gas_profiler: Option<Rc<RefCell<GasProfiler>>>,
+    module_cache: Option<Arc<ModuleCache>>,
 }
 
 impl<'a, R> RuntimeContext<'a, R>
@@ -155,7 +163,10 @@ where
             phase,
             engine_config,
             transfers,
+            events: Vec::new(),
             remaining_spending_limit,
+            gas_profiler: None,
+            module_cache: None,
         }
     }
 
@@ -183,7 +194,10 @@ where
         let phase = self.phase;
         let engine_config = self.engine_config;
         let transfers = self.transfers.clone();
+        let events = self.events.clone();
         let remaining_spending_limit = self.remaining_spending_limit();
+        let gas_profiler = self.gas_profiler.clone();
+        let module_cache = self.module_cache.clone();
 
         RuntimeContext {
             tracking_copy,
@@ -204,7 +218,10 @@ where
             phase,
             engine_config,
             transfers,
+            events,
             remaining_spending_limit,
+            gas_profiler,
+            module_cache,
         }
     }
 
@@ -405,6 +422,26 @@ where
         self.gas_counter = new_gas_counter;
     }
 
+    /// Returns the profiler recording where gas is consumed, if profiling is enabled.
+    pub(crate) fn gas_profiler(&self) -> Option<Rc<RefCell<GasProfiler>>> {
+        self.gas_profiler.clone()
+    }
+
+    /// Enables gas profiling, recording all subsequent charges with `gas_profiler`.
+    pub(crate) fn set_gas_profiler(&mut self, gas_profiler: Rc<RefCell<GasProfiler>>) {
+        self.gas_profiler = Some(gas_profiler);
+    }
+
+    /// Returns the cache of prepared Wasm modules, if enabled.
+    pub(crate) fn module_cache(&self) -> Option<&Arc<ModuleCache>> {
+        self.module_cache.as_ref()
+    }
+
+    /// Enables caching the Wasm modules prepared for execution in `module_cache`.
+    pub(crate) fn set_module_cache(&mut self, module_cache: Arc<ModuleCache>) {
+        self.module_cache = Some(module_cache);
+    }
+
     /// Returns the base key.
     ///
     /// This could be either a [`Key::Account`] or a [`Key::Hash`] depending on the entry point
@@ -655,6 +692,16 @@ where
         &mut self.transfers
     }
 
+    /// Returns the events emitted so far, in the order in which they were emitted.
+    pub fn events(&self) -> &Vec<ContractEvent> {
+        &self.events
+    }
+
+    /// Returns mutable list of emitted events.
+    pub fn events_mut(&mut self) -> &mut Vec<ContractEvent> {
+        &mut self.events
+    }
+
     fn validate_cl_value(&self, cl_value: &CLValue) -> Result<(), Error> {
         match cl_value.cl_type() {
             CLType::Bool
@@ -856,7 +903,7 @@ where
         let prev = self.gas_counter();
         let gas_limit = self.gas_limit();
         // gas charge overflow protection
-        match prev.checked_add(amount) {
+        let result = match prev.checked_add(amount) {
             None => {
                 self.set_gas_counter(gas_limit);
                 Err(Error::GasLimit)
@@ -869,7 +916,12 @@ where
                 self.set_gas_counter(val);
                 Ok(())
             }
+        };
+        if let Some(gas_profiler) = &self.gas_profiler {
+            let consumed = self.gas_counter().checked_sub(prev).unwrap_or_default();
+            gas_profiler.borrow_mut().record_charge(consumed);
         }
+        result
     }
 
     /// Checks if we are calling a system contract.
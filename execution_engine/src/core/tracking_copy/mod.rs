@@ -19,7 +19,8 @@ use thiserror::Error;
 use casper_hashing::Digest;
 use casper_types::{
     bytesrepr::{self},
-    CLType, CLValue, CLValueError, Key, KeyTag, StoredValue, StoredValueTypeMismatch, Tagged, U512,
+    CLType, CLValue, CLValueError, Key, KeyTag, StoredValue, StoredValueTypeMismatch, Tagged, URef,
+    U512,
 };
 
 pub use self::ext::TrackingCopyExt;
@@ -492,73 +493,115 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
 
             proofs.push(stored_value);
 
-            if query.unvisited_names.is_empty() {
-                return Ok(TrackingCopyQueryResult::Success { value, proofs });
-            }
-
-            let stored_value: &StoredValue = proofs
-                .last()
-                .map(|r| r.value())
-                .expect("but we just pushed");
+            // Path components of the form `[<index>]` select an element of a `CLValue` without
+            // reading from global state, so they are followed within this inner loop.
+            let mut value = value;
+            let mut is_element = false;
+            loop {
+                if query.unvisited_names.is_empty() {
+                    return Ok(TrackingCopyQueryResult::Success { value, proofs });
+                }
 
-            match stored_value {
-                StoredValue::Account(account) => {
-                    let name = query.next_name();
-                    if let Some(key) = account.named_keys().get(name) {
-                        query.navigate(*key);
-                    } else {
-                        let msg_prefix = format!("Name {} not found in Account", name);
-                        return Ok(query.into_not_found_result(&msg_prefix));
+                let element = match &value {
+                    StoredValue::Account(account) => {
+                        let name = query.next_name();
+                        if let Some(key) = account.named_keys().get(name) {
+                            query.navigate(*key);
+                            break;
+                        } else {
+                            let msg_prefix = format!("Name {} not found in Account", name);
+                            return Ok(query.into_not_found_result(&msg_prefix));
+                        }
                     }
-                }
-                StoredValue::CLValue(cl_value) if cl_value.cl_type() == &CLType::Key => {
-                    if let Ok(key) = cl_value.to_owned().into_t::<Key>() {
-                        query.navigate(key);
-                    } else {
-                        return Ok(query.into_not_found_result("Failed to parse CLValue as Key"));
+                    StoredValue::CLValue(cl_value) if cl_value.cl_type() == &CLType::Key => {
+                        if let Ok(key) = cl_value.to_owned().into_t::<Key>() {
+                            query.navigate(key);
+                            break;
+                        } else {
+                            return Ok(
+                                query.into_not_found_result("Failed to parse CLValue as Key")
+                            );
+                        }
                     }
-                }
-                StoredValue::CLValue(cl_value) => {
-                    let msg_prefix = format!(
-                        "Query cannot continue as {:?} is not an account, contract nor key to \
-                        such.  Value found",
-                        cl_value
-                    );
-                    return Ok(query.into_not_found_result(&msg_prefix));
-                }
-                StoredValue::Contract(contract) => {
-                    let name = query.next_name();
-                    if let Some(key) = contract.named_keys().get(name) {
-                        query.navigate(*key);
-                    } else {
-                        let msg_prefix = format!("Name {} not found in Contract", name);
-                        return Ok(query.into_not_found_result(&msg_prefix));
+                    StoredValue::CLValue(cl_value) if cl_value.cl_type() == &CLType::URef => {
+                        if let Ok(uref) = cl_value.to_owned().into_t::<URef>() {
+                            query.navigate(Key::URef(uref));
+                            break;
+                        } else {
+                            return Ok(
+                                query.into_not_found_result("Failed to parse CLValue as URef")
+                            );
+                        }
                     }
-                }
-                StoredValue::ContractPackage(_) => {
-                    return Ok(query.into_not_found_result("ContractPackage value found."));
-                }
-                StoredValue::ContractWasm(_) => {
-                    return Ok(query.into_not_found_result("ContractWasm value found."));
-                }
-                StoredValue::Transfer(_) => {
-                    return Ok(query.into_not_found_result("Transfer value found."));
-                }
-                StoredValue::DeployInfo(_) => {
-                    return Ok(query.into_not_found_result("DeployInfo value found."));
-                }
-                StoredValue::EraInfo(_) => {
-                    return Ok(query.into_not_found_result("EraInfo value found."));
-                }
-                StoredValue::Bid(_) => {
-                    return Ok(query.into_not_found_result("Bid value found."));
-                }
-                StoredValue::Withdraw(_) => {
-                    return Ok(query.into_not_found_result("WithdrawPurses value found."));
-                }
-                StoredValue::Unbonding(_) => {
-                    return Ok(query.into_not_found_result("UnbondingPurses value found."));
-                }
+                    StoredValue::CLValue(cl_value) => {
+                        let current_key = query.current_key;
+                        let index = match query_path::parse_index(query.next_name()) {
+                            Some(index) => index,
+                            None => {
+                                let msg_prefix = format!(
+                                    "Query cannot continue as {:?} is not an account, contract \
+                                    nor key to such, and the path component is not an index.  \
+                                    Value found",
+                                    cl_value
+                                );
+                                return Ok(query.into_not_found_result(&msg_prefix));
+                            }
+                        };
+                        match current_key {
+                            // A unit value stored under a URef is the seed of a dictionary, in
+                            // which case the index is a dictionary item key.
+                            Key::URef(seed_uref)
+                                if !is_element && cl_value.cl_type() == &CLType::Unit =>
+                            {
+                                let dictionary_key = Key::dictionary(seed_uref, index.as_bytes());
+                                query.navigate(dictionary_key);
+                                break;
+                            }
+                            _ => match query_path::index_cl_value(cl_value, index) {
+                                Ok(element) => element,
+                                Err(msg_prefix) => {
+                                    return Ok(query.into_not_found_result(&msg_prefix));
+                                }
+                            },
+                        }
+                    }
+                    StoredValue::Contract(contract) => {
+                        let name = query.next_name();
+                        if let Some(key) = contract.named_keys().get(name) {
+                            query.navigate(*key);
+                            break;
+                        } else {
+                            let msg_prefix = format!("Name {} not found in Contract", name);
+                            return Ok(query.into_not_found_result(&msg_prefix));
+                        }
+                    }
+                    StoredValue::ContractPackage(_) => {
+                        return Ok(query.into_not_found_result("ContractPackage value found."));
+                    }
+                    StoredValue::ContractWasm(_) => {
+                        return Ok(query.into_not_found_result("ContractWasm value found."));
+                    }
+                    StoredValue::Transfer(_) => {
+                        return Ok(query.into_not_found_result("Transfer value found."));
+                    }
+                    StoredValue::DeployInfo(_) => {
+                        return Ok(query.into_not_found_result("DeployInfo value found."));
+                    }
+                    StoredValue::EraInfo(_) => {
+                        return Ok(query.into_not_found_result("EraInfo value found."));
+                    }
+                    StoredValue::Bid(_) => {
+                        return Ok(query.into_not_found_result("Bid value found."));
+                    }
+                    StoredValue::Withdraw(_) => {
+                        return Ok(query.into_not_found_result("WithdrawPurses value found."));
+                    }
+                    StoredValue::Unbonding(_) => {
+                        return Ok(query.into_not_found_result("UnbondingPurses value found."));
+                    }
+                };
+                value = StoredValue::CLValue(element);
+                is_element = true;
             }
         }
     }
@@ -0,0 +1,258 @@
+//! Support for query path components which select an element of a `CLValue`.
+//!
+//! A path component of the form `[<index>]` selects:
+//!   * the element at the given position of a list or tuple, or
+//!   * the value under the given key of a map, where the key is written with strings verbatim,
+//!     numbers in decimal, keys and URefs as formatted strings, and public keys and byte arrays as
+//!     hex.
+//!
+//! When the value being queried is the unit value stored under a dictionary's seed URef, the
+//! index is instead a dictionary item key; see `TrackingCopy::query`.
+use casper_types::{
+    bytesrepr::{self, FromBytes, OPTION_NONE_TAG, OPTION_SOME_TAG, RESULT_ERR_TAG, RESULT_OK_TAG},
+    AsymmetricType, CLType, CLValue, Key, PublicKey, URef, U128, U256, U512,
+};
+
+/// Returns the index held by a path component of the form `[<index>]`, or `None` if the
+/// component is not of that form.
+pub(super) fn parse_index(path_component: &str) -> Option<&str> {
+    path_component
+        .strip_prefix('[')
+        .and_then(|remainder| remainder.strip_suffix(']'))
+}
+
+/// Returns the element of `cl_value` selected by `index`.
+pub(super) fn index_cl_value(cl_value: &CLValue, index: &str) -> Result<CLValue, String> {
+    let bytes = cl_value.inner_bytes().as_slice();
+    let malformed = || format!("Failed to parse value of type {:?}", cl_value.cl_type());
+    match cl_value.cl_type() {
+        CLType::List(element_type) => {
+            let position = parse_position(index)?;
+            let (count, mut remainder) = u32::from_bytes(bytes).map_err(|_| malformed())?;
+            if position >= count as usize {
+                return Err(format!(
+                    "Index {} out of bounds for list of length {}",
+                    position, count
+                ));
+            }
+            for _ in 0..position {
+                remainder = skip_value(element_type, remainder).ok_or_else(malformed)?;
+            }
+            let element_bytes = value_bytes(element_type, remainder).ok_or_else(malformed)?;
+            Ok(CLValue::from_components(
+                (**element_type).clone(),
+                element_bytes.to_vec(),
+            ))
+        }
+        CLType::Tuple1(element_types) => index_tuple(&element_types[..], bytes, index),
+        CLType::Tuple2(element_types) => index_tuple(&element_types[..], bytes, index),
+        CLType::Tuple3(element_types) => index_tuple(&element_types[..], bytes, index),
+        CLType::Map { key, value } => {
+            let (count, mut remainder) = u32::from_bytes(bytes).map_err(|_| malformed())?;
+            for _ in 0..count {
+                let key_bytes = value_bytes(key, remainder).ok_or_else(malformed)?;
+                remainder = &remainder[key_bytes.len()..];
+                let entry_value_bytes = value_bytes(value, remainder).ok_or_else(malformed)?;
+                if format_map_key(key, key_bytes).as_deref() == Some(index) {
+                    return Ok(CLValue::from_components(
+                        (**value).clone(),
+                        entry_value_bytes.to_vec(),
+                    ));
+                }
+                remainder = &remainder[entry_value_bytes.len()..];
+            }
+            Err(format!("Map key {} not found", index))
+        }
+        cl_type => Err(format!("Cannot index into value of type {:?}", cl_type)),
+    }
+}
+
+fn parse_position(index: &str) -> Result<usize, String> {
+    index
+        .parse()
+        .map_err(|_| format!("Invalid index {}, expected a non-negative integer", index))
+}
+
+fn index_tuple(
+    element_types: &[Box<CLType>],
+    bytes: &[u8],
+    index: &str,
+) -> Result<CLValue, String> {
+    let position = parse_position(index)?;
+    if position >= element_types.len() {
+        return Err(format!(
+            "Index {} out of bounds for tuple of length {}",
+            position,
+            element_types.len()
+        ));
+    }
+    let malformed = || "Failed to parse tuple value".to_string();
+    let mut remainder = bytes;
+    for element_type in &element_types[..position] {
+        remainder = skip_value(element_type, remainder).ok_or_else(malformed)?;
+    }
+    let element_type = &element_types[position];
+    let element_bytes = value_bytes(element_type, remainder).ok_or_else(malformed)?;
+    Ok(CLValue::from_components(
+        (**element_type).clone(),
+        element_bytes.to_vec(),
+    ))
+}
+
+/// Returns the leading bytes of `bytes` which encode a single value of type `cl_type`.
+fn value_bytes<'a>(cl_type: &CLType, bytes: &'a [u8]) -> Option<&'a [u8]> {
+    let remainder = skip_value(cl_type, bytes)?;
+    Some(&bytes[..bytes.len() - remainder.len()])
+}
+
+/// Returns the bytes remaining after skipping over a single value of type `cl_type`.
+fn skip_value<'a>(cl_type: &CLType, bytes: &'a [u8]) -> Option<&'a [u8]> {
+    match cl_type {
+        CLType::Bool => skip_simple_value::<bool>(bytes),
+        CLType::I32 => skip_simple_value::<i32>(bytes),
+        CLType::I64 => skip_simple_value::<i64>(bytes),
+        CLType::U8 => skip_simple_value::<u8>(bytes),
+        CLType::U32 => skip_simple_value::<u32>(bytes),
+        CLType::U64 => skip_simple_value::<u64>(bytes),
+        CLType::U128 => skip_simple_value::<U128>(bytes),
+        CLType::U256 => skip_simple_value::<U256>(bytes),
+        CLType::U512 => skip_simple_value::<U512>(bytes),
+        CLType::Unit => Some(bytes),
+        CLType::String => skip_simple_value::<String>(bytes),
+        CLType::Key => skip_simple_value::<Key>(bytes),
+        CLType::URef => skip_simple_value::<URef>(bytes),
+        CLType::PublicKey => skip_simple_value::<PublicKey>(bytes),
+        CLType::Option(inner_type) => {
+            let (tag, remainder) = u8::from_bytes(bytes).ok()?;
+            match tag {
+                OPTION_NONE_TAG => Some(remainder),
+                OPTION_SOME_TAG => skip_value(inner_type, remainder),
+                _ => None,
+            }
+        }
+        CLType::List(element_type) => {
+            let (count, mut remainder) = u32::from_bytes(bytes).ok()?;
+            for _ in 0..count {
+                remainder = skip_value(element_type, remainder)?;
+            }
+            Some(remainder)
+        }
+        CLType::ByteArray(length) => bytes.get(*length as usize..),
+        CLType::Result { ok, err } => {
+            let (tag, remainder) = u8::from_bytes(bytes).ok()?;
+            match tag {
+                RESULT_ERR_TAG => skip_value(err, remainder),
+                RESULT_OK_TAG => skip_value(ok, remainder),
+                _ => None,
+            }
+        }
+        CLType::Map { key, value } => {
+            let (count, mut remainder) = u32::from_bytes(bytes).ok()?;
+            for _ in 0..count {
+                remainder = skip_value(key, remainder)?;
+                remainder = skip_value(value, remainder)?;
+            }
+            Some(remainder)
+        }
+        CLType::Tuple1(element_types) => skip_values(&element_types[..], bytes),
+        CLType::Tuple2(element_types) => skip_values(&element_types[..], bytes),
+        CLType::Tuple3(element_types) => skip_values(&element_types[..], bytes),
+        CLType::Any => None,
+    }
+}
+
+fn skip_values<'a>(cl_types: &[Box<CLType>], bytes: &'a [u8]) -> Option<&'a [u8]> {
+    cl_types
+        .iter()
+        .try_fold(bytes, |remainder, cl_type| skip_value(cl_type, remainder))
+}
+
+fn skip_simple_value<T: FromBytes>(bytes: &[u8]) -> Option<&[u8]> {
+    T::from_bytes(bytes).ok().map(|(_, remainder)| remainder)
+}
+
+/// Formats a map key of type `cl_type` for comparison with an index given in a query path, or
+/// returns `None` if keys of that type can't be selected by a query path.
+fn format_map_key(cl_type: &CLType, bytes: &[u8]) -> Option<String> {
+    fn parse<T: FromBytes>(bytes: &[u8]) -> Option<T> {
+        bytesrepr::deserialize_from_slice(bytes).ok()
+    }
+
+    match cl_type {
+        CLType::Bool => parse::<bool>(bytes).map(|value| value.to_string()),
+        CLType::I32 => parse::<i32>(bytes).map(|value| value.to_string()),
+        CLType::I64 => parse::<i64>(bytes).map(|value| value.to_string()),
+        CLType::U8 => parse::<u8>(bytes).map(|value| value.to_string()),
+        CLType::U32 => parse::<u32>(bytes).map(|value| value.to_string()),
+        CLType::U64 => parse::<u64>(bytes).map(|value| value.to_string()),
+        CLType::U128 => parse::<U128>(bytes).map(|value| value.to_string()),
+        CLType::U256 => parse::<U256>(bytes).map(|value| value.to_string()),
+        CLType::U512 => parse::<U512>(bytes).map(|value| value.to_string()),
+        CLType::String => parse::<String>(bytes),
+        CLType::Key => parse::<Key>(bytes).map(Key::to_formatted_string),
+        CLType::URef => parse::<URef>(bytes).map(URef::to_formatted_string),
+        CLType::PublicKey => parse::<PublicKey>(bytes).map(|public_key| public_key.to_hex()),
+        CLType::ByteArray(_) => Some(base16::encode_lower(bytes)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_index() {
+        assert_eq!(parse_index("[0]"), Some("0"));
+        assert_eq!(parse_index("[alice]"), Some("alice"));
+        assert_eq!(parse_index("[]"), Some(""));
+        assert_eq!(parse_index("name"), None);
+        assert_eq!(parse_index("[name"), None);
+    }
+
+    #[test]
+    fn should_index_into_list() {
+        let list =
+            CLValue::from_t(vec!["a".to_string(), "bc".to_string(), "def".to_string()]).unwrap();
+        let element = index_cl_value(&list, "1").unwrap();
+        assert_eq!(element.into_t::<String>().unwrap(), "bc");
+        assert!(index_cl_value(&list, "3").is_err());
+        assert!(index_cl_value(&list, "a").is_err());
+    }
+
+    #[test]
+    fn should_index_into_tuple() {
+        let tuple = CLValue::from_t((vec![1u8, 2, 3], "b".to_string(), 7u64)).unwrap();
+        let element = index_cl_value(&tuple, "2").unwrap();
+        assert_eq!(element.into_t::<u64>().unwrap(), 7);
+        assert!(index_cl_value(&tuple, "3").is_err());
+    }
+
+    #[test]
+    fn should_index_into_map() {
+        let mut map = BTreeMap::new();
+        map.insert("alice".to_string(), vec![Some(1u32), None]);
+        map.insert("bob".to_string(), vec![]);
+        let map = CLValue::from_t(map).unwrap();
+        let element = index_cl_value(&map, "alice").unwrap();
+        assert_eq!(
+            element.into_t::<Vec<Option<u32>>>().unwrap(),
+            vec![Some(1), None]
+        );
+        assert!(index_cl_value(&map, "carol").is_err());
+
+        let mut map = BTreeMap::new();
+        map.insert(10u64, Key::Hash([1; 32]));
+        let map = CLValue::from_t(map).unwrap();
+        let element = index_cl_value(&map, "10").unwrap();
+        assert_eq!(element.into_t::<Key>().unwrap(), Key::Hash([1; 32]));
+    }
+
+    #[test]
+    fn should_not_index_into_scalar() {
+        let value = CLValue::from_t(1u64).unwrap();
+        assert!(index_cl_value(&value, "0").is_err());
+    }
+}
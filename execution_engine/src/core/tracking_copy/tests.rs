@@ -578,6 +578,104 @@ fn query_for_circular_references_should_fail() {
     }
 }
 
+#[test]
+fn query_should_follow_indices_urefs_and_dictionary_items() {
+    let correlation_id = CorrelationId::new();
+
+    let target_key = Key::Hash([4; 32]);
+
+    let seed_uref = URef::new([5; 32], AccessRights::READ_ADD_WRITE);
+    let item_key = Key::dictionary(seed_uref, b"alice");
+    let item_value = dictionary::handle_stored_value_into(
+        item_key,
+        StoredValue::CLValue(CLValue::from_t(vec![7u8, 8, 9]).unwrap()),
+    )
+    .unwrap();
+
+    let list_uref = URef::new([6; 32], AccessRights::READ_ADD_WRITE);
+    let list_value = StoredValue::CLValue(
+        CLValue::from_t(vec![(1u8, Key::Hash([9; 32])), (2u8, target_key)]).unwrap(),
+    );
+    let pointer_uref = URef::new([7; 32], AccessRights::READ_ADD_WRITE);
+    let pointer_value = StoredValue::CLValue(CLValue::from_t(list_uref).unwrap());
+
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert("list".to_string(), Key::URef(list_uref));
+    named_keys.insert("pointer".to_string(), Key::URef(pointer_uref));
+    named_keys.insert("dictionary".to_string(), Key::URef(seed_uref));
+    let contract_key = Key::Hash([1; 32]);
+    let contract = StoredValue::Contract(Contract::new(
+        [2; 32].into(),
+        [3; 32].into(),
+        named_keys,
+        EntryPoints::default(),
+        ProtocolVersion::V1_0_0,
+    ));
+
+    let (global_state, root_hash) = InMemoryGlobalState::from_pairs(
+        correlation_id,
+        &[
+            (contract_key, contract),
+            (Key::URef(list_uref), list_value),
+            (Key::URef(pointer_uref), pointer_value),
+            (Key::URef(seed_uref), StoredValue::CLValue(CLValue::unit())),
+            (item_key, item_value),
+        ],
+    )
+    .unwrap();
+    let view = global_state.checkout(root_hash).unwrap().unwrap();
+    let tracking_copy = TrackingCopy::new(view);
+    let query = |path: &[&str]| {
+        let path: Vec<String> = path.iter().map(|component| component.to_string()).collect();
+        tracking_copy
+            .query(
+                correlation_id,
+                &EngineConfig::default(),
+                contract_key,
+                &path,
+            )
+            .unwrap()
+    };
+
+    // index into the list, then into the tuple
+    assert_matches!(
+        query(&["list", "[1]", "[1]"]),
+        TrackingCopyQueryResult::Success { value, proofs }
+            if value == StoredValue::CLValue(CLValue::from_t(target_key).unwrap())
+                && proofs.len() == 2
+    );
+    assert_matches!(
+        query(&["list", "[1]", "[0]"]),
+        TrackingCopyQueryResult::Success { value, .. }
+            if value == StoredValue::CLValue(CLValue::from_t(2u8).unwrap())
+    );
+    // follow the URef to the list, then index into it
+    assert_matches!(
+        query(&["pointer", "[0]", "[0]"]),
+        TrackingCopyQueryResult::Success { value, proofs }
+            if value == StoredValue::CLValue(CLValue::from_t(1u8).unwrap()) && proofs.len() == 3
+    );
+    assert_matches!(
+        query(&["list", "[2]"]),
+        TrackingCopyQueryResult::ValueNotFound(_)
+    );
+    assert_matches!(
+        query(&["list", "element"]),
+        TrackingCopyQueryResult::ValueNotFound(_)
+    );
+
+    // look up a dictionary item, then index into its value
+    assert_matches!(
+        query(&["dictionary", "[alice]", "[2]"]),
+        TrackingCopyQueryResult::Success { value, .. }
+            if value == StoredValue::CLValue(CLValue::from_t(9u8).unwrap())
+    );
+    assert_matches!(
+        query(&["dictionary", "[bob]"]),
+        TrackingCopyQueryResult::ValueNotFound(_)
+    );
+}
+
 #[test]
 fn validate_query_proof_should_work() {
     // create account
@@ -0,0 +1,189 @@
+//! Definition of how transaction fees and refunds are handled once a deploy has been executed.
+use datasize::DataSize;
+use num_rational::Ratio;
+use rand::{distributions::Standard, prelude::*, Rng};
+use serde::{Deserialize, Serialize};
+
+use casper_types::bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH};
+
+const REFUND_TAG: u8 = 0;
+
+const PAY_TO_PROPOSER_TAG: u8 = 0;
+const BURN_TAG: u8 = 1;
+
+/// Default refund handling, under which no part of the unspent payment is refunded.
+pub const DEFAULT_REFUND_HANDLING: RefundHandling = RefundHandling::Refund {
+    refund_ratio: Ratio::new_raw(0, 1),
+};
+/// Default fee handling, under which the fees are paid to the block proposer.
+pub const DEFAULT_FEE_HANDLING: FeeHandling = FeeHandling::PayToProposer;
+
+/// Defines how the unspent part of a deploy's payment is handled.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RefundHandling {
+    /// The given share of the unspent payment is refunded to the deploy's refund purse, or to the
+    /// account's main purse if no refund purse was set.  The rest is handled as a fee.
+    Refund {
+        /// The share of the unspent payment which is refunded.
+        refund_ratio: Ratio<u64>,
+    },
+}
+
+impl RefundHandling {
+    /// Returns `true` if the refund ratio is a valid share, i.e. it has a non-zero denominator
+    /// and doesn't exceed one.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            RefundHandling::Refund { refund_ratio } => {
+                *refund_ratio.denom() != 0 && refund_ratio.numer() <= refund_ratio.denom()
+            }
+        }
+    }
+}
+
+impl Default for RefundHandling {
+    fn default() -> Self {
+        DEFAULT_REFUND_HANDLING
+    }
+}
+
+impl DataSize for RefundHandling {
+    const IS_DYNAMIC: bool = false;
+    const STATIC_HEAP_SIZE: usize = 0;
+
+    fn estimate_heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl Distribution<RefundHandling> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> RefundHandling {
+        let denom = rng.gen_range(1..=100);
+        let numer = rng.gen_range(0..=denom);
+        RefundHandling::Refund {
+            refund_ratio: Ratio::new(numer, denom),
+        }
+    }
+}
+
+impl ToBytes for RefundHandling {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        match self {
+            RefundHandling::Refund { refund_ratio } => {
+                buffer.push(REFUND_TAG);
+                buffer.extend(refund_ratio.to_bytes()?);
+            }
+        }
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                RefundHandling::Refund { refund_ratio } => refund_ratio.serialized_length(),
+            }
+    }
+}
+
+impl FromBytes for RefundHandling {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        match tag {
+            REFUND_TAG => {
+                let (refund_ratio, remainder) = Ratio::<u64>::from_bytes(remainder)?;
+                Ok((RefundHandling::Refund { refund_ratio }, remainder))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+/// Defines how the fees paid for executing a deploy are handled.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug, DataSize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FeeHandling {
+    /// The fees are paid to the proposer of the block containing the deploy.
+    PayToProposer,
+    /// The fees are burned, reducing the total supply.
+    Burn,
+}
+
+impl Default for FeeHandling {
+    fn default() -> Self {
+        DEFAULT_FEE_HANDLING
+    }
+}
+
+impl Distribution<FeeHandling> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> FeeHandling {
+        if rng.gen() {
+            FeeHandling::PayToProposer
+        } else {
+            FeeHandling::Burn
+        }
+    }
+}
+
+impl ToBytes for FeeHandling {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let tag = match self {
+            FeeHandling::PayToProposer => PAY_TO_PROPOSER_TAG,
+            FeeHandling::Burn => BURN_TAG,
+        };
+        Ok(vec![tag])
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for FeeHandling {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        match tag {
+            PAY_TO_PROPOSER_TAG => Ok((FeeHandling::PayToProposer, remainder)),
+            BURN_TAG => Ok((FeeHandling::Burn, remainder)),
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::testing::TestRng;
+
+    use super::*;
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        let mut rng = TestRng::new();
+        for _ in 0..10 {
+            let refund_handling: RefundHandling = rng.gen();
+            bytesrepr::test_serialization_roundtrip(&refund_handling);
+            let fee_handling: FeeHandling = rng.gen();
+            bytesrepr::test_serialization_roundtrip(&fee_handling);
+        }
+    }
+
+    #[test]
+    fn should_validate_refund_ratio() {
+        let valid = RefundHandling::Refund {
+            refund_ratio: Ratio::new_raw(1, 1),
+        };
+        assert!(valid.is_valid());
+        assert!(DEFAULT_REFUND_HANDLING.is_valid());
+
+        let too_large = RefundHandling::Refund {
+            refund_ratio: Ratio::new_raw(3, 2),
+        };
+        assert!(!too_large.is_valid());
+
+        let zero_denominator = RefundHandling::Refund {
+            refund_ratio: Ratio::new_raw(0, 0),
+        };
+        assert!(!zero_denominator.is_valid());
+    }
+}
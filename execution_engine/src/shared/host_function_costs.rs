@@ -78,6 +78,10 @@ const DEFAULT_DICTIONARY_PUT_VALUE_SIZE_WEIGHT: u32 = 520;
 
 const DEFAULT_NEW_DICTIONARY_COST: u32 = DEFAULT_NEW_UREF_COST;
 
+const DEFAULT_EMIT_EVENT_COST: u32 = 14_000;
+const DEFAULT_EMIT_EVENT_NAME_SIZE_WEIGHT: u32 = 440;
+const DEFAULT_EMIT_EVENT_VALUE_SIZE_WEIGHT: u32 = 980;
+
 pub(crate) const DEFAULT_HOST_FUNCTION_NEW_DICTIONARY: HostFunction<[Cost; 1]> =
     HostFunction::new(DEFAULT_NEW_DICTIONARY_COST, [NOT_USED]);
 
@@ -286,6 +290,12 @@ pub struct HostFunctionCosts {
     pub blake2b: HostFunction<[Cost; 4]>,
     /// Cost of calling the `next address` host function.
     pub random_bytes: HostFunction<[Cost; 2]>,
+    /// Cost of calling the `emit_event` host function.
+    ///
+    /// Defaults to the intended cost if absent, so that chainspecs predating the host function
+    /// still parse.
+    #[serde(default = "default_emit_event_cost")]
+    pub emit_event: HostFunction<[Cost; 4]>,
 }
 
 impl Default for HostFunctionCosts {
@@ -404,10 +414,24 @@ impl Default for HostFunctionCosts {
             ),
             blake2b: HostFunction::default(),
             random_bytes: HostFunction::default(),
+            emit_event: default_emit_event_cost(),
         }
     }
 }
 
+/// The default for `HostFunctionCosts::emit_event`.
+fn default_emit_event_cost() -> HostFunction<[Cost; 4]> {
+    HostFunction::new(
+        DEFAULT_EMIT_EVENT_COST,
+        [
+            NOT_USED,
+            DEFAULT_EMIT_EVENT_NAME_SIZE_WEIGHT,
+            NOT_USED,
+            DEFAULT_EMIT_EVENT_VALUE_SIZE_WEIGHT,
+        ],
+    )
+}
+
 impl ToBytes for HostFunctionCosts {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut ret = bytesrepr::unchecked_allocate_buffer(self);
@@ -454,6 +478,7 @@ impl ToBytes for HostFunctionCosts {
         ret.append(&mut self.print.to_bytes()?);
         ret.append(&mut self.blake2b.to_bytes()?);
         ret.append(&mut self.random_bytes.to_bytes()?);
+        ret.append(&mut self.emit_event.to_bytes()?);
         Ok(ret)
     }
 
@@ -501,6 +526,7 @@ impl ToBytes for HostFunctionCosts {
             + self.print.serialized_length()
             + self.blake2b.serialized_length()
             + self.random_bytes.serialized_length()
+            + self.emit_event.serialized_length()
     }
 }
 
@@ -549,6 +575,7 @@ impl FromBytes for HostFunctionCosts {
         let (print, rem) = FromBytes::from_bytes(rem)?;
         let (blake2b, rem) = FromBytes::from_bytes(rem)?;
         let (random_bytes, rem) = FromBytes::from_bytes(rem)?;
+        let (emit_event, rem) = FromBytes::from_bytes(rem)?;
         Ok((
             HostFunctionCosts {
                 read_value,
@@ -594,6 +621,7 @@ impl FromBytes for HostFunctionCosts {
                 print,
                 blake2b,
                 random_bytes,
+                emit_event,
             },
             rem,
         ))
@@ -646,6 +674,7 @@ impl Distribution<HostFunctionCosts> for Standard {
             print: rng.gen(),
             blake2b: rng.gen(),
             random_bytes: rng.gen(),
+            emit_event: rng.gen(),
         }
     }
 }
@@ -706,6 +735,7 @@ pub mod gens {
             print in host_function_cost_arb(),
             blake2b in host_function_cost_arb(),
             random_bytes in host_function_cost_arb(),
+            emit_event in host_function_cost_arb(),
         ) -> HostFunctionCosts {
             HostFunctionCosts {
                 read_value,
@@ -751,6 +781,7 @@ pub mod gens {
                 print,
                 blake2b,
                 random_bytes,
+                emit_event,
             }
         }
     }
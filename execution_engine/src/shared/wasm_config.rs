@@ -13,6 +13,8 @@ use super::{
 pub const DEFAULT_WASM_MAX_MEMORY: u32 = 64;
 /// Default maximum stack height.
 pub const DEFAULT_MAX_STACK_HEIGHT: u32 = 188;
+/// The maximum number of pages addressable by a Wasm memory, i.e. 4 GiB in 64 KiB pages.
+pub const MAX_WASM_MEMORY_PAGES: u32 = 65_536;
 
 /// Configuration of the Wasm execution environment.
 ///
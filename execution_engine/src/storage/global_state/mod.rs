@@ -26,7 +26,7 @@ use crate::{
         transaction_source::{Transaction, TransactionSource},
         trie::{merkle_proof::TrieMerkleProof, Trie, TrieOrChunk, TrieOrChunkId},
         trie_store::{
-            operations::{read, write, ReadResult, WriteResult},
+            operations::{read, write, DiffResult, ReadResult, WriteResult},
             TrieStore,
         },
     },
@@ -127,6 +127,20 @@ pub trait StateProvider {
         correlation_id: CorrelationId,
         trie_keys: Vec<Digest>,
     ) -> Result<Vec<Digest>, Self::Error>;
+
+    /// Returns the changes to the values stored under keys between the states at `old_state_hash`
+    /// and `new_state_hash`, ordered by the serialized form of their keys.
+    ///
+    /// At most `limit` changes are returned, and if `start_after` is provided, only changes to
+    /// keys whose serialized form is ordered after it are returned.
+    fn diff(
+        &self,
+        correlation_id: CorrelationId,
+        old_state_hash: Digest,
+        new_state_hash: Digest,
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<DiffResult<Key, StoredValue>, Self::Error>;
 }
 
 /// Write multiple key/stored value pairs to the store in a single rw transaction.
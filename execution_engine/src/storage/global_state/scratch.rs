@@ -21,7 +21,8 @@ use crate::{
         trie_store::{
             lmdb::LmdbTrieStore,
             operations::{
-                keys_with_prefix, missing_trie_keys, put_trie, read, read_with_proof, ReadResult,
+                diff, keys_with_prefix, missing_trie_keys, put_trie, read, read_with_proof,
+                DiffResult, ReadResult,
             },
         },
     },
@@ -355,6 +356,28 @@ impl StateProvider for ScratchGlobalState {
         txn.commit()?;
         Ok(missing_descendants)
     }
+
+    fn diff(
+        &self,
+        correlation_id: CorrelationId,
+        old_state_hash: Digest,
+        new_state_hash: Digest,
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<DiffResult<Key, StoredValue>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let diff_result = diff::<Key, StoredValue, lmdb::RoTransaction, LmdbTrieStore, Self::Error>(
+            correlation_id,
+            &txn,
+            self.trie_store.deref(),
+            &old_state_hash,
+            &new_state_hash,
+            start_after,
+            limit,
+        )?;
+        txn.commit()?;
+        Ok(diff_result)
+    }
 }
 
 #[cfg(test)]
@@ -1269,3 +1269,299 @@ where
         state: init_state,
     }
 }
+
+/// A change to the value stored under a key between two tries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyChange<K, V> {
+    /// The key whose value changed.
+    pub key: K,
+    /// The value stored under the key in the trie compared from, if any.
+    pub old_value: Option<V>,
+    /// The value stored under the key in the trie compared to, if any.
+    pub new_value: Option<V>,
+}
+
+/// The result of comparing two tries with [`diff`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum DiffResult<K, V> {
+    /// The changes were found.
+    Found {
+        /// The changes, ordered by the serialized form of their keys.
+        changes: Vec<KeyChange<K, V>>,
+        /// Whether further changes exist after the last one returned.
+        has_more: bool,
+    },
+    /// A trie needed to compute the changes is missing from the store.
+    TrieNotFound(Digest),
+}
+
+/// A subtrie being compared by [`diff`], positioned at the current depth of the comparison.
+#[derive(PartialEq, Eq)]
+enum DiffNode<K, V> {
+    /// A trie held in the store.
+    Stored(Digest),
+    /// The part of an extension's affix not yet descended through, followed by the trie it
+    /// points to.
+    Affix { affix: Vec<u8>, pointer: Digest },
+    /// A leaf.
+    Leaf { key: K, value: V },
+}
+
+/// A [`DiffNode`] expanded by a single level.
+enum ExpandedDiffNode<K, V> {
+    Leaf { key: K, value: V },
+    Branch(Vec<(u8, DiffNode<K, V>)>),
+}
+
+struct Differ<'a, K, V, T, S> {
+    txn: &'a T,
+    store: &'a S,
+    start_after: Option<&'a [u8]>,
+    limit: usize,
+    changes: Vec<KeyChange<K, V>>,
+    has_more: bool,
+    missing_trie: Option<Digest>,
+}
+
+impl<'a, K, V, T, S> Differ<'a, K, V, T, S>
+where
+    K: ToBytes + FromBytes + Eq,
+    V: ToBytes + FromBytes + Eq,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+{
+    fn is_done(&self) -> bool {
+        self.has_more || self.missing_trie.is_some()
+    }
+
+    /// Returns `true` if every key under `path` is ordered before the key to start after.
+    fn precedes_start(&self, path: &[u8]) -> bool {
+        match self.start_after {
+            Some(start_after) => {
+                let len = cmp::min(path.len(), start_after.len());
+                path[..len] < start_after[..len]
+            }
+            None => false,
+        }
+    }
+
+    fn record<E>(&mut self, key: K, old_value: Option<V>, new_value: Option<V>) -> Result<(), E>
+    where
+        E: From<bytesrepr::Error>,
+    {
+        if let Some(start_after) = self.start_after {
+            if key.to_bytes()?.as_slice() <= start_after {
+                return Ok(());
+            }
+        }
+        if self.changes.len() == self.limit {
+            self.has_more = true;
+            return Ok(());
+        }
+        self.changes.push(KeyChange {
+            key,
+            old_value,
+            new_value,
+        });
+        Ok(())
+    }
+
+    /// Expands `node` by a single level, returning `None` if it refers to a trie missing from the
+    /// store.
+    fn expand<E>(&mut self, node: DiffNode<K, V>) -> Result<Option<ExpandedDiffNode<K, V>>, E>
+    where
+        E: From<S::Error> + From<bytesrepr::Error>,
+    {
+        let trie = match node {
+            DiffNode::Stored(hash) => match self.store.get(self.txn, &hash)? {
+                Some(trie) => trie,
+                None => {
+                    self.missing_trie = Some(hash);
+                    return Ok(None);
+                }
+            },
+            DiffNode::Affix { affix, pointer } => {
+                let child = if affix.len() == 1 {
+                    DiffNode::Stored(pointer)
+                } else {
+                    DiffNode::Affix {
+                        affix: affix[1..].to_vec(),
+                        pointer,
+                    }
+                };
+                return Ok(Some(ExpandedDiffNode::Branch(vec![(affix[0], child)])));
+            }
+            DiffNode::Leaf { key, value } => {
+                return Ok(Some(ExpandedDiffNode::Leaf { key, value }));
+            }
+        };
+        match trie {
+            Trie::Leaf { key, value } => Ok(Some(ExpandedDiffNode::Leaf { key, value })),
+            Trie::Node { pointer_block } => {
+                let children = pointer_block
+                    .as_indexed_pointers()
+                    .map(|(index, pointer)| (index, DiffNode::Stored(*pointer.hash())))
+                    .collect();
+                Ok(Some(ExpandedDiffNode::Branch(children)))
+            }
+            Trie::Extension { affix, pointer } => self.expand(DiffNode::Affix {
+                affix: affix.to_vec(),
+                pointer: *pointer.hash(),
+            }),
+        }
+    }
+
+    /// Returns the children of an expanded node, indexed by the byte at `depth` of their keys.  A
+    /// leaf is pushed down a level so that it can be compared with the children of a branch.
+    fn children<E>(
+        depth: usize,
+        expanded: Option<ExpandedDiffNode<K, V>>,
+    ) -> Result<Vec<Option<DiffNode<K, V>>>, E>
+    where
+        E: From<bytesrepr::Error>,
+    {
+        let mut children: Vec<Option<DiffNode<K, V>>> = (0..RADIX).map(|_| None).collect();
+        match expanded {
+            Some(ExpandedDiffNode::Branch(branch)) => {
+                for (index, child) in branch {
+                    children[usize::from(index)] = Some(child);
+                }
+            }
+            Some(ExpandedDiffNode::Leaf { key, value }) => {
+                // Keys are serialized without any being a prefix of another, so a leaf compared
+                // with a branch at this depth always has a byte to be indexed by.
+                let index = *key
+                    .to_bytes()?
+                    .get(depth)
+                    .ok_or(bytesrepr::Error::Formatting)?;
+                children[usize::from(index)] = Some(DiffNode::Leaf { key, value });
+            }
+            None => (),
+        }
+        Ok(children)
+    }
+
+    /// Records the changes between the subtries `old` and `new` found under `path`.
+    fn compare<E>(
+        &mut self,
+        path: &mut Vec<u8>,
+        old: Option<DiffNode<K, V>>,
+        new: Option<DiffNode<K, V>>,
+    ) -> Result<(), E>
+    where
+        E: From<S::Error> + From<bytesrepr::Error>,
+    {
+        if self.is_done() || old == new || self.precedes_start(path) {
+            return Ok(());
+        }
+        let old = match old {
+            Some(node) => match self.expand(node)? {
+                Some(expanded) => Some(expanded),
+                None => return Ok(()),
+            },
+            None => None,
+        };
+        let new = match new {
+            Some(node) => match self.expand(node)? {
+                Some(expanded) => Some(expanded),
+                None => return Ok(()),
+            },
+            None => None,
+        };
+
+        match (old, new) {
+            (
+                Some(ExpandedDiffNode::Leaf {
+                    key: old_key,
+                    value: old_value,
+                }),
+                Some(ExpandedDiffNode::Leaf {
+                    key: new_key,
+                    value: new_value,
+                }),
+            ) => {
+                if old_key == new_key {
+                    if old_value != new_value {
+                        self.record(old_key, Some(old_value), Some(new_value))?;
+                    }
+                } else if old_key.to_bytes()? < new_key.to_bytes()? {
+                    self.record(old_key, Some(old_value), None)?;
+                    self.record(new_key, None, Some(new_value))?;
+                } else {
+                    self.record(new_key, None, Some(new_value))?;
+                    self.record(old_key, Some(old_value), None)?;
+                }
+                Ok(())
+            }
+            (Some(ExpandedDiffNode::Leaf { key, value }), None) => {
+                self.record(key, Some(value), None)
+            }
+            (None, Some(ExpandedDiffNode::Leaf { key, value })) => {
+                self.record(key, None, Some(value))
+            }
+            (old, new) => {
+                let depth = path.len();
+                let old_children = Self::children::<E>(depth, old)?;
+                let new_children = Self::children::<E>(depth, new)?;
+                for (index, (old_child, new_child)) in
+                    old_children.into_iter().zip(new_children).enumerate()
+                {
+                    path.push(index as u8);
+                    self.compare::<E>(path, old_child, new_child)?;
+                    path.pop();
+                    if self.is_done() {
+                        break;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Returns the changes to the values stored under keys between the tries at `old_root` and
+/// `new_root`, ordered by the serialized form of their keys.
+///
+/// Subtries shared by both tries are skipped without being read.  At most `limit` changes are
+/// returned, and if `start_after` is provided, only changes to keys whose serialized form is
+/// ordered after it are returned.
+pub fn diff<K, V, T, S, E>(
+    _correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    old_root: &Digest,
+    new_root: &Digest,
+    start_after: Option<&[u8]>,
+    limit: usize,
+) -> Result<DiffResult<K, V>, E>
+where
+    K: ToBytes + FromBytes + Eq,
+    V: ToBytes + FromBytes + Eq,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<bytesrepr::Error>,
+{
+    let mut differ = Differ {
+        txn,
+        store,
+        start_after,
+        limit,
+        changes: Vec::new(),
+        has_more: false,
+        missing_trie: None,
+    };
+    differ.compare::<E>(
+        &mut Vec::new(),
+        Some(DiffNode::Stored(*old_root)),
+        Some(DiffNode::Stored(*new_root)),
+    )?;
+    match differ.missing_trie {
+        Some(digest) => Ok(DiffResult::TrieNotFound(digest)),
+        None => Ok(DiffResult::Found {
+            changes: differ.changes,
+            has_more: differ.has_more,
+        }),
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::*;
+use crate::storage::{
+    error::in_memory,
+    trie_store::operations::{DiffResult, KeyChange},
+};
+
+type TestKeyChange = KeyChange<TestKey, TestValue>;
+
+fn diff_page(
+    context: &InMemoryTestContext,
+    old_root: &Digest,
+    new_root: &Digest,
+    start_after: Option<&TestKey>,
+    limit: usize,
+) -> (Vec<TestKeyChange>, bool) {
+    let start_after = start_after.map(|key| key.to_bytes().unwrap());
+    let txn = context.environment.create_read_txn().unwrap();
+    let result = operations::diff::<TestKey, TestValue, _, _, in_memory::Error>(
+        CorrelationId::new(),
+        &txn,
+        &context.store,
+        old_root,
+        new_root,
+        start_after.as_deref(),
+        limit,
+    )
+    .unwrap();
+    txn.commit().unwrap();
+    match result {
+        DiffResult::Found { changes, has_more } => (changes, has_more),
+        DiffResult::TrieNotFound(digest) => panic!("missing trie {}", digest),
+    }
+}
+
+fn leaf_map(leaves: &[TestTrie]) -> BTreeMap<TestKey, TestValue> {
+    leaves
+        .iter()
+        .map(|leaf| match leaf {
+            Trie::Leaf { key, value } => (*key, *value),
+            _ => panic!("leaves should contain only leaves"),
+        })
+        .collect()
+}
+
+fn expected_changes(old_leaves: &[TestTrie], new_leaves: &[TestTrie]) -> Vec<TestKeyChange> {
+    let old_leaves = leaf_map(old_leaves);
+    let new_leaves = leaf_map(new_leaves);
+    let keys: BTreeSet<TestKey> = old_leaves
+        .keys()
+        .chain(new_leaves.keys())
+        .copied()
+        .collect();
+    keys.into_iter()
+        .map(|key| KeyChange {
+            key,
+            old_value: old_leaves.get(&key).copied(),
+            new_value: new_leaves.get(&key).copied(),
+        })
+        .filter(|change| change.old_value != change.new_value)
+        .collect()
+}
+
+#[test]
+fn in_memory_diff_between_partial_tries_had_expected_results() {
+    let mut roots = Vec::new();
+    let context = InMemoryTestContext::new(EMPTY_HASHED_TEST_TRIES).unwrap();
+    for generator in TEST_TRIE_GENERATORS.iter() {
+        let (root_hash, tries) = generator().unwrap();
+        context.update(&tries).unwrap();
+        roots.push(root_hash);
+    }
+
+    for (old_num_leaves, old_root) in roots.iter().enumerate() {
+        for (new_num_leaves, new_root) in roots.iter().enumerate() {
+            let (changes, has_more) =
+                diff_page(&context, old_root, new_root, None, TEST_LEAVES_LENGTH);
+            let expected = expected_changes(
+                &TEST_LEAVES[..old_num_leaves],
+                &TEST_LEAVES[..new_num_leaves],
+            );
+            assert_eq!(changes, expected);
+            assert!(!has_more);
+        }
+    }
+}
+
+#[test]
+fn in_memory_diff_after_updates_had_expected_results() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[TEST_LEAVES_LENGTH]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    let updates = [
+        TEST_LEAVES_UPDATED[1].clone(),
+        TEST_LEAVES_UPDATED[4].clone(),
+        TEST_LEAVES_ADJACENTS[0].clone(),
+    ];
+    let write_results = write_leaves::<_, _, _, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &root_hash,
+        &updates,
+    )
+    .unwrap();
+    let new_root = match write_results.last() {
+        Some(WriteResult::Written(hash)) => *hash,
+        other => panic!("unexpected write result {:?}", other),
+    };
+
+    let mut new_leaves = TEST_LEAVES.to_vec();
+    new_leaves[1] = TEST_LEAVES_UPDATED[1].clone();
+    new_leaves[4] = TEST_LEAVES_UPDATED[4].clone();
+    new_leaves.push(TEST_LEAVES_ADJACENTS[0].clone());
+
+    let (changes, has_more) = diff_page(&context, &root_hash, &new_root, None, TEST_LEAVES_LENGTH);
+    assert_eq!(changes, expected_changes(&TEST_LEAVES, &new_leaves));
+    assert_eq!(changes.len(), 3);
+    assert!(!has_more);
+
+    let (changes, has_more) = diff_page(&context, &new_root, &root_hash, None, TEST_LEAVES_LENGTH);
+    assert_eq!(changes, expected_changes(&new_leaves, &TEST_LEAVES));
+    assert!(!has_more);
+}
+
+#[test]
+fn in_memory_diff_should_page_through_changes() {
+    let context = InMemoryTestContext::new(EMPTY_HASHED_TEST_TRIES).unwrap();
+    let (empty_root, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    context.update(&tries).unwrap();
+    let (full_root, tries) = TEST_TRIE_GENERATORS[TEST_LEAVES_LENGTH]().unwrap();
+    context.update(&tries).unwrap();
+
+    let mut paged = Vec::new();
+    let mut start_after: Option<TestKey> = None;
+    loop {
+        let (changes, has_more) =
+            diff_page(&context, &empty_root, &full_root, start_after.as_ref(), 2);
+        assert!(changes.len() <= 2);
+        paged.extend(changes);
+        if !has_more {
+            break;
+        }
+        start_after = paged.last().map(|change| change.key);
+    }
+
+    assert_eq!(paged, expected_changes(&[], &TEST_LEAVES));
+}
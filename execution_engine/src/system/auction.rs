@@ -9,8 +9,8 @@ use num_traits::{CheckedMul, CheckedSub};
 use casper_types::{
     account::AccountHash,
     system::auction::{
-        Bid, DelegationRate, EraInfo, EraValidators, Error, SeigniorageRecipients,
-        ValidatorWeights, BLOCK_REWARD, DELEGATION_RATE_DENOMINATOR,
+        Bid, DelegationRate, EraInfo, EraValidators, Error, SeigniorageAllocation,
+        SeigniorageRecipients, ValidatorWeights, BLOCK_REWARD, DELEGATION_RATE_DENOMINATOR,
     },
     ApiError, EraId, PublicKey, U512,
 };
@@ -601,14 +601,27 @@ pub trait Auction:
 
             let validators_part: Ratio<U512> = total_reward - Ratio::from(total_delegator_payout);
             let validator_reward = validators_part.to_integer();
-            let validator_bonding_purse = detail::reinvest_validator_reward(
-                self,
-                seigniorage_allocations,
-                public_key.clone(),
-                validator_reward,
-            )?;
+            let validator_reward_purse =
+                match detail::get_validator_reward_purse(self, &public_key)? {
+                    Some(reward_purse) => {
+                        seigniorage_allocations.push(
+                            SeigniorageAllocation::validator_reward_purse(
+                                public_key.clone(),
+                                reward_purse,
+                                validator_reward,
+                            ),
+                        );
+                        reward_purse
+                    }
+                    None => detail::reinvest_validator_reward(
+                        self,
+                        seigniorage_allocations,
+                        public_key.clone(),
+                        validator_reward,
+                    )?,
+                };
 
-            self.mint_into_existing_purse(validator_reward, validator_bonding_purse)
+            self.mint_into_existing_purse(validator_reward, validator_reward_purse)
                 .map_err(Error::from)?;
 
             for (_delegator_account_hash, delegator_payout, bonding_purse) in delegator_payouts {
@@ -373,6 +373,42 @@ where
     Ok(bonding_purse)
 }
 
+/// Returns the purse designated by the validator to receive its rewards, if separate reward purses
+/// are allowed and the validator designated an existing purse other than its bonding purse.
+pub fn get_validator_reward_purse<P>(
+    provider: &mut P,
+    validator_public_key: &PublicKey,
+) -> Result<Option<URef>, Error>
+where
+    P: RuntimeProvider + StorageProvider + MintProvider,
+{
+    if !provider.allow_separate_reward_purses() {
+        return Ok(None);
+    }
+
+    let validator_account_hash = AccountHash::from(validator_public_key);
+    let reward_purse = match provider.read_reward_purse(&validator_account_hash)? {
+        Some(reward_purse) => reward_purse,
+        None => return Ok(None),
+    };
+
+    let bonding_purse = match provider.read_bid(&validator_account_hash)? {
+        Some(bid) => *bid.bonding_purse(),
+        None => return Err(Error::ValidatorNotFound),
+    };
+    if reward_purse.addr() == bonding_purse.addr() {
+        return Ok(None);
+    }
+
+    // Rewards are only redirected into an existing purse, as failing to mint into the designated
+    // purse would fail the distribution for all validators.
+    if provider.get_balance(reward_purse)?.is_none() {
+        return Ok(None);
+    }
+
+    Ok(Some(reward_purse))
+}
+
 pub(crate) fn handle_delegation<P>(
     provider: &mut P,
     mut bid: Bid,
@@ -29,6 +29,9 @@ pub trait RuntimeProvider {
 
     /// Returns vesting schedule period.
     fn vesting_schedule_period_millis(&self) -> u64;
+
+    /// Returns whether validators may have their rewards paid into a separate reward purse.
+    fn allow_separate_reward_purses(&self) -> bool;
 }
 
 /// Provides functionality of a contract storage.
@@ -55,6 +58,9 @@ pub trait StorageProvider {
         unbonding_purses: Vec<UnbondingPurse>,
     ) -> Result<(), Error>;
 
+    /// Reads the purse stored under the reward purse named key of the account with the given hash.
+    fn read_reward_purse(&mut self, account_hash: &AccountHash) -> Result<Option<URef>, Error>;
+
     /// Records era info at the given era id.
     fn record_era_info(&mut self, era_id: EraId, era_info: EraInfo) -> Result<(), Error>;
 }
@@ -5,9 +5,7 @@ use casper_types::{
 };
 
 use super::{mint_provider::MintProvider, runtime_provider::RuntimeProvider};
-
-// A simplified representation of a refund percentage which is currently hardcoded to 0%.
-const REFUND_PERCENTAGE: U512 = U512::zero();
+use crate::shared::fee_handling::{FeeHandling, RefundHandling};
 
 /// Returns the purse for accepting payment for transactions.
 pub fn get_payment_purse<R: RuntimeProvider>(runtime_provider: &R) -> Result<URef, Error> {
@@ -39,9 +37,11 @@ pub fn get_refund_purse<R: RuntimeProvider>(runtime_provider: &R) -> Result<Opti
 }
 
 /// Transfers funds from the payment purse to the validator rewards purse, as well as to the
-/// refund purse, depending on how much was spent on the computation. This function maintains
-/// the invariant that the balance of the payment purse is zero at the beginning and end of each
-/// deploy and that the refund purse is unset at the beginning and end of each deploy.
+/// refund purse, depending on how much was spent on the computation and on the configured refund
+/// handling.  Depending on the configured fee handling, the fees are either paid to the validator
+/// rewards purse or burned.  This function maintains the invariant that the balance of the payment
+/// purse is zero at the beginning and end of each deploy and that the refund purse is unset at the
+/// beginning and end of each deploy.
 pub fn finalize_payment<P: MintProvider + RuntimeProvider>(
     provider: &mut P,
     amount_spent: U512,
@@ -68,21 +68,30 @@ pub fn finalize_payment<P: MintProvider + RuntimeProvider>(
         let refund_amount_raw = total
             .checked_sub(amount_spent)
             .ok_or(Error::ArithmeticOverflow)?;
-        // Currently refund percentage is zero and we expect no overflows.
-        // However, we put this check should the constant change in the future.
-        refund_amount_raw
-            .checked_mul(REFUND_PERCENTAGE)
-            .ok_or(Error::ArithmeticOverflow)?
+        let refund_handling = provider.refund_handling();
+        // The refund ratio is validated with the chainspec, but we must never refund more than
+        // was left unspent.
+        if !refund_handling.is_valid() {
+            return Err(Error::ArithmeticOverflow);
+        }
+        match refund_handling {
+            RefundHandling::Refund { refund_ratio } => {
+                refund_amount_raw
+                    .checked_mul(U512::from(*refund_ratio.numer()))
+                    .ok_or(Error::ArithmeticOverflow)?
+                    / U512::from(*refund_ratio.denom())
+            }
+        }
     };
 
-    // Validator reward
-    let validator_reward = total
+    // Validator reward, or the amount to be burned
+    let fees = total
         .checked_sub(refund_amount)
         .ok_or(Error::ArithmeticOverflow)?;
 
     // Makes sure both parts: for user, and for validator sums to the total amount in the
     // payment's purse.
-    debug_assert_eq!(validator_reward + refund_amount, total);
+    debug_assert_eq!(fees + refund_amount, total);
 
     let refund_purse = get_refund_purse(provider)?;
 
@@ -96,10 +105,15 @@ pub fn finalize_payment<P: MintProvider + RuntimeProvider>(
 
     provider.remove_key(REFUND_PURSE_KEY)?; //unset refund purse after reading it
 
-    // pay target validator
-    provider
-        .transfer_purse_to_purse(payment_purse, target, validator_reward)
-        .map_err(|_| Error::FailedTransferToRewardsPurse)?;
+    match provider.fee_handling() {
+        FeeHandling::PayToProposer => {
+            // pay target validator
+            provider
+                .transfer_purse_to_purse(payment_purse, target, fees)
+                .map_err(|_| Error::FailedTransferToRewardsPurse)?;
+        }
+        FeeHandling::Burn => provider.burn_from_purse(payment_purse, fees)?,
+    }
 
     if refund_amount.is_zero() {
         return Ok(());
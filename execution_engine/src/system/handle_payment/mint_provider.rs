@@ -22,4 +22,7 @@ pub trait MintProvider {
 
     /// Checks balance of a `purse`. Returns `None` if given purse does not exist.
     fn balance(&mut self, purse: URef) -> Result<Option<U512>, Error>;
+
+    /// Burn `amount` from `source` purse, reducing the total supply accordingly.
+    fn burn_from_purse(&mut self, source: URef, amount: U512) -> Result<(), Error>;
 }
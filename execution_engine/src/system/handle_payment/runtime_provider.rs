@@ -1,5 +1,7 @@
 use casper_types::{account::AccountHash, system::handle_payment::Error, BlockTime, Key, Phase};
 
+use crate::shared::fee_handling::{FeeHandling, RefundHandling};
+
 /// Provider of runtime host functionality.
 pub trait RuntimeProvider {
     /// Get named key under a `name`.
@@ -19,4 +21,10 @@ pub trait RuntimeProvider {
 
     /// Get caller.
     fn get_caller(&self) -> AccountHash;
+
+    /// Get the configured handling of the unspent part of a deploy's payment.
+    fn refund_handling(&self) -> RefundHandling;
+
+    /// Get the configured handling of the fees paid for executing a deploy.
+    fn fee_handling(&self) -> FeeHandling;
 }
@@ -10,7 +10,11 @@ use serde::{Deserialize, Serialize};
 
 use casper_execution_engine::{
     core::engine_state::{run_genesis_request::RunGenesisRequest, ExecConfig, GenesisAccount},
-    shared::{system_config::SystemConfig, wasm_config::WasmConfig},
+    shared::{
+        fee_handling::{FeeHandling, RefundHandling},
+        system_config::SystemConfig,
+        wasm_config::WasmConfig,
+    },
 };
 use casper_types::ProtocolVersion;
 
@@ -68,6 +72,15 @@ pub struct CoreConfig {
     pub(crate) minimum_delegation_amount: u64,
     /// Enables strict arguments checking when calling a contract.
     pub(crate) strict_argument_checking: bool,
+    /// Allows validators to have their rewards paid into a separate reward purse.
+    #[serde(default)]
+    pub(crate) allow_separate_reward_purses: bool,
+    /// How the unspent part of a deploy's payment is handled.
+    #[serde(default)]
+    pub(crate) refund_handling: RefundHandling,
+    /// How the fees paid for executing a deploy are handled.
+    #[serde(default)]
+    pub(crate) fee_handling: FeeHandling,
 }
 
 /// This struct can be parsed from a TOML-encoded chainspec file.  It means that as the
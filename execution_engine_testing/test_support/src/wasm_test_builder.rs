@@ -25,8 +25,8 @@ use casper_execution_engine::{
             run_genesis_request::RunGenesisRequest,
             step::{StepRequest, StepSuccess},
             BalanceResult, EngineConfig, EngineState, Error, GenesisSuccess, GetBidsRequest,
-            QueryRequest, QueryResult, RewardItem, StepError, SystemContractRegistry,
-            UpgradeConfig, UpgradeSuccess, DEFAULT_MAX_QUERY_DEPTH,
+            QueryRequest, QueryResult, RewardItem, SessionEffectsCheck, StepError,
+            SystemContractRegistry, UpgradeConfig, UpgradeSuccess, DEFAULT_MAX_QUERY_DEPTH,
         },
         execution,
     },
@@ -221,6 +221,9 @@ impl InMemoryWasmTestBuilder {
             chainspec_config.core_config.minimum_delegation_amount,
             chainspec_config.core_config.strict_argument_checking,
             vesting_schedule_period_millis,
+            chainspec_config.core_config.allow_separate_reward_purses,
+            chainspec_config.core_config.refund_handling,
+            chainspec_config.core_config.fee_handling,
             chainspec_config.wasm_config,
             chainspec_config.system_costs_config,
         );
@@ -293,6 +296,9 @@ impl LmdbWasmTestBuilder {
             chainspec_config.core_config.minimum_delegation_amount,
             chainspec_config.core_config.strict_argument_checking,
             vesting_schedule_period_millis,
+            chainspec_config.core_config.allow_separate_reward_purses,
+            chainspec_config.core_config.refund_handling,
+            chainspec_config.core_config.fee_handling,
             chainspec_config.wasm_config,
             chainspec_config.system_costs_config,
         );
@@ -472,6 +478,19 @@ where
     engine_state::Error: From<S::Error>,
     S::Error: Into<execution::Error>,
 {
+    /// Sets a check which the effects of each deploy's session code must pass to be committed.
+    ///
+    /// Panics if the engine state is shared with a clone of this builder.
+    pub fn with_session_effects_check(
+        mut self,
+        session_effects_check: Arc<dyn SessionEffectsCheck>,
+    ) -> Self {
+        let engine_state = Rc::try_unwrap(self.engine_state)
+            .unwrap_or_else(|_| panic!("engine state should not be shared"));
+        self.engine_state = Rc::new(engine_state.with_session_effects_check(session_effects_check));
+        self
+    }
+
     /// Takes a [`RunGenesisRequest`], executes the request and returns Self.
     pub fn run_genesis(&mut self, run_genesis_request: &RunGenesisRequest) -> &mut Self {
         let system_account = Key::Account(PublicKey::System.to_account_hash());
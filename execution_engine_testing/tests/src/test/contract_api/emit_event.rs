@@ -0,0 +1,109 @@
+use casper_engine_test_support::{
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_PROTOCOL_VERSION, PRODUCTION_RUN_GENESIS_REQUEST,
+};
+use casper_execution_engine::core::{
+    engine_state::Error, execution, resolvers::EMIT_EVENT_PROTOCOL_VERSION,
+};
+use casper_types::{runtime_args, CLValue, EraId, Key, RuntimeArgs};
+
+const CONTRACT_EMIT_EVENTS: &str = "emit_events.wasm";
+const ARG_REVERT: &str = "revert";
+const DEFAULT_ACTIVATION_POINT: EraId = EraId::new(1);
+
+/// Runs genesis and upgrades to the first protocol version providing `casper_emit_event`.
+fn setup_upgraded() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+        .with_new_protocol_version(EMIT_EVENT_PROTOCOL_VERSION)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .build();
+    builder
+        .upgrade_with_upgrade_request(*builder.get_engine_state().config(), &mut upgrade_request)
+        .expect_upgrade_success();
+    builder
+}
+
+fn execute_emit_events(revert: bool) -> InMemoryWasmTestBuilder {
+    let mut builder = setup_upgraded();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_EMIT_EVENTS,
+        runtime_args! { ARG_REVERT => revert },
+    )
+    .with_protocol_version(EMIT_EVENT_PROTOCOL_VERSION)
+    .build();
+    builder.exec(exec_request);
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_record_events_in_emission_order() {
+    let mut builder = execute_emit_events(false);
+    builder.expect_success().commit();
+
+    let exec_results = builder
+        .get_last_exec_results()
+        .expect("should have exec results");
+    let events = exec_results[0].events();
+
+    let names: Vec<&str> = events.iter().map(|event| event.name()).collect();
+    assert_eq!(names, vec!["counter", "from_contract", "counter"]);
+    let values: Vec<CLValue> = events.iter().map(|event| event.value().clone()).collect();
+    assert_eq!(
+        values,
+        vec![
+            CLValue::from_t(1u64).unwrap(),
+            CLValue::from_t(2u64).unwrap(),
+            CLValue::from_t(3u64).unwrap(),
+        ]
+    );
+
+    // Session code emits as the account, the stored contract as itself.
+    let account_key = Key::Account(*DEFAULT_ACCOUNT_ADDR);
+    assert_eq!(events[0].emitter(), account_key);
+    assert!(matches!(events[1].emitter(), Key::Hash(_)));
+    assert_eq!(events[2].emitter(), account_key);
+}
+
+#[ignore]
+#[test]
+fn should_not_record_events_of_failed_deploy() {
+    let mut builder = execute_emit_events(true);
+    builder.expect_failure();
+
+    let exec_results = builder
+        .get_last_exec_results()
+        .expect("should have exec results");
+    assert!(exec_results[0].events().is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_not_resolve_emit_event_before_protocol_upgrade() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_EMIT_EVENTS,
+        runtime_args! { ARG_REVERT => false },
+    )
+    .build();
+    builder.exec(exec_request).expect_failure();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(
+            &error,
+            Error::Exec(execution::Error::Interpreter(msg)) if msg.contains("casper_emit_event")
+        ),
+        "{:?}",
+        error
+    );
+}
@@ -1,6 +1,7 @@
 mod account;
 mod create_purse;
 mod dictionary;
+mod emit_event;
 mod get_arg;
 mod get_blocktime;
 mod get_call_stack;
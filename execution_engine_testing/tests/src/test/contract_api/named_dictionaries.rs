@@ -1,23 +1,62 @@
+use std::collections::BTreeMap;
+
 use casper_engine_test_support::{
     ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
     PRODUCTION_RUN_GENESIS_REQUEST,
 };
+use casper_execution_engine::{
+    core::engine_state::{GetDictionaryItemsRequest, GetDictionaryItemsResult},
+    shared::newtypes::CorrelationId,
+};
 use casper_types::{runtime_args, RuntimeArgs};
 use rand::{rngs::StdRng, Rng, SeedableRng};
 
+// Types from `smart_contracts/contracts/test/named-dictionary-test/src/main.rs`.
+type DictIndex = u8;
+type KeySeed = u8;
+type Value = u8;
+
+const DICTIONARY_NAMES: &[&str] = &[
+    "the", "quick", "brown", "fox", "jumps", "over", "the_", "lazy", "dog",
+];
+
+fn random_puts() -> Vec<(DictIndex, KeySeed, Value)> {
+    let mut rng = StdRng::seed_from_u64(0);
+    (0..1_000)
+        .map(|_| (rng.gen_range(0..9), rng.gen_range(0..20), rng.gen()))
+        .collect()
+}
+
 #[ignore]
 #[test]
 fn named_dictionaries_should_work_as_expected() {
-    // Types from `smart_contracts/contracts/test/named-dictionary-test/src/main.rs`.
-    type DictIndex = u8;
-    type KeySeed = u8;
-    type Value = u8;
+    let puts = random_puts();
 
-    let mut rng = StdRng::seed_from_u64(0);
+    let builder = &mut InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+    builder
+        .exec(
+            ExecuteRequestBuilder::standard(
+                *DEFAULT_ACCOUNT_ADDR,
+                "named-dictionary-test.wasm",
+                runtime_args! { "puts" => puts },
+            )
+            .build(),
+        )
+        .expect_success();
+}
 
-    let puts: Vec<(DictIndex, KeySeed, Value)> = (0..1_000)
-        .map(|_| (rng.gen_range(0..9), rng.gen_range(0..20), rng.gen()))
-        .collect();
+#[ignore]
+#[test]
+fn should_page_through_dictionary_items() {
+    const LIMIT: usize = 3;
+
+    let puts = random_puts();
+    let mut expected_items: Vec<BTreeMap<Vec<u8>, Value>> =
+        vec![BTreeMap::new(); DICTIONARY_NAMES.len()];
+    for (dict_index, key_seed, value) in &puts {
+        expected_items[*dict_index as usize].insert(key_seed.to_string().into_bytes(), *value);
+    }
 
     let builder = &mut InMemoryWasmTestBuilder::default();
     builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
@@ -30,5 +69,52 @@ fn named_dictionaries_should_work_as_expected() {
             )
             .build(),
         )
-        .expect_success();
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+
+    for (name, expected_items) in DICTIONARY_NAMES.iter().zip(expected_items) {
+        let seed_uref = account
+            .named_keys()
+            .get(*name)
+            .and_then(|key| key.into_uref())
+            .expect("should have dictionary");
+
+        let mut items = BTreeMap::new();
+        let mut after = None;
+        loop {
+            let request = GetDictionaryItemsRequest::new(
+                builder.get_post_state_hash(),
+                seed_uref,
+                after,
+                LIMIT,
+            );
+            let (page, next_page_after) = match builder
+                .get_engine_state()
+                .get_dictionary_items(CorrelationId::new(), request)
+                .expect("should get dictionary items")
+            {
+                GetDictionaryItemsResult::Success {
+                    items,
+                    next_page_after,
+                } => (items, next_page_after),
+                GetDictionaryItemsResult::RootNotFound => panic!("should have root"),
+            };
+            assert!(page.len() <= LIMIT);
+            for item in page {
+                let value: Value = item.value.into_t().expect("should be u8");
+                let previous = items.insert(item.dictionary_item_key.into(), value);
+                assert!(previous.is_none(), "items should be returned only once");
+            }
+            match next_page_after {
+                Some(next_page_after) => after = Some(next_page_after),
+                None => break,
+            }
+        }
+
+        assert_eq!(items, expected_items, "unexpected items of {}", name);
+    }
 }
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use casper_engine_test_support::{
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    PRODUCTION_RUN_GENESIS_REQUEST,
+};
+use casper_execution_engine::{
+    core::engine_state::{Error, SessionEffectsCheck},
+    shared::{additive_map::AdditiveMap, transform::Transform},
+};
+use casper_types::{Key, RuntimeArgs};
+
+const DO_NOTHING_STORED_WASM: &str = "do_nothing_stored.wasm";
+const HASH_KEY_NAME: &str = "do_nothing_hash";
+
+/// Forbids session code from writing to any `Key::Hash`, i.e. from installing contracts.
+#[derive(Debug)]
+struct NoContractInstallation;
+
+impl SessionEffectsCheck for NoContractInstallation {
+    fn check(&self, effects: &AdditiveMap<Key, Transform>) -> Result<(), String> {
+        let installs_contract = effects.iter().any(|(key, transform)| {
+            matches!(key, Key::Hash(_)) && *transform != Transform::Identity
+        });
+        if installs_contract {
+            return Err("contract installation is disabled".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[ignore]
+#[test]
+fn should_discard_session_effects_failing_check_but_charge_for_payment() {
+    let mut builder = InMemoryWasmTestBuilder::default()
+        .with_session_effects_check(Arc::new(NoContractInstallation));
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let main_purse = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .main_purse();
+    let balance_before = builder.get_purse_balance(main_purse);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        DO_NOTHING_STORED_WASM,
+        RuntimeArgs::default(),
+    )
+    .build();
+    builder.exec(exec_request).expect_failure().commit();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(error, Error::SessionEffectsRejected(_)),
+        "unexpected error: {:?}",
+        error
+    );
+
+    // The contract was not installed, but the deploy was still charged.
+    let account = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(!account.named_keys().contains_key(HASH_KEY_NAME));
+    assert!(builder.get_purse_balance(main_purse) < balance_before);
+}
@@ -11,8 +11,8 @@ use casper_execution_engine::{
     core::{
         engine_state::{
             engine_config::{
-                DEFAULT_MINIMUM_DELEGATION_AMOUNT, DEFAULT_STRICT_ARGUMENT_CHECKING,
-                DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS,
+                DEFAULT_ALLOW_SEPARATE_REWARD_PURSES, DEFAULT_MINIMUM_DELEGATION_AMOUNT,
+                DEFAULT_STRICT_ARGUMENT_CHECKING, DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS,
             },
             EngineConfig, Error, ExecuteRequest, DEFAULT_MAX_QUERY_DEPTH,
             DEFAULT_MAX_RUNTIME_CALL_STACK_HEIGHT,
@@ -20,6 +20,7 @@ use casper_execution_engine::{
         execution::Error as ExecError,
     },
     shared::{
+        fee_handling::{DEFAULT_FEE_HANDLING, DEFAULT_REFUND_HANDLING},
         host_function_costs::HostFunctionCosts,
         opcode_costs::OpcodeCosts,
         storage_costs::StorageCosts,
@@ -287,6 +288,9 @@ fn should_run_ee_966_regression_when_growing_mem_after_upgrade() {
         DEFAULT_MINIMUM_DELEGATION_AMOUNT,
         DEFAULT_STRICT_ARGUMENT_CHECKING,
         DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS,
+        DEFAULT_ALLOW_SEPARATE_REWARD_PURSES,
+        DEFAULT_REFUND_HANDLING,
+        DEFAULT_FEE_HANDLING,
         *DOUBLED_WASM_MEMORY_LIMIT,
         SystemConfig::default(),
     );
@@ -9,14 +9,19 @@ use casper_execution_engine::{
     core::{
         engine_state::{
             engine_config::{
-                DEFAULT_MINIMUM_DELEGATION_AMOUNT, DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS,
+                DEFAULT_ALLOW_SEPARATE_REWARD_PURSES, DEFAULT_MINIMUM_DELEGATION_AMOUNT,
+                DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS,
             },
             EngineConfig, Error, SystemContractRegistry, DEFAULT_MAX_QUERY_DEPTH,
             DEFAULT_MAX_RUNTIME_CALL_STACK_HEIGHT,
         },
         execution,
     },
-    shared::{system_config::SystemConfig, wasm_config::WasmConfig},
+    shared::{
+        fee_handling::{DEFAULT_FEE_HANDLING, DEFAULT_REFUND_HANDLING},
+        system_config::SystemConfig,
+        wasm_config::WasmConfig,
+    },
 };
 use casper_hashing::Digest;
 use casper_types::{
@@ -81,6 +86,9 @@ fn setup() -> InMemoryWasmTestBuilder {
         DEFAULT_MINIMUM_DELEGATION_AMOUNT,
         strict_argument_checking,
         DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS,
+        DEFAULT_ALLOW_SEPARATE_REWARD_PURSES,
+        DEFAULT_REFUND_HANDLING,
+        DEFAULT_FEE_HANDLING,
         WasmConfig::default(),
         SystemConfig::default(),
     );
@@ -10,15 +10,21 @@ use casper_engine_test_support::{
     DEFAULT_SYSTEM_CONFIG, DEFAULT_UNBONDING_DELAY, DEFAULT_VALIDATOR_SLOTS,
     DEFAULT_VESTING_SCHEDULE_PERIOD_MILLIS, DEFAULT_WASM_CONFIG,
 };
-use casper_execution_engine::core::{
-    engine_state::{
-        self,
-        engine_config::{DEFAULT_MINIMUM_DELEGATION_AMOUNT, DEFAULT_STRICT_ARGUMENT_CHECKING},
-        genesis::GenesisValidator,
-        EngineConfig, ExecConfig, GenesisAccount, RunGenesisRequest, DEFAULT_MAX_QUERY_DEPTH,
-        DEFAULT_MAX_RUNTIME_CALL_STACK_HEIGHT,
+use casper_execution_engine::{
+    core::{
+        engine_state::{
+            self,
+            engine_config::{
+                DEFAULT_ALLOW_SEPARATE_REWARD_PURSES, DEFAULT_MINIMUM_DELEGATION_AMOUNT,
+                DEFAULT_STRICT_ARGUMENT_CHECKING,
+            },
+            genesis::GenesisValidator,
+            EngineConfig, ExecConfig, GenesisAccount, RunGenesisRequest, DEFAULT_MAX_QUERY_DEPTH,
+            DEFAULT_MAX_RUNTIME_CALL_STACK_HEIGHT,
+        },
+        execution,
     },
-    execution,
+    shared::fee_handling::{DEFAULT_FEE_HANDLING, DEFAULT_REFUND_HANDLING},
 };
 use casper_types::{
     runtime_args,
@@ -198,6 +204,9 @@ fn should_immediatelly_unbond_genesis_validator_with_zero_day_vesting_schedule()
         DEFAULT_MINIMUM_DELEGATION_AMOUNT,
         DEFAULT_STRICT_ARGUMENT_CHECKING,
         vesting_schedule_period_millis,
+        DEFAULT_ALLOW_SEPARATE_REWARD_PURSES,
+        DEFAULT_REFUND_HANDLING,
+        DEFAULT_FEE_HANDLING,
         Default::default(),
         Default::default(),
     );
@@ -346,6 +355,9 @@ fn should_immediatelly_unbond_genesis_validator_with_zero_day_vesting_schedule_a
         DEFAULT_MINIMUM_DELEGATION_AMOUNT,
         DEFAULT_STRICT_ARGUMENT_CHECKING,
         vesting_schedule_period_millis,
+        DEFAULT_ALLOW_SEPARATE_REWARD_PURSES,
+        DEFAULT_REFUND_HANDLING,
+        DEFAULT_FEE_HANDLING,
         Default::default(),
         Default::default(),
     );
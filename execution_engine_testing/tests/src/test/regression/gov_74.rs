@@ -8,14 +8,15 @@ use casper_execution_engine::{
     core::{
         engine_state::{
             engine_config::{
-                DEFAULT_MINIMUM_DELEGATION_AMOUNT, DEFAULT_STRICT_ARGUMENT_CHECKING,
-                DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS,
+                DEFAULT_ALLOW_SEPARATE_REWARD_PURSES, DEFAULT_MINIMUM_DELEGATION_AMOUNT,
+                DEFAULT_STRICT_ARGUMENT_CHECKING, DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS,
             },
             EngineConfig, Error, DEFAULT_MAX_QUERY_DEPTH, DEFAULT_MAX_RUNTIME_CALL_STACK_HEIGHT,
         },
         execution::Error as ExecError,
     },
     shared::{
+        fee_handling::{DEFAULT_FEE_HANDLING, DEFAULT_REFUND_HANDLING},
         wasm_config::{WasmConfig, DEFAULT_WASM_MAX_MEMORY},
         wasm_prep::DEFAULT_MAX_PARAMETER_COUNT,
     },
@@ -111,6 +112,9 @@ fn should_observe_stack_height_limit() {
             DEFAULT_MINIMUM_DELEGATION_AMOUNT,
             DEFAULT_STRICT_ARGUMENT_CHECKING,
             DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS,
+            DEFAULT_ALLOW_SEPARATE_REWARD_PURSES,
+            DEFAULT_REFUND_HANDLING,
+            DEFAULT_FEE_HANDLING,
             WasmConfig::new(
                 DEFAULT_WASM_MAX_MEMORY,
                 NEW_WASM_STACK_HEIGHT,
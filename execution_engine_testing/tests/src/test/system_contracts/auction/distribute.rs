@@ -11,8 +11,17 @@ use casper_engine_test_support::{
     PRODUCTION_ROUND_SEIGNIORAGE_RATE, PRODUCTION_RUN_GENESIS_REQUEST, SYSTEM_ADDR,
     TIMESTAMP_MILLIS_INCREMENT,
 };
-use casper_execution_engine::core::engine_state::{
-    engine_config::DEFAULT_MINIMUM_DELEGATION_AMOUNT, step::RewardItem,
+use casper_execution_engine::{
+    core::engine_state::{
+        engine_config::{
+            DEFAULT_MAX_ASSOCIATED_KEYS, DEFAULT_MINIMUM_DELEGATION_AMOUNT,
+            DEFAULT_STRICT_ARGUMENT_CHECKING, DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS,
+        },
+        step::RewardItem,
+        EngineConfig, DEFAULT_MAX_QUERY_DEPTH, DEFAULT_MAX_RUNTIME_CALL_STACK_HEIGHT,
+    },
+    shared::{system_config::SystemConfig, wasm_config::WasmConfig},
+    storage::global_state::in_memory::InMemoryGlobalState,
 };
 use casper_types::{
     self,
@@ -22,18 +31,21 @@ use casper_types::{
         self, Bid, Bids, DelegationRate, Delegator, SeigniorageAllocation,
         SeigniorageRecipientsSnapshot, ARG_AMOUNT, ARG_DELEGATION_RATE, ARG_DELEGATOR,
         ARG_PUBLIC_KEY, ARG_REWARD_FACTORS, ARG_VALIDATOR, BLOCK_REWARD,
-        DELEGATION_RATE_DENOMINATOR, METHOD_DISTRIBUTE, SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY,
+        DELEGATION_RATE_DENOMINATOR, METHOD_DISTRIBUTE, REWARD_PURSE_KEY,
+        SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY,
     },
     EraId, Key, ProtocolVersion, PublicKey, RuntimeArgs, SecretKey, U512,
 };
 
 const ARG_ENTRY_POINT: &str = "entry_point";
 const ARG_TARGET: &str = "target";
+const ARG_PURSE_NAME: &str = "purse_name";
 
 const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
 const CONTRACT_AUCTION_BIDS: &str = "auction_bids.wasm";
 const CONTRACT_ADD_BID: &str = "add_bid.wasm";
 const CONTRACT_DELEGATE: &str = "delegate.wasm";
+const CONTRACT_CREATE_PURSE_01: &str = "create_purse_01.wasm";
 const TRANSFER_AMOUNT: u64 = MINIMUM_ACCOUNT_CREATION_BALANCE;
 
 static VALIDATOR_1: Lazy<PublicKey> = Lazy::new(|| {
@@ -4659,3 +4671,152 @@ fn delegator_full_unbond_during_first_reward_era() {
     let delegator = get_delegator_bid(&mut builder, VALIDATOR_1.clone(), DELEGATOR_1.clone());
     assert!(delegator.is_none());
 }
+
+#[test]
+fn should_distribute_validator_reward_into_reward_purse() {
+    const VALIDATOR_1_STAKE: u64 = 1_000_000;
+    const DELEGATION_RATE: DelegationRate = DELEGATION_RATE_DENOMINATOR;
+    const VALIDATOR_1_REWARD_FACTOR: u64 = BLOCK_REWARD;
+
+    let engine_config = EngineConfig::new(
+        DEFAULT_MAX_QUERY_DEPTH,
+        DEFAULT_MAX_ASSOCIATED_KEYS,
+        DEFAULT_MAX_RUNTIME_CALL_STACK_HEIGHT,
+        DEFAULT_MINIMUM_DELEGATION_AMOUNT,
+        DEFAULT_STRICT_ARGUMENT_CHECKING,
+        DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS,
+        true,
+        WasmConfig::default(),
+        SystemConfig::default(),
+    );
+
+    let system_fund_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *SYSTEM_ADDR,
+            ARG_AMOUNT => U512::from(TRANSFER_AMOUNT)
+        },
+    )
+    .build();
+
+    let validator_1_fund_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *VALIDATOR_1_ADDR,
+            ARG_AMOUNT => U512::from(TRANSFER_AMOUNT)
+        },
+    )
+    .build();
+
+    let validator_1_create_reward_purse_request = ExecuteRequestBuilder::standard(
+        *VALIDATOR_1_ADDR,
+        CONTRACT_CREATE_PURSE_01,
+        runtime_args! {
+            ARG_PURSE_NAME => REWARD_PURSE_KEY,
+        },
+    )
+    .build();
+
+    let validator_1_add_bid_request = ExecuteRequestBuilder::standard(
+        *VALIDATOR_1_ADDR,
+        CONTRACT_ADD_BID,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(VALIDATOR_1_STAKE),
+            ARG_DELEGATION_RATE => DELEGATION_RATE,
+            ARG_PUBLIC_KEY => VALIDATOR_1.clone(),
+        },
+    )
+    .build();
+
+    let post_genesis_requests = vec![
+        system_fund_request,
+        validator_1_fund_request,
+        validator_1_create_reward_purse_request,
+        validator_1_add_bid_request,
+    ];
+
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let mut builder = InMemoryWasmTestBuilder::new(global_state, engine_config, None);
+
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let initial_supply = builder.total_supply(None);
+    let expected_total_reward = *GENESIS_ROUND_SEIGNIORAGE_RATE * initial_supply;
+
+    for request in post_genesis_requests {
+        builder.exec(request).commit().expect_success();
+    }
+
+    let reward_purse = *builder
+        .get_expected_account(*VALIDATOR_1_ADDR)
+        .named_keys()
+        .get(REWARD_PURSE_KEY)
+        .and_then(Key::as_uref)
+        .expect("should have reward purse");
+
+    for _ in 0..=builder.get_auction_delay() {
+        let step_request = StepRequestBuilder::new()
+            .with_parent_state_hash(builder.get_post_state_hash())
+            .with_protocol_version(ProtocolVersion::V1_0_0)
+            .with_next_era_id(builder.get_era().successor())
+            .with_run_auction(true)
+            .build();
+        builder
+            .step(step_request)
+            .expect("must execute step request");
+    }
+
+    let step_request = StepRequestBuilder::new()
+        .with_parent_state_hash(builder.get_post_state_hash())
+        .with_protocol_version(ProtocolVersion::V1_0_0)
+        .with_reward_item(RewardItem::new(
+            VALIDATOR_1.clone(),
+            VALIDATOR_1_REWARD_FACTOR,
+        ))
+        .with_run_auction(true)
+        .with_next_era_id(builder.get_era().successor())
+        .build();
+
+    builder
+        .step(step_request)
+        .expect("must execute step successfully");
+
+    let validator_1_expected_payout = expected_total_reward.to_integer();
+
+    // The reward is paid into the reward purse rather than reinvested.
+    let validator_1_stake = *get_validator_bid(&mut builder, VALIDATOR_1.clone())
+        .expect("should have validator bid")
+        .staked_amount();
+    assert_eq!(validator_1_stake, U512::from(VALIDATOR_1_STAKE));
+    assert_eq!(
+        builder.get_purse_balance(reward_purse),
+        validator_1_expected_payout
+    );
+
+    let era_info = {
+        let era = builder.get_era() - 1;
+
+        let era_info_value = builder
+            .query(None, Key::EraInfo(era), &[])
+            .expect("should have value");
+
+        era_info_value
+            .as_era_info()
+            .cloned()
+            .expect("should be era info")
+    };
+
+    assert!(matches!(
+        era_info.select(VALIDATOR_1.clone()).next(),
+        Some(SeigniorageAllocation::ValidatorRewardPurse {
+            validator_public_key,
+            reward_purse: allocated_purse,
+            amount,
+        })
+        if *validator_public_key == *VALIDATOR_1
+            && allocated_purse.addr() == reward_purse.addr()
+            && *amount == validator_1_expected_payout
+    ));
+}
@@ -0,0 +1,113 @@
+use num_rational::Ratio;
+
+use casper_engine_test_support::{
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR, DEFAULT_PAYMENT,
+    PRODUCTION_RUN_GENESIS_REQUEST,
+};
+use casper_execution_engine::{
+    core::engine_state::{
+        engine_config::{
+            DEFAULT_ALLOW_SEPARATE_REWARD_PURSES, DEFAULT_MAX_ASSOCIATED_KEYS,
+            DEFAULT_MINIMUM_DELEGATION_AMOUNT, DEFAULT_STRICT_ARGUMENT_CHECKING,
+            DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS,
+        },
+        EngineConfig, DEFAULT_MAX_QUERY_DEPTH, DEFAULT_MAX_RUNTIME_CALL_STACK_HEIGHT,
+    },
+    shared::{
+        fee_handling::{
+            FeeHandling, RefundHandling, DEFAULT_FEE_HANDLING, DEFAULT_REFUND_HANDLING,
+        },
+        system_config::SystemConfig,
+        wasm_config::WasmConfig,
+    },
+};
+use casper_types::{RuntimeArgs, U512};
+
+const DO_NOTHING_WASM: &str = "do_nothing.wasm";
+
+fn setup(refund_handling: RefundHandling, fee_handling: FeeHandling) -> InMemoryWasmTestBuilder {
+    let engine_config = EngineConfig::new(
+        DEFAULT_MAX_QUERY_DEPTH,
+        DEFAULT_MAX_ASSOCIATED_KEYS,
+        DEFAULT_MAX_RUNTIME_CALL_STACK_HEIGHT,
+        DEFAULT_MINIMUM_DELEGATION_AMOUNT,
+        DEFAULT_STRICT_ARGUMENT_CHECKING,
+        DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS,
+        DEFAULT_ALLOW_SEPARATE_REWARD_PURSES,
+        refund_handling,
+        fee_handling,
+        WasmConfig::default(),
+        SystemConfig::default(),
+    );
+
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+    builder
+}
+
+fn main_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let account = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account");
+    builder.get_purse_balance(account.main_purse())
+}
+
+fn do_nothing(builder: &mut InMemoryWasmTestBuilder) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        DO_NOTHING_WASM,
+        RuntimeArgs::default(),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_burn_fees() {
+    let mut builder = setup(DEFAULT_REFUND_HANDLING, FeeHandling::Burn);
+
+    let total_supply_before = builder.total_supply(None);
+    let proposer_balance_before = builder.get_proposer_purse_balance();
+
+    do_nothing(&mut builder);
+
+    assert_eq!(
+        builder.get_proposer_purse_balance(),
+        proposer_balance_before,
+        "proposer should not be paid if fees are burned"
+    );
+    assert_eq!(
+        builder.total_supply(None),
+        total_supply_before - *DEFAULT_PAYMENT,
+        "the whole payment should be burned as nothing is refunded"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_refund_unspent_payment() {
+    let refund_handling = RefundHandling::Refund {
+        refund_ratio: Ratio::new(1, 1),
+    };
+    let mut builder = setup(refund_handling, DEFAULT_FEE_HANDLING);
+
+    let total_supply_before = builder.total_supply(None);
+    let proposer_balance_before = builder.get_proposer_purse_balance();
+    let account_balance_before = main_purse_balance(&builder);
+
+    do_nothing(&mut builder);
+
+    let fees = builder.get_proposer_purse_balance() - proposer_balance_before;
+    assert!(!fees.is_zero());
+    assert!(
+        fees < *DEFAULT_PAYMENT,
+        "unspent payment should be refunded"
+    );
+    assert_eq!(
+        account_balance_before - main_purse_balance(&builder),
+        fees,
+        "account should only pay the fees"
+    );
+    assert_eq!(builder.total_supply(None), total_supply_before);
+}
@@ -15,6 +15,9 @@ use tracing::{error, warn};
 /// Generally the "message" will be a brief const &str, where additional request-specific info can
 /// be provided via the `additional_info` parameter of [`Error::new`].
 ///
+/// Error codes indicating a transient condition, e.g. that the server is too busy, should override
+/// [`ErrorCodeT::is_retriable`] so that clients can tell which failed requests are worth retrying.
+///
 /// # Example
 ///
 /// ```
@@ -51,6 +54,13 @@ pub trait ErrorCodeT:
     fn is_reserved() -> bool {
         false
     }
+
+    /// Whether a request which failed with this error code may succeed if retried unchanged later.
+    ///
+    /// This is returned as the "retriable" field of the `Error`, and defaults to `false`.
+    fn is_retriable(self) -> bool {
+        false
+    }
 }
 
 /// The various reserved codes which can be returned in the JSON-RPC response's "error" object.
@@ -88,6 +98,11 @@ impl ErrorCodeT for ReservedErrorCode {
     fn is_reserved() -> bool {
         true
     }
+
+    /// Only an internal error may be resolved by retrying; the others indicate a malformed request.
+    fn is_retriable(self) -> bool {
+        self == ReservedErrorCode::InternalError
+    }
 }
 
 /// An object suitable to be returned in a JSON-RPC response as the "error" field.
@@ -104,6 +119,9 @@ pub struct Error {
     /// Additional information about the error.
     #[serde(skip_serializing_if = "Option::is_none")]
     data: Option<Value>,
+    /// Whether the request may succeed if retried unchanged later.
+    #[serde(default)]
+    retriable: bool,
 }
 
 impl Error {
@@ -123,6 +141,7 @@ impl Error {
     /// [`ReservedErrorCode::InternalError`] with the "data" field being a String providing more
     /// info on the underlying error.
     pub fn new<C: ErrorCodeT, T: Serialize>(error_code: C, additional_info: T) -> Self {
+        let retriable = error_code.is_retriable();
         let (code, message): (i64, &'static str) = error_code.into();
 
         if !C::is_reserved() && (-32768..=-32100).contains(&code) {
@@ -135,6 +154,7 @@ impl Error {
                     "attempted to return reserved error code {}",
                     code
                 ))),
+                retriable: ReservedErrorCode::InternalError.is_retriable(),
             };
         }
 
@@ -151,6 +171,7 @@ impl Error {
                         "failed to json-encode additional info in json-rpc error: {}",
                         error
                     ))),
+                    retriable: ReservedErrorCode::InternalError.is_retriable(),
                 };
             }
         };
@@ -159,8 +180,14 @@ impl Error {
             code,
             message: Cow::Borrowed(message),
             data,
+            retriable,
         }
     }
+
+    /// Returns whether the request may succeed if retried unchanged later.
+    pub fn is_retriable(&self) -> bool {
+        self.retriable
+    }
 }
 
 #[cfg(test)]
@@ -212,10 +239,10 @@ mod tests {
 
     #[test]
     fn should_construct_reserved_error() {
-        const EXPECTED_WITH_DATA: &str =
-            r#"{"code":-32700,"message":"Parse error","data":{"context":"TEST","id":1314}}"#;
-        const EXPECTED_WITHOUT_DATA: &str = r#"{"code":-32601,"message":"Method not found"}"#;
-        const EXPECTED_WITH_BAD_DATA: &str = r#"{"code":-32603,"message":"Internal error","data":"failed to json-encode additional info in json-rpc error: won't encode"}"#;
+        const EXPECTED_WITH_DATA: &str = r#"{"code":-32700,"message":"Parse error","data":{"context":"TEST","id":1314},"retriable":false}"#;
+        const EXPECTED_WITHOUT_DATA: &str =
+            r#"{"code":-32601,"message":"Method not found","retriable":false}"#;
+        const EXPECTED_WITH_BAD_DATA: &str = r#"{"code":-32603,"message":"Internal error","data":"failed to json-encode additional info in json-rpc error: won't encode","retriable":true}"#;
 
         let error_with_data = Error::new(ReservedErrorCode::ParseError, AdditionalInfo::default());
         let encoded = serde_json::to_string(&error_with_data).unwrap();
@@ -232,10 +259,10 @@ mod tests {
 
     #[test]
     fn should_construct_custom_error() {
-        const EXPECTED_WITH_DATA: &str =
-            r#"{"code":-123,"message":"Valid test error","data":{"context":"TEST","id":1314}}"#;
-        const EXPECTED_WITHOUT_DATA: &str = r#"{"code":-123,"message":"Valid test error"}"#;
-        const EXPECTED_WITH_BAD_DATA: &str = r#"{"code":-32603,"message":"Internal error","data":"failed to json-encode additional info in json-rpc error: won't encode"}"#;
+        const EXPECTED_WITH_DATA: &str = r#"{"code":-123,"message":"Valid test error","data":{"context":"TEST","id":1314},"retriable":false}"#;
+        const EXPECTED_WITHOUT_DATA: &str =
+            r#"{"code":-123,"message":"Valid test error","retriable":false}"#;
+        const EXPECTED_WITH_BAD_DATA: &str = r#"{"code":-32603,"message":"Internal error","data":"failed to json-encode additional info in json-rpc error: won't encode","retriable":true}"#;
 
         let good_error_code = TestErrorCode {
             in_reserved_range: false,
@@ -254,9 +281,34 @@ mod tests {
         assert_eq!(encoded, EXPECTED_WITH_BAD_DATA);
     }
 
+    #[test]
+    fn should_construct_retriable_error() {
+        const EXPECTED: &str = r#"{"code":-124,"message":"Retriable test error","retriable":true}"#;
+
+        #[derive(Copy, Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+        struct RetriableErrorCode;
+
+        impl From<RetriableErrorCode> for (i64, &'static str) {
+            fn from(_: RetriableErrorCode) -> Self {
+                (-124, "Retriable test error")
+            }
+        }
+
+        impl ErrorCodeT for RetriableErrorCode {
+            fn is_retriable(self) -> bool {
+                true
+            }
+        }
+
+        let error = Error::new(RetriableErrorCode, None::<u8>);
+        assert!(error.is_retriable());
+        let encoded = serde_json::to_string(&error).unwrap();
+        assert_eq!(encoded, EXPECTED);
+    }
+
     #[test]
     fn should_fall_back_to_internal_error_on_bad_custom_error() {
-        const EXPECTED: &str = r#"{"code":-32603,"message":"Internal error","data":"attempted to return reserved error code -32603"}"#;
+        const EXPECTED: &str = r#"{"code":-32603,"message":"Internal error","data":"attempted to return reserved error code -32603","retriable":true}"#;
 
         let bad_error_code = TestErrorCode {
             in_reserved_range: true,
@@ -75,12 +75,12 @@ pub fn base_filter<P: AsRef<str>>(path: P, max_body_bytes: u32) -> BoxedFilter<(
 ///
 /// If `allow_unknown_fields` is `false`, requests with unknown fields will cause the server to
 /// respond with an error.
-async fn handle_body(
-    body: Bytes,
-    handlers: RequestHandlers,
+pub(crate) async fn handle_body(
+    body: &[u8],
+    handlers: &RequestHandlers,
     allow_unknown_fields: bool,
 ) -> Result<Response, Rejection> {
-    let response = match serde_json::from_slice::<Map<String, Value>>(&*body) {
+    let response = match serde_json::from_slice::<Map<String, Value>>(body) {
         Ok(unvalidated_request) => match Request::new(unvalidated_request, allow_unknown_fields) {
             Ok(request) => handlers.handle_request(request).await,
             Err(ErrorOrRejection::Error { id, error }) => {
@@ -117,9 +117,9 @@ pub fn main_filter(
     allow_unknown_fields: bool,
 ) -> BoxedFilter<(WithStatus<reply::Json>,)> {
     body::bytes()
-        .and_then(move |body| {
+        .and_then(move |body: Bytes| {
             let handlers = handlers.clone();
-            async move { handle_body(body, handlers, allow_unknown_fields).await }
+            async move { handle_body(&body, &handlers, allow_unknown_fields).await }
         })
         .map(|response| reply::with_status(reply::json(&response), StatusCode::OK))
         .boxed()
@@ -86,7 +86,7 @@ mod request_handlers;
 mod response;
 
 use http::{header::CONTENT_TYPE, Method};
-use warp::{filters::BoxedFilter, Filter, Reply};
+use warp::{cors::Builder as CorsBuilder, filters::BoxedFilter, Filter, Reply};
 
 pub use error::{Error, ErrorCodeT, ReservedErrorCode};
 pub use request::Params;
@@ -122,15 +122,31 @@ pub fn route<P: AsRef<str>>(
     max_body_bytes: u32,
     handlers: RequestHandlers,
     allow_unknown_fields: bool,
+) -> BoxedFilter<(impl Reply,)> {
+    route_with_cors(
+        path,
+        max_body_bytes,
+        handlers,
+        allow_unknown_fields,
+        warp::cors().allow_any_origin().allow_method(Method::POST),
+    )
+}
+
+/// Constructs a set of warp filters suitable for use in a JSON-RPC server, in the same way as
+/// [`route`], but using the given CORS filter in place of the default one.
+///
+/// `cors` is extended to allow "content-type" as a header, as this is required by all JSON-RPC
+/// requests.
+pub fn route_with_cors<P: AsRef<str>>(
+    path: P,
+    max_body_bytes: u32,
+    handlers: RequestHandlers,
+    allow_unknown_fields: bool,
+    cors: CorsBuilder,
 ) -> BoxedFilter<(impl Reply,)> {
     filters::base_filter(path, max_body_bytes)
         .and(filters::main_filter(handlers, allow_unknown_fields))
         .recover(filters::handle_rejection)
-        .with(
-            warp::cors()
-                .allow_any_origin()
-                .allow_header(CONTENT_TYPE)
-                .allow_method(Method::POST),
-        )
+        .with(cors.allow_header(CONTENT_TYPE))
         .boxed()
 }
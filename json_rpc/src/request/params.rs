@@ -137,7 +137,7 @@ mod tests {
             ErrorOrRejection::Error { id, error } => {
                 assert_eq!(id, original_id);
                 let expected_error = format!(
-                    r#"{{"code":-32600,"message":"Invalid Request","data":"If present, 'params' must be an Array or Object, but was {}"}}"#,
+                    r#"{{"code":-32600,"message":"Invalid Request","data":"If present, 'params' must be an Array or Object, but was {}","retriable":false}}"#,
                     expected_invalid_type_msg
                 );
                 assert_eq!(serde_json::to_string(&error).unwrap(), expected_error);
@@ -1,12 +1,21 @@
-use std::{collections::HashMap, future::Future, pin::Pin, sync::Arc};
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 
-use futures::FutureExt;
+use futures::{future, FutureExt};
 use serde::Serialize;
 use serde_json::Value;
 use tracing::{debug, error};
 
 use crate::{
     error::{Error, ReservedErrorCode},
+    filters,
     request::{Params, Request},
     response::Response,
 };
@@ -53,6 +62,28 @@ impl RequestHandlers {
             Err(error) => Response::new_failure(request.id, error),
         }
     }
+
+    /// Parses a JSON-RPC request from the given raw message, and handles it in the same way as a
+    /// request received via the HTTP filters.
+    ///
+    /// This is intended for transports other than HTTP where each message holds a single request,
+    /// e.g. WebSocket.
+    ///
+    /// Returns `None` if the request is a Notification as per the JSON-RPC specification, i.e. the
+    /// request doesn't contain an "id" field.  In this case, no response should be sent to the
+    /// client.
+    ///
+    /// If `allow_unknown_fields` is `false`, requests with unknown fields will cause an error
+    /// response.
+    pub async fn handle_raw_request(
+        &self,
+        raw_request: &[u8],
+        allow_unknown_fields: bool,
+    ) -> Option<Response> {
+        filters::handle_body(raw_request, self, allow_unknown_fields)
+            .await
+            .ok()
+    }
 }
 
 /// A builder for [`RequestHandlers`].
@@ -68,6 +99,14 @@ impl RequestHandlersBuilder {
         Self::default()
     }
 
+    /// Returns a new builder populated with all the handlers of `handlers`.
+    ///
+    /// This allows e.g. extending a shared set of handlers with ones specific to a single client
+    /// connection.
+    pub fn from_handlers(handlers: &RequestHandlers) -> Self {
+        Self((*handlers.0).clone())
+    }
+
     /// Adds a new request-handler which will be called to handle all JSON-RPC requests with the
     /// given "method" field.
     ///
@@ -108,8 +147,264 @@ impl RequestHandlersBuilder {
         }
     }
 
+    /// Replaces the handler of every registered "method" for which `is_permitted` returns `false`
+    /// with one which always fails with the given `error`.
+    ///
+    /// Unlike requests for unregistered methods, requests for such methods are answered with
+    /// `error` rather than a MethodNotFound error.
+    pub fn deny_methods<F>(&mut self, is_permitted: F, error: Error)
+    where
+        F: Fn(&str) -> bool,
+    {
+        for (method, handler) in self.0.iter_mut() {
+            if is_permitted(method) {
+                continue;
+            }
+            let error = error.clone();
+            *handler = Arc::new(move |_maybe_params| future::ready(Err(error.clone())).boxed());
+        }
+    }
+
+    /// Limits the number of requests handled concurrently by the handler of each registered
+    /// "method" to `max_concurrent_requests`.
+    ///
+    /// Requests exceeding the limit fail immediately with the error returned by
+    /// `on_limit_exceeded`, which is passed the relevant method.  Handlers registered after calling
+    /// this are not limited.
+    pub fn limit_concurrency<F>(&mut self, max_concurrent_requests: usize, on_limit_exceeded: F)
+    where
+        F: Fn(&str) -> Error + Send + Sync + 'static,
+    {
+        let on_limit_exceeded = Arc::new(on_limit_exceeded);
+        for (method, handler) in self.0.iter_mut() {
+            let method: &'static str = method;
+            let inner_handler = Arc::clone(handler);
+            let in_flight = Arc::new(AtomicUsize::new(0));
+            let on_limit_exceeded = Arc::clone(&on_limit_exceeded);
+            *handler = Arc::new(move |maybe_params| {
+                if in_flight.fetch_add(1, Ordering::SeqCst) >= max_concurrent_requests {
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    debug!(method, "exceeded concurrency limit");
+                    return future::ready(Err(on_limit_exceeded(method))).boxed();
+                }
+                let guard = InFlightGuard(Arc::clone(&in_flight));
+                let handle_request = inner_handler(maybe_params);
+                async move {
+                    let _guard = guard;
+                    handle_request.await
+                }
+                .boxed()
+            });
+        }
+    }
+
+    /// Wraps the handler of every registered "method" for which `is_guarded` returns `true` so that
+    /// `guard`, which is passed the relevant method, is awaited before the handler is invoked.
+    ///
+    /// If the guard fails, the request fails with the guard's error without invoking the handler.
+    /// Handlers registered after calling this are not guarded.
+    pub fn guard_methods<F, G, Fut>(&mut self, is_guarded: F, guard: G)
+    where
+        F: Fn(&str) -> bool,
+        G: Fn(&'static str) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), Error>> + Send + 'static,
+    {
+        let guard = Arc::new(guard);
+        for (method, handler) in self.0.iter_mut() {
+            if !is_guarded(method) {
+                continue;
+            }
+            let method: &'static str = *method;
+            let inner_handler = Arc::clone(handler);
+            let guard = Arc::clone(&guard);
+            *handler = Arc::new(move |maybe_params| {
+                let inner_handler = Arc::clone(&inner_handler);
+                let check_guard = guard(method);
+                async move {
+                    check_guard.await?;
+                    inner_handler(maybe_params).await
+                }
+                .boxed()
+            });
+        }
+    }
+
     /// Finalize building by converting `self` to a [`RequestHandlers`].
     pub fn build(self) -> RequestHandlers {
         RequestHandlers(Arc::new(self.0))
     }
 }
+
+/// Decrements the count of requests in flight for a single handler when dropped.
+struct InFlightGuard(Arc<AtomicUsize>);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn get(_params: Option<Params>) -> Result<&'static str, Error> {
+        Ok("got it")
+    }
+
+    async fn put(_params: Option<Params>) -> Result<&'static str, Error> {
+        Ok("put it")
+    }
+
+    fn handlers_with_get() -> RequestHandlers {
+        let mut handlers = RequestHandlersBuilder::new();
+        handlers.register_handler("get", Arc::new(get));
+        handlers.build()
+    }
+
+    #[tokio::test]
+    async fn should_handle_raw_request() {
+        let handlers = handlers_with_get();
+
+        let response = handlers
+            .handle_raw_request(br#"{"jsonrpc":"2.0","id":1,"method":"get"}"#, false)
+            .await
+            .expect("should respond to request with id");
+        assert_eq!(response.result::<String>().unwrap(), "got it");
+
+        let response = handlers
+            .handle_raw_request(br#"{"jsonrpc":"2.0","id":1,"method":"put"}"#, false)
+            .await
+            .expect("should respond to request with id");
+        assert!(response.is_failure());
+
+        let response = handlers
+            .handle_raw_request(b"not json", false)
+            .await
+            .expect("should respond to invalid request");
+        assert!(response.is_failure());
+    }
+
+    #[tokio::test]
+    async fn should_not_respond_to_raw_notification() {
+        let handlers = handlers_with_get();
+        assert!(handlers
+            .handle_raw_request(br#"{"jsonrpc":"2.0","method":"get"}"#, false)
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn should_extend_existing_handlers() {
+        let handlers = handlers_with_get();
+        let mut extended_handlers = RequestHandlersBuilder::from_handlers(&handlers);
+        extended_handlers.register_handler("put", Arc::new(put));
+        let extended_handlers = extended_handlers.build();
+
+        for (method, expected) in [("get", "got it"), ("put", "put it")] {
+            let raw_request = format!(r#"{{"jsonrpc":"2.0","id":1,"method":"{}"}}"#, method);
+            let response = extended_handlers
+                .handle_raw_request(raw_request.as_bytes(), false)
+                .await
+                .unwrap();
+            assert_eq!(response.result::<String>().unwrap(), expected);
+        }
+
+        // The original handlers should be unaffected.
+        let response = handlers
+            .handle_raw_request(br#"{"jsonrpc":"2.0","id":1,"method":"put"}"#, false)
+            .await
+            .unwrap();
+        assert!(response.is_failure());
+    }
+
+    #[tokio::test]
+    async fn should_deny_methods() {
+        let mut handlers = RequestHandlersBuilder::new();
+        handlers.register_handler("get", Arc::new(get));
+        handlers.register_handler("put", Arc::new(put));
+        let denied_error = Error::new(ReservedErrorCode::InvalidRequest, "denied");
+        handlers.deny_methods(|method| method == "get", denied_error.clone());
+        let handlers = handlers.build();
+
+        let response = handlers
+            .handle_raw_request(br#"{"jsonrpc":"2.0","id":1,"method":"get"}"#, false)
+            .await
+            .unwrap();
+        assert_eq!(response.result::<String>().unwrap(), "got it");
+
+        let response = handlers
+            .handle_raw_request(br#"{"jsonrpc":"2.0","id":1,"method":"put"}"#, false)
+            .await
+            .unwrap();
+        assert_eq!(response.error(), Some(&denied_error));
+    }
+
+    #[tokio::test]
+    async fn should_limit_concurrency() {
+        const WAIT_REQUEST: &[u8] = br#"{"jsonrpc":"2.0","id":1,"method":"wait"}"#;
+
+        async fn wait(_params: Option<Params>) -> Result<(), Error> {
+            future::pending().await
+        }
+
+        let mut handlers = RequestHandlersBuilder::new();
+        handlers.register_handler("get", Arc::new(get));
+        handlers.register_handler("wait", Arc::new(wait));
+        let limited_error = Error::new(ReservedErrorCode::InternalError, "busy");
+        let expected_error = limited_error.clone();
+        handlers.limit_concurrency(1, move |_method| limited_error.clone());
+        let handlers = handlers.build();
+
+        let mut first_wait = handlers.handle_raw_request(WAIT_REQUEST, false).boxed();
+        assert!(futures::poll!(&mut first_wait).is_pending());
+
+        // A second concurrent request for the same method should be refused.
+        let response = handlers
+            .handle_raw_request(WAIT_REQUEST, false)
+            .await
+            .unwrap();
+        assert_eq!(response.error(), Some(&expected_error));
+
+        // Other methods should be unaffected.
+        let response = handlers
+            .handle_raw_request(br#"{"jsonrpc":"2.0","id":1,"method":"get"}"#, false)
+            .await
+            .unwrap();
+        assert_eq!(response.result::<String>().unwrap(), "got it");
+
+        // Once the first request is finished, the method should be available again.
+        drop(first_wait);
+        let mut second_wait = handlers.handle_raw_request(WAIT_REQUEST, false).boxed();
+        assert!(futures::poll!(&mut second_wait).is_pending());
+    }
+
+    #[tokio::test]
+    async fn should_guard_methods() {
+        let mut handlers = RequestHandlersBuilder::new();
+        handlers.register_handler("get", Arc::new(get));
+        handlers.register_handler("put", Arc::new(put));
+        let guard_error = Error::new(ReservedErrorCode::InternalError, "not ready");
+        let expected_error = guard_error.clone();
+        handlers.guard_methods(
+            |method| method == "put",
+            move |method| {
+                assert_eq!(method, "put");
+                future::ready(Err(guard_error.clone()))
+            },
+        );
+        let handlers = handlers.build();
+
+        let response = handlers
+            .handle_raw_request(br#"{"jsonrpc":"2.0","id":1,"method":"get"}"#, false)
+            .await
+            .unwrap();
+        assert_eq!(response.result::<String>().unwrap(), "got it");
+
+        let response = handlers
+            .handle_raw_request(br#"{"jsonrpc":"2.0","id":1,"method":"put"}"#, false)
+            .await
+            .unwrap();
+        assert_eq!(response.error(), Some(&expected_error));
+    }
+}
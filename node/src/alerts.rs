@@ -0,0 +1,507 @@
+//! Alerting via webhooks.
+//!
+//! Critical node events, e.g. fatal errors, equivocations involving this node's key, the disk
+//! running out of space or block execution falling behind, are posted as JSON to a configured webhook, so that operators can be
+//! paged without running a full metrics stack.  The payload is rendered from a template, allowing
+//! it to match what the receiving service expects.
+//!
+//! Alerts of the same kind are sent at most once per configured repeat interval, except for fatal
+//! errors, which are always sent since the node exits right after.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use datasize::DataSize;
+use once_cell::sync::OnceCell;
+use reqwest::header::CONTENT_TYPE;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{debug, info, warn};
+
+use casper_types::{EraId, TimeDiff, Timestamp};
+
+use crate::reactor::supervision::SupervisedComponent;
+
+/// Default payload template.
+const DEFAULT_PAYLOAD_TEMPLATE: &str = r#"{"kind":"{{kind}}","severity":"{{severity}}","node":"{{node}}","timestamp":"{{timestamp}}","message":"{{message}}"}"#;
+/// Default timeout of a webhook request.
+const DEFAULT_TIMEOUT: &str = "10sec";
+/// Default minimum interval between alerts of the same kind.
+const DEFAULT_REPEAT_INTERVAL: &str = "30min";
+/// Default peer count below which the peer count is considered to have collapsed.
+const DEFAULT_MIN_PEER_COUNT: usize = 3;
+/// Default duration the peer count needs to stay collapsed for before alerting.
+const DEFAULT_PEER_COLLAPSE_DURATION: &str = "5min";
+/// Default free disk space below which to alert, in bytes.
+const DEFAULT_MIN_FREE_DISK_SPACE: u64 = 10 * 1024 * 1024 * 1024;
+
+/// The alerter, initialized at startup.
+///
+/// We use a static variable since fatal errors need to be alerted on by the reactor runner, which
+/// has no access to the components.
+static ALERTER: OnceCell<Alerter> = OnceCell::new();
+
+/// Alerting configuration.
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct AlertsConfig {
+    /// URL to post alerts to.  If not set, alerting is disabled.
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    /// Template of the JSON payload posted to the webhook.
+    ///
+    /// The placeholders `{{kind}}`, `{{severity}}`, `{{node}}`, `{{timestamp}}` and `{{message}}`
+    /// are replaced with the JSON-escaped values of the alert, so need to be enclosed in quotes.
+    pub payload_template: String,
+    /// Timeout of a webhook request.
+    pub timeout: TimeDiff,
+    /// Minimum interval between alerts of the same kind.
+    pub repeat_interval: TimeDiff,
+    /// Peer count below which the peer count is considered to have collapsed.
+    pub min_peer_count: usize,
+    /// Duration the peer count needs to stay collapsed for before alerting.
+    pub peer_collapse_duration: TimeDiff,
+    /// Free disk space on the storage volume below which to alert, in bytes.
+    pub min_free_disk_space: u64,
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        AlertsConfig {
+            webhook_url: None,
+            payload_template: DEFAULT_PAYLOAD_TEMPLATE.to_string(),
+            timeout: DEFAULT_TIMEOUT.parse().unwrap(),
+            repeat_interval: DEFAULT_REPEAT_INTERVAL.parse().unwrap(),
+            min_peer_count: DEFAULT_MIN_PEER_COUNT,
+            peer_collapse_duration: DEFAULT_PEER_COLLAPSE_DURATION.parse().unwrap(),
+            min_free_disk_space: DEFAULT_MIN_FREE_DISK_SPACE,
+        }
+    }
+}
+
+/// An error initializing alerting.
+#[derive(Debug, Error)]
+pub(crate) enum AlertsError {
+    /// The payload template doesn't render valid JSON.
+    #[error("alerts payload template does not render valid JSON: {0}")]
+    InvalidTemplate(serde_json::Error),
+    /// The HTTP client couldn't be created.
+    #[error("failed to create alerts HTTP client: {0}")]
+    Client(#[from] reqwest::Error),
+}
+
+/// The severity of an alert.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Severity {
+    /// The node stopped or is misbehaving, and needs attention right away.
+    Critical,
+    /// The node will likely need attention soon.
+    Warning,
+}
+
+impl Display for Severity {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Critical => write!(formatter, "critical"),
+            Severity::Warning => write!(formatter, "warning"),
+        }
+    }
+}
+
+/// A critical node event to alert on.
+#[derive(Clone, Debug)]
+pub(crate) enum Alert {
+    /// The node encountered a fatal error and is shutting down.
+    FatalError {
+        /// File the fatal error occurred in.
+        file: &'static str,
+        /// Line number where the fatal error occurred.
+        line: u32,
+        /// Error message.
+        msg: String,
+    },
+    /// This node's key equivocated, so it has been deactivated as a validator in the era.
+    OwnEquivocation {
+        /// The era of the equivocation.
+        era_id: EraId,
+    },
+    /// Another node is signing with this node's key, so this node deactivated itself as a
+    /// validator in the era.
+    DoppelgangerDetected {
+        /// The era the doppelganger was detected in.
+        era_id: EraId,
+    },
+    /// A new era couldn't be initialized.
+    EraTransitionFailed {
+        /// Description of the error.
+        error: String,
+    },
+    /// The peer count has been below the configured minimum for longer than the configured
+    /// duration.
+    PeerCountCollapse {
+        /// The current peer count.
+        peer_count: usize,
+        /// The configured minimum peer count.
+        min_peer_count: usize,
+        /// How long the peer count has been below the minimum.
+        duration: TimeDiff,
+    },
+    /// The free disk space on the storage volume is below the configured minimum.
+    LowDiskSpace {
+        /// The storage directory.
+        path: PathBuf,
+        /// The available disk space, in bytes.
+        available: u64,
+        /// The configured minimum disk space, in bytes.
+        min_free_disk_space: u64,
+    },
+    /// A supervised component crashed and will be restarted.
+    ComponentCrashed {
+        /// The crashed component.
+        component: SupervisedComponent,
+        /// The panic message or restart error.
+        message: String,
+    },
+    /// Block execution fell too far behind finalization, so consensus has been paused.
+    ExecutionBacklog {
+        /// The number of finalized blocks that have not been executed yet.
+        backlog: u64,
+        /// The configured maximum number of blocks execution may lag behind.
+        max_execution_delay: u64,
+    },
+}
+
+impl Alert {
+    /// Returns the kind of the alert, used to limit how often alerts are repeated.
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            Alert::FatalError { .. } => "fatal_error",
+            Alert::OwnEquivocation { .. } => "own_equivocation",
+            Alert::DoppelgangerDetected { .. } => "doppelganger_detected",
+            Alert::EraTransitionFailed { .. } => "era_transition_failed",
+            Alert::PeerCountCollapse { .. } => "peer_count_collapse",
+            Alert::LowDiskSpace { .. } => "low_disk_space",
+            Alert::ComponentCrashed { .. } => "component_crashed",
+            Alert::ExecutionBacklog { .. } => "execution_backlog",
+        }
+    }
+
+    /// Returns the severity of the alert.
+    pub(crate) fn severity(&self) -> Severity {
+        match self {
+            Alert::FatalError { .. }
+            | Alert::OwnEquivocation { .. }
+            | Alert::DoppelgangerDetected { .. }
+            | Alert::EraTransitionFailed { .. } => Severity::Critical,
+            Alert::PeerCountCollapse { .. }
+            | Alert::LowDiskSpace { .. }
+            | Alert::ComponentCrashed { .. }
+            | Alert::ExecutionBacklog { .. } => Severity::Warning,
+        }
+    }
+}
+
+impl Display for Alert {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Alert::FatalError { file, line, msg } => {
+                write!(formatter, "fatal error at {}:{}: {}", file, line, msg)
+            }
+            Alert::OwnEquivocation { era_id } => {
+                write!(formatter, "this node's key equivocated in {}", era_id)
+            }
+            Alert::DoppelgangerDetected { era_id } => write!(
+                formatter,
+                "another node is signing with this node's key in {}",
+                era_id
+            ),
+            Alert::EraTransitionFailed { error } => {
+                write!(formatter, "failed to create new era: {}", error)
+            }
+            Alert::PeerCountCollapse {
+                peer_count,
+                min_peer_count,
+                duration,
+            } => write!(
+                formatter,
+                "connected to {} peers, below the minimum of {}, for {}",
+                peer_count, min_peer_count, duration
+            ),
+            Alert::LowDiskSpace {
+                path,
+                available,
+                min_free_disk_space,
+            } => write!(
+                formatter,
+                "{} bytes free on the volume of {}, below the minimum of {} bytes",
+                available,
+                path.display(),
+                min_free_disk_space
+            ),
+            Alert::ComponentCrashed { component, message } => {
+                write!(formatter, "{} crashed: {}", component, message)
+            }
+            Alert::ExecutionBacklog {
+                backlog,
+                max_execution_delay,
+            } => write!(
+                formatter,
+                "{} finalized blocks not executed yet, more than the maximum of {}; consensus is \
+                paused",
+                backlog, max_execution_delay
+            ),
+        }
+    }
+}
+
+/// Posts alerts to the configured webhook.
+#[derive(Debug)]
+struct Alerter {
+    /// The URL to post alerts to.
+    webhook_url: String,
+    /// The template of the JSON payload.
+    payload_template: String,
+    /// The name of the node included in alerts.
+    node: String,
+    /// The minimum interval between alerts of the same kind.
+    repeat_interval: Duration,
+    /// The peer count below which the peer count is considered to have collapsed.
+    min_peer_count: usize,
+    /// The duration the peer count needs to stay collapsed for before alerting.
+    peer_collapse_duration: Duration,
+    /// The free disk space below which to alert, in bytes.
+    min_free_disk_space: u64,
+    /// The HTTP client, with the configured timeout.
+    client: reqwest::Client,
+    /// The time at which an alert of each kind was last sent.
+    last_sent: Mutex<HashMap<&'static str, Instant>>,
+}
+
+impl Alerter {
+    /// Returns whether an alert of the given kind is due, recording it as sent if so.
+    fn is_due(&self, alert: &Alert) -> bool {
+        if matches!(alert, Alert::FatalError { .. }) {
+            return true;
+        }
+        let mut last_sent = self
+            .last_sent
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let now = Instant::now();
+        match last_sent.get(alert.kind()) {
+            Some(sent) if now.duration_since(*sent) < self.repeat_interval => false,
+            _ => {
+                last_sent.insert(alert.kind(), now);
+                true
+            }
+        }
+    }
+}
+
+/// Initializes alerting with the given config, naming the node `node` in alerts.
+///
+/// Alerting stays disabled if no webhook URL is configured.  Only the first call has any effect.
+pub(crate) fn init(config: &AlertsConfig, node: String) -> Result<(), AlertsError> {
+    let webhook_url = match &config.webhook_url {
+        Some(webhook_url) => webhook_url.clone(),
+        None => return Ok(()),
+    };
+    let sample = Alert::EraTransitionFailed {
+        error: "\"sample\"\n".to_string(),
+    };
+    serde_json::from_str::<serde_json::Value>(&render(
+        &config.payload_template,
+        &sample,
+        &node,
+        Timestamp::zero(),
+    ))
+    .map_err(AlertsError::InvalidTemplate)?;
+    let client = reqwest::Client::builder()
+        .timeout(config.timeout.into())
+        .build()?;
+    let alerter = Alerter {
+        webhook_url,
+        payload_template: config.payload_template.clone(),
+        node,
+        repeat_interval: config.repeat_interval.into(),
+        min_peer_count: config.min_peer_count,
+        peer_collapse_duration: config.peer_collapse_duration.into(),
+        min_free_disk_space: config.min_free_disk_space,
+        client,
+        last_sent: Mutex::new(HashMap::new()),
+    };
+    drop(ALERTER.set(alerter));
+    Ok(())
+}
+
+/// Posts the alert to the webhook, unless alerting is disabled or an alert of the same kind was
+/// sent recently.
+///
+/// Failures to deliver the alert are logged.
+pub(crate) async fn send(alert: Alert) {
+    let alerter = match ALERTER.get() {
+        Some(alerter) => alerter,
+        None => return,
+    };
+    if !alerter.is_due(&alert) {
+        debug!(%alert, "not repeating recent alert");
+        return;
+    }
+    let payload = render(
+        &alerter.payload_template,
+        &alert,
+        &alerter.node,
+        Timestamp::now(),
+    );
+    let result = alerter
+        .client
+        .post(&alerter.webhook_url)
+        .header(CONTENT_TYPE, "application/json")
+        .body(payload)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status);
+    match result {
+        Ok(_) => info!(kind = alert.kind(), %alert, "sent alert"),
+        Err(error) => warn!(kind = alert.kind(), %alert, %error, "failed to send alert"),
+    }
+}
+
+/// Renders the payload of `alert` from `template`.
+fn render(template: &str, alert: &Alert, node: &str, timestamp: Timestamp) -> String {
+    template
+        .replace("{{kind}}", &json_escape(alert.kind()))
+        .replace("{{severity}}", &json_escape(&alert.severity().to_string()))
+        .replace("{{node}}", &json_escape(node))
+        .replace("{{timestamp}}", &json_escape(&timestamp.to_string()))
+        .replace("{{message}}", &json_escape(&alert.to_string()))
+}
+
+/// Escapes `value` for use inside a JSON string.
+fn json_escape(value: &str) -> String {
+    let quoted = serde_json::Value::from(value).to_string();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+/// Periodic checks of the node's health, alerting on a collapsed peer count or low disk space.
+#[derive(DataSize, Debug, Default)]
+pub(crate) struct HealthChecks {
+    /// The time since which the peer count has been below the minimum, if it is.
+    #[data_size(skip)]
+    peer_count_low_since: Option<Instant>,
+    /// Whether the current collapse of the peer count has been alerted on.
+    peer_collapse_alerted: bool,
+}
+
+impl HealthChecks {
+    /// Checks the peer count and the free disk space on the volume of `storage_path`, alerting if
+    /// either is too low.
+    pub(crate) fn check(&mut self, peer_count: usize, storage_path: &Path) {
+        let alerter = match ALERTER.get() {
+            Some(alerter) => alerter,
+            None => return,
+        };
+        if let Some(alert) = self.check_peer_count(
+            peer_count,
+            alerter.min_peer_count,
+            alerter.peer_collapse_duration,
+        ) {
+            tokio::spawn(send(alert));
+        }
+        match fs2::available_space(storage_path) {
+            Ok(available) if available < alerter.min_free_disk_space => {
+                tokio::spawn(send(Alert::LowDiskSpace {
+                    path: storage_path.to_path_buf(),
+                    available,
+                    min_free_disk_space: alerter.min_free_disk_space,
+                }));
+            }
+            Ok(_) => (),
+            Err(error) => {
+                warn!(path = %storage_path.display(), %error, "failed to check free disk space")
+            }
+        }
+    }
+
+    /// Returns an alert if the peer count has been below `min_peer_count` for `collapse_duration`
+    /// and it hasn't been alerted on yet.
+    fn check_peer_count(
+        &mut self,
+        peer_count: usize,
+        min_peer_count: usize,
+        collapse_duration: Duration,
+    ) -> Option<Alert> {
+        if peer_count >= min_peer_count {
+            self.peer_count_low_since = None;
+            self.peer_collapse_alerted = false;
+            return None;
+        }
+        let low_since = *self.peer_count_low_since.get_or_insert_with(Instant::now);
+        let duration = low_since.elapsed();
+        if self.peer_collapse_alerted || duration < collapse_duration {
+            return None;
+        }
+        self.peer_collapse_alerted = true;
+        Some(Alert::PeerCountCollapse {
+            peer_count,
+            min_peer_count,
+            duration: duration.into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_render_escaped_payload() {
+        let alert = Alert::FatalError {
+            file: "src/reactor.rs",
+            line: 42,
+            msg: "\"quoted\"\nand on a new line".to_string(),
+        };
+        let payload = render(
+            AlertsConfig::default().payload_template.as_str(),
+            &alert,
+            "node-1",
+            Timestamp::zero(),
+        );
+        let json: serde_json::Value = serde_json::from_str(&payload).unwrap();
+        assert_eq!(json["kind"], "fatal_error");
+        assert_eq!(json["severity"], "critical");
+        assert_eq!(json["node"], "node-1");
+        assert_eq!(
+            json["message"],
+            "fatal error at src/reactor.rs:42: \"quoted\"\nand on a new line"
+        );
+    }
+
+    #[test]
+    fn should_alert_once_per_peer_count_collapse() {
+        let mut health_checks = HealthChecks::default();
+        assert!(health_checks
+            .check_peer_count(1, 3, Duration::ZERO)
+            .is_some());
+        assert!(health_checks
+            .check_peer_count(0, 3, Duration::ZERO)
+            .is_none());
+
+        // Recovering ends the collapse, so the next one is alerted on again.
+        assert!(health_checks
+            .check_peer_count(3, 3, Duration::ZERO)
+            .is_none());
+        assert!(health_checks
+            .check_peer_count(2, 3, Duration::from_secs(60))
+            .is_none());
+        assert!(health_checks
+            .check_peer_count(2, 3, Duration::ZERO)
+            .is_some());
+    }
+}
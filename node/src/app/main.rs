@@ -13,11 +13,12 @@ use structopt::StructOpt;
 use tokio::runtime::Builder;
 use tracing::info;
 
-use casper_node::{cli::Cli, MAX_THREAD_COUNT};
+use casper_node::{cli::Cli, in_supervised_component, MAX_THREAD_COUNT};
 
 /// Aborting panic hook.
 ///
-/// Will exit the application using `abort` when an error occurs. Always shows a backtrace.
+/// Will exit the application using `abort` when an error occurs, unless it occurred in a supervised
+/// component. Always shows a backtrace.
 fn panic_hook(info: &PanicInfo) {
     let backtrace = Backtrace::new();
 
@@ -33,6 +34,11 @@ fn panic_hook(info: &PanicInfo) {
         eprintln!("{}", info);
     }
 
+    // Panics in supervised components are caught, and the component restarted.
+    if in_supervised_component() {
+        return;
+    }
+
     // Abort after a panic, even if only a worker thread panicked.
     process::abort()
 }
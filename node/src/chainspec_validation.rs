@@ -0,0 +1,792 @@
+//! Validation of a chainspec and config pair ahead of running the node.
+//!
+//! Unlike the checks the node runs on startup, which stop at the first problem found, this reports
+//! every problem found, each with the setting it concerns, so that operators can fix all of them
+//! before the network starts.
+
+use std::{
+    fmt::{self, Display, Formatter},
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use num::rational::Ratio;
+use serde::Serialize;
+use toml::Value;
+
+use casper_execution_engine::shared::wasm_config::MAX_WASM_MEMORY_PAGES;
+use casper_hashing::ChunkWithProof;
+use casper_types::{ProtocolVersion, PublicKey, TimeDiff};
+
+use crate::{
+    cli::{self, ConfigExt},
+    components::chainspec_loader,
+    reactor::participating,
+    types::{
+        chainspec::{ChainspecRawBytes, CHAINSPEC_FILENAME},
+        ActivationPoint, Chainspec, NodeMode,
+    },
+    utils::Loadable,
+};
+
+/// Deprecated names of cost table entries which are still accepted, and their current names.
+const COST_ALIASES: [(&str, &str); 2] = [
+    ("read_value_local", "dictionary_get"),
+    ("write_local", "dictionary_put"),
+];
+
+/// The severity of a problem found.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Severity {
+    /// The node will refuse to run, or the network can't operate as intended.
+    Error,
+    /// The node will run, but the setting is likely a mistake.
+    Warning,
+}
+
+impl Display for Severity {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(formatter, "error"),
+            Severity::Warning => write!(formatter, "warning"),
+        }
+    }
+}
+
+/// A problem found with a chainspec or config.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
+pub(crate) struct Diagnostic {
+    /// The severity of the problem.
+    pub(crate) severity: Severity,
+    /// The file, or the dotted path of the setting, which the problem concerns.
+    pub(crate) location: String,
+    /// A description of the problem.
+    pub(crate) message: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "{}: {}: {}",
+            self.severity, self.location, self.message
+        )
+    }
+}
+
+/// The problems found so far.
+#[derive(Default)]
+struct Diagnostics(Vec<Diagnostic>);
+
+impl Diagnostics {
+    fn error<L: Display, M: Display>(&mut self, location: L, message: M) {
+        self.push(Severity::Error, location, message)
+    }
+
+    fn warning<L: Display, M: Display>(&mut self, location: L, message: M) {
+        self.push(Severity::Warning, location, message)
+    }
+
+    fn push<L: Display, M: Display>(&mut self, severity: Severity, location: L, message: M) {
+        self.0.push(Diagnostic {
+            severity,
+            location: location.to_string(),
+            message: message.to_string(),
+        })
+    }
+
+    fn has_errors(&self) -> bool {
+        self.0
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Error)
+    }
+}
+
+/// Validates the given config file, with the given override file and command-line overrides
+/// applied, and the chainspec in the same directory, along with the upgrade points installed
+/// alongside it.
+pub(crate) fn validate(
+    config_path: &Path,
+    config_override: Option<&Path>,
+    config_ext: Vec<ConfigExt>,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Diagnostics::default();
+    let root = config_path.parent().unwrap_or_else(|| Path::new("/"));
+
+    let maybe_config = match cli::load_config(config_path, config_override, config_ext) {
+        Ok(config) => Some(config),
+        Err(error) => {
+            diagnostics.error(config_path.display(), format!("{:#}", error));
+            None
+        }
+    };
+
+    let maybe_chainspec = match <(Chainspec, ChainspecRawBytes)>::from_path(root) {
+        Ok((chainspec, chainspec_raw_bytes)) => {
+            check_chainspec(&chainspec, &mut diagnostics);
+            check_cost_tables(&chainspec, &chainspec_raw_bytes, &mut diagnostics);
+            if let Some(config) = &maybe_config {
+                check_config(&chainspec, config, root, &mut diagnostics);
+            }
+            Some(chainspec)
+        }
+        Err(error) => {
+            diagnostics.error(root.join(CHAINSPEC_FILENAME).display(), error);
+            None
+        }
+    };
+
+    if let Some(chainspec) = &maybe_chainspec {
+        check_upgrade_points(chainspec, root, &mut diagnostics);
+        // Guard against any check the node runs on startup which isn't covered above.
+        if !diagnostics.has_errors() && !chainspec.is_valid() {
+            diagnostics.error(
+                root.join(CHAINSPEC_FILENAME).display(),
+                "chainspec is rejected by the node",
+            );
+        }
+    }
+
+    diagnostics.0
+}
+
+/// Checks the bounds of the chainspec's parameters, and the consistency of its timings.
+fn check_chainspec(chainspec: &Chainspec, diagnostics: &mut Diagnostics) {
+    let protocol = &chainspec.protocol_config;
+    if let Some(emergency_restart) = &protocol.emergency_restart {
+        if protocol.activation_point.era_id().is_genesis() {
+            diagnostics.error(
+                "protocol.activation_point",
+                "an emergency restart cannot be activated at genesis",
+            );
+        }
+        if !protocol.hard_reset {
+            diagnostics.error(
+                "protocol.hard_reset",
+                "an emergency restart requires hard_reset to be enabled",
+            );
+        }
+        let validators = &emergency_restart.validators;
+        if validators.is_empty() {
+            diagnostics.error(
+                "protocol.emergency_restart.validators",
+                "must contain at least one validator",
+            );
+        }
+        if validators.len() > chainspec.core_config.validator_slots as usize {
+            diagnostics.error(
+                "protocol.emergency_restart.validators",
+                format!(
+                    "contains {} validators, more than the {} validator slots",
+                    validators.len(),
+                    chainspec.core_config.validator_slots
+                ),
+            );
+        }
+        for (public_key, weight) in validators {
+            if *public_key == PublicKey::System || weight.is_zero() {
+                diagnostics.error(
+                    "protocol.emergency_restart.validators",
+                    format!(
+                        "validator {} must be a non-system key with non-zero weight",
+                        public_key
+                    ),
+                );
+            }
+        }
+    }
+
+    let network = &chainspec.network_config;
+    if network.name.is_empty() {
+        diagnostics.error("network.name", "must not be empty");
+    }
+    let min_message_size = ChunkWithProof::CHUNK_SIZE_BYTES * 3;
+    if (network.maximum_net_message_size as usize) < min_message_size {
+        diagnostics.warning(
+            "network.maximum_net_message_size",
+            format!(
+                "should be at least {} bytes to fit three global state chunks",
+                min_message_size
+            ),
+        );
+    }
+
+    let core = &chainspec.core_config;
+    if core.validator_slots == 0 {
+        diagnostics.error("core.validator_slots", "must be greater than zero");
+    }
+    if core.minimum_era_height == 0 {
+        diagnostics.error("core.minimum_era_height", "must be greater than zero");
+    }
+    if core.unbonding_delay <= core.auction_delay {
+        diagnostics.error(
+            "core.unbonding_delay",
+            format!(
+                "must be greater than core.auction_delay ({})",
+                core.auction_delay
+            ),
+        );
+    }
+    if core.max_associated_keys == 0 {
+        diagnostics.error("core.max_associated_keys", "must be greater than zero");
+    }
+    if core.max_runtime_call_stack_height == 0 {
+        diagnostics.error(
+            "core.max_runtime_call_stack_height",
+            "must be greater than zero",
+        );
+    }
+    if core.round_seigniorage_rate > Ratio::new(1, 1) {
+        diagnostics.warning(
+            "core.round_seigniorage_rate",
+            "is greater than 1, so the supply more than doubles every round",
+        );
+    }
+
+    let highway = &chainspec.highway_config;
+    let mut are_round_exponents_valid = true;
+    for (name, exponent) in [
+        ("minimum_round_exponent", highway.minimum_round_exponent),
+        ("maximum_round_exponent", highway.maximum_round_exponent),
+    ] {
+        if exponent >= 64 {
+            diagnostics.error(format!("highway.{}", name), "must be less than 64");
+            are_round_exponents_valid = false;
+        }
+    }
+    if highway.minimum_round_exponent > highway.maximum_round_exponent {
+        diagnostics.error(
+            "highway.minimum_round_exponent",
+            format!(
+                "must not be greater than highway.maximum_round_exponent ({})",
+                highway.maximum_round_exponent
+            ),
+        );
+        are_round_exponents_valid = false;
+    }
+    if highway.finality_threshold_fraction <= Ratio::new(0, 1)
+        || highway.finality_threshold_fraction >= Ratio::new(1, 1)
+    {
+        diagnostics.error("highway.finality_threshold_fraction", "must be in (0, 1)");
+    }
+    if highway.reduced_reward_multiplier > Ratio::new(1, 1) {
+        diagnostics.error("highway.reduced_reward_multiplier", "must be in [0, 1]");
+    }
+
+    let deploys = &chainspec.deploy_config;
+    if deploys.max_ttl.millis() == 0 {
+        diagnostics.error("deploys.max_ttl", "must be greater than zero");
+    }
+    if deploys.max_block_size != 0 {
+        if deploys.max_deploy_size > deploys.max_block_size {
+            diagnostics.error(
+                "deploys.max_deploy_size",
+                format!(
+                    "is greater than deploys.max_block_size ({}), so the largest deploys can't be \
+                    included in any block",
+                    deploys.max_block_size
+                ),
+            );
+        }
+        if deploys.max_block_size > network.maximum_net_message_size {
+            diagnostics.warning(
+                "deploys.max_block_size",
+                format!(
+                    "is greater than network.maximum_net_message_size ({}), so the largest \
+                    blocks can't be sent to peers",
+                    network.maximum_net_message_size
+                ),
+            );
+        }
+    }
+    let max_deploys_per_block = deploys
+        .block_max_deploy_count
+        .saturating_add(deploys.block_max_transfer_count);
+    if max_deploys_per_block == 0 {
+        diagnostics.warning(
+            "deploys.block_max_deploy_count",
+            "blocks can't contain any deploys or transfers",
+        );
+    }
+    if deploys.block_max_approval_count < max_deploys_per_block {
+        diagnostics.warning(
+            "deploys.block_max_approval_count",
+            format!(
+                "is less than the {} deploys and transfers a block may contain, each of which \
+                needs at least one approval",
+                max_deploys_per_block
+            ),
+        );
+    }
+
+    if are_round_exponents_valid {
+        let min_round_length = TimeDiff::from(1 << highway.minimum_round_exponent);
+        let max_round_length = TimeDiff::from(1 << highway.maximum_round_exponent);
+        if core.era_duration.millis() > 0 {
+            let min_era_length = min_round_length.saturating_mul(core.minimum_era_height);
+            if core.era_duration < min_era_length {
+                diagnostics.warning(
+                    "core.era_duration",
+                    format!(
+                        "is shorter than core.minimum_era_height rounds of the minimum length \
+                        ({}), so eras will last longer",
+                        min_era_length
+                    ),
+                );
+            }
+            if core.era_duration < max_round_length {
+                diagnostics.warning(
+                    "core.era_duration",
+                    format!(
+                        "is shorter than a single round of the maximum length ({})",
+                        max_round_length
+                    ),
+                );
+            }
+        }
+        if deploys.max_ttl < min_round_length {
+            diagnostics.warning(
+                "deploys.max_ttl",
+                format!(
+                    "is shorter than a round of the minimum length ({}), so deploys may expire \
+                    before they can be included in a block",
+                    min_round_length
+                ),
+            );
+        }
+    }
+
+    let wasm = &chainspec.wasm_config;
+    if wasm.max_memory == 0 || wasm.max_memory > MAX_WASM_MEMORY_PAGES {
+        diagnostics.error(
+            "wasm.max_memory",
+            format!("must be in the range [1, {}] pages", MAX_WASM_MEMORY_PAGES),
+        );
+    }
+    if wasm.max_stack_height == 0 {
+        diagnostics.error("wasm.max_stack_height", "must be greater than zero");
+    }
+
+    if chainspec
+        .commit_rules_config
+        .reserved_key_prefixes
+        .iter()
+        .any(String::is_empty)
+    {
+        diagnostics.error(
+            "commit_rules.reserved_key_prefixes",
+            "must not contain empty prefixes",
+        );
+    }
+
+    if chainspec.governance_config.enabled && chainspec.governance_config.contract_hash.is_none() {
+        diagnostics.error(
+            "governance.contract_hash",
+            "must be set when governance is enabled",
+        );
+    }
+
+    for (index, checkpoint) in chainspec.checkpoints.iter().enumerate() {
+        if chainspec.checkpoints[..index]
+            .iter()
+            .any(|earlier| earlier.height == checkpoint.height)
+        {
+            diagnostics.error(
+                format!("checkpoints[{}].height", index),
+                format!("another checkpoint is at height {}", checkpoint.height),
+            );
+        }
+    }
+}
+
+/// Checks that the cost tables contain no unrecognized entries, which would be silently ignored,
+/// and warns of costs which are zero.
+fn check_cost_tables(
+    chainspec: &Chainspec,
+    chainspec_raw_bytes: &ChainspecRawBytes,
+    diagnostics: &mut Diagnostics,
+) {
+    let raw_chainspec: Value = match toml::from_slice(chainspec_raw_bytes.chainspec_bytes()) {
+        Ok(raw_chainspec) => raw_chainspec,
+        Err(_) => return,
+    };
+    let parsed_tables = [
+        ("wasm", Value::try_from(chainspec.wasm_config)),
+        (
+            "system_costs",
+            Value::try_from(chainspec.system_costs_config),
+        ),
+    ];
+    for (name, maybe_parsed) in parsed_tables {
+        let parsed = match maybe_parsed {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                diagnostics.error(name, format!("failed to encode: {}", error));
+                continue;
+            }
+        };
+        if let Some(raw) = raw_chainspec.get(name) {
+            check_unrecognized_entries(name, raw, &parsed, diagnostics);
+        }
+        check_zero_costs(name, &parsed, diagnostics);
+    }
+}
+
+fn check_unrecognized_entries(
+    location: &str,
+    raw: &Value,
+    parsed: &Value,
+    diagnostics: &mut Diagnostics,
+) {
+    let (raw_table, parsed_table) = match (raw.as_table(), parsed.as_table()) {
+        (Some(raw_table), Some(parsed_table)) => (raw_table, parsed_table),
+        _ => return,
+    };
+    for (key, raw_value) in raw_table {
+        let current_key = COST_ALIASES
+            .iter()
+            .find(|(alias, _)| alias == key)
+            .map_or(key.as_str(), |(_, current_key)| current_key);
+        let entry_location = format!("{}.{}", location, key);
+        match parsed_table.get(current_key) {
+            Some(parsed_value) => {
+                check_unrecognized_entries(&entry_location, raw_value, parsed_value, diagnostics)
+            }
+            None => diagnostics.warning(entry_location, "unrecognized entry, which is ignored"),
+        }
+    }
+}
+
+fn check_zero_costs(location: &str, parsed: &Value, diagnostics: &mut Diagnostics) {
+    match parsed {
+        Value::Table(table) => {
+            for (key, value) in table {
+                // Host function argument weights are commonly zero.
+                if key == "arguments" {
+                    continue;
+                }
+                check_zero_costs(&format!("{}.{}", location, key), value, diagnostics);
+            }
+        }
+        Value::Integer(0) if !location.starts_with("wasm.max_") => {
+            diagnostics.warning(location, "cost is zero, so the operation is free")
+        }
+        _ => (),
+    }
+}
+
+/// Cross-checks the config against the chainspec.
+fn check_config(
+    chainspec: &Chainspec,
+    config: &participating::Config,
+    root: &Path,
+    diagnostics: &mut Diagnostics,
+) {
+    if let Err(error) = config.load_validator_keys(root) {
+        diagnostics.error("consensus.secret_key_path", error);
+    }
+    if config.node.node_mode == NodeMode::Observer {
+        for (option, sign_status_responses) in [
+            (
+                "rest_server.sign_status_responses",
+                config.rest_server.sign_status_responses,
+            ),
+            (
+                "rpc_server.sign_status_responses",
+                config.rpc_server.sign_status_responses,
+            ),
+        ] {
+            if sign_status_responses {
+                diagnostics.warning(
+                    option,
+                    "is set, but no validator key is loaded in observer mode, so status \
+                    responses are not signed",
+                );
+            }
+        }
+    }
+
+    if config.network.known_addresses.is_empty() {
+        diagnostics.warning(
+            "network.known_addresses",
+            "is empty, so the node can only join the network if peers connect to it",
+        );
+    }
+
+    let maybe_emergency_restart = chainspec.protocol_config.emergency_restart.as_ref();
+    match (
+        maybe_emergency_restart,
+        config.node.confirm_emergency_restart,
+    ) {
+        (Some(_), None) => diagnostics.warning(
+            "node.confirm_emergency_restart",
+            "is not set, so the node will refuse to apply the chainspec's emergency restart",
+        ),
+        (None, Some(_)) => diagnostics.warning(
+            "node.confirm_emergency_restart",
+            "is set, but the chainspec specifies no emergency restart",
+        ),
+        (Some(emergency_restart), Some(confirmation))
+            if emergency_restart.state_root_hash != confirmation =>
+        {
+            diagnostics.error(
+                "node.confirm_emergency_restart",
+                format!(
+                    "differs from the emergency restart's state root hash {}",
+                    emergency_restart.state_root_hash
+                ),
+            )
+        }
+        _ => (),
+    }
+
+    let maximum_round_exponent = chainspec.highway_config.maximum_round_exponent;
+    if maximum_round_exponent < 64 {
+        let max_round_length = TimeDiff::from(1 << maximum_round_exponent);
+        let highway = &config.consensus.highway;
+        if let Some(standstill_timeout) = highway.standstill_timeout {
+            if standstill_timeout <= max_round_length {
+                diagnostics.warning(
+                    "consensus.highway.standstill_timeout",
+                    format!(
+                        "is not longer than a round of the maximum length ({}), so the node may \
+                        restart during normal operation",
+                        max_round_length
+                    ),
+                );
+            }
+        }
+        if highway.pending_vertex_timeout < max_round_length {
+            diagnostics.warning(
+                "consensus.highway.pending_vertex_timeout",
+                format!(
+                    "is shorter than a round of the maximum length ({})",
+                    max_round_length
+                ),
+            );
+        }
+    }
+}
+
+/// Checks that the chainspec's directory is named after its protocol version, and that the
+/// upgrade points installed alongside it activate in the order of their protocol versions.
+fn check_upgrade_points(chainspec: &Chainspec, root: &Path, diagnostics: &mut Diagnostics) {
+    let current_version = chainspec.protocol_version();
+    let parse_dir_name = |dir: &Path| {
+        let dir_name = dir.file_name()?.to_string_lossy().replace('_', ".");
+        ProtocolVersion::from_str(&dir_name).ok()
+    };
+    if let Some(dir_version) = parse_dir_name(root) {
+        if dir_version != current_version {
+            diagnostics.error(
+                "protocol.version",
+                format!(
+                    "is {}, but the chainspec is installed in the directory for {}",
+                    current_version, dir_version
+                ),
+            );
+        }
+    }
+
+    let entries = match root.parent().map(fs::read_dir) {
+        Some(Ok(entries)) => entries,
+        _ => return,
+    };
+    let mut upgrade_points: Vec<(ProtocolVersion, ActivationPoint, PathBuf)> = vec![];
+    for path in entries.filter_map(|entry| Some(entry.ok()?.path())) {
+        let version = match parse_dir_name(&path) {
+            Some(version) if path.is_dir() => version,
+            _ => continue,
+        };
+        let chainspec_path = path.join(CHAINSPEC_FILENAME);
+        match chainspec_loader::read_protocol_config(&path) {
+            Ok(protocol_config) if protocol_config.version != version => diagnostics.error(
+                chainspec_path.display(),
+                format!(
+                    "protocol.version is {}, but the chainspec is installed in the directory for \
+                    {}",
+                    protocol_config.version, version
+                ),
+            ),
+            Ok(protocol_config) => {
+                upgrade_points.push((version, protocol_config.activation_point, chainspec_path))
+            }
+            Err(error) => diagnostics.error(chainspec_path.display(), error),
+        }
+    }
+    upgrade_points.sort_by_key(|(version, ..)| *version);
+
+    for pair in upgrade_points.windows(2) {
+        let (previous_version, previous_point, _) = &pair[0];
+        let (_, activation_point, chainspec_path) = &pair[1];
+        if activation_point.is_genesis() {
+            diagnostics.error(
+                chainspec_path.display(),
+                format!(
+                    "protocol.activation_point is genesis, but the earlier version {} is \
+                    installed",
+                    previous_version
+                ),
+            );
+        } else if activation_point.era_id() < previous_point.era_id() {
+            diagnostics.error(
+                chainspec_path.display(),
+                format!(
+                    "protocol.activation_point is era {}, earlier than that of version {} (era {})",
+                    activation_point.era_id(),
+                    previous_version,
+                    previous_point.era_id()
+                ),
+            );
+        } else if activation_point.era_id() == previous_point.era_id() {
+            diagnostics.warning(
+                chainspec_path.display(),
+                format!(
+                    "protocol.activation_point is era {}, the same as that of version {}, which \
+                    will therefore be skipped",
+                    activation_point.era_id(),
+                    previous_version
+                ),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::EraId;
+
+    use super::*;
+    use crate::utils::RESOURCES_PATH;
+
+    fn production_chainspec() -> (Chainspec, ChainspecRawBytes) {
+        <(Chainspec, ChainspecRawBytes)>::from_path(RESOURCES_PATH.join("production")).unwrap()
+    }
+
+    fn locations(diagnostics: &Diagnostics) -> Vec<&str> {
+        diagnostics
+            .0
+            .iter()
+            .map(|diagnostic| diagnostic.location.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn production_chainspec_should_be_valid() {
+        let (chainspec, chainspec_raw_bytes) = production_chainspec();
+        let mut diagnostics = Diagnostics::default();
+        check_chainspec(&chainspec, &mut diagnostics);
+        check_cost_tables(&chainspec, &chainspec_raw_bytes, &mut diagnostics);
+        assert!(!diagnostics.has_errors(), "{:?}", diagnostics.0);
+    }
+
+    #[test]
+    fn should_report_every_invalid_parameter() {
+        let (mut chainspec, _) = production_chainspec();
+        chainspec.core_config.unbonding_delay = chainspec.core_config.auction_delay;
+        chainspec.highway_config.minimum_round_exponent =
+            chainspec.highway_config.maximum_round_exponent + 1;
+        chainspec.deploy_config.max_deploy_size = chainspec.deploy_config.max_block_size + 1;
+        chainspec.wasm_config.max_memory = 0;
+
+        let mut diagnostics = Diagnostics::default();
+        check_chainspec(&chainspec, &mut diagnostics);
+        diagnostics
+            .0
+            .retain(|diagnostic| diagnostic.severity == Severity::Error);
+        assert_eq!(
+            locations(&diagnostics),
+            vec![
+                "core.unbonding_delay",
+                "highway.minimum_round_exponent",
+                "deploys.max_deploy_size",
+                "wasm.max_memory",
+            ]
+        );
+    }
+
+    #[test]
+    fn should_report_unrecognized_and_zero_costs() {
+        let (mut chainspec, chainspec_raw_bytes) = production_chainspec();
+        let raw_chainspec = String::from_utf8(chainspec_raw_bytes.chainspec_bytes().to_vec())
+            .unwrap()
+            .replace("[wasm.opcode_costs]\n", "[wasm.opcode_costs]\nmull = 240\n");
+        let chainspec_raw_bytes =
+            ChainspecRawBytes::new(raw_chainspec.into_bytes().into(), None, None);
+        chainspec.system_costs_config = toml::from_str(
+            &toml::to_string(&chainspec.system_costs_config)
+                .unwrap()
+                .replace("pay = 10000", "pay = 0"),
+        )
+        .unwrap();
+
+        let mut diagnostics = Diagnostics::default();
+        check_cost_tables(&chainspec, &chainspec_raw_bytes, &mut diagnostics);
+        assert_eq!(
+            locations(&diagnostics),
+            vec![
+                "wasm.opcode_costs.mull",
+                "system_costs.standard_payment_costs.pay"
+            ]
+        );
+        assert!(!diagnostics.has_errors());
+    }
+
+    #[test]
+    fn should_not_load_validator_key_in_observer_mode() {
+        let (chainspec, _) = production_chainspec();
+        let tempdir = tempfile::tempdir().unwrap();
+        let mut config = participating::Config::default();
+
+        let mut diagnostics = Diagnostics::default();
+        check_config(&chainspec, &config, tempdir.path(), &mut diagnostics);
+        assert!(locations(&diagnostics).contains(&"consensus.secret_key_path"));
+
+        config.node.node_mode = NodeMode::Observer;
+        config.rpc_server.sign_status_responses = true;
+        let mut diagnostics = Diagnostics::default();
+        check_config(&chainspec, &config, tempdir.path(), &mut diagnostics);
+        let locations = locations(&diagnostics);
+        assert!(!locations.contains(&"consensus.secret_key_path"));
+        assert!(locations.contains(&"rpc_server.sign_status_responses"));
+        assert!(!locations.contains(&"rest_server.sign_status_responses"));
+    }
+
+    #[test]
+    fn should_check_upgrade_point_order() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let install = |version: &str, activation_point: u64| {
+            let dir = tempdir.path().join(version);
+            fs::create_dir(&dir).unwrap();
+            fs::write(
+                dir.join(CHAINSPEC_FILENAME),
+                format!(
+                    "[protocol]\nversion = '{}'\nhard_reset = false\nactivation_point = {}\n",
+                    version.replace('_', "."),
+                    activation_point
+                ),
+            )
+            .unwrap();
+            dir
+        };
+        let (mut chainspec, _) = production_chainspec();
+        chainspec.protocol_config.version = ProtocolVersion::from_parts(1, 1, 0);
+        chainspec.protocol_config.activation_point = ActivationPoint::EraId(EraId::new(20));
+        let root = install("1_1_0", 20);
+        install("1_0_0", 10);
+
+        let mut diagnostics = Diagnostics::default();
+        check_upgrade_points(&chainspec, &root, &mut diagnostics);
+        assert!(diagnostics.0.is_empty(), "{:?}", diagnostics.0);
+
+        let chainspec_path = install("1_2_0", 15).join(CHAINSPEC_FILENAME);
+        check_upgrade_points(&chainspec, &root, &mut diagnostics);
+        assert_eq!(
+            locations(&diagnostics),
+            vec![chainspec_path.display().to_string()]
+        );
+        assert!(diagnostics.has_errors());
+    }
+}
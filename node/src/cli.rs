@@ -3,6 +3,7 @@
 //! Most configuration is done via config files (see [`config`](../config/index.html) for details).
 
 pub mod arglang;
+mod config_layers;
 
 use std::{
     alloc::System,
@@ -20,12 +21,17 @@ use toml::{value::Table, Value};
 use tracing::{error, info};
 
 use crate::{
-    logging,
+    alerts,
+    chainspec_validation::{self, Severity},
+    config_reload, contract_runtime, crash_dump, logging,
+    preflight::{self, CheckStatus},
+    profiling,
     reactor::{initializer, joiner, participating, ReactorExit, Runner},
     setup_signal_hooks,
-    types::ExitCode,
-    utils::WithDir,
+    types::{ExitCode, NodeFeatures},
+    utils::{key_file, sd_notify, WithDir},
 };
+use config_layers::LayeredConfig;
 
 // We override the standard allocator to gather metrics and tune the allocator via th MALLOC_CONF
 // env var.
@@ -45,6 +51,11 @@ pub enum Cli {
         /// Path to configuration file.
         config: PathBuf,
 
+        /// Path to a file overriding entries of the configuration file, whose sections are merged
+        /// into those of the configuration file.
+        #[structopt(long, env = "NODE_CONFIG_OVERRIDE")]
+        config_override: Option<PathBuf>,
+
         #[structopt(
             short = "C",
             long,
@@ -74,9 +85,80 @@ pub enum Cli {
         #[structopt(long)]
         new_config: PathBuf,
     },
+    /// Validate a chainspec and config pair without running the node.
+    ///
+    /// Parses the given configuration file and the chainspec in the same directory, cross-checks
+    /// them along with the upgrade points installed alongside, and prints every problem found.
+    ValidateChainspec {
+        /// Path to configuration file.
+        config: PathBuf,
+
+        /// Path to a file overriding entries of the configuration file, whose sections are merged
+        /// into those of the configuration file.
+        #[structopt(long, env = "NODE_CONFIG_OVERRIDE")]
+        config_override: Option<PathBuf>,
+
+        #[structopt(
+            short = "C",
+            long,
+            env = "NODE_CONFIG",
+            use_delimiter(true),
+            value_delimiter(";")
+        )]
+        /// Overrides and extensions for configuration file entries in the form
+        /// <SECTION>.<KEY>=<VALUE>.  For example, '-C=node.chainspec_config_path=chainspec.toml'
+        config_ext: Vec<ConfigExt>,
+
+        /// Print the problems found as JSON.
+        #[structopt(long)]
+        json: bool,
+    },
+    /// Build the genesis global state and print its state root hash without running the node.
+    ///
+    /// Builds the global state described by the chainspec and accounts in the given directory in a
+    /// temporary location, so that the participants of a new network can compare the resulting
+    /// hash ahead of the genesis timestamp.
+    GenesisStateRoot {
+        /// Path to the directory containing the genesis `chainspec.toml` and `accounts.toml`.
+        chainspec_dir: PathBuf,
+    },
+    /// Check the host environment before starting the node.
+    ///
+    /// Checks the free disk space and filesystem of the storage, the clock offset from an NTP
+    /// server, the open file limit, the availability of the ports to listen on and reachability
+    /// of the known addresses, the permissions of the secret key file and the consistency of the
+    /// chainspec and config, and prints the outcome of each check.
+    Preflight {
+        /// Path to configuration file.
+        config: PathBuf,
+
+        /// Path to a file overriding entries of the configuration file, whose sections are merged
+        /// into those of the configuration file.
+        #[structopt(long, env = "NODE_CONFIG_OVERRIDE")]
+        config_override: Option<PathBuf>,
+
+        #[structopt(
+            short = "C",
+            long,
+            env = "NODE_CONFIG",
+            use_delimiter(true),
+            value_delimiter(";")
+        )]
+        /// Overrides and extensions for configuration file entries in the form
+        /// <SECTION>.<KEY>=<VALUE>.  For example, '-C=node.chainspec_config_path=chainspec.toml'
+        config_ext: Vec<ConfigExt>,
+
+        /// NTP server to measure the clock offset against.
+        #[structopt(long, default_value = "pool.ntp.org:123")]
+        ntp_server: String,
+
+        /// Print the outcome of the checks as JSON.
+        #[structopt(long)]
+        json: bool,
+    },
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 /// Command line extension to be applied to TOML-based config file values.
 pub struct ConfigExt {
     section: String,
@@ -141,11 +223,20 @@ impl Cli {
     /// Executes selected CLI command.
     pub async fn run(self) -> anyhow::Result<i32> {
         match self {
-            Cli::Validator { config, config_ext } => {
+            Cli::Validator {
+                config,
+                config_override,
+                config_ext,
+            } => {
                 // Setup UNIX signal hooks.
                 setup_signal_hooks();
 
-                let validator_config = Self::init(&config, config_ext)?;
+                config_reload::set_config_source(
+                    config.clone(),
+                    config_override.clone(),
+                    config_ext.clone(),
+                );
+                let validator_config = Self::init(&config, config_override.as_deref(), config_ext)?;
                 info!(version = %crate::VERSION_STRING.as_str(), "node starting up");
 
                 // We use a `ChaCha20Rng` for the production node. For one, we want to completely
@@ -184,6 +275,7 @@ impl Cli {
                     ReactorExit::ProcessShouldExit(exit_code) => return Ok(exit_code as i32),
                     ReactorExit::ProcessShouldContinue => info!("finished joining"),
                 }
+                sd_notify::ready();
 
                 let joiner_reactor = joiner_runner.drain_into_inner().await;
                 let config = joiner_reactor.into_participating_config().await?;
@@ -191,8 +283,12 @@ impl Cli {
                 let mut participating_runner =
                     Runner::<participating::Reactor>::with_metrics(config, &mut rng, &registry)
                         .await?;
+                // All components have loaded their secret keys by now.
+                key_file::forget_unlocked_keys();
 
-                match participating_runner.run(&mut rng).await {
+                let reactor_exit = participating_runner.run(&mut rng).await;
+                sd_notify::stopping();
+                match reactor_exit {
                     ReactorExit::ProcessShouldExit(exit_code) => Ok(exit_code as i32),
                     reactor_exit => {
                         error!("validator should not exit with {:?}", reactor_exit);
@@ -204,7 +300,7 @@ impl Cli {
                 old_config,
                 new_config,
             } => {
-                let new_config = Self::init(&new_config, vec![])?;
+                let new_config = Self::init(&new_config, None, vec![])?;
 
                 let old_root = old_config
                     .parent()
@@ -226,7 +322,7 @@ impl Cli {
                 old_config,
                 new_config,
             } => {
-                let new_config = Self::init(&new_config, vec![])?;
+                let new_config = Self::init(&new_config, None, vec![])?;
 
                 let old_root = old_config
                     .parent()
@@ -244,12 +340,88 @@ impl Cli {
                 )?;
                 Ok(ExitCode::Success as i32)
             }
+            Cli::ValidateChainspec {
+                config,
+                config_override,
+                config_ext,
+                json,
+            } => {
+                let diagnostics =
+                    chainspec_validation::validate(&config, config_override.as_deref(), config_ext);
+                let error_count = diagnostics
+                    .iter()
+                    .filter(|diagnostic| diagnostic.severity == Severity::Error)
+                    .count();
+
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&diagnostics)?);
+                } else {
+                    for diagnostic in &diagnostics {
+                        println!("{}", diagnostic);
+                    }
+                    println!(
+                        "{} error(s), {} warning(s)",
+                        error_count,
+                        diagnostics.len() - error_count
+                    );
+                }
+
+                if error_count == 0 {
+                    Ok(ExitCode::Success as i32)
+                } else {
+                    Ok(ExitCode::Abort as i32)
+                }
+            }
+            Cli::GenesisStateRoot { chainspec_dir } => {
+                let state_root_hash = contract_runtime::build_genesis_state(&chainspec_dir)
+                    .with_context(|| chainspec_dir.display().to_string())?;
+                println!("{}", state_root_hash);
+                Ok(ExitCode::Success as i32)
+            }
+            Cli::Preflight {
+                config,
+                config_override,
+                config_ext,
+                ntp_server,
+                json,
+            } => {
+                let results =
+                    preflight::run(&config, config_override.as_deref(), config_ext, &ntp_server);
+                let count = |status: CheckStatus| {
+                    results
+                        .iter()
+                        .filter(|result| result.status == status)
+                        .count()
+                };
+                let failure_count = count(CheckStatus::Fail);
+
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&results)?);
+                } else {
+                    for result in &results {
+                        println!("{}", result);
+                    }
+                    println!(
+                        "{} failure(s), {} warning(s)",
+                        failure_count,
+                        count(CheckStatus::Warning)
+                    );
+                }
+
+                if failure_count == 0 {
+                    Ok(ExitCode::Success as i32)
+                } else {
+                    Ok(ExitCode::Abort as i32)
+                }
+            }
         }
     }
 
-    /// Parses the config file for the current version of casper-node, and initializes logging.
+    /// Parses the config file for the current version of casper-node along with its overrides, and
+    /// initializes logging.
     fn init(
         config: &Path,
+        config_override: Option<&Path>,
         config_ext: Vec<ConfigExt>,
     ) -> anyhow::Result<WithDir<participating::Config>> {
         // Determine the parent directory of the configuration file, if any.
@@ -259,24 +431,59 @@ impl Cli {
             .map(|path| path.to_owned())
             .unwrap_or_else(|| "/".into());
 
-        // The app supports running without a config file, using default values.
-        let encoded_config = fs::read_to_string(&config)
-            .context("could not read configuration file")
-            .with_context(|| config.display().to_string())?;
-
-        // Get the TOML table version of the config indicated from CLI args, or from a new
-        // defaulted config instance if one is not provided.
-        let mut config_table: Value = toml::from_str(&encoded_config)?;
-
-        // If any command line overrides to the config values are passed, apply them.
-        for item in config_ext {
-            item.update_toml_table(&mut config_table)?;
-        }
-
-        // Create participating config, including any overridden values.
-        let participating_config: participating::Config = config_table.try_into()?;
+        let layered_config = load_layered_config(config, config_override, config_ext)?;
+        let participating_config: participating::Config =
+            layered_config.value().clone().try_into()?;
         logging::init_with_config(&participating_config.logging)?;
+        layered_config.log_provenance();
+
+        let config = WithDir::new(root, participating_config);
+        profiling::init(&config.map_ref(|config| config.profiling.clone()));
+        crash_dump::init(
+            &config.map_ref(|config| config.crash_dump.clone()),
+            layered_config.value(),
+        );
+        let features = NodeFeatures::from_config(config.value());
+        info!(%features, "enabled node features");
+        features.register();
+        alerts::init(
+            &config.value().alerts,
+            config.value().network.public_address.clone(),
+        )?;
+        Ok(config)
+    }
+}
+
+/// Parses the config file for the current version of casper-node, applying the given override
+/// file, the `CASPER_NODE__<SECTION>__<KEY>` environment variables and the given command line
+/// overrides, in that order.
+pub(crate) fn load_config(
+    config: &Path,
+    config_override: Option<&Path>,
+    config_ext: Vec<ConfigExt>,
+) -> anyhow::Result<participating::Config> {
+    let layered_config = load_layered_config(config, config_override, config_ext)?;
+    Ok(layered_config.value().clone().try_into()?)
+}
 
-        Ok(WithDir::new(root, participating_config))
+/// Resolves the config table from the config file and each of its override layers.
+fn load_layered_config(
+    config: &Path,
+    config_override: Option<&Path>,
+    config_ext: Vec<ConfigExt>,
+) -> anyhow::Result<LayeredConfig> {
+    let mut layered_config = LayeredConfig::from_file(config)?;
+    if let Some(config_override) = config_override {
+        layered_config.apply_override_file(config_override)?;
+    }
+    // Environment variables whose name or value isn't valid unicode can't be overrides.
+    layered_config.apply_env_vars(
+        env::vars_os().filter_map(|(name, value)| {
+            Some((name.into_string().ok()?, value.into_string().ok()?))
+        }),
+    )?;
+    for item in &config_ext {
+        layered_config.apply_config_ext(item)?;
     }
+    Ok(layered_config)
 }
@@ -0,0 +1,338 @@
+//! Layered resolution of the node's configuration.
+//!
+//! The configuration is resolved from the following layers, each overriding the values set by
+//! the previous ones:
+//!
+//! 1. the config file,
+//! 2. an optional override file, whose tables are merged into those of the config file,
+//! 3. environment variables named `CASPER_NODE__<SECTION>__<KEY>`, e.g.
+//!    `CASPER_NODE__NETWORK__BIND_ADDRESS`, whose values use the same syntax as those given via
+//!    `-C`,
+//! 4. the command-line overrides given via `-C`.
+//!
+//! The layer which set each value is recorded, and logged once logging is initialized.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context};
+use toml::{value::Table, Value};
+use tracing::{debug, info};
+
+use super::{arglang, ConfigExt};
+
+/// The prefix of environment variables overriding config values.
+const ENV_VAR_PREFIX: &str = "CASPER_NODE__";
+
+/// The separator between the sections and the key in the name of an environment variable.
+const ENV_VAR_SEPARATOR: &str = "__";
+
+/// The layer from which a config value was taken.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum ConfigSource {
+    /// The config file at the given path.
+    File(PathBuf),
+    /// The override file at the given path.
+    OverrideFile(PathBuf),
+    /// The environment variable with the given name.
+    EnvVar(String),
+    /// A `-C` command-line override.
+    CommandLine,
+}
+
+impl Display for ConfigSource {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigSource::File(path) => write!(formatter, "config file {}", path.display()),
+            ConfigSource::OverrideFile(path) => {
+                write!(formatter, "override file {}", path.display())
+            }
+            ConfigSource::EnvVar(name) => write!(formatter, "environment variable {}", name),
+            ConfigSource::CommandLine => write!(formatter, "command line"),
+        }
+    }
+}
+
+/// A TOML config table resolved from layered sources, along with the source of each value.
+#[derive(Debug)]
+pub(crate) struct LayeredConfig {
+    /// The resolved config table.
+    value: Value,
+    /// The source of each value, keyed by its dotted path.
+    provenance: BTreeMap<String, ConfigSource>,
+}
+
+impl LayeredConfig {
+    /// Reads the config file at `path` as the base layer.
+    pub(super) fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let value = read_toml(path)?;
+        let mut layered_config = LayeredConfig {
+            value: Value::Table(Table::new()),
+            provenance: BTreeMap::new(),
+        };
+        layered_config.merge(value, &ConfigSource::File(path.to_path_buf()));
+        Ok(layered_config)
+    }
+
+    /// Merges the tables of the override file at `path` into the config.
+    pub(super) fn apply_override_file(&mut self, path: &Path) -> anyhow::Result<()> {
+        let value = read_toml(path)?;
+        self.merge(value, &ConfigSource::OverrideFile(path.to_path_buf()));
+        Ok(())
+    }
+
+    /// Applies the values of those of the given environment variables which are named
+    /// `CASPER_NODE__<SECTION>__<KEY>`.
+    pub(super) fn apply_env_vars<I>(&mut self, env_vars: I) -> anyhow::Result<()>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        for (name, value) in env_vars {
+            let path = match name.strip_prefix(ENV_VAR_PREFIX) {
+                Some(path) => path,
+                None => continue,
+            };
+            let segments: Vec<String> = path
+                .split(ENV_VAR_SEPARATOR)
+                .map(str::to_lowercase)
+                .collect();
+            if segments.len() < 2 || segments.iter().any(String::is_empty) {
+                bail!(
+                    "environment variable {} must be named {}<SECTION>{}<KEY>",
+                    name,
+                    ENV_VAR_PREFIX,
+                    ENV_VAR_SEPARATOR
+                );
+            }
+            let value = arglang::parse(&value)
+                .with_context(|| format!("could not parse environment variable {}", name))?;
+            self.insert(&segments, value, ConfigSource::EnvVar(name.clone()))
+                .with_context(|| format!("could not apply environment variable {}", name))?;
+        }
+        Ok(())
+    }
+
+    /// Applies a `-C` command-line override.
+    pub(super) fn apply_config_ext(&mut self, config_ext: &ConfigExt) -> anyhow::Result<()> {
+        config_ext.update_toml_table(&mut self.value)?;
+        let path = format!("{}.{}", config_ext.section, config_ext.key);
+        self.record(path, ConfigSource::CommandLine);
+        Ok(())
+    }
+
+    /// Returns the resolved config table.
+    pub(super) fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// Logs the source of each config value: at info level if it was overridden, and at debug
+    /// level if it was taken from the config file.
+    pub(super) fn log_provenance(&self) {
+        for (path, source) in &self.provenance {
+            match source {
+                ConfigSource::File(_) => debug!(%path, %source, "config value"),
+                _ => info!(%path, %source, "config value overridden"),
+            }
+        }
+    }
+
+    /// Merges `value` into the config, recursing into tables present in both.
+    fn merge(&mut self, value: Value, source: &ConfigSource) {
+        let mut leaves = Vec::new();
+        if let (Value::Table(table), Value::Table(overrides)) = (&mut self.value, value) {
+            merge_tables(table, overrides, "", &mut leaves);
+        }
+        for path in leaves {
+            self.record(path, source.clone());
+        }
+    }
+
+    /// Inserts `value` at the given path of nested tables, creating any missing tables.
+    fn insert(
+        &mut self,
+        segments: &[String],
+        value: Value,
+        source: ConfigSource,
+    ) -> anyhow::Result<()> {
+        let (key, sections) = segments.split_last().context("empty config path")?;
+        let mut table = self
+            .value
+            .as_table_mut()
+            .context("configuration table is not a table")?;
+        for section in sections {
+            table = table
+                .entry(section.clone())
+                .or_insert(Value::Table(Table::new()))
+                .as_table_mut()
+                .with_context(|| format!("configuration section {} is not a table", section))?;
+        }
+        table.insert(key.clone(), value);
+        self.record(segments.join("."), source);
+        Ok(())
+    }
+
+    /// Records `source` as that of the value at `path`, replacing the sources of any values
+    /// nested below it.
+    fn record(&mut self, path: String, source: ConfigSource) {
+        let nested_prefix = format!("{}.", path);
+        self.provenance
+            .retain(|existing, _| !existing.starts_with(&nested_prefix));
+        self.provenance.insert(path, source);
+    }
+}
+
+/// Reads and parses the TOML file at `path`.
+fn read_toml(path: &Path) -> anyhow::Result<Value> {
+    let encoded = fs::read_to_string(path)
+        .context("could not read configuration file")
+        .with_context(|| path.display().to_string())?;
+    toml::from_str(&encoded)
+        .context("could not parse configuration file")
+        .with_context(|| path.display().to_string())
+}
+
+/// Merges `overrides` into `table`, recursing into tables present in both, and collects the
+/// dotted paths of the values set.
+fn merge_tables(table: &mut Table, overrides: Table, prefix: &str, leaves: &mut Vec<String>) {
+    for (key, value) in overrides {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match (table.get_mut(&key), value) {
+            (Some(Value::Table(existing)), Value::Table(nested)) => {
+                merge_tables(existing, nested, &path, leaves)
+            }
+            (_, Value::Table(nested)) => {
+                let mut new_table = Table::new();
+                merge_tables(&mut new_table, nested, &path, leaves);
+                table.insert(key, Value::Table(new_table));
+            }
+            (_, value) => {
+                table.insert(key, value);
+                leaves.push(path);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn source_of<'a>(layered_config: &'a LayeredConfig, path: &str) -> Option<&'a ConfigSource> {
+        layered_config.provenance.get(path)
+    }
+
+    #[test]
+    fn should_resolve_layers_in_order() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let config_path = tempdir.path().join("config.toml");
+        let override_path = tempdir.path().join("override.toml");
+        fs::write(
+            &config_path,
+            "[network]\nbind_address = '0.0.0.0:34553'\nknown_addresses = []\n\n\
+             [consensus]\nsecret_key_path = 'secret_key.pem'\n\n\
+             [consensus.highway]\npending_vertex_timeout = '10min'\n",
+        )
+        .unwrap();
+        fs::write(
+            &override_path,
+            "[network]\nknown_addresses = ['1.2.3.4:34553']\n\n\
+             [consensus.highway]\npending_vertex_timeout = '5min'\n",
+        )
+        .unwrap();
+
+        let mut layered_config = LayeredConfig::from_file(&config_path).unwrap();
+        layered_config.apply_override_file(&override_path).unwrap();
+        layered_config
+            .apply_env_vars(vec![
+                (
+                    "CASPER_NODE__NETWORK__BIND_ADDRESS".to_string(),
+                    "0.0.0.0:1234".to_string(),
+                ),
+                ("UNRELATED".to_string(), "value".to_string()),
+            ])
+            .unwrap();
+        layered_config
+            .apply_config_ext(&ConfigExt::from_str("network.bind_address=0.0.0.0:5678").unwrap())
+            .unwrap();
+
+        let value = layered_config.value();
+        assert_eq!(
+            value["network"]["bind_address"].as_str(),
+            Some("0.0.0.0:5678")
+        );
+        assert_eq!(
+            value["network"]["known_addresses"][0].as_str(),
+            Some("1.2.3.4:34553")
+        );
+        assert_eq!(
+            value["consensus"]["secret_key_path"].as_str(),
+            Some("secret_key.pem")
+        );
+        assert_eq!(
+            value["consensus"]["highway"]["pending_vertex_timeout"].as_str(),
+            Some("5min")
+        );
+
+        assert_eq!(
+            source_of(&layered_config, "consensus.secret_key_path"),
+            Some(&ConfigSource::File(config_path))
+        );
+        assert_eq!(
+            source_of(&layered_config, "consensus.highway.pending_vertex_timeout"),
+            Some(&ConfigSource::OverrideFile(override_path.clone()))
+        );
+        assert_eq!(
+            source_of(&layered_config, "network.known_addresses"),
+            Some(&ConfigSource::OverrideFile(override_path))
+        );
+        assert_eq!(
+            source_of(&layered_config, "network.bind_address"),
+            Some(&ConfigSource::CommandLine)
+        );
+    }
+
+    #[test]
+    fn should_apply_nested_env_vars() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let config_path = tempdir.path().join("config.toml");
+        fs::write(&config_path, "[consensus]\nsecret_key_path = 'key.pem'\n").unwrap();
+
+        let mut layered_config = LayeredConfig::from_file(&config_path).unwrap();
+        let name = "CASPER_NODE__CONSENSUS__HIGHWAY__MAX_ROUND_EXPONENT".to_string();
+        layered_config
+            .apply_env_vars(vec![(name.clone(), "20".to_string())])
+            .unwrap();
+        assert_eq!(
+            layered_config.value()["consensus"]["highway"]["max_round_exponent"].as_integer(),
+            Some(20)
+        );
+        assert_eq!(
+            source_of(&layered_config, "consensus.highway.max_round_exponent"),
+            Some(&ConfigSource::EnvVar(name))
+        );
+
+        // A variable without a key, or whose section isn't a table, is rejected.
+        assert!(layered_config
+            .apply_env_vars(vec![(
+                "CASPER_NODE__CONSENSUS".to_string(),
+                "1".to_string()
+            )])
+            .is_err());
+        assert!(layered_config
+            .apply_env_vars(vec![(
+                "CASPER_NODE__CONSENSUS__SECRET_KEY_PATH__NESTED".to_string(),
+                "1".to_string()
+            )])
+            .is_err());
+    }
+}
@@ -27,7 +27,7 @@ use casper_types::{PublicKey, Timestamp};
 
 use crate::{
     components::{
-        consensus::{BlockContext, ClContext},
+        consensus::{BlockContext, ClContext, ProposedBlock},
         Component,
     },
     effect::{
@@ -136,6 +136,17 @@ impl BlockProposer {
 
         Ok((block_proposer, effects))
     }
+
+    /// Sets the deploy config for proposing blocks, e.g. after it was updated via governance.
+    pub(crate) fn set_deploy_config(&mut self, deploy_config: DeployConfig) {
+        match &mut self.state {
+            BlockProposerState::Initializing {
+                deploy_config: current_deploy_config,
+                ..
+            } => *current_deploy_config = deploy_config,
+            BlockProposerState::Ready(ready_state) => ready_state.deploy_config = deploy_config,
+        }
+    }
 }
 
 impl<REv> Component<REv> for BlockProposer
@@ -186,6 +197,8 @@ where
                     deploy_config: *deploy_config,
                     request_queue: Default::default(),
                     local_config: local_config.clone(),
+                    dependency_deferred_count: 0,
+                    dependency_cycle_count: 0,
                 };
 
                 // Announce pruned hashes.
@@ -236,6 +249,12 @@ where
                     ready_state.sets.pending_deploys.len() as i64
                         + ready_state.sets.pending_transfers.len() as i64,
                 );
+                self.metrics
+                    .dependency_deferred_deploys
+                    .set(ready_state.dependency_deferred_count as i64);
+                self.metrics
+                    .dependency_cycle_deploys
+                    .set(ready_state.dependency_cycle_count as i64);
             }
         };
 
@@ -255,6 +274,12 @@ struct BlockProposerReady {
     request_queue: RequestQueue,
     /// The block proposer configuration, containing local settings for selecting deploys.
     local_config: Config,
+    /// The number of deploys and transfers left out of the last proposed block because their
+    /// dependencies were not resolved.
+    dependency_deferred_count: usize,
+    /// The number of deploys and transfers which were part of, or depended on, a dependency cycle
+    /// when the last block was proposed.
+    dependency_cycle_count: usize,
 }
 
 impl BlockProposerReady {
@@ -351,6 +376,10 @@ impl BlockProposerReady {
                     effects
                 }
             }
+            Event::ProposedBlock(proposed_block) => {
+                self.handle_proposed_block(&proposed_block);
+                Effects::new()
+            }
         }
     }
 
@@ -403,6 +432,15 @@ impl BlockProposerReady {
         }
     }
 
+    /// Records the deploys and transfers of a block proposed by another validator, so they are
+    /// not proposed again while that block can still be finalized.
+    fn handle_proposed_block(&mut self, proposed_block: &ProposedBlock<ClContext>) {
+        let proposed_at = proposed_block.context().timestamp();
+        for hash in proposed_block.value().deploys_and_transfers_iter() {
+            self.sets.add_proposed_deploy(hash.into(), proposed_at);
+        }
+    }
+
     /// Handles finalization of a block.
     fn handle_finalized_block(&mut self, block: &FinalizedBlock) -> Effects<Event> {
         for deploy_hash in block.deploy_hashes() {
@@ -419,6 +457,7 @@ impl BlockProposerReady {
             };
             self.sets.add_finalized_transfer(*transfer_hash, expiry);
         }
+        self.sets.release_proposed_deploys(block.timestamp());
 
         self.sets.next_finalized = self.sets.next_finalized.max(block.height() + 1);
         if let Some(requests) = self.request_queue.remove(&self.sets.next_finalized) {
@@ -443,11 +482,85 @@ impl BlockProposerReady {
     }
 
     /// Checks if a deploy's dependencies are satisfied, so the deploy is eligible for inclusion.
-    fn deps_resolved(&self, header: &DeployHeader, past_deploys: &HashSet<DeployHash>) -> bool {
-        header
-            .dependencies()
+    ///
+    /// A dependency is satisfied if it is finalized, in an ancestor block, or already included
+    /// earlier in the block being proposed.
+    fn deps_resolved(
+        &self,
+        header: &DeployHeader,
+        past_deploys: &HashSet<DeployHash>,
+        included: &HashSet<DeployHash>,
+    ) -> bool {
+        header.dependencies().iter().all(|dep| {
+            past_deploys.contains(dep) || included.contains(dep) || self.contains_finalized(dep)
+        })
+    }
+
+    /// Returns the hashes of the pending deploys or transfers in `pending` which are eligible for
+    /// the block being proposed, ordered so that each one comes after those of its dependencies
+    /// which are eligible as well.
+    ///
+    /// Deploys which are part of, or depend on, a dependency cycle can never be proposed; they are
+    /// left out and their number is returned as the second element.
+    fn dependency_order(
+        &self,
+        pending: &HashMap<DeployHash, PendingDeployInfo>,
+        past_deploys: &HashSet<DeployHash>,
+        block_timestamp: Timestamp,
+    ) -> (Vec<DeployHash>, usize) {
+        let eligible: HashSet<DeployHash> = pending
             .iter()
-            .all(|dep| past_deploys.contains(dep) || self.contains_finalized(dep))
+            .filter(|(hash, pending_deploy_info)| {
+                !past_deploys.contains(*hash)
+                    && !self.contains_finalized(hash)
+                    && !self.sets.proposed_deploys.contains_key(hash)
+                    && block_timestamp.saturating_diff(pending_deploy_info.timestamp)
+                        >= self.local_config.deploy_delay
+            })
+            .map(|(hash, _)| *hash)
+            .collect();
+
+        // For each eligible deploy, count its eligible dependencies and record it as a dependent
+        // of each of them.
+        let mut unordered_deps: HashMap<DeployHash, usize> = HashMap::new();
+        let mut dependents: HashMap<DeployHash, Vec<DeployHash>> = HashMap::new();
+        for hash in &eligible {
+            let deps: HashSet<&DeployHash> = pending[hash]
+                .info
+                .header
+                .dependencies()
+                .iter()
+                .filter(|dep| eligible.contains(*dep))
+                .collect();
+            unordered_deps.insert(*hash, deps.len());
+            for dep in deps {
+                dependents.entry(*dep).or_default().push(*hash);
+            }
+        }
+
+        let mut ready: Vec<DeployHash> = unordered_deps
+            .iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(hash, _)| *hash)
+            .collect();
+        let mut ordered = Vec::with_capacity(eligible.len());
+        while let Some(hash) = ready.pop() {
+            ordered.push(hash);
+            for dependent in dependents.remove(&hash).unwrap_or_default() {
+                if let Some(count) = unordered_deps.get_mut(&dependent) {
+                    *count -= 1;
+                    if *count == 0 {
+                        ready.push(dependent);
+                    }
+                }
+            }
+        }
+
+        let cycle_count = eligible.len() - ordered.len();
+        if cycle_count > 0 {
+            debug!(%cycle_count, "skipping deploys with cyclic dependencies");
+        }
+        (ordered, cycle_count)
     }
 
     /// Returns a list of candidates for inclusion into a block.
@@ -467,15 +580,21 @@ impl BlockProposerReady {
             .collect();
         let block_timestamp = context.timestamp();
         let mut appendable_block = AppendableBlock::new(deploy_config, block_timestamp);
+        let mut deferred_count = 0;
+
+        // All deploys in a block are executed before its transfers, so a deploy or transfer can
+        // only depend on one of the same kind included earlier in this block.
+        let (transfers, transfer_cycle_count) =
+            self.dependency_order(&self.sets.pending_transfers, &past_deploys, block_timestamp);
+        let (deploys, deploy_cycle_count) =
+            self.dependency_order(&self.sets.pending_deploys, &past_deploys, block_timestamp);
 
         // We prioritize transfers over deploys, so we try to include them first.
-        for (hash, pending_deploy_info) in &self.sets.pending_transfers {
-            if !self.deps_resolved(&pending_deploy_info.info.header, &past_deploys)
-                || past_deploys.contains(hash)
-                || self.contains_finalized(hash)
-                || block_timestamp.saturating_diff(pending_deploy_info.timestamp)
-                    < self.local_config.deploy_delay
-            {
+        let mut included = HashSet::new();
+        for hash in &transfers {
+            let pending_deploy_info = &self.sets.pending_transfers[hash];
+            if !self.deps_resolved(&pending_deploy_info.info.header, &past_deploys, &included) {
+                deferred_count += 1;
                 continue;
             }
 
@@ -497,17 +616,17 @@ impl BlockProposerReady {
                         error!(?err, "unexpected error when adding transfer")
                     }
                 }
+            } else {
+                included.insert(*hash);
             }
         }
 
         // Now we try to add other deploys to the block.
-        for (hash, pending_deploy_info) in &self.sets.pending_deploys {
-            if !self.deps_resolved(&pending_deploy_info.info.header, &past_deploys)
-                || past_deploys.contains(hash)
-                || self.contains_finalized(hash)
-                || block_timestamp.saturating_diff(pending_deploy_info.timestamp)
-                    < self.local_config.deploy_delay
-            {
+        let mut included = HashSet::new();
+        for hash in &deploys {
+            let pending_deploy_info = &self.sets.pending_deploys[hash];
+            if !self.deps_resolved(&pending_deploy_info.info.header, &past_deploys, &included) {
+                deferred_count += 1;
                 continue;
             }
 
@@ -540,16 +659,28 @@ impl BlockProposerReady {
                         error!("payment_amount couldn't be converted from motes to gas")
                     }
                 }
+            } else {
+                included.insert(*hash);
             }
         }
 
-        Arc::new(appendable_block.into_block_payload(accusations, random_bit))
+        if deferred_count > 0 {
+            debug!(%deferred_count, "deferred deploys with unresolved dependencies");
+        }
+        self.dependency_deferred_count = deferred_count;
+        self.dependency_cycle_count = transfer_cycle_count + deploy_cycle_count;
+
+        let block_payload = appendable_block.into_block_payload(accusations, random_bit);
+        for hash in block_payload.deploys_and_transfers_iter() {
+            self.sets.add_proposed_deploy(hash.into(), block_timestamp);
+        }
+        Arc::new(block_payload)
     }
 
     /// Prunes expired deploy information from the BlockProposer, returns the hashes of deploys
     /// pruned.
     fn prune(&mut self, current_instant: Timestamp) -> PruneResult {
-        self.sets.prune(current_instant)
+        self.sets.prune(current_instant, self.deploy_config.max_ttl)
     }
 
     fn contains_finalized(&self, hash: &DeployHash) -> bool {
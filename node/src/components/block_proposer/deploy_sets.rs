@@ -49,6 +49,9 @@ pub(super) struct BlockProposerDeploySets {
     /// The transfers that have already been included in a finalized block, and their earliest
     /// known expiry date.
     pub(super) finalized_transfers: HashMap<DeployHash, Timestamp>,
+    /// The deploys and transfers included in proposed blocks which have not been finalized yet,
+    /// and the latest timestamp of such a block.
+    pub(super) proposed_deploys: HashMap<DeployHash, Timestamp>,
     /// The next block height we expect to be finalized.
     /// If we receive a notification of finalization of a later block, we will store it in
     /// finalization_queue.
@@ -96,13 +99,13 @@ impl BlockProposerDeploySets {
             next_finalized: next_finalized_height,
             ..Default::default()
         };
-        let prune_result = sets.prune(Timestamp::now());
+        let prune_result = sets.prune(Timestamp::now(), max_ttl);
         (sets, prune_result)
     }
 
     /// Prunes expired deploy information from the BlockProposerState, returns the
     /// hashes of deploys pruned.
-    pub(super) fn prune(&mut self, current_instant: Timestamp) -> PruneResult {
+    pub(super) fn prune(&mut self, current_instant: Timestamp, max_ttl: TimeDiff) -> PruneResult {
         let pending_deploys = prune_pending_deploys(&mut self.pending_deploys, current_instant);
         let pending_transfers = prune_pending_deploys(&mut self.pending_transfers, current_instant);
 
@@ -116,6 +119,12 @@ impl BlockProposerDeploySets {
             hashmap_drain_filter_in_place(&mut self.finalized_transfers, |expiry| {
                 *expiry < current_instant
             });
+        // Deploys included in a block expire at most `max_ttl` after the block's timestamp, so
+        // they can't be proposed again after that either.
+        let proposed_deploys =
+            hashmap_drain_filter_in_place(&mut self.proposed_deploys, |proposed_at| {
+                proposed_at.saturating_add(max_ttl) < current_instant
+            });
 
         // We return a total of pruned deploys, but for the deploys pruned
         // from the `finalized` collection we don't want to send
@@ -124,7 +133,8 @@ impl BlockProposerDeploySets {
             pending_deploys.len()
                 + pending_transfers.len()
                 + finalized_deploys.len()
-                + finalized_transfers.len(),
+                + finalized_transfers.len()
+                + proposed_deploys.len(),
             [pending_deploys, pending_transfers].concat(),
         )
     }
@@ -144,14 +154,33 @@ impl BlockProposerDeploySets {
             .and_modify(|expiry| *expiry = new_expiry.min(*expiry))
             .or_insert(new_expiry);
     }
+
+    /// Adds the hash of a deploy or transfer included in a proposed block with the given
+    /// timestamp.
+    pub(super) fn add_proposed_deploy(&mut self, hash: DeployHash, proposed_at: Timestamp) {
+        self.proposed_deploys
+            .entry(hash)
+            .and_modify(|timestamp| *timestamp = proposed_at.max(*timestamp))
+            .or_insert(proposed_at);
+    }
+
+    /// Forgets the deploys and transfers of proposed blocks which can't be finalized anymore.
+    ///
+    /// Blocks are finalized in the order of their timestamps, so a proposed block that isn't
+    /// finalized by the time a block with the same or a later timestamp is, never will be.
+    pub(super) fn release_proposed_deploys(&mut self, finalized_timestamp: Timestamp) {
+        self.proposed_deploys
+            .retain(|_, proposed_at| *proposed_at > finalized_timestamp);
+    }
 }
 
 impl Display for BlockProposerDeploySets {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(
             f,
-            "(pending:{}, finalized:{})",
+            "(pending:{}, proposed:{}, finalized:{})",
             self.pending_deploys.len() + self.pending_transfers.len(),
+            self.proposed_deploys.len(),
             self.finalized_deploys.len() + self.finalized_transfers.len(),
         )
     }
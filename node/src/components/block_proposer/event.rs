@@ -12,6 +12,7 @@ use casper_types::Motes;
 
 use super::{BlockHeight, CachedState};
 use crate::{
+    components::consensus::{ClContext, ProposedBlock},
     effect::requests::BlockProposerRequest,
     types::{Approval, Block, DeployHeader, DeployOrTransferHash, FinalizedBlock},
 };
@@ -50,6 +51,9 @@ pub(crate) enum Event {
     Prune,
     /// A block has been finalized. We should never propose its deploys again.
     FinalizedBlock(Box<FinalizedBlock>),
+    /// A valid block was proposed by another validator. We should not propose its deploys again
+    /// unless it can't be finalized anymore.
+    ProposedBlock(Box<ProposedBlock<ClContext>>),
 }
 
 impl Display for Event {
@@ -69,6 +73,9 @@ impl Display for Event {
             Event::FinalizedBlock(block) => {
                 write!(f, "block-proposer finalized block {}", block)
             }
+            Event::ProposedBlock(proposed_block) => {
+                write!(f, "block-proposer {}", proposed_block)
+            }
         }
     }
 }
@@ -9,6 +9,13 @@ pub(super) struct Metrics {
     /// Amount of pending deploys
     #[data_size(skip)]
     pub(super) pending_deploys: IntGauge,
+    /// Number of deploys left out of the last proposed block because of unresolved dependencies.
+    #[data_size(skip)]
+    pub(super) dependency_deferred_deploys: IntGauge,
+    /// Number of deploys which were part of, or depended on, a dependency cycle when the last
+    /// block was proposed.
+    #[data_size(skip)]
+    pub(super) dependency_cycle_deploys: IntGauge,
     /// Registry stored to allow deregistration later.
     #[data_size(skip)]
     registry: Registry,
@@ -18,9 +25,22 @@ impl Metrics {
     /// Creates a new instance of the block proposer metrics.
     pub fn new(registry: Registry) -> Result<Self, prometheus::Error> {
         let pending_deploys = IntGauge::new("pending_deploy", "the number of pending deploys")?;
+        let dependency_deferred_deploys = IntGauge::new(
+            "dependency_deferred_deploys",
+            "the number of deploys left out of the last proposed block because of unresolved \
+             dependencies",
+        )?;
+        let dependency_cycle_deploys = IntGauge::new(
+            "dependency_cycle_deploys",
+            "the number of pending deploys which are part of, or depend on, a dependency cycle",
+        )?;
         registry.register(Box::new(pending_deploys.clone()))?;
+        registry.register(Box::new(dependency_deferred_deploys.clone()))?;
+        registry.register(Box::new(dependency_cycle_deploys.clone()))?;
         Ok(Metrics {
             pending_deploys,
+            dependency_deferred_deploys,
+            dependency_cycle_deploys,
             registry,
         })
     }
@@ -29,5 +49,7 @@ impl Metrics {
 impl Drop for Metrics {
     fn drop(&mut self) {
         unregister_metric!(self.registry, self.pending_deploys);
+        unregister_metric!(self.registry, self.dependency_deferred_deploys);
+        unregister_metric!(self.registry, self.dependency_cycle_deploys);
     }
 }
@@ -667,8 +667,6 @@ fn should_return_deploy_dependencies() {
     let creation_time = Timestamp::from(100);
     let ttl = TimeDiff::from(Duration::from_millis(100));
     let block_time = Timestamp::from(120);
-    let era1 = EraId::from(1);
-    let pub_key = PublicKey::from(&SecretKey::random_secp256k1(&mut rng));
 
     let deploy1 = generate_deploy(
         &mut rng,
@@ -722,24 +720,261 @@ fn should_return_deploy_dependencies() {
         vec![],
         true,
     );
-    let deploys: Vec<DeployOrTransferHash> = block.deploys_and_transfers_iter().collect();
-    // only deploy1 should be returned, as it has no dependencies
-    assert_eq!(deploys.len(), 1);
-    assert!(deploys.contains(&deploy1.deploy_or_transfer_hash()));
+    // both deploys should be returned, with deploy2 ordered after its dependency
+    let deploys: Vec<&DeployHash> = block.deploy_hashes().collect();
+    assert_eq!(deploys, vec![deploy1.id(), deploy2.id()]);
+    assert_eq!(proposer.dependency_deferred_count, 0);
+}
+
+#[test]
+fn should_not_propose_deploys_of_blocks_that_may_still_be_finalized() {
+    let mut rng = crate::new_rng();
+    let creation_time = Timestamp::from(100);
+    let ttl = TimeDiff::from(Duration::from_millis(100));
+    let block_time1 = Timestamp::from(110);
+    let block_time2 = Timestamp::from(120);
+    let block_time3 = Timestamp::from(130);
+    let era1 = EraId::from(1);
+    let pub_key = PublicKey::from(&SecretKey::random_secp256k1(&mut rng));
+
+    let deploy1 = generate_deploy(
+        &mut rng,
+        creation_time,
+        ttl,
+        vec![],
+        default_gas_payment(),
+        DEFAULT_TEST_GAS_PRICE,
+    );
+    let deploy2 = generate_deploy(
+        &mut rng,
+        creation_time,
+        ttl,
+        vec![],
+        default_gas_payment(),
+        DEFAULT_TEST_GAS_PRICE,
+    );
+
+    let mut proposer = create_test_proposer(0.into());
+    for deploy in [&deploy1, &deploy2] {
+        proposer.add_deploy(
+            creation_time,
+            deploy.deploy_or_transfer_hash(),
+            BTreeSet::new(),
+            deploy.deploy_info().unwrap(),
+        );
+    }
+
+    // another validator proposed a block containing deploy1, which we are not building on
+    let other_payload = BlockPayload::new(
+        vec![DeployWithApprovals::from(&deploy1)],
+        vec![],
+        vec![],
+        false,
+    );
+    proposer.handle_proposed_block(&ProposedBlock::new(
+        Arc::new(other_payload),
+        BlockContext::new(block_time1, vec![]),
+    ));
+
+    let block = proposer.propose_block_payload(
+        DeployConfig::default(),
+        BlockContext::new(block_time2, vec![]),
+        vec![],
+        true,
+    );
+    let deploys: Vec<&DeployHash> = block.deploy_hashes().collect();
+    assert_eq!(deploys, vec![deploy2.id()]);
+
+    // deploy2 is now part of our own proposal, so neither deploy is proposed again
+    let block = proposer.propose_block_payload(
+        DeployConfig::default(),
+        BlockContext::new(block_time3, vec![]),
+        vec![],
+        true,
+    );
+    assert!(block.deploys().is_empty());
+
+    // an empty block at block_time2 is finalized, so neither proposal can be finalized anymore
+    let empty_payload = BlockPayload::new(vec![], vec![], vec![], false);
+    let finalized_block = FinalizedBlock::new(empty_payload, None, block_time2, era1, 1, pub_key);
+    proposer.handle_finalized_block(&finalized_block);
+    assert!(proposer.sets.proposed_deploys.is_empty());
+
+    let block = proposer.propose_block_payload(
+        DeployConfig::default(),
+        BlockContext::new(block_time3, vec![]),
+        vec![],
+        true,
+    );
+    assert_eq!(block.deploys().len(), 2);
+}
+
+#[test]
+fn should_propose_dependent_deploy_after_its_dependency_is_finalized() {
+    let mut rng = crate::new_rng();
+    let creation_time = Timestamp::from(100);
+    let ttl = TimeDiff::from(Duration::from_millis(100));
+    let block_time = Timestamp::from(120);
+    let era1 = EraId::from(1);
+    let pub_key = PublicKey::from(&SecretKey::random_secp256k1(&mut rng));
+
+    let deploy1 = generate_deploy(
+        &mut rng,
+        creation_time,
+        ttl,
+        vec![],
+        default_gas_payment(),
+        DEFAULT_TEST_GAS_PRICE,
+    );
+    let deploy2 = generate_deploy(
+        &mut rng,
+        creation_time,
+        ttl,
+        vec![*deploy1.id()],
+        default_gas_payment(),
+        DEFAULT_TEST_GAS_PRICE,
+    );
+
+    let mut proposer = create_test_proposer(0.into());
+    // only one deploy fits into a block
+    let mut config = proposer.deploy_config;
+    config.block_max_deploy_count = 1;
+
+    for deploy in [&deploy2, &deploy1] {
+        proposer.add_deploy(
+            creation_time,
+            deploy.deploy_or_transfer_hash(),
+            BTreeSet::new(),
+            deploy.deploy_info().unwrap(),
+        );
+    }
+
+    let block =
+        proposer.propose_block_payload(config, BlockContext::new(block_time, vec![]), vec![], true);
+    let deploys: Vec<&DeployHash> = block.deploy_hashes().collect();
+    // only deploy1 fits, and deploy2 must not be proposed before it
+    assert_eq!(deploys, vec![deploy1.id()]);
 
     // the deploy will be included in block 1
     let finalized_block = FinalizedBlock::new((*block).clone(), None, block_time, era1, 1, pub_key);
     proposer.handle_finalized_block(&finalized_block);
 
+    let block =
+        proposer.propose_block_payload(config, BlockContext::new(block_time, vec![]), vec![], true);
+    // deploy1 is finalized now, so we should get deploy2
+    let deploys: Vec<&DeployHash> = block.deploy_hashes().collect();
+    assert_eq!(deploys, vec![deploy2.id()]);
+}
+
+#[test]
+fn should_not_propose_deploy_depending_on_transfer_in_same_block() {
+    let mut rng = crate::new_rng();
+    let creation_time = Timestamp::from(100);
+    let ttl = TimeDiff::from(Duration::from_millis(100));
+    let block_time = Timestamp::from(120);
+
+    let transfer = generate_transfer(&mut rng, creation_time, ttl, vec![], default_gas_payment());
+    // deploys are executed before transfers, so this can't go into the same block
+    let deploy = generate_deploy(
+        &mut rng,
+        creation_time,
+        ttl,
+        vec![*transfer.id()],
+        default_gas_payment(),
+        DEFAULT_TEST_GAS_PRICE,
+    );
+
+    let mut proposer = create_test_proposer(0.into());
+    for deploy in [&transfer, &deploy] {
+        proposer.add_deploy(
+            creation_time,
+            deploy.deploy_or_transfer_hash(),
+            BTreeSet::new(),
+            deploy.deploy_info().unwrap(),
+        );
+    }
+
     let block = proposer.propose_block_payload(
         DeployConfig::default(),
         BlockContext::new(block_time, vec![]),
         vec![],
         true,
     );
-    // `blocks` contains a block that contains deploy1 now, so we should get deploy2
-    assert_eq!(block.deploys().len(), 1);
-    assert!(block.deploy_hashes().any(|hash| hash == deploy2.id()));
+    assert_eq!(block.transfers().len(), 1);
+    assert!(block.deploys().is_empty());
+    assert_eq!(proposer.dependency_deferred_count, 1);
+}
+
+#[test]
+fn should_skip_deploys_with_cyclic_dependencies() {
+    let mut rng = crate::new_rng();
+    let creation_time = Timestamp::from(100);
+    let ttl = TimeDiff::from(Duration::from_millis(100));
+    let block_time = Timestamp::from(120);
+
+    // Deploy hashes commit to the dependencies, so a real cycle can't be constructed.  Instead we
+    // buffer deploy2 under the hash which deploy1 depends on.
+    let hash2 = DeployHash::random(&mut rng);
+    let deploy1 = generate_deploy(
+        &mut rng,
+        creation_time,
+        ttl,
+        vec![hash2],
+        default_gas_payment(),
+        DEFAULT_TEST_GAS_PRICE,
+    );
+    let deploy2 = generate_deploy(
+        &mut rng,
+        creation_time,
+        ttl,
+        vec![*deploy1.id()],
+        default_gas_payment(),
+        DEFAULT_TEST_GAS_PRICE,
+    );
+    // deploy3 depends on the cycle, deploy4 is independent
+    let deploy3 = generate_deploy(
+        &mut rng,
+        creation_time,
+        ttl,
+        vec![hash2],
+        default_gas_payment(),
+        DEFAULT_TEST_GAS_PRICE,
+    );
+    let deploy4 = generate_deploy(
+        &mut rng,
+        creation_time,
+        ttl,
+        vec![],
+        default_gas_payment(),
+        DEFAULT_TEST_GAS_PRICE,
+    );
+
+    let mut proposer = create_test_proposer(0.into());
+    proposer.add_deploy(
+        creation_time,
+        DeployOrTransferHash::Deploy(hash2),
+        BTreeSet::new(),
+        deploy2.deploy_info().unwrap(),
+    );
+    for deploy in [&deploy1, &deploy3, &deploy4] {
+        proposer.add_deploy(
+            creation_time,
+            deploy.deploy_or_transfer_hash(),
+            BTreeSet::new(),
+            deploy.deploy_info().unwrap(),
+        );
+    }
+
+    let block = proposer.propose_block_payload(
+        DeployConfig::default(),
+        BlockContext::new(block_time, vec![]),
+        vec![],
+        true,
+    );
+    let deploys: Vec<&DeployHash> = block.deploy_hashes().collect();
+    assert_eq!(deploys, vec![deploy4.id()]);
+    assert_eq!(proposer.dependency_cycle_count, 3);
+    assert_eq!(proposer.dependency_deferred_count, 0);
 }
 
 #[test]
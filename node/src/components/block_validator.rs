@@ -16,6 +16,7 @@ use std::{
     convert::Infallible,
     fmt::Debug,
     hash::Hash,
+    iter,
     sync::Arc,
 };
 
@@ -25,7 +26,7 @@ use itertools::Itertools;
 use smallvec::{smallvec, SmallVec};
 use tracing::{info, warn};
 
-use casper_types::Timestamp;
+use casper_types::{EraId, Timestamp};
 
 use crate::{
     components::{
@@ -38,8 +39,8 @@ use crate::{
         EffectBuilder, EffectExt, Effects, Responder,
     },
     types::{
-        appendable_block::AppendableBlock, Approval, Block, Chainspec, Deploy, DeployHash,
-        DeployOrTransferHash, DeployWithApprovals, NodeId,
+        appendable_block::AppendableBlock, chainspec::DeployConfig, Approval, Block, Chainspec,
+        Deploy, DeployHash, DeployOrTransferHash, DeployWithApprovals, NodeId,
     },
     NodeRng,
 };
@@ -191,9 +192,9 @@ impl BlockValidationState {
 
 #[derive(DataSize, Debug)]
 pub(crate) struct BlockValidator {
-    /// Chainspec loaded for deploy validation.
-    #[data_size(skip)]
-    chainspec: Arc<Chainspec>,
+    /// The deploy configs for deploy validation, keyed by the era from which each one applies.
+    /// Initially only that of the chainspec, applying from genesis.
+    deploy_configs: BTreeMap<EraId, DeployConfig>,
     /// State of validation of a specific block.
     validation_states: HashMap<ValidatingBlock, BlockValidationState>,
     /// Number of requests for a specific deploy hash still in flight.
@@ -204,12 +205,32 @@ impl BlockValidator {
     /// Creates a new block validator instance.
     pub(crate) fn new(chainspec: Arc<Chainspec>) -> Self {
         BlockValidator {
-            chainspec,
+            deploy_configs: iter::once((EraId::from(0), chainspec.deploy_config)).collect(),
             validation_states: HashMap::new(),
             in_flight: KeyedCounter::default(),
         }
     }
 
+    /// Sets the deploy config for validating blocks from the given era onwards, e.g. after it was
+    /// updated via governance.
+    ///
+    /// Blocks of earlier eras are still validated with the config that was in effect in their era.
+    pub(crate) fn set_deploy_config(&mut self, era_id: EraId, deploy_config: DeployConfig) {
+        // The config is read at every switch block, so only record actual changes.
+        if self.deploy_config(era_id) != deploy_config {
+            self.deploy_configs.insert(era_id, deploy_config);
+        }
+    }
+
+    /// Returns the deploy config in effect in the given era.
+    fn deploy_config(&self, era_id: EraId) -> DeployConfig {
+        self.deploy_configs
+            .range(..=era_id)
+            .next_back()
+            .map(|(_, deploy_config)| *deploy_config)
+            .expect("should always contain the chainspec's deploy config")
+    }
+
     /// Prints a log message about an invalid block with duplicated deploys.
     fn log_block_with_replay(&self, sender: NodeId, block: &ValidatingBlock) {
         let mut deploy_counts = BTreeMap::new();
@@ -249,6 +270,7 @@ where
         let mut effects = Effects::new();
         match event {
             Event::Request(BlockValidationRequest {
+                era_id,
                 block,
                 sender,
                 responder,
@@ -265,6 +287,7 @@ where
                     return responder.respond(false).ignore();
                 }
 
+                let deploy_config = self.deploy_config(era_id);
                 match self.validation_states.entry(block) {
                     Entry::Occupied(mut entry) => {
                         // The entry already exists.
@@ -292,7 +315,6 @@ where
                             },
                         ));
                         let block_timestamp = entry.key().timestamp();
-                        let deploy_config = self.chainspec.deploy_config;
                         entry.insert(BlockValidationState {
                             appendable_block: AppendableBlock::new(deploy_config, block_timestamp),
                             missing_deploys: block_deploys,
@@ -156,6 +156,28 @@ async fn validate_block(
     timestamp: Timestamp,
     deploys: Vec<Deploy>,
     transfers: Vec<Deploy>,
+) -> bool {
+    let (chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_resources("local");
+    let mut block_validator = BlockValidator::new(Arc::new(chainspec));
+    validate_block_in_era(
+        rng,
+        &mut block_validator,
+        EraId::from(0),
+        timestamp,
+        deploys,
+        transfers,
+    )
+    .await
+}
+
+/// Validates a block of the given era using the given `BlockValidator`, and returns the result.
+async fn validate_block_in_era(
+    rng: &mut TestRng,
+    block_validator: &mut BlockValidator,
+    era_id: EraId,
+    timestamp: Timestamp,
+    deploys: Vec<Deploy>,
+    transfers: Vec<Deploy>,
 ) -> bool {
     // Assemble the block to be validated.
     let deploys_for_block = deploys.iter().map(DeployWithApprovals::from).collect_vec();
@@ -168,14 +190,12 @@ async fn validate_block(
     // Create the reactor and component.
     let reactor = MockReactor::new();
     let effect_builder = EffectBuilder::new(EventQueueHandle::without_shutdown(reactor.scheduler));
-    let (chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_resources("local");
-    let mut block_validator = BlockValidator::new(Arc::new(chainspec));
 
     // Pass the block to the component. This future will eventually resolve to the result, i.e.
     // whether the block is valid or not.
     let bob_node_id = NodeId::random(rng);
     let validation_result =
-        tokio::spawn(effect_builder.validate_block(bob_node_id, proposed_block.clone()));
+        tokio::spawn(effect_builder.validate_block(bob_node_id, era_id, proposed_block.clone()));
     let event = reactor.expect_block_validator_event().await;
     let effects = block_validator.handle_event(effect_builder, rng, event);
 
@@ -289,3 +309,37 @@ async fn transfer_deploy_mixup_and_replay() {
     let transfers = vec![transfer1.clone(), transfer2.clone(), transfer2.clone()];
     assert!(!validate_block(&mut rng, timestamp, deploys, transfers).await);
 }
+
+/// Verifies that each block is validated with the deploy config of its own era.
+#[tokio::test]
+async fn deploy_config_per_era() {
+    let mut rng = TestRng::new();
+    let ttl = TimeDiff::from(200);
+    let timestamp = Timestamp::from(1000);
+    let deploys = vec![
+        new_deploy(&mut rng, timestamp, ttl),
+        new_deploy(&mut rng, timestamp, ttl),
+    ];
+
+    let (chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_resources("local");
+    let mut deploy_config = chainspec.deploy_config;
+    deploy_config.block_max_deploy_count = 1;
+    let mut block_validator = BlockValidator::new(Arc::new(chainspec));
+    block_validator.set_deploy_config(EraId::from(5), deploy_config);
+
+    // Blocks before era 5 may still contain two deploys, but from era 5 onwards only one.
+    for (era_id, valid) in [(0, true), (4, true), (5, false), (6, false)] {
+        assert_eq!(
+            valid,
+            validate_block_in_era(
+                &mut rng,
+                &mut block_validator,
+                EraId::from(era_id),
+                timestamp,
+                deploys.clone(),
+                vec![],
+            )
+            .await
+        );
+    }
+}
@@ -3,11 +3,13 @@ mod error;
 mod event;
 mod metrics;
 mod operations;
+mod peer_stats;
 mod progress;
 
 use std::{convert::Infallible, fmt::Debug, marker::PhantomData, sync::Arc};
 
 use datasize::DataSize;
+use num::rational::Ratio;
 use prometheus::Registry;
 use tracing::{debug, error, info};
 
@@ -24,16 +26,17 @@ use crate::{
         },
         requests::{
             ChainspecLoaderRequest, ContractRuntimeRequest, FetcherRequest,
-            MarkBlockCompletedRequest, NetworkInfoRequest, NodeStateRequest,
+            MarkBlockCompletedRequest, NetworkInfoRequest, NodeStateRequest, StateStoreRequest,
+            SyncProgressRequest,
         },
         EffectBuilder, EffectExt, Effects,
     },
     fatal,
     storage::StorageRequest,
     types::{
-        Block, BlockAndDeploys, BlockHeader, BlockHeaderWithMetadata, BlockHeadersBatch,
-        BlockSignatures, BlockWithMetadata, Chainspec, Deploy, FinalizedApprovalsWithId,
-        NodeConfig, NodeState,
+        BackfillBlockSignatures, Block, BlockAndDeploys, BlockHeader, BlockHeaderWithMetadata,
+        BlockHeadersBatch, BlockSignatures, BlockWithMetadata, Chainspec, Deploy,
+        FinalizedApprovalsWithId, NodeConfig, NodeState,
     },
     NodeRng, SmallNetworkConfig,
 };
@@ -42,8 +45,10 @@ pub(crate) use error::Error;
 pub(crate) use event::Event;
 pub(crate) use metrics::Metrics;
 pub(crate) use operations::KeyBlockInfo;
-pub(crate) use progress::Progress;
 use progress::ProgressHolder;
+#[cfg(test)]
+pub(crate) use progress::{FastSync, SyncToGenesis};
+pub(crate) use progress::{Progress, SyncPhase, SyncProgress};
 
 #[derive(DataSize, Debug)]
 pub(crate) enum JoiningOutcome {
@@ -91,10 +96,11 @@ where
         + From<BlocklistAnnouncement>
         + From<ControlAnnouncement>
         + From<MarkBlockCompletedRequest>
+        + From<StateStoreRequest>
         + Send,
 {
     /// Constructs a new `ChainSynchronizer` suitable for use in the joiner reactor to perform the
-    /// initial fast sync.
+    /// initial fast sync, or if configured to run as a light node, to perform the light sync.
     pub(crate) fn new_for_fast_sync(
         chainspec: Arc<Chainspec>,
         node_config: NodeConfig,
@@ -107,13 +113,23 @@ where
         let progress = ProgressHolder::new_fast_sync();
         let node_state = NodeState::Joining(progress.progress());
 
-        let effects = operations::run_fast_sync_task(
-            effect_builder,
-            config.clone(),
-            metrics.clone(),
-            progress.clone(),
-        )
-        .event(|result| Event::FastSyncResult(Box::new(result)));
+        let effects = if config.light_sync() {
+            operations::run_light_sync_task(
+                effect_builder,
+                config.clone(),
+                metrics.clone(),
+                progress.clone(),
+            )
+            .event(|result| Event::LightSyncResult(Box::new(result)))
+        } else {
+            operations::run_fast_sync_task(
+                effect_builder,
+                config.clone(),
+                metrics.clone(),
+                progress.clone(),
+            )
+            .event(|result| Event::FastSyncResult(Box::new(result)))
+        };
 
         let synchronizer = ChainSynchronizer {
             config,
@@ -167,6 +183,34 @@ where
             }
         }
     }
+
+    /// Handles the light sync task returning, which only happens on error.  As with fast sync, the
+    /// node shuts down for upgrade if a block from a future protocol version was received.
+    fn handle_light_sync_result(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        result: Result<(), Error>,
+    ) -> Effects<Event> {
+        match result {
+            Ok(()) => {
+                error!("light sync task returned unexpectedly");
+                fatal!(effect_builder, "light sync task returned unexpectedly").ignore()
+            }
+            Err(Error::RetrievedBlockHeaderFromFutureVersion {
+                current_version,
+                block_header_with_future_version,
+            }) => {
+                let future_version = block_header_with_future_version.protocol_version();
+                info!(%current_version, %future_version, "shutting down for upgrade");
+                self.joining_outcome = Some(JoiningOutcome::ShouldExitForUpgrade);
+                Effects::new()
+            }
+            Err(error) => {
+                error!(%error, "failed to light sync linear chain");
+                fatal!(effect_builder, "{}", error).ignore()
+            }
+        }
+    }
 }
 
 impl<REv> ChainSynchronizer<REv>
@@ -176,6 +220,7 @@ where
         + From<FetcherRequest<TrieOrChunk>>
         + From<FetcherRequest<BlockAndDeploys>>
         + From<FetcherRequest<BlockSignatures>>
+        + From<FetcherRequest<BackfillBlockSignatures>>
         + From<FetcherRequest<BlockHeadersBatch>>
         + From<ContractRuntimeRequest>
         + From<BlocklistAnnouncement>
@@ -195,6 +240,27 @@ where
         let config = Config::new(chainspec, node_config, small_network_config);
         let progress = ProgressHolder::new_sync_to_genesis();
 
+        let mut effects = Effects::new();
+        if config.backfill_finality_signatures() {
+            let threshold = config.finality_signature_backfill_threshold();
+            if threshold <= Ratio::new(0, 1) || threshold >= Ratio::new(1, 1) {
+                error!(
+                    %threshold,
+                    "finality signature backfill threshold must be between 0 and 1; not starting \
+                    finality signature backfill"
+                );
+            } else {
+                effects.extend(
+                    operations::run_finality_signature_backfill_task(
+                        effect_builder,
+                        config.clone(),
+                        metrics.clone(),
+                    )
+                    .event(|result| Event::FinalitySignatureBackfillResult(Box::new(result))),
+                );
+            }
+        }
+
         if config.sync_to_genesis() {
             let node_state = NodeState::ParticipatingAndSyncingToGenesis {
                 sync_progress: progress.progress(),
@@ -209,13 +275,15 @@ where
                 _phantom: PhantomData,
             };
 
-            let effects = operations::run_sync_to_genesis_task(
-                effect_builder,
-                synchronizer.config.clone(),
-                synchronizer.metrics.clone(),
-                progress,
-            )
-            .event(|result| Event::SyncToGenesisResult(Box::new(result)));
+            effects.extend(
+                operations::run_sync_to_genesis_task(
+                    effect_builder,
+                    synchronizer.config.clone(),
+                    synchronizer.metrics.clone(),
+                    progress,
+                )
+                .event(|result| Event::SyncToGenesisResult(Box::new(result))),
+            );
 
             return Ok((synchronizer, effects));
         }
@@ -232,17 +300,23 @@ where
             _phantom: PhantomData,
         };
 
-        Ok((
-            synchronizer,
-            effect_builder.announce_finished_chain_syncing().ignore(),
-        ))
+        effects.extend(effect_builder.announce_finished_chain_syncing().ignore());
+        Ok((synchronizer, effects))
     }
 }
 
 impl<REv> ChainSynchronizer<REv> {
-    fn handle_get_node_state_request(&mut self, request: NodeStateRequest) -> Effects<Event> {
-        self.node_state = match self.node_state {
-            NodeState::Joining(_) => NodeState::Joining(self.progress.progress()),
+    /// Returns the node's current state, taking the progress of syncing into account.
+    pub(crate) fn node_state(&self) -> NodeState {
+        match self.node_state {
+            NodeState::Joining(_) => {
+                let progress = self.progress.progress();
+                if self.config.light_sync() && progress.is_finished() {
+                    NodeState::Observing
+                } else {
+                    NodeState::Joining(progress)
+                }
+            }
             NodeState::ParticipatingAndSyncingToGenesis { .. } => {
                 let sync_progress = self.progress.progress();
                 if sync_progress.is_finished() {
@@ -252,10 +326,18 @@ impl<REv> ChainSynchronizer<REv> {
                 }
             }
             NodeState::Participating => NodeState::Participating,
-        };
+            NodeState::Observing => NodeState::Observing,
+        }
+    }
 
+    fn handle_get_node_state_request(&mut self, request: NodeStateRequest) -> Effects<Event> {
+        self.node_state = self.node_state();
         request.0.respond(self.node_state.clone()).ignore()
     }
+
+    fn handle_get_sync_progress_request(&self, request: SyncProgressRequest) -> Effects<Event> {
+        request.0.respond(self.progress.sync_progress()).ignore()
+    }
 }
 
 impl<REv> Component<REv> for ChainSynchronizer<REv>
@@ -276,6 +358,7 @@ where
         + From<BlocklistAnnouncement>
         + From<ControlAnnouncement>
         + From<MarkBlockCompletedRequest>
+        + From<StateStoreRequest>
         + Send,
 {
     type Event = Event;
@@ -302,12 +385,31 @@ where
                         "sync to genesis failed due to fetch retries exhaustion; please retry",
                     )
                     .ignore()
+                } else if matches!(*result, Err(Error::ConflictsWithCheckpoint { .. }))
+                    | matches!(*result, Err(Error::FetchHeadersBatch(ref err)) if matches!(err, FetchBlockHeadersBatchError::ConflictsWithCheckpoint { .. }))
+                {
+                    error!("sync to genesis found a chain conflicting with a chainspec checkpoint; shutting down");
+                    fatal!(
+                        effect_builder,
+                        "sync to genesis found a chain conflicting with a chainspec checkpoint",
+                    )
+                    .ignore()
                 } else {
                     Effects::new()
                 }
             }
             Event::FastSyncResult(result) => self.handle_fast_sync_result(effect_builder, *result),
+            Event::LightSyncResult(result) => {
+                self.handle_light_sync_result(effect_builder, *result)
+            }
+            Event::FinalitySignatureBackfillResult(result) => {
+                if let Err(error) = *result {
+                    error!(%error, "finality signature backfill failed");
+                }
+                Effects::new()
+            }
             Event::GetNodeState(request) => self.handle_get_node_state_request(request),
+            Event::GetSyncProgress(request) => self.handle_get_sync_progress_request(request),
         }
     }
 }
@@ -1,4 +1,4 @@
-use std::{sync::Arc, time::Duration};
+use std::{cmp, sync::Arc, time::Duration};
 
 use datasize::DataSize;
 use num::rational::Ratio;
@@ -7,7 +7,7 @@ use casper_types::{EraId, ProtocolVersion, TimeDiff};
 
 use crate::{
     components::consensus::ChainspecConsensusExt,
-    types::{BlockHash, Chainspec, NodeConfig},
+    types::{chainspec::Checkpoint, BlockHash, BlockHeader, Chainspec, NodeConfig},
     SmallNetworkConfig,
 };
 
@@ -22,6 +22,8 @@ pub(super) struct Config {
     max_parallel_trie_fetches: u32,
     /// Maximum number of blocks to fetch in parallel.
     max_parallel_block_fetches: u32,
+    /// Maximum number of blocks to fetch ahead of the block being executed during fast sync.
+    max_blocks_to_prefetch: u32,
     /// The maximum number of retries of fetch operations during the chain synchronization process.
     /// The retry limit is in effect only when the network component reports that enough peers
     /// are connected, until that happens, the retries are unbounded.
@@ -31,11 +33,27 @@ pub(super) struct Config {
     /// Whether to run in sync-to-genesis mode which captures all data (blocks, deploys
     /// and global state) back to genesis.
     sync_to_genesis: bool,
+    /// Whether to run in light sync mode, syncing only block headers and finality signatures.
+    light_sync: bool,
+    /// Whether to fetch further finality signatures from peers for each stored block whose
+    /// signatures' total weight doesn't exceed `finality_signature_backfill_threshold`.
+    backfill_finality_signatures: bool,
+    /// The fraction of the total validator weight which the stored finality signatures of each
+    /// block should exceed.
+    #[data_size(skip)]
+    finality_signature_backfill_threshold: Ratio<u64>,
+    /// The duration for which to pause between passes over the stored blocks while backfilling
+    /// finality signatures.
+    finality_signature_backfill_interval: Duration,
     /// The maximum number of consecutive times we'll allow the network component to return an
     /// empty set of fully-connected peers before we give up.
     max_retries_while_not_connected: u64,
     /// How many fetches in between attempting to redeem one bad node.
     pub(crate) redemption_interval: u32,
+    /// The weight given to a peer's response time when choosing which peers to sync from.
+    peer_latency_weight: u32,
+    /// The weight given to a peer's throughput when choosing which peers to sync from.
+    peer_throughput_weight: u32,
     /// Block and block header fetch operations are retried forever until we have enough connected
     /// peers. If the operation fails while there are enough peers, the process gives up. By
     /// default, this is the number of items on the `known_addresses` list in the node config
@@ -58,11 +76,21 @@ impl Config {
             max_parallel_deploy_fetches: node_config.max_parallel_deploy_fetches,
             max_parallel_trie_fetches: node_config.max_parallel_trie_fetches,
             max_parallel_block_fetches: node_config.max_parallel_block_fetches,
+            max_blocks_to_prefetch: node_config.max_blocks_to_prefetch,
             max_sync_fetch_attempts: node_config.max_sync_fetch_attempts,
             retry_interval: Duration::from_millis(node_config.retry_interval.millis()),
             sync_to_genesis: node_config.sync_to_genesis,
+            light_sync: node_config.light_sync,
+            backfill_finality_signatures: node_config.backfill_finality_signatures,
+            finality_signature_backfill_threshold: node_config
+                .finality_signature_backfill_threshold,
+            finality_signature_backfill_interval: Duration::from_millis(
+                node_config.finality_signature_backfill_interval.millis(),
+            ),
             max_retries_while_not_connected,
             redemption_interval: node_config.sync_peer_redemption_interval,
+            peer_latency_weight: node_config.sync_peer_latency_weight,
+            peer_throughput_weight: node_config.sync_peer_throughput_weight,
             minimum_peer_count_threshold_for_block_fetch_retry: small_network_config
                 .known_addresses
                 .len()
@@ -102,6 +130,11 @@ impl Config {
         self.chainspec.highway_config.min_round_length()
     }
 
+    /// Returns the chainspec checkpoint which `block_header` conflicts with, if any.
+    pub(super) fn conflicting_checkpoint(&self, block_header: &BlockHeader) -> Option<Checkpoint> {
+        self.chainspec.conflicting_checkpoint(block_header).copied()
+    }
+
     pub(super) fn trusted_hash(&self) -> Option<BlockHash> {
         self.trusted_hash
     }
@@ -118,6 +151,12 @@ impl Config {
         self.max_parallel_block_fetches as usize
     }
 
+    /// Returns the maximum number of blocks to fetch ahead of the block being executed during fast
+    /// sync, which is at least one.
+    pub(super) fn max_blocks_to_prefetch(&self) -> usize {
+        cmp::max(self.max_blocks_to_prefetch as usize, 1)
+    }
+
     pub(super) fn max_sync_fetch_attempts(&self) -> usize {
         self.max_sync_fetch_attempts as usize
     }
@@ -130,6 +169,30 @@ impl Config {
         self.sync_to_genesis
     }
 
+    pub(super) fn light_sync(&self) -> bool {
+        self.light_sync
+    }
+
+    pub(super) fn backfill_finality_signatures(&self) -> bool {
+        self.backfill_finality_signatures
+    }
+
+    pub(super) fn finality_signature_backfill_threshold(&self) -> Ratio<u64> {
+        self.finality_signature_backfill_threshold
+    }
+
+    pub(super) fn finality_signature_backfill_interval(&self) -> Duration {
+        self.finality_signature_backfill_interval
+    }
+
+    pub(super) fn peer_latency_weight(&self) -> u32 {
+        self.peer_latency_weight
+    }
+
+    pub(super) fn peer_throughput_weight(&self) -> u32 {
+        self.peer_throughput_weight
+    }
+
     pub(super) fn max_retries_while_not_connected(&self) -> u64 {
         self.max_retries_while_not_connected
     }
@@ -17,8 +17,9 @@ use crate::{
         linear_chain::BlockSignatureError,
     },
     types::{
-        Block, BlockAndDeploys, BlockHash, BlockHeader, BlockHeaderWithMetadata, BlockHeadersBatch,
-        BlockWithMetadata, Deploy, FinalizedApprovalsWithId, Item,
+        chainspec::Checkpoint, Block, BlockAndDeploys, BlockHash, BlockHeader,
+        BlockHeaderWithMetadata, BlockHeadersBatch, BlockWithMetadata, Deploy,
+        FinalizedApprovalsWithId, Item,
     },
 };
 
@@ -137,6 +138,12 @@ pub(crate) enum Error {
     #[error("parent block has a height of u64::MAX")]
     HeightOverflow { parent: Box<BlockHeader> },
 
+    #[error("block header {block_header:?} conflicts with chainspec {checkpoint}")]
+    ConflictsWithCheckpoint {
+        checkpoint: Checkpoint,
+        block_header: Box<BlockHeader>,
+    },
+
     /// Error joining tokio task.
     #[error(transparent)]
     Join(
@@ -255,6 +262,12 @@ pub(crate) enum FetchBlockHeadersBatchError {
     #[error("Batch from storage was empty")]
     EmptyBatchFromStorage,
 
+    #[error("block header {block_header:?} in batch conflicts with chainspec {checkpoint}")]
+    ConflictsWithCheckpoint {
+        checkpoint: Checkpoint,
+        block_header: Box<BlockHeader>,
+    },
+
     #[error("fetch attempts exhausted")]
     AttemptsExhausted,
 }
@@ -4,7 +4,10 @@ use derive_more::From;
 use serde::Serialize;
 
 use super::Error;
-use crate::{effect::requests::NodeStateRequest, types::BlockHeader};
+use crate::{
+    effect::requests::{NodeStateRequest, SyncProgressRequest},
+    types::BlockHeader,
+};
 
 #[derive(Debug, Serialize, From)]
 #[allow(clippy::enum_variant_names)]
@@ -13,9 +16,16 @@ pub(crate) enum Event {
     SyncToGenesisResult(Box<Result<(), Error>>),
     /// The result of running the fast sync task.
     FastSyncResult(Box<Result<BlockHeader, Error>>),
+    /// The result of running the finality signature backfill task.
+    FinalitySignatureBackfillResult(Box<Result<(), Error>>),
+    /// The result of running the light sync task.
+    LightSyncResult(Box<Result<(), Error>>),
     /// A request to provide the node state.
     #[from]
     GetNodeState(NodeStateRequest),
+    /// A request to provide a summary of the sync progress.
+    #[from]
+    GetSyncProgress(SyncProgressRequest),
 }
 
 impl Display for Event {
@@ -27,7 +37,18 @@ impl Display for Event {
             Event::SyncToGenesisResult(result) => {
                 write!(formatter, "sync to genesis result: {:?}", result)
             }
+            Event::FinalitySignatureBackfillResult(result) => {
+                write!(
+                    formatter,
+                    "finality signature backfill result: {:?}",
+                    result
+                )
+            }
+            Event::LightSyncResult(result) => {
+                write!(formatter, "light sync result: {:?}", result)
+            }
             Event::GetNodeState(_) => write!(formatter, "get node state"),
+            Event::GetSyncProgress(_) => write!(formatter, "get sync progress"),
         }
     }
 }
@@ -3,6 +3,7 @@ use prometheus::{self, Histogram, IntCounter, IntGauge, Registry};
 
 use casper_types::Timestamp;
 
+use super::progress::SyncProgress;
 use crate::utils;
 
 /// Bucket parameters for the `chain_sync_sync_trie_store_duration_seconds` and
@@ -67,6 +68,18 @@ pub(crate) struct Metrics {
     /// Integer representing number of blocks that we've successfully downloaded.
     #[data_size(skip)]
     pub(super) chain_sync_blocks_synced: IntCounter,
+    /// Number of blocks remaining to be synced in the current phase, or -1 if unknown.
+    #[data_size(skip)]
+    chain_sync_blocks_remaining: IntGauge,
+    /// Number of tries fetched during chain sync.
+    #[data_size(skip)]
+    chain_sync_tries_fetched: IntGauge,
+    /// Estimated time in seconds until the current phase is finished, or -1 if unknown.
+    #[data_size(skip)]
+    chain_sync_estimated_seconds_remaining: IntGauge,
+    /// Number of blocks received from peers which conflict with a stored block at the same height.
+    #[data_size(skip)]
+    pub(super) chain_sync_forks_detected: IntCounter,
     /// Registry stored to allow deregistration later.
     #[data_size(skip)]
     registry: Registry,
@@ -126,6 +139,26 @@ impl Metrics {
             "Number of full blocks we've synchronized.",
         )?;
 
+        let chain_sync_blocks_remaining = IntGauge::new(
+            "chain_sync_blocks_remaining",
+            "number of blocks remaining to be synced in the current phase of chain sync, or -1 if \
+            unknown",
+        )?;
+        let chain_sync_tries_fetched = IntGauge::new(
+            "chain_sync_tries_fetched",
+            "number of tries fetched during chain sync",
+        )?;
+        let chain_sync_estimated_seconds_remaining = IntGauge::new(
+            "chain_sync_estimated_seconds_remaining",
+            "estimated time in seconds until the current phase of chain sync is finished, or -1 if \
+            unknown",
+        )?;
+        let chain_sync_forks_detected = IntCounter::new(
+            "chain_sync_forks_detected",
+            "number of finalized blocks received from peers which conflict with a stored finalized \
+            block at the same height; any non-zero value indicates a fork of the linear chain",
+        )?;
+
         let buckets = prometheus::exponential_buckets(
             SYNC_TRIE_OR_DEPLOY_BUCKET_START,
             SYNC_TRIE_OR_DEPLOY_BUCKET_FACTOR,
@@ -156,6 +189,10 @@ impl Metrics {
             chain_sync_era_supervisor_init_duration_seconds.clone(),
         ))?;
         registry.register(Box::new(chain_sync_blocks_synced.clone()))?;
+        registry.register(Box::new(chain_sync_blocks_remaining.clone()))?;
+        registry.register(Box::new(chain_sync_tries_fetched.clone()))?;
+        registry.register(Box::new(chain_sync_estimated_seconds_remaining.clone()))?;
+        registry.register(Box::new(chain_sync_forks_detected.clone()))?;
         registry.register(Box::new(chain_sync_execute_blocks_duration_seconds.clone()))?;
         registry.register(Box::new(
             chain_sync_fetch_and_store_initial_trusted_block_header_duration_seconds.clone(),
@@ -199,6 +236,10 @@ impl Metrics {
                     buckets,
                 )?,
             chain_sync_blocks_synced,
+            chain_sync_blocks_remaining,
+            chain_sync_tries_fetched,
+            chain_sync_estimated_seconds_remaining,
+            chain_sync_forks_detected,
             registry: registry.clone(),
         })
     }
@@ -218,6 +259,21 @@ impl Metrics {
             .observe(start.elapsed().millis() as f64 / 1000.0);
     }
 
+    pub(super) fn record_sync_progress(&self, sync_progress: &SyncProgress) {
+        self.chain_sync_blocks_remaining.set(
+            sync_progress
+                .blocks_remaining
+                .map_or(-1, |blocks_remaining| blocks_remaining as i64),
+        );
+        self.chain_sync_tries_fetched
+            .set(sync_progress.tries_fetched as i64);
+        self.chain_sync_estimated_seconds_remaining.set(
+            sync_progress
+                .estimated_time_remaining
+                .map_or(-1, |time_remaining| (time_remaining.millis() / 1000) as i64),
+        );
+    }
+
     pub(super) fn observe_fetch_finality_signatures_duration_seconds(&self, start: Timestamp) {
         self.chain_sync_fetch_finality_signatures_duration_seconds
             .observe(start.elapsed().millis() as f64 / 1000.0);
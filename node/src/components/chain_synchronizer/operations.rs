@@ -11,6 +11,7 @@ use std::{
 use async_trait::async_trait;
 use datasize::DataSize;
 use futures::{
+    future,
     stream::{futures_unordered::FuturesUnordered, StreamExt},
     TryStreamExt,
 };
@@ -19,7 +20,7 @@ use prometheus::IntGauge;
 use quanta::Instant;
 use serde::Serialize;
 use thiserror::Error;
-use tokio::sync::Semaphore;
+use tokio::sync::{mpsc, Semaphore};
 use tracing::{debug, error, info, trace, warn};
 
 use casper_execution_engine::storage::trie::{TrieOrChunk, TrieOrChunkId};
@@ -30,11 +31,12 @@ use crate::{
     components::{
         chain_synchronizer::{
             error::{Error, FetchBlockHeadersBatchError, FetchTrieError},
+            peer_stats::PeerStats,
             Config, Metrics, ProgressHolder,
         },
         contract_runtime::{BlockAndExecutionEffects, ExecutionPreState},
         fetcher::{FetchedData, FetcherError},
-        linear_chain::{self, BlockSignatureError},
+        linear_chain::{self, BlockSignatureError, ForkEvidence},
     },
     effect::{
         announcements::{
@@ -42,15 +44,16 @@ use crate::{
         },
         requests::{
             ContractRuntimeRequest, FetcherRequest, MarkBlockCompletedRequest, NetworkInfoRequest,
+            StateStoreRequest,
         },
         EffectBuilder,
     },
     storage::StorageRequest,
     types::{
-        AvailableBlockRange, Block, BlockAndDeploys, BlockHash, BlockHeader,
-        BlockHeaderWithMetadata, BlockHeadersBatch, BlockHeadersBatchId, BlockSignatures,
-        BlockWithMetadata, Deploy, DeployHash, FinalizedApprovals, FinalizedApprovalsWithId,
-        FinalizedBlock, Item, NodeId,
+        AvailableBlockRange, BackfillBlockSignatures, Block, BlockAndDeploys, BlockHash,
+        BlockHeader, BlockHeaderWithMetadata, BlockHeadersBatch, BlockHeadersBatchId, BlockHeight,
+        BlockSignatures, BlockWithMetadata, Deploy, DeployHash, FinalizedApprovals,
+        FinalizedApprovalsWithId, FinalizedBlock, Item, NodeId,
     },
     utils::work_queue::WorkQueue,
 };
@@ -84,6 +87,11 @@ impl<'a> Drop for ScopeTimer<'a> {
     }
 }
 
+/// Returns the approximate number of bytes transferred to fetch `item`.
+fn serialized_size<T: Item>(item: &T) -> u64 {
+    bincode::serialized_size(item).unwrap_or_default()
+}
+
 struct ChainSyncContext<'a, REv>
 where
     REv: 'static,
@@ -100,6 +108,8 @@ where
     /// A range of blocks for which we already have all required data stored locally.
     locally_available_block_range_on_start: AvailableBlockRange,
     trie_fetch_limit: Semaphore,
+    /// The observed performance of peers, used to prefer the fastest ones.
+    peer_stats: PeerStats,
 }
 
 impl<'a, REv> ChainSyncContext<'a, REv>
@@ -132,6 +142,10 @@ where
             filter_count: AtomicI64::new(0),
             locally_available_block_range_on_start,
             trie_fetch_limit: Semaphore::new(config.max_parallel_trie_fetches()),
+            peer_stats: PeerStats::new(
+                config.peer_latency_weight(),
+                config.peer_throughput_weight(),
+            ),
         };
 
         // The config may contain the hash of a block that is known to be on the correct chain. We
@@ -155,7 +169,10 @@ where
                     config_header
                 } else {
                     if let Some(stored_header_at_same_height) = effect_builder
-                        .get_block_header_at_height_from_storage(config_header.height(), false)
+                        .get_block_header_at_height_from_storage(
+                            BlockHeight::from(config_header.height()),
+                            false,
+                        )
                         .await
                     {
                         if stored_header_at_same_height != config_header {
@@ -221,10 +238,17 @@ where
             filter_count: AtomicI64::new(0),
             locally_available_block_range_on_start,
             trie_fetch_limit: Semaphore::new(config.max_parallel_trie_fetches()),
+            peer_stats: PeerStats::new(
+                config.peer_latency_weight(),
+                config.peer_throughput_weight(),
+            ),
         };
 
         let trusted_block_header = match effect_builder
-            .get_block_header_at_height_from_storage(highest_available_block_height, true)
+            .get_block_header_at_height_from_storage(
+                BlockHeight::from(highest_available_block_height),
+                true,
+            )
             .await
         {
             Some(block_header) => block_header,
@@ -288,6 +312,12 @@ impl<'a, REv> ChainSyncContext<'a, REv> {
         }
     }
 
+    /// Updates the sync progress metrics with the latest progress.
+    fn update_sync_progress_metrics(&self) {
+        self.metrics
+            .record_sync_progress(&self.progress.sync_progress());
+    }
+
     /// Marks a peer as bad.
     fn mark_bad_peer(&self, peer: NodeId) {
         if self.config.redemption_interval == 0 {
@@ -357,7 +387,8 @@ impl CanUseSyncingNodes for Deploy {}
 impl CanUseSyncingNodes for BlockAndDeploys {}
 impl CanUseSyncingNodes for BlockHeadersBatch {}
 
-/// Gets a list of peers suitable for the fetch operation.
+/// Gets a list of peers suitable for the fetch operation, ordered from the most to the least
+/// preferred.
 async fn get_peers<REv>(include_syncing: bool, ctx: &ChainSyncContext<'_, REv>) -> Vec<NodeId>
 where
     REv: From<NetworkInfoRequest>,
@@ -370,6 +401,22 @@ where
             .await
     };
     ctx.filter_bad_peers(&mut peer_list);
+    ctx.peer_stats.rank(&mut peer_list);
+    peer_list
+}
+
+/// Gets a list of peers able to serve finality signatures to backfill, ordered from the most to
+/// the least preferred.
+async fn get_finality_signature_backfill_peers<REv>(ctx: &ChainSyncContext<'_, REv>) -> Vec<NodeId>
+where
+    REv: From<NetworkInfoRequest>,
+{
+    let mut peer_list = ctx
+        .effect_builder
+        .get_fully_connected_finality_signature_backfill_peers()
+        .await;
+    ctx.filter_bad_peers(&mut peer_list);
+    ctx.peer_stats.rank(&mut peer_list);
     peer_list
 }
 
@@ -464,6 +511,7 @@ where
             id,
             peer
         );
+        let request = ctx.peer_stats.start_request(peer);
         match ctx.effect_builder.fetch::<T>(id, peer).await {
             Ok(fetched_data @ FetchedData::FromStorage { .. }) => {
                 trace!(
@@ -474,9 +522,10 @@ where
                 );
                 return Some(Ok(fetched_data));
             }
-            Ok(fetched_data @ FetchedData::FromPeer { .. }) => {
+            Ok(FetchedData::FromPeer { item, peer }) => {
                 trace!("fetched {:?} with id {:?} from {:?}", T::TAG, id, peer);
-                return Some(Ok(fetched_data));
+                request.succeeded(serialized_size(&*item));
+                return Some(Ok(FetchedData::FromPeer { item, peer }));
             }
             Err(FetcherError::Absent { .. }) => {
                 warn!(
@@ -485,6 +534,7 @@ where
                     ?peer,
                     "chain sync could not fetch; trying next peer",
                 );
+                request.failed();
                 ctx.mark_bad_peer(peer);
             }
             Err(FetcherError::TimedOut { .. }) => {
@@ -494,6 +544,7 @@ where
                     ?peer,
                     "peer timed out",
                 );
+                request.failed();
                 ctx.mark_bad_peer(peer);
             }
             Err(error @ FetcherError::CouldNotConstructGetRequest { .. }) => {
@@ -612,14 +663,21 @@ where
         .get_block_header_from_storage(block_hash, false)
         .await
     {
+        check_against_checkpoints(&stored_block_header, ctx)?;
         return Ok(Box::new(stored_block_header));
     }
 
     match fetch_with_retries::<_, BlockHeader>(ctx, block_hash).await? {
-        FetchedData::FromStorage { item: block_header } => Ok(block_header),
+        FetchedData::FromStorage { item: block_header } => {
+            check_against_checkpoints(&block_header, ctx)?;
+            Ok(block_header)
+        }
         FetchedData::FromPeer {
             item: block_header, ..
         } => {
+            // The header has the requested hash, so if it conflicts with a checkpoint, the chain
+            // we're syncing diverges from the checkpointed one.
+            check_against_checkpoints(&block_header, ctx)?;
             ctx.effect_builder
                 .put_block_header_to_storage(block_header.clone())
                 .await;
@@ -628,6 +686,28 @@ where
     }
 }
 
+/// Returns an error if `block_header` is at the height of one of the chainspec's checkpoints but
+/// has a different hash.
+fn check_against_checkpoints<REv>(
+    block_header: &BlockHeader,
+    ctx: &ChainSyncContext<'_, REv>,
+) -> Result<(), Error> {
+    match ctx.config.conflicting_checkpoint(block_header) {
+        Some(checkpoint) => {
+            error!(
+                %checkpoint,
+                ?block_header,
+                "block header conflicts with chainspec checkpoint"
+            );
+            Err(Error::ConflictsWithCheckpoint {
+                checkpoint,
+                block_header: Box::new(block_header.clone()),
+            })
+        }
+        None => Ok(()),
+    }
+}
+
 /// Fetches and stores a deploy.
 async fn fetch_and_store_deploy<REv>(
     deploy_or_transfer_hash: DeployHash,
@@ -802,6 +882,8 @@ where
         + From<NetworkInfoRequest>
         + From<BlocklistAnnouncement>
         + From<StorageRequest>
+        + From<StateStoreRequest>
+        + From<ControlAnnouncement>
         + Send,
     Error: From<FetcherError<I>>,
 {
@@ -825,20 +907,7 @@ where
         .await?;
         match maybe_item {
             Some(item) => {
-                if item.header().protocol_version() < parent_header.protocol_version() {
-                    return Err(Error::LowerVersionThanParent {
-                        parent: Box::new(parent_header.clone()),
-                        child: Box::new(item.header().clone()),
-                    });
-                }
-
-                if item.header().protocol_version() > ctx.config.protocol_version() {
-                    return Err(Error::RetrievedBlockHeaderFromFutureVersion {
-                        current_version: ctx.config.protocol_version(),
-                        block_header_with_future_version: Box::new(item.header().clone()),
-                    });
-                }
-
+                check_protocol_version(item.header(), parent_header, ctx)?;
                 return Ok(Some(item));
             }
             None => {
@@ -868,10 +937,34 @@ where
     }
 }
 
-/// Fetches the next block or block header from the network by height.
+/// Checks that the protocol version of a fetched block header is neither lower than that of its
+/// parent nor higher than ours.
+fn check_protocol_version<REv>(
+    block_header: &BlockHeader,
+    parent_header: &BlockHeader,
+    ctx: &ChainSyncContext<'_, REv>,
+) -> Result<(), Error> {
+    if block_header.protocol_version() < parent_header.protocol_version() {
+        return Err(Error::LowerVersionThanParent {
+            parent: Box::new(parent_header.clone()),
+            child: Box::new(block_header.clone()),
+        });
+    }
+
+    if block_header.protocol_version() > ctx.config.protocol_version() {
+        return Err(Error::RetrievedBlockHeaderFromFutureVersion {
+            current_version: ctx.config.protocol_version(),
+            block_header_with_future_version: Box::new(block_header.clone()),
+        });
+    }
+
+    Ok(())
+}
+
+/// Fetches the next block or block header from the network by height, trying `peers` in order.
 /// Returns `Ok(None)` if there are no more peers left.
 async fn try_fetch_block_or_block_header_by_height<REv, I>(
-    mut peers: Vec<NodeId>,
+    peers: Vec<NodeId>,
     ctx: &ChainSyncContext<'_, REv>,
     height: u64,
     parent_header: &BlockHeader,
@@ -879,14 +972,21 @@ async fn try_fetch_block_or_block_header_by_height<REv, I>(
 ) -> Result<Option<Box<I>>, Error>
 where
     I: BlockOrHeaderWithMetadata,
-    REv: From<FetcherRequest<I>> + From<BlocklistAnnouncement> + From<StorageRequest> + Send,
+    REv: From<FetcherRequest<I>>
+        + From<BlocklistAnnouncement>
+        + From<StorageRequest>
+        + From<StateStoreRequest>
+        + From<ControlAnnouncement>
+        + Send,
     Error: From<FetcherError<I>>,
 {
+    let mut peers = peers.into_iter();
     Ok(loop {
-        let peer = match peers.pop() {
+        let peer = match peers.next() {
             Some(peer) => peer,
             None => return Ok(None),
         };
+        let request = ctx.peer_stats.start_request(peer);
         match ctx.effect_builder.fetch::<I>(height, peer).await {
             Ok(FetchedData::FromStorage { item }) => {
                 if *item.header().parent_hash() != parent_header.hash() {
@@ -895,9 +995,11 @@ where
                         child: Box::new(item.header().clone()),
                     });
                 }
+                check_against_checkpoints(item.header(), ctx)?;
                 break Some(item);
             }
             Ok(FetchedData::FromPeer { item, .. }) => {
+                request.succeeded(serialized_size(&*item));
                 if *item.header().parent_hash() != parent_header.hash() {
                     warn!(
                         ?peer,
@@ -909,6 +1011,17 @@ where
                     continue;
                 }
 
+                if let Some(checkpoint) = ctx.config.conflicting_checkpoint(item.header()) {
+                    warn!(
+                        ?peer,
+                        %checkpoint,
+                        fetched_header = ?item.header(),
+                        "received block conflicting with chainspec checkpoint from peer",
+                    );
+                    ctx.effect_builder.announce_disconnect_from_peer(peer).await;
+                    continue;
+                }
+
                 if key_block_info.era_id() != item.header().era_id() {
                     error!(
                         key_block_info_era_id = key_block_info.era_id().value(),
@@ -955,6 +1068,12 @@ where
                     continue;
                 }
 
+                // The block is finalized, so it can't be stored if we already have a different
+                // finalized block at its height.
+                if detect_fork(item.header(), item.block_signatures(), peer, ctx).await {
+                    continue;
+                }
+
                 // Store the block or header itself, and the finality signatures.
                 item.store_block_or_header(*ctx.effect_builder).await;
                 let sigs = item.block_signatures().clone();
@@ -965,11 +1084,13 @@ where
             Err(FetcherError::Absent { .. }) => {
                 warn!(height, tag = ?I::TAG, ?peer, "block by height absent from peer");
                 // If the peer we requested doesn't have the item, continue with the next peer
+                request.failed();
                 continue;
             }
             Err(FetcherError::TimedOut { .. }) => {
                 warn!(height, tag = ?I::TAG, ?peer, "peer timed out");
                 // Peer timed out fetching the item, continue with the next peer
+                request.failed();
                 continue;
             }
             Err(error) => return Err(error.into()),
@@ -977,6 +1098,53 @@ where
     })
 }
 
+/// Checks whether `block_header`, received from `peer` with sufficient finality signatures,
+/// conflicts with the block stored at the same height.
+///
+/// If it does, the linear chain has forked past finalization: the evidence is persisted, the fork
+/// is announced and `true` is returned.
+async fn detect_fork<REv>(
+    block_header: &BlockHeader,
+    block_signatures: &BlockSignatures,
+    peer: NodeId,
+    ctx: &ChainSyncContext<'_, REv>,
+) -> bool
+where
+    REv: From<StorageRequest> + From<StateStoreRequest> + From<ControlAnnouncement>,
+{
+    let local = match ctx
+        .effect_builder
+        .get_block_header_with_metadata_from_storage_by_height(
+            BlockHeight::from(block_header.height()),
+            false,
+        )
+        .await
+    {
+        Some(local) if local.block_header.hash() != block_header.hash() => local,
+        Some(_) | None => return false,
+    };
+
+    let evidence = ForkEvidence {
+        local,
+        conflicting: BlockHeaderWithMetadata {
+            block_header: block_header.clone(),
+            block_signatures: block_signatures.clone(),
+        },
+        peer,
+    };
+    error!(%evidence, "received finalized block conflicting with stored finalized block");
+    ctx.metrics.chain_sync_forks_detected.inc();
+    if !ctx
+        .effect_builder
+        .save_state(evidence.state_store_key(), evidence.clone())
+        .await
+    {
+        error!(%evidence, "failed to persist fork evidence");
+    }
+    ctx.effect_builder.announce_fork_detected(evidence).await;
+    true
+}
+
 /// Prepares a list of peers applicable for the next fetch operation.
 async fn prepare_peers_applicable_for_block_fetch<REv>(
     ctx: &ChainSyncContext<'_, REv>,
@@ -1098,6 +1266,8 @@ where
                 err
             })?;
         trace!(?child_jobs, trie_key = %job.inner(), "downloaded trie node");
+        ctx.progress.record_trie_fetched();
+        ctx.update_sync_progress_metrics();
         if abort.load(Ordering::Relaxed) {
             return Ok(()); // Another task failed and sent an error.
         }
@@ -1207,6 +1377,8 @@ where
         + From<FetcherRequest<Block>>
         + From<FetcherRequest<BlockHeader>>
         + From<FetcherRequest<BlockHeaderWithMetadata>>
+        + From<StateStoreRequest>
+        + From<ControlAnnouncement>
         + Send,
 {
     let _metric = ScopeTimer::new(&ctx.metrics.chain_sync_fast_sync_total_duration_seconds);
@@ -1284,6 +1456,8 @@ where
         + From<NetworkInfoRequest>
         + From<BlocklistAnnouncement>
         + From<StorageRequest>
+        + From<StateStoreRequest>
+        + From<ControlAnnouncement>
         + Send,
 {
     let _metric = ScopeTimer::new(&ctx.metrics.chain_sync_fetch_block_headers_duration_seconds);
@@ -1455,6 +1629,7 @@ where
     loop {
         ctx.progress
             .set_fetching_headers_back_to_genesis(lowest_trusted_block_header.height());
+        ctx.update_sync_progress_metrics();
         match fetch_block_headers_batch(&lowest_trusted_block_header, ctx).await {
             Ok(new_lowest) => {
                 if new_lowest.height() % 1_000 == 0 {
@@ -1505,6 +1680,26 @@ where
             FetchedData::FromPeer { item, peer } => {
                 match BlockHeadersBatch::validate(&*item, &batch_id, lowest_trusted_block_header) {
                     Ok(new_lowest) => {
+                        // The batch descends from our lowest trusted block header, so if any of
+                        // its headers conflicts with a checkpoint, the chain we're syncing
+                        // diverges from the checkpointed one.
+                        if let Some((checkpoint, block_header)) =
+                            item.inner().iter().find_map(|block_header| {
+                                ctx.config
+                                    .conflicting_checkpoint(block_header)
+                                    .map(|checkpoint| (checkpoint, block_header))
+                            })
+                        {
+                            error!(
+                                %checkpoint,
+                                ?block_header,
+                                "block header conflicts with chainspec checkpoint"
+                            );
+                            return Err(FetchBlockHeadersBatchError::ConflictsWithCheckpoint {
+                                checkpoint,
+                                block_header: Box::new(block_header.clone()),
+                            });
+                        }
                         info!(?batch_id, ?peer, "received valid batch of headers");
                         ctx.effect_builder
                             .put_block_headers_batch_to_storage(item.into_inner())
@@ -1556,6 +1751,9 @@ where
     // choose to "re-sync" from Genesis, even if it means we will go through thousands of blocks
     // that we already have. Hopefully, local checks will be fast enough.
     let latest_height_requested: Arc<AtomicU64> = Arc::new(AtomicU64::new(0));
+    ctx.progress
+        .start_syncing_forward_from_genesis(ctx.trusted_block_header().height());
+    ctx.update_sync_progress_metrics();
 
     let mut workers: FuturesUnordered<_> = (0..ctx.config.max_parallel_block_fetches())
         .map(|worker_id| fetch_block_worker(worker_id, latest_height_requested.clone(), ctx))
@@ -1595,7 +1793,7 @@ where
 
         let block_header = ctx
             .effect_builder
-            .get_block_header_at_height_from_storage(block_height, false)
+            .get_block_header_at_height_from_storage(BlockHeight::from(block_height), false)
             .await
             .ok_or(Error::NoSuchBlockHeight(block_height))?;
 
@@ -1621,7 +1819,9 @@ where
             error!(?error, ?block_hash, "failed to download trie");
             return Err(error);
         }
-        ctx.effect_builder.mark_block_completed(block_height).await;
+        ctx.effect_builder
+            .mark_block_completed(BlockHeight::from(block_height))
+            .await;
         ctx.metrics.chain_sync_blocks_synced.inc();
 
         ctx.progress
@@ -1629,6 +1829,7 @@ where
         fetch_and_store_finality_signatures_by_block_header(block_header, ctx).await?;
         ctx.progress
             .finish_syncing_block_for_sync_forward(block_height);
+        ctx.update_sync_progress_metrics();
     }
 }
 
@@ -1687,6 +1888,21 @@ impl BlockSignaturesCollector {
         )
     }
 
+    fn check_if_sufficient_for_backfill(
+        &self,
+        validator_weights: &BTreeMap<PublicKey, U512>,
+        backfill_threshold: Ratio<u64>,
+    ) -> Result<(), BlockSignatureError> {
+        are_signatures_sufficient_for_sync_to_genesis(
+            linear_chain::check_sufficient_block_signatures_with_quorum_formula(
+                validator_weights,
+                backfill_threshold,
+                self.0.as_ref(),
+                std::convert::identity,
+            ),
+        )
+    }
+
     fn into_inner(self) -> Option<BlockSignatures> {
         self.0
     }
@@ -1750,20 +1966,18 @@ impl BlockSignaturesCollector {
 
 /// Fetches the finality signatures from the given peer. In case of timeout, it'll retry up to
 /// `retries` times. Other errors interrupt the process immediately.
-async fn fetch_finality_signatures_with_retry<REv>(
+async fn fetch_finality_signatures_with_retry<REv, I>(
     block_hash: BlockHash,
     peer: NodeId,
     retries: usize,
     ctx: &ChainSyncContext<'_, REv>,
-) -> Result<FetchedData<BlockSignatures>, FetcherError<BlockSignatures>>
+) -> Result<FetchedData<I>, FetcherError<I>>
 where
-    REv: From<FetcherRequest<BlockSignatures>>,
+    I: Item<Id = BlockHash> + 'static,
+    REv: From<FetcherRequest<I>>,
 {
     for _ in 0..retries {
-        let maybe_signatures = ctx
-            .effect_builder
-            .fetch::<BlockSignatures>(block_hash, peer)
-            .await;
+        let maybe_signatures = ctx.effect_builder.fetch::<I>(block_hash, peer).await;
         match maybe_signatures {
             Ok(result) => return Ok(result),
             Err(FetcherError::TimedOut { .. }) => continue,
@@ -1797,7 +2011,7 @@ where
     let block_header_hash = block_header.hash();
 
     for peer in peer_list {
-        let fetched_signatures = fetch_finality_signatures_with_retry(
+        let fetched_signatures = fetch_finality_signatures_with_retry::<_, BlockSignatures>(
             block_header_hash,
             peer,
             FINALITY_SIGNATURE_FETCH_RETRY_COUNT,
@@ -1898,6 +2112,166 @@ async fn finalize_finality_signature_fetch<REv>(
     }
 }
 
+/// Runs the finality signature backfill task.
+///
+/// Repeatedly passes over the blocks in the available block range, fetching further finality
+/// signatures from peers for each block whose stored signatures' total weight doesn't exceed the
+/// configured threshold, then pausing for the configured interval.  Signatures are only ever added,
+/// so blocks found to exceed the threshold are not checked again.
+pub(super) async fn run_finality_signature_backfill_task<REv>(
+    effect_builder: EffectBuilder<REv>,
+    config: Config,
+    metrics: Metrics,
+) -> Result<(), Error>
+where
+    REv: From<StorageRequest>
+        + From<NetworkInfoRequest>
+        + From<FetcherRequest<BackfillBlockSignatures>>
+        + From<BlocklistAnnouncement>
+        + From<ContractRuntimeRequest>
+        + Send,
+{
+    info!("starting finality signature backfill");
+    let progress = ProgressHolder::new_sync_to_genesis();
+    let ctx =
+        ChainSyncContext::new_for_sync_to_genesis(&effect_builder, &config, &metrics, &progress)
+            .await?;
+
+    // All blocks below this height are known to have sufficient signatures.
+    let mut lowest_height_to_check = 0;
+    loop {
+        let available_block_range = effect_builder
+            .get_available_block_range_from_storage()
+            .await;
+        let mut all_sufficient = true;
+        for height in cmp::max(lowest_height_to_check, available_block_range.low())
+            ..=available_block_range.high()
+        {
+            let sufficient = backfill_finality_signatures(height, &ctx).await?;
+            all_sufficient &= sufficient;
+            if all_sufficient {
+                lowest_height_to_check = height + 1;
+            }
+        }
+        debug!(
+            lowest_height_to_check,
+            "finished pass of finality signature backfill"
+        );
+        tokio::time::sleep(config.finality_signature_backfill_interval()).await;
+    }
+}
+
+/// Fetches finality signatures from peers for the block at `height` if the total weight of its
+/// stored signatures doesn't exceed the backfill threshold, and stores any valid signatures
+/// received.
+///
+/// Returns whether the block's signatures exceed the threshold on completion.
+async fn backfill_finality_signatures<REv>(
+    height: u64,
+    ctx: &ChainSyncContext<'_, REv>,
+) -> Result<bool, Error>
+where
+    REv: From<StorageRequest>
+        + From<NetworkInfoRequest>
+        + From<FetcherRequest<BackfillBlockSignatures>>
+        + From<BlocklistAnnouncement>
+        + From<ContractRuntimeRequest>
+        + Send,
+{
+    let BlockHeaderWithMetadata {
+        block_header,
+        block_signatures,
+    } = match ctx
+        .effect_builder
+        .get_block_header_with_metadata_from_storage_by_height(BlockHeight::from(height), true)
+        .await
+    {
+        Some(block_header_with_metadata) => block_header_with_metadata,
+        None => {
+            debug!(
+                height,
+                "no block header to backfill finality signatures for"
+            );
+            return Ok(false);
+        }
+    };
+
+    let (_, validator_weights) =
+        linear_chain::era_validator_weights_for_block(&block_header, *ctx.effect_builder).await?;
+    let backfill_threshold = ctx.config.finality_signature_backfill_threshold();
+    let mut sig_collector = BlockSignaturesCollector::new();
+    if !block_signatures.proofs.is_empty() {
+        sig_collector.add(block_signatures);
+    }
+    if sig_collector
+        .check_if_sufficient_for_backfill(&validator_weights, backfill_threshold)
+        .is_ok()
+    {
+        return Ok(true);
+    }
+
+    let start = Timestamp::now();
+    let block_hash = block_header.hash();
+    let mut sufficient = false;
+    for peer in get_finality_signature_backfill_peers(ctx).await {
+        let signatures = match fetch_finality_signatures_with_retry::<_, BackfillBlockSignatures>(
+            block_hash,
+            peer,
+            FINALITY_SIGNATURE_FETCH_RETRY_COUNT,
+            ctx,
+        )
+        .await
+        {
+            Ok(FetchedData::FromPeer { item, .. }) => item.into_inner(),
+            Ok(FetchedData::FromStorage { .. }) => continue,
+            Err(error) => {
+                trace!(
+                    ?block_hash,
+                    ?peer,
+                    ?error,
+                    "error fetching finality signatures to backfill"
+                );
+                continue;
+            }
+        };
+        if signatures.proofs.is_empty() {
+            continue;
+        }
+
+        if let Err(error) = linear_chain::validate_block_signatures(&signatures, &validator_weights)
+        {
+            warn!(
+                ?peer,
+                ?error,
+                height,
+                "peer sent invalid finality signatures to backfill, banning peer"
+            );
+            ctx.effect_builder.announce_disconnect_from_peer(peer).await;
+            continue;
+        }
+        sig_collector.add(signatures);
+
+        if sig_collector
+            .check_if_sufficient_for_backfill(&validator_weights, backfill_threshold)
+            .is_ok()
+        {
+            sufficient = true;
+            break;
+        }
+    }
+
+    if sufficient {
+        info!(height, "backfilled finality signatures");
+    } else {
+        debug!(
+            height,
+            "couldn't backfill sufficient finality signatures from peers"
+        );
+    }
+    finalize_finality_signature_fetch(ctx, start, sig_collector).await;
+    Ok(sufficient)
+}
+
 /// Runs the initial chain synchronization task ("fast sync").
 pub(super) async fn run_fast_sync_task<REv>(
     effect_builder: EffectBuilder<REv>,
@@ -1919,6 +2293,7 @@ where
         + From<FetcherRequest<TrieOrChunk>>
         + From<BlocklistAnnouncement>
         + From<MarkBlockCompletedRequest>
+        + From<StateStoreRequest>
         + From<ControlAnnouncement>
         + Send,
 {
@@ -1959,7 +2334,7 @@ where
     }
 
     ctx.effect_builder
-        .mark_block_completed(highest_block_header.height())
+        .mark_block_completed(BlockHeight::from(highest_block_header.height()))
         .await;
 
     info!(
@@ -1973,6 +2348,92 @@ where
     Ok(highest_block_header)
 }
 
+/// Runs the light sync task, which syncs only block headers and their finality signatures.
+///
+/// The headers are fetched and verified from the trusted block header up to the current era, as
+/// during fast sync, but no deploys or global state are fetched and no blocks are executed.  The
+/// task then keeps following the chain, fetching each newly finalized block header as it becomes
+/// available.
+///
+/// Only returns on error, including on receiving a block header from a future protocol version.
+pub(super) async fn run_light_sync_task<REv>(
+    effect_builder: EffectBuilder<REv>,
+    config: Config,
+    metrics: Metrics,
+    progress: ProgressHolder,
+) -> Result<(), Error>
+where
+    REv: From<StorageRequest>
+        + From<NetworkInfoRequest>
+        + From<FetcherRequest<BlockHeader>>
+        + From<FetcherRequest<BlockHeaderWithMetadata>>
+        + From<BlocklistAnnouncement>
+        + From<StateStoreRequest>
+        + From<ControlAnnouncement>
+        + Send,
+{
+    info!("light syncing chain");
+    progress.start();
+
+    let ctx =
+        ChainSyncContext::new_for_fast_sync(&effect_builder, &config, &metrics, &progress).await?;
+    verify_trusted_block_header(&ctx)?;
+
+    let trusted_key_block_info = get_trusted_key_block_info(&ctx).await?;
+    let (mut highest_synced_block_header, mut highest_synced_key_block_info) =
+        fetch_block_headers_up_to_current_era(&trusted_key_block_info, &ctx).await?;
+    progress.finish();
+
+    info!(
+        era_id = ?highest_synced_block_header.era_id(),
+        height = highest_synced_block_header.height(),
+        "finished initial light sync, following the chain"
+    );
+
+    loop {
+        let height = highest_synced_block_header
+            .height()
+            .checked_add(1)
+            .ok_or_else(|| Error::HeightOverflow {
+                parent: Box::new(highest_synced_block_header.clone()),
+            })?;
+        let peers = prepare_peers_applicable_for_block_fetch(&ctx).await;
+        let maybe_fetched_block: Option<Box<BlockHeaderWithMetadata>> =
+            try_fetch_block_or_block_header_by_height(
+                peers,
+                &ctx,
+                height,
+                &highest_synced_block_header,
+                &highest_synced_key_block_info,
+            )
+            .await?;
+
+        match maybe_fetched_block {
+            Some(block_header_with_metadata) => {
+                check_protocol_version(
+                    &block_header_with_metadata.block_header,
+                    &highest_synced_block_header,
+                    &ctx,
+                )?;
+                highest_synced_block_header = block_header_with_metadata.block_header;
+                debug!(
+                    height = highest_synced_block_header.height(),
+                    "light sync stored block header"
+                );
+                if let Some(key_block_info) =
+                    KeyBlockInfo::maybe_from_block_header(&highest_synced_block_header)
+                {
+                    highest_synced_key_block_info = key_block_info;
+                }
+            }
+            None => {
+                // No peer has the next block yet: wait for it to be finalized.
+                tokio::time::sleep(ctx.config.min_round_length().into()).await;
+            }
+        }
+    }
+}
+
 async fn fetch_and_store_initial_trusted_block_header<REv>(
     ctx: &ChainSyncContext<'_, REv>,
     metrics: &Metrics,
@@ -2048,8 +2509,96 @@ where
         .await?)
 }
 
+/// A block fetched during fast sync along with its deploys and transfers, ready to be executed.
+struct BlockToExecute {
+    block: Block,
+    deploys: Vec<Deploy>,
+    transfers: Vec<Deploy>,
+}
+
+/// Fetches the blocks following `highest_synced_block_header` along with their deploys, sending
+/// them on `blocks_to_execute`, until we can get no higher block from any peer, or the block
+/// fetched is in the current era.
+///
+/// Each block is validated against its fetched parent rather than its executed parent, allowing
+/// fetching to run ahead of execution by as many blocks as `blocks_to_execute` can buffer.
+async fn fetch_blocks_to_execute<REv>(
+    highest_synced_block_header: BlockHeader,
+    highest_synced_key_block_info: KeyBlockInfo,
+    blocks_to_execute: mpsc::Sender<BlockToExecute>,
+    ctx: &ChainSyncContext<'_, REv>,
+) -> Result<(), Error>
+where
+    REv: From<FetcherRequest<BlockWithMetadata>>
+        + From<FetcherRequest<Deploy>>
+        + From<NetworkInfoRequest>
+        + From<BlocklistAnnouncement>
+        + From<StorageRequest>
+        + From<StateStoreRequest>
+        + From<ControlAnnouncement>
+        + Send,
+{
+    let mut parent_header = highest_synced_block_header;
+    let mut key_block_info = highest_synced_key_block_info;
+    loop {
+        ctx.progress
+            .start_fetching_block_and_deploys_to_execute(parent_header.height().saturating_add(1));
+        let result =
+            fetch_and_store_next::<_, BlockWithMetadata>(&parent_header, &key_block_info, ctx)
+                .await?;
+        let block = match result {
+            None => {
+                let in_current_era = is_current_era(&parent_header, &key_block_info, ctx.config);
+                info!(
+                    era = parent_header.era_id().value(),
+                    in_current_era,
+                    height = parent_header.height(),
+                    timestamp = %parent_header.timestamp(),
+                    "couldn't download a higher block; finishing syncing",
+                );
+                return Ok(());
+            }
+            Some(block_with_metadata) => block_with_metadata.block,
+        };
+
+        let deploys = fetch_and_store_deploys(block.deploy_hashes().iter(), ctx).await?;
+
+        let transfers = fetch_and_store_deploys(block.transfer_hashes().iter(), ctx).await?;
+
+        parent_header = block.header().clone();
+        if let Some(new_key_block_info) = KeyBlockInfo::maybe_from_block_header(&parent_header) {
+            key_block_info = new_key_block_info;
+        }
+
+        let block_to_execute = BlockToExecute {
+            block,
+            deploys,
+            transfers,
+        };
+        if blocks_to_execute.send(block_to_execute).await.is_err() {
+            // Execution has stopped, so there is no point in fetching further blocks.
+            return Ok(());
+        }
+
+        // If we managed to sync up to the current era, stop - we'll have to sync the consensus
+        // protocol state, anyway.
+        if is_current_era(&parent_header, &key_block_info, ctx.config) {
+            info!(
+                era = parent_header.era_id().value(),
+                height = parent_header.height(),
+                timestamp = %parent_header.timestamp(),
+                "synchronized up to the current era; finishing syncing",
+            );
+            return Ok(());
+        }
+    }
+}
+
 /// Executes forwards from the block after `highest_synced_block_header` until we can get no higher
 /// block from any peer, or the block we executed is in the current era.
+///
+/// Blocks are fetched concurrently with the execution of their predecessors, up to
+/// `max_blocks_to_prefetch` blocks ahead of the block being executed.
 async fn fetch_and_execute_blocks<REv>(
     highest_synced_block_header: &BlockHeader,
     highest_synced_key_block_info: KeyBlockInfo,
@@ -2064,13 +2613,13 @@ where
         + From<BlocklistAnnouncement>
         + From<StorageRequest>
         + From<MarkBlockCompletedRequest>
+        + From<StateStoreRequest>
         + From<ControlAnnouncement>
         + Send,
 {
     let _metric = ScopeTimer::new(&ctx.metrics.chain_sync_execute_blocks_duration_seconds);
 
     // Execute blocks to get to current.
-    let mut execution_pre_state = ExecutionPreState::from_block_header(highest_synced_block_header);
     info!(
         era_id = ?highest_synced_block_header.era_id(),
         height = highest_synced_block_header.height(),
@@ -2079,128 +2628,102 @@ where
         "fetching and executing blocks to synchronize to current",
     );
 
-    let mut highest_synced_block_header = highest_synced_block_header.clone();
-    let mut key_block_info = highest_synced_key_block_info;
-    loop {
-        ctx.progress.start_fetching_block_and_deploys_to_execute(
-            highest_synced_block_header.height().saturating_add(1),
-        );
-        let result = fetch_and_store_next::<_, BlockWithMetadata>(
-            &highest_synced_block_header,
-            &key_block_info,
-            ctx,
-        )
-        .await?;
-        let block = match result {
-            None => {
-                let in_current_era =
-                    is_current_era(&highest_synced_block_header, &key_block_info, ctx.config);
-                info!(
-                    era = highest_synced_block_header.era_id().value(),
-                    in_current_era,
-                    height = highest_synced_block_header.height(),
-                    timestamp = %highest_synced_block_header.timestamp(),
-                    "couldn't download a higher block; finishing syncing",
-                );
-                break;
-            }
-            Some(block_with_metadata) => block_with_metadata.block,
-        };
-
-        let mut deploys = fetch_and_store_deploys(block.deploy_hashes().iter(), ctx).await?;
-
-        let mut transfers = fetch_and_store_deploys(block.transfer_hashes().iter(), ctx).await?;
-
-        info!(
-            era_id = ?block.header().era_id(),
-            height = block.height(),
-            now = %Timestamp::now(),
-            block_timestamp = %block.timestamp(),
-            "executing block",
-        );
-        ctx.progress.start_executing_block(block.height());
-        let block_and_execution_effects = ctx
-            .effect_builder
-            .execute_finalized_block(
-                block.protocol_version(),
-                execution_pre_state.clone(),
-                FinalizedBlock::from(block.clone()),
-                deploys.clone(),
-                transfers.clone(),
-            )
-            .await?;
-
-        let mut blocks_match = block == *block_and_execution_effects.block();
-
-        let mut attempts = 0;
-        while !blocks_match {
-            // Could be wrong approvals - fetch new sets of approvals from a single peer and retry.
-            for peer in get_peers(true, ctx).await {
-                attempts += 1;
-                warn!(
-                    fetched_block=%block,
-                    executed_block=%block_and_execution_effects.block(),
-                    attempts,
-                    "retrying execution due to deploy approvals mismatch"
-                );
-                ctx.progress.retry_executing_block(block.height(), attempts);
-                let block_and_execution_effects = retry_execution_with_approvals_from_peer(
-                    &mut deploys,
-                    &mut transfers,
-                    peer,
-                    &block,
-                    &execution_pre_state,
-                    ctx,
+    let (sender, mut receiver) = mpsc::channel(ctx.config.max_blocks_to_prefetch());
+    let fetch = fetch_blocks_to_execute(
+        highest_synced_block_header.clone(),
+        highest_synced_key_block_info,
+        sender,
+        ctx,
+    );
+    let execute = async {
+        let mut highest_synced_block_header = highest_synced_block_header.clone();
+        let mut execution_pre_state =
+            ExecutionPreState::from_block_header(&highest_synced_block_header);
+        while let Some(BlockToExecute {
+            block,
+            mut deploys,
+            mut transfers,
+        }) = receiver.recv().await
+        {
+            info!(
+                era_id = ?block.header().era_id(),
+                height = block.height(),
+                now = %Timestamp::now(),
+                block_timestamp = %block.timestamp(),
+                "executing block",
+            );
+            ctx.progress.start_executing_block(block.height());
+            let block_and_execution_effects = ctx
+                .effect_builder
+                .execute_finalized_block(
+                    block.protocol_version(),
+                    execution_pre_state.clone(),
+                    FinalizedBlock::from(block.clone()),
+                    deploys.clone(),
+                    transfers.clone(),
                 )
                 .await?;
-                debug!(block_hash=%block.hash(), "finish - re-executing finalized block");
-                blocks_match = block == *block_and_execution_effects.block();
-                if blocks_match {
-                    break;
+
+            let mut blocks_match = block == *block_and_execution_effects.block();
+
+            let mut attempts = 0;
+            while !blocks_match {
+                // Could be wrong approvals - fetch new sets of approvals from a single peer and
+                // retry.
+                for peer in get_peers(true, ctx).await {
+                    attempts += 1;
+                    warn!(
+                        fetched_block=%block,
+                        executed_block=%block_and_execution_effects.block(),
+                        attempts,
+                        "retrying execution due to deploy approvals mismatch"
+                    );
+                    ctx.progress.retry_executing_block(block.height(), attempts);
+                    let block_and_execution_effects = retry_execution_with_approvals_from_peer(
+                        &mut deploys,
+                        &mut transfers,
+                        peer,
+                        &block,
+                        &execution_pre_state,
+                        ctx,
+                    )
+                    .await?;
+                    debug!(block_hash=%block.hash(), "finish - re-executing finalized block");
+                    blocks_match = block == *block_and_execution_effects.block();
+                    if blocks_match {
+                        break;
+                    }
+                    warn!(
+                        %peer,
+                        "block executed with approvals from this peer doesn't match the received \
+                        block; blocking peer"
+                    );
+                    ctx.effect_builder.announce_disconnect_from_peer(peer).await;
                 }
-                warn!(
-                    %peer,
-                    "block executed with approvals from this peer doesn't match the received \
-                    block; blocking peer"
-                );
-                ctx.effect_builder.announce_disconnect_from_peer(peer).await;
             }
-        }
 
-        // Matching now - store new approval sets for the deploys.
-        for deploy in deploys.into_iter().chain(transfers.into_iter()) {
+            // Matching now - store new approval sets for the deploys.
+            for deploy in deploys.into_iter().chain(transfers.into_iter()) {
+                ctx.effect_builder
+                    .store_finalized_approvals(
+                        *deploy.id(),
+                        FinalizedApprovals::new(deploy.approvals().clone()),
+                    )
+                    .await;
+            }
             ctx.effect_builder
-                .store_finalized_approvals(
-                    *deploy.id(),
-                    FinalizedApprovals::new(deploy.approvals().clone()),
-                )
+                .mark_block_completed(BlockHeight::from(
+                    block_and_execution_effects.block().height(),
+                ))
                 .await;
-        }
-        ctx.effect_builder
-            .mark_block_completed(block_and_execution_effects.block().height())
-            .await;
-
-        highest_synced_block_header = block.take_header();
-        execution_pre_state = ExecutionPreState::from_block_header(&highest_synced_block_header);
-
-        if let Some(new_key_block_info) =
-            KeyBlockInfo::maybe_from_block_header(&highest_synced_block_header)
-        {
-            key_block_info = new_key_block_info;
-        }
 
-        // If we managed to sync up to the current era, stop - we'll have to sync the consensus
-        // protocol state, anyway.
-        if is_current_era(&highest_synced_block_header, &key_block_info, ctx.config) {
-            info!(
-                era = highest_synced_block_header.era_id().value(),
-                height = highest_synced_block_header.height(),
-                timestamp = %highest_synced_block_header.timestamp(),
-                "synchronized up to the current era; finishing syncing",
-            );
-            break;
+            highest_synced_block_header = block.take_header();
+            execution_pre_state =
+                ExecutionPreState::from_block_header(&highest_synced_block_header);
         }
-    }
+        Ok::<_, Error>(highest_synced_block_header)
+    };
+    let ((), highest_synced_block_header) = future::try_join(fetch, execute).await?;
     Ok(highest_synced_block_header)
 }
 
@@ -0,0 +1,210 @@
+//! Tracking of how quickly peers serve chain sync requests, used to prefer fast peers.
+
+use std::{cmp::Ordering, collections::HashMap, sync::Mutex, time::Duration};
+
+use quanta::Instant;
+
+use crate::types::NodeId;
+
+/// The weight given to the newest sample in the moving averages of a peer's performance.
+const SMOOTHING_FACTOR: f64 = 0.2;
+
+/// The observed performance of a single peer.
+#[derive(Debug, Default)]
+struct PeerPerformance {
+    /// Moving average of the response time in seconds, or `None` if no request has completed.
+    latency: Option<f64>,
+    /// Moving average of the throughput in bytes per second, or `None` if no request has
+    /// succeeded.
+    throughput: Option<f64>,
+    /// Number of requests to the peer currently in flight.
+    in_flight: usize,
+}
+
+/// Returns the moving average updated with `sample`.
+fn update_average(average: Option<f64>, sample: f64) -> f64 {
+    match average {
+        Some(average) => average + SMOOTHING_FACTOR * (sample - average),
+        None => sample,
+    }
+}
+
+/// The performance of the peers serving chain sync requests.
+///
+/// Peers are ranked by the weighted sum of their response time relative to the fastest peer's and
+/// the fastest peer's throughput relative to theirs, multiplied by one more than the number of
+/// their requests in flight, so that requests are spread away from slow or busy peers.  Peers
+/// without any completed requests are ranked as if they were the fastest, so that each gets tried.
+#[derive(Debug)]
+pub(super) struct PeerStats {
+    latency_weight: f64,
+    throughput_weight: f64,
+    peers: Mutex<HashMap<NodeId, PeerPerformance>>,
+}
+
+impl PeerStats {
+    /// Creates a new instance.  If both weights are zero, peers are left in their given order.
+    pub(super) fn new(latency_weight: u32, throughput_weight: u32) -> Self {
+        PeerStats {
+            latency_weight: latency_weight as f64,
+            throughput_weight: throughput_weight as f64,
+            peers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Sorts `peers` from the most to the least preferred.
+    ///
+    /// Peers which are ranked equally keep their relative order.
+    pub(super) fn rank(&self, peers: &mut [NodeId]) {
+        if self.latency_weight == 0.0 && self.throughput_weight == 0.0 {
+            return;
+        }
+
+        let performances = self.peers.lock().expect("peer stats lock poisoned");
+        let known = || peers.iter().filter_map(|peer| performances.get(peer));
+        let min_latency = known()
+            .filter_map(|performance| performance.latency)
+            .fold(f64::INFINITY, f64::min);
+        let max_throughput = known()
+            .filter_map(|performance| performance.throughput)
+            .fold(0.0, f64::max);
+
+        let cost = |peer: &NodeId| {
+            let performance = performances.get(peer);
+            let latency_ratio = performance
+                .and_then(|performance| performance.latency)
+                .filter(|_| min_latency > 0.0)
+                .map_or(1.0, |latency| latency / min_latency);
+            let throughput_ratio = performance
+                .and_then(|performance| performance.throughput)
+                .map_or(1.0, |throughput| {
+                    max_throughput.max(1.0) / throughput.max(1.0)
+                });
+            let in_flight = performance.map_or(0, |performance| performance.in_flight);
+            (self.latency_weight * latency_ratio + self.throughput_weight * throughput_ratio)
+                * (in_flight + 1) as f64
+        };
+
+        peers.sort_by(|peer1, peer2| {
+            cost(peer1)
+                .partial_cmp(&cost(peer2))
+                .unwrap_or(Ordering::Equal)
+        });
+    }
+
+    /// Records the start of a request to `peer`.
+    ///
+    /// The request is regarded as in flight until the returned value is dropped.
+    pub(super) fn start_request(&self, peer: NodeId) -> PeerRequest<'_> {
+        self.peers
+            .lock()
+            .expect("peer stats lock poisoned")
+            .entry(peer)
+            .or_default()
+            .in_flight += 1;
+        PeerRequest {
+            peer_stats: self,
+            peer,
+            start: Instant::now(),
+        }
+    }
+
+    fn record(&self, peer: NodeId, duration: Duration, maybe_bytes: Option<u64>) {
+        let mut peers = self.peers.lock().expect("peer stats lock poisoned");
+        let performance = peers.entry(peer).or_default();
+        let seconds = duration.as_secs_f64();
+        performance.latency = Some(update_average(performance.latency, seconds));
+        if let Some(bytes) = maybe_bytes {
+            let throughput = bytes as f64 / seconds.max(f64::EPSILON);
+            performance.throughput = Some(update_average(performance.throughput, throughput));
+        }
+    }
+}
+
+/// A request to a peer which is in flight.
+pub(super) struct PeerRequest<'a> {
+    peer_stats: &'a PeerStats,
+    peer: NodeId,
+    start: Instant,
+}
+
+impl<'a> PeerRequest<'a> {
+    /// Records that the peer responded with `bytes` bytes of data.
+    pub(super) fn succeeded(self, bytes: u64) {
+        let duration = Instant::now().duration_since(self.start);
+        self.peer_stats.record(self.peer, duration, Some(bytes));
+    }
+
+    /// Records that the request failed, e.g. by timing out.
+    ///
+    /// Only the time taken is recorded, penalizing the peer's response time.
+    pub(super) fn failed(self) {
+        let duration = Instant::now().duration_since(self.start);
+        self.peer_stats.record(self.peer, duration, None);
+    }
+}
+
+impl<'a> Drop for PeerRequest<'a> {
+    fn drop(&mut self) {
+        if let Some(performance) = self
+            .peer_stats
+            .peers
+            .lock()
+            .expect("peer stats lock poisoned")
+            .get_mut(&self.peer)
+        {
+            performance.in_flight = performance.in_flight.saturating_sub(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(peer_stats: &PeerStats, peer: NodeId, millis: u64, bytes: u64) {
+        let _ = peer_stats.start_request(peer);
+        peer_stats.record(peer, Duration::from_millis(millis), Some(bytes));
+    }
+
+    #[test]
+    fn should_prefer_fast_idle_peers() {
+        let mut rng = crate::new_rng();
+        let fast = NodeId::random(&mut rng);
+        let slow = NodeId::random(&mut rng);
+        let unknown = NodeId::random(&mut rng);
+
+        let peer_stats = PeerStats::new(1, 1);
+        record(&peer_stats, fast, 10, 10_000);
+        record(&peer_stats, slow, 1_000, 10_000);
+
+        let mut peers = vec![slow, unknown, fast];
+        peer_stats.rank(&mut peers);
+        assert_eq!(peers, vec![unknown, fast, slow]);
+
+        // Requests in flight to the fast peer and the unknown one make them less preferred.
+        let _request1 = peer_stats.start_request(fast);
+        let _request2 = peer_stats.start_request(unknown);
+        let mut peers = vec![slow, unknown, fast];
+        peer_stats.rank(&mut peers);
+        assert_eq!(peers, vec![unknown, fast, slow]);
+        let _request3 = peer_stats.start_request(unknown);
+        peer_stats.rank(&mut peers);
+        assert_eq!(peers, vec![fast, unknown, slow]);
+    }
+
+    #[test]
+    fn should_keep_order_if_disabled() {
+        let mut rng = crate::new_rng();
+        let fast = NodeId::random(&mut rng);
+        let slow = NodeId::random(&mut rng);
+
+        let peer_stats = PeerStats::new(0, 0);
+        record(&peer_stats, fast, 10, 10_000);
+        record(&peer_stats, slow, 1_000, 10_000);
+
+        let mut peers = vec![slow, fast];
+        peer_stats.rank(&mut peers);
+        assert_eq!(peers, vec![slow, fast]);
+    }
+}
@@ -1,4 +1,7 @@
-use std::sync::{Arc, Mutex, MutexGuard};
+use std::{
+    convert::TryFrom,
+    sync::{Arc, Mutex, MutexGuard},
+};
 
 use datasize::DataSize;
 use schemars::JsonSchema;
@@ -6,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use tracing::error;
 
 use casper_hashing::Digest;
+use casper_types::{TimeDiff, Timestamp};
 
 use crate::types::BlockHash;
 
@@ -155,9 +159,120 @@ impl Progress {
     }
 }
 
+/// The phase of chain synchronization, summarizing the detailed [`Progress`].
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, DataSize, Debug, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncPhase {
+    /// Syncing has not started yet.
+    NotYetStarted,
+    /// Initial setup is being performed.
+    Starting,
+    /// Fast-sync is fetching the trusted block header and the block headers following it.
+    FetchingTrustedBlockHeader,
+    /// Fast-sync is fetching the global state under the highest synced block.
+    FetchingGlobalState,
+    /// Fast-sync is fetching and executing blocks towards the tip of the chain.
+    ExecutingBlocks,
+    /// Sync-to-genesis is fetching block headers back towards the genesis block.
+    FetchingHeadersBackToGenesis,
+    /// Sync-to-genesis is fetching blocks, deploys and global state forwards from genesis.
+    SyncingBlocksFromGenesis,
+    /// Syncing has finished.
+    Finished,
+}
+
+impl From<&Progress> for SyncPhase {
+    fn from(progress: &Progress) -> Self {
+        match progress {
+            Progress::FastSync(FastSync::NotYetStarted)
+            | Progress::SyncToGenesis(SyncToGenesis::NotYetStarted) => SyncPhase::NotYetStarted,
+            Progress::FastSync(FastSync::Starting)
+            | Progress::SyncToGenesis(SyncToGenesis::Starting) => SyncPhase::Starting,
+            Progress::FastSync(FastSync::FetchingTrustedBlockHeader(_)) => {
+                SyncPhase::FetchingTrustedBlockHeader
+            }
+            Progress::FastSync(FastSync::FetchingTries { .. }) => SyncPhase::FetchingGlobalState,
+            Progress::FastSync(FastSync::FetchingBlockAndDeploysToExecute(_))
+            | Progress::FastSync(FastSync::ExecutingBlock(_))
+            | Progress::FastSync(FastSync::RetryingBlockExecution { .. }) => {
+                SyncPhase::ExecutingBlocks
+            }
+            Progress::SyncToGenesis(SyncToGenesis::FetchingHeadersBackToGenesis { .. }) => {
+                SyncPhase::FetchingHeadersBackToGenesis
+            }
+            Progress::SyncToGenesis(SyncToGenesis::SyncingForwardFromGenesis(_)) => {
+                SyncPhase::SyncingBlocksFromGenesis
+            }
+            Progress::FastSync(FastSync::Finished)
+            | Progress::SyncToGenesis(SyncToGenesis::Finished) => SyncPhase::Finished,
+        }
+    }
+}
+
+/// A summary of the progress of the chain-synchronizer task, allowing operators to tell whether
+/// a node is syncing and roughly how long it will take.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, DataSize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SyncProgress {
+    /// The current phase of chain synchronization.
+    pub phase: SyncPhase,
+    /// The number of blocks (or block headers, while fetching headers back to genesis) still to be
+    /// synced in the current phase, if known.
+    pub blocks_remaining: Option<u64>,
+    /// The number of tries fetched by the current chain-synchronizer task.
+    pub tries_fetched: u64,
+    /// The estimated time until the blocks remaining are synced, extrapolated from the rate at
+    /// which blocks have been synced so far in the current phase, if known.
+    pub estimated_time_remaining: Option<TimeDiff>,
+}
+
+/// The number of blocks remaining to be synced in the current phase.
+#[derive(Clone, Copy, DataSize, Debug)]
+struct BlocksRemaining {
+    /// When counting started.
+    started: Timestamp,
+    /// The number of blocks remaining when counting started.
+    initial: u64,
+    /// The number of blocks remaining now.
+    remaining: u64,
+}
+
+impl BlocksRemaining {
+    fn new(initial: u64) -> Self {
+        BlocksRemaining {
+            started: Timestamp::now(),
+            initial,
+            remaining: initial,
+        }
+    }
+
+    /// Extrapolates the time until no blocks remain from the rate at which blocks have been synced
+    /// since counting started.  Returns `None` if no blocks have been synced yet.
+    fn estimated_time_remaining(&self, now: Timestamp) -> Option<TimeDiff> {
+        let blocks_synced = self.initial.checked_sub(self.remaining)?;
+        if blocks_synced == 0 {
+            return None;
+        }
+        let elapsed_millis = u128::from(now.saturating_diff(self.started).millis());
+        let remaining_millis =
+            elapsed_millis * u128::from(self.remaining) / u128::from(blocks_synced);
+        Some(TimeDiff::from(
+            u64::try_from(remaining_millis).unwrap_or(u64::MAX),
+        ))
+    }
+}
+
+/// Counters from which the [`SyncProgress`] is derived alongside the current [`Progress`].
+#[derive(Default, DataSize, Debug)]
+struct Counters {
+    tries_fetched: u64,
+    blocks_remaining: Option<BlocksRemaining>,
+}
+
 #[derive(Clone, DataSize, Debug)]
 pub(super) struct ProgressHolder {
     inner: Arc<Mutex<Progress>>,
+    counters: Arc<Mutex<Counters>>,
 }
 
 /// This impl is specific to fast-sync progress.
@@ -165,6 +280,7 @@ impl ProgressHolder {
     pub(super) fn new_fast_sync() -> Self {
         ProgressHolder {
             inner: Arc::new(Mutex::new(Progress::FastSync(FastSync::NotYetStarted))),
+            counters: Arc::new(Mutex::new(Counters::default())),
         }
     }
 
@@ -228,16 +344,35 @@ impl ProgressHolder {
             inner: Arc::new(Mutex::new(Progress::SyncToGenesis(
                 SyncToGenesis::NotYetStarted,
             ))),
+            counters: Arc::new(Mutex::new(Counters::default())),
         }
     }
 
     pub(super) fn set_fetching_headers_back_to_genesis(&self, lowest_block_height: u64) {
         let inner = &mut *self.inner.lock().expect("lock poisoned");
+        let counters = &mut *self.counters.lock().expect("lock poisoned");
+        match (&*inner, counters.blocks_remaining.as_mut()) {
+            (
+                Progress::SyncToGenesis(SyncToGenesis::FetchingHeadersBackToGenesis { .. }),
+                Some(blocks_remaining),
+            ) => blocks_remaining.remaining = lowest_block_height,
+            _ => counters.blocks_remaining = Some(BlocksRemaining::new(lowest_block_height)),
+        }
         *inner = Progress::SyncToGenesis(SyncToGenesis::FetchingHeadersBackToGenesis {
             lowest_block_height,
         })
     }
 
+    /// Starts syncing the blocks from genesis up to but excluding `trusted_block_height`.
+    pub(super) fn start_syncing_forward_from_genesis(&self, trusted_block_height: u64) {
+        let inner = &mut *self.inner.lock().expect("lock poisoned");
+        *inner = Progress::SyncToGenesis(SyncToGenesis::SyncingForwardFromGenesis(Vec::new()));
+        self.counters
+            .lock()
+            .expect("lock poisoned")
+            .blocks_remaining = Some(BlocksRemaining::new(trusted_block_height));
+    }
+
     pub(super) fn start_syncing_block_for_sync_forward(&self, block_height: u64) {
         let inner = &mut *self.inner.lock().expect("lock poisoned");
         if !matches!(
@@ -394,6 +529,16 @@ impl ProgressHolder {
                 return;
             };
 
+        if let Some(blocks_remaining) = self
+            .counters
+            .lock()
+            .expect("lock poisoned")
+            .blocks_remaining
+            .as_mut()
+        {
+            blocks_remaining.remaining = blocks_remaining.remaining.saturating_sub(1);
+        }
+
         match tasks.binary_search_by(|task| task.block_height.cmp(&block_height)) {
             Ok(index) => {
                 let existing_progress = tasks.remove(index);
@@ -467,16 +612,40 @@ impl ProgressHolder {
         }
     }
 
+    /// Records that a trie has been fetched and stored.
+    pub(super) fn record_trie_fetched(&self) {
+        self.counters.lock().expect("lock poisoned").tries_fetched += 1;
+    }
+
     pub(super) fn finish(&self) {
         match &mut *self.inner.lock().expect("lock poisoned") {
             Progress::FastSync(progress) => *progress = FastSync::Finished,
             Progress::SyncToGenesis(progress) => *progress = SyncToGenesis::Finished,
         }
+        self.counters
+            .lock()
+            .expect("lock poisoned")
+            .blocks_remaining = None;
     }
 
     pub(super) fn progress(&self) -> Progress {
         self.inner.lock().expect("lock poisoned").clone()
     }
+
+    pub(super) fn sync_progress(&self) -> SyncProgress {
+        let phase = SyncPhase::from(&*self.inner.lock().expect("lock poisoned"));
+        let counters = self.counters.lock().expect("lock poisoned");
+        SyncProgress {
+            phase,
+            blocks_remaining: counters
+                .blocks_remaining
+                .map(|blocks_remaining| blocks_remaining.remaining),
+            tries_fetched: counters.tries_fetched,
+            estimated_time_remaining: counters.blocks_remaining.and_then(|blocks_remaining| {
+                blocks_remaining.estimated_time_remaining(Timestamp::now())
+            }),
+        }
+    }
 }
 
 /// This impl is specific to functionality used for `debug_assert`s.
@@ -527,3 +696,57 @@ impl ProgressHolder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_estimate_time_remaining_from_sync_rate() {
+        let mut blocks_remaining = BlocksRemaining::new(100);
+        let now = blocks_remaining.started + TimeDiff::from(10_000);
+        assert_eq!(blocks_remaining.estimated_time_remaining(now), None);
+
+        blocks_remaining.remaining = 80;
+        assert_eq!(
+            blocks_remaining.estimated_time_remaining(now),
+            Some(TimeDiff::from(40_000))
+        );
+
+        blocks_remaining.remaining = 0;
+        assert_eq!(
+            blocks_remaining.estimated_time_remaining(now),
+            Some(TimeDiff::from(0))
+        );
+    }
+
+    #[test]
+    fn should_count_blocks_remaining_while_syncing_to_genesis() {
+        let progress = ProgressHolder::new_sync_to_genesis();
+        assert_eq!(progress.sync_progress().phase, SyncPhase::NotYetStarted);
+        assert_eq!(progress.sync_progress().blocks_remaining, None);
+
+        progress.set_fetching_headers_back_to_genesis(100);
+        progress.set_fetching_headers_back_to_genesis(50);
+        let sync_progress = progress.sync_progress();
+        assert_eq!(sync_progress.phase, SyncPhase::FetchingHeadersBackToGenesis);
+        assert_eq!(sync_progress.blocks_remaining, Some(50));
+
+        progress.start_syncing_forward_from_genesis(100);
+        progress.start_syncing_block_for_sync_forward(0);
+        progress.start_fetching_tries_for_sync_forward(0, Digest::hash([0]));
+        progress.record_trie_fetched();
+        progress.start_fetching_block_signatures_for_sync_forward(0);
+        progress.finish_syncing_block_for_sync_forward(0);
+        let sync_progress = progress.sync_progress();
+        assert_eq!(sync_progress.phase, SyncPhase::SyncingBlocksFromGenesis);
+        assert_eq!(sync_progress.blocks_remaining, Some(99));
+        assert_eq!(sync_progress.tries_fetched, 1);
+
+        progress.finish();
+        let sync_progress = progress.sync_progress();
+        assert_eq!(sync_progress.phase, SyncPhase::Finished);
+        assert_eq!(sync_progress.blocks_remaining, None);
+        assert_eq!(sync_progress.estimated_time_remaining, None);
+    }
+}
@@ -10,7 +10,10 @@
 // TODO - remove once schemars stops causing warning.
 #![allow(clippy::field_reassign_with_default)]
 
+mod staged_upgrade;
+
 use std::{
+    collections::BTreeMap,
     fmt::{self, Display, Formatter},
     fs,
     path::{Path, PathBuf},
@@ -23,13 +26,17 @@ use datasize::DataSize;
 use derive_more::From;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 use tokio::task;
 use tracing::{debug, error, info, trace, warn};
 
 use casper_execution_engine::core::engine_state::{
     self, ChainspecRegistry, GenesisSuccess, UpgradeConfig, UpgradeSuccess,
 };
-use casper_types::{bytesrepr, crypto::PublicKey, file_utils, EraId, ProtocolVersion, Timestamp};
+use casper_hashing::Digest;
+use casper_types::{
+    bytesrepr, crypto::PublicKey, file_utils, EraId, ProtocolVersion, Timestamp, U512,
+};
 
 #[cfg(test)]
 use crate::utils::RESOURCES_PATH;
@@ -51,12 +58,13 @@ use crate::{
     reactor::ReactorExit,
     types::{
         chainspec::{ChainspecRawBytes, Error, ProtocolConfig, CHAINSPEC_FILENAME},
-        ActivationPoint, BlockHeader, BlockPayload, Chainspec, ChainspecInfo, ExitCode,
-        FinalizedBlock,
+        ActivationPoint, BlockHeader, BlockHeight, BlockPayload, Chainspec, ChainspecInfo,
+        ExitCode, FinalizedBlock, NodeConfig,
     },
-    utils::Loadable,
+    utils::{Loadable, WithDir},
     NodeRng,
 };
+use staged_upgrade::{StagedUpgrade, StagedUpgradeError};
 
 const UPGRADE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
 
@@ -77,7 +85,7 @@ pub(crate) enum Event {
     },
     ExecuteImmediateSwitchBlockResult {
         #[serde(skip_serializing)]
-        result: Result<BlockAndExecutionEffects, BlockExecutionError>,
+        result: Result<BlockAndExecutionEffects, ImmediateSwitchBlockError>,
     },
     #[from]
     Request(ChainspecLoaderRequest),
@@ -85,6 +93,10 @@ pub(crate) enum Event {
     CheckForNextUpgrade,
     /// If the result of checking for an upgrade is successful, it is passed here.
     GotNextUpgrade(NextUpgrade),
+    /// The result of verifying the files staged for the next upgrade, if one is installed.
+    StagedUpgradeVerified(
+        #[serde(skip_serializing)] Option<Result<StagedUpgrade, StagedUpgradeError>>,
+    ),
 }
 
 impl Display for Event {
@@ -121,10 +133,49 @@ impl Display for Event {
             Event::GotNextUpgrade(next_upgrade) => {
                 write!(formatter, "got {}", next_upgrade)
             }
+            Event::StagedUpgradeVerified(result) => {
+                write!(formatter, "staged upgrade verified: {:?}", result)
+            }
         }
     }
 }
 
+/// An error creating the immediate switch block after an upgrade or genesis.
+#[derive(Debug, Error)]
+pub(crate) enum ImmediateSwitchBlockError {
+    /// Executing the block failed.
+    #[error(transparent)]
+    Execution(#[from] BlockExecutionError),
+    /// The validators of the era following the block differ from the ones designated by the
+    /// emergency restart.
+    #[error(
+        "next era validators {actual:?} differ from the emergency restart validators {expected:?}"
+    )]
+    UnexpectedValidators {
+        expected: BTreeMap<PublicKey, U512>,
+        actual: Option<BTreeMap<PublicKey, U512>>,
+    },
+}
+
+/// An error loading the chainspec in force before the most recent upgrade.
+#[derive(Debug, Error)]
+pub(crate) enum PreviousChainspecError {
+    /// The installed chainspec failed to load.
+    #[error("failed to load previous chainspec from {}: {error}", subdir.display())]
+    Load {
+        /// The versioned subdir the chainspec is installed to.
+        subdir: PathBuf,
+        /// The underlying error.
+        error: Error,
+    },
+    /// The installed chainspec is invalid.
+    #[error("previous chainspec in {} is invalid", subdir.display())]
+    Invalid {
+        /// The versioned subdir the chainspec is installed to.
+        subdir: PathBuf,
+    },
+}
+
 /// Information about the next protocol upgrade.
 #[derive(PartialEq, Eq, DataSize, Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct NextUpgrade {
@@ -148,6 +199,10 @@ impl NextUpgrade {
     pub(crate) fn activation_point(&self) -> ActivationPoint {
         self.activation_point
     }
+
+    pub(crate) fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
 }
 
 impl From<ProtocolConfig> for NextUpgrade {
@@ -170,6 +225,31 @@ impl Display for NextUpgrade {
     }
 }
 
+/// The status of an installed upgrade point relative to the protocol version currently running.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UpgradePointStatus {
+    /// The upgrade point is for an earlier protocol version than the current one.
+    Superseded,
+    /// The upgrade point is for the current protocol version.
+    Active,
+    /// The upgrade point is for a later protocol version than the current one.
+    Pending,
+}
+
+/// Information about a protocol upgrade point installed on the node.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UpgradePointInfo {
+    /// The protocol version of the upgrade point.
+    #[schemars(with = "String")]
+    protocol_version: ProtocolVersion,
+    /// The activation point of the upgrade point.
+    activation_point: ActivationPoint,
+    /// The status of the upgrade point relative to the current protocol version.
+    status: UpgradePointStatus,
+}
+
 #[derive(Clone, DataSize, Debug)]
 pub(crate) struct ChainspecLoader {
     chainspec: Arc<Chainspec>,
@@ -180,11 +260,22 @@ pub(crate) struct ChainspecLoader {
     reactor_exit: Option<ReactorExit>,
     next_upgrade: Option<NextUpgrade>,
     maybe_immediate_switch_block_data: Option<BlockAndExecutionEffects>,
+    /// The state root hash of the emergency restart which the operator has confirmed should be
+    /// applied, if any.
+    emergency_restart_confirmation: Option<Digest>,
+    /// The folder where the `casper-node` binaries are installed in subdirs corresponding to
+    /// their versions, if the staged binaries should be verified.
+    upgrade_binary_dir: Option<PathBuf>,
+    /// The file in which to record the verified upgrade when exiting to upgrade, if any.
+    upgrade_handoff_file: Option<PathBuf>,
+    /// The most recently verified upgrade staged for the next protocol version.
+    staged_upgrade: Option<StagedUpgrade>,
 }
 
 impl ChainspecLoader {
     pub(crate) fn new<P, REv>(
         chainspec_dir: P,
+        node_config: &NodeConfig,
         effect_builder: EffectBuilder<REv>,
     ) -> Result<(Self, Effects<Event>), Error>
     where
@@ -197,6 +288,7 @@ impl ChainspecLoader {
             Arc::new(chainspec),
             Arc::new(chainspec_raw_bytes),
             chainspec_dir,
+            node_config,
             effect_builder,
         ))
     }
@@ -214,6 +306,7 @@ impl ChainspecLoader {
             chainspec,
             chainspec_raw_bytes,
             &RESOURCES_PATH.join("local"),
+            &NodeConfig::default(),
             effect_builder,
         )
     }
@@ -222,6 +315,7 @@ impl ChainspecLoader {
         chainspec: Arc<Chainspec>,
         chainspec_raw_bytes: Arc<ChainspecRawBytes>,
         chainspec_dir: P,
+        node_config: &NodeConfig,
         effect_builder: EffectBuilder<REv>,
     ) -> (Self, Effects<Event>)
     where
@@ -237,19 +331,38 @@ impl ChainspecLoader {
                 PathBuf::new()
             });
 
-        if !chainspec.is_valid() || root_dir.as_os_str().is_empty() {
-            let chainspec_loader = ChainspecLoader {
-                chainspec,
-                chainspec_raw_bytes,
-                root_dir,
-                reactor_exit: Some(ReactorExit::ProcessShouldExit(ExitCode::Abort)),
-                next_upgrade: None,
-                maybe_immediate_switch_block_data: None,
-            };
+        let config_dir = WithDir::new(chainspec_dir.as_ref(), ());
+        let mut chainspec_loader = ChainspecLoader {
+            chainspec,
+            chainspec_raw_bytes,
+            root_dir,
+            reactor_exit: None,
+            next_upgrade: None,
+            maybe_immediate_switch_block_data: None,
+            emergency_restart_confirmation: node_config.confirm_emergency_restart,
+            upgrade_binary_dir: node_config
+                .upgrade_binary_dir
+                .clone()
+                .map(|dir| config_dir.with_dir(dir)),
+            upgrade_handoff_file: node_config
+                .upgrade_handoff_file
+                .clone()
+                .map(|path| config_dir.with_dir(path)),
+            staged_upgrade: None,
+        };
+
+        if !chainspec_loader.chainspec.is_valid()
+            || chainspec_loader.root_dir.as_os_str().is_empty()
+        {
+            chainspec_loader.reactor_exit = Some(ReactorExit::ProcessShouldExit(ExitCode::Abort));
             return (chainspec_loader, Effects::new());
         }
 
-        let next_upgrade = next_upgrade(root_dir.clone(), chainspec.protocol_config.version);
+        let chainspec = &chainspec_loader.chainspec;
+        let next_upgrade = next_upgrade(
+            chainspec_loader.root_dir.clone(),
+            chainspec.protocol_config.version,
+        );
 
         // If the next activation point is the same as the current chainspec one, we've installed
         // two new versions, where the first which we're currently running should be immediately
@@ -280,16 +393,11 @@ impl ChainspecLoader {
                 .event(|_| Event::CheckForNextUpgrade),
         );
 
-        let reactor_exit = should_stop.then(|| ReactorExit::ProcessShouldExit(ExitCode::Success));
-
-        let chainspec_loader = ChainspecLoader {
-            chainspec,
-            chainspec_raw_bytes,
-            root_dir,
-            reactor_exit,
-            next_upgrade,
-            maybe_immediate_switch_block_data: None,
-        };
+        chainspec_loader.next_upgrade = next_upgrade;
+        if should_stop {
+            let exit_code = chainspec_loader.exit_code_for_upgrade();
+            chainspec_loader.reactor_exit = Some(ReactorExit::ProcessShouldExit(exit_code));
+        }
 
         (chainspec_loader, effects)
     }
@@ -308,7 +416,8 @@ impl ChainspecLoader {
             maybe_highest_block_header.as_deref(),
             self.next_upgrade_activation_point(),
         ) {
-            self.reactor_exit = Some(ReactorExit::ProcessShouldExit(ExitCode::Success));
+            let exit_code = self.exit_code_for_upgrade();
+            self.reactor_exit = Some(ReactorExit::ProcessShouldExit(exit_code));
             return Effects::new();
         }
 
@@ -322,6 +431,10 @@ impl ChainspecLoader {
                 // This is a valid run immediately after upgrading the node version, we'll need to
                 // create an immediate switch block.
                 info!("valid run immediately after upgrade");
+                if !self.is_emergency_restart_confirmed() {
+                    self.reactor_exit = Some(ReactorExit::ProcessShouldExit(ExitCode::Abort));
+                    return Effects::new();
+                }
                 let upgrade_config_result =
                     self.new_upgrade_config(&header, Arc::clone(&self.chainspec_raw_bytes));
                 async move {
@@ -373,18 +486,52 @@ impl ChainspecLoader {
         }
     }
 
+    /// Returns `false` and logs an error if the chainspec specifies an emergency restart which the
+    /// operator hasn't confirmed by setting its state root hash in the node config.
+    fn is_emergency_restart_confirmed(&self) -> bool {
+        let emergency_restart = match &self.chainspec.protocol_config.emergency_restart {
+            Some(emergency_restart) => emergency_restart,
+            None => return true,
+        };
+        if self.emergency_restart_confirmation != Some(emergency_restart.state_root_hash) {
+            error!(
+                state_root_hash = %emergency_restart.state_root_hash,
+                "chainspec specifies an emergency restart which has not been confirmed; set \
+                [node][confirm_emergency_restart] to its state root hash to apply it"
+            );
+            return false;
+        }
+        warn!(
+            state_root_hash = %emergency_restart.state_root_hash,
+            validator_count = emergency_restart.validators.len(),
+            "applying confirmed emergency restart"
+        );
+        true
+    }
+
     fn new_upgrade_config(
         &self,
         upgrade_block_header: &BlockHeader,
         chainspec_raw_bytes: Arc<ChainspecRawBytes>,
     ) -> Result<Box<UpgradeConfig>, bytesrepr::Error> {
         let global_state_update = self.chainspec.protocol_config.get_update_mapping()?;
+        // An emergency restart continues from the designated global state rather than the one
+        // produced by the last block before the activation point.
+        let pre_state_hash = self
+            .chainspec
+            .protocol_config
+            .emergency_restart
+            .as_ref()
+            .map_or(
+                *upgrade_block_header.state_root_hash(),
+                |emergency_restart| emergency_restart.state_root_hash,
+            );
         let chainspec_registry = ChainspecRegistry::new_with_optional_global_state(
             chainspec_raw_bytes.chainspec_bytes(),
             chainspec_raw_bytes.maybe_global_state_bytes(),
         );
         let upgrade_config = UpgradeConfig::new(
-            *upgrade_block_header.state_root_hash(),
+            pre_state_hash,
             upgrade_block_header.protocol_version(),
             self.chainspec.protocol_version(),
             Some(self.chainspec.protocol_config.activation_point.era_id()),
@@ -554,6 +701,12 @@ impl ChainspecLoader {
             + Send,
     {
         let protocol_version = self.chainspec.protocol_version();
+        let maybe_expected_validators = self
+            .chainspec
+            .protocol_config
+            .emergency_restart
+            .as_ref()
+            .map(|emergency_restart| emergency_restart.validators.clone());
         async move {
             let block_and_execution_effects = effect_builder
                 .execute_finalized_block(
@@ -564,13 +717,29 @@ impl ChainspecLoader {
                     vec![],
                 )
                 .await?;
+            // Don't store the block if it doesn't hand over to the validators designated by an
+            // emergency restart.
+            if let Some(expected) = maybe_expected_validators {
+                let actual = block_and_execution_effects
+                    .block
+                    .header()
+                    .next_era_validator_weights();
+                if actual != Some(&expected) {
+                    return Err(ImmediateSwitchBlockError::UnexpectedValidators {
+                        expected,
+                        actual: actual.cloned(),
+                    });
+                }
+            }
             // We need to store the block now so that the era supervisor can be properly
             // initialized in the participating reactor's constructor.
             effect_builder
                 .put_block_to_storage(block_and_execution_effects.block.clone())
                 .await;
             effect_builder
-                .mark_block_completed(block_and_execution_effects.block.height())
+                .mark_block_completed(BlockHeight::from(
+                    block_and_execution_effects.block.height(),
+                ))
                 .await;
             info!(
                 immediate_switch_block = ?block_and_execution_effects.block.clone(),
@@ -584,7 +753,7 @@ impl ChainspecLoader {
     fn handle_execute_immediate_switch_block_result<REv>(
         &mut self,
         effect_builder: EffectBuilder<REv>,
-        result: Result<BlockAndExecutionEffects, BlockExecutionError>,
+        result: Result<BlockAndExecutionEffects, ImmediateSwitchBlockError>,
     ) -> Effects<Event>
     where
         REv: From<ControlAnnouncement> + Send,
@@ -631,12 +800,84 @@ impl ChainspecLoader {
         &self.chainspec
     }
 
+    /// Returns the chainspec of the greatest protocol version installed which is lower than the
+    /// current one, if any.
+    ///
+    /// This is the chainspec in force before the most recent upgrade, which is required to
+    /// re-execute blocks created before the upgrade's activation point.  Fails if that chainspec
+    /// cannot be loaded or is invalid.
+    pub(crate) fn previous_chainspec(&self) -> Result<Option<Chainspec>, PreviousChainspecError> {
+        previous_chainspec(&self.root_dir, self.chainspec.protocol_config.version)
+    }
+
     pub(crate) fn next_upgrade_activation_point(&self) -> Option<ActivationPoint> {
         self.next_upgrade
             .as_ref()
             .map(|next_upgrade| next_upgrade.activation_point())
     }
 
+    /// Returns the code with which the process should exit in order to upgrade to the next protocol
+    /// version.
+    ///
+    /// If an upgrade handoff file is configured, the staged upgrade is verified once more and
+    /// recorded in the file for the launcher, and the process should exit with
+    /// `ExitCode::UpgradeHandoff`.  If this fails, the process should abort rather than hand off to
+    /// an unverified upgrade.
+    pub(crate) fn exit_code_for_upgrade(&self) -> ExitCode {
+        let handoff_file = match &self.upgrade_handoff_file {
+            Some(handoff_file) => handoff_file,
+            None => return ExitCode::Success,
+        };
+        let next_version = match &self.next_upgrade {
+            Some(next_upgrade) => next_upgrade.protocol_version(),
+            None => {
+                error!("exiting to upgrade without a next upgrade installed");
+                return ExitCode::Abort;
+            }
+        };
+
+        let staged_upgrade = match staged_upgrade::verify_staged_upgrade(
+            &self.root_dir,
+            self.upgrade_binary_dir.as_deref(),
+            &self.chainspec,
+            next_version,
+        ) {
+            Ok(staged_upgrade) => staged_upgrade,
+            Err(error) => {
+                error!(%error, %next_version, "failed to verify staged upgrade");
+                return ExitCode::Abort;
+            }
+        };
+        if let Some(verified) = &self.staged_upgrade {
+            if *verified != staged_upgrade {
+                warn!(
+                    ?verified,
+                    ?staged_upgrade,
+                    "staged upgrade changed since it was last verified"
+                );
+            }
+        }
+
+        let current_version = self.chainspec.protocol_config.version;
+        if let Err(error) =
+            staged_upgrade::write_handoff(handoff_file, current_version, &staged_upgrade)
+        {
+            error!(
+                %error,
+                path = %handoff_file.display(),
+                "failed to write upgrade handoff file"
+            );
+            return ExitCode::Abort;
+        }
+        info!(
+            path = %handoff_file.display(),
+            %current_version,
+            %next_version,
+            "wrote upgrade handoff file"
+        );
+        ExitCode::UpgradeHandoff
+    }
+
     /// Returns the era ID of where we should reset back to.  This means stored blocks in that and
     /// subsequent eras are deleted from storage.
     pub(crate) fn hard_reset_to_start_of_era(&self) -> Option<EraId> {
@@ -658,6 +899,9 @@ impl ChainspecLoader {
         REv: From<ChainspecLoaderAnnouncement> + Send,
     {
         let root_dir = self.root_dir.clone();
+        let staged_root_dir = self.root_dir.clone();
+        let maybe_binary_dir = self.upgrade_binary_dir.clone();
+        let current_chainspec = Arc::clone(&self.chainspec);
         let current_version = self.chainspec.protocol_config.version;
         let mut effects = async move {
             let maybe_next_upgrade =
@@ -667,13 +911,26 @@ impl ChainspecLoader {
                         warn!(%error, "failed to join tokio task");
                         None
                     });
-            if let Some(next_upgrade) = maybe_next_upgrade {
-                effect_builder
-                    .announce_upgrade_activation_point_read(next_upgrade)
-                    .await
-            }
+            let next_upgrade = maybe_next_upgrade?;
+            let next_version = next_upgrade.protocol_version();
+            effect_builder
+                .announce_upgrade_activation_point_read(next_upgrade)
+                .await;
+            // Verify the staged upgrade well ahead of its activation point, so that operators are
+            // warned of any problems with it in time.
+            task::spawn_blocking(move || {
+                staged_upgrade::verify_staged_upgrade(
+                    &staged_root_dir,
+                    maybe_binary_dir.as_deref(),
+                    &current_chainspec,
+                    next_version,
+                )
+            })
+            .await
+            .map_err(|error| warn!(%error, "failed to join tokio task"))
+            .ok()
         }
-        .ignore();
+        .event(Event::StagedUpgradeVerified);
 
         effects.extend(
             effect_builder
@@ -698,6 +955,26 @@ impl ChainspecLoader {
         self.next_upgrade = Some(next_upgrade);
         Effects::new()
     }
+
+    fn handle_staged_upgrade_verified(
+        &mut self,
+        result: Option<Result<StagedUpgrade, StagedUpgradeError>>,
+    ) -> Effects<Event> {
+        match result {
+            Some(Ok(staged_upgrade)) => {
+                if self.staged_upgrade.as_ref() != Some(&staged_upgrade) {
+                    info!(?staged_upgrade, "verified staged upgrade");
+                }
+                self.staged_upgrade = Some(staged_upgrade);
+            }
+            Some(Err(error)) => {
+                error!(%error, "staged upgrade failed verification");
+                self.staged_upgrade = None;
+            }
+            None => self.staged_upgrade = None,
+        }
+        Effects::new()
+    }
 }
 
 impl<REv> Component<REv> for ChainspecLoader
@@ -740,8 +1017,28 @@ where
             Event::Request(ChainspecLoaderRequest::GetChainspecRawBytes(responder)) => responder
                 .respond(Arc::clone(&self.chainspec_raw_bytes))
                 .ignore(),
+            Event::Request(ChainspecLoaderRequest::GetChainspec(responder)) => {
+                responder.respond(Arc::clone(&self.chainspec)).ignore()
+            }
+            Event::Request(ChainspecLoaderRequest::GetUpgradePoints(responder)) => {
+                let root_dir = self.root_dir.clone();
+                let current_version = self.chainspec.protocol_config.version;
+                async move {
+                    let upgrade_points = task::spawn_blocking(move || {
+                        installed_upgrade_points(&root_dir, current_version)
+                    })
+                    .await
+                    .unwrap_or_else(|error| {
+                        warn!(%error, "failed to join tokio task");
+                        vec![]
+                    });
+                    responder.respond(upgrade_points).await
+                }
+                .ignore()
+            }
             Event::CheckForNextUpgrade => self.check_for_next_upgrade(effect_builder),
             Event::GotNextUpgrade(next_upgrade) => self.handle_got_next_upgrade(next_upgrade),
+            Event::StagedUpgradeVerified(result) => self.handle_staged_upgrade_verified(result),
         }
     }
 }
@@ -764,6 +1061,14 @@ impl UpgradePoint {
     }
 }
 
+/// Reads the protocol config of the chainspec in the given dir.
+///
+/// Only the chainspec's `[protocol]` section is parsed, so this succeeds for the chainspecs of other
+/// protocol versions, as long as that section remains in the current form.
+pub(crate) fn read_protocol_config(dir: &Path) -> Result<ProtocolConfig, Error> {
+    UpgradePoint::from_chainspec_path(dir).map(|upgrade_point| upgrade_point.protocol_config)
+}
+
 fn dir_name_from_version(version: &ProtocolVersion) -> PathBuf {
     PathBuf::from(version.to_string().replace('.', "_"))
 }
@@ -862,6 +1167,84 @@ fn next_upgrade(dir: PathBuf, current_version: ProtocolVersion) -> Option<NextUp
     Some(NextUpgrade::from(upgrade_point.protocol_config))
 }
 
+/// Returns the upgrade points installed in the versioned subdirs of the given path, ordered by
+/// protocol version.  Subdirs which don't hold a valid upgrade point for their version are skipped.
+fn installed_upgrade_points(dir: &Path, current_version: ProtocolVersion) -> Vec<UpgradePointInfo> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) => {
+            warn!(dir=%dir.display(), %error, "failed to read dir");
+            return vec![];
+        }
+    };
+
+    let mut upgrade_points: Vec<UpgradePointInfo> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let subdir_name = path.file_name()?.to_string_lossy().replace('_', ".");
+            let version = ProtocolVersion::from_str(&subdir_name).ok()?;
+            let upgrade_point = match UpgradePoint::from_chainspec_path(&path) {
+                Ok(upgrade_point) => upgrade_point,
+                Err(error) => {
+                    debug!(subdir=%path.display(), %error, "failed to load upgrade point");
+                    return None;
+                }
+            };
+            if upgrade_point.protocol_config.version != version {
+                warn!(
+                    upgrade_point_version=%upgrade_point.protocol_config.version,
+                    subdir_version=%version,
+                    "chainspec installed to wrong subdir"
+                );
+                return None;
+            }
+            let status = if version < current_version {
+                UpgradePointStatus::Superseded
+            } else if version == current_version {
+                UpgradePointStatus::Active
+            } else {
+                UpgradePointStatus::Pending
+            };
+            Some(UpgradePointInfo {
+                protocol_version: version,
+                activation_point: upgrade_point.protocol_config.activation_point,
+                status,
+            })
+        })
+        .collect();
+    upgrade_points.sort_by(|lhs, rhs| lhs.protocol_version.cmp(&rhs.protocol_version));
+    upgrade_points
+}
+
+/// Loads the chainspec installed in the versioned subdir of the given path with the greatest
+/// protocol version lower than `current_version`.  Returns `None` if there is no such subdir, and
+/// an error if its chainspec fails to load or is invalid.
+fn previous_chainspec(
+    dir: &Path,
+    current_version: ProtocolVersion,
+) -> Result<Option<Chainspec>, PreviousChainspecError> {
+    let previous_version = match installed_upgrade_points(dir, current_version)
+        .into_iter()
+        .rev()
+        .find(|upgrade_point| upgrade_point.status == UpgradePointStatus::Superseded)
+    {
+        Some(upgrade_point) => upgrade_point.protocol_version,
+        None => return Ok(None),
+    };
+
+    let subdir = dir.join(dir_name_from_version(&previous_version));
+    let chainspec = match <(Chainspec, ChainspecRawBytes)>::from_path(&subdir) {
+        Ok((chainspec, _)) => chainspec,
+        Err(error) => return Err(PreviousChainspecError::Load { subdir, error }),
+    };
+
+    if !chainspec.is_valid() {
+        return Err(PreviousChainspecError::Invalid { subdir });
+    }
+
+    Ok(Some(chainspec))
+}
+
 #[cfg(test)]
 mod tests {
     use casper_types::testing::TestRng;
@@ -871,11 +1254,8 @@ mod tests {
 
     #[test]
     fn correctly_detects_when_to_exit_for_upgrade() {
-        let mut rng = crate::new_rng();
         const HEIGHT: u64 = 10;
         const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
-        const IS_NOT_SWITCH: bool = false;
-        const IS_SWITCH: bool = true;
 
         let highest_block_header = None;
         let next_upgrade_activation_point = None;
@@ -885,16 +1265,13 @@ mod tests {
         ));
 
         let highest_block_header = Some(Box::new(
-            Block::random_with_specifics(
-                &mut rng,
-                EraId::from(2),
-                HEIGHT,
-                PROTOCOL_VERSION,
-                IS_NOT_SWITCH,
-                None,
-            )
-            .header()
-            .clone(),
+            Block::builder()
+                .era_id(EraId::from(2))
+                .height(HEIGHT)
+                .protocol_version(PROTOCOL_VERSION)
+                .build()
+                .header()
+                .clone(),
         ));
         let next_upgrade_activation_point = None;
         assert!(!ChainspecLoader::should_exit_for_upgrade(
@@ -910,16 +1287,13 @@ mod tests {
         ));
 
         let highest_block_header = Some(Box::new(
-            Block::random_with_specifics(
-                &mut rng,
-                EraId::from(2),
-                HEIGHT,
-                PROTOCOL_VERSION,
-                IS_NOT_SWITCH,
-                None,
-            )
-            .header()
-            .clone(),
+            Block::builder()
+                .era_id(EraId::from(2))
+                .height(HEIGHT)
+                .protocol_version(PROTOCOL_VERSION)
+                .build()
+                .header()
+                .clone(),
         ));
         let next_upgrade_activation_point = Some(ActivationPoint::EraId(3.into()));
         assert!(!ChainspecLoader::should_exit_for_upgrade(
@@ -928,16 +1302,13 @@ mod tests {
         ));
 
         let highest_block_header = Some(Box::new(
-            Block::random_with_specifics(
-                &mut rng,
-                EraId::from(2),
-                HEIGHT,
-                PROTOCOL_VERSION,
-                IS_NOT_SWITCH,
-                None,
-            )
-            .header()
-            .clone(),
+            Block::builder()
+                .era_id(EraId::from(2))
+                .height(HEIGHT)
+                .protocol_version(PROTOCOL_VERSION)
+                .build()
+                .header()
+                .clone(),
         ));
         let next_upgrade_activation_point = Some(ActivationPoint::EraId(2.into()));
         assert!(ChainspecLoader::should_exit_for_upgrade(
@@ -946,16 +1317,14 @@ mod tests {
         ));
 
         let highest_block_header = Some(Box::new(
-            Block::random_with_specifics(
-                &mut rng,
-                EraId::from(2),
-                HEIGHT,
-                PROTOCOL_VERSION,
-                IS_SWITCH,
-                None,
-            )
-            .header()
-            .clone(),
+            Block::builder()
+                .era_id(EraId::from(2))
+                .height(HEIGHT)
+                .protocol_version(PROTOCOL_VERSION)
+                .switch_block()
+                .build()
+                .header()
+                .clone(),
         ));
         let next_upgrade_activation_point = Some(ActivationPoint::EraId(3.into()));
         assert!(ChainspecLoader::should_exit_for_upgrade(
@@ -1146,4 +1515,72 @@ mod tests {
         fs::remove_file(&path_v1_0_0).unwrap();
         assert!(maybe_next_point(&current).is_none());
     }
+
+    #[test]
+    fn should_get_installed_upgrade_points() {
+        let tempdir = tempfile::tempdir().expect("should create temp dir");
+        let mut rng = crate::new_rng();
+
+        let v1_0_0 = ProtocolVersion::from_parts(1, 0, 0);
+        let v1_1_0 = ProtocolVersion::from_parts(1, 1, 0);
+        let v2_0_0 = ProtocolVersion::from_parts(2, 0, 0);
+        let chainspec_v2_0_0 = install_chainspec(&mut rng, tempdir.path(), &v2_0_0);
+        let chainspec_v1_0_0 = install_chainspec(&mut rng, tempdir.path(), &v1_0_0);
+        let chainspec_v1_1_0 = install_chainspec(&mut rng, tempdir.path(), &v1_1_0);
+
+        // Invalid subdirs should be ignored.
+        fs::create_dir(tempdir.path().join("not_a_version")).unwrap();
+        fs::create_dir(tempdir.path().join("3_0_0")).unwrap();
+
+        let upgrade_point = |chainspec: &Chainspec, status| UpgradePointInfo {
+            protocol_version: chainspec.protocol_config.version,
+            activation_point: chainspec.protocol_config.activation_point,
+            status,
+        };
+        assert_eq!(
+            installed_upgrade_points(tempdir.path(), v1_1_0),
+            vec![
+                upgrade_point(&chainspec_v1_0_0, UpgradePointStatus::Superseded),
+                upgrade_point(&chainspec_v1_1_0, UpgradePointStatus::Active),
+                upgrade_point(&chainspec_v2_0_0, UpgradePointStatus::Pending),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_get_previous_chainspec() {
+        let tempdir = tempfile::tempdir().expect("should create temp dir");
+
+        // Install the production chainspec and accounts to the subdir for its version.
+        let resources_dir = RESOURCES_PATH.join("production");
+        let (production_chainspec, _) =
+            <(Chainspec, ChainspecRawBytes)>::from_path(&resources_dir).unwrap();
+        let production_version = production_chainspec.protocol_config.version;
+        let subdir = tempdir
+            .path()
+            .join(dir_name_from_version(&production_version));
+        fs::create_dir(&subdir).unwrap();
+        for file_name in &[CHAINSPEC_FILENAME, "accounts.toml"] {
+            fs::copy(resources_dir.join(file_name), subdir.join(file_name)).unwrap();
+        }
+
+        // It should be found as the previous chainspec of any greater version.
+        let next_version = ProtocolVersion::from_parts(production_version.value().major + 1, 0, 0);
+        assert_eq!(
+            previous_chainspec(tempdir.path(), next_version).unwrap(),
+            Some(production_chainspec)
+        );
+
+        // It should not be found as the previous chainspec of its own version.
+        assert!(previous_chainspec(tempdir.path(), production_version)
+            .unwrap()
+            .is_none());
+
+        // A chainspec failing to load should be reported.
+        fs::write(subdir.join(CHAINSPEC_FILENAME), "bad data".as_bytes()).unwrap();
+        assert!(matches!(
+            previous_chainspec(tempdir.path(), next_version),
+            Err(PreviousChainspecError::Load { .. })
+        ));
+    }
 }
@@ -0,0 +1,261 @@
+//! Verification of the next protocol version staged on the node, and the handoff to the launcher.
+//!
+//! Ahead of an upgrade's activation point, the node checks that the chainspec installed for the
+//! next version is valid and belongs to the same network, and hashes the `casper-node` binary
+//! installed for it.  When the activation point is reached, the node records the verified upgrade
+//! in a handoff file for the launcher before exiting.
+
+use std::{
+    fs, io,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+};
+
+use datasize::DataSize;
+use serde::Serialize;
+use thiserror::Error;
+
+use casper_hashing::Digest;
+use casper_types::{
+    file_utils::{self, ReadFileError},
+    EraId, ProtocolVersion,
+};
+
+use super::dir_name_from_version;
+use crate::{
+    types::{
+        chainspec::{ChainspecRawBytes, Error as ChainspecError},
+        Chainspec,
+    },
+    utils::Loadable,
+};
+
+/// The filename of the `casper-node` binary installed for each protocol version.
+const BINARY_FILENAME: &str = "casper-node";
+
+/// A staged protocol upgrade which has been verified.
+#[derive(Clone, DataSize, PartialEq, Eq, Debug)]
+pub(crate) struct StagedUpgrade {
+    /// The protocol version of the upgrade.
+    #[data_size(skip)]
+    pub(crate) protocol_version: ProtocolVersion,
+    /// The era at the start of which the upgrade activates.
+    pub(crate) activation_era: EraId,
+    /// The hash of the upgrade's chainspec file.
+    pub(crate) chainspec_hash: Digest,
+    /// The hash of the upgrade's `casper-node` binary, if a binary dir is configured.
+    pub(crate) binary_hash: Option<Digest>,
+}
+
+/// An error verifying a staged protocol upgrade.
+#[derive(Debug, Error)]
+pub(crate) enum StagedUpgradeError {
+    /// The staged chainspec couldn't be loaded.
+    #[error("failed to load staged chainspec: {0}")]
+    LoadChainspec(#[from] ChainspecError),
+    /// The staged chainspec failed validation.
+    #[error("staged chainspec is invalid")]
+    InvalidChainspec,
+    /// The staged chainspec's protocol version differs from that of its subdir.
+    #[error("staged chainspec has version {actual}, expected {expected}")]
+    WrongVersion {
+        expected: ProtocolVersion,
+        actual: ProtocolVersion,
+    },
+    /// The staged chainspec is for a different network.
+    #[error("staged chainspec is for network {actual}, expected {expected}")]
+    WrongNetwork { expected: String, actual: String },
+    /// The staged upgrade activates before the current protocol version.
+    #[error("staged upgrade activates at era {staged}, before the current era {current}")]
+    ActivationTooEarly { current: EraId, staged: EraId },
+    /// The staged binary couldn't be read.
+    #[error("failed to read staged binary: {0}")]
+    ReadBinary(#[from] ReadFileError),
+    /// The staged binary isn't executable.
+    #[error("staged binary {} is not executable", path.display())]
+    NotExecutable { path: PathBuf },
+}
+
+/// Verifies the upgrade to `next_version` staged in the versioned subdirs of `root_dir` and, if
+/// given, of `maybe_binary_dir`.
+pub(super) fn verify_staged_upgrade(
+    root_dir: &Path,
+    maybe_binary_dir: Option<&Path>,
+    current_chainspec: &Chainspec,
+    next_version: ProtocolVersion,
+) -> Result<StagedUpgrade, StagedUpgradeError> {
+    let version_dir = dir_name_from_version(&next_version);
+    let (chainspec, chainspec_raw_bytes) =
+        <(Chainspec, ChainspecRawBytes)>::from_path(root_dir.join(&version_dir))?;
+
+    if chainspec.protocol_config.version != next_version {
+        return Err(StagedUpgradeError::WrongVersion {
+            expected: next_version,
+            actual: chainspec.protocol_config.version,
+        });
+    }
+    if chainspec.network_config.name != current_chainspec.network_config.name {
+        return Err(StagedUpgradeError::WrongNetwork {
+            expected: current_chainspec.network_config.name.clone(),
+            actual: chainspec.network_config.name,
+        });
+    }
+    let current_era = current_chainspec.protocol_config.activation_point.era_id();
+    let staged_era = chainspec.protocol_config.activation_point.era_id();
+    if staged_era < current_era {
+        return Err(StagedUpgradeError::ActivationTooEarly {
+            current: current_era,
+            staged: staged_era,
+        });
+    }
+    if !chainspec.is_valid() {
+        return Err(StagedUpgradeError::InvalidChainspec);
+    }
+
+    let binary_hash = match maybe_binary_dir {
+        Some(binary_dir) => {
+            let path = binary_dir.join(&version_dir).join(BINARY_FILENAME);
+            let binary = file_utils::read_file(&path)?;
+            let is_executable = fs::metadata(&path)
+                .map(|metadata| metadata.permissions().mode() & 0o111 != 0)
+                .unwrap_or(false);
+            if !is_executable {
+                return Err(StagedUpgradeError::NotExecutable { path });
+            }
+            Some(Digest::hash(&binary))
+        }
+        None => None,
+    };
+
+    Ok(StagedUpgrade {
+        protocol_version: next_version,
+        activation_era: staged_era,
+        chainspec_hash: Digest::hash(chainspec_raw_bytes.chainspec_bytes()),
+        binary_hash,
+    })
+}
+
+/// The record of a verified upgrade which the node hands off to the launcher when it exits at the
+/// upgrade's activation point.
+#[derive(Serialize)]
+struct Handoff {
+    current_version: ProtocolVersion,
+    next_version: ProtocolVersion,
+    activation_era: EraId,
+    chainspec_hash: Digest,
+    binary_hash: Option<Digest>,
+}
+
+/// Writes the handoff file for `staged_upgrade` to `path`, replacing any existing one.
+pub(super) fn write_handoff(
+    path: &Path,
+    current_version: ProtocolVersion,
+    staged_upgrade: &StagedUpgrade,
+) -> io::Result<()> {
+    let handoff = Handoff {
+        current_version,
+        next_version: staged_upgrade.protocol_version,
+        activation_era: staged_upgrade.activation_era,
+        chainspec_hash: staged_upgrade.chainspec_hash,
+        binary_hash: staged_upgrade.binary_hash,
+    };
+    let contents = toml::to_string_pretty(&handoff)
+        .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+    // Write to a temporary file first so that the launcher never reads a partial handoff file.
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        types::{chainspec::CHAINSPEC_FILENAME, ActivationPoint},
+        utils::RESOURCES_PATH,
+    };
+
+    /// Installs the production chainspec in `root_dir`, and returns it along with a chainspec for
+    /// the protocol version preceding it.
+    fn install_production_chainspec(root_dir: &Path) -> (Chainspec, Chainspec) {
+        let production_dir = RESOURCES_PATH.join("production");
+        let (next_chainspec, _) =
+            <(Chainspec, ChainspecRawBytes)>::from_path(&production_dir).unwrap();
+        let subdir = root_dir.join(dir_name_from_version(&next_chainspec.protocol_version()));
+        fs::create_dir(&subdir).unwrap();
+        for filename in [CHAINSPEC_FILENAME, "accounts.toml"] {
+            fs::copy(production_dir.join(filename), subdir.join(filename)).unwrap();
+        }
+
+        let mut current_chainspec = next_chainspec.clone();
+        current_chainspec.protocol_config.version = ProtocolVersion::V1_0_0;
+        current_chainspec.protocol_config.activation_point = ActivationPoint::EraId(EraId::new(1));
+        (current_chainspec, next_chainspec)
+    }
+
+    #[test]
+    fn should_verify_staged_upgrade() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let binary_dir = tempfile::tempdir().unwrap();
+        let (current_chainspec, next_chainspec) = install_production_chainspec(tempdir.path());
+        let next_version = next_chainspec.protocol_version();
+
+        let staged_upgrade =
+            verify_staged_upgrade(tempdir.path(), None, &current_chainspec, next_version).unwrap();
+        assert_eq!(staged_upgrade.protocol_version, next_version);
+        assert_eq!(
+            staged_upgrade.activation_era,
+            next_chainspec.protocol_config.activation_point.era_id()
+        );
+        assert!(staged_upgrade.binary_hash.is_none());
+
+        // A missing or non-executable binary fails verification.
+        let verify = || {
+            verify_staged_upgrade(
+                tempdir.path(),
+                Some(binary_dir.path()),
+                &current_chainspec,
+                next_version,
+            )
+        };
+        assert!(matches!(verify(), Err(StagedUpgradeError::ReadBinary(_))));
+        let binary_subdir = binary_dir.path().join(dir_name_from_version(&next_version));
+        fs::create_dir(&binary_subdir).unwrap();
+        let binary_path = binary_subdir.join(BINARY_FILENAME);
+        fs::write(&binary_path, b"binary").unwrap();
+        assert!(matches!(
+            verify(),
+            Err(StagedUpgradeError::NotExecutable { .. })
+        ));
+        fs::set_permissions(&binary_path, fs::Permissions::from_mode(0o755)).unwrap();
+        assert_eq!(verify().unwrap().binary_hash, Some(Digest::hash(b"binary")));
+
+        // A chainspec for another network fails verification.
+        let mut other_network_chainspec = current_chainspec;
+        other_network_chainspec.network_config.name = "other-network".to_string();
+        assert!(matches!(
+            verify_staged_upgrade(tempdir.path(), None, &other_network_chainspec, next_version),
+            Err(StagedUpgradeError::WrongNetwork { .. })
+        ));
+    }
+
+    #[test]
+    fn should_write_handoff() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("upgrade_handoff.toml");
+        let staged_upgrade = StagedUpgrade {
+            protocol_version: ProtocolVersion::from_parts(1, 1, 0),
+            activation_era: EraId::new(10),
+            chainspec_hash: Digest::hash(b"chainspec"),
+            binary_hash: Some(Digest::hash(b"binary")),
+        };
+
+        write_handoff(&path, ProtocolVersion::V1_0_0, &staged_upgrade).unwrap();
+
+        let handoff: toml::Value = toml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(handoff["current_version"].as_str(), Some("1.0.0"));
+        assert_eq!(handoff["next_version"].as_str(), Some("1.1.0"));
+        assert_eq!(handoff["activation_era"].as_integer(), Some(10));
+        assert!(!tempdir.path().join("upgrade_handoff.tmp").exists());
+    }
+}
@@ -83,6 +83,13 @@ pub struct NewBlockPayload {
     block_context: BlockContext<ClContext>,
 }
 
+impl NewBlockPayload {
+    /// Returns the payload of the block to be proposed.
+    pub(crate) fn block_payload(&self) -> &BlockPayload {
+        &self.block_payload
+    }
+}
+
 #[derive(DataSize, Debug, From)]
 pub struct ResolveValidity {
     era_id: EraId,
@@ -10,7 +10,10 @@ use crate::{
         era_supervisor::PAST_OPEN_ERAS, protocols::highway::config::Config as HighwayConfig, EraId,
     },
     types::Chainspec,
-    utils::{External, LoadError, Loadable},
+    utils::{
+        key_file::{self, KeyFileError, PassphraseSource},
+        External, LoadError,
+    },
 };
 
 /// Consensus configuration.
@@ -20,6 +23,8 @@ use crate::{
 pub(crate) struct Config {
     /// Path to secret key file.
     pub(crate) secret_key_path: External,
+    /// Source of the passphrase unlocking the secret key file, if it is encrypted.
+    pub(crate) secret_key_passphrase: Option<PassphraseSource>,
     /// Highway-specific node configuration.
     pub(crate) highway: HighwayConfig,
 }
@@ -28,19 +33,22 @@ impl Default for Config {
     fn default() -> Self {
         Config {
             secret_key_path: External::Missing,
+            secret_key_passphrase: None,
             highway: HighwayConfig::default(),
         }
     }
 }
 
 impl Config {
-    /// Loads the secret key from the configuration file and derives the public key.
-    #[allow(clippy::type_complexity)]
+    /// Loads the secret key from the configuration file, unlocking it if encrypted, and derives the
+    /// public key.
     pub(crate) fn load_keys<P: AsRef<Path>>(
         &self,
         root: P,
-    ) -> Result<(Arc<SecretKey>, PublicKey), LoadError<<Arc<SecretKey> as Loadable>::Error>> {
-        let secret_signing_key: Arc<SecretKey> = self.secret_key_path.clone().load(root)?;
+    ) -> Result<(Arc<SecretKey>, PublicKey), LoadError<KeyFileError>> {
+        let secret_signing_key = self.secret_key_path.clone().load_with(root, |path| {
+            key_file::load_secret_key(path, self.secret_key_passphrase.as_ref()).map(Arc::new)
+        })?;
         let public_key: PublicKey = PublicKey::from(secret_signing_key.as_ref());
         Ok((secret_signing_key, public_key))
     }
@@ -166,6 +166,11 @@ pub(crate) struct TerminalBlockData<C: Context> {
     pub(crate) rewards: BTreeMap<C::ValidatorId, u64>,
     /// The list of validators that haven't produced any units.
     pub(crate) inactive_validators: Vec<C::ValidatorId>,
+    /// The number of rounds each validator missed.
+    ///
+    /// This is not part of the era report, i.e. only nodes which took part in the era's consensus
+    /// know it.
+    pub(crate) missed_rounds: BTreeMap<C::ValidatorId, u64>,
 }
 
 /// A finalized block. All nodes are guaranteed to see the same sequence of blocks, and to agree
@@ -282,6 +287,10 @@ pub(crate) trait ConsensusProtocol<C: Context>: Send {
     /// Clears this instance and keeps only the information necessary to validate evidence.
     fn set_evidence_only(&mut self);
 
+    /// Moves the values of older blocks to a file at the given path once their size in memory
+    /// exceeds the configured budget, if any.
+    fn enable_value_spilling(&mut self, path: PathBuf);
+
     /// Returns whether the validator `vid` is known to be faulty.
     fn has_evidence(&self, vid: &C::ValidatorId) -> bool;
 
@@ -308,4 +317,8 @@ pub(crate) trait ConsensusProtocol<C: Context>: Send {
 
     // TODO: Make this less Highway-specific.
     fn next_round_length(&self) -> Option<TimeDiff>;
+
+    /// Returns for every validator the fraction of recent rounds in which their messages were
+    /// received on time, unless no round has been completed yet.
+    fn round_participation(&self, now: Timestamp) -> Vec<(C::ValidatorId, f64)>;
 }
@@ -12,7 +12,7 @@ use std::{
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     convert::TryInto,
     fmt::{self, Debug, Formatter},
-    fs, io,
+    fs, io, mem,
     path::{Path, PathBuf},
     sync::Arc,
     time::Duration,
@@ -30,6 +30,7 @@ use casper_hashing::Digest;
 use casper_types::{AsymmetricType, EraId, PublicKey, SecretKey, TimeDiff, Timestamp, U512};
 
 use crate::{
+    alerts::{self, Alert},
     components::{
         consensus::{
             cl_context::{ClContext, Keypair},
@@ -46,12 +47,13 @@ use crate::{
     },
     effect::{
         announcements::ControlAnnouncement,
+        diagnostics_port::ComponentMemoryUsage,
         requests::{BlockValidationRequest, ContractRuntimeRequest, StorageRequest},
         EffectBuilder, EffectExt, Effects, Responder,
     },
     fatal,
     types::{
-        ActivationPoint, BlockHash, BlockHeader, Chainspec, Deploy, DeployHash,
+        ActivationPoint, BlockHash, BlockHeader, BlockHeight, Chainspec, Deploy, DeployHash,
         DeployOrTransferHash, FinalitySignature, FinalizedApprovals, FinalizedBlock, NodeId,
     },
     NodeRng,
@@ -104,8 +106,8 @@ pub struct EraSupervisor {
     /// Since eras at or before the most recent activation point are never instantiated, shortly
     /// after that there can temporarily be fewer than three entries in the map.
     open_eras: HashMap<EraId, Era>,
-    secret_signing_key: Arc<SecretKey>,
-    public_signing_key: PublicKey,
+    /// Our validator keys, or `None` if the node runs in observer mode.
+    validator_keys: Option<(Arc<SecretKey>, PublicKey)>,
     current_era: EraId,
     chainspec: Arc<Chainspec>,
     config: Config,
@@ -120,6 +122,8 @@ pub struct EraSupervisor {
     next_block_height: u64,
     /// The height of the next block to be executed. If this falls too far behind, we pause.
     next_executed_height: u64,
+    /// Whether consensus is paused because execution fell too far behind finalization.
+    paused: bool,
     #[data_size(skip)]
     metrics: Metrics,
     /// The path to the folder where unit files will be stored.
@@ -145,8 +149,7 @@ impl EraSupervisor {
     pub(crate) fn new<REv: ReactorEventT>(
         current_era: EraId,
         storage_dir: &Path,
-        secret_signing_key: Arc<SecretKey>,
-        public_signing_key: PublicKey,
+        validator_keys: Option<(Arc<SecretKey>, PublicKey)>,
         config: Config,
         effect_builder: EffectBuilder<REv>,
         chainspec: Arc<Chainspec>,
@@ -166,7 +169,12 @@ impl EraSupervisor {
             );
         }
         let unit_files_folder = storage_dir.join("unit_files");
-        info!(our_id = %public_signing_key, "EraSupervisor pubkey",);
+        match &validator_keys {
+            Some((_, public_signing_key)) => {
+                info!(our_id = %public_signing_key, "EraSupervisor pubkey")
+            }
+            None => info!("EraSupervisor running in observer mode"),
+        }
         let metrics =
             Metrics::new(registry).expect("failed to set up and register consensus metrics");
         #[allow(clippy::integer_arithmetic)] // Block height should never reach u64::MAX.
@@ -174,8 +182,7 @@ impl EraSupervisor {
 
         let mut era_supervisor = Self {
             open_eras: Default::default(),
-            secret_signing_key,
-            public_signing_key,
+            validator_keys,
             current_era,
             chainspec,
             config,
@@ -185,6 +192,7 @@ impl EraSupervisor {
             unit_files_folder,
             next_upgrade_activation_point,
             next_executed_height: next_height,
+            paused: false,
             era_where_we_joined: current_era,
         };
 
@@ -260,13 +268,12 @@ impl EraSupervisor {
     /// contains only the single switch block we created after the upgrade. There is no consensus
     /// instance for it.
     pub(crate) fn iter_past(&self, era_id: EraId, num_eras: u64) -> impl Iterator<Item = EraId> {
-        (self
+        let start = self
             .chainspec
             .activation_era()
             .successor()
-            .max(era_id.saturating_sub(num_eras))
-            .value()..=era_id.value())
-            .map(EraId::from)
+            .max(era_id.saturating_sub(num_eras));
+        EraId::range_inclusive(start, era_id)
     }
 
     /// Returns an iterator over era IDs of `num_eras` past eras, excluding the provided one.
@@ -290,7 +297,7 @@ impl EraSupervisor {
 
     /// Returns an iterator over era IDs of `num_eras` future eras, plus the provided one.
     fn iter_future(&self, era_id: EraId, num_eras: u64) -> impl Iterator<Item = EraId> {
-        (era_id.value()..=era_id.value().saturating_add(num_eras)).map(EraId::from)
+        EraId::range_inclusive(era_id, era_id.saturating_add(num_eras))
     }
 
     /// Returns whether the validator with the given public key is bonded in that era.
@@ -302,18 +309,22 @@ impl EraSupervisor {
     /// Updates `next_executed_height` based on the given block header, and unpauses consensus if
     /// block execution has caught up with finalization.
     #[allow(clippy::integer_arithmetic)] // Block height should never reach u64::MAX.
-    fn executed_block(&mut self, block_header: &BlockHeader) {
+    fn executed_block(&mut self, block_header: &BlockHeader) -> Effects<Event> {
         self.next_executed_height = self.next_executed_height.max(block_header.height() + 1);
-        self.update_consensus_pause();
+        self.update_consensus_pause()
     }
 
     /// Pauses or unpauses consensus: Whenever the last executed block is too far behind the last
     /// finalized block, we suspend consensus.
-    fn update_consensus_pause(&mut self) {
-        let paused = self
+    ///
+    /// Raises an alert when pausing.
+    fn update_consensus_pause(&mut self) -> Effects<Event> {
+        let backlog = self
             .next_block_height
-            .saturating_sub(self.next_executed_height)
-            > self.config.highway.max_execution_delay;
+            .saturating_sub(self.next_executed_height);
+        let max_execution_delay = self.config.highway.max_execution_delay;
+        let paused = backlog > max_execution_delay;
+        self.metrics.execution_backlog(backlog, paused);
         match self.open_eras.get_mut(&self.current_era) {
             Some(era) => era.set_paused(paused),
             None => error!(
@@ -321,6 +332,23 @@ impl EraSupervisor {
                 "current era not initialized"
             ),
         }
+        let was_paused = mem::replace(&mut self.paused, paused);
+        if paused && !was_paused {
+            warn!(
+                %backlog,
+                %max_execution_delay,
+                "execution is lagging behind; pausing consensus"
+            );
+            let alert = Alert::ExecutionBacklog {
+                backlog,
+                max_execution_delay,
+            };
+            return alerts::send(alert).ignore();
+        }
+        if was_paused && !paused {
+            info!(%backlog, "execution caught up; resuming consensus");
+        }
+        Effects::new()
     }
 
     /// Initializes a new era. The switch blocks must contain the most recent `auction_delay + 1`
@@ -333,14 +361,36 @@ impl EraSupervisor {
     ) -> Effects<Event> {
         match self.create_new_era(switch_blocks) {
             Ok((era_id, outcomes)) => {
-                self.handle_consensus_outcomes(effect_builder, rng, era_id, outcomes)
+                let mut effects =
+                    self.handle_consensus_outcomes(effect_builder, rng, era_id, outcomes);
+                // Let the networking layer know the validators as soon as the era starts, so it
+                // can connect to them before the first messages need to be sent.
+                if era_id == self.current_era {
+                    if let Some(era) = self.open_eras.get(&era_id) {
+                        let validators = era.validators().keys().cloned().collect();
+                        effects.extend(
+                            effect_builder
+                                .announce_era_validators(era_id, validators)
+                                .ignore(),
+                        );
+                    }
+                }
+                effects
+            }
+            Err(err) => {
+                let alert = Alert::EraTransitionFailed {
+                    error: err.to_string(),
+                };
+                alerts::send(alert)
+                    .then(move |_| {
+                        fatal!(
+                            effect_builder,
+                            "failed to create era; this is a bug: {:?}",
+                            err,
+                        )
+                    })
+                    .ignore()
             }
-            Err(err) => fatal!(
-                effect_builder,
-                "failed to create era; this is a bug: {:?}",
-                err,
-            )
-            .ignore(),
         }
     }
 
@@ -471,10 +521,13 @@ impl EraSupervisor {
             validators.clone(),
         );
         let _ = self.open_eras.insert(era_id, era);
+        let value_spill_file = self.value_spill_file(&instance_id);
+        self.era_mut(era_id)
+            .consensus
+            .enable_value_spilling(value_spill_file);
 
         // Activate the era if this node was already running when the era began, it is still
         // ongoing based on its minimum duration, and we are one of the validators.
-        let our_id = self.public_signing_key.clone();
         if self.current_era > era_id {
             trace!(
                 era = era_id.value(),
@@ -486,18 +539,22 @@ impl EraSupervisor {
             self.metrics.current_era.set(era_id.value() as i64);
             self.next_block_height = self.next_block_height.max(start_height);
             outcomes.extend(self.era_mut(era_id).consensus.handle_is_current(now));
-            if !self.era(era_id).validators().contains_key(&our_id) {
-                info!(era = era_id.value(), %our_id, "not voting; not a validator");
-            } else {
-                info!(era = era_id.value(), %our_id, "start voting");
-                let secret = Keypair::new(self.secret_signing_key.clone(), our_id.clone());
-                let unit_hash_file = self.unit_file(&instance_id);
-                outcomes.extend(self.era_mut(era_id).consensus.activate_validator(
-                    our_id,
-                    secret,
-                    now,
-                    Some(unit_hash_file),
-                ))
+            match self.validator_keys.clone() {
+                None => info!(era = era_id.value(), "not voting; observer mode"),
+                Some((_, our_id)) if !self.era(era_id).validators().contains_key(&our_id) => {
+                    info!(era = era_id.value(), %our_id, "not voting; not a validator");
+                }
+                Some((secret_signing_key, our_id)) => {
+                    info!(era = era_id.value(), %our_id, "start voting");
+                    let secret = Keypair::new(secret_signing_key, our_id.clone());
+                    let unit_hash_file = self.unit_file(&instance_id);
+                    outcomes.extend(self.era_mut(era_id).consensus.activate_validator(
+                        our_id,
+                        secret,
+                        now,
+                        unit_hash_file,
+                    ))
+                }
             };
         }
 
@@ -528,12 +585,14 @@ impl EraSupervisor {
             if let Some(obsolete_era_id) = evidence_only_era_id.checked_sub(1) {
                 if let Some(era) = self.open_eras.remove(&obsolete_era_id) {
                     trace!(era = obsolete_era_id.value(), "removing obsolete era");
-                    match fs::remove_file(self.unit_file(era.consensus.instance_id())) {
-                        Ok(_) => {}
-                        Err(err) => match err.kind() {
-                            io::ErrorKind::NotFound => {}
-                            err => warn!(?err, "could not delete unit hash file"),
-                        },
+                    if let Some(unit_file) = self.unit_file(era.consensus.instance_id()) {
+                        match fs::remove_file(unit_file) {
+                            Ok(_) => {}
+                            Err(err) => match err.kind() {
+                                io::ErrorKind::NotFound => {}
+                                err => warn!(?err, "could not delete unit hash file"),
+                            },
+                        }
                     }
                 }
             }
@@ -542,13 +601,21 @@ impl EraSupervisor {
         Ok((era_id, outcomes))
     }
 
-    /// Returns the path to the era's unit file.
-    fn unit_file(&self, instance_id: &Digest) -> PathBuf {
-        self.unit_files_folder.join(format!(
+    /// Returns the path to the era's unit file, or `None` in observer mode.
+    fn unit_file(&self, instance_id: &Digest) -> Option<PathBuf> {
+        let (_, public_signing_key) = self.validator_keys.as_ref()?;
+        Some(self.unit_files_folder.join(format!(
             "unit_{:?}_{}.dat",
             instance_id,
-            self.public_signing_key.to_hex()
-        ))
+            public_signing_key.to_hex()
+        )))
+    }
+
+    /// Returns the path to the file the values of the era's older blocks are moved to if they
+    /// exceed the configured memory budget.
+    fn value_spill_file(&self, instance_id: &Digest) -> PathBuf {
+        self.unit_files_folder
+            .join(format!("values_{:?}.dat", instance_id))
     }
 
     /// Applies `f` to the consensus protocol of the specified era.
@@ -668,22 +735,19 @@ impl EraSupervisor {
         effect_builder: EffectBuilder<REv>,
         block_header: BlockHeader,
     ) -> Effects<Event> {
-        let our_pk = self.public_signing_key.clone();
-        let our_sk = self.secret_signing_key.clone();
         let era_id = block_header.era_id();
-        self.executed_block(&block_header);
-        let mut effects = if self.is_validator_in(&our_pk, era_id) {
-            effect_builder
+        let mut effects = self.executed_block(&block_header);
+        effects.extend(match self.validator_keys.clone() {
+            Some((our_sk, our_pk)) if self.is_validator_in(&our_pk, era_id) => effect_builder
                 .announce_created_finality_signature(FinalitySignature::new(
                     block_header.hash(),
                     era_id,
                     &our_sk,
                     our_pk,
                 ))
-                .ignore()
-        } else {
-            Effects::new()
-        };
+                .ignore(),
+            _ => Effects::new(),
+        });
         if era_id < self.current_era {
             trace!(era = era_id.value(), "executed block in old era");
             return effects;
@@ -764,6 +828,13 @@ impl EraSupervisor {
                 "invalid consensus value; disconnecting from the sender"
             );
             effects.extend(self.disconnect(effect_builder, sender));
+        } else {
+            // Let the block proposer know, so it doesn't propose the same deploys again.
+            effects.extend(
+                effect_builder
+                    .announce_proposed_block(proposed_block.clone())
+                    .ignore(),
+            );
         }
         if self
             .open_eras
@@ -909,11 +980,17 @@ impl EraSupervisor {
                 era.add_accusations(value.accusations());
                 // If this is the era's last block, it contains rewards. Everyone who is accused in
                 // the block or seen as equivocating via the consensus protocol gets faulty.
-                let report = terminal_block_data.map(|tbd| EraReport {
-                    rewards: tbd.rewards,
-                    equivocators: era.accusations(),
-                    inactive_validators: tbd.inactive_validators,
-                });
+                let (report, maybe_missed_rounds) = match terminal_block_data {
+                    Some(tbd) => {
+                        let report = EraReport {
+                            rewards: tbd.rewards,
+                            equivocators: era.accusations(),
+                            inactive_validators: tbd.inactive_validators,
+                        };
+                        (Some(report), Some(tbd.missed_rounds))
+                    }
+                    None => (None, None),
+                };
                 let proposed_block = Arc::try_unwrap(value).unwrap_or_else(|arc| (*arc).clone());
                 let finalized_approvals: HashMap<_, _> = proposed_block
                     .deploys()
@@ -941,17 +1018,30 @@ impl EraSupervisor {
                     "finalized block"
                 );
                 self.metrics.finalized_block(&finalized_block);
+                let participation = self
+                    .era(era_id)
+                    .consensus
+                    .round_participation(Timestamp::now());
+                self.metrics.round_participation(era_id, participation);
                 // Announce the finalized block.
                 let mut effects = effect_builder
                     .announce_finalized_block(finalized_block.clone())
                     .ignore();
+                // The missed rounds are not part of the era report, so we store them separately.
+                if let Some(missed_rounds) = maybe_missed_rounds {
+                    effects.extend(
+                        effect_builder
+                            .put_era_missed_rounds_to_storage(era_id, missed_rounds)
+                            .ignore(),
+                    );
+                }
                 self.next_block_height = self.next_block_height.max(finalized_block.height() + 1);
                 // Request execution of the finalized block.
                 effects.extend(
                     execute_finalized_block(effect_builder, finalized_approvals, finalized_block)
                         .ignore(),
                 );
-                self.update_consensus_pause();
+                effects.extend(self.update_consensus_pause());
                 effects
             }
             ProtocolOutcome::ValidateConsensusValue {
@@ -1036,8 +1126,12 @@ impl EraSupervisor {
                     })
                 })
                 .collect(),
-            ProtocolOutcome::WeAreFaulty => Default::default(),
-            ProtocolOutcome::DoppelgangerDetected => Default::default(),
+            ProtocolOutcome::WeAreFaulty => {
+                alerts::send(Alert::OwnEquivocation { era_id }).ignore()
+            }
+            ProtocolOutcome::DoppelgangerDetected => {
+                alerts::send(Alert::DoppelgangerDetected { era_id }).ignore()
+            }
             ProtocolOutcome::FttExceeded => effect_builder
                 .set_timeout(Duration::from_millis(FTT_EXCEEDED_SHUTDOWN_DELAY_MILLIS))
                 .then(move |_| fatal!(effect_builder, "too many faulty validators"))
@@ -1070,7 +1164,10 @@ impl EraSupervisor {
         &self,
         responder: Responder<Option<(PublicKey, Option<TimeDiff>)>>,
     ) -> Effects<Event> {
-        let public_key = self.public_signing_key.clone();
+        let public_key = match &self.validator_keys {
+            Some((_, public_key)) => public_key.clone(),
+            None => return responder.respond(None).ignore(),
+        };
         let round_length = self
             .open_eras
             .get(&self.current_era)
@@ -1104,13 +1201,33 @@ impl EraSupervisor {
     pub(crate) fn current_era(&self) -> EraId {
         self.current_era
     }
+
+    /// Returns the estimated heap memory used by the era supervisor, with the open eras broken
+    /// down into their consensus protocol states and the remaining per-era data.
+    pub(crate) fn estimate_memory_usage(&self) -> ComponentMemoryUsage {
+        let mut usage = ComponentMemoryUsage::of(self);
+        usage.parts.remove("open_eras");
+        for (era_id, era) in &self.open_eras {
+            let consensus = era.estimate_consensus_heap_size();
+            let other = era.estimate_heap_size().saturating_sub(consensus);
+            usage
+                .parts
+                .insert(format!("{} consensus", era_id), consensus);
+            usage.parts.insert(format!("{} other", era_id), other);
+        }
+        usage
+    }
 }
 
 #[cfg(test)]
 impl EraSupervisor {
     /// Returns this node's validator key.
     pub(crate) fn public_key(&self) -> &PublicKey {
-        &self.public_signing_key
+        let (_, public_key) = self
+            .validator_keys
+            .as_ref()
+            .expect("should not run in observer mode");
+        public_key
     }
 }
 
@@ -1175,7 +1292,7 @@ async fn execute_finalized_block<REv>(
     // if the block exists in storage, it either has been executed before, or we fast synced to a
     // higher block - skip execution
     if effect_builder
-        .block_header_exists(finalized_block.height())
+        .block_header_exists(BlockHeight::from(finalized_block.height()))
         .await
     {
         return;
@@ -1274,7 +1391,11 @@ where
 
     let sender_for_validate_block: NodeId = sender;
     let valid = effect_builder
-        .validate_block(sender_for_validate_block, proposed_block.clone())
+        .validate_block(
+            sender_for_validate_block,
+            proposed_block_era_id,
+            proposed_block.clone(),
+        )
         .await;
 
     Event::ResolveValidity(ResolveValidity {
@@ -157,6 +157,11 @@ impl Era {
     pub(crate) fn set_paused(&mut self, paused: bool) {
         self.consensus.set_paused(paused);
     }
+
+    /// Returns the estimated heap memory used by the era's consensus protocol state.
+    pub(crate) fn estimate_consensus_heap_size(&self) -> usize {
+        consensus_heap_size(self.consensus.as_ref())
+    }
 }
 
 impl DataSize for Era {
@@ -178,33 +183,7 @@ impl DataSize for Era {
             validators,
         } = self;
 
-        // `DataSize` cannot be made object safe due its use of associated constants. We implement
-        // it manually here, downcasting the consensus protocol as a workaround.
-
-        let consensus_heap_size = {
-            let any_ref = consensus.as_any();
-
-            if let Some(highway) = any_ref.downcast_ref::<HighwayProtocol<ClContext>>() {
-                if *CASPER_ENABLE_DETAILED_CONSENSUS_METRICS {
-                    let detailed = (*highway).estimate_detailed_heap_size();
-                    match serde_json::to_string(&detailed) {
-                        Ok(encoded) => debug!(%encoded, "consensus memory metrics"),
-                        Err(err) => warn!(%err, "error encoding consensus memory metrics"),
-                    }
-                    detailed.total()
-                } else {
-                    (*highway).estimate_heap_size()
-                }
-            } else {
-                warn!(
-                    "could not downcast consensus protocol to \
-                    HighwayProtocol<ClContext> to determine heap allocation size"
-                );
-                0
-            }
-        };
-
-        consensus_heap_size
+        consensus_heap_size(consensus.as_ref())
             .saturating_add(start_time.estimate_heap_size())
             .saturating_add(start_height.estimate_heap_size())
             .saturating_add(validation_states.estimate_heap_size())
@@ -214,3 +193,30 @@ impl DataSize for Era {
             .saturating_add(validators.estimate_heap_size())
     }
 }
+
+/// Returns the estimated heap memory used by the consensus protocol instance.
+///
+/// `DataSize` cannot be made object safe due its use of associated constants, so the consensus
+/// protocol is downcast as a workaround.
+fn consensus_heap_size(consensus: &dyn ConsensusProtocol<ClContext>) -> usize {
+    let any_ref = consensus.as_any();
+
+    if let Some(highway) = any_ref.downcast_ref::<HighwayProtocol<ClContext>>() {
+        if *CASPER_ENABLE_DETAILED_CONSENSUS_METRICS {
+            let detailed = (*highway).estimate_detailed_heap_size();
+            match serde_json::to_string(&detailed) {
+                Ok(encoded) => debug!(%encoded, "consensus memory metrics"),
+                Err(err) => warn!(%err, "error encoding consensus memory metrics"),
+            }
+            detailed.total()
+        } else {
+            (*highway).estimate_heap_size()
+        }
+    } else {
+        warn!(
+            "could not downcast consensus protocol to \
+            HighwayProtocol<ClContext> to determine heap allocation size"
+        );
+        0
+    }
+}
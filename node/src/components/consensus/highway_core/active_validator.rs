@@ -323,7 +323,7 @@ impl<C: Context> ActiveValidator<C> {
             None => vec![],
             Some(parent_hash) => iter::once(parent_hash)
                 .chain(state.ancestor_hashes(parent_hash))
-                .map(|bhash| state.block(bhash).value.clone())
+                .map(|bhash| state.block_value(bhash))
                 .collect(),
         };
         let block_context = BlockContext::new(timestamp, ancestor_values);
@@ -68,7 +68,7 @@ impl<C: Context> FinalityDetector<C> {
                 .is_terminal_block(bhash)
                 .then(|| Self::create_terminal_block_data(bhash, unit, highway));
             let finalized_block = FinalizedBlock {
-                value: block.value.clone(),
+                value: state.block_value(bhash),
                 timestamp: unit.timestamp,
                 relative_height: block.height,
                 terminal_block_data,
@@ -167,8 +167,8 @@ impl<C: Context> FinalityDetector<C> {
         self.ftt
     }
 
-    /// Creates the information for the terminal block: which validators were inactive, and how
-    /// rewards should be distributed.
+    /// Creates the information for the terminal block: which validators were inactive, how many
+    /// rounds each of them missed, and how rewards should be distributed.
     fn create_terminal_block_data(
         bhash: &C::Hash,
         unit: &Unit<C>,
@@ -183,6 +183,12 @@ impl<C: Context> FinalityDetector<C> {
         let rewards_iter = rewards.enumerate();
         let rewards = rewards_iter.map(|(vidx, r)| (to_id(vidx), *r)).collect();
 
+        let missed_rounds = rewards::compute_missed_rounds(state, bhash);
+        let missed_rounds_iter = missed_rounds.enumerate();
+        let missed_rounds = missed_rounds_iter
+            .map(|(vidx, count)| (to_id(vidx), *count))
+            .collect();
+
         // Report inactive validators, but only if they had sufficient time to create a unit, i.e.
         // if at least one maximum-length round passed between the first and last block.
         // Safe to unwrap: Ancestor at height 0 always exists.
@@ -198,6 +204,7 @@ impl<C: Context> FinalityDetector<C> {
         TerminalBlockData {
             rewards,
             inactive_validators,
+            missed_rounds,
         }
     }
 }
@@ -110,6 +110,27 @@ fn compute_rewards_for<C: Context>(
         .collect()
 }
 
+/// Returns the number of rounds each validator missed, as seen from the block `bhash`.
+///
+/// Only rounds in which an ancestor of `bhash` was proposed are considered. A validator missed such
+/// a round if it was assigned to it but did not create any unit in it.
+pub(crate) fn compute_missed_rounds<C: Context>(
+    state: &State<C>,
+    bhash: &C::Hash,
+) -> ValidatorMap<u64> {
+    let panorama = &state.unit(bhash).panorama;
+    let mut missed_rounds = ValidatorMap::from(vec![0u64; panorama.len()]);
+    for proposal_hash in state.ancestor_hashes(bhash) {
+        let r_id = state.unit(proposal_hash).round_id();
+        for (vidx, obs) in panorama.enumerate() {
+            if let RoundParticipation::No = round_participation(state, obs, r_id) {
+                missed_rounds[vidx] = missed_rounds[vidx].saturating_add(1);
+            }
+        }
+    }
+    missed_rounds
+}
+
 /// Information about how a validator participated in a particular round.
 #[derive(Debug, PartialEq)]
 enum RoundParticipation<'a, C: Context> {
@@ -189,6 +210,26 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn compute_missed_rounds_test() -> Result<(), AddUnitError<TestContext>> {
+        // Bob cannot propose, so Alice is the leader of every round.
+        let mut state = State::new(&[Weight(5), Weight(4)], test_params(0), vec![], vec![BOB]);
+
+        // Round 0: Alice proposes, Bob confirms.
+        let p0 = add_unit!(state, ALICE, 0, 4u8, 0x1; N, N)?;
+        let b0 = add_unit!(state, BOB, 1, 4u8, None; p0, N)?;
+
+        // Round 16: Alice proposes, Bob doesn't participate.
+        let p16 = add_unit!(state, ALICE, 16, 4u8, 0x2; p0, b0)?;
+
+        // Round 32: Alice proposes, Bob doesn't participate.
+        let p32 = add_unit!(state, ALICE, 32, 4u8, 0x3; p16, b0)?;
+
+        let missed_rounds = compute_missed_rounds(&state, &p32);
+        assert_eq!(ValidatorMap::from(vec![0, 2]), missed_rounds);
+        Ok(())
+    }
+
     // To keep the form of the reward formula, we spell out Carol's weight 1.
     #[allow(clippy::identity_op)]
     #[test]
@@ -5,7 +5,7 @@ pub(crate) use vertex::{
     Dependency, Endorsements, HashedWireUnit, Ping, SignedWireUnit, Vertex, WireUnit,
 };
 
-use std::path::PathBuf;
+use std::{io, path::PathBuf};
 
 use datasize::DataSize;
 use thiserror::Error;
@@ -484,6 +484,12 @@ impl<C: Context> Highway<C> {
         }
     }
 
+    /// Moves the consensus values of older blocks to a file at the given path whenever their
+    /// estimated size in memory exceeds `budget` bytes.
+    pub(crate) fn enable_value_spilling(&mut self, path: PathBuf, budget: usize) -> io::Result<()> {
+        self.state.enable_value_spilling(path, budget)
+    }
+
     /// Drops all state other than evidence.
     pub(crate) fn retain_evidence_only(&mut self) {
         self.deactivate_validator();
@@ -4,6 +4,7 @@ mod panorama;
 mod params;
 mod tallies;
 mod unit;
+mod value_store;
 mod weight;
 
 #[cfg(test)]
@@ -21,7 +22,8 @@ pub(super) use unit::Unit;
 use std::{
     borrow::Borrow,
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
-    iter,
+    io, iter,
+    path::PathBuf,
 };
 
 use datasize::DataSize;
@@ -48,6 +50,7 @@ use crate::{
 };
 use block::Block;
 use tallies::Tallies;
+use value_store::ValueStore;
 
 // TODO: The restart mechanism only persists and loads our own latest unit, so that we don't
 // equivocate after a restart. It doesn't yet persist our latest endorsed units, so we could
@@ -166,6 +169,8 @@ where
     /// introduce a new block don't have their own entry here.
     #[data_size(with = ds::hashmap_sample)]
     blocks: HashMap<C::Hash, Block<C>>,
+    /// The consensus values of all blocks, by block hash.
+    values: ValueStore<C>,
     /// List of faulty validators and their type of fault.
     /// Every validator that has an equivocation in `units` must have an entry here, but there can
     /// be additional entries for other kinds of faults.
@@ -254,6 +259,7 @@ impl<C: Context> State<C> {
             cumulative_w_leaders,
             units: HashMap::new(),
             blocks: HashMap::new(),
+            values: ValueStore::new(),
             faults,
             can_propose,
             panorama,
@@ -413,6 +419,24 @@ impl<C: Context> State<C> {
         self.maybe_block(hash).expect("block hash must exist")
     }
 
+    /// Returns the consensus value of the block contained in the unit with the given hash, if
+    /// present.
+    pub(crate) fn maybe_block_value(&self, hash: &C::Hash) -> Option<C::ConsensusValue> {
+        self.values.get(hash)
+    }
+
+    /// Returns the consensus value of the block contained in the unit with the given hash. Panics
+    /// if not found.
+    pub(crate) fn block_value(&self, hash: &C::Hash) -> C::ConsensusValue {
+        self.maybe_block_value(hash).expect("block hash must exist")
+    }
+
+    /// Moves the consensus values of the lowest blocks to a file at the given path whenever their
+    /// estimated size in memory exceeds `budget` bytes.
+    pub(crate) fn enable_value_spilling(&mut self, path: PathBuf, budget: usize) -> io::Result<()> {
+        self.values.enable_spilling(path, budget)
+    }
+
     /// Returns the complete protocol state's latest panorama.
     pub(crate) fn panorama(&self) -> &Panorama<C> {
         &self.panorama
@@ -473,7 +497,8 @@ impl<C: Context> State<C> {
         let fork_choice = self.fork_choice(&wunit.panorama).cloned();
         let (unit, maybe_value) = Unit::new(swunit, fork_choice.as_ref(), self);
         if let Some(value) = maybe_value {
-            let block = Block::new(fork_choice, value, self);
+            let block = Block::new(fork_choice, self);
+            self.values.insert(hash, block.height, value);
             self.blocks.insert(hash, block);
         }
         self.add_ping(unit.creator, unit.timestamp);
@@ -683,8 +708,7 @@ impl<C: Context> State<C> {
         instance_id: C::InstanceId,
     ) -> Option<SignedWireUnit<C>> {
         let unit = self.maybe_unit(hash)?.clone();
-        let maybe_block = self.maybe_block(hash);
-        let value = maybe_block.map(|block| block.value.clone());
+        let value = self.maybe_block_value(hash);
         let endorsed = unit.claims_endorsed().cloned().collect();
         let wunit = WireUnit {
             panorama: unit.panorama.clone(),
@@ -964,6 +988,7 @@ impl<C: Context> State<C> {
     pub(crate) fn retain_evidence_only(&mut self) {
         self.units.clear();
         self.blocks.clear();
+        self.values.clear();
         for obs in self.panorama.iter_mut() {
             if obs.is_correct() {
                 *obs = Observation::None;
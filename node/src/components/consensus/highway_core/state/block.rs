@@ -12,8 +12,6 @@ where
 {
     /// The total number of ancestors, i.e. the height in the blockchain.
     pub(crate) height: u64,
-    /// The payload, e.g. a list of transactions.
-    pub(crate) value: C::ConsensusValue,
     /// A skip list index of the block's ancestors.
     ///
     /// For every `p = 1 << i` that divides `height`, this contains an `i`-th entry pointing to the
@@ -22,14 +20,12 @@ where
 }
 
 impl<C: Context> Block<C> {
-    /// Creates a new block with the given parent and values. Panics if parent does not exist.
-    pub(crate) fn new(
-        parent_hash: Option<C::Hash>,
-        value: C::ConsensusValue,
-        state: &State<C>,
-    ) -> Block<C> {
+    /// Creates a new block with the given parent. Panics if parent does not exist.
+    ///
+    /// The block's payload, e.g. a list of transactions, is kept in the state separately.
+    pub(crate) fn new(parent_hash: Option<C::Hash>, state: &State<C>) -> Block<C> {
         let (parent, mut skip_idx) = match parent_hash {
-            None => return Block::initial(value),
+            None => return Block::initial(),
             Some(hash) => (state.block(&hash), vec![hash]),
         };
         // In a trillion years, we need to make block height u128.
@@ -39,11 +35,7 @@ impl<C: Context> Block<C> {
             let ancestor = state.block(&skip_idx[i]);
             skip_idx.push(ancestor.skip_idx[i]);
         }
-        Block {
-            height,
-            value,
-            skip_idx,
-        }
+        Block { height, skip_idx }
     }
 
     /// Returns the block's parent, or `None` if it has height 0.
@@ -51,10 +43,9 @@ impl<C: Context> Block<C> {
         self.skip_idx.first()
     }
 
-    fn initial(value: C::ConsensusValue) -> Block<C> {
+    fn initial() -> Block<C> {
         Block {
             height: 0,
-            value,
             skip_idx: vec![],
         }
     }
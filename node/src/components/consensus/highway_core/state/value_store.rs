@@ -0,0 +1,268 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs::{self, File},
+    io, mem,
+    os::unix::fs::FileExt,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use datasize::DataSize;
+use serde::Serialize;
+use tracing::{debug, warn};
+
+use crate::{components::consensus::traits::Context, utils::ds};
+
+/// The consensus values of the blocks in the protocol state, by block hash.
+///
+/// If spilling is enabled, the values of the lowest blocks are moved to a file on disk whenever
+/// the estimated size of the values kept in memory exceeds the configured budget. Only their
+/// positions in the file are kept in memory then, and they are read back when accessed.
+#[derive(Clone, DataSize, Debug, Serialize)]
+pub(super) struct ValueStore<C>
+where
+    C: Context,
+{
+    /// The values kept in memory.
+    #[data_size(with = ds::hashmap_sample)]
+    in_memory: HashMap<C::Hash, C::ConsensusValue>,
+    /// The heights and hashes of the blocks whose values are kept in memory, lowest first.
+    #[serde(skip)]
+    heights: BTreeSet<(u64, C::Hash)>,
+    /// The estimated size of the values kept in memory, in bytes.
+    #[serde(skip)]
+    in_memory_size: usize,
+    /// The file values are moved to, if spilling is enabled.
+    #[serde(skip)]
+    spill: Option<Spill<C>>,
+}
+
+/// The values that were moved to disk, and the file they were written to.
+#[derive(Clone, DataSize, Debug)]
+struct Spill<C>
+where
+    C: Context,
+{
+    /// The estimated size in bytes the values kept in memory must not exceed.
+    budget: usize,
+    /// The file the values are appended to.
+    #[data_size(skip)]
+    file: Arc<SpillFile>,
+    /// The offset and length in the file of every value moved there, by block hash.
+    #[data_size(with = ds::hashmap_sample)]
+    index: HashMap<C::Hash, (u64, usize)>,
+}
+
+/// A file holding serialized consensus values, which is removed once no longer referenced.
+#[derive(Debug)]
+struct SpillFile {
+    path: PathBuf,
+    file: File,
+    /// The length of the file, including regions reserved by writes in progress.
+    len: AtomicU64,
+}
+
+impl SpillFile {
+    /// Creates the file at the given path, replacing any file left over from a previous run.
+    fn create(path: PathBuf) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)?;
+        Ok(SpillFile {
+            path,
+            file,
+            len: AtomicU64::new(0),
+        })
+    }
+
+    /// Appends the bytes to the file and returns the offset they were written at.
+    fn append(&self, bytes: &[u8]) -> io::Result<u64> {
+        let offset = self.len.fetch_add(bytes.len() as u64, Ordering::SeqCst);
+        self.file.write_all_at(bytes, offset)?;
+        Ok(offset)
+    }
+
+    /// Reads `len` bytes starting at the given offset.
+    fn read_at(&self, offset: u64, len: usize) -> io::Result<Vec<u8>> {
+        let mut bytes = vec![0; len];
+        self.file.read_exact_at(&mut bytes, offset)?;
+        Ok(bytes)
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        if let Err(err) = fs::remove_file(&self.path) {
+            warn!(path = %self.path.display(), %err, "could not remove consensus value spill file");
+        }
+    }
+}
+
+impl<C: Context> ValueStore<C> {
+    /// Creates an empty store which keeps all values in memory.
+    pub(super) fn new() -> Self {
+        ValueStore {
+            in_memory: HashMap::new(),
+            heights: BTreeSet::new(),
+            in_memory_size: 0,
+            spill: None,
+        }
+    }
+
+    /// Enables moving values to a file at the given path once the estimated size of the values
+    /// kept in memory exceeds `budget` bytes.
+    pub(super) fn enable_spilling(&mut self, path: PathBuf, budget: usize) -> io::Result<()> {
+        if self.spill.is_some() {
+            return Ok(());
+        }
+        let file = Arc::new(SpillFile::create(path)?);
+        self.spill = Some(Spill {
+            budget,
+            file,
+            index: HashMap::new(),
+        });
+        self.spill_excess();
+        Ok(())
+    }
+
+    /// Adds the value of the block with the given hash and height.
+    pub(super) fn insert(&mut self, hash: C::Hash, height: u64, value: C::ConsensusValue) {
+        self.in_memory_size = self.in_memory_size.saturating_add(estimated_size(&value));
+        self.heights.insert((height, hash));
+        self.in_memory.insert(hash, value);
+        self.spill_excess();
+    }
+
+    /// Returns the value of the block with the given hash, reading it from disk if necessary.
+    ///
+    /// Panics if the value was moved to disk but cannot be read back.
+    pub(super) fn get(&self, hash: &C::Hash) -> Option<C::ConsensusValue> {
+        if let Some(value) = self.in_memory.get(hash) {
+            return Some(value.clone());
+        }
+        let spill = self.spill.as_ref()?;
+        let (offset, len) = *spill.index.get(hash)?;
+        let value = spill
+            .file
+            .read_at(offset, len)
+            .and_then(|bytes| {
+                bincode::deserialize(&bytes)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            })
+            .unwrap_or_else(|err| {
+                panic!(
+                    "could not read spilled consensus value of block {}: {}",
+                    hash, err
+                )
+            });
+        Some(value)
+    }
+
+    /// Removes all values, and the spill file if there is one.
+    pub(super) fn clear(&mut self) {
+        self.in_memory.clear();
+        self.heights.clear();
+        self.in_memory_size = 0;
+        self.spill = None;
+    }
+
+    /// Moves the values of the lowest blocks to disk until the ones in memory fit the budget.
+    fn spill_excess(&mut self) {
+        let spill = match self.spill.as_mut() {
+            Some(spill) => spill,
+            None => return,
+        };
+        let mut spilled = 0;
+        while self.in_memory_size > spill.budget {
+            let (height, hash) = match self.heights.iter().next() {
+                Some(entry) => *entry,
+                None => break,
+            };
+            let value = &self.in_memory[&hash];
+            let bytes = match bincode::serialize(value) {
+                Ok(bytes) => bytes,
+                Err(err) => {
+                    warn!(%hash, %err, "could not serialize consensus value; keeping it in memory");
+                    break;
+                }
+            };
+            let offset = match spill.file.append(&bytes) {
+                Ok(offset) => offset,
+                Err(err) => {
+                    warn!(
+                        %hash,
+                        %err,
+                        "could not spill consensus value to disk; keeping it in memory"
+                    );
+                    break;
+                }
+            };
+            spill.index.insert(hash, (offset, bytes.len()));
+            self.in_memory_size = self.in_memory_size.saturating_sub(estimated_size(value));
+            self.heights.remove(&(height, hash));
+            self.in_memory.remove(&hash);
+            spilled += 1;
+        }
+        if spilled > 0 {
+            debug!(
+                spilled,
+                total_spilled = spill.index.len(),
+                in_memory = self.in_memory.len(),
+                "spilled consensus values to disk"
+            );
+        }
+    }
+}
+
+/// Returns the estimated memory used by the value, in bytes.
+fn estimated_size<T: DataSize>(value: &T) -> usize {
+    mem::size_of::<T>().saturating_add(value.estimate_heap_size())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::consensus::highway_core::state::tests::TestContext;
+
+    #[test]
+    fn should_spill_lowest_values_above_budget() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("values.dat");
+        let mut store: ValueStore<TestContext> = ValueStore::new();
+        store.insert(10, 0, 100);
+        store.insert(11, 1, 101);
+        store.insert(12, 2, 102);
+        assert_eq!(store.in_memory.len(), 3);
+
+        // Two `u32` values fit the budget; the lowest one has to go to disk.
+        store.enable_spilling(path.clone(), 8).unwrap();
+        assert!(path.exists());
+        assert_eq!(store.in_memory.len(), 2);
+        assert!(!store.in_memory.contains_key(&10));
+
+        store.insert(13, 3, 103);
+        assert_eq!(store.in_memory.len(), 2);
+        assert!(!store.in_memory.contains_key(&11));
+        for (hash, value) in [(10, 100), (11, 101), (12, 102), (13, 103)] {
+            assert_eq!(store.get(&hash), Some(value));
+        }
+        assert_eq!(store.get(&14), None);
+
+        // Clones share the file, which is only removed once the last of them is cleared.
+        let mut clone = store.clone();
+        store.clear();
+        assert_eq!(store.get(&10), None);
+        assert_eq!(clone.get(&10), Some(100));
+        clone.clear();
+        assert!(!path.exists());
+    }
+}
@@ -1,6 +1,6 @@
-use prometheus::{Gauge, IntGauge, Registry};
+use prometheus::{Gauge, GaugeVec, IntGauge, Opts, Registry};
 
-use casper_types::Timestamp;
+use casper_types::{EraId, PublicKey, Timestamp};
 
 use crate::{types::FinalizedBlock, unregister_metric};
 
@@ -17,6 +17,14 @@ pub(super) struct Metrics {
     time_of_last_finalized_block: IntGauge,
     /// The Current era.
     pub(super) current_era: IntGauge,
+    /// The number of finalized blocks that have not been executed yet.
+    execution_backlog: IntGauge,
+    /// Whether consensus is paused because execution is lagging behind.
+    consensus_paused: IntGauge,
+    /// The fraction of recent rounds in which each validator's units were received on time.
+    validator_round_participation: GaugeVec,
+    /// The era the `validator_round_participation` gauges refer to.
+    round_participation_era: Option<EraId>,
     /// registry component.
     registry: Registry,
 }
@@ -38,17 +46,39 @@ impl Metrics {
             "timestamp of the most recently finalized block",
         )?;
         let current_era = IntGauge::new("current_era", "the current era")?;
+        let execution_backlog = IntGauge::new(
+            "execution_backlog",
+            "the number of finalized blocks that have not been executed yet",
+        )?;
+        let consensus_paused = IntGauge::new(
+            "consensus_paused",
+            "1 if consensus is paused because block execution is lagging behind, 0 otherwise",
+        )?;
+        let validator_round_participation = GaugeVec::new(
+            Opts::new(
+                "validator_round_participation",
+                "the fraction of recent rounds in which the validator's units were received on time",
+            ),
+            &["validator"],
+        )?;
         registry.register(Box::new(finalization_time.clone()))?;
         registry.register(Box::new(finalized_block_count.clone()))?;
         registry.register(Box::new(current_era.clone()))?;
         registry.register(Box::new(time_of_last_proposed_block.clone()))?;
         registry.register(Box::new(time_of_last_finalized_block.clone()))?;
+        registry.register(Box::new(execution_backlog.clone()))?;
+        registry.register(Box::new(consensus_paused.clone()))?;
+        registry.register(Box::new(validator_round_participation.clone()))?;
         Ok(Metrics {
             finalization_time,
             finalized_block_count,
             time_of_last_proposed_block,
             time_of_last_finalized_block,
             current_era,
+            execution_backlog,
+            consensus_paused,
+            validator_round_participation,
+            round_participation_era: None,
             registry: registry.clone(),
         })
     }
@@ -68,6 +98,30 @@ impl Metrics {
         self.time_of_last_proposed_block
             .set(Timestamp::now().millis() as i64);
     }
+
+    /// Updates the metrics tracking finalized blocks that have not been executed yet.
+    pub(super) fn execution_backlog(&mut self, backlog: u64, paused: bool) {
+        self.execution_backlog.set(backlog as i64);
+        self.consensus_paused.set(i64::from(paused));
+    }
+
+    /// Updates the per-validator round participation of the current era.
+    pub(super) fn round_participation(
+        &mut self,
+        era_id: EraId,
+        participation: Vec<(PublicKey, f64)>,
+    ) {
+        // Drop the gauges of the previous era's validators.
+        if self.round_participation_era != Some(era_id) {
+            self.validator_round_participation.reset();
+            self.round_participation_era = Some(era_id);
+        }
+        for (validator, fraction) in participation {
+            self.validator_round_participation
+                .with_label_values(&[&validator.to_hex()])
+                .set(fraction);
+        }
+    }
 }
 
 impl Drop for Metrics {
@@ -77,5 +131,8 @@ impl Drop for Metrics {
         unregister_metric!(self.registry, self.current_era);
         unregister_metric!(self.registry, self.time_of_last_finalized_block);
         unregister_metric!(self.registry, self.time_of_last_proposed_block);
+        unregister_metric!(self.registry, self.execution_backlog);
+        unregister_metric!(self.registry, self.consensus_paused);
+        unregister_metric!(self.registry, self.validator_round_participation);
     }
 }
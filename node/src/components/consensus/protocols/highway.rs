@@ -1,5 +1,6 @@
 pub(crate) mod config;
 mod participation;
+mod round_participation;
 mod round_success_meter;
 #[cfg(test)]
 mod tests;
@@ -45,7 +46,7 @@ use crate::{
     NodeRng,
 };
 
-use self::round_success_meter::RoundSuccessMeter;
+use self::{round_participation::RoundParticipation, round_success_meter::RoundSuccessMeter};
 
 /// Never allow more than this many units in a piece of evidence for conflicting endorsements,
 /// even if eras are longer than this.
@@ -80,6 +81,8 @@ where
     highway: Highway<C>,
     /// A tracker for whether we are keeping up with the current round exponent or not.
     round_success_meter: RoundSuccessMeter<C>,
+    /// A tracker for which validators' units are received on time.
+    round_participation: RoundParticipation,
     synchronizer: Synchronizer<C>,
     pvv_cache: HashMap<Dependency<C>, PreValidatedVertex<C>>,
     evidence_only: bool,
@@ -198,6 +201,10 @@ impl<C: Context + 'static> HighwayProtocol<C> {
             finality_detector: FinalityDetector::new(ftt),
             highway,
             round_success_meter,
+            round_participation: RoundParticipation::new(
+                validators_count,
+                config.highway.round_success_meter.num_rounds_to_consider,
+            ),
             synchronizer: Synchronizer::new(validators_count, instance_id),
             pvv_cache: Default::default(),
             evidence_only: false,
@@ -253,6 +260,7 @@ impl<C: Context + 'static> HighwayProtocol<C> {
             AvEffect::NewVertex(vv) => {
                 self.log_unit_size(vv.inner(), "sending new unit");
                 self.calculate_round_exponent(&vv, now);
+                self.record_round_participation(&vv, now);
                 self.process_new_vertex(vv)
             }
             AvEffect::ScheduleTimer(timestamp) => {
@@ -351,7 +359,7 @@ impl<C: Context + 'static> HighwayProtocol<C> {
             let fork_choice = self.highway.state().fork_choice(panorama);
             if value.needs_validation() {
                 self.log_proposal(vertex, "requesting proposal validation");
-                let ancestor_values = self.ancestors(fork_choice).cloned().collect();
+                let ancestor_values = self.ancestors(fork_choice).collect();
                 let block_context = BlockContext::new(timestamp, ancestor_values);
                 let proposed_block = ProposedBlock::new(value.clone(), block_context);
                 if self
@@ -402,6 +410,19 @@ impl<C: Context + 'static> HighwayProtocol<C> {
         self.highway.set_round_exp(new_round_exp);
     }
 
+    /// If the vertex is a unit, records whether it was received before the end of its round.
+    fn record_round_participation(&mut self, vv: &ValidVertex<C>, now: Timestamp) {
+        if let Some(swunit) = vv.inner().unit() {
+            let wunit = swunit.wire_unit();
+            self.round_participation.record_unit(
+                wunit.creator,
+                wunit.timestamp,
+                wunit.round_exp,
+                now,
+            );
+        }
+    }
+
     fn add_valid_vertex(&mut self, vv: ValidVertex<C>, now: Timestamp) -> ProtocolOutcomes<C> {
         if self.evidence_only && !vv.inner().is_evidence() {
             error!(vertex = ?vv.inner(), "unexpected vertex in evidence-only mode");
@@ -425,6 +446,7 @@ impl<C: Context + 'static> HighwayProtocol<C> {
         // round has finished, we now have all the vertices from that round in the state, and no
         // newer ones.
         self.calculate_round_exponent(&vv, now);
+        self.record_round_participation(&vv, now);
         let av_effects = self.highway.add_valid_vertex(vv, now);
         // Once vertex is added to the state, we can remove it from the cache.
         self.pvv_cache.remove(&vertex_id);
@@ -441,12 +463,12 @@ impl<C: Context + 'static> HighwayProtocol<C> {
     fn ancestors<'a>(
         &'a self,
         mut maybe_hash: Option<&'a C::Hash>,
-    ) -> impl Iterator<Item = &'a C::ConsensusValue> {
+    ) -> impl Iterator<Item = C::ConsensusValue> + 'a {
         iter::from_fn(move || {
             let hash = maybe_hash.take()?;
-            let block = self.highway.state().block(hash);
-            let value = Some(&block.value);
-            maybe_hash = block.parent();
+            let state = self.highway.state();
+            let value = Some(state.block_value(hash));
+            maybe_hash = state.block(hash).parent();
             value
         })
     }
@@ -1033,6 +1055,16 @@ where
         self.evidence_only = true;
     }
 
+    fn enable_value_spilling(&mut self, path: PathBuf) {
+        let budget = self.config.block_value_memory_budget;
+        if budget == 0 {
+            return;
+        }
+        if let Err(err) = self.highway.enable_value_spilling(path, budget) {
+            warn!(%err, "could not create block value spill file; keeping all values in memory");
+        }
+    }
+
     fn has_evidence(&self, vid: &C::ValidatorId) -> bool {
         self.highway.has_evidence(vid)
     }
@@ -1091,4 +1123,25 @@ where
     fn next_round_length(&self) -> Option<TimeDiff> {
         self.highway.next_round_length()
     }
+
+    fn round_participation(&self, now: Timestamp) -> Vec<(C::ValidatorId, f64)> {
+        let state = self.highway.state();
+        let start = state.params().start_timestamp();
+        self.highway
+            .validators()
+            .enumerate_ids()
+            .filter_map(|(idx, v_id)| {
+                // Measure rounds with the validator's latest round length.
+                let round_exp = state.panorama()[idx]
+                    .correct()
+                    .map_or(state.params().init_round_exp(), |hash| {
+                        state.unit(hash).round_exp
+                    });
+                let fraction = self
+                    .round_participation
+                    .fraction(idx, round_exp, start, now)?;
+                Some((v_id.clone(), fraction))
+            })
+            .collect()
+    }
 }
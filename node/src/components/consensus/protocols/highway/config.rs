@@ -32,6 +32,11 @@ pub struct Config {
     /// Limits requests per validator in panorama - in order to get a total number of
     /// requests, multiply by # of validators.
     pub max_request_batch_size: usize,
+    /// The estimated size in bytes up to which the consensus values of an era's blocks are kept
+    /// in memory. Beyond that, the values of the lowest blocks are moved to a file on disk. If
+    /// `0`, all values are kept in memory.
+    #[serde(default)]
+    pub block_value_memory_budget: usize,
     pub round_success_meter: RSMConfig,
 }
 
@@ -47,7 +52,25 @@ impl Default for Config {
             max_execution_delay: 3,
             max_requests_for_vertex: 5,
             max_request_batch_size: 20,
+            block_value_memory_budget: 0,
             round_success_meter: RSMConfig::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_keep_all_block_values_in_memory_if_budget_not_configured() {
+        let mut encoded = toml::Value::try_from(Config::default()).unwrap();
+        encoded
+            .as_table_mut()
+            .unwrap()
+            .remove("block_value_memory_budget")
+            .unwrap();
+        let config: Config = encoded.try_into().unwrap();
+        assert_eq!(config.block_value_memory_budget, 0);
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::VecDeque;
+
+use datasize::DataSize;
+
+use casper_types::Timestamp;
+
+use crate::components::consensus::highway_core::{
+    state,
+    validators::{ValidatorIndex, ValidatorMap},
+};
+
+/// Tracks for every validator in which of the recent rounds their units were received on time,
+/// i.e. before the end of the round they belong to.
+#[derive(DataSize, Debug)]
+pub(crate) struct RoundParticipation {
+    /// The number of most recent rounds to consider.
+    num_rounds: u64,
+    /// The IDs of the recent rounds in which a unit by the validator was received on time, oldest
+    /// first.
+    on_time_rounds: ValidatorMap<VecDeque<Timestamp>>,
+}
+
+impl RoundParticipation {
+    /// Creates a tracker for the given number of validators, considering the `num_rounds` most
+    /// recent rounds.
+    pub(crate) fn new(validator_count: usize, num_rounds: u64) -> Self {
+        RoundParticipation {
+            num_rounds,
+            on_time_rounds: ValidatorMap::from(vec![VecDeque::new(); validator_count]),
+        }
+    }
+
+    /// Records a unit by `creator` with the given timestamp and round exponent, received at `now`.
+    pub(crate) fn record_unit(
+        &mut self,
+        creator: ValidatorIndex,
+        timestamp: Timestamp,
+        round_exp: u8,
+        now: Timestamp,
+    ) {
+        let round_id = state::round_id(timestamp, round_exp);
+        if now >= round_id + state::round_len(round_exp) {
+            return; // The unit arrived after its round had ended.
+        }
+        let rounds = &mut self.on_time_rounds[creator];
+        if rounds.back().map_or(false, |last| *last >= round_id) {
+            return; // We already counted this round.
+        }
+        rounds.push_back(round_id);
+        while rounds.len() as u64 > self.num_rounds {
+            rounds.pop_front();
+        }
+    }
+
+    /// Returns the fraction of the most recent completed rounds in which the validator's units
+    /// were received on time, or `None` if no round has been completed since `start`.
+    ///
+    /// Rounds are measured with the validator's current round exponent.
+    pub(crate) fn fraction(
+        &self,
+        idx: ValidatorIndex,
+        round_exp: u8,
+        start: Timestamp,
+        now: Timestamp,
+    ) -> Option<f64> {
+        let round_len = state::round_len(round_exp).millis();
+        let current_round = state::round_id(now, round_exp).millis();
+        let first_round = state::round_id(start, round_exp).millis();
+        let completed = current_round
+            .saturating_sub(first_round)
+            .checked_div(round_len)?
+            .min(self.num_rounds);
+        if completed == 0 {
+            return None;
+        }
+        let window_start = current_round.saturating_sub(completed.saturating_mul(round_len));
+        let on_time = self
+            .on_time_rounds
+            .get(idx)?
+            .iter()
+            .filter(|round_id| (window_start..current_round).contains(&round_id.millis()))
+            .count() as u64;
+        // If the validator switched to shorter rounds, more than `completed` can be on time.
+        Some(on_time.min(completed) as f64 / completed as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::integer_arithmetic)] // Overflows in tests would panic anyway.
+
+    use super::*;
+
+    #[test]
+    fn should_count_rounds_with_units_received_on_time() {
+        // Rounds of 2^4 = 16 milliseconds.
+        let round_exp = 4;
+        let start = Timestamp::from(0);
+        let alice = ValidatorIndex(0);
+        let bob = ValidatorIndex(1);
+        let mut participation = RoundParticipation::new(2, 3);
+
+        // Alice's units always arrive within their round; Bob's unit in round 16 is late.
+        let mut record_round = |round_id: u64| {
+            let timestamp = Timestamp::from(round_id + 10);
+            participation.record_unit(alice, timestamp, round_exp, timestamp);
+            participation.record_unit(alice, timestamp, round_exp, Timestamp::from(round_id + 12));
+            let received = if round_id == 16 { 40 } else { round_id + 11 };
+            participation.record_unit(bob, timestamp, round_exp, Timestamp::from(received));
+        };
+        record_round(0);
+        record_round(16);
+
+        // No round has been completed yet.
+        assert_eq!(
+            participation.fraction(alice, round_exp, start, Timestamp::from(5)),
+            None
+        );
+        // Rounds 0 and 16 are completed.
+        let now = Timestamp::from(40);
+        assert_eq!(
+            participation.fraction(alice, round_exp, start, now),
+            Some(1.0)
+        );
+        assert_eq!(
+            participation.fraction(bob, round_exp, start, now),
+            Some(0.5)
+        );
+
+        let mut record_round = |round_id: u64| {
+            let timestamp = Timestamp::from(round_id + 10);
+            participation.record_unit(alice, timestamp, round_exp, timestamp);
+            participation.record_unit(bob, timestamp, round_exp, Timestamp::from(round_id + 11));
+        };
+        record_round(32);
+        record_round(48);
+
+        // Only the three most recent completed rounds, 16 to 48, are considered.
+        let now = Timestamp::from(70);
+        assert_eq!(
+            participation.fraction(alice, round_exp, start, now),
+            Some(1.0)
+        );
+        let fraction = participation.fraction(bob, round_exp, start, now).unwrap();
+        assert!((fraction - 2.0 / 3.0).abs() < 1e-9);
+    }
+}
@@ -1,35 +1,49 @@
 //! Contract Runtime component.
 
+mod auction_cache;
+mod commit_rules;
 mod config;
 mod error;
+mod genesis;
+mod governance;
 mod metrics;
 mod operations;
+mod request_scheduler;
 mod types;
 
 use std::{
     collections::BTreeMap,
     fmt::{self, Debug, Display, Formatter},
     path::Path,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
     time::Instant,
 };
 
 use datasize::DataSize;
 use derive_more::From;
+use futures::future;
 use lmdb::DatabaseFlags;
-use once_cell::sync::Lazy;
 use prometheus::Registry;
 use serde::Serialize;
 use thiserror::Error;
+use tokio::sync::mpsc;
 use tracing::{debug, error, info, trace};
 
 use casper_execution_engine::{
     core::engine_state::{
-        self, genesis::GenesisError, ChainspecRegistry, EngineConfig, EngineState, GenesisSuccess,
-        GetEraValidatorsError, GetEraValidatorsRequest, SystemContractRegistry, UpgradeConfig,
+        self, EngineConfig, EngineState, GenesisSuccess, GetEraValidatorsError,
+        GetEraValidatorsRequest, ModuleCache, SystemContractRegistry, UpgradeConfig,
         UpgradeSuccess,
     },
-    shared::{newtypes::CorrelationId, system_config::SystemConfig, wasm_config::WasmConfig},
+    shared::{
+        fee_handling::{FeeHandling, RefundHandling},
+        newtypes::CorrelationId,
+        system_config::SystemConfig,
+        wasm_config::WasmConfig,
+    },
     storage::{
         global_state::lmdb::LmdbGlobalState,
         transaction_source::lmdb::LmdbEnvironment,
@@ -50,16 +64,25 @@ use crate::{
     },
     fatal,
     protocol::Message,
-    types::{BlockHash, BlockHeader, Chainspec, ChainspecRawBytes, Deploy, FinalizedBlock},
+    types::{
+        chainspec::DeployConfig, BlockHash, BlockHeader, BlockHeight, Chainspec, ChainspecRawBytes,
+        Deploy, FinalizedBlock,
+    },
     NodeRng,
 };
+use auction_cache::AuctionCache;
+pub(crate) use commit_rules::CommitRules;
 pub(crate) use config::Config;
-pub(crate) use error::{BlockExecutionError, ConfigError};
+pub(crate) use error::{BlockExecutionError, ConfigError, GovernanceError};
+pub use genesis::{build_genesis_state, GenesisStateError};
+pub(crate) use governance::Governance;
 use metrics::Metrics;
 pub use operations::execute_finalized_block;
-pub(crate) use types::{BlockAndExecutionEffects, EraValidatorsRequest};
+pub(crate) use request_scheduler::RequestPriority;
+use request_scheduler::RequestScheduler;
+pub(crate) use types::{BlockAndExecutionEffects, DeployExecutionProgress, EraValidatorsRequest};
 
-use self::operations::execute_only;
+use self::operations::{execute_only, execute_only_with_gas_profile};
 
 use super::fetcher::FetchedOrNotFound;
 
@@ -74,32 +97,6 @@ pub(crate) enum ContractRuntimeError {
     FailedToRetrieveTrieById(#[source] engine_state::Error),
 }
 
-/// Maximum number of resource intensive tasks that can be run in parallel.
-///
-/// TODO: Fine tune this constant to the machine executing the node.
-const MAX_PARALLEL_INTENSIVE_TASKS: usize = 4;
-
-/// Semaphore enforcing maximum number of parallel resource intensive tasks.
-static INTENSIVE_TASKS_SEMAPHORE: Lazy<tokio::sync::Semaphore> =
-    Lazy::new(|| tokio::sync::Semaphore::new(MAX_PARALLEL_INTENSIVE_TASKS));
-
-/// Asynchronously runs a resource intensive task.
-/// At most `MAX_PARALLEL_INTENSIVE_TASKS` are being run in parallel at any time.
-///
-/// The task is a closure that takes no arguments and returns a value.
-/// This function returns a future for that value.
-async fn run_intensive_task<T, V>(task: T) -> V
-where
-    T: 'static + Send + FnOnce() -> V,
-    V: 'static + Send,
-{
-    // This will never panic since the semaphore is never closed.
-    let _permit = INTENSIVE_TASKS_SEMAPHORE.acquire().await.unwrap();
-    tokio::task::spawn_blocking(task)
-        .await
-        .expect("task panicked")
-}
-
 #[derive(DataSize, Debug, Clone, Serialize)]
 /// Wrapper for speculative execution prestate.
 pub struct SpeculativeExecutionState {
@@ -191,13 +188,27 @@ impl Display for Event {
 pub(crate) struct ContractRuntime {
     execution_pre_state: Arc<Mutex<ExecutionPreState>>,
     engine_state: Arc<EngineState<LmdbGlobalState>>,
+    /// The protocol version preceding the current one, along with an engine state configured as
+    /// set by its chainspec, used to execute blocks created before the most recent upgrade.
+    previous_engine_state: Option<(ProtocolVersion, Arc<EngineState<LmdbGlobalState>>)>,
     metrics: Arc<Metrics>,
+    /// Runs resource intensive requests by priority.
+    request_scheduler: Arc<RequestScheduler>,
+    /// Caches the bids and era validators read from global state.
+    auction_cache: Arc<AuctionCache>,
+    /// Reads the deploy limits from the on-chain configuration contract, if enabled.
+    governance: Option<Arc<Governance>>,
     protocol_version: ProtocolVersion,
 
     /// Finalized blocks waiting for their pre-state hash to start executing.
     exec_queue: ExecQueue,
     /// Cached instance of a [`SystemContractRegistry`].
     system_contract_registry: Option<SystemContractRegistry>,
+    /// The number of finalized blocks currently being executed.
+    #[data_size(skip)]
+    executing_blocks: Arc<AtomicUsize>,
+    /// Whether finalized blocks are still executed, i.e. the node is not shutting down.
+    is_executing_blocks: bool,
 }
 
 impl Debug for ContractRuntime {
@@ -325,16 +336,23 @@ impl ContractRuntime {
                 .ignore(),
             ContractRuntimeRequest::Query {
                 query_request,
+                priority,
                 responder,
             } => {
-                trace!(?query_request, "query");
+                trace!(?query_request, %priority, "query");
                 let engine_state = Arc::clone(&self.engine_state);
                 let metrics = Arc::clone(&self.metrics);
+                let request_scheduler = Arc::clone(&self.request_scheduler);
                 async move {
-                    let correlation_id = CorrelationId::new();
-                    let start = Instant::now();
-                    let result = engine_state.run_query(correlation_id, query_request);
-                    metrics.run_query.observe(start.elapsed().as_secs_f64());
+                    let result = request_scheduler
+                        .run(priority, move || {
+                            let correlation_id = CorrelationId::new();
+                            let start = Instant::now();
+                            let result = engine_state.run_query(correlation_id, query_request);
+                            metrics.run_query.observe(start.elapsed().as_secs_f64());
+                            result
+                        })
+                        .await;
                     trace!(?result, "query result");
                     responder.respond(result).await
                 }
@@ -342,20 +360,27 @@ impl ContractRuntime {
             }
             ContractRuntimeRequest::GetBalance {
                 balance_request,
+                priority,
                 responder,
             } => {
-                trace!(?balance_request, "balance");
+                trace!(?balance_request, %priority, "balance");
                 let engine_state = Arc::clone(&self.engine_state);
                 let metrics = Arc::clone(&self.metrics);
+                let request_scheduler = Arc::clone(&self.request_scheduler);
                 async move {
-                    let correlation_id = CorrelationId::new();
-                    let start = Instant::now();
-                    let result = engine_state.get_purse_balance(
-                        correlation_id,
-                        balance_request.state_hash(),
-                        balance_request.purse_uref(),
-                    );
-                    metrics.get_balance.observe(start.elapsed().as_secs_f64());
+                    let result = request_scheduler
+                        .run(priority, move || {
+                            let correlation_id = CorrelationId::new();
+                            let start = Instant::now();
+                            let result = engine_state.get_purse_balance(
+                                correlation_id,
+                                balance_request.state_hash(),
+                                balance_request.purse_uref(),
+                            );
+                            metrics.get_balance.observe(start.elapsed().as_secs_f64());
+                            result
+                        })
+                        .await;
                     trace!(?result, "balance result");
                     responder.respond(result).await
                 }
@@ -371,19 +396,28 @@ impl ContractRuntime {
                 trace!(era=%era_id, public_key = %validator_key, "is validator bonded request");
                 let engine_state = Arc::clone(&self.engine_state);
                 let metrics = Arc::clone(&self.metrics);
+                let request_scheduler = Arc::clone(&self.request_scheduler);
+                let auction_cache = Arc::clone(&self.auction_cache);
                 let system_contract_registry = self.system_contract_registry.clone();
                 let request = GetEraValidatorsRequest::new(state_root_hash, protocol_version);
                 async move {
-                    let correlation_id = CorrelationId::new();
-                    let start = Instant::now();
-                    let era_validators = engine_state.get_era_validators(
-                        correlation_id,
-                        system_contract_registry,
-                        request,
-                    );
-                    metrics
-                        .get_validator_weights
-                        .observe(start.elapsed().as_secs_f64());
+                    let era_validators = request_scheduler
+                        .run(RequestPriority::Validation, move || {
+                            auction_cache.get_era_validators(state_root_hash, || {
+                                let correlation_id = CorrelationId::new();
+                                let start = Instant::now();
+                                let era_validators = engine_state.get_era_validators(
+                                    correlation_id,
+                                    system_contract_registry,
+                                    request,
+                                );
+                                metrics
+                                    .get_validator_weights
+                                    .observe(start.elapsed().as_secs_f64());
+                                era_validators
+                            })
+                        })
+                        .await;
                     trace!(?era_validators, "is validator bonded result");
                     let is_bonded =
                         era_validators.and_then(|validator_map| match validator_map.get(&era_id) {
@@ -398,20 +432,30 @@ impl ContractRuntime {
                 trace!(?request, "get era validators request");
                 let engine_state = Arc::clone(&self.engine_state);
                 let metrics = Arc::clone(&self.metrics);
+                let request_scheduler = Arc::clone(&self.request_scheduler);
+                let auction_cache = Arc::clone(&self.auction_cache);
                 let system_contract_registry = self.system_contract_registry.clone();
                 // Increment the counter to track the amount of times GetEraValidators was
                 // requested.
                 async move {
-                    let correlation_id = CorrelationId::new();
-                    let start = Instant::now();
-                    let era_validators = engine_state.get_era_validators(
-                        correlation_id,
-                        system_contract_registry,
-                        request.into(),
-                    );
-                    metrics
-                        .get_era_validators
-                        .observe(start.elapsed().as_secs_f64());
+                    let era_validators = request_scheduler
+                        .run(RequestPriority::Validation, move || {
+                            let request: GetEraValidatorsRequest = request.into();
+                            auction_cache.get_era_validators(request.state_hash(), || {
+                                let correlation_id = CorrelationId::new();
+                                let start = Instant::now();
+                                let era_validators = engine_state.get_era_validators(
+                                    correlation_id,
+                                    system_contract_registry,
+                                    request,
+                                );
+                                metrics
+                                    .get_era_validators
+                                    .observe(start.elapsed().as_secs_f64());
+                                era_validators
+                            })
+                        })
+                        .await;
                     trace!(?era_validators, "get era validators response");
                     responder.respond(era_validators).await
                 }
@@ -490,21 +534,24 @@ impl ContractRuntime {
                     ?deploys,
                     "execute block request"
                 );
-                let engine_state = Arc::clone(&self.engine_state);
+                let engine_state = self.engine_state_for(protocol_version);
                 let metrics = Arc::clone(&self.metrics);
+                let request_scheduler = Arc::clone(&self.request_scheduler);
                 async move {
-                    let result = run_intensive_task(move || {
-                        execute_finalized_block(
-                            engine_state.as_ref(),
-                            Some(metrics),
-                            protocol_version,
-                            execution_pre_state,
-                            finalized_block,
-                            deploys,
-                            transfers,
-                        )
-                    })
-                    .await;
+                    let result = request_scheduler
+                        .run(RequestPriority::BlockExecution, move || {
+                            execute_finalized_block(
+                                engine_state.as_ref(),
+                                Some(metrics),
+                                None,
+                                protocol_version,
+                                execution_pre_state,
+                                finalized_block,
+                                deploys,
+                                transfers,
+                            )
+                        })
+                        .await;
                     trace!(?result, "execute block response");
                     responder.respond(result).await
                 }
@@ -515,28 +562,47 @@ impl ContractRuntime {
                 deploys,
                 transfers,
             } => {
+                if !self.is_executing_blocks {
+                    debug!(
+                        height = finalized_block.height(),
+                        "not executing finalized block, as the node is shutting down"
+                    );
+                    return Effects::new();
+                }
                 info!(?finalized_block, "enqueuing finalized block for execution");
                 let mut effects = Effects::new();
                 let engine_state = Arc::clone(&self.engine_state);
                 let metrics = Arc::clone(&self.metrics);
+                let request_scheduler = Arc::clone(&self.request_scheduler);
+                let auction_cache = Arc::clone(&self.auction_cache);
+                let governance = self.governance.clone();
                 let exec_queue = Arc::clone(&self.exec_queue);
                 let execution_pre_state = Arc::clone(&self.execution_pre_state);
                 let protocol_version = self.protocol_version;
                 if self.execution_pre_state.lock().unwrap().next_block_height
                     == finalized_block.height()
                 {
+                    let executing_blocks = Arc::clone(&self.executing_blocks);
+                    executing_blocks.fetch_add(1, Ordering::SeqCst);
                     effects.extend(
-                        Self::execute_finalized_block_or_requeue(
-                            engine_state,
-                            metrics,
-                            exec_queue,
-                            execution_pre_state,
-                            effect_builder,
-                            protocol_version,
-                            finalized_block,
-                            deploys,
-                            transfers,
-                        )
+                        async move {
+                            Self::execute_finalized_block_or_requeue(
+                                engine_state,
+                                metrics,
+                                request_scheduler,
+                                auction_cache,
+                                governance,
+                                exec_queue,
+                                execution_pre_state,
+                                effect_builder,
+                                protocol_version,
+                                finalized_block,
+                                deploys,
+                                transfers,
+                            )
+                            .await;
+                            executing_blocks.fetch_sub(1, Ordering::SeqCst);
+                        }
                         .ignore(),
                     )
                 } else {
@@ -554,16 +620,102 @@ impl ContractRuntime {
                 trace!(?get_bids_request, "get bids request");
                 let engine_state = Arc::clone(&self.engine_state);
                 let metrics = Arc::clone(&self.metrics);
+                let request_scheduler = Arc::clone(&self.request_scheduler);
+                let auction_cache = Arc::clone(&self.auction_cache);
                 async move {
-                    let correlation_id = CorrelationId::new();
-                    let start = Instant::now();
-                    let result = engine_state.get_bids(correlation_id, get_bids_request);
-                    metrics.get_bids.observe(start.elapsed().as_secs_f64());
+                    let result = request_scheduler
+                        .run(RequestPriority::Query, move || {
+                            auction_cache.get_bids(get_bids_request.state_hash(), || {
+                                let correlation_id = CorrelationId::new();
+                                let start = Instant::now();
+                                let result =
+                                    engine_state.get_bids(correlation_id, get_bids_request);
+                                metrics.get_bids.observe(start.elapsed().as_secs_f64());
+                                result
+                            })
+                        })
+                        .await;
                     trace!(?result, "get bids result");
                     responder.respond(result).await
                 }
                 .ignore()
             }
+            ContractRuntimeRequest::GetBalances {
+                get_balances_request,
+                responder,
+            } => {
+                trace!(?get_balances_request, "get balances request");
+                let engine_state = Arc::clone(&self.engine_state);
+                let metrics = Arc::clone(&self.metrics);
+                let request_scheduler = Arc::clone(&self.request_scheduler);
+                async move {
+                    let result = request_scheduler
+                        .run(RequestPriority::Query, move || {
+                            let correlation_id = CorrelationId::new();
+                            let start = Instant::now();
+                            let result =
+                                engine_state.get_balances(correlation_id, get_balances_request);
+                            metrics.get_balances.observe(start.elapsed().as_secs_f64());
+                            result
+                        })
+                        .await;
+                    trace!(?result, "get balances result");
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
+            ContractRuntimeRequest::GetDictionaryItems {
+                get_dictionary_items_request,
+                responder,
+            } => {
+                trace!(?get_dictionary_items_request, "get dictionary items request");
+                let engine_state = Arc::clone(&self.engine_state);
+                let metrics = Arc::clone(&self.metrics);
+                let request_scheduler = Arc::clone(&self.request_scheduler);
+                async move {
+                    let result = request_scheduler
+                        .run(RequestPriority::Query, move || {
+                            let correlation_id = CorrelationId::new();
+                            let start = Instant::now();
+                            let result = engine_state
+                                .get_dictionary_items(correlation_id, get_dictionary_items_request);
+                            metrics
+                                .get_dictionary_items
+                                .observe(start.elapsed().as_secs_f64());
+                            result
+                        })
+                        .await;
+                    trace!(?result, "get dictionary items result");
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
+            ContractRuntimeRequest::GetStateDiff {
+                get_state_diff_request,
+                responder,
+            } => {
+                trace!(?get_state_diff_request, "get state diff request");
+                let engine_state = Arc::clone(&self.engine_state);
+                let metrics = Arc::clone(&self.metrics);
+                let request_scheduler = Arc::clone(&self.request_scheduler);
+                async move {
+                    let result = request_scheduler
+                        .run(RequestPriority::Query, move || {
+                            let correlation_id = CorrelationId::new();
+                            let start = Instant::now();
+                            let result =
+                                engine_state.get_state_diff(correlation_id, get_state_diff_request);
+                            metrics
+                                .get_state_diff
+                                .observe(start.elapsed().as_secs_f64());
+                            result
+                        })
+                        .await;
+                    trace!(?result, "get state diff result");
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
             ContractRuntimeRequest::FindMissingDescendantTrieKeys {
                 trie_key,
                 responder,
@@ -589,11 +741,78 @@ impl ContractRuntime {
                 responder,
             } => {
                 let engine_state = Arc::clone(&self.engine_state);
+                let metrics = Arc::clone(&self.metrics);
+                let request_scheduler = Arc::clone(&self.request_scheduler);
                 async move {
-                    let result = run_intensive_task(move || {
-                        execute_only(engine_state.as_ref(), execution_prestate, (*deploy).into())
-                    })
-                    .await;
+                    let start = Instant::now();
+                    let result = request_scheduler
+                        .run(RequestPriority::Query, move || {
+                            execute_only(
+                                engine_state.as_ref(),
+                                execution_prestate,
+                                (*deploy).into(),
+                            )
+                        })
+                        .await;
+                    metrics
+                        .speculative_execution
+                        .observe(start.elapsed().as_secs_f64());
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
+            ContractRuntimeRequest::ReplayBlock {
+                protocol_version,
+                execution_pre_state,
+                finalized_block,
+                deploys,
+                transfers,
+                responder,
+            } => {
+                debug!(?finalized_block, "replay block request");
+                let engine_state = self.engine_state_for(protocol_version);
+                let request_scheduler = Arc::clone(&self.request_scheduler);
+                async move {
+                    let result = request_scheduler
+                        .run(RequestPriority::Query, move || {
+                            execute_finalized_block(
+                                engine_state.as_ref(),
+                                None,
+                                None,
+                                protocol_version,
+                                execution_pre_state,
+                                finalized_block,
+                                deploys,
+                                transfers,
+                            )
+                        })
+                        .await;
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
+            ContractRuntimeRequest::ProfileDeployGas {
+                execution_prestate,
+                deploy,
+                responder,
+            } => {
+                let engine_state = Arc::clone(&self.engine_state);
+                let metrics = Arc::clone(&self.metrics);
+                let request_scheduler = Arc::clone(&self.request_scheduler);
+                async move {
+                    let start = Instant::now();
+                    let result = request_scheduler
+                        .run(RequestPriority::Query, move || {
+                            execute_only_with_gas_profile(
+                                engine_state.as_ref(),
+                                execution_prestate,
+                                (*deploy).into(),
+                            )
+                        })
+                        .await;
+                    metrics
+                        .speculative_execution
+                        .observe(start.elapsed().as_secs_f64());
                     responder.respond(result).await
                 }
                 .ignore()
@@ -615,6 +834,12 @@ impl ContractRuntime {
         minimum_delegation_amount: u64,
         strict_argument_checking: bool,
         vesting_schedule_period_millis: u64,
+        allow_separate_reward_purses: bool,
+        refund_handling: RefundHandling,
+        fee_handling: FeeHandling,
+        previous_engine_config: Option<(ProtocolVersion, EngineConfig, CommitRules)>,
+        commit_rules: CommitRules,
+        governance: Option<Governance>,
         registry: &Registry,
     ) -> Result<Self, ConfigError> {
         // TODO: This is bogus, get rid of this
@@ -638,6 +863,23 @@ impl ContractRuntime {
             DatabaseFlags::empty(),
         )?);
 
+        // The engine state of the previous protocol version shares the current one's global state
+        // store, but doesn't cache modules as these are prepared according to the engine config.
+        let previous_engine_state = match previous_engine_config {
+            Some((previous_protocol_version, previous_engine_config, previous_commit_rules)) => {
+                info!(
+                    %previous_protocol_version,
+                    "hosting execution engine config of previous protocol version"
+                );
+                let global_state =
+                    LmdbGlobalState::empty(Arc::clone(&environment), Arc::clone(&trie_store))?;
+                let engine_state = EngineState::new(global_state, previous_engine_config)
+                    .with_session_effects_check(Arc::new(previous_commit_rules));
+                Some((previous_protocol_version, Arc::new(engine_state)))
+            }
+            None => None,
+        };
+
         let global_state = LmdbGlobalState::empty(environment, trie_store)?;
         let engine_config = EngineConfig::new(
             contract_runtime_config.max_query_depth(),
@@ -646,57 +888,78 @@ impl ContractRuntime {
             minimum_delegation_amount,
             strict_argument_checking,
             vesting_schedule_period_millis,
+            allow_separate_reward_purses,
+            refund_handling,
+            fee_handling,
             wasm_config,
             system_config,
         );
 
-        let engine_state = Arc::new(EngineState::new(global_state, engine_config));
+        let engine_state = match contract_runtime_config.wasm_module_cache_size() {
+            0 => EngineState::new(global_state, engine_config),
+            max_size_bytes => EngineState::with_module_cache(
+                global_state,
+                engine_config,
+                Arc::new(ModuleCache::new(max_size_bytes)),
+            ),
+        };
+        let engine_state =
+            Arc::new(engine_state.with_session_effects_check(Arc::new(commit_rules)));
 
         let metrics = Arc::new(Metrics::new(registry)?);
+        let request_scheduler = Arc::new(RequestScheduler::new(
+            contract_runtime_config,
+            Arc::clone(&metrics),
+        ));
 
         Ok(ContractRuntime {
             execution_pre_state,
             engine_state,
+            previous_engine_state,
             metrics,
+            request_scheduler,
+            auction_cache: Arc::new(AuctionCache::new(
+                contract_runtime_config.auction_cache_size(),
+            )),
+            governance: governance.map(Arc::new),
             protocol_version,
             exec_queue: Arc::new(Mutex::new(BTreeMap::new())),
             system_contract_registry: None,
+            executing_blocks: Arc::new(AtomicUsize::new(0)),
+            is_executing_blocks: true,
         })
     }
 
+    /// Returns the engine state to execute blocks of the given protocol version with.
+    ///
+    /// Blocks of a protocol version lower than the current one are executed by the engine state of
+    /// the previous protocol version if one is hosted, and by the current engine state otherwise.
+    fn engine_state_for(
+        &self,
+        protocol_version: ProtocolVersion,
+    ) -> Arc<EngineState<LmdbGlobalState>> {
+        match &self.previous_engine_state {
+            Some((previous_protocol_version, previous_engine_state))
+                if protocol_version < self.protocol_version =>
+            {
+                debug!(
+                    %protocol_version,
+                    %previous_protocol_version,
+                    "executing with previous protocol version's engine config"
+                );
+                Arc::clone(previous_engine_state)
+            }
+            _ => Arc::clone(&self.engine_state),
+        }
+    }
+
     /// Commits a genesis request.
     fn commit_genesis(
         &self,
         chainspec: &Chainspec,
         chainspec_raw_bytes: &ChainspecRawBytes,
     ) -> Result<GenesisSuccess, engine_state::Error> {
-        let correlation_id = CorrelationId::new();
-        let genesis_config_hash = chainspec.hash();
-        let protocol_version = chainspec.protocol_config.version;
-        // Transforms a chainspec into a valid genesis config for execution engine.
-        let ee_config = chainspec.into();
-
-        let chainspec_registry = ChainspecRegistry::new_with_genesis(
-            chainspec_raw_bytes.chainspec_bytes(),
-            chainspec_raw_bytes
-                .maybe_genesis_accounts_bytes()
-                .ok_or_else(|| {
-                    error!("failed to provide genesis account bytes in commit genesis");
-                    engine_state::Error::Genesis(Box::new(
-                        GenesisError::MissingChainspecRegistryEntry,
-                    ))
-                })?,
-        );
-
-        let result = self.engine_state.commit_genesis(
-            correlation_id,
-            genesis_config_hash,
-            protocol_version,
-            &ee_config,
-            chainspec_registry,
-        );
-        self.engine_state.flush_environment()?;
-        result
+        genesis::commit_genesis(&self.engine_state, chainspec, chainspec_raw_bytes)
     }
 
     fn commit_upgrade(
@@ -733,6 +996,18 @@ impl ContractRuntime {
         result
     }
 
+    /// Returns the deploy limits read from the on-chain configuration contract in the global state
+    /// with the given root hash, or `None` if governance is disabled.
+    pub(crate) fn governed_deploy_config(
+        &self,
+        state_root_hash: Digest,
+    ) -> Result<Option<DeployConfig>, GovernanceError> {
+        self.governance
+            .as_ref()
+            .map(|governance| governance.read_deploy_config(&self.engine_state, state_root_hash))
+            .transpose()
+    }
+
     pub(crate) fn set_initial_state(
         &mut self,
         sequential_block_state: ExecutionPreState,
@@ -767,6 +1042,9 @@ impl ContractRuntime {
     async fn execute_finalized_block_or_requeue<REv>(
         engine_state: Arc<EngineState<LmdbGlobalState>>,
         metrics: Arc<Metrics>,
+        request_scheduler: Arc<RequestScheduler>,
+        auction_cache: Arc<AuctionCache>,
+        governance: Option<Arc<Governance>>,
         exec_queue: ExecQueue,
         execution_pre_state: Arc<Mutex<ExecutionPreState>>,
         effect_builder: EffectBuilder<REv>,
@@ -782,23 +1060,35 @@ impl ContractRuntime {
             + Send,
     {
         let current_execution_pre_state = execution_pre_state.lock().unwrap().clone();
-        let BlockAndExecutionEffects {
-            block,
-            execution_results,
-            maybe_step_effect_and_upcoming_era_validators,
-        } = match run_intensive_task(move || {
+        let maybe_governance = governance.map(|governance| (governance, Arc::clone(&engine_state)));
+        let (progress_sender, mut progress_receiver) = mpsc::unbounded_channel();
+        let execution = request_scheduler.run(RequestPriority::BlockExecution, move || {
             execute_finalized_block(
                 engine_state.as_ref(),
                 Some(metrics),
+                Some(progress_sender),
                 protocol_version,
                 current_execution_pre_state,
                 finalized_block,
                 deploys,
                 transfers,
             )
-        })
-        .await
-        {
+        });
+        // Announce the progress of each deploy while the block executes.  The channel is closed
+        // once execution has finished, so all progress is announced before the block itself.
+        let announce_progress = async move {
+            while let Some(progress) = progress_receiver.recv().await {
+                effect_builder
+                    .announce_deploy_execution_progress(progress)
+                    .await;
+            }
+        };
+        let BlockAndExecutionEffects {
+            block,
+            execution_results,
+            contract_events,
+            maybe_step_effect_and_upcoming_era_validators,
+        } = match future::join(execution, announce_progress).await.0 {
             Ok(block_and_execution_effects) => block_and_execution_effects,
             Err(error) => return fatal!(effect_builder, "{}", error).await,
         };
@@ -807,10 +1097,31 @@ impl ContractRuntime {
         *execution_pre_state.lock().unwrap() = new_execution_pre_state.clone();
 
         let current_era_id = block.header().era_id();
+        let next_block_era_id = block.header().next_block_era_id();
+        let state_root_hash = *block.state_root_hash();
 
-        let block_height = block.height();
+        // Announce the deploy limits for the next era before the switch block itself, so that they
+        // are in effect by the time the next era starts.
+        if let Some((governance, engine_state)) =
+            maybe_governance.filter(|_| block.header().is_switch_block())
+        {
+            let deploy_config = match request_scheduler
+                .run(RequestPriority::BlockExecution, move || {
+                    governance.read_deploy_config(&engine_state, state_root_hash)
+                })
+                .await
+            {
+                Ok(deploy_config) => deploy_config,
+                Err(error) => return fatal!(effect_builder, "{}", error).await,
+            };
+            effect_builder
+                .announce_deploy_config_updated(next_block_era_id, deploy_config)
+                .await;
+        }
+
+        let block_height = BlockHeight::from(block.height());
         effect_builder
-            .announce_new_linear_chain_block(block, execution_results)
+            .announce_new_linear_chain_block(block, execution_results, contract_events)
             .await;
 
         effect_builder.mark_block_completed(block_height).await;
@@ -824,6 +1135,8 @@ impl ContractRuntime {
                 .announce_commit_step_success(current_era_id, step_execution_journal)
                 .await;
 
+            auction_cache.insert_era_validators(state_root_hash, upcoming_era_validators.clone());
+
             effect_builder
                 .announce_upcoming_era_validators(current_era_id, upcoming_era_validators)
                 .await;
@@ -878,6 +1191,22 @@ impl ContractRuntime {
         result
     }
 
+    /// Stops executing finalized blocks, other than those already being executed, as the node is
+    /// shutting down.
+    pub(crate) fn stop_executing(&mut self) {
+        self.is_executing_blocks = false;
+    }
+
+    /// Returns whether a finalized block is currently being executed.
+    pub(crate) fn is_executing(&self) -> bool {
+        self.executing_blocks.load(Ordering::SeqCst) > 0
+    }
+
+    /// Flushes global state to disk.
+    pub(crate) fn flush(&self) -> Result<(), lmdb::Error> {
+        self.engine_state.flush_environment()
+    }
+
     /// Returns the engine state, for testing only.
     #[cfg(test)]
     pub(crate) fn engine_state(&self) -> &Arc<EngineState<LmdbGlobalState>> {
@@ -0,0 +1,188 @@
+//! Caching of the auction state read from global state.
+
+use std::{collections::VecDeque, sync::Mutex};
+
+use casper_execution_engine::core::engine_state::GetBidsResult;
+use casper_hashing::Digest;
+use casper_types::system::auction::{Bids, EraValidators};
+
+/// The most recently cached values, each under the state root hash it was read from.
+struct Entries<T> {
+    capacity: usize,
+    /// The cached values, ordered from least to most recently inserted.
+    entries: VecDeque<(Digest, T)>,
+}
+
+impl<T: Clone> Entries<T> {
+    fn new(capacity: usize) -> Self {
+        Entries {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    fn find(&self, state_root_hash: &Digest) -> Option<&T> {
+        self.entries
+            .iter()
+            .find(|(cached_state_root_hash, _)| cached_state_root_hash == state_root_hash)
+            .map(|(_, value)| value)
+    }
+
+    fn get(&self, state_root_hash: &Digest) -> Option<T> {
+        self.find(state_root_hash).cloned()
+    }
+
+    fn insert(&mut self, state_root_hash: Digest, value: T) {
+        if self.capacity == 0 || self.find(&state_root_hash).is_some() {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((state_root_hash, value));
+    }
+}
+
+/// A cache of the bids and era validators read from the auction contract's state, so that repeated
+/// requests against the same global state don't deserialize them from the trie store again.
+///
+/// As global state under a given state root hash is immutable, cached values never become stale.
+/// The values of the `capacity` most recently cached state root hashes are kept, and the era
+/// validators are cached as each switch block is executed, i.e. at every era boundary.
+pub(super) struct AuctionCache {
+    bids: Mutex<Entries<Bids>>,
+    era_validators: Mutex<Entries<EraValidators>>,
+}
+
+impl AuctionCache {
+    /// Creates a new, empty cache holding the values of up to `capacity` state root hashes.  If
+    /// `capacity` is 0, nothing is cached.
+    pub(super) fn new(capacity: usize) -> Self {
+        AuctionCache {
+            bids: Mutex::new(Entries::new(capacity)),
+            era_validators: Mutex::new(Entries::new(capacity)),
+        }
+    }
+
+    /// Returns the bids under `state_root_hash`, calling `read` to read them from global state if
+    /// they are not cached.
+    pub(super) fn get_bids<E>(
+        &self,
+        state_root_hash: Digest,
+        read: impl FnOnce() -> Result<GetBidsResult, E>,
+    ) -> Result<GetBidsResult, E> {
+        if let Some(bids) = self.bids.lock().unwrap().get(&state_root_hash) {
+            return Ok(GetBidsResult::Success { bids });
+        }
+        let result = read();
+        if let Ok(GetBidsResult::Success { bids }) = &result {
+            self.bids
+                .lock()
+                .unwrap()
+                .insert(state_root_hash, bids.clone());
+        }
+        result
+    }
+
+    /// Returns the era validators under `state_root_hash`, calling `read` to read them from global
+    /// state if they are not cached.
+    pub(super) fn get_era_validators<E>(
+        &self,
+        state_root_hash: Digest,
+        read: impl FnOnce() -> Result<EraValidators, E>,
+    ) -> Result<EraValidators, E> {
+        if let Some(era_validators) = self.era_validators.lock().unwrap().get(&state_root_hash) {
+            return Ok(era_validators);
+        }
+        let era_validators = read()?;
+        self.insert_era_validators(state_root_hash, era_validators.clone());
+        Ok(era_validators)
+    }
+
+    /// Caches the era validators under `state_root_hash`.
+    pub(super) fn insert_era_validators(
+        &self,
+        state_root_hash: Digest,
+        era_validators: EraValidators,
+    ) {
+        self.era_validators
+            .lock()
+            .unwrap()
+            .insert(state_root_hash, era_validators);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use casper_types::EraId;
+
+    use super::*;
+
+    fn era_validators(era: u64) -> EraValidators {
+        let mut era_validators = BTreeMap::new();
+        era_validators.insert(EraId::new(era), BTreeMap::new());
+        era_validators
+    }
+
+    fn read_era_validators(era: u64) -> Result<EraValidators, ()> {
+        Ok(era_validators(era))
+    }
+
+    fn not_read() -> Result<EraValidators, ()> {
+        panic!("should have been cached")
+    }
+
+    #[test]
+    fn should_cache_era_validators_of_most_recent_state_root_hashes() {
+        let cache = AuctionCache::new(2);
+        let state_root_hash = |byte: u8| Digest::hash([byte]);
+
+        cache.insert_era_validators(state_root_hash(1), era_validators(1));
+        assert_eq!(
+            cache.get_era_validators(state_root_hash(2), || read_era_validators(2)),
+            Ok(era_validators(2))
+        );
+        assert_eq!(
+            cache.get_era_validators(state_root_hash(1), not_read),
+            Ok(era_validators(1))
+        );
+        assert_eq!(
+            cache.get_era_validators(state_root_hash(2), not_read),
+            Ok(era_validators(2))
+        );
+
+        // Inserting a third entry evicts the first.
+        cache.insert_era_validators(state_root_hash(3), era_validators(3));
+        assert_eq!(
+            cache.get_era_validators(state_root_hash(1), || read_era_validators(4)),
+            Ok(era_validators(4))
+        );
+        assert_eq!(
+            cache.get_era_validators(state_root_hash(3), not_read),
+            Ok(era_validators(3))
+        );
+    }
+
+    #[test]
+    fn should_not_cache_failed_reads_or_when_disabled() {
+        let cache = AuctionCache::new(1);
+        let state_root_hash = Digest::hash([1]);
+        assert_eq!(
+            cache.get_era_validators(state_root_hash, || Err(())),
+            Err(())
+        );
+        assert_eq!(
+            cache.get_era_validators(state_root_hash, || read_era_validators(1)),
+            Ok(era_validators(1))
+        );
+
+        let cache = AuctionCache::new(0);
+        cache.insert_era_validators(state_root_hash, era_validators(1));
+        assert_eq!(
+            cache.get_era_validators(state_root_hash, || read_era_validators(2)),
+            Ok(era_validators(2))
+        );
+    }
+}
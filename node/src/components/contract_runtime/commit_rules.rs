@@ -0,0 +1,196 @@
+//! Rules enforced on the effects of each deploy's session code before they are committed to global
+//! state.
+
+use std::fmt::Debug;
+
+use itertools::Itertools;
+
+use casper_execution_engine::{
+    core::engine_state::SessionEffectsCheck,
+    shared::{additive_map::AdditiveMap, transform::Transform},
+};
+use casper_types::Key;
+
+use crate::types::chainspec::CommitRulesConfig;
+
+/// A rule which the effects of each deploy's session code must satisfy before they are committed to
+/// global state.
+///
+/// Rules must be deterministic, as every node executing a block must agree on which deploys
+/// violate them.
+pub(crate) trait CommitRule: Send + Sync + Debug {
+    /// Returns a description of the violation if `effects` break the rule.
+    fn check(&self, effects: &AdditiveMap<Key, Transform>) -> Result<(), String>;
+}
+
+/// Forbids writes to keys whose formatted string form starts with any of the given prefixes.
+///
+/// Writes to the keys managed by the system contracts, e.g. purse balances, transfers and bids, are
+/// always allowed, as session code can only write to them via the system contracts.
+#[derive(Debug)]
+pub(crate) struct ReservedKeyPrefixes(Vec<String>);
+
+impl CommitRule for ReservedKeyPrefixes {
+    fn check(&self, effects: &AdditiveMap<Key, Transform>) -> Result<(), String> {
+        // Keys are checked in order, so that all nodes report the same violation.
+        let written_keys = effects
+            .iter()
+            .filter(|(key, transform)| **transform != Transform::Identity && !is_system_key(key))
+            .map(|(key, _)| key)
+            .sorted();
+        for key in written_keys {
+            let formatted_key = key.to_formatted_string();
+            if let Some(prefix) = self
+                .0
+                .iter()
+                .find(|prefix| formatted_key.starts_with(prefix.as_str()))
+            {
+                return Err(format!(
+                    "write to {} is forbidden by reserved key prefix {}",
+                    formatted_key, prefix
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns `true` if the key is only ever written by the system contracts.
+fn is_system_key(key: &Key) -> bool {
+    match key {
+        Key::Transfer(_)
+        | Key::DeployInfo(_)
+        | Key::EraInfo(_)
+        | Key::Balance(_)
+        | Key::Bid(_)
+        | Key::Withdraw(_)
+        | Key::Unbond(_)
+        | Key::SystemContractRegistry
+        | Key::ChainspecRegistry
+        | Key::BlockEffectsRootHash { .. }
+        | Key::DeployApprovalsRootHash { .. } => true,
+        Key::Account(_) | Key::Hash(_) | Key::URef(_) | Key::Dictionary(_) => false,
+    }
+}
+
+/// The rules enforced on the effects of each deploy's session code before they are committed to
+/// global state.
+///
+/// The session effects of a deploy which break any rule are discarded, as if its session code had
+/// failed with an error giving the reason, while its payment is still collected.
+#[derive(Debug, Default)]
+pub(crate) struct CommitRules {
+    rules: Vec<Box<dyn CommitRule>>,
+}
+
+impl CommitRules {
+    /// Returns the rules set by the chainspec.
+    pub(crate) fn new(config: &CommitRulesConfig) -> Self {
+        let mut commit_rules = CommitRules::default();
+        if !config.reserved_key_prefixes.is_empty() {
+            commit_rules =
+                commit_rules.with_rule(ReservedKeyPrefixes(config.reserved_key_prefixes.clone()));
+        }
+        commit_rules
+    }
+
+    /// Adds a rule to be enforced in addition to those already set.
+    pub(crate) fn with_rule<R: CommitRule + 'static>(mut self, rule: R) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Returns a description of the first rule violation if `effects` break any rule.
+    pub(crate) fn check(&self, effects: &AdditiveMap<Key, Transform>) -> Result<(), String> {
+        self.rules.iter().try_for_each(|rule| rule.check(effects))
+    }
+}
+
+impl SessionEffectsCheck for CommitRules {
+    fn check(&self, effects: &AdditiveMap<Key, Transform>) -> Result<(), String> {
+        CommitRules::check(self, effects)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{CLValue, TransferAddr, URef};
+
+    use super::*;
+
+    fn write_to(key: Key) -> AdditiveMap<Key, Transform> {
+        let mut effects = AdditiveMap::new();
+        let _ = effects.insert(key, Transform::Write(CLValue::from_t(1u64).unwrap().into()));
+        effects
+    }
+
+    #[test]
+    fn should_forbid_writes_to_reserved_keys() {
+        let commit_rules = CommitRules::new(&CommitRulesConfig {
+            reserved_key_prefixes: vec!["hash-ff".to_string()],
+        });
+
+        assert!(commit_rules
+            .check(&write_to(Key::Hash([0xff; 32])))
+            .is_err());
+        assert!(commit_rules.check(&write_to(Key::Hash([0xfe; 32]))).is_ok());
+        assert!(commit_rules
+            .check(&write_to(Key::URef(URef::default())))
+            .is_ok());
+
+        // Reading a reserved key is allowed.
+        let mut effects = AdditiveMap::new();
+        let _ = effects.insert(Key::Hash([0xff; 32]), Transform::Identity);
+        assert!(commit_rules.check(&effects).is_ok());
+    }
+
+    #[test]
+    fn should_report_lowest_violating_key() {
+        let commit_rules = CommitRules::new(&CommitRulesConfig {
+            reserved_key_prefixes: vec!["hash-".to_string()],
+        });
+
+        let mut effects = AdditiveMap::new();
+        for byte in (0..32).rev() {
+            let _ = effects.insert(
+                Key::Hash([byte; 32]),
+                Transform::Write(CLValue::from_t(1u64).unwrap().into()),
+            );
+        }
+        let violation = commit_rules.check(&effects).unwrap_err();
+        assert!(violation.contains(&Key::Hash([0; 32]).to_formatted_string()));
+    }
+
+    #[test]
+    fn should_allow_writes_to_system_keys() {
+        let commit_rules = CommitRules::new(&CommitRulesConfig {
+            reserved_key_prefixes: vec!["balance-".to_string(), "transfer-".to_string()],
+        });
+
+        assert!(commit_rules.check(&write_to(Key::Balance([1; 32]))).is_ok());
+        assert!(commit_rules
+            .check(&write_to(Key::Transfer(TransferAddr::new([1; 32]))))
+            .is_ok());
+    }
+
+    #[test]
+    fn should_enforce_additional_rules() {
+        #[derive(Debug)]
+        struct NoUnbonds;
+
+        impl CommitRule for NoUnbonds {
+            fn check(&self, effects: &AdditiveMap<Key, Transform>) -> Result<(), String> {
+                if effects.keys().any(|key| matches!(key, Key::Unbond(_))) {
+                    return Err("unbonding is disabled".to_string());
+                }
+                Ok(())
+            }
+        }
+
+        let commit_rules = CommitRules::default().with_rule(NoUnbonds);
+        assert!(commit_rules
+            .check(&write_to(Key::Unbond(Default::default())))
+            .is_err());
+        assert!(commit_rules.check(&write_to(Key::Hash([0; 32]))).is_ok());
+    }
+}
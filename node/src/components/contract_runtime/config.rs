@@ -7,6 +7,11 @@ const DEFAULT_MAX_GLOBAL_STATE_SIZE: usize = 805_306_368_000; // 750 GiB
 const DEFAULT_MAX_READERS: u32 = 512;
 const DEFAULT_MAX_QUERY_DEPTH: u64 = 5;
 const DEFAULT_MANUAL_SYNC_ENABLED: bool = true;
+const DEFAULT_WASM_MODULE_CACHE_SIZE: usize = 67_108_864; // 64 MiB
+const DEFAULT_MAX_PARALLEL_BLOCK_EXECUTIONS: usize = 2;
+const DEFAULT_MAX_PARALLEL_VALIDATION_QUERIES: usize = 4;
+const DEFAULT_MAX_PARALLEL_QUERIES: usize = 2;
+const DEFAULT_AUCTION_CACHE_SIZE: usize = 4;
 
 /// Contract runtime configuration.
 #[derive(Clone, Copy, DataSize, Debug, Deserialize, Serialize)]
@@ -31,6 +36,31 @@ pub struct Config {
     ///
     /// Defaults to `false`.
     enable_manual_sync: Option<bool>,
+    /// The maximum total size in bytes of the Wasm modules cached after being prepared for
+    /// execution, so that deploys executing the same code skip preparing it again.  If set to 0,
+    /// modules are not cached.
+    ///
+    /// Defaults to 67,108,864 == 64 MiB.
+    wasm_module_cache_size: Option<usize>,
+    /// The maximum number of block execution requests run in parallel.
+    ///
+    /// Defaults to 2.
+    max_parallel_block_executions: Option<usize>,
+    /// The maximum number of queries made to validate incoming data, e.g. by the deploy acceptor,
+    /// run in parallel.
+    ///
+    /// Defaults to 4.
+    max_parallel_validation_queries: Option<usize>,
+    /// The maximum number of queries and speculative executions made on behalf of API clients run
+    /// in parallel.
+    ///
+    /// Defaults to 2.
+    max_parallel_queries: Option<usize>,
+    /// The number of global states, identified by their state root hashes, whose auction bids and
+    /// era validators are cached after being read.  If set to 0, they are not cached.
+    ///
+    /// Defaults to 4.
+    auction_cache_size: Option<usize>,
 }
 
 impl Config {
@@ -54,6 +84,34 @@ impl Config {
         self.enable_manual_sync
             .unwrap_or(DEFAULT_MANUAL_SYNC_ENABLED)
     }
+
+    pub(crate) fn wasm_module_cache_size(&self) -> usize {
+        self.wasm_module_cache_size
+            .unwrap_or(DEFAULT_WASM_MODULE_CACHE_SIZE)
+    }
+
+    pub(crate) fn max_parallel_block_executions(&self) -> usize {
+        self.max_parallel_block_executions
+            .unwrap_or(DEFAULT_MAX_PARALLEL_BLOCK_EXECUTIONS)
+            .max(1)
+    }
+
+    pub(crate) fn max_parallel_validation_queries(&self) -> usize {
+        self.max_parallel_validation_queries
+            .unwrap_or(DEFAULT_MAX_PARALLEL_VALIDATION_QUERIES)
+            .max(1)
+    }
+
+    pub(crate) fn max_parallel_queries(&self) -> usize {
+        self.max_parallel_queries
+            .unwrap_or(DEFAULT_MAX_PARALLEL_QUERIES)
+            .max(1)
+    }
+
+    pub(crate) fn auction_cache_size(&self) -> usize {
+        self.auction_cache_size
+            .unwrap_or(DEFAULT_AUCTION_CACHE_SIZE)
+    }
 }
 
 impl Default for Config {
@@ -63,6 +121,11 @@ impl Default for Config {
             max_readers: Some(DEFAULT_MAX_READERS),
             max_query_depth: Some(DEFAULT_MAX_QUERY_DEPTH),
             enable_manual_sync: Some(DEFAULT_MANUAL_SYNC_ENABLED),
+            wasm_module_cache_size: Some(DEFAULT_WASM_MODULE_CACHE_SIZE),
+            max_parallel_block_executions: Some(DEFAULT_MAX_PARALLEL_BLOCK_EXECUTIONS),
+            max_parallel_validation_queries: Some(DEFAULT_MAX_PARALLEL_VALIDATION_QUERIES),
+            max_parallel_queries: Some(DEFAULT_MAX_PARALLEL_QUERIES),
+            auction_cache_size: Some(DEFAULT_AUCTION_CACHE_SIZE),
         }
     }
 }
@@ -8,6 +8,8 @@ use casper_execution_engine::{
     storage::error::lmdb::Error as StorageLmdbError,
 };
 
+use casper_hashing::Digest;
+
 use crate::{
     components::contract_runtime::ExecutionPreState,
     types::{error::BlockCreationError, FinalizedBlock},
@@ -28,6 +30,43 @@ pub(crate) enum ConfigError {
     EngineState(#[from] EngineStateError),
 }
 
+/// An error reading the deploy limits from the on-chain configuration contract.
+///
+/// Nodes falling back to the chainspec's limits on such an error could disagree on which blocks
+/// are valid, so it is fatal.
+#[derive(Debug, Error)]
+pub(crate) enum GovernanceError {
+    /// The global state with the given root hash is not available.
+    #[error("failed to read governed value {name}: state root hash {state_root_hash} not found")]
+    RootNotFound {
+        /// The root hash of the global state.
+        state_root_hash: Digest,
+        /// The named key of the governed value.
+        name: String,
+    },
+    /// Querying the global state failed.
+    #[error("failed to read governed value {name} at state root hash {state_root_hash}: {error}")]
+    Query {
+        /// The root hash of the global state.
+        state_root_hash: Digest,
+        /// The named key of the governed value.
+        name: String,
+        /// The underlying error.
+        #[source]
+        error: EngineStateError,
+    },
+    /// The query failed to resolve the governed value.
+    #[error("failed to read governed value {name} at state root hash {state_root_hash}: {result}")]
+    UnresolvedQuery {
+        /// The root hash of the global state.
+        state_root_hash: Digest,
+        /// The named key of the governed value.
+        name: String,
+        /// The description of the query result.
+        result: String,
+    },
+}
+
 /// An error during block execution.
 #[derive(Debug, Error, Serialize)]
 pub enum BlockExecutionError {
@@ -0,0 +1,146 @@
+//! Construction of the genesis global state without running a node.
+//!
+//! Operators of a private network can build the genesis global state from the chainspec and
+//! accounts they intend to launch with, and compare the resulting state root hash with the other
+//! participants ahead of the genesis timestamp.
+
+use std::{io, path::Path, sync::Arc};
+
+use lmdb::DatabaseFlags;
+use thiserror::Error;
+use tracing::error;
+
+use casper_execution_engine::{
+    core::engine_state::{
+        self, genesis::GenesisError, ChainspecRegistry, EngineState, GenesisSuccess,
+    },
+    shared::newtypes::CorrelationId,
+    storage::{
+        error::lmdb::Error as StorageLmdbError, global_state::lmdb::LmdbGlobalState,
+        transaction_source::lmdb::LmdbEnvironment, trie_store::lmdb::LmdbTrieStore,
+    },
+};
+use casper_hashing::Digest;
+
+use super::Config;
+use crate::{
+    types::{chainspec::Error as ChainspecError, Chainspec, ChainspecRawBytes},
+    utils::Loadable,
+};
+
+/// An error building the genesis global state.
+#[derive(Debug, Error)]
+pub enum GenesisStateError {
+    /// The chainspec couldn't be loaded.
+    #[error("failed to load chainspec: {0}")]
+    LoadChainspec(#[from] ChainspecError),
+    /// The chainspec failed validation.
+    #[error("chainspec is invalid")]
+    InvalidChainspec,
+    /// The chainspec's activation point is an era rather than a genesis timestamp.
+    #[error("chainspec is for an upgrade, not for genesis")]
+    NotGenesis,
+    /// The temporary directory for the global state couldn't be created.
+    #[error("failed to create temporary directory: {0}")]
+    TempDir(#[from] io::Error),
+    /// The global state store couldn't be created.
+    #[error("failed to create global state: {0}")]
+    GlobalState(#[from] StorageLmdbError),
+    /// Committing the genesis global state failed.
+    #[error("failed to commit genesis: {0}")]
+    Commit(#[from] engine_state::Error),
+}
+
+/// Builds the genesis global state from the `chainspec.toml` and `accounts.toml` in
+/// `chainspec_dir`, and returns its state root hash.
+///
+/// The global state is built in a temporary directory which is removed afterwards.  The state root
+/// hash depends only on the contents of the two files, so all participants building it from the
+/// same files get the same hash.
+pub fn build_genesis_state(chainspec_dir: &Path) -> Result<Digest, GenesisStateError> {
+    let (chainspec, chainspec_raw_bytes) =
+        <(Chainspec, ChainspecRawBytes)>::from_path(chainspec_dir)?;
+    if !chainspec.protocol_config.activation_point.is_genesis() {
+        return Err(GenesisStateError::NotGenesis);
+    }
+    if !chainspec.is_valid() {
+        return Err(GenesisStateError::InvalidChainspec);
+    }
+
+    let config = Config::default();
+    let tempdir = tempfile::tempdir()?;
+    let environment = Arc::new(LmdbEnvironment::new(
+        tempdir.path(),
+        config.max_global_state_size(),
+        config.max_readers(),
+        config.manual_sync_enabled(),
+    )?);
+    let trie_store = Arc::new(LmdbTrieStore::new(
+        &environment,
+        None,
+        DatabaseFlags::empty(),
+    )?);
+    let global_state = LmdbGlobalState::empty(environment, trie_store)?;
+    let engine_state = EngineState::new(
+        global_state,
+        chainspec.engine_config(config.max_query_depth()),
+    );
+
+    let genesis_success = commit_genesis(&engine_state, &chainspec, &chainspec_raw_bytes)?;
+    Ok(genesis_success.post_state_hash)
+}
+
+/// Commits the genesis global state described by the chainspec to `engine_state`.
+pub(super) fn commit_genesis(
+    engine_state: &EngineState<LmdbGlobalState>,
+    chainspec: &Chainspec,
+    chainspec_raw_bytes: &ChainspecRawBytes,
+) -> Result<GenesisSuccess, engine_state::Error> {
+    let correlation_id = CorrelationId::new();
+    let genesis_config_hash = chainspec.hash();
+    let protocol_version = chainspec.protocol_config.version;
+    // Transforms a chainspec into a valid genesis config for execution engine.
+    let ee_config = chainspec.into();
+
+    let chainspec_registry = ChainspecRegistry::new_with_genesis(
+        chainspec_raw_bytes.chainspec_bytes(),
+        chainspec_raw_bytes
+            .maybe_genesis_accounts_bytes()
+            .ok_or_else(|| {
+                error!("failed to provide genesis account bytes in commit genesis");
+                engine_state::Error::Genesis(Box::new(GenesisError::MissingChainspecRegistryEntry))
+            })?,
+    );
+
+    let result = engine_state.commit_genesis(
+        correlation_id,
+        genesis_config_hash,
+        protocol_version,
+        &ee_config,
+        chainspec_registry,
+    );
+    engine_state.flush_environment()?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::RESOURCES_PATH;
+
+    #[test]
+    fn should_build_same_genesis_state_twice() {
+        let local_dir = RESOURCES_PATH.join("local");
+        let state_root_hash = build_genesis_state(&local_dir).unwrap();
+        assert_eq!(build_genesis_state(&local_dir).unwrap(), state_root_hash);
+    }
+
+    #[test]
+    fn should_reject_upgrade_chainspec() {
+        let production_dir = RESOURCES_PATH.join("production");
+        assert!(matches!(
+            build_genesis_state(&production_dir),
+            Err(GenesisStateError::NotGenesis)
+        ));
+    }
+}
@@ -0,0 +1,212 @@
+//! Protocol parameters governed by an on-chain configuration contract.
+//!
+//! When enabled in the chainspec, the deploy limits which apply during an era are read from the
+//! named keys of the configuration contract in the global state resulting from the switch block
+//! of the preceding era.  Every node reads the same state, so all nodes agree on the limits
+//! without coordinated restarts.  The chainspec's values act as upper bounds, and apply for any
+//! named key which is absent from the global state or holds an invalid value.  Failing to read the
+//! global state is fatal, as the node could otherwise apply different limits than its peers.
+
+use std::convert::TryFrom;
+
+use datasize::DataSize;
+use tracing::warn;
+
+use casper_execution_engine::{
+    core::engine_state::{EngineState, QueryRequest, QueryResult},
+    shared::newtypes::CorrelationId,
+    storage::global_state::lmdb::LmdbGlobalState,
+};
+use casper_hashing::Digest;
+use casper_types::{ContractHash, Key, StoredValue, TimeDiff};
+
+use super::GovernanceError;
+use crate::types::{chainspec::DeployConfig, Chainspec};
+
+/// The named key holding the maximum gas of the deploys in a block.
+const BLOCK_GAS_LIMIT_KEY: &str = "block_gas_limit";
+/// The named key holding the maximum time to live of a deploy, in milliseconds.
+const MAX_TTL_KEY: &str = "max_ttl";
+/// The named key holding the maximum number of non-transfer deploys in a block.
+const BLOCK_MAX_DEPLOY_COUNT_KEY: &str = "block_max_deploy_count";
+/// The named key holding the maximum number of transfers in a block.
+const BLOCK_MAX_TRANSFER_COUNT_KEY: &str = "block_max_transfer_count";
+
+/// Reads the deploy limits from the configuration contract named in the chainspec.
+#[derive(DataSize, Debug)]
+pub(crate) struct Governance {
+    /// The hash of the configuration contract.
+    #[data_size(skip)]
+    contract_hash: ContractHash,
+    /// The deploy config from the chainspec, bounding the values read from the contract.
+    chainspec_deploy_config: DeployConfig,
+}
+
+impl Governance {
+    /// Returns a new instance if governance is enabled in the chainspec, or `None` otherwise.
+    pub(crate) fn new(chainspec: &Chainspec) -> Option<Self> {
+        let contract_hash = chainspec.governance_config.enabled_contract_hash()?;
+        Some(Governance {
+            contract_hash,
+            chainspec_deploy_config: chainspec.deploy_config,
+        })
+    }
+
+    /// Returns the deploy config for the era following the switch block with the given state root
+    /// hash.
+    ///
+    /// Only a governed value which is absent from the global state falls back to the chainspec's
+    /// limit.  Any failure to read the global state is returned as an error.
+    pub(super) fn read_deploy_config(
+        &self,
+        engine_state: &EngineState<LmdbGlobalState>,
+        state_root_hash: Digest,
+    ) -> Result<DeployConfig, GovernanceError> {
+        let key = Key::from(self.contract_hash);
+        apply_governed_values(self.chainspec_deploy_config, |name| {
+            let query_request = QueryRequest::new(state_root_hash, key, vec![name.to_string()]);
+            match engine_state.run_query(CorrelationId::new(), query_request) {
+                Ok(QueryResult::Success { value, .. }) => match *value {
+                    StoredValue::CLValue(cl_value) => Ok(cl_value.into_t::<u64>().ok()),
+                    _ => Ok(None),
+                },
+                Ok(QueryResult::ValueNotFound(_)) => Ok(None),
+                Ok(QueryResult::RootNotFound) => Err(GovernanceError::RootNotFound {
+                    state_root_hash,
+                    name: name.to_string(),
+                }),
+                Ok(result) => Err(GovernanceError::UnresolvedQuery {
+                    state_root_hash,
+                    name: name.to_string(),
+                    result: format!("{:?}", result),
+                }),
+                Err(error) => Err(GovernanceError::Query {
+                    state_root_hash,
+                    name: name.to_string(),
+                    error,
+                }),
+            }
+        })
+    }
+}
+
+/// Returns `chainspec_deploy_config` with the governed limits replaced by the values returned by
+/// `read_value` for their named keys, or the first error returned by `read_value`.
+///
+/// A value is ignored with a warning if it is zero or exceeds the limit set in the chainspec.
+fn apply_governed_values<F, E>(
+    chainspec_deploy_config: DeployConfig,
+    mut read_value: F,
+) -> Result<DeployConfig, E>
+where
+    F: FnMut(&str) -> Result<Option<u64>, E>,
+{
+    let mut read_limit = |name: &str, maximum: u64| -> Result<Option<u64>, E> {
+        let value = match read_value(name)? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        if value == 0 || value > maximum {
+            warn!(
+                name,
+                value,
+                maximum,
+                "ignoring governed value outside the range allowed by the chainspec"
+            );
+            return Ok(None);
+        }
+        Ok(Some(value))
+    };
+
+    let mut deploy_config = chainspec_deploy_config;
+    if let Some(value) = read_limit(BLOCK_GAS_LIMIT_KEY, deploy_config.block_gas_limit)? {
+        deploy_config.block_gas_limit = value;
+    }
+    if let Some(value) = read_limit(MAX_TTL_KEY, deploy_config.max_ttl.millis())? {
+        deploy_config.max_ttl = TimeDiff::from(value);
+    }
+    if let Some(value) = read_limit(
+        BLOCK_MAX_DEPLOY_COUNT_KEY,
+        deploy_config.block_max_deploy_count.into(),
+    )? {
+        deploy_config.block_max_deploy_count = u32::try_from(value).unwrap_or(u32::MAX);
+    }
+    if let Some(value) = read_limit(
+        BLOCK_MAX_TRANSFER_COUNT_KEY,
+        deploy_config.block_max_transfer_count.into(),
+    )? {
+        deploy_config.block_max_transfer_count = u32::try_from(value).unwrap_or(u32::MAX);
+    }
+    Ok(deploy_config)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    #[test]
+    fn should_apply_governed_values_within_chainspec_bounds() {
+        let chainspec_deploy_config = DeployConfig::default();
+        let values: BTreeMap<&str, u64> = vec![
+            (
+                BLOCK_GAS_LIMIT_KEY,
+                chainspec_deploy_config.block_gas_limit / 2,
+            ),
+            (MAX_TTL_KEY, 60_000),
+            // Values outside the bounds are ignored.
+            (BLOCK_MAX_DEPLOY_COUNT_KEY, 0),
+            (
+                BLOCK_MAX_TRANSFER_COUNT_KEY,
+                u64::from(chainspec_deploy_config.block_max_transfer_count) + 1,
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let deploy_config = apply_governed_values(chainspec_deploy_config, |name| {
+            Ok::<_, ()>(values.get(name).copied())
+        })
+        .unwrap();
+        assert_eq!(
+            deploy_config.block_gas_limit,
+            chainspec_deploy_config.block_gas_limit / 2
+        );
+        assert_eq!(deploy_config.max_ttl, TimeDiff::from(60_000));
+        assert_eq!(
+            deploy_config.block_max_deploy_count,
+            chainspec_deploy_config.block_max_deploy_count
+        );
+        assert_eq!(
+            deploy_config.block_max_transfer_count,
+            chainspec_deploy_config.block_max_transfer_count
+        );
+
+        // Missing values leave the chainspec's limits in place.
+        assert_eq!(
+            apply_governed_values(chainspec_deploy_config, |_| Ok::<_, ()>(None)),
+            Ok(chainspec_deploy_config)
+        );
+    }
+
+    #[test]
+    fn should_not_fall_back_to_chainspec_limits_on_read_error() {
+        let chainspec_deploy_config = DeployConfig::default();
+        let mut names_read = vec![];
+        let result = apply_governed_values(chainspec_deploy_config, |name| {
+            names_read.push(name.to_string());
+            if name == MAX_TTL_KEY {
+                Err("root not found")
+            } else {
+                Ok(None)
+            }
+        });
+        assert_eq!(result, Err("root not found"));
+        // Reading stops at the first error.
+        assert_eq!(
+            names_read,
+            vec![BLOCK_GAS_LIMIT_KEY.to_string(), MAX_TTL_KEY.to_string()]
+        );
+    }
+}
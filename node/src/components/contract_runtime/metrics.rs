@@ -1,4 +1,9 @@
-use prometheus::{self, Gauge, Histogram, IntGauge, Registry};
+use std::time::Duration;
+
+use prometheus::{self, Gauge, Histogram, IntCounter, IntGauge, Registry};
+
+use casper_execution_engine::core::engine_state::{ExecutableDeployItem, ModuleCacheStats};
+use casper_types::U512;
 
 use crate::{unregister_metric, utils};
 
@@ -14,6 +19,11 @@ const EXPONENTIAL_BUCKET_FACTOR: f64 = 2.0;
 /// - Values above 10.24 (f64 seconds here) will not fall in a bucket that is kept.
 const EXPONENTIAL_BUCKET_COUNT: usize = 10;
 
+/// Buckets of the deploy gas histograms, from 100,000 to 4^13 * 100,000 (~6.7 * 10^12) gas.
+const GAS_BUCKET_START: f64 = 100_000.0;
+const GAS_BUCKET_FACTOR: f64 = 4.0;
+const GAS_BUCKET_COUNT: usize = 14;
+
 const RUN_EXECUTE_NAME: &str = "contract_runtime_run_execute";
 const RUN_EXECUTE_HELP: &str = "time in seconds to execute but not commit a contract";
 
@@ -43,6 +53,18 @@ const GET_ERA_VALIDATORS_HELP: &str =
 const GET_BIDS_NAME: &str = "contract_runtime_get_bids";
 const GET_BIDS_HELP: &str = "time in seconds to get bids from global state";
 
+const GET_BALANCES_NAME: &str = "contract_runtime_get_balances";
+const GET_BALANCES_HELP: &str =
+    "time in seconds to get the balances of many purses from global state";
+
+const GET_DICTIONARY_ITEMS_NAME: &str = "contract_runtime_get_dictionary_items";
+const GET_DICTIONARY_ITEMS_HELP: &str =
+    "time in seconds to get a page of dictionary items from global state";
+
+const GET_STATE_DIFF_NAME: &str = "contract_runtime_get_state_diff";
+const GET_STATE_DIFF_HELP: &str =
+    "time in seconds to get a page of the changes between two global states";
+
 const MISSING_TRIE_KEYS_NAME: &str = "contract_runtime_missing_trie_keys";
 const MISSING_TRIE_KEYS_HELP: &str = "time in seconds to get missing trie keys";
 
@@ -52,15 +74,131 @@ const PUT_TRIE_HELP: &str = "time in seconds to put a trie";
 const GET_TRIE_NAME: &str = "contract_runtime_get_trie";
 const GET_TRIE_HELP: &str = "time in seconds to get a trie";
 
+const SPECULATIVE_EXECUTION_NAME: &str = "contract_runtime_speculative_execution";
+const SPECULATIVE_EXECUTION_HELP: &str =
+    "time in seconds to speculatively execute a deploy without committing its effects";
+
 const CHAIN_HEIGHT_NAME: &str = "chain_height";
 const CHAIN_HEIGHT_HELP: &str = "current chain height";
 
 const EXEC_BLOCK_NAME: &str = "contract_runtime_execute_block";
 const EXEC_BLOCK_HELP: &str = "time in seconds to execute all deploys in a block";
 
+const COMMIT_BLOCK_NAME: &str = "contract_runtime_commit_block";
+const COMMIT_BLOCK_HELP: &str =
+    "time in seconds to write the effects of all deploys in a block to global state";
+
+const NATIVE_TRANSFER_EXECUTION_TIME_NAME: &str = "contract_runtime_native_transfer_execution_time";
+const NATIVE_TRANSFER_EXECUTION_TIME_HELP: &str =
+    "time in seconds to execute a native transfer deploy as part of a finalized block";
+
+const NATIVE_TRANSFER_GAS_NAME: &str = "contract_runtime_native_transfer_gas";
+const NATIVE_TRANSFER_GAS_HELP: &str =
+    "gas cost of a native transfer deploy executed as part of a finalized block";
+
+const STORED_CONTRACT_EXECUTION_TIME_NAME: &str = "contract_runtime_stored_contract_execution_time";
+const STORED_CONTRACT_EXECUTION_TIME_HELP: &str =
+    "time in seconds to execute a deploy calling a stored contract as part of a finalized block";
+
+const STORED_CONTRACT_GAS_NAME: &str = "contract_runtime_stored_contract_gas";
+const STORED_CONTRACT_GAS_HELP: &str =
+    "gas cost of a deploy calling a stored contract executed as part of a finalized block";
+
+const MODULE_BYTES_EXECUTION_TIME_NAME: &str = "contract_runtime_module_bytes_execution_time";
+const MODULE_BYTES_EXECUTION_TIME_HELP: &str =
+    "time in seconds to execute a deploy with module bytes session code as part of a finalized \
+    block";
+
+const MODULE_BYTES_GAS_NAME: &str = "contract_runtime_module_bytes_gas";
+const MODULE_BYTES_GAS_HELP: &str =
+    "gas cost of a deploy with module bytes session code executed as part of a finalized block";
+
 const LATEST_COMMIT_STEP_NAME: &str = "contract_runtime_latest_commit_step";
 const LATEST_COMMIT_STEP_HELP: &str = "duration in seconds of latest commit step at era end";
 
+const EXEC_BLOCK_PENDING_DEPLOYS_NAME: &str = "contract_runtime_execute_block_pending_deploys";
+const EXEC_BLOCK_PENDING_DEPLOYS_HELP: &str =
+    "number of deploys in the block being executed which have yet to finish executing";
+
+const DEPLOYS_EXECUTED_NAME: &str = "contract_runtime_deploys_executed";
+const DEPLOYS_EXECUTED_HELP: &str = "number of deploys executed as part of a finalized block";
+
+const DEPLOY_GAS_USED_NAME: &str = "contract_runtime_deploy_gas_used";
+const DEPLOY_GAS_USED_HELP: &str =
+    "total gas cost of the deploys executed as part of finalized blocks";
+
+const MODULE_CACHE_HITS_NAME: &str = "contract_runtime_wasm_module_cache_hits";
+const MODULE_CACHE_HITS_HELP: &str =
+    "number of Wasm module executions which reused a module prepared by an earlier execution";
+
+const MODULE_CACHE_MISSES_NAME: &str = "contract_runtime_wasm_module_cache_misses";
+const MODULE_CACHE_MISSES_HELP: &str =
+    "number of Wasm module executions which had to prepare the module for execution";
+
+const MODULE_CACHE_ENTRIES_NAME: &str = "contract_runtime_wasm_module_cache_entries";
+const MODULE_CACHE_ENTRIES_HELP: &str = "number of prepared Wasm modules currently cached";
+
+const MODULE_CACHE_SIZE_NAME: &str = "contract_runtime_wasm_module_cache_size_bytes";
+const MODULE_CACHE_SIZE_HELP: &str =
+    "approximate size in bytes of the prepared Wasm modules currently cached";
+
+const BLOCK_EXECUTION_REQUESTS_QUEUED_NAME: &str =
+    "contract_runtime_block_execution_requests_queued";
+const BLOCK_EXECUTION_REQUESTS_QUEUED_HELP: &str =
+    "number of block execution requests waiting to run";
+
+const BLOCK_EXECUTION_REQUESTS_RUNNING_NAME: &str =
+    "contract_runtime_block_execution_requests_running";
+const BLOCK_EXECUTION_REQUESTS_RUNNING_HELP: &str = "number of block execution requests running";
+
+const VALIDATION_REQUESTS_QUEUED_NAME: &str = "contract_runtime_validation_requests_queued";
+const VALIDATION_REQUESTS_QUEUED_HELP: &str =
+    "number of queries made to validate incoming data waiting to run";
+
+const VALIDATION_REQUESTS_RUNNING_NAME: &str = "contract_runtime_validation_requests_running";
+const VALIDATION_REQUESTS_RUNNING_HELP: &str =
+    "number of queries made to validate incoming data running";
+
+const QUERY_REQUESTS_QUEUED_NAME: &str = "contract_runtime_query_requests_queued";
+const QUERY_REQUESTS_QUEUED_HELP: &str =
+    "number of queries and speculative executions made on behalf of API clients waiting to run";
+
+const QUERY_REQUESTS_RUNNING_NAME: &str = "contract_runtime_query_requests_running";
+const QUERY_REQUESTS_RUNNING_HELP: &str =
+    "number of queries and speculative executions made on behalf of API clients running";
+
+/// The category of a deploy, determined by its session code, under which its execution is
+/// recorded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum DeployCategory {
+    /// A native transfer, executed without Wasm.
+    NativeTransfer,
+    /// A call to a contract or contract package stored in global state.
+    StoredContract,
+    /// Session code provided as Wasm module bytes.
+    ModuleBytes,
+}
+
+impl DeployCategory {
+    /// Returns the category of a deploy with the given session code.
+    pub(super) fn new(session: &ExecutableDeployItem) -> Self {
+        if session.is_transfer() {
+            DeployCategory::NativeTransfer
+        } else if session.is_module_bytes() {
+            DeployCategory::ModuleBytes
+        } else {
+            DeployCategory::StoredContract
+        }
+    }
+}
+
+/// The execution time and gas histograms of a single deploy category.
+#[derive(Debug)]
+struct DeployCategoryMetrics {
+    execution_time: Histogram,
+    gas: Histogram,
+}
+
 /// Metrics for the contract runtime component.
 #[derive(Debug)]
 pub struct Metrics {
@@ -73,12 +211,33 @@ pub struct Metrics {
     pub(super) get_validator_weights: Histogram,
     pub(super) get_era_validators: Histogram,
     pub(super) get_bids: Histogram,
+    pub(super) get_balances: Histogram,
+    pub(super) get_dictionary_items: Histogram,
+    pub(super) get_state_diff: Histogram,
     pub(super) missing_trie_keys: Histogram,
     pub(super) put_trie: Histogram,
     pub(super) get_trie: Histogram,
+    pub(super) speculative_execution: Histogram,
     pub(super) chain_height: IntGauge,
     pub(super) exec_block: Histogram,
+    pub(super) commit_block: Histogram,
+    native_transfer: DeployCategoryMetrics,
+    stored_contract: DeployCategoryMetrics,
+    module_bytes: DeployCategoryMetrics,
     pub(super) latest_commit_step: Gauge,
+    pub(super) exec_block_pending_deploys: IntGauge,
+    pub(super) deploys_executed: IntCounter,
+    pub(super) deploy_gas_used: IntCounter,
+    module_cache_hits: IntGauge,
+    module_cache_misses: IntGauge,
+    module_cache_entries: IntGauge,
+    module_cache_size: IntGauge,
+    pub(super) block_execution_requests_queued: IntGauge,
+    pub(super) block_execution_requests_running: IntGauge,
+    pub(super) validation_requests_queued: IntGauge,
+    pub(super) validation_requests_running: IntGauge,
+    pub(super) query_requests_queued: IntGauge,
+    pub(super) query_requests_running: IntGauge,
     registry: Registry,
 }
 
@@ -97,12 +256,118 @@ impl Metrics {
         // Anything above that should be a warning signal.
         let tiny_buckets = prometheus::exponential_buckets(0.001, 2.0, 10)?;
 
+        let gas_buckets =
+            prometheus::exponential_buckets(GAS_BUCKET_START, GAS_BUCKET_FACTOR, GAS_BUCKET_COUNT)?;
+
+        let native_transfer = DeployCategoryMetrics {
+            execution_time: utils::register_histogram_metric(
+                registry,
+                NATIVE_TRANSFER_EXECUTION_TIME_NAME,
+                NATIVE_TRANSFER_EXECUTION_TIME_HELP,
+                common_buckets.clone(),
+            )?,
+            gas: utils::register_histogram_metric(
+                registry,
+                NATIVE_TRANSFER_GAS_NAME,
+                NATIVE_TRANSFER_GAS_HELP,
+                gas_buckets.clone(),
+            )?,
+        };
+
+        let stored_contract = DeployCategoryMetrics {
+            execution_time: utils::register_histogram_metric(
+                registry,
+                STORED_CONTRACT_EXECUTION_TIME_NAME,
+                STORED_CONTRACT_EXECUTION_TIME_HELP,
+                common_buckets.clone(),
+            )?,
+            gas: utils::register_histogram_metric(
+                registry,
+                STORED_CONTRACT_GAS_NAME,
+                STORED_CONTRACT_GAS_HELP,
+                gas_buckets.clone(),
+            )?,
+        };
+
+        let module_bytes = DeployCategoryMetrics {
+            execution_time: utils::register_histogram_metric(
+                registry,
+                MODULE_BYTES_EXECUTION_TIME_NAME,
+                MODULE_BYTES_EXECUTION_TIME_HELP,
+                common_buckets.clone(),
+            )?,
+            gas: utils::register_histogram_metric(
+                registry,
+                MODULE_BYTES_GAS_NAME,
+                MODULE_BYTES_GAS_HELP,
+                gas_buckets,
+            )?,
+        };
+
         let chain_height = IntGauge::new(CHAIN_HEIGHT_NAME, CHAIN_HEIGHT_HELP)?;
         registry.register(Box::new(chain_height.clone()))?;
 
         let latest_commit_step = Gauge::new(LATEST_COMMIT_STEP_NAME, LATEST_COMMIT_STEP_HELP)?;
         registry.register(Box::new(latest_commit_step.clone()))?;
 
+        let exec_block_pending_deploys = IntGauge::new(
+            EXEC_BLOCK_PENDING_DEPLOYS_NAME,
+            EXEC_BLOCK_PENDING_DEPLOYS_HELP,
+        )?;
+        registry.register(Box::new(exec_block_pending_deploys.clone()))?;
+
+        let deploys_executed = IntCounter::new(DEPLOYS_EXECUTED_NAME, DEPLOYS_EXECUTED_HELP)?;
+        registry.register(Box::new(deploys_executed.clone()))?;
+
+        let deploy_gas_used = IntCounter::new(DEPLOY_GAS_USED_NAME, DEPLOY_GAS_USED_HELP)?;
+        registry.register(Box::new(deploy_gas_used.clone()))?;
+
+        let module_cache_hits = IntGauge::new(MODULE_CACHE_HITS_NAME, MODULE_CACHE_HITS_HELP)?;
+        registry.register(Box::new(module_cache_hits.clone()))?;
+
+        let module_cache_misses =
+            IntGauge::new(MODULE_CACHE_MISSES_NAME, MODULE_CACHE_MISSES_HELP)?;
+        registry.register(Box::new(module_cache_misses.clone()))?;
+
+        let module_cache_entries =
+            IntGauge::new(MODULE_CACHE_ENTRIES_NAME, MODULE_CACHE_ENTRIES_HELP)?;
+        registry.register(Box::new(module_cache_entries.clone()))?;
+
+        let module_cache_size = IntGauge::new(MODULE_CACHE_SIZE_NAME, MODULE_CACHE_SIZE_HELP)?;
+        registry.register(Box::new(module_cache_size.clone()))?;
+
+        let block_execution_requests_queued = IntGauge::new(
+            BLOCK_EXECUTION_REQUESTS_QUEUED_NAME,
+            BLOCK_EXECUTION_REQUESTS_QUEUED_HELP,
+        )?;
+        registry.register(Box::new(block_execution_requests_queued.clone()))?;
+
+        let block_execution_requests_running = IntGauge::new(
+            BLOCK_EXECUTION_REQUESTS_RUNNING_NAME,
+            BLOCK_EXECUTION_REQUESTS_RUNNING_HELP,
+        )?;
+        registry.register(Box::new(block_execution_requests_running.clone()))?;
+
+        let validation_requests_queued = IntGauge::new(
+            VALIDATION_REQUESTS_QUEUED_NAME,
+            VALIDATION_REQUESTS_QUEUED_HELP,
+        )?;
+        registry.register(Box::new(validation_requests_queued.clone()))?;
+
+        let validation_requests_running = IntGauge::new(
+            VALIDATION_REQUESTS_RUNNING_NAME,
+            VALIDATION_REQUESTS_RUNNING_HELP,
+        )?;
+        registry.register(Box::new(validation_requests_running.clone()))?;
+
+        let query_requests_queued =
+            IntGauge::new(QUERY_REQUESTS_QUEUED_NAME, QUERY_REQUESTS_QUEUED_HELP)?;
+        registry.register(Box::new(query_requests_queued.clone()))?;
+
+        let query_requests_running =
+            IntGauge::new(QUERY_REQUESTS_RUNNING_NAME, QUERY_REQUESTS_RUNNING_HELP)?;
+        registry.register(Box::new(query_requests_running.clone()))?;
+
         Ok(Metrics {
             run_execute: utils::register_histogram_metric(
                 registry,
@@ -158,6 +423,24 @@ impl Metrics {
                 GET_BIDS_HELP,
                 common_buckets.clone(),
             )?,
+            get_balances: utils::register_histogram_metric(
+                registry,
+                GET_BALANCES_NAME,
+                GET_BALANCES_HELP,
+                common_buckets.clone(),
+            )?,
+            get_dictionary_items: utils::register_histogram_metric(
+                registry,
+                GET_DICTIONARY_ITEMS_NAME,
+                GET_DICTIONARY_ITEMS_HELP,
+                common_buckets.clone(),
+            )?,
+            get_state_diff: utils::register_histogram_metric(
+                registry,
+                GET_STATE_DIFF_NAME,
+                GET_STATE_DIFF_HELP,
+                common_buckets.clone(),
+            )?,
             get_trie: utils::register_histogram_metric(
                 registry,
                 GET_TRIE_NAME,
@@ -176,17 +459,73 @@ impl Metrics {
                 MISSING_TRIE_KEYS_HELP,
                 tiny_buckets,
             )?,
+            speculative_execution: utils::register_histogram_metric(
+                registry,
+                SPECULATIVE_EXECUTION_NAME,
+                SPECULATIVE_EXECUTION_HELP,
+                common_buckets.clone(),
+            )?,
             chain_height,
             exec_block: utils::register_histogram_metric(
                 registry,
                 EXEC_BLOCK_NAME,
                 EXEC_BLOCK_HELP,
+                common_buckets.clone(),
+            )?,
+            commit_block: utils::register_histogram_metric(
+                registry,
+                COMMIT_BLOCK_NAME,
+                COMMIT_BLOCK_HELP,
                 common_buckets,
             )?,
+            native_transfer,
+            stored_contract,
+            module_bytes,
             latest_commit_step,
+            exec_block_pending_deploys,
+            deploys_executed,
+            deploy_gas_used,
+            module_cache_hits,
+            module_cache_misses,
+            module_cache_entries,
+            module_cache_size,
+            block_execution_requests_queued,
+            block_execution_requests_running,
+            validation_requests_queued,
+            validation_requests_running,
+            query_requests_queued,
+            query_requests_running,
             registry: registry.clone(),
         })
     }
+
+    /// Records the wall time taken to execute a deploy of the given category, and its gas cost.
+    pub(super) fn record_deploy_execution(
+        &self,
+        category: DeployCategory,
+        execution_time: Duration,
+        gas: U512,
+    ) {
+        let category_metrics = match category {
+            DeployCategory::NativeTransfer => &self.native_transfer,
+            DeployCategory::StoredContract => &self.stored_contract,
+            DeployCategory::ModuleBytes => &self.module_bytes,
+        };
+        category_metrics
+            .execution_time
+            .observe(execution_time.as_secs_f64());
+        category_metrics
+            .gas
+            .observe(gas.min(U512::from(u64::MAX)).as_u64() as f64);
+    }
+
+    /// Updates the Wasm module cache metrics with the cache's latest statistics.
+    pub(super) fn record_module_cache_stats(&self, stats: ModuleCacheStats) {
+        self.module_cache_hits.set(stats.hits as i64);
+        self.module_cache_misses.set(stats.misses as i64);
+        self.module_cache_entries.set(stats.entries as i64);
+        self.module_cache_size.set(stats.size_bytes as i64);
+    }
 }
 
 impl Drop for Metrics {
@@ -200,11 +539,35 @@ impl Drop for Metrics {
         unregister_metric!(self.registry, self.get_validator_weights);
         unregister_metric!(self.registry, self.get_era_validators);
         unregister_metric!(self.registry, self.get_bids);
+        unregister_metric!(self.registry, self.get_balances);
+        unregister_metric!(self.registry, self.get_dictionary_items);
+        unregister_metric!(self.registry, self.get_state_diff);
         unregister_metric!(self.registry, self.missing_trie_keys);
         unregister_metric!(self.registry, self.put_trie);
         unregister_metric!(self.registry, self.get_trie);
+        unregister_metric!(self.registry, self.speculative_execution);
         unregister_metric!(self.registry, self.chain_height);
         unregister_metric!(self.registry, self.exec_block);
+        unregister_metric!(self.registry, self.commit_block);
+        unregister_metric!(self.registry, self.native_transfer.execution_time);
+        unregister_metric!(self.registry, self.native_transfer.gas);
+        unregister_metric!(self.registry, self.stored_contract.execution_time);
+        unregister_metric!(self.registry, self.stored_contract.gas);
+        unregister_metric!(self.registry, self.module_bytes.execution_time);
+        unregister_metric!(self.registry, self.module_bytes.gas);
         unregister_metric!(self.registry, self.latest_commit_step);
+        unregister_metric!(self.registry, self.exec_block_pending_deploys);
+        unregister_metric!(self.registry, self.deploys_executed);
+        unregister_metric!(self.registry, self.deploy_gas_used);
+        unregister_metric!(self.registry, self.module_cache_hits);
+        unregister_metric!(self.registry, self.module_cache_misses);
+        unregister_metric!(self.registry, self.module_cache_entries);
+        unregister_metric!(self.registry, self.module_cache_size);
+        unregister_metric!(self.registry, self.block_execution_requests_queued);
+        unregister_metric!(self.registry, self.block_execution_requests_running);
+        unregister_metric!(self.registry, self.validation_requests_queued);
+        unregister_metric!(self.registry, self.validation_requests_running);
+        unregister_metric!(self.registry, self.query_requests_queued);
+        unregister_metric!(self.registry, self.query_requests_running);
     }
 }
@@ -1,16 +1,21 @@
-use std::{collections::BTreeMap, sync::Arc, time::Instant};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::Instant,
+};
 
 use itertools::Itertools;
+use tokio::sync::mpsc::UnboundedSender;
 use tracing::{debug, trace, warn};
 
 use casper_execution_engine::{
     core::engine_state::{
         self, step::EvictItem, DeployItem, EngineState, ExecuteRequest,
-        ExecutionResult as EngineExecutionResult, GetEraValidatorsRequest, RewardItem, StepError,
-        StepRequest, StepSuccess,
+        ExecutionResult as EngineExecutionResult, GasProfile, GetEraValidatorsRequest, RewardItem,
+        StepError, StepRequest, StepSuccess,
     },
     shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
-    storage::global_state::lmdb::LmdbGlobalState,
+    storage::global_state::{lmdb::LmdbGlobalState, scratch::ScratchGlobalState},
 };
 use casper_hashing::Digest;
 use casper_types::{
@@ -22,11 +27,15 @@ use crate::{
     components::{
         consensus::EraReport,
         contract_runtime::{
-            error::BlockExecutionError, types::StepEffectAndUpcomingEraValidators,
+            error::BlockExecutionError,
+            metrics::DeployCategory,
+            types::{DeployExecutionProgress, StepEffectAndUpcomingEraValidators},
             BlockAndExecutionEffects, ExecutionPreState, Metrics,
         },
     },
-    types::{error::BlockCreationError, Block, Deploy, DeployHeader, FinalizedBlock},
+    types::{
+        error::BlockCreationError, Block, ContractEvent, Deploy, DeployHeader, FinalizedBlock,
+    },
 };
 use casper_execution_engine::{
     core::{engine_state::execution_result::ExecutionResults, execution},
@@ -36,10 +45,14 @@ use casper_execution_engine::{
 use super::SpeculativeExecutionState;
 
 /// Executes a finalized block.
+///
+/// If `progress_sender` is provided, a [`DeployExecutionProgress`] is sent through it as each
+/// deploy starts and finishes executing.
 #[allow(clippy::too_many_arguments)]
 pub fn execute_finalized_block(
     engine_state: &EngineState<LmdbGlobalState>,
     metrics: Option<Arc<Metrics>>,
+    progress_sender: Option<UnboundedSender<DeployExecutionProgress>>,
     protocol_version: ProtocolVersion,
     execution_pre_state: ExecutionPreState,
     finalized_block: FinalizedBlock,
@@ -61,6 +74,7 @@ pub fn execute_finalized_block(
     let mut state_root_hash = pre_state_root_hash;
     let mut execution_results: Vec<(_, DeployHeader, ExecutionResult)> =
         Vec::with_capacity(deploys.len() + transfers.len());
+    let mut contract_events = HashMap::new();
     // Run any deploys that must be executed
     let block_time = finalized_block.timestamp().millis();
     let start = Instant::now();
@@ -69,9 +83,34 @@ pub fn execute_finalized_block(
     // Create a new EngineState that reads from LMDB but only caches changes in memory.
     let scratch_state = engine_state.get_scratch_engine_state();
 
+    let block_height = finalized_block.height();
+    let report_progress = |progress: DeployExecutionProgress| {
+        if let Some(progress_sender) = progress_sender.as_ref() {
+            // The receiver only stops listening if the node is shutting down.
+            let _ = progress_sender.send(progress);
+        }
+    };
+    if let Some(metrics) = metrics.as_ref() {
+        metrics
+            .exec_block_pending_deploys
+            .set((deploys.len() + transfers.len()) as i64);
+    }
+
     for deploy in deploys.into_iter().chain(transfers) {
         let deploy_hash = *deploy.id();
         let deploy_header = deploy.header().clone();
+        let deploy_category = DeployCategory::new(deploy.session());
+        report_progress(DeployExecutionProgress::Started {
+            block_height,
+            deploy_hash,
+            account: deploy_header.account().clone(),
+        });
+
+        // TODO: this is currently working coincidentally because we are passing only one
+        // deploy_item per exec. The execution results coming back from the EE lack the
+        // mapping between deploy_hash and execution result, and this outer logic is
+        // enriching it with the deploy hash. If we were passing multiple deploys per exec
+        // the relation between the deploy and the execution results would be lost.
         let execute_request = ExecuteRequest::new(
             state_root_hash,
             block_time,
@@ -79,30 +118,53 @@ pub fn execute_finalized_block(
             protocol_version,
             *finalized_block.proposer(),
         );
-
-        // TODO: this is currently working coincidentally because we are passing only one
-        // deploy_item per exec. The execution results coming back from the EE lack the
-        // mapping between deploy_hash and execution result, and this outer logic is
-        // enriching it with the deploy hash. If we were passing multiple deploys per exec
-        // the relation between the deploy and the execution results would be lost.
+        let execution_start = Instant::now();
         let result = execute(&scratch_state, metrics.clone(), execute_request)?;
+        let execution_time = execution_start.elapsed();
 
         trace!(?deploy_hash, ?result, "deploy execution result");
         // As for now a given state is expected to exist.
-        let (state_hash, execution_result) = commit_execution_effects(
+        let (state_hash, execution_result, events) = commit_execution_effects(
             &scratch_state,
             metrics.clone(),
             state_root_hash,
             deploy_hash.into(),
             result,
         )?;
+
+        let (cost, error_message) = match &execution_result {
+            ExecutionResult::Success { cost, .. } => (*cost, None),
+            ExecutionResult::Failure {
+                cost,
+                error_message,
+                ..
+            } => (*cost, Some(error_message.clone())),
+        };
+        if let Some(metrics) = metrics.as_ref() {
+            metrics.exec_block_pending_deploys.dec();
+            metrics.deploys_executed.inc();
+            metrics
+                .deploy_gas_used
+                .inc_by(cost.min(U512::from(u64::MAX)).as_u64());
+            metrics.record_deploy_execution(deploy_category, execution_time, cost);
+        }
+        report_progress(DeployExecutionProgress::Finished {
+            block_height,
+            deploy_hash,
+            account: deploy_header.account().clone(),
+            cost,
+            error_message,
+        });
+
         execution_results.push((deploy_hash, deploy_header, execution_result));
+        if !events.is_empty() {
+            contract_events.insert(deploy_hash, events);
+        }
         state_root_hash = state_hash;
     }
 
     // Write the deploy approvals and execution results Merkle root hashes to global state if there
     // were any deploys.
-    let block_height = finalized_block.height();
     if let Some(deploy_approvals_root_hash) = maybe_deploy_approvals_root_hash {
         let execution_results_root_hash = compute_execution_results_root_hash(
             &mut execution_results.iter().map(|(_, _, result)| result),
@@ -130,6 +192,9 @@ pub fn execute_finalized_block(
 
     if let Some(metrics) = metrics.as_ref() {
         metrics.exec_block.observe(start.elapsed().as_secs_f64());
+        if let Some(module_cache) = engine_state.module_cache() {
+            metrics.record_module_cache_stats(module_cache.stats());
+        }
     }
 
     // If the finalized block has an era report, run the auction contract and get the upcoming era
@@ -149,8 +214,12 @@ pub fn execute_finalized_block(
                 finalized_block.era_id().successor(),
             )?;
 
-            state_root_hash =
-                engine_state.write_scratch_to_db(state_root_hash, scratch_state.into_inner())?;
+            state_root_hash = commit_block_effects(
+                engine_state,
+                metrics.clone(),
+                state_root_hash,
+                scratch_state,
+            )?;
 
             // In this flow we execute using a recent state root hash where the system contract
             // registry is guaranteed to exist.
@@ -168,8 +237,12 @@ pub fn execute_finalized_block(
         } else {
             // Finally, the new state-root-hash from the cumulative changes to global state is
             // returned when they are written to LMDB.
-            state_root_hash =
-                engine_state.write_scratch_to_db(state_root_hash, scratch_state.into_inner())?;
+            state_root_hash = commit_block_effects(
+                engine_state,
+                metrics.clone(),
+                state_root_hash,
+                scratch_state,
+            )?;
             None
         };
 
@@ -206,18 +279,21 @@ pub fn execute_finalized_block(
     Ok(BlockAndExecutionEffects {
         block,
         execution_results,
+        contract_events,
         maybe_step_effect_and_upcoming_era_validators,
     })
 }
 
 /// Commits the execution effects.
+///
+/// Returns the new state root hash, the execution result and the events emitted by contracts.
 fn commit_execution_effects<S>(
     engine_state: &EngineState<S>,
     metrics: Option<Arc<Metrics>>,
     state_root_hash: Digest,
     deploy_hash: DeployHash,
     execution_results: ExecutionResults,
-) -> Result<(Digest, ExecutionResult), BlockExecutionError>
+) -> Result<(Digest, ExecutionResult, Vec<ContractEvent>), BlockExecutionError>
 where
     S: StateProvider + CommitProvider,
     S::Error: Into<execution::Error>,
@@ -227,6 +303,7 @@ where
         .exactly_one()
         .map_err(|_| BlockExecutionError::MoreThanOneExecutionResult)?;
     let json_execution_result = ExecutionResult::from(&ee_execution_result);
+    let events = ee_execution_result.events().to_vec();
 
     let execution_effect: AdditiveMap<Key, Transform> = match ee_execution_result {
         EngineExecutionResult::Success {
@@ -255,7 +332,7 @@ where
     .into();
     let new_state_root =
         commit_transforms(engine_state, metrics, state_root_hash, execution_effect)?;
-    Ok((new_state_root, json_execution_result))
+    Ok((new_state_root, json_execution_result, events))
 }
 
 fn commit_transforms<S>(
@@ -292,36 +369,70 @@ where
     S: StateProvider + CommitProvider,
     S::Error: Into<execution::Error>,
 {
+    let deploy_hash = deploy.deploy_hash;
+    let execute_request = speculative_execute_request(execution_state, deploy);
+    let results = execute(engine_state, None, execute_request);
+    results.map(|execution_results| single_execution_result(deploy_hash, execution_results))
+}
+
+/// Execute the transaction without commiting the effects, recording where the gas it consumes is
+/// spent.
+///
+/// Returns effects of the execution along with the breakdown of its gas consumption.
+pub fn execute_only_with_gas_profile<S>(
+    engine_state: &EngineState<S>,
+    execution_state: SpeculativeExecutionState,
+    deploy: DeployItem,
+) -> Result<Option<(ExecutionResult, GasProfile)>, engine_state::Error>
+where
+    S: StateProvider + CommitProvider,
+    S::Error: Into<execution::Error>,
+{
+    let deploy_hash = deploy.deploy_hash;
+    let execute_request = speculative_execute_request(execution_state, deploy);
+    trace!(?execute_request, "execute with gas profile");
+    let (execution_results, gas_profile) =
+        engine_state.run_execute_with_gas_profile(CorrelationId::new(), execute_request)?;
+    Ok(single_execution_result(deploy_hash, execution_results)
+        .map(|execution_result| (execution_result, gas_profile)))
+}
+
+fn speculative_execute_request(
+    execution_state: SpeculativeExecutionState,
+    deploy: DeployItem,
+) -> ExecuteRequest {
     let SpeculativeExecutionState {
         state_root_hash,
         block_time,
         protocol_version,
     } = execution_state;
-    let deploy_hash = deploy.deploy_hash;
-    let execute_request = ExecuteRequest::new(
+    ExecuteRequest::new(
         state_root_hash,
         block_time.millis(),
         vec![deploy],
         protocol_version,
         PublicKey::System,
-    );
-    let results = execute(engine_state, None, execute_request);
-    results.map(|mut execution_results| {
-        let len = execution_results.len();
-        if len != 1 {
-            warn!(
-                ?deploy_hash,
-                "got more ({}) execution results from a single transaction", len
-            );
-            None
-        } else {
-            // We know it must be 1, we could unwrap and then wrap
-            // with `Some(_)` but `pop_front` already returns an `Option`.
-            // We need to transform the `engine_state::ExecutionResult` into
-            // `casper_types::ExecutionResult` as well.
-            execution_results.pop_front().map(Into::into)
-        }
-    })
+    )
+}
+
+fn single_execution_result(
+    deploy_hash: DeployHash,
+    mut execution_results: ExecutionResults,
+) -> Option<ExecutionResult> {
+    let len = execution_results.len();
+    if len != 1 {
+        warn!(
+            ?deploy_hash,
+            "got more ({}) execution results from a single transaction", len
+        );
+        None
+    } else {
+        // We know it must be 1, we could unwrap and then wrap
+        // with `Some(_)` but `pop_front` already returns an `Option`.
+        // We need to transform the `engine_state::ExecutionResult` into
+        // `casper_types::ExecutionResult` as well.
+        execution_results.pop_front().map(Into::into)
+    }
 }
 
 fn execute<S>(
@@ -344,6 +455,22 @@ where
     result
 }
 
+/// Writes the effects of the block's deploys, cached in `scratch_state`, to global state and returns
+/// the resulting state root hash.
+fn commit_block_effects(
+    engine_state: &EngineState<LmdbGlobalState>,
+    metrics: Option<Arc<Metrics>>,
+    state_root_hash: Digest,
+    scratch_state: EngineState<ScratchGlobalState>,
+) -> Result<Digest, engine_state::Error> {
+    let start = Instant::now();
+    let result = engine_state.write_scratch_to_db(state_root_hash, scratch_state.into_inner());
+    if let Some(metrics) = metrics {
+        metrics.commit_block.observe(start.elapsed().as_secs_f64());
+    }
+    result
+}
+
 fn commit_step<S>(
     engine_state: &EngineState<S>,
     maybe_metrics: Option<Arc<Metrics>>,
@@ -0,0 +1,168 @@
+//! Scheduling of resource intensive contract runtime requests by priority.
+
+use std::{
+    fmt::{self, Display, Formatter},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use prometheus::IntGauge;
+use serde::Serialize;
+use tokio::sync::{Notify, Semaphore};
+
+use super::{metrics::Metrics, Config};
+
+/// The priority class of a contract runtime request.
+///
+/// Requests of each class are run subject to an independent concurrency limit, and a request only
+/// starts running once no requests of a higher priority class are waiting to run.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize)]
+pub(crate) enum RequestPriority {
+    /// Execution of finalized blocks; the highest priority.
+    BlockExecution,
+    /// Queries made by the node itself to validate incoming data, such as deploys received by
+    /// the deploy acceptor.
+    Validation,
+    /// Queries and speculative execution made on behalf of API clients; the lowest priority.
+    Query,
+}
+
+impl RequestPriority {
+    /// All priority classes, from highest to lowest priority.
+    const ALL: [RequestPriority; 3] = [
+        RequestPriority::BlockExecution,
+        RequestPriority::Validation,
+        RequestPriority::Query,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl Display for RequestPriority {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestPriority::BlockExecution => write!(formatter, "block execution"),
+            RequestPriority::Validation => write!(formatter, "validation"),
+            RequestPriority::Query => write!(formatter, "query"),
+        }
+    }
+}
+
+/// The state of a single priority class.
+struct PriorityClass {
+    /// Enforces the maximum number of requests of this class running in parallel.
+    running_limit: Semaphore,
+    /// The number of requests of this class waiting to run.
+    queued: AtomicUsize,
+}
+
+/// Decrements the queued request count of a class when dropped, i.e. once a request stops
+/// waiting, whether it is about to run or was cancelled.
+struct QueuedGuard<'a> {
+    scheduler: &'a RequestScheduler,
+    priority: RequestPriority,
+}
+
+impl<'a> Drop for QueuedGuard<'a> {
+    fn drop(&mut self) {
+        let class = &self.scheduler.classes[self.priority.index()];
+        self.scheduler.queued_gauge(self.priority).dec();
+        if class.queued.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.scheduler.queue_emptied.notify_waiters();
+        }
+    }
+}
+
+/// Runs resource intensive contract runtime requests on blocking threads, prioritizing them by
+/// their [`RequestPriority`].
+pub(super) struct RequestScheduler {
+    classes: [PriorityClass; 3],
+    /// Notified whenever the last queued request of a class stops waiting.
+    queue_emptied: Notify,
+    metrics: Arc<Metrics>,
+}
+
+impl RequestScheduler {
+    /// Creates a new scheduler with the concurrency limits given in `config`.
+    pub(super) fn new(config: &Config, metrics: Arc<Metrics>) -> Self {
+        let class = |limit: usize| PriorityClass {
+            running_limit: Semaphore::new(limit),
+            queued: AtomicUsize::new(0),
+        };
+        RequestScheduler {
+            classes: [
+                class(config.max_parallel_block_executions()),
+                class(config.max_parallel_validation_queries()),
+                class(config.max_parallel_queries()),
+            ],
+            queue_emptied: Notify::new(),
+            metrics,
+        }
+    }
+
+    fn queued_gauge(&self, priority: RequestPriority) -> &IntGauge {
+        match priority {
+            RequestPriority::BlockExecution => &self.metrics.block_execution_requests_queued,
+            RequestPriority::Validation => &self.metrics.validation_requests_queued,
+            RequestPriority::Query => &self.metrics.query_requests_queued,
+        }
+    }
+
+    fn running_gauge(&self, priority: RequestPriority) -> &IntGauge {
+        match priority {
+            RequestPriority::BlockExecution => &self.metrics.block_execution_requests_running,
+            RequestPriority::Validation => &self.metrics.validation_requests_running,
+            RequestPriority::Query => &self.metrics.query_requests_running,
+        }
+    }
+
+    /// Returns `true` if any requests of a higher priority than `priority` are waiting to run.
+    fn higher_priority_queued(&self, priority: RequestPriority) -> bool {
+        RequestPriority::ALL
+            .iter()
+            .take_while(|higher_priority| **higher_priority < priority)
+            .any(|higher_priority| {
+                self.classes[higher_priority.index()]
+                    .queued
+                    .load(Ordering::SeqCst)
+                    > 0
+            })
+    }
+
+    /// Runs `task` on a blocking thread once the concurrency limit of `priority` allows and no
+    /// requests of a higher priority are waiting to run.
+    pub(super) async fn run<T, V>(&self, priority: RequestPriority, task: T) -> V
+    where
+        T: 'static + Send + FnOnce() -> V,
+        V: 'static + Send,
+    {
+        let class = &self.classes[priority.index()];
+        class.queued.fetch_add(1, Ordering::SeqCst);
+        self.queued_gauge(priority).inc();
+        let queued_guard = QueuedGuard {
+            scheduler: self,
+            priority,
+        };
+
+        // This will never panic since the semaphore is never closed.
+        let _permit = class.running_limit.acquire().await.unwrap();
+        loop {
+            let queue_emptied = self.queue_emptied.notified();
+            if !self.higher_priority_queued(priority) {
+                break;
+            }
+            queue_emptied.await;
+        }
+        drop(queued_guard);
+
+        let running_gauge = self.running_gauge(priority);
+        running_gauge.inc();
+        let result = tokio::task::spawn_blocking(task).await;
+        running_gauge.dec();
+        result.expect("task panicked")
+    }
+}
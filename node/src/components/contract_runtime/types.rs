@@ -1,6 +1,7 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 use datasize::DataSize;
+use serde::Serialize;
 
 use casper_execution_engine::{
     core::engine_state::GetEraValidatorsRequest, shared::execution_journal::ExecutionJournal,
@@ -8,7 +9,7 @@ use casper_execution_engine::{
 use casper_hashing::Digest;
 use casper_types::{EraId, ExecutionResult, ProtocolVersion, PublicKey, U512};
 
-use crate::types::{Block, DeployHash, DeployHeader};
+use crate::types::{Block, ContractEvent, DeployHash, DeployHeader};
 
 /// Request for validator weights for a specific era.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -100,6 +101,9 @@ pub struct BlockAndExecutionEffects {
     pub block: Box<Block>,
     /// The results from executing the deploys in the block.
     pub execution_results: Vec<(DeployHash, DeployHeader, ExecutionResult)>,
+    /// The events emitted by contracts, in the order emitted, by the hash of the deploy emitting
+    /// them.
+    pub contract_events: HashMap<DeployHash, Vec<ContractEvent>>,
     /// The [`ExecutionJournal`] and the upcoming validator sets determined by the `step`
     pub maybe_step_effect_and_upcoming_era_validators: Option<StepEffectAndUpcomingEraValidators>,
 }
@@ -116,3 +120,32 @@ impl From<BlockAndExecutionEffects> for Block {
         *block_and_execution_effects.block
     }
 }
+
+/// Progress made while executing the deploys of a finalized block, reported for each deploy as
+/// the block executes.
+#[derive(Clone, Debug, DataSize, Serialize)]
+pub enum DeployExecutionProgress {
+    /// Execution of the given deploy has started.
+    Started {
+        /// The height of the block being executed.
+        block_height: u64,
+        /// The hash of the deploy.
+        deploy_hash: DeployHash,
+        /// The account which sent the deploy.
+        account: PublicKey,
+    },
+    /// Execution of the given deploy has finished and its effects have been applied to the
+    /// block's uncommitted state.
+    Finished {
+        /// The height of the block being executed.
+        block_height: u64,
+        /// The hash of the deploy.
+        deploy_hash: DeployHash,
+        /// The account which sent the deploy.
+        account: PublicKey,
+        /// The gas cost of executing the deploy.
+        cost: U512,
+        /// The error message if execution failed.
+        error_message: Option<String>,
+    },
+}
@@ -1,8 +1,9 @@
 mod event;
 mod metrics;
+mod pending_deploys;
 mod tests;
 
-use std::fmt::Debug;
+use std::{collections::BTreeSet, fmt::Debug};
 
 use datasize::DataSize;
 use prometheus::Registry;
@@ -10,18 +11,22 @@ use serde::Serialize;
 use thiserror::Error;
 use tracing::{debug, error};
 
-use casper_execution_engine::core::engine_state::{
-    executable_deploy_item::{
-        ContractIdentifier, ContractPackageIdentifier, ExecutableDeployItemIdentifier,
+use casper_execution_engine::{
+    core::engine_state::{
+        executable_deploy_item::{
+            ContractIdentifier, ContractPackageIdentifier, ExecutableDeployItemIdentifier,
+        },
+        ExecutableDeployItem, MAX_PAYMENT,
     },
-    ExecutableDeployItem, MAX_PAYMENT,
+    storage::trie::merkle_proof::TrieMerkleProof,
 };
 use casper_hashing::Digest;
 use casper_types::{
-    account::{Account, AccountHash},
+    account::{Account, AccountHash, Weight},
     system::auction::ARG_AMOUNT,
-    Contract, ContractHash, ContractPackage, ContractPackageHash, ContractVersion,
-    ContractVersionKey, Key, ProtocolVersion, Timestamp, U512,
+    CLType, Contract, ContractHash, ContractPackage, ContractPackageHash, ContractVersion,
+    ContractVersionKey, EntryPoint, Key, ProtocolVersion, RuntimeArgs, StoredValue, Timestamp,
+    U512,
 };
 
 use crate::{
@@ -31,15 +36,33 @@ use crate::{
         requests::{ContractRuntimeRequest, StorageRequest},
         EffectBuilder, EffectExt, Effects, Responder,
     },
-    types::{chainspec::DeployConfig, BlockHeader, Chainspec, Deploy, DeployConfigurationFailure},
+    reactor::QueueKind,
+    types::{
+        chainspec::DeployConfig, Approval, BlockHeader, Chainspec, Deploy,
+        DeployConfigurationFailure, DeployHash, DeployWithApprovals,
+    },
     utils::Source,
     NodeRng,
 };
 
 pub(crate) use event::{Event, EventMetadata};
+use pending_deploys::{PendingDeploys, MAX_PENDING_DEPLOYS};
 
 const ARG_TARGET: &str = "target";
 
+/// The balance of a client deploy's account as read during the balance check, along with a proof
+/// of it.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct BalanceProof {
+    /// The state root hash the balance was read at.
+    pub(crate) state_root_hash: Digest,
+    /// The balance of the account's main purse.
+    pub(crate) balance_value: U512,
+    /// The Merkle proof of the balance.
+    #[serde(skip_serializing)]
+    pub(crate) proof: Box<TrieMerkleProof<Key, StoredValue>>,
+}
+
 #[derive(Debug, Error, Serialize)]
 pub(crate) enum Error {
     /// The block chain has no blocks.
@@ -65,6 +88,49 @@ pub(crate) enum Error {
         /// The timestamp when the node validated the expiry timestamp.
         current_node_timestamp: Timestamp,
     },
+
+    /// The node is shutting down and no longer accepts deploys.
+    #[error("node is shutting down")]
+    ShuttingDown,
+
+    /// No deploy with the given hash is awaiting approvals.
+    #[error("no deploy with hash {deploy_hash} is awaiting approvals")]
+    UnknownPendingDeploy { deploy_hash: DeployHash },
+
+    /// The deploy cannot await approvals, as too many other deploys are awaiting approvals.
+    #[error("cannot hold more than {max_pending_deploys} deploys awaiting approvals")]
+    TooManyPendingDeploys { max_pending_deploys: usize },
+}
+
+impl Error {
+    /// Returns whether the deploy was rejected due to the state of its account, in which case all
+    /// other deploys from the same account would currently be rejected too.
+    pub(crate) fn is_account_failure(&self) -> bool {
+        matches!(
+            self,
+            Error::InvalidDeployParameters {
+                failure: DeployParameterFailure::NonexistentAccount { .. }
+                    | DeployParameterFailure::InsufficientBalance { .. }
+                    | DeployParameterFailure::UnknownBalance { .. },
+                ..
+            }
+        )
+    }
+
+    /// Returns whether the deploy was rejected due to a transient condition of the node rather
+    /// than a flaw of the deploy, in which case it may be accepted if submitted again later.
+    pub(crate) fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Error::EmptyBlockchain
+                | Error::ShuttingDown
+                | Error::TooManyPendingDeploys { .. }
+                | Error::InvalidDeployParameters {
+                    failure: DeployParameterFailure::UnknownBalance { .. },
+                    ..
+                }
+        )
+    }
 }
 
 /// A representation of the way in which a deploy failed validation checks.
@@ -108,6 +174,22 @@ pub(crate) enum DeployParameterFailure {
     /// Failed to parse payment "amount" runtime argument.
     #[error("failed to parse payment 'amount' runtime argument as U512")]
     FailedToParsePaymentAmount,
+    /// Payment "amount" runtime argument is zero.
+    #[error("payment 'amount' runtime argument must be greater than zero")]
+    ZeroPaymentAmount,
+    /// Payment "amount" runtime argument exceeds the maximum payment cost.
+    #[error("payment 'amount' of {got} exceeds the maximum payment cost of {max_payment_cost}")]
+    ExcessivePaymentAmount { max_payment_cost: U512, got: U512 },
+    /// Payment runtime argument has a type other than the one expected by the entry point.
+    #[error(
+        "payment runtime argument '{arg_name}' has type {got:?}, but the entry point expects \
+        {expected:?}"
+    )]
+    InvalidPaymentArgType {
+        arg_name: String,
+        expected: CLType,
+        got: CLType,
+    },
     /// Missing transfer "target" runtime argument.
     #[error("missing transfer 'target' runtime argument")]
     MissingTransferTarget,
@@ -116,6 +198,21 @@ pub(crate) enum DeployParameterFailure {
     MissingModuleBytes,
 }
 
+/// The status of a deploy submitted with possibly insufficient approvals.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub(crate) enum PendingDeployStatus {
+    /// The approvals do not yet meet the account's deployment threshold; the deploy is held by this
+    /// node until further approvals are added.
+    AwaitingApprovals {
+        /// The cumulative weight of the keys which approved the deploy.
+        signature_weight: Weight,
+        /// The account's deployment threshold.
+        deployment_threshold: Weight,
+    },
+    /// The approvals meet the account's deployment threshold and the deploy has been accepted.
+    Accepted,
+}
+
 /// A helper trait constraining `DeployAcceptor` compatible reactor events.
 pub(crate) trait ReactorEventT:
     From<Event>
@@ -147,6 +244,10 @@ pub struct DeployAcceptor {
     deploy_config: DeployConfig,
     max_associated_keys: u32,
     metrics: metrics::Metrics,
+    /// Whether deploys are still accepted, i.e. the node is not shutting down.
+    is_accepting: bool,
+    /// Deploys submitted by clients which are awaiting further approvals.
+    pending_deploys: PendingDeploys,
 }
 
 impl DeployAcceptor {
@@ -160,9 +261,21 @@ impl DeployAcceptor {
             deploy_config: chainspec.deploy_config,
             max_associated_keys: chainspec.core_config.max_associated_keys,
             metrics: metrics::Metrics::new(registry)?,
+            is_accepting: true,
+            pending_deploys: PendingDeploys::default(),
         })
     }
 
+    /// Sets the deploy config for accepting deploys, e.g. after it was updated via governance.
+    pub(crate) fn set_deploy_config(&mut self, deploy_config: DeployConfig) {
+        self.deploy_config = deploy_config;
+    }
+
+    /// Stops accepting deploys, as the node is shutting down.
+    pub(crate) fn stop_accepting(&mut self) {
+        self.is_accepting = false;
+    }
+
     /// Handles receiving a new `Deploy` from a peer or client.
     /// In the case of a peer, there should be no responder and the variant should be `None`
     /// In the case of a client, there should be a responder to communicate the validity of the
@@ -172,8 +285,16 @@ impl DeployAcceptor {
         effect_builder: EffectBuilder<REv>,
         deploy: Box<Deploy>,
         source: Source,
-        maybe_responder: Option<Responder<Result<(), Error>>>,
+        maybe_responder: Option<Responder<Result<Option<BalanceProof>, Error>>>,
     ) -> Effects<Event> {
+        if !self.is_accepting {
+            debug!(%deploy, "not accepting deploy, as the node is shutting down");
+            return match maybe_responder {
+                Some(responder) => responder.respond(Err(Error::ShuttingDown)).ignore(),
+                None => Effects::new(),
+            };
+        }
+
         let verification_start_timestamp = Timestamp::now();
         let acceptable_result = deploy.is_config_compliant(
             &self.chain_name,
@@ -322,10 +443,16 @@ impl DeployAcceptor {
                 }
                 effect_builder
                     .check_purse_balance(prestate_hash, account.main_purse())
-                    .event(move |maybe_balance_value| Event::GetBalanceResult {
+                    .event(move |maybe_balance| Event::GetBalanceResult {
                         event_metadata,
                         prestate_hash,
-                        maybe_balance_value,
+                        maybe_balance_proof: maybe_balance.map(|(balance_value, proof)| {
+                            BalanceProof {
+                                state_root_hash: prestate_hash,
+                                balance_value,
+                                proof,
+                            }
+                        }),
                         account_hash: account.account_hash(),
                         verification_start_timestamp,
                     })
@@ -336,9 +463,9 @@ impl DeployAcceptor {
     fn handle_get_balance_result<REv: ReactorEventT>(
         &self,
         effect_builder: EffectBuilder<REv>,
-        event_metadata: EventMetadata,
+        mut event_metadata: EventMetadata,
         prestate_hash: Digest,
-        maybe_balance_value: Option<U512>,
+        maybe_balance_proof: Option<BalanceProof>,
         account_hash: AccountHash,
         verification_start_timestamp: Timestamp,
     ) -> Effects<Event> {
@@ -349,7 +476,7 @@ impl DeployAcceptor {
             // TODO: Change this to a fatal!
             panic!("Balance checks for deploys received from peers should never occur.")
         }
-        match maybe_balance_value {
+        match maybe_balance_proof {
             None => {
                 let error = Error::InvalidDeployParameters {
                     prestate_hash,
@@ -363,8 +490,8 @@ impl DeployAcceptor {
                     verification_start_timestamp,
                 )
             }
-            Some(balance) => {
-                let has_minimum_balance = balance >= *MAX_PAYMENT;
+            Some(balance_proof) => {
+                let has_minimum_balance = balance_proof.balance_value >= *MAX_PAYMENT;
                 if !has_minimum_balance {
                     let error = Error::InvalidDeployParameters {
                         prestate_hash,
@@ -378,6 +505,8 @@ impl DeployAcceptor {
                         verification_start_timestamp,
                     )
                 } else {
+                    // Keep the proof to return it to the client once the deploy is accepted.
+                    event_metadata.balance_proof = Some(balance_proof);
                     self.verify_payment_logic(
                         effect_builder,
                         event_metadata,
@@ -416,22 +545,35 @@ impl DeployAcceptor {
             ExecutableDeployItem::ModuleBytes { module_bytes, args } => {
                 // module bytes being empty implies the payment executable is standard payment.
                 if module_bytes.is_empty() {
-                    if let Some(value) = args.get(ARG_AMOUNT) {
-                        if value.clone().into_t::<U512>().is_err() {
-                            debug!("failed to parse payment amount in payment logic");
+                    let amount = match args.get(ARG_AMOUNT) {
+                        Some(value) => match value.clone().into_t::<U512>() {
+                            Ok(amount) => amount,
+                            Err(_) => {
+                                debug!("failed to parse payment amount in payment logic");
+                                return self.handle_invalid_deploy_result(
+                                    effect_builder,
+                                    event_metadata,
+                                    make_error(DeployParameterFailure::FailedToParsePaymentAmount),
+                                    verification_start_timestamp,
+                                );
+                            }
+                        },
+                        None => {
+                            debug!("payment amount missing in payment logic");
                             return self.handle_invalid_deploy_result(
                                 effect_builder,
                                 event_metadata,
-                                make_error(DeployParameterFailure::FailedToParsePaymentAmount),
+                                make_error(DeployParameterFailure::MissingPaymentAmount),
                                 verification_start_timestamp,
                             );
                         }
-                    } else {
-                        debug!("payment amount missing in payment logic");
+                    };
+                    if let Err(failure) = self.check_payment_amount(amount) {
+                        debug!(%amount, "payment amount out of bounds in payment logic");
                         return self.handle_invalid_deploy_result(
                             effect_builder,
                             event_metadata,
-                            make_error(DeployParameterFailure::MissingPaymentAmount),
+                            make_error(failure),
                             verification_start_timestamp,
                         );
                     }
@@ -499,6 +641,22 @@ impl DeployAcceptor {
         }
     }
 
+    /// Checks that the standard payment amount is non-zero and, if a maximum payment cost is
+    /// configured, doesn't exceed it.
+    fn check_payment_amount(&self, amount: U512) -> Result<(), DeployParameterFailure> {
+        if amount.is_zero() {
+            return Err(DeployParameterFailure::ZeroPaymentAmount);
+        }
+        let max_payment_cost = self.deploy_config.max_payment_cost.value();
+        if !max_payment_cost.is_zero() && amount > max_payment_cost {
+            return Err(DeployParameterFailure::ExcessivePaymentAmount {
+                max_payment_cost,
+                got: amount,
+            });
+        }
+        Ok(())
+    }
+
     fn verify_session_logic<REv: ReactorEventT>(
         &self,
         effect_builder: EffectBuilder<REv>,
@@ -632,6 +790,30 @@ impl DeployAcceptor {
                 );
             }
             if is_payment {
+                let arg_types_check =
+                    contract
+                        .entry_point(&entry_point)
+                        .map_or(Ok(()), |entry_point| {
+                            check_arg_types(entry_point, event_metadata.deploy.payment().args())
+                        });
+                if let Err(failure) = arg_types_check {
+                    debug!(
+                        ?entry_point,
+                        ?contract_hash,
+                        %failure,
+                        "incompatible payment args for entry point"
+                    );
+                    let error = Error::InvalidDeployParameters {
+                        prestate_hash,
+                        failure,
+                    };
+                    return self.handle_invalid_deploy_result(
+                        effect_builder,
+                        event_metadata,
+                        error,
+                        verification_start_timestamp,
+                    );
+                }
                 return self.verify_session_logic(
                     effect_builder,
                     event_metadata,
@@ -765,6 +947,7 @@ impl DeployAcceptor {
             deploy,
             source,
             maybe_responder,
+            balance_proof,
         } = event_metadata;
         self.metrics.observe_accepted(verification_start_timestamp);
         let mut effects = Effects::new();
@@ -774,11 +957,19 @@ impl DeployAcceptor {
                     .announce_new_deploy_accepted(deploy, source)
                     .ignore(),
             );
+        } else {
+            // The deploy's approvals have been verified already, but may differ from the ones of
+            // the stored copy.
+            effects.extend(self.merge_approvals(
+                effect_builder,
+                Box::new(DeployWithApprovals::from(&*deploy)),
+                source,
+            ));
         }
 
         // success
         if let Some(responder) = maybe_responder {
-            effects.extend(responder.respond(Ok(())).ignore());
+            effects.extend(responder.respond(Ok(balance_proof)).ignore());
         }
         effects
     }
@@ -810,6 +1001,303 @@ impl DeployAcceptor {
             })
     }
 
+    /// Handles receiving a deploy from a client which may lack approvals needed to meet its
+    /// account's deployment threshold.
+    ///
+    /// The approvals are combined with those of the pending deploy with the same hash, if any.
+    fn accept_pending<REv: ReactorEventT>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        deploy: Box<Deploy>,
+        responder: Responder<Result<PendingDeployStatus, Error>>,
+    ) -> Effects<Event> {
+        if !self.is_accepting {
+            debug!(%deploy, "not accepting pending deploy, as the node is shutting down");
+            return responder.respond(Err(Error::ShuttingDown)).ignore();
+        }
+        self.pending_deploys.prune_expired(Timestamp::now());
+        self.check_pending(effect_builder, deploy, responder)
+    }
+
+    /// Handles receiving further approvals from a client for a pending deploy.
+    fn add_approvals<REv: ReactorEventT>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        deploy_hash: DeployHash,
+        approvals: BTreeSet<Approval>,
+        responder: Responder<Result<PendingDeployStatus, Error>>,
+    ) -> Effects<Event> {
+        if !self.is_accepting {
+            debug!(%deploy_hash, "not adding approvals, as the node is shutting down");
+            return responder.respond(Err(Error::ShuttingDown)).ignore();
+        }
+        self.pending_deploys.prune_expired(Timestamp::now());
+        let mut deploy = match self.pending_deploys.get(&deploy_hash) {
+            Some(deploy) => deploy.clone(),
+            None => {
+                debug!(%deploy_hash, "no pending deploy to add approvals to");
+                return responder
+                    .respond(Err(Error::UnknownPendingDeploy { deploy_hash }))
+                    .ignore();
+            }
+        };
+        deploy.replace_approvals(approvals);
+        self.check_pending(effect_builder, Box::new(deploy), responder)
+    }
+
+    /// Checks a pending deploy, before looking up its account's deployment threshold.
+    fn check_pending<REv: ReactorEventT>(
+        &self,
+        effect_builder: EffectBuilder<REv>,
+        deploy: Box<Deploy>,
+        responder: Responder<Result<PendingDeployStatus, Error>>,
+    ) -> Effects<Event> {
+        if let Err(error) = deploy.is_config_compliant(
+            &self.chain_name,
+            &self.deploy_config,
+            self.max_associated_keys,
+        ) {
+            debug!(%deploy, %error, "pending deploy is incorrectly configured");
+            return responder
+                .respond(Err(Error::InvalidDeployConfiguration(error)))
+                .ignore();
+        }
+        let current_node_timestamp = Timestamp::now();
+        if deploy.header().expired(current_node_timestamp) {
+            debug!(%deploy, "pending deploy has expired");
+            return responder
+                .respond(Err(Error::ExpiredDeploy {
+                    deploy_expiry_timestamp: deploy.header().expires(),
+                    current_node_timestamp,
+                }))
+                .ignore();
+        }
+        // Only cryptographically valid approvals are ever held, so merging them with the pending
+        // deploy's keeps it valid.
+        if let Err(error) = deploy.is_valid() {
+            debug!(%deploy, %error, "pending deploy is cryptographically invalid");
+            return responder
+                .respond(Err(Error::InvalidDeployConfiguration(error)))
+                .ignore();
+        }
+
+        effect_builder
+            .get_highest_block_header_from_storage()
+            .event(
+                move |maybe_block_header| Event::GetPendingBlockHeaderResult {
+                    deploy,
+                    maybe_block_header: Box::new(maybe_block_header),
+                    responder,
+                },
+            )
+    }
+
+    fn handle_get_pending_block_result<REv: ReactorEventT>(
+        &self,
+        effect_builder: EffectBuilder<REv>,
+        deploy: Box<Deploy>,
+        maybe_block: Option<BlockHeader>,
+        responder: Responder<Result<PendingDeployStatus, Error>>,
+    ) -> Effects<Event> {
+        let prestate_hash = match maybe_block {
+            Some(block) => *block.state_root_hash(),
+            // this should be unreachable per current design of the system
+            None => return responder.respond(Err(Error::EmptyBlockchain)).ignore(),
+        };
+        let account_key = deploy.header().account().to_account_hash().into();
+        effect_builder
+            .get_account_from_global_state(prestate_hash, account_key)
+            .event(move |maybe_account| Event::GetPendingAccountResult {
+                deploy,
+                prestate_hash,
+                maybe_account,
+                responder,
+            })
+    }
+
+    /// Accepts the pending deploy if its approvals, combined with those already held, meet its
+    /// account's deployment threshold, or holds it until further approvals are added otherwise.
+    fn handle_get_pending_account_result<REv: ReactorEventT>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        deploy: Box<Deploy>,
+        prestate_hash: Digest,
+        maybe_account: Option<Account>,
+        responder: Responder<Result<PendingDeployStatus, Error>>,
+    ) -> Effects<Event> {
+        let make_error = |failure: DeployParameterFailure| Error::InvalidDeployParameters {
+            prestate_hash,
+            failure,
+        };
+
+        let account = match maybe_account {
+            Some(account) => account,
+            None => {
+                let account_hash = deploy.header().account().to_account_hash();
+                debug!(
+                    ?account_hash,
+                    "nonexistent account associated with the deploy"
+                );
+                return responder
+                    .respond(Err(make_error(
+                        DeployParameterFailure::NonexistentAccount { account_hash },
+                    )))
+                    .ignore();
+            }
+        };
+
+        // Approvals may have been added concurrently, so merge with the latest pending deploy.
+        let deploy = self.pending_deploys.merge(*deploy);
+        let authorization_keys: BTreeSet<AccountHash> = deploy
+            .approvals()
+            .iter()
+            .map(|approval| approval.signer().to_account_hash())
+            .collect();
+        if !account.can_authorize(&authorization_keys) {
+            debug!(?authorization_keys, "account authorization invalid");
+            return responder
+                .respond(Err(make_error(
+                    DeployParameterFailure::InvalidAssociatedKeys,
+                )))
+                .ignore();
+        }
+
+        if account.can_deploy_with(&authorization_keys) {
+            debug!(%deploy, "pending deploy meets deployment threshold");
+            self.pending_deploys.remove(deploy.id());
+            return async move {
+                let result = effect_builder
+                    .make_request(
+                        |responder| Event::Accept {
+                            deploy: Box::new(deploy),
+                            source: Source::Client,
+                            maybe_responder: Some(responder),
+                        },
+                        QueueKind::Api,
+                    )
+                    .await;
+                responder
+                    .respond(result.map(|_| PendingDeployStatus::Accepted))
+                    .await
+            }
+            .ignore();
+        }
+
+        let status = PendingDeployStatus::AwaitingApprovals {
+            signature_weight: account
+                .associated_keys()
+                .calculate_keys_weight(&authorization_keys),
+            deployment_threshold: *account.action_thresholds().deployment(),
+        };
+        if !self.pending_deploys.insert(deploy) {
+            debug!("too many pending deploys");
+            return responder
+                .respond(Err(Error::TooManyPendingDeploys {
+                    max_pending_deploys: MAX_PENDING_DEPLOYS,
+                }))
+                .ignore();
+        }
+        responder.respond(Ok(status)).ignore()
+    }
+
+    /// Handles receiving approvals of an already stored deploy from a peer.
+    fn accept_approvals<REv: ReactorEventT>(
+        &self,
+        effect_builder: EffectBuilder<REv>,
+        deploy_approvals: Box<DeployWithApprovals>,
+        source: Source,
+    ) -> Effects<Event> {
+        if !self.is_accepting {
+            debug!(%deploy_approvals, "not accepting approvals, as the node is shutting down");
+            return Effects::new();
+        }
+        if deploy_approvals.approvals().len() > self.max_associated_keys as usize {
+            debug!(%deploy_approvals, %source, "received too many deploy approvals");
+            return Effects::new();
+        }
+        if let Err(error) = deploy_approvals.verify_approvals() {
+            debug!(%deploy_approvals, %source, %error, "received invalid deploy approvals");
+            return Effects::new();
+        }
+        self.merge_approvals(effect_builder, deploy_approvals, source)
+    }
+
+    /// Merges the given verified approvals into the ones known for the stored deploy.
+    ///
+    /// Only approvals by associated keys of the deploy's account are kept, so the approvals known
+    /// for a deploy can't grow beyond the account's associated keys.
+    fn merge_approvals<REv: ReactorEventT>(
+        &self,
+        effect_builder: EffectBuilder<REv>,
+        deploy_approvals: Box<DeployWithApprovals>,
+        source: Source,
+    ) -> Effects<Event> {
+        let deploy_hash = *deploy_approvals.deploy_hash();
+        async move {
+            let deploy = effect_builder
+                .get_deploys_from_storage(vec![deploy_hash])
+                .await
+                .pop()
+                .flatten()?
+                .discard_finalized_approvals();
+            let block_header = effect_builder
+                .get_highest_block_header_from_storage()
+                .await?;
+            let account_key = deploy.header().account().to_account_hash().into();
+            let account = effect_builder
+                .get_account_from_global_state(*block_header.state_root_hash(), account_key)
+                .await?;
+            let approvals = deploy_approvals
+                .into_approvals()
+                .into_iter()
+                .filter(|approval| {
+                    account
+                        .associated_keys()
+                        .contains_key(&approval.signer().to_account_hash())
+                })
+                .collect();
+            let new_approvals = effect_builder
+                .put_deploy_approvals_to_storage(Box::new(DeployWithApprovals::new(
+                    deploy_hash,
+                    approvals,
+                )))
+                .await;
+            Some(new_approvals)
+        }
+        .event(
+            move |maybe_new_approvals| Event::PutApprovalsToStorageResult {
+                deploy_hash,
+                new_approvals: maybe_new_approvals.unwrap_or_default(),
+                source,
+            },
+        )
+    }
+
+    /// Announces the approvals not previously known for the stored deploy, if any.
+    fn handle_put_approvals_to_storage<REv: ReactorEventT>(
+        &self,
+        effect_builder: EffectBuilder<REv>,
+        deploy_hash: DeployHash,
+        new_approvals: BTreeSet<Approval>,
+        source: Source,
+    ) -> Effects<Event> {
+        if new_approvals.is_empty() {
+            return Effects::new();
+        }
+        debug!(
+            %deploy_hash,
+            count = new_approvals.len(),
+            %source,
+            "learned new deploy approvals"
+        );
+        effect_builder
+            .announce_new_deploy_approvals(
+                Box::new(DeployWithApprovals::new(deploy_hash, new_approvals)),
+                source,
+            )
+            .ignore()
+    }
+
     fn handle_invalid_deploy_result<REv: ReactorEventT>(
         &self,
         effect_builder: EffectBuilder<REv>,
@@ -821,6 +1309,7 @@ impl DeployAcceptor {
             deploy,
             source,
             maybe_responder,
+            ..
         } = event_metadata;
         self.metrics.observe_rejected(verification_start_timestamp);
         let mut effects = Effects::new();
@@ -838,6 +1327,29 @@ impl DeployAcceptor {
     }
 }
 
+/// Checks that each of the given args which is a parameter of the entry point has the parameter's
+/// type.
+fn check_arg_types(
+    entry_point: &EntryPoint,
+    args: &RuntimeArgs,
+) -> Result<(), DeployParameterFailure> {
+    for parameter in entry_point.args() {
+        if *parameter.cl_type() == CLType::Any {
+            continue;
+        }
+        if let Some(value) = args.get(parameter.name()) {
+            if value.cl_type() != parameter.cl_type() {
+                return Err(DeployParameterFailure::InvalidPaymentArgType {
+                    arg_name: parameter.name().to_string(),
+                    expected: parameter.cl_type().clone(),
+                    got: value.cl_type().clone(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
 impl<REv: ReactorEventT> Component<REv> for DeployAcceptor {
     type Event = Event;
     type ConstructionError = prometheus::Error;
@@ -880,14 +1392,14 @@ impl<REv: ReactorEventT> Component<REv> for DeployAcceptor {
             Event::GetBalanceResult {
                 event_metadata,
                 prestate_hash,
-                maybe_balance_value,
+                maybe_balance_proof,
                 account_hash,
                 verification_start_timestamp,
             } => self.handle_get_balance_result(
                 effect_builder,
                 event_metadata,
                 prestate_hash,
-                maybe_balance_value,
+                maybe_balance_proof,
                 account_hash,
                 verification_start_timestamp,
             ),
@@ -944,6 +1456,50 @@ impl<REv: ReactorEventT> Component<REv> for DeployAcceptor {
                 is_new,
                 verification_start_timestamp,
             ),
+            Event::AcceptPending { deploy, responder } => {
+                self.accept_pending(effect_builder, deploy, responder)
+            }
+            Event::AddApprovals {
+                deploy_hash,
+                approvals,
+                responder,
+            } => self.add_approvals(effect_builder, deploy_hash, approvals, responder),
+            Event::GetPendingBlockHeaderResult {
+                deploy,
+                maybe_block_header,
+                responder,
+            } => self.handle_get_pending_block_result(
+                effect_builder,
+                deploy,
+                *maybe_block_header,
+                responder,
+            ),
+            Event::GetPendingAccountResult {
+                deploy,
+                prestate_hash,
+                maybe_account,
+                responder,
+            } => self.handle_get_pending_account_result(
+                effect_builder,
+                deploy,
+                prestate_hash,
+                maybe_account,
+                responder,
+            ),
+            Event::AcceptApprovals {
+                deploy_approvals,
+                source,
+            } => self.accept_approvals(effect_builder, deploy_approvals, source),
+            Event::PutApprovalsToStorageResult {
+                deploy_hash,
+                new_approvals,
+                source,
+            } => self.handle_put_approvals_to_storage(
+                effect_builder,
+                deploy_hash,
+                new_approvals,
+                source,
+            ),
         }
     }
 }
@@ -1,18 +1,21 @@
-use std::fmt::{self, Display, Formatter};
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display, Formatter},
+};
 
 use serde::Serialize;
 
 use super::Source;
 use crate::{
-    components::deploy_acceptor::Error,
-    effect::{announcements::RpcServerAnnouncement, Responder},
-    types::{BlockHeader, Deploy},
+    components::deploy_acceptor::{BalanceProof, Error, PendingDeployStatus},
+    effect::{announcements::RpcServerAnnouncement, incoming::DeployApprovalsIncoming, Responder},
+    types::{Approval, BlockHeader, Deploy, DeployHash, DeployWithApprovals},
 };
 
 use casper_hashing::Digest;
 use casper_types::{
     account::{Account, AccountHash},
-    Contract, ContractHash, ContractPackage, ContractPackageHash, ContractVersion, Timestamp, U512,
+    Contract, ContractHash, ContractPackage, ContractPackageHash, ContractVersion, Timestamp,
 };
 
 /// A utility struct to hold duplicated information across events.
@@ -20,19 +23,22 @@ use casper_types::{
 pub(crate) struct EventMetadata {
     pub(crate) deploy: Box<Deploy>,
     pub(crate) source: Source,
-    pub(crate) maybe_responder: Option<Responder<Result<(), Error>>>,
+    pub(crate) maybe_responder: Option<Responder<Result<Option<BalanceProof>, Error>>>,
+    /// The proof of the account's balance, once it has been checked.
+    pub(crate) balance_proof: Option<BalanceProof>,
 }
 
 impl EventMetadata {
     pub(crate) fn new(
         deploy: Box<Deploy>,
         source: Source,
-        maybe_responder: Option<Responder<Result<(), Error>>>,
+        maybe_responder: Option<Responder<Result<Option<BalanceProof>, Error>>>,
     ) -> Self {
         EventMetadata {
             deploy,
             source,
             maybe_responder,
+            balance_proof: None,
         }
     }
 }
@@ -44,7 +50,7 @@ pub(crate) enum Event {
     Accept {
         deploy: Box<Deploy>,
         source: Source,
-        maybe_responder: Option<Responder<Result<(), Error>>>,
+        maybe_responder: Option<Responder<Result<Option<BalanceProof>, Error>>>,
     },
     /// The result of the `DeployAcceptor` putting a `Deploy` to the storage component.
     PutToStorageResult {
@@ -69,7 +75,7 @@ pub(crate) enum Event {
     GetBalanceResult {
         event_metadata: EventMetadata,
         prestate_hash: Digest,
-        maybe_balance_value: Option<U512>,
+        maybe_balance_proof: Option<BalanceProof>,
         account_hash: AccountHash,
         verification_start_timestamp: Timestamp,
     },
@@ -92,6 +98,52 @@ pub(crate) enum Event {
         maybe_contract_package: Option<ContractPackage>,
         verification_start_timestamp: Timestamp,
     },
+    /// The initiating event to accept a `Deploy` which may lack approvals needed to meet its
+    /// account's deployment threshold.
+    AcceptPending {
+        deploy: Box<Deploy>,
+        responder: Responder<Result<PendingDeployStatus, Error>>,
+    },
+    /// The initiating event to add approvals to a `Deploy` awaiting approvals.
+    AddApprovals {
+        deploy_hash: DeployHash,
+        approvals: BTreeSet<Approval>,
+        responder: Responder<Result<PendingDeployStatus, Error>>,
+    },
+    /// The result of querying the highest available `BlockHeader` to check a pending `Deploy`.
+    GetPendingBlockHeaderResult {
+        deploy: Box<Deploy>,
+        maybe_block_header: Box<Option<BlockHeader>>,
+        responder: Responder<Result<PendingDeployStatus, Error>>,
+    },
+    /// The result of querying global state for the `Account` associated with a pending `Deploy`.
+    GetPendingAccountResult {
+        deploy: Box<Deploy>,
+        prestate_hash: Digest,
+        maybe_account: Option<Account>,
+        responder: Responder<Result<PendingDeployStatus, Error>>,
+    },
+    /// The initiating event to merge approvals received from a peer into the ones known for an
+    /// already stored `Deploy`.
+    AcceptApprovals {
+        deploy_approvals: Box<DeployWithApprovals>,
+        source: Source,
+    },
+    /// The result of merging approvals into the ones known for an already stored `Deploy`.
+    PutApprovalsToStorageResult {
+        deploy_hash: DeployHash,
+        new_approvals: BTreeSet<Approval>,
+        source: Source,
+    },
+}
+
+impl From<DeployApprovalsIncoming> for Event {
+    fn from(incoming: DeployApprovalsIncoming) -> Self {
+        Event::AcceptApprovals {
+            deploy_approvals: incoming.message,
+            source: Source::Peer(incoming.sender),
+        }
+    }
 }
 
 impl From<RpcServerAnnouncement> for Event {
@@ -102,6 +154,18 @@ impl From<RpcServerAnnouncement> for Event {
                 source: Source::Client,
                 maybe_responder: responder,
             },
+            RpcServerAnnouncement::PendingDeployReceived { deploy, responder } => {
+                Event::AcceptPending { deploy, responder }
+            }
+            RpcServerAnnouncement::DeployApprovalsReceived {
+                deploy_hash,
+                approvals,
+                responder,
+            } => Event::AddApprovals {
+                deploy_hash,
+                approvals,
+                responder,
+            },
         }
     }
 }
@@ -176,6 +240,43 @@ impl Display for Event {
                     prestate_hash
                 )
             }
+            Event::AcceptPending { deploy, .. } => {
+                write!(formatter, "accept pending {}", deploy.id())
+            }
+            Event::AddApprovals {
+                deploy_hash,
+                approvals,
+                ..
+            } => write!(
+                formatter,
+                "add {} approvals to pending {}",
+                approvals.len(),
+                deploy_hash
+            ),
+            Event::GetPendingBlockHeaderResult { deploy, .. } => write!(
+                formatter,
+                "received highest block from storage to check pending deploy with hash {}.",
+                deploy.id()
+            ),
+            Event::GetPendingAccountResult { deploy, .. } => write!(
+                formatter,
+                "verifying account to check pending deploy with hash {}.",
+                deploy.id()
+            ),
+            Event::AcceptApprovals {
+                deploy_approvals,
+                source,
+            } => write!(formatter, "accept {} from {}", deploy_approvals, source),
+            Event::PutApprovalsToStorageResult {
+                deploy_hash,
+                new_approvals,
+                ..
+            } => write!(
+                formatter,
+                "put {} new approvals of {} to storage",
+                new_approvals.len(),
+                deploy_hash
+            ),
         }
     }
 }
@@ -0,0 +1,104 @@
+//! Deploys whose approvals do not yet meet their account's deployment threshold.
+//!
+//! Such deploys are held by this node only, i.e. they are neither stored nor gossiped, while the
+//! remaining approvals are collected via the RPC server.  Once the approvals meet the threshold,
+//! the deploy is accepted as if it had been submitted by a client in full.
+
+use std::collections::BTreeMap;
+
+use casper_types::Timestamp;
+
+use crate::types::{Deploy, DeployHash};
+
+/// The maximum number of deploys held while awaiting further approvals.
+pub(super) const MAX_PENDING_DEPLOYS: usize = 1_000;
+
+/// Deploys awaiting further approvals, by hash.
+#[derive(Debug, Default)]
+pub(super) struct PendingDeploys {
+    deploys: BTreeMap<DeployHash, Deploy>,
+}
+
+impl PendingDeploys {
+    /// Drops all deploys which have expired at `now`.
+    pub(super) fn prune_expired(&mut self, now: Timestamp) {
+        self.deploys
+            .retain(|_, deploy| !deploy.header().expired(now));
+    }
+
+    /// Returns the deploy with the given hash, if it is awaiting further approvals.
+    pub(super) fn get(&self, deploy_hash: &DeployHash) -> Option<&Deploy> {
+        self.deploys.get(deploy_hash)
+    }
+
+    /// Returns `deploy` with the approvals of the pending deploy with the same hash, if any, added
+    /// to its own.
+    pub(super) fn merge(&self, mut deploy: Deploy) -> Deploy {
+        if let Some(pending_deploy) = self.deploys.get(deploy.id()) {
+            let mut approvals = deploy.approvals().clone();
+            approvals.extend(pending_deploy.approvals().iter().cloned());
+            deploy.replace_approvals(approvals);
+        }
+        deploy
+    }
+
+    /// Holds `deploy` until further approvals are added, replacing the pending deploy with the same
+    /// hash, if any.
+    ///
+    /// Returns `false` if the deploy was not held, as no deploy with its hash is pending and the
+    /// maximum number of pending deploys is reached.
+    pub(super) fn insert(&mut self, deploy: Deploy) -> bool {
+        if !self.deploys.contains_key(deploy.id()) && self.deploys.len() >= MAX_PENDING_DEPLOYS {
+            return false;
+        }
+        self.deploys.insert(*deploy.id(), deploy);
+        true
+    }
+
+    /// Stops holding the deploy with the given hash.
+    pub(super) fn remove(&mut self, deploy_hash: &DeployHash) {
+        self.deploys.remove(deploy_hash);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use casper_types::{testing::TestRng, SecretKey, TimeDiff};
+
+    use super::*;
+
+    #[test]
+    fn should_merge_approvals_of_pending_deploy() {
+        let mut rng = TestRng::new();
+        let mut deploy = Deploy::random(&mut rng);
+        let first_approvals = deploy.approvals().clone();
+        let mut pending_deploys = PendingDeploys::default();
+        assert!(pending_deploys.insert(deploy.clone()));
+
+        deploy.replace_approvals(BTreeSet::new());
+        deploy.sign(&SecretKey::random(&mut rng));
+        let second_approvals = deploy.approvals().clone();
+
+        let merged = pending_deploys.merge(deploy);
+        let expected: BTreeSet<_> = first_approvals.union(&second_approvals).cloned().collect();
+        assert_eq!(merged.approvals(), &expected);
+        assert!(merged.is_valid().is_ok());
+    }
+
+    #[test]
+    fn should_prune_expired_deploys() {
+        let mut rng = TestRng::new();
+        let deploy = Deploy::random(&mut rng);
+        let deploy_hash = *deploy.id();
+        let mut pending_deploys = PendingDeploys::default();
+        assert!(pending_deploys.insert(deploy.clone()));
+
+        pending_deploys.prune_expired(deploy.header().expires());
+        assert!(pending_deploys.get(&deploy_hash).is_some());
+
+        pending_deploys.prune_expired(deploy.header().expires() + TimeDiff::from(1));
+        assert!(pending_deploys.get(&deploy_hash).is_none());
+    }
+}
@@ -9,6 +9,7 @@ use std::{
 use derive_more::From;
 use futures::channel::oneshot;
 use prometheus::Registry;
+use rand::Rng;
 use reactor::ReactorEvent;
 use serde::Serialize;
 use tempfile::TempDir;
@@ -21,12 +22,17 @@ use casper_execution_engine::{
 };
 use casper_types::{
     account::{Account, ActionThresholds, AssociatedKeys, Weight},
-    CLValue, StoredValue, URef, U512,
+    bytesrepr::Bytes,
+    runtime_args, CLValue, EntryPointAccess, EntryPointType, EntryPoints, Parameter, PublicKey,
+    RuntimeArgs, StoredValue, TimeDiff, URef, U512,
 };
 
 use super::*;
 use crate::{
-    components::storage::{self, Storage},
+    components::{
+        contract_runtime::RequestPriority,
+        storage::{self, Storage},
+    },
     effect::{
         announcements::{ControlAnnouncement, DeployAcceptorAnnouncement},
         requests::{ContractRuntimeRequest, NetworkRequest},
@@ -115,6 +121,64 @@ enum ContractScenario {
     MissingContractAtHash,
     MissingContractAtName,
     MissingEntryPoint,
+    InvalidArgType,
+}
+
+impl ContractScenario {
+    /// Returns the payment or session code calling a stored contract for this scenario.
+    fn item(self) -> ExecutableDeployItem {
+        match self {
+            ContractScenario::Valid | ContractScenario::MissingContractAtName => {
+                ExecutableDeployItem::StoredContractByName {
+                    name: "Test".to_string(),
+                    entry_point: "call".to_string(),
+                    args: Default::default(),
+                }
+            }
+            ContractScenario::MissingContractAtHash => ExecutableDeployItem::StoredContractByHash {
+                hash: [19; 32].into(),
+                entry_point: "call".to_string(),
+                args: Default::default(),
+            },
+            ContractScenario::MissingEntryPoint => ExecutableDeployItem::StoredContractByHash {
+                hash: [19; 32].into(),
+                entry_point: "non-existent-entry-point".to_string(),
+                args: Default::default(),
+            },
+            ContractScenario::InvalidArgType => ExecutableDeployItem::StoredContractByHash {
+                hash: [19; 32].into(),
+                entry_point: "call".to_string(),
+                args: runtime_args! { "amount" => "invalid-argument" },
+            },
+        }
+    }
+
+    /// Returns the contract stored under the hash called by this scenario's code.
+    fn contract(self) -> Contract {
+        match self {
+            ContractScenario::InvalidArgType => {
+                let mut entry_points = EntryPoints::new();
+                entry_points.add_entry_point(EntryPoint::new(
+                    "call",
+                    vec![Parameter::new("amount", CLType::U512)],
+                    CLType::Unit,
+                    EntryPointAccess::Public,
+                    EntryPointType::Session,
+                ));
+                Contract::new(
+                    Default::default(),
+                    Default::default(),
+                    Default::default(),
+                    entry_points,
+                    ProtocolVersion::V1_0_0,
+                )
+            }
+            ContractScenario::Valid
+            | ContractScenario::MissingContractAtHash
+            | ContractScenario::MissingContractAtName
+            | ContractScenario::MissingEntryPoint => Contract::default(),
+        }
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -125,6 +189,38 @@ enum ContractPackageScenario {
     MissingContractVersion,
 }
 
+impl ContractPackageScenario {
+    /// Returns the payment or session code calling a stored contract package for this scenario.
+    fn item(self) -> ExecutableDeployItem {
+        match self {
+            ContractPackageScenario::Valid | ContractPackageScenario::MissingPackageAtName => {
+                ExecutableDeployItem::StoredVersionedContractByName {
+                    name: "Test".to_string(),
+                    version: None,
+                    entry_point: "call".to_string(),
+                    args: Default::default(),
+                }
+            }
+            ContractPackageScenario::MissingPackageAtHash => {
+                ExecutableDeployItem::StoredVersionedContractByHash {
+                    hash: Default::default(),
+                    version: None,
+                    entry_point: "call".to_string(),
+                    args: Default::default(),
+                }
+            }
+            ContractPackageScenario::MissingContractVersion => {
+                ExecutableDeployItem::StoredVersionedContractByHash {
+                    hash: [19; 32].into(),
+                    version: Some(6u32),
+                    entry_point: "non-existent-entry-point".to_string(),
+                    args: Default::default(),
+                }
+            }
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 enum TestScenario {
     FromPeerInvalidDeploy,
@@ -153,6 +249,7 @@ enum TestScenario {
     DeployWithEmptySessionModuleBytes,
     DeployWithoutPaymentAmount,
     DeployWithMangledPaymentAmount,
+    DeployWithZeroPaymentAmount,
     DeployWithMangledTransferAmount,
     DeployWithoutTransferTarget,
     DeployWithoutTransferAmount,
@@ -188,6 +285,7 @@ impl TestScenario {
             | TestScenario::AccountWithUnknownBalance
             | TestScenario::DeployWithoutPaymentAmount
             | TestScenario::DeployWithMangledPaymentAmount
+            | TestScenario::DeployWithZeroPaymentAmount
             | TestScenario::DeployWithMangledTransferAmount
             | TestScenario::DeployWithoutTransferAmount
             | TestScenario::DeployWithoutTransferTarget
@@ -202,9 +300,12 @@ impl TestScenario {
     }
 
     fn deploy(&self, rng: &mut NodeRng) -> Deploy {
+        let builder = Deploy::builder()
+            .seed(rng.gen())
+            .timestamp(Timestamp::now());
         match self {
             TestScenario::FromPeerInvalidDeploy | TestScenario::FromClientInvalidDeploy => {
-                let mut deploy = Deploy::random_valid_native_transfer(rng);
+                let mut deploy = builder.build();
                 deploy.invalidate();
                 deploy
             }
@@ -220,90 +321,78 @@ impl TestScenario {
             | TestScenario::FromClientAccountWithInvalidAssociatedKeys
             | TestScenario::FromClientAccountWithInsufficientWeight
             | TestScenario::AccountWithUnknownBalance
-            | TestScenario::BalanceCheckForDeploySentByPeer => {
-                Deploy::random_valid_native_transfer(rng)
-            }
-            TestScenario::DeployWithoutPaymentAmount => Deploy::random_without_payment_amount(rng),
-            TestScenario::DeployWithMangledPaymentAmount => {
-                Deploy::random_with_mangled_payment_amount(rng)
-            }
-            TestScenario::DeployWithoutTransferTarget => {
-                Deploy::random_without_transfer_target(rng)
-            }
-            TestScenario::DeployWithoutTransferAmount => {
-                Deploy::random_without_transfer_amount(rng)
-            }
-            TestScenario::DeployWithMangledTransferAmount => {
-                Deploy::random_with_mangled_transfer_amount(rng)
-            }
+            | TestScenario::BalanceCheckForDeploySentByPeer => builder.build(),
+            TestScenario::DeployWithoutPaymentAmount => builder
+                .payment(ExecutableDeployItem::ModuleBytes {
+                    module_bytes: Bytes::new(),
+                    args: RuntimeArgs::default(),
+                })
+                .build(),
+            TestScenario::DeployWithMangledPaymentAmount => builder
+                .payment(ExecutableDeployItem::ModuleBytes {
+                    module_bytes: Bytes::new(),
+                    args: runtime_args! { "amount" => "invalid-argument" },
+                })
+                .build(),
+            TestScenario::DeployWithZeroPaymentAmount => builder
+                .payment(ExecutableDeployItem::ModuleBytes {
+                    module_bytes: Bytes::new(),
+                    args: runtime_args! { "amount" => U512::zero() },
+                })
+                .build(),
+            TestScenario::DeployWithoutTransferTarget => builder
+                .transfer_args(runtime_args! {
+                    "amount" => *MAX_PAYMENT,
+                    "source" => PublicKey::random(rng).to_account_hash(),
+                })
+                .build(),
+            TestScenario::DeployWithoutTransferAmount => builder
+                .transfer_args(runtime_args! {
+                    "source" => PublicKey::random(rng).to_account_hash(),
+                    "target" => PublicKey::random(rng).to_account_hash(),
+                })
+                .build(),
+            TestScenario::DeployWithMangledTransferAmount => builder
+                .transfer_args(runtime_args! {
+                    "amount" => "mangled-transfer-amount",
+                    "source" => PublicKey::random(rng).to_account_hash(),
+                    "target" => PublicKey::random(rng).to_account_hash(),
+                })
+                .build(),
 
             TestScenario::FromPeerCustomPaymentContract(contract_scenario)
             | TestScenario::FromClientCustomPaymentContract(contract_scenario) => {
-                match contract_scenario {
-                    ContractScenario::Valid | ContractScenario::MissingContractAtName => {
-                        Deploy::random_with_valid_custom_payment_contract_by_name(rng)
-                    }
-                    ContractScenario::MissingEntryPoint => {
-                        Deploy::random_with_missing_entry_point_in_payment_contract(rng)
-                    }
-                    ContractScenario::MissingContractAtHash => {
-                        Deploy::random_with_missing_payment_contract_by_hash(rng)
-                    }
-                }
+                builder.payment(contract_scenario.item()).build()
             }
             TestScenario::FromPeerCustomPaymentContractPackage(contract_package_scenario)
             | TestScenario::FromClientCustomPaymentContractPackage(contract_package_scenario) => {
-                match contract_package_scenario {
-                    ContractPackageScenario::Valid
-                    | ContractPackageScenario::MissingPackageAtName => {
-                        Deploy::random_with_valid_custom_payment_package_by_name(rng)
-                    }
-                    ContractPackageScenario::MissingPackageAtHash => {
-                        Deploy::random_with_missing_payment_package_by_hash(rng)
-                    }
-                    ContractPackageScenario::MissingContractVersion => {
-                        Deploy::random_with_nonexistent_contract_version_in_payment_package(rng)
-                    }
-                }
+                builder.payment(contract_package_scenario.item()).build()
             }
             TestScenario::FromPeerSessionContract(contract_scenario)
-            | TestScenario::FromClientSessionContract(contract_scenario) => match contract_scenario
-            {
-                ContractScenario::Valid | ContractScenario::MissingContractAtName => {
-                    Deploy::random_with_valid_session_contract_by_name(rng)
-                }
-                ContractScenario::MissingContractAtHash => {
-                    Deploy::random_with_missing_session_contract_by_hash(rng)
-                }
-                ContractScenario::MissingEntryPoint => {
-                    Deploy::random_with_missing_entry_point_in_session_contract(rng)
-                }
-            },
+            | TestScenario::FromClientSessionContract(contract_scenario) => {
+                builder.session(contract_scenario.item()).build()
+            }
             TestScenario::FromPeerSessionContractPackage(contract_package_scenario)
             | TestScenario::FromClientSessionContractPackage(contract_package_scenario) => {
-                match contract_package_scenario {
-                    ContractPackageScenario::Valid
-                    | ContractPackageScenario::MissingPackageAtName => {
-                        Deploy::random_with_valid_session_package_by_name(rng)
-                    }
-                    ContractPackageScenario::MissingPackageAtHash => {
-                        Deploy::random_with_missing_session_package_by_hash(rng)
-                    }
-                    ContractPackageScenario::MissingContractVersion => {
-                        Deploy::random_with_nonexistent_contract_version_in_session_package(rng)
-                    }
-                }
-            }
-            TestScenario::DeployWithEmptySessionModuleBytes => {
-                Deploy::random_with_empty_session_module_bytes(rng)
+                builder.session(contract_package_scenario.item()).build()
             }
+            TestScenario::DeployWithEmptySessionModuleBytes => builder
+                .session(ExecutableDeployItem::ModuleBytes {
+                    module_bytes: Bytes::new(),
+                    args: Default::default(),
+                })
+                .build(),
             TestScenario::DeployWithNativeTransferInPayment => {
-                Deploy::random_with_native_transfer_in_payment_logic(rng)
+                let args = builder.native_transfer_args(*MAX_PAYMENT);
+                builder
+                    .payment(ExecutableDeployItem::Transfer { args })
+                    .build()
             }
             TestScenario::ShouldAcceptExpiredDeploySentByPeer
-            | TestScenario::ShouldNotAcceptExpiredDeploySentByClient => {
-                Deploy::random_expired_deploy(rng)
-            }
+            | TestScenario::ShouldNotAcceptExpiredDeploySentByClient => builder
+                .timestamp(Timestamp::zero())
+                .ttl(TimeDiff::from_seconds(1))
+                .build(),
         }
     }
 
@@ -328,19 +417,29 @@ impl TestScenario {
             | TestScenario::DeployWithNativeTransferInPayment
             | TestScenario::DeployWithoutPaymentAmount
             | TestScenario::DeployWithMangledPaymentAmount
+            | TestScenario::DeployWithZeroPaymentAmount
             | TestScenario::DeployWithMangledTransferAmount
             | TestScenario::DeployWithoutTransferAmount
             | TestScenario::DeployWithoutTransferTarget
             | TestScenario::BalanceCheckForDeploySentByPeer
             | TestScenario::ShouldNotAcceptExpiredDeploySentByClient => false,
             TestScenario::FromPeerCustomPaymentContract(contract_scenario)
-            | TestScenario::FromPeerSessionContract(contract_scenario)
-            | TestScenario::FromClientCustomPaymentContract(contract_scenario)
-            | TestScenario::FromClientSessionContract(contract_scenario) => match contract_scenario
+            | TestScenario::FromClientCustomPaymentContract(contract_scenario) => match contract_scenario
             {
                 ContractScenario::Valid
                 | ContractScenario::MissingContractAtName => true,
                 | ContractScenario::MissingContractAtHash
+                | ContractScenario::MissingEntryPoint
+                | ContractScenario::InvalidArgType => false,
+            },
+            // The types of session args are not checked.
+            TestScenario::FromPeerSessionContract(contract_scenario)
+            | TestScenario::FromClientSessionContract(contract_scenario) => match contract_scenario
+            {
+                ContractScenario::Valid
+                | ContractScenario::MissingContractAtName
+                | ContractScenario::InvalidArgType => true,
+                | ContractScenario::MissingContractAtHash
                 | ContractScenario::MissingEntryPoint => false,
             },
             TestScenario::FromPeerCustomPaymentContractPackage(contract_package_scenario)
@@ -463,8 +562,10 @@ impl reactor::Reactor for Reactor {
             Event::ContractRuntime(event) => match event {
                 ContractRuntimeRequest::Query {
                     query_request,
+                    priority,
                     responder,
                 } => {
+                    assert_eq!(priority, RequestPriority::Validation);
                     let query_result = if self.test_scenario
                         == TestScenario::FromClientMissingAccount
                         || self.test_scenario == TestScenario::FromPeerMissingAccount
@@ -526,8 +627,11 @@ impl reactor::Reactor for Reactor {
                             | TestScenario::FromClientCustomPaymentContract(contract_scenario) => {
                                 match contract_scenario {
                                     ContractScenario::Valid
-                                    | ContractScenario::MissingEntryPoint => QueryResult::Success {
-                                        value: Box::new(StoredValue::Contract(Contract::default())),
+                                    | ContractScenario::MissingEntryPoint
+                                    | ContractScenario::InvalidArgType => QueryResult::Success {
+                                        value: Box::new(StoredValue::Contract(
+                                            contract_scenario.contract(),
+                                        )),
                                         proofs: vec![],
                                     },
                                     ContractScenario::MissingContractAtHash
@@ -571,8 +675,10 @@ impl reactor::Reactor for Reactor {
                 }
                 ContractRuntimeRequest::GetBalance {
                     balance_request,
+                    priority,
                     responder,
                 } => {
+                    assert_eq!(priority, RequestPriority::Validation);
                     let proof = TrieMerkleProof::new(
                         balance_request.purse_uref().into(),
                         StoredValue::CLValue(CLValue::from_t(()).expect("should get CLValue")),
@@ -639,7 +745,7 @@ fn put_deploy_to_storage(
 fn schedule_accept_deploy(
     deploy: Box<Deploy>,
     source: Source,
-    responder: Responder<Result<(), super::Error>>,
+    responder: Responder<Result<Option<BalanceProof>, super::Error>>,
 ) -> impl FnOnce(EffectBuilder<Event>) -> Effects<Event> {
     |effect_builder: EffectBuilder<Event>| {
         effect_builder
@@ -659,7 +765,7 @@ fn schedule_accept_deploy(
 fn inject_balance_check_for_peer(
     deploy: Box<Deploy>,
     source: Source,
-    responder: Responder<Result<(), super::Error>>,
+    responder: Responder<Result<Option<BalanceProof>, super::Error>>,
 ) -> impl FnOnce(EffectBuilder<Event>) -> Effects<Event> {
     |effect_builder: EffectBuilder<Event>| {
         let event_metadata = EventMetadata::new(deploy, source, Some(responder));
@@ -669,7 +775,7 @@ fn inject_balance_check_for_peer(
                 super::Event::GetBalanceResult {
                     event_metadata,
                     prestate_hash: Default::default(),
-                    maybe_balance_value: None,
+                    maybe_balance_proof: None,
                     account_hash: Default::default(),
                     verification_start_timestamp: Timestamp::now(),
                 },
@@ -681,7 +787,7 @@ fn inject_balance_check_for_peer(
 
 async fn run_deploy_acceptor_without_timeout(
     test_scenario: TestScenario,
-) -> Result<(), super::Error> {
+) -> Result<Option<BalanceProof>, super::Error> {
     let _ = logging::init();
     let mut rng = crate::new_rng();
 
@@ -773,6 +879,7 @@ async fn run_deploy_acceptor_without_timeout(
             | TestScenario::DeployWithNativeTransferInPayment
             | TestScenario::DeployWithoutPaymentAmount
             | TestScenario::DeployWithMangledPaymentAmount
+            | TestScenario::DeployWithZeroPaymentAmount
             | TestScenario::DeployWithMangledTransferAmount
             | TestScenario::DeployWithoutTransferTarget
             | TestScenario::DeployWithoutTransferAmount
@@ -798,7 +905,17 @@ async fn run_deploy_acceptor_without_timeout(
                         DeployAcceptorAnnouncement::AcceptedNewDeploy { .. }
                     )
                 ),
-                ContractScenario::MissingContractAtHash | ContractScenario::MissingEntryPoint => {
+                ContractScenario::InvalidArgType if test_scenario.is_valid_deploy_case() => {
+                    matches!(
+                        event,
+                        Event::DeployAcceptorAnnouncement(
+                            DeployAcceptorAnnouncement::AcceptedNewDeploy { .. }
+                        )
+                    )
+                }
+                ContractScenario::MissingContractAtHash
+                | ContractScenario::MissingEntryPoint
+                | ContractScenario::InvalidArgType => {
                     matches!(
                         event,
                         Event::DeployAcceptorAnnouncement(
@@ -917,7 +1034,9 @@ async fn run_deploy_acceptor_without_timeout(
     deploy_receiver.await.unwrap()
 }
 
-async fn run_deploy_acceptor(test_scenario: TestScenario) -> Result<(), super::Error> {
+async fn run_deploy_acceptor(
+    test_scenario: TestScenario,
+) -> Result<Option<BalanceProof>, super::Error> {
     time::timeout(TIMEOUT, run_deploy_acceptor_without_timeout(test_scenario))
         .await
         .unwrap()
@@ -959,7 +1078,10 @@ async fn should_accept_valid_deploy_from_peer_for_account_with_insufficient_weig
 #[tokio::test]
 async fn should_accept_valid_deploy_from_client() {
     let result = run_deploy_acceptor(TestScenario::FromClientValidDeploy).await;
-    assert!(result.is_ok())
+    let balance_proof = result
+        .expect("should accept deploy")
+        .expect("should return balance proof");
+    assert_eq!(balance_proof.balance_value, U512::from(MAX_PAYMENT_AMOUNT));
 }
 
 #[tokio::test]
@@ -1087,6 +1209,20 @@ async fn should_reject_deploy_with_missing_entry_point_custom_payment_from_clien
     ))
 }
 
+#[tokio::test]
+async fn should_reject_deploy_with_invalid_arg_type_for_custom_payment_from_client() {
+    let test_scenario =
+        TestScenario::FromClientCustomPaymentContract(ContractScenario::InvalidArgType);
+    let result = run_deploy_acceptor(test_scenario).await;
+    assert!(matches!(
+        result,
+        Err(super::Error::InvalidDeployParameters {
+            failure: DeployParameterFailure::InvalidPaymentArgType { .. },
+            ..
+        })
+    ))
+}
+
 #[tokio::test]
 async fn should_accept_deploy_with_valid_payment_contract_package_by_name_from_client() {
     let test_scenario =
@@ -1453,6 +1589,19 @@ async fn should_reject_deploy_with_mangled_payment_amount() {
     ))
 }
 
+#[tokio::test]
+async fn should_reject_deploy_with_zero_payment_amount() {
+    let test_scenario = TestScenario::DeployWithZeroPaymentAmount;
+    let result = run_deploy_acceptor(test_scenario).await;
+    assert!(matches!(
+        result,
+        Err(super::Error::InvalidDeployParameters {
+            failure: DeployParameterFailure::ZeroPaymentAmount,
+            ..
+        })
+    ))
+}
+
 #[tokio::test]
 async fn should_reject_deploy_without_transfer_amount() {
     let test_scenario = TestScenario::DeployWithoutTransferAmount;
@@ -0,0 +1,264 @@
+//! Replaying stored blocks to check that their execution is deterministic.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Display, Formatter},
+};
+
+use serde::Serialize;
+
+use casper_hashing::Digest;
+use casper_types::{ExecutionResult, PublicKey, Transform, TransformEntry};
+
+use crate::{
+    components::contract_runtime::ExecutionPreState,
+    effect::{
+        requests::{ContractRuntimeRequest, StorageRequest},
+        EffectBuilder,
+    },
+    rpcs::chain::BlockIdentifier,
+    types::{BlockHash, BlockHeight, Deploy, DeployHash, FinalizedBlock},
+};
+
+/// A difference between the stored and replayed execution results of a deploy.
+#[derive(Debug, Serialize)]
+pub(super) struct DeployDivergence {
+    /// The hash of the deploy.
+    deploy_hash: DeployHash,
+    /// The execution result stored for the deploy in the block, if any.
+    stored: Option<ExecutionResult>,
+    /// The execution result of the deploy when the block was replayed, if any.
+    replayed: Option<ExecutionResult>,
+    /// The keys whose transforms differ between the stored and replayed execution effects.
+    divergent_keys: BTreeSet<String>,
+}
+
+/// The outcome of re-executing a stored block against its parent's global state.
+#[derive(Debug, Serialize)]
+pub(super) struct BlockReplayReport {
+    /// The hash of the stored block.
+    block_hash: BlockHash,
+    /// The height of the stored block.
+    block_height: u64,
+    /// The hash of the block created by the replay.
+    replayed_block_hash: BlockHash,
+    /// The post-state root hash of the stored block.
+    stored_state_root_hash: Digest,
+    /// The post-state root hash resulting from the replay.
+    replayed_state_root_hash: Digest,
+    /// The deploys whose execution results differ from those stored.
+    deploy_divergences: Vec<DeployDivergence>,
+}
+
+impl BlockReplayReport {
+    /// Returns `true` if the replay reproduced the stored block and its execution results.
+    fn is_consistent(&self) -> bool {
+        self.block_hash == self.replayed_block_hash
+            && self.stored_state_root_hash == self.replayed_state_root_hash
+            && self.deploy_divergences.is_empty()
+    }
+}
+
+fn describe_execution_result(execution_result: &Option<ExecutionResult>) -> String {
+    match execution_result {
+        None => "none".to_string(),
+        Some(ExecutionResult::Success { cost, .. }) => format!("success costing {}", cost),
+        Some(ExecutionResult::Failure {
+            cost,
+            error_message,
+            ..
+        }) => format!("failure costing {}: {}", cost, error_message),
+    }
+}
+
+impl Display for BlockReplayReport {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_consistent() {
+            return write!(
+                formatter,
+                "replay of block {} at height {} matches the stored block",
+                self.block_hash, self.block_height
+            );
+        }
+        writeln!(
+            formatter,
+            "replay of block {} at height {} diverges from the stored block:",
+            self.block_hash, self.block_height
+        )?;
+        writeln!(
+            formatter,
+            "  block hash: stored {}, replayed {}",
+            self.block_hash, self.replayed_block_hash
+        )?;
+        write!(
+            formatter,
+            "  state root hash: stored {}, replayed {}",
+            self.stored_state_root_hash, self.replayed_state_root_hash
+        )?;
+        for divergence in &self.deploy_divergences {
+            write!(
+                formatter,
+                "\n  deploy {}: stored {}, replayed {}",
+                divergence.deploy_hash,
+                describe_execution_result(&divergence.stored),
+                describe_execution_result(&divergence.replayed)
+            )?;
+            for key in &divergence.divergent_keys {
+                write!(formatter, "\n    transforms differ for {}", key)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the transforms of the given execution result, grouped by key in journal order.
+fn transforms_by_key(
+    execution_result: &Option<ExecutionResult>,
+) -> BTreeMap<&str, Vec<&Transform>> {
+    let transforms: &[TransformEntry] = match execution_result {
+        None => &[],
+        Some(ExecutionResult::Success { effect, .. })
+        | Some(ExecutionResult::Failure { effect, .. }) => &effect.transforms,
+    };
+    let mut transforms_by_key: BTreeMap<&str, Vec<&Transform>> = BTreeMap::new();
+    for entry in transforms {
+        transforms_by_key
+            .entry(entry.key.as_str())
+            .or_default()
+            .push(&entry.transform);
+    }
+    transforms_by_key
+}
+
+fn divergent_keys(
+    stored: &Option<ExecutionResult>,
+    replayed: &Option<ExecutionResult>,
+) -> BTreeSet<String> {
+    let stored = transforms_by_key(stored);
+    let replayed = transforms_by_key(replayed);
+    stored
+        .keys()
+        .chain(replayed.keys())
+        .filter(|key| stored.get(*key) != replayed.get(*key))
+        .map(|key| key.to_string())
+        .collect()
+}
+
+/// Re-executes the identified stored block against its parent's global state, comparing the
+/// resulting block and execution results with those stored.
+///
+/// The global state written by the replay is committed to the trie store, but as tries are keyed
+/// by their hashes, a replay matching the stored block leaves global state unchanged.
+pub(super) async fn replay_block<REv>(
+    effect_builder: EffectBuilder<REv>,
+    block_identifier: BlockIdentifier,
+) -> Result<BlockReplayReport, String>
+where
+    REv: From<StorageRequest> + From<ContractRuntimeRequest> + Send,
+{
+    let maybe_block_with_metadata = match block_identifier {
+        BlockIdentifier::Hash(block_hash) => {
+            effect_builder
+                .get_block_with_metadata_from_storage(block_hash, false)
+                .await
+        }
+        BlockIdentifier::Height(block_height) => {
+            effect_builder
+                .get_block_at_height_with_metadata_from_storage(
+                    BlockHeight::from(block_height),
+                    false,
+                )
+                .await
+        }
+    };
+    let block = maybe_block_with_metadata
+        .ok_or_else(|| "block not found in storage".to_string())?
+        .block;
+    if *block.body().proposer() == PublicKey::System {
+        return Err(
+            "the block is an immediate switch block, executed on top of the global state \
+            resulting from genesis or an upgrade rather than its parent's"
+                .to_string(),
+        );
+    }
+    let parent_hash = block
+        .parent()
+        .ok_or_else(|| "the block has no parent".to_string())?;
+    let parent_header = effect_builder
+        .get_block_header_from_storage(*parent_hash, false)
+        .await
+        .ok_or_else(|| format!("parent block {} not found in storage", parent_hash))?;
+
+    let deploys = effect_builder
+        .get_block_deploys_with_execution_results_from_storage(
+            *block.hash(),
+            block.deploy_hashes().clone(),
+        )
+        .await
+        .ok_or_else(|| "not all of the block's deploys are stored".to_string())?;
+    let transfers = effect_builder
+        .get_block_deploys_with_execution_results_from_storage(
+            *block.hash(),
+            block.transfer_hashes().clone(),
+        )
+        .await
+        .ok_or_else(|| "not all of the block's transfers are stored".to_string())?;
+    let mut stored_results = BTreeMap::new();
+    let mut record_stored_results = |deploys: Vec<(Deploy, Option<ExecutionResult>)>| {
+        deploys
+            .into_iter()
+            .map(|(deploy, execution_result)| {
+                stored_results.insert(*deploy.id(), execution_result);
+                deploy
+            })
+            .collect::<Vec<_>>()
+    };
+    let deploys = record_stored_results(deploys);
+    let transfers = record_stored_results(transfers);
+
+    let block_and_execution_effects = effect_builder
+        .replay_finalized_block(
+            block.protocol_version(),
+            ExecutionPreState::from_block_header(&parent_header),
+            FinalizedBlock::from(block.clone()),
+            deploys,
+            transfers,
+        )
+        .await
+        .map_err(|error| format!("failed to execute block: {}", error))?;
+
+    let mut replayed_results: BTreeMap<_, _> = block_and_execution_effects
+        .execution_results
+        .into_iter()
+        .map(|(deploy_hash, _, execution_result)| (deploy_hash, execution_result))
+        .collect();
+    let deploy_divergences = block
+        .deploy_hashes()
+        .iter()
+        .chain(block.transfer_hashes())
+        .filter_map(|deploy_hash| {
+            let stored = stored_results.remove(deploy_hash).flatten();
+            let replayed = replayed_results.remove(deploy_hash);
+            if stored == replayed {
+                return None;
+            }
+            let divergent_keys = divergent_keys(&stored, &replayed);
+            Some(DeployDivergence {
+                deploy_hash: *deploy_hash,
+                stored,
+                replayed,
+                divergent_keys,
+            })
+        })
+        .collect();
+
+    let replayed_block = block_and_execution_effects.block;
+    Ok(BlockReplayReport {
+        block_hash: *block.hash(),
+        block_height: block.height(),
+        replayed_block_hash: *replayed_block.hash(),
+        stored_state_root_hash: *block.state_root_hash(),
+        replayed_state_root_hash: *replayed_block.state_root_hash(),
+        deploy_divergences,
+    })
+}
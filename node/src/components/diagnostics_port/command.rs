@@ -1,11 +1,18 @@
 use std::{
     fmt::{self, Display, Formatter},
+    path::PathBuf,
     str::FromStr,
 };
 
 use serde::Serialize;
 use structopt::StructOpt;
 use thiserror::Error;
+use tracing::level_filters::LevelFilter;
+
+use casper_hashing::Digest;
+use casper_types::TimeDiff;
+
+use crate::{rpcs::chain::BlockIdentifier, types::DeployHash};
 
 /// Command processing error.
 ///
@@ -80,6 +87,13 @@ pub(super) enum Action {
     GetLogFilter,
     /// Change the current log filter configuration.
     SetLogFilter { directive: String },
+    /// Change the log level of a single module, keeping the filter for all other modules.
+    SetModuleLogLevel {
+        /// Module path, e.g. `casper_node::components::small_network`.
+        module: String,
+        /// Log level, one of `trace`, `debug`, `info`, `warn`, `error` or `off`.
+        level: LevelFilter,
+    },
     /// Dump the state of the consensus component.
     ///
     /// It is recommended to set the output format to `bincode` if the data is to be visualized
@@ -90,10 +104,63 @@ pub(super) enum Action {
     },
     /// Dump the event queues.
     DumpQueues,
+    /// Show the number of events waiting in each of the event queues.
+    QueueDepths,
+    /// Show how long events wait in each event queue and which queues have been starved.
+    QueueFairness,
+    /// Show the number of events dispatched to each component since the node started.
+    EventCounts,
+    /// List the fetches waiting for responses from peers.
+    InFlightFetches,
+    /// Show the estimated heap memory used by each component, broken down into its parts.
+    DumpMemory,
+    /// Sample the CPU usage of the node for the given duration, e.g. `30s`, and write the profile
+    /// in pprof format to the profiling output directory.
+    ProfileCpu {
+        /// Duration to sample for.
+        duration: TimeDiff,
+    },
+    /// Write a dump of the effects which have not completed yet to the profiling output
+    /// directory.
+    DumpTasks,
+    /// Reload the node-local settings which can be changed without restarting from the config
+    /// file.
+    ReloadConfig,
+    /// Execute a deploy on top of the highest block without committing its effects, showing the
+    /// gas it consumed per host function and per class of Wasm opcodes.
+    ProfileGas {
+        /// Path on the node's filesystem of the deploy to execute, in JSON format.
+        #[structopt(parse(from_os_str))]
+        deploy_path: PathBuf,
+    },
+    /// Re-execute a stored block on top of its parent's global state, reporting any divergence
+    /// of the resulting block, state root hash or deploy execution results from those stored.
+    ReplayBlock {
+        /// Hash or height of the block to replay.
+        block: BlockIdentifier,
+    },
+    /// Show the events relating to a deploy recorded so far, starting to trace the deploy if it
+    /// isn't traced yet.
+    ///
+    /// Events are recorded if their description mentions the deploy hash, or if they result from
+    /// the same trigger as a recorded event.
+    TraceDeploy {
+        /// Hex-encoded hash of the deploy.
+        #[structopt(parse(try_from_str = parse_deploy_hash))]
+        deploy_hash: DeployHash,
+        /// Stop tracing the deploy after showing its events.
+        #[structopt(short, long)]
+        stop: bool,
+    },
     /// Close connection server-side.
     Quit,
 }
 
+/// Parses a hex-encoded deploy hash.
+fn parse_deploy_hash(hex: &str) -> Result<DeployHash, casper_hashing::Error> {
+    Digest::from_hex(hex).map(DeployHash::new)
+}
+
 /// A command to be performed on the node's diagnostic port.
 #[derive(Debug, StructOpt)]
 pub(super) struct Command {
@@ -112,7 +179,16 @@ impl Command {
 
 #[cfg(test)]
 mod tests {
-    use crate::components::diagnostics_port::command::{Action, Command};
+    use tracing::level_filters::LevelFilter;
+
+    use casper_hashing::Digest;
+    use casper_types::TimeDiff;
+
+    use crate::{
+        components::diagnostics_port::command::{Action, Command},
+        rpcs::chain::BlockIdentifier,
+        types::DeployHash,
+    };
 
     #[test]
     fn can_parse_simple_commands() {
@@ -121,5 +197,66 @@ mod tests {
 
         let cmd = Command::from_line("dump-queues").expect("command parsing failed");
         assert!(matches!(cmd.action, Action::DumpQueues));
+
+        let cmd = Command::from_line("queue-depths").expect("command parsing failed");
+        assert!(matches!(cmd.action, Action::QueueDepths));
+
+        let hex = "09".repeat(32);
+        let cmd = Command::from_line(&format!("trace-deploy --stop {}", hex))
+            .expect("command parsing failed");
+        assert!(matches!(
+            cmd.action,
+            Action::TraceDeploy { deploy_hash, stop: true }
+                if deploy_hash == DeployHash::new(Digest::from_hex(&hex).unwrap())
+        ));
+        assert!(Command::from_line("trace-deploy nothex").is_err());
+
+        let cmd = Command::from_line("queue-fairness").expect("command parsing failed");
+        assert!(matches!(cmd.action, Action::QueueFairness));
+
+        let cmd = Command::from_line("event-counts").expect("command parsing failed");
+        assert!(matches!(cmd.action, Action::EventCounts));
+
+        let cmd = Command::from_line("in-flight-fetches").expect("command parsing failed");
+        assert!(matches!(cmd.action, Action::InFlightFetches));
+
+        let cmd = Command::from_line("dump-memory").expect("command parsing failed");
+        assert!(matches!(cmd.action, Action::DumpMemory));
+
+        let cmd = Command::from_line("profile-cpu 30s").expect("command parsing failed");
+        assert!(matches!(
+            cmd.action,
+            Action::ProfileCpu { duration } if duration == TimeDiff::from_seconds(30)
+        ));
+
+        let cmd = Command::from_line("dump-tasks").expect("command parsing failed");
+        assert!(matches!(cmd.action, Action::DumpTasks));
+
+        let cmd = Command::from_line("reload-config").expect("command parsing failed");
+        assert!(matches!(cmd.action, Action::ReloadConfig));
+
+        let cmd =
+            Command::from_line("set-module-log-level casper_node::components::consensus debug")
+                .expect("command parsing failed");
+        assert!(matches!(
+            cmd.action,
+            Action::SetModuleLogLevel { ref module, level }
+                if module == "casper_node::components::consensus" && level == LevelFilter::DEBUG
+        ));
+
+        let cmd =
+            Command::from_line("profile-gas /tmp/deploy.json").expect("command parsing failed");
+        assert!(matches!(
+            cmd.action,
+            Action::ProfileGas { ref deploy_path } if deploy_path.to_str() == Some("/tmp/deploy.json")
+        ));
+
+        let cmd = Command::from_line("replay-block 42").expect("command parsing failed");
+        assert!(matches!(
+            cmd.action,
+            Action::ReplayBlock {
+                block: BlockIdentifier::Height(42)
+            }
+        ));
     }
 }
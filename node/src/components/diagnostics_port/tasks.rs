@@ -3,7 +3,7 @@ use std::{
     fmt::{self, Debug, Display, Formatter},
     fs::{self, File},
     io,
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 use bincode::{
@@ -19,23 +19,26 @@ use tokio::{
     net::{unix::OwnedWriteHalf, UnixListener, UnixStream},
     sync::watch,
 };
-use tracing::{debug, info, info_span, warn, Instrument};
+use tracing::{debug, info, info_span, level_filters::LevelFilter, warn, Instrument};
 
 use casper_types::EraId;
 use tracing_subscriber::{filter::ParseError, EnvFilter};
 
 use super::{
+    block_replay,
     command::{Action, Command, OutputFormat},
     util::ShowUnixAddr,
 };
 use crate::{
-    components::consensus::EraDump,
+    components::{consensus::EraDump, contract_runtime::SpeculativeExecutionState},
     effect::{
         announcements::{ControlAnnouncement, QueueDumpFormat},
         diagnostics_port::DumpConsensusStateRequest,
+        requests::{ContractRuntimeRequest, StorageRequest},
         EffectBuilder,
     },
-    logging,
+    logging, profiling,
+    types::{json_compatibility::GasProfile, Deploy},
     utils::display_error,
 };
 
@@ -197,7 +200,11 @@ impl Session {
         line: &str,
     ) -> io::Result<bool>
     where
-        REv: From<DumpConsensusStateRequest> + From<ControlAnnouncement> + Send,
+        REv: From<DumpConsensusStateRequest>
+            + From<ControlAnnouncement>
+            + From<StorageRequest>
+            + From<ContractRuntimeRequest>
+            + Send,
     {
         debug!(%line, "line received");
         match Command::from_line(line) {
@@ -263,6 +270,30 @@ impl Session {
                             .await?;
                         }
                     },
+                    Action::SetModuleLogLevel { ref module, level } => {
+                        match set_module_log_level(module, level) {
+                            Ok(()) => {
+                                self.send_outcome(
+                                    writer,
+                                    &Outcome::success(format!(
+                                        "log level of {} set to {}",
+                                        module, level
+                                    )),
+                                )
+                                .await?;
+                            }
+                            Err(err) => {
+                                self.send_outcome(
+                                    writer,
+                                    &Outcome::failed(format!(
+                                        "failed to set log level of {}: {}",
+                                        module, err
+                                    )),
+                                )
+                                .await?;
+                            }
+                        }
+                    }
                     Action::DumpConsensus { era } => {
                         let output = effect_builder
                             .diagnostics_port_dump_consensus_state(
@@ -311,6 +342,129 @@ impl Session {
                             }
                         };
                     }
+                    Action::QueueDepths => {
+                        let queue_depths = effect_builder.diagnostics_port_queue_depths();
+                        self.send_outcome(writer, &Outcome::success("showing queue depths"))
+                            .await?;
+                        self.send_to_client(writer, &queue_depths).await?;
+                    }
+                    Action::QueueFairness => {
+                        let queue_fairness = effect_builder.diagnostics_port_queue_fairness().await;
+                        self.send_outcome(writer, &Outcome::success("showing queue fairness"))
+                            .await?;
+                        self.send_to_client(writer, &queue_fairness).await?;
+                    }
+                    Action::EventCounts => {
+                        let event_counts = effect_builder.diagnostics_port_event_counts().await;
+                        self.send_outcome(writer, &Outcome::success("showing event counts"))
+                            .await?;
+                        self.send_to_client(writer, &event_counts).await?;
+                    }
+                    Action::InFlightFetches => {
+                        let in_flight_fetches =
+                            effect_builder.diagnostics_port_in_flight_fetches().await;
+                        self.send_outcome(writer, &Outcome::success("showing in-flight fetches"))
+                            .await?;
+                        self.send_to_client(writer, &in_flight_fetches).await?;
+                    }
+                    Action::DumpMemory => {
+                        let memory_usage = effect_builder.diagnostics_port_memory_usage().await;
+                        self.send_outcome(writer, &Outcome::success("dumping memory usage"))
+                            .await?;
+                        self.send_to_client(writer, &memory_usage).await?;
+                    }
+                    Action::ProfileCpu { duration } => {
+                        match profiling::profile_cpu(duration).await {
+                            Ok(path) => {
+                                self.send_outcome(
+                                    writer,
+                                    &Outcome::success(format!(
+                                        "wrote CPU profile to {}",
+                                        path.display()
+                                    )),
+                                )
+                                .await?;
+                            }
+                            Err(err) => {
+                                self.send_outcome(
+                                    writer,
+                                    &Outcome::failed(format!("failed to profile CPU: {}", err)),
+                                )
+                                .await?;
+                            }
+                        }
+                    }
+                    Action::DumpTasks => match profiling::dump_tasks() {
+                        Ok(path) => {
+                            self.send_outcome(
+                                writer,
+                                &Outcome::success(format!("wrote task dump to {}", path.display())),
+                            )
+                            .await?;
+                        }
+                        Err(err) => {
+                            self.send_outcome(
+                                writer,
+                                &Outcome::failed(format!("failed to dump tasks: {}", err)),
+                            )
+                            .await?;
+                        }
+                    },
+                    Action::ReloadConfig => {
+                        match effect_builder.diagnostics_port_reload_config().await {
+                            Ok(()) => {
+                                self.send_outcome(writer, &Outcome::success("config reloaded"))
+                                    .await?;
+                            }
+                            Err(err) => {
+                                self.send_outcome(
+                                    writer,
+                                    &Outcome::failed(format!("failed to reload config: {}", err)),
+                                )
+                                .await?;
+                            }
+                        }
+                    }
+                    Action::ProfileGas { ref deploy_path } => {
+                        match profile_deploy_gas(effect_builder, deploy_path).await {
+                            Ok(gas_profile) => {
+                                self.send_outcome(writer, &Outcome::success("profiled gas"))
+                                    .await?;
+                                self.send_to_client(writer, &gas_profile).await?;
+                            }
+                            Err(err) => {
+                                self.send_outcome(
+                                    writer,
+                                    &Outcome::failed(format!("failed to profile gas: {}", err)),
+                                )
+                                .await?;
+                            }
+                        }
+                    }
+                    Action::ReplayBlock { block } => {
+                        match block_replay::replay_block(effect_builder, block).await {
+                            Ok(report) => {
+                                self.send_outcome(writer, &Outcome::success("replayed block"))
+                                    .await?;
+                                self.send_to_client(writer, &report).await?;
+                            }
+                            Err(err) => {
+                                self.send_outcome(
+                                    writer,
+                                    &Outcome::failed(format!("failed to replay block: {}", err)),
+                                )
+                                .await?;
+                            }
+                        }
+                    }
+                    Action::TraceDeploy { deploy_hash, stop } => {
+                        let trace = effect_builder
+                            .diagnostics_port_trace_deploy(deploy_hash, stop)
+                            .await;
+                        self.send_outcome(writer, &Outcome::success("showing deploy trace"))
+                            .await?;
+                        self.send_to_client(writer, &trace).await?;
+                    }
                     Action::Quit => {
                         self.send_outcome(writer, &Outcome::success("goodbye!"))
                             .await?;
@@ -422,6 +576,9 @@ enum SetLogFilterError {
     /// Failure setting the correctly parsed filter.
     #[error("failed to set global filter")]
     SetFailed(anyhow::Error),
+    /// Failure retrieving the current filter to be amended.
+    #[error("failed to retrieve global filter")]
+    GetFailed(anyhow::Error),
 }
 
 /// Sets the global log using the given new directive.
@@ -431,6 +588,56 @@ fn set_log_filter(filter_str: &str) -> Result<(), SetLogFilterError> {
     logging::reload_global_env_filter(new_filter).map_err(SetLogFilterError::SetFailed)
 }
 
+/// Sets the level of a single module in the global log filter, keeping all other directives.
+fn set_module_log_level(module: &str, level: LevelFilter) -> Result<(), SetLogFilterError> {
+    let current = logging::display_global_env_filter().map_err(SetLogFilterError::GetFailed)?;
+    set_log_filter(&with_module_level(&current, module, level))
+}
+
+/// Returns the filter directive `filter` with any directives for `module` replaced by one setting
+/// its level to `level`.
+fn with_module_level(filter: &str, module: &str, level: LevelFilter) -> String {
+    let mut directives: Vec<&str> = filter
+        .split(',')
+        .filter(|directive| !directive.is_empty() && directive.split('=').next() != Some(module))
+        .collect();
+    let new_directive = format!("{}={}", module, level);
+    directives.push(&new_directive);
+    directives.join(",")
+}
+
+/// Executes the deploy stored as JSON at `deploy_path` on top of the highest block, without
+/// committing its effects, returning the breakdown of the gas it consumed.
+async fn profile_deploy_gas<REv>(
+    effect_builder: EffectBuilder<REv>,
+    deploy_path: &Path,
+) -> Result<GasProfile, String>
+where
+    REv: From<StorageRequest> + From<ContractRuntimeRequest> + Send,
+{
+    let deploy_json = fs::read_to_string(deploy_path)
+        .map_err(|err| format!("could not read {}: {}", deploy_path.display(), err))?;
+    let deploy: Deploy = serde_json::from_str(&deploy_json)
+        .map_err(|err| format!("could not parse deploy: {}", err))?;
+    let block_header = effect_builder
+        .get_highest_block_header_from_storage()
+        .await
+        .ok_or_else(|| "no block to execute the deploy on top of".to_string())?;
+    let execution_prestate = SpeculativeExecutionState {
+        state_root_hash: *block_header.state_root_hash(),
+        block_time: block_header.timestamp(),
+        protocol_version: block_header.protocol_version(),
+    };
+    match effect_builder
+        .profile_deploy_gas(execution_prestate, deploy)
+        .await
+    {
+        Ok(Some((_, gas_profile))) => Ok(GasProfile::from(&gas_profile)),
+        Ok(None) => Err("deploy produced no execution result".to_string()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
 /// Handler for client connection.
 ///
 /// The core loop for the diagnostics port; reads commands via unix socket and processes them.
@@ -446,7 +653,11 @@ async fn handler<REv>(
     mut shutdown_receiver: watch::Receiver<()>,
 ) -> io::Result<()>
 where
-    REv: From<DumpConsensusStateRequest> + From<ControlAnnouncement> + Send,
+    REv: From<DumpConsensusStateRequest>
+        + From<ControlAnnouncement>
+        + From<StorageRequest>
+        + From<ContractRuntimeRequest>
+        + Send,
 {
     debug!("accepted new connection on diagnostics port");
 
@@ -486,7 +697,11 @@ pub(super) async fn server<REv>(
     listener: UnixListener,
     mut shutdown_receiver: watch::Receiver<()>,
 ) where
-    REv: From<DumpConsensusStateRequest> + From<ControlAnnouncement> + Send,
+    REv: From<DumpConsensusStateRequest>
+        + From<ControlAnnouncement>
+        + From<StorageRequest>
+        + From<ContractRuntimeRequest>
+        + Send,
 {
     let handling_shutdown_receiver = shutdown_receiver.clone();
     let mut next_client_id: u64 = 0;
@@ -561,6 +776,7 @@ mod tests {
         net::UnixStream,
         sync::Notify,
     };
+    use tracing::level_filters::LevelFilter;
 
     pub struct TestReactorConfig {
         base_dir: PathBuf,
@@ -597,6 +813,8 @@ mod tests {
 
         requests: {
             DumpConsensusStateRequest -> !;
+            StorageRequest -> !;
+            ContractRuntimeRequest -> !;
         }
 
         announcements: {}
@@ -708,4 +926,21 @@ mod tests {
             })
             .await;
     }
+
+    #[test]
+    fn should_replace_module_directives_only() {
+        let filter = "warn,casper_node=info,casper_node::components::consensus=trace";
+        assert_eq!(
+            super::with_module_level(
+                filter,
+                "casper_node::components::consensus",
+                LevelFilter::DEBUG
+            ),
+            "warn,casper_node=info,casper_node::components::consensus=debug"
+        );
+        assert_eq!(
+            super::with_module_level(filter, "hyper", LevelFilter::OFF),
+            "warn,casper_node=info,casper_node::components::consensus=trace,hyper=off"
+        );
+    }
 }
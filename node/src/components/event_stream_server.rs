@@ -11,11 +11,14 @@
 //!
 //! This component is passive and receives announcements made by other components while never making
 //! a request of other components itself. The handled announcements are serialized to JSON and
-//! pushed to subscribers.
+//! pushed to subscribers.  Events emitted by contracts while executing processed deploys are
+//! pushed to subscribers on their own channel.
 //!
 //! This component uses a ring buffer for outbound events providing some robustness against
 //! unintended subscriber disconnects, if a disconnected subscriber re-subscribes before the buffer
-//! has advanced past their last received event.
+//! has advanced past their last received event.  Events are also persisted to a bounded on-disk
+//! log, from which subscribers can be served events no longer held in the buffer, including those
+//! emitted before the node restarted.
 //!
 //! For details about the SSE model and a list of supported SSEs, see:
 //! <https://github.com/CasperLabs/ceps/blob/master/text/0009-client-api.md#rpcs>
@@ -23,7 +26,9 @@
 mod config;
 mod event;
 mod event_indexer;
+mod event_log;
 mod http_server;
+mod metrics;
 mod sse_server;
 #[cfg(test)]
 mod tests;
@@ -31,6 +36,10 @@ mod tests;
 use std::{convert::Infallible, fmt::Debug, net::SocketAddr, path::PathBuf, sync::Arc};
 
 use datasize::DataSize;
+use futures::{future, FutureExt};
+use hyper::Server;
+use prometheus::Registry;
+use thiserror::Error;
 use tokio::sync::{
     mpsc::{self, UnboundedSender},
     oneshot,
@@ -40,18 +49,24 @@ use warp::Filter;
 
 use casper_types::ProtocolVersion;
 
-use super::Component;
+use super::{contract_runtime::DeployExecutionProgress, Component};
 use crate::{
     effect::{EffectBuilder, Effects},
+    reactor::{
+        correlation::{self, CorrelationId},
+        supervision::{SpawnsSupervisedTasks, SupervisedTasks},
+    },
     types::JsonBlock,
     utils::{self, ListeningError},
     NodeRng,
 };
-pub use config::Config;
+pub use config::{Config, SlowClientPolicy};
 pub(crate) use event::Event;
 use event_indexer::{EventIndex, EventIndexer};
-use sse_server::ChannelsAndFilter;
+use event_log::EventLog;
+use metrics::Metrics;
 pub(crate) use sse_server::SseData;
+use sse_server::{ChannelsAndFilter, SlowClientHandler};
 
 /// This is used to define the number of events to buffer in the tokio broadcast channel to help
 /// slower clients to try to avoid missing events (See
@@ -63,6 +78,18 @@ pub(crate) use sse_server::SseData;
 /// that a new client can retrieve the entire set of buffered events if desired.
 const ADDITIONAL_PERCENT_FOR_BROADCAST_CHANNEL_SIZE: u32 = 20;
 
+/// An error starting the event stream server.
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    /// An error starting the HTTP server.
+    #[error(transparent)]
+    Listening(#[from] ListeningError),
+
+    /// Metrics-related error.
+    #[error("prometheus (metrics) error: {0}")]
+    Metrics(#[from] prometheus::Error),
+}
+
 /// A helper trait whose bounds represent the requirements for a reactor event that `run_server` can
 /// work with.
 pub trait ReactorEventT: From<Event> + Send {}
@@ -74,9 +101,15 @@ struct InnerServer {
     /// Channel sender to pass event-stream data to the event-stream server.
     // TODO - this should not be skipped.  Awaiting support for `UnboundedSender` in datasize crate.
     #[data_size(skip)]
-    sse_data_sender: UnboundedSender<(EventIndex, SseData)>,
+    sse_data_sender: UnboundedSender<(EventIndex, SseData, Option<CorrelationId>)>,
     event_indexer: EventIndexer,
     listening_address: SocketAddr,
+    /// The metrics of the server.
+    #[data_size(skip)]
+    metrics: Arc<Metrics>,
+    /// The tasks running the server and its connections.
+    #[data_size(skip)]
+    tasks: SupervisedTasks,
 }
 
 #[derive(DataSize, Debug)]
@@ -89,21 +122,37 @@ impl EventStreamServer {
         config: Config,
         storage_path: PathBuf,
         api_version: ProtocolVersion,
-    ) -> Result<Self, ListeningError> {
+        registry: &Registry,
+    ) -> Result<Self, Error> {
+        if !config.enable_server {
+            return Ok(EventStreamServer { inner: None });
+        }
+
+        let metrics = Arc::new(Metrics::new(registry)?);
+        Self::with_metrics(config, storage_path, api_version, metrics)
+    }
+
+    /// Creates the server, updating the given `metrics` rather than registering new ones.
+    pub(crate) fn with_metrics(
+        config: Config,
+        storage_path: PathBuf,
+        api_version: ProtocolVersion,
+        metrics: Arc<Metrics>,
+    ) -> Result<Self, Error> {
         if !config.enable_server {
             return Ok(EventStreamServer { inner: None });
         }
 
-        let required_address = utils::resolve_address(&config.address).map_err(|error| {
-            warn!(
-                %error,
-                address=%config.address,
-                "failed to start event stream server, cannot parse address"
-            );
-            ListeningError::ResolveAddress(error)
-        })?;
+        let incoming = utils::start_listening(&config.address)?;
+        let listening_address = incoming.local_addr();
 
+        let event_log = EventLog::new(
+            &storage_path,
+            config.event_log_max_size_bytes,
+            config.event_log_max_age,
+        );
         let event_indexer = EventIndexer::new(storage_path);
+        let slow_client_handler = SlowClientHandler::new(&config, Arc::clone(&metrics));
         let (sse_data_sender, sse_data_receiver) = mpsc::unbounded_channel();
 
         // Event stream channels and filter.
@@ -117,29 +166,40 @@ impl EventStreamServer {
         } = ChannelsAndFilter::new(
             broadcast_channel_size as usize,
             config.max_concurrent_subscribers,
+            slow_client_handler.clone(),
         );
 
         let (shutdown_sender, shutdown_receiver) = oneshot::channel::<()>();
 
-        let (listening_address, server_with_shutdown) =
-            warp::serve(sse_filter.with(warp::cors().allow_any_origin()))
-                .try_bind_with_graceful_shutdown(required_address, async {
-                    shutdown_receiver.await.ok();
-                })
-                .map_err(|error| ListeningError::Listen {
-                    address: required_address,
-                    error: Box::new(error),
-                })?;
+        // The server is run by hyper rather than warp so that its connection tasks are supervised.
+        let tasks = SupervisedTasks::new();
+        let service = warp::service(sse_filter.with(warp::cors().allow_any_origin()));
+        let make_svc =
+            hyper::service::make_service_fn(move |_| future::ok::<_, Infallible>(service.clone()));
+        let server_with_shutdown = Server::builder(incoming)
+            .executor(tasks.supervisor().clone())
+            .serve(make_svc)
+            .with_graceful_shutdown(async {
+                shutdown_receiver.await.ok();
+            })
+            .map(|result| {
+                if let Err(error) = result {
+                    warn!(%error, "error running event stream server");
+                }
+            });
         info!(address=%listening_address, "started event stream server");
 
-        tokio::spawn(http_server::run(
+        tasks.supervisor().spawn(http_server::run(
             config,
             api_version,
+            tasks.supervisor().clone(),
             server_with_shutdown,
             shutdown_sender,
             sse_data_receiver,
             event_broadcaster,
             new_subscriber_info_receiver,
+            event_log,
+            slow_client_handler,
         ));
 
         Ok(EventStreamServer {
@@ -147,15 +207,27 @@ impl EventStreamServer {
                 sse_data_sender,
                 event_indexer,
                 listening_address,
+                metrics,
+                tasks,
             }),
         })
     }
 
+    /// Returns the metrics of the server, if enabled.
+    pub(crate) fn metrics(&self) -> Option<Arc<Metrics>> {
+        self.inner
+            .as_ref()
+            .map(|server| Arc::clone(&server.metrics))
+    }
+
     /// Broadcasts the SSE data to all clients connected to the event stream.
     fn broadcast(&mut self, sse_data: SseData) -> Effects<Event> {
         if let Some(server) = self.inner.as_mut() {
             let event_index = server.event_indexer.next_index();
-            let _ = server.sse_data_sender.send((event_index, sse_data));
+            let correlation_id = correlation::current();
+            let _ = server
+                .sse_data_sender
+                .send((event_index, sse_data, correlation_id));
         }
         Effects::new()
     }
@@ -167,6 +239,12 @@ impl Drop for EventStreamServer {
     }
 }
 
+impl SpawnsSupervisedTasks for EventStreamServer {
+    fn supervised_tasks(&mut self) -> Option<&mut SupervisedTasks> {
+        self.inner.as_mut().map(|server| &mut server.tasks)
+    }
+}
+
 impl<REv> Component<REv> for EventStreamServer
 where
     REv: ReactorEventT,
@@ -193,15 +271,53 @@ where
                 deploy_header,
                 block_hash,
                 execution_result,
-            } => self.broadcast(SseData::DeployProcessed {
-                deploy_hash: Box::new(deploy_hash),
-                account: Box::new(deploy_header.account().clone()),
-                timestamp: deploy_header.timestamp(),
-                ttl: deploy_header.ttl(),
-                dependencies: deploy_header.dependencies().clone(),
-                block_hash: Box::new(block_hash),
-                execution_result,
-            }),
+                contract_events,
+            } => {
+                // Any events emitted by contracts are sent after the `DeployProcessed` itself, in
+                // the order emitted.
+                let mut effects = self.broadcast(SseData::DeployProcessed {
+                    deploy_hash: Box::new(deploy_hash),
+                    account: Box::new(deploy_header.account().clone()),
+                    timestamp: deploy_header.timestamp(),
+                    ttl: deploy_header.ttl(),
+                    dependencies: deploy_header.dependencies().clone(),
+                    block_hash: Box::new(block_hash),
+                    execution_result,
+                });
+                for contract_event in contract_events {
+                    effects.extend(self.broadcast(SseData::ContractEvent {
+                        deploy_hash: Box::new(deploy_hash),
+                        account: Box::new(deploy_header.account().clone()),
+                        block_hash: Box::new(block_hash),
+                        contract_event: Box::new(contract_event),
+                    }));
+                }
+                effects
+            }
+            Event::DeployExecutionProgress(progress) => match *progress {
+                DeployExecutionProgress::Started {
+                    block_height,
+                    deploy_hash,
+                    account,
+                } => self.broadcast(SseData::DeployExecutionStarted {
+                    deploy_hash: Box::new(deploy_hash),
+                    account: Box::new(account),
+                    block_height,
+                }),
+                DeployExecutionProgress::Finished {
+                    block_height,
+                    deploy_hash,
+                    account,
+                    cost,
+                    error_message,
+                } => self.broadcast(SseData::DeployExecutionFinished {
+                    deploy_hash: Box::new(deploy_hash),
+                    account: Box::new(account),
+                    block_height,
+                    cost,
+                    error_message,
+                }),
+            },
             Event::DeploysExpired(deploy_hashes) => deploy_hashes
                 .into_iter()
                 .flat_map(|deploy_hash| self.broadcast(SseData::DeployExpired { deploy_hash }))
@@ -215,7 +331,12 @@ where
                 public_key,
                 timestamp,
             }),
-            Event::FinalitySignature(fs) => self.broadcast(SseData::FinalitySignature(fs)),
+            Event::FinalitySignature(finality_signature, cumulative_weight) => {
+                self.broadcast(SseData::FinalitySignature {
+                    finality_signature,
+                    cumulative_weight,
+                })
+            }
             Event::Step {
                 era_id,
                 execution_effect,
@@ -1,6 +1,8 @@
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
+use casper_types::TimeDiff;
+
 /// Default binding address for the SSE HTTP server.
 ///
 /// Uses a fixed port per node, but binds on any interface.
@@ -12,6 +14,32 @@ const DEFAULT_EVENT_STREAM_BUFFER_LENGTH: u32 = 5000;
 /// Default maximum number of subscribers.
 const DEFAULT_MAX_CONCURRENT_SUBSCRIBERS: u32 = 100;
 
+/// Default maximum total size of the persisted event log: 100 MiB.
+const DEFAULT_EVENT_LOG_MAX_SIZE_BYTES: u64 = 104_857_600;
+
+/// Default maximum age of events retained in the persisted event log.
+const DEFAULT_EVENT_LOG_MAX_AGE: &str = "1day";
+
+/// Default maximum number of events a client may fall behind the latest event.
+const DEFAULT_CLIENT_BUFFER_LENGTH: u32 = 5000;
+
+/// How to handle a client which has fallen too far behind the latest event.
+#[derive(Clone, Copy, DataSize, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SlowClientPolicy {
+    /// Disconnect the client.
+    Disconnect,
+    /// Drop the oldest events not yet sent to the client, sending an `EventsDropped` event in their
+    /// place.
+    DropOldest,
+}
+
+impl Default for SlowClientPolicy {
+    fn default() -> Self {
+        SlowClientPolicy::Disconnect
+    }
+}
+
 /// SSE HTTP server configuration.
 #[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
@@ -28,6 +56,25 @@ pub struct Config {
 
     /// Default maximum number of subscribers across all event streams permitted at any one time.
     pub max_concurrent_subscribers: u32,
+
+    /// Maximum total size in bytes of the on-disk log of events, from which reconnecting clients
+    /// can be served events no longer held in the in-memory buffer.  Set to 0 to disable the log.
+    #[serde(default = "default_event_log_max_size_bytes")]
+    pub event_log_max_size_bytes: u64,
+
+    /// Maximum age of events retained in the on-disk log of events.
+    #[serde(default = "default_event_log_max_age")]
+    pub event_log_max_age: TimeDiff,
+
+    /// Maximum number of events an individual client may fall behind the latest event before
+    /// being handled according to `slow_client_policy`.  This also limits the number of events
+    /// replayed to a client from the on-disk log.
+    #[serde(default = "default_client_buffer_length")]
+    pub client_buffer_length: u32,
+
+    /// How to handle a client which has fallen more than `client_buffer_length` events behind.
+    #[serde(default)]
+    pub slow_client_policy: SlowClientPolicy,
 }
 
 impl Config {
@@ -38,6 +85,10 @@ impl Config {
             address: DEFAULT_ADDRESS.to_string(),
             event_stream_buffer_length: DEFAULT_EVENT_STREAM_BUFFER_LENGTH,
             max_concurrent_subscribers: DEFAULT_MAX_CONCURRENT_SUBSCRIBERS,
+            event_log_max_size_bytes: DEFAULT_EVENT_LOG_MAX_SIZE_BYTES,
+            event_log_max_age: default_event_log_max_age(),
+            client_buffer_length: DEFAULT_CLIENT_BUFFER_LENGTH,
+            slow_client_policy: SlowClientPolicy::default(),
         }
     }
 }
@@ -47,3 +98,15 @@ impl Default for Config {
         Config::new()
     }
 }
+
+fn default_event_log_max_size_bytes() -> u64 {
+    DEFAULT_EVENT_LOG_MAX_SIZE_BYTES
+}
+
+fn default_event_log_max_age() -> TimeDiff {
+    DEFAULT_EVENT_LOG_MAX_AGE.parse().unwrap()
+}
+
+fn default_client_buffer_length() -> u32 {
+    DEFAULT_CLIENT_BUFFER_LENGTH
+}
@@ -3,7 +3,13 @@ use std::fmt::{self, Display, Formatter};
 use casper_types::{EraId, ExecutionEffect, ExecutionResult, PublicKey, Timestamp};
 use itertools::Itertools;
 
-use crate::types::{Block, BlockHash, Deploy, DeployHash, DeployHeader, FinalitySignature};
+use crate::{
+    components::contract_runtime::DeployExecutionProgress,
+    types::{
+        Block, BlockHash, ContractEvent, Deploy, DeployHash, DeployHeader, FinalitySignature,
+        SignedWeight,
+    },
+};
 
 #[derive(Debug)]
 pub enum Event {
@@ -14,14 +20,16 @@ pub enum Event {
         deploy_header: Box<DeployHeader>,
         block_hash: BlockHash,
         execution_result: Box<ExecutionResult>,
+        contract_events: Vec<ContractEvent>,
     },
     DeploysExpired(Vec<DeployHash>),
+    DeployExecutionProgress(Box<DeployExecutionProgress>),
     Fault {
         era_id: EraId,
         public_key: PublicKey,
         timestamp: Timestamp,
     },
-    FinalitySignature(Box<FinalitySignature>),
+    FinalitySignature(Box<FinalitySignature>, Option<Box<SignedWeight>>),
     Step {
         era_id: EraId,
         execution_effect: ExecutionEffect,
@@ -45,6 +53,14 @@ impl Display for Event {
             Event::DeployProcessed { deploy_hash, .. } => {
                 write!(formatter, "deploy processed {}", deploy_hash)
             }
+            Event::DeployExecutionProgress(progress) => match progress.as_ref() {
+                DeployExecutionProgress::Started { deploy_hash, .. } => {
+                    write!(formatter, "deploy execution started {}", deploy_hash)
+                }
+                DeployExecutionProgress::Finished { deploy_hash, .. } => {
+                    write!(formatter, "deploy execution finished {}", deploy_hash)
+                }
+            },
             Event::Fault {
                 era_id,
                 public_key,
@@ -54,7 +70,7 @@ impl Display for Event {
                 "An equivocator with public key: {} has been identified at time: {} in era: {}",
                 public_key, timestamp, era_id,
             ),
-            Event::FinalitySignature(fs) => write!(formatter, "finality signature {}", fs),
+            Event::FinalitySignature(fs, _) => write!(formatter, "finality signature {}", fs),
             Event::Step { era_id, .. } => write!(formatter, "step committed for {}", era_id),
         }
     }
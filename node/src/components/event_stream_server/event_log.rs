@@ -0,0 +1,468 @@
+//! A bounded on-disk log of the events emitted by the event stream server.
+//!
+//! Events are appended as lines of JSON to a series of segment files.  Once the current segment
+//! reaches its share of the configured maximum size, a new segment is started.  The oldest segments
+//! are deleted while the log exceeds its maximum size, or while their newest event exceeds the
+//! maximum age, so the log acts as a ring buffer which persists across node restarts.
+//!
+//! While the event stream server runs, the log is owned by a dedicated blocking task, so that its
+//! file I/O does not hold up the handling of new events.
+
+use std::{
+    collections::VecDeque,
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, oneshot};
+use tracing::{debug, warn};
+
+use casper_types::{TimeDiff, Timestamp};
+
+use super::{
+    sse_server::{Id, ServerSentEvent},
+    SseData,
+};
+use crate::reactor::supervision::TaskSupervisor;
+
+/// The name of the folder holding the event log's segment files.
+const EVENT_LOG_DIR: &str = "sse_event_log";
+/// The file extension of the segment files.
+const SEGMENT_EXTENSION: &str = "jsonl";
+/// The number of segments between which the maximum size of the log is divided.
+const SEGMENT_COUNT: u64 = 8;
+
+/// A single line of a segment file.
+#[derive(Serialize, Deserialize)]
+struct LoggedEvent<T> {
+    id: Id,
+    timestamp: Timestamp,
+    data: T,
+}
+
+/// A single segment file of the event log.
+#[derive(Debug)]
+struct Segment {
+    sequence_number: u64,
+    size_bytes: u64,
+    last_written: Timestamp,
+}
+
+/// A request to the task owning an `EventLog`.
+enum Request {
+    /// Append the given event to the log.
+    Append(Id, SseData),
+    /// Provide a reader over the events logged so far.
+    Reader(oneshot::Sender<EventLogReader>),
+}
+
+/// A handle to an `EventLog` owned by a dedicated blocking task.
+#[derive(Clone, Debug)]
+pub(super) struct EventLogHandle {
+    sender: mpsc::UnboundedSender<Request>,
+}
+
+impl EventLogHandle {
+    /// Queues the given event to be appended to the log.
+    pub(super) fn append(&self, id: Id, data: SseData) {
+        // This only fails if the owning task has exited, in which case the log is unavailable.
+        let _ = self.sender.send(Request::Append(id, data));
+    }
+
+    /// Returns a reader over the events logged so far, including all those queued before calling
+    /// this, or `None` if the owning task has exited.
+    ///
+    /// Blocks until the owning task has caught up, so must only be called where blocking is
+    /// acceptable.
+    pub(super) fn blocking_reader(&self) -> Option<EventLogReader> {
+        let (responder, receiver) = oneshot::channel();
+        self.sender.send(Request::Reader(responder)).ok()?;
+        receiver.blocking_recv().ok()
+    }
+}
+
+/// The events replayed from an `EventLog`.
+pub(super) struct Replay {
+    /// The number of events which were skipped as they were too old.
+    pub(super) skipped_count: u64,
+    /// The replayed events, oldest first.
+    pub(super) events: VecDeque<ServerSentEvent>,
+}
+
+#[derive(Debug)]
+pub(super) struct EventLog {
+    dir: PathBuf,
+    max_size_bytes: u64,
+    max_age: TimeDiff,
+    /// The segments of the log, oldest first.  Events are appended to the last one.
+    segments: VecDeque<Segment>,
+    /// The open handle to the last segment, if any.
+    current_file: Option<File>,
+}
+
+impl EventLog {
+    /// Opens the event log held under `storage_path`, creating it if required.
+    ///
+    /// Returns `None` if `max_size_bytes` is 0, or if the log's folder cannot be created.
+    pub(super) fn new(storage_path: &Path, max_size_bytes: u64, max_age: TimeDiff) -> Option<Self> {
+        if max_size_bytes == 0 {
+            return None;
+        }
+
+        let dir = storage_path.join(EVENT_LOG_DIR);
+        if let Err(error) = fs::create_dir_all(&dir) {
+            warn!(dir = %dir.display(), %error, "failed to create sse event log folder");
+            return None;
+        }
+
+        let mut segments: Vec<Segment> = match fs::read_dir(&dir) {
+            Ok(entries) => entries
+                .filter_map(|maybe_entry| {
+                    let path = maybe_entry.ok()?.path();
+                    if path.extension()?.to_str()? != SEGMENT_EXTENSION {
+                        return None;
+                    }
+                    let sequence_number = path.file_stem()?.to_str()?.parse().ok()?;
+                    let metadata = fs::metadata(&path).ok()?;
+                    let last_written = metadata
+                        .modified()
+                        .ok()
+                        .and_then(|modified| modified.duration_since(SystemTime::UNIX_EPOCH).ok())
+                        .map(|since_epoch| Timestamp::from(since_epoch.as_millis() as u64))
+                        .unwrap_or_else(Timestamp::now);
+                    Some(Segment {
+                        sequence_number,
+                        size_bytes: metadata.len(),
+                        last_written,
+                    })
+                })
+                .collect(),
+            Err(error) => {
+                warn!(dir = %dir.display(), %error, "failed to read sse event log folder");
+                return None;
+            }
+        };
+        segments.sort_by_key(|segment| segment.sequence_number);
+
+        let mut event_log = EventLog {
+            dir,
+            max_size_bytes,
+            max_age,
+            segments: segments.into(),
+            current_file: None,
+        };
+        event_log.purge(Timestamp::now());
+        debug!(
+            dir = %event_log.dir.display(),
+            segment_count = event_log.segments.len(),
+            "initialized sse event log"
+        );
+        Some(event_log)
+    }
+
+    /// Moves the log to a blocking task spawned by `supervisor`, which appends to and reads from
+    /// it as requested via the returned handle, and exits once all handles have been dropped.
+    pub(super) fn spawn(mut self, supervisor: &TaskSupervisor) -> EventLogHandle {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let _ = supervisor.spawn_blocking(move || {
+            while let Some(request) = receiver.blocking_recv() {
+                match request {
+                    Request::Append(id, data) => self.append(id, &data),
+                    Request::Reader(responder) => {
+                        let _ = responder.send(self.reader());
+                    }
+                }
+            }
+        });
+        EventLogHandle { sender }
+    }
+
+    /// Appends the given event to the log, purging the oldest events if required.
+    fn append(&mut self, id: Id, data: &SseData) {
+        let timestamp = Timestamp::now();
+        let mut line = match serde_json::to_vec(&LoggedEvent {
+            id,
+            timestamp,
+            data,
+        }) {
+            Ok(line) => line,
+            Err(error) => {
+                warn!(%id, %error, "failed to serialize event for sse event log");
+                return;
+            }
+        };
+        line.push(b'\n');
+
+        let max_segment_size = (self.max_size_bytes / SEGMENT_COUNT).max(1);
+        let needs_new_segment = match self.segments.back() {
+            Some(segment) => {
+                segment.size_bytes > 0
+                    && segment.size_bytes + line.len() as u64 > max_segment_size
+            }
+            None => true,
+        };
+        if needs_new_segment {
+            let sequence_number = self
+                .segments
+                .back()
+                .map_or(0, |segment| segment.sequence_number + 1);
+            self.segments.push_back(Segment {
+                sequence_number,
+                size_bytes: 0,
+                last_written: timestamp,
+            });
+            self.current_file = None;
+        }
+
+        // Every branch above leaves at least one segment.
+        let segment = self.segments.back_mut().unwrap();
+        let path = self.dir.join(segment_filename(segment.sequence_number));
+        if self.current_file.is_none() {
+            match OpenOptions::new().create(true).append(true).open(&path) {
+                Ok(file) => self.current_file = Some(file),
+                Err(error) => {
+                    warn!(file = %path.display(), %error, "failed to open sse event log file");
+                    return;
+                }
+            }
+        }
+        if let Some(file) = self.current_file.as_mut() {
+            if let Err(error) = file.write_all(&line) {
+                warn!(file = %path.display(), %error, "failed to write to sse event log file");
+                self.current_file = None;
+                return;
+            }
+        }
+        segment.size_bytes += line.len() as u64;
+        segment.last_written = timestamp;
+
+        self.purge(timestamp);
+    }
+
+    /// Returns a reader over the events currently held in the log.
+    fn reader(&self) -> EventLogReader {
+        EventLogReader {
+            paths: self
+                .segments
+                .iter()
+                .map(|segment| self.dir.join(segment_filename(segment.sequence_number)))
+                .collect(),
+        }
+    }
+
+    /// Deletes the oldest segments while the log is too large, or while their newest event is too
+    /// old.
+    fn purge(&mut self, now: Timestamp) {
+        while let Some(oldest) = self.segments.front() {
+            let total_size: u64 = self.segments.iter().map(|segment| segment.size_bytes).sum();
+            if total_size <= self.max_size_bytes
+                && now.saturating_diff(oldest.last_written) <= self.max_age
+            {
+                break;
+            }
+
+            let path = self.dir.join(segment_filename(oldest.sequence_number));
+            if let Err(error) = fs::remove_file(&path) {
+                warn!(file = %path.display(), %error, "failed to remove sse event log file");
+            }
+            let _ = self.segments.pop_front();
+            if self.segments.is_empty() {
+                self.current_file = None;
+            }
+        }
+    }
+}
+
+/// A snapshot of the segment files of an `EventLog`, which can be read without blocking the event
+/// stream server.
+pub(super) struct EventLogReader {
+    paths: Vec<PathBuf>,
+}
+
+impl EventLogReader {
+    /// Returns the logged event with ID `start_from` and all subsequent logged events, of which
+    /// only the latest `max_count` are retained while reading the log.
+    ///
+    /// Returns `None` if there is no logged event with ID `start_from`.
+    pub(super) fn replay_from(&self, start_from: Id, max_count: usize) -> Option<Replay> {
+        let mut found_start = false;
+        let mut replay = Replay {
+            skipped_count: 0,
+            events: VecDeque::new(),
+        };
+        for path in &self.paths {
+            let file = match File::open(path) {
+                Ok(file) => file,
+                Err(error) => {
+                    // The segment may have been purged since this reader was created.
+                    debug!(file = %path.display(), %error, "failed to open sse event log file");
+                    continue;
+                }
+            };
+            for line in BufReader::new(file).lines() {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(error) => {
+                        warn!(file = %path.display(), %error, "failed to read sse event log file");
+                        break;
+                    }
+                };
+                // A partially-written final line can't be parsed, and is skipped.
+                let logged_event: LoggedEvent<SseData> = match serde_json::from_str(&line) {
+                    Ok(logged_event) => logged_event,
+                    Err(error) => {
+                        debug!(
+                            file = %path.display(),
+                            %error,
+                            "skipping invalid sse event log entry"
+                        );
+                        continue;
+                    }
+                };
+                found_start = found_start || logged_event.id == start_from;
+                if !found_start {
+                    continue;
+                }
+                replay.events.push_back(ServerSentEvent {
+                    id: Some(logged_event.id),
+                    data: logged_event.data,
+                    correlation_id: None,
+                });
+                if replay.events.len() > max_count {
+                    let _ = replay.events.pop_front();
+                    replay.skipped_count += 1;
+                }
+            }
+        }
+        if found_start {
+            Some(replay)
+        } else {
+            None
+        }
+    }
+}
+
+fn segment_filename(sequence_number: u64) -> String {
+    format!("{:020}.{}", sequence_number, SEGMENT_EXTENSION)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logging;
+
+    const MAX_AGE: &str = "1day";
+
+    fn replayed_ids(event_log: &EventLog, start_from: Id) -> Option<Vec<Id>> {
+        let replay = event_log.reader().replay_from(start_from, usize::MAX)?;
+        assert_eq!(replay.skipped_count, 0);
+        Some(
+            replay
+                .events
+                .iter()
+                .map(|event| event.id.unwrap())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn should_replay_events_after_restart() {
+        let _ = logging::init();
+        let mut rng = crate::new_rng();
+        let tempdir = tempfile::tempdir().unwrap();
+
+        {
+            let mut event_log =
+                EventLog::new(tempdir.path(), 1_000_000, MAX_AGE.parse().unwrap()).unwrap();
+            for id in 0..10 {
+                event_log.append(id, &SseData::random_block_added(&mut rng));
+            }
+        }
+
+        let mut event_log =
+            EventLog::new(tempdir.path(), 1_000_000, MAX_AGE.parse().unwrap()).unwrap();
+        event_log.append(10, &SseData::random_fault(&mut rng));
+
+        assert_eq!(replayed_ids(&event_log, 7), Some(vec![7, 8, 9, 10]));
+        assert_eq!(replayed_ids(&event_log, 0), Some((0..=10).collect()));
+        // Events which were never logged can't be replayed.
+        assert_eq!(replayed_ids(&event_log, 11), None);
+    }
+
+    #[test]
+    fn should_only_replay_latest_events() {
+        let _ = logging::init();
+        let mut rng = crate::new_rng();
+        let tempdir = tempfile::tempdir().unwrap();
+
+        let mut event_log =
+            EventLog::new(tempdir.path(), 1_000_000, MAX_AGE.parse().unwrap()).unwrap();
+        for id in 0..10 {
+            event_log.append(id, &SseData::random_block_added(&mut rng));
+        }
+
+        let replay = event_log.reader().replay_from(2, 3).unwrap();
+        assert_eq!(replay.skipped_count, 5);
+        let ids: Vec<Id> = replay
+            .events
+            .iter()
+            .map(|event| event.id.unwrap())
+            .collect();
+        assert_eq!(ids, vec![7, 8, 9]);
+    }
+
+    #[test]
+    fn should_purge_oldest_events_when_too_large() {
+        let _ = logging::init();
+        let mut rng = crate::new_rng();
+        let tempdir = tempfile::tempdir().unwrap();
+
+        let max_size_bytes = 20_000;
+        let mut event_log =
+            EventLog::new(tempdir.path(), max_size_bytes, MAX_AGE.parse().unwrap()).unwrap();
+        for id in 0..500 {
+            event_log.append(id, &SseData::random_fault(&mut rng));
+        }
+
+        let total_size: u64 = fs::read_dir(tempdir.path().join(EVENT_LOG_DIR))
+            .unwrap()
+            .map(|entry| entry.unwrap().metadata().unwrap().len())
+            .sum();
+        assert!(total_size <= max_size_bytes);
+        assert_eq!(replayed_ids(&event_log, 0), None);
+        assert_eq!(replayed_ids(&event_log, 499), Some(vec![499]));
+    }
+
+    #[test]
+    fn should_purge_events_when_too_old() {
+        let _ = logging::init();
+        let mut rng = crate::new_rng();
+        let tempdir = tempfile::tempdir().unwrap();
+
+        {
+            let mut event_log =
+                EventLog::new(tempdir.path(), 1_000_000, MAX_AGE.parse().unwrap()).unwrap();
+            event_log.append(0, &SseData::random_step(&mut rng));
+        }
+
+        let mut event_log = EventLog::new(tempdir.path(), 1_000_000, TimeDiff::from(0)).unwrap();
+        event_log.purge(Timestamp::now() + TimeDiff::from(1_000));
+        assert_eq!(replayed_ids(&event_log, 0), None);
+        assert_eq!(
+            fs::read_dir(tempdir.path().join(EVENT_LOG_DIR))
+                .unwrap()
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn should_be_disabled_with_zero_max_size() {
+        let tempdir = tempfile::tempdir().unwrap();
+        assert!(EventLog::new(tempdir.path(), 0, MAX_AGE.parse().unwrap()).is_none());
+        assert!(!tempdir.path().join(EVENT_LOG_DIR).exists());
+    }
+}
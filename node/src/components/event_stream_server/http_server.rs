@@ -2,20 +2,26 @@ use futures::{future, Future, FutureExt};
 use tokio::{
     select,
     sync::{broadcast, mpsc, oneshot},
-    task,
 };
 use tracing::{info, trace};
 use wheelbuf::WheelBuf;
 
 use casper_types::ProtocolVersion;
 
+use crate::reactor::{correlation::CorrelationId, supervision::TaskSupervisor};
+
 use super::{
-    sse_server::{BroadcastChannelMessage, Id, NewSubscriberInfo, ServerSentEvent},
+    event_log::EventLog,
+    sse_server::{
+        BroadcastChannelMessage, ClientMessage, Id, NewSubscriberInfo, ServerSentEvent,
+        SlowClientHandler,
+    },
     Config, EventIndex, SseData,
 };
 
 /// Run the HTTP server.
 ///
+/// * `supervisor` spawns the tasks running the server and reading the event log.
 /// * `server_with_shutdown` is the actual server as a future which can be gracefully shut down.
 /// * `server_shutdown_sender` is the channel by which the server will be notified to shut down.
 /// * `data_receiver` will provide the server with local events which should then be sent to all
@@ -25,16 +31,27 @@ use super::{
 /// * `new_subscriber_info_receiver` is used to notify the server of the details of a new client
 ///   having subscribed to the event stream.  It allows the server to populate that client's stream
 ///   with the requested number of historical events.
+/// * `event_log` is the persisted log of events, if enabled.  It is moved to a blocking task, where
+///   events other than `Shutdown` are appended to it, and it is read to serve clients requesting
+///   events which are no longer held in the in-memory buffer.
+/// * `slow_client_handler` is updated with the ID of each event before it is broadcast, allowing
+///   it to determine how far behind each subscribed client is.  It also bounds the events replayed
+///   from the event log.
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn run(
     config: Config,
     api_version: ProtocolVersion,
+    supervisor: TaskSupervisor,
     server_with_shutdown: impl Future<Output = ()> + Send + 'static,
     server_shutdown_sender: oneshot::Sender<()>,
-    mut data_receiver: mpsc::UnboundedReceiver<(EventIndex, SseData)>,
+    mut data_receiver: mpsc::UnboundedReceiver<(EventIndex, SseData, Option<CorrelationId>)>,
     broadcaster: broadcast::Sender<BroadcastChannelMessage>,
     mut new_subscriber_info_receiver: mpsc::UnboundedReceiver<NewSubscriberInfo>,
+    event_log: Option<EventLog>,
+    slow_client_handler: SlowClientHandler,
 ) {
-    let server_joiner = task::spawn(server_with_shutdown);
+    let server_joiner = supervisor.spawn(server_with_shutdown);
+    let event_log = event_log.map(|event_log| event_log.spawn(&supervisor));
 
     // Initialize the index and buffer for the SSEs.
     let mut buffer = WheelBuf::new(vec![
@@ -51,38 +68,54 @@ pub(super) async fn run(
                     if let Some(subscriber) = maybe_new_subscriber {
                         // First send the client the `ApiVersion` event.  We don't care if this
                         // errors - the client may have disconnected already.
-                        let _ = subscriber
-                            .initial_events_sender
-                            .send(ServerSentEvent::initial_event(api_version));
+                        let _ = subscriber.initial_events_sender.send(Ok(ClientMessage::Event(
+                            ServerSentEvent::initial_event(api_version),
+                        )));
                         // If the client supplied a "start_from" index, provide the buffered events.
-                        // If they requested more than is buffered, just provide the whole buffer.
+                        // If they requested more than is buffered, provide the events from the
+                        // event log if it holds the requested one, or else the whole buffer.
                         if let Some(start_index) = subscriber.start_from {
-                            // If the buffer's first event ID is in the range [0, buffer size) or
-                            // (Id::MAX - buffer size, Id::MAX], then the events in the buffer are
-                            // considered to have their IDs wrapping round, or that was recently the
-                            // case.  In this case, we add `buffer.capacity()` to `start_index` and
-                            // the buffered events' IDs when considering which events to include in
-                            // the requested initial events, effectively shifting all the IDs past
-                            // the wrapping transition.
-                            let buffer_size = buffer.capacity() as Id;
-                            let in_wraparound_zone = buffer
-                                .iter()
-                                .next()
-                                .map(|event| {
-                                    let id = event.id.unwrap();
-                                    id > Id::MAX - buffer_size || id < buffer_size
-                                })
-                                .unwrap_or_default();
-                            for event in buffer.iter().skip_while(|event| {
-                                if in_wraparound_zone {
-                                    event.id.unwrap().wrapping_add(buffer_size)
-                                        < start_index.wrapping_add(buffer_size)
-                                } else {
-                                    event.id.unwrap() < start_index
+                            let is_buffered =
+                                buffer.iter().any(|event| event.id == Some(start_index));
+                            let buffered_events = buffered_events(&buffer, start_index);
+                            match event_log.as_ref() {
+                                Some(event_log) if !is_buffered => {
+                                    // Read the log on a blocking thread so as not to hold up the
+                                    // handling of new events.  Those emitted in the meantime are
+                                    // also sent via the broadcast channel, and are deduplicated by
+                                    // the client's handler.
+                                    let event_log = event_log.clone();
+                                    let slow_client_handler = slow_client_handler.clone();
+                                    let _ = supervisor.spawn_blocking(move || {
+                                        let messages = event_log
+                                            .blocking_reader()
+                                            .and_then(|reader| {
+                                                slow_client_handler.replay(
+                                                    &reader,
+                                                    start_index,
+                                                    &subscriber.remote_address,
+                                                )
+                                            })
+                                            .unwrap_or_else(|| {
+                                                buffered_events
+                                                    .into_iter()
+                                                    .map(|event| Ok(ClientMessage::Event(event)))
+                                                    .collect()
+                                            });
+                                        for message in messages {
+                                            let _ = subscriber.initial_events_sender.send(message);
+                                        }
+                                    });
+                                }
+                                _ => {
+                                    for event in buffered_events {
+                                        // As per sending `SSE_INITIAL_EVENT`, we don't care if this
+                                        // errors.
+                                        let _ = subscriber
+                                            .initial_events_sender
+                                            .send(Ok(ClientMessage::Event(event)));
+                                    }
                                 }
-                            }) {
-                                // As per sending `SSE_INITIAL_EVENT`, we don't care if this errors.
-                                let _ = subscriber.initial_events_sender.send(event.clone());
                             }
                         }
                     }
@@ -90,11 +123,23 @@ pub(super) async fn run(
 
                 maybe_data = data_receiver.recv() => {
                     match maybe_data {
-                        Some((event_index, data)) => {
+                        Some((event_index, data, correlation_id)) => {
                             // Buffer the data and broadcast it to subscribed clients.
                             trace!("Event stream server received {:?}", data);
-                            let event = ServerSentEvent { id: Some(event_index), data };
+                            // Don't persist `Shutdown` events, as replaying them after a restart
+                            // would wrongly indicate to clients that the node is shutting down.
+                            if let Some(event_log) = event_log.as_ref() {
+                                if !matches!(data, SseData::Shutdown) {
+                                    event_log.append(event_index, data.clone());
+                                }
+                            }
+                            let event = ServerSentEvent {
+                                id: Some(event_index),
+                                data,
+                                correlation_id,
+                            };
                             buffer.push(event.clone());
+                            slow_client_handler.set_latest_event_id(event_index);
                             let message = BroadcastChannelMessage::ServerSentEvent(event);
                             // This can validly fail if there are no connected clients, so don't log
                             // the error.
@@ -121,3 +166,36 @@ pub(super) async fn run(
 
     trace!("Event stream server stopped");
 }
+
+/// Returns the buffered events with IDs from `start_index` onwards, allowing for the IDs wrapping
+/// round.
+fn buffered_events(
+    buffer: &WheelBuf<Vec<ServerSentEvent>, ServerSentEvent>,
+    start_index: Id,
+) -> Vec<ServerSentEvent> {
+    // If the buffer's first event ID is in the range [0, buffer size) or
+    // (Id::MAX - buffer size, Id::MAX], then the events in the buffer are considered to have their
+    // IDs wrapping round, or that was recently the case.  In this case, we add `buffer.capacity()`
+    // to `start_index` and the buffered events' IDs when considering which events to include in the
+    // requested initial events, effectively shifting all the IDs past the wrapping transition.
+    let buffer_size = buffer.capacity() as Id;
+    let in_wraparound_zone = buffer
+        .iter()
+        .next()
+        .map(|event| {
+            let id = event.id.unwrap();
+            id > Id::MAX - buffer_size || id < buffer_size
+        })
+        .unwrap_or_default();
+    buffer
+        .iter()
+        .skip_while(|event| {
+            if in_wraparound_zone {
+                event.id.unwrap().wrapping_add(buffer_size) < start_index.wrapping_add(buffer_size)
+            } else {
+                event.id.unwrap() < start_index
+            }
+        })
+        .cloned()
+        .collect()
+}
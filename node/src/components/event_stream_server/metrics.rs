@@ -0,0 +1,43 @@
+use prometheus::{IntCounter, Registry};
+
+use crate::unregister_metric;
+
+#[derive(Debug)]
+pub(crate) struct Metrics {
+    /// Number of events not sent to clients which had fallen too far behind the latest event.
+    pub(super) dropped_events: IntCounter,
+    /// Number of clients disconnected for having fallen too far behind the latest event.
+    pub(super) slow_client_disconnections: IntCounter,
+    /// Reference to the registry for unregistering.
+    registry: Registry,
+}
+
+impl Metrics {
+    pub(super) fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
+        let dropped_events = IntCounter::new(
+            "event_stream_dropped_events",
+            "number of events not sent to event stream clients which had fallen too far behind \
+            the latest event",
+        )?;
+        let slow_client_disconnections = IntCounter::new(
+            "event_stream_slow_client_disconnections",
+            "number of event stream clients disconnected for having fallen too far behind the \
+            latest event",
+        )?;
+        registry.register(Box::new(dropped_events.clone()))?;
+        registry.register(Box::new(slow_client_disconnections.clone()))?;
+
+        Ok(Metrics {
+            dropped_events,
+            slow_client_disconnections,
+            registry: registry.clone(),
+        })
+    }
+}
+
+impl Drop for Metrics {
+    fn drop(&mut self) {
+        unregister_metric!(self.registry, self.dropped_events);
+        unregister_metric!(self.registry, self.slow_client_disconnections);
+    }
+}
@@ -2,12 +2,16 @@
 
 use std::{
     collections::{HashMap, HashSet},
+    convert::TryFrom,
     net::SocketAddr,
-    sync::{Arc, RwLock},
+    sync::{
+        atomic::{AtomicU32, AtomicU64, Ordering},
+        Arc, RwLock,
+    },
 };
 
 use datasize::DataSize;
-use futures::{future, Stream, StreamExt};
+use futures::{future, stream, Stream, StreamExt};
 use http::StatusCode;
 use hyper::Body;
 #[cfg(test)]
@@ -32,15 +36,23 @@ use warp::{
     Filter, Reply,
 };
 
+use casper_execution_engine::core::engine_state::executable_deploy_item::ContractIdentifier;
 #[cfg(test)]
 use casper_types::testing::TestRng;
 use casper_types::{
-    EraId, ExecutionEffect, ExecutionResult, ProtocolVersion, PublicKey, TimeDiff, Timestamp,
+    AsymmetricType, ContractHash, EraId, ExecutionEffect, ExecutionResult, HashAddr, Key,
+    ProtocolVersion, PublicKey, TimeDiff, Timestamp, U512,
 };
 
-use crate::types::{BlockHash, Deploy, DeployHash, FinalitySignature, JsonBlock};
+use super::{config::SlowClientPolicy, event_log::EventLogReader, metrics::Metrics, Config};
 #[cfg(test)]
-use crate::{testing, types::Block};
+use crate::{components::rpc_server::rpcs::docs::DocExample, testing, types::Block};
+use crate::{
+    reactor::correlation::CorrelationId,
+    types::{
+        BlockHash, ContractEvent, Deploy, DeployHash, FinalitySignature, JsonBlock, SignedWeight,
+    },
+};
 
 /// The URL root path.
 pub const SSE_API_ROOT_PATH: &str = "events";
@@ -51,8 +63,23 @@ pub const SSE_API_MAIN_PATH: &str = "main";
 pub const SSE_API_DEPLOYS_PATH: &str = "deploys";
 /// The URL path part to subscribe to only `FinalitySignature` events.
 pub const SSE_API_SIGNATURES_PATH: &str = "sigs";
+/// The URL path part to subscribe to only `ContractEvent` events.
+pub const SSE_API_CONTRACT_EVENTS_PATH: &str = "contract-events";
+/// The URL path part to subscribe to only `DeployExecutionStarted` and `DeployExecutionFinished`
+/// events.
+pub const SSE_API_DEPLOY_EXECUTION_PATH: &str = "deploy-execution";
 /// The URL query string field name.
 pub const QUERY_FIELD: &str = "start_from";
+/// The URL query string field name for restricting deploy events to the given accounts.
+pub const ACCOUNT_QUERY_FIELD: &str = "account";
+/// The URL query string field name for restricting deploy events to the given contracts.
+pub const CONTRACT_QUERY_FIELD: &str = "contract";
+/// The URL query string field name for restricting events to the given event types.
+pub const EVENT_TYPE_QUERY_FIELD: &str = "event_type";
+/// The separator between multiple values provided for a single query string field.
+const QUERY_VALUE_SEPARATOR: char = ',';
+/// The header sent by reconnecting clients holding the ID of the last event they received.
+const LAST_EVENT_ID_HEADER: &str = "last-event-id";
 
 /// The filter associated with `/events/main` path.
 const MAIN_FILTER: [EventFilter; 5] = [
@@ -66,6 +93,13 @@ const MAIN_FILTER: [EventFilter; 5] = [
 const DEPLOYS_FILTER: [EventFilter; 1] = [EventFilter::DeployAccepted];
 /// The filter associated with `/events/sigs` path.
 const SIGNATURES_FILTER: [EventFilter; 1] = [EventFilter::FinalitySignature];
+/// The filter associated with `/events/contract-events` path.
+const CONTRACT_EVENTS_FILTER: [EventFilter; 1] = [EventFilter::ContractEvent];
+/// The filter associated with `/events/deploy-execution` path.
+const DEPLOY_EXECUTION_FILTER: [EventFilter; 2] = [
+    EventFilter::DeployExecutionStarted,
+    EventFilter::DeployExecutionFinished,
+];
 
 /// The "id" field of the events sent on the event stream to clients.
 pub type Id = u32;
@@ -107,14 +141,42 @@ pub enum SseData {
         public_key: PublicKey,
         timestamp: Timestamp,
     },
-    /// New finality signature received.
-    FinalitySignature(Box<FinalitySignature>),
+    /// New finality signature received, along with the cumulative weight of the block's known
+    /// signatures if the validators of the block's era are known.
+    FinalitySignature {
+        #[serde(flatten)]
+        finality_signature: Box<FinalitySignature>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cumulative_weight: Option<Box<SignedWeight>>,
+    },
     /// The execution effects produced by a `StepRequest`.
     Step {
         era_id: EraId,
         #[data_size(skip)]
         execution_effect: ExecutionEffect,
     },
+    /// The given event was emitted by a contract while executing the given deploy.
+    ContractEvent {
+        deploy_hash: Box<DeployHash>,
+        account: Box<PublicKey>,
+        block_hash: Box<BlockHash>,
+        contract_event: Box<ContractEvent>,
+    },
+    /// Execution of the given deploy in the block at the given height has started.
+    DeployExecutionStarted {
+        deploy_hash: Box<DeployHash>,
+        account: Box<PublicKey>,
+        block_height: u64,
+    },
+    /// Execution of the given deploy in the block at the given height has finished.  Its effects
+    /// are not committed until the block is added.
+    DeployExecutionFinished {
+        deploy_hash: Box<DeployHash>,
+        account: Box<PublicKey>,
+        block_height: u64,
+        cost: U512,
+        error_message: Option<String>,
+    },
     /// The node is about to shut down.
     Shutdown,
 }
@@ -128,8 +190,15 @@ impl SseData {
             SseData::DeployProcessed { .. } => filter.contains(&EventFilter::DeployProcessed),
             SseData::DeployExpired { .. } => filter.contains(&EventFilter::DeployExpired),
             SseData::Fault { .. } => filter.contains(&EventFilter::Fault),
-            SseData::FinalitySignature(_) => filter.contains(&EventFilter::FinalitySignature),
+            SseData::FinalitySignature { .. } => filter.contains(&EventFilter::FinalitySignature),
             SseData::Step { .. } => filter.contains(&EventFilter::Step),
+            SseData::ContractEvent { .. } => filter.contains(&EventFilter::ContractEvent),
+            SseData::DeployExecutionStarted { .. } => {
+                filter.contains(&EventFilter::DeployExecutionStarted)
+            }
+            SseData::DeployExecutionFinished { .. } => {
+                filter.contains(&EventFilter::DeployExecutionFinished)
+            }
         }
     }
 }
@@ -197,10 +266,53 @@ impl SseData {
 
     /// Returns a random `SseData::FinalitySignature`.
     pub(super) fn random_finality_signature(rng: &mut TestRng) -> Self {
-        SseData::FinalitySignature(Box::new(FinalitySignature::random_for_block(
-            BlockHash::random(rng),
-            rng.gen(),
-        )))
+        let cumulative_weight = rng.gen::<bool>().then(|| {
+            Box::new(SignedWeight {
+                signed_weight: rng.gen::<u64>().into(),
+                total_weight: u64::MAX.into(),
+            })
+        });
+        SseData::FinalitySignature {
+            finality_signature: Box::new(FinalitySignature::random_for_block(
+                BlockHash::random(rng),
+                rng.gen(),
+            )),
+            cumulative_weight,
+        }
+    }
+
+    /// Returns a random `SseData::ContractEvent`.
+    pub(super) fn random_contract_event(rng: &mut TestRng) -> Self {
+        SseData::ContractEvent {
+            deploy_hash: Box::new(DeployHash::random(rng)),
+            account: Box::new(PublicKey::random(rng)),
+            block_hash: Box::new(BlockHash::random(rng)),
+            contract_event: Box::new(ContractEvent::doc_example().clone()),
+        }
+    }
+
+    /// Returns a random `SseData::DeployExecutionStarted`.
+    pub(super) fn random_deploy_execution_started(rng: &mut TestRng) -> Self {
+        SseData::DeployExecutionStarted {
+            deploy_hash: Box::new(DeployHash::random(rng)),
+            account: Box::new(PublicKey::random(rng)),
+            block_height: rng.gen(),
+        }
+    }
+
+    /// Returns a random `SseData::DeployExecutionFinished`.
+    pub(super) fn random_deploy_execution_finished(rng: &mut TestRng) -> Self {
+        SseData::DeployExecutionFinished {
+            deploy_hash: Box::new(DeployHash::random(rng)),
+            account: Box::new(PublicKey::random(rng)),
+            block_height: rng.gen(),
+            cost: U512::from(rng.gen::<u64>()),
+            error_message: if rng.gen() {
+                Some("Insufficient payment".to_string())
+            } else {
+                None
+            },
+        }
     }
 
     /// Returns a random `SseData::Step`.
@@ -223,12 +335,25 @@ pub(super) struct DeployAccepted {
     pub(super) deploy_accepted: Arc<Deploy>,
 }
 
+/// The data of the event sent to a client in place of events dropped for having fallen too far
+/// behind the latest event.  It has no associated event ID.
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub(super) struct EventsDropped {
+    pub(super) events_dropped: u64,
+}
+
 /// The components of a single SSE.
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub(super) struct ServerSentEvent {
     /// The ID should only be `None` where the `data` is `SseData::ApiVersion`.
     pub(super) id: Option<Id>,
     pub(super) data: SseData,
+    /// The correlation ID of the trigger the event originates from, sent to clients as a comment.
+    ///
+    /// Only known for events emitted since the node started, not for those replayed from the
+    /// event log.
+    pub(super) correlation_id: Option<CorrelationId>,
 }
 
 impl ServerSentEvent {
@@ -237,6 +362,7 @@ impl ServerSentEvent {
         ServerSentEvent {
             id: None,
             data: SseData::ApiVersion(client_api_version),
+            correlation_id: None,
         }
     }
 }
@@ -255,13 +381,158 @@ pub(super) enum BroadcastChannelMessage {
     Shutdown,
 }
 
+/// A message to be sent to a client via its event stream.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(super) enum ClientMessage {
+    /// An event, to be sent if it passes the client's filters.
+    Event(ServerSentEvent),
+    /// The given number of events were dropped as the client had fallen too far behind.
+    EventsDropped(u64),
+}
+
+/// Applies the configured `SlowClientPolicy` to clients which have fallen more than
+/// `client_buffer_length` events behind the latest event.
+#[derive(Clone, Debug)]
+pub(super) struct SlowClientHandler {
+    policy: SlowClientPolicy,
+    client_buffer_length: u32,
+    /// The ID of the latest event sent via the broadcast channel.
+    latest_event_id: Arc<AtomicU32>,
+    metrics: Arc<Metrics>,
+}
+
+impl SlowClientHandler {
+    pub(super) fn new(config: &Config, metrics: Arc<Metrics>) -> Self {
+        SlowClientHandler {
+            policy: config.slow_client_policy,
+            client_buffer_length: config.client_buffer_length,
+            latest_event_id: Arc::new(AtomicU32::new(0)),
+            metrics,
+        }
+    }
+
+    /// Records the ID of the latest event, which must be called before the event is sent via the
+    /// broadcast channel.
+    pub(super) fn set_latest_event_id(&self, id: Id) {
+        self.latest_event_id.store(id, Ordering::SeqCst);
+    }
+
+    /// Returns the messages to send to a client on receiving `event` via the broadcast channel.
+    ///
+    /// `dropped_count` holds the number of events dropped for the client since it was last sent
+    /// an event, and `is_wanted` indicates whether the client would be sent `event` if it were
+    /// not too far behind.
+    fn handle_event(
+        &self,
+        event: ServerSentEvent,
+        is_wanted: bool,
+        dropped_count: &AtomicU64,
+        remote_address: &str,
+    ) -> Vec<Result<ClientMessage, RecvError>> {
+        let lag = event.id.map_or(0, |id| {
+            self.latest_event_id.load(Ordering::SeqCst).wrapping_sub(id)
+        });
+        if lag > self.client_buffer_length {
+            match self.policy {
+                SlowClientPolicy::Disconnect => return self.disconnect(remote_address, lag as u64),
+                SlowClientPolicy::DropOldest => {
+                    if is_wanted {
+                        dropped_count.fetch_add(1, Ordering::SeqCst);
+                        self.metrics.dropped_events.inc();
+                    }
+                    return vec![];
+                }
+            }
+        }
+
+        let mut messages = Vec::with_capacity(2);
+        let events_dropped = dropped_count.swap(0, Ordering::SeqCst);
+        if events_dropped > 0 {
+            messages.push(Ok(ClientMessage::EventsDropped(events_dropped)));
+        }
+        messages.push(Ok(ClientMessage::Event(event)));
+        messages
+    }
+
+    /// Returns the messages to send to a client which has lagged so far behind that
+    /// `lagged_count` events have been purged from the broadcast channel before it received them.
+    fn handle_lagged(
+        &self,
+        lagged_count: u64,
+        dropped_count: &AtomicU64,
+        remote_address: &str,
+    ) -> Vec<Result<ClientMessage, RecvError>> {
+        match self.policy {
+            SlowClientPolicy::Disconnect => self.disconnect(remote_address, lagged_count),
+            SlowClientPolicy::DropOldest => {
+                // We can't tell which of the purged events would have been sent to the client, so
+                // count them all as dropped.
+                dropped_count.fetch_add(lagged_count, Ordering::SeqCst);
+                self.metrics.dropped_events.inc_by(lagged_count);
+                vec![]
+            }
+        }
+    }
+
+    /// Returns the messages to send to a client requesting the events from `start_from` onwards
+    /// held in the event log, or `None` if the log holds no event with that ID.
+    ///
+    /// Only the latest `client_buffer_length` events are replayed, any older ones are handled as
+    /// if the client had fallen too far behind to receive them.
+    pub(super) fn replay(
+        &self,
+        reader: &EventLogReader,
+        start_from: Id,
+        remote_address: &str,
+    ) -> Option<Vec<Result<ClientMessage, RecvError>>> {
+        let replay = reader.replay_from(start_from, self.client_buffer_length as usize)?;
+        let mut messages = Vec::with_capacity(replay.events.len() + 1);
+        if replay.skipped_count > 0 {
+            match self.policy {
+                SlowClientPolicy::Disconnect => {
+                    return Some(self.disconnect(remote_address, replay.skipped_count))
+                }
+                SlowClientPolicy::DropOldest => {
+                    // As when lagging, the skipped events are all counted as dropped.
+                    self.metrics.dropped_events.inc_by(replay.skipped_count);
+                    messages.push(Ok(ClientMessage::EventsDropped(replay.skipped_count)));
+                }
+            }
+        }
+        messages.extend(
+            replay
+                .events
+                .into_iter()
+                .map(|event| Ok(ClientMessage::Event(event))),
+        );
+        Some(messages)
+    }
+
+    fn disconnect(
+        &self,
+        remote_address: &str,
+        lagged_count: u64,
+    ) -> Vec<Result<ClientMessage, RecvError>> {
+        info!(
+            %remote_address,
+            %lagged_count,
+            "client lagged: dropping event stream connection to client",
+        );
+        self.metrics.slow_client_disconnections.inc();
+        vec![Err(RecvError::Lagged(lagged_count))]
+    }
+}
+
 /// Passed to the server whenever a new client subscribes.
 pub(super) struct NewSubscriberInfo {
     /// The event ID from which the stream should start for this client.
     pub(super) start_from: Option<Id>,
     /// A channel to send the initial events to the client's handler.  This will always send the
-    /// ApiVersion as the first event, and then any buffered events as indicated by `start_from`.
-    pub(super) initial_events_sender: mpsc::UnboundedSender<ServerSentEvent>,
+    /// ApiVersion as the first event, and then any buffered or logged events as indicated by
+    /// `start_from`.
+    pub(super) initial_events_sender: mpsc::UnboundedSender<Result<ClientMessage, RecvError>>,
+    /// The address of the client, for logging.
+    pub(super) remote_address: String,
 }
 
 /// A filter for event types a client has subscribed to receive.
@@ -274,14 +545,137 @@ pub(super) enum EventFilter {
     Fault,
     FinalitySignature,
     Step,
+    ContractEvent,
+    DeployExecutionStarted,
+    DeployExecutionFinished,
+}
+
+impl EventFilter {
+    /// Returns the filter for the event type with the given name, as used in the JSON-encoded
+    /// events, or `None` if there is no such event type.
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "BlockAdded" => Some(EventFilter::BlockAdded),
+            "DeployAccepted" => Some(EventFilter::DeployAccepted),
+            "DeployProcessed" => Some(EventFilter::DeployProcessed),
+            "DeployExpired" => Some(EventFilter::DeployExpired),
+            "Fault" => Some(EventFilter::Fault),
+            "FinalitySignature" => Some(EventFilter::FinalitySignature),
+            "Step" => Some(EventFilter::Step),
+            "ContractEvent" => Some(EventFilter::ContractEvent),
+            "DeployExecutionStarted" => Some(EventFilter::DeployExecutionStarted),
+            "DeployExecutionFinished" => Some(EventFilter::DeployExecutionFinished),
+            _ => None,
+        }
+    }
+}
+
+/// Optional restrictions specified by a client via the query string, further limiting the events
+/// sent to it beyond those implied by the URL path.
+///
+/// If any accounts or contracts are specified, deploy-related events are only sent if they relate
+/// to at least one of the given accounts or contracts.  `DeployExpired` events carry neither, so
+/// are not sent in this case.  Other events are unaffected by these restrictions.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub(super) struct SubscriberFilter {
+    /// The event types to send, or `None` for all those implied by the URL path.
+    event_types: Option<Vec<EventFilter>>,
+    /// The accounts to which deploy-related events must relate.
+    accounts: Option<HashSet<PublicKey>>,
+    /// The contracts to which deploy-related events must relate.
+    contracts: Option<HashSet<ContractHash>>,
+}
+
+impl SubscriberFilter {
+    /// Returns whether the event with the given data should be sent to the client.
+    pub(super) fn should_include(&self, data: &SseData) -> bool {
+        if matches!(data, SseData::ApiVersion(_) | SseData::Shutdown) {
+            return true;
+        }
+
+        if let Some(event_types) = &self.event_types {
+            if !data.should_include(event_types) {
+                return false;
+            }
+        }
+
+        if self.accounts.is_none() && self.contracts.is_none() {
+            return true;
+        }
+
+        match data {
+            SseData::DeployAccepted { deploy } => {
+                self.includes_account(deploy.header().account())
+                    || [deploy.session(), deploy.payment()].iter().any(|item| {
+                        match item.contract_identifier() {
+                            Some(ContractIdentifier::Hash(contract_hash)) => {
+                                self.includes_contract(&contract_hash)
+                            }
+                            _ => false,
+                        }
+                    })
+            }
+            SseData::DeployProcessed {
+                account,
+                execution_result,
+                ..
+            } => {
+                let effect = match execution_result.as_ref() {
+                    ExecutionResult::Success { effect, .. }
+                    | ExecutionResult::Failure { effect, .. } => effect,
+                };
+                self.includes_account(account)
+                    || self.contracts.iter().flatten().any(|contract_hash| {
+                        let key = Key::from(*contract_hash).to_formatted_string();
+                        effect
+                            .transforms
+                            .iter()
+                            .any(|transform_entry| transform_entry.key == key)
+                    })
+            }
+            SseData::ContractEvent {
+                account,
+                contract_event,
+                ..
+            } => {
+                self.includes_account(account)
+                    || match contract_event.emitter() {
+                        Key::Hash(addr) => self.includes_contract(&ContractHash::new(addr)),
+                        _ => false,
+                    }
+            }
+            SseData::DeployExecutionStarted { account, .. }
+            | SseData::DeployExecutionFinished { account, .. } => self.includes_account(account),
+            SseData::DeployExpired { .. } => false,
+            SseData::ApiVersion(_)
+            | SseData::BlockAdded { .. }
+            | SseData::Fault { .. }
+            | SseData::FinalitySignature { .. }
+            | SseData::Step { .. }
+            | SseData::Shutdown => true,
+        }
+    }
+
+    fn includes_account(&self, account: &PublicKey) -> bool {
+        self.accounts
+            .as_ref()
+            .map_or(false, |accounts| accounts.contains(account))
+    }
+
+    fn includes_contract(&self, contract_hash: &ContractHash) -> bool {
+        self.contracts
+            .as_ref()
+            .map_or(false, |contracts| contracts.contains(contract_hash))
+    }
 }
 
 /// Filters the `event`, mapping it to a warp event, or `None` if it should be filtered out.
 async fn filter_map_server_sent_event(
     event: &ServerSentEvent,
     event_filter: &[EventFilter],
+    subscriber_filter: &SubscriberFilter,
 ) -> Option<Result<WarpServerSentEvent, RecvError>> {
-    if !event.data.should_include(event_filter) {
+    if !event.data.should_include(event_filter) || !subscriber_filter.should_include(&event.data) {
         return None;
     }
 
@@ -302,7 +696,7 @@ async fn filter_map_server_sent_event(
         }
     };
 
-    match &event.data {
+    let maybe_warp_event = match &event.data {
         &SseData::ApiVersion { .. } => Some(Ok(WarpServerSentEvent::default()
             .json_data(&event.data)
             .unwrap_or_else(|error| {
@@ -315,7 +709,10 @@ async fn filter_map_server_sent_event(
         | &SseData::DeployExpired { .. }
         | &SseData::Fault { .. }
         | &SseData::Step { .. }
-        | &SseData::FinalitySignature(_)
+        | &SseData::FinalitySignature { .. }
+        | &SseData::ContractEvent { .. }
+        | &SseData::DeployExecutionStarted { .. }
+        | &SseData::DeployExecutionFinished { .. }
         | &SseData::Shutdown => Some(Ok(WarpServerSentEvent::default()
             .json_data(&event.data)
             .unwrap_or_else(|error| {
@@ -333,6 +730,14 @@ async fn filter_map_server_sent_event(
                 WarpServerSentEvent::default()
             })
             .id(event.id.unwrap().to_string()))),
+    };
+
+    match event.correlation_id {
+        Some(correlation_id) => maybe_warp_event.map(|result| {
+            result
+                .map(|warp_event| warp_event.comment(format!("correlation_id={}", correlation_id)))
+        }),
+        None => maybe_warp_event,
     }
 }
 
@@ -342,40 +747,93 @@ pub(super) fn get_filter(path_param: &str) -> Option<&'static [EventFilter]> {
         SSE_API_MAIN_PATH => Some(&MAIN_FILTER[..]),
         SSE_API_DEPLOYS_PATH => Some(&DEPLOYS_FILTER[..]),
         SSE_API_SIGNATURES_PATH => Some(&SIGNATURES_FILTER[..]),
+        SSE_API_CONTRACT_EVENTS_PATH => Some(&CONTRACT_EVENTS_FILTER[..]),
+        SSE_API_DEPLOY_EXECUTION_PATH => Some(&DEPLOY_EXECUTION_FILTER[..]),
         _ => None,
     }
 }
 
-/// Extracts the starting event ID from the provided query, or `None` if `query` is empty.
+/// Extracts the optional starting event ID and the subscriber's filter from the provided query.
 ///
-/// If `query` is not empty, returns a 422 response if `query` doesn't have exactly one entry,
-/// "starts_from" mapped to a value representing an event ID.
-fn parse_query(query: HashMap<String, String>) -> Result<Option<Id>, Response> {
-    if query.is_empty() {
-        return Ok(None);
+/// Returns a 422 response if `query` has any field other than "start_from" mapped to a value
+/// representing an event ID, "account" mapped to hex-encoded public keys, "contract" mapped to
+/// contract hashes or "event_type" mapped to names of event types included in `event_filter`.
+/// Fields other than "start_from" accept multiple comma-separated values.
+fn parse_query(
+    query: HashMap<String, String>,
+    event_filter: &[EventFilter],
+) -> Result<(Option<Id>, SubscriberFilter), Response> {
+    let mut start_from = None;
+    let mut subscriber_filter = SubscriberFilter::default();
+    for (field, value) in query {
+        match field.as_str() {
+            QUERY_FIELD => {
+                start_from = Some(value.parse::<Id>().map_err(|_| create_422())?);
+            }
+            ACCOUNT_QUERY_FIELD => {
+                let accounts =
+                    parse_query_values(&value, |account| PublicKey::from_hex(account).ok())?;
+                subscriber_filter.accounts = Some(accounts.into_iter().collect());
+            }
+            CONTRACT_QUERY_FIELD => {
+                let contracts = parse_query_values(&value, parse_contract_hash)?;
+                subscriber_filter.contracts = Some(contracts.into_iter().collect());
+            }
+            EVENT_TYPE_QUERY_FIELD => {
+                let event_types = parse_query_values(&value, |name| {
+                    EventFilter::from_name(name).filter(|filter| event_filter.contains(filter))
+                })?;
+                subscriber_filter.event_types = Some(event_types);
+            }
+            _ => return Err(create_422()),
+        }
     }
+    Ok((start_from, subscriber_filter))
+}
 
-    if query.len() > 1 {
-        return Err(create_422());
-    }
+/// Parses each of the comma-separated values in `value` using `parse`, returning a 422 response if
+/// any fails to parse.
+fn parse_query_values<T, F>(value: &str, parse: F) -> Result<Vec<T>, Response>
+where
+    F: Fn(&str) -> Option<T>,
+{
+    value
+        .split(QUERY_VALUE_SEPARATOR)
+        .map(|item| parse(item.trim()).ok_or_else(create_422))
+        .collect()
+}
 
-    match query
-        .get(QUERY_FIELD)
-        .and_then(|id_str| id_str.parse::<Id>().ok())
-    {
-        Some(id) => Ok(Some(id)),
-        None => Err(create_422()),
+/// Parses a contract hash given either in its formatted form "contract-<HEX>" or as plain hex.
+fn parse_contract_hash(input: &str) -> Option<ContractHash> {
+    if let Ok(contract_hash) = ContractHash::from_formatted_str(input) {
+        return Some(contract_hash);
     }
+    let bytes = base16::decode(input).ok()?;
+    HashAddr::try_from(bytes.as_slice())
+        .ok()
+        .map(ContractHash::new)
+}
+
+/// Returns the ID of the first event to send to a client which reconnected having last received
+/// the event with ID given in the "Last-Event-ID" header.
+///
+/// An unparseable header is ignored, as such a client cannot have received the ID from this server.
+fn parse_last_event_id(maybe_last_event_id: Option<String>) -> Option<Id> {
+    let last_event_id = maybe_last_event_id?.trim().parse::<Id>().ok()?;
+    Some(last_event_id.wrapping_add(1))
 }
 
 /// Creates a 404 response with a useful error message in the body.
 fn create_404() -> Response {
     let mut response = Response::new(Body::from(format!(
-        "invalid path: expected '/{root}/{main}', '/{root}/{deploys}' or '/{root}/{sigs}'\n",
+        "invalid path: expected '/{root}/{main}', '/{root}/{deploys}', '/{root}/{sigs}', \
+        '/{root}/{contract_events}' or '/{root}/{deploy_execution}'\n",
         root = SSE_API_ROOT_PATH,
         main = SSE_API_MAIN_PATH,
         deploys = SSE_API_DEPLOYS_PATH,
-        sigs = SSE_API_SIGNATURES_PATH
+        sigs = SSE_API_SIGNATURES_PATH,
+        contract_events = SSE_API_CONTRACT_EVENTS_PATH,
+        deploy_execution = SSE_API_DEPLOY_EXECUTION_PATH
     )));
     *response.status_mut() = StatusCode::NOT_FOUND;
     response
@@ -385,8 +843,9 @@ fn create_404() -> Response {
 /// string.
 fn create_422() -> Response {
     let mut response = Response::new(Body::from(format!(
-        "invalid query: expected single field '{}=<EVENT ID>'\n",
-        QUERY_FIELD
+        "invalid query: expected optional fields '{}=<EVENT ID>', '{}=<PUBLIC KEY>[,...]', \
+        '{}=<CONTRACT HASH>[,...]' and '{}=<EVENT TYPE>[,...]'\n",
+        QUERY_FIELD, ACCOUNT_QUERY_FIELD, CONTRACT_QUERY_FIELD, EVENT_TYPE_QUERY_FIELD
     )));
     *response.status_mut() = StatusCode::UNPROCESSABLE_ENTITY;
     response
@@ -409,7 +868,11 @@ pub(super) struct ChannelsAndFilter {
 impl ChannelsAndFilter {
     /// Creates the message-passing channels required to run the event-stream server and the warp
     /// filter for the event-stream server.
-    pub(super) fn new(broadcast_channel_size: usize, max_concurrent_subscribers: u32) -> Self {
+    pub(super) fn new(
+        broadcast_channel_size: usize,
+        max_concurrent_subscribers: u32,
+        slow_client_handler: SlowClientHandler,
+    ) -> Self {
         // Create a channel to broadcast new events to all subscribed clients' streams.
         let (event_broadcaster, _) = broadcast::channel(broadcast_channel_size);
         let cloned_broadcaster = event_broadcaster.clone();
@@ -420,6 +883,7 @@ impl ChannelsAndFilter {
 
         let serve = move |path_param: String,
                           query: HashMap<String, String>,
+                          maybe_last_event_id: Option<String>,
                           maybe_remote_address: Option<SocketAddr>| {
             let remote_address = match maybe_remote_address {
                 Some(address) => address.to_string(),
@@ -442,8 +906,12 @@ impl ChannelsAndFilter {
                 None => return create_404(),
             };
 
-            let start_from = match parse_query(query) {
-                Ok(maybe_id) => maybe_id,
+            // A "start_from" query takes precedence over a "Last-Event-ID" header.
+            let (start_from, subscriber_filter) = match parse_query(query, event_filter) {
+                Ok((Some(id), subscriber_filter)) => (Some(id), subscriber_filter),
+                Ok((None, subscriber_filter)) => {
+                    (parse_last_event_id(maybe_last_event_id), subscriber_filter)
+                }
                 Err(error_response) => return error_response,
             };
 
@@ -455,6 +923,7 @@ impl ChannelsAndFilter {
             let new_subscriber_info = NewSubscriberInfo {
                 start_from,
                 initial_events_sender,
+                remote_address: remote_address.clone(),
             };
             if new_subscriber_info_sender
                 .send(new_subscriber_info)
@@ -470,6 +939,8 @@ impl ChannelsAndFilter {
                 initial_events_receiver,
                 ongoing_events_receiver,
                 event_filter,
+                subscriber_filter,
+                slow_client_handler.clone(),
                 remote_address,
             )))
             .into_response()
@@ -480,6 +951,7 @@ impl ChannelsAndFilter {
             .and(path::param::<String>())
             .and(path::end())
             .and(warp::query())
+            .and(warp::header::optional::<String>(LAST_EVENT_ID_HEADER))
             .and(addr::remote())
             .map(serve)
             .or_else(|_| async move { Ok::<_, Rejection>((create_404(),)) })
@@ -505,23 +977,36 @@ impl ChannelsAndFilter {
 /// variant via the channel).  This channel will receive all SSEs created from the moment the client
 /// subscribed to the server's event stream.
 ///
-/// It also takes an `EventFilter` which causes events to which the client didn't subscribe to be
-/// skipped.
+/// It also takes an `EventFilter` and a `SubscriberFilter` which cause events to which the client
+/// didn't subscribe to be skipped, and a `SlowClientHandler` which disconnects the client or drops
+/// events for it if it falls too far behind.
 fn stream_to_client(
-    initial_events: mpsc::UnboundedReceiver<ServerSentEvent>,
+    initial_events: mpsc::UnboundedReceiver<Result<ClientMessage, RecvError>>,
     ongoing_events: broadcast::Receiver<BroadcastChannelMessage>,
     event_filter: &'static [EventFilter],
+    subscriber_filter: SubscriberFilter,
+    slow_client_handler: SlowClientHandler,
     remote_address: String,
 ) -> impl Stream<Item = Result<WarpServerSentEvent, RecvError>> + 'static {
+    let subscriber_filter = Arc::new(subscriber_filter);
+    let cloned_subscriber_filter = Arc::clone(&subscriber_filter);
+
     // Keep a record of the IDs of the events delivered via the `initial_events` receiver.
     let initial_stream_ids = Arc::new(RwLock::new(HashSet::new()));
     let cloned_initial_ids = Arc::clone(&initial_stream_ids);
 
+    // Keep a count of the events dropped for this client since it was last sent an event.
+    let dropped_count = Arc::new(AtomicU64::new(0));
+
     // Map the events arriving after the initial stream to the correct error type, filtering out any
-    // that have already been sent in the initial stream.
+    // that have already been sent in the initial stream, and applying the slow client policy if
+    // the client has fallen too far behind.
     let ongoing_stream = BroadcastStream::new(ongoing_events)
-        .filter_map(move |result| {
+        .then(move |result| {
             let cloned_initial_ids = Arc::clone(&cloned_initial_ids);
+            let subscriber_filter = Arc::clone(&cloned_subscriber_filter);
+            let slow_client_handler = slow_client_handler.clone();
+            let dropped_count = Arc::clone(&dropped_count);
             let remote_address = remote_address.clone();
             async move {
                 match result {
@@ -529,39 +1014,54 @@ fn stream_to_client(
                         if let Some(id) = event.id {
                             if cloned_initial_ids.read().unwrap().contains(&id) {
                                 debug!(event_id=%id, "skipped duplicate event");
-                                return None;
+                                return vec![];
                             }
                         }
-                        Some(Ok(event))
-                    }
-                    Ok(BroadcastChannelMessage::Shutdown) => Some(Err(RecvError::Closed)),
-                    Err(BroadcastStreamRecvError::Lagged(lagged_count)) => {
-                        info!(
-                            %remote_address,
-                            %lagged_count,
-                            "client lagged: dropping event stream connection to client",
-                        );
-                        Some(Err(RecvError::Lagged(lagged_count)))
+                        let is_wanted = event.data.should_include(event_filter)
+                            && subscriber_filter.should_include(&event.data);
+                        slow_client_handler.handle_event(
+                            event,
+                            is_wanted,
+                            &dropped_count,
+                            &remote_address,
+                        )
                     }
+                    Ok(BroadcastChannelMessage::Shutdown) => vec![Err(RecvError::Closed)],
+                    Err(BroadcastStreamRecvError::Lagged(lagged_count)) => slow_client_handler
+                        .handle_lagged(lagged_count, &dropped_count, &remote_address),
                 }
             }
         })
+        .flat_map(stream::iter)
         .take_while(|result| future::ready(!matches!(result, Err(RecvError::Closed))));
 
     // Serve the initial events followed by the ongoing ones, filtering as dictated by the
-    // `event_filter`.
+    // `event_filter` and `subscriber_filter`.
     UnboundedReceiverStream::new(initial_events)
-        .map(move |event| {
-            if let Some(id) = event.id {
-                let _ = initial_stream_ids.write().unwrap().insert(id);
+        .map(move |result| {
+            if let Ok(ClientMessage::Event(ServerSentEvent { id: Some(id), .. })) = &result {
+                let _ = initial_stream_ids.write().unwrap().insert(*id);
             }
-            Ok(event)
+            result
         })
         .chain(ongoing_stream)
-        .filter_map(move |result| async move {
-            match result {
-                Ok(event) => filter_map_server_sent_event(&event, event_filter).await,
-                Err(error) => Some(Err(error)),
+        .filter_map(move |result| {
+            let subscriber_filter = Arc::clone(&subscriber_filter);
+            async move {
+                match result {
+                    Ok(ClientMessage::Event(event)) => {
+                        filter_map_server_sent_event(&event, event_filter, &subscriber_filter).await
+                    }
+                    Ok(ClientMessage::EventsDropped(events_dropped)) => {
+                        Some(Ok(WarpServerSentEvent::default()
+                            .json_data(&EventsDropped { events_dropped })
+                            .unwrap_or_else(|error| {
+                                warn!(%error, "failed to jsonify sse event");
+                                WarpServerSentEvent::default()
+                            })))
+                    }
+                    Err(error) => Some(Err(error)),
+                }
             }
         })
 }
@@ -570,14 +1070,31 @@ fn stream_to_client(
 mod tests {
     use std::iter;
 
+    use casper_execution_engine::core::engine_state::executable_deploy_item::ExecutableDeployItem;
     use casper_types::testing::TestRng;
+    use prometheus::Registry;
 
     use super::*;
     use crate::logging;
 
+    fn new_slow_client_handler(
+        policy: SlowClientPolicy,
+        client_buffer_length: u32,
+    ) -> SlowClientHandler {
+        let config = Config {
+            client_buffer_length,
+            slow_client_policy: policy,
+            ..Config::new()
+        };
+        let metrics = Metrics::new(&Registry::new()).unwrap();
+        SlowClientHandler::new(&config, Arc::new(metrics))
+    }
+
     async fn should_filter_out(event: &ServerSentEvent, filter: &'static [EventFilter]) {
         assert!(
-            filter_map_server_sent_event(event, filter).await.is_none(),
+            filter_map_server_sent_event(event, filter, &SubscriberFilter::default())
+                .await
+                .is_none(),
             "should filter out {:?} with {:?}",
             event,
             filter
@@ -586,7 +1103,9 @@ mod tests {
 
     async fn should_not_filter_out(event: &ServerSentEvent, filter: &'static [EventFilter]) {
         assert!(
-            filter_map_server_sent_event(event, filter).await.is_some(),
+            filter_map_server_sent_event(event, filter, &SubscriberFilter::default())
+                .await
+                .is_some(),
             "should not filter out {:?} with {:?}",
             event,
             filter
@@ -603,41 +1122,65 @@ mod tests {
         let api_version = ServerSentEvent {
             id: None,
             data: SseData::random_api_version(&mut rng),
+            correlation_id: None,
         };
         let block_added = ServerSentEvent {
             id: Some(rng.gen()),
             data: SseData::random_block_added(&mut rng),
+            correlation_id: None,
         };
         let (sse_data, deploy) = SseData::random_deploy_accepted(&mut rng);
         let deploy_accepted = ServerSentEvent {
             id: Some(rng.gen()),
             data: sse_data,
+            correlation_id: None,
         };
         let mut deploys = HashMap::new();
         let _ = deploys.insert(*deploy.id(), deploy);
         let deploy_processed = ServerSentEvent {
             id: Some(rng.gen()),
             data: SseData::random_deploy_processed(&mut rng),
+            correlation_id: None,
         };
         let deploy_expired = ServerSentEvent {
             id: Some(rng.gen()),
             data: SseData::random_deploy_expired(&mut rng),
+            correlation_id: None,
         };
         let fault = ServerSentEvent {
             id: Some(rng.gen()),
             data: SseData::random_fault(&mut rng),
+            correlation_id: None,
         };
         let finality_signature = ServerSentEvent {
             id: Some(rng.gen()),
             data: SseData::random_finality_signature(&mut rng),
+            correlation_id: None,
         };
         let step = ServerSentEvent {
             id: Some(rng.gen()),
             data: SseData::random_step(&mut rng),
+            correlation_id: None,
+        };
+        let contract_event = ServerSentEvent {
+            id: Some(rng.gen()),
+            data: SseData::random_contract_event(&mut rng),
+            correlation_id: None,
+        };
+        let deploy_execution_started = ServerSentEvent {
+            id: Some(rng.gen()),
+            data: SseData::random_deploy_execution_started(&mut rng),
+            correlation_id: None,
+        };
+        let deploy_execution_finished = ServerSentEvent {
+            id: Some(rng.gen()),
+            data: SseData::random_deploy_execution_finished(&mut rng),
+            correlation_id: None,
         };
         let shutdown = ServerSentEvent {
             id: Some(rng.gen()),
             data: SseData::Shutdown,
+            correlation_id: None,
         };
 
         // `EventFilter::Main` should only filter out `DeployAccepted`s and `FinalitySignature`s.
@@ -651,6 +1194,9 @@ mod tests {
 
         should_filter_out(&deploy_accepted, &MAIN_FILTER[..]).await;
         should_filter_out(&finality_signature, &MAIN_FILTER[..]).await;
+        should_filter_out(&contract_event, &MAIN_FILTER[..]).await;
+        should_filter_out(&deploy_execution_started, &MAIN_FILTER[..]).await;
+        should_filter_out(&deploy_execution_finished, &MAIN_FILTER[..]).await;
 
         // `EventFilter::DeployAccepted` should filter out everything except `ApiVersion`s and
         // `DeployAccepted`s.
@@ -664,6 +1210,9 @@ mod tests {
         should_filter_out(&fault, &DEPLOYS_FILTER[..]).await;
         should_filter_out(&finality_signature, &DEPLOYS_FILTER[..]).await;
         should_filter_out(&step, &DEPLOYS_FILTER[..]).await;
+        should_filter_out(&contract_event, &DEPLOYS_FILTER[..]).await;
+        should_filter_out(&deploy_execution_started, &DEPLOYS_FILTER[..]).await;
+        should_filter_out(&deploy_execution_finished, &DEPLOYS_FILTER[..]).await;
 
         // `EventFilter::Signatures` should filter out everything except `ApiVersion`s and
         // `FinalitySignature`s.
@@ -677,6 +1226,41 @@ mod tests {
         should_filter_out(&deploy_expired, &SIGNATURES_FILTER[..]).await;
         should_filter_out(&fault, &SIGNATURES_FILTER[..]).await;
         should_filter_out(&step, &SIGNATURES_FILTER[..]).await;
+        should_filter_out(&contract_event, &SIGNATURES_FILTER[..]).await;
+        should_filter_out(&deploy_execution_started, &SIGNATURES_FILTER[..]).await;
+        should_filter_out(&deploy_execution_finished, &SIGNATURES_FILTER[..]).await;
+
+        // `EventFilter::ContractEvent` should filter out everything except `ApiVersion`s and
+        // `ContractEvent`s.
+        should_not_filter_out(&api_version, &CONTRACT_EVENTS_FILTER[..]).await;
+        should_not_filter_out(&contract_event, &CONTRACT_EVENTS_FILTER[..]).await;
+        should_not_filter_out(&shutdown, &CONTRACT_EVENTS_FILTER[..]).await;
+
+        should_filter_out(&block_added, &CONTRACT_EVENTS_FILTER[..]).await;
+        should_filter_out(&deploy_accepted, &CONTRACT_EVENTS_FILTER[..]).await;
+        should_filter_out(&deploy_processed, &CONTRACT_EVENTS_FILTER[..]).await;
+        should_filter_out(&deploy_expired, &CONTRACT_EVENTS_FILTER[..]).await;
+        should_filter_out(&fault, &CONTRACT_EVENTS_FILTER[..]).await;
+        should_filter_out(&finality_signature, &CONTRACT_EVENTS_FILTER[..]).await;
+        should_filter_out(&step, &CONTRACT_EVENTS_FILTER[..]).await;
+        should_filter_out(&deploy_execution_started, &CONTRACT_EVENTS_FILTER[..]).await;
+        should_filter_out(&deploy_execution_finished, &CONTRACT_EVENTS_FILTER[..]).await;
+
+        // `EventFilter::DeployExecutionStarted` and `EventFilter::DeployExecutionFinished` should
+        // filter out everything except `ApiVersion`s and the two deploy execution events.
+        should_not_filter_out(&api_version, &DEPLOY_EXECUTION_FILTER[..]).await;
+        should_not_filter_out(&deploy_execution_started, &DEPLOY_EXECUTION_FILTER[..]).await;
+        should_not_filter_out(&deploy_execution_finished, &DEPLOY_EXECUTION_FILTER[..]).await;
+        should_not_filter_out(&shutdown, &DEPLOY_EXECUTION_FILTER[..]).await;
+
+        should_filter_out(&block_added, &DEPLOY_EXECUTION_FILTER[..]).await;
+        should_filter_out(&deploy_accepted, &DEPLOY_EXECUTION_FILTER[..]).await;
+        should_filter_out(&deploy_processed, &DEPLOY_EXECUTION_FILTER[..]).await;
+        should_filter_out(&deploy_expired, &DEPLOY_EXECUTION_FILTER[..]).await;
+        should_filter_out(&fault, &DEPLOY_EXECUTION_FILTER[..]).await;
+        should_filter_out(&finality_signature, &DEPLOY_EXECUTION_FILTER[..]).await;
+        should_filter_out(&step, &DEPLOY_EXECUTION_FILTER[..]).await;
+        should_filter_out(&contract_event, &DEPLOY_EXECUTION_FILTER[..]).await;
     }
 
     /// This test checks that events with incorrect IDs (i.e. no types have an ID except for
@@ -689,41 +1273,50 @@ mod tests {
         let malformed_api_version = ServerSentEvent {
             id: Some(rng.gen()),
             data: SseData::random_api_version(&mut rng),
+            correlation_id: None,
         };
         let malformed_block_added = ServerSentEvent {
             id: None,
             data: SseData::random_block_added(&mut rng),
+            correlation_id: None,
         };
         let (sse_data, deploy) = SseData::random_deploy_accepted(&mut rng);
         let malformed_deploy_accepted = ServerSentEvent {
             id: None,
             data: sse_data,
+            correlation_id: None,
         };
         let mut deploys = HashMap::new();
         let _ = deploys.insert(*deploy.id(), deploy);
         let malformed_deploy_processed = ServerSentEvent {
             id: None,
             data: SseData::random_deploy_processed(&mut rng),
+            correlation_id: None,
         };
         let malformed_deploy_expired = ServerSentEvent {
             id: None,
             data: SseData::random_deploy_expired(&mut rng),
+            correlation_id: None,
         };
         let malformed_fault = ServerSentEvent {
             id: None,
             data: SseData::random_fault(&mut rng),
+            correlation_id: None,
         };
         let malformed_finality_signature = ServerSentEvent {
             id: None,
             data: SseData::random_finality_signature(&mut rng),
+            correlation_id: None,
         };
         let malformed_step = ServerSentEvent {
             id: None,
             data: SseData::random_step(&mut rng),
+            correlation_id: None,
         };
         let malformed_shutdown = ServerSentEvent {
             id: None,
             data: SseData::Shutdown,
+            correlation_id: None,
         };
 
         for filter in &[
@@ -767,7 +1360,11 @@ mod tests {
                         SSE_API_SIGNATURES_PATH => SseData::random_finality_signature(rng),
                         _ => unreachable!(),
                     };
-                    ServerSentEvent { id: Some(id), data }
+                    ServerSentEvent {
+                        id: Some(id),
+                        data,
+                        correlation_id: None,
+                    }
                 })
                 .collect()
         }
@@ -840,7 +1437,9 @@ mod tests {
 
             // Send all the events.
             for event in initial_events.iter().cloned() {
-                initial_events_sender.send(event).unwrap();
+                initial_events_sender
+                    .send(Ok(ClientMessage::Event(event)))
+                    .unwrap();
             }
             for event in ongoing_events.iter().cloned() {
                 let _ = ongoing_events_sender
@@ -851,11 +1450,19 @@ mod tests {
             drop(initial_events_sender);
             drop(ongoing_events_sender);
 
+            let slow_client_handler = new_slow_client_handler(
+                SlowClientPolicy::Disconnect,
+                (NUM_INITIAL_EVENTS + NUM_ONGOING_EVENTS) as u32,
+            );
+            slow_client_handler.set_latest_event_id(ongoing_events.last().unwrap().id.unwrap());
+
             // Collect the events emitted by `stream_to_client()` - should not contain duplicates.
             let received_events: Vec<Result<WarpServerSentEvent, RecvError>> = stream_to_client(
                 initial_events_receiver,
                 ongoing_events_receiver,
                 get_filter(path_filter).unwrap(),
+                SubscriberFilter::default(),
+                slow_client_handler,
                 "127.0.0.1:3456".to_string(),
             )
             .collect()
@@ -921,4 +1528,208 @@ mod tests {
     async fn should_filter_duplicate_signature_events() {
         should_filter_duplicate_events(SSE_API_SIGNATURES_PATH).await
     }
+
+    /// This test checks that deploy-related events are filtered according to the accounts,
+    /// contracts and event types requested by the client, while other events are unaffected.
+    #[tokio::test]
+    async fn should_filter_by_subscriber_filter() {
+        let _ = logging::init();
+        let mut rng = crate::new_rng();
+
+        let deploy = Deploy::builder()
+            .seed(rng.gen())
+            .timestamp(Timestamp::now())
+            .session(ExecutableDeployItem::StoredContractByHash {
+                hash: [19; 32].into(),
+                entry_point: "non-existent-entry-point".to_string(),
+                args: Default::default(),
+            })
+            .build();
+        let account = deploy.header().account().clone();
+        let deploy_accepted = ServerSentEvent {
+            id: Some(rng.gen()),
+            data: SseData::DeployAccepted {
+                deploy: Arc::new(deploy),
+            },
+            correlation_id: None,
+        };
+        let deploy_processed = ServerSentEvent {
+            id: Some(rng.gen()),
+            data: SseData::random_deploy_processed(&mut rng),
+            correlation_id: None,
+        };
+        let deploy_expired = ServerSentEvent {
+            id: Some(rng.gen()),
+            data: SseData::random_deploy_expired(&mut rng),
+            correlation_id: None,
+        };
+        let block_added = ServerSentEvent {
+            id: Some(rng.gen()),
+            data: SseData::random_block_added(&mut rng),
+            correlation_id: None,
+        };
+        // Emitted by the contract with hash `[2; 32]`.
+        let contract_event = ServerSentEvent {
+            id: Some(rng.gen()),
+            data: SseData::random_contract_event(&mut rng),
+            correlation_id: None,
+        };
+        let api_version = ServerSentEvent::initial_event(ProtocolVersion::V1_0_0);
+
+        let includes = |event: &ServerSentEvent, query: &[(&str, String)]| {
+            let query = query
+                .iter()
+                .map(|(field, value)| (field.to_string(), value.clone()))
+                .collect();
+            let (_, subscriber_filter) = parse_query(query, &ALL_FILTERS).unwrap();
+            subscriber_filter.should_include(&event.data)
+        };
+        const ALL_FILTERS: [EventFilter; 8] = [
+            EventFilter::BlockAdded,
+            EventFilter::DeployAccepted,
+            EventFilter::DeployProcessed,
+            EventFilter::DeployExpired,
+            EventFilter::Fault,
+            EventFilter::FinalitySignature,
+            EventFilter::Step,
+            EventFilter::ContractEvent,
+        ];
+
+        let by_account = [(ACCOUNT_QUERY_FIELD, account.to_hex())];
+        assert!(includes(&deploy_accepted, &by_account));
+        assert!(!includes(&deploy_processed, &by_account));
+        assert!(!includes(&deploy_expired, &by_account));
+        assert!(includes(&block_added, &by_account));
+        assert!(includes(&api_version, &by_account));
+
+        let by_contract = [(
+            CONTRACT_QUERY_FIELD,
+            format!(
+                "{},{}",
+                base16::encode_lower(&[19; 32]),
+                ContractHash::default().to_formatted_string()
+            ),
+        )];
+        assert!(includes(&deploy_accepted, &by_contract));
+        assert!(!includes(&deploy_processed, &by_contract));
+        assert!(!includes(&contract_event, &by_contract));
+        assert!(includes(&block_added, &by_contract));
+
+        let by_emitter = [(CONTRACT_QUERY_FIELD, base16::encode_lower(&[2; 32]))];
+        assert!(includes(&contract_event, &by_emitter));
+        assert!(!includes(&deploy_accepted, &by_emitter));
+
+        let by_other_account = [(ACCOUNT_QUERY_FIELD, PublicKey::random(&mut rng).to_hex())];
+        assert!(!includes(&deploy_accepted, &by_other_account));
+
+        let by_event_type = [(EVENT_TYPE_QUERY_FIELD, "DeployProcessed,Fault".to_string())];
+        assert!(includes(&deploy_processed, &by_event_type));
+        assert!(!includes(&deploy_accepted, &by_event_type));
+        assert!(!includes(&block_added, &by_event_type));
+        assert!(includes(&api_version, &by_event_type));
+    }
+
+    #[test]
+    fn should_reject_invalid_subscriber_filter_query() {
+        let parse = |field: &str, value: &str| {
+            let query = iter::once((field.to_string(), value.to_string())).collect();
+            parse_query(query, &DEPLOYS_FILTER[..])
+        };
+
+        assert!(parse(ACCOUNT_QUERY_FIELD, "not-a-key").is_err());
+        assert!(parse(CONTRACT_QUERY_FIELD, "contract-00").is_err());
+        assert!(parse(EVENT_TYPE_QUERY_FIELD, "NotAnEventType").is_err());
+        // Event types not served via the requested path are rejected.
+        assert!(parse(EVENT_TYPE_QUERY_FIELD, "BlockAdded").is_err());
+        assert!(parse(EVENT_TYPE_QUERY_FIELD, "DeployAccepted").is_ok());
+        assert!(parse("unknown", "0").is_err());
+    }
+
+    #[test]
+    fn should_start_after_last_event_id() {
+        assert_eq!(parse_last_event_id(Some("41".to_string())), Some(42));
+        assert_eq!(parse_last_event_id(Some(Id::MAX.to_string())), Some(0));
+        assert_eq!(parse_last_event_id(Some("not an ID".to_string())), None);
+        assert_eq!(parse_last_event_id(None), None);
+    }
+
+    /// Sends `NUM_EVENTS` `BlockAdded` events with consecutive IDs via the ongoing stream to a
+    /// client with a buffer length of `CLIENT_BUFFER_LENGTH` and the given slow client policy,
+    /// returning the string representations of the SSEs emitted by `stream_to_client()`.
+    async fn stream_to_slow_client(policy: SlowClientPolicy) -> Vec<Result<String, RecvError>> {
+        const NUM_EVENTS: Id = 10;
+        const CLIENT_BUFFER_LENGTH: u32 = 3;
+
+        let mut rng = crate::new_rng();
+
+        let (initial_events_sender, initial_events_receiver) = mpsc::unbounded_channel();
+        let (ongoing_events_sender, ongoing_events_receiver) =
+            broadcast::channel(NUM_EVENTS as usize);
+        for id in 0..NUM_EVENTS {
+            let event = ServerSentEvent {
+                id: Some(id),
+                data: SseData::random_block_added(&mut rng),
+                correlation_id: None,
+            };
+            let _ = ongoing_events_sender
+                .send(BroadcastChannelMessage::ServerSentEvent(event))
+                .unwrap();
+        }
+        drop(initial_events_sender);
+        drop(ongoing_events_sender);
+
+        let slow_client_handler = new_slow_client_handler(policy, CLIENT_BUFFER_LENGTH);
+        slow_client_handler.set_latest_event_id(NUM_EVENTS - 1);
+
+        stream_to_client(
+            initial_events_receiver,
+            ongoing_events_receiver,
+            &MAIN_FILTER,
+            SubscriberFilter::default(),
+            slow_client_handler,
+            "127.0.0.1:3456".to_string(),
+        )
+        .map(|result| result.map(|event| event.to_string()))
+        .collect()
+        .await
+    }
+
+    /// This test checks that a client which has fallen too far behind is disconnected when the
+    /// slow client policy is `Disconnect`.
+    #[tokio::test]
+    async fn should_disconnect_slow_client() {
+        let _ = logging::init();
+
+        let received_events = stream_to_slow_client(SlowClientPolicy::Disconnect).await;
+
+        // The first event is 9 behind the latest, so the client should be disconnected immediately.
+        assert_eq!(received_events.len(), 1);
+        assert!(matches!(received_events[0], Err(RecvError::Lagged(9))));
+    }
+
+    /// This test checks that events are dropped for a client which has fallen too far behind, and
+    /// that it is sent a gap marker in their place, when the slow client policy is `DropOldest`.
+    #[tokio::test]
+    async fn should_drop_oldest_events_for_slow_client() {
+        let _ = logging::init();
+
+        let received_events = stream_to_slow_client(SlowClientPolicy::DropOldest).await;
+
+        // Events 0 to 5 are more than 3 behind the latest, so should be replaced by a single gap
+        // marker followed by events 6 to 9.
+        assert_eq!(received_events.len(), 5);
+        let gap_marker = serde_json::to_string(&EventsDropped { events_dropped: 6 }).unwrap();
+        assert_eq!(
+            received_events[0].as_ref().unwrap().trim(),
+            format!("data:{}", gap_marker)
+        );
+        for (index, received_event) in received_events.iter().skip(1).enumerate() {
+            let expected_id = format!("id:{}", index + 6);
+            assert!(received_event
+                .as_ref()
+                .unwrap()
+                .trim()
+                .ends_with(&expected_id));
+        }
+    }
 }
@@ -26,7 +26,9 @@ use tracing::debug;
 use super::*;
 use crate::{logging, testing::assert_schema};
 use sse_server::{
-    DeployAccepted, Id, QUERY_FIELD, SSE_API_DEPLOYS_PATH as DEPLOYS_PATH,
+    DeployAccepted, Id, ACCOUNT_QUERY_FIELD, CONTRACT_QUERY_FIELD, EVENT_TYPE_QUERY_FIELD,
+    QUERY_FIELD, SSE_API_CONTRACT_EVENTS_PATH as CONTRACT_EVENTS_PATH,
+    SSE_API_DEPLOYS_PATH as DEPLOYS_PATH, SSE_API_DEPLOY_EXECUTION_PATH as DEPLOY_EXECUTION_PATH,
     SSE_API_MAIN_PATH as MAIN_PATH, SSE_API_ROOT_PATH as ROOT_PATH,
     SSE_API_SIGNATURES_PATH as SIGS_PATH,
 };
@@ -81,6 +83,8 @@ struct ServerBehavior {
     /// If `Some`, sets the `max_concurrent_subscribers` server config value, otherwise uses the
     /// config default.
     max_concurrent_subscribers: Option<u32>,
+    /// Whether the server should persist events to its event log.
+    enable_event_log: bool,
     clients: Vec<ClientSyncBehavior>,
 }
 
@@ -93,6 +97,7 @@ impl ServerBehavior {
             has_delay_between_events: true,
             repeat_events: false,
             max_concurrent_subscribers: None,
+            enable_event_log: false,
             clients: Vec::new(),
         }
     }
@@ -106,6 +111,7 @@ impl ServerBehavior {
             has_delay_between_events: false,
             repeat_events: true,
             max_concurrent_subscribers: None,
+            enable_event_log: false,
             clients: Vec::new(),
         }
     }
@@ -198,7 +204,7 @@ struct TestFixture {
 impl TestFixture {
     /// Constructs a new `TestFixture` including `EVENT_COUNT` random events ready to be served.
     fn new(rng: &mut TestRng) -> Self {
-        const DISTINCT_EVENTS_COUNT: u32 = 7;
+        const DISTINCT_EVENTS_COUNT: u32 = 10;
 
         let _ = logging::init();
         let storage_dir = tempfile::tempdir().unwrap();
@@ -219,6 +225,9 @@ impl TestFixture {
                 4 => SseData::random_fault(rng),
                 5 => SseData::random_step(rng),
                 6 => SseData::random_finality_signature(rng),
+                7 => SseData::random_contract_event(rng),
+                8 => SseData::random_deploy_execution_started(rng),
+                9 => SseData::random_deploy_execution_finished(rng),
                 _ => unreachable!(),
             })
             .collect();
@@ -261,12 +270,18 @@ impl TestFixture {
             max_concurrent_subscribers: server_behavior
                 .max_concurrent_subscribers
                 .unwrap_or(Config::default().max_concurrent_subscribers),
+            event_log_max_size_bytes: if server_behavior.enable_event_log {
+                Config::default().event_log_max_size_bytes
+            } else {
+                0
+            },
             ..Default::default()
         };
         let mut server = EventStreamServer::new(
             config,
             self.storage_dir.path().to_path_buf(),
             self.protocol_version,
+            &Registry::new(),
         )
         .unwrap();
         assert!(server.inner.is_some());
@@ -608,6 +623,16 @@ async fn should_serve_signature_events_with_no_query() {
     should_serve_events_with_no_query(SIGS_PATH).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn should_serve_contract_events_with_no_query() {
+    should_serve_events_with_no_query(CONTRACT_EVENTS_PATH).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn should_serve_deploy_execution_events_with_no_query() {
+    should_serve_events_with_no_query(DEPLOY_EXECUTION_PATH).await;
+}
+
 /// Client setup:
 ///   * `<IP:port>/events/<path>?start_from=25`
 ///   * connected just before event ID 50
@@ -689,6 +714,49 @@ async fn should_serve_remaining_signature_events_with_query() {
     should_serve_remaining_events_with_query(SIGS_PATH).await;
 }
 
+/// Client setup:
+///   * `<IP:port>/events/<path>?start_from=0`
+///   * connected just before event ID 75
+///   * server has its event log enabled
+///
+/// Expected to receive main, deploy-accepted or signature events (depending on `path`) from ID 0
+/// onwards, as events 0 to 24 should have been purged from the server buffer, but not from its
+/// event log.
+async fn should_serve_purged_events_from_event_log(path: &str) {
+    let mut rng = crate::new_rng();
+    let mut fixture = TestFixture::new(&mut rng);
+
+    let connect_at_event_id = BUFFER_LENGTH * 3 / 2;
+    let start_from_event_id = 0;
+
+    let mut server_behavior = ServerBehavior::new();
+    server_behavior.enable_event_log = true;
+    let barrier = server_behavior.add_client_sync_before_event(connect_at_event_id);
+    let server_address = fixture.run_server(server_behavior).await;
+
+    let url = url(server_address, path, Some(start_from_event_id));
+    let (expected_events, final_id) = fixture.filtered_events(path, start_from_event_id);
+    let received_events = subscribe(&url, barrier, final_id, "client").await.unwrap();
+    fixture.stop_server().await;
+
+    assert_eq!(received_events, expected_events);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn should_serve_purged_main_events_from_event_log() {
+    should_serve_purged_events_from_event_log(MAIN_PATH).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn should_serve_purged_deploy_accepted_events_from_event_log() {
+    should_serve_purged_events_from_event_log(DEPLOYS_PATH).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn should_serve_purged_signature_events_from_event_log() {
+    should_serve_purged_events_from_event_log(SIGS_PATH).await;
+}
+
 /// Client setup:
 ///   * `<IP:port>/events/<path>?start_from=25`
 ///   * connected before first event
@@ -899,8 +967,8 @@ async fn should_handle_bad_url_path() {
     ];
 
     let expected_body = format!(
-        "invalid path: expected '/{0}/{1}', '/{0}/{2}' or '/{0}/{3}'",
-        ROOT_PATH, MAIN_PATH, DEPLOYS_PATH, SIGS_PATH
+        "invalid path: expected '/{0}/{1}', '/{0}/{2}', '/{0}/{3}', '/{0}/{4}' or '/{0}/{5}'",
+        ROOT_PATH, MAIN_PATH, DEPLOYS_PATH, SIGS_PATH, CONTRACT_EVENTS_PATH, DEPLOY_EXECUTION_PATH
     );
     for url in &urls {
         let response = reqwest::get(url).await.unwrap();
@@ -947,8 +1015,9 @@ async fn should_handle_bad_url_query() {
     ];
 
     let expected_body = format!(
-        "invalid query: expected single field '{}=<EVENT ID>'",
-        QUERY_FIELD
+        "invalid query: expected optional fields '{}=<EVENT ID>', '{}=<PUBLIC KEY>[,...]', \
+        '{}=<CONTRACT HASH>[,...]' and '{}=<EVENT TYPE>[,...]'",
+        QUERY_FIELD, ACCOUNT_QUERY_FIELD, CONTRACT_QUERY_FIELD, EVENT_TYPE_QUERY_FIELD
     );
     for url in &urls {
         let response = reqwest::get(url).await.unwrap();
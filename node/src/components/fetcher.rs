@@ -3,7 +3,11 @@ mod event;
 mod metrics;
 mod tests;
 
-use std::{collections::HashMap, fmt::Debug, time::Duration};
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    time::{Duration, Instant},
+};
 
 use datasize::DataSize;
 use num_rational::Ratio;
@@ -20,14 +24,16 @@ use crate::{
         Component,
     },
     effect::{
+        diagnostics_port::InFlightFetch,
         requests::{ContractRuntimeRequest, FetcherRequest, NetworkRequest, StorageRequest},
         EffectBuilder, EffectExt, Effects,
     },
     protocol::Message,
     types::{
-        Block, BlockAndDeploys, BlockHash, BlockHeader, BlockHeaderWithMetadata, BlockHeadersBatch,
-        BlockHeadersBatchId, BlockSignatures, BlockWithMetadata, Deploy, DeployHash,
-        DeployWithFinalizedApprovals, FinalizedApprovals, FinalizedApprovalsWithId, Item, NodeId,
+        BackfillBlockSignatures, Block, BlockAndDeploys, BlockHash, BlockHeader,
+        BlockHeaderWithMetadata, BlockHeadersBatch, BlockHeadersBatchId, BlockHeight,
+        BlockSignatures, BlockWithMetadata, Deploy, DeployHash, DeployWithFinalizedApprovals,
+        FinalizedApprovals, FinalizedApprovalsWithId, Item, NodeId,
     },
     utils::Source,
     FetcherConfig, NodeRng,
@@ -276,6 +282,10 @@ where
     #[data_size(skip)]
     fault_tolerance_fraction: Ratio<u64>,
     responders: HashMap<T::Id, HashMap<NodeId, Vec<FetchResponder<T>>>>,
+    /// Items peers reported not to have, with the time until which we believe them.
+    #[data_size(skip)]
+    absent: HashMap<(T::Id, NodeId), Instant>,
+    absent_item_cache_duration: Duration,
     #[data_size(skip)]
     metrics: Metrics,
 }
@@ -291,9 +301,50 @@ impl<T: Item> Fetcher<T> {
             get_from_peer_timeout: config.get_from_peer_timeout().into(),
             fault_tolerance_fraction,
             responders: HashMap::new(),
+            absent: HashMap::new(),
+            absent_item_cache_duration: config.absent_item_cache_duration().into(),
             metrics: Metrics::new(name, registry)?,
         })
     }
+
+    /// Sets the time to wait for a peer to respond to a request.
+    pub(crate) fn set_get_from_peer_timeout(&mut self, get_from_peer_timeout: Duration) {
+        self.get_from_peer_timeout = get_from_peer_timeout;
+    }
+
+    /// Returns whether the peer recently reported not to have the item.
+    fn is_absent_on(&self, id: &T::Id, peer: NodeId) -> bool {
+        self.absent
+            .get(&(*id, peer))
+            .map_or(false, |until| *until > Instant::now())
+    }
+
+    /// Records that the peer reported not to have the item, if we asked it for the item.
+    fn record_absent(&mut self, id: T::Id, peer: NodeId) {
+        let requested = self
+            .responders
+            .get(&id)
+            .map_or(false, |peers| peers.contains_key(&peer));
+        if !requested || self.absent_item_cache_duration.is_zero() {
+            return;
+        }
+        let now = Instant::now();
+        self.absent.retain(|_, until| *until > now);
+        self.absent
+            .insert((id, peer), now + self.absent_item_cache_duration);
+    }
+
+    /// Returns the fetches which are waiting for responses from peers.
+    pub(crate) fn in_flight(&self) -> impl Iterator<Item = InFlightFetch> + '_ {
+        self.responders.iter().map(|(id, peers)| InFlightFetch {
+            tag: T::TAG,
+            id: id.to_string(),
+            peers: peers
+                .iter()
+                .map(|(peer, responders)| (*peer, responders.len()))
+                .collect(),
+        })
+    }
 }
 
 impl ItemFetcher<Deploy> for Fetcher<Deploy> {
@@ -479,7 +530,7 @@ impl ItemFetcher<BlockWithMetadata> for Fetcher<BlockWithMetadata> {
         let fault_tolerance_fraction = self.fault_tolerance_fraction;
         async move {
             let block_with_metadata = effect_builder
-                .get_block_with_metadata_from_storage_by_height(id, false)
+                .get_block_with_metadata_from_storage_by_height(BlockHeight::from(id), false)
                 .await?;
             has_enough_block_signatures(
                 effect_builder,
@@ -526,7 +577,7 @@ impl ItemFetcher<BlockHeaderWithMetadata> for Fetcher<BlockHeaderWithMetadata> {
         let fault_tolerance_fraction = self.fault_tolerance_fraction;
         async move {
             let block_header_with_metadata = effect_builder
-                .get_block_header_with_metadata_from_storage_by_height(id, false)
+                .get_block_header_with_metadata_from_storage_by_height(BlockHeight::from(id), false)
                 .await?;
             has_enough_block_signatures(
                 effect_builder,
@@ -593,6 +644,37 @@ impl ItemFetcher<BlockSignatures> for Fetcher<BlockSignatures> {
     }
 }
 
+impl ItemFetcher<BackfillBlockSignatures> for Fetcher<BackfillBlockSignatures> {
+    const SAFE_TO_RESPOND_TO_ALL: bool = false;
+
+    fn responders(
+        &mut self,
+    ) -> &mut HashMap<BlockHash, HashMap<NodeId, Vec<FetchResponder<BackfillBlockSignatures>>>>
+    {
+        &mut self.responders
+    }
+
+    fn metrics(&mut self) -> &Metrics {
+        &self.metrics
+    }
+
+    fn peer_timeout(&self) -> Duration {
+        self.get_from_peer_timeout
+    }
+
+    /// Signatures to backfill are always requested from the peer, as the point of fetching them is
+    /// to add to those already held in storage.
+    fn get_from_storage<REv: ReactorEventT<BackfillBlockSignatures>>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        id: BlockHash,
+        peer: NodeId,
+        responder: FetchResponder<BackfillBlockSignatures>,
+    ) -> Effects<Event<BackfillBlockSignatures>> {
+        self.failed_to_get_from_storage(effect_builder, id, peer, responder)
+    }
+}
+
 impl ItemFetcher<TrieOrChunk> for Fetcher<TrieOrChunk> {
     const SAFE_TO_RESPOND_TO_ALL: bool = true;
 
@@ -744,12 +826,20 @@ where
                         .respond(Ok(FetchedData::from_storage(item)))
                         .ignore()
                 }
+                None if self.is_absent_on(&id, peer) => {
+                    trace!(TAG=%T::TAG, %id, %peer, "item recently reported absent on peer");
+                    self.metrics().absent_cached.inc();
+                    responder
+                        .respond(Err(FetcherError::Absent { id, peer }))
+                        .ignore()
+                }
                 None => self.failed_to_get_from_storage(effect_builder, id, peer, responder),
             },
             Event::GotRemotely { item, source } => {
                 match source {
                     Source::Peer(peer) => {
                         self.metrics().found_on_peer.inc();
+                        self.absent.remove(&(item.id(), peer));
                         if let Err(err) = item.validate() {
                             warn!(?peer, ?err, ?item, "Peer sent invalid item, banning peer");
                             effect_builder.announce_disconnect_from_peer(peer).ignore()
@@ -766,6 +856,7 @@ where
             Event::RejectedRemotely { .. } => Effects::new(),
             Event::AbsentRemotely { id, peer } => {
                 trace!(TAG=%T::TAG, %id, %peer, "item absent on the remote node");
+                self.record_absent(id, peer);
                 self.signal(id, Err(FetcherError::Absent { id, peer }), peer)
             }
             Event::TimeoutPeer { id, peer } => {
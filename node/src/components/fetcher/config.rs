@@ -6,23 +6,56 @@ use serde::{Deserialize, Serialize};
 use casper_types::TimeDiff;
 
 const DEFAULT_GET_FROM_PEER_TIMEOUT: &str = "3sec";
+const DEFAULT_ABSENT_ITEM_CACHE_DURATION: &str = "5sec";
 
 /// Configuration options for fetching.
 #[derive(Copy, Clone, DataSize, Debug, Deserialize, Serialize)]
 pub struct Config {
     get_from_peer_timeout: TimeDiff,
+    #[serde(default = "default_absent_item_cache_duration")]
+    absent_item_cache_duration: TimeDiff,
 }
 
 impl Config {
     pub(crate) fn get_from_peer_timeout(&self) -> TimeDiff {
         self.get_from_peer_timeout
     }
+
+    /// How long to answer requests for an item from a peer which said it doesn't have it without
+    /// asking that peer again.
+    pub(crate) fn absent_item_cache_duration(&self) -> TimeDiff {
+        self.absent_item_cache_duration
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Config {
             get_from_peer_timeout: TimeDiff::from_str(DEFAULT_GET_FROM_PEER_TIMEOUT).unwrap(),
+            absent_item_cache_duration: default_absent_item_cache_duration(),
         }
     }
 }
+
+/// The default for `Config::absent_item_cache_duration`.
+fn default_absent_item_cache_duration() -> TimeDiff {
+    TimeDiff::from_str(DEFAULT_ABSENT_ITEM_CACHE_DURATION).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_use_default_absent_item_cache_duration_if_not_configured() {
+        let config: Config = toml::from_str(r#"get_from_peer_timeout = "10sec""#).unwrap();
+        assert_eq!(
+            config.get_from_peer_timeout(),
+            TimeDiff::from_str("10sec").unwrap()
+        );
+        assert_eq!(
+            config.absent_item_cache_duration(),
+            default_absent_item_cache_duration()
+        );
+    }
+}
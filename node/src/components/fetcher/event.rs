@@ -8,8 +8,8 @@ use tracing::error;
 use super::Item;
 use crate::{
     components::fetcher::FetchedOrNotFound,
-    effect::{announcements::DeployAcceptorAnnouncement, requests::FetcherRequest, Responder},
-    types::{Deploy, NodeId},
+    effect::{requests::FetcherRequest, Responder},
+    types::NodeId,
     utils::Source,
 };
 
@@ -103,27 +103,6 @@ impl<T: Item> From<FetcherRequest<T>> for Event<T> {
     }
 }
 
-// A deploy fetcher knows how to update its state if deploys are coming in via the deploy acceptor.
-impl From<DeployAcceptorAnnouncement> for Event<Deploy> {
-    #[inline]
-    fn from(announcement: DeployAcceptorAnnouncement) -> Self {
-        match announcement {
-            DeployAcceptorAnnouncement::AcceptedNewDeploy { deploy, source } => {
-                Event::GotRemotely {
-                    item: deploy,
-                    source,
-                }
-            }
-            DeployAcceptorAnnouncement::InvalidDeploy { deploy, source } => {
-                Event::RejectedRemotely {
-                    id: *Deploy::id(&deploy),
-                    source,
-                }
-            }
-        }
-    }
-}
-
 impl<T: Item> Display for Event<T> {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
         match self {
@@ -12,6 +12,8 @@ pub(crate) struct Metrics {
     pub timeouts: IntCounter,
     /// Number of total fetch requests made.
     pub fetch_total: IntCounter,
+    /// Number of fetch requests that failed because the peer recently reported the item absent.
+    pub absent_cached: IntCounter,
     /// Reference to the registry for unregistering.
     registry: Registry,
 }
@@ -37,16 +39,25 @@ impl Metrics {
             format!("{}_fetch_total", name),
             format!("number of {} all fetch requests made", name),
         )?;
+        let absent_cached = IntCounter::new(
+            format!("{}_absent_cached", name),
+            format!(
+                "number of {} fetch requests that failed without asking the peer, as it recently reported the item absent",
+                name
+            ),
+        )?;
         registry.register(Box::new(found_in_storage.clone()))?;
         registry.register(Box::new(found_on_peer.clone()))?;
         registry.register(Box::new(timeouts.clone()))?;
         registry.register(Box::new(fetch_total.clone()))?;
+        registry.register(Box::new(absent_cached.clone()))?;
 
         Ok(Metrics {
             found_in_storage,
             found_on_peer,
             timeouts,
             fetch_total,
+            absent_cached,
             registry: registry.clone(),
         })
     }
@@ -58,5 +69,6 @@ impl Drop for Metrics {
         unregister_metric!(self.registry, self.found_on_peer);
         unregister_metric!(self.registry, self.timeouts);
         unregister_metric!(self.registry, self.fetch_total);
+        unregister_metric!(self.registry, self.absent_cached);
     }
 }
@@ -4,8 +4,9 @@
 use std::sync::{Arc, Mutex};
 
 use casper_node_macros::reactor;
-use casper_types::testing::TestRng;
+use casper_types::{testing::TestRng, Timestamp};
 use futures::FutureExt;
+use rand::Rng;
 use tempfile::TempDir;
 use thiserror::Error;
 
@@ -108,7 +109,7 @@ reactor!(Reactor {
 
     announcements: {
         // The deploy fetcher needs to be notified about new deploys.
-        DeployAcceptorAnnouncement -> [deploy_fetcher];
+        DeployAcceptorAnnouncement -> [fn handle_deploy_acceptor_announcement];
         // Currently the RpcServerAnnouncement is misnamed - it solely tells of new deploys arriving
         // from a client.
         RpcServerAnnouncement -> [fake_deploy_acceptor];
@@ -132,11 +133,37 @@ reactor!(Reactor {
         // No consensus component.
         ConsensusMessageIncoming -> [!];
         FinalitySignatureIncoming -> [!];
+        DeployApprovalsIncoming -> [!];
         BlocklistAnnouncement -> [!];
     }
 });
 
 impl Reactor {
+    fn handle_deploy_acceptor_announcement(
+        &mut self,
+        effect_builder: EffectBuilder<ReactorEvent>,
+        rng: &mut NodeRng,
+        announcement: DeployAcceptorAnnouncement,
+    ) -> Effects<ReactorEvent> {
+        let event = match announcement {
+            DeployAcceptorAnnouncement::AcceptedNewDeploy { deploy, source } => {
+                Event::GotRemotely {
+                    item: deploy,
+                    source,
+                }
+            }
+            DeployAcceptorAnnouncement::InvalidDeploy { deploy, source } => {
+                Event::RejectedRemotely {
+                    id: *deploy.id(),
+                    source,
+                }
+            }
+            // The deploy fetcher does not track approvals of already stored deploys.
+            DeployAcceptorAnnouncement::NewDeployApprovals { .. } => return Effects::new(),
+        };
+        self.dispatch_event(effect_builder, rng, ReactorEvent::DeployFetcher(event))
+    }
+
     fn handle_net_response(
         &mut self,
         effect_builder: EffectBuilder<ReactorEvent>,
@@ -150,13 +177,14 @@ impl Reactor {
                 ) {
                     Ok(FetchedOrNotFound::Fetched(deploy)) => Box::new(deploy),
                     Ok(FetchedOrNotFound::NotFound(deploy_hash)) => {
-                        return fatal!(
+                        return self.dispatch_event(
                             effect_builder,
-                            "peer did not have deploy with hash {}: {}",
-                            deploy_hash,
-                            response.sender,
-                        )
-                        .ignore();
+                            rng,
+                            ReactorEvent::DeployFetcher(Event::AbsentRemotely {
+                                id: deploy_hash,
+                                peer: response.sender,
+                            }),
+                        );
                     }
                     Err(error) => {
                         return fatal!(
@@ -196,7 +224,9 @@ impl NetworkedReactor for Reactor {
 
 fn announce_deploy_received(
     deploy: Deploy,
-    responder: Option<Responder<Result<(), deploy_acceptor::Error>>>,
+    responder: Option<
+        Responder<Result<Option<deploy_acceptor::BalanceProof>, deploy_acceptor::Error>>,
+    >,
 ) -> impl FnOnce(EffectBuilder<ReactorEvent>) -> Effects<ReactorEvent> {
     |effect_builder: EffectBuilder<ReactorEvent>| {
         effect_builder
@@ -229,7 +259,9 @@ async fn store_deploy(
     deploy: &Deploy,
     node_id: &NodeId,
     network: &mut Network<Reactor>,
-    responder: Option<Responder<Result<(), deploy_acceptor::Error>>>,
+    responder: Option<
+        Responder<Result<Option<deploy_acceptor::BalanceProof>, deploy_acceptor::Error>>,
+    >,
     rng: &mut TestRng,
 ) {
     network
@@ -257,6 +289,7 @@ async fn store_deploy(
 #[derive(Debug)]
 enum ExpectedFetchedDeployResult {
     TimedOut,
+    Absent,
     FromStorage {
         expected_deploy: Box<Deploy>,
     },
@@ -297,6 +330,8 @@ async fn assert_settled(
         // and the TestDeployAcceptor unconditionally accepts the deploy and stores it. For the
         // test, we don't care whether it was stored or not, just that the TimedOut event fired.
         (ExpectedFetchedDeployResult::TimedOut, Some(Err(FetcherError::TimedOut { .. })), _) => {}
+        // Absent case: the peer reported not to have the deploy, and it was not stored.
+        (ExpectedFetchedDeployResult::Absent, Some(Err(FetcherError::Absent { .. })), None) => {}
         // FromStorage case: expect deploy to correspond to item fetched, as well as stored item
         (
             ExpectedFetchedDeployResult::FromStorage { expected_deploy },
@@ -336,7 +371,10 @@ async fn should_fetch_from_local() {
     };
 
     // Create a random deploy.
-    let deploy = Deploy::random_valid_native_transfer(&mut rng);
+    let deploy = Deploy::builder()
+        .seed(rng.gen())
+        .timestamp(Timestamp::now())
+        .build();
 
     // Store deploy on a node.
     let node_to_store_on = &node_ids[0];
@@ -383,7 +421,10 @@ async fn should_fetch_from_peer() {
     };
 
     // Create a random deploy.
-    let deploy = Deploy::random_valid_native_transfer(&mut rng);
+    let deploy = Deploy::builder()
+        .seed(rng.gen())
+        .timestamp(Timestamp::now())
+        .build();
 
     // Store deploy on a node.
     let node_with_deploy = node_ids[0];
@@ -432,7 +473,10 @@ async fn should_timeout_fetch_from_peer() {
     };
 
     // Create a random deploy.
-    let deploy = Deploy::random_valid_native_transfer(&mut rng);
+    let deploy = Deploy::builder()
+        .seed(rng.gen())
+        .timestamp(Timestamp::now())
+        .build();
     let deploy_hash = *deploy.id();
 
     let holding_node = node_ids[0];
@@ -510,3 +554,61 @@ async fn should_timeout_fetch_from_peer() {
 
     NetworkController::<Message>::remove_active();
 }
+
+#[tokio::test]
+async fn should_not_ask_peer_again_for_absent_item() {
+    const NETWORK_SIZE: usize = 2;
+
+    NetworkController::<Message>::create_active();
+    let (mut network, mut rng, node_ids) = {
+        let mut network = Network::<Reactor>::new();
+        let mut rng = TestRng::new();
+        let node_ids = network.add_nodes(&mut rng, NETWORK_SIZE).await;
+        (network, rng, node_ids)
+    };
+
+    // Create a random deploy, but don't store it anywhere.
+    let deploy = Deploy::builder()
+        .seed(rng.gen())
+        .timestamp(Timestamp::now())
+        .build();
+    let deploy_hash = *deploy.id();
+
+    let peer = node_ids[0];
+    let requesting_node = node_ids[1];
+
+    // Request the deploy from the peer twice.
+    for _ in 0..2 {
+        let fetched = Arc::new(Mutex::new((false, None)));
+        network
+            .process_injected_effect_on(
+                &requesting_node,
+                fetch_deploy(deploy_hash, peer, Arc::clone(&fetched)),
+            )
+            .await;
+        assert_settled(
+            &requesting_node,
+            deploy_hash,
+            ExpectedFetchedDeployResult::Absent,
+            fetched,
+            &mut network,
+            &mut rng,
+            TIMEOUT,
+        )
+        .await;
+    }
+
+    // Only the first request was sent to the peer; the second one failed right away.
+    let metrics = &network
+        .nodes()
+        .get(&requesting_node)
+        .unwrap()
+        .reactor()
+        .inner()
+        .deploy_fetcher
+        .metrics;
+    assert_eq!(metrics.fetch_total.get(), 1);
+    assert_eq!(metrics.absent_cached.get(), 1);
+
+    NetworkController::<Message>::remove_active();
+}
@@ -170,6 +170,11 @@ impl<T: Item + 'static, REv: ReactorEventT<T>> Gossiper<T, REv> {
         })
     }
 
+    /// Returns the number of peers each item is gossiped to.
+    pub(crate) fn infection_target(&self) -> usize {
+        self.table.infection_target()
+    }
+
     /// Handles a new item received from a peer or client for which we should begin gossiping.
     ///
     /// Note that this doesn't include items gossiped to us; those are handled in `handle_gossip()`.
@@ -215,6 +215,11 @@ impl<T: Copy + Eq + Hash + Display> GossipTable<T> {
         }
     }
 
+    /// Returns the number of peers each item is gossiped to.
+    pub(crate) fn infection_target(&self) -> usize {
+        self.infection_target
+    }
+
     /// We received knowledge about potentially new data with given ID from the given peer.  This
     /// should only be called where we don't already hold everything locally we need to be able to
     /// gossip it onwards.  If we are able to gossip the data already, call `new_data` instead.
@@ -20,19 +20,23 @@ use tracing::debug;
 use casper_execution_engine::{
     core::engine_state::{
         engine_config::{
-            DEFAULT_MINIMUM_DELEGATION_AMOUNT, DEFAULT_STRICT_ARGUMENT_CHECKING,
-            DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS,
+            DEFAULT_ALLOW_SEPARATE_REWARD_PURSES, DEFAULT_MINIMUM_DELEGATION_AMOUNT,
+            DEFAULT_STRICT_ARGUMENT_CHECKING, DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS,
         },
         DEFAULT_MAX_RUNTIME_CALL_STACK_HEIGHT,
     },
-    shared::{system_config::SystemConfig, wasm_config::WasmConfig},
+    shared::{
+        fee_handling::{DEFAULT_FEE_HANDLING, DEFAULT_REFUND_HANDLING},
+        system_config::SystemConfig,
+        wasm_config::WasmConfig,
+    },
 };
-use casper_types::{testing::TestRng, ProtocolVersion};
+use casper_types::{testing::TestRng, ProtocolVersion, Timestamp};
 
 use super::*;
 use crate::{
     components::{
-        contract_runtime::{self, ContractRuntime},
+        contract_runtime::{self, CommitRules, ContractRuntime},
         deploy_acceptor,
         fake_deploy_acceptor::FakeDeployAcceptor,
         in_memory_network::{self, InMemoryNetwork, NetworkController},
@@ -45,8 +49,9 @@ use crate::{
             GossiperAnnouncement, RpcServerAnnouncement,
         },
         incoming::{
-            ConsensusMessageIncoming, FinalitySignatureIncoming, NetRequestIncoming, NetResponse,
-            NetResponseIncoming, TrieDemand, TrieRequestIncoming, TrieResponseIncoming,
+            ConsensusMessageIncoming, DeployApprovalsIncoming, FinalitySignatureIncoming,
+            NetRequestIncoming, NetResponse, NetResponseIncoming, TrieDemand, TrieRequestIncoming,
+            TrieResponseIncoming,
         },
         requests::{ConsensusRequest, ContractRuntimeRequest, MarkBlockCompletedRequest},
         Responder,
@@ -114,6 +119,8 @@ enum Event {
     TrieResponseIncoming(TrieResponseIncoming),
     #[from]
     FinalitySignatureIncoming(FinalitySignatureIncoming),
+    #[from]
+    DeployApprovalsIncoming(DeployApprovalsIncoming),
 }
 
 impl ReactorEvent for Event {
@@ -187,6 +194,7 @@ impl Display for Event {
             Event::TrieDemand(inner) => write!(formatter, "demand: {}", inner),
             Event::TrieResponseIncoming(inner) => write!(formatter, "incoming: {}", inner),
             Event::FinalitySignatureIncoming(inner) => write!(formatter, "incoming: {}", inner),
+            Event::DeployApprovalsIncoming(inner) => write!(formatter, "incoming: {}", inner),
         }
     }
 }
@@ -248,6 +256,12 @@ impl reactor::Reactor for Reactor {
             DEFAULT_MINIMUM_DELEGATION_AMOUNT,
             DEFAULT_STRICT_ARGUMENT_CHECKING,
             DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS,
+            DEFAULT_ALLOW_SEPARATE_REWARD_PURSES,
+            DEFAULT_REFUND_HANDLING,
+            DEFAULT_FEE_HANDLING,
+            None,
+            CommitRules::default(),
+            None,
             registry,
         )
         .unwrap();
@@ -311,15 +325,8 @@ impl reactor::Reactor for Reactor {
             Event::ControlAnnouncement(ctrl_ann) => {
                 unreachable!("unhandled control announcement: {}", ctrl_ann)
             }
-            Event::RpcServerAnnouncement(RpcServerAnnouncement::DeployReceived {
-                deploy,
-                responder,
-            }) => {
-                let event = deploy_acceptor::Event::Accept {
-                    deploy,
-                    source: Source::Client,
-                    maybe_responder: responder,
-                };
+            Event::RpcServerAnnouncement(ann) => {
+                let event = deploy_acceptor::Event::from(ann);
                 self.dispatch_event(effect_builder, rng, Event::DeployAcceptor(event))
             }
             Event::DeployAcceptorAnnouncement(DeployAcceptorAnnouncement::AcceptedNewDeploy {
@@ -335,6 +342,9 @@ impl reactor::Reactor for Reactor {
             Event::DeployAcceptorAnnouncement(DeployAcceptorAnnouncement::InvalidDeploy {
                 deploy: _,
                 source: _,
+            })
+            | Event::DeployAcceptorAnnouncement(DeployAcceptorAnnouncement::NewDeployApprovals {
+                ..
             }) => Effects::new(),
             Event::DeployGossiperAnnouncement(_ann) => {
                 // We do not care about deploy gossiper announcements in the gossiper test.
@@ -410,12 +420,14 @@ impl reactor::Reactor for Reactor {
                 | NetResponse::BlockHeaderByHash(_)
                 | NetResponse::BlockHeaderAndFinalitySignaturesByHeight(_)
                 | NetResponse::BlockHeadersBatch(_)
-                | NetResponse::FinalitySignatures(_)) => {
+                | NetResponse::FinalitySignatures(_)
+                | NetResponse::BackfillFinalitySignatures(_)) => {
                     fatal!(effect_builder, "unexpected net response: {:?}", other).ignore()
                 }
             },
             other @ (Event::ConsensusMessageIncoming(_)
             | Event::FinalitySignatureIncoming(_)
+            | Event::DeployApprovalsIncoming(_)
             | Event::AddressGossiperIncoming(_)
             | Event::TrieRequestIncoming(_)
             | Event::TrieDemand(_)
@@ -438,7 +450,9 @@ impl NetworkedReactor for Reactor {
 
 fn announce_deploy_received(
     deploy: Box<Deploy>,
-    responder: Option<Responder<Result<(), deploy_acceptor::Error>>>,
+    responder: Option<
+        Responder<Result<Option<deploy_acceptor::BalanceProof>, deploy_acceptor::Error>>,
+    >,
 ) -> impl FnOnce(EffectBuilder<Event>) -> Effects<Event> {
     |effect_builder: EffectBuilder<Event>| {
         effect_builder
@@ -459,7 +473,12 @@ async fn run_gossip(rng: &mut TestRng, network_size: usize, deploy_count: usize)
 
     // Create `deploy_count` random deploys.
     let (all_deploy_hashes, mut deploys): (BTreeSet<_>, Vec<_>) = iter::repeat_with(|| {
-        let deploy = Box::new(Deploy::random_valid_native_transfer(rng));
+        let deploy = Box::new(
+            Deploy::builder()
+                .seed(rng.gen())
+                .timestamp(Timestamp::now())
+                .build(),
+        );
         (*deploy.id(), deploy)
     })
     .take(deploy_count)
@@ -516,7 +535,12 @@ async fn should_get_from_alternate_source() {
     let node_ids = network.add_nodes(&mut rng, NETWORK_SIZE).await;
 
     // Create random deploy.
-    let deploy = Box::new(Deploy::random_valid_native_transfer(&mut rng));
+    let deploy = Box::new(
+        Deploy::builder()
+            .seed(rng.gen())
+            .timestamp(Timestamp::now())
+            .build(),
+    );
     let deploy_id = *deploy.id();
 
     // Give the deploy to nodes 0 and 1 to be gossiped.
@@ -595,7 +619,12 @@ async fn should_timeout_gossip_response() {
         .await;
 
     // Create random deploy.
-    let deploy = Box::new(Deploy::random_valid_native_transfer(&mut rng));
+    let deploy = Box::new(
+        Deploy::builder()
+            .seed(rng.gen())
+            .timestamp(Timestamp::now())
+            .build(),
+    );
     let deploy_id = *deploy.id();
 
     // Give the deploy to node 0 to be gossiped.
@@ -1,5 +1,6 @@
 mod error;
 mod event;
+mod fork_evidence;
 mod metrics;
 mod pending_signatures;
 mod signature;
@@ -36,11 +37,12 @@ use crate::{
         EffectBuilder, EffectExt, EffectResultExt, Effects,
     },
     protocol::Message,
-    types::{ActivationPoint, BlockHeader},
+    types::{ActivationPoint, BlockHeader, BlockHeight},
     NodeRng,
 };
 pub(crate) use error::{BlockSignatureError, Error};
 pub(crate) use event::Event;
+pub(crate) use fork_evidence::ForkEvidence;
 pub(crate) use utils::{
     check_sufficient_block_signatures, check_sufficient_block_signatures_with_quorum_formula,
     validate_block_signatures,
@@ -115,9 +117,9 @@ where
                 let message = Message::FinalitySignature(fs);
                 effect_builder.broadcast_message(message).ignore()
             }
-            Outcome::AnnounceSignature(fs) => {
-                effect_builder.announce_finality_signature(fs).ignore()
-            }
+            Outcome::AnnounceSignature(fs, signed_weight) => effect_builder
+                .announce_finality_signature(fs, signed_weight)
+                .ignore(),
             Outcome::AnnounceBlock(block) => effect_builder.announce_block_added(block).ignore(),
             Outcome::LoadSignatures(fs) => effect_builder
                 .get_signatures_from_storage(fs.block_hash)
@@ -304,7 +306,7 @@ pub(crate) async fn era_validator_weights_for_block<T: EraValidatorsGetter>(
     let validator_weights = switch_block_of_previous_era
         .next_era_validator_weights()
         .ok_or(Error::MissingNextEraValidators {
-            height: switch_block_of_previous_era.height(),
+            height: BlockHeight::from(switch_block_of_previous_era.height()),
             era_id: era_for_validators_retrieval,
         })?;
     Ok((era_for_validators_retrieval, validator_weights.clone()))
@@ -7,7 +7,7 @@ use thiserror::Error;
 use casper_execution_engine::core::engine_state::GetEraValidatorsError;
 use casper_types::{EraId, PublicKey, U512};
 
-use crate::types::{BlockHeader, BlockSignatures};
+use crate::types::{BlockHeader, BlockHeight, BlockSignatures};
 
 #[derive(Error, Debug, Serialize)]
 pub(crate) enum Error {
@@ -15,7 +15,7 @@ pub(crate) enum Error {
     NoSwitchBlockForEra { era_id: EraId },
 
     #[error("switch block at height {height} for era {era_id} contains no validator weights")]
-    MissingNextEraValidators { height: u64, era_id: EraId },
+    MissingNextEraValidators { height: BlockHeight, era_id: EraId },
 
     /// Error getting era validators from the execution engine.
     #[error(transparent)]
@@ -0,0 +1,52 @@
+use std::{
+    borrow::Cow,
+    fmt::{self, Display, Formatter},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{BlockHeaderWithMetadata, BlockHeight, NodeId};
+
+/// Prefix of the state store keys under which fork evidence is persisted.
+const FORK_EVIDENCE_KEY_PREFIX: &str = "fork_evidence";
+
+/// Two blocks at the same height, each with finality signatures, proving that the linear chain has
+/// forked past finalization.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct ForkEvidence {
+    /// The block stored locally, with its stored finality signatures.
+    pub(crate) local: BlockHeaderWithMetadata,
+    /// The conflicting block advertised by a peer, with the finality signatures it was sent with.
+    pub(crate) conflicting: BlockHeaderWithMetadata,
+    /// The peer which advertised the conflicting block.
+    pub(crate) peer: NodeId,
+}
+
+impl ForkEvidence {
+    /// Returns the height at which the chain has forked.
+    pub(crate) fn height(&self) -> BlockHeight {
+        BlockHeight::from(self.local.block_header.height())
+    }
+
+    /// Returns the key under which the evidence is persisted in the state store.
+    ///
+    /// Only the most recent evidence of a fork at any given height is kept.
+    pub(crate) fn state_store_key(&self) -> Cow<'static, [u8]> {
+        format!("{}_{}", FORK_EVIDENCE_KEY_PREFIX, self.height())
+            .into_bytes()
+            .into()
+    }
+}
+
+impl Display for ForkEvidence {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "fork at height {}: local block {}, conflicting block {} from {}",
+            self.height(),
+            self.local.block_header.hash(),
+            self.conflicting.block_header.hash(),
+            self.peer
+        )
+    }
+}
@@ -16,7 +16,10 @@ use crate::{
         chain_synchronizer::KeyBlockInfo,
         linear_chain::{self, BlockSignatureError},
     },
-    types::{ActivationPoint, Block, BlockHash, BlockSignatures, DeployHash, FinalitySignature},
+    types::{
+        ActivationPoint, Block, BlockHash, BlockSignatures, DeployHash, FinalitySignature,
+        SignedWeight,
+    },
 };
 
 #[derive(DataSize, Debug)]
@@ -51,8 +54,9 @@ pub(super) enum Outcome {
     LoadSignatures(Box<FinalitySignature>),
     // Gossip finality signature to peers.
     Gossip(Box<FinalitySignature>),
-    // Create a reactor announcement about new (valid) finality signatures.
-    AnnounceSignature(Box<FinalitySignature>),
+    // Create a reactor announcement about new (valid) finality signatures, along with the
+    // cumulative weight of the block's known signatures if the validators of its era are known.
+    AnnounceSignature(Box<FinalitySignature>, Option<Box<SignedWeight>>),
     // Create a reactor announcement about new (valid) block.
     AnnounceBlock(Box<Block>),
     // Check if creator of `new_fs` is known trusted validator.
@@ -206,6 +210,26 @@ impl LinearChain {
         self.signature_cache.get(block_hash)
     }
 
+    /// Returns the combined weight of the validators that signed the block, if the validators of
+    /// the block's era are known.
+    fn signed_weight(&self, signatures: &BlockSignatures) -> Option<Box<SignedWeight>> {
+        let validator_weights = self
+            .key_block_info
+            .get(&signatures.era_id)?
+            .validator_weights();
+        let signed_weight = signatures
+            .proofs
+            .keys()
+            .filter_map(|public_key| validator_weights.get(public_key))
+            .copied()
+            .sum();
+        let total_weight = validator_weights.values().copied().sum();
+        Some(Box::new(SignedWeight {
+            signed_weight,
+            total_weight,
+        }))
+    }
+
     fn current_protocol_version(&self) -> ProtocolVersion {
         self.protocol_version
     }
@@ -263,6 +287,7 @@ impl LinearChain {
                 block_signatures.insert_proof(sig.public_key(), sig.signature());
             }
             let should_upgrade = self.should_upgrade(&block_signatures);
+            let signed_weight = self.signed_weight(&block_signatures);
             outcomes.push(Outcome::StoreBlockSignatures(
                 block_signatures,
                 should_upgrade,
@@ -271,7 +296,10 @@ impl LinearChain {
                 if signature.is_local() {
                     outcomes.push(Outcome::Gossip(Box::new(signature.to_inner().clone())));
                 }
-                outcomes.push(Outcome::AnnounceSignature(signature.take()));
+                outcomes.push(Outcome::AnnounceSignature(
+                    signature.take(),
+                    signed_weight.clone(),
+                ));
             }
         };
         outcomes.push(Outcome::AnnounceBlock(block));
@@ -422,7 +450,8 @@ impl LinearChain {
                 self.cache_signatures(*known_signatures.clone());
                 debug!(hash = %known_signatures.block_hash, "storing finality signatures");
                 // Announce new finality signatures for other components to pick up.
-                let mut outcomes = vec![Outcome::AnnounceSignature(new_fs.clone())];
+                let signed_weight = self.signed_weight(&known_signatures);
+                let mut outcomes = vec![Outcome::AnnounceSignature(new_fs.clone(), signed_weight)];
                 if let Some(signature) = self.remove_from_pending_fs(&*new_fs) {
                     // This shouldn't return `None` as we added the `fs` to the pending collection
                     // when we received it. If it _is_ `None` then a concurrent
@@ -453,7 +482,7 @@ mod tests {
         crypto::generate_ed25519_keypair, testing::TestRng, EraId, PublicKey, SecretKey,
     };
 
-    use crate::{logging, types::FinalizedBlock};
+    use crate::{components::consensus::EraReport, logging};
 
     use super::*;
 
@@ -578,8 +607,8 @@ mod tests {
             tmp.push(Outcome::StoreBlockSignatures(block_signatures, false));
             // Only `sig_a` was created locally and we don't "regossip" incoming signatures.
             tmp.push(Outcome::Gossip(Box::new(sig_a.clone())));
-            tmp.push(Outcome::AnnounceSignature(Box::new(sig_a.clone())));
-            tmp.push(Outcome::AnnounceSignature(Box::new(sig_b.clone())));
+            tmp.push(Outcome::AnnounceSignature(Box::new(sig_a.clone()), None));
+            tmp.push(Outcome::AnnounceSignature(Box::new(sig_b.clone()), None));
             tmp.push(Outcome::AnnounceBlock(block));
             tmp
         };
@@ -591,7 +620,7 @@ mod tests {
         #[allow(clippy::vec_init_then_push)]
         let expected_outcomes = {
             let mut tmp = vec![];
-            tmp.push(Outcome::AnnounceSignature(Box::new(sig_c.clone())));
+            tmp.push(Outcome::AnnounceSignature(Box::new(sig_c.clone()), None));
             tmp.push(Outcome::Gossip(Box::new(sig_c.clone())));
             let mut block_signatures = BlockSignatures::new(block_hash, block_era);
             block_signatures.insert_proof(sig_a.public_key.clone(), sig_a.signature);
@@ -640,7 +669,7 @@ mod tests {
                 // After confirming that signature is valid and block known, we want to store the
                 // signature and announce it.
                 match &*outcomes {
-                    [Outcome::AnnounceSignature(outcome_fs), Outcome::StoreBlockSignatures(outcome_block_signatures, false)] =>
+                    [Outcome::AnnounceSignature(outcome_fs, None), Outcome::StoreBlockSignatures(outcome_block_signatures, false)] =>
                     {
                         assert_eq!(&fs, &**outcome_fs);
                         // LinearChain component will update the `block_signatures` with a new
@@ -688,14 +717,11 @@ mod tests {
         );
 
         // Set the latest known block so that we can trigger the following checks.
-        let block = Block::random_with_specifics(
-            &mut rng,
-            EraId::new(3),
-            10,
-            ProtocolVersion::V1_0_0,
-            false,
-            None,
-        );
+        let block = Block::builder()
+            .seed(rng.gen())
+            .era_id(EraId::new(3))
+            .height(10)
+            .build();
         let block_hash = *block.hash();
         let block_era = block.header().era_id();
 
@@ -740,14 +766,13 @@ mod tests {
         );
 
         // Set the latest known block so that we can trigger the following checks.
-        let block = Box::new(Block::random_with_specifics(
-            &mut rng,
-            EraId::new(3),
-            10,
-            ProtocolVersion::V1_0_0,
-            false,
-            None,
-        ));
+        let block = Box::new(
+            Block::builder()
+                .seed(rng.gen())
+                .era_id(EraId::new(3))
+                .height(10)
+                .build(),
+        );
         let block_hash = *block.hash();
         let block_era = block.header().era_id();
         let new_block_outcomes = lc.handle_new_block(block.clone(), HashMap::new());
@@ -773,13 +798,21 @@ mod tests {
             vec![
                 Outcome::StoreBlockSignatures(block_signatures, false),
                 Outcome::Gossip(Box::new(valid_sig.clone())),
-                Outcome::AnnounceSignature(Box::new(valid_sig)),
+                Outcome::AnnounceSignature(Box::new(valid_sig), None),
             ]
         };
         // Verify that all outcomes are expected.
         assert_equal(expected_outcomes, outcomes);
     }
 
+    /// Returns the signed weight out of the total weight of four validators with weight 100 each.
+    fn signed_weight(signed_weight: u64) -> Option<Box<SignedWeight>> {
+        Some(Box::new(SignedWeight {
+            signed_weight: signed_weight.into(),
+            total_weight: 400.into(),
+        }))
+    }
+
     #[test]
     fn upgrade_when_fully_signed() {
         let _ = logging::init();
@@ -810,15 +843,13 @@ mod tests {
 
         // The switch block in era 1 defines how many validators need to sign the one in era 2.
         let block = Box::new(
-            Block::new(
-                BlockHash::random(&mut rng),              // parent hash
-                rng.gen::<[u8; Digest::LENGTH]>().into(), // parent seed
-                rng.gen::<[u8; Digest::LENGTH]>().into(), // state root hash
-                FinalizedBlock::random_with_specifics(&mut rng, EraId::from(1), 10, true, None),
-                Some(validators.clone()),
-                protocol_version,
-            )
-            .unwrap(),
+            Block::builder()
+                .seed(rng.gen())
+                .era_id(EraId::from(1))
+                .height(10)
+                .protocol_version(protocol_version)
+                .era_end(EraReport::default(), validators.clone())
+                .build(),
         );
 
         let outcomes = lc.handle_put_block(block.clone());
@@ -826,15 +857,13 @@ mod tests {
 
         // The switch block in era 2 is the last before the upgrade.
         let block = Box::new(
-            Block::new(
-                BlockHash::random(&mut rng),              // parent hash
-                rng.gen::<[u8; Digest::LENGTH]>().into(), // parent seed
-                rng.gen::<[u8; Digest::LENGTH]>().into(), // state root hash
-                FinalizedBlock::random_with_specifics(&mut rng, EraId::from(2), 20, true, None),
-                Some(validators),
-                protocol_version,
-            )
-            .unwrap(),
+            Block::builder()
+                .seed(rng.gen())
+                .era_id(EraId::from(2))
+                .height(20)
+                .protocol_version(protocol_version)
+                .era_end(EraReport::default(), validators)
+                .build(),
         );
 
         let era_id = block.header().era_id();
@@ -862,7 +891,7 @@ mod tests {
         assert_equal(
             vec![
                 Outcome::AnnounceBlock(block.clone()),
-                Outcome::AnnounceSignature(signatures[0].clone()),
+                Outcome::AnnounceSignature(signatures[0].clone(), signed_weight(100)),
                 Outcome::StoreBlockSignatures(*stored_sigs.clone(), false),
             ],
             lc.handle_put_block(block),
@@ -874,7 +903,7 @@ mod tests {
         stored_sigs.insert_proof(signatures[1].public_key.clone(), signatures[1].signature);
         assert_equal(
             vec![
-                Outcome::AnnounceSignature(signatures[1].clone()),
+                Outcome::AnnounceSignature(signatures[1].clone(), signed_weight(200)),
                 Outcome::StoreBlockSignatures(*stored_sigs.clone(), false),
             ],
             outcomes,
@@ -886,7 +915,7 @@ mod tests {
         stored_sigs.insert_proof(signatures[2].public_key.clone(), signatures[2].signature);
         assert_equal(
             vec![
-                Outcome::AnnounceSignature(signatures[2].clone()),
+                Outcome::AnnounceSignature(signatures[2].clone(), signed_weight(300)),
                 Outcome::StoreBlockSignatures(*stored_sigs, true),
             ],
             outcomes,
@@ -923,30 +952,26 @@ mod tests {
 
         // The switch block in era 1 defines how many validators need to sign the one in era 2.
         let block = Box::new(
-            Block::new(
-                BlockHash::random(&mut rng),              // parent hash
-                rng.gen::<[u8; Digest::LENGTH]>().into(), // parent seed
-                rng.gen::<[u8; Digest::LENGTH]>().into(), // state root hash
-                FinalizedBlock::random_with_specifics(&mut rng, EraId::from(1), 10, true, None),
-                Some(validators.clone()),
-                protocol_version,
-            )
-            .unwrap(),
+            Block::builder()
+                .seed(rng.gen())
+                .era_id(EraId::from(1))
+                .height(10)
+                .protocol_version(protocol_version)
+                .era_end(EraReport::default(), validators.clone())
+                .build(),
         );
         let outcomes = lc.handle_put_block(block.clone());
         assert_equal(vec![Outcome::AnnounceBlock(block)], outcomes);
 
         // The switch block in era 2 is the last before the upgrade.
         let block = Box::new(
-            Block::new(
-                BlockHash::random(&mut rng),              // parent hash
-                rng.gen::<[u8; Digest::LENGTH]>().into(), // parent seed
-                rng.gen::<[u8; Digest::LENGTH]>().into(), // state root hash
-                FinalizedBlock::random_with_specifics(&mut rng, EraId::from(2), 20, true, None),
-                Some(validators),
-                protocol_version,
-            )
-            .unwrap(),
+            Block::builder()
+                .seed(rng.gen())
+                .era_id(EraId::from(2))
+                .height(20)
+                .protocol_version(protocol_version)
+                .era_end(EraReport::default(), validators)
+                .build(),
         );
 
         let era_id = block.header().era_id();
@@ -972,10 +997,10 @@ mod tests {
         assert_equal(
             vec![
                 Outcome::AnnounceBlock(block),
-                Outcome::AnnounceSignature(signatures[0].clone()),
-                Outcome::AnnounceSignature(signatures[1].clone()),
-                Outcome::AnnounceSignature(signatures[2].clone()),
-                Outcome::AnnounceSignature(signatures[3].clone()),
+                Outcome::AnnounceSignature(signatures[0].clone(), signed_weight(400)),
+                Outcome::AnnounceSignature(signatures[1].clone(), signed_weight(400)),
+                Outcome::AnnounceSignature(signatures[2].clone(), signed_weight(400)),
+                Outcome::AnnounceSignature(signatures[3].clone(), signed_weight(400)),
                 Outcome::StoreBlockSignatures(expected_sigs, true),
             ],
             outcomes,
@@ -17,42 +17,75 @@
 //!     example: curl -X GET 'http://<ip>:8888/status'
 //! /metrics : time series data collected from the internals of the node being queried.
 //!     example: curl -X GET 'http://<ip>:8888/metrics'
+//!
+//! Probes suitable for e.g. Kubernetes deployments are also provided, replying with "503 Service
+//! Unavailable" if failing:
+//! /health/live : whether the node is responsive.
+//!     example: curl -X GET 'http://<ip>:8888/health/live'
+//! /health/ready : whether the node is synced, has enough peers and healthy storage.
+//!     example: curl -X GET 'http://<ip>:8888/health/ready'
+//!
+//! The configuration the node is running can be retrieved for verification by tooling:
+//! /chainspec : the raw and parsed chainspec, along with the installed upgrade points and whether
+//!     each is superseded, active or pending.
+//!     example: curl -X GET 'http://<ip>:8888/chainspec'
+//!
+//! Blocks can also be retrieved by their hash, with responses marked as cacheable:
+//! /block/<hash> : the block with the given hex-encoded hash, without its finality signatures.
+//!     example: curl -X GET 'http://<ip>:8888/block/<hash>'
+//!
+//! The peers the node is connected to can be inspected to debug connectivity issues:
+//! /peers/detailed : each peer's address, connection direction, consensus role, protocol version,
+//!     connection uptime, last received message, handshake round-trip time and reputation score.
+//!     example: curl -X GET 'http://<ip>:8888/peers/detailed'
+//!
+//! If a profiling token is configured, the estimated heap memory used by each component, broken
+//! down into its parts, can be retrieved by passing the token as a bearer token:
+//! /memory : the estimated memory usage, as also reported by the diagnostics port's `dump-memory`.
+//!     example: curl -X GET -H 'Authorization: Bearer <token>' 'http://<ip>:8888/memory'
 
 mod config;
 mod event;
 mod filters;
+mod health;
 mod http_server;
 
-use std::{convert::Infallible, fmt::Debug, time::Instant};
+use std::{convert::Infallible, fmt::Debug, sync::Arc, time::Instant};
 
 use datasize::DataSize;
 use futures::{future::BoxFuture, join, FutureExt};
 use tokio::{sync::oneshot, task::JoinHandle};
 use tracing::{debug, error, info, warn};
 
-use casper_types::ProtocolVersion;
+use casper_types::{ProtocolVersion, SecretKey};
 
 use super::Component;
 use crate::{
     components::rpc_server::rpcs::docs::OPEN_RPC_SCHEMA,
     effect::{
+        announcements::ControlAnnouncement,
         requests::{
             ChainspecLoaderRequest, ConsensusRequest, MetricsRequest, NetworkInfoRequest,
-            NodeStateRequest, RestRequest, StorageRequest,
+            NodeStateRequest, RestRequest, StorageRequest, SyncProgressRequest,
         },
         EffectBuilder, EffectExt, Effects,
     },
-    reactor::Finalize,
+    reactor::{
+        supervision::{SpawnsSupervisedTasks, SupervisedTasks},
+        Finalize,
+    },
     types::StatusFeed,
-    utils::{self, ListeningError},
-    NodeRng,
+    utils::{self, listener::Listener, ListeningError},
+    NodeRng, WithDir,
 };
 pub use config::Config;
 pub(crate) use event::Event;
+pub(crate) use health::Readiness;
 
 /// A helper trait capturing all of this components Request type dependencies.
 pub(crate) trait ReactorEventT:
     From<Event>
+    + From<ControlAnnouncement>
     + From<RestRequest>
     + From<NetworkInfoRequest>
     + From<StorageRequest>
@@ -60,12 +93,14 @@ pub(crate) trait ReactorEventT:
     + From<ConsensusRequest>
     + From<MetricsRequest>
     + From<NodeStateRequest>
+    + From<SyncProgressRequest>
     + Send
 {
 }
 
 impl<REv> ReactorEventT for REv where
     REv: From<Event>
+        + From<ControlAnnouncement>
         + From<RestRequest>
         + From<NetworkInfoRequest>
         + From<StorageRequest>
@@ -73,6 +108,7 @@ impl<REv> ReactorEventT for REv where
         + From<ConsensusRequest>
         + From<MetricsRequest>
         + From<NodeStateRequest>
+        + From<SyncProgressRequest>
         + Send
         + 'static
 {
@@ -88,6 +124,12 @@ pub(crate) struct InnerRestServer {
     server_join_handle: Option<JoinHandle<()>>,
     /// The instant at which the node has started.
     node_startup_instant: Instant,
+    /// The key to sign status responses with, if enabled.
+    #[data_size(skip)]
+    status_signing_key: Option<Arc<SecretKey>>,
+    /// The tasks running the server and its connections.
+    #[data_size(skip)]
+    tasks: SupervisedTasks,
 }
 
 #[derive(DataSize, Debug)]
@@ -98,27 +140,46 @@ pub(crate) struct RestServer {
 
 impl RestServer {
     pub(crate) fn new<REv>(
-        config: Config,
+        config: WithDir<Config>,
         effect_builder: EffectBuilder<REv>,
         api_version: ProtocolVersion,
         node_startup_instant: Instant,
+        signing_key: Option<Arc<SecretKey>>,
     ) -> Result<Self, ListeningError>
     where
         REv: ReactorEventT,
     {
+        let (root, config) = config.into_parts();
         if !config.enable_server {
             return Ok(RestServer { inner_rest: None });
         }
 
         let (shutdown_sender, shutdown_receiver) = oneshot::channel::<()>();
 
-        let builder = utils::start_listening(&config.address)?;
-        let server_join_handle = Some(tokio::spawn(http_server::run(
-            builder,
+        let tcp = if config.enable_tcp {
+            Some(utils::start_listening(&config.address)?)
+        } else {
+            None
+        };
+        let listener = Listener::bind(
+            tcp,
+            config.unix_socket_path.as_ref().map(|path| root.join(path)),
+            // Mac OS X / Linux use different types for the mask, so we need to call .into() here.
+            #[allow(clippy::useless_conversion)]
+            config.unix_socket_umask.into(),
+        )?;
+        let tasks = SupervisedTasks::new();
+        let server_join_handle = Some(tasks.supervisor().spawn(http_server::run(
+            listener,
+            tasks.supervisor().clone(),
             effect_builder,
             api_version,
             shutdown_receiver,
             config.qps_limit,
+            config.readiness_min_peers as usize,
+            config.redact_peer_reputation,
+            config.cors.to_cors_builder(),
+            config.profiling_token,
         )));
 
         Ok(RestServer {
@@ -126,11 +187,21 @@ impl RestServer {
                 shutdown_sender,
                 server_join_handle,
                 node_startup_instant,
+                status_signing_key: signing_key.filter(|_| config.sign_status_responses),
+                tasks,
             }),
         })
     }
 }
 
+impl SpawnsSupervisedTasks for RestServer {
+    fn supervised_tasks(&mut self) -> Option<&mut SupervisedTasks> {
+        self.inner_rest
+            .as_mut()
+            .map(|rest_server| &mut rest_server.tasks)
+    }
+}
+
 impl<REv> Component<REv> for RestServer
 where
     REv: ReactorEventT,
@@ -154,6 +225,7 @@ where
         match event {
             Event::RestRequest(RestRequest::Status { responder }) => {
                 let node_uptime = rest_server.node_startup_instant.elapsed();
+                let status_signing_key = rest_server.status_signing_key.clone();
                 async move {
                     let (
                         last_added_block,
@@ -161,12 +233,14 @@ where
                         chainspec_info,
                         consensus_status,
                         node_state,
+                        sync_progress,
                     ) = join!(
                         effect_builder.get_highest_block_from_storage(),
                         effect_builder.network_peers(),
                         effect_builder.get_chainspec_info(),
                         effect_builder.consensus_status(),
-                        effect_builder.get_node_state()
+                        effect_builder.get_node_state(),
+                        effect_builder.get_sync_progress()
                     );
 
                     let status_feed = StatusFeed::new(
@@ -176,6 +250,8 @@ where
                         consensus_status,
                         node_uptime,
                         node_state,
+                        sync_progress,
+                        status_signing_key,
                     );
                     responder.respond(status_feed).await;
                 }
@@ -191,6 +267,28 @@ where
                 let schema = OPEN_RPC_SCHEMA.clone();
                 responder.respond(schema).ignore()
             }
+            Event::RestRequest(RestRequest::Liveness { responder }) => {
+                responder.respond(()).ignore()
+            }
+            Event::RestRequest(RestRequest::Readiness {
+                min_peer_count,
+                responder,
+            }) => async move {
+                let (highest_block_header, peers, node_state) = join!(
+                    effect_builder.get_highest_block_header_from_storage(),
+                    effect_builder.network_peers(),
+                    effect_builder.get_node_state()
+                );
+
+                let readiness = Readiness::new(
+                    &node_state,
+                    peers.len(),
+                    min_peer_count,
+                    highest_block_header.is_some(),
+                );
+                responder.respond(readiness).await;
+            }
+            .ignore(),
             Event::GetMetricsResult {
                 text,
                 main_responder,
@@ -227,7 +325,7 @@ mod schema_tests {
     use crate::{
         rpcs::{
             docs::OpenRpcSchema,
-            info::{GetChainspecResult, GetValidatorChangesResult},
+            info::{GetChainspecDetailsResult, GetChainspecResult, GetValidatorChangesResult},
         },
         testing::assert_schema,
         types::GetStatusResult,
@@ -269,4 +367,13 @@ mod schema_tests {
         );
         assert_schema(schema_path, schema_for!(GetChainspecResult));
     }
+
+    #[test]
+    fn schema_chainspec() {
+        let schema_path = format!(
+            "{}/../resources/test/rest_schema_chainspec.json",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        assert_schema(schema_path, schema_for!(GetChainspecDetailsResult));
+    }
 }
@@ -1,12 +1,20 @@
+use std::path::PathBuf;
+
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
+use crate::components::rpc_server::CorsConfig;
+
 /// Default binding address for the REST HTTP server.
 ///
 /// Uses a fixed port per node, but binds on any interface.
 const DEFAULT_ADDRESS: &str = "0.0.0.0:0";
 /// Default rate limit in qps.
 const DEFAULT_QPS_LIMIT: u64 = 100;
+/// Default minimum number of peers required for the node to be ready.
+const DEFAULT_READINESS_MIN_PEERS: u32 = 1;
+/// Default umask to apply before creating the unix domain socket.
+const DEFAULT_UNIX_SOCKET_UMASK: u16 = 0o077;
 
 /// REST HTTP server configuration.
 #[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
@@ -19,8 +27,42 @@ pub struct Config {
     /// Address to bind REST HTTP server to.
     pub address: String,
 
+    /// Whether to listen on `address` via TCP.  Can be disabled to only listen on the unix domain
+    /// socket.
+    #[serde(default = "default_enable_tcp")]
+    pub enable_tcp: bool,
+
+    /// Path of a unix domain socket to listen on, if any.
+    #[serde(default)]
+    pub unix_socket_path: Option<PathBuf>,
+
+    /// `umask` to apply before creating the unix domain socket.
+    #[serde(default = "default_unix_socket_umask")]
+    pub unix_socket_umask: u16,
+
     /// Max rate limit in qps.
     pub qps_limit: u64,
+
+    /// Minimum number of connected peers required for the `/health/ready` endpoint to report the
+    /// node as ready.
+    #[serde(default = "default_readiness_min_peers")]
+    pub readiness_min_peers: u32,
+
+    /// Cross-origin resource sharing settings, applicable to browser-based clients.
+    #[serde(default = "default_cors")]
+    pub cors: CorsConfig,
+
+    /// Bearer token required by the `/profile` endpoints.  The endpoints are disabled if unset.
+    #[serde(default)]
+    pub profiling_token: Option<String>,
+
+    /// Whether to sign `/status` responses with the node's validator key.
+    #[serde(default)]
+    pub sign_status_responses: bool,
+
+    /// Whether to omit the peers' reputation scores from `/peers/detailed` responses.
+    #[serde(default)]
+    pub redact_peer_reputation: bool,
 }
 
 impl Config {
@@ -29,11 +71,39 @@ impl Config {
         Config {
             enable_server: true,
             address: DEFAULT_ADDRESS.to_string(),
+            enable_tcp: true,
+            unix_socket_path: None,
+            unix_socket_umask: DEFAULT_UNIX_SOCKET_UMASK,
             qps_limit: DEFAULT_QPS_LIMIT,
+            readiness_min_peers: DEFAULT_READINESS_MIN_PEERS,
+            cors: default_cors(),
+            profiling_token: None,
+            sign_status_responses: false,
+            redact_peer_reputation: false,
         }
     }
 }
 
+/// The default for `Config::enable_tcp`.
+fn default_enable_tcp() -> bool {
+    true
+}
+
+/// The default for `Config::unix_socket_umask`.
+fn default_unix_socket_umask() -> u16 {
+    DEFAULT_UNIX_SOCKET_UMASK
+}
+
+/// The default for `Config::readiness_min_peers`.
+fn default_readiness_min_peers() -> u32 {
+    DEFAULT_READINESS_MIN_PEERS
+}
+
+/// The default for `Config::cors`.
+fn default_cors() -> CorsConfig {
+    CorsConfig::allow_any_origin(&["GET"])
+}
+
 impl Default for Config {
     fn default() -> Self {
         Config::new()
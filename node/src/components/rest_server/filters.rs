@@ -1,7 +1,13 @@
-use futures::FutureExt;
-use http::Response;
+use std::{path::PathBuf, time::Duration};
+
+use futures::{future, FutureExt};
+use http::{
+    header::{AUTHORIZATION, CACHE_CONTROL},
+    Response,
+};
 use hyper::Body;
-use tracing::warn;
+use serde::Deserialize;
+use tracing::{info, warn};
 use warp::{
     filters::BoxedFilter,
     http::StatusCode,
@@ -10,14 +16,19 @@ use warp::{
     Filter,
 };
 
-use casper_types::ProtocolVersion;
+use casper_hashing::Digest;
+use casper_types::{ProtocolVersion, TimeDiff};
 
-use super::ReactorEventT;
+use super::{ReactorEventT, Readiness};
 use crate::{
     effect::{requests::RestRequest, EffectBuilder},
+    profiling::{self, ProfilingError},
     reactor::QueueKind,
-    rpcs::info::{GetChainspecResult, GetValidatorChangesResult},
-    types::GetStatusResult,
+    rpcs::{
+        chain::GetBlockResult,
+        info::{GetChainspecDetailsResult, GetValidatorChangesResult},
+    },
+    types::{BlockHash, GetStatusResult, JsonBlock},
 };
 
 /// The status URL path.
@@ -35,6 +46,42 @@ pub const VALIDATOR_CHANGES_API_PATH: &str = "validator-changes";
 /// The chainspec file URL path.
 pub const CHAINSPEC_API_PATH: &str = "chainspec";
 
+/// The block URL path, followed by the hex-encoded block hash.
+pub const BLOCK_API_PATH: &str = "block";
+
+/// The peers URL path, followed by the kind of peer listing.
+pub const PEERS_API_PATH: &str = "peers";
+
+/// The detailed peers URL path, following the peers URL path.
+pub const DETAILED_PEERS_API_PATH: &str = "detailed";
+
+/// The health probes URL path, followed by the probe's path.
+pub const HEALTH_API_PATH: &str = "health";
+
+/// The liveness probe URL path, following the health probes URL path.
+pub const LIVENESS_API_PATH: &str = "live";
+
+/// The readiness probe URL path, following the health probes URL path.
+pub const READINESS_API_PATH: &str = "ready";
+
+/// The profiling URL path, followed by the kind of profile.
+pub const PROFILE_API_PATH: &str = "profile";
+
+/// The CPU profile URL path, following the profiling URL path.
+pub const CPU_PROFILE_API_PATH: &str = "cpu";
+
+/// The task dump URL path, following the profiling URL path.
+pub const TASK_DUMP_API_PATH: &str = "tasks";
+
+/// The memory usage breakdown URL path.
+pub const MEMORY_API_PATH: &str = "memory";
+
+/// The time within which the node needs to handle a liveness request to be considered live.
+const LIVENESS_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The `Cache-Control` header value for responses which never change, e.g. blocks by hash.
+const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
 pub(super) fn create_status_filter<REv: ReactorEventT>(
     effect_builder: EffectBuilder<REv>,
     api_version: ProtocolVersion,
@@ -124,13 +171,289 @@ pub(super) fn create_chainspec_filter<REv: ReactorEventT>(
 ) -> BoxedFilter<(Response<Body>,)> {
     warp::get()
         .and(warp::path(CHAINSPEC_API_PATH))
+        .and_then(move || async move {
+            let (chainspec_bytes, chainspec, upgrade_points) = future::join3(
+                effect_builder.get_chainspec_raw_bytes(),
+                effect_builder.get_chainspec(),
+                effect_builder.get_upgrade_points(),
+            )
+            .await;
+            let response = match GetChainspecDetailsResult::new(
+                api_version,
+                (*chainspec_bytes).clone(),
+                &chainspec,
+                upgrade_points,
+            ) {
+                Ok(result) => reply::json(&result).into_response(),
+                Err(error) => {
+                    warn!(%error, "failed to encode chainspec");
+                    reply::with_status(
+                        "failed to encode chainspec",
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                    )
+                    .into_response()
+                }
+            };
+            Ok::<_, Rejection>(response)
+        })
+        .boxed()
+}
+
+pub(super) fn create_block_filter<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    api_version: ProtocolVersion,
+) -> BoxedFilter<(Response<Body>,)> {
+    warp::get()
+        .and(warp::path(BLOCK_API_PATH))
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and_then(move |hex_block_hash: String| async move {
+            let block_hash = match Digest::from_hex(&hex_block_hash) {
+                Ok(digest) => BlockHash::new(digest),
+                Err(error) => {
+                    return Ok::<_, Rejection>(
+                        reply::with_status(
+                            format!("invalid block hash: {}", error),
+                            StatusCode::BAD_REQUEST,
+                        )
+                        .into_response(),
+                    )
+                }
+            };
+            let block = match effect_builder.get_block_from_storage(block_hash).await {
+                Some(block) => block,
+                None => {
+                    return Ok(reply::with_status("block not found", StatusCode::NOT_FOUND)
+                        .into_response())
+                }
+            };
+            // The finality signatures are omitted, as unlike the block itself, they may still
+            // change.
+            let result = GetBlockResult {
+                api_version,
+                block: Some(JsonBlock::new(block, None)),
+            };
+            Ok(
+                reply::with_header(reply::json(&result), CACHE_CONTROL, IMMUTABLE_CACHE_CONTROL)
+                    .into_response(),
+            )
+        })
+        .boxed()
+}
+
+pub(super) fn create_detailed_peers_filter<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    redact_reputation: bool,
+) -> BoxedFilter<(Response<Body>,)> {
+    warp::get()
+        .and(warp::path(PEERS_API_PATH))
+        .and(warp::path(DETAILED_PEERS_API_PATH))
+        .and(warp::path::end())
         .and_then(move || {
             effect_builder
-                .get_chainspec_raw_bytes()
-                .map(move |chainspec_bytes| {
-                    let result = GetChainspecResult::new(api_version, (*chainspec_bytes).clone());
-                    Ok::<_, Rejection>(reply::json(&result).into_response())
+                .network_detailed_peers()
+                .map(move |mut peers| {
+                    if redact_reputation {
+                        for peer in &mut peers {
+                            peer.reputation = None;
+                        }
+                    }
+                    Ok::<_, Rejection>(reply::json(&peers).into_response())
+                })
+        })
+        .boxed()
+}
+
+pub(super) fn create_liveness_filter<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+) -> BoxedFilter<(Response<Body>,)> {
+    warp::get()
+        .and(warp::path(HEALTH_API_PATH))
+        .and(warp::path(LIVENESS_API_PATH))
+        .and(warp::path::end())
+        .and_then(move || async move {
+            let liveness_request = effect_builder.make_request(
+                |responder| RestRequest::Liveness { responder },
+                QueueKind::Api,
+            );
+            let reply = match tokio::time::timeout(LIVENESS_TIMEOUT, liveness_request).await {
+                Ok(()) => reply::with_status("live", StatusCode::OK),
+                Err(_) => {
+                    warn!("liveness request timed out");
+                    reply::with_status("not responding", StatusCode::SERVICE_UNAVAILABLE)
+                }
+            };
+            Ok::<_, Rejection>(reply.into_response())
+        })
+        .boxed()
+}
+
+pub(super) fn create_readiness_filter<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    min_peer_count: usize,
+) -> BoxedFilter<(Response<Body>,)> {
+    warp::get()
+        .and(warp::path(HEALTH_API_PATH))
+        .and(warp::path(READINESS_API_PATH))
+        .and(warp::path::end())
+        .and_then(move || {
+            effect_builder
+                .make_request(
+                    |responder| RestRequest::Readiness {
+                        min_peer_count,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .map(|readiness: Readiness| {
+                    let status = if readiness.is_ready {
+                        StatusCode::OK
+                    } else {
+                        StatusCode::SERVICE_UNAVAILABLE
+                    };
+                    Ok::<_, Rejection>(
+                        reply::with_status(reply::json(&readiness), status).into_response(),
+                    )
                 })
         })
         .boxed()
 }
+
+/// The query parameters of a CPU profile request.
+#[derive(Deserialize)]
+struct CpuProfileQuery {
+    /// The number of seconds to sample for.
+    seconds: u64,
+}
+
+pub(super) fn create_cpu_profile_filter(
+    profiling_token: Option<String>,
+) -> BoxedFilter<(Response<Body>,)> {
+    warp::post()
+        .and(warp::path(PROFILE_API_PATH))
+        .and(warp::path(CPU_PROFILE_API_PATH))
+        .and(warp::path::end())
+        .and(authorize_profiling(profiling_token))
+        .and(warp::query::<CpuProfileQuery>())
+        .and_then(|query: CpuProfileQuery| async move {
+            info!(seconds = query.seconds, "CPU profile requested via REST");
+            let result = profiling::profile_cpu(TimeDiff::from_seconds(query.seconds)).await;
+            Ok::<_, Rejection>(profile_response(result))
+        })
+        .boxed()
+}
+
+pub(super) fn create_task_dump_filter(
+    profiling_token: Option<String>,
+) -> BoxedFilter<(Response<Body>,)> {
+    warp::post()
+        .and(warp::path(PROFILE_API_PATH))
+        .and(warp::path(TASK_DUMP_API_PATH))
+        .and(warp::path::end())
+        .and(authorize_profiling(profiling_token))
+        .and_then(|| async move {
+            info!("task dump requested via REST");
+            Ok::<_, Rejection>(profile_response(profiling::dump_tasks()))
+        })
+        .boxed()
+}
+
+pub(super) fn create_memory_usage_filter<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    profiling_token: Option<String>,
+) -> BoxedFilter<(Response<Body>,)> {
+    warp::get()
+        .and(warp::path(MEMORY_API_PATH))
+        .and(warp::path::end())
+        .and(authorize_profiling(profiling_token))
+        .and_then(move || {
+            effect_builder
+                .diagnostics_port_memory_usage()
+                .map(|memory_usage| Ok::<_, Rejection>(reply::json(&memory_usage).into_response()))
+        })
+        .boxed()
+}
+
+/// Admits only requests carrying the configured profiling token as a bearer token.
+///
+/// All other requests, and all requests if no token is configured, are rejected as not found so
+/// as not to reveal the existence of the endpoints.
+fn authorize_profiling(profiling_token: Option<String>) -> BoxedFilter<()> {
+    warp::header::optional::<String>(AUTHORIZATION.as_str())
+        .and_then(move |authorization: Option<String>| {
+            let authorized = match (&profiling_token, authorization) {
+                (Some(token), Some(authorization)) => authorization
+                    .strip_prefix("Bearer ")
+                    .map_or(false, |provided| {
+                        constant_time_eq(provided.as_bytes(), token.as_bytes())
+                    }),
+                _ => false,
+            };
+            async move {
+                if authorized {
+                    Ok(())
+                } else {
+                    Err(warp::reject::not_found())
+                }
+            }
+        })
+        .untuple_one()
+        .boxed()
+}
+
+/// Compares two byte strings in time independent of the position of the first difference.
+fn constant_time_eq(lhs: &[u8], rhs: &[u8]) -> bool {
+    lhs.len() == rhs.len()
+        && lhs
+            .iter()
+            .zip(rhs)
+            .fold(0, |acc, (left, right)| acc | (left ^ right))
+            == 0
+}
+
+/// Converts the result of a profiling request into a response holding the path of the written
+/// file.
+fn profile_response(result: Result<PathBuf, ProfilingError>) -> Response<Body> {
+    match result {
+        Ok(path) => reply::json(&path).into_response(),
+        Err(error) => {
+            let status = match error {
+                ProfilingError::DurationTooLong { .. } => StatusCode::BAD_REQUEST,
+                ProfilingError::AlreadyRunning => StatusCode::CONFLICT,
+                _ => {
+                    warn!(%error, "profiling request failed");
+                    StatusCode::INTERNAL_SERVER_ERROR
+                }
+            };
+            reply::with_status(error.to_string(), status).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_require_profiling_token() {
+        let filter = authorize_profiling(Some("secret".to_string()));
+        assert!(warp::test::request()
+            .header("authorization", "Bearer secret")
+            .filter(&filter)
+            .await
+            .is_ok());
+        assert!(warp::test::request()
+            .header("authorization", "Bearer wrong")
+            .filter(&filter)
+            .await
+            .is_err());
+        assert!(warp::test::request().filter(&filter).await.is_err());
+
+        let disabled = authorize_profiling(None);
+        assert!(warp::test::request()
+            .header("authorization", "Bearer secret")
+            .filter(&disabled)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,72 @@
+//! Types supporting the readiness and liveness probes of the REST server.
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::NodeState;
+
+/// The readiness of the node to serve client requests, as reported by the `/health/ready`
+/// endpoint.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Readiness {
+    /// Whether all the checks below passed.
+    pub is_ready: bool,
+    /// Whether the node has finished joining the network.
+    pub is_synced: bool,
+    /// The number of peers the node is connected to.
+    pub peer_count: usize,
+    /// The minimum number of peers required for the node to be ready.
+    pub min_peer_count: usize,
+    /// Whether the node's storage holds at least one block.
+    pub is_storage_healthy: bool,
+}
+
+impl Readiness {
+    /// Returns the readiness of a node in the given state.
+    pub(crate) fn new(
+        node_state: &NodeState,
+        peer_count: usize,
+        min_peer_count: usize,
+        has_highest_block: bool,
+    ) -> Self {
+        // Syncing to genesis happens in the background while the node is already at the tip of
+        // the chain, so doesn't prevent the node from serving requests.
+        let is_synced = match node_state {
+            NodeState::Joining(_) => false,
+            NodeState::ParticipatingAndSyncingToGenesis { .. }
+            | NodeState::Participating
+            | NodeState::Observing => true,
+        };
+        let is_storage_healthy = has_highest_block;
+        Readiness {
+            is_ready: is_synced && peer_count >= min_peer_count && is_storage_healthy,
+            is_synced,
+            peer_count,
+            min_peer_count,
+            is_storage_healthy,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_only_be_ready_if_all_checks_pass() {
+        let readiness = Readiness::new(&NodeState::Participating, 3, 2, true);
+        assert!(readiness.is_ready);
+
+        let joining: NodeState = serde_json::from_str(r#"{"joining":"starting"}"#).unwrap();
+        let readiness = Readiness::new(&joining, 3, 2, true);
+        assert!(!readiness.is_synced);
+        assert!(!readiness.is_ready);
+
+        let readiness = Readiness::new(&NodeState::Participating, 1, 2, true);
+        assert!(!readiness.is_ready);
+
+        let readiness = Readiness::new(&NodeState::Participating, 3, 2, false);
+        assert!(!readiness.is_storage_healthy);
+        assert!(!readiness.is_ready);
+    }
+}
@@ -1,26 +1,36 @@
 use std::{convert::Infallible, time::Duration};
 
 use futures::{future, TryFutureExt};
-use hyper::server::{conn::AddrIncoming, Builder};
+use hyper::Server;
 use tokio::sync::oneshot;
 use tower::builder::ServiceBuilder;
 use tracing::{info, warn};
-use warp::Filter;
+use warp::{cors::Builder as CorsBuilder, Filter};
 
 use casper_types::ProtocolVersion;
 
 use super::{filters, ReactorEventT};
-use crate::effect::EffectBuilder;
+use crate::{
+    effect::EffectBuilder, reactor::supervision::TaskSupervisor, utils::listener::Listener,
+};
 
 /// Run the REST HTTP server.
 ///
-/// A message received on `shutdown_receiver` will cause the server to exit cleanly.
+/// The connection tasks are spawned via `supervisor`.  A message received on `shutdown_receiver`
+/// will cause the server to exit cleanly.  Cross-origin requests are permitted as per `cors`.  The
+/// profiling and memory usage endpoints are served only if a `profiling_token` is given.
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn run<REv: ReactorEventT>(
-    builder: Builder<AddrIncoming>,
+    listener: Listener,
+    supervisor: TaskSupervisor,
     effect_builder: EffectBuilder<REv>,
     api_version: ProtocolVersion,
     shutdown_receiver: oneshot::Receiver<()>,
     qps_limit: u64,
+    readiness_min_peers: usize,
+    redact_peer_reputation: bool,
+    cors: CorsBuilder,
+    profiling_token: Option<String>,
 ) {
     // REST filters.
     let rest_status = filters::create_status_filter(effect_builder, api_version);
@@ -29,6 +39,16 @@ pub(super) async fn run<REv: ReactorEventT>(
     let rest_validator_changes =
         filters::create_validator_changes_filter(effect_builder, api_version);
     let rest_chainspec_filter = filters::create_chainspec_filter(effect_builder, api_version);
+    let rest_block_filter = filters::create_block_filter(effect_builder, api_version);
+    let rest_detailed_peers_filter =
+        filters::create_detailed_peers_filter(effect_builder, redact_peer_reputation);
+    let rest_liveness_filter = filters::create_liveness_filter(effect_builder);
+    let rest_readiness_filter =
+        filters::create_readiness_filter(effect_builder, readiness_min_peers);
+    let rest_cpu_profile_filter = filters::create_cpu_profile_filter(profiling_token.clone());
+    let rest_task_dump_filter = filters::create_task_dump_filter(profiling_token.clone());
+    let rest_memory_usage_filter =
+        filters::create_memory_usage_filter(effect_builder, profiling_token);
 
     let service = warp::service(
         rest_status
@@ -36,7 +56,14 @@ pub(super) async fn run<REv: ReactorEventT>(
             .or(rest_open_rpc)
             .or(rest_validator_changes)
             .or(rest_chainspec_filter)
-            .with(warp::cors().allow_any_origin()),
+            .or(rest_block_filter)
+            .or(rest_detailed_peers_filter)
+            .or(rest_liveness_filter)
+            .or(rest_readiness_filter)
+            .or(rest_cpu_profile_filter)
+            .or(rest_task_dump_filter)
+            .or(rest_memory_usage_filter)
+            .with(cors),
     );
 
     // Start the server, passing a oneshot receiver to allow the server to be shut down gracefully.
@@ -47,8 +74,10 @@ pub(super) async fn run<REv: ReactorEventT>(
         .rate_limit(qps_limit, Duration::from_secs(1))
         .service(make_svc);
 
-    let server = builder.serve(rate_limited_service);
-    info!(address = %server.local_addr(), "started REST server");
+    info!(address = %listener, "started REST server");
+    let server = Server::builder(listener)
+        .executor(supervisor)
+        .serve(rate_limited_service);
 
     // Shutdown the server gracefully.
     let _ = server
@@ -14,25 +14,43 @@
 //! For the list of supported RPC methods, see:
 //! <https://github.com/CasperLabs/ceps/blob/master/text/0009-client-api.md#rpcs>
 
+mod access_control;
 mod config;
 mod event;
 mod http_server;
+mod metrics;
+mod rate_limiter;
 pub mod rpcs;
 mod speculative_exec_config;
 mod speculative_exec_server;
+mod sync_gate;
+mod ws_server;
 
-use std::{convert::Infallible, fmt::Debug, time::Instant};
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    fmt::Debug,
+    mem,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use datasize::DataSize;
 use futures::join;
+use prometheus::Registry;
+use thiserror::Error;
+use tokio::sync::broadcast;
 use tracing::error;
 
 use casper_execution_engine::core::engine_state::{
-    self, BalanceRequest, BalanceResult, GetBidsRequest, GetEraValidatorsError, QueryRequest,
+    self, BalanceRequest, BalanceResult, GasProfile, GetBalancesRequest, GetBidsRequest,
+    GetDictionaryItemsRequest, GetEraValidatorsError, GetStateDiffRequest, QueryRequest,
     QueryResult,
 };
 use casper_hashing::Digest;
-use casper_types::{system::auction::EraValidators, ExecutionResult, Key, ProtocolVersion, URef};
+use casper_types::{
+    system::auction::EraValidators, ExecutionResult, Key, ProtocolVersion, SecretKey, URef,
+};
 
 use self::rpcs::chain::BlockIdentifier;
 use super::Component;
@@ -43,17 +61,34 @@ use crate::{
         announcements::RpcServerAnnouncement,
         requests::{
             ChainspecLoaderRequest, ConsensusRequest, ContractRuntimeRequest, MetricsRequest,
-            NetworkInfoRequest, NodeStateRequest, RpcRequest, StorageRequest,
+            NetworkInfoRequest, NodeStateRequest, RpcRequest, StorageRequest, SyncProgressRequest,
         },
         EffectBuilder, EffectExt, Effects, Responder,
     },
-    types::{BlockHeader, Deploy, StatusFeed},
-    utils::{self, ListeningError},
-    NodeRng,
+    types::{
+        Block, BlockHash, BlockHeader, BlockHeight, Deploy, DeployHash, DeployMetadataExt,
+        StatusFeed,
+    },
+    utils::{self, listener::Listener, ListeningError},
+    NodeRng, WithDir,
 };
-pub use config::Config;
+pub use config::{AccessControlConfig, ApiKeyConfig, Config, CorsConfig};
 pub(crate) use event::Event;
+use metrics::Metrics;
 pub use speculative_exec_config::Config as SpeculativeExecConfig;
+use ws_server::Notification;
+
+/// An error starting the JSON-RPC server.
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    /// An error starting one of the HTTP servers.
+    #[error(transparent)]
+    Listening(#[from] ListeningError),
+
+    /// Metrics-related error.
+    #[error("prometheus (metrics) error: {0}")]
+    Metrics(#[from] prometheus::Error),
+}
 
 /// A helper trait capturing all of this components Request type dependencies.
 pub(crate) trait ReactorEventT:
@@ -67,6 +102,7 @@ pub(crate) trait ReactorEventT:
     + From<NetworkInfoRequest>
     + From<StorageRequest>
     + From<NodeStateRequest>
+    + From<SyncProgressRequest>
     + Send
 {
 }
@@ -82,21 +118,171 @@ impl<REv> ReactorEventT for REv where
         + From<NetworkInfoRequest>
         + From<StorageRequest>
         + From<NodeStateRequest>
+        + From<SyncProgressRequest>
         + Send
         + 'static
 {
 }
 
+/// A request waiting for a deploy to be executed.
+#[derive(DataSize, Debug)]
+struct DeployWaiter {
+    /// When the request times out.
+    deadline: Instant,
+    /// Responder to call with the hash of the block in which the deploy was executed and its
+    /// execution result, or `None` on timeout.
+    responder: Responder<Option<(BlockHash, ExecutionResult)>>,
+}
+
 #[derive(DataSize, Debug)]
 pub(crate) struct InnerRpcServer {
     /// The instant at which the node has started.
     node_startup_instant: Instant,
+    /// The key to sign status responses with, if enabled.
+    #[data_size(skip)]
+    status_signing_key: Option<Arc<SecretKey>>,
+    /// Sender of notifications to WebSocket clients, present only when the WebSocket endpoint is
+    /// enabled.
+    #[data_size(skip)]
+    notification_sender: Option<broadcast::Sender<Arc<Notification>>>,
+    /// Requests waiting for deploys to be executed, by deploy hash.
+    deploy_waiters: HashMap<DeployHash, Vec<DeployWaiter>>,
 }
 
 impl InnerRpcServer {
     pub fn node_startup_instant(&self) -> Instant {
         self.node_startup_instant
     }
+
+    /// Sends the given notification to all WebSocket clients, which forward it to their matching
+    /// subscriptions.
+    fn notify<F>(&self, make_notification: F) -> Effects<Event>
+    where
+        F: FnOnce() -> Result<Notification, serde_json::Error>,
+    {
+        if let Some(notification_sender) = &self.notification_sender {
+            // Only bother encoding the notification if there are connected clients.
+            if notification_sender.receiver_count() > 0 {
+                match make_notification() {
+                    Ok(notification) => {
+                        let _ = notification_sender.send(Arc::new(notification));
+                    }
+                    Err(error) => error!(%error, "failed to encode websocket notification"),
+                }
+            }
+        }
+        Effects::new()
+    }
+
+    /// Registers a request waiting for the given deploy to be executed, and checks whether it
+    /// already has been.
+    fn wait_for_deploy<REv: ReactorEventT>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        deploy_hash: DeployHash,
+        timeout: Duration,
+        responder: Responder<Option<(BlockHash, ExecutionResult)>>,
+    ) -> Effects<Event> {
+        self.deploy_waiters
+            .entry(deploy_hash)
+            .or_default()
+            .push(DeployWaiter {
+                deadline: Instant::now() + timeout,
+                responder,
+            });
+        let mut effects = check_deploy_executed(effect_builder, deploy_hash);
+        effects.extend(
+            effect_builder
+                .set_timeout(timeout)
+                .event(move |_| Event::WaitForDeployTimeout { deploy_hash }),
+        );
+        effects
+    }
+
+    /// Checks whether any of the deploys waited for were executed in the given block.
+    ///
+    /// The block is only announced once its execution results are stored, so they can be read
+    /// from storage.
+    fn check_deploys_in_block<REv: ReactorEventT>(
+        &self,
+        effect_builder: EffectBuilder<REv>,
+        block: &Block,
+    ) -> Effects<Event> {
+        block
+            .deploy_hashes()
+            .iter()
+            .chain(block.transfer_hashes())
+            .filter(|deploy_hash| self.deploy_waiters.contains_key(*deploy_hash))
+            .flat_map(|deploy_hash| check_deploy_executed(effect_builder, *deploy_hash))
+            .collect()
+    }
+
+    /// Responds to all requests waiting for the given deploy if it has been executed.
+    fn handle_wait_for_deploy_result(
+        &mut self,
+        deploy_hash: DeployHash,
+        maybe_execution_result: Option<(BlockHash, ExecutionResult)>,
+    ) -> Effects<Event> {
+        let execution_result = match maybe_execution_result {
+            Some(execution_result) => execution_result,
+            None => return Effects::new(),
+        };
+        self.deploy_waiters
+            .remove(&deploy_hash)
+            .into_iter()
+            .flatten()
+            .flat_map(|waiter| {
+                waiter
+                    .responder
+                    .respond(Some(execution_result.clone()))
+                    .ignore()
+            })
+            .collect()
+    }
+
+    /// Responds with `None` to all requests waiting for the given deploy whose timeout expired.
+    fn handle_wait_for_deploy_timeout(&mut self, deploy_hash: DeployHash) -> Effects<Event> {
+        let waiters = match self.deploy_waiters.get_mut(&deploy_hash) {
+            Some(waiters) => waiters,
+            None => return Effects::new(),
+        };
+        let now = Instant::now();
+        let (expired, remaining): (Vec<_>, Vec<_>) = mem::take(waiters)
+            .into_iter()
+            .partition(|waiter| waiter.deadline <= now);
+        if remaining.is_empty() {
+            self.deploy_waiters.remove(&deploy_hash);
+        } else {
+            *waiters = remaining;
+        }
+        expired
+            .into_iter()
+            .flat_map(|waiter| waiter.responder.respond(None).ignore())
+            .collect()
+    }
+}
+
+/// Reads the execution result of the given deploy from storage, if it has been executed.
+fn check_deploy_executed<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    deploy_hash: DeployHash,
+) -> Effects<Event> {
+    effect_builder
+        .get_deploy_and_metadata_from_storage(deploy_hash)
+        .event(move |result| {
+            let maybe_execution_result = match result {
+                Some((_, DeployMetadataExt::Metadata(metadata))) => {
+                    metadata.execution_results.into_iter().next()
+                }
+                Some((_, DeployMetadataExt::BlockInfo(_)))
+                | Some((_, DeployMetadataExt::Empty))
+                | None => None,
+            };
+            Event::WaitForDeployResult {
+                deploy_hash,
+                maybe_execution_result: Box::new(maybe_execution_result),
+            }
+        })
 }
 
 #[derive(DataSize, Debug)]
@@ -114,22 +300,26 @@ pub(crate) struct RpcServer {
 
 impl RpcServer {
     pub(crate) fn new<REv>(
-        config: Config,
+        config: WithDir<Config>,
         speculative_exec_config: SpeculativeExecConfig,
         effect_builder: EffectBuilder<REv>,
         api_version: ProtocolVersion,
         node_startup_instant: Instant,
-    ) -> Result<Self, ListeningError>
+        signing_key: Option<Arc<SecretKey>>,
+        registry: &Registry,
+    ) -> Result<Self, Error>
     where
         REv: ReactorEventT,
     {
+        let (root, config) = config.into_parts();
+
         // Set the speculative execution HTTP server up first. The speculative
         // execution server can operate independently from the JSON-RPC server,
         // so we save its state before we construct the `RpcServer`.
         let speculative_exec = if speculative_exec_config.enable_server {
-            let builder = utils::start_listening(&speculative_exec_config.address)?;
+            let listener = Listener::tcp(utils::start_listening(&speculative_exec_config.address)?);
             tokio::spawn(speculative_exec_server::run(
-                builder,
+                listener,
                 effect_builder,
                 api_version,
                 speculative_exec_config.qps_limit,
@@ -147,17 +337,38 @@ impl RpcServer {
             });
         }
 
-        let builder = utils::start_listening(&config.address)?;
+        let notification_sender = config
+            .enable_websocket
+            .then(|| broadcast::channel(ws_server::NOTIFICATION_CHANNEL_CAPACITY).0);
+
+        let status_signing_key = signing_key.filter(|_| config.sign_status_responses);
+        let metrics = Arc::new(Metrics::new(registry)?);
+        let tcp = if config.enable_tcp {
+            Some(utils::start_listening(&config.address)?)
+        } else {
+            None
+        };
+        let listener = Listener::bind(
+            tcp,
+            config.unix_socket_path.as_ref().map(|path| root.join(path)),
+            // Mac OS X / Linux use different types for the mask, so we need to call .into() here.
+            #[allow(clippy::useless_conversion)]
+            config.unix_socket_umask.into(),
+        )?;
         tokio::spawn(http_server::run(
-            builder,
+            listener,
             effect_builder,
             api_version,
-            config.qps_limit,
-            config.max_body_bytes,
+            config,
+            notification_sender.clone(),
+            metrics,
         ));
 
         let inner_rpc = Some(InnerRpcServer {
             node_startup_instant,
+            status_signing_key,
+            notification_sender,
+            deploy_waiters: HashMap::new(),
         });
 
         Ok(RpcServer {
@@ -222,7 +433,10 @@ impl RpcServer {
         effect_builder: EffectBuilder<REv>,
         block_header: BlockHeader,
         deploy: Deploy,
-        responder: Responder<Result<Option<ExecutionResult>, engine_state::Error>>,
+        profile_gas: bool,
+        responder: Responder<
+            Result<Option<(ExecutionResult, Option<GasProfile>)>, engine_state::Error>,
+        >,
     ) -> Effects<Event> {
         async move {
             let execution_prestate = SpeculativeExecutionState {
@@ -230,9 +444,23 @@ impl RpcServer {
                 block_time: block_header.timestamp(),
                 protocol_version: block_header.protocol_version(),
             };
-            let result = effect_builder
-                .speculative_execute_deploy(execution_prestate, deploy)
-                .await;
+            let result = if profile_gas {
+                effect_builder
+                    .profile_deploy_gas(execution_prestate, deploy)
+                    .await
+                    .map(|maybe_result| {
+                        maybe_result.map(|(execution_result, gas_profile)| {
+                            (execution_result, Some(gas_profile))
+                        })
+                    })
+            } else {
+                effect_builder
+                    .speculative_execute_deploy(execution_prestate, deploy)
+                    .await
+                    .map(|maybe_result| {
+                        maybe_result.map(|execution_result| (execution_result, None))
+                    })
+            };
             responder.respond(result).await
         }
         .ignore()
@@ -257,6 +485,7 @@ where
         if let Event::RpcRequest(RpcRequest::SpeculativeDeployExecute {
             block_header,
             deploy,
+            profile_gas,
             responder,
         }) = event
         {
@@ -266,6 +495,7 @@ where
                         effect_builder,
                         block_header,
                         *deploy,
+                        profile_gas,
                         responder,
                     );
                 }
@@ -277,7 +507,7 @@ where
 
         // For all requests other than `SpeculativeDeployExecute`, we return
         // empty effects if the JSON-RPC server is disabled.
-        let rpc_server = match &self.inner_rpc {
+        let rpc_server = match &mut self.inner_rpc {
             Some(rpc_server) => rpc_server,
             None => {
                 return Effects::new();
@@ -288,6 +518,18 @@ where
             Event::RpcRequest(RpcRequest::SubmitDeploy { deploy, responder }) => effect_builder
                 .announce_deploy_received(deploy, Some(responder))
                 .ignore(),
+            Event::RpcRequest(RpcRequest::SubmitPendingDeploy { deploy, responder }) => {
+                effect_builder
+                    .announce_pending_deploy_received(deploy, responder)
+                    .ignore()
+            }
+            Event::RpcRequest(RpcRequest::AddDeployApprovals {
+                deploy_hash,
+                approvals,
+                responder,
+            }) => effect_builder
+                .announce_deploy_approvals_received(deploy_hash, approvals, responder)
+                .ignore(),
             Event::RpcRequest(RpcRequest::GetBlock {
                 maybe_id: Some(BlockIdentifier::Hash(hash)),
                 only_from_available_block_range,
@@ -305,7 +547,7 @@ where
                 responder,
             }) => effect_builder
                 .get_block_at_height_with_metadata_from_storage(
-                    height,
+                    BlockHeight::from(height),
                     only_from_available_block_range,
                 )
                 .event(move |result| Event::GetBlockResult {
@@ -325,6 +567,53 @@ where
                     result: Box::new(result),
                     main_responder: responder,
                 }),
+            Event::RpcRequest(RpcRequest::GetBlockHeader {
+                maybe_id,
+                responder,
+            }) => async move {
+                // Block headers are verified before being stored, so they can be served regardless
+                // of the block availability index.
+                let maybe_block_header_with_metadata = match maybe_id {
+                    Some(BlockIdentifier::Hash(block_hash)) => {
+                        effect_builder
+                            .get_block_header_with_metadata_from_storage(block_hash, false)
+                            .await
+                    }
+                    Some(BlockIdentifier::Height(height)) => {
+                        effect_builder
+                            .get_block_header_with_metadata_from_storage_by_height(
+                                BlockHeight::from(height),
+                                false,
+                            )
+                            .await
+                    }
+                    None => match effect_builder.get_highest_block_header_from_storage().await {
+                        Some(block_header) => {
+                            effect_builder
+                                .get_block_header_with_metadata_from_storage(
+                                    block_header.hash(),
+                                    false,
+                                )
+                                .await
+                        }
+                        None => None,
+                    },
+                };
+                responder.respond(maybe_block_header_with_metadata).await
+            }
+            .ignore(),
+            Event::RpcRequest(RpcRequest::GetSwitchBlockHeader { era_id, responder }) => {
+                async move {
+                    responder
+                        .respond(
+                            effect_builder
+                                .get_switch_block_header_at_era_id_from_storage(era_id)
+                                .await,
+                        )
+                        .await
+                }
+                .ignore()
+            }
             Event::RpcRequest(RpcRequest::GetBlockTransfers {
                 block_hash,
                 responder,
@@ -335,6 +624,23 @@ where
                     result: Box::new(result),
                     main_responder: responder,
                 }),
+            Event::RpcRequest(RpcRequest::GetBlockDeploys {
+                block_hash,
+                deploy_hashes,
+                responder,
+            }) => async move {
+                responder
+                    .respond(
+                        effect_builder
+                            .get_block_deploys_with_execution_results_from_storage(
+                                block_hash,
+                                deploy_hashes,
+                            )
+                            .await,
+                    )
+                    .await
+            }
+            .ignore(),
             Event::RpcRequest(RpcRequest::QueryGlobalState {
                 state_root_hash,
                 base_key,
@@ -363,6 +669,59 @@ where
                         main_responder: responder,
                     })
             }
+            Event::RpcRequest(RpcRequest::GetBalances {
+                state_root_hash,
+                purses,
+                responder,
+            }) => {
+                let get_balances_request = GetBalancesRequest::new(state_root_hash, purses);
+                async move {
+                    responder
+                        .respond(effect_builder.get_balances(get_balances_request).await)
+                        .await
+                }
+                .ignore()
+            }
+            Event::RpcRequest(RpcRequest::GetDictionaryItems {
+                state_root_hash,
+                seed_uref,
+                after,
+                limit,
+                responder,
+            }) => {
+                let get_dictionary_items_request =
+                    GetDictionaryItemsRequest::new(state_root_hash, seed_uref, after, limit);
+                async move {
+                    responder
+                        .respond(
+                            effect_builder
+                                .get_dictionary_items(get_dictionary_items_request)
+                                .await,
+                        )
+                        .await
+                }
+                .ignore()
+            }
+            Event::RpcRequest(RpcRequest::GetStateDiff {
+                old_state_root_hash,
+                new_state_root_hash,
+                after,
+                limit,
+                responder,
+            }) => {
+                let get_state_diff_request = GetStateDiffRequest::new(
+                    old_state_root_hash,
+                    new_state_root_hash,
+                    after,
+                    limit,
+                );
+                async move {
+                    responder
+                        .respond(effect_builder.get_state_diff(get_state_diff_request).await)
+                        .await
+                }
+                .ignore()
+            }
             Event::RpcRequest(RpcRequest::GetBalance {
                 state_root_hash,
                 purse_uref,
@@ -390,6 +749,13 @@ where
                     )),
                     main_responder: responder,
                 }),
+            Event::RpcRequest(RpcRequest::WaitForDeploy {
+                deploy_hash,
+                timeout,
+                responder,
+            }) => {
+                rpc_server.wait_for_deploy(effect_builder, deploy_hash, timeout.into(), responder)
+            }
             Event::RpcRequest(RpcRequest::GetPeers { responder }) => effect_builder
                 .network_peers()
                 .event(move |peers| Event::GetPeersResult {
@@ -398,13 +764,22 @@ where
                 }),
             Event::RpcRequest(RpcRequest::GetStatus { responder }) => {
                 let node_uptime = rpc_server.node_startup_instant().elapsed();
+                let status_signing_key = rpc_server.status_signing_key.clone();
                 async move {
-                    let (last_added_block, peers, chainspec_info, consensus_status, node_state) = join!(
+                    let (
+                        last_added_block,
+                        peers,
+                        chainspec_info,
+                        consensus_status,
+                        node_state,
+                        sync_progress,
+                    ) = join!(
                         effect_builder.get_highest_block_from_storage(),
                         effect_builder.network_peers(),
                         effect_builder.get_chainspec_info(),
                         effect_builder.consensus_status(),
-                        effect_builder.get_node_state()
+                        effect_builder.get_node_state(),
+                        effect_builder.get_sync_progress()
                     );
                     let status_feed = StatusFeed::new(
                         last_added_block,
@@ -413,6 +788,8 @@ where
                         consensus_status,
                         node_uptime,
                         node_state,
+                        sync_progress,
+                        status_signing_key,
                     );
                     responder.respond(status_feed).await;
                 }
@@ -436,6 +813,30 @@ where
                 );
                 Effects::new()
             }
+            Event::BlockAdded(block) => {
+                let mut effects = rpc_server.check_deploys_in_block(effect_builder, &block);
+                effects.extend(rpc_server.notify(|| Notification::block_added(*block)));
+                effects
+            }
+            Event::FinalitySignature(fs, signed_weight) => {
+                rpc_server.notify(|| Notification::finality_signature(fs, signed_weight))
+            }
+            Event::DeployAccepted(deploy) => {
+                rpc_server.notify(|| Notification::deploy_accepted(*deploy))
+            }
+            Event::DeployProcessed {
+                deploy_hash,
+                deploy_header,
+                block_hash,
+                execution_result,
+            } => rpc_server.notify(|| {
+                Notification::deploy_processed(
+                    deploy_hash,
+                    *deploy_header,
+                    block_hash,
+                    execution_result,
+                )
+            }),
             Event::GetBlockResult {
                 maybe_id: _,
                 result,
@@ -471,16 +872,73 @@ where
                 peers,
                 main_responder,
             } => main_responder.respond(peers).ignore(),
+            Event::WaitForDeployResult {
+                deploy_hash,
+                maybe_execution_result,
+            } => rpc_server.handle_wait_for_deploy_result(deploy_hash, *maybe_execution_result),
+            Event::WaitForDeployTimeout { deploy_hash } => {
+                rpc_server.handle_wait_for_deploy_timeout(deploy_hash)
+            }
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use futures::channel::oneshot;
     use schemars::schema_for_value;
 
+    use super::*;
     use crate::{rpcs::docs::OPEN_RPC_SCHEMA, testing::assert_schema};
 
+    #[tokio::test]
+    async fn should_respond_to_deploy_waiters_on_timeout_or_execution() {
+        let mut rng = crate::new_rng();
+        let deploy_hash = DeployHash::random(&mut rng);
+        let now = Instant::now();
+        let (expired_sender, expired_receiver) = oneshot::channel();
+        let (pending_sender, pending_receiver) = oneshot::channel();
+        let mut rpc_server = InnerRpcServer {
+            node_startup_instant: now,
+            status_signing_key: None,
+            notification_sender: None,
+            deploy_waiters: HashMap::new(),
+        };
+        rpc_server.deploy_waiters.insert(
+            deploy_hash,
+            vec![
+                DeployWaiter {
+                    deadline: now,
+                    responder: Responder::without_shutdown(expired_sender),
+                },
+                DeployWaiter {
+                    deadline: now + Duration::from_secs(60),
+                    responder: Responder::without_shutdown(pending_sender),
+                },
+            ],
+        );
+
+        // Only the waiter whose deadline passed should be responded to on timeout.
+        for effect in rpc_server.handle_wait_for_deploy_timeout(deploy_hash) {
+            effect.await;
+        }
+        assert_eq!(expired_receiver.await, Ok(None));
+        assert_eq!(rpc_server.deploy_waiters[&deploy_hash].len(), 1);
+
+        // The remaining waiter should be responded to once the deploy is executed.
+        let execution_result = (
+            BlockHash::random(&mut rng),
+            ExecutionResult::example().clone(),
+        );
+        for effect in
+            rpc_server.handle_wait_for_deploy_result(deploy_hash, Some(execution_result.clone()))
+        {
+            effect.await;
+        }
+        assert_eq!(pending_receiver.await, Ok(Some(execution_result)));
+        assert!(rpc_server.deploy_waiters.is_empty());
+    }
+
     #[test]
     fn schema() {
         // To generate the contents to replace the input JSON file, uncomment the `println!` and run
@@ -0,0 +1,258 @@
+//! API key based access control for the JSON-RPC server.
+//!
+//! Clients identify themselves by passing an API key in the `X-API-Key` HTTP header.  Each kind of
+//! client is served by its own set of request handlers, in which the methods it may not call
+//! always fail with a `MethodNotPermitted` error.
+
+use std::{collections::HashSet, sync::Arc};
+
+use http::StatusCode;
+use serde_json::json;
+use warp::{
+    filters::BoxedFilter,
+    reject::{self, Rejection},
+    reply, Filter, Reply,
+};
+
+use casper_hashing::Digest;
+use casper_json_rpc::{Error, RequestHandlers, RequestHandlersBuilder};
+
+use super::{
+    config::{AccessControlConfig, ALL_METHODS},
+    rpcs::ErrorCode,
+};
+
+/// The HTTP header in which clients pass their API key.
+pub(super) const API_KEY_HEADER: &str = "x-api-key";
+
+/// The request handlers applicable to each kind of client.
+#[derive(Clone)]
+pub(super) struct AccessControl {
+    /// Handlers for clients not providing an API key.
+    public_handlers: RequestHandlers,
+    /// Handlers for clients providing the API key with the given digest.
+    ///
+    /// Only the digests of the API keys are compared against those of the provided ones, so that
+    /// the time taken by the comparison reveals nothing about the keys themselves.
+    api_key_handlers: Vec<(Digest, RequestHandlers)>,
+}
+
+impl AccessControl {
+    /// Returns an instance permitting all clients to call all methods of `handlers`.
+    pub(super) fn unrestricted(handlers: RequestHandlers) -> Self {
+        AccessControl {
+            public_handlers: handlers,
+            api_key_handlers: vec![],
+        }
+    }
+
+    /// Returns an instance restricting the methods of `handlers` as specified in `config`.
+    pub(super) fn new(config: &AccessControlConfig, handlers: &RequestHandlers) -> Self {
+        let public_handlers = restrict(
+            handlers,
+            &config.public_methods,
+            "an API key is required to call this method",
+        );
+        let api_key_handlers = config
+            .api_keys
+            .iter()
+            .map(|api_key| {
+                (
+                    Digest::hash(&api_key.key),
+                    restrict(
+                        handlers,
+                        &api_key.allowed_methods,
+                        "the provided API key does not permit calling this method",
+                    ),
+                )
+            })
+            .collect();
+        AccessControl {
+            public_handlers,
+            api_key_handlers,
+        }
+    }
+
+    /// Returns a filter serving each client via the route constructed by `make_route` from the
+    /// handlers applicable to that client.
+    ///
+    /// Clients providing an unknown API key are refused with "401 Unauthorized".
+    pub(super) fn route<F>(&self, make_route: F) -> BoxedFilter<(Box<dyn Reply>,)>
+    where
+        F: Fn(RequestHandlers) -> BoxedFilter<(Box<dyn Reply>,)>,
+    {
+        let public_route = warp::header::optional::<String>(API_KEY_HEADER)
+            .and_then(|maybe_api_key: Option<String>| async move {
+                match maybe_api_key {
+                    None => Ok(()),
+                    Some(_) => Err::<(), Rejection>(reject::not_found()),
+                }
+            })
+            .untuple_one()
+            .and(make_route(self.public_handlers.clone()))
+            .boxed();
+
+        let route =
+            self.api_key_handlers
+                .iter()
+                .fold(public_route, |route, (api_key_digest, handlers)| {
+                    let api_key_digest = *api_key_digest;
+                    let api_key_route = warp::header::<String>(API_KEY_HEADER)
+                        .and_then(move |provided_api_key: String| {
+                            let is_match = Digest::hash(&provided_api_key) == api_key_digest;
+                            async move {
+                                if is_match {
+                                    Ok(())
+                                } else {
+                                    Err::<(), Rejection>(reject::not_found())
+                                }
+                            }
+                        })
+                        .untuple_one()
+                        .and(make_route(handlers.clone()));
+                    route.or(api_key_route).unify().boxed()
+                });
+
+        let known_api_key_digests: Arc<HashSet<Digest>> = Arc::new(
+            self.api_key_handlers
+                .iter()
+                .map(|(api_key_digest, _)| *api_key_digest)
+                .collect(),
+        );
+        let unknown_api_key_route =
+            warp::header::<String>(API_KEY_HEADER).and_then(move |provided_api_key: String| {
+                let is_known = known_api_key_digests.contains(&Digest::hash(&provided_api_key));
+                async move {
+                    if is_known {
+                        // Known keys which didn't match the route are left to other routes.
+                        return Err(reject::not_found());
+                    }
+                    let reply: Box<dyn Reply> = Box::new(reply::with_status(
+                        reply::json(&json!({ "message": "invalid API key" })),
+                        StatusCode::UNAUTHORIZED,
+                    ));
+                    Ok::<_, Rejection>(reply)
+                }
+            });
+
+        route.or(unknown_api_key_route).unify().boxed()
+    }
+}
+
+/// Returns a copy of `handlers` in which all methods not listed in `allowed_methods` always fail
+/// with the given message.
+fn restrict(
+    handlers: &RequestHandlers,
+    allowed_methods: &[String],
+    message: &str,
+) -> RequestHandlers {
+    let mut builder = RequestHandlersBuilder::from_handlers(handlers);
+    if !allowed_methods.iter().any(|method| method == ALL_METHODS) {
+        builder.deny_methods(
+            |method| allowed_methods.iter().any(|allowed| allowed == method),
+            Error::new(ErrorCode::MethodNotPermitted, message),
+        );
+    }
+    builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_json_rpc::{Params, Response};
+
+    use super::*;
+    use crate::components::rpc_server::config::ApiKeyConfig;
+
+    const API_KEY: &str = "secret";
+
+    async fn get(_params: Option<Params>) -> Result<&'static str, Error> {
+        Ok("got it")
+    }
+
+    async fn put(_params: Option<Params>) -> Result<&'static str, Error> {
+        Ok("put it")
+    }
+
+    fn test_route() -> BoxedFilter<(Box<dyn Reply>,)> {
+        let mut handlers = RequestHandlersBuilder::new();
+        handlers.register_handler("get", Arc::new(get));
+        handlers.register_handler("put", Arc::new(put));
+        let handlers = handlers.build();
+
+        let config = AccessControlConfig {
+            public_methods: vec!["get".to_string()],
+            api_keys: vec![ApiKeyConfig {
+                key: API_KEY.to_string(),
+                allowed_methods: vec![ALL_METHODS.to_string()],
+            }],
+        };
+        AccessControl::new(&config, &handlers).route(|handlers| {
+            casper_json_rpc::route("rpc", 1024, handlers, false)
+                .map(|reply| {
+                    let reply: Box<dyn Reply> = Box::new(reply);
+                    reply
+                })
+                .boxed()
+        })
+    }
+
+    async fn send_request(
+        method: &str,
+        maybe_api_key: Option<&str>,
+        route: &BoxedFilter<(Box<dyn Reply>,)>,
+    ) -> (StatusCode, Option<Response>) {
+        let mut request = warp::test::request()
+            .method("POST")
+            .path("/rpc")
+            .header("content-type", "application/json")
+            .body(format!(
+                r#"{{"jsonrpc":"2.0","id":1,"method":"{}"}}"#,
+                method
+            ));
+        if let Some(api_key) = maybe_api_key {
+            request = request.header(API_KEY_HEADER, api_key);
+        }
+        let http_response = request.filter(route).await.unwrap().into_response();
+        let status = http_response.status();
+        let body_bytes = hyper::body::to_bytes(http_response.into_body())
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body_bytes).ok())
+    }
+
+    #[tokio::test]
+    async fn should_restrict_public_methods() {
+        let route = test_route();
+
+        let (status, response) = send_request("get", None, &route).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(response.unwrap().result::<String>().unwrap(), "got it");
+
+        let (status, response) = send_request("put", None, &route).await;
+        assert_eq!(status, StatusCode::OK);
+        let expected_error = Error::new(
+            ErrorCode::MethodNotPermitted,
+            "an API key is required to call this method",
+        );
+        assert_eq!(response.unwrap().error(), Some(&expected_error));
+    }
+
+    #[tokio::test]
+    async fn should_permit_methods_allowed_for_api_key() {
+        let route = test_route();
+
+        for (method, expected) in [("get", "got it"), ("put", "put it")] {
+            let (status, response) = send_request(method, Some(API_KEY), &route).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(response.unwrap().result::<String>().unwrap(), expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn should_refuse_unknown_api_key() {
+        let route = test_route();
+
+        let (status, _) = send_request("get", Some("wrong"), &route).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+}
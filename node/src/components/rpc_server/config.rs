@@ -1,15 +1,40 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    path::PathBuf,
+    str::FromStr,
+};
+
 use datasize::DataSize;
+use http::{Method, Uri};
 use serde::{Deserialize, Serialize};
+use tracing::warn;
+use warp::cors::Builder as CorsBuilder;
 
 /// Default binding address for the JSON-RPC HTTP server.
 ///
 /// Uses a fixed port per node, but binds on any interface.
 const DEFAULT_ADDRESS: &str = "0.0.0.0:0";
+/// Default umask to apply before creating the unix domain socket.
+const DEFAULT_UNIX_SOCKET_UMASK: u16 = 0o077;
 /// Default rate limit in qps.
 const DEFAULT_QPS_LIMIT: u64 = 100;
 /// Default max body bytes.  This is 2.5MB which should be able to accommodate the largest valid
 /// JSON-RPC request, which would be an "account_put_deploy".
 const DEFAULT_MAX_BODY_BYTES: u32 = 2_621_440;
+/// Default rate limit per client IP address in qps, where 0 means unlimited.
+const DEFAULT_CLIENT_QPS_LIMIT: u64 = 0;
+/// Default maximum number of requests handled concurrently for each JSON-RPC method, where 0 means
+/// unlimited.
+const DEFAULT_MAX_CONCURRENT_REQUESTS_PER_METHOD: u32 = 0;
+/// Default maximum number of subscriptions held by a single WebSocket client.
+const DEFAULT_MAX_SUBSCRIPTIONS_PER_CONNECTION: u32 = 16;
+/// Default maximum number of blocks the node may be behind the tip of the chain while serving
+/// requests, where 0 means unlimited.
+const DEFAULT_MAX_BLOCKS_BEHIND: u64 = 0;
+/// Entry of a method allowlist which permits all methods.
+pub(super) const ALL_METHODS: &str = "*";
+/// Entry of an origin allowlist which permits all origins.
+const ALL_ORIGINS: &str = "*";
 
 /// JSON-RPC HTTP server configuration.
 #[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
@@ -20,10 +45,52 @@ pub struct Config {
     pub enable_server: bool,
     /// Address to bind JSON-RPC HTTP server to.
     pub address: String,
+    /// Whether to listen on `address` via TCP.  Can be disabled to only listen on the unix domain
+    /// socket.
+    #[serde(default = "default_enable_tcp")]
+    pub enable_tcp: bool,
+    /// Path of a unix domain socket to listen on, if any.
+    #[serde(default)]
+    pub unix_socket_path: Option<PathBuf>,
+    /// `umask` to apply before creating the unix domain socket.
+    #[serde(default = "default_unix_socket_umask")]
+    pub unix_socket_umask: u16,
     /// Maximum rate limit in queries per second.
     pub qps_limit: u64,
     /// Maximum number of bytes to accept in a single request body.
     pub max_body_bytes: u32,
+    /// Maximum rate limit in queries per second for a single client IP address, or 0 for no limit.
+    #[serde(default = "default_client_qps_limit")]
+    pub client_qps_limit: u64,
+    /// Whether to identify clients for rate limiting by the last address in the `X-Forwarded-For`
+    /// header, as appended by a reverse proxy, rather than by the address of the connection.
+    #[serde(default)]
+    pub trust_forwarded_for: bool,
+    /// Maximum number of requests handled concurrently for each JSON-RPC method, or 0 for no
+    /// limit.  Disabled by default; a limit keeps expensive methods, e.g. global state queries,
+    /// from starving the others when set to a few times the number of CPU cores.
+    #[serde(default = "default_max_concurrent_requests_per_method")]
+    pub max_concurrent_requests_per_method: u32,
+    /// Maximum number of blocks the node may be behind the tip of the chain while serving requests
+    /// which depend on the state of the chain, or 0 for no limit.
+    #[serde(default = "default_max_blocks_behind")]
+    pub max_blocks_behind: u64,
+    /// Setting to enable the WebSocket endpoint, served under the `/ws` path of the JSON-RPC HTTP
+    /// server.
+    #[serde(default)]
+    pub enable_websocket: bool,
+    /// Maximum number of subscriptions a single WebSocket client may hold at once.
+    #[serde(default = "default_max_subscriptions_per_connection")]
+    pub max_subscriptions_per_connection: u32,
+    /// Restrictions on which JSON-RPC methods may be called by which clients.
+    #[serde(default)]
+    pub access_control: AccessControlConfig,
+    /// Cross-origin resource sharing settings, applicable to browser-based clients.
+    #[serde(default = "default_cors")]
+    pub cors: CorsConfig,
+    /// Whether to sign `info_get_status` responses with the node's validator key.
+    #[serde(default)]
+    pub sign_status_responses: bool,
 }
 
 impl Config {
@@ -32,14 +99,207 @@ impl Config {
         Config {
             enable_server: true,
             address: DEFAULT_ADDRESS.to_string(),
+            enable_tcp: true,
+            unix_socket_path: None,
+            unix_socket_umask: DEFAULT_UNIX_SOCKET_UMASK,
             qps_limit: DEFAULT_QPS_LIMIT,
             max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            client_qps_limit: DEFAULT_CLIENT_QPS_LIMIT,
+            trust_forwarded_for: false,
+            max_concurrent_requests_per_method: DEFAULT_MAX_CONCURRENT_REQUESTS_PER_METHOD,
+            max_blocks_behind: DEFAULT_MAX_BLOCKS_BEHIND,
+            enable_websocket: false,
+            max_subscriptions_per_connection: DEFAULT_MAX_SUBSCRIPTIONS_PER_CONNECTION,
+            access_control: AccessControlConfig::default(),
+            cors: default_cors(),
+            sign_status_responses: false,
         }
     }
 }
 
+/// The default for `Config::enable_tcp`.
+fn default_enable_tcp() -> bool {
+    true
+}
+
+/// The default for `Config::unix_socket_umask`.
+fn default_unix_socket_umask() -> u16 {
+    DEFAULT_UNIX_SOCKET_UMASK
+}
+
+/// The default for `Config::client_qps_limit`.
+fn default_client_qps_limit() -> u64 {
+    DEFAULT_CLIENT_QPS_LIMIT
+}
+
+/// The default for `Config::max_concurrent_requests_per_method`.
+fn default_max_concurrent_requests_per_method() -> u32 {
+    DEFAULT_MAX_CONCURRENT_REQUESTS_PER_METHOD
+}
+
+/// The default for `Config::max_blocks_behind`.
+fn default_max_blocks_behind() -> u64 {
+    DEFAULT_MAX_BLOCKS_BEHIND
+}
+
+/// The default for `Config::max_subscriptions_per_connection`.
+fn default_max_subscriptions_per_connection() -> u32 {
+    DEFAULT_MAX_SUBSCRIPTIONS_PER_CONNECTION
+}
+
+/// The default for `Config::cors`.
+fn default_cors() -> CorsConfig {
+    CorsConfig::allow_any_origin(&["POST"])
+}
+
 impl Default for Config {
     fn default() -> Self {
         Config::new()
     }
 }
+
+/// Access control configuration for the JSON-RPC HTTP server.
+///
+/// Clients identify themselves by passing an API key in the `X-API-Key` HTTP header.
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct AccessControlConfig {
+    /// Methods which may be called by clients not providing an API key.  An entry of `"*"`
+    /// permits all methods.
+    pub public_methods: Vec<String>,
+    /// The accepted API keys.
+    #[serde(default)]
+    pub api_keys: Vec<ApiKeyConfig>,
+}
+
+impl Default for AccessControlConfig {
+    fn default() -> Self {
+        AccessControlConfig {
+            public_methods: vec![ALL_METHODS.to_string()],
+            api_keys: vec![],
+        }
+    }
+}
+
+/// An API key along with the methods it permits calling.
+#[derive(Clone, DataSize, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct ApiKeyConfig {
+    /// The secret key, passed by clients in the `X-API-Key` HTTP header.
+    pub key: String,
+    /// Methods which may be called by clients providing this key.  An entry of `"*"` permits all
+    /// methods.
+    pub allowed_methods: Vec<String>,
+}
+
+// Manual implementation to avoid leaking the key into logs.
+impl Debug for ApiKeyConfig {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("ApiKeyConfig")
+            .field("key", &"<redacted>")
+            .field("allowed_methods", &self.allowed_methods)
+            .finish()
+    }
+}
+
+/// Cross-origin resource sharing (CORS) configuration for an HTTP server.
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct CorsConfig {
+    /// Origins permitted to make cross-origin requests, e.g. `"https://example.com"`.  An entry of
+    /// `"*"` permits all origins.
+    pub allowed_origins: Vec<String>,
+    /// HTTP methods permitted in cross-origin requests.
+    pub allowed_methods: Vec<String>,
+}
+
+impl CorsConfig {
+    /// Returns an instance permitting all origins to make cross-origin requests using the given
+    /// methods.
+    pub(crate) fn allow_any_origin(allowed_methods: &[&str]) -> Self {
+        CorsConfig {
+            allowed_origins: vec![ALL_ORIGINS.to_string()],
+            allowed_methods: allowed_methods
+                .iter()
+                .map(|method| method.to_string())
+                .collect(),
+        }
+    }
+
+    /// Returns a warp CORS filter builder as per this config.
+    ///
+    /// Invalid origins and methods are ignored with a warning, i.e. they are not permitted.
+    pub(crate) fn to_cors_builder(&self) -> CorsBuilder {
+        let allowed_methods = self
+            .allowed_methods
+            .iter()
+            .filter(|method| match Method::from_str(method) {
+                Ok(_) => true,
+                Err(error) => {
+                    warn!(%method, %error, "ignoring invalid CORS method");
+                    false
+                }
+            })
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        let cors = warp::cors().allow_methods(allowed_methods);
+
+        if self
+            .allowed_origins
+            .iter()
+            .any(|origin| origin == ALL_ORIGINS)
+        {
+            return cors.allow_any_origin();
+        }
+
+        let allowed_origins = self
+            .allowed_origins
+            .iter()
+            .filter_map(|origin| match parse_origin(origin) {
+                Some(origin) => Some(origin),
+                None => {
+                    warn!(%origin, "ignoring invalid CORS origin");
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        cors.allow_origins(allowed_origins.iter().map(String::as_str))
+    }
+}
+
+/// Returns `origin` normalized to the form "<scheme>://<host>[:<port>]", or `None` if it is not a
+/// valid origin.
+fn parse_origin(origin: &str) -> Option<String> {
+    let uri = Uri::from_str(origin).ok()?;
+    let scheme = uri.scheme_str()?;
+    let authority = uri.authority()?;
+    if !matches!(uri.path(), "" | "/") || uri.query().is_some() {
+        return None;
+    }
+    Some(format!("{}://{}", scheme, authority))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_origins() {
+        assert_eq!(
+            parse_origin("https://example.com"),
+            Some("https://example.com".to_string())
+        );
+        assert_eq!(
+            parse_origin("http://localhost:8080/"),
+            Some("http://localhost:8080".to_string())
+        );
+        assert_eq!(parse_origin("example.com"), None);
+        assert_eq!(parse_origin("https://example.com/path"), None);
+        assert_eq!(parse_origin("https://example.com?query"), None);
+        assert_eq!(parse_origin("not an origin"), None);
+    }
+}
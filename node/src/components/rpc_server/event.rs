@@ -8,12 +8,15 @@ use derive_more::From;
 use casper_execution_engine::core::engine_state::{
     self, BalanceResult, GetBidsResult, GetEraValidatorsError, QueryResult,
 };
-use casper_types::{system::auction::EraValidators, Transfer};
+use casper_types::{system::auction::EraValidators, ExecutionResult, Transfer};
 
 use crate::{
     effect::{requests::RpcRequest, Responder},
     rpcs::chain::BlockIdentifier,
-    types::{BlockHash, BlockWithMetadata, Deploy, DeployHash, DeployMetadataExt, NodeId},
+    types::{
+        Block, BlockHash, BlockWithMetadata, Deploy, DeployHash, DeployHeader, DeployMetadataExt,
+        FinalitySignature, NodeId, SignedWeight,
+    },
 };
 
 #[derive(Debug, From)]
@@ -55,6 +58,27 @@ pub(crate) enum Event {
         result: Result<BalanceResult, engine_state::Error>,
         main_responder: Responder<Result<BalanceResult, engine_state::Error>>,
     },
+    /// The execution result of a deploy being waited for has been read from storage.
+    WaitForDeployResult {
+        deploy_hash: DeployHash,
+        maybe_execution_result: Box<Option<(BlockHash, ExecutionResult)>>,
+    },
+    /// The timeout of a request waiting for a deploy has expired.
+    WaitForDeployTimeout { deploy_hash: DeployHash },
+    /// A block has been added to the linear chain, to be notified to WebSocket subscribers.
+    BlockAdded(Box<Block>),
+    /// A finality signature has been received, to be notified to WebSocket subscribers along with
+    /// the cumulative weight of the block's known signatures.
+    FinalitySignature(Box<FinalitySignature>, Option<Box<SignedWeight>>),
+    /// A deploy has been newly accepted, to be notified to WebSocket subscribers.
+    DeployAccepted(Box<Deploy>),
+    /// A deploy has been executed, to be notified to WebSocket subscribers.
+    DeployProcessed {
+        deploy_hash: DeployHash,
+        deploy_header: Box<DeployHeader>,
+        block_hash: BlockHash,
+        execution_result: Box<ExecutionResult>,
+    },
 }
 
 impl Display for Event {
@@ -99,6 +123,24 @@ impl Display for Event {
                 write!(formatter, "get deploy result for {}: {:?}", hash, result)
             }
             Event::GetPeersResult { peers, .. } => write!(formatter, "get peers: {}", peers.len()),
+            Event::WaitForDeployResult {
+                deploy_hash,
+                maybe_execution_result,
+            } => write!(
+                formatter,
+                "wait for deploy result for {}: executed: {}",
+                deploy_hash,
+                maybe_execution_result.is_some()
+            ),
+            Event::WaitForDeployTimeout { deploy_hash } => {
+                write!(formatter, "wait for {} timed out", deploy_hash)
+            }
+            Event::BlockAdded(block) => write!(formatter, "block added {}", block.hash()),
+            Event::FinalitySignature(fs, _) => write!(formatter, "finality signature {}", fs),
+            Event::DeployAccepted(deploy) => write!(formatter, "deploy accepted {}", deploy.id()),
+            Event::DeployProcessed { deploy_hash, .. } => {
+                write!(formatter, "deploy processed {}", deploy_hash)
+            }
         }
     }
 }
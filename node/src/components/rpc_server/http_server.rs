@@ -1,23 +1,38 @@
-use hyper::server::{conn::AddrIncoming, Builder};
+use std::sync::Arc;
 
-use casper_json_rpc::RequestHandlersBuilder;
+use tokio::sync::broadcast;
+
+use casper_json_rpc::{Error, RequestHandlersBuilder};
 use casper_types::ProtocolVersion;
 
 use super::{
+    access_control::AccessControl,
+    metrics::Metrics,
+    rate_limiter::ClientRateLimiter,
     rpcs::{
-        account::PutDeploy,
-        chain::{GetBlock, GetBlockTransfers, GetEraInfoBySwitchBlock, GetStateRootHash},
-        docs::ListRpcs,
-        info::{GetChainspec, GetDeploy, GetPeers, GetStatus, GetValidatorChanges},
+        account::{AddDeployApprovals, PutDeploy, PutDeployBatch, PutPendingDeploy},
+        chain::{
+            GetBlock, GetBlockHeader, GetBlockTransfers, GetBlockTransfersPage,
+            GetBlockWithDeploys, GetEraInfoBySwitchBlock, GetEraSummary, GetStateRootHash,
+        },
+        docs::{self, ListRpcs},
+        info::{
+            GetAccountDeploys, GetChainspec, GetContractEvents, GetDeploy,
+            GetEraValidatorPerformance, GetPeers, GetStatus, GetSyncProgress, GetValidatorChanges,
+            WaitForDeploy,
+        },
         state::{
-            GetAccountInfo, GetAuctionInfo, GetBalance, GetDictionaryItem, GetItem, GetTrie,
-            QueryBalance, QueryGlobalState,
+            GetAccountInfo, GetAuctionInfo, GetAuctionInfoPage, GetAuctionSummary, GetBalance,
+            GetContractNamedKeys, GetDictionaryItem, GetDictionaryItems, GetItem, GetStateDiff,
+            GetTrie, QueryBalance, QueryBalances, QueryGlobalState,
         },
-        RpcWithOptionalParams, RpcWithParams, RpcWithoutParams,
+        ErrorCode, RpcWithOptionalParams, RpcWithParams, RpcWithoutParams,
     },
-    ReactorEventT,
+    sync_gate,
+    ws_server::{self, Notification},
+    Config, ReactorEventT,
 };
-use crate::effect::EffectBuilder;
+use crate::{effect::EffectBuilder, utils::listener::Listener};
 
 /// The URL path for all JSON-RPC requests.
 pub const RPC_API_PATH: &str = "rpc";
@@ -25,40 +40,108 @@ pub const RPC_API_PATH: &str = "rpc";
 pub const RPC_API_SERVER_NAME: &str = "JSON RPC";
 
 /// Run the JSON-RPC server.
+///
+/// If `notification_sender` is provided, the WebSocket endpoint is served as well, sending the
+/// notifications from the given channel to its subscribers.
+///
+/// Access to both the JSON-RPC and WebSocket endpoints is restricted and rate limited as per
+/// `config`.  While the node is syncing, requests are refused as per `config`.
 pub(super) async fn run<REv: ReactorEventT>(
-    builder: Builder<AddrIncoming>,
+    listener: Listener,
     effect_builder: EffectBuilder<REv>,
     api_version: ProtocolVersion,
-    qps_limit: u64,
-    max_body_bytes: u32,
+    config: Config,
+    notification_sender: Option<broadcast::Sender<Arc<Notification>>>,
+    metrics: Arc<Metrics>,
 ) {
     let mut handlers = RequestHandlersBuilder::new();
     PutDeploy::register_as_handler(effect_builder, api_version, &mut handlers);
+    PutDeployBatch::register_as_handler(effect_builder, api_version, &mut handlers);
+    PutPendingDeploy::register_as_handler(effect_builder, api_version, &mut handlers);
+    AddDeployApprovals::register_as_handler(effect_builder, api_version, &mut handlers);
     GetBlock::register_as_handler(effect_builder, api_version, &mut handlers);
+    GetBlockWithDeploys::register_as_handler(effect_builder, api_version, &mut handlers);
     GetBlockTransfers::register_as_handler(effect_builder, api_version, &mut handlers);
+    GetBlockTransfersPage::register_as_handler(effect_builder, api_version, &mut handlers);
+    GetBlockHeader::register_as_handler(effect_builder, api_version, &mut handlers);
     GetStateRootHash::register_as_handler(effect_builder, api_version, &mut handlers);
     GetItem::register_as_handler(effect_builder, api_version, &mut handlers);
     QueryGlobalState::register_as_handler(effect_builder, api_version, &mut handlers);
     GetBalance::register_as_handler(effect_builder, api_version, &mut handlers);
     GetAccountInfo::register_as_handler(effect_builder, api_version, &mut handlers);
     GetDeploy::register_as_handler(effect_builder, api_version, &mut handlers);
+    GetContractEvents::register_as_handler(effect_builder, api_version, &mut handlers);
+    WaitForDeploy::register_as_handler(effect_builder, api_version, &mut handlers);
+    GetAccountDeploys::register_as_handler(effect_builder, api_version, &mut handlers);
     GetPeers::register_as_handler(effect_builder, api_version, &mut handlers);
     GetStatus::register_as_handler(effect_builder, api_version, &mut handlers);
+    GetSyncProgress::register_as_handler(effect_builder, api_version, &mut handlers);
     GetEraInfoBySwitchBlock::register_as_handler(effect_builder, api_version, &mut handlers);
+    GetEraSummary::register_as_handler(effect_builder, api_version, &mut handlers);
     GetAuctionInfo::register_as_handler(effect_builder, api_version, &mut handlers);
+    GetAuctionInfoPage::register_as_handler(effect_builder, api_version, &mut handlers);
+    GetAuctionSummary::register_as_handler(effect_builder, api_version, &mut handlers);
     GetTrie::register_as_handler(effect_builder, api_version, &mut handlers);
     GetValidatorChanges::register_as_handler(effect_builder, api_version, &mut handlers);
+    GetEraValidatorPerformance::register_as_handler(effect_builder, api_version, &mut handlers);
     ListRpcs::register_as_handler(effect_builder, api_version, &mut handlers);
     GetDictionaryItem::register_as_handler(effect_builder, api_version, &mut handlers);
+    GetDictionaryItems::register_as_handler(effect_builder, api_version, &mut handlers);
+    GetContractNamedKeys::register_as_handler(effect_builder, api_version, &mut handlers);
+    GetStateDiff::register_as_handler(effect_builder, api_version, &mut handlers);
     GetChainspec::register_as_handler(effect_builder, api_version, &mut handlers);
     QueryBalance::register_as_handler(effect_builder, api_version, &mut handlers);
+    QueryBalances::register_as_handler(effect_builder, api_version, &mut handlers);
+    if config.max_blocks_behind > 0 {
+        sync_gate::gate_methods(&mut handlers, effect_builder, config.max_blocks_behind);
+    }
+    if config.max_concurrent_requests_per_method > 0 {
+        let metrics = Arc::clone(&metrics);
+        handlers.limit_concurrency(
+            config.max_concurrent_requests_per_method as usize,
+            move |method| {
+                metrics.concurrency_limited_requests.inc();
+                Error::new(
+                    ErrorCode::TooManyRequests,
+                    format!("too many concurrent '{}' requests, retry later", method),
+                )
+            },
+        );
+    }
     let handlers = handlers.build();
 
+    let access_control = AccessControl::new(&config.access_control, &handlers);
+
+    let client_rate_limiter = (config.client_qps_limit > 0).then(|| {
+        Arc::new(ClientRateLimiter::new(
+            config.client_qps_limit,
+            config.trust_forwarded_for,
+            metrics,
+        ))
+    });
+    let cors = config.cors.to_cors_builder();
+
+    let ws_route = notification_sender.map(|notification_sender| {
+        access_control.route(|handlers| {
+            ws_server::route(
+                handlers,
+                notification_sender.clone(),
+                config.max_subscriptions_per_connection as usize,
+                config.max_body_bytes as usize,
+                client_rate_limiter.clone(),
+            )
+        })
+    });
+
     super::rpcs::run(
-        builder,
-        handlers,
-        qps_limit,
-        max_body_bytes,
+        listener,
+        access_control,
+        ws_route,
+        Some(docs::discover_route(RPC_API_PATH, cors.clone())),
+        client_rate_limiter,
+        cors,
+        config.qps_limit,
+        config.max_body_bytes,
         RPC_API_PATH,
         RPC_API_SERVER_NAME,
     )
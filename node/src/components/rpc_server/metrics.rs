@@ -0,0 +1,42 @@
+use prometheus::{IntCounter, Registry};
+
+use crate::unregister_metric;
+
+#[derive(Debug)]
+pub(super) struct Metrics {
+    /// Number of requests refused since their client exceeded its rate limit.
+    pub(super) rate_limited_requests: IntCounter,
+    /// Number of requests refused since too many requests for the same method were in flight.
+    pub(super) concurrency_limited_requests: IntCounter,
+    /// Reference to the registry for unregistering.
+    registry: Registry,
+}
+
+impl Metrics {
+    pub(super) fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
+        let rate_limited_requests = IntCounter::new(
+            "rpc_server_rate_limited_requests",
+            "number of JSON-RPC requests refused since their client exceeded its rate limit",
+        )?;
+        let concurrency_limited_requests = IntCounter::new(
+            "rpc_server_concurrency_limited_requests",
+            "number of JSON-RPC requests refused since too many requests for the same method were \
+            in flight",
+        )?;
+        registry.register(Box::new(rate_limited_requests.clone()))?;
+        registry.register(Box::new(concurrency_limited_requests.clone()))?;
+
+        Ok(Metrics {
+            rate_limited_requests,
+            concurrency_limited_requests,
+            registry: registry.clone(),
+        })
+    }
+}
+
+impl Drop for Metrics {
+    fn drop(&mut self) {
+        unregister_metric!(self.registry, self.rate_limited_requests);
+        unregister_metric!(self.registry, self.concurrency_limited_requests);
+    }
+}
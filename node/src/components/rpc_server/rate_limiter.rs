@@ -0,0 +1,277 @@
+//! Per-client rate limiting for the JSON-RPC server.
+//!
+//! Clients are identified by their IP address, which can optionally be taken from the
+//! `X-Forwarded-For` header set by a reverse proxy.  The requests of each client are counted over
+//! fixed windows of one second, and requests exceeding the limit within a window are refused with
+//! "429 Too Many Requests".  Each message received from a WebSocket client counts as a request.
+
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use http::StatusCode;
+use serde_json::json;
+use warp::{
+    filters::BoxedFilter,
+    reject::{self, Rejection},
+    reply, Filter, Reply,
+};
+
+use super::metrics::Metrics;
+
+/// The period over which the requests of a single client are counted.
+const WINDOW: Duration = Duration::from_secs(1);
+/// The HTTP header in which reverse proxies pass the addresses of the clients they forward for.
+const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+
+/// The requests made by a single client in its current window.
+struct ClientWindow {
+    start: Instant,
+    request_count: u64,
+}
+
+/// The windows of all clients which made requests recently.
+struct ClientWindows {
+    windows: HashMap<IpAddr, ClientWindow>,
+    /// When windows which expired were last removed.
+    last_purge: Instant,
+}
+
+/// The IP address of the remote end of the connection a request was received via, stored in the
+/// request's extensions.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct RemoteIp(pub(super) IpAddr);
+
+/// Limits the rate of requests made by each client.
+pub(super) struct ClientRateLimiter {
+    qps_limit: u64,
+    trust_forwarded_for: bool,
+    clients: Mutex<ClientWindows>,
+    metrics: Arc<Metrics>,
+}
+
+impl ClientRateLimiter {
+    /// Returns a new rate limiter permitting `qps_limit` requests per second and client.
+    ///
+    /// If `trust_forwarded_for` is `true`, clients are identified by the last address in the
+    /// `X-Forwarded-For` header of their requests where present.
+    pub(super) fn new(qps_limit: u64, trust_forwarded_for: bool, metrics: Arc<Metrics>) -> Self {
+        ClientRateLimiter {
+            qps_limit,
+            trust_forwarded_for,
+            clients: Mutex::new(ClientWindows {
+                windows: HashMap::new(),
+                last_purge: Instant::now(),
+            }),
+            metrics,
+        }
+    }
+
+    /// Records a request from `client` made at `now`, returning `false` if the request exceeds the
+    /// client's rate limit.
+    fn try_record_request(&self, client: IpAddr, now: Instant) -> bool {
+        let mut clients = self
+            .clients
+            .lock()
+            .expect("client rate limiter mutex should not be poisoned");
+
+        // Forget clients which made no requests in the last window, to bound memory usage.
+        if now.saturating_duration_since(clients.last_purge) >= WINDOW {
+            clients
+                .windows
+                .retain(|_, window| now.saturating_duration_since(window.start) < WINDOW);
+            clients.last_purge = now;
+        }
+
+        let window = clients.windows.entry(client).or_insert(ClientWindow {
+            start: now,
+            request_count: 0,
+        });
+        if now.saturating_duration_since(window.start) >= WINDOW {
+            window.start = now;
+            window.request_count = 0;
+        }
+        if window.request_count >= self.qps_limit {
+            return false;
+        }
+        window.request_count += 1;
+        true
+    }
+
+    /// Records a request from `client` made now, returning `false` and counting it as rate limited
+    /// if it exceeds the client's rate limit.
+    pub(super) fn permits(&self, client: IpAddr) -> bool {
+        let is_permitted = self.try_record_request(client, Instant::now());
+        if !is_permitted {
+            self.metrics.rate_limited_requests.inc();
+        }
+        is_permitted
+    }
+
+    /// Returns the client which made a request received via a connection from `remote_addr`.
+    fn client(&self, remote_addr: IpAddr, maybe_forwarded_for: Option<String>) -> IpAddr {
+        maybe_forwarded_for
+            .filter(|_| self.trust_forwarded_for)
+            .and_then(|forwarded_for| last_forwarded_for(&forwarded_for))
+            .unwrap_or(remote_addr)
+    }
+
+    /// Returns a filter extracting the client which made a request, for requests whose extensions
+    /// hold the [`RemoteIp`] of the connection they were received via.
+    pub(super) fn client_filter(self: &Arc<Self>) -> BoxedFilter<(IpAddr,)> {
+        let rate_limiter = Arc::clone(self);
+        warp::ext::get::<RemoteIp>()
+            .and(warp::header::optional::<String>(FORWARDED_FOR_HEADER))
+            .map(move |RemoteIp(remote_addr), maybe_forwarded_for| {
+                rate_limiter.client(remote_addr, maybe_forwarded_for)
+            })
+            .boxed()
+    }
+
+    /// Returns a filter which replies with "429 Too Many Requests" if a request received via a
+    /// connection from `remote_addr` exceeds its client's rate limit, and otherwise rejects,
+    /// leaving the request to other routes.
+    pub(super) fn refuse_if_limited(
+        self: &Arc<Self>,
+        remote_addr: IpAddr,
+    ) -> BoxedFilter<(Box<dyn Reply>,)> {
+        let rate_limiter = Arc::clone(self);
+        warp::header::optional::<String>(FORWARDED_FOR_HEADER)
+            .and_then(move |maybe_forwarded_for: Option<String>| {
+                let client = rate_limiter.client(remote_addr, maybe_forwarded_for);
+                let is_permitted = rate_limiter.permits(client);
+                async move {
+                    if is_permitted {
+                        return Err(reject::not_found());
+                    }
+                    let reply: Box<dyn Reply> = Box::new(reply::with_status(
+                        reply::json(&json!({ "message": "too many requests" })),
+                        StatusCode::TOO_MANY_REQUESTS,
+                    ));
+                    Ok::<_, Rejection>(reply)
+                }
+            })
+            .boxed()
+    }
+}
+
+/// Returns the last address listed in the value of an `X-Forwarded-For` header, i.e. the one
+/// appended by the closest proxy, or `None` if it isn't a valid IP address.
+fn last_forwarded_for(forwarded_for: &str) -> Option<IpAddr> {
+    forwarded_for.rsplit(',').next()?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use prometheus::Registry;
+
+    use super::*;
+
+    #[test]
+    fn should_limit_each_client_separately() {
+        let metrics = Arc::new(Metrics::new(&Registry::new()).unwrap());
+        let rate_limiter = ClientRateLimiter::new(2, false, metrics);
+        let client_1 = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let client_2 = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+        let start = Instant::now();
+
+        assert!(rate_limiter.try_record_request(client_1, start));
+        assert!(rate_limiter.try_record_request(client_1, start));
+        assert!(!rate_limiter.try_record_request(client_1, start));
+        assert!(rate_limiter.try_record_request(client_2, start));
+
+        // The limit should be reset in the next window.
+        let next_window = start + WINDOW;
+        assert!(rate_limiter.try_record_request(client_1, next_window));
+    }
+
+    #[test]
+    fn should_forget_inactive_clients() {
+        let metrics = Arc::new(Metrics::new(&Registry::new()).unwrap());
+        let rate_limiter = ClientRateLimiter::new(1, false, metrics);
+        let start = Instant::now();
+        for host in 0..10 {
+            let client = IpAddr::V4(Ipv4Addr::new(10, 0, 0, host));
+            assert!(rate_limiter.try_record_request(client, start));
+        }
+        assert_eq!(rate_limiter.clients.lock().unwrap().windows.len(), 10);
+
+        let later = start + 2 * WINDOW;
+        let client = IpAddr::V4(Ipv4Addr::new(10, 0, 1, 0));
+        assert!(rate_limiter.try_record_request(client, later));
+        assert_eq!(rate_limiter.clients.lock().unwrap().windows.len(), 1);
+    }
+
+    #[test]
+    fn should_parse_last_forwarded_for_address() {
+        assert_eq!(
+            last_forwarded_for("10.0.0.1"),
+            Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)))
+        );
+        assert_eq!(
+            last_forwarded_for("203.0.113.7, 10.0.0.1, 10.0.0.2"),
+            Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)))
+        );
+        assert_eq!(last_forwarded_for("10.0.0.1, unknown"), None);
+        assert_eq!(last_forwarded_for(""), None);
+    }
+
+    #[tokio::test]
+    async fn should_limit_clients_by_forwarded_for_only_if_trusted() {
+        let proxy = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        for trust_forwarded_for in [false, true] {
+            let metrics = Arc::new(Metrics::new(&Registry::new()).unwrap());
+            let rate_limiter = Arc::new(ClientRateLimiter::new(1, trust_forwarded_for, metrics));
+            let filter = rate_limiter.refuse_if_limited(proxy);
+
+            for client in ["203.0.113.1", "203.0.113.2"] {
+                let is_refused = warp::test::request()
+                    .header(FORWARDED_FOR_HEADER, client)
+                    .filter(&filter)
+                    .await
+                    .is_ok();
+                // Only the second request should be refused, and only if all requests appear to
+                // come from the proxy.
+                let expect_refused = !trust_forwarded_for && client == "203.0.113.2";
+                assert_eq!(is_refused, expect_refused);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn should_extract_client_from_remote_ip_extension() {
+        let proxy = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let client = IpAddr::V4(Ipv4Addr::new(203, 0, 113, 1));
+        for trust_forwarded_for in [false, true] {
+            let metrics = Arc::new(Metrics::new(&Registry::new()).unwrap());
+            let rate_limiter = Arc::new(ClientRateLimiter::new(1, trust_forwarded_for, metrics));
+            let filter = rate_limiter.client_filter();
+
+            let extracted = warp::test::request()
+                .extension(RemoteIp(proxy))
+                .header(FORWARDED_FOR_HEADER, client.to_string())
+                .filter(&filter)
+                .await
+                .unwrap();
+            let expected = if trust_forwarded_for { client } else { proxy };
+            assert_eq!(extracted, expected);
+
+            assert!(rate_limiter.permits(extracted));
+            assert!(!rate_limiter.permits(extracted));
+            assert_eq!(rate_limiter.metrics.rate_limited_requests.get(), 1);
+        }
+
+        // Without the extension, the client cannot be determined.
+        let metrics = Arc::new(Metrics::new(&Registry::new()).unwrap());
+        let rate_limiter = Arc::new(ClientRateLimiter::new(1, false, metrics));
+        assert!(warp::test::request()
+            .filter(&rate_limiter.client_filter())
+            .await
+            .is_err());
+    }
+}
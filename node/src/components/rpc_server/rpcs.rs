@@ -15,29 +15,36 @@ use std::{convert::Infallible, str, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
 use http::header::ACCEPT_ENCODING;
-use hyper::server::{conn::AddrIncoming, Builder};
+use hyper::{service::Service, Body, Request, Server};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tokio::sync::oneshot;
 use tower::ServiceBuilder;
 use tracing::info;
-use warp::Filter;
+use warp::{cors::Builder as CorsBuilder, filters::BoxedFilter, Filter, Reply};
 
-use casper_json_rpc::{Error, Params, RequestHandlers, RequestHandlersBuilder, ReservedErrorCode};
+use casper_json_rpc::{Error, Params, RequestHandlersBuilder, ReservedErrorCode};
 use casper_types::ProtocolVersion;
 
-use super::{ReactorEventT, RpcRequest};
-use crate::effect::EffectBuilder;
+use super::{
+    access_control::AccessControl,
+    rate_limiter::{ClientRateLimiter, RemoteIp},
+    ReactorEventT, RpcRequest,
+};
+use crate::{
+    effect::EffectBuilder,
+    utils::listener::{Connection, Listener},
+};
 pub use common::ErrorData;
 use docs::DocExample;
-pub use error_code::ErrorCode;
+pub use error_code::{ErrorCategory, ErrorCode};
 
 /// This setting causes the server to ignore extra fields in JSON-RPC requests other than the
 /// standard 'id', 'jsonrpc', 'method', and 'params' fields.
 ///
 /// It will be changed to `false` for casper-node v2.0.0.
-const ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST: bool = true;
+pub(super) const ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST: bool = true;
 
 /// A JSON-RPC requiring the "params" field to be present.
 #[async_trait]
@@ -253,21 +260,49 @@ pub(super) trait RpcWithOptionalParams {
 }
 
 /// Start JSON RPC server in a background.
+///
+/// If `ws_route` or `discover_route` are provided, they are served alongside the JSON-RPC route.
+/// If `client_rate_limiter` is provided, requests exceeding their client's rate limit are refused.
+/// Cross-origin requests to the JSON-RPC route are permitted as per `cors`.
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn run(
-    builder: Builder<AddrIncoming>,
-    handlers: RequestHandlers,
+    listener: Listener,
+    access_control: AccessControl,
+    ws_route: Option<BoxedFilter<(Box<dyn Reply>,)>>,
+    discover_route: Option<BoxedFilter<(Box<dyn Reply>,)>>,
+    client_rate_limiter: Option<Arc<ClientRateLimiter>>,
+    cors: CorsBuilder,
     qps_limit: u64,
     max_body_bytes: u32,
     api_path: &'static str,
     server_name: &'static str,
 ) {
-    let make_svc = hyper::service::make_service_fn(move |_| {
-        let service_routes = casper_json_rpc::route(
+    // Without a WebSocket route, use one which never matches, leaving all requests to the JSON-RPC
+    // route.
+    let ws_route = ws_route.unwrap_or_else(never_matching_route);
+    let discover_route = discover_route.unwrap_or_else(never_matching_route);
+
+    let service_routes = access_control.route(|handlers| {
+        casper_json_rpc::route_with_cors(
             api_path,
             max_body_bytes,
-            handlers.clone(),
+            handlers,
             ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST,
-        );
+            cors.clone(),
+        )
+        .map(|reply| {
+            let reply: Box<dyn Reply> = Box::new(reply);
+            reply
+        })
+        .boxed()
+    });
+
+    let make_svc = hyper::service::make_service_fn(move |connection: &Connection| {
+        let remote_ip = connection.remote_ip();
+        let rate_limit_route = match &client_rate_limiter {
+            Some(rate_limiter) => rate_limiter.refuse_if_limited(remote_ip),
+            None => never_matching_route(),
+        };
 
         // Supports content negotiation for gzip responses. This is an interim fix until
         // https://github.com/seanmonstar/warp/pull/513 moves forward.
@@ -275,16 +310,29 @@ pub(super) async fn run(
             .and(service_routes.clone())
             .with(warp::compression::gzip());
 
-        let service = warp::service(service_routes_gzip.or(service_routes));
-        async move { Ok::<_, Infallible>(service.clone()) }
+        let service = warp::service(
+            rate_limit_route
+                .or(ws_route.clone())
+                .unify()
+                .or(discover_route.clone())
+                .unify()
+                .or(service_routes_gzip)
+                .or(service_routes.clone()),
+        );
+        // Makes the address of the connection available to routes handling its requests.
+        let service = hyper::service::service_fn(move |mut request: Request<Body>| {
+            let _ = request.extensions_mut().insert(RemoteIp(remote_ip));
+            service.clone().call(request)
+        });
+        async move { Ok::<_, Infallible>(service) }
     });
 
     let make_svc = ServiceBuilder::new()
         .rate_limit(qps_limit, Duration::from_secs(1))
         .service(make_svc);
 
-    let server = builder.serve(make_svc);
-    info!(address = %server.local_addr(), "started {} server", server_name);
+    info!(address = %listener, "started {} server", server_name);
+    let server = Server::builder(listener).serve(make_svc);
 
     let (shutdown_sender, shutdown_receiver) = oneshot::channel::<()>();
     let server_with_shutdown = server.with_graceful_shutdown(async {
@@ -296,6 +344,13 @@ pub(super) async fn run(
     info!("{} server shut down", server_name);
 }
 
+/// Returns a route which rejects all requests, leaving them to other routes.
+fn never_matching_route() -> BoxedFilter<(Box<dyn Reply>,)> {
+    warp::any()
+        .and_then(|| async { Err::<Box<dyn Reply>, _>(warp::reject::not_found()) })
+        .boxed()
+}
+
 #[cfg(test)]
 mod tests {
     use std::fmt::Write;
@@ -525,4 +580,47 @@ mod tests {
             );
         }
     }
+
+    mod discover_route {
+        use super::*;
+        use crate::components::rpc_server::rpcs::docs::{self, OpenRpcSchema, OPEN_RPC_SCHEMA};
+
+        #[tokio::test]
+        async fn should_serve_open_rpc_schema() {
+            let filter = docs::discover_route("rpc", warp::cors().allow_any_origin());
+
+            let http_response = warp::test::request()
+                .method("GET")
+                .path("/rpc/discover")
+                .filter(&filter)
+                .await
+                .unwrap()
+                .into_response();
+
+            assert_eq!(http_response.status(), StatusCode::OK);
+            let body_bytes = hyper::body::to_bytes(http_response.into_body())
+                .await
+                .unwrap();
+            let schema: OpenRpcSchema = serde_json::from_slice(&body_bytes).unwrap();
+            assert_eq!(schema, *OPEN_RPC_SCHEMA);
+        }
+
+        #[tokio::test]
+        async fn should_not_match_other_requests() {
+            let filter = docs::discover_route("rpc", warp::cors().allow_any_origin());
+
+            assert!(warp::test::request()
+                .method("POST")
+                .path("/rpc/discover")
+                .filter(&filter)
+                .await
+                .is_err());
+            assert!(warp::test::request()
+                .method("GET")
+                .path("/rpc")
+                .filter(&filter)
+                .await
+                .is_err());
+        }
+    }
 }
@@ -3,7 +3,10 @@
 // TODO - remove once schemars stops causing warning.
 #![allow(clippy::field_reassign_with_default)]
 
-use std::str;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    str,
+};
 
 use async_trait::async_trait;
 use once_cell::sync::Lazy;
@@ -11,33 +14,83 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
-use casper_types::ProtocolVersion;
+use casper_hashing::Digest;
+use casper_json_rpc::ReservedErrorCode;
+use casper_types::{bytesrepr::ToBytes, ProtocolVersion, PublicKey, U512};
 
 use super::{
+    common::{self, MERKLE_PROOF},
     docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
     Error, ReactorEventT, RpcRequest, RpcWithParams,
 };
 use crate::{
-    components::rpc_server::rpcs::ErrorCode,
+    components::{
+        deploy_acceptor::{BalanceProof, Error as DeployAcceptorError, PendingDeployStatus},
+        rpc_server::rpcs::ErrorCode,
+    },
     effect::EffectBuilder,
     reactor::QueueKind,
-    types::{Deploy, DeployHash},
+    types::{Approval, Block, Deploy, DeployHash},
 };
 
 static PUT_DEPLOY_PARAMS: Lazy<PutDeployParams> = Lazy::new(|| PutDeployParams {
     deploy: Deploy::doc_example().clone(),
+    include_balance_proof: true,
+});
+static PUT_DEPLOY_BATCH_PARAMS: Lazy<PutDeployBatchParams> = Lazy::new(|| PutDeployBatchParams {
+    deploys: vec![Deploy::doc_example().clone()],
+    include_balance_proofs: true,
+});
+static DEPLOY_BALANCE_PROOF: Lazy<DeployBalanceProof> = Lazy::new(|| DeployBalanceProof {
+    state_root_hash: *Block::doc_example().header().state_root_hash(),
+    balance_value: U512::from(123_456),
+    merkle_proof: MERKLE_PROOF.clone(),
 });
 static PUT_DEPLOY_RESULT: Lazy<PutDeployResult> = Lazy::new(|| PutDeployResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     deploy_hash: *Deploy::doc_example().id(),
+    balance_proof: Some(DEPLOY_BALANCE_PROOF.clone()),
+});
+static PUT_DEPLOY_BATCH_RESULT: Lazy<PutDeployBatchResult> = Lazy::new(|| PutDeployBatchResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    results: vec![PutDeployBatchItem {
+        deploy_hash: *Deploy::doc_example().id(),
+        error: None,
+        balance_proof: Some(DEPLOY_BALANCE_PROOF.clone()),
+    }],
+});
+static PUT_PENDING_DEPLOY_PARAMS: Lazy<PutPendingDeployParams> =
+    Lazy::new(|| PutPendingDeployParams {
+        deploy: Deploy::doc_example().clone(),
+    });
+static ADD_DEPLOY_APPROVALS_PARAMS: Lazy<AddDeployApprovalsParams> =
+    Lazy::new(|| AddDeployApprovalsParams {
+        deploy_hash: *Deploy::doc_example().id(),
+        approvals: Deploy::doc_example().approvals().iter().cloned().collect(),
+    });
+static PENDING_DEPLOY_RESULT: Lazy<PendingDeployResult> = Lazy::new(|| PendingDeployResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    deploy_hash: *Deploy::doc_example().id(),
+    status: JsonPendingDeployStatus::AwaitingApprovals {
+        signature_weight: 1,
+        deployment_threshold: 2,
+    },
 });
 
+/// The maximum number of deploys which can be submitted in a single "account_put_deploy_batch"
+/// request.
+pub const MAX_DEPLOYS_PER_BATCH: usize = 100;
+
 /// Params for "account_put_deploy" RPC request.
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct PutDeployParams {
     /// The `Deploy`.
     pub deploy: Deploy,
+    /// Whether to return a proof of the paying account's main purse balance, against which the
+    /// deploy was accepted.
+    #[serde(default)]
+    pub include_balance_proof: bool,
 }
 
 impl DocExample for PutDeployParams {
@@ -46,6 +99,48 @@ impl DocExample for PutDeployParams {
     }
 }
 
+/// A proof of the balance of the paying account's main purse, against which a deploy was accepted.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DeployBalanceProof {
+    /// The state root hash against which the balance was checked.
+    pub state_root_hash: Digest,
+    /// The balance value.
+    pub balance_value: U512,
+    /// The merkle proof.
+    pub merkle_proof: String,
+}
+
+impl DeployBalanceProof {
+    /// Encodes the proof returned by the deploy acceptor.
+    fn from_balance_proof(balance_proof: BalanceProof) -> Result<Self, Error> {
+        let proof_bytes = balance_proof.proof.to_bytes().map_err(|error| {
+            let message = format!("failed to encode proof: {}", error);
+            info!("{}", message);
+            Error::new(ReservedErrorCode::InternalError, message)
+        })?;
+        Ok(DeployBalanceProof {
+            state_root_hash: balance_proof.state_root_hash,
+            balance_value: balance_proof.balance_value,
+            merkle_proof: base16::encode_lower(&proof_bytes),
+        })
+    }
+
+    /// Encodes the proof returned by the deploy acceptor if the client asked for it and one was
+    /// provided.
+    fn maybe_from_balance_proof(
+        include_balance_proof: bool,
+        maybe_balance_proof: Option<BalanceProof>,
+    ) -> Result<Option<Self>, Error> {
+        match maybe_balance_proof {
+            Some(balance_proof) if include_balance_proof => {
+                Self::from_balance_proof(balance_proof).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
 /// Result for "account_put_deploy" RPC response.
 #[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -55,6 +150,9 @@ pub struct PutDeployResult {
     pub api_version: ProtocolVersion,
     /// The deploy hash.
     pub deploy_hash: DeployHash,
+    /// The proof of the paying account's balance, if requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance_proof: Option<DeployBalanceProof>,
 }
 
 impl DocExample for PutDeployResult {
@@ -91,11 +189,16 @@ impl RpcWithParams for PutDeploy {
             .await;
 
         match put_deploy_result {
-            Ok(_) => {
+            Ok(maybe_balance_proof) => {
                 info!(%deploy_hash, "deploy was stored");
+                let balance_proof = DeployBalanceProof::maybe_from_balance_proof(
+                    params.include_balance_proof,
+                    maybe_balance_proof,
+                )?;
                 let result = Self::ResponseResult {
                     api_version,
                     deploy_hash,
+                    balance_proof,
                 };
                 Ok(result)
             }
@@ -105,8 +208,322 @@ impl RpcWithParams for PutDeploy {
                     %error,
                     "the deploy submitted by the client was invalid",
                 );
-                Err(Error::new(ErrorCode::InvalidDeploy, error.to_string()))
+                Err(common::deploy_rejected_error(&error))
+            }
+        }
+    }
+}
+
+/// Params for "account_put_deploy_batch" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PutDeployBatchParams {
+    /// The `Deploy`s, in the order in which they should be submitted.
+    pub deploys: Vec<Deploy>,
+    /// Whether to return a proof of the paying account's main purse balance for each accepted
+    /// deploy.
+    #[serde(default)]
+    pub include_balance_proofs: bool,
+}
+
+impl DocExample for PutDeployBatchParams {
+    fn doc_example() -> &'static Self {
+        &*PUT_DEPLOY_BATCH_PARAMS
+    }
+}
+
+/// The outcome of submitting a single deploy via "account_put_deploy_batch".
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PutDeployBatchItem {
+    /// The deploy hash.
+    pub deploy_hash: DeployHash,
+    /// The reason the deploy was rejected, or `None` if it was accepted.
+    pub error: Option<String>,
+    /// The proof of the paying account's balance, if requested and the deploy was accepted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance_proof: Option<DeployBalanceProof>,
+}
+
+/// Result for "account_put_deploy_batch" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PutDeployBatchResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The outcome for each of the given deploys, in the same order.
+    pub results: Vec<PutDeployBatchItem>,
+}
+
+impl DocExample for PutDeployBatchResult {
+    fn doc_example() -> &'static Self {
+        &*PUT_DEPLOY_BATCH_RESULT
+    }
+}
+
+/// "account_put_deploy_batch" RPC
+pub struct PutDeployBatch {}
+
+#[async_trait]
+impl RpcWithParams for PutDeployBatch {
+    const METHOD: &'static str = "account_put_deploy_batch";
+    type RequestParams = PutDeployBatchParams;
+    type ResponseResult = PutDeployBatchResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        if params.deploys.len() > MAX_DEPLOYS_PER_BATCH {
+            let message = format!(
+                "batch contains {} deploys, but at most {} are permitted",
+                params.deploys.len(),
+                MAX_DEPLOYS_PER_BATCH
+            );
+            info!("{}", message);
+            return Err(Error::new(ErrorCode::TooManyDeploysInBatch, message));
+        }
+
+        // Accounts for which a deploy was rejected due to the state of the account itself, along
+        // with the rejection reason.  Any later deploys from these accounts would be rejected for
+        // the same reason, so they are not submitted at all.
+        let mut rejected_accounts: BTreeMap<PublicKey, String> = BTreeMap::new();
+        let mut results = Vec::with_capacity(params.deploys.len());
+
+        for deploy in params.deploys {
+            let deploy_hash = *deploy.id();
+            let account = deploy.header().account().clone();
+
+            if let Some(error) = rejected_accounts.get(&account) {
+                info!(%deploy_hash, %error, "skipping deploy from rejected account");
+                results.push(PutDeployBatchItem {
+                    deploy_hash,
+                    error: Some(error.clone()),
+                    balance_proof: None,
+                });
+                continue;
             }
+
+            let put_deploy_result = effect_builder
+                .make_request(
+                    |responder| RpcRequest::SubmitDeploy {
+                        deploy: Box::new(deploy),
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let (error, balance_proof) = match put_deploy_result {
+                Ok(maybe_balance_proof) => {
+                    info!(%deploy_hash, "deploy was stored");
+                    let balance_proof = DeployBalanceProof::maybe_from_balance_proof(
+                        params.include_balance_proofs,
+                        maybe_balance_proof,
+                    )?;
+                    (None, balance_proof)
+                }
+                Err(error) => {
+                    info!(
+                        %deploy_hash,
+                        %error,
+                        "the deploy submitted by the client in a batch was invalid",
+                    );
+                    if error.is_account_failure() {
+                        let _ = rejected_accounts.insert(account, error.to_string());
+                    }
+                    (Some(error.to_string()), None)
+                }
+            };
+            results.push(PutDeployBatchItem {
+                deploy_hash,
+                error,
+                balance_proof,
+            });
         }
+
+        Ok(Self::ResponseResult {
+            api_version,
+            results,
+        })
+    }
+}
+
+/// Params for "account_put_pending_deploy" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PutPendingDeployParams {
+    /// The `Deploy`, whose approvals need not meet its account's deployment threshold.
+    pub deploy: Deploy,
+}
+
+impl DocExample for PutPendingDeployParams {
+    fn doc_example() -> &'static Self {
+        &*PUT_PENDING_DEPLOY_PARAMS
+    }
+}
+
+/// Params for "account_add_deploy_approvals" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AddDeployApprovalsParams {
+    /// The hash of a deploy submitted via "account_put_pending_deploy".
+    pub deploy_hash: DeployHash,
+    /// The approvals to add to the deploy.
+    pub approvals: Vec<Approval>,
+}
+
+impl DocExample for AddDeployApprovalsParams {
+    fn doc_example() -> &'static Self {
+        &*ADD_DEPLOY_APPROVALS_PARAMS
+    }
+}
+
+/// The status of a deploy submitted via "account_put_pending_deploy".
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum JsonPendingDeployStatus {
+    /// The deploy's approvals meet its account's deployment threshold, and the deploy was stored.
+    Accepted,
+    /// The deploy's approvals do not yet meet its account's deployment threshold.  It is held by
+    /// this node, without being gossiped, until further approvals are added or it expires.
+    AwaitingApprovals {
+        /// The cumulative weight of the account's keys which approved the deploy.
+        signature_weight: u8,
+        /// The account's deployment threshold.
+        deployment_threshold: u8,
+    },
+}
+
+impl From<PendingDeployStatus> for JsonPendingDeployStatus {
+    fn from(status: PendingDeployStatus) -> Self {
+        match status {
+            PendingDeployStatus::Accepted => JsonPendingDeployStatus::Accepted,
+            PendingDeployStatus::AwaitingApprovals {
+                signature_weight,
+                deployment_threshold,
+            } => JsonPendingDeployStatus::AwaitingApprovals {
+                signature_weight: signature_weight.value(),
+                deployment_threshold: deployment_threshold.value(),
+            },
+        }
+    }
+}
+
+/// Result for "account_put_pending_deploy" and "account_add_deploy_approvals" RPC responses.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PendingDeployResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The deploy hash.
+    pub deploy_hash: DeployHash,
+    /// The status of the deploy.
+    pub status: JsonPendingDeployStatus,
+}
+
+impl DocExample for PendingDeployResult {
+    fn doc_example() -> &'static Self {
+        &*PENDING_DEPLOY_RESULT
+    }
+}
+
+impl PendingDeployResult {
+    /// Converts the deploy acceptor's response for the pending deploy into an RPC result.
+    fn from_response(
+        api_version: ProtocolVersion,
+        deploy_hash: DeployHash,
+        response: Result<PendingDeployStatus, DeployAcceptorError>,
+    ) -> Result<Self, Error> {
+        match response {
+            Ok(status) => {
+                info!(%deploy_hash, ?status, "pending deploy was checked");
+                Ok(PendingDeployResult {
+                    api_version,
+                    deploy_hash,
+                    status: status.into(),
+                })
+            }
+            Err(error @ DeployAcceptorError::UnknownPendingDeploy { .. }) => {
+                info!(%deploy_hash, %error, "no such pending deploy");
+                Err(Error::new(
+                    ErrorCode::NoSuchPendingDeploy,
+                    error.to_string(),
+                ))
+            }
+            Err(error) => {
+                info!(
+                    %deploy_hash,
+                    %error,
+                    "the pending deploy submitted by the client was invalid",
+                );
+                Err(common::deploy_rejected_error(&error))
+            }
+        }
+    }
+}
+
+/// "account_put_pending_deploy" RPC
+pub struct PutPendingDeploy {}
+
+#[async_trait]
+impl RpcWithParams for PutPendingDeploy {
+    const METHOD: &'static str = "account_put_pending_deploy";
+    type RequestParams = PutPendingDeployParams;
+    type ResponseResult = PendingDeployResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let deploy_hash = *params.deploy.id();
+
+        let response = effect_builder
+            .make_request(
+                |responder| RpcRequest::SubmitPendingDeploy {
+                    deploy: Box::new(params.deploy),
+                    responder,
+                },
+                QueueKind::Api,
+            )
+            .await;
+
+        PendingDeployResult::from_response(api_version, deploy_hash, response)
+    }
+}
+
+/// "account_add_deploy_approvals" RPC
+pub struct AddDeployApprovals {}
+
+#[async_trait]
+impl RpcWithParams for AddDeployApprovals {
+    const METHOD: &'static str = "account_add_deploy_approvals";
+    type RequestParams = AddDeployApprovalsParams;
+    type ResponseResult = PendingDeployResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let deploy_hash = params.deploy_hash;
+        let approvals: BTreeSet<Approval> = params.approvals.into_iter().collect();
+
+        let response = effect_builder
+            .make_request(
+                |responder| RpcRequest::AddDeployApprovals {
+                    deploy_hash,
+                    approvals,
+                    responder,
+                },
+                QueueKind::Api,
+            )
+            .await;
+
+        PendingDeployResult::from_response(api_version, deploy_hash, response)
     }
 }
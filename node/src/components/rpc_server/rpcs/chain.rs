@@ -11,23 +11,33 @@ use async_trait::async_trait;
 use once_cell::sync::Lazy;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use tracing::info;
 
 use casper_hashing::Digest;
-use casper_types::{Key, ProtocolVersion, Transfer};
+use casper_types::{EraId, ExecutionResult, Key, ProtocolVersion, Timestamp, Transfer};
 
 use super::{
     docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
+    info::AccountIdentifier,
     Error, ErrorCode, ReactorEventT, ReservedErrorCode, RpcRequest, RpcWithOptionalParams,
+    RpcWithParams,
 };
 use crate::{
     effect::EffectBuilder,
     reactor::QueueKind,
     rpcs::common,
-    types::{Block, BlockHash, BlockWithMetadata, JsonBlock},
+    types::{
+        json_compatibility::JsonValidatorWeights, Block, BlockHash, BlockHeader,
+        BlockHeaderWithMetadata, BlockWithMetadata, Deploy, JsonBlock, JsonBlockHeader, JsonEraEnd,
+        JsonProof,
+    },
 };
 pub use era_summary::EraSummary;
 use era_summary::ERA_SUMMARY;
 
+/// Maximum number of transfers returned per page by the "chain_get_block_transfers_page" RPC.
+const BLOCK_TRANSFERS_PAGE_SIZE: u32 = 100;
+
 static GET_BLOCK_PARAMS: Lazy<GetBlockParams> = Lazy::new(|| GetBlockParams {
     block_identifier: BlockIdentifier::Hash(*Block::doc_example().hash()),
 });
@@ -35,6 +45,28 @@ static GET_BLOCK_RESULT: Lazy<GetBlockResult> = Lazy::new(|| GetBlockResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     block: Some(JsonBlock::doc_example().clone()),
 });
+static GET_BLOCK_WITH_DEPLOYS_PARAMS: Lazy<GetBlockWithDeploysParams> =
+    Lazy::new(|| GetBlockWithDeploysParams {
+        block_identifier: BlockIdentifier::Hash(*Block::doc_example().hash()),
+    });
+static GET_BLOCK_WITH_DEPLOYS_RESULT: Lazy<GetBlockWithDeploysResult> =
+    Lazy::new(|| GetBlockWithDeploysResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        block: Some(JsonBlock::doc_example().clone()),
+        deploys: vec![JsonBlockDeploy {
+            deploy: Deploy::doc_example().clone(),
+            execution_result: Some(ExecutionResult::example().clone()),
+        }],
+    });
+static GET_BLOCK_HEADER_PARAMS: Lazy<GetBlockHeaderParams> = Lazy::new(|| GetBlockHeaderParams {
+    block_identifier: BlockIdentifier::Hash(*Block::doc_example().hash()),
+});
+static GET_BLOCK_HEADER_RESULT: Lazy<GetBlockHeaderResult> = Lazy::new(|| GetBlockHeaderResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    block_hash: *Block::doc_example().hash(),
+    block_header: JsonBlockHeader::doc_example().clone(),
+    proofs: JsonBlock::doc_example().proofs.clone(),
+});
 static GET_BLOCK_TRANSFERS_PARAMS: Lazy<GetBlockTransfersParams> =
     Lazy::new(|| GetBlockTransfersParams {
         block_identifier: BlockIdentifier::Hash(*Block::doc_example().hash()),
@@ -44,6 +76,15 @@ static GET_BLOCK_TRANSFERS_RESULT: Lazy<GetBlockTransfersResult> =
         api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
         block_hash: Some(*Block::doc_example().hash()),
         transfers: Some(vec![Transfer::default()]),
+        block_timestamp: Some(Block::doc_example().timestamp()),
+        next_page: None,
+    });
+static GET_BLOCK_TRANSFERS_PAGE_PARAMS: Lazy<GetBlockTransfersPageParams> =
+    Lazy::new(|| GetBlockTransfersPageParams {
+        block_identifier: Some(BlockIdentifier::Hash(*Block::doc_example().hash())),
+        account_identifier: Some(AccountIdentifier::AccountHash(Transfer::default().from)),
+        direction: Some(TransferDirection::Outgoing),
+        page: Some(0),
     });
 static GET_STATE_ROOT_HASH_PARAMS: Lazy<GetStateRootHashParams> =
     Lazy::new(|| GetStateRootHashParams {
@@ -61,6 +102,19 @@ static GET_ERA_INFO_RESULT: Lazy<GetEraInfoResult> = Lazy::new(|| GetEraInfoResu
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     era_summary: Some(ERA_SUMMARY.clone()),
 });
+static GET_ERA_SUMMARY_PARAMS: Lazy<GetEraSummaryParams> = Lazy::new(|| GetEraSummaryParams {
+    block_identifier: BlockIdentifier::Hash(*Block::doc_example().hash()),
+});
+static GET_ERA_SUMMARY_RESULT: Lazy<GetEraSummaryResult> = Lazy::new(|| {
+    let block_header = Block::doc_example().header();
+    GetEraSummaryResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        era_id: block_header.era_id(),
+        validator_weights: json_validator_weights(block_header),
+        switch_block_hash: Some(*Block::doc_example().hash()),
+        era_end: JsonBlockHeader::doc_example().era_end.clone(),
+    }
+});
 
 /// Identifier for possible ways to retrieve a block.
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, JsonSchema)]
@@ -177,6 +231,124 @@ impl RpcWithOptionalParams for GetBlock {
     }
 }
 
+/// Params for "chain_get_block_with_deploys" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetBlockWithDeploysParams {
+    /// The block identifier.
+    pub block_identifier: BlockIdentifier,
+}
+
+impl DocExample for GetBlockWithDeploysParams {
+    fn doc_example() -> &'static Self {
+        &*GET_BLOCK_WITH_DEPLOYS_PARAMS
+    }
+}
+
+/// A deploy contained in a block, along with its execution result in that block.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct JsonBlockDeploy {
+    /// The deploy, with the approvals finalized in the block.
+    pub deploy: Deploy,
+    /// The result of executing the deploy in the block, if known to this node.
+    pub execution_result: Option<ExecutionResult>,
+}
+
+/// Result for "chain_get_block_with_deploys" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetBlockWithDeploysResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The block, if found.
+    pub block: Option<JsonBlock>,
+    /// The block's deploys followed by its transfers, in the order listed in the block.
+    pub deploys: Vec<JsonBlockDeploy>,
+}
+
+impl DocExample for GetBlockWithDeploysResult {
+    fn doc_example() -> &'static Self {
+        &*GET_BLOCK_WITH_DEPLOYS_RESULT
+    }
+}
+
+/// "chain_get_block_with_deploys" RPC.
+pub struct GetBlockWithDeploys {}
+
+#[async_trait]
+impl RpcWithOptionalParams for GetBlockWithDeploys {
+    const METHOD: &'static str = "chain_get_block_with_deploys";
+    type OptionalRequestParams = GetBlockWithDeploysParams;
+    type ResponseResult = GetBlockWithDeploysResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        maybe_params: Option<Self::OptionalRequestParams>,
+    ) -> Result<Self::ResponseResult, Error> {
+        // This RPC request is restricted by the block availability index.
+        let only_from_available_block_range = true;
+
+        // Get the block.
+        let maybe_block_id = maybe_params.map(|params| params.block_identifier);
+        let BlockWithMetadata {
+            block,
+            block_signatures,
+        } = get_block_with_metadata(
+            maybe_block_id,
+            only_from_available_block_range,
+            effect_builder,
+        )
+        .await?;
+
+        // Get all of the block's deploys and their execution results in a single request.
+        let block_hash = *block.hash();
+        let deploy_hashes = block
+            .deploy_hashes()
+            .iter()
+            .chain(block.transfer_hashes())
+            .copied()
+            .collect();
+        let maybe_deploys = effect_builder
+            .make_request(
+                |responder| RpcRequest::GetBlockDeploys {
+                    block_hash,
+                    deploy_hashes,
+                    responder,
+                },
+                QueueKind::Api,
+            )
+            .await;
+        let deploys = match maybe_deploys {
+            Some(deploys) => deploys
+                .into_iter()
+                .map(|(deploy, execution_result)| JsonBlockDeploy {
+                    deploy,
+                    execution_result,
+                })
+                .collect(),
+            None => {
+                let message = format!(
+                    "failed to get all deploys of block {} from storage",
+                    block_hash
+                );
+                info!("{}", message);
+                return Err(Error::new(ErrorCode::NoSuchDeploy, message));
+            }
+        };
+
+        // Return the result.
+        let result = Self::ResponseResult {
+            api_version,
+            block: Some(JsonBlock::new(block, Some(block_signatures))),
+            deploys,
+        };
+        Ok(result)
+    }
+}
+
 /// Params for "chain_get_block_transfers" RPC request.
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -202,6 +374,13 @@ pub struct GetBlockTransfersResult {
     pub block_hash: Option<BlockHash>,
     /// The block's transfers, if found.
     pub transfers: Option<Vec<Transfer>>,
+    /// The timestamp of the block, if found.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_timestamp: Option<Timestamp>,
+    /// The page holding the next matching transfers, if the transfers were requested by page and
+    /// there are more.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_page: Option<u32>,
 }
 
 impl GetBlockTransfersResult {
@@ -215,6 +394,8 @@ impl GetBlockTransfersResult {
             api_version,
             block_hash,
             transfers,
+            block_timestamp: None,
+            next_page: None,
         }
     }
 }
@@ -244,13 +425,13 @@ impl RpcWithOptionalParams for GetBlockTransfers {
 
         // Get the block.
         let maybe_block_id = maybe_params.map(|params| params.block_identifier);
-        let block_hash = common::get_block(
+        let block = common::get_block(
             maybe_block_id,
             only_from_available_block_range,
             effect_builder,
         )
-        .await
-        .map(|block| *block.hash())?;
+        .await?;
+        let block_hash = *block.hash();
 
         let transfers = effect_builder
             .make_request(
@@ -263,7 +444,235 @@ impl RpcWithOptionalParams for GetBlockTransfers {
             .await;
 
         // Return the result.
-        let result = Self::ResponseResult::new(api_version, Some(block_hash), transfers);
+        let result = Self::ResponseResult {
+            block_timestamp: Some(block.timestamp()),
+            ..Self::ResponseResult::new(api_version, Some(block_hash), transfers)
+        };
+        Ok(result)
+    }
+}
+
+/// The direction of the transfers to return, relative to a given account.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum TransferDirection {
+    /// Transfers from or to the account.
+    Any,
+    /// Transfers from the account.
+    Outgoing,
+    /// Transfers to the account.
+    Incoming,
+}
+
+impl TransferDirection {
+    /// Returns whether the transfer goes in this direction relative to the given account.
+    fn matches(self, transfer: &Transfer, account_identifier: &AccountIdentifier) -> bool {
+        let account_hash = account_identifier.account_hash();
+        let is_outgoing = transfer.from == account_hash;
+        let is_incoming = transfer.to == Some(account_hash);
+        match self {
+            TransferDirection::Any => is_outgoing || is_incoming,
+            TransferDirection::Outgoing => is_outgoing,
+            TransferDirection::Incoming => is_incoming,
+        }
+    }
+}
+
+/// Params for "chain_get_block_transfers_page" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetBlockTransfersPageParams {
+    /// The block identifier. If not provided, the most recently added block is used.
+    pub block_identifier: Option<BlockIdentifier>,
+    /// If provided, only transfers from and/or to this account are returned, as per `direction`.
+    pub account_identifier: Option<AccountIdentifier>,
+    /// The direction of the transfers to return relative to `account_identifier`. Defaults to
+    /// `any`, and is ignored if no account is provided.
+    pub direction: Option<TransferDirection>,
+    /// The zero-based page of transfers to return, each holding up to 100 transfers in execution
+    /// order. Defaults to the first page.
+    pub page: Option<u32>,
+}
+
+impl DocExample for GetBlockTransfersPageParams {
+    fn doc_example() -> &'static Self {
+        &*GET_BLOCK_TRANSFERS_PAGE_PARAMS
+    }
+}
+
+/// "chain_get_block_transfers_page" RPC.
+pub struct GetBlockTransfersPage {}
+
+#[async_trait]
+impl RpcWithParams for GetBlockTransfersPage {
+    const METHOD: &'static str = "chain_get_block_transfers_page";
+    type RequestParams = GetBlockTransfersPageParams;
+    type ResponseResult = GetBlockTransfersResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        // This RPC request is restricted by the block availability index.
+        let only_from_available_block_range = true;
+
+        // Get the block.
+        let block = common::get_block(
+            params.block_identifier,
+            only_from_available_block_range,
+            effect_builder,
+        )
+        .await?;
+        let block_hash = *block.hash();
+
+        let maybe_transfers = effect_builder
+            .make_request(
+                |responder| RpcRequest::GetBlockTransfers {
+                    block_hash,
+                    responder,
+                },
+                QueueKind::Api,
+            )
+            .await;
+
+        let direction = params.direction.unwrap_or(TransferDirection::Any);
+        let page = params.page.unwrap_or_default();
+        let mut next_page = None;
+        let transfers = maybe_transfers.map(|transfers| {
+            let matching =
+                transfers
+                    .into_iter()
+                    .filter(|transfer| match &params.account_identifier {
+                        Some(account_identifier) => direction.matches(transfer, account_identifier),
+                        None => true,
+                    });
+            let (page_transfers, next) = page_of(matching, page);
+            next_page = next;
+            page_transfers
+        });
+
+        // Return the result.
+        let result = Self::ResponseResult {
+            api_version,
+            block_hash: Some(block_hash),
+            transfers,
+            block_timestamp: Some(block.timestamp()),
+            next_page,
+        };
+        Ok(result)
+    }
+}
+
+/// Returns the given page of the items, along with the index of the next page if there are more.
+fn page_of<T>(items: impl Iterator<Item = T>, page: u32) -> (Vec<T>, Option<u32>) {
+    // Take one more item than fits on the page to find out whether there is a next page.
+    let mut page_items = items
+        .skip(page.saturating_mul(BLOCK_TRANSFERS_PAGE_SIZE) as usize)
+        .take(BLOCK_TRANSFERS_PAGE_SIZE as usize + 1)
+        .collect::<Vec<_>>();
+    if page_items.len() > BLOCK_TRANSFERS_PAGE_SIZE as usize {
+        page_items.truncate(BLOCK_TRANSFERS_PAGE_SIZE as usize);
+        return (page_items, page.checked_add(1));
+    }
+    (page_items, None)
+}
+
+/// Params for "chain_get_block_header" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetBlockHeaderParams {
+    /// The block identifier.
+    pub block_identifier: BlockIdentifier,
+}
+
+impl DocExample for GetBlockHeaderParams {
+    fn doc_example() -> &'static Self {
+        &*GET_BLOCK_HEADER_PARAMS
+    }
+}
+
+/// Result for "chain_get_block_header" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetBlockHeaderResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The block hash.
+    pub block_hash: BlockHash,
+    /// The block header.
+    pub block_header: JsonBlockHeader,
+    /// The finality signatures of the block known to this node.
+    pub proofs: Vec<JsonProof>,
+}
+
+impl DocExample for GetBlockHeaderResult {
+    fn doc_example() -> &'static Self {
+        &*GET_BLOCK_HEADER_RESULT
+    }
+}
+
+/// "chain_get_block_header" RPC.
+pub struct GetBlockHeader {}
+
+#[async_trait]
+impl RpcWithOptionalParams for GetBlockHeader {
+    const METHOD: &'static str = "chain_get_block_header";
+    type OptionalRequestParams = GetBlockHeaderParams;
+    type ResponseResult = GetBlockHeaderResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        maybe_params: Option<Self::OptionalRequestParams>,
+    ) -> Result<Self::ResponseResult, Error> {
+        // Get the block header.  Unlike blocks, headers are served regardless of the block
+        // availability index, so that light nodes can serve them too.
+        let maybe_id = maybe_params.map(|params| params.block_identifier);
+        let maybe_result = effect_builder
+            .make_request(
+                |responder| RpcRequest::GetBlockHeader {
+                    maybe_id,
+                    responder,
+                },
+                QueueKind::Api,
+            )
+            .await;
+
+        let BlockHeaderWithMetadata {
+            block_header,
+            block_signatures,
+        } = match maybe_result {
+            Some(block_header_with_metadata) => block_header_with_metadata,
+            None => {
+                let message = match maybe_id {
+                    Some(BlockIdentifier::Hash(block_hash)) => format!(
+                        "block header {:?} not stored on this node",
+                        block_hash.inner()
+                    ),
+                    Some(BlockIdentifier::Height(block_height)) => format!(
+                        "block header at height {} not stored on this node",
+                        block_height
+                    ),
+                    None => "failed to get highest block header".to_string(),
+                };
+                info!("{}", message);
+                return Err(Error::new(ErrorCode::NoSuchBlock, message));
+            }
+        };
+
+        // Return the result.
+        let result = Self::ResponseResult {
+            api_version,
+            block_hash: block_header.hash(),
+            block_header: JsonBlockHeader::from(block_header),
+            proofs: block_signatures
+                .proofs
+                .into_iter()
+                .map(JsonProof::from)
+                .collect(),
+        };
         Ok(result)
     }
 }
@@ -420,6 +829,148 @@ impl RpcWithOptionalParams for GetEraInfoBySwitchBlock {
     }
 }
 
+/// Params for "chain_get_era_summary" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetEraSummaryParams {
+    /// The identifier of any block in the era.
+    pub block_identifier: BlockIdentifier,
+}
+
+impl DocExample for GetEraSummaryParams {
+    fn doc_example() -> &'static Self {
+        &*GET_ERA_SUMMARY_PARAMS
+    }
+}
+
+/// Result for "chain_get_era_summary" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetEraSummaryResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The ID of the era containing the given block.
+    pub era_id: EraId,
+    /// The weights of the era's validators, as recorded in the switch block of the previous era.
+    /// `None` for the genesis era, or if that switch block is not stored on this node.
+    pub validator_weights: Option<Vec<JsonValidatorWeights>>,
+    /// The hash of the switch block which ended the era.  `None` if the era has not ended yet, or
+    /// if its switch block is not stored on this node.
+    pub switch_block_hash: Option<BlockHash>,
+    /// The era end report, as recorded in the switch block which ended the era.
+    pub era_end: Option<JsonEraEnd>,
+}
+
+impl DocExample for GetEraSummaryResult {
+    fn doc_example() -> &'static Self {
+        &*GET_ERA_SUMMARY_RESULT
+    }
+}
+
+/// "chain_get_era_summary" RPC.
+pub struct GetEraSummary {}
+
+#[async_trait]
+impl RpcWithOptionalParams for GetEraSummary {
+    const METHOD: &'static str = "chain_get_era_summary";
+    type OptionalRequestParams = GetEraSummaryParams;
+    type ResponseResult = GetEraSummaryResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        maybe_params: Option<Self::OptionalRequestParams>,
+    ) -> Result<Self::ResponseResult, Error> {
+        // The summary is assembled from stored block headers only, so like block headers it is
+        // served regardless of the block availability index.
+        let maybe_id = maybe_params.map(|params| params.block_identifier);
+        let block_header = match effect_builder
+            .make_request(
+                |responder| RpcRequest::GetBlockHeader {
+                    maybe_id,
+                    responder,
+                },
+                QueueKind::Api,
+            )
+            .await
+        {
+            Some(block_header_with_metadata) => block_header_with_metadata.block_header,
+            None => {
+                let message = match maybe_id {
+                    Some(BlockIdentifier::Hash(block_hash)) => format!(
+                        "block header {:?} not stored on this node",
+                        block_hash.inner()
+                    ),
+                    Some(BlockIdentifier::Height(block_height)) => format!(
+                        "block header at height {} not stored on this node",
+                        block_height
+                    ),
+                    None => "failed to get highest block header".to_string(),
+                };
+                info!("{}", message);
+                return Err(Error::new(ErrorCode::NoSuchBlock, message));
+            }
+        };
+        let era_id = block_header.era_id();
+
+        // The validators of an era are recorded in the switch block which ended the previous era.
+        let validator_weights = match era_id.checked_sub(1) {
+            Some(previous_era_id) => get_switch_block_header(effect_builder, previous_era_id)
+                .await
+                .as_ref()
+                .and_then(json_validator_weights),
+            None => None,
+        };
+
+        let maybe_switch_block_header = if block_header.is_switch_block() {
+            Some(block_header)
+        } else {
+            get_switch_block_header(effect_builder, era_id).await
+        };
+        let (switch_block_hash, era_end) = match maybe_switch_block_header {
+            Some(switch_block_header) => (
+                Some(switch_block_header.hash()),
+                switch_block_header.era_end().cloned().map(JsonEraEnd::from),
+            ),
+            None => (None, None),
+        };
+
+        Ok(Self::ResponseResult {
+            api_version,
+            era_id,
+            validator_weights,
+            switch_block_hash,
+            era_end,
+        })
+    }
+}
+
+/// Returns the header of the switch block which ended the given era, if it is stored.
+async fn get_switch_block_header<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    era_id: EraId,
+) -> Option<BlockHeader> {
+    effect_builder
+        .make_request(
+            |responder| RpcRequest::GetSwitchBlockHeader { era_id, responder },
+            QueueKind::Api,
+        )
+        .await
+}
+
+/// Returns the validator weights for the next era recorded in the given switch block header.
+fn json_validator_weights(switch_block_header: &BlockHeader) -> Option<Vec<JsonValidatorWeights>> {
+    switch_block_header
+        .next_era_validator_weights()
+        .map(|validator_weights| {
+            validator_weights
+                .iter()
+                .map(|(public_key, weight)| JsonValidatorWeights::new(public_key.clone(), *weight))
+                .collect()
+        })
+}
+
 pub(super) async fn get_block_with_metadata<REv: ReactorEventT>(
     maybe_id: Option<BlockIdentifier>,
     only_from_available_block_range: bool,
@@ -11,9 +11,10 @@ use casper_types::{bytesrepr::ToBytes, Key};
 
 use super::{
     chain::{self, BlockIdentifier},
-    state, Error, ReactorEventT, RpcRequest,
+    state, Error, ErrorCode, ReactorEventT, RpcRequest,
 };
 use crate::{
+    components::deploy_acceptor::Error as DeployAcceptorError,
     effect::EffectBuilder,
     reactor::QueueKind,
     types::{json_compatibility::StoredValue, AvailableBlockRange, Block},
@@ -84,6 +85,24 @@ pub enum ErrorData {
         /// The height range (inclusive) of fully available blocks.
         available_block_range: AvailableBlockRange,
     },
+    /// The node is syncing and too far behind the tip of the chain to handle the request.
+    NodeIsSyncing {
+        /// Additional info.
+        message: String,
+        /// The number of blocks by which the node is behind the tip of the chain, if known.
+        blocks_behind: Option<u64>,
+    },
+}
+
+/// Returns the `Error` for a deploy rejected by the node, which is retriable if the deploy was only
+/// rejected due to a transient condition of the node.
+pub(super) fn deploy_rejected_error(error: &DeployAcceptorError) -> Error {
+    let error_code = if error.is_transient() {
+        ErrorCode::DeployTemporarilyRejected
+    } else {
+        ErrorCode::InvalidDeploy
+    };
+    Error::new(error_code, error.to_string())
 }
 
 /// Returns an `Error` which includes the height range of fully available blocks as the additional
@@ -4,6 +4,7 @@
 #![allow(clippy::field_reassign_with_default)]
 
 use async_trait::async_trait;
+use http::Method;
 use once_cell::sync::Lazy;
 use schemars::{
     gen::{SchemaGenerator, SchemaSettings},
@@ -12,16 +13,24 @@ use schemars::{
 };
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use warp::{cors::Builder as CorsBuilder, filters::BoxedFilter, reply, Filter, Reply};
 
 use casper_types::ProtocolVersion;
 
 use super::{
-    account::PutDeploy,
-    chain::{GetBlock, GetBlockTransfers, GetEraInfoBySwitchBlock, GetStateRootHash},
-    info::{GetChainspec, GetDeploy, GetPeers, GetStatus, GetValidatorChanges},
+    account::{AddDeployApprovals, PutDeploy, PutDeployBatch, PutPendingDeploy},
+    chain::{
+        GetBlock, GetBlockHeader, GetBlockTransfers, GetBlockTransfersPage, GetBlockWithDeploys,
+        GetEraInfoBySwitchBlock, GetEraSummary, GetStateRootHash,
+    },
+    info::{
+        GetAccountDeploys, GetChainspec, GetContractEvents, GetDeploy, GetEraValidatorPerformance,
+        GetPeers, GetStatus, GetSyncProgress, GetValidatorChanges, WaitForDeploy,
+    },
     state::{
-        GetAccountInfo, GetAuctionInfo, GetBalance, GetDictionaryItem, GetItem, QueryBalance,
-        QueryGlobalState,
+        GetAccountInfo, GetAuctionInfo, GetAuctionInfoPage, GetAuctionSummary, GetBalance,
+        GetContractNamedKeys, GetDictionaryItem, GetDictionaryItems, GetItem, GetStateDiff,
+        QueryBalance, QueryBalances, QueryGlobalState,
     },
     Error, ReactorEventT, RpcWithOptionalParams, RpcWithParams, RpcWithoutParams,
 };
@@ -32,6 +41,9 @@ pub(crate) const DOCS_EXAMPLE_PROTOCOL_VERSION: ProtocolVersion =
 
 const DEFINITIONS_PATH: &str = "#/components/schemas/";
 
+/// The URL path segment, appended to the JSON-RPC path, at which the OpenRPC schema is served.
+pub const DISCOVER_API_PATH: &str = "discover";
+
 // As per https://spec.open-rpc.org/#service-discovery-method.
 pub(crate) static OPEN_RPC_SCHEMA: Lazy<OpenRpcSchema> = Lazy::new(|| {
     let contact = OpenRpcContactField {
@@ -67,17 +79,53 @@ pub(crate) static OPEN_RPC_SCHEMA: Lazy<OpenRpcSchema> = Lazy::new(|| {
     };
 
     schema.push_with_params::<PutDeploy>("receives a Deploy to be executed by the network");
+    schema.push_with_params::<PutDeployBatch>(
+        "receives several Deploys to be executed by the network, returning the outcome for each",
+    );
+    schema.push_with_params::<PutPendingDeploy>(
+        "receives a Deploy which may lack approvals, holding it until its approvals meet the \
+        account's deployment threshold",
+    );
+    schema.push_with_params::<AddDeployApprovals>(
+        "adds approvals to a Deploy held by this node, accepting it once its approvals meet the \
+        account's deployment threshold",
+    );
     schema.push_with_params::<GetDeploy>("returns a Deploy from the network");
+    schema.push_with_params::<GetContractEvents>(
+        "returns the events emitted by contracts during the execution of a Deploy",
+    );
+    schema.push_with_params::<WaitForDeploy>(
+        "waits until a Deploy has been executed in a Block, returning its execution result",
+    );
+    schema.push_with_params::<GetAccountDeploys>(
+        "returns the hashes and execution statuses of Deploys sent by an Account",
+    );
     schema.push_with_params::<GetAccountInfo>("returns an Account from the network");
     schema.push_with_params::<GetDictionaryItem>("returns an item from a Dictionary");
+    schema.push_with_params::<GetDictionaryItems>(
+        "returns a page of the items stored in a Dictionary",
+    );
+    schema.push_with_params::<GetContractNamedKeys>("returns a page of a contract's named keys");
+    schema.push_with_params::<GetStateDiff>(
+        "returns a page of the keys whose values differ between two global states",
+    );
     schema.push_with_params::<QueryGlobalState>(
         "a query to global state using either a Block hash or state root hash",
     );
     schema.push_with_params::<QueryBalance>(
         "query for a balance using a purse identifier and a state identifier",
     );
+    schema.push_with_params::<QueryBalances>(
+        "query for the balances of many purses using purse identifiers and a state identifier",
+    );
+    schema.push_with_params::<GetEraValidatorPerformance>(
+        "returns how each validator performed during an era, as seen by this node",
+    );
     schema.push_without_params::<GetPeers>("returns a list of peers connected to the node");
     schema.push_without_params::<GetStatus>("returns the current status of the node");
+    schema.push_without_params::<GetSyncProgress>(
+        "returns a summary of the progress of chain synchronization",
+    );
     schema
         .push_without_params::<GetValidatorChanges>("returns status changes of active validators");
     schema.push_without_params::<GetChainspec>(
@@ -85,9 +133,20 @@ pub(crate) static OPEN_RPC_SCHEMA: Lazy<OpenRpcSchema> = Lazy::new(|| {
         global_state.toml files",
     );
     schema.push_with_optional_params::<GetBlock>("returns a Block from the network");
+    schema.push_with_optional_params::<GetBlockWithDeploys>(
+        "returns a Block from the network along with all of its Deploys and their execution \
+        results",
+    );
     schema.push_with_optional_params::<GetBlockTransfers>(
         "returns all transfers for a Block from the network",
     );
+    schema.push_with_params::<GetBlockTransfersPage>(
+        "returns a page of the transfers for a Block from the network, optionally restricted to \
+        those from and/or to a specific Account",
+    );
+    schema.push_with_optional_params::<GetBlockHeader>(
+        "returns a Block's header along with its finality signatures from the network",
+    );
     schema.push_with_optional_params::<GetStateRootHash>(
         "returns a state root hash at a given Block",
     );
@@ -99,10 +158,22 @@ pub(crate) static OPEN_RPC_SCHEMA: Lazy<OpenRpcSchema> = Lazy::new(|| {
     schema.push_with_optional_params::<GetEraInfoBySwitchBlock>(
         "returns an EraInfo from the network",
     );
+    schema.push_with_optional_params::<GetEraSummary>(
+        "returns the ID, validator weights and era end report of the era containing a given Block",
+    );
     schema.push_with_optional_params::<GetAuctionInfo>(
         "returns the bids and validators as of either a specific block (by height or hash), or \
         the most recently added block",
     );
+    schema.push_with_params::<GetAuctionInfoPage>(
+        "returns a page of the bids, optionally restricted to specific validators, and the \
+        validators as of either a specific block (by height or hash), or the most recently added \
+        block",
+    );
+    schema.push_with_optional_params::<GetAuctionSummary>(
+        "returns the validator weights and staking totals as of either a specific block (by \
+        height or hash), or the most recently added block",
+    );
 
     schema
 });
@@ -452,6 +523,28 @@ impl RpcWithoutParams for ListRpcs {
     }
 }
 
+/// Returns a route serving the OpenRPC schema in response to `GET /<api_path>/discover`.
+///
+/// This provides the same document as the "rpc.discover" RPC, but is directly consumable by tools
+/// which fetch an OpenRPC document by URL, such as client SDK generators.  Cross-origin requests
+/// are permitted as per `cors`, extended to allow the method "GET".
+pub(crate) fn discover_route(
+    api_path: &'static str,
+    cors: CorsBuilder,
+) -> BoxedFilter<(Box<dyn Reply>,)> {
+    warp::get()
+        .and(warp::path(api_path))
+        .and(warp::path(DISCOVER_API_PATH))
+        .and(warp::path::end())
+        .map(|| reply::json(&*OPEN_RPC_SCHEMA))
+        .with(cors.allow_method(Method::GET))
+        .map(|reply| {
+            let reply: Box<dyn Reply> = Box::new(reply);
+            reply
+        })
+        .boxed()
+}
+
 mod doc_example_impls {
     use std::str::FromStr;
 
@@ -551,6 +644,12 @@ mod tests {
         assert!(incorrect_optional_params.is_empty())
     }
 
+    #[test]
+    fn check_chain_get_block_header_required_fields() {
+        let incorrect_optional_params = check_optional_params_fields::<GetBlockHeader>();
+        assert!(incorrect_optional_params.is_empty())
+    }
+
     #[test]
     fn check_chain_get_state_root_hash_required_fields() {
         let incorrect_optional_params = check_optional_params_fields::<GetStateRootHash>();
@@ -563,9 +662,21 @@ mod tests {
         assert!(incorrect_optional_params.is_empty())
     }
 
+    #[test]
+    fn check_chain_get_era_summary_required_fields() {
+        let incorrect_optional_params = check_optional_params_fields::<GetEraSummary>();
+        assert!(incorrect_optional_params.is_empty())
+    }
+
     #[test]
     fn check_state_get_auction_info_required_fields() {
         let incorrect_optional_params = check_optional_params_fields::<GetAuctionInfo>();
         assert!(incorrect_optional_params.is_empty())
     }
+
+    #[test]
+    fn check_state_get_auction_summary_required_fields() {
+        let incorrect_optional_params = check_optional_params_fields::<GetAuctionSummary>();
+        assert!(incorrect_optional_params.is_empty())
+    }
 }
@@ -2,8 +2,46 @@ use serde::{Deserialize, Serialize};
 
 use casper_json_rpc::ErrorCodeT;
 
+/// The broad class of failure indicated by an [`ErrorCode`].
+///
+/// Every error code belongs to exactly one category, which determines whether a request failing
+/// with that code is worth retrying unchanged.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    /// The request itself is invalid, and will fail again if retried unchanged.
+    Validation,
+    /// The requested item is not known to this node.
+    NotFound,
+    /// The node is syncing and cannot serve the request until it has caught up.
+    NodeSyncing,
+    /// The client has exceeded a limit on concurrent requests or subscriptions.
+    RateLimited,
+    /// The awaited outcome did not occur within the time the client was willing to wait.
+    TimedOut,
+    /// The node failed to handle a valid request.
+    Internal,
+}
+
+impl ErrorCategory {
+    /// Returns whether a request failing with an error of this category may succeed if retried
+    /// unchanged later.
+    pub fn is_retriable(self) -> bool {
+        match self {
+            ErrorCategory::Validation | ErrorCategory::NotFound => false,
+            ErrorCategory::NodeSyncing
+            | ErrorCategory::RateLimited
+            | ErrorCategory::TimedOut
+            | ErrorCategory::Internal => true,
+        }
+    }
+}
+
 /// The various codes which can be returned in the JSON-RPC Response's error object.
 ///
+/// The numeric values are stable; see [`ErrorCode::category`] for how they are classified.  The
+/// "retriable" field of the error object is set according to the code's category.
+///
 /// **NOTE:** These values will be changed to lie outside the restricted range as defined in the
 /// JSON-RPC spec as of casper-node v2.0.0.
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, Debug)]
@@ -35,6 +73,31 @@ pub enum ErrorCode {
     FailedToGetTrie = -32011,
     /// The requested state root hash was not found.
     NoSuchStateRoot = -32012,
+    /// The subscription to cancel was not found.
+    NoSuchSubscription = -32013,
+    /// The client already holds the maximum number of subscriptions.
+    TooManySubscriptions = -32014,
+    /// The client is not permitted to call the requested method.
+    MethodNotPermitted = -32015,
+    /// Too many requests for the requested method are currently being handled.
+    TooManyRequests = -32016,
+    /// The batch of deploys to submit contains too many deploys.
+    TooManyDeploysInBatch = -32017,
+    /// The node is syncing and too far behind the tip of the chain to handle the request.  The
+    /// request may be retried later.
+    NodeIsSyncing = -32018,
+    /// The request to query balances contains too many purses.
+    TooManyPursesInRequest = -32019,
+    /// No deploy with the given hash is awaiting approvals.
+    NoSuchPendingDeploy = -32020,
+    /// No snapshot of how validators performed during the given era is stored.
+    NoSuchEraValidatorPerformance = -32021,
+    /// The deploy waited for was not executed before the timeout expired.  The request may be
+    /// retried.
+    WaitForDeployTimedOut = -32022,
+    /// The given Deploy was rejected due to a transient condition of the node, e.g. it is shutting
+    /// down or holds too many deploys awaiting approvals.  The request may be retried later.
+    DeployTemporarilyRejected = -32023,
 }
 
 impl From<ErrorCode> for (i64, &'static str) {
@@ -59,8 +122,60 @@ impl From<ErrorCode> for (i64, &'static str) {
             }
             ErrorCode::FailedToGetTrie => (error_code as i64, "Failed to get trie"),
             ErrorCode::NoSuchStateRoot => (error_code as i64, "No such state root"),
+            ErrorCode::NoSuchSubscription => (error_code as i64, "No such subscription"),
+            ErrorCode::TooManySubscriptions => (error_code as i64, "Too many subscriptions"),
+            ErrorCode::MethodNotPermitted => (error_code as i64, "Method not permitted"),
+            ErrorCode::TooManyRequests => (error_code as i64, "Too many requests"),
+            ErrorCode::TooManyDeploysInBatch => (error_code as i64, "Too many deploys in batch"),
+            ErrorCode::NodeIsSyncing => (error_code as i64, "Node is syncing"),
+            ErrorCode::TooManyPursesInRequest => (error_code as i64, "Too many purses in request"),
+            ErrorCode::NoSuchPendingDeploy => (error_code as i64, "No such pending deploy"),
+            ErrorCode::NoSuchEraValidatorPerformance => {
+                (error_code as i64, "No such era validator performance")
+            }
+            ErrorCode::WaitForDeployTimedOut => (error_code as i64, "Timed out waiting for deploy"),
+            ErrorCode::DeployTemporarilyRejected => {
+                (error_code as i64, "Deploy temporarily rejected")
+            }
         }
     }
 }
 
-impl ErrorCodeT for ErrorCode {}
+impl ErrorCode {
+    /// Returns the category of this error code.
+    pub fn category(self) -> ErrorCategory {
+        match self {
+            ErrorCode::FailedToParseQueryKey
+            | ErrorCode::FailedToParseGetBalanceURef
+            | ErrorCode::InvalidDeploy
+            | ErrorCode::MethodNotPermitted
+            | ErrorCode::TooManyDeploysInBatch
+            | ErrorCode::TooManyPursesInRequest => ErrorCategory::Validation,
+            ErrorCode::NoSuchDeploy
+            | ErrorCode::NoSuchBlock
+            | ErrorCode::QueryFailed
+            | ErrorCode::FailedToGetBalance
+            | ErrorCode::NoSuchAccount
+            | ErrorCode::FailedToGetDictionaryURef
+            | ErrorCode::NoSuchStateRoot
+            | ErrorCode::NoSuchSubscription
+            | ErrorCode::NoSuchPendingDeploy
+            | ErrorCode::NoSuchEraValidatorPerformance => ErrorCategory::NotFound,
+            ErrorCode::NodeIsSyncing => ErrorCategory::NodeSyncing,
+            ErrorCode::TooManySubscriptions | ErrorCode::TooManyRequests => {
+                ErrorCategory::RateLimited
+            }
+            ErrorCode::WaitForDeployTimedOut => ErrorCategory::TimedOut,
+            ErrorCode::QueryFailedToExecute
+            | ErrorCode::GetBalanceFailedToExecute
+            | ErrorCode::FailedToGetTrie
+            | ErrorCode::DeployTemporarilyRejected => ErrorCategory::Internal,
+        }
+    }
+}
+
+impl ErrorCodeT for ErrorCode {
+    fn is_retriable(self) -> bool {
+        self.category().is_retriable()
+    }
+}
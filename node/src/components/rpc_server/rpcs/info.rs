@@ -6,24 +6,32 @@
 use std::{collections::BTreeMap, str};
 
 use async_trait::async_trait;
+use casper_json_rpc::ReservedErrorCode;
 use once_cell::sync::Lazy;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
-use casper_types::{EraId, ExecutionResult, ProtocolVersion, PublicKey};
+use casper_types::{
+    account::AccountHash, EraId, ExecutionResult, ProtocolVersion, PublicKey, TimeDiff,
+};
 
 use super::{
     docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
     Error, ErrorCode, ReactorEventT, RpcRequest, RpcWithParams, RpcWithoutParams,
 };
 use crate::{
-    components::consensus::ValidatorChange,
+    components::{
+        chain_synchronizer::{SyncPhase, SyncProgress},
+        chainspec_loader::UpgradePointInfo,
+        consensus::ValidatorChange,
+    },
     effect::EffectBuilder,
     reactor::QueueKind,
     types::{
-        Block, BlockHash, BlockHashAndHeight, ChainspecRawBytes, Deploy, DeployHash,
-        DeployMetadataExt, GetStatusResult, PeersMap,
+        AccountDeploy, Block, BlockHash, BlockHashAndHeight, Chainspec, ChainspecRawBytes,
+        ContractEvent, Deploy, DeployHash, DeployMetadataExt, EraValidatorPerformance,
+        GetStatusResult, PeersMap,
     },
 };
 
@@ -40,6 +48,44 @@ static GET_DEPLOY_RESULT: Lazy<GetDeployResult> = Lazy::new(|| GetDeployResult {
     }],
     block_hash_and_height: None,
 });
+static GET_CONTRACT_EVENTS_PARAMS: Lazy<GetContractEventsParams> =
+    Lazy::new(|| GetContractEventsParams {
+        deploy_hash: *Deploy::doc_example().id(),
+    });
+static GET_CONTRACT_EVENTS_RESULT: Lazy<GetContractEventsResult> =
+    Lazy::new(|| GetContractEventsResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        block_hash: Some(*Block::doc_example().hash()),
+        events: vec![ContractEvent::doc_example().clone()],
+    });
+static WAIT_FOR_DEPLOY_PARAMS: Lazy<WaitForDeployParams> = Lazy::new(|| WaitForDeployParams {
+    deploy_hash: *Deploy::doc_example().id(),
+    timeout: DEFAULT_WAIT_FOR_DEPLOY_TIMEOUT,
+});
+static WAIT_FOR_DEPLOY_RESULT: Lazy<WaitForDeployResult> = Lazy::new(|| WaitForDeployResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    deploy_hash: *Deploy::doc_example().id(),
+    execution_result: JsonExecutionResult {
+        block_hash: *Block::doc_example().hash(),
+        result: ExecutionResult::example().clone(),
+    },
+});
+static GET_ACCOUNT_DEPLOYS_PARAMS: Lazy<GetAccountDeploysParams> =
+    Lazy::new(|| GetAccountDeploysParams {
+        account_identifier: AccountIdentifier::PublicKey(PublicKey::doc_example().clone()),
+        after: None,
+    });
+static GET_ACCOUNT_DEPLOYS_RESULT: Lazy<GetAccountDeploysResult> =
+    Lazy::new(|| GetAccountDeploysResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        deploys: vec![JsonAccountDeploy {
+            deploy_hash: *Deploy::doc_example().id(),
+            block_hash: Some(*Block::doc_example().hash()),
+            block_height: Some(Block::doc_example().height()),
+            execution_status: JsonDeployExecutionStatus::Success,
+        }],
+        next_page_after: None,
+    });
 static GET_PEERS_RESULT: Lazy<GetPeersResult> = Lazy::new(|| GetPeersResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     peers: GetStatusResult::doc_example().peers.clone(),
@@ -53,10 +99,36 @@ static GET_VALIDATOR_CHANGES_RESULT: Lazy<GetValidatorChangesResult> = Lazy::new
         changes,
     }
 });
+static GET_ERA_VALIDATOR_PERFORMANCE_PARAMS: Lazy<GetEraValidatorPerformanceParams> =
+    Lazy::new(|| GetEraValidatorPerformanceParams {
+        era_id: EraId::new(1),
+    });
+static GET_ERA_VALIDATOR_PERFORMANCE_RESULT: Lazy<GetEraValidatorPerformanceResult> =
+    Lazy::new(|| GetEraValidatorPerformanceResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        era_id: EraId::new(1),
+        validators: vec![JsonValidatorPerformance {
+            public_key: PublicKey::doc_example().clone(),
+            blocks_proposed: 12,
+            rounds_missed: Some(3),
+            finality_signatures: 96,
+            equivocated: false,
+        }],
+    });
 static GET_CHAINSPEC_RESULT: Lazy<GetChainspecResult> = Lazy::new(|| GetChainspecResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     chainspec_bytes: ChainspecRawBytes::new(vec![42, 42].into(), None, None),
 });
+static GET_SYNC_PROGRESS_RESULT: Lazy<GetSyncProgressResult> =
+    Lazy::new(|| GetSyncProgressResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        sync_progress: SyncProgress {
+            phase: SyncPhase::SyncingBlocksFromGenesis,
+            blocks_remaining: Some(1_000),
+            tries_fetched: 25_000,
+            estimated_time_remaining: Some(TimeDiff::from(600_000)),
+        },
+    });
 
 /// Params for "info_get_deploy" RPC request.
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
@@ -177,6 +249,359 @@ impl RpcWithParams for GetDeploy {
     }
 }
 
+/// Params for "info_get_contract_events" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetContractEventsParams {
+    /// The hash of the deploy whose execution emitted the events.
+    pub deploy_hash: DeployHash,
+}
+
+impl DocExample for GetContractEventsParams {
+    fn doc_example() -> &'static Self {
+        &*GET_CONTRACT_EVENTS_PARAMS
+    }
+}
+
+/// Result for "info_get_contract_events" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetContractEventsResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The hash of the block in which the deploy was executed, if its execution result is known.
+    pub block_hash: Option<BlockHash>,
+    /// The events emitted by contracts during the deploy's execution, in the order emitted.
+    pub events: Vec<ContractEvent>,
+}
+
+impl DocExample for GetContractEventsResult {
+    fn doc_example() -> &'static Self {
+        &*GET_CONTRACT_EVENTS_RESULT
+    }
+}
+
+/// "info_get_contract_events" RPC.
+pub struct GetContractEvents {}
+
+#[async_trait]
+impl RpcWithParams for GetContractEvents {
+    const METHOD: &'static str = "info_get_contract_events";
+    type RequestParams = GetContractEventsParams;
+    type ResponseResult = GetContractEventsResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let maybe_deploy_and_metadata = effect_builder
+            .make_request(
+                |responder| RpcRequest::GetDeploy {
+                    hash: params.deploy_hash,
+                    finalized_approvals: false,
+                    responder,
+                },
+                QueueKind::Api,
+            )
+            .await;
+
+        let metadata_ext = match maybe_deploy_and_metadata {
+            Some((_deploy, metadata_ext)) => metadata_ext,
+            None => {
+                let message = format!(
+                    "failed to get {} and metadata from storage",
+                    params.deploy_hash
+                );
+                info!("{}", message);
+                return Err(Error::new(ErrorCode::NoSuchDeploy, message));
+            }
+        };
+
+        let block_hash = match metadata_ext {
+            DeployMetadataExt::Metadata(metadata) => {
+                metadata.execution_results.keys().next().copied()
+            }
+            DeployMetadataExt::BlockInfo(_) | DeployMetadataExt::Empty => None,
+        };
+        let events = match block_hash {
+            Some(_) => effect_builder
+                .get_contract_events_from_storage(params.deploy_hash)
+                .await
+                .unwrap_or_default(),
+            None => Vec::new(),
+        };
+
+        let result = Self::ResponseResult {
+            api_version,
+            block_hash,
+            events,
+        };
+        Ok(result)
+    }
+}
+
+/// The time the "info_wait_for_deploy" RPC waits for the deploy to be executed if no timeout is
+/// given.
+const DEFAULT_WAIT_FOR_DEPLOY_TIMEOUT: TimeDiff = TimeDiff::from_seconds(60);
+
+/// The longest time the "info_wait_for_deploy" RPC waits for the deploy to be executed.
+const MAX_WAIT_FOR_DEPLOY_TIMEOUT: TimeDiff = TimeDiff::from_seconds(300);
+
+/// Params for "info_wait_for_deploy" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct WaitForDeployParams {
+    /// The hash of the deploy to wait for.
+    pub deploy_hash: DeployHash,
+    /// How long to wait for the deploy to be executed before giving up.  If omitted, waits for one
+    /// minute.  Values above five minutes are treated as five minutes.
+    #[serde(default = "wait_for_deploy_timeout_default")]
+    pub timeout: TimeDiff,
+}
+
+/// The default for `WaitForDeployParams::timeout`.
+fn wait_for_deploy_timeout_default() -> TimeDiff {
+    DEFAULT_WAIT_FOR_DEPLOY_TIMEOUT
+}
+
+impl DocExample for WaitForDeployParams {
+    fn doc_example() -> &'static Self {
+        &*WAIT_FOR_DEPLOY_PARAMS
+    }
+}
+
+/// Result for "info_wait_for_deploy" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct WaitForDeployResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The hash of the deploy.
+    pub deploy_hash: DeployHash,
+    /// The hash of the block in which the deploy was executed, and the execution result.
+    pub execution_result: JsonExecutionResult,
+}
+
+impl DocExample for WaitForDeployResult {
+    fn doc_example() -> &'static Self {
+        &*WAIT_FOR_DEPLOY_RESULT
+    }
+}
+
+/// "info_wait_for_deploy" RPC.
+///
+/// Waits until the deploy has been executed in a block added to the linear chain, returning
+/// immediately if it already has been.
+pub struct WaitForDeploy {}
+
+#[async_trait]
+impl RpcWithParams for WaitForDeploy {
+    const METHOD: &'static str = "info_wait_for_deploy";
+    type RequestParams = WaitForDeployParams;
+    type ResponseResult = WaitForDeployResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let timeout = params.timeout.min(MAX_WAIT_FOR_DEPLOY_TIMEOUT);
+        let maybe_execution_result = effect_builder
+            .make_request(
+                |responder| RpcRequest::WaitForDeploy {
+                    deploy_hash: params.deploy_hash,
+                    timeout,
+                    responder,
+                },
+                QueueKind::Api,
+            )
+            .await;
+
+        let (block_hash, result) = match maybe_execution_result {
+            Some(execution_result) => execution_result,
+            None => {
+                let message = format!("{} was not executed within {}", params.deploy_hash, timeout);
+                info!("{}", message);
+                return Err(Error::new(ErrorCode::WaitForDeployTimedOut, message));
+            }
+        };
+
+        let result = Self::ResponseResult {
+            api_version,
+            deploy_hash: params.deploy_hash,
+            execution_result: JsonExecutionResult { block_hash, result },
+        };
+        Ok(result)
+    }
+}
+
+/// Maximum number of deploys returned per page by the "info_get_account_deploys" RPC.
+const ACCOUNT_DEPLOYS_PAGE_SIZE: u32 = 100;
+
+/// Identifier of an account.
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum AccountIdentifier {
+    /// The account identified by this public key.
+    PublicKey(PublicKey),
+    /// The account identified by this account hash.
+    AccountHash(AccountHash),
+}
+
+impl AccountIdentifier {
+    /// Returns the hash of the identified account.
+    pub(crate) fn account_hash(&self) -> AccountHash {
+        match self {
+            AccountIdentifier::PublicKey(public_key) => public_key.to_account_hash(),
+            AccountIdentifier::AccountHash(account_hash) => *account_hash,
+        }
+    }
+}
+
+/// Params for "info_get_account_deploys" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetAccountDeploysParams {
+    /// The account which sent the deploys.
+    pub account_identifier: AccountIdentifier,
+    /// If provided, only deploys sent before the one at this position are returned. Use the
+    /// `next_page_after` value of the previous page to continue paging.
+    pub after: Option<String>,
+}
+
+impl DocExample for GetAccountDeploysParams {
+    fn doc_example() -> &'static Self {
+        &*GET_ACCOUNT_DEPLOYS_PARAMS
+    }
+}
+
+/// The execution status of a deploy, as known to this node.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum JsonDeployExecutionStatus {
+    /// The deploy has not been included in any block known to this node.
+    Pending,
+    /// The deploy has been included in a block, but its execution result is not known to this
+    /// node.
+    Unknown,
+    /// The deploy was executed successfully.
+    Success,
+    /// The deploy failed to execute.
+    Failure {
+        /// The error message associated with the failure.
+        error_message: String,
+    },
+}
+
+/// A deploy sent by an account.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct JsonAccountDeploy {
+    /// The deploy hash.
+    pub deploy_hash: DeployHash,
+    /// The hash of the block containing the deploy, if known.
+    pub block_hash: Option<BlockHash>,
+    /// The height of the block containing the deploy, if known.
+    pub block_height: Option<u64>,
+    /// The execution status of the deploy.
+    pub execution_status: JsonDeployExecutionStatus,
+}
+
+impl From<AccountDeploy> for JsonAccountDeploy {
+    fn from(account_deploy: AccountDeploy) -> Self {
+        let execution_status = match (
+            account_deploy.block_hash_and_height,
+            account_deploy.execution_result,
+        ) {
+            (None, _) => JsonDeployExecutionStatus::Pending,
+            (Some(_), None) => JsonDeployExecutionStatus::Unknown,
+            (Some(_), Some(ExecutionResult::Success { .. })) => JsonDeployExecutionStatus::Success,
+            (Some(_), Some(ExecutionResult::Failure { error_message, .. })) => {
+                JsonDeployExecutionStatus::Failure { error_message }
+            }
+        };
+        JsonAccountDeploy {
+            deploy_hash: account_deploy.deploy_hash,
+            block_hash: account_deploy
+                .block_hash_and_height
+                .map(|block_hash_and_height| block_hash_and_height.block_hash),
+            block_height: account_deploy
+                .block_hash_and_height
+                .map(|block_hash_and_height| block_hash_and_height.block_height),
+            execution_status,
+        }
+    }
+}
+
+/// Result for "info_get_account_deploys" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetAccountDeploysResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// Up to 100 of the deploys sent by the account, most recent first.
+    pub deploys: Vec<JsonAccountDeploy>,
+    /// The opaque position of the last deploy of this page if more deploys follow, to be passed
+    /// as `after` to retrieve the next page.
+    pub next_page_after: Option<String>,
+}
+
+impl DocExample for GetAccountDeploysResult {
+    fn doc_example() -> &'static Self {
+        &*GET_ACCOUNT_DEPLOYS_RESULT
+    }
+}
+
+/// "info_get_account_deploys" RPC.
+pub struct GetAccountDeploys {}
+
+#[async_trait]
+impl RpcWithParams for GetAccountDeploys {
+    const METHOD: &'static str = "info_get_account_deploys";
+    type RequestParams = GetAccountDeploysParams;
+    type ResponseResult = GetAccountDeploysResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let after = match params.after.as_deref().map(base16::decode) {
+            None => None,
+            Some(Ok(after)) => Some(after),
+            Some(Err(error)) => {
+                let error_msg = format!("failed to parse after: {}", error);
+                info!("{}", error_msg);
+                return Err(Error::new(ReservedErrorCode::InvalidParams, error_msg));
+            }
+        };
+        let account_deploys = effect_builder
+            .get_account_deploys_from_storage(
+                params.account_identifier.account_hash(),
+                after,
+                ACCOUNT_DEPLOYS_PAGE_SIZE,
+            )
+            .await;
+
+        let result = Self::ResponseResult {
+            api_version,
+            deploys: account_deploys
+                .deploys
+                .into_iter()
+                .map(JsonAccountDeploy::from)
+                .collect(),
+            next_page_after: account_deploys
+                .next_page_after
+                .map(|after| base16::encode_lower(&after)),
+        };
+        Ok(result)
+    }
+}
+
 /// Result for "info_get_peers" RPC response.
 #[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -247,6 +672,44 @@ impl RpcWithoutParams for GetStatus {
     }
 }
 
+/// Result for the "info_get_sync_progress" RPC.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetSyncProgressResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// A summary of the progress of chain synchronization.
+    pub sync_progress: SyncProgress,
+}
+
+impl DocExample for GetSyncProgressResult {
+    fn doc_example() -> &'static Self {
+        &*GET_SYNC_PROGRESS_RESULT
+    }
+}
+
+/// "info_get_sync_progress" RPC.
+pub struct GetSyncProgress {}
+
+#[async_trait]
+impl RpcWithoutParams for GetSyncProgress {
+    const METHOD: &'static str = "info_get_sync_progress";
+    type ResponseResult = GetSyncProgressResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+    ) -> Result<Self::ResponseResult, Error> {
+        let sync_progress = effect_builder.get_sync_progress().await;
+        let result = Self::ResponseResult {
+            api_version,
+            sync_progress,
+        };
+        Ok(result)
+    }
+}
+
 /// A single change to a validator's status in the given era.
 #[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -348,6 +811,115 @@ impl RpcWithoutParams for GetValidatorChanges {
     }
 }
 
+/// Params for "info_get_era_validator_performance" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetEraValidatorPerformanceParams {
+    /// The era to return the validator performance for.
+    pub era_id: EraId,
+}
+
+impl DocExample for GetEraValidatorPerformanceParams {
+    fn doc_example() -> &'static Self {
+        &*GET_ERA_VALIDATOR_PERFORMANCE_PARAMS
+    }
+}
+
+/// How a validator performed during an era.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct JsonValidatorPerformance {
+    /// The public key of the validator.
+    pub public_key: PublicKey,
+    /// The number of the era's blocks proposed by the validator.
+    pub blocks_proposed: u64,
+    /// The number of rounds in which the validator was assigned but did not create any unit.
+    /// Only known to nodes which took part in the era's consensus.
+    pub rounds_missed: Option<u64>,
+    /// The number of the era's blocks for which this node holds a finality signature by the
+    /// validator.
+    pub finality_signatures: u64,
+    /// Whether the validator was reported as an equivocator in the era's switch block.
+    pub equivocated: bool,
+}
+
+/// Result for "info_get_era_validator_performance" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetEraValidatorPerformanceResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The era the validators' performance relates to.
+    pub era_id: EraId,
+    /// How each validator performed during the era.
+    pub validators: Vec<JsonValidatorPerformance>,
+}
+
+impl GetEraValidatorPerformanceResult {
+    pub(crate) fn new(
+        api_version: ProtocolVersion,
+        era_id: EraId,
+        performance: EraValidatorPerformance,
+    ) -> Self {
+        let validators = performance
+            .validators
+            .into_iter()
+            .map(|(public_key, performance)| JsonValidatorPerformance {
+                public_key,
+                blocks_proposed: performance.blocks_proposed,
+                rounds_missed: performance.rounds_missed,
+                finality_signatures: performance.finality_signatures,
+                equivocated: performance.equivocated,
+            })
+            .collect();
+        GetEraValidatorPerformanceResult {
+            api_version,
+            era_id,
+            validators,
+        }
+    }
+}
+
+impl DocExample for GetEraValidatorPerformanceResult {
+    fn doc_example() -> &'static Self {
+        &*GET_ERA_VALIDATOR_PERFORMANCE_RESULT
+    }
+}
+
+/// "info_get_era_validator_performance" RPC.
+pub struct GetEraValidatorPerformance {}
+
+#[async_trait]
+impl RpcWithParams for GetEraValidatorPerformance {
+    const METHOD: &'static str = "info_get_era_validator_performance";
+    type RequestParams = GetEraValidatorPerformanceParams;
+    type ResponseResult = GetEraValidatorPerformanceResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let performance = match effect_builder
+            .get_era_validator_performance_from_storage(params.era_id)
+            .await
+        {
+            Some(performance) => performance,
+            None => {
+                let message = format!("no validator performance stored for era {}", params.era_id);
+                info!("{}", message);
+                return Err(Error::new(
+                    ErrorCode::NoSuchEraValidatorPerformance,
+                    message,
+                ));
+            }
+        };
+        let result = Self::ResponseResult::new(api_version, params.era_id, performance);
+        Ok(result)
+    }
+}
+
 /// Result for the "info_get_chainspec" RPC.
 #[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
 pub struct GetChainspecResult {
@@ -373,6 +945,43 @@ impl DocExample for GetChainspecResult {
     }
 }
 
+/// Result for the REST "/chainspec" request.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetChainspecDetailsResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The chainspec file bytes.
+    pub chainspec_bytes: ChainspecRawBytes,
+    /// The contents of the chainspec.toml file.
+    pub chainspec_toml: String,
+    /// The chainspec as parsed by the node.
+    #[schemars(with = "serde_json::Map<String, serde_json::Value>")]
+    pub chainspec: serde_json::Value,
+    /// The upgrade points installed on the node, ordered by protocol version.
+    pub upgrade_points: Vec<UpgradePointInfo>,
+}
+
+impl GetChainspecDetailsResult {
+    pub(crate) fn new(
+        api_version: ProtocolVersion,
+        chainspec_bytes: ChainspecRawBytes,
+        chainspec: &Chainspec,
+        upgrade_points: Vec<UpgradePointInfo>,
+    ) -> Result<Self, serde_json::Error> {
+        let chainspec_toml =
+            String::from_utf8_lossy(chainspec_bytes.chainspec_bytes()).into_owned();
+        Ok(Self {
+            api_version,
+            chainspec_bytes,
+            chainspec_toml,
+            chainspec: serde_json::to_value(chainspec)?,
+            upgrade_points,
+        })
+    }
+}
+
 /// "info_get_chainspec" RPC.
 pub struct GetChainspec {}
 
@@ -23,17 +23,19 @@ use super::{
 use crate::{
     effect::{requests::RpcRequest, EffectBuilder},
     reactor::QueueKind,
-    types::{Block, BlockHash, Deploy},
+    types::{json_compatibility::GasProfile, Block, BlockHash, Deploy},
 };
 
 static SPECULATIVE_EXEC_PARAMS: Lazy<SpeculativeExecParams> = Lazy::new(|| SpeculativeExecParams {
     block_identifier: Some(BlockIdentifier::Hash(*Block::doc_example().hash())),
     deploy: Deploy::doc_example().clone(),
+    profile_gas: true,
 });
 static SPECULATIVE_EXEC_RESULT: Lazy<SpeculativeExecResult> = Lazy::new(|| SpeculativeExecResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     block_hash: *Block::doc_example().hash(),
     execution_result: ExecutionResult::example().clone(),
+    gas_profile: Some(GasProfile::doc_example().clone()),
 });
 
 /// Params for "speculative_exec" RPC request.
@@ -44,6 +46,10 @@ pub struct SpeculativeExecParams {
     pub block_identifier: Option<BlockIdentifier>,
     /// Deploy to execute.
     pub deploy: Deploy,
+    /// Whether to return a breakdown of the gas consumed per host function and per class of Wasm
+    /// opcodes.
+    #[serde(default)]
+    pub profile_gas: bool,
 }
 
 impl DocExample for SpeculativeExecParams {
@@ -63,6 +69,9 @@ pub struct SpeculativeExecResult {
     pub block_hash: BlockHash,
     /// Result of the execution.
     pub execution_result: ExecutionResult,
+    /// Breakdown of the gas consumed by the execution, if requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_profile: Option<GasProfile>,
 }
 
 impl DocExample for SpeculativeExecResult {
@@ -88,6 +97,7 @@ impl RpcWithParams for SpeculativeExec {
         let SpeculativeExecParams {
             block_identifier: maybe_block_id,
             deploy,
+            profile_gas,
         } = params;
         // This RPC request is restricted by the block availability index.
         let only_from_available_block_range = true;
@@ -104,6 +114,7 @@ impl RpcWithParams for SpeculativeExec {
                 |responder| RpcRequest::SpeculativeDeployExecute {
                     block_header: block.take_header(),
                     deploy: Box::new(deploy),
+                    profile_gas,
                     responder,
                 },
                 QueueKind::Api,
@@ -111,17 +122,18 @@ impl RpcWithParams for SpeculativeExec {
             .await;
 
         match result {
-            Ok(Some(execution_result)) => {
+            Ok(Some((execution_result, gas_profile))) => {
                 let result = Self::ResponseResult {
                     api_version,
                     block_hash,
                     execution_result,
+                    gas_profile: gas_profile.as_ref().map(GasProfile::from),
                 };
                 Ok(result)
             }
             Ok(None) => Err(Error::new(
-                ErrorCode::NoSuchBlock,
-                "block hash not found".to_string(),
+                ReservedErrorCode::InternalError,
+                "unexpected number of execution results for a single deploy".to_string(),
             )),
             Err(error) => {
                 let rpc_error = match error {
@@ -3,7 +3,7 @@
 // TODO - remove once schemars stops causing warning.
 #![allow(clippy::field_reassign_with_default)]
 
-use std::str;
+use std::{collections::BTreeSet, convert::TryFrom, str};
 
 use async_trait::async_trait;
 use once_cell::sync::Lazy;
@@ -12,7 +12,9 @@ use serde::{Deserialize, Serialize};
 use tracing::{error, info, warn};
 
 use casper_execution_engine::{
-    core::engine_state::{BalanceResult, GetBidsResult, QueryResult},
+    core::engine_state::{
+        self, BalanceIdentifier, BalanceResult, GetBalancesResult, GetBidsResult, QueryResult,
+    },
     storage::trie::merkle_proof::TrieMerkleProof,
 };
 use casper_hashing::Digest;
@@ -20,8 +22,9 @@ use casper_json_rpc::ReservedErrorCode;
 use casper_types::{
     account::AccountHash,
     bytesrepr::{Bytes, ToBytes},
-    CLValue, Key, ProtocolVersion, PublicKey, SecretKey, StoredValue as DomainStoredValue, URef,
-    U512,
+    system::auction::{Bids, EraValidators},
+    AccessRights, CLValue, ContractHash, Key, NamedKey, ProtocolVersion, PublicKey, SecretKey,
+    StoredValue as DomainStoredValue, URef, U512,
 };
 
 use crate::{
@@ -31,14 +34,28 @@ use crate::{
         chain::BlockIdentifier,
         common::{self, MERKLE_PROOF},
         docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
+        info::AccountIdentifier,
         Error, ErrorCode, ReactorEventT, RpcRequest, RpcWithOptionalParams, RpcWithParams,
     },
     types::{
-        json_compatibility::{Account as JsonAccount, AuctionState, StoredValue},
-        Block, BlockHash, JsonBlockHeader,
+        json_compatibility::{
+            Account as JsonAccount, AuctionState, AuctionStatePage, AuctionSummary, StoredValue,
+        },
+        Block, BlockHash, BlockHeight, JsonBlockHeader,
     },
 };
 
+/// The maximum number of bids returned by a single "state_get_auction_info_page" request.
+const MAX_AUCTION_INFO_PAGE_LIMIT: u32 = 100;
+/// The maximum number of items returned by a single "state_get_dictionary_items" request.
+const MAX_DICTIONARY_ITEMS_PAGE_LIMIT: u32 = 100;
+/// The maximum number of named keys returned by a single "state_get_contract_named_keys" request.
+const MAX_NAMED_KEYS_PAGE_LIMIT: u32 = 100;
+/// The maximum number of changes returned by a single "state_get_diff" request.
+const MAX_STATE_DIFF_PAGE_LIMIT: u32 = 100;
+/// The maximum number of purses whose balances are returned by a single "query_balances" request.
+const MAX_QUERY_BALANCES_PURSES: usize = 5_000;
+
 static GET_ITEM_PARAMS: Lazy<GetItemParams> = Lazy::new(|| GetItemParams {
     state_root_hash: *Block::doc_example().header().state_root_hash(),
     key: "deploy-af684263911154d26fa05be9963171802801a0b6aff8f199b7391eacb8edc9e1".to_string(),
@@ -50,9 +67,12 @@ static GET_ITEM_RESULT: Lazy<GetItemResult> = Lazy::new(|| GetItemResult {
     merkle_proof: MERKLE_PROOF.clone(),
 });
 static GET_BALANCE_PARAMS: Lazy<GetBalanceParams> = Lazy::new(|| GetBalanceParams {
-    state_root_hash: *Block::doc_example().header().state_root_hash(),
-    purse_uref: "uref-09480c3248ef76b603d386f3f4f8a5f87f597d4eaffd475433f861af187ab5db-007"
-        .to_string(),
+    state_root_hash: Some(*Block::doc_example().header().state_root_hash()),
+    state_identifier: None,
+    purse_uref: Some(
+        "uref-09480c3248ef76b603d386f3f4f8a5f87f597d4eaffd475433f861af187ab5db-007".to_string(),
+    ),
+    account_identifier: None,
 });
 static GET_BALANCE_RESULT: Lazy<GetBalanceResult> = Lazy::new(|| GetBalanceResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
@@ -66,6 +86,23 @@ static GET_AUCTION_INFO_RESULT: Lazy<GetAuctionInfoResult> = Lazy::new(|| GetAuc
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     auction_state: AuctionState::doc_example().clone(),
 });
+static GET_AUCTION_INFO_PAGE_PARAMS: Lazy<GetAuctionInfoPageParams> =
+    Lazy::new(|| GetAuctionInfoPageParams {
+        block_identifier: Some(BlockIdentifier::Hash(*Block::doc_example().hash())),
+        validators: None,
+        after: None,
+        limit: Some(MAX_AUCTION_INFO_PAGE_LIMIT),
+    });
+static GET_AUCTION_INFO_PAGE_RESULT: Lazy<GetAuctionInfoPageResult> =
+    Lazy::new(|| GetAuctionInfoPageResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        auction_state_page: AuctionStatePage::doc_example().clone(),
+    });
+static GET_AUCTION_SUMMARY_RESULT: Lazy<GetAuctionSummaryResult> =
+    Lazy::new(|| GetAuctionSummaryResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        auction_summary: AuctionSummary::doc_example().clone(),
+    });
 static GET_ACCOUNT_INFO_PARAMS: Lazy<GetAccountInfoParams> = Lazy::new(|| {
     let secret_key = SecretKey::ed25519_from_bytes([0; 32]).unwrap();
     let public_key = PublicKey::from(&secret_key);
@@ -97,6 +134,69 @@ static GET_DICTIONARY_ITEM_RESULT: Lazy<GetDictionaryItemResult> =
         stored_value: StoredValue::CLValue(CLValue::from_t(1u64).unwrap()),
         merkle_proof: MERKLE_PROOF.clone(),
     });
+static GET_DICTIONARY_ITEMS_PARAMS: Lazy<GetDictionaryItemsParams> =
+    Lazy::new(|| GetDictionaryItemsParams {
+        seed_uref: URef::from_formatted_str(
+            "uref-09480c3248ef76b603d386f3f4f8a5f87f597d4eaffd475433f861af187ab5db-007",
+        )
+        .unwrap(),
+        state_identifier: Some(GlobalStateIdentifier::BlockHash(
+            *Block::doc_example().hash(),
+        )),
+        after: None,
+        limit: Some(MAX_DICTIONARY_ITEMS_PAGE_LIMIT),
+    });
+static GET_DICTIONARY_ITEMS_RESULT: Lazy<GetDictionaryItemsResult> =
+    Lazy::new(|| GetDictionaryItemsResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        items: vec![JsonDictionaryItem {
+            dictionary_key:
+                "dictionary-67518854aa916c97d4e53df8570c8217ccc259da2721b692102d76acd0ee8d1f"
+                    .to_string(),
+            dictionary_item_key: "a_unique_entry_identifier".to_string(),
+            value: CLValue::from_t(1u64).unwrap(),
+        }],
+        next_page_after: None,
+    });
+static GET_CONTRACT_NAMED_KEYS_PARAMS: Lazy<GetContractNamedKeysParams> =
+    Lazy::new(|| GetContractNamedKeysParams {
+        contract_hash: ContractHash::new([42; 32]),
+        state_identifier: Some(GlobalStateIdentifier::BlockHash(
+            *Block::doc_example().hash(),
+        )),
+        after: None,
+        limit: Some(MAX_NAMED_KEYS_PAGE_LIMIT),
+    });
+static GET_CONTRACT_NAMED_KEYS_RESULT: Lazy<GetContractNamedKeysResult> =
+    Lazy::new(|| GetContractNamedKeysResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        named_keys: vec![NamedKey {
+            name: "example_dictionary".to_string(),
+            key: "uref-09480c3248ef76b603d386f3f4f8a5f87f597d4eaffd475433f861af187ab5db-007"
+                .to_string(),
+        }],
+        next_page_after: None,
+    });
+static GET_STATE_DIFF_PARAMS: Lazy<GetStateDiffParams> = Lazy::new(|| GetStateDiffParams {
+    old_state_identifier: GlobalStateIdentifier::BlockHeight(Block::doc_example().height() - 1),
+    new_state_identifier: Some(GlobalStateIdentifier::BlockHash(
+        *Block::doc_example().hash(),
+    )),
+    after: None,
+    limit: Some(MAX_STATE_DIFF_PAGE_LIMIT),
+});
+static GET_STATE_DIFF_RESULT: Lazy<GetStateDiffResult> = Lazy::new(|| GetStateDiffResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    old_state_root_hash: Digest::from([7; Digest::LENGTH]),
+    new_state_root_hash: *Block::doc_example().header().state_root_hash(),
+    changes: vec![JsonStateChange {
+        key: "uref-09480c3248ef76b603d386f3f4f8a5f87f597d4eaffd475433f861af187ab5db-007"
+            .to_string(),
+        old_value: Some(StoredValue::CLValue(CLValue::from_t(1u64).unwrap())),
+        new_value: Some(StoredValue::CLValue(CLValue::from_t(2u64).unwrap())),
+    }],
+    next_page_after: None,
+});
 static QUERY_GLOBAL_STATE_PARAMS: Lazy<QueryGlobalStateParams> =
     Lazy::new(|| QueryGlobalStateParams {
         state_identifier: GlobalStateIdentifier::BlockHash(*Block::doc_example().hash()),
@@ -127,6 +227,28 @@ static QUERY_BALANCE_RESULT: Lazy<QueryBalanceResult> = Lazy::new(|| QueryBalanc
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     balance: U512::from(123_456),
 });
+static QUERY_BALANCES_PARAMS: Lazy<QueryBalancesParams> = Lazy::new(|| QueryBalancesParams {
+    state_identifier: Some(GlobalStateIdentifier::BlockHash(
+        *Block::doc_example().hash(),
+    )),
+    purse_identifiers: vec![
+        PurseIdentifier::MainPurseUnderAccountHash(AccountHash::new([9u8; 32])),
+        PurseIdentifier::PurseUref(URef::new([7u8; 32], AccessRights::READ_ADD_WRITE)),
+    ],
+});
+static QUERY_BALANCES_RESULT: Lazy<QueryBalancesResult> = Lazy::new(|| QueryBalancesResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    state_root_hash: *Block::doc_example().header().state_root_hash(),
+    balances: QUERY_BALANCES_PARAMS
+        .purse_identifiers
+        .iter()
+        .map(|purse_identifier| PurseBalance {
+            purse_identifier: purse_identifier.clone(),
+            balance: Some(U512::from(123_456)),
+            error: None,
+        })
+        .collect(),
+});
 
 /// Params for "state_get_item" RPC request.
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
@@ -136,7 +258,9 @@ pub struct GetItemParams {
     pub state_root_hash: Digest,
     /// `casper_types::Key` as formatted string.
     pub key: String,
-    /// The path components starting from the key as base.
+    /// The path components starting from the key as base. A component of the form `[<index>]`
+    /// selects an element of a list or tuple by position, a value of a map by key, or an item of
+    /// a dictionary by item key.
     #[serde(default)]
     pub path: Vec<String>,
 }
@@ -213,10 +337,20 @@ impl RpcWithParams for GetItem {
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct GetBalanceParams {
-    /// The hash of state root.
-    pub state_root_hash: Digest,
-    /// Formatted URef.
-    pub purse_uref: String,
+    /// The hash of state root. Cannot be combined with `state_identifier`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_root_hash: Option<Digest>,
+    /// The state identifier used for the query. Cannot be combined with `state_root_hash`. If
+    /// neither is passed, the tip of the chain will be used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_identifier: Option<GlobalStateIdentifier>,
+    /// Formatted URef. Either this or `account_identifier` must be passed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purse_uref: Option<String>,
+    /// The account whose main purse's balance is returned. Either this or `purse_uref` must be
+    /// passed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_identifier: Option<AccountIdentifier>,
 }
 
 impl DocExample for GetBalanceParams {
@@ -258,17 +392,48 @@ impl RpcWithParams for GetBalance {
         api_version: ProtocolVersion,
         params: Self::RequestParams,
     ) -> Result<Self::ResponseResult, Error> {
-        // Try to parse the purse's URef from the params.
-        let purse_uref = match URef::from_formatted_str(&params.purse_uref)
-            .map_err(|error| format!("failed to parse purse_uref: {}", error))
-        {
-            Ok(uref) => uref,
-            Err(error_msg) => {
+        let state_root_hash = match (params.state_root_hash, params.state_identifier) {
+            (Some(state_root_hash), None) => state_root_hash,
+            (None, maybe_state_identifier) => {
+                get_state_root_hash(effect_builder, maybe_state_identifier).await?
+            }
+            (Some(_), Some(_)) => {
+                let error_msg =
+                    "only one of state_root_hash and state_identifier may be passed".to_string();
                 info!("{}", error_msg);
-                return Err(Error::new(
-                    ErrorCode::FailedToParseGetBalanceURef,
-                    error_msg,
-                ));
+                return Err(Error::new(ReservedErrorCode::InvalidParams, error_msg));
+            }
+        };
+
+        let purse_uref = match (params.purse_uref, params.account_identifier) {
+            // Try to parse the purse's URef from the params.
+            (Some(formatted_uref), None) => match URef::from_formatted_str(&formatted_uref)
+                .map_err(|error| format!("failed to parse purse_uref: {}", error))
+            {
+                Ok(uref) => uref,
+                Err(error_msg) => {
+                    info!("{}", error_msg);
+                    return Err(Error::new(
+                        ErrorCode::FailedToParseGetBalanceURef,
+                        error_msg,
+                    ));
+                }
+            },
+            // Resolve the main purse of the given account.
+            (None, Some(account_identifier)) => {
+                get_account(
+                    effect_builder,
+                    state_root_hash,
+                    account_identifier.account_hash(),
+                )
+                .await?
+                .main_purse()
+            }
+            _ => {
+                let error_msg =
+                    "exactly one of purse_uref and account_identifier must be passed".to_string();
+                info!("{}", error_msg);
+                return Err(Error::new(ReservedErrorCode::InvalidParams, error_msg));
             }
         };
 
@@ -276,7 +441,7 @@ impl RpcWithParams for GetBalance {
         let balance_result = effect_builder
             .make_request(
                 |responder| RpcRequest::GetBalance {
-                    state_root_hash: params.state_root_hash,
+                    state_root_hash,
                     purse_uref,
                     responder,
                 },
@@ -379,88 +544,165 @@ impl RpcWithOptionalParams for GetAuctionInfo {
         )
         .await?;
 
-        let protocol_version = api_version;
-
         // the global state hash of the last block
         let state_root_hash = *block.header().state_root_hash();
         // the block height of the last added block
         let block_height = block.header().height();
 
-        let get_bids_result = effect_builder
-            .make_request(
-                |responder| RpcRequest::GetBids {
-                    state_root_hash,
-                    responder,
-                },
-                QueueKind::Api,
-            )
-            .await;
+        let (era_validators, bids) = get_auction_data(effect_builder, api_version, &block).await?;
 
-        let bids = match get_bids_result {
-            Ok(GetBidsResult::Success { bids }) => bids,
-            Ok(GetBidsResult::RootNotFound) => {
-                error!(
-                    block_hash=?block.hash(),
-                    ?state_root_hash,
-                    "root not found while trying to get bids"
-                );
-                return Err(Error::new(
-                    ReservedErrorCode::InternalError,
-                    format!(
-                        "root not found when getting bids at block {:?}",
-                        block.hash().inner()
-                    ),
-                ));
-            }
-            Err(error) => {
-                error!(
-                    block_hash=?block.hash(),
-                    ?state_root_hash,
-                    ?error,
-                    "failed to get bids"
-                );
-                return Err(Error::new(
-                    ReservedErrorCode::InternalError,
-                    format!(
-                        "error getting bids at block {:?}: {}",
-                        block.hash().inner(),
-                        error
-                    ),
-                ));
-            }
+        let auction_state = AuctionState::new(state_root_hash, block_height, era_validators, bids);
+
+        let result = Self::ResponseResult {
+            api_version,
+            auction_state,
         };
+        Ok(result)
+    }
+}
 
-        let era_validators_result = effect_builder
-            .make_request(
-                |responder| RpcRequest::QueryEraValidators {
-                    state_root_hash,
-                    protocol_version,
-                    responder,
-                },
-                QueueKind::Api,
-            )
-            .await;
+/// Params for "state_get_auction_info_page" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetAuctionInfoPageParams {
+    /// The block identifier. If not provided, the most recently added block is used.
+    pub block_identifier: Option<BlockIdentifier>,
+    /// If provided, only the bids and weights of these validators are returned.
+    pub validators: Option<Vec<PublicKey>>,
+    /// If provided, only bids of validators whose public key is ordered after this one are
+    /// returned. Use the `next_page_after` value of the previous page to continue paging.
+    pub after: Option<PublicKey>,
+    /// The maximum number of bids to return. Defaults to, and is capped at, 100.
+    pub limit: Option<u32>,
+}
 
-        let era_validators = match era_validators_result {
-            Ok(validators) => validators,
-            Err(error) => {
-                error!(block_hash=?block.hash(), ?state_root_hash, ?error, "failed to get era validators");
-                return Err(Error::new(
-                    ReservedErrorCode::InternalError,
-                    format!(
-                        "failed to get validators at block {:?}: {}",
-                        block.hash().inner(),
-                        error
-                    ),
-                ));
-            }
+impl DocExample for GetAuctionInfoPageParams {
+    fn doc_example() -> &'static Self {
+        &*GET_AUCTION_INFO_PAGE_PARAMS
+    }
+}
+
+/// Result for "state_get_auction_info_page" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetAuctionInfoPageResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The requested page of the auction state.
+    pub auction_state_page: AuctionStatePage,
+}
+
+impl DocExample for GetAuctionInfoPageResult {
+    fn doc_example() -> &'static Self {
+        &*GET_AUCTION_INFO_PAGE_RESULT
+    }
+}
+
+/// "state_get_auction_info_page" RPC.
+pub struct GetAuctionInfoPage {}
+
+#[async_trait]
+impl RpcWithParams for GetAuctionInfoPage {
+    const METHOD: &'static str = "state_get_auction_info_page";
+    type RequestParams = GetAuctionInfoPageParams;
+    type ResponseResult = GetAuctionInfoPageResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        // This RPC request is restricted by the block availability index.
+        let only_from_available_block_range = true;
+
+        let block = common::get_block(
+            params.block_identifier,
+            only_from_available_block_range,
+            effect_builder,
+        )
+        .await?;
+        let (era_validators, bids) = get_auction_data(effect_builder, api_version, &block).await?;
+
+        let validators: Option<BTreeSet<PublicKey>> = params
+            .validators
+            .map(|validators| validators.into_iter().collect());
+        let limit = params
+            .limit
+            .unwrap_or(MAX_AUCTION_INFO_PAGE_LIMIT)
+            .clamp(1, MAX_AUCTION_INFO_PAGE_LIMIT);
+
+        let auction_state_page = AuctionStatePage::new(
+            *block.header().state_root_hash(),
+            block.header().height(),
+            &era_validators,
+            &bids,
+            validators.as_ref(),
+            params.after.as_ref(),
+            limit as usize,
+        );
+
+        let result = Self::ResponseResult {
+            api_version,
+            auction_state_page,
         };
+        Ok(result)
+    }
+}
 
-        let auction_state = AuctionState::new(state_root_hash, block_height, era_validators, bids);
+/// Result for "state_get_auction_summary" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetAuctionSummaryResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The summary of the auction state.
+    pub auction_summary: AuctionSummary,
+}
+
+impl DocExample for GetAuctionSummaryResult {
+    fn doc_example() -> &'static Self {
+        &*GET_AUCTION_SUMMARY_RESULT
+    }
+}
+
+/// "state_get_auction_summary" RPC.
+pub struct GetAuctionSummary {}
+
+#[async_trait]
+impl RpcWithOptionalParams for GetAuctionSummary {
+    const METHOD: &'static str = "state_get_auction_summary";
+    type OptionalRequestParams = GetAuctionInfoParams;
+    type ResponseResult = GetAuctionSummaryResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        maybe_params: Option<Self::OptionalRequestParams>,
+    ) -> Result<Self::ResponseResult, Error> {
+        // This RPC request is restricted by the block availability index.
+        let only_from_available_block_range = true;
+
+        let maybe_block_id = maybe_params.map(|params| params.block_identifier);
+        let block = common::get_block(
+            maybe_block_id,
+            only_from_available_block_range,
+            effect_builder,
+        )
+        .await?;
+        let (era_validators, bids) = get_auction_data(effect_builder, api_version, &block).await?;
+
+        let auction_summary = AuctionSummary::new(
+            *block.header().state_root_hash(),
+            block.header().height(),
+            &era_validators,
+            &bids,
+        );
 
         let result = Self::ResponseResult {
             api_version,
-            auction_state,
+            auction_summary,
         };
         Ok(result)
     }
@@ -757,6 +999,432 @@ impl RpcWithParams for GetDictionaryItem {
     }
 }
 
+/// Params for "state_get_dictionary_items" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetDictionaryItemsParams {
+    /// The dictionary's seed URef.
+    pub seed_uref: URef,
+    /// The state identifier used for the query, if none is passed the tip of the chain will be
+    /// used.
+    pub state_identifier: Option<GlobalStateIdentifier>,
+    /// If provided, only items stored under a dictionary key ordered after this one are
+    /// returned. Use the `next_page_after` value of the previous page to continue paging.
+    pub after: Option<String>,
+    /// The maximum number of items to return. Defaults to, and is capped at, 100.
+    pub limit: Option<u32>,
+}
+
+impl DocExample for GetDictionaryItemsParams {
+    fn doc_example() -> &'static Self {
+        &*GET_DICTIONARY_ITEMS_PARAMS
+    }
+}
+
+/// An item of a dictionary.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct JsonDictionaryItem {
+    /// The key under which the item is stored.
+    pub dictionary_key: String,
+    /// The key of the item within the dictionary.
+    pub dictionary_item_key: String,
+    /// The value of the item.
+    pub value: CLValue,
+}
+
+/// Result for "state_get_dictionary_items" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetDictionaryItemsResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The items contained in this page, ordered by dictionary key.
+    pub items: Vec<JsonDictionaryItem>,
+    /// The dictionary key up to which global state was scanned if the scan did not reach its end,
+    /// to be passed as `after` to retrieve the next page. As the work done per request is bounded,
+    /// a page can hold fewer than `limit` items, or none at all, even though more items follow.
+    pub next_page_after: Option<String>,
+}
+
+impl DocExample for GetDictionaryItemsResult {
+    fn doc_example() -> &'static Self {
+        &*GET_DICTIONARY_ITEMS_RESULT
+    }
+}
+
+/// "state_get_dictionary_items" RPC.
+pub struct GetDictionaryItems {}
+
+#[async_trait]
+impl RpcWithParams for GetDictionaryItems {
+    const METHOD: &'static str = "state_get_dictionary_items";
+    type RequestParams = GetDictionaryItemsParams;
+    type ResponseResult = GetDictionaryItemsResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let after = match params.after.as_deref().map(Key::from_formatted_str) {
+            None => None,
+            Some(Ok(Key::Dictionary(addr))) => Some(addr),
+            Some(Ok(key)) => {
+                let error_msg = format!("after must be a dictionary key, got {}", key);
+                info!("{}", error_msg);
+                return Err(Error::new(ErrorCode::FailedToParseQueryKey, error_msg));
+            }
+            Some(Err(error)) => {
+                let error_msg = format!("failed to parse after: {}", error);
+                info!("{}", error_msg);
+                return Err(Error::new(ErrorCode::FailedToParseQueryKey, error_msg));
+            }
+        };
+        let limit = params
+            .limit
+            .unwrap_or(MAX_DICTIONARY_ITEMS_PAGE_LIMIT)
+            .clamp(1, MAX_DICTIONARY_ITEMS_PAGE_LIMIT);
+        let state_root_hash = get_state_root_hash(effect_builder, params.state_identifier).await?;
+
+        let get_dictionary_items_result = effect_builder
+            .make_request(
+                |responder| RpcRequest::GetDictionaryItems {
+                    state_root_hash,
+                    seed_uref: params.seed_uref,
+                    after,
+                    limit: limit as usize,
+                    responder,
+                },
+                QueueKind::Api,
+            )
+            .await;
+
+        let (items, next_page_after) = match get_dictionary_items_result {
+            Ok(engine_state::GetDictionaryItemsResult::Success {
+                items,
+                next_page_after,
+            }) => (items, next_page_after),
+            Ok(engine_state::GetDictionaryItemsResult::RootNotFound) => {
+                info!(%state_root_hash, "get-dictionary-items failed: root not found");
+                let error = common::missing_block_or_state_root_error(
+                    effect_builder,
+                    ErrorCode::NoSuchStateRoot,
+                    format!("failed to get state root at {:?}", state_root_hash),
+                )
+                .await;
+                return Err(error);
+            }
+            Err(error) => {
+                info!(%error, "get-dictionary-items failed to execute");
+                return Err(Error::new(
+                    ErrorCode::QueryFailedToExecute,
+                    error.to_string(),
+                ));
+            }
+        };
+
+        let items = items
+            .into_iter()
+            .map(|item| JsonDictionaryItem {
+                dictionary_key: item.key.to_formatted_string(),
+                dictionary_item_key: String::from_utf8_lossy(item.dictionary_item_key.as_slice())
+                    .into_owned(),
+                value: item.value,
+            })
+            .collect();
+
+        let result = Self::ResponseResult {
+            api_version,
+            items,
+            next_page_after: next_page_after
+                .map(|addr| Key::Dictionary(addr).to_formatted_string()),
+        };
+        Ok(result)
+    }
+}
+
+/// Params for "state_get_contract_named_keys" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetContractNamedKeysParams {
+    /// The hash of the contract.
+    pub contract_hash: ContractHash,
+    /// The state identifier used for the query, if none is passed the tip of the chain will be
+    /// used.
+    pub state_identifier: Option<GlobalStateIdentifier>,
+    /// If provided, only named keys whose name is ordered after this one are returned. Use the
+    /// `next_page_after` value of the previous page to continue paging.
+    pub after: Option<String>,
+    /// The maximum number of named keys to return. Defaults to, and is capped at, 100.
+    pub limit: Option<u32>,
+}
+
+impl DocExample for GetContractNamedKeysParams {
+    fn doc_example() -> &'static Self {
+        &*GET_CONTRACT_NAMED_KEYS_PARAMS
+    }
+}
+
+/// Result for "state_get_contract_named_keys" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetContractNamedKeysResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The named keys contained in this page, ordered by name.
+    pub named_keys: Vec<NamedKey>,
+    /// The name of the last named key in this page if more named keys are available, to be
+    /// passed as `after` to retrieve the next page.
+    pub next_page_after: Option<String>,
+}
+
+impl DocExample for GetContractNamedKeysResult {
+    fn doc_example() -> &'static Self {
+        &*GET_CONTRACT_NAMED_KEYS_RESULT
+    }
+}
+
+/// "state_get_contract_named_keys" RPC.
+pub struct GetContractNamedKeys {}
+
+#[async_trait]
+impl RpcWithParams for GetContractNamedKeys {
+    const METHOD: &'static str = "state_get_contract_named_keys";
+    type RequestParams = GetContractNamedKeysParams;
+    type ResponseResult = GetContractNamedKeysResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let limit = params
+            .limit
+            .unwrap_or(MAX_NAMED_KEYS_PAGE_LIMIT)
+            .clamp(1, MAX_NAMED_KEYS_PAGE_LIMIT) as usize;
+        let state_root_hash = get_state_root_hash(effect_builder, params.state_identifier).await?;
+
+        let base_key = Key::from(params.contract_hash);
+        let (stored_value, _) =
+            run_query(effect_builder, state_root_hash, base_key, vec![]).await?;
+        let contract = match stored_value {
+            DomainStoredValue::Contract(contract) => contract,
+            _ => {
+                let error_msg = format!("failed to get contract {}", params.contract_hash);
+                info!(?stored_value, "{}", error_msg);
+                return Err(Error::new(ErrorCode::QueryFailed, error_msg));
+            }
+        };
+
+        let mut remaining_named_keys =
+            contract
+                .named_keys()
+                .iter()
+                .filter(|(name, _)| match params.after.as_ref() {
+                    Some(after) => *name > after,
+                    None => true,
+                });
+        let named_keys: Vec<NamedKey> = remaining_named_keys
+            .by_ref()
+            .take(limit)
+            .map(|(name, key)| NamedKey {
+                name: name.clone(),
+                key: key.to_formatted_string(),
+            })
+            .collect();
+        let next_page_after = if remaining_named_keys.next().is_some() {
+            named_keys.last().map(|named_key| named_key.name.clone())
+        } else {
+            None
+        };
+
+        let result = Self::ResponseResult {
+            api_version,
+            named_keys,
+            next_page_after,
+        };
+        Ok(result)
+    }
+}
+
+/// Params for "state_get_diff" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetStateDiffParams {
+    /// The identifier of the global state to compare from.
+    pub old_state_identifier: GlobalStateIdentifier,
+    /// The identifier of the global state to compare to, if none is passed the tip of the chain
+    /// will be used.
+    pub new_state_identifier: Option<GlobalStateIdentifier>,
+    /// If provided, only changes to keys ordered after this one are returned. Use the
+    /// `next_page_after` value of the previous page to continue paging.
+    pub after: Option<String>,
+    /// The maximum number of changes to return. Defaults to, and is capped at, 100.
+    pub limit: Option<u32>,
+}
+
+impl DocExample for GetStateDiffParams {
+    fn doc_example() -> &'static Self {
+        &*GET_STATE_DIFF_PARAMS
+    }
+}
+
+/// A change to the value stored under a key between two global states.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct JsonStateChange {
+    /// The key as a formatted string.
+    pub key: String,
+    /// The value stored under the key in the global state compared from, or `null` if the key
+    /// was added.
+    pub old_value: Option<StoredValue>,
+    /// The value stored under the key in the global state compared to, or `null` if the key was
+    /// removed.
+    pub new_value: Option<StoredValue>,
+}
+
+/// Result for "state_get_diff" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetStateDiffResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The state root hash of the global state compared from.
+    pub old_state_root_hash: Digest,
+    /// The state root hash of the global state compared to.
+    pub new_state_root_hash: Digest,
+    /// The changes contained in this page, ordered by key.
+    pub changes: Vec<JsonStateChange>,
+    /// The key of the last change in this page if more changes are available, to be passed as
+    /// `after` to retrieve the next page.
+    pub next_page_after: Option<String>,
+}
+
+impl DocExample for GetStateDiffResult {
+    fn doc_example() -> &'static Self {
+        &*GET_STATE_DIFF_RESULT
+    }
+}
+
+/// "state_get_diff" RPC.
+pub struct GetStateDiff {}
+
+#[async_trait]
+impl RpcWithParams for GetStateDiff {
+    const METHOD: &'static str = "state_get_diff";
+    type RequestParams = GetStateDiffParams;
+    type ResponseResult = GetStateDiffResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let after = match params
+            .after
+            .as_deref()
+            .map(Key::from_formatted_str)
+            .transpose()
+        {
+            Ok(after) => after,
+            Err(error) => {
+                let error_msg = format!("failed to parse after: {}", error);
+                info!("{}", error_msg);
+                return Err(Error::new(ErrorCode::FailedToParseQueryKey, error_msg));
+            }
+        };
+        let limit = params
+            .limit
+            .unwrap_or(MAX_STATE_DIFF_PAGE_LIMIT)
+            .clamp(1, MAX_STATE_DIFF_PAGE_LIMIT);
+        let (old_state_root_hash, _) =
+            get_state_root_hash_and_optional_header(effect_builder, params.old_state_identifier)
+                .await?;
+        let new_state_root_hash =
+            get_state_root_hash(effect_builder, params.new_state_identifier).await?;
+
+        let get_state_diff_result = effect_builder
+            .make_request(
+                |responder| RpcRequest::GetStateDiff {
+                    old_state_root_hash,
+                    new_state_root_hash,
+                    after,
+                    limit: limit as usize,
+                    responder,
+                },
+                QueueKind::Api,
+            )
+            .await;
+
+        let (changes, next_page_after) = match get_state_diff_result {
+            Ok(engine_state::GetStateDiffResult::Success {
+                changes,
+                next_page_after,
+            }) => (changes, next_page_after),
+            Ok(engine_state::GetStateDiffResult::RootNotFound(state_root_hash)) => {
+                info!(%state_root_hash, "get-state-diff failed: root not found");
+                let error = common::missing_block_or_state_root_error(
+                    effect_builder,
+                    ErrorCode::NoSuchStateRoot,
+                    format!("failed to get state root at {:?}", state_root_hash),
+                )
+                .await;
+                return Err(error);
+            }
+            Ok(engine_state::GetStateDiffResult::MissingTrie(trie_key)) => {
+                info!(%trie_key, "get-state-diff failed: missing trie");
+                return Err(Error::new(
+                    ErrorCode::QueryFailed,
+                    format!("global state is incomplete, missing trie {}", trie_key),
+                ));
+            }
+            Err(error) => {
+                info!(%error, "get-state-diff failed to execute");
+                return Err(Error::new(
+                    ErrorCode::QueryFailedToExecute,
+                    error.to_string(),
+                ));
+            }
+        };
+
+        let changes = changes
+            .into_iter()
+            .map(|change| {
+                let key = change.key.to_formatted_string();
+                let to_json = |value: DomainStoredValue| {
+                    StoredValue::try_from(value).map_err(|error| {
+                        warn!(?error, %key, "failed to encode stored value");
+                        Error::new(
+                            ReservedErrorCode::InternalError,
+                            format!("failed to encode stored value: {}", error),
+                        )
+                    })
+                };
+                let old_value = change.old_value.map(to_json).transpose()?;
+                let new_value = change.new_value.map(to_json).transpose()?;
+                Ok(JsonStateChange {
+                    key,
+                    old_value,
+                    new_value,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let result = Self::ResponseResult {
+            api_version,
+            old_state_root_hash,
+            new_state_root_hash,
+            changes,
+            next_page_after: next_page_after.map(|key| key.to_formatted_string()),
+        };
+        Ok(result)
+    }
+}
+
 /// Identifier for possible ways to query Global State
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
 #[serde(deny_unknown_fields)]
@@ -777,7 +1445,9 @@ pub struct QueryGlobalStateParams {
     pub state_identifier: GlobalStateIdentifier,
     /// `casper_types::Key` as formatted string.
     pub key: String,
-    /// The path components starting from the key as base.
+    /// The path components starting from the key as base. A component of the form `[<index>]`
+    /// selects an element of a list or tuple by position, a value of a map by key, or an item of
+    /// a dictionary by item key.
     #[serde(default)]
     pub path: Vec<String>,
 }
@@ -852,7 +1522,7 @@ impl RpcWithParams for QueryGlobalState {
 }
 
 /// Identifier of a purse.
-#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub enum PurseIdentifier {
     /// The main purse of the account identified by this public key.
@@ -909,23 +1579,7 @@ impl RpcWithParams for QueryBalance {
         api_version: ProtocolVersion,
         params: Self::RequestParams,
     ) -> Result<Self::ResponseResult, Error> {
-        let state_root_hash = match params.state_identifier {
-            None => match effect_builder.get_highest_block_header_from_storage().await {
-                None => {
-                    return Err(Error::new(
-                        ErrorCode::NoSuchBlock,
-                        "query-balance failed to retrieve highest block header",
-                    ))
-                }
-                Some(block_header) => *block_header.state_root_hash(),
-            },
-            Some(state_identifier) => {
-                let (state_root_hash, _) =
-                    get_state_root_hash_and_optional_header(effect_builder, state_identifier)
-                        .await?;
-                state_root_hash
-            }
-        };
+        let state_root_hash = get_state_root_hash(effect_builder, params.state_identifier).await?;
 
         let purse_uref = match params.purse_identifier {
             PurseIdentifier::MainPurseUnderPublicKey(account_public_key) => {
@@ -989,6 +1643,154 @@ impl RpcWithParams for QueryBalance {
     }
 }
 
+/// Params for "query_balances" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct QueryBalancesParams {
+    /// The state identifier used for the query, if none is passed
+    /// the tip of the chain will be used.
+    pub state_identifier: Option<GlobalStateIdentifier>,
+    /// The identifiers of the purses whose balances are queried, at most 5000.
+    pub purse_identifiers: Vec<PurseIdentifier>,
+}
+
+impl DocExample for QueryBalancesParams {
+    fn doc_example() -> &'static Self {
+        &*QUERY_BALANCES_PARAMS
+    }
+}
+
+/// The balance of a purse queried via "query_balances".
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PurseBalance {
+    /// The identifier of the purse.
+    pub purse_identifier: PurseIdentifier,
+    /// The balance represented in motes, if it was found.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub balance: Option<U512>,
+    /// The reason the balance could not be found, if it wasn't.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub error: Option<String>,
+}
+
+/// Result for "query_balances" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct QueryBalancesResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The state root hash the balances were read from.
+    pub state_root_hash: Digest,
+    /// The balances of the purses, in the order they were requested.
+    pub balances: Vec<PurseBalance>,
+}
+
+impl DocExample for QueryBalancesResult {
+    fn doc_example() -> &'static Self {
+        &*QUERY_BALANCES_RESULT
+    }
+}
+
+/// "query_balances" RPC.
+pub struct QueryBalances {}
+
+#[async_trait]
+impl RpcWithParams for QueryBalances {
+    const METHOD: &'static str = "query_balances";
+    type RequestParams = QueryBalancesParams;
+    type ResponseResult = QueryBalancesResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        if params.purse_identifiers.len() > MAX_QUERY_BALANCES_PURSES {
+            let message = format!(
+                "number of purses {} exceeds the maximum of {}",
+                params.purse_identifiers.len(),
+                MAX_QUERY_BALANCES_PURSES
+            );
+            info!("{}", message);
+            return Err(Error::new(ErrorCode::TooManyPursesInRequest, message));
+        }
+
+        let state_root_hash = get_state_root_hash(effect_builder, params.state_identifier).await?;
+
+        let purses = params
+            .purse_identifiers
+            .iter()
+            .map(|purse_identifier| match purse_identifier {
+                PurseIdentifier::MainPurseUnderPublicKey(account_public_key) => {
+                    BalanceIdentifier::MainPurseUnderAccountHash(
+                        account_public_key.to_account_hash(),
+                    )
+                }
+                PurseIdentifier::MainPurseUnderAccountHash(account_hash) => {
+                    BalanceIdentifier::MainPurseUnderAccountHash(*account_hash)
+                }
+                PurseIdentifier::PurseUref(purse_uref) => BalanceIdentifier::Purse(*purse_uref),
+            })
+            .collect();
+
+        let get_balances_result = effect_builder
+            .make_request(
+                |responder| RpcRequest::GetBalances {
+                    state_root_hash,
+                    purses,
+                    responder,
+                },
+                QueueKind::Api,
+            )
+            .await;
+
+        let balances = match get_balances_result {
+            Ok(GetBalancesResult::Success { balances }) => balances,
+            Ok(GetBalancesResult::RootNotFound) => {
+                info!(%state_root_hash, "query-balances failed: root not found");
+                return Err(Error::new(
+                    ErrorCode::FailedToGetBalance,
+                    format!("root hash {} not found", state_root_hash),
+                ));
+            }
+            Err(error) => {
+                info!("query-balances failed to execute: {}", error);
+                return Err(Error::new(
+                    ErrorCode::GetBalanceFailedToExecute,
+                    error.to_string(),
+                ));
+            }
+        };
+
+        let balances = params
+            .purse_identifiers
+            .into_iter()
+            .zip(balances)
+            .map(|(purse_identifier, balance)| match balance {
+                Ok(balance) => PurseBalance {
+                    purse_identifier,
+                    balance: Some(balance),
+                    error: None,
+                },
+                Err(error) => PurseBalance {
+                    purse_identifier,
+                    balance: None,
+                    error: Some(error.to_string()),
+                },
+            })
+            .collect();
+
+        let result = Self::ResponseResult {
+            api_version,
+            state_root_hash,
+            balances,
+        };
+        Ok(result)
+    }
+}
+
 /// Parameters for "state_get_trie" RPC request.
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct GetTrieParams {
@@ -1114,6 +1916,88 @@ pub(super) async fn run_query<REv: ReactorEventT>(
     }
 }
 
+/// Retrieves the era validators and bids as of the given block.
+async fn get_auction_data<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    api_version: ProtocolVersion,
+    block: &Block,
+) -> Result<(EraValidators, Bids), Error> {
+    let protocol_version = api_version;
+    let state_root_hash = *block.header().state_root_hash();
+
+    let get_bids_result = effect_builder
+        .make_request(
+            |responder| RpcRequest::GetBids {
+                state_root_hash,
+                responder,
+            },
+            QueueKind::Api,
+        )
+        .await;
+
+    let bids = match get_bids_result {
+        Ok(GetBidsResult::Success { bids }) => bids,
+        Ok(GetBidsResult::RootNotFound) => {
+            error!(
+                block_hash=?block.hash(),
+                ?state_root_hash,
+                "root not found while trying to get bids"
+            );
+            return Err(Error::new(
+                ReservedErrorCode::InternalError,
+                format!(
+                    "root not found when getting bids at block {:?}",
+                    block.hash().inner()
+                ),
+            ));
+        }
+        Err(error) => {
+            error!(
+                block_hash=?block.hash(),
+                ?state_root_hash,
+                ?error,
+                "failed to get bids"
+            );
+            return Err(Error::new(
+                ReservedErrorCode::InternalError,
+                format!(
+                    "error getting bids at block {:?}: {}",
+                    block.hash().inner(),
+                    error
+                ),
+            ));
+        }
+    };
+
+    let era_validators_result = effect_builder
+        .make_request(
+            |responder| RpcRequest::QueryEraValidators {
+                state_root_hash,
+                protocol_version,
+                responder,
+            },
+            QueueKind::Api,
+        )
+        .await;
+
+    let era_validators = match era_validators_result {
+        Ok(validators) => validators,
+        Err(error) => {
+            error!(block_hash=?block.hash(), ?state_root_hash, ?error, "failed to get era validators");
+            return Err(Error::new(
+                ReservedErrorCode::InternalError,
+                format!(
+                    "failed to get validators at block {:?}: {}",
+                    block.hash().inner(),
+                    error
+                ),
+            ));
+        }
+    };
+
+    Ok((era_validators, bids))
+}
+
 async fn get_account<REv: ReactorEventT>(
     effect_builder: EffectBuilder<REv>,
     state_root_hash: Digest,
@@ -1136,6 +2020,28 @@ async fn get_account<REv: ReactorEventT>(
     }
 }
 
+/// Returns the state root hash identified by `maybe_state_identifier`, or that of the highest block
+/// if `None`.
+async fn get_state_root_hash<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    maybe_state_identifier: Option<GlobalStateIdentifier>,
+) -> Result<Digest, Error> {
+    match maybe_state_identifier {
+        None => match effect_builder.get_highest_block_header_from_storage().await {
+            None => Err(Error::new(
+                ErrorCode::NoSuchBlock,
+                "failed to retrieve highest block header",
+            )),
+            Some(block_header) => Ok(*block_header.state_root_hash()),
+        },
+        Some(state_identifier) => {
+            let (state_root_hash, _) =
+                get_state_root_hash_and_optional_header(effect_builder, state_identifier).await?;
+            Ok(state_root_hash)
+        }
+    }
+}
+
 pub(super) async fn get_state_root_hash_and_optional_header<REv: ReactorEventT>(
     effect_builder: EffectBuilder<REv>,
     state_identifier: GlobalStateIdentifier,
@@ -1151,7 +2057,12 @@ pub(super) async fn get_state_root_hash_and_optional_header<REv: ReactorEventT>(
                 None => {
                     let error_msg =
                         format!("failed to retrieve specified block header {}", block_hash);
-                    Err(Error::new(ErrorCode::NoSuchBlock, error_msg))
+                    Err(common::missing_block_or_state_root_error(
+                        effect_builder,
+                        ErrorCode::NoSuchBlock,
+                        error_msg,
+                    )
+                    .await)
                 }
                 Some(block_header) => {
                     let json_block_header = JsonBlockHeader::from(block_header.clone());
@@ -1162,7 +2073,7 @@ pub(super) async fn get_state_root_hash_and_optional_header<REv: ReactorEventT>(
         GlobalStateIdentifier::BlockHeight(block_height) => {
             match effect_builder
                 .get_block_header_at_height_from_storage(
-                    block_height,
+                    BlockHeight::from(block_height),
                     only_from_available_block_range,
                 )
                 .await
@@ -1170,7 +2081,12 @@ pub(super) async fn get_state_root_hash_and_optional_header<REv: ReactorEventT>(
                 None => {
                     let error_msg =
                         format!("failed to retrieve block header at height {}", block_height);
-                    Err(Error::new(ErrorCode::NoSuchBlock, error_msg))
+                    Err(common::missing_block_or_state_root_error(
+                        effect_builder,
+                        ErrorCode::NoSuchBlock,
+                        error_msg,
+                    )
+                    .await)
                 }
                 Some(block_header) => {
                     let json_block_header = JsonBlockHeader::from(block_header.clone());
@@ -1,12 +1,11 @@
-use hyper::server::{conn::AddrIncoming, Builder};
-
 use casper_json_rpc::RequestHandlersBuilder;
 use casper_types::ProtocolVersion;
 
-use super::ReactorEventT;
+use super::{access_control::AccessControl, CorsConfig, ReactorEventT};
 use crate::{
     effect::EffectBuilder,
     rpcs::{speculative_exec::SpeculativeExec, RpcWithParams},
+    utils::listener::Listener,
 };
 
 /// The URL path for all JSON-RPC requests.
@@ -16,7 +15,7 @@ pub const SPECULATIVE_EXEC_SERVER_NAME: &str = "speculative execution";
 
 /// Run the speculative execution server.
 pub(super) async fn run<REv: ReactorEventT>(
-    builder: Builder<AddrIncoming>,
+    listener: Listener,
     effect_builder: EffectBuilder<REv>,
     api_version: ProtocolVersion,
     qps_limit: u64,
@@ -27,8 +26,12 @@ pub(super) async fn run<REv: ReactorEventT>(
     let handlers = handlers.build();
 
     super::rpcs::run(
-        builder,
-        handlers,
+        listener,
+        AccessControl::unrestricted(handlers),
+        None,
+        None,
+        None,
+        CorsConfig::allow_any_origin(&["POST"]).to_cors_builder(),
         qps_limit,
         max_body_bytes,
         SPECULATIVE_EXEC_API_PATH,
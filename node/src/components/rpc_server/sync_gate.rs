@@ -0,0 +1,201 @@
+//! Refusal of JSON-RPC requests while the node is syncing.
+//!
+//! Whether the node is still syncing towards the tip of the chain, and how many blocks it has left
+//! to sync, is taken from the progress reported by the chain synchronizer.  While the node is
+//! further behind than the configured limit, requests for methods depending on the state of the
+//! chain fail with a `NodeIsSyncing` error rather than returning misleadingly stale data.  Clients
+//! may retry such requests later.
+//!
+//! The progress is cached briefly, so that busy servers don't query the chain synchronizer for
+//! every request.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use casper_json_rpc::{Error, RequestHandlersBuilder};
+use tracing::debug;
+
+use super::{
+    rpcs::{
+        docs::ListRpcs,
+        info::{GetChainspec, GetPeers, GetStatus, GetSyncProgress},
+        ErrorCode, ErrorData, RpcWithoutParams,
+    },
+    ReactorEventT,
+};
+use crate::{
+    components::chain_synchronizer::SyncProgress, effect::EffectBuilder, types::NodeState,
+};
+
+/// The methods which are served regardless of whether the node is syncing, as they don't depend on
+/// the state of the chain.
+const UNGATED_METHODS: [&str; 5] = [
+    GetStatus::METHOD,
+    GetSyncProgress::METHOD,
+    GetPeers::METHOD,
+    GetChainspec::METHOD,
+    ListRpcs::METHOD,
+];
+
+/// How long the sync status is cached before being queried from the chain synchronizer again.
+const SYNC_STATUS_CACHE_DURATION: Duration = Duration::from_secs(1);
+
+/// Whether the node has synced to the tip of the chain.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum SyncStatus {
+    /// The node has synced to the tip of the chain, though it may still be syncing historical
+    /// blocks in the background.
+    Synced,
+    /// The node is syncing towards the tip of the chain.
+    Syncing {
+        /// The number of blocks still to be synced, if known.
+        blocks_behind: Option<u64>,
+    },
+}
+
+impl SyncStatus {
+    /// Returns the sync status given the node's state and the chain synchronizer's progress.
+    fn new(node_state: &NodeState, sync_progress: &SyncProgress) -> Self {
+        match node_state {
+            NodeState::Joining(_) => SyncStatus::Syncing {
+                blocks_behind: sync_progress.blocks_remaining,
+            },
+            NodeState::ParticipatingAndSyncingToGenesis { .. }
+            | NodeState::Participating
+            | NodeState::Observing => SyncStatus::Synced,
+        }
+    }
+
+    /// Returns `true` if requests depending on the state of the chain should be refused, i.e. if
+    /// the node is more than `max_blocks_behind` blocks behind the tip of the chain, or is syncing
+    /// without knowing how far behind it is.
+    fn should_refuse(&self, max_blocks_behind: u64) -> bool {
+        match self {
+            SyncStatus::Synced => false,
+            SyncStatus::Syncing {
+                blocks_behind: Some(blocks_behind),
+            } => *blocks_behind > max_blocks_behind,
+            SyncStatus::Syncing {
+                blocks_behind: None,
+            } => true,
+        }
+    }
+}
+
+/// The most recently queried sync status.
+struct CachedSyncStatus {
+    queried: Instant,
+    sync_status: SyncStatus,
+}
+
+/// Refuses requests for all methods of `handlers` which depend on the state of the chain while the
+/// node is more than `max_blocks_behind` blocks behind the tip of the chain.
+pub(super) fn gate_methods<REv: ReactorEventT>(
+    handlers: &mut RequestHandlersBuilder,
+    effect_builder: EffectBuilder<REv>,
+    max_blocks_behind: u64,
+) {
+    let cache: Arc<Mutex<Option<CachedSyncStatus>>> = Arc::new(Mutex::new(None));
+    handlers.guard_methods(
+        |method| !UNGATED_METHODS.contains(&method),
+        move |method| {
+            let cache = Arc::clone(&cache);
+            async move {
+                let sync_status = get_sync_status(effect_builder, &cache).await;
+                if !sync_status.should_refuse(max_blocks_behind) {
+                    return Ok(());
+                }
+                let blocks_behind = match sync_status {
+                    SyncStatus::Syncing { blocks_behind } => blocks_behind,
+                    SyncStatus::Synced => None,
+                };
+                debug!(method, ?blocks_behind, "refusing request while syncing");
+                let message = match blocks_behind {
+                    Some(blocks_behind) => {
+                        format!("node is syncing, behind by {} blocks", blocks_behind)
+                    }
+                    None => "node is syncing".to_string(),
+                };
+                let error_data = ErrorData::NodeIsSyncing {
+                    message,
+                    blocks_behind,
+                };
+                Err(Error::new(ErrorCode::NodeIsSyncing, error_data))
+            }
+        },
+    );
+}
+
+/// Returns the cached sync status, querying the chain synchronizer for it if the cached one has
+/// expired.
+async fn get_sync_status<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    cache: &Mutex<Option<CachedSyncStatus>>,
+) -> SyncStatus {
+    if let Some(cached) = cache
+        .lock()
+        .expect("sync status cache mutex should not be poisoned")
+        .as_ref()
+        .filter(|cached| cached.queried.elapsed() < SYNC_STATUS_CACHE_DURATION)
+    {
+        return cached.sync_status;
+    }
+
+    let node_state = effect_builder.get_node_state().await;
+    let sync_progress = effect_builder.get_sync_progress().await;
+    let sync_status = SyncStatus::new(&node_state, &sync_progress);
+    *cache
+        .lock()
+        .expect("sync status cache mutex should not be poisoned") = Some(CachedSyncStatus {
+        queried: Instant::now(),
+        sync_status,
+    });
+    sync_status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::chain_synchronizer::{FastSync, Progress, SyncPhase, SyncToGenesis};
+
+    fn sync_progress(blocks_remaining: Option<u64>) -> SyncProgress {
+        SyncProgress {
+            phase: SyncPhase::SyncingBlocksFromGenesis,
+            blocks_remaining,
+            tries_fetched: 0,
+            estimated_time_remaining: None,
+        }
+    }
+
+    #[test]
+    fn should_refuse_while_joining_and_too_far_behind() {
+        let joining = NodeState::Joining(Progress::FastSync(FastSync::ExecutingBlock(10)));
+
+        let sync_status = SyncStatus::new(&joining, &sync_progress(Some(5)));
+        assert!(!sync_status.should_refuse(5));
+        assert!(sync_status.should_refuse(4));
+
+        // If the node doesn't know how far behind it is, it refuses requests.
+        let sync_status = SyncStatus::new(&joining, &sync_progress(None));
+        assert!(sync_status.should_refuse(u64::MAX));
+    }
+
+    #[test]
+    fn should_not_refuse_when_synced_to_tip() {
+        // Syncing historical blocks doesn't count as being behind the tip of the chain.
+        let syncing_to_genesis = NodeState::ParticipatingAndSyncingToGenesis {
+            sync_progress: Progress::SyncToGenesis(SyncToGenesis::NotYetStarted),
+        };
+        for node_state in [
+            syncing_to_genesis,
+            NodeState::Participating,
+            NodeState::Observing,
+        ] {
+            let sync_status = SyncStatus::new(&node_state, &sync_progress(Some(1_000)));
+            assert_eq!(sync_status, SyncStatus::Synced);
+            assert!(!sync_status.should_refuse(1));
+        }
+    }
+}
@@ -0,0 +1,650 @@
+//! WebSocket transport for the JSON-RPC server.
+//!
+//! WebSocket clients can call all JSON-RPC methods available over HTTP.  In addition, they can
+//! subscribe to notifications via the "chain_subscribe" method, which are then pushed to them as
+//! JSON-RPC notifications with the method "chain_subscription" until they call
+//! "chain_unsubscribe" or disconnect.
+//!
+//! Every message received from a client counts as a request towards the client's rate limit, if
+//! any, and messages larger than the server's maximum request body size close the connection.
+
+use std::{
+    collections::BTreeMap,
+    future,
+    net::IpAddr,
+    sync::{Arc, Mutex},
+};
+
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::{debug, error, warn};
+use warp::{
+    filters::BoxedFilter,
+    ws::{Message, WebSocket, Ws},
+    Filter, Reply,
+};
+
+use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+use casper_hashing::Digest;
+use casper_json_rpc::{
+    Error, Params, RequestHandlers, RequestHandlersBuilder, ReservedErrorCode, Response,
+};
+use casper_types::{account::AccountHash, ExecutionResult, Key};
+
+use super::{
+    rate_limiter::ClientRateLimiter,
+    rpcs::{info::AccountIdentifier, ErrorCode, ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST},
+};
+use crate::{
+    components::event_stream_server::SseData,
+    types::{
+        Block, BlockHash, Deploy, DeployHash, DeployHeader, FinalitySignature, JsonBlock,
+        SignedWeight,
+    },
+};
+
+/// The URL path for WebSocket connections.
+pub const RPC_WS_API_PATH: &str = "ws";
+
+/// The JSON-RPC method used to subscribe to notifications.
+const SUBSCRIBE_METHOD: &str = "chain_subscribe";
+/// The JSON-RPC method used to cancel a subscription.
+const UNSUBSCRIBE_METHOD: &str = "chain_unsubscribe";
+/// The JSON-RPC method of the notifications sent to subscribers.
+const NOTIFICATION_METHOD: &str = "chain_subscription";
+/// The number of notifications buffered for each WebSocket client before it is considered lagging.
+pub(super) const NOTIFICATION_CHANNEL_CAPACITY: usize = 1024;
+
+/// The kind of notifications a subscription is for.
+#[derive(Copy, Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum SubscriptionTopic {
+    /// A block has been added to the linear chain.
+    BlockAdded,
+    /// A finality signature has been received.
+    FinalitySignature,
+    /// A deploy has been newly accepted by this node.
+    DeployAccepted,
+    /// A deploy has been executed and forms part of a block.
+    DeployProcessed,
+}
+
+impl SubscriptionTopic {
+    /// Returns whether notifications of this topic can be filtered by account or contract.
+    fn is_deploy_topic(self) -> bool {
+        matches!(
+            self,
+            SubscriptionTopic::DeployAccepted | SubscriptionTopic::DeployProcessed
+        )
+    }
+}
+
+/// A notification to be sent to the WebSocket clients subscribed to its topic.
+#[derive(Debug)]
+pub(super) struct Notification {
+    topic: SubscriptionTopic,
+    /// The account which sent the deploy, for deploy notifications.
+    account: Option<AccountHash>,
+    /// The hashes of the stored contracts or contract packages involved in the deploy, for deploy
+    /// notifications.
+    contracts: Vec<Digest>,
+    /// The payload of the notification, in the same format as the event stream's data.
+    payload: Value,
+}
+
+impl Notification {
+    fn new(
+        topic: SubscriptionTopic,
+        account: Option<AccountHash>,
+        contracts: Vec<Digest>,
+        sse_data: SseData,
+    ) -> Result<Self, serde_json::Error> {
+        Ok(Notification {
+            topic,
+            account,
+            contracts,
+            payload: serde_json::to_value(sse_data)?,
+        })
+    }
+
+    /// Returns a notification about a block added to the linear chain.
+    pub(super) fn block_added(block: Block) -> Result<Self, serde_json::Error> {
+        let sse_data = SseData::BlockAdded {
+            block_hash: *block.hash(),
+            block: Box::new(JsonBlock::new(block, None)),
+        };
+        Notification::new(SubscriptionTopic::BlockAdded, None, vec![], sse_data)
+    }
+
+    /// Returns a notification about a received finality signature.
+    pub(super) fn finality_signature(
+        finality_signature: Box<FinalitySignature>,
+        cumulative_weight: Option<Box<SignedWeight>>,
+    ) -> Result<Self, serde_json::Error> {
+        let sse_data = SseData::FinalitySignature {
+            finality_signature,
+            cumulative_weight,
+        };
+        Notification::new(SubscriptionTopic::FinalitySignature, None, vec![], sse_data)
+    }
+
+    /// Returns a notification about a newly accepted deploy.
+    ///
+    /// The deploy involves the contracts called by hash in its payment or session code.
+    pub(super) fn deploy_accepted(deploy: Deploy) -> Result<Self, serde_json::Error> {
+        let account = deploy.header().account().to_account_hash();
+        let contracts = [deploy.payment(), deploy.session()]
+            .iter()
+            .filter_map(|item| match item {
+                ExecutableDeployItem::StoredContractByHash { hash, .. } => {
+                    Some(Digest::from(hash.value()))
+                }
+                ExecutableDeployItem::StoredVersionedContractByHash { hash, .. } => {
+                    Some(Digest::from(hash.value()))
+                }
+                _ => None,
+            })
+            .collect();
+        let sse_data = SseData::DeployAccepted {
+            deploy: Arc::new(deploy),
+        };
+        Notification::new(
+            SubscriptionTopic::DeployAccepted,
+            Some(account),
+            contracts,
+            sse_data,
+        )
+    }
+
+    /// Returns a notification about a processed deploy.
+    ///
+    /// The deploy involves the contracts and contract packages whose keys appear in the
+    /// transforms of its execution.
+    pub(super) fn deploy_processed(
+        deploy_hash: DeployHash,
+        deploy_header: DeployHeader,
+        block_hash: BlockHash,
+        execution_result: Box<ExecutionResult>,
+    ) -> Result<Self, serde_json::Error> {
+        let account = deploy_header.account().to_account_hash();
+        let effect = match &*execution_result {
+            ExecutionResult::Success { effect, .. } | ExecutionResult::Failure { effect, .. } => {
+                effect
+            }
+        };
+        let mut contracts: Vec<Digest> = effect
+            .transforms
+            .iter()
+            .filter_map(|entry| match Key::from_formatted_str(&entry.key) {
+                Ok(Key::Hash(hash_addr)) => Some(Digest::from(hash_addr)),
+                _ => None,
+            })
+            .collect();
+        contracts.sort();
+        contracts.dedup();
+        let sse_data = SseData::DeployProcessed {
+            deploy_hash: Box::new(deploy_hash),
+            account: Box::new(deploy_header.account().clone()),
+            timestamp: deploy_header.timestamp(),
+            ttl: deploy_header.ttl(),
+            dependencies: deploy_header.dependencies().clone(),
+            block_hash: Box::new(block_hash),
+            execution_result,
+        };
+        Notification::new(
+            SubscriptionTopic::DeployProcessed,
+            Some(account),
+            contracts,
+            sse_data,
+        )
+    }
+}
+
+/// Params for "chain_subscribe" request.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct SubscribeParams {
+    /// The kind of notifications to subscribe to.
+    topic: SubscriptionTopic,
+    /// Only notify about deploys sent by this account.
+    #[serde(default)]
+    account_identifier: Option<AccountIdentifier>,
+    /// Only notify about deploys involving the stored contract or contract package with this
+    /// hash.
+    #[serde(default)]
+    contract_hash: Option<Digest>,
+}
+
+/// Params for "chain_unsubscribe" request.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct UnsubscribeParams {
+    /// The ID of the subscription to cancel.
+    subscription_id: u64,
+}
+
+/// Result for "chain_subscribe" and "chain_unsubscribe" responses.
+#[derive(Serialize, Debug)]
+struct SubscriptionResult {
+    /// The ID of the created or cancelled subscription.
+    subscription_id: u64,
+}
+
+/// A "chain_subscription" notification sent to a subscriber.
+#[derive(Serialize, Debug)]
+struct NotificationMessage<'a> {
+    jsonrpc: &'static str,
+    method: &'static str,
+    params: NotificationParams<'a>,
+}
+
+/// The params of a "chain_subscription" notification.
+#[derive(Serialize, Debug)]
+struct NotificationParams<'a> {
+    /// The ID of the subscription the notification is sent for.
+    subscription_id: u64,
+    /// The payload of the notification.
+    result: &'a Value,
+}
+
+/// A single subscription held by a WebSocket client.
+#[derive(Debug)]
+struct Subscription {
+    topic: SubscriptionTopic,
+    account: Option<AccountHash>,
+    contract: Option<Digest>,
+}
+
+impl Subscription {
+    /// Returns whether the given notification should be sent for this subscription.
+    fn matches(&self, notification: &Notification) -> bool {
+        self.topic == notification.topic
+            && self
+                .account
+                .map_or(true, |account| notification.account == Some(account))
+            && self
+                .contract
+                .map_or(true, |contract| notification.contracts.contains(&contract))
+    }
+}
+
+/// The subscriptions held by a single WebSocket client.
+#[derive(Debug)]
+struct Subscriptions {
+    subscriptions: BTreeMap<u64, Subscription>,
+    next_subscription_id: u64,
+    max_subscriptions: usize,
+}
+
+impl Subscriptions {
+    fn new(max_subscriptions: usize) -> Self {
+        Subscriptions {
+            subscriptions: BTreeMap::new(),
+            next_subscription_id: 0,
+            max_subscriptions,
+        }
+    }
+
+    fn subscribe(&mut self, maybe_params: Option<Params>) -> Result<SubscriptionResult, Error> {
+        let params: SubscribeParams = parse_params(maybe_params)?;
+        if !params.topic.is_deploy_topic()
+            && (params.account_identifier.is_some() || params.contract_hash.is_some())
+        {
+            return Err(Error::new(
+                ReservedErrorCode::InvalidParams,
+                "only deploy subscriptions can be filtered by account or contract",
+            ));
+        }
+        if self.subscriptions.len() >= self.max_subscriptions {
+            return Err(Error::new(
+                ErrorCode::TooManySubscriptions,
+                format!(
+                    "at most {} subscriptions are allowed per connection",
+                    self.max_subscriptions
+                ),
+            ));
+        }
+
+        let subscription_id = self.next_subscription_id;
+        self.next_subscription_id += 1;
+        let subscription = Subscription {
+            topic: params.topic,
+            account: params
+                .account_identifier
+                .as_ref()
+                .map(AccountIdentifier::account_hash),
+            contract: params.contract_hash,
+        };
+        let _ = self.subscriptions.insert(subscription_id, subscription);
+        Ok(SubscriptionResult { subscription_id })
+    }
+
+    fn unsubscribe(&mut self, maybe_params: Option<Params>) -> Result<SubscriptionResult, Error> {
+        let params: UnsubscribeParams = parse_params(maybe_params)?;
+        match self.subscriptions.remove(&params.subscription_id) {
+            Some(_) => Ok(SubscriptionResult {
+                subscription_id: params.subscription_id,
+            }),
+            None => Err(Error::new(
+                ErrorCode::NoSuchSubscription,
+                format!("no subscription with id {}", params.subscription_id),
+            )),
+        }
+    }
+
+    /// Returns the IDs of the subscriptions the given notification should be sent for.
+    fn matching(&self, notification: &Notification) -> Vec<u64> {
+        self.subscriptions
+            .iter()
+            .filter(|(_, subscription)| subscription.matches(notification))
+            .map(|(subscription_id, _)| *subscription_id)
+            .collect()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.subscriptions.is_empty()
+    }
+}
+
+/// Parses the "params" field of a subscription request.
+fn parse_params<T: for<'de> Deserialize<'de>>(maybe_params: Option<Params>) -> Result<T, Error> {
+    let params = match maybe_params {
+        Some(params) => Value::from(params),
+        None => {
+            return Err(Error::new(
+                ReservedErrorCode::InvalidParams,
+                "Missing 'params' field",
+            ))
+        }
+    };
+    serde_json::from_value(params).map_err(|error| {
+        Error::new(
+            ReservedErrorCode::InvalidParams,
+            format!("Failed to parse 'params' field: {}", error),
+        )
+    })
+}
+
+/// Returns the shared handlers extended by the subscription methods operating on the given
+/// client's subscriptions.
+fn connection_handlers(
+    handlers: &RequestHandlers,
+    subscriptions: &Arc<Mutex<Subscriptions>>,
+) -> RequestHandlers {
+    let mut handlers = RequestHandlersBuilder::from_handlers(handlers);
+
+    let subscribe_to = Arc::clone(subscriptions);
+    let subscribe = move |maybe_params| {
+        let result = subscribe_to
+            .lock()
+            .expect("subscriptions lock poisoned")
+            .subscribe(maybe_params);
+        future::ready(result)
+    };
+    handlers.register_handler(SUBSCRIBE_METHOD, Arc::new(subscribe));
+
+    let unsubscribe_from = Arc::clone(subscriptions);
+    let unsubscribe = move |maybe_params| {
+        let result = unsubscribe_from
+            .lock()
+            .expect("subscriptions lock poisoned")
+            .unsubscribe(maybe_params);
+        future::ready(result)
+    };
+    handlers.register_handler(UNSUBSCRIBE_METHOD, Arc::new(unsubscribe));
+
+    handlers.build()
+}
+
+/// The rate limiter applying to a WebSocket client, and the client as identified by it.
+type ClientRateLimit = (Arc<ClientRateLimiter>, IpAddr);
+
+/// Returns a boxed warp filter upgrading requests on the WebSocket path to WebSocket connections.
+///
+/// Messages larger than `max_message_bytes` close the connection, and if `client_rate_limiter` is
+/// provided, messages exceeding their client's rate limit are refused.
+pub(super) fn route(
+    handlers: RequestHandlers,
+    notification_sender: broadcast::Sender<Arc<Notification>>,
+    max_subscriptions: usize,
+    max_message_bytes: usize,
+    client_rate_limiter: Option<Arc<ClientRateLimiter>>,
+) -> BoxedFilter<(Box<dyn Reply>,)> {
+    let client_filter = match &client_rate_limiter {
+        Some(rate_limiter) => rate_limiter.client_filter().map(Some).boxed(),
+        None => warp::any().map(|| None).boxed(),
+    };
+    warp::path(RPC_WS_API_PATH)
+        .and(warp::path::end())
+        .and(warp::ws())
+        .and(client_filter)
+        .map(move |ws: Ws, maybe_client: Option<IpAddr>| {
+            let handlers = handlers.clone();
+            let notification_receiver = notification_sender.subscribe();
+            let rate_limit = client_rate_limiter.clone().zip(maybe_client);
+            let reply = ws
+                .max_message_size(max_message_bytes)
+                .on_upgrade(move |websocket| {
+                    handle_connection(
+                        websocket,
+                        handlers,
+                        notification_receiver,
+                        max_subscriptions,
+                        rate_limit,
+                    )
+                });
+            let reply: Box<dyn Reply> = Box::new(reply);
+            reply
+        })
+        .boxed()
+}
+
+/// Returns the response to a request refused as its client exceeded its rate limit.
+fn rate_limited_response(request: &[u8]) -> Response {
+    /// The ID of a request, if it can be parsed.
+    #[derive(Deserialize)]
+    struct RequestId {
+        #[serde(default)]
+        id: Value,
+    }
+
+    let id = serde_json::from_slice::<RequestId>(request).map_or(Value::Null, |request| request.id);
+    Response::new_failure(
+        id,
+        Error::new(ErrorCode::TooManyRequests, "too many requests, retry later"),
+    )
+}
+
+/// Serves a single WebSocket client until it disconnects.
+///
+/// Requests are handled one at a time, in the order in which they are received.  Notifications are
+/// sent in between requests.
+async fn handle_connection(
+    websocket: WebSocket,
+    handlers: RequestHandlers,
+    mut notification_receiver: broadcast::Receiver<Arc<Notification>>,
+    max_subscriptions: usize,
+    rate_limit: Option<ClientRateLimit>,
+) {
+    let subscriptions = Arc::new(Mutex::new(Subscriptions::new(max_subscriptions)));
+    let handlers = connection_handlers(&handlers, &subscriptions);
+    let (mut sink, mut stream) = websocket.split();
+
+    loop {
+        let outgoing_messages = tokio::select! {
+            maybe_message = stream.next() => match maybe_message {
+                Some(Ok(message)) if message.is_text() || message.is_binary() => {
+                    let is_permitted = rate_limit
+                        .as_ref()
+                        .map_or(true, |(rate_limiter, client)| rate_limiter.permits(*client));
+                    if !is_permitted {
+                        vec![serde_json::to_string(&rate_limited_response(message.as_bytes()))]
+                    } else {
+                        match handlers
+                            .handle_raw_request(
+                                message.as_bytes(),
+                                ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST,
+                            )
+                            .await
+                        {
+                            Some(response) => vec![serde_json::to_string(&response)],
+                            None => continue,
+                        }
+                    }
+                }
+                Some(Ok(message)) if message.is_close() => break,
+                // Pings are answered by the underlying library; pongs need no action.
+                Some(Ok(_)) => continue,
+                Some(Err(error)) => {
+                    debug!(%error, "error receiving websocket message");
+                    break;
+                }
+                None => break,
+            },
+            result = notification_receiver.recv() => match result {
+                Ok(notification) => {
+                    let subscriptions = subscriptions.lock().expect("subscriptions lock poisoned");
+                    subscriptions
+                        .matching(&notification)
+                        .into_iter()
+                        .map(|subscription_id| {
+                            serde_json::to_string(&NotificationMessage {
+                                jsonrpc: "2.0",
+                                method: NOTIFICATION_METHOD,
+                                params: NotificationParams {
+                                    subscription_id,
+                                    result: &notification.payload,
+                                },
+                            })
+                        })
+                        .collect()
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    // A subscriber missing notifications would silently get out of sync, so we
+                    // disconnect it instead.
+                    if subscriptions.lock().expect("subscriptions lock poisoned").is_empty() {
+                        continue;
+                    }
+                    warn!(%skipped, "websocket client too slow to keep up with notifications");
+                    break;
+                }
+                Err(RecvError::Closed) => break,
+            },
+        };
+
+        for outgoing_message in outgoing_messages {
+            let text = match outgoing_message {
+                Ok(text) => text,
+                Err(error) => {
+                    error!(%error, "failed to encode websocket message");
+                    continue;
+                }
+            };
+            if let Err(error) = sink.send(Message::text(text)).await {
+                debug!(%error, "error sending websocket message");
+                return;
+            }
+        }
+    }
+
+    let _ = sink.close().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use casper_types::testing::TestRng;
+
+    use super::*;
+
+    fn params(value: Value) -> Option<Params> {
+        match value {
+            Value::Object(map) => Some(Params::Object(map)),
+            _ => panic!("params should be an object"),
+        }
+    }
+
+    #[test]
+    fn should_filter_deploy_notifications_by_account() {
+        let mut rng = TestRng::new();
+        let deploy = Deploy::random(&mut rng);
+        let account_hash = deploy.header().account().to_account_hash();
+        let other_deploy = Deploy::random(&mut rng);
+        let notification = Notification::deploy_accepted(deploy).unwrap();
+        let other_notification = Notification::deploy_accepted(other_deploy).unwrap();
+
+        let mut subscriptions = Subscriptions::new(10);
+        let all_deploys = subscriptions
+            .subscribe(params(json!({ "topic": "deploy_accepted" })))
+            .unwrap()
+            .subscription_id;
+        let account_deploys = subscriptions
+            .subscribe(params(json!({
+                "topic": "deploy_accepted",
+                "account_identifier": { "account_hash": account_hash },
+            })))
+            .unwrap()
+            .subscription_id;
+        let _processed_deploys = subscriptions
+            .subscribe(params(json!({ "topic": "deploy_processed" })))
+            .unwrap();
+
+        assert_eq!(
+            subscriptions.matching(&notification),
+            vec![all_deploys, account_deploys]
+        );
+        assert_eq!(
+            subscriptions.matching(&other_notification),
+            vec![all_deploys]
+        );
+
+        subscriptions
+            .unsubscribe(params(json!({ "subscription_id": all_deploys })))
+            .unwrap();
+        assert_eq!(subscriptions.matching(&notification), vec![account_deploys]);
+        assert!(subscriptions.matching(&other_notification).is_empty());
+        assert!(subscriptions
+            .unsubscribe(params(json!({ "subscription_id": all_deploys }),))
+            .is_err());
+    }
+
+    #[test]
+    fn should_refuse_rate_limited_request_with_its_id() {
+        let response =
+            rate_limited_response(br#"{"jsonrpc":"2.0","id":7,"method":"info_get_peers"}"#);
+        assert_eq!(response.id(), &json!(7));
+        assert_eq!(
+            response.error().unwrap(),
+            &Error::new(ErrorCode::TooManyRequests, "too many requests, retry later")
+        );
+
+        let response = rate_limited_response(b"not json");
+        assert_eq!(response.id(), &Value::Null);
+        assert!(response.is_failure());
+    }
+
+    #[test]
+    fn should_reject_invalid_subscriptions() {
+        let mut subscriptions = Subscriptions::new(1);
+        assert!(subscriptions
+            .subscribe(params(json!({
+                "topic": "block_added",
+                "contract_hash": Digest::hash([1]),
+            })))
+            .is_err());
+        assert!(subscriptions
+            .subscribe(params(json!({ "topic": "unknown" })))
+            .is_err());
+        assert!(subscriptions.subscribe(None).is_err());
+
+        assert!(subscriptions
+            .subscribe(params(json!({ "topic": "block_added" })))
+            .is_ok());
+        assert!(subscriptions
+            .subscribe(params(json!({ "topic": "block_added" })))
+            .is_err());
+    }
+}
@@ -22,29 +22,39 @@
 //!
 //! Nodes gossip their public listening addresses periodically, and will try to establish and
 //! maintain an outgoing connection to any new address learned.
+//!
+//! # Handover
+//!
+//! When the joiner reactor transitions to the participating one, the network is handed over rather
+//! than rebuilt: the outgoing connections are kept, while the server and incoming connections,
+//! whose tasks deliver events to the joiner's queue, are restarted.  Peers reconnect to us just as
+//! they would after the sync finished, but we don't need to redial all of them.
 
 mod bincode_format;
 mod chain_info;
 mod config;
 mod counting_format;
+mod envelope_format;
 mod error;
 mod event;
 mod gossiped_address;
+mod identity_linkage;
 mod limiter;
 mod message;
 mod message_pack_format;
 mod metrics;
 mod outgoing;
+mod peer_details;
 mod symmetry;
 pub(crate) mod tasks;
 #[cfg(test)]
 mod tests;
 
 use std::{
-    collections::{BTreeMap, HashMap, HashSet},
+    collections::{hash_map::Entry, BTreeMap, HashMap, HashSet},
     convert::Infallible,
     fmt::{self, Debug, Display, Formatter},
-    io,
+    io, iter, mem,
     net::{SocketAddr, TcpListener},
     result,
     sync::{
@@ -74,7 +84,7 @@ use tokio_openssl::SslStream;
 use tokio_util::codec::LengthDelimitedCodec;
 use tracing::{debug, error, info, trace, warn, Instrument, Span};
 
-use casper_types::{EraId, PublicKey};
+use casper_types::{EraId, ProtocolVersion, PublicKey};
 
 pub(crate) use self::{
     bincode_format::BincodeFormat,
@@ -83,37 +93,44 @@ pub(crate) use self::{
     event::Event,
     gossiped_address::GossipedAddress,
     message::{EstimatorWeights, FromIncoming, Message, MessageKind, Payload},
+    peer_details::{ConnectionDirection, PeerDetails},
 };
 use self::{
     chain_info::ChainInfo,
     config::IdentityConfig,
     counting_format::{ConnectionId, CountingFormat, Role},
+    envelope_format::EnvelopeFormat,
     error::{ConnectionError, Result},
     event::{IncomingConnection, OutgoingConnection},
+    identity_linkage::IdentityLinkage,
     limiter::Limiter,
     message::ConsensusKeyPair,
     metrics::Metrics,
     outgoing::{DialOutcome, DialRequest, OutgoingConfig, OutgoingManager},
+    peer_details::{PeerRole, PeerStats},
     symmetry::ConnectionSymmetry,
     tasks::{MessageQueueItem, NetworkContext},
 };
 
 use crate::{
     components::{consensus, Component},
+    config_reload::ReloadableConfig,
     effect::{
         announcements::{
-            BlocklistAnnouncement, ChainSynchronizerAnnouncement, ContractRuntimeAnnouncement,
+            BlocklistAnnouncement, ChainSynchronizerAnnouncement, ConsensusAnnouncement,
+            ContractRuntimeAnnouncement,
         },
         requests::{BeginGossipRequest, NetworkInfoRequest, NetworkRequest, StorageRequest},
         AutoClosingResponder, EffectBuilder, EffectExt, Effects,
     },
     reactor::{EventQueueHandle, Finalize, ReactorEvent},
-    tls::{
-        self, validate_cert_with_authority, LoadCertError, LoadSecretKeyError, TlsCert,
-        ValidationError,
+    tls::{self, validate_cert_with_authority, LoadCertError, TlsCert, ValidationError},
+    types::{NodeFeatures, NodeId},
+    utils::{
+        self, display_error,
+        key_file::{self, KeyFileError},
+        Source, WithDir,
     },
-    types::NodeId,
-    utils::{self, display_error, Source, WithDir},
     NodeRng,
 };
 
@@ -137,6 +154,8 @@ pub(crate) struct OutgoingHandle<P> {
     #[data_size(skip)] // Unfortunately, there is no way to inspect an `UnboundedSender`.
     sender: UnboundedSender<MessageQueueItem<P>>,
     peer_addr: SocketAddr,
+    /// Whether the peer accepts payloads wrapped in envelopes.
+    accepts_envelopes: bool,
 }
 
 impl<P> Display for OutgoingHandle<P> {
@@ -160,10 +179,15 @@ where
     outgoing_manager: OutgoingManager<OutgoingHandle<P>, ConnectionError>,
     /// Tracks whether a connection is symmetric or not.
     connection_symmetries: HashMap<NodeId, ConnectionSymmetry>,
+    /// What we learned about each connected peer.
+    peer_stats: HashMap<NodeId, PeerStats>,
 
     /// Tracks nodes that have announced themselves as nodes that are syncing.
     syncing_nodes: HashSet<NodeId>,
 
+    /// Tracks nodes that have said goodbye, as they are shutting down.
+    departing_nodes: HashSet<NodeId>,
+
     /// Channel signaling a shutdown of the small network.
     // Note: This channel is closed when `SmallNetwork` is dropped, signalling the receivers that
     // they should cease operation.
@@ -200,6 +224,50 @@ where
 
     /// The era that is considered the active era by the small network component.
     active_era: EraId,
+    /// The validators of the active and upcoming eras, as far as known.
+    era_validators: BTreeMap<EraId, HashSet<PublicKey>>,
+
+    /// Peers with an outgoing connection handed over from the previous reactor.
+    ///
+    /// The events signaling these connections being dropped are delivered to the previous reactor,
+    /// so we check for that ourselves.
+    handed_over_peers: HashSet<NodeId>,
+}
+
+/// The state of a network shut down except for its outgoing connections, to be taken over by the
+/// next reactor.
+pub(crate) struct NetworkHandover<P>
+where
+    P: Payload,
+{
+    identity: SmallNetworkIdentity,
+    outgoing_manager: OutgoingManager<OutgoingHandle<P>, ConnectionError>,
+    connection_symmetries: HashMap<NodeId, ConnectionSymmetry>,
+    peer_stats: HashMap<NodeId, PeerStats>,
+    syncing_nodes: HashSet<NodeId>,
+    departing_nodes: HashSet<NodeId>,
+    net_metrics: Arc<Metrics>,
+    outgoing_limiter: Box<dyn Limiter>,
+    incoming_limiter: Box<dyn Limiter>,
+    active_era: EraId,
+    era_validators: BTreeMap<EraId, HashSet<PublicKey>>,
+    is_syncing: bool,
+}
+
+impl<P> Debug for NetworkHandover<P>
+where
+    P: Payload,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NetworkHandover")
+            .field(
+                "connected_peers",
+                &self.outgoing_manager.connected_peers().count(),
+            )
+            .field("active_era", &self.active_era)
+            .field("is_syncing", &self.is_syncing)
+            .finish()
+    }
 }
 
 impl<REv, P> SmallNetwork<REv, P>
@@ -221,26 +289,6 @@ where
         small_network_identity: SmallNetworkIdentity,
         chain_info_source: C,
     ) -> Result<(SmallNetwork<REv, P>, Effects<Event<P>>)> {
-        let mut known_addresses = HashSet::new();
-        for address in &cfg.known_addresses {
-            match utils::resolve_address(address) {
-                Ok(known_address) => {
-                    if !known_addresses.insert(known_address) {
-                        warn!(%address, resolved=%known_address, "ignoring duplicated known address");
-                    };
-                }
-                Err(ref err) => {
-                    warn!(%address, err=display_error(err), "failed to resolve known address");
-                }
-            }
-        }
-
-        // Assert we have at least one known address in the config.
-        if known_addresses.is_empty() {
-            warn!("no known addresses provided via config or all failed DNS resolution");
-            return Err(Error::EmptyKnownHosts);
-        }
-
         let net_metrics = Arc::new(Metrics::new(registry)?);
 
         let outgoing_limiter: Box<dyn Limiter> = if cfg.max_outgoing_byte_rate_non_validators == 0 {
@@ -272,6 +320,70 @@ where
             net_metrics.create_outgoing_metrics(),
         );
 
+        let handover = NetworkHandover {
+            identity: small_network_identity,
+            outgoing_manager,
+            connection_symmetries: HashMap::new(),
+            peer_stats: HashMap::new(),
+            syncing_nodes: HashSet::new(),
+            departing_nodes: HashSet::new(),
+            net_metrics,
+            outgoing_limiter,
+            incoming_limiter,
+            // We start with an empty set of validators for era 0 and expect to be updated.
+            active_era: EraId::new(0),
+            era_validators: BTreeMap::new(),
+            is_syncing: true,
+        };
+
+        Self::from_handover(event_queue, cfg, consensus_cfg, handover, chain_info_source)
+    }
+
+    /// Creates a small network component instance taking over the outgoing connections of a
+    /// network handed over by the previous reactor.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn from_handover<C: Into<ChainInfo>>(
+        event_queue: EventQueueHandle<REv>,
+        cfg: Config,
+        consensus_cfg: Option<WithDir<&consensus::Config>>,
+        handover: NetworkHandover<P>,
+        chain_info_source: C,
+    ) -> Result<(SmallNetwork<REv, P>, Effects<Event<P>>)> {
+        let NetworkHandover {
+            identity: small_network_identity,
+            outgoing_manager,
+            mut connection_symmetries,
+            mut peer_stats,
+            syncing_nodes,
+            departing_nodes,
+            net_metrics,
+            outgoing_limiter,
+            incoming_limiter,
+            active_era,
+            era_validators,
+            is_syncing,
+        } = handover;
+
+        let mut known_addresses = HashSet::new();
+        for address in &cfg.known_addresses {
+            match utils::resolve_address(address) {
+                Ok(known_address) => {
+                    if !known_addresses.insert(known_address) {
+                        warn!(%address, resolved=%known_address, "ignoring duplicated known address");
+                    };
+                }
+                Err(ref err) => {
+                    warn!(%address, err=display_error(err), "failed to resolve known address");
+                }
+            }
+        }
+
+        // Assert we have at least one known address in the config.
+        if known_addresses.is_empty() {
+            warn!("no known addresses provided via config or all failed DNS resolution");
+            return Err(Error::EmptyKnownHosts);
+        }
+
         let mut public_addr =
             utils::resolve_address(&cfg.public_address).map_err(Error::ResolveAddr)?;
 
@@ -336,6 +448,7 @@ where
             our_cert: small_network_identity.tls_certificate,
             network_ca: ca_certificate.map(Arc::new),
             secret_key: small_network_identity.secret_key,
+            identity_linkage: small_network_identity.identity_linkage,
             net_metrics: Arc::downgrade(&net_metrics),
             chain_info,
             public_addr,
@@ -346,7 +459,7 @@ where
             tarpit_duration: cfg.tarpit_duration,
             tarpit_chance: cfg.tarpit_chance,
             max_in_flight_demands: demand_max,
-            is_syncing: AtomicBool::new(true),
+            is_syncing: AtomicBool::new(is_syncing),
         });
 
         // Run the server task.
@@ -366,12 +479,26 @@ where
             .in_current_span(),
         );
 
+        // Any incoming connections were closed on handover, so only outgoing ones remain.
+        let now = Instant::now();
+        connection_symmetries.retain(|_, symmetry| symmetry.remove_all_incoming(now));
+        peer_stats.retain(|peer_id, _| connection_symmetries.contains_key(peer_id));
+        let handed_over_peers: HashSet<NodeId> = outgoing_manager.connected_peers().collect();
+        if !handed_over_peers.is_empty() {
+            info!(
+                count = handed_over_peers.len(),
+                "took over outgoing connections from previous reactor"
+            );
+        }
+
         let mut component = SmallNetwork {
             cfg,
             context,
             outgoing_manager,
-            connection_symmetries: HashMap::new(),
-            syncing_nodes: HashSet::new(),
+            connection_symmetries,
+            peer_stats,
+            syncing_nodes,
+            departing_nodes,
             shutdown_sender: Some(server_shutdown_sender),
             close_incoming_sender: Some(close_incoming_sender),
             close_incoming_receiver,
@@ -379,14 +506,14 @@ where
             net_metrics,
             outgoing_limiter,
             incoming_limiter,
-            // We start with an empty set of validators for era 0 and expect to be updated.
-            active_era: EraId::new(0),
+            active_era,
+            era_validators,
+            handed_over_peers,
         };
 
         let effect_builder = EffectBuilder::new(event_queue);
 
         // Learn all known addresses and mark them as unforgettable.
-        let now = Instant::now();
         let dial_requests: Vec<_> = known_addresses
             .into_iter()
             .filter_map(|addr| component.outgoing_manager.learn_addr(addr, true, now))
@@ -411,6 +538,59 @@ where
         Ok((component, effects))
     }
 
+    /// Shuts down the server and incoming connections, whose tasks deliver events to this reactor,
+    /// and returns the remaining state to be taken over by the next reactor via
+    /// [`SmallNetwork::from_handover`].
+    pub(crate) async fn into_handover(mut self) -> NetworkHandover<P> {
+        let identity = SmallNetworkIdentity::from(&self);
+
+        // Close the shutdown socket, causing the server to exit, and the incoming connections.
+        drop(self.shutdown_sender.take());
+        drop(self.close_incoming_sender.take());
+
+        // Wait for the server to exit cleanly, so that its address can be bound again.
+        if let Some(join_handle) = self.server_join_handle.take() {
+            if let Err(ref err) = join_handle.await {
+                error!(our_id=%self.context.our_id, err=display_error(err), "could not join server task cleanly");
+            }
+        }
+
+        NetworkHandover {
+            identity,
+            outgoing_manager: self.outgoing_manager,
+            connection_symmetries: self.connection_symmetries,
+            peer_stats: self.peer_stats,
+            syncing_nodes: self.syncing_nodes,
+            departing_nodes: self.departing_nodes,
+            net_metrics: self.net_metrics,
+            outgoing_limiter: self.outgoing_limiter,
+            incoming_limiter: self.incoming_limiter,
+            active_era: self.active_era,
+            era_validators: self.era_validators,
+            is_syncing: self.context.is_syncing.load(Ordering::SeqCst),
+        }
+    }
+
+    /// Handles the outgoing connections handed over from the previous reactor which have been
+    /// dropped since the last check.
+    fn sweep_handed_over_peers(&mut self) -> Effects<Event<P>> {
+        let mut effects = Effects::new();
+        for peer_id in mem::take(&mut self.handed_over_peers) {
+            match self.outgoing_manager.get_route(peer_id) {
+                Some(handle) if handle.sender.is_closed() => {
+                    let peer_addr = handle.peer_addr;
+                    debug!(%peer_id, %peer_addr, "handed over outgoing connection dropped");
+                    effects.extend(self.handle_outgoing_dropped(peer_id, peer_addr));
+                }
+                Some(_) => {
+                    self.handed_over_peers.insert(peer_id);
+                }
+                None => (),
+            }
+        }
+        effects
+    }
+
     fn close_incoming_connections(&mut self) {
         info!("disconnecting incoming connections");
         let (close_incoming_sender, close_incoming_receiver) = watch::channel(());
@@ -437,7 +617,8 @@ where
         let peer_ids = self
             .outgoing_manager
             .connected_peers()
-            .filter(|peer_id| !exclude.contains(peer_id))
+            .filter(|peer_id| !exclude.contains(peer_id) && !self.departing_nodes.contains(peer_id))
+            .filter(|peer_id| !msg.payload_requires_envelope() || self.accepts_envelopes(*peer_id))
             .choose_multiple(rng, count);
 
         if peer_ids.len() != count {
@@ -459,6 +640,13 @@ where
         peer_ids.into_iter().collect()
     }
 
+    /// Returns whether the peer accepts payloads wrapped in envelopes on our outgoing connection.
+    fn accepts_envelopes(&self, peer_id: NodeId) -> bool {
+        self.outgoing_manager
+            .get_route(peer_id)
+            .map_or(false, |handle| handle.accepts_envelopes)
+    }
+
     /// Queues a message to be sent to a specific node.
     fn send_message(
         &self,
@@ -475,6 +663,13 @@ where
                 error!(kind=%msg.classify(), node_id=%dest, "sending unsafe message to syncing node");
             }
 
+            if msg.payload_requires_envelope() && !connection.accepts_envelopes {
+                // The peer predates the payload and would drop the connection on failing to decode
+                // it, so we rather drop the message.
+                debug!(kind=%msg.classify(), node_id=%dest, "dropped outgoing message, peer does not accept envelopes");
+                return;
+            }
+
             if let Err(msg) = connection.sender.send((msg, opt_responder)) {
                 // We lost the connection, but that fact has not reached us yet.
                 warn!(our_id=%self.context.our_id, %dest, ?msg, "dropped outgoing message, lost connection");
@@ -527,6 +722,10 @@ where
                 peer_id,
                 peer_consensus_public_key,
                 stream,
+                previous_peer_id,
+                peer_protocol_version,
+                peer_features,
+                handshake_rtt,
             } => {
                 if self.cfg.max_incoming_peer_connections != 0 {
                     if let Some(symmetries) = self.connection_symmetries.get(&peer_id) {
@@ -549,6 +748,20 @@ where
 
                 info!(%public_addr, "new incoming connection established");
 
+                if let Some(previous_peer_id) = previous_peer_id {
+                    self.migrate_peer(previous_peer_id, peer_id);
+                }
+                // A peer that said goodbye and connects again has restarted.
+                self.departing_nodes.remove(&peer_id);
+                self.record_handshake(
+                    peer_id,
+                    peer_protocol_version,
+                    handshake_rtt,
+                    peer_consensus_public_key.clone(),
+                    public_addr,
+                    peer_features,
+                );
+
                 // Learn the address the peer gave us.
                 let dial_requests =
                     self.outgoing_manager
@@ -620,10 +833,14 @@ where
             }
 
             // Update the connection symmetries.
-            self.connection_symmetries
+            if !self
+                .connection_symmetries
                 .entry(*peer_id)
                 .or_default()
-                .remove_incoming(peer_addr, Instant::now());
+                .remove_incoming(peer_addr, Instant::now())
+            {
+                self.peer_stats.remove(&*peer_id);
+            }
 
             Effects::new()
         })
@@ -654,7 +871,8 @@ where
             | ConnectionError::PeerCertificateInvalid(_)
             | ConnectionError::DidNotSendHandshake
             | ConnectionError::InvalidRemoteHandshakeMessage(_)
-            | ConnectionError::InvalidConsensusCertificate(_) => false,
+            | ConnectionError::InvalidConsensusCertificate(_)
+            | ConnectionError::InvalidIdentityLinkage(_) => false,
 
             // Definitely something we want to avoid.
             ConnectionError::WrongNetwork(_)
@@ -715,11 +933,35 @@ where
                 peer_consensus_public_key,
                 sink,
                 is_syncing,
+                peer_accepts_envelopes,
+                previous_peer_id,
+                peer_protocol_version,
+                peer_features,
+                handshake_rtt,
             } => {
                 info!("new outgoing connection established");
 
+                self.handed_over_peers.remove(&peer_id);
+
+                if let Some(previous_peer_id) = previous_peer_id {
+                    self.migrate_peer(previous_peer_id, peer_id);
+                }
+                self.departing_nodes.remove(&peer_id);
+                self.record_handshake(
+                    peer_id,
+                    peer_protocol_version,
+                    handshake_rtt,
+                    peer_consensus_public_key.clone(),
+                    peer_addr,
+                    peer_features,
+                );
+
                 let (sender, receiver) = mpsc::unbounded_channel();
-                let handle = OutgoingHandle { sender, peer_addr };
+                let handle = OutgoingHandle {
+                    sender,
+                    peer_addr,
+                    accepts_envelopes: peer_accepts_envelopes,
+                };
 
                 let request = self
                     .outgoing_manager
@@ -771,10 +1013,14 @@ where
             .outgoing_manager
             .handle_connection_drop(peer_addr, Instant::now());
 
-        self.connection_symmetries
+        if !self
+            .connection_symmetries
             .entry(peer_id)
             .or_default()
-            .unmark_outgoing(Instant::now());
+            .unmark_outgoing(Instant::now())
+        {
+            self.peer_stats.remove(&peer_id);
+        }
 
         self.process_dial_requests(requests)
     }
@@ -820,6 +1066,10 @@ where
     where
         REv: FromIncoming<P>,
     {
+        if let Some(stats) = self.peer_stats.get_mut(&peer_id) {
+            stats.record_message(Instant::now(), msg.classify());
+        }
+
         span.in_scope(|| match msg {
             Message::Handshake { .. } => {
                 // We should never receive a handshake message on an established connection. Simply
@@ -831,6 +1081,20 @@ where
             Message::Payload(payload) => {
                 effect_builder.announce_incoming(peer_id, payload).ignore()
             }
+            Message::Envelope(envelope) => {
+                // The peer is likely running a newer version, sending a type of payload we do not
+                // know about yet, so we drop it rather than treating it as misbehavior.
+                debug!(%envelope, "dropping envelope with unknown payload");
+                self.net_metrics.in_count_unknown.inc();
+                Effects::new()
+            }
+            Message::Goodbye => {
+                // The peer is shutting down, so we stop relying on it right away rather than
+                // waiting for its connections to drop.
+                info!("peer said goodbye");
+                self.departing_nodes.insert(peer_id);
+                Effects::new()
+            }
         })
     }
 
@@ -840,6 +1104,120 @@ where
         self.net_metrics.peers.set(self.peers().len() as i64);
     }
 
+    /// Records a completed handshake with a peer, starting to track it if newly connected.
+    fn record_handshake(
+        &mut self,
+        peer_id: NodeId,
+        protocol_version: ProtocolVersion,
+        handshake_rtt: Duration,
+        consensus_key: Option<PublicKey>,
+        public_addr: SocketAddr,
+        features: Option<NodeFeatures>,
+    ) {
+        match self.peer_stats.entry(peer_id) {
+            Entry::Occupied(mut entry) => entry.get_mut().record_handshake(
+                protocol_version,
+                handshake_rtt,
+                consensus_key,
+                public_addr,
+                features,
+            ),
+            Entry::Vacant(entry) => {
+                entry.insert(PeerStats::new(
+                    Instant::now(),
+                    protocol_version,
+                    handshake_rtt,
+                    consensus_key,
+                    public_addr,
+                    features,
+                ));
+            }
+        }
+    }
+
+    /// Returns the role of a peer identified by the given consensus key.
+    fn peer_role(&self, consensus_key: Option<&PublicKey>) -> PeerRole {
+        let is_validator_of = |era_id: EraId| {
+            consensus_key.map_or(false, |key| {
+                self.era_validators
+                    .get(&era_id)
+                    .map_or(false, |validators| validators.contains(key))
+            })
+        };
+        if is_validator_of(self.active_era) {
+            PeerRole::ActiveValidator
+        } else if is_validator_of(self.active_era.successor()) {
+            PeerRole::UpcomingValidator
+        } else {
+            PeerRole::NonValidator
+        }
+    }
+
+    /// Records the validators of the given eras, with `active_era` being the current one.
+    ///
+    /// Updates the limiters, and connects to validators of the active and upcoming eras we have no
+    /// outgoing connection to without waiting for the next reconnection attempt, so that messages
+    /// can be sent to them as soon as the era starts.
+    fn update_era_validators<I>(
+        &mut self,
+        active_era: EraId,
+        era_validators: I,
+    ) -> Effects<Event<P>>
+    where
+        I: IntoIterator<Item = (EraId, HashSet<PublicKey>)>,
+    {
+        // We have a new `active_era`, even if we may have skipped some, as this one is the highest
+        // seen.
+        self.active_era = active_era;
+        self.era_validators.extend(era_validators);
+        let upcoming_era = active_era.successor();
+        self.era_validators
+            .retain(|era_id, _| *era_id == active_era || *era_id == upcoming_era);
+
+        let active_validators = self
+            .era_validators
+            .get(&active_era)
+            .cloned()
+            .unwrap_or_default();
+
+        if active_validators.is_empty() {
+            error!("received an empty set of active era validators");
+        }
+
+        let upcoming_validators = self
+            .era_validators
+            .get(&upcoming_era)
+            .cloned()
+            .unwrap_or_default();
+
+        debug!(
+            %active_era,
+            active = active_validators.len(),
+            upcoming = upcoming_validators.len(),
+            "updating active and upcoming validators"
+        );
+        self.incoming_limiter
+            .update_validators(active_validators.clone(), upcoming_validators.clone());
+        self.outgoing_limiter
+            .update_validators(active_validators, upcoming_validators);
+
+        let now = Instant::now();
+        let addrs: Vec<SocketAddr> = self
+            .peer_stats
+            .iter()
+            .filter(|(peer_id, stats)| {
+                self.outgoing_manager.get_route(**peer_id).is_none()
+                    && self.peer_role(stats.consensus_key()) != PeerRole::NonValidator
+            })
+            .map(|(_, stats)| stats.public_addr())
+            .collect();
+        let requests: Vec<_> = addrs
+            .into_iter()
+            .filter_map(|addr| self.outgoing_manager.expedite_addr(addr, now))
+            .collect();
+        self.process_dial_requests(requests)
+    }
+
     /// Updates a set of known joining nodes.
     /// If we've just connected to a non-joining node that peer will be removed from the set.
     fn update_syncing_nodes_set(&mut self, peer_id: NodeId, is_syncing: bool) {
@@ -853,6 +1231,34 @@ where
         }
     }
 
+    /// Forgets what we know about a peer's previous identity after it proved it rotated to
+    /// `peer_id`.
+    fn migrate_peer(&mut self, previous_peer_id: NodeId, peer_id: NodeId) {
+        let was_connected = self
+            .connection_symmetries
+            .remove(&previous_peer_id)
+            .is_some();
+        self.peer_stats.remove(&previous_peer_id);
+        let was_syncing = self.syncing_nodes.remove(&previous_peer_id);
+        self.departing_nodes.remove(&previous_peer_id);
+        if was_connected || was_syncing {
+            info!(%previous_peer_id, %peer_id, "peer rotated its identity");
+        } else {
+            debug!(%previous_peer_id, %peer_id, "peer rotated identity unknown to us");
+        }
+    }
+
+    /// Tells all peers that we are shutting down, so they stop relying on us right away.
+    pub(crate) fn say_goodbye(&self) {
+        info!("saying goodbye to peers");
+        self.broadcast_message(Arc::new(Message::Goodbye));
+    }
+
+    /// Returns whether all messages queued for peers have been sent.
+    pub(crate) fn has_sent_queued_messages(&self) -> bool {
+        self.net_metrics.queued_messages.get() <= 0
+    }
+
     /// Returns the set of connected nodes.
     pub(crate) fn peers(&self) -> BTreeMap<NodeId, String> {
         let mut ret = BTreeMap::new();
@@ -876,6 +1282,59 @@ where
         ret
     }
 
+    /// Returns the details of all connected nodes.
+    pub(crate) fn detailed_peers(&self) -> Vec<PeerDetails> {
+        let now = Instant::now();
+        self.peers()
+            .into_iter()
+            .filter_map(|(node_id, address)| {
+                let direction = self
+                    .connection_symmetries
+                    .get(&node_id)
+                    .and_then(ConnectionSymmetry::direction)
+                    // Outgoing connections handed over from the previous reactor may not have
+                    // been marked yet.
+                    .unwrap_or(ConnectionDirection::Outgoing);
+                match self.peer_stats.get(&node_id) {
+                    Some(stats) => {
+                        let role = self.peer_role(stats.consensus_key());
+                        Some(stats.to_details(node_id, address, direction, role, now))
+                    }
+                    None => {
+                        // This should never happen unless we failed to record a handshake.
+                        warn!(%node_id, "stats of connected peer missing");
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Applies the reloaded limits on peer connections and non-validator traffic.
+    ///
+    /// The connection limit only applies to connections established after the reload.
+    pub(crate) fn reload_config(&mut self, reloadable_config: &ReloadableConfig) {
+        self.cfg.max_incoming_peer_connections = reloadable_config.max_incoming_peer_connections;
+        if !self
+            .outgoing_limiter
+            .set_resources_per_second(reloadable_config.max_outgoing_byte_rate_non_validators)
+        {
+            warn!(
+                "max_outgoing_byte_rate_non_validators was 0 when the node started, restart the \
+                node to impose a limit"
+            );
+        }
+        if !self
+            .incoming_limiter
+            .set_resources_per_second(reloadable_config.max_incoming_message_rate_non_validators)
+        {
+            warn!(
+                "max_incoming_message_rate_non_validators was 0 when the node started, restart \
+                the node to impose a limit"
+            );
+        }
+    }
+
     /// Returns the node id of this network node.
     #[cfg(test)]
     pub(crate) fn node_id(&self) -> NodeId {
@@ -1003,6 +1462,9 @@ where
             }
             Event::NetworkInfoRequest { req } => match *req {
                 NetworkInfoRequest::Peers { responder } => responder.respond(self.peers()).ignore(),
+                NetworkInfoRequest::DetailedPeers { responder } => {
+                    responder.respond(self.detailed_peers()).ignore()
+                }
                 NetworkInfoRequest::FullyConnectedPeers { responder } => {
                     let mut symmetric_peers: Vec<NodeId> = self
                         .connection_symmetries
@@ -1010,6 +1472,7 @@ where
                         .filter_map(|(node_id, sym)| {
                             matches!(sym, ConnectionSymmetry::Symmetric { .. }).then(|| *node_id)
                         })
+                        .filter(|node_id| !self.departing_nodes.contains(node_id))
                         .collect();
 
                     symmetric_peers.shuffle(rng);
@@ -1023,13 +1486,39 @@ where
                         .filter_map(|(node_id, sym)| {
                             matches!(sym, ConnectionSymmetry::Symmetric { .. }).then(|| *node_id)
                         })
-                        .filter(|node_id| !self.syncing_nodes.contains(node_id))
+                        .filter(|node_id| {
+                            !self.syncing_nodes.contains(node_id)
+                                && !self.departing_nodes.contains(node_id)
+                        })
                         .collect();
 
                     symmetric_validator_peers.shuffle(rng);
 
                     responder.respond(symmetric_validator_peers).ignore()
                 }
+                NetworkInfoRequest::FullyConnectedFinalitySignatureBackfillPeers { responder } => {
+                    let mut backfill_peers: Vec<NodeId> = self
+                        .connection_symmetries
+                        .iter()
+                        .filter_map(|(node_id, sym)| {
+                            matches!(sym, ConnectionSymmetry::Symmetric { .. }).then(|| *node_id)
+                        })
+                        .filter(|node_id| {
+                            !self.departing_nodes.contains(node_id)
+                                && self
+                                    .peer_stats
+                                    .get(node_id)
+                                    .and_then(PeerStats::features)
+                                    .map_or(false, |features| {
+                                        features.finality_signature_backfill_server
+                                    })
+                        })
+                        .collect();
+
+                    backfill_peers.shuffle(rng);
+
+                    responder.respond(backfill_peers).ignore()
+                }
             },
             Event::PeerAddressReceived(gossiped_address) => {
                 let requests = self.outgoing_manager.learn_addr(
@@ -1043,6 +1532,9 @@ where
                 // TODO: We do not have a proper by-node-ID blocklist, but rather only block the
                 // current outgoing address of a peer.
                 warn!(%peer_id, "adding peer to blocklist after transgression");
+                if let Some(stats) = self.peer_stats.get_mut(&*peer_id) {
+                    stats.record_offense();
+                }
 
                 if let Some(addr) = self.outgoing_manager.get_addr(*peer_id) {
                     let requests = self.outgoing_manager.block_addr(addr, Instant::now());
@@ -1054,51 +1546,47 @@ where
             }
             Event::ContractRuntimeAnnouncement(
                 ContractRuntimeAnnouncement::LinearChainBlock { .. }
-                | ContractRuntimeAnnouncement::CommitStepSuccess { .. },
+                | ContractRuntimeAnnouncement::DeployExecutionProgress(_)
+                | ContractRuntimeAnnouncement::CommitStepSuccess { .. }
+                | ContractRuntimeAnnouncement::DeployConfigUpdated { .. },
             ) => Effects::new(),
             Event::ContractRuntimeAnnouncement(
                 ContractRuntimeAnnouncement::UpcomingEraValidators {
                     era_that_is_ending,
-                    mut upcoming_era_validators,
+                    upcoming_era_validators,
                 },
             ) => {
                 if era_that_is_ending < self.active_era {
                     debug!("ignoring past era end announcement");
+                    Effects::new()
                 } else {
-                    // We have a new `active_era`, even if we may have skipped some, as this one
-                    // is the highest seen.
-                    self.active_era = era_that_is_ending + 1;
-
-                    let active_validators: HashSet<PublicKey> = upcoming_era_validators
-                        .remove(&self.active_era)
-                        .unwrap_or_default()
-                        .into_keys()
-                        .collect();
-
-                    if active_validators.is_empty() {
-                        error!("received an empty set of active era validators");
-                    }
-
-                    let upcoming_validators: HashSet<PublicKey> = upcoming_era_validators
-                        .remove(&(self.active_era + 1))
-                        .unwrap_or_default()
-                        .into_keys()
-                        .collect();
-
-                    debug!(
-                        %era_that_is_ending,
-                        active = active_validators.len(),
-                        upcoming = upcoming_validators.len(),
-                        "updating active and upcoming validators"
-                    );
-                    self.incoming_limiter
-                        .update_validators(active_validators.clone(), upcoming_validators.clone());
-                    self.outgoing_limiter
-                        .update_validators(active_validators, upcoming_validators);
+                    let era_validators =
+                        upcoming_era_validators
+                            .into_iter()
+                            .map(|(era_id, validator_weights)| {
+                                (era_id, validator_weights.into_keys().collect())
+                            });
+                    self.update_era_validators(era_that_is_ending + 1, era_validators)
+                }
+            }
+            Event::ConsensusAnnouncement(ConsensusAnnouncement::EraValidators {
+                era_id,
+                validators,
+            }) => {
+                if era_id < self.active_era {
+                    debug!(%era_id, "ignoring past era validators announcement");
+                    Effects::new()
+                } else {
+                    let era_validators = (era_id, validators.into_iter().collect());
+                    self.update_era_validators(era_id, iter::once(era_validators))
                 }
-
-                Effects::new()
             }
+            Event::ConsensusAnnouncement(
+                ConsensusAnnouncement::Finalized(_)
+                | ConsensusAnnouncement::ProposedBlock(_)
+                | ConsensusAnnouncement::CreatedFinalitySignature(_)
+                | ConsensusAnnouncement::Fault { .. },
+            ) => Effects::new(),
 
             Event::GossipOurAddress => {
                 let our_address = GossipedAddress::new(self.context.public_addr);
@@ -1118,6 +1606,7 @@ where
                 let requests = self.outgoing_manager.perform_housekeeping(now);
 
                 let mut effects = self.process_dial_requests(requests);
+                effects.extend(self.sweep_handed_over_peers());
 
                 effects.extend(
                     effect_builder
@@ -1145,7 +1634,9 @@ pub(crate) enum SmallNetworkIdentityError {
     #[error(transparent)]
     LoadCertError(#[from] LoadCertError),
     #[error(transparent)]
-    LoadSecretKeyError(#[from] LoadSecretKeyError),
+    LoadSecretKeyError(#[from] KeyFileError),
+    #[error("could not sign identity linkage: {0}")]
+    CouldNotSignIdentityLinkage(OpenSslErrorStack),
 }
 
 /// An ephemeral [PKey<Private>] and [TlsCert] that identifies this node
@@ -1153,6 +1644,8 @@ pub(crate) enum SmallNetworkIdentityError {
 pub(crate) struct SmallNetworkIdentity {
     secret_key: Arc<PKey<Private>>,
     tls_certificate: Arc<TlsCert>,
+    /// Linkage to the previous identity, if the identity was rotated.
+    identity_linkage: Option<IdentityLinkage>,
 }
 
 impl SmallNetworkIdentity {
@@ -1160,6 +1653,7 @@ impl SmallNetworkIdentity {
         Self {
             secret_key: Arc::new(secret_key),
             tls_certificate: Arc::new(tls_certificate),
+            identity_linkage: None,
         }
     }
 
@@ -1176,10 +1670,35 @@ impl SmallNetworkIdentity {
         identity: &IdentityConfig,
     ) -> result::Result<Self, SmallNetworkIdentityError> {
         let not_yet_validated_x509_cert = tls::load_cert(&identity.tls_certificate)?;
-        let secret_key = tls::load_secret_key(&identity.secret_key)?;
+        let secret_key = key_file::load_tls_secret_key(
+            &identity.secret_key,
+            identity.secret_key_passphrase.as_ref(),
+        )?;
         let x509_cert = tls::tls_cert_from_x509(not_yet_validated_x509_cert)?;
 
-        Ok(SmallNetworkIdentity::new(secret_key, x509_cert))
+        let identity_linkage = match &identity.previous {
+            Some(previous) => {
+                let previous_cert =
+                    tls::tls_cert_from_x509(tls::load_cert(&previous.tls_certificate)?)?;
+                let previous_secret_key = key_file::load_tls_secret_key(
+                    &previous.secret_key,
+                    previous.secret_key_passphrase.as_ref(),
+                )?;
+                let linkage = IdentityLinkage::create(
+                    &previous_cert,
+                    &previous_secret_key,
+                    NodeId::from(x509_cert.public_key_fingerprint()),
+                )
+                .map_err(SmallNetworkIdentityError::CouldNotSignIdentityLinkage)?;
+                Some(linkage)
+            }
+            None => None,
+        };
+
+        Ok(SmallNetworkIdentity {
+            identity_linkage,
+            ..SmallNetworkIdentity::new(secret_key, x509_cert)
+        })
     }
 
     pub(crate) fn with_generated_certs() -> result::Result<Self, SmallNetworkIdentityError> {
@@ -1198,6 +1717,7 @@ where
         SmallNetworkIdentity {
             secret_key: small_network.context.secret_key.clone(),
             tls_certificate: small_network.context.our_cert.clone(),
+            identity_linkage: small_network.context.identity_linkage.clone(),
         }
     }
 }
@@ -1220,7 +1740,7 @@ pub(crate) type FullTransport<P> = tokio_serde::Framed<
     FramedTransport,
     Message<P>,
     Arc<Message<P>>,
-    CountingFormat<BincodeFormat>,
+    CountingFormat<EnvelopeFormat<BincodeFormat>>,
 >;
 
 pub(crate) type FramedTransport = tokio_util::codec::Framed<Transport, LengthDelimitedCodec>;
@@ -1228,11 +1748,13 @@ pub(crate) type FramedTransport = tokio_util::codec::Framed<Transport, LengthDel
 /// Constructs a new full transport on a stream.
 ///
 /// A full transport contains the framing as well as the encoding scheme used to send messages.
+/// Outgoing payloads are wrapped in envelopes if `peer_accepts_envelopes` is true.
 fn full_transport<P>(
     metrics: Weak<Metrics>,
     connection_id: ConnectionId,
     framed: FramedTransport,
     role: Role,
+    peer_accepts_envelopes: bool,
 ) -> FullTransport<P>
 where
     for<'de> P: Serialize + Deserialize<'de>,
@@ -1240,7 +1762,12 @@ where
 {
     tokio_serde::Framed::new(
         framed,
-        CountingFormat::new(metrics, connection_id, role, BincodeFormat::default()),
+        CountingFormat::new(
+            metrics,
+            connection_id,
+            role,
+            EnvelopeFormat::new(peer_accepts_envelopes, BincodeFormat::default()),
+        ),
     )
 }
 
@@ -1254,6 +1781,21 @@ fn framed_transport(transport: Transport, maximum_net_message_size: u32) -> Fram
     )
 }
 
+/// Decodes a single message the way it is decoded when read from a connection, i.e. after its
+/// frame has been split off.
+#[cfg(feature = "fuzzing")]
+pub(crate) fn decode_message<P>(frame: &[u8]) -> io::Result<Message<P>>
+where
+    P: Payload,
+    for<'de> Message<P>: Deserialize<'de>,
+{
+    let mut format = EnvelopeFormat::new(false, BincodeFormat::default());
+    tokio_serde::Deserializer::deserialize(
+        std::pin::Pin::new(&mut format),
+        &bytes::BytesMut::from(frame),
+    )
+}
+
 impl<R, P> Debug for SmallNetwork<R, P>
 where
     P: Payload,
@@ -11,10 +11,11 @@ use datasize::DataSize;
 
 use super::{
     counting_format::ConnectionId,
+    identity_linkage::IdentityLinkage,
     message::{ConsensusCertificate, ConsensusKeyPair},
     Message,
 };
-use crate::types::Chainspec;
+use crate::types::{Chainspec, NodeFeatures};
 
 /// Data retained from the chainspec by the small networking component.
 ///
@@ -52,6 +53,7 @@ impl ChainInfo {
         consensus_keys: Option<&ConsensusKeyPair>,
         connection_id: ConnectionId,
         is_syncing: bool,
+        identity_linkage: Option<&IdentityLinkage>,
     ) -> Message<P> {
         Message::Handshake {
             network_name: self.network_name.clone(),
@@ -61,6 +63,9 @@ impl ChainInfo {
                 .map(|key_pair| ConsensusCertificate::create(connection_id, key_pair)),
             is_syncing,
             chainspec_hash: Some(self.chainspec_hash),
+            features: Some(NodeFeatures::current()),
+            accepts_envelopes: true,
+            identity_linkage: identity_linkage.cloned(),
         }
     }
 }
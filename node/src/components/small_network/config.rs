@@ -7,6 +7,7 @@ use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
 use super::EstimatorWeights;
+use crate::utils::key_file::PassphraseSource;
 
 /// Default binding address.
 ///
@@ -64,8 +65,28 @@ pub struct IdentityConfig {
     pub tls_certificate: PathBuf,
     /// Path to a secret key.
     pub secret_key: PathBuf,
+    /// Source of the passphrase unlocking the secret key file, if it is encrypted.
+    pub secret_key_passphrase: Option<PassphraseSource>,
     /// Path to a certificate authority certificate
     pub ca_certificate: PathBuf,
+    /// The identity used before the most recent key rotation, if any.
+    ///
+    /// If set, the node proves to its peers that it is the same node as the one with the previous
+    /// identity.
+    pub previous: Option<PreviousIdentityConfig>,
+}
+
+/// Small network identity used before a key rotation.
+#[derive(DataSize, Debug, Clone, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct PreviousIdentityConfig {
+    /// Path to the previous signed certificate.
+    pub tls_certificate: PathBuf,
+    /// Path to the previous secret key.
+    pub secret_key: PathBuf,
+    /// Source of the passphrase unlocking the previous secret key file, if it is encrypted.
+    pub secret_key_passphrase: Option<PassphraseSource>,
 }
 
 /// Small network configuration.
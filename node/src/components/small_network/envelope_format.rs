@@ -0,0 +1,79 @@
+//! Envelope wire format wrapper.
+//!
+//! Wraps outgoing payloads in versioned [`Envelope`]s, if the peer accepts them, and opens incoming
+//! envelopes holding payloads of a known type and version.
+
+use std::{io, pin::Pin, sync::Arc};
+
+use bytes::{Bytes, BytesMut};
+use pin_project::pin_project;
+use tokio_serde::{Deserializer, Serializer};
+
+use super::{message::Envelope, Message, Payload};
+
+/// A serializer/deserializer wrapper sealing and opening payload envelopes.
+#[pin_project]
+#[derive(Debug)]
+pub struct EnvelopeFormat<F> {
+    /// The actual serializer performing the work.
+    #[pin]
+    inner: F,
+    /// Whether outgoing payloads are wrapped in envelopes.
+    seal_outgoing: bool,
+}
+
+impl<F> EnvelopeFormat<F> {
+    /// Creates a new envelope formatter.
+    ///
+    /// Outgoing payloads are only wrapped if `seal_outgoing` is true, which must only be set if the
+    /// peer accepts envelopes. Incoming envelopes are always opened.
+    #[inline]
+    pub(super) fn new(seal_outgoing: bool, inner: F) -> Self {
+        Self {
+            inner,
+            seal_outgoing,
+        }
+    }
+}
+
+impl<F, P> Serializer<Arc<Message<P>>> for EnvelopeFormat<F>
+where
+    F: Serializer<Arc<Message<P>>, Error = io::Error>,
+    P: Payload,
+{
+    type Error = io::Error;
+
+    #[inline]
+    fn serialize(self: Pin<&mut Self>, item: &Arc<Message<P>>) -> Result<Bytes, Self::Error> {
+        let this = self.project();
+        match &**item {
+            Message::Payload(payload) if *this.seal_outgoing => {
+                let envelope = Envelope::seal(payload)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+                F::serialize(this.inner, &Arc::new(Message::Envelope(envelope)))
+            }
+            _ => F::serialize(this.inner, item),
+        }
+    }
+}
+
+impl<F, P> Deserializer<Message<P>> for EnvelopeFormat<F>
+where
+    F: Deserializer<Message<P>, Error = io::Error>,
+    P: Payload,
+{
+    type Error = io::Error;
+
+    #[inline]
+    fn deserialize(self: Pin<&mut Self>, src: &BytesMut) -> Result<Message<P>, Self::Error> {
+        match F::deserialize(self.project().inner, src)? {
+            Message::Envelope(envelope) => match envelope.open() {
+                Ok(Some(payload)) => Ok(Message::Payload(payload)),
+                // The payload's type or version is unknown, leave it to the caller to drop it.
+                Ok(None) => Ok(Message::Envelope(envelope)),
+                Err(err) => Err(io::Error::new(io::ErrorKind::InvalidData, err)),
+            },
+            message => Ok(message),
+        }
+    }
+}
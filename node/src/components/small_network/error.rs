@@ -1,15 +1,16 @@
-use std::{error, io, net::SocketAddr, result, sync::Arc};
+use std::{error, io, net::SocketAddr, result};
 
 use casper_hashing::Digest;
-use casper_types::{crypto, ProtocolVersion, SecretKey};
+use casper_types::{crypto, ProtocolVersion};
 use datasize::DataSize;
 use openssl::{error::ErrorStack, ssl};
 use serde::Serialize;
 use thiserror::Error;
 
+use super::identity_linkage::IdentityLinkageError;
 use crate::{
     tls::{LoadCertError, ValidationError},
-    utils::{LoadError, Loadable, ResolveAddressError},
+    utils::{key_file::KeyFileError, LoadError, ResolveAddressError},
 };
 
 pub(super) type Result<T> = result::Result<T, Error>;
@@ -25,7 +26,7 @@ pub enum Error {
     LoadConsensusKeys(
         #[serde(skip_serializing)]
         #[source]
-        LoadError<<Arc<SecretKey> as Loadable>::Error>,
+        LoadError<KeyFileError>,
     ),
     /// Failed to create a TCP listener.
     #[error("failed to create listener on {1}")]
@@ -205,6 +206,13 @@ pub enum ConnectionError {
         #[source]
         crypto::Error,
     ),
+    /// The peer sent an identity linkage, but it was invalid.
+    #[error("invalid identity linkage")]
+    InvalidIdentityLinkage(
+        #[serde(skip_serializing)]
+        #[source]
+        IdentityLinkageError,
+    ),
     /// Failed to reunite handshake sink/stream.
     ///
     /// This is usually a bug.
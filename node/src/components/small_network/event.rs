@@ -3,9 +3,10 @@ use std::{
     io, mem,
     net::SocketAddr,
     sync::Arc,
+    time::Duration,
 };
 
-use casper_types::PublicKey;
+use casper_types::{ProtocolVersion, PublicKey};
 use derive_more::From;
 use futures::stream::{SplitSink, SplitStream};
 use serde::Serialize;
@@ -16,11 +17,13 @@ use super::{error::ConnectionError, FullTransport, GossipedAddress, Message, Nod
 use crate::{
     effect::{
         announcements::{
-            BlocklistAnnouncement, ChainSynchronizerAnnouncement, ContractRuntimeAnnouncement,
+            BlocklistAnnouncement, ChainSynchronizerAnnouncement, ConsensusAnnouncement,
+            ContractRuntimeAnnouncement,
         },
         requests::{NetworkInfoRequest, NetworkRequest},
     },
     protocol::Message as ProtocolMessage,
+    types::NodeFeatures,
 };
 
 const _SMALL_NETWORK_EVENT_SIZE: usize = mem::size_of::<Event<ProtocolMessage>>();
@@ -101,6 +104,10 @@ pub(crate) enum Event<P> {
     /// Chain synchronizer announcement.
     #[from]
     ChainSynchronizerAnnouncement(ChainSynchronizerAnnouncement),
+
+    /// Consensus announcement.
+    #[from]
+    ConsensusAnnouncement(ConsensusAnnouncement),
 }
 
 impl From<NetworkRequest<ProtocolMessage>> for Event<ProtocolMessage> {
@@ -153,6 +160,9 @@ impl<P: Display> Display for Event<P> {
             Event::ChainSynchronizerAnnouncement(ann) => {
                 write!(f, "handling chain synchronizer announcement: {}", ann)
             }
+            Event::ConsensusAnnouncement(ann) => {
+                write!(f, "handling consensus announcement: {}", ann)
+            }
         }
     }
 }
@@ -191,6 +201,14 @@ pub(crate) enum IncomingConnection<P> {
         /// Stream of incoming messages. for incoming connections.
         #[serde(skip_serializing)]
         stream: SplitStream<FullTransport<P>>,
+        /// The peer's previous [`NodeId`], if it rotated its identity.
+        previous_peer_id: Option<NodeId>,
+        /// The protocol version the peer is speaking.
+        peer_protocol_version: ProtocolVersion,
+        /// The features the peer advertised, if any.
+        peer_features: Option<NodeFeatures>,
+        /// The time between sending our handshake and receiving the peer's.
+        handshake_rtt: Duration,
     },
 }
 
@@ -212,6 +230,10 @@ impl<P> Display for IncomingConnection<P> {
                 peer_id,
                 peer_consensus_public_key,
                 stream: _,
+                previous_peer_id,
+                peer_protocol_version: _,
+                peer_features: _,
+                handshake_rtt: _,
             } => {
                 write!(
                     f,
@@ -219,6 +241,10 @@ impl<P> Display for IncomingConnection<P> {
                     peer_addr, peer_id, public_addr
                 )?;
 
+                if let Some(previous_peer_id) = previous_peer_id {
+                    write!(f, ", previously {}", previous_peer_id)?;
+                }
+
                 if let Some(public_key) = peer_consensus_public_key {
                     write!(f, " [{}]", public_key)
                 } else {
@@ -263,6 +289,16 @@ pub(crate) enum OutgoingConnection<P> {
         sink: SplitSink<FullTransport<P>, Arc<Message<P>>>,
         /// Holds the information whether the remote node is syncing.
         is_syncing: bool,
+        /// Whether the peer accepts payloads wrapped in envelopes.
+        peer_accepts_envelopes: bool,
+        /// The peer's previous [`NodeId`], if it rotated its identity.
+        previous_peer_id: Option<NodeId>,
+        /// The protocol version the peer is speaking.
+        peer_protocol_version: ProtocolVersion,
+        /// The features the peer advertised, if any.
+        peer_features: Option<NodeFeatures>,
+        /// The time between sending our handshake and receiving the peer's.
+        handshake_rtt: Duration,
     },
 }
 
@@ -284,6 +320,11 @@ impl<P> Display for OutgoingConnection<P> {
                 peer_consensus_public_key,
                 sink: _,
                 is_syncing,
+                peer_accepts_envelopes: _,
+                previous_peer_id,
+                peer_protocol_version: _,
+                peer_features: _,
+                handshake_rtt: _,
             } => {
                 write!(
                     f,
@@ -291,6 +332,10 @@ impl<P> Display for OutgoingConnection<P> {
                     peer_addr, peer_id, is_syncing
                 )?;
 
+                if let Some(previous_peer_id) = previous_peer_id {
+                    write!(f, ", previously {}", previous_peer_id)?;
+                }
+
                 if let Some(public_key) = peer_consensus_public_key {
                     write!(f, " [{}]", public_key)
                 } else {
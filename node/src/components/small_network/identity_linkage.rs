@@ -0,0 +1,170 @@
+//! Linkage of a rotated node identity to its previous one.
+//!
+//! A node's [`NodeId`] is derived from its TLS key, so rotating the key makes the node look like a
+//! brand-new peer. To avoid this, a node configured with its previous identity signs its new
+//! `NodeId` with the previous secret key and sends the resulting [`IdentityLinkage`] in every
+//! handshake. Peers validating the linkage migrate what they know about the previous identity to
+//! the new one.
+
+use std::fmt::{self, Display, Formatter};
+
+use datasize::DataSize;
+use openssl::{
+    error::ErrorStack,
+    hash::MessageDigest,
+    pkey::{PKeyRef, Private},
+    sign::{Signer, Verifier},
+    x509::X509,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    tls::{self, TlsCert, ValidationError},
+    types::NodeId,
+};
+
+/// Domain separation prefix of the signed data, so a linkage signature cannot be mistaken for any
+/// other signature made with a TLS key.
+const LINKAGE_DOMAIN: &[u8] = b"casper-node-identity-linkage:";
+
+/// A statement, signed with the previous identity's secret key, that a node now uses a new identity.
+#[derive(Clone, DataSize, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub(crate) struct IdentityLinkage {
+    /// The DER-encoded certificate of the previous identity.
+    #[serde(with = "serde_bytes")]
+    previous_certificate: Vec<u8>,
+    /// Signature of the new `NodeId`, made with the previous identity's secret key.
+    #[serde(with = "serde_bytes")]
+    signature: Vec<u8>,
+}
+
+impl IdentityLinkage {
+    /// Creates a linkage from the previous identity's certificate and secret key to `new_id`.
+    pub(super) fn create(
+        previous_certificate: &TlsCert,
+        previous_secret_key: &PKeyRef<Private>,
+        new_id: NodeId,
+    ) -> Result<Self, ErrorStack> {
+        let mut signer = Signer::new(MessageDigest::sha512(), previous_secret_key)?;
+        signer.update(LINKAGE_DOMAIN)?;
+        signer.update(new_id.hash_bytes())?;
+        Ok(IdentityLinkage {
+            previous_certificate: previous_certificate.as_x509().to_der()?,
+            signature: signer.sign_to_vec()?,
+        })
+    }
+
+    /// Validates the linkage as sent by the peer authenticated as `new_id`, returning the peer's
+    /// previous `NodeId` if valid.
+    pub(super) fn validate(&self, new_id: NodeId) -> Result<NodeId, IdentityLinkageError> {
+        let previous_x509 = X509::from_der(&self.previous_certificate)
+            .map_err(IdentityLinkageError::CorruptCertificate)?;
+        let public_key = previous_x509
+            .public_key()
+            .map_err(IdentityLinkageError::CorruptCertificate)?;
+        let previous_id = NodeId::from(
+            tls::tls_cert_from_x509(previous_x509)
+                .map_err(IdentityLinkageError::InvalidCertificate)?
+                .public_key_fingerprint(),
+        );
+        if previous_id == new_id {
+            return Err(IdentityLinkageError::LinksToItself);
+        }
+
+        let mut verifier = Verifier::new(MessageDigest::sha512(), &public_key)
+            .map_err(IdentityLinkageError::Verification)?;
+        verifier
+            .update(LINKAGE_DOMAIN)
+            .map_err(IdentityLinkageError::Verification)?;
+        verifier
+            .update(new_id.hash_bytes())
+            .map_err(IdentityLinkageError::Verification)?;
+        // OpenSSL reports some malformed signatures as errors rather than as failed verification.
+        match verifier.verify(&self.signature) {
+            Ok(true) => Ok(previous_id),
+            Ok(false) | Err(_) => Err(IdentityLinkageError::InvalidSignature),
+        }
+    }
+}
+
+impl Display for IdentityLinkage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "identity linkage ({} byte certificate)",
+            self.previous_certificate.len()
+        )
+    }
+}
+
+/// Error validating an [`IdentityLinkage`].
+#[derive(Debug, Error)]
+pub enum IdentityLinkageError {
+    /// The previous identity's certificate could not be decoded.
+    #[error("could not decode previous certificate: {0}")]
+    CorruptCertificate(#[source] ErrorStack),
+    /// The previous identity's certificate is not a valid node certificate.
+    #[error("invalid previous certificate: {0}")]
+    InvalidCertificate(#[source] ValidationError),
+    /// The previous identity is the same as the current one.
+    #[error("previous identity is the current one")]
+    LinksToItself,
+    /// Verifying the signature failed unexpectedly.
+    #[error("could not verify signature: {0}")]
+    Verification(#[source] ErrorStack),
+    /// The signature is not a signature of the peer's current `NodeId` by its previous identity.
+    #[error("invalid signature")]
+    InvalidSignature,
+}
+
+#[cfg(test)]
+mod tests {
+    use openssl::pkey::PKey;
+
+    use super::*;
+
+    fn generate_identity() -> (TlsCert, PKey<Private>) {
+        let (cert, secret_key) = tls::generate_node_cert().unwrap();
+        (tls::validate_self_signed_cert(cert).unwrap(), secret_key)
+    }
+
+    #[test]
+    fn should_validate_linkage_to_new_identity() {
+        let (previous_cert, previous_secret_key) = generate_identity();
+        let (new_cert, _) = generate_identity();
+        let previous_id = NodeId::from(previous_cert.public_key_fingerprint());
+        let new_id = NodeId::from(new_cert.public_key_fingerprint());
+
+        let linkage =
+            IdentityLinkage::create(&previous_cert, &previous_secret_key, new_id).unwrap();
+        assert_eq!(linkage.validate(new_id).unwrap(), previous_id);
+
+        // The linkage is bound to the new identity.
+        let (other_cert, _) = generate_identity();
+        let other_id = NodeId::from(other_cert.public_key_fingerprint());
+        assert!(matches!(
+            linkage.validate(other_id),
+            Err(IdentityLinkageError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn should_reject_linkage_not_signed_by_previous_identity() {
+        let (previous_cert, _) = generate_identity();
+        let (new_cert, new_secret_key) = generate_identity();
+        let new_id = NodeId::from(new_cert.public_key_fingerprint());
+
+        let forged = IdentityLinkage::create(&previous_cert, &new_secret_key, new_id).unwrap();
+        assert!(matches!(
+            forged.validate(new_id),
+            Err(IdentityLinkageError::InvalidSignature)
+        ));
+
+        let to_itself = IdentityLinkage::create(&new_cert, &new_secret_key, new_id).unwrap();
+        assert!(matches!(
+            to_itself.validate(new_id),
+            Err(IdentityLinkageError::LinksToItself)
+        ));
+    }
+}
@@ -5,7 +5,10 @@
 
 use std::{
     collections::HashSet,
-    sync::{Arc, RwLock},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, RwLock,
+    },
     time::{Duration, Instant},
 };
 
@@ -38,6 +41,11 @@ pub(super) trait Limiter: Send + Sync {
         active_validators: HashSet<PublicKey>,
         upcoming_validators: HashSet<PublicKey>,
     );
+
+    /// Update the number of resource units allowed per second, with `0` meaning unlimited.
+    ///
+    /// Returns `false` if the limiter cannot impose the given limit.
+    fn set_resources_per_second(&self, resources_per_second: u32) -> bool;
 }
 
 /// A per-peer handle for a limiter.
@@ -71,6 +79,10 @@ impl Limiter for Unlimited {
         _upcoming_validators: HashSet<PublicKey>,
     ) {
     }
+
+    fn set_resources_per_second(&self, resources_per_second: u32) -> bool {
+        resources_per_second == 0
+    }
 }
 
 #[async_trait]
@@ -92,8 +104,8 @@ pub(super) struct ClassBasedLimiter {
 /// The limiter's state.
 #[derive(Debug)]
 struct ClassBasedLimiterData {
-    /// Number of resource units to allow for non-validators per second.
-    resources_per_second: u32,
+    /// Number of resource units to allow for non-validators per second, or `0` if unlimited.
+    resources_per_second: AtomicU32,
     /// Set of active and upcoming validators.
     validator_sets: RwLock<ValidatorSets>,
     /// Information about available resources.
@@ -119,7 +131,7 @@ impl ClassBasedLimiterData {
     /// Initial resources will be initialized to 0, with the last refill set to the current time.
     fn new(resources_per_second: u32, wait_time_sec: Counter) -> Self {
         ClassBasedLimiterData {
-            resources_per_second,
+            resources_per_second: AtomicU32::new(resources_per_second),
             validator_sets: Default::default(),
             resources: Mutex::new(ResourceData {
                 available: 0,
@@ -217,6 +229,14 @@ impl Limiter for ClassBasedLimiter {
             }
         }
     }
+
+    fn set_resources_per_second(&self, resources_per_second: u32) -> bool {
+        debug!(resources_per_second, "updating resource limit");
+        self.data
+            .resources_per_second
+            .store(resources_per_second, Ordering::SeqCst);
+        true
+    }
 }
 
 #[async_trait]
@@ -259,9 +279,14 @@ impl LimiterHandle for ClassBasedHandle {
                 return;
             }
             PeerClass::Bulk => {
-                let max_stored_resource = ((self.data.resources_per_second as f64)
-                    * STORED_BUFFER_SECS.as_secs_f64())
-                    as u32;
+                let resources_per_second = self.data.resources_per_second.load(Ordering::SeqCst);
+                if resources_per_second == 0 {
+                    // The limit has been lifted.
+                    return;
+                }
+
+                let max_stored_resource =
+                    ((resources_per_second as f64) * STORED_BUFFER_SECS.as_secs_f64()) as u32;
 
                 // We are a low-priority sender. Obtain a lock on the resources and wait an
                 // appropriate amount of time to fill them up.
@@ -277,16 +302,14 @@ impl LimiterHandle for ClassBasedHandle {
                         // Add appropriate amount of resources, capped at `max_stored_bytes`. We
                         // are still maintaining the lock here to avoid issues with other
                         // low-priority requestors.
-                        resources.available += ((elapsed.as_nanos()
-                            * self.data.resources_per_second as u128)
+                        resources.available += ((elapsed.as_nanos() * resources_per_second as u128)
                             / 1_000_000_000) as i64;
                         resources.available = resources.available.min(max_stored_resource as i64);
 
                         // If we do not have enough resources available, sleep until we do.
                         if resources.available < 0 {
                             let estimated_time_remaining = Duration::from_millis(
-                                (-resources.available) as u64 * 1000
-                                    / self.data.resources_per_second as u64,
+                                (-resources.available) as u64 * 1000 / resources_per_second as u64,
                             );
 
                             // Note: This sleep call is the reason we are using a tokio mutex
@@ -402,6 +425,30 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn limit_can_be_changed() {
+        let mut rng = crate::new_rng();
+
+        let limiter = ClassBasedLimiter::new(1_000, new_wait_time_sec());
+
+        let mut active_validators = HashSet::new();
+        active_validators.insert(PublicKey::random(&mut rng));
+        limiter.update_validators(active_validators, HashSet::new());
+
+        let handle = limiter.create_handle(NodeId::random(&mut rng), None);
+
+        // Lifting the limit lets requests through immediately.
+        assert!(limiter.set_resources_per_second(0));
+        let start = Instant::now();
+        handle.request_allowance(u32::MAX).await;
+        handle.request_allowance(1).await;
+        assert!(Instant::now() - start < SHORT_TIME);
+
+        // An unlimited limiter can only stay unlimited.
+        assert!(Unlimited.set_resources_per_second(0));
+        assert!(!Unlimited.set_resources_per_second(1_000));
+    }
+
     #[tokio::test]
     async fn nonvalidators_parallel_limited() {
         let mut rng = crate::new_rng();
@@ -4,6 +4,7 @@ use std::{
     sync::Arc,
 };
 
+use bincode::Options;
 use casper_hashing::Digest;
 #[cfg(test)]
 use casper_types::testing::TestRng;
@@ -15,9 +16,13 @@ use serde::{
     Deserialize, Deserializer, Serialize, Serializer,
 };
 
-use crate::{effect::EffectBuilder, types::NodeId, utils::opt_display::OptDisplay};
+use crate::{
+    effect::EffectBuilder,
+    types::{NodeFeatures, NodeId},
+    utils::opt_display::OptDisplay,
+};
 
-use super::counting_format::ConnectionId;
+use super::{counting_format::ConnectionId, identity_linkage::IdentityLinkage, BincodeFormat};
 
 /// The default protocol version to use in absence of one in the protocol version field.
 #[inline]
@@ -45,8 +50,27 @@ pub(crate) enum Message<P> {
         /// Hash of the chainspec the node is running.
         #[serde(default)]
         chainspec_hash: Option<Digest>,
+        /// The optional features enabled on the node.
+        #[serde(default)]
+        features: Option<NodeFeatures>,
+        /// True if the node accepts payloads wrapped in envelopes.
+        #[serde(default)]
+        accepts_envelopes: bool,
+        /// A linkage to the node's identity before its most recent key rotation.
+        #[serde(default)]
+        identity_linkage: Option<IdentityLinkage>,
     },
     Payload(P),
+    /// A payload wrapped in a versioned envelope, only sent to peers accepting envelopes.
+    ///
+    /// Envelopes are opened while decoding, so only envelopes holding payloads of an unknown type
+    /// or version are passed on as such.
+    Envelope(Envelope),
+    /// Notice that the sender is shutting down, so it should no longer be relied upon.
+    ///
+    /// Peers too old to know this message fail to decode it and close the connection, which has
+    /// much the same effect.
+    Goodbye,
 }
 
 impl<P: Payload> Message<P> {
@@ -56,6 +80,8 @@ impl<P: Payload> Message<P> {
         match self {
             Message::Handshake { .. } => MessageKind::Protocol,
             Message::Payload(payload) => payload.classify(),
+            Message::Envelope(_) => MessageKind::Other,
+            Message::Goodbye => MessageKind::Protocol,
         }
     }
 
@@ -63,16 +89,25 @@ impl<P: Payload> Message<P> {
     #[inline]
     pub(super) fn is_low_priority(&self) -> bool {
         match self {
-            Message::Handshake { .. } => false,
+            Message::Handshake { .. } | Message::Envelope(_) | Message::Goodbye => false,
             Message::Payload(payload) => payload.is_low_priority(),
         }
     }
 
+    /// Determines whether or not a message may be dropped when the node is under load.
+    #[inline]
+    pub(super) fn is_droppable(&self) -> bool {
+        match self {
+            Message::Handshake { .. } | Message::Envelope(_) | Message::Goodbye => false,
+            Message::Payload(payload) => payload.is_droppable(),
+        }
+    }
+
     /// Returns the incoming resource estimate of the payload.
     #[inline]
     pub(super) fn payload_incoming_resource_estimate(&self, weights: &EstimatorWeights) -> u32 {
         match self {
-            Message::Handshake { .. } => 0,
+            Message::Handshake { .. } | Message::Envelope(_) | Message::Goodbye => 0,
             Message::Payload(payload) => payload.incoming_resource_estimate(weights),
         }
     }
@@ -81,11 +116,20 @@ impl<P: Payload> Message<P> {
     #[inline]
     pub(super) fn payload_is_unsafe_for_syncing_nodes(&self) -> bool {
         match self {
-            Message::Handshake { .. } => false,
+            Message::Handshake { .. } | Message::Envelope(_) | Message::Goodbye => false,
             Message::Payload(payload) => payload.is_unsafe_for_syncing_peers(),
         }
     }
 
+    /// Returns whether or not the payload may only be sent to peers accepting envelopes.
+    #[inline]
+    pub(super) fn payload_requires_envelope(&self) -> bool {
+        match self {
+            Message::Handshake { .. } | Message::Envelope(_) | Message::Goodbye => false,
+            Message::Payload(payload) => payload.requires_envelope(),
+        }
+    }
+
     /// Attempts to create a demand-event from this message.
     ///
     /// Succeeds if the outer message contains a payload that can be converd into a demand.
@@ -98,7 +142,7 @@ impl<P: Payload> Message<P> {
         REv: FromIncoming<P> + Send,
     {
         match self {
-            Message::Handshake { .. } => Err(self),
+            Message::Handshake { .. } | Message::Envelope(_) | Message::Goodbye => Err(self),
             Message::Payload(payload) => {
                 // Note: For now, the wrapping/unwrapp of the payload is a bit unfortunate here.
                 REv::try_demand_from_incoming(effect_builder, sender, payload)
@@ -108,6 +152,59 @@ impl<P: Payload> Message<P> {
     }
 }
 
+/// A payload wrapped in a self-describing, versioned envelope.
+///
+/// Wrapping payloads allows new payload types, or new versions of existing ones, to be introduced
+/// without breaking older peers: envelopes holding a payload of a type or version unknown to the
+/// receiver are dropped, rather than failing to decode and closing the connection.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct Envelope {
+    /// The tag identifying the type of the payload.
+    tag: u16,
+    /// The version of the encoding of the payload.
+    version: u16,
+    /// The encoded payload.
+    bytes: Vec<u8>,
+}
+
+impl Envelope {
+    /// Wraps the given payload.
+    pub(super) fn seal<P: Payload>(payload: &P) -> Result<Self, bincode::Error> {
+        let tag = payload.envelope_tag();
+        // A payload's own tag is always known.
+        let version = P::envelope_version(tag).unwrap_or_default();
+        let bytes = BincodeFormat::default().0.serialize(payload)?;
+        Ok(Envelope {
+            tag,
+            version,
+            bytes,
+        })
+    }
+
+    /// Returns the payload, or `None` if its type or version is unknown.
+    pub(super) fn open<P: Payload>(&self) -> Result<Option<P>, bincode::Error> {
+        match P::envelope_version(self.tag) {
+            Some(version) if self.version <= version => BincodeFormat::default()
+                .0
+                .deserialize(&self.bytes)
+                .map(Some),
+            _ => Ok(None),
+        }
+    }
+}
+
+impl Display for Envelope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "envelope with tag {}, version {}, {} bytes",
+            self.tag,
+            self.version,
+            self.bytes.len()
+        )
+    }
+}
+
 /// A pair of secret keys used by consensus.
 pub(super) struct ConsensusKeyPair {
     secret_key: Arc<SecretKey>,
@@ -265,19 +362,27 @@ impl<P: Display> Display for Message<P> {
                 consensus_certificate,
                 is_syncing,
                 chainspec_hash,
+                features,
+                accepts_envelopes,
+                identity_linkage,
             } => {
                 write!(
                     f,
-                    "handshake: {}, public addr: {}, protocol_version: {}, consensus_certificate: {}, is_syncing: {}, chainspec_hash: {}",
+                    "handshake: {}, public addr: {}, protocol_version: {}, consensus_certificate: {}, is_syncing: {}, chainspec_hash: {}, features: {}, accepts_envelopes: {}, identity_linkage: {}",
                     network_name,
                     public_addr,
                     protocol_version,
                     OptDisplay::new(consensus_certificate.as_ref(), "none"),
                     is_syncing,
-                    OptDisplay::new(chainspec_hash.as_ref(), "none")
+                    OptDisplay::new(chainspec_hash.as_ref(), "none"),
+                    OptDisplay::new(features.as_ref(), "unknown"),
+                    accepts_envelopes,
+                    OptDisplay::new(identity_linkage.as_ref(), "none")
                 )
             }
             Message::Payload(payload) => write!(f, "payload: {}", payload),
+            Message::Envelope(envelope) => write!(f, "{}", envelope),
+            Message::Goodbye => f.write_str("goodbye"),
         }
     }
 }
@@ -339,10 +444,34 @@ pub(crate) trait Payload:
         false
     }
 
+    /// Determines if the payload may be dropped instead of being handled when the event queue it
+    /// is scheduled on is full.
+    fn is_droppable(&self) -> bool {
+        false
+    }
+
     /// Indicates a message is not safe to send to a syncing node.
     ///
     /// This functionality should be removed once multiplexed networking lands.
     fn is_unsafe_for_syncing_peers(&self) -> bool;
+
+    /// Indicates a payload which peers not accepting envelopes can't decode, as it was added after
+    /// envelopes were introduced.
+    ///
+    /// Such payloads are never sent to peers not accepting envelopes.
+    fn requires_envelope(&self) -> bool;
+
+    /// Returns the tag identifying the type of the payload when wrapped in an [`Envelope`].
+    ///
+    /// Tags must never be reused for a different type of payload.
+    fn envelope_tag(&self) -> u16;
+
+    /// Returns the version of the encoding of payloads with the given tag, or `None` if the tag is
+    /// unknown.
+    ///
+    /// The version must be incremented whenever the encoding of the tagged type changes in a way
+    /// older peers cannot decode.
+    fn envelope_version(tag: u16) -> Option<u16>;
 }
 
 /// Network message conversion support.
@@ -410,7 +539,9 @@ mod tests {
     use serde::{de::DeserializeOwned, Deserialize, Serialize};
     use tokio_serde::{Deserializer, Serializer};
 
-    use crate::{components::small_network::message_pack_format::MessagePackFormat, protocol};
+    use crate::{
+        components::small_network::message_pack_format::MessagePackFormat, protocol, types::Tag,
+    };
 
     use super::*;
 
@@ -562,6 +693,9 @@ mod tests {
             consensus_certificate: Some(ConsensusCertificate::random(&mut rng)),
             is_syncing: false,
             chainspec_hash: Some(Digest::hash("example-chainspec")),
+            features: Some(NodeFeatures::default()),
+            accepts_envelopes: true,
+            identity_linkage: None,
         };
 
         let legacy_handshake: V1_0_0_Message = roundtrip_message(&modern_handshake);
@@ -597,15 +731,21 @@ mod tests {
                 consensus_certificate,
                 is_syncing,
                 chainspec_hash,
+                features,
+                accepts_envelopes,
+                identity_linkage,
             } => {
                 assert_eq!(network_name, "example-handshake");
                 assert_eq!(public_addr, ([12, 34, 56, 78], 12346).into());
                 assert_eq!(protocol_version, ProtocolVersion::V1_0_0);
                 assert!(consensus_certificate.is_none());
                 assert!(!is_syncing);
-                assert!(chainspec_hash.is_none())
+                assert!(chainspec_hash.is_none());
+                assert!(features.is_none());
+                assert!(!accepts_envelopes);
+                assert!(identity_linkage.is_none());
             }
-            Message::Payload(_) => {
+            Message::Payload(_) | Message::Envelope(_) | Message::Goodbye => {
                 panic!("did not expect modern handshake to deserialize to payload")
             }
         }
@@ -623,6 +763,9 @@ mod tests {
                 consensus_certificate,
                 is_syncing,
                 chainspec_hash,
+                features,
+                accepts_envelopes,
+                identity_linkage,
             } => {
                 assert!(!is_syncing);
                 assert_eq!(network_name, "serialization-test");
@@ -630,9 +773,12 @@ mod tests {
                 assert_eq!(protocol_version, ProtocolVersion::V1_0_0);
                 assert!(consensus_certificate.is_none());
                 assert!(!is_syncing);
-                assert!(chainspec_hash.is_none())
+                assert!(chainspec_hash.is_none());
+                assert!(features.is_none());
+                assert!(!accepts_envelopes);
+                assert!(identity_linkage.is_none());
             }
-            Message::Payload(_) => {
+            Message::Payload(_) | Message::Envelope(_) | Message::Goodbye => {
                 panic!("did not expect modern handshake to deserialize to payload")
             }
         }
@@ -650,6 +796,9 @@ mod tests {
                 consensus_certificate,
                 is_syncing,
                 chainspec_hash,
+                features,
+                accepts_envelopes,
+                identity_linkage,
             } => {
                 assert_eq!(network_name, "example-handshake");
                 assert_eq!(public_addr, ([12, 34, 56, 78], 12346).into());
@@ -676,9 +825,12 @@ mod tests {
                     .unwrap()
                 );
                 assert!(!is_syncing);
-                assert!(chainspec_hash.is_none())
+                assert!(chainspec_hash.is_none());
+                assert!(features.is_none());
+                assert!(!accepts_envelopes);
+                assert!(identity_linkage.is_none());
             }
-            Message::Payload(_) => {
+            Message::Payload(_) | Message::Envelope(_) | Message::Goodbye => {
                 panic!("did not expect modern handshake to deserialize to payload")
             }
         }
@@ -696,6 +848,9 @@ mod tests {
                 consensus_certificate,
                 is_syncing,
                 chainspec_hash,
+                features,
+                accepts_envelopes,
+                identity_linkage,
             } => {
                 assert!(!is_syncing);
                 assert_eq!(network_name, "example-handshake");
@@ -722,9 +877,12 @@ mod tests {
                     .unwrap()
                 );
                 assert!(!is_syncing);
-                assert!(chainspec_hash.is_none())
+                assert!(chainspec_hash.is_none());
+                assert!(features.is_none());
+                assert!(!accepts_envelopes);
+                assert!(identity_linkage.is_none());
             }
-            Message::Payload(_) => {
+            Message::Payload(_) | Message::Envelope(_) | Message::Goodbye => {
                 panic!("did not expect modern handshake to deserialize to payload")
             }
         }
@@ -753,4 +911,43 @@ mod tests {
     fn bincode_roundtrip_certificate() {
         roundtrip_certificate(false)
     }
+
+    #[test]
+    fn envelopes_with_unknown_payloads_are_not_opened() {
+        let payload = protocol::Message::GetRequest {
+            tag: Tag::Deploy,
+            serialized_id: vec![1, 2, 3],
+        };
+        let envelope = Envelope::seal(&payload).unwrap();
+        match envelope.open::<protocol::Message>().unwrap() {
+            Some(protocol::Message::GetRequest { tag, serialized_id }) => {
+                assert_eq!(tag, Tag::Deploy);
+                assert_eq!(serialized_id, vec![1, 2, 3]);
+            }
+            _ => panic!("expected the sealed request"),
+        }
+
+        let unknown_tag = Envelope {
+            tag: u16::MAX,
+            ..envelope.clone()
+        };
+        assert!(unknown_tag.open::<protocol::Message>().unwrap().is_none());
+
+        let newer_version = Envelope {
+            version: envelope.version + 1,
+            ..envelope
+        };
+        assert!(newer_version.open::<protocol::Message>().unwrap().is_none());
+    }
+
+    #[test]
+    fn goodbye_roundtrips_as_protocol_message() {
+        let goodbye: Message<protocol::Message> = Message::Goodbye;
+        assert!(matches!(goodbye.classify(), MessageKind::Protocol));
+
+        let serialized = BincodeFormat::default().0.serialize(&goodbye).unwrap();
+        let deserialized: Message<protocol::Message> =
+            BincodeFormat::default().0.deserialize(&serialized).unwrap();
+        assert!(matches!(deserialized, Message::Goodbye));
+    }
 }
@@ -106,6 +106,8 @@ pub(super) struct Metrics {
     pub(super) in_count_trie_transfer: IntCounter,
     /// Count of incoming messages with other payload.
     pub(super) in_count_other: IntCounter,
+    /// Count of incoming envelopes with payloads of an unknown type or version.
+    pub(super) in_count_unknown: IntCounter,
 
     /// Number of trie requests accepted for processing.
     pub(super) requests_for_trie_accepted: IntCounter,
@@ -307,6 +309,11 @@ impl Metrics {
             "volume in bytes of incoming messages with other payload",
         )?;
 
+        let in_count_unknown = IntCounter::new(
+            "net_in_count_unknown",
+            "count of dropped incoming envelopes with payloads of an unknown type or version",
+        )?;
+
         let requests_for_trie_accepted = IntCounter::new(
             "requests_for_trie_accepted",
             "number of trie requests accepted for processing",
@@ -376,6 +383,7 @@ impl Metrics {
         registry.register(Box::new(in_bytes_block_transfer.clone()))?;
         registry.register(Box::new(in_bytes_trie_transfer.clone()))?;
         registry.register(Box::new(in_bytes_other.clone()))?;
+        registry.register(Box::new(in_count_unknown.clone()))?;
 
         registry.register(Box::new(requests_for_trie_accepted.clone()))?;
         registry.register(Box::new(requests_for_trie_finished.clone()))?;
@@ -430,6 +438,7 @@ impl Metrics {
             in_bytes_block_transfer,
             in_bytes_trie_transfer,
             in_bytes_other,
+            in_count_unknown,
             requests_for_trie_accepted,
             requests_for_trie_finished,
             accumulated_outgoing_limiter_delay,
@@ -613,6 +622,7 @@ impl Drop for Metrics {
         unregister_metric!(self.registry, self.in_bytes_block_transfer);
         unregister_metric!(self.registry, self.in_bytes_trie_transfer);
         unregister_metric!(self.registry, self.in_bytes_other);
+        unregister_metric!(self.registry, self.in_count_unknown);
 
         unregister_metric!(self.registry, self.requests_for_trie_accepted);
         unregister_metric!(self.registry, self.requests_for_trie_finished);
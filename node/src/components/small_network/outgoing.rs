@@ -621,6 +621,39 @@ where
             })
     }
 
+    /// Reconnects to an address waiting for its next reconnection attempt immediately.
+    ///
+    /// Does nothing unless the address is waiting, i.e. if it is unknown, blocked, or already
+    /// connecting or connected.
+    pub(crate) fn expedite_addr(
+        &mut self,
+        addr: SocketAddr,
+        now: Instant,
+    ) -> Option<DialRequest<H>> {
+        let span = make_span(addr, self.outgoing.get(&addr));
+        span.clone().in_scope(move || {
+            match self.outgoing.get(&addr).map(|outgoing| &outgoing.state) {
+                Some(&OutgoingState::Waiting {
+                    failures_so_far, ..
+                }) => {
+                    info!("reconnecting to waiting address early");
+                    self.change_outgoing_state(
+                        addr,
+                        OutgoingState::Connecting {
+                            failures_so_far,
+                            since: now,
+                        },
+                    );
+                    Some(DialRequest::Dial { addr, span })
+                }
+                _ => {
+                    debug!("early reconnection ignored, address not waiting");
+                    None
+                }
+            }
+        })
+    }
+
     /// Performs housekeeping like reconnection or unblocking peers.
     ///
     /// This function must periodically be called. A good interval is every second.
@@ -1422,4 +1455,43 @@ mod tests {
         assert!(manager.perform_housekeeping(clock.now()).is_empty());
         assert!(manager.is_blocked(addr_a));
     }
+
+    #[test]
+    fn expediting_reconnects_waiting_addresses_only() {
+        init_logging();
+
+        let mut clock = TestClock::new();
+
+        let addr_a: SocketAddr = "1.2.3.4:1234".parse().unwrap();
+        let addr_b: SocketAddr = "5.6.7.8:5678".parse().unwrap();
+
+        let mut manager = OutgoingManager::<u32, TestDialerError>::new(test_config());
+
+        // Unknown addresses are not dialed.
+        assert!(manager.expedite_addr(addr_a, clock.now()).is_none());
+
+        // Neither are addresses that are still connecting.
+        assert!(dials(
+            addr_a,
+            &manager.learn_addr(addr_a, false, clock.now())
+        ));
+        assert!(manager.expedite_addr(addr_a, clock.now()).is_none());
+
+        // Once the connection attempt failed, the address is redialed without waiting.
+        assert!(manager
+            .handle_dial_outcome(DialOutcome::Failed {
+                addr: addr_a,
+                error: TestDialerError { id: 1 },
+                when: clock.now(),
+            })
+            .is_none());
+        clock.advance_time(10);
+        assert!(dials(addr_a, &manager.expedite_addr(addr_a, clock.now())));
+        assert!(manager.expedite_addr(addr_a, clock.now()).is_none());
+
+        // Blocked addresses stay blocked.
+        assert!(manager.block_addr(addr_b, clock.now()).is_none());
+        assert!(manager.expedite_addr(addr_b, clock.now()).is_none());
+        assert!(manager.is_blocked(addr_b));
+    }
 }
@@ -0,0 +1,233 @@
+//! Per-peer connection details.
+//!
+//! Collects what is learned about a connected peer over the lifetime of its connections, to be
+//! reported to operators debugging connectivity issues.
+
+use std::{
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
+
+use datasize::DataSize;
+use serde::Serialize;
+
+use casper_types::{ProtocolVersion, PublicKey, TimeDiff};
+
+use super::MessageKind;
+use crate::types::{NodeFeatures, NodeId};
+
+/// What we learned about a peer since first connecting to it.
+#[derive(DataSize, Debug)]
+pub(super) struct PeerStats {
+    /// When the first of the current connections to or from the peer was established.
+    connected_since: Instant,
+    /// The protocol version announced in the peer's most recent handshake.
+    protocol_version: ProtocolVersion,
+    /// The round-trip time measured during the peer's most recent handshake.
+    handshake_rtt: Duration,
+    /// The consensus key the peer identified with during its most recent handshake, if any.
+    consensus_key: Option<PublicKey>,
+    /// The public listening address of the peer.
+    public_addr: SocketAddr,
+    /// The features the peer advertised during its most recent handshake, if any.
+    features: Option<NodeFeatures>,
+    /// When the last message was received from the peer, and its kind.
+    #[data_size(skip)]
+    last_message: Option<(Instant, MessageKind)>,
+    /// The number of offenses the peer committed while connected.
+    offenses: u32,
+}
+
+impl PeerStats {
+    /// Creates the stats of a peer which has just completed its first handshake.
+    pub(super) fn new(
+        now: Instant,
+        protocol_version: ProtocolVersion,
+        handshake_rtt: Duration,
+        consensus_key: Option<PublicKey>,
+        public_addr: SocketAddr,
+        features: Option<NodeFeatures>,
+    ) -> Self {
+        PeerStats {
+            connected_since: now,
+            protocol_version,
+            handshake_rtt,
+            consensus_key,
+            public_addr,
+            features,
+            last_message: None,
+            offenses: 0,
+        }
+    }
+
+    /// Records a further completed handshake, e.g. when the peer connected back to us.
+    pub(super) fn record_handshake(
+        &mut self,
+        protocol_version: ProtocolVersion,
+        handshake_rtt: Duration,
+        consensus_key: Option<PublicKey>,
+        public_addr: SocketAddr,
+        features: Option<NodeFeatures>,
+    ) {
+        self.protocol_version = protocol_version;
+        self.handshake_rtt = handshake_rtt;
+        self.consensus_key = consensus_key;
+        self.public_addr = public_addr;
+        self.features = features;
+    }
+
+    /// Returns the consensus key the peer identified with, if any.
+    pub(super) fn consensus_key(&self) -> Option<&PublicKey> {
+        self.consensus_key.as_ref()
+    }
+
+    /// Returns the public listening address of the peer.
+    pub(super) fn public_addr(&self) -> SocketAddr {
+        self.public_addr
+    }
+
+    /// Returns the features the peer advertised, if any.
+    pub(super) fn features(&self) -> Option<NodeFeatures> {
+        self.features
+    }
+
+    /// Records a message received from the peer.
+    pub(super) fn record_message(&mut self, now: Instant, kind: MessageKind) {
+        self.last_message = Some((now, kind));
+    }
+
+    /// Records an offense committed by the peer.
+    pub(super) fn record_offense(&mut self) {
+        self.offenses = self.offenses.saturating_add(1);
+    }
+
+    /// Returns the peer's reputation score.
+    ///
+    /// The score starts at zero and decreases by one for every offense the peer committed.
+    pub(super) fn reputation(&self) -> i64 {
+        -i64::from(self.offenses)
+    }
+
+    /// Returns the details of the peer as reported to operators.
+    pub(super) fn to_details(
+        &self,
+        node_id: NodeId,
+        address: String,
+        direction: ConnectionDirection,
+        role: PeerRole,
+        now: Instant,
+    ) -> PeerDetails {
+        PeerDetails {
+            node_id,
+            address,
+            direction,
+            role,
+            protocol_version: self.protocol_version,
+            connected_for: now.saturating_duration_since(self.connected_since).into(),
+            handshake_rtt: self.handshake_rtt.into(),
+            last_message: self.last_message.map(|(received, kind)| LastMessage {
+                kind: kind.to_string(),
+                received_ago: now.saturating_duration_since(received).into(),
+            }),
+            reputation: Some(self.reputation()),
+        }
+    }
+}
+
+/// The directions in which a peer is connected.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ConnectionDirection {
+    /// The peer connected to us, but we have no connection to it.
+    Incoming,
+    /// We connected to the peer, but it has not connected back to us.
+    Outgoing,
+    /// The peer is connected in both directions.
+    Bidirectional,
+}
+
+/// The role of a peer in consensus, as far as known to us.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum PeerRole {
+    /// The peer is a validator of the active era.
+    ActiveValidator,
+    /// The peer is a validator of the upcoming era, but not of the active one.
+    UpcomingValidator,
+    /// The peer is not known to be a validator of the active or upcoming era.
+    NonValidator,
+}
+
+/// The last message received from a peer.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct LastMessage {
+    /// The kind of the message.
+    pub(crate) kind: String,
+    /// How long ago the message was received.
+    pub(crate) received_ago: TimeDiff,
+}
+
+/// The details of a connected peer.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct PeerDetails {
+    /// The peer's node ID.
+    pub(crate) node_id: NodeId,
+    /// The address of the peer's connection.
+    pub(crate) address: String,
+    /// The directions in which the peer is connected.
+    pub(crate) direction: ConnectionDirection,
+    /// The peer's role in consensus.
+    pub(crate) role: PeerRole,
+    /// The protocol version the peer is speaking.
+    pub(crate) protocol_version: ProtocolVersion,
+    /// How long the peer has been connected.
+    pub(crate) connected_for: TimeDiff,
+    /// The round-trip time measured during the peer's most recent handshake.
+    pub(crate) handshake_rtt: TimeDiff,
+    /// The last message received from the peer, if any.
+    pub(crate) last_message: Option<LastMessage>,
+    /// The peer's reputation score, unless redacted.
+    pub(crate) reputation: Option<i64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reputation_should_decrease_with_offenses() {
+        let mut rng = crate::new_rng();
+        let now = Instant::now();
+        let mut stats = PeerStats::new(
+            now,
+            ProtocolVersion::V1_0_0,
+            Duration::from_millis(20),
+            None,
+            "127.0.0.1:34553".parse().unwrap(),
+            None,
+        );
+        assert_eq!(stats.reputation(), 0);
+
+        stats.record_offense();
+        stats.record_offense();
+        assert_eq!(stats.reputation(), -2);
+
+        stats.record_message(now, MessageKind::Consensus);
+        let details = stats.to_details(
+            NodeId::random(&mut rng),
+            "127.0.0.1:34553".to_string(),
+            ConnectionDirection::Bidirectional,
+            PeerRole::NonValidator,
+            now + Duration::from_secs(3),
+        );
+        assert_eq!(details.reputation, Some(-2));
+        assert_eq!(details.connected_for, TimeDiff::from_seconds(3));
+        assert_eq!(
+            details.handshake_rtt,
+            TimeDiff::from(Duration::from_millis(20))
+        );
+        let last_message = details.last_message.expect("should have last message");
+        assert_eq!(last_message.kind, "consensus");
+        assert_eq!(last_message.received_ago, TimeDiff::from_seconds(3));
+    }
+}
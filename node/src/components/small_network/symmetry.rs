@@ -8,6 +8,8 @@ use std::{collections::BTreeSet, mem, net::SocketAddr, time::Instant};
 use datasize::DataSize;
 use tracing::{debug, warn};
 
+use super::peer_details::ConnectionDirection;
+
 /// Describes whether a connection is uni- or bi-directional.
 #[derive(DataSize, Debug)]
 pub(super) enum ConnectionSymmetry {
@@ -191,6 +193,24 @@ impl ConnectionSymmetry {
         }
     }
 
+    /// Removes all incoming connections, e.g. after they were closed on a network handover.
+    ///
+    /// Returns `false` if the `ConnectionSymmetry` should be removed after this.
+    pub(super) fn remove_all_incoming(&mut self, now: Instant) -> bool {
+        match self {
+            ConnectionSymmetry::IncomingOnly { .. } | ConnectionSymmetry::Gone => {
+                *self = ConnectionSymmetry::Gone;
+                false
+            }
+            ConnectionSymmetry::OutgoingOnly { .. } => true,
+            ConnectionSymmetry::Symmetric { .. } => {
+                *self = ConnectionSymmetry::OutgoingOnly { since: now };
+                debug!("removed all incoming connections, now outgoing only");
+                true
+            }
+        }
+    }
+
     /// Returns the set of incoming addresses, if any.
     pub(super) fn incoming_addrs(&self) -> Option<&BTreeSet<SocketAddr>> {
         match self {
@@ -199,6 +219,16 @@ impl ConnectionSymmetry {
             ConnectionSymmetry::OutgoingOnly { .. } | ConnectionSymmetry::Gone => None,
         }
     }
+
+    /// Returns the directions in which the peer is connected, if at all.
+    pub(super) fn direction(&self) -> Option<ConnectionDirection> {
+        match self {
+            ConnectionSymmetry::IncomingOnly { .. } => Some(ConnectionDirection::Incoming),
+            ConnectionSymmetry::OutgoingOnly { .. } => Some(ConnectionDirection::Outgoing),
+            ConnectionSymmetry::Symmetric { .. } => Some(ConnectionDirection::Bidirectional),
+            ConnectionSymmetry::Gone => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -302,4 +332,22 @@ mod tests {
         clock.advance(Duration::from_secs(120));
         assert!(should_be_reaped(&sym, clock.now(), max_time_asymmetric));
     }
+
+    #[test]
+    fn removing_all_incoming_keeps_outgoing() {
+        let clock = TestClock::new();
+        let peer_addr: SocketAddr = "1.2.3.4:1234".parse().unwrap();
+
+        let mut sym = ConnectionSymmetry::default();
+        sym.add_incoming(peer_addr, clock.now());
+        assert!(!sym.remove_all_incoming(clock.now()));
+        assert!(matches!(sym, ConnectionSymmetry::Gone));
+
+        let mut sym = ConnectionSymmetry::default();
+        sym.add_incoming(peer_addr, clock.now());
+        sym.mark_outgoing(clock.now());
+        assert!(sym.remove_all_incoming(clock.now()));
+        assert!(matches!(sym, ConnectionSymmetry::OutgoingOnly { .. }));
+        assert_eq!(sym.incoming_addrs(), None);
+    }
 }
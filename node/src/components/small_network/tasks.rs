@@ -10,7 +10,7 @@ use std::{
         atomic::{AtomicBool, Ordering},
         Arc, Weak,
     },
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use bincode::Options;
@@ -47,6 +47,7 @@ use super::{
     error::{ConnectionError, IoError},
     event::{IncomingConnection, OutgoingConnection},
     full_transport,
+    identity_linkage::IdentityLinkage,
     limiter::LimiterHandle,
     message::ConsensusKeyPair,
     message_pack_format::MessagePackFormat,
@@ -57,8 +58,8 @@ use crate::{
     effect::{requests::NetworkRequest, AutoClosingResponder, EffectBuilder},
     reactor::{EventQueueHandle, QueueKind},
     tls::{self, TlsCert, ValidationError},
-    types::NodeId,
-    utils::display_error,
+    types::{NodeFeatures, NodeId},
+    utils::{display_error, opt_display::OptDisplay},
 };
 
 /// An item on the internal outgoing message queue.
@@ -77,6 +78,16 @@ struct HandshakeOutcome {
     peer_consensus_public_key: Option<PublicKey>,
     /// Holds the information whether the remote node is syncing.
     is_peer_syncing: bool,
+    /// Whether the peer accepts payloads wrapped in envelopes.
+    peer_accepts_envelopes: bool,
+    /// The peer's previous [`NodeId`], if it sent a valid identity linkage.
+    peer_previous_id: Option<NodeId>,
+    /// The protocol version the peer is speaking.
+    peer_protocol_version: ProtocolVersion,
+    /// The features the peer advertised, if any.
+    peer_features: Option<NodeFeatures>,
+    /// The time between sending our handshake and receiving the peer's.
+    handshake_rtt: Duration,
 }
 
 /// Low-level TLS connection function.
@@ -153,12 +164,17 @@ where
     let framed_transport = framed_transport(transport, context.chain_info.maximum_net_message_size);
 
     // Negotiate the handshake, concluding the incoming connection process.
-    match negotiate_handshake::<P, _>(&context, framed_transport, connection_id).await {
+    match negotiate_handshake::<P, _>(&context, framed_transport, connection_id, peer_id).await {
         Ok(HandshakeOutcome {
             framed_transport,
             public_addr,
             peer_consensus_public_key,
             is_peer_syncing: is_syncing,
+            peer_accepts_envelopes,
+            peer_previous_id,
+            peer_protocol_version,
+            peer_features,
+            handshake_rtt,
         }) => {
             if let Some(ref public_key) = peer_consensus_public_key {
                 Span::current().record("validator_id", &field::display(public_key));
@@ -175,6 +191,7 @@ where
                 connection_id,
                 framed_transport,
                 Role::Dialer,
+                peer_accepts_envelopes,
             );
             let (sink, _stream) = full_transport.split();
 
@@ -184,6 +201,11 @@ where
                 peer_consensus_public_key,
                 sink,
                 is_syncing,
+                peer_accepts_envelopes,
+                previous_peer_id: peer_previous_id,
+                peer_protocol_version,
+                peer_features,
+                handshake_rtt,
             }
         }
         Err(error) => OutgoingConnection::Failed {
@@ -209,6 +231,8 @@ where
     pub(super) network_ca: Option<Arc<X509>>,
     /// Secret key associated with `our_cert`.
     pub(super) secret_key: Arc<PKey<Private>>,
+    /// Linkage to our previous identity, sent in every handshake if the identity was rotated.
+    pub(super) identity_linkage: Option<IdentityLinkage>,
     /// Weak reference to the networking metrics shared by all sender/receiver tasks.
     pub(super) net_metrics: Weak<Metrics>,
     /// Chain info extract from chainspec.
@@ -278,23 +302,30 @@ where
     let framed_transport = framed_transport(transport, context.chain_info.maximum_net_message_size);
 
     // Negotiate the handshake, concluding the incoming connection process.
-    match negotiate_handshake::<P, _>(&context, framed_transport, connection_id).await {
+    match negotiate_handshake::<P, _>(&context, framed_transport, connection_id, peer_id).await {
         Ok(HandshakeOutcome {
             framed_transport,
             public_addr,
             peer_consensus_public_key,
             is_peer_syncing: _,
+            peer_accepts_envelopes: _,
+            peer_previous_id,
+            peer_protocol_version,
+            peer_features,
+            handshake_rtt,
         }) => {
             if let Some(ref public_key) = peer_consensus_public_key {
                 Span::current().record("validator_id", &field::display(public_key));
             }
 
-            // Establish full transport and close the receiving end.
+            // Establish full transport and close the receiving end. Since we never send on it, the
+            // peer's acceptance of envelopes is irrelevant.
             let full_transport = full_transport::<P>(
                 context.net_metrics.clone(),
                 connection_id,
                 framed_transport,
                 Role::Listener,
+                false,
             );
 
             let (_sink, stream) = full_transport.split();
@@ -305,6 +336,10 @@ where
                 peer_id,
                 peer_consensus_public_key,
                 stream,
+                previous_peer_id: peer_previous_id,
+                peer_protocol_version,
+                peer_features,
+                handshake_rtt,
             }
         }
         Err(error) => IncomingConnection::Failed {
@@ -384,6 +419,7 @@ async fn negotiate_handshake<P, REv>(
     context: &NetworkContext<REv>,
     framed: FramedTransport,
     connection_id: ConnectionId,
+    peer_id: NodeId,
 ) -> Result<HandshakeOutcome, ConnectionError>
 where
     P: Payload,
@@ -396,6 +432,7 @@ where
         context.consensus_keys.as_ref(),
         connection_id,
         context.is_syncing.load(Ordering::SeqCst),
+        context.identity_linkage.as_ref(),
     );
 
     let serialized_handshake_message = Pin::new(&mut encoder)
@@ -407,6 +444,7 @@ where
     // regardless of the size of the outgoing handshake.
     let (mut sink, mut stream) = framed.split();
 
+    let handshake_started = Instant::now();
     let handshake_send = tokio::spawn(io_timeout(context.handshake_timeout.into(), async move {
         sink.send(serialized_handshake_message).await?;
         Ok(sink)
@@ -417,6 +455,7 @@ where
     let remote_message_raw = io_opt_timeout(context.handshake_timeout.into(), stream.next())
         .await
         .map_err(ConnectionError::HandshakeRecv)?;
+    let handshake_rtt = handshake_started.elapsed();
 
     // Ensure the handshake was sent correctly.
     let sink = handshake_send
@@ -435,9 +474,17 @@ where
         consensus_certificate,
         is_syncing,
         chainspec_hash,
+        features,
+        accepts_envelopes,
+        identity_linkage,
     } = remote_message
     {
-        debug!(%protocol_version, "handshake received");
+        debug!(
+            %protocol_version,
+            features = %OptDisplay::new(features.as_ref(), "unknown"),
+            accepts_envelopes,
+            "handshake received"
+        );
 
         // The handshake was valid, we can check the network name.
         if network_name != context.chain_info.network_name {
@@ -484,6 +531,14 @@ where
             })
             .transpose()?;
 
+        let peer_previous_id = identity_linkage
+            .map(|linkage| {
+                linkage
+                    .validate(peer_id)
+                    .map_err(ConnectionError::InvalidIdentityLinkage)
+            })
+            .transpose()?;
+
         let framed_transport = sink
             .reunite(stream)
             .map_err(|_| ConnectionError::FailedToReuniteHandshakeSinkAndStream)?;
@@ -493,6 +548,11 @@ where
             public_addr,
             peer_consensus_public_key,
             is_peer_syncing: is_syncing,
+            peer_accepts_envelopes: accepts_envelopes,
+            peer_previous_id,
+            peer_protocol_version: protocol_version,
+            peer_features: features,
+            handshake_rtt,
         })
     } else {
         // Received a non-handshake, this is an error.
@@ -671,18 +731,24 @@ where
                             } else {
                                 QueueKind::NetworkIncoming
                             };
+                            let is_droppable = msg.is_droppable();
 
-                            context
-                                .event_queue
-                                .schedule(
-                                    Event::IncomingMessage {
-                                        peer_id: Box::new(peer_id),
-                                        msg: Box::new(msg),
-                                        span: span.clone(),
-                                    },
-                                    queue_kind,
-                                )
-                                .await;
+                            let event = Event::IncomingMessage {
+                                peer_id: Box::new(peer_id),
+                                msg: Box::new(msg),
+                                span: span.clone(),
+                            };
+
+                            if is_droppable {
+                                // Shed load by dropping the message if the queue is full.
+                                if let Err(err) =
+                                    context.event_queue.try_schedule(event, queue_kind).await
+                                {
+                                    debug!(%queue_kind, err = display_error(&err), "dropping incoming message");
+                                }
+                            } else {
+                                context.event_queue.schedule(event, queue_kind).await;
+                            }
                         }
                     }
                 }
@@ -163,6 +163,24 @@ impl Payload for Message {
     fn is_unsafe_for_syncing_peers(&self) -> bool {
         false
     }
+
+    fn requires_envelope(&self) -> bool {
+        false
+    }
+
+    fn envelope_tag(&self) -> u16 {
+        match self {
+            Message::AddressGossiper(_) => 0,
+        }
+    }
+
+    fn envelope_version(tag: u16) -> Option<u16> {
+        if tag == 0 {
+            Some(1)
+        } else {
+            None
+        }
+    }
 }
 
 /// Test reactor.
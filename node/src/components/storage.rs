@@ -6,7 +6,9 @@
 //! * storing and loading blocks,
 //! * storing and loading deploys,
 //! * [temporary until refactored] holding `DeployMetadata` for each deploy,
-//! * keeping an index of blocks by height and
+//! * keeping an index of blocks by height,
+//! * keeping an index of deploys by the account which sent them,
+//! * keeping a snapshot of how validators performed during each era and
 //! * [unimplemented] managing disk usage by pruning blocks and deploys from storage.
 //!
 //! Any I/O performed by the component is done on the event handling thread, this is on purpose as
@@ -29,6 +31,13 @@
 //!
 //! The storage component itself is panic free and in general reports three classes of errors:
 //! Corruption, temporary resource exhaustion and potential bugs.
+//!
+//! Resource exhaustion which is expected to resolve itself shortly (e.g. running out of LMDB reader
+//! slots) is reported as a `RecoverableStorageError`. An event whose handling fails with such an
+//! error is deferred along with any events received after it, and retried in the order they were
+//! received after a delay, until the error persisted for too many attempts and is escalated. Any
+//! `FatalStorageError` results in a shutdown of the node, logging the event being handled along
+//! with a hint on how to proceed.
 
 pub(crate) mod disjoint_sequences;
 mod error;
@@ -41,29 +50,31 @@ mod tests;
 use std::collections::BTreeSet;
 use std::{
     borrow::Cow,
-    collections::{btree_map::Entry, BTreeMap, HashSet},
+    collections::{btree_map::Entry, BTreeMap, HashSet, VecDeque},
     convert::TryFrom,
-    fmt::{self, Display, Formatter},
+    fmt::{self, Debug, Display, Formatter},
     fs, mem,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use datasize::DataSize;
 use derive_more::From;
 use lmdb::{
-    Cursor, Database, DatabaseFlags, Environment, EnvironmentFlags, RwTransaction, Transaction,
-    WriteFlags,
+    Cursor, Database, DatabaseFlags, Environment, EnvironmentFlags, RoTransaction, RwTransaction,
+    Transaction, WriteFlags,
 };
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 use static_assertions::const_assert;
 #[cfg(test)]
 use tempfile::TempDir;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, warn, Level};
 
 use casper_hashing::Digest;
 use casper_types::{
+    account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
     EraId, ExecutionResult, ProtocolVersion, TimeDiff, Transfer, Transform,
 };
@@ -75,23 +86,25 @@ use crate::{
     effect::{
         incoming::{NetRequest, NetRequestIncoming},
         requests::{MarkBlockCompletedRequest, NetworkRequest, StateStoreRequest},
-        EffectBuilder, EffectExt, Effects,
+        EffectBuilder, EffectExt, Effects, Responder,
     },
     fatal,
     protocol::Message,
     reactor::ReactorEvent,
     types::{
-        AvailableBlockRange, Block, BlockAndDeploys, BlockBody, BlockHash, BlockHashAndHeight,
-        BlockHeader, BlockHeaderWithMetadata, BlockHeadersBatch, BlockHeadersBatchId,
-        BlockSignatures, BlockWithMetadata, Deploy, DeployHash, DeployMetadata, DeployMetadataExt,
-        DeployWithFinalizedApprovals, FinalizedApprovals, FinalizedApprovalsWithId, Item, NodeId,
+        AccountDeploy, AccountDeploys, Approval, AvailableBlockRange, BackfillBlockSignatures,
+        Block, BlockAndDeploys, BlockBody, BlockHash, BlockHashAndHeight, BlockHeader,
+        BlockHeaderWithMetadata, BlockHeadersBatch, BlockHeadersBatchId, BlockSignatures,
+        BlockWithMetadata, Deploy, DeployHash, DeployMetadata, DeployMetadataExt,
+        DeployWithApprovals, DeployWithFinalizedApprovals, EraValidatorPerformance,
+        FinalizedApprovals, FinalizedApprovalsWithId, Item, NodeId,
     },
     utils::{display_error, WithDir},
     NodeRng,
 };
 use disjoint_sequences::{DisjointSequences, Sequence};
-pub use error::FatalStorageError;
 use error::GetRequestError;
+pub use error::{FatalStorageError, RecoverableStorageError};
 use lmdb_ext::{LmdbExtError, TransactionExt, WriteTransactionExt};
 use object_pool::ObjectPool;
 
@@ -114,9 +127,20 @@ const DEFAULT_MAX_DEPLOY_METADATA_STORE_SIZE: usize = 300 * GIB;
 /// Default max state store size.
 const DEFAULT_MAX_STATE_STORE_SIZE: usize = 10 * GIB;
 /// Maximum number of allowed dbs.
-const MAX_DB_COUNT: u32 = 8;
+const MAX_DB_COUNT: u32 = 12;
+/// Maximum number of attempts made for an operation failing with a recoverable error.
+const MAX_RECOVERABLE_ATTEMPTS: u32 = 5;
+/// Base delay between attempts of an operation failing with a recoverable error.
+const RECOVERABLE_RETRY_DELAY: Duration = Duration::from_millis(10);
+/// Maximum number of events deferred while storage is temporarily unavailable.
+const MAX_DEFERRED_EVENTS: usize = 10_000;
 /// Key under which completed blocks are to be stored.
 const COMPLETED_BLOCKS_STORAGE_KEY: &[u8] = b"completed_blocks_disjoint_sequences";
+/// Key whose presence marks the account deploys database as populated from the deploy database.
+const ACCOUNT_DEPLOYS_INDEXED_STORAGE_KEY: &[u8] = b"account_deploys_indexed";
+/// Number of deploys indexed by account per transaction when populating the account deploys
+/// database.
+const ACCOUNT_DEPLOYS_INDEXING_BATCH_SIZE: usize = 10_000;
 
 /// OS-specific lmdb flags.
 #[cfg(not(target_os = "macos"))]
@@ -170,6 +194,23 @@ pub struct Storage {
     /// The finalized approvals database.
     #[data_size(skip)]
     finalized_approvals_db: Database,
+    /// The database of all approvals learned for each deploy.
+    ///
+    /// Only holds an entry for deploys for which approvals beyond the ones they were first stored
+    /// with have been learned.
+    #[data_size(skip)]
+    deploy_approvals_db: Database,
+    /// The database of deploys sent by each account.
+    ///
+    /// Keyed by account hash, holds one value per deploy, see `account_deploys_entry`.
+    #[data_size(skip)]
+    account_deploys_db: Database,
+    /// The database of how validators performed during each era, keyed by era ID.
+    #[data_size(skip)]
+    era_validator_performance_db: Database,
+    /// The database of events emitted by contracts, keyed by the hash of the deploy emitting them.
+    #[data_size(skip)]
+    contract_events_db: Database,
     /// A map of block height to block ID.
     block_height_index: BTreeMap<u64, BlockHash>,
     /// A map of era ID to switch block ID.
@@ -184,6 +225,13 @@ pub struct Storage {
     ///
     /// Keyed by serialized item ID, contains the serialized item.
     serialized_item_pool: ObjectPool<Box<[u8]>>,
+    /// Events which could not be handled yet as storage is temporarily unavailable, oldest first.
+    #[data_size(skip)]
+    deferred_events: VecDeque<Event>,
+    /// The number of consecutive attempts to handle events which failed with a recoverable error.
+    recoverable_failures: u32,
+    /// Whether handling the deferred events will be retried after a delay.
+    retry_scheduled: bool,
 }
 
 /// A storage component event.
@@ -201,6 +249,8 @@ pub(crate) enum Event {
     /// Block completion announcement.
     #[from]
     MarkBlockCompletedRequest(MarkBlockCompletedRequest),
+    /// Retry handling the events deferred while storage was temporarily unavailable.
+    RetryDeferredEvents,
 }
 
 impl Display for Event {
@@ -210,6 +260,7 @@ impl Display for Event {
             Event::NetRequestIncoming(incoming) => incoming.fmt(f),
             Event::StateStoreRequest(req) => req.fmt(f),
             Event::MarkBlockCompletedRequest(req) => req.fmt(f),
+            Event::RetryDeferredEvents => write!(f, "retry deferred events"),
         }
     }
 }
@@ -234,12 +285,161 @@ where
         _rng: &mut NodeRng,
         event: Self::Event,
     ) -> Effects<Self::Event> {
+        match event {
+            Event::RetryDeferredEvents => self.retry_scheduled = false,
+            _ if self.deferred_events.len() >= MAX_DEFERRED_EVENTS => {
+                return fatal!(
+                    effect_builder,
+                    "storage unavailable for too long, {} events deferred",
+                    self.deferred_events.len()
+                )
+                .ignore();
+            }
+            // Events are always handled in the order they were received, so any event received
+            // while others are deferred is deferred as well.
+            event => self.deferred_events.push_back(event),
+        }
+
+        let mut effects = Effects::new();
+        // While storage is unavailable, the deferred events are only retried once the delay
+        // elapsed.
+        if self.retry_scheduled {
+            return effects;
+        }
+        while let Some(event) = self.deferred_events.pop_front() {
+            let (event, error) = match self.handle_available_event(effect_builder, event) {
+                Ok(handled) => {
+                    self.recoverable_failures = 0;
+                    effects.extend(handled);
+                    continue;
+                }
+                Err(deferred) => deferred,
+            };
+            match self.defer_event(event, error) {
+                Ok(()) => {
+                    self.retry_scheduled = true;
+                    let delay = RECOVERABLE_RETRY_DELAY * self.recoverable_failures;
+                    effects.extend(
+                        effect_builder
+                            .set_timeout(delay)
+                            .event(|_| Event::RetryDeferredEvents),
+                    );
+                }
+                Err(err) => effects.extend(
+                    fatal!(
+                        effect_builder,
+                        "storage unavailable with {} events deferred: {} ({})",
+                        self.deferred_events.len(),
+                        err,
+                        err.remediation_hint()
+                    )
+                    .ignore(),
+                ),
+            }
+            break;
+        }
+        effects
+    }
+}
+
+/// An error handling a storage event.
+#[derive(Debug)]
+enum HandlingError {
+    /// Storage is temporarily unavailable, the event has not been handled.
+    Deferred {
+        /// The event to be handled once storage is available again.
+        event: Event,
+        /// The error which prevented handling the event.
+        error: RecoverableStorageError,
+    },
+    /// Storage is irrecoverably broken.
+    Fatal(FatalStorageError),
+}
+
+impl From<FatalStorageError> for HandlingError {
+    fn from(err: FatalStorageError) -> Self {
+        HandlingError::Fatal(err)
+    }
+}
+
+/// Responds with the outcome of a storage operation.
+///
+/// If the operation failed with a recoverable error, nothing is sent and the request rebuilt from
+/// the responder by `rebuild` is returned to be handled again later.
+fn respond_or_defer<T, R>(
+    responder: Responder<T>,
+    result: Result<T, FatalStorageError>,
+    rebuild: impl FnOnce(Responder<T>) -> R,
+) -> Result<Effects<Event>, HandlingError>
+where
+    T: Debug + Send + 'static,
+    R: Into<Event>,
+{
+    match result {
+        Ok(value) => Ok(responder.respond(value).ignore()),
+        Err(err) => match RecoverableStorageError::try_from(err) {
+            Ok(error) => Err(HandlingError::Deferred {
+                event: rebuild(responder).into(),
+                error,
+            }),
+            Err(err) => Err(HandlingError::Fatal(err)),
+        },
+    }
+}
+
+impl Storage {
+    /// Puts an event which could not be handled as storage is temporarily unavailable back in
+    /// front of the deferred events.
+    ///
+    /// Returns an error once storage has been unavailable for `MAX_RECOVERABLE_ATTEMPTS`
+    /// consecutive attempts.
+    fn defer_event(
+        &mut self,
+        event: Event,
+        error: RecoverableStorageError,
+    ) -> Result<(), FatalStorageError> {
+        self.deferred_events.push_front(event);
+        self.recoverable_failures += 1;
+        if self.recoverable_failures >= MAX_RECOVERABLE_ATTEMPTS {
+            return Err(FatalStorageError::RetriesExhausted {
+                attempts: self.recoverable_failures,
+                last_error: error,
+            });
+        }
+        warn!(
+            attempts = self.recoverable_failures,
+            err = display_error(&error),
+            "storage temporarily unavailable, deferring events"
+        );
+        Ok(())
+    }
+
+    /// Handles an event, returning it along with the cause if storage is temporarily unavailable.
+    fn handle_available_event<REv>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        event: Event,
+    ) -> Result<Effects<Event>, (Event, RecoverableStorageError)>
+    where
+        REv: ReactorEvent + From<NetworkRequest<Message>>,
+    {
+        // Only used to provide context should handling the event fail fatally, so it is only
+        // formatted if it would be logged.
+        let maybe_event_description = tracing::enabled!(Level::ERROR).then(|| event.to_string());
         let result = match event {
             Event::StorageRequest(req) => self.handle_storage_request::<REv>(req),
-            Event::NetRequestIncoming(ref incoming) => {
-                match self.handle_net_request_incoming::<REv>(effect_builder, incoming) {
+            Event::NetRequestIncoming(incoming) => {
+                match self.handle_net_request_incoming::<REv>(effect_builder, &incoming) {
                     Ok(effects) => Ok(effects),
-                    Err(GetRequestError::Fatal(fatal_error)) => Err(fatal_error),
+                    Err(GetRequestError::Fatal(fatal_error)) => {
+                        match RecoverableStorageError::try_from(fatal_error) {
+                            Ok(error) => Err(HandlingError::Deferred {
+                                event: Event::NetRequestIncoming(incoming),
+                                error,
+                            }),
+                            Err(fatal_error) => Err(HandlingError::Fatal(fatal_error)),
+                        }
+                    }
                     Err(ref other_err) => {
                         warn!(
                             sender=%incoming.sender,
@@ -257,16 +457,34 @@ where
                 self.handle_state_store_request::<REv>(effect_builder, req)
             }
             Event::MarkBlockCompletedRequest(req) => self.handle_mark_block_completed_request(req),
+            Event::RetryDeferredEvents => Ok(Effects::new()),
         };
 
-        // Any error is turned into a fatal effect, the component itself does not panic. Note that
-        // we are dropping a lot of responders this way, but since we are crashing with fatal
+        // Any fatal error is turned into a fatal effect, the component itself does not panic. Note
+        // that we are dropping a lot of responders this way, but since we are crashing with fatal
         // anyway, it should not matter.
         match result {
-            Ok(effects) => effects,
-            Err(err) => fatal!(effect_builder, "storage error: {}", err).ignore(),
+            Ok(effects) => Ok(effects),
+            Err(HandlingError::Deferred { event, error }) => Err((event, error)),
+            Err(HandlingError::Fatal(err)) => Ok(fatal!(
+                effect_builder,
+                "storage error while handling {}: {} ({})",
+                maybe_event_description.as_deref().unwrap_or("event"),
+                err,
+                err.remediation_hint()
+            )
+            .ignore()),
         }
     }
+
+    /// Runs `f` within a new read transaction.
+    fn with_read_txn<T, F>(&self, f: F) -> Result<T, FatalStorageError>
+    where
+        F: FnOnce(&mut RoTransaction) -> Result<T, FatalStorageError>,
+    {
+        let mut txn = self.env.begin_ro_txn()?;
+        f(&mut txn)
+    }
 }
 
 impl Storage {
@@ -326,7 +544,13 @@ impl Storage {
         let state_store_db = env.create_db(Some("state_store"), DatabaseFlags::empty())?;
         let finalized_approvals_db =
             env.create_db(Some("finalized_approvals"), DatabaseFlags::empty())?;
+        let deploy_approvals_db =
+            env.create_db(Some("deploy_approvals"), DatabaseFlags::empty())?;
         let block_body_db = env.create_db(Some("block_body"), DatabaseFlags::empty())?;
+        let account_deploys_db = env.create_db(Some("account_deploys"), DatabaseFlags::DUP_SORT)?;
+        let era_validator_performance_db =
+            env.create_db(Some("era_validator_performance"), DatabaseFlags::empty())?;
+        let contract_events_db = env.create_db(Some("contract_events"), DatabaseFlags::empty())?;
 
         // We now need to restore the block-height index. Log messages allow timing here.
         info!("indexing block store");
@@ -401,6 +625,7 @@ impl Storage {
 
         initialize_block_metadata_db(&env, &block_metadata_db, &deleted_block_hashes_raw)?;
         initialize_deploy_metadata_db(&env, &deploy_metadata_db, &deleted_deploy_hashes)?;
+        initialize_account_deploys_db(&env, &deploy_db, &account_deploys_db, &state_store_db)?;
 
         let mut component = Self {
             root,
@@ -413,12 +638,19 @@ impl Storage {
             transfer_db,
             state_store_db,
             finalized_approvals_db,
+            deploy_approvals_db,
+            account_deploys_db,
+            era_validator_performance_db,
+            contract_events_db,
             block_height_index,
             switch_block_era_id_index,
             deploy_hash_index,
             completed_blocks: Default::default(),
             enable_mem_deduplication: config.enable_mem_deduplication,
             serialized_item_pool: ObjectPool::new(config.mem_pool_prune_interval),
+            deferred_events: VecDeque::new(),
+            recoverable_failures: 0,
+            retry_scheduled: false,
         };
 
         match component.read_state_store(&Cow::Borrowed(COMPLETED_BLOCKS_STORAGE_KEY))? {
@@ -462,7 +694,7 @@ impl Storage {
         &self,
         _effect_builder: EffectBuilder<REv>,
         req: StateStoreRequest,
-    ) -> Result<Effects<Event>, FatalStorageError> {
+    ) -> Result<Effects<Event>, HandlingError> {
         // Incoming requests are fairly simple database write. Errors are handled one level above on
         // the call stack, so all we have to do is load or store a value.
         match req {
@@ -475,8 +707,11 @@ impl Storage {
                 Ok(responder.respond(()).ignore())
             }
             StateStoreRequest::Load { key, responder } => {
-                let bytes = self.read_state_store(&key)?;
-                Ok(responder.respond(bytes).ignore())
+                let result = self.read_state_store(&key);
+                respond_or_defer(responder, result, |responder| StateStoreRequest::Load {
+                    key,
+                    responder,
+                })
             }
         }
     }
@@ -521,6 +756,12 @@ impl Storage {
         &self.root
     }
 
+    /// Flushes the storage to disk.
+    pub(crate) fn flush(&self) -> Result<(), FatalStorageError> {
+        self.env.sync(true)?;
+        Ok(())
+    }
+
     fn handle_net_request_incoming<REv>(
         &mut self,
         effect_builder: EffectBuilder<REv>,
@@ -550,7 +791,7 @@ impl Storage {
         match incoming.message {
             NetRequest::Deploy(ref serialized_id) => {
                 let id = decode_item_id::<Deploy>(serialized_id)?;
-                let opt_item = self.get_deploy(id).map_err(FatalStorageError::from)?;
+                let opt_item = self.get_deploy(id)?;
 
                 Ok(self.update_pool_and_send(
                     effect_builder,
@@ -562,20 +803,17 @@ impl Storage {
             }
             NetRequest::FinalizedApprovals(ref serialized_id) => {
                 let id = decode_item_id::<FinalizedApprovalsWithId>(serialized_id)?;
-                let opt_item = self
-                    .env
-                    .begin_ro_txn()
-                    .map_err(Into::into)
-                    .and_then(|mut txn| {
-                        self.get_deploy_with_finalized_approvals(&mut txn, &id)
-                            .map_err(FatalStorageError::from)
-                    })?
-                    .map(|deploy| {
-                        FinalizedApprovalsWithId::new(
-                            id,
-                            FinalizedApprovals::new(deploy.into_naive().approvals().clone()),
-                        )
-                    });
+                let opt_item = {
+                    let mut txn = self.env.begin_ro_txn()?;
+                    self.get_deploy_with_finalized_approvals(&mut txn, &id)
+                        .map_err(FatalStorageError::from)?
+                        .map(|deploy| {
+                            FinalizedApprovalsWithId::new(
+                                id,
+                                FinalizedApprovals::new(deploy.into_naive().approvals().clone()),
+                            )
+                        })
+                };
 
                 Ok(self.update_pool_and_send(
                     effect_builder,
@@ -672,6 +910,21 @@ impl Storage {
 
                 let opt_item = self.read_block_signatures(&item_id)?;
 
+                Ok(self.update_pool_and_send(
+                    effect_builder,
+                    incoming.sender,
+                    serialized_id,
+                    item_id,
+                    opt_item,
+                )?)
+            }
+            NetRequest::BackfillFinalitySignatures(ref serialized_id) => {
+                let item_id = decode_item_id::<BackfillBlockSignatures>(serialized_id)?;
+
+                let opt_item = self
+                    .read_block_signatures(&item_id)?
+                    .map(BackfillBlockSignatures::new);
+
                 Ok(self.update_pool_and_send(
                     effect_builder,
                     incoming.sender,
@@ -687,10 +940,12 @@ impl Storage {
     fn handle_storage_request<REv>(
         &mut self,
         req: StorageRequest,
-    ) -> Result<Effects<Event>, FatalStorageError> {
+    ) -> Result<Effects<Event>, HandlingError> {
         // Note: Database IO is handled in a blocking fashion on purpose throughout this function.
         // The rationale is that long IO operations are very rare and cache misses frequent, so on
         // average the actual execution time will be very low.
+        //
+        // Reads are deferred if storage is temporarily unavailable, see `respond_or_defer`.
         Ok(match req {
             StorageRequest::PutBlock { block, responder } => {
                 responder.respond(self.write_block(&*block)?).ignore()
@@ -698,76 +953,101 @@ impl Storage {
             StorageRequest::GetBlock {
                 block_hash,
                 responder,
-            } => responder.respond(self.read_block(&block_hash)?).ignore(),
+            } => respond_or_defer(responder, self.read_block(&block_hash), |responder| {
+                StorageRequest::GetBlock {
+                    block_hash,
+                    responder,
+                }
+            })?,
             StorageRequest::GetHighestBlock { responder } => {
-                responder.respond(self.read_highest_block()?).ignore()
+                respond_or_defer(responder, self.read_highest_block(), |responder| {
+                    StorageRequest::GetHighestBlock { responder }
+                })?
             }
             StorageRequest::GetHighestBlockHeader { responder } => {
-                let mut txn = self.env.begin_ro_txn()?;
-                responder
-                    .respond(self.get_highest_block_header(&mut txn)?)
-                    .ignore()
+                let result = self.with_read_txn(|txn| self.get_highest_block_header(txn));
+                respond_or_defer(responder, result, |responder| {
+                    StorageRequest::GetHighestBlockHeader { responder }
+                })?
             }
             StorageRequest::GetSwitchBlockHeaderAtEraId { era_id, responder } => {
-                let mut txn = self.env.begin_ro_txn()?;
-                responder
-                    .respond(self.get_switch_block_header_by_era_id(&mut txn, era_id)?)
-                    .ignore()
+                let result =
+                    self.with_read_txn(|txn| self.get_switch_block_header_by_era_id(txn, era_id));
+                respond_or_defer(responder, result, |responder| {
+                    StorageRequest::GetSwitchBlockHeaderAtEraId { era_id, responder }
+                })?
             }
             StorageRequest::GetBlockHeaderForDeploy {
                 deploy_hash,
                 responder,
             } => {
-                let mut txn = self.env.begin_ro_txn()?;
-                responder
-                    .respond(self.get_block_header_by_deploy_hash(&mut txn, deploy_hash)?)
-                    .ignore()
+                let result = self
+                    .with_read_txn(|txn| self.get_block_header_by_deploy_hash(txn, deploy_hash));
+                respond_or_defer(responder, result, |responder| {
+                    StorageRequest::GetBlockHeaderForDeploy {
+                        deploy_hash,
+                        responder,
+                    }
+                })?
             }
             StorageRequest::GetBlockHeader {
                 block_hash,
                 only_from_available_block_range,
                 responder,
             } => {
-                let mut txn = self.env.begin_ro_txn()?;
-                responder
-                    .respond(self.get_single_block_header_restricted(
-                        &mut txn,
+                let result = self.with_read_txn(|txn| {
+                    self.get_single_block_header_restricted(
+                        txn,
                         &block_hash,
                         only_from_available_block_range,
-                    )?)
-                    .ignore()
+                    )
+                });
+                respond_or_defer(responder, result, |responder| {
+                    StorageRequest::GetBlockHeader {
+                        block_hash,
+                        only_from_available_block_range,
+                        responder,
+                    }
+                })?
             }
             StorageRequest::CheckBlockHeaderExistence {
                 block_height,
                 responder,
             } => responder
-                .respond(self.block_header_exists(block_height))
+                .respond(self.block_header_exists(block_height.value()))
                 .ignore(),
             StorageRequest::GetBlockTransfers {
                 block_hash,
                 responder,
             } => {
-                let mut txn = self.env.begin_ro_txn()?;
-                responder
-                    .respond(self.get_transfers(&mut txn, &block_hash)?)
-                    .ignore()
+                let result = self.with_read_txn(|txn| self.get_transfers(txn, &block_hash));
+                respond_or_defer(responder, result, |responder| {
+                    StorageRequest::GetBlockTransfers {
+                        block_hash,
+                        responder,
+                    }
+                })?
             }
             StorageRequest::PutDeploy { deploy, responder } => {
                 responder.respond(self.put_deploy(&*deploy)?).ignore()
             }
+            StorageRequest::PutDeployApprovals {
+                deploy_approvals,
+                responder,
+            } => responder
+                .respond(self.put_deploy_approvals(&*deploy_approvals)?)
+                .ignore(),
             StorageRequest::GetDeploys {
                 deploy_hashes,
                 responder,
             } => {
-                let mut txn = self.env.begin_ro_txn()?;
-                responder
-                    .respond(
-                        self.get_deploys_with_finalized_approvals(
-                            &mut txn,
-                            deploy_hashes.as_slice(),
-                        )?,
-                    )
-                    .ignore()
+                let result = self.with_read_txn(|txn| {
+                    self.get_deploys_with_finalized_approvals(txn, deploy_hashes.as_slice())
+                });
+                respond_or_defer(responder, result, |responder| StorageRequest::GetDeploys {
+                    deploy_hashes,
+                    responder,
+                })?
             }
             StorageRequest::PutExecutionResults {
                 block_hash,
@@ -828,201 +1108,327 @@ impl Storage {
                 txn.commit()?;
                 responder.respond(()).ignore()
             }
-            StorageRequest::GetDeployAndMetadata {
+            StorageRequest::PutContractEvents {
+                contract_events,
+                responder,
+            } => {
+                let mut txn = self.env.begin_rw_txn()?;
+                for (deploy_hash, events) in contract_events {
+                    let _ = txn.put_value(self.contract_events_db, &deploy_hash, &events, true)?;
+                }
+                txn.commit()?;
+                responder.respond(()).ignore()
+            }
+            StorageRequest::GetContractEvents {
                 deploy_hash,
                 responder,
             } => {
-                let mut txn = self.env.begin_ro_txn()?;
-
-                let deploy = {
-                    let opt_deploy =
-                        self.get_deploy_with_finalized_approvals(&mut txn, &deploy_hash)?;
-
-                    if let Some(deploy) = opt_deploy {
-                        deploy
-                    } else {
-                        return Ok(responder.respond(None).ignore());
+                let result = self
+                    .with_read_txn(|txn| Ok(txn.get_value(self.contract_events_db, &deploy_hash)?));
+                respond_or_defer(responder, result, |responder| {
+                    StorageRequest::GetContractEvents {
+                        deploy_hash,
+                        responder,
                     }
-                };
+                })?
+            }
+            StorageRequest::GetDeployAndMetadata {
+                deploy_hash,
+                responder,
+            } => {
+                let result = self.with_read_txn(|txn| {
+                    let deploy = {
+                        let opt_deploy =
+                            self.get_deploy_with_finalized_approvals(txn, &deploy_hash)?;
 
-                // Missing metadata is filled using a default.
-                let metadata_ext: DeployMetadataExt =
-                    if let Some(metadata) = self.get_deploy_metadata(&mut txn, &deploy_hash)? {
-                        metadata.into()
-                    } else if let Some(block_hash_and_height) =
-                        self.get_block_hash_and_height_by_deploy_hash(deploy_hash)?
-                    {
-                        block_hash_and_height.into()
-                    } else {
-                        DeployMetadataExt::Empty
+                        if let Some(deploy) = opt_deploy {
+                            deploy
+                        } else {
+                            return Ok(None);
+                        }
                     };
 
-                responder.respond(Some((deploy, metadata_ext))).ignore()
+                    // Missing metadata is filled using a default.
+                    let metadata_ext: DeployMetadataExt =
+                        if let Some(metadata) = self.get_deploy_metadata(txn, &deploy_hash)? {
+                            metadata.into()
+                        } else if let Some(block_hash_and_height) =
+                            self.get_block_hash_and_height_by_deploy_hash(deploy_hash)?
+                        {
+                            block_hash_and_height.into()
+                        } else {
+                            DeployMetadataExt::Empty
+                        };
+
+                    Ok(Some((deploy, metadata_ext)))
+                });
+                respond_or_defer(responder, result, |responder| {
+                    StorageRequest::GetDeployAndMetadata {
+                        deploy_hash,
+                        responder,
+                    }
+                })?
+            }
+            StorageRequest::GetBlockDeploysWithExecutionResults {
+                block_hash,
+                deploy_hashes,
+                responder,
+            } => {
+                let result = self.with_read_txn(|txn| {
+                    self.get_block_deploys_with_execution_results(txn, &block_hash, &deploy_hashes)
+                });
+                respond_or_defer(responder, result, |responder| {
+                    StorageRequest::GetBlockDeploysWithExecutionResults {
+                        block_hash,
+                        deploy_hashes,
+                        responder,
+                    }
+                })?
+            }
+            StorageRequest::GetAccountDeploys {
+                account_hash,
+                after,
+                limit,
+                responder,
+            } => {
+                let result = self.with_read_txn(|txn| {
+                    self.get_account_deploys(txn, &account_hash, after.as_deref(), limit)
+                });
+                respond_or_defer(responder, result, |responder| {
+                    StorageRequest::GetAccountDeploys {
+                        account_hash,
+                        after,
+                        limit,
+                        responder,
+                    }
+                })?
+            }
+            StorageRequest::PutEraMissedRounds {
+                era_id,
+                missed_rounds,
+                responder,
+            } => {
+                let mut txn = self.env.begin_rw_txn()?;
+                let key = era_validator_performance_key(era_id);
+                let mut performance: EraValidatorPerformance = txn
+                    .get_value(self.era_validator_performance_db, &key)?
+                    .unwrap_or_default();
+                for (public_key, count) in missed_rounds {
+                    performance
+                        .validators
+                        .entry(public_key)
+                        .or_default()
+                        .rounds_missed = Some(count);
+                }
+                let _ =
+                    txn.put_value(self.era_validator_performance_db, &key, &performance, true)?;
+                txn.commit()?;
+                responder.respond(()).ignore()
+            }
+            StorageRequest::GetEraValidatorPerformance { era_id, responder } => {
+                let result = self.with_read_txn(|txn| {
+                    Ok(txn.get_value(
+                        self.era_validator_performance_db,
+                        &era_validator_performance_key(era_id),
+                    )?)
+                });
+                respond_or_defer(responder, result, |responder| {
+                    StorageRequest::GetEraValidatorPerformance { era_id, responder }
+                })?
             }
             StorageRequest::GetBlockAndMetadataByHash {
                 block_hash,
                 only_from_available_block_range,
                 responder,
             } => {
-                let mut txn = self.env.begin_ro_txn()?;
-
-                let block: Block =
-                    if let Some(block) = self.get_single_block(&mut txn, &block_hash)? {
-                        block
-                    } else {
-                        return Ok(responder.respond(None).ignore());
-                    };
+                let result = self.with_read_txn(|txn| {
+                    let block: Block =
+                        if let Some(block) = self.get_single_block(txn, &block_hash)? {
+                            block
+                        } else {
+                            return Ok(None);
+                        };
 
-                if !(self.should_return_block(block.height(), only_from_available_block_range)?) {
-                    return Ok(responder.respond(None).ignore());
-                }
+                    if !(self
+                        .should_return_block(block.height(), only_from_available_block_range)?)
+                    {
+                        return Ok(None);
+                    }
 
-                // Check that the hash of the block retrieved is correct.
-                if block_hash != *block.hash() {
-                    error!(
-                        queried_block_hash = ?block_hash,
-                        actual_block_hash = ?block.hash(),
-                        "block not stored under hash"
-                    );
-                    debug_assert_eq!(&block_hash, block.hash());
-                    return Ok(responder.respond(None).ignore());
-                }
-                let block_signatures = match self.get_block_signatures(&mut txn, &block_hash)? {
-                    Some(signatures) => signatures,
-                    None => BlockSignatures::new(block_hash, block.header().era_id()),
-                };
-                if block_signatures.verify().is_err() {
-                    error!(?block, "invalid block signatures for block");
-                    debug_assert!(block_signatures.verify().is_ok());
-                    return Ok(responder.respond(None).ignore());
-                }
-                responder
-                    .respond(Some(BlockWithMetadata {
+                    // Check that the hash of the block retrieved is correct.
+                    if block_hash != *block.hash() {
+                        error!(
+                            queried_block_hash = ?block_hash,
+                            actual_block_hash = ?block.hash(),
+                            "block not stored under hash"
+                        );
+                        debug_assert_eq!(&block_hash, block.hash());
+                        return Ok(None);
+                    }
+                    let block_signatures = match self.get_block_signatures(txn, &block_hash)? {
+                        Some(signatures) => signatures,
+                        None => BlockSignatures::new(block_hash, block.header().era_id()),
+                    };
+                    if block_signatures.verify().is_err() {
+                        error!(?block, "invalid block signatures for block");
+                        debug_assert!(block_signatures.verify().is_ok());
+                        return Ok(None);
+                    }
+                    Ok(Some(BlockWithMetadata {
                         block,
                         block_signatures,
                     }))
-                    .ignore()
+                });
+                respond_or_defer(responder, result, |responder| {
+                    StorageRequest::GetBlockAndMetadataByHash {
+                        block_hash,
+                        only_from_available_block_range,
+                        responder,
+                    }
+                })?
             }
             StorageRequest::GetBlockHeaderAndMetadataByHash {
                 block_hash,
                 only_from_available_block_range,
                 responder,
             } => {
-                let mut txn = self.env.begin_ro_txn()?;
-
-                let block_header: BlockHeader = {
-                    if let Some(block_header) = self.get_single_block_header_restricted(
-                        &mut txn,
-                        &block_hash,
-                        only_from_available_block_range,
-                    )? {
-                        block_header
-                    } else {
-                        return Ok(responder.respond(None).ignore());
-                    }
-                };
-                let block_signatures = match self.get_block_signatures(&mut txn, &block_hash)? {
-                    Some(signatures) => signatures,
-                    None => BlockSignatures::new(block_hash, block_header.era_id()),
-                };
-                responder
-                    .respond(Some(BlockHeaderWithMetadata {
+                let result = self.with_read_txn(|txn| {
+                    let block_header: BlockHeader = {
+                        if let Some(block_header) = self.get_single_block_header_restricted(
+                            txn,
+                            &block_hash,
+                            only_from_available_block_range,
+                        )? {
+                            block_header
+                        } else {
+                            return Ok(None);
+                        }
+                    };
+                    let block_signatures = match self.get_block_signatures(txn, &block_hash)? {
+                        Some(signatures) => signatures,
+                        None => BlockSignatures::new(block_hash, block_header.era_id()),
+                    };
+                    Ok(Some(BlockHeaderWithMetadata {
                         block_header,
                         block_signatures,
                     }))
-                    .ignore()
+                });
+                respond_or_defer(responder, result, |responder| {
+                    StorageRequest::GetBlockHeaderAndMetadataByHash {
+                        block_hash,
+                        only_from_available_block_range,
+                        responder,
+                    }
+                })?
             }
             StorageRequest::GetBlockAndMetadataByHeight {
                 block_height,
                 only_from_available_block_range,
                 responder,
             } => {
-                if !(self.should_return_block(block_height, only_from_available_block_range)?) {
-                    return Ok(responder.respond(None).ignore());
-                }
-
-                let mut txn = self.env.begin_ro_txn()?;
-
-                let block: Block = {
-                    if let Some(block) = self.get_block_by_height(&mut txn, block_height)? {
-                        block
-                    } else {
-                        return Ok(responder.respond(None).ignore());
+                let result = self.with_read_txn(|txn| {
+                    if !(self.should_return_block(
+                        block_height.value(),
+                        only_from_available_block_range,
+                    )?) {
+                        return Ok(None);
                     }
-                };
 
-                let hash = block.hash();
-                let block_signatures = match self.get_block_signatures(&mut txn, hash)? {
-                    Some(signatures) => signatures,
-                    None => BlockSignatures::new(*hash, block.header().era_id()),
-                };
-                responder
-                    .respond(Some(BlockWithMetadata {
+                    let block: Block = {
+                        if let Some(block) = self.get_block_by_height(txn, block_height.value())? {
+                            block
+                        } else {
+                            return Ok(None);
+                        }
+                    };
+
+                    let hash = block.hash();
+                    let block_signatures = match self.get_block_signatures(txn, hash)? {
+                        Some(signatures) => signatures,
+                        None => BlockSignatures::new(*hash, block.header().era_id()),
+                    };
+                    Ok(Some(BlockWithMetadata {
                         block,
                         block_signatures,
                     }))
-                    .ignore()
+                });
+                respond_or_defer(responder, result, |responder| {
+                    StorageRequest::GetBlockAndMetadataByHeight {
+                        block_height,
+                        only_from_available_block_range,
+                        responder,
+                    }
+                })?
             }
             StorageRequest::GetBlockHeaderAndMetadataByHeight {
                 block_height,
                 only_from_available_block_range,
                 responder,
             } => {
-                if !(self.should_return_block(block_height, only_from_available_block_range)?) {
-                    return Ok(responder.respond(None).ignore());
-                }
-
-                let mut txn = self.env.begin_ro_txn()?;
-
-                let block_header = {
-                    if let Some(block_header) =
-                        self.get_block_header_by_height(&mut txn, block_height)?
-                    {
-                        block_header
-                    } else {
-                        return Ok(responder.respond(None).ignore());
+                let result = self.with_read_txn(|txn| {
+                    if !(self.should_return_block(
+                        block_height.value(),
+                        only_from_available_block_range,
+                    )?) {
+                        return Ok(None);
                     }
-                };
 
-                let hash = block_header.hash();
-                let block_signatures = match self.get_block_signatures(&mut txn, &hash)? {
-                    Some(signatures) => signatures,
-                    None => BlockSignatures::new(hash, block_header.era_id()),
-                };
-                responder
-                    .respond(Some(BlockHeaderWithMetadata {
+                    let block_header = {
+                        if let Some(block_header) =
+                            self.get_block_header_by_height(txn, block_height.value())?
+                        {
+                            block_header
+                        } else {
+                            return Ok(None);
+                        }
+                    };
+
+                    let hash = block_header.hash();
+                    let block_signatures = match self.get_block_signatures(txn, &hash)? {
+                        Some(signatures) => signatures,
+                        None => BlockSignatures::new(hash, block_header.era_id()),
+                    };
+                    Ok(Some(BlockHeaderWithMetadata {
                         block_header,
                         block_signatures,
                     }))
-                    .ignore()
+                });
+                respond_or_defer(responder, result, |responder| {
+                    StorageRequest::GetBlockHeaderAndMetadataByHeight {
+                        block_height,
+                        only_from_available_block_range,
+                        responder,
+                    }
+                })?
             }
             StorageRequest::GetHighestBlockWithMetadata { responder } => {
-                let mut txn = self.env.begin_ro_txn()?;
-
-                let highest_block: Block = {
-                    if let Some(block) = self
-                        .block_height_index
-                        .keys()
-                        .last()
-                        .and_then(|&height| self.get_block_by_height(&mut txn, height).transpose())
-                        .transpose()?
-                    {
-                        block
-                    } else {
-                        return Ok(responder.respond(None).ignore());
-                    }
-                };
-                let hash = highest_block.hash();
-                let block_signatures = match self.get_block_signatures(&mut txn, hash)? {
-                    Some(signatures) => signatures,
-                    None => BlockSignatures::new(*hash, highest_block.header().era_id()),
-                };
-                responder
-                    .respond(Some(BlockWithMetadata {
+                let result = self.with_read_txn(|txn| {
+                    let highest_block: Block = {
+                        if let Some(block) = self
+                            .block_height_index
+                            .keys()
+                            .last()
+                            .and_then(|&height| self.get_block_by_height(txn, height).transpose())
+                            .transpose()?
+                        {
+                            block
+                        } else {
+                            return Ok(None);
+                        }
+                    };
+                    let hash = highest_block.hash();
+                    let block_signatures = match self.get_block_signatures(txn, hash)? {
+                        Some(signatures) => signatures,
+                        None => BlockSignatures::new(*hash, highest_block.header().era_id()),
+                    };
+                    Ok(Some(BlockWithMetadata {
                         block: highest_block,
                         block_signatures,
                     }))
-                    .ignore()
+                });
+                respond_or_defer(responder, result, |responder| {
+                    StorageRequest::GetHighestBlockWithMetadata { responder }
+                })?
             }
             StorageRequest::PutBlockSignatures {
                 signatures,
@@ -1060,26 +1466,38 @@ impl Storage {
                 block_hash,
                 responder,
             } => {
-                let mut txn = self.env.begin_ro_txn()?;
-                responder
-                    .respond(self.get_block_signatures(&mut txn, &block_hash)?)
-                    .ignore()
+                let result = self.with_read_txn(|txn| self.get_block_signatures(txn, &block_hash));
+                respond_or_defer(responder, result, |responder| {
+                    StorageRequest::GetBlockSignatures {
+                        block_hash,
+                        responder,
+                    }
+                })?
             }
             StorageRequest::GetFinalizedBlocks { ttl, responder } => {
-                responder.respond(self.get_finalized_blocks(ttl)?).ignore()
+                respond_or_defer(responder, self.get_finalized_blocks(ttl), |responder| {
+                    StorageRequest::GetFinalizedBlocks { ttl, responder }
+                })?
             }
             StorageRequest::GetBlockHeaderByHeight {
                 block_height,
                 only_from_available_block_range,
                 responder,
             } => {
-                let mut txn = self.env.begin_ro_txn()?;
-                let result = self.get_block_header_by_height_restricted(
-                    &mut txn,
-                    block_height,
-                    only_from_available_block_range,
-                )?;
-                responder.respond(result).ignore()
+                let result = self.with_read_txn(|txn| {
+                    self.get_block_header_by_height_restricted(
+                        txn,
+                        block_height.value(),
+                        only_from_available_block_range,
+                    )
+                });
+                respond_or_defer(responder, result, |responder| {
+                    StorageRequest::GetBlockHeaderByHeight {
+                        block_height,
+                        only_from_available_block_range,
+                        responder,
+                    }
+                })?
             }
             StorageRequest::PutBlockHeader {
                 block_header,
@@ -1090,7 +1508,7 @@ impl Storage {
                     Ok(result) => responder.respond(result).ignore(),
                     Err(err) => {
                         error!(?err, ?block_header_hash, "error when storing block header");
-                        return Err(err);
+                        return Err(err.into());
                     }
                 }
             }
@@ -1116,15 +1534,25 @@ impl Storage {
             StorageRequest::GetBlockAndDeploys {
                 block_hash,
                 responder,
-            } => responder
-                .respond(self.read_block_and_deploys_by_hash(block_hash)?)
-                .ignore(),
+            } => respond_or_defer(
+                responder,
+                self.read_block_and_deploys_by_hash(block_hash),
+                |responder| StorageRequest::GetBlockAndDeploys {
+                    block_hash,
+                    responder,
+                },
+            )?,
             StorageRequest::GetHeadersBatch {
                 block_headers_id,
                 responder,
-            } => responder
-                .respond(self.read_block_headers_batch(&block_headers_id)?)
-                .ignore(),
+            } => respond_or_defer(
+                responder,
+                self.read_block_headers_batch(&block_headers_id),
+                |responder| StorageRequest::GetHeadersBatch {
+                    block_headers_id,
+                    responder,
+                },
+            )?,
         })
     }
 
@@ -1135,11 +1563,37 @@ impl Storage {
             block_height,
             responder,
         }: MarkBlockCompletedRequest,
-    ) -> Result<Effects<Event>, FatalStorageError> {
-        self.completed_blocks.insert(block_height);
+    ) -> Result<Effects<Event>, HandlingError> {
+        let result = self.mark_block_completed(block_height.value());
+        respond_or_defer(responder, result, |responder| MarkBlockCompletedRequest {
+            block_height,
+            responder,
+        })
+    }
+
+    /// Marks the block at the given height as completed.
+    fn mark_block_completed(&mut self, height: u64) -> Result<(), FatalStorageError> {
+        self.completed_blocks.insert(height);
         self.persist_completed_blocks()?;
 
-        Ok(responder.respond(()).ignore())
+        // Once an era's switch block is complete, snapshot how validators performed during that
+        // era.  The previous era's snapshot is refreshed too, as finality signatures for its last
+        // blocks may only have arrived since.
+        let maybe_block_header =
+            self.with_read_txn(|txn| self.get_block_header_by_height(txn, height))?;
+        if let Some(block_header) = maybe_block_header {
+            if block_header.is_switch_block() {
+                let era_id = block_header.era_id();
+                let mut txn = self.env.begin_rw_txn()?;
+                self.put_era_validator_performance(&mut txn, era_id)?;
+                if let Some(previous_era_id) = era_id.checked_sub(1) {
+                    self.put_era_validator_performance(&mut txn, previous_era_id)?;
+                }
+                txn.commit()?;
+            }
+        }
+
+        Ok(())
     }
 
     /// Persists the completed blocks disjoint sequences state to the database.
@@ -1155,10 +1609,47 @@ impl Storage {
     pub fn put_deploy(&self, deploy: &Deploy) -> Result<bool, FatalStorageError> {
         let mut txn = self.env.begin_rw_txn()?;
         let outcome = txn.put_value(self.deploy_db, deploy.id(), deploy, false)?;
+        put_account_deploys_entry(&mut txn, self.account_deploys_db, deploy)?;
         txn.commit()?;
         Ok(outcome)
     }
 
+    /// Merges the given approvals into the ones known for the stored deploy.
+    ///
+    /// Returns the approvals which were not known before, which is empty if the deploy is not
+    /// stored.
+    pub fn put_deploy_approvals(
+        &self,
+        deploy_approvals: &DeployWithApprovals,
+    ) -> Result<BTreeSet<Approval>, FatalStorageError> {
+        let deploy_hash = deploy_approvals.deploy_hash();
+        let mut txn = self.env.begin_rw_txn()?;
+        let mut known_approvals: BTreeSet<Approval> =
+            match txn.get_value(self.deploy_approvals_db, deploy_hash)? {
+                Some(approvals) => approvals,
+                None => match txn.get_value::<_, Deploy>(self.deploy_db, deploy_hash)? {
+                    Some(deploy) => deploy.approvals().clone(),
+                    None => return Ok(BTreeSet::new()),
+                },
+            };
+        let new_approvals: BTreeSet<Approval> = deploy_approvals
+            .approvals()
+            .difference(&known_approvals)
+            .cloned()
+            .collect();
+        if !new_approvals.is_empty() {
+            known_approvals.extend(new_approvals.iter().cloned());
+            let _ = txn.put_value(
+                self.deploy_approvals_db,
+                deploy_hash,
+                &known_approvals,
+                true,
+            )?;
+            txn.commit()?;
+        }
+        Ok(new_approvals)
+    }
+
     /// Puts block and its deploys into storage.
     ///
     /// Returns `Ok` only if the block and all deploys were successfully written.
@@ -1170,6 +1661,7 @@ impl Storage {
 
         block.verify()?;
         let deploy_db = self.deploy_db;
+        let account_deploys_db = self.account_deploys_db;
         let (wrote, mut txn) = self.write_validated_block(block)?;
         if !wrote {
             return Err(FatalStorageError::FailedToOverwriteBlock);
@@ -1177,6 +1669,7 @@ impl Storage {
 
         for deploy in deploys {
             let _ = txn.put_value(deploy_db, deploy.id(), deploy, false)?;
+            put_account_deploys_entry(&mut txn, account_deploys_db, deploy)?;
         }
         txn.commit()?;
 
@@ -1275,10 +1768,7 @@ impl Storage {
         &self,
         height: u64,
     ) -> Result<Option<BlockWithMetadata>, FatalStorageError> {
-        let mut txn = self
-            .env
-            .begin_ro_txn()
-            .expect("could not create RO transaction");
+        let mut txn = self.env.begin_ro_txn()?;
         let block = if let Some(block) = self.get_block_by_height(&mut txn, height)? {
             block
         } else {
@@ -1305,10 +1795,7 @@ impl Storage {
         &self,
         height: u64,
     ) -> Result<Option<BlockHeaderWithMetadata>, FatalStorageError> {
-        let mut txn = self
-            .env
-            .begin_ro_txn()
-            .expect("could not create RO transaction");
+        let mut txn = self.env.begin_ro_txn()?;
         let block_header =
             if let Some(block_header) = self.get_block_header_by_height(&mut txn, height)? {
                 block_header
@@ -1684,6 +2171,168 @@ impl Storage {
         Ok(txn.get_value(self.deploy_metadata_db, deploy_hash)?)
     }
 
+    /// Retrieves the given deploys contained in the given block, with the approvals finalized in
+    /// the block, along with their execution results in that block, if known.
+    ///
+    /// Returns `None` if any of the deploys is not stored.
+    fn get_block_deploys_with_execution_results<Tx: Transaction>(
+        &self,
+        txn: &mut Tx,
+        block_hash: &BlockHash,
+        deploy_hashes: &[DeployHash],
+    ) -> Result<Option<Vec<(Deploy, Option<ExecutionResult>)>>, FatalStorageError> {
+        let mut deploys = Vec::with_capacity(deploy_hashes.len());
+        for deploy_hash in deploy_hashes {
+            let deploy = match self.get_deploy_with_finalized_approvals(txn, deploy_hash)? {
+                Some(deploy) => deploy.into_naive(),
+                None => return Ok(None),
+            };
+            let execution_result = self
+                .get_deploy_metadata(txn, deploy_hash)?
+                .and_then(|mut metadata| metadata.execution_results.remove(block_hash));
+            deploys.push((deploy, execution_result));
+        }
+        Ok(Some(deploys))
+    }
+
+    /// Retrieves up to `limit` of the deploys sent by the given account, most recent first.
+    ///
+    /// If `after` is given, only the deploys after this position are retrieved.  The position is
+    /// the deploy's entry in the account deploys database, so that pages stay consistent while
+    /// new deploys are stored.
+    fn get_account_deploys<Tx: Transaction>(
+        &self,
+        txn: &mut Tx,
+        account_hash: &AccountHash,
+        after: Option<&[u8]>,
+        limit: u32,
+    ) -> Result<AccountDeploys, FatalStorageError> {
+        let (deploy_hashes, next_page_after) = {
+            let mut cursor = txn.open_ro_cursor(self.account_deploys_db)?;
+            let iter = match cursor.iter_dup_of(account_hash) {
+                Ok(iter) => iter,
+                Err(lmdb::Error::NotFound) => return Ok(AccountDeploys::default()),
+                Err(error) => return Err(error.into()),
+            };
+            let mut entries = iter
+                .map(|(_, raw_val)| raw_val)
+                .skip_while(|raw_val| after.map_or(false, |after| *raw_val <= after));
+            let mut deploy_hashes = Vec::new();
+            let mut last_entry = None;
+            for raw_val in entries.by_ref().take(limit as usize) {
+                deploy_hashes.push(deploy_hash_from_account_deploys_entry(raw_val)?);
+                last_entry = Some(raw_val);
+            }
+            let next_page_after = match entries.next() {
+                Some(_) => last_entry.map(<[u8]>::to_vec),
+                None => None,
+            };
+            (deploy_hashes, next_page_after)
+        };
+
+        let mut account_deploys = Vec::with_capacity(deploy_hashes.len());
+        for deploy_hash in deploy_hashes {
+            let block_hash_and_height =
+                self.get_block_hash_and_height_by_deploy_hash(deploy_hash)?;
+            let execution_result = match (
+                block_hash_and_height,
+                self.get_deploy_metadata(txn, &deploy_hash)?,
+            ) {
+                (Some(block_hash_and_height), Some(mut metadata)) => metadata
+                    .execution_results
+                    .remove(&block_hash_and_height.block_hash),
+                _ => None,
+            };
+            account_deploys.push(AccountDeploy {
+                deploy_hash,
+                block_hash_and_height,
+                execution_result,
+            });
+        }
+        Ok(AccountDeploys {
+            deploys: account_deploys,
+            next_page_after,
+        })
+    }
+
+    /// Computes how validators performed during the given era from the era's blocks and their
+    /// finality signatures, and stores the result.
+    ///
+    /// The number of missed rounds, if already stored for the era, is kept.  Nothing is stored if
+    /// any of the era's blocks is missing.
+    fn put_era_validator_performance(
+        &self,
+        txn: &mut RwTransaction,
+        era_id: EraId,
+    ) -> Result<(), FatalStorageError> {
+        let switch_block_header = match self.get_switch_block_header_by_era_id(txn, era_id)? {
+            Some(switch_block_header) => switch_block_header,
+            None => return Ok(()),
+        };
+        let first_height = match era_id.checked_sub(1) {
+            None => 0,
+            Some(previous_era_id) => {
+                match self.get_switch_block_header_by_era_id(txn, previous_era_id)? {
+                    Some(previous_switch_block_header) => previous_switch_block_header.height() + 1,
+                    None => return Ok(()),
+                }
+            }
+        };
+
+        let key = era_validator_performance_key(era_id);
+        let previous: Option<EraValidatorPerformance> =
+            txn.get_value(self.era_validator_performance_db, &key)?;
+        let mut performance = EraValidatorPerformance::default();
+        if let Some(era_end) = switch_block_header.era_end() {
+            let era_report = era_end.era_report();
+            for public_key in era_report.rewards.keys() {
+                performance
+                    .validators
+                    .entry(public_key.clone())
+                    .or_default();
+            }
+            for public_key in &era_report.equivocators {
+                performance
+                    .validators
+                    .entry(public_key.clone())
+                    .or_default()
+                    .equivocated = true;
+            }
+        }
+        for height in first_height..=switch_block_header.height() {
+            let block = match self.get_block_by_height(txn, height)? {
+                Some(block) => block,
+                None => return Ok(()),
+            };
+            performance
+                .validators
+                .entry(block.body().proposer().clone())
+                .or_default()
+                .blocks_proposed += 1;
+            if let Some(block_signatures) = self.get_block_signatures(txn, block.hash())? {
+                for public_key in block_signatures.proofs.into_keys() {
+                    performance
+                        .validators
+                        .entry(public_key)
+                        .or_default()
+                        .finality_signatures += 1;
+                }
+            }
+        }
+        if let Some(previous) = previous {
+            for (public_key, previous_performance) in previous.validators {
+                performance
+                    .validators
+                    .entry(public_key)
+                    .or_default()
+                    .rounds_missed = previous_performance.rounds_missed;
+            }
+        }
+
+        let _ = txn.put_value(self.era_validator_performance_db, &key, &performance, true)?;
+        Ok(())
+    }
+
     /// Retrieves transfers associated with block.
     ///
     /// If no transfers are stored for the block, an empty transfers instance will be
@@ -1766,12 +2415,17 @@ impl Storage {
         Ok(())
     }
 
-    /// Retrieves a deploy from the deploy store.
-    fn get_deploy(&self, deploy_hash: DeployHash) -> Result<Option<Deploy>, LmdbExtError> {
-        self.env
-            .begin_ro_txn()
-            .map_err(Into::into)
-            .and_then(|mut txn| txn.get_value(self.deploy_db, &deploy_hash))
+    /// Retrieves a deploy from the deploy store, along with all approvals known for it.
+    fn get_deploy(&self, deploy_hash: DeployHash) -> Result<Option<Deploy>, FatalStorageError> {
+        let mut txn = self.env.begin_ro_txn()?;
+        let mut deploy: Deploy = match txn.get_value(self.deploy_db, &deploy_hash)? {
+            Some(deploy) => deploy,
+            None => return Ok(None),
+        };
+        if let Some(approvals) = txn.get_value(self.deploy_approvals_db, &deploy_hash)? {
+            deploy.replace_approvals(approvals);
+        }
+        Ok(Some(deploy))
     }
 
     fn read_block_headers_batch(
@@ -2271,6 +2925,126 @@ fn initialize_block_metadata_db(
     Ok(())
 }
 
+/// Returns the key under which the validator performance during the given era is stored.
+fn era_validator_performance_key(era_id: EraId) -> [u8; 8] {
+    era_id.value().to_be_bytes()
+}
+
+/// Builds the value stored in the account deploys database for the given deploy.
+///
+/// The value is the deploy's timestamp subtracted from `u64::MAX` in big-endian, followed by the
+/// deploy hash. Since LMDB sorts duplicate values lexicographically, this orders the deploys sent
+/// by an account from most to least recent.
+fn account_deploys_entry(deploy: &Deploy) -> Vec<u8> {
+    let inverted_timestamp = u64::MAX - deploy.header().timestamp().millis();
+    let mut entry = inverted_timestamp.to_be_bytes().to_vec();
+    entry.extend_from_slice(deploy.id().as_ref());
+    entry
+}
+
+/// Extracts the deploy hash from a value stored in the account deploys database.
+fn deploy_hash_from_account_deploys_entry(raw: &[u8]) -> Result<DeployHash, FatalStorageError> {
+    raw.get(mem::size_of::<u64>()..)
+        .and_then(|raw_hash| Digest::try_from(raw_hash).ok())
+        .map(DeployHash::new)
+        .ok_or_else(|| FatalStorageError::CorruptedAccountDeploysEntry {
+            raw_value: raw.to_vec(),
+        })
+}
+
+/// Indexes the given deploy under the account which sent it.
+///
+/// Writing an entry which already exists is a no-op.
+fn put_account_deploys_entry(
+    txn: &mut RwTransaction,
+    account_deploys_db: Database,
+    deploy: &Deploy,
+) -> Result<(), lmdb::Error> {
+    let account_hash = deploy.header().account().to_account_hash();
+    txn.put(
+        account_deploys_db,
+        &account_hash,
+        &account_deploys_entry(deploy),
+        WriteFlags::empty(),
+    )
+}
+
+/// Populates the account deploys database from the deploy database, unless it has been populated
+/// before, i.e. when it was created by a node version which didn't maintain it yet.
+///
+/// The deploys are indexed in batches, each written in its own transaction, so that neither the
+/// index entries nor the transaction need to hold the whole deploy store.  Completion is recorded
+/// in the state store, so that this happens only once.  If interrupted, indexing starts over on the
+/// next startup, rewriting the existing entries is a no-op.
+fn initialize_account_deploys_db(
+    env: &Environment,
+    deploy_db: &Database,
+    account_deploys_db: &Database,
+    state_store_db: &Database,
+) -> Result<(), FatalStorageError> {
+    {
+        let txn = env.begin_ro_txn()?;
+        match txn.get(*state_store_db, &ACCOUNT_DEPLOYS_INDEXED_STORAGE_KEY) {
+            Ok(_) => return Ok(()),
+            Err(lmdb::Error::NotFound) => (),
+            Err(error) => return Err(error.into()),
+        }
+    }
+
+    info!("indexing deploys by account");
+    let mut deploy_count = 0;
+    let mut batch = Vec::with_capacity(ACCOUNT_DEPLOYS_INDEXING_BATCH_SIZE);
+    let read_txn = env.begin_ro_txn()?;
+    let mut cursor = read_txn.open_ro_cursor(*deploy_db)?;
+    for (_, raw_val) in cursor.iter() {
+        let deploy: Deploy = lmdb_ext::deserialize(raw_val)?;
+        batch.push((
+            deploy.header().account().to_account_hash(),
+            account_deploys_entry(&deploy),
+        ));
+        deploy_count += 1;
+        if batch.len() == ACCOUNT_DEPLOYS_INDEXING_BATCH_SIZE {
+            put_account_deploys_batch(env, account_deploys_db, state_store_db, &mut batch, false)?;
+            debug!(deploy_count, "indexing deploys by account");
+        }
+    }
+    drop(cursor);
+    drop(read_txn);
+    put_account_deploys_batch(env, account_deploys_db, state_store_db, &mut batch, true)?;
+
+    info!(deploy_count, "deploys indexed by account");
+    Ok(())
+}
+
+/// Writes and clears the given batch of account deploys entries in a transaction of its own,
+/// marking the account deploys database as populated if `complete` is `true`.
+fn put_account_deploys_batch(
+    env: &Environment,
+    account_deploys_db: &Database,
+    state_store_db: &Database,
+    batch: &mut Vec<(AccountHash, Vec<u8>)>,
+    complete: bool,
+) -> Result<(), lmdb::Error> {
+    let mut txn = env.begin_rw_txn()?;
+    for (account_hash, entry) in batch.drain(..) {
+        txn.put(
+            *account_deploys_db,
+            &account_hash,
+            &entry,
+            WriteFlags::empty(),
+        )?;
+    }
+    if complete {
+        txn.put(
+            *state_store_db,
+            &ACCOUNT_DEPLOYS_INDEXED_STORAGE_KEY,
+            b"",
+            WriteFlags::empty(),
+        )?;
+    }
+    txn.commit()
+}
+
 /// Purges stale entries from the deploy metadata database.
 fn initialize_deploy_metadata_db(
     env: &Environment,
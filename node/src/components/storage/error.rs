@@ -1,4 +1,4 @@
-use std::{io, path::PathBuf};
+use std::{convert::TryFrom, io, path::PathBuf};
 
 use thiserror::Error;
 use tracing::error;
@@ -117,6 +117,12 @@ pub enum FatalStorageError {
         /// The block hash of the signatures found in the index.
         block_hash_bytes: Vec<u8>,
     },
+    /// Corrupted account deploys index.
+    #[error("malformed entry in the account deploys index: {raw_value:x?}")]
+    CorruptedAccountDeploysEntry {
+        /// The malformed value found in the index.
+        raw_value: Vec<u8>,
+    },
     /// Switch block does not contain era end.
     #[error("switch block does not contain era end: {0:?}")]
     InvalidSwitchBlock(Box<BlockHeader>),
@@ -148,6 +154,56 @@ pub enum FatalStorageError {
     /// `ToBytes` deserialization failure of an item that should never fail to serialize.
     #[error("unexpected deserialization failure: {0}")]
     UnexpectedDeserializationFailure(bytesrepr::Error),
+    /// A recoverable error persisted across all retry attempts.
+    #[error("giving up after {attempts} attempts: {last_error}")]
+    RetriesExhausted {
+        /// The number of attempts made.
+        attempts: u32,
+        /// The error returned by the final attempt.
+        last_error: RecoverableStorageError,
+    },
+}
+
+impl FatalStorageError {
+    /// Returns a short hint for the node operator on how to proceed after this error.
+    pub(crate) fn remediation_hint(&self) -> &'static str {
+        match self {
+            FatalStorageError::InternalStorage(LmdbExtError::ResourceExhausted(_))
+            | FatalStorageError::RetriesExhausted { .. } => {
+                "storage resources were exhausted but the data is intact; restarting the node, \
+                 possibly with larger `max_*_store_size` settings, should resolve this"
+            }
+            FatalStorageError::InternalStorage(LmdbExtError::LmdbCorrupted(_))
+            | FatalStorageError::InternalStorage(LmdbExtError::DataCorrupted(_))
+            | FatalStorageError::DuplicateBlockIndex { .. }
+            | FatalStorageError::DuplicateEraIdIndex { .. }
+            | FatalStorageError::DuplicateDeployIndex { .. }
+            | FatalStorageError::BlockHeaderNotStoredUnderItsHash { .. }
+            | FatalStorageError::NoBlockHeaderForBlockBody { .. }
+            | FatalStorageError::SignatureVerification(_)
+            | FatalStorageError::CorruptedBlockSignatureIndex { .. }
+            | FatalStorageError::CorruptedAccountDeploysEntry { .. }
+            | FatalStorageError::InvalidSwitchBlock(_)
+            | FatalStorageError::UnexpectedBlockBodyPart { .. }
+            | FatalStorageError::UnexpectedDeserializationFailure(_) => {
+                "storage appears to be corrupted; the storage directory may need to be cleared \
+                 before restarting the node"
+            }
+            FatalStorageError::CreateDatabaseDirectory(..)
+            | FatalStorageError::UnableToMoveFile { .. }
+            | FatalStorageError::MissingStorageFiles { .. } => {
+                "check the storage path and its permissions before restarting the node"
+            }
+            FatalStorageError::InternalStorage(LmdbExtError::Other(_))
+            | FatalStorageError::FailedToOverwriteBlock
+            | FatalStorageError::BlockValidation(_)
+            | FatalStorageError::StoredItemSerializationFailure(_)
+            | FatalStorageError::UnexpectedFinalizedApprovals { .. }
+            | FatalStorageError::UnexpectedSerializationFailure(_) => {
+                "this is likely a bug; please report it along with the node logs"
+            }
+        }
+    }
 }
 
 // We wholesale wrap lmdb errors and treat them as internal errors here.
@@ -157,6 +213,44 @@ impl From<lmdb::Error> for FatalStorageError {
     }
 }
 
+/// A recoverable storage component error.
+///
+/// An error of this kind indicates a transient condition, storage itself is intact and the failed
+/// operation may succeed if retried.
+#[derive(Debug, Error)]
+pub enum RecoverableStorageError {
+    /// LMDB ran out of a resource which is expected to be released again shortly, e.g. reader
+    /// slots.
+    #[error("transient LMDB resource exhaustion: {0}")]
+    TransientResourceExhaustion(lmdb::Error),
+}
+
+impl TryFrom<lmdb::Error> for RecoverableStorageError {
+    type Error = lmdb::Error;
+
+    fn try_from(err: lmdb::Error) -> Result<Self, Self::Error> {
+        match err {
+            lmdb::Error::ReadersFull | lmdb::Error::TlsFull => {
+                Ok(RecoverableStorageError::TransientResourceExhaustion(err))
+            }
+            _ => Err(err),
+        }
+    }
+}
+
+impl TryFrom<FatalStorageError> for RecoverableStorageError {
+    type Error = FatalStorageError;
+
+    fn try_from(err: FatalStorageError) -> Result<Self, Self::Error> {
+        match err {
+            FatalStorageError::InternalStorage(LmdbExtError::ResourceExhausted(lmdb_error)) => {
+                RecoverableStorageError::try_from(lmdb_error).map_err(FatalStorageError::from)
+            }
+            err => Err(err),
+        }
+    }
+}
+
 /// An error that may occur when handling a get request.
 ///
 /// Wraps a fatal error, callers should check whether the variant is of the fatal or non-fatal kind.
@@ -1,31 +1,41 @@
 //! Unit tests for the storage component.
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, BTreeSet, HashMap},
     fs::{self, File},
     iter,
 };
 
+use futures::channel::oneshot;
+use lmdb::Transaction;
 use rand::{prelude::SliceRandom, Rng};
 use serde::{Deserialize, Serialize};
 use smallvec::smallvec;
 
 use casper_types::{
-    system::auction::UnbondingPurse, testing::TestRng, AccessRights, EraId, ExecutionResult,
-    ProtocolVersion, PublicKey, SecretKey, URef, U512,
+    account::AccountHash, system::auction::UnbondingPurse, testing::TestRng, AccessRights, CLValue,
+    EraId, ExecutionResult, Key, ProtocolVersion, PublicKey, SecretKey, TimeDiff, Timestamp, URef,
+    U512,
 };
 
 use super::{
-    move_storage_files_to_network_subdir, should_move_storage_files_to_network_subdir, Config,
-    Storage,
+    move_storage_files_to_network_subdir, respond_or_defer,
+    should_move_storage_files_to_network_subdir, Config, Event, FatalStorageError, HandlingError,
+    LmdbExtError, RecoverableStorageError, Storage, ACCOUNT_DEPLOYS_INDEXED_STORAGE_KEY,
+    MAX_DEFERRED_EVENTS, MAX_RECOVERABLE_ATTEMPTS,
 };
 use crate::{
-    effect::{requests::StorageRequest, Multiple},
+    effect::{
+        requests::{MarkBlockCompletedRequest, StorageRequest},
+        Multiple, Responder,
+    },
     storage::lmdb_ext::{deserialize_internal, serialize_internal},
     testing::{ComponentHarness, UnitTestEvent},
     types::{
-        Block, BlockHash, BlockHashAndHeight, BlockHeader, BlockSignatures, Deploy, DeployHash,
-        DeployMetadata, DeployMetadataExt, DeployWithFinalizedApprovals, FinalitySignature,
+        AccountDeploy, AccountDeploys, Approval, Block, BlockHash, BlockHashAndHeight, BlockHeader,
+        BlockHeight, BlockSignatures, ContractEvent, Deploy, DeployHash, DeployMetadata,
+        DeployMetadataExt, DeployWithApprovals, DeployWithFinalizedApprovals,
+        EraValidatorPerformance, FinalitySignature, ValidatorPerformance,
     },
     utils::WithDir,
 };
@@ -139,7 +149,7 @@ fn get_block(
 fn get_block_header_by_height(
     harness: &mut ComponentHarness<UnitTestEvent>,
     storage: &mut Storage,
-    block_height: u64,
+    block_height: BlockHeight,
 ) -> Option<BlockHeader> {
     let response = harness.send_request(storage, move |responder| {
         StorageRequest::GetBlockHeaderByHeight {
@@ -280,6 +290,24 @@ fn put_deploy(
     response
 }
 
+/// Merges approvals into the ones known for a deploy in a storage component.
+fn put_deploy_approvals(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    deploy_hash: DeployHash,
+    approvals: BTreeSet<Approval>,
+) -> BTreeSet<Approval> {
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::PutDeployApprovals {
+            deploy_approvals: Box::new(DeployWithApprovals::new(deploy_hash, approvals)),
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
 fn insert_to_deploy_index(
     storage: &mut Storage,
     deploy: Deploy,
@@ -291,6 +319,146 @@ fn insert_to_deploy_index(
         .is_none()
 }
 
+/// Requests deploys sent by the given account from a storage component.
+fn get_account_deploys(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    account_hash: AccountHash,
+    after: Option<Vec<u8>>,
+    limit: u32,
+) -> AccountDeploys {
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::GetAccountDeploys {
+            account_hash,
+            after,
+            limit,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
+/// Marks the block at the given height as complete in a storage component.
+fn mark_block_completed(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    block_height: u64,
+) {
+    harness.send_request(storage, move |responder| {
+        MarkBlockCompletedRequest {
+            block_height: BlockHeight::from(block_height),
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+}
+
+/// Stores the number of rounds each validator missed during an era in a storage component.
+fn put_era_missed_rounds(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    era_id: EraId,
+    missed_rounds: BTreeMap<PublicKey, u64>,
+) {
+    harness.send_request(storage, move |responder| {
+        StorageRequest::PutEraMissedRounds {
+            era_id,
+            missed_rounds,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+}
+
+/// Requests how each validator performed during an era from a storage component.
+fn get_era_validator_performance(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    era_id: EraId,
+) -> Option<EraValidatorPerformance> {
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::GetEraValidatorPerformance { era_id, responder }.into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
+/// Stores the events emitted by contracts in a storage component.
+fn put_contract_events(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    contract_events: HashMap<DeployHash, Vec<ContractEvent>>,
+) {
+    harness.send_request(storage, move |responder| {
+        StorageRequest::PutContractEvents {
+            contract_events,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+}
+
+/// Requests the events emitted by contracts while executing a deploy from a storage component.
+fn get_contract_events(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    deploy_hash: DeployHash,
+) -> Option<Vec<ContractEvent>> {
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::GetContractEvents {
+            deploy_hash,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
+/// Requests the given deploys of a block along with their execution results from a storage
+/// component.
+fn get_block_deploys_with_execution_results(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    block_hash: BlockHash,
+    deploy_hashes: Vec<DeployHash>,
+) -> Option<Vec<(Deploy, Option<ExecutionResult>)>> {
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::GetBlockDeploysWithExecutionResults {
+            block_hash,
+            deploy_hashes,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
+/// Creates random deploys sent by the given account, in order of increasing timestamps.
+fn random_account_deploys(rng: &mut TestRng, secret_key: &SecretKey, count: u64) -> Vec<Deploy> {
+    (1..=count)
+        .map(|index| {
+            Deploy::new(
+                Timestamp::from(index * 1_000),
+                TimeDiff::from(60_000),
+                1,
+                vec![],
+                String::from("casper-example"),
+                rng.gen(),
+                rng.gen(),
+                secret_key,
+                None,
+            )
+        })
+        .collect()
+}
+
 /// Stores execution results in a storage component.
 fn put_execution_results(
     harness: &mut ComponentHarness<UnitTestEvent>,
@@ -326,30 +494,32 @@ fn can_retrieve_block_by_height() {
     let mut harness = ComponentHarness::default();
 
     // Create a random blocks, load and store them.
-    let block_33 = Box::new(Block::random_with_specifics(
-        &mut harness.rng,
-        EraId::new(1),
-        33,
-        ProtocolVersion::from_parts(1, 5, 0),
-        true,
-        None,
-    ));
-    let block_14 = Box::new(Block::random_with_specifics(
-        &mut harness.rng,
-        EraId::new(1),
-        14,
-        ProtocolVersion::from_parts(1, 5, 0),
-        false,
-        None,
-    ));
-    let block_99 = Box::new(Block::random_with_specifics(
-        &mut harness.rng,
-        EraId::new(2),
-        99,
-        ProtocolVersion::from_parts(1, 5, 0),
-        true,
-        None,
-    ));
+    let block_33 = Box::new(
+        Block::builder()
+            .seed(harness.rng.gen())
+            .era_id(EraId::new(1))
+            .height(33)
+            .protocol_version(ProtocolVersion::from_parts(1, 5, 0))
+            .switch_block()
+            .build(),
+    );
+    let block_14 = Box::new(
+        Block::builder()
+            .seed(harness.rng.gen())
+            .era_id(EraId::new(1))
+            .height(14)
+            .protocol_version(ProtocolVersion::from_parts(1, 5, 0))
+            .build(),
+    );
+    let block_99 = Box::new(
+        Block::builder()
+            .seed(harness.rng.gen())
+            .era_id(EraId::new(2))
+            .height(99)
+            .protocol_version(ProtocolVersion::from_parts(1, 5, 0))
+            .switch_block()
+            .build(),
+    );
 
     let mut storage = storage_fixture(&harness);
 
@@ -472,22 +642,20 @@ fn different_block_at_height_is_fatal() {
     let mut storage = storage_fixture(&harness);
 
     // Create two different blocks at the same height.
-    let block_44_a = Box::new(Block::random_with_specifics(
-        &mut harness.rng,
-        EraId::new(1),
-        44,
-        ProtocolVersion::V1_0_0,
-        false,
-        None,
-    ));
-    let block_44_b = Box::new(Block::random_with_specifics(
-        &mut harness.rng,
-        EraId::new(1),
-        44,
-        ProtocolVersion::V1_0_0,
-        false,
-        None,
-    ));
+    let block_44_a = Box::new(
+        Block::builder()
+            .seed(harness.rng.gen())
+            .era_id(EraId::new(1))
+            .height(44)
+            .build(),
+    );
+    let block_44_b = Box::new(
+        Block::builder()
+            .seed(harness.rng.gen())
+            .era_id(EraId::new(1))
+            .height(44)
+            .build(),
+    );
 
     let was_new = put_block(&mut harness, &mut storage, block_44_a.clone());
     assert!(was_new);
@@ -689,6 +857,61 @@ fn store_execution_results_for_two_blocks() {
     );
 }
 
+#[test]
+fn get_block_deploys_with_execution_results_for_block() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let executed_deploy = Deploy::random(&mut harness.rng);
+    let pending_deploy = Deploy::random(&mut harness.rng);
+    let missing_deploy = Deploy::random(&mut harness.rng);
+
+    let block_hash_a = BlockHash::random(&mut harness.rng);
+    let block_hash_b = BlockHash::random(&mut harness.rng);
+
+    put_deploy(
+        &mut harness,
+        &mut storage,
+        Box::new(executed_deploy.clone()),
+    );
+    put_deploy(&mut harness, &mut storage, Box::new(pending_deploy.clone()));
+
+    // Store different execution results of the same deploy for two blocks.
+    let result_a: ExecutionResult = harness.rng.gen();
+    let result_b: ExecutionResult = harness.rng.gen();
+    let mut results_a = HashMap::new();
+    results_a.insert(*executed_deploy.id(), result_a.clone());
+    put_execution_results(&mut harness, &mut storage, block_hash_a, results_a);
+    let mut results_b = HashMap::new();
+    results_b.insert(*executed_deploy.id(), result_b);
+    put_execution_results(&mut harness, &mut storage, block_hash_b, results_b);
+
+    // Only the execution result for the requested block should be returned, in the given order.
+    let deploys = get_block_deploys_with_execution_results(
+        &mut harness,
+        &mut storage,
+        block_hash_a,
+        vec![*pending_deploy.id(), *executed_deploy.id()],
+    )
+    .expect("should have all deploys");
+    assert_eq!(
+        deploys,
+        vec![
+            (pending_deploy.clone(), None),
+            (executed_deploy.clone(), Some(result_a)),
+        ]
+    );
+
+    // A single missing deploy should fail the whole request.
+    assert!(get_block_deploys_with_execution_results(
+        &mut harness,
+        &mut storage,
+        block_hash_a,
+        vec![*executed_deploy.id(), *missing_deploy.id()],
+    )
+    .is_none());
+}
+
 #[test]
 fn store_random_execution_results() {
     let mut harness = ComponentHarness::default();
@@ -837,6 +1060,37 @@ fn store_identical_execution_results() {
     put_execution_results(&mut harness, &mut storage, block_hash, exec_result);
 }
 
+#[test]
+fn store_and_load_contract_events() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let deploy_hash = DeployHash::random(&mut harness.rng);
+    let other_deploy_hash = DeployHash::random(&mut harness.rng);
+    let emitter = Key::Hash(harness.rng.gen());
+    let events: Vec<ContractEvent> = ["transfer", "approval", "transfer"]
+        .iter()
+        .zip(0u64..)
+        .map(|(name, value)| {
+            ContractEvent::new(emitter, name.to_string(), CLValue::from_t(value).unwrap())
+        })
+        .collect();
+
+    let mut contract_events = HashMap::new();
+    contract_events.insert(deploy_hash, events.clone());
+    put_contract_events(&mut harness, &mut storage, contract_events);
+
+    // Events are returned in the order emitted, including repeated ones.
+    assert_eq!(
+        get_contract_events(&mut harness, &mut storage, deploy_hash),
+        Some(events)
+    );
+    assert_eq!(
+        get_contract_events(&mut harness, &mut storage, other_deploy_hash),
+        None
+    );
+}
+
 /// Example state used in storage.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 struct StateData {
@@ -936,14 +1190,18 @@ fn should_hard_reset() {
     let blocks: Vec<Block> = (0..blocks_count)
         .map(|height| {
             let is_switch = height % blocks_per_era == blocks_per_era - 1;
-            Block::random_with_specifics(
-                &mut harness.rng,
-                EraId::from(height as u64 / 3),
-                height as u64,
-                ProtocolVersion::V1_0_0,
-                is_switch,
-                iter::once(random_deploys.get(height).expect("should_have_deploy")),
-            )
+            let builder = Block::builder()
+                .seed(harness.rng.gen())
+                .era_id(EraId::from(height as u64 / 3))
+                .height(height as u64)
+                .deploys(iter::once(
+                    random_deploys.get(height).expect("should_have_deploy"),
+                ));
+            if is_switch {
+                builder.switch_block().build()
+            } else {
+                builder.build()
+            }
         })
         .collect();
 
@@ -1199,14 +1457,12 @@ fn should_restrict_returned_blocks() {
 
     // Create the following disjoint sequences: 1-2 4-5
     [1, 2, 4, 5].iter().for_each(|height| {
-        let block = Block::random_with_specifics(
-            &mut harness.rng,
-            EraId::from(1),
-            *height,
-            ProtocolVersion::from_parts(1, 5, 0),
-            false,
-            None,
-        );
+        let block = Block::builder()
+            .seed(harness.rng.gen())
+            .era_id(EraId::from(1))
+            .height(*height)
+            .protocol_version(ProtocolVersion::from_parts(1, 5, 0))
+            .build();
         storage.write_block(&block).unwrap();
         storage.completed_blocks.insert(*height);
     });
@@ -1267,7 +1523,7 @@ fn should_get_block_header_by_height() {
 
     let block = Block::random(&mut harness.rng);
     let expected_header = block.header().clone();
-    let height = block.height();
+    let height = BlockHeight::from(block.height());
 
     // Requesting the block header before it is in storage should return None.
     assert!(get_block_header_by_height(&mut harness, &mut storage, height).is_none());
@@ -1332,3 +1588,404 @@ fn unbonding_purse_serialization_roundtrip() {
     // Explicitly assert that the `new_validator` is not `None`
     assert!(deserialized.new_validator().is_some())
 }
+
+#[test]
+fn should_escalate_persisting_recoverable_errors() {
+    let harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    for _ in 1..MAX_RECOVERABLE_ATTEMPTS {
+        storage
+            .defer_event(
+                Event::RetryDeferredEvents,
+                RecoverableStorageError::TransientResourceExhaustion(lmdb::Error::ReadersFull),
+            )
+            .unwrap();
+    }
+    assert!(matches!(
+        storage.defer_event(
+            Event::RetryDeferredEvents,
+            RecoverableStorageError::TransientResourceExhaustion(lmdb::Error::TlsFull),
+        ),
+        Err(FatalStorageError::RetriesExhausted {
+            attempts: MAX_RECOVERABLE_ATTEMPTS,
+            last_error: RecoverableStorageError::TransientResourceExhaustion(lmdb::Error::TlsFull),
+        })
+    ));
+    assert_eq!(
+        storage.deferred_events.len(),
+        MAX_RECOVERABLE_ATTEMPTS as usize
+    );
+}
+
+#[test]
+fn should_defer_requests_failing_with_recoverable_errors() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+    let deploy = Deploy::random(&mut harness.rng);
+    let deploy_hash = *deploy.id();
+    put_deploy(&mut harness, &mut storage, Box::new(deploy.clone()));
+
+    // A read failing due to temporary resource exhaustion is not responded to, but rebuilt.
+    let (sender, receiver) = oneshot::channel();
+    let outcome = respond_or_defer(
+        Responder::without_shutdown(sender),
+        Err(FatalStorageError::InternalStorage(
+            LmdbExtError::ResourceExhausted(lmdb::Error::ReadersFull),
+        )),
+        |responder| StorageRequest::GetDeploys {
+            deploy_hashes: vec![deploy_hash],
+            responder,
+        },
+    );
+    let event = match outcome {
+        Err(HandlingError::Deferred { event, error }) => {
+            assert!(matches!(
+                error,
+                RecoverableStorageError::TransientResourceExhaustion(lmdb::Error::ReadersFull)
+            ));
+            event
+        }
+        _ => panic!("should have deferred the request"),
+    };
+
+    // Handling the rebuilt request responds to the original requester.
+    for effect in harness.send_event(&mut storage, event) {
+        harness.runtime.spawn(effect);
+    }
+    let response = harness.runtime.block_on(receiver).unwrap();
+    assert_eq!(
+        response
+            .into_iter()
+            .map(|opt_dfa| opt_dfa.map(DeployWithFinalizedApprovals::into_naive))
+            .collect::<Vec<_>>(),
+        vec![Some(deploy)]
+    );
+
+    // Any other error is fatal.
+    let (sender, _receiver) = oneshot::channel::<Option<Deploy>>();
+    assert!(matches!(
+        respond_or_defer(
+            Responder::without_shutdown(sender),
+            Err(FatalStorageError::InternalStorage(
+                LmdbExtError::LmdbCorrupted(lmdb::Error::Corrupted)
+            )),
+            |_| -> Event { unreachable!("fatal errors are not deferred") },
+        ),
+        Err(HandlingError::Fatal(FatalStorageError::InternalStorage(
+            LmdbExtError::LmdbCorrupted(lmdb::Error::Corrupted)
+        )))
+    ));
+}
+
+#[test]
+fn should_handle_deferred_events_in_order() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+    let deploy = Box::new(Deploy::random(&mut harness.rng));
+    let deploy_hash = *deploy.id();
+
+    // Defer storing the deploy, as if storage had been temporarily unavailable.
+    let (put_sender, put_receiver) = oneshot::channel();
+    storage.deferred_events.push_back(
+        StorageRequest::PutDeploy {
+            deploy: deploy.clone(),
+            responder: Responder::without_shutdown(put_sender),
+        }
+        .into(),
+    );
+    storage.retry_scheduled = true;
+
+    // A new request is queued behind the deferred one until the retry is due.
+    let (get_sender, get_receiver) = oneshot::channel();
+    let get_request = StorageRequest::GetDeploys {
+        deploy_hashes: vec![deploy_hash],
+        responder: Responder::without_shutdown(get_sender),
+    };
+    assert!(harness
+        .send_event(&mut storage, get_request.into())
+        .is_empty());
+    assert_eq!(storage.deferred_events.len(), 2);
+
+    // Once it is, both are handled in the order they were received.
+    for effect in harness.send_event(&mut storage, Event::RetryDeferredEvents) {
+        harness.runtime.spawn(effect);
+    }
+    assert!(harness.runtime.block_on(put_receiver).unwrap());
+    let response = harness.runtime.block_on(get_receiver).unwrap();
+    assert_eq!(
+        response
+            .into_iter()
+            .map(|opt_dfa| opt_dfa.map(DeployWithFinalizedApprovals::into_naive))
+            .collect::<Vec<_>>(),
+        vec![Some(*deploy)]
+    );
+    assert!(storage.deferred_events.is_empty());
+    assert!(!storage.retry_scheduled);
+}
+
+#[test]
+fn should_not_defer_more_than_max_events() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+    let deploy_hash = *Deploy::random(&mut harness.rng).id();
+
+    storage
+        .deferred_events
+        .extend(iter::repeat_with(|| Event::RetryDeferredEvents).take(MAX_DEFERRED_EVENTS));
+    storage.retry_scheduled = true;
+
+    // Instead of growing the queue any further, the node is shut down.
+    let (sender, _receiver) = oneshot::channel();
+    let request = StorageRequest::GetDeploys {
+        deploy_hashes: vec![deploy_hash],
+        responder: Responder::without_shutdown(sender),
+    };
+    assert!(!harness.send_event(&mut storage, request.into()).is_empty());
+    assert_eq!(storage.deferred_events.len(), MAX_DEFERRED_EVENTS);
+}
+
+#[test]
+fn should_index_deploys_by_account() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let secret_key = SecretKey::random(&mut harness.rng);
+    let account_hash = PublicKey::from(&secret_key).to_account_hash();
+    let deploys = random_account_deploys(&mut harness.rng, &secret_key, 3);
+    let other_deploy = Deploy::random(&mut harness.rng);
+    for deploy in deploys.iter().chain(iter::once(&other_deploy)) {
+        put_deploy(&mut harness, &mut storage, Box::new(deploy.clone()));
+    }
+    // Storing a deploy again must not duplicate its index entry.
+    put_deploy(&mut harness, &mut storage, Box::new(deploys[0].clone()));
+
+    // Execute the oldest deploy.
+    let block_hash_and_height = BlockHashAndHeight::random(&mut harness.rng);
+    assert!(insert_to_deploy_index(
+        &mut storage,
+        deploys[0].clone(),
+        block_hash_and_height
+    ));
+    let execution_result: ExecutionResult = harness.rng.gen();
+    put_execution_results(
+        &mut harness,
+        &mut storage,
+        block_hash_and_height.block_hash,
+        iter::once((*deploys[0].id(), execution_result.clone())).collect(),
+    );
+
+    let account_deploys =
+        get_account_deploys(&mut harness, &mut storage, account_hash, None, 10).deploys;
+    let newest_first: Vec<_> = deploys.iter().rev().map(|deploy| *deploy.id()).collect();
+    assert_eq!(
+        account_deploys
+            .iter()
+            .map(|account_deploy| account_deploy.deploy_hash)
+            .collect::<Vec<_>>(),
+        newest_first
+    );
+    assert_eq!(
+        account_deploys[2],
+        AccountDeploy {
+            deploy_hash: *deploys[0].id(),
+            block_hash_and_height: Some(block_hash_and_height),
+            execution_result: Some(execution_result),
+        }
+    );
+    assert_eq!(account_deploys[0].block_hash_and_height, None);
+    assert_eq!(account_deploys[0].execution_result, None);
+
+    // Page through the deploys one at a time.
+    let mut after = None;
+    let mut paged = Vec::new();
+    loop {
+        let page = get_account_deploys(&mut harness, &mut storage, account_hash, after, 1);
+        assert_eq!(page.deploys.len(), 1);
+        paged.push(page.deploys[0].deploy_hash);
+        match page.next_page_after {
+            Some(next_page_after) => after = Some(next_page_after),
+            None => break,
+        }
+        // A deploy sent meanwhile must neither shift nor duplicate the following pages.
+        if paged.len() == 1 {
+            let newer_deploy = random_account_deploys(&mut harness.rng, &secret_key, 4)
+                .pop()
+                .unwrap();
+            put_deploy(&mut harness, &mut storage, Box::new(newer_deploy));
+        }
+    }
+    assert_eq!(paged, newest_first);
+
+    let unknown_account = AccountHash::new([0; 32]);
+    let page = get_account_deploys(&mut harness, &mut storage, unknown_account, None, 10);
+    assert_eq!(page, AccountDeploys::default());
+}
+
+#[test]
+fn should_populate_account_deploys_index_on_startup() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let secret_key = SecretKey::random(&mut harness.rng);
+    let account_hash = PublicKey::from(&secret_key).to_account_hash();
+    let deploys = random_account_deploys(&mut harness.rng, &secret_key, 2);
+    for deploy in &deploys {
+        put_deploy(&mut harness, &mut storage, Box::new(deploy.clone()));
+    }
+
+    // Simulate a database created before the index existed.
+    let mut txn = storage.env.begin_rw_txn().unwrap();
+    txn.clear_db(storage.account_deploys_db).unwrap();
+    txn.del(
+        storage.state_store_db,
+        &ACCOUNT_DEPLOYS_INDEXED_STORAGE_KEY,
+        None,
+    )
+    .unwrap();
+    txn.commit().unwrap();
+    assert!(
+        get_account_deploys(&mut harness, &mut storage, account_hash, None, 10)
+            .deploys
+            .is_empty()
+    );
+    drop(storage);
+
+    let (on_disk, rng) = harness.into_parts();
+    let mut harness = ComponentHarness::builder()
+        .on_disk(on_disk)
+        .rng(rng)
+        .build();
+    let mut storage = storage_fixture(&harness);
+
+    let account_deploys =
+        get_account_deploys(&mut harness, &mut storage, account_hash, None, 10).deploys;
+    assert_eq!(
+        account_deploys
+            .iter()
+            .map(|account_deploy| account_deploy.deploy_hash)
+            .collect::<Vec<_>>(),
+        vec![*deploys[1].id(), *deploys[0].id()]
+    );
+}
+
+#[test]
+fn should_snapshot_validator_performance_at_era_end() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let block = Block::builder()
+        .seed(harness.rng.gen())
+        .era_id(EraId::new(0))
+        .height(0)
+        .protocol_version(ProtocolVersion::from_parts(1, 5, 0))
+        .build();
+    let switch_block = Block::builder()
+        .seed(harness.rng.gen())
+        .era_id(EraId::new(0))
+        .height(1)
+        .protocol_version(ProtocolVersion::from_parts(1, 5, 0))
+        .switch_block()
+        .build();
+    let signatures = random_signatures(&mut harness.rng, &block);
+    let proposer = block.body().proposer().clone();
+    put_block(&mut harness, &mut storage, Box::new(block));
+    put_block(&mut harness, &mut storage, Box::new(switch_block.clone()));
+    put_block_signatures(&mut harness, &mut storage, signatures.clone());
+    put_era_missed_rounds(
+        &mut harness,
+        &mut storage,
+        EraId::new(0),
+        iter::once((proposer.clone(), 2)).collect(),
+    );
+
+    // Nothing but the missed rounds is known until the switch block is complete.
+    let performance = get_era_validator_performance(&mut harness, &mut storage, EraId::new(0))
+        .expect("should have missed rounds");
+    assert_eq!(performance.validators.len(), 1);
+    assert_eq!(performance.validators[&proposer].blocks_proposed, 0);
+
+    mark_block_completed(&mut harness, &mut storage, 1);
+    let performance = get_era_validator_performance(&mut harness, &mut storage, EraId::new(0))
+        .expect("should have performance");
+    assert_eq!(
+        performance.validators[&proposer],
+        ValidatorPerformance {
+            blocks_proposed: 1,
+            rounds_missed: Some(2),
+            finality_signatures: 0,
+            equivocated: false,
+        }
+    );
+    assert_eq!(
+        performance.validators[switch_block.body().proposer()].blocks_proposed,
+        1
+    );
+    for public_key in signatures.proofs.keys() {
+        assert_eq!(performance.validators[public_key].finality_signatures, 1);
+        assert_eq!(performance.validators[public_key].rounds_missed, None);
+    }
+    let era_end = switch_block
+        .header()
+        .era_end()
+        .expect("should have era end");
+    for public_key in &era_end.era_report().equivocators {
+        assert!(performance.validators[public_key].equivocated);
+    }
+
+    assert!(get_era_validator_performance(&mut harness, &mut storage, EraId::new(1)).is_none());
+}
+
+#[test]
+fn should_merge_deploy_approvals() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let deploy = Deploy::random(&mut harness.rng);
+    let deploy_hash = *deploy.id();
+    let new_approval = |rng: &mut TestRng| Approval::create(&deploy_hash, &SecretKey::random(rng));
+    let approval_1 = new_approval(&mut harness.rng);
+    let approval_2 = new_approval(&mut harness.rng);
+
+    // Approvals of a deploy which is not stored are discarded.
+    let approvals: BTreeSet<_> = iter::once(approval_1.clone()).collect();
+    assert!(put_deploy_approvals(&mut harness, &mut storage, deploy_hash, approvals).is_empty());
+
+    put_deploy(&mut harness, &mut storage, Box::new(deploy.clone()));
+    assert!(put_deploy_approvals(
+        &mut harness,
+        &mut storage,
+        deploy_hash,
+        deploy.approvals().clone()
+    )
+    .is_empty());
+
+    let approvals: BTreeSet<_> = iter::once(approval_1.clone()).collect();
+    assert_eq!(
+        put_deploy_approvals(&mut harness, &mut storage, deploy_hash, approvals.clone()),
+        approvals
+    );
+
+    // Only approvals not known yet are returned.
+    let approvals: BTreeSet<_> = vec![approval_1.clone(), approval_2.clone()]
+        .into_iter()
+        .collect();
+    assert_eq!(
+        put_deploy_approvals(&mut harness, &mut storage, deploy_hash, approvals),
+        iter::once(approval_2.clone()).collect()
+    );
+
+    // The deploy is served with the union of all approvals, while the original is kept.
+    let mut all_approvals = deploy.approvals().clone();
+    all_approvals.insert(approval_1);
+    all_approvals.insert(approval_2);
+    let served_deploy = storage
+        .get_deploy(deploy_hash)
+        .expect("should read deploy")
+        .expect("should have deploy");
+    assert_eq!(served_deploy.approvals(), &all_approvals);
+    assert_eq!(
+        get_naive_deploys(&mut harness, &mut storage, smallvec![deploy_hash]),
+        vec![Some(deploy)]
+    );
+}
@@ -0,0 +1,72 @@
+//! Reloading of node-local settings while the node is running.
+//!
+//! The chainspec holds the consensus-critical settings, which all nodes of a network must agree on
+//! and which only change via protocol upgrades.  The config file holds node-local settings.  Most
+//! of these take effect only when the node starts, but those gathered in [`ReloadableConfig`] are
+//! re-read from the config file and applied to the running node whenever it receives a `SIGHUP`
+//! signal or the `reload-config` diagnostics port command.
+
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::Context;
+use once_cell::sync::OnceCell;
+
+use crate::{
+    cli::{self, ConfigExt},
+    reactor::participating,
+};
+
+/// The config file the node was started with, and the override file and command-line overrides
+/// applied to it.
+static CONFIG_SOURCE: OnceCell<(PathBuf, Option<PathBuf>, Vec<ConfigExt>)> = OnceCell::new();
+
+/// The node-local settings which can be changed without restarting the node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ReloadableConfig {
+    /// The timeout for fetch requests to peers, from `[fetcher] get_from_peer_timeout`.
+    pub(crate) get_from_peer_timeout: Duration,
+    /// The maximum number of incoming connections per peer, from
+    /// `[network] max_incoming_peer_connections`.
+    pub(crate) max_incoming_peer_connections: u16,
+    /// The maximum outgoing bytes per second to non-validators, from
+    /// `[network] max_outgoing_byte_rate_non_validators`.
+    pub(crate) max_outgoing_byte_rate_non_validators: u32,
+    /// The maximum incoming message impact per second from non-validators, from
+    /// `[network] max_incoming_message_rate_non_validators`.
+    pub(crate) max_incoming_message_rate_non_validators: u32,
+}
+
+impl From<&participating::Config> for ReloadableConfig {
+    fn from(config: &participating::Config) -> Self {
+        ReloadableConfig {
+            get_from_peer_timeout: config.fetcher.get_from_peer_timeout().into(),
+            max_incoming_peer_connections: config.network.max_incoming_peer_connections,
+            max_outgoing_byte_rate_non_validators: config
+                .network
+                .max_outgoing_byte_rate_non_validators,
+            max_incoming_message_rate_non_validators: config
+                .network
+                .max_incoming_message_rate_non_validators,
+        }
+    }
+}
+
+/// Records the config file the node was started with, so that it can be reloaded later.
+pub(crate) fn set_config_source(
+    config: PathBuf,
+    config_override: Option<PathBuf>,
+    config_ext: Vec<ConfigExt>,
+) {
+    let _ = CONFIG_SOURCE.set((config, config_override, config_ext));
+}
+
+/// Re-reads the node-local settings which can be changed without restarting from the config file
+/// the node was started with.
+pub(crate) fn load() -> anyhow::Result<ReloadableConfig> {
+    let (config, config_override, config_ext) = CONFIG_SOURCE
+        .get()
+        .context("the node was not started from a config file")?;
+    let participating_config =
+        cli::load_config(config, config_override.as_deref(), config_ext.clone())?;
+    Ok(ReloadableConfig::from(&participating_config))
+}
@@ -0,0 +1,229 @@
+//! Crash dump bundles written when the node stops due to a fatal error.
+//!
+//! A bundle is a directory named after the time of the crash, holding everything needed to make a
+//! bug report actionable: the reason the node stopped, its version and syncing state, the most
+//! recently logged lines, a dump of the reactor's event queues, the connected peers and the config
+//! the node was started with, with secrets redacted.
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use datasize::DataSize;
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use toml::Value as TomlValue;
+use tracing::{error, info};
+
+use casper_types::Timestamp;
+
+use crate::{
+    logging,
+    types::{NodeId, NodeState},
+    WithDir,
+};
+
+/// Default directory to write crash dumps to, relative to the config file's directory.
+const DEFAULT_OUTPUT_DIR: &str = "crash_dumps";
+/// Default number of most recently logged lines included in a crash dump.
+const DEFAULT_LOG_LINES: usize = 1000;
+/// Value replacing secrets in the config included in a crash dump.
+const REDACTED: &str = "<redacted>";
+
+/// The crash dumper, initialized at startup.
+///
+/// We use a static variable since crash dumps are written by the reactor runner, which has no
+/// access to the config.
+static CRASH_DUMPER: OnceCell<CrashDumper> = OnceCell::new();
+
+/// Crash dump configuration.
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct CrashDumpConfig {
+    /// Whether to write a crash dump when the node stops due to a fatal error.
+    pub enabled: bool,
+    /// Directory to write crash dumps to.
+    ///
+    /// If relative, it is relative to the directory of the config file.
+    pub output_dir: PathBuf,
+    /// Number of most recently logged lines to include in a crash dump.
+    pub log_lines: usize,
+}
+
+impl Default for CrashDumpConfig {
+    fn default() -> Self {
+        CrashDumpConfig {
+            enabled: true,
+            output_dir: DEFAULT_OUTPUT_DIR.into(),
+            log_lines: DEFAULT_LOG_LINES,
+        }
+    }
+}
+
+/// Settings for writing crash dumps, taken from the config.
+#[derive(Debug)]
+struct CrashDumper {
+    /// The directory to write crash dumps to.
+    output_dir: PathBuf,
+    /// The config the node was started with, with secrets redacted.
+    redacted_config: String,
+}
+
+/// The state of the node at the time it stopped, as included in a crash dump.
+#[derive(Debug)]
+pub(crate) struct Crash {
+    /// The reason the node stopped.
+    pub(crate) reason: String,
+    /// The node's syncing state, if known.
+    pub(crate) node_state: Option<NodeState>,
+    /// The connected peers and their addresses.
+    pub(crate) peers: BTreeMap<NodeId, String>,
+    /// The events queued in the reactor, if they could be encoded.
+    pub(crate) queue_dump: Option<JsonValue>,
+}
+
+/// The summary of a crash, written as `summary.json`.
+#[derive(Debug, Serialize)]
+struct Summary<'a> {
+    reason: &'a str,
+    timestamp: Timestamp,
+    version: &'a str,
+    node_state: Option<&'a NodeState>,
+}
+
+/// Initializes crash dumps with the given config, and `config_table`, the config the node was
+/// started with.
+///
+/// Crash dumps stay disabled if not enabled in the config.  Only the first call has any effect.
+pub(crate) fn init(config: &WithDir<CrashDumpConfig>, config_table: &TomlValue) {
+    let crash_dump_config = config.value();
+    if !crash_dump_config.enabled {
+        return;
+    }
+    let mut config_table = config_table.clone();
+    redact(&mut config_table);
+    let redacted_config = toml::to_string(&config_table)
+        .unwrap_or_else(|error| format!("failed to encode config: {}", error));
+    let crash_dumper = CrashDumper {
+        output_dir: config.with_dir(crash_dump_config.output_dir.clone()),
+        redacted_config,
+    };
+    if CRASH_DUMPER.set(crash_dumper).is_ok() {
+        logging::retain_recent_log_lines(crash_dump_config.log_lines);
+    }
+}
+
+/// Returns whether crash dumps are enabled.
+pub(crate) fn is_enabled() -> bool {
+    CRASH_DUMPER.get().is_some()
+}
+
+/// Writes a crash dump bundle for `crash`, unless crash dumps are disabled.
+///
+/// Failures to write the bundle are logged.
+pub(crate) fn write(crash: Crash) {
+    let crash_dumper = match CRASH_DUMPER.get() {
+        Some(crash_dumper) => crash_dumper,
+        None => return,
+    };
+    let timestamp = Timestamp::now();
+    let dir = crash_dumper
+        .output_dir
+        .join(format!("crash-{}", timestamp.millis()));
+    match write_bundle(&dir, crash_dumper, &crash, timestamp) {
+        Ok(()) => info!(path = %dir.display(), "wrote crash dump"),
+        Err(error) => error!(path = %dir.display(), %error, "failed to write crash dump"),
+    }
+}
+
+/// Writes the files of the crash dump bundle for `crash` to `dir`.
+fn write_bundle(
+    dir: &Path,
+    crash_dumper: &CrashDumper,
+    crash: &Crash,
+    timestamp: Timestamp,
+) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let summary = Summary {
+        reason: &crash.reason,
+        timestamp,
+        version: &crate::VERSION_STRING,
+        node_state: crash.node_state.as_ref(),
+    };
+    fs::write(dir.join("summary.json"), to_json(&summary)?)?;
+    let mut log_lines = logging::recent_log_lines().join("\n");
+    log_lines.push('\n');
+    fs::write(dir.join("log.txt"), log_lines)?;
+    if let Some(queue_dump) = &crash.queue_dump {
+        fs::write(dir.join("queue_dump.json"), to_json(queue_dump)?)?;
+    }
+    fs::write(dir.join("peers.json"), to_json(&crash.peers)?)?;
+    fs::write(dir.join("config.toml"), &crash_dumper.redacted_config)
+}
+
+/// Encodes `value` as pretty-printed JSON.
+fn to_json<T: Serialize>(value: &T) -> io::Result<Vec<u8>> {
+    serde_json::to_vec_pretty(value).map_err(io::Error::from)
+}
+
+/// Replaces the values of all secrets in the config table with a placeholder.
+fn redact(value: &mut TomlValue) {
+    match value {
+        TomlValue::Table(table) => {
+            for (key, value) in table.iter_mut() {
+                if is_secret(key) {
+                    *value = TomlValue::String(REDACTED.to_string());
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        TomlValue::Array(array) => array.iter_mut().for_each(redact),
+        _ => (),
+    }
+}
+
+/// Returns whether the config value named `key` holds a secret, e.g. an API key or a token.
+fn is_secret(key: &str) -> bool {
+    key == "key" || key == "webhook_url" || key.contains("token") || key.contains("password")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_redact_secrets() {
+        let mut config: TomlValue = toml::from_str(
+            r#"
+            [consensus]
+            secret_key_path = 'secret_key.pem'
+
+            [rest_server]
+            profiling_token = 'hunter2'
+
+            [[rpc_server.access_control.api_keys]]
+            key = 'abc'
+            allowed_methods = ['*']
+            "#,
+        )
+        .unwrap();
+        redact(&mut config);
+
+        assert_eq!(
+            config["consensus"]["secret_key_path"].as_str(),
+            Some("secret_key.pem")
+        );
+        assert_eq!(
+            config["rest_server"]["profiling_token"].as_str(),
+            Some(REDACTED)
+        );
+        let api_key = &config["rpc_server"]["access_control"]["api_keys"][0];
+        assert_eq!(api_key["key"].as_str(), Some(REDACTED));
+        assert_eq!(api_key["allowed_methods"][0].as_str(), Some("*"));
+    }
+}
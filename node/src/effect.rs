@@ -98,7 +98,7 @@ pub(crate) mod requests;
 use std::{
     any::type_name,
     borrow::Cow,
-    collections::{BTreeMap, HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fmt::{self, Debug, Display, Formatter},
     future::Future,
     mem,
@@ -117,40 +117,48 @@ use tracing::{debug, error, warn};
 use casper_execution_engine::{
     core::engine_state::{
         self, era_validators::GetEraValidatorsError, genesis::GenesisSuccess, BalanceRequest,
-        BalanceResult, GetBidsRequest, GetBidsResult, QueryRequest, QueryResult, UpgradeConfig,
-        UpgradeSuccess,
+        BalanceResult, GasProfile, GetBalancesRequest, GetBalancesResult, GetBidsRequest,
+        GetBidsResult, GetDictionaryItemsRequest, GetDictionaryItemsResult, GetStateDiffRequest,
+        GetStateDiffResult, QueryRequest, QueryResult, UpgradeConfig, UpgradeSuccess,
     },
     shared::execution_journal::ExecutionJournal,
-    storage::trie::{TrieOrChunk, TrieOrChunkId},
+    storage::trie::{merkle_proof::TrieMerkleProof, TrieOrChunk, TrieOrChunkId},
 };
 use casper_hashing::Digest;
 use casper_types::{
-    account::Account, bytesrepr::Bytes, system::auction::EraValidators, Contract, ContractPackage,
-    EraId, ExecutionEffect, ExecutionResult, Key, ProtocolVersion, PublicKey, TimeDiff, Timestamp,
-    Transfer, URef, U512,
+    account::{Account, AccountHash},
+    bytesrepr::Bytes,
+    system::auction::EraValidators,
+    Contract, ContractPackage, EraId, ExecutionEffect, ExecutionResult, Key, ProtocolVersion,
+    PublicKey, StoredValue, TimeDiff, Timestamp, Transfer, URef, U512,
 };
 
 use crate::{
     components::{
         block_validator::ValidatingBlock,
-        chainspec_loader::NextUpgrade,
-        consensus::{BlockContext, ClContext, EraDump, ValidatorChange},
+        chain_synchronizer::SyncProgress,
+        chainspec_loader::{NextUpgrade, UpgradePointInfo},
+        consensus::{BlockContext, ClContext, EraDump, ProposedBlock, ValidatorChange},
         contract_runtime::{
-            BlockAndExecutionEffects, BlockExecutionError, EraValidatorsRequest, ExecutionPreState,
+            BlockAndExecutionEffects, BlockExecutionError, DeployExecutionProgress,
+            EraValidatorsRequest, ExecutionPreState, RequestPriority,
         },
-        deploy_acceptor,
+        deploy_acceptor::{self, PendingDeployStatus},
         fetcher::FetchResult,
-        small_network::FromIncoming,
+        linear_chain::ForkEvidence,
+        small_network::{FromIncoming, PeerDetails},
     },
     contract_runtime::SpeculativeExecutionState,
     effect::announcements::ChainSynchronizerAnnouncement,
-    reactor::{EventQueueHandle, QueueKind},
+    reactor::{correlation, supervision::SupervisedComponent, EventQueueHandle, QueueKind},
     types::{
-        AvailableBlockRange, Block, BlockAndDeploys, BlockHash, BlockHeader,
-        BlockHeaderWithMetadata, BlockHeadersBatch, BlockHeadersBatchId, BlockPayload,
-        BlockSignatures, BlockWithMetadata, Chainspec, ChainspecInfo, ChainspecRawBytes, Deploy,
-        DeployHash, DeployHeader, DeployMetadataExt, DeployWithFinalizedApprovals,
-        FinalitySignature, FinalizedApprovals, FinalizedBlock, Item, NodeId, NodeState,
+        chainspec::DeployConfig, AccountDeploys, Approval, AvailableBlockRange, Block,
+        BlockAndDeploys, BlockHash, BlockHeader, BlockHeaderWithMetadata, BlockHeadersBatch,
+        BlockHeadersBatchId, BlockHeight, BlockPayload, BlockSignatures, BlockWithMetadata,
+        Chainspec, ChainspecInfo, ChainspecRawBytes, ContractEvent, Deploy, DeployHash,
+        DeployHeader, DeployMetadataExt, DeployWithApprovals, DeployWithFinalizedApprovals,
+        EraValidatorPerformance, FinalitySignature, FinalizedApprovals, FinalizedBlock, Item,
+        NodeId, NodeState, SignedWeight,
     },
     utils::{fmt_limit::FmtLimit, SharedFlag, Source},
 };
@@ -158,14 +166,17 @@ use announcements::{
     BlockProposerAnnouncement, BlocklistAnnouncement, ChainspecLoaderAnnouncement,
     ConsensusAnnouncement, ContractRuntimeAnnouncement, ControlAnnouncement,
     DeployAcceptorAnnouncement, GossiperAnnouncement, LinearChainAnnouncement, QueueDumpFormat,
-    RpcServerAnnouncement,
+    RpcServerAnnouncement, SupervisorAnnouncement,
+};
+use diagnostics_port::{
+    DeployTrace, DumpConsensusStateRequest, EventCounts, InFlightFetches, MemoryUsage, QueueDepths,
+    QueueFairness,
 };
-use diagnostics_port::DumpConsensusStateRequest;
 use requests::{
     BeginGossipRequest, BlockPayloadRequest, BlockProposerRequest, BlockValidationRequest,
     ChainspecLoaderRequest, ConsensusRequest, ContractRuntimeRequest, FetcherRequest,
     MarkBlockCompletedRequest, MetricsRequest, NetworkInfoRequest, NetworkRequest,
-    NodeStateRequest, StateStoreRequest, StorageRequest,
+    NodeStateRequest, StateStoreRequest, StorageRequest, SyncProgressRequest,
 };
 
 /// A resource that will never be available, thus trying to acquire it will wait forever.
@@ -635,10 +646,25 @@ impl<REv> EffectBuilder<REv> {
             .await
     }
 
+    /// Announces that the linear chain has forked past finalization.
+    pub(crate) async fn announce_fork_detected(self, evidence: ForkEvidence)
+    where
+        REv: From<ControlAnnouncement>,
+    {
+        self.event_queue
+            .schedule(
+                ControlAnnouncement::ForkDetected(Box::new(evidence)),
+                QueueKind::Control,
+            )
+            .await
+    }
+
     /// Sets a timeout.
     pub(crate) async fn set_timeout(self, timeout: Duration) -> Duration {
         let then = Instant::now();
         time::sleep(timeout).await;
+        // An expired timer is a trigger in its own right, so starts a new correlation.
+        correlation::detach();
         Instant::now() - then
     }
 
@@ -754,6 +780,18 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Gets the details of the current network peers.
+    pub(crate) async fn network_detailed_peers(self) -> Vec<PeerDetails>
+    where
+        REv: From<NetworkInfoRequest>,
+    {
+        self.make_request(
+            |responder| NetworkInfoRequest::DetailedPeers { responder },
+            QueueKind::Api,
+        )
+        .await
+    }
+
     /// Gets the current network peers in random order.
     pub async fn get_fully_connected_peers(self) -> Vec<NodeId>
     where
@@ -778,6 +816,20 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Gets the current network peers serving finality signatures to backfill in random order.
+    pub async fn get_fully_connected_finality_signature_backfill_peers(self) -> Vec<NodeId>
+    where
+        REv: From<NetworkInfoRequest>,
+    {
+        self.make_request(
+            |responder| NetworkInfoRequest::FullyConnectedFinalitySignatureBackfillPeers {
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Announces which deploys have expired.
     pub(crate) async fn announce_expired_deploys(self, hashes: Vec<DeployHash>)
     where
@@ -827,7 +879,7 @@ impl<REv> EffectBuilder<REv> {
     /// Completion means that the block itself (along with its header) and all of its deploys have
     /// been persisted to storage and its global state root hash is missing no dependencies in the
     /// global state.
-    pub(crate) async fn mark_block_completed(self, block_height: u64)
+    pub(crate) async fn mark_block_completed(self, block_height: BlockHeight)
     where
         REv: From<MarkBlockCompletedRequest>,
     {
@@ -845,7 +897,9 @@ impl<REv> EffectBuilder<REv> {
     pub(crate) async fn announce_deploy_received(
         self,
         deploy: Box<Deploy>,
-        responder: Option<Responder<Result<(), deploy_acceptor::Error>>>,
+        responder: Option<
+            Responder<Result<Option<deploy_acceptor::BalanceProof>, deploy_acceptor::Error>>,
+        >,
     ) where
         REv: From<RpcServerAnnouncement>,
     {
@@ -857,6 +911,43 @@ impl<REv> EffectBuilder<REv> {
             .await;
     }
 
+    /// Announces that the HTTP API server has received a deploy which may lack approvals.
+    pub(crate) async fn announce_pending_deploy_received(
+        self,
+        deploy: Box<Deploy>,
+        responder: Responder<Result<PendingDeployStatus, deploy_acceptor::Error>>,
+    ) where
+        REv: From<RpcServerAnnouncement>,
+    {
+        self.event_queue
+            .schedule(
+                RpcServerAnnouncement::PendingDeployReceived { deploy, responder },
+                QueueKind::Api,
+            )
+            .await;
+    }
+
+    /// Announces that the HTTP API server has received further approvals for a pending deploy.
+    pub(crate) async fn announce_deploy_approvals_received(
+        self,
+        deploy_hash: DeployHash,
+        approvals: BTreeSet<Approval>,
+        responder: Responder<Result<PendingDeployStatus, deploy_acceptor::Error>>,
+    ) where
+        REv: From<RpcServerAnnouncement>,
+    {
+        self.event_queue
+            .schedule(
+                RpcServerAnnouncement::DeployApprovalsReceived {
+                    deploy_hash,
+                    approvals,
+                    responder,
+                },
+                QueueKind::Api,
+            )
+            .await;
+    }
+
     /// Announces that a deploy not previously stored has now been accepted and stored.
     pub(crate) fn announce_new_deploy_accepted(
         self,
@@ -872,6 +963,25 @@ impl<REv> EffectBuilder<REv> {
         )
     }
 
+    /// Announces that approvals not previously known for an already stored deploy have been
+    /// accepted and stored.
+    pub(crate) fn announce_new_deploy_approvals(
+        self,
+        deploy_approvals: Box<DeployWithApprovals>,
+        source: Source,
+    ) -> impl Future<Output = ()>
+    where
+        REv: From<DeployAcceptorAnnouncement>,
+    {
+        self.event_queue.schedule(
+            DeployAcceptorAnnouncement::NewDeployApprovals {
+                deploy_approvals,
+                source,
+            },
+            QueueKind::Regular,
+        )
+    }
+
     /// Announces that we have finished gossiping the indicated item.
     pub(crate) async fn announce_finished_gossiping<T>(self, item_id: T::Id)
     where
@@ -933,11 +1043,25 @@ impl<REv> EffectBuilder<REv> {
             .await
     }
 
+    /// Announces the progress made executing a deploy in a block.
+    pub(crate) async fn announce_deploy_execution_progress(self, progress: DeployExecutionProgress)
+    where
+        REv: From<ContractRuntimeAnnouncement>,
+    {
+        self.event_queue
+            .schedule(
+                ContractRuntimeAnnouncement::DeployExecutionProgress(Box::new(progress)),
+                QueueKind::Regular,
+            )
+            .await
+    }
+
     /// Announces a new block has been created.
     pub(crate) async fn announce_new_linear_chain_block(
         self,
         block: Box<Block>,
         execution_results: Vec<(DeployHash, DeployHeader, ExecutionResult)>,
+        contract_events: HashMap<DeployHash, Vec<ContractEvent>>,
     ) where
         REv: From<ContractRuntimeAnnouncement>,
     {
@@ -946,6 +1070,7 @@ impl<REv> EffectBuilder<REv> {
                 ContractRuntimeAnnouncement::LinearChainBlock {
                     block,
                     execution_results,
+                    contract_events,
                 },
                 QueueKind::Regular,
             )
@@ -971,6 +1096,25 @@ impl<REv> EffectBuilder<REv> {
             .await
     }
 
+    /// Announces the deploy limits read from the on-chain configuration contract.
+    pub(crate) async fn announce_deploy_config_updated(
+        self,
+        era_id: EraId,
+        deploy_config: DeployConfig,
+    ) where
+        REv: From<ContractRuntimeAnnouncement>,
+    {
+        self.event_queue
+            .schedule(
+                ContractRuntimeAnnouncement::DeployConfigUpdated {
+                    era_id,
+                    deploy_config,
+                },
+                QueueKind::Regular,
+            )
+            .await
+    }
+
     /// Begins gossiping an item.
     pub(crate) async fn begin_gossip<T>(self, item_id: T::Id, source: Source)
     where
@@ -1067,7 +1211,7 @@ impl<REv> EffectBuilder<REv> {
 
     pub(crate) async fn get_block_header_at_height_from_storage(
         self,
-        block_height: u64,
+        block_height: BlockHeight,
         only_from_available_block_range: bool,
     ) -> Option<BlockHeader>
     where
@@ -1085,7 +1229,7 @@ impl<REv> EffectBuilder<REv> {
     }
 
     /// Checks if a block header exists in storage
-    pub(crate) async fn block_header_exists(self, block_height: u64) -> bool
+    pub(crate) async fn block_header_exists(self, block_height: BlockHeight) -> bool
     where
         REv: From<StorageRequest>,
     {
@@ -1321,6 +1465,26 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Merges the given approvals into the ones known for the stored deploy.
+    ///
+    /// Returns the approvals which were not previously known.
+    pub(crate) async fn put_deploy_approvals_to_storage(
+        self,
+        deploy_approvals: Box<DeployWithApprovals>,
+    ) -> BTreeSet<Approval>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::PutDeployApprovals {
+                deploy_approvals,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Gets the requested deploys from the deploy store.
     ///
     /// Returns the "original" deploys, which are the first received by the node, along with a
@@ -1362,6 +1526,43 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Stores the events emitted by contracts while executing the given deploys.
+    pub(crate) async fn put_contract_events_to_storage(
+        self,
+        contract_events: HashMap<DeployHash, Vec<ContractEvent>>,
+    ) where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::PutContractEvents {
+                contract_events,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Gets the events emitted by contracts while executing the given deploy from storage.
+    ///
+    /// Returns `None` if the deploy emitted no events or has not been executed.
+    pub(crate) async fn get_contract_events_from_storage(
+        self,
+        deploy_hash: DeployHash,
+    ) -> Option<Vec<ContractEvent>>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetContractEvents {
+                deploy_hash,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Gets the requested deploys from the deploy store.
     pub(crate) async fn get_deploy_and_metadata_from_storage(
         self,
@@ -1380,10 +1581,90 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Gets the given deploys contained in the given block, along with their execution results in
+    /// that block, from storage.
+    ///
+    /// Returns `None` if any of the deploys is not stored.
+    pub(crate) async fn get_block_deploys_with_execution_results_from_storage(
+        self,
+        block_hash: BlockHash,
+        deploy_hashes: Vec<DeployHash>,
+    ) -> Option<Vec<(Deploy, Option<ExecutionResult>)>>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetBlockDeploysWithExecutionResults {
+                block_hash,
+                deploy_hashes,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Gets up to `limit` of the deploys sent by the given account from storage, most recent
+    /// first, starting after the position `after` if given.
+    pub(crate) async fn get_account_deploys_from_storage(
+        self,
+        account_hash: AccountHash,
+        after: Option<Vec<u8>>,
+        limit: u32,
+    ) -> AccountDeploys
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetAccountDeploys {
+                account_hash,
+                after,
+                limit,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Puts the number of rounds each validator missed during the given era to storage.
+    pub(crate) async fn put_era_missed_rounds_to_storage(
+        self,
+        era_id: EraId,
+        missed_rounds: BTreeMap<PublicKey, u64>,
+    ) where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::PutEraMissedRounds {
+                era_id,
+                missed_rounds,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Gets how each validator performed during the given era from storage.
+    pub(crate) async fn get_era_validator_performance_from_storage(
+        self,
+        era_id: EraId,
+    ) -> Option<EraValidatorPerformance>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetEraValidatorPerformance { era_id, responder },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Gets the requested block and its finality signatures.
     pub(crate) async fn get_block_at_height_with_metadata_from_storage(
         self,
-        block_height: u64,
+        block_height: BlockHeight,
         only_from_available_block_range: bool,
     ) -> Option<BlockWithMetadata>
     where
@@ -1443,7 +1724,7 @@ impl<REv> EffectBuilder<REv> {
     /// Gets the requested block by height with its associated metadata.
     pub(crate) async fn get_block_with_metadata_from_storage_by_height(
         self,
-        block_height: u64,
+        block_height: BlockHeight,
         only_from_available_block_range: bool,
     ) -> Option<BlockWithMetadata>
     where
@@ -1463,7 +1744,7 @@ impl<REv> EffectBuilder<REv> {
     /// Gets the requested block header by height with its associated metadata.
     pub(crate) async fn get_block_header_with_metadata_from_storage_by_height(
         self,
-        block_height: u64,
+        block_height: BlockHeight,
         only_from_available_block_range: bool,
     ) -> Option<BlockHeaderWithMetadata>
     where
@@ -1592,13 +1873,14 @@ impl<REv> EffectBuilder<REv> {
 
     /// Checks whether the deploys included in the block exist on the network and the block is
     /// valid.
-    pub(crate) async fn validate_block<T>(self, sender: NodeId, block: T) -> bool
+    pub(crate) async fn validate_block<T>(self, sender: NodeId, era_id: EraId, block: T) -> bool
     where
         REv: From<BlockValidationRequest>,
         T: Into<ValidatingBlock>,
     {
         self.make_request(
             |responder| BlockValidationRequest {
+                era_id,
                 block: block.into(),
                 sender,
                 responder,
@@ -1636,6 +1918,19 @@ impl<REv> EffectBuilder<REv> {
             .await
     }
 
+    /// Announces that a block proposed by another validator is valid.
+    pub(crate) async fn announce_proposed_block(self, proposed_block: ProposedBlock<ClContext>)
+    where
+        REv: From<ConsensusAnnouncement>,
+    {
+        self.event_queue
+            .schedule(
+                ConsensusAnnouncement::ProposedBlock(Box::new(proposed_block)),
+                QueueKind::Regular,
+            )
+            .await
+    }
+
     /// An equivocation has been detected.
     pub(crate) async fn announce_fault_event(
         self,
@@ -1657,6 +1952,22 @@ impl<REv> EffectBuilder<REv> {
             .await
     }
 
+    /// Announces that consensus started a new era with the given validators.
+    pub(crate) async fn announce_era_validators(
+        self,
+        era_id: EraId,
+        validators: BTreeSet<PublicKey>,
+    ) where
+        REv: From<ConsensusAnnouncement>,
+    {
+        self.event_queue
+            .schedule(
+                ConsensusAnnouncement::EraValidators { era_id, validators },
+                QueueKind::Regular,
+            )
+            .await
+    }
+
     /// Announce the intent to disconnect from a specific peer, which consensus thinks is faulty.
     pub(crate) async fn announce_disconnect_from_peer(self, peer: NodeId)
     where
@@ -1670,6 +1981,48 @@ impl<REv> EffectBuilder<REv> {
             .await
     }
 
+    /// Announce that a supervised component crashed and should be restarted after `restart_delay`.
+    pub(crate) async fn announce_component_crashed(
+        self,
+        component: SupervisedComponent,
+        message: String,
+        restart_delay: Duration,
+    ) where
+        REv: From<SupervisorAnnouncement>,
+    {
+        self.event_queue
+            .schedule(
+                SupervisorAnnouncement::ComponentCrashed {
+                    component,
+                    message,
+                    restart_delay,
+                },
+                QueueKind::Regular,
+            )
+            .await
+    }
+
+    /// Announce that a task of the given generation of a supervised component panicked.
+    pub(crate) async fn announce_task_panicked(
+        self,
+        component: SupervisedComponent,
+        generation: u64,
+        message: String,
+    ) where
+        REv: From<SupervisorAnnouncement>,
+    {
+        self.event_queue
+            .schedule(
+                SupervisorAnnouncement::TaskPanicked {
+                    component,
+                    generation,
+                    message,
+                },
+                QueueKind::Regular,
+            )
+            .await
+    }
+
     /// Announce that the sync process has finished.
     pub(crate) async fn announce_finished_chain_syncing(self)
     where
@@ -1697,13 +2050,19 @@ impl<REv> EffectBuilder<REv> {
     }
 
     /// The linear chain has stored a new finality signature.
-    pub(crate) async fn announce_finality_signature(self, fs: Box<FinalitySignature>)
-    where
+    ///
+    /// `signed_weight` is the cumulative weight of the block's known signatures, if the validators
+    /// of the block's era are known.
+    pub(crate) async fn announce_finality_signature(
+        self,
+        fs: Box<FinalitySignature>,
+        signed_weight: Option<Box<SignedWeight>>,
+    ) where
         REv: From<LinearChainAnnouncement>,
     {
         self.event_queue
             .schedule(
-                LinearChainAnnouncement::NewFinalitySignature(fs),
+                LinearChainAnnouncement::NewFinalitySignature(fs, signed_weight),
                 QueueKind::Regular,
             )
             .await
@@ -1763,6 +2122,14 @@ impl<REv> EffectBuilder<REv> {
         self.make_request(NodeStateRequest, QueueKind::Api).await
     }
 
+    /// Gets a summary of the chain synchronization progress from the chain synchronizer.
+    pub(crate) async fn get_sync_progress(self) -> SyncProgress
+    where
+        REv: From<SyncProgressRequest> + Send,
+    {
+        self.make_request(SyncProgressRequest, QueueKind::Api).await
+    }
+
     /// Retrieves finalized blocks with timestamps no older than the maximum deploy TTL.
     ///
     /// These blocks contain all deploy and transfer hashes that are known to be finalized but
@@ -1841,12 +2208,26 @@ impl<REv> EffectBuilder<REv> {
         self,
         query_request: QueryRequest,
     ) -> Result<QueryResult, engine_state::Error>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.query_global_state_with_priority(query_request, RequestPriority::Query)
+            .await
+    }
+
+    /// Requests a query be executed on the Contract Runtime component with the given priority.
+    async fn query_global_state_with_priority(
+        self,
+        query_request: QueryRequest,
+        priority: RequestPriority,
+    ) -> Result<QueryResult, engine_state::Error>
     where
         REv: From<ContractRuntimeRequest>,
     {
         self.make_request(
             |responder| ContractRuntimeRequest::Query {
                 query_request,
+                priority,
                 responder,
             },
             QueueKind::Regular,
@@ -1864,7 +2245,10 @@ impl<REv> EffectBuilder<REv> {
         REv: From<ContractRuntimeRequest>,
     {
         let query_request = QueryRequest::new(prestate_hash, account_key, vec![]);
-        match self.query_global_state(query_request).await {
+        match self
+            .query_global_state_with_priority(query_request, RequestPriority::Validation)
+            .await
+        {
             Ok(QueryResult::Success { value, .. }) => value.as_account().cloned(),
             Ok(_) | Err(_) => None,
         }
@@ -1875,19 +2259,17 @@ impl<REv> EffectBuilder<REv> {
         self,
         prestate_hash: Digest,
         main_purse: URef,
-    ) -> Option<U512>
+    ) -> Option<(U512, Box<TrieMerkleProof<Key, StoredValue>>)>
     where
         REv: From<ContractRuntimeRequest>,
     {
         let balance_request = BalanceRequest::new(prestate_hash, main_purse);
-        match self.get_balance(balance_request).await {
-            Ok(balance_result) => {
-                if let Some(motes) = balance_result.motes() {
-                    return Some(*motes);
-                }
-                None
-            }
-            Err(_) => None,
+        match self
+            .get_balance_with_priority(balance_request, RequestPriority::Validation)
+            .await
+        {
+            Ok(BalanceResult::Success { motes, proof }) => Some((motes, proof)),
+            Ok(_) | Err(_) => None,
         }
     }
 
@@ -1902,7 +2284,10 @@ impl<REv> EffectBuilder<REv> {
         REv: From<ContractRuntimeRequest>,
     {
         let query_request = QueryRequest::new(prestate_hash, query_key, path);
-        match self.query_global_state(query_request).await {
+        match self
+            .query_global_state_with_priority(query_request, RequestPriority::Validation)
+            .await
+        {
             Ok(QueryResult::Success { value, .. }) => value.as_contract().cloned(),
             Ok(_) | Err(_) => None,
         }
@@ -1919,7 +2304,10 @@ impl<REv> EffectBuilder<REv> {
         REv: From<ContractRuntimeRequest>,
     {
         let query_request = QueryRequest::new(prestate_hash, query_key, path);
-        match self.query_global_state(query_request).await {
+        match self
+            .query_global_state_with_priority(query_request, RequestPriority::Validation)
+            .await
+        {
             Ok(QueryResult::Success { value, .. }) => value.as_contract_package().cloned(),
             Ok(_) | Err(_) => None,
         }
@@ -1930,12 +2318,27 @@ impl<REv> EffectBuilder<REv> {
         self,
         balance_request: BalanceRequest,
     ) -> Result<BalanceResult, engine_state::Error>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.get_balance_with_priority(balance_request, RequestPriority::Query)
+            .await
+    }
+
+    /// Requests a balance query be executed on the Contract Runtime component with the given
+    /// priority.
+    async fn get_balance_with_priority(
+        self,
+        balance_request: BalanceRequest,
+        priority: RequestPriority,
+    ) -> Result<BalanceResult, engine_state::Error>
     where
         REv: From<ContractRuntimeRequest>,
     {
         self.make_request(
             |responder| ContractRuntimeRequest::GetBalance {
                 balance_request,
+                priority,
                 responder,
             },
             QueueKind::Regular,
@@ -1978,6 +2381,61 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Requests the balances of a number of purses from the Contract Runtime component.
+    pub(crate) async fn get_balances(
+        self,
+        get_balances_request: GetBalancesRequest,
+    ) -> Result<GetBalancesResult, engine_state::Error>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::GetBalances {
+                get_balances_request,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Requests a page of the items of a dictionary from the Contract Runtime component.
+    pub(crate) async fn get_dictionary_items(
+        self,
+        get_dictionary_items_request: GetDictionaryItemsRequest,
+    ) -> Result<GetDictionaryItemsResult, engine_state::Error>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::GetDictionaryItems {
+                get_dictionary_items_request,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Requests a page of the changes between two global states from the Contract Runtime
+    /// component.
+    pub(crate) async fn get_state_diff(
+        self,
+        get_state_diff_request: GetStateDiffRequest,
+    ) -> Result<GetStateDiffResult, engine_state::Error>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::GetStateDiff {
+                get_state_diff_request,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Gets the correct era validators set for the given era.
     /// Takes emergency restarts into account based on the information in the immediate switch
     /// block after a restart.
@@ -2103,6 +2561,93 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Returns the number of events waiting in each of the scheduler's queues.
+    pub(crate) fn diagnostics_port_queue_depths(self) -> QueueDepths {
+        QueueDepths(self.event_queue.event_queues_counts().into_iter().collect())
+    }
+
+    /// Returns the number of events dispatched to each component since the reactor started.
+    pub(crate) async fn diagnostics_port_event_counts(self) -> EventCounts
+    where
+        REv: From<ControlAnnouncement>,
+    {
+        self.make_request(
+            |responder| ControlAnnouncement::EventCountsRequest { responder },
+            QueueKind::Control,
+        )
+        .await
+    }
+
+    /// Returns statistics on how fairly the scheduler's queues have been serviced.
+    pub(crate) async fn diagnostics_port_queue_fairness(self) -> QueueFairness
+    where
+        REv: From<ControlAnnouncement>,
+    {
+        self.make_request(
+            |responder| ControlAnnouncement::QueueFairnessRequest { responder },
+            QueueKind::Control,
+        )
+        .await
+    }
+
+    /// Returns the events relating to the given deploy recorded so far, starting to trace it if it
+    /// isn't traced yet.  If `stop` is true, the deploy is no longer traced afterwards.
+    pub(crate) async fn diagnostics_port_trace_deploy(
+        self,
+        deploy_hash: DeployHash,
+        stop: bool,
+    ) -> DeployTrace
+    where
+        REv: From<ControlAnnouncement>,
+    {
+        self.make_request(
+            |responder| ControlAnnouncement::DeployTraceRequest {
+                deploy_hash,
+                stop,
+                responder,
+            },
+            QueueKind::Control,
+        )
+        .await
+    }
+
+    /// Returns the fetches of all fetchers which are waiting for responses from peers.
+    pub(crate) async fn diagnostics_port_in_flight_fetches(self) -> InFlightFetches
+    where
+        REv: From<ControlAnnouncement>,
+    {
+        self.make_request(
+            |responder| ControlAnnouncement::InFlightFetchesRequest { responder },
+            QueueKind::Control,
+        )
+        .await
+    }
+
+    /// Returns the estimated heap memory used by each component.
+    pub(crate) async fn diagnostics_port_memory_usage(self) -> MemoryUsage
+    where
+        REv: From<ControlAnnouncement>,
+    {
+        self.make_request(
+            |responder| ControlAnnouncement::MemoryUsageRequest { responder },
+            QueueKind::Control,
+        )
+        .await
+    }
+
+    /// Reloads the node-local settings which can be changed without restarting from the config
+    /// file, returning a description of the error if it failed.
+    pub(crate) async fn diagnostics_port_reload_config(self) -> Result<(), String>
+    where
+        REv: From<ControlAnnouncement>,
+    {
+        self.make_request(
+            |responder| ControlAnnouncement::ReloadConfigRequest { responder },
+            QueueKind::Control,
+        )
+        .await
+    }
+
     /// Get the bytes for the chainspec file and genesis_accounts
     /// and global_state bytes if the files are present.
     pub(crate) async fn get_chainspec_raw_bytes(self) -> Arc<ChainspecRawBytes>
@@ -2116,6 +2661,25 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Gets the parsed chainspec.
+    pub(crate) async fn get_chainspec(self) -> Arc<Chainspec>
+    where
+        REv: From<ChainspecLoaderRequest> + Send,
+    {
+        self.make_request(ChainspecLoaderRequest::GetChainspec, QueueKind::Regular)
+            .await
+    }
+
+    /// Gets the upgrade points installed on the node, including the one for the current protocol
+    /// version.
+    pub(crate) async fn get_upgrade_points(self) -> Vec<UpgradePointInfo>
+    where
+        REv: From<ChainspecLoaderRequest> + Send,
+    {
+        self.make_request(ChainspecLoaderRequest::GetUpgradePoints, QueueKind::Regular)
+            .await
+    }
+
     /// Stores a set of given finalized approvals in storage.
     ///
     /// Any previously stored finalized approvals for the given hash are quietly overwritten
@@ -2195,6 +2759,54 @@ impl<REv> EffectBuilder<REv> {
         )
         .await
     }
+
+    /// Requests execution of a single deploy, without commiting its effects, returning a breakdown
+    /// of the gas it consumed per host function and per class of Wasm opcodes.
+    pub(crate) async fn profile_deploy_gas(
+        self,
+        execution_prestate: SpeculativeExecutionState,
+        deploy: Deploy,
+    ) -> Result<Option<(ExecutionResult, GasProfile)>, engine_state::Error>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::ProfileDeployGas {
+                execution_prestate,
+                deploy: Box::new(deploy),
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Requests re-execution of a stored block, returning the resulting block and execution
+    /// results for comparison with those stored.
+    pub(crate) async fn replay_finalized_block(
+        self,
+        protocol_version: ProtocolVersion,
+        execution_pre_state: ExecutionPreState,
+        finalized_block: FinalizedBlock,
+        deploys: Vec<Deploy>,
+        transfers: Vec<Deploy>,
+    ) -> Result<BlockAndExecutionEffects, BlockExecutionError>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::ReplayBlock {
+                protocol_version,
+                execution_pre_state,
+                finalized_block,
+                deploys,
+                transfers,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
 }
 
 /// Construct a fatal error effect.
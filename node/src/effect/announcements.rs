@@ -4,9 +4,10 @@
 //! module documentation for details.
 
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet, HashMap},
     fmt::{self, Debug, Display, Formatter},
     fs::File,
+    time::Duration,
 };
 
 use itertools::Itertools;
@@ -16,11 +17,21 @@ use casper_types::{EraId, ExecutionEffect, ExecutionResult, PublicKey, Timestamp
 
 use crate::{
     components::{
-        chainspec_loader::NextUpgrade, deploy_acceptor::Error, diagnostics_port::FileSerializer,
+        chainspec_loader::NextUpgrade,
+        consensus::{ClContext, ProposedBlock},
+        contract_runtime::DeployExecutionProgress,
+        deploy_acceptor::{BalanceProof, Error, PendingDeployStatus},
+        diagnostics_port::FileSerializer,
+        linear_chain::ForkEvidence,
     },
-    effect::Responder,
+    effect::{
+        diagnostics_port::{DeployTrace, EventCounts, InFlightFetches, MemoryUsage, QueueFairness},
+        Responder,
+    },
+    reactor::supervision::SupervisedComponent,
     types::{
-        Block, Deploy, DeployHash, DeployHeader, FinalitySignature, FinalizedBlock, Item, NodeId,
+        chainspec::DeployConfig, Approval, Block, ContractEvent, Deploy, DeployHash, DeployHeader,
+        DeployWithApprovals, FinalitySignature, FinalizedBlock, Item, NodeId, SignedWeight,
     },
     utils::Source,
 };
@@ -55,6 +66,46 @@ pub(crate) enum ControlAnnouncement {
         /// Responder called when the dump has been finished.
         finished: Responder<()>,
     },
+    /// Two finalized blocks at the same height have been found, i.e. the linear chain has forked.
+    ///
+    /// The node keeps running, but the fork requires the operator's attention.
+    ForkDetected(Box<ForkEvidence>),
+    /// A reload of the node-local settings which can be changed without restarting has been
+    /// requested.
+    ReloadConfigRequest {
+        /// Responder called with the outcome of the reload.
+        responder: Responder<Result<(), String>>,
+    },
+    /// The number of events dispatched to each component has been requested.
+    EventCountsRequest {
+        /// Responder called with the event counts.
+        responder: Responder<EventCounts>,
+    },
+    /// Statistics on how fairly the event queues have been serviced have been requested.
+    QueueFairnessRequest {
+        /// Responder called with the queue fairness statistics.
+        responder: Responder<QueueFairness>,
+    },
+    /// The events relating to a deploy have been requested, starting to trace the deploy if it
+    /// isn't traced yet.
+    DeployTraceRequest {
+        /// The deploy to trace.
+        deploy_hash: DeployHash,
+        /// Whether to stop tracing the deploy after responding.
+        stop: bool,
+        /// Responder called with the events recorded so far.
+        responder: Responder<DeployTrace>,
+    },
+    /// The fetches waiting for responses from peers have been requested.
+    InFlightFetchesRequest {
+        /// Responder called with the in-flight fetches.
+        responder: Responder<InFlightFetches>,
+    },
+    /// An estimate of the heap memory used by each component has been requested.
+    MemoryUsageRequest {
+        /// Responder called with the memory usage estimates.
+        responder: Responder<MemoryUsage>,
+    },
 }
 
 /// Queue dump format with handler.
@@ -88,6 +139,29 @@ impl Debug for ControlAnnouncement {
                 .field("msg", msg)
                 .finish(),
             Self::QueueDumpRequest { .. } => f.debug_struct("QueueDump").finish_non_exhaustive(),
+            Self::ForkDetected(evidence) => f.debug_tuple("ForkDetected").field(evidence).finish(),
+            Self::ReloadConfigRequest { .. } => f
+                .debug_struct("ReloadConfigRequest")
+                .finish_non_exhaustive(),
+            Self::EventCountsRequest { .. } => {
+                f.debug_struct("EventCountsRequest").finish_non_exhaustive()
+            }
+            Self::QueueFairnessRequest { .. } => f
+                .debug_struct("QueueFairnessRequest")
+                .finish_non_exhaustive(),
+            Self::DeployTraceRequest {
+                deploy_hash, stop, ..
+            } => f
+                .debug_struct("DeployTraceRequest")
+                .field("deploy_hash", deploy_hash)
+                .field("stop", stop)
+                .finish_non_exhaustive(),
+            Self::InFlightFetchesRequest { .. } => f
+                .debug_struct("InFlightFetchesRequest")
+                .finish_non_exhaustive(),
+            Self::MemoryUsageRequest { .. } => {
+                f.debug_struct("MemoryUsageRequest").finish_non_exhaustive()
+            }
         }
     }
 }
@@ -101,6 +175,27 @@ impl Display for ControlAnnouncement {
             ControlAnnouncement::QueueDumpRequest { .. } => {
                 write!(f, "dump event queue")
             }
+            ControlAnnouncement::ForkDetected(evidence) => {
+                write!(f, "fork detected: {}", evidence)
+            }
+            ControlAnnouncement::ReloadConfigRequest { .. } => {
+                write!(f, "reload config")
+            }
+            ControlAnnouncement::EventCountsRequest { .. } => {
+                write!(f, "event counts")
+            }
+            ControlAnnouncement::QueueFairnessRequest { .. } => {
+                write!(f, "queue fairness")
+            }
+            ControlAnnouncement::DeployTraceRequest { deploy_hash, .. } => {
+                write!(f, "trace {}", deploy_hash)
+            }
+            ControlAnnouncement::InFlightFetchesRequest { .. } => {
+                write!(f, "in-flight fetches")
+            }
+            ControlAnnouncement::MemoryUsageRequest { .. } => {
+                write!(f, "memory usage")
+            }
         }
     }
 }
@@ -114,7 +209,24 @@ pub(crate) enum RpcServerAnnouncement {
         /// The received deploy.
         deploy: Box<Deploy>,
         /// A client responder in the case where a client submits a deploy.
-        responder: Option<Responder<Result<(), Error>>>,
+        responder: Option<Responder<Result<Option<BalanceProof>, Error>>>,
+    },
+    /// A new deploy received, which may lack approvals needed to meet its account's deployment
+    /// threshold.
+    PendingDeployReceived {
+        /// The received deploy.
+        deploy: Box<Deploy>,
+        /// A client responder reporting whether the deploy was accepted or awaits approvals.
+        responder: Responder<Result<PendingDeployStatus, Error>>,
+    },
+    /// Further approvals received for a deploy awaiting approvals.
+    DeployApprovalsReceived {
+        /// The hash of the pending deploy.
+        deploy_hash: DeployHash,
+        /// The received approvals.
+        approvals: BTreeSet<Approval>,
+        /// A client responder reporting whether the deploy was accepted or awaits approvals.
+        responder: Responder<Result<PendingDeployStatus, Error>>,
     },
 }
 
@@ -124,6 +236,19 @@ impl Display for RpcServerAnnouncement {
             RpcServerAnnouncement::DeployReceived { deploy, .. } => {
                 write!(formatter, "api server received {}", deploy.id())
             }
+            RpcServerAnnouncement::PendingDeployReceived { deploy, .. } => {
+                write!(formatter, "api server received pending {}", deploy.id())
+            }
+            RpcServerAnnouncement::DeployApprovalsReceived {
+                deploy_hash,
+                approvals,
+                ..
+            } => write!(
+                formatter,
+                "api server received {} approvals for {}",
+                approvals.len(),
+                deploy_hash
+            ),
         }
     }
 }
@@ -146,6 +271,14 @@ pub(crate) enum DeployAcceptorAnnouncement {
         /// The source (peer or client) of the deploy.
         source: Source,
     },
+
+    /// Approvals not previously known for an already stored deploy have been accepted and stored.
+    NewDeployApprovals {
+        /// The deploy hash along with the newly learned approvals.
+        deploy_approvals: Box<DeployWithApprovals>,
+        /// The source (peer or client) of the approvals.
+        source: Source,
+    },
 }
 
 impl Display for DeployAcceptorAnnouncement {
@@ -160,6 +293,10 @@ impl Display for DeployAcceptorAnnouncement {
             DeployAcceptorAnnouncement::InvalidDeploy { deploy, source } => {
                 write!(formatter, "invalid deploy {} from {}", deploy.id(), source)
             }
+            DeployAcceptorAnnouncement::NewDeployApprovals {
+                deploy_approvals,
+                source,
+            } => write!(formatter, "new {} from {}", deploy_approvals, source),
         }
     }
 }
@@ -186,6 +323,8 @@ impl Display for BlockProposerAnnouncement {
 pub(crate) enum ConsensusAnnouncement {
     /// A block was finalized.
     Finalized(Box<FinalizedBlock>),
+    /// A block proposed by another validator was found to be valid.
+    ProposedBlock(Box<ProposedBlock<ClContext>>),
     /// A finality signature was created.
     CreatedFinalitySignature(Box<FinalitySignature>),
     /// An equivocation has been detected.
@@ -197,6 +336,13 @@ pub(crate) enum ConsensusAnnouncement {
         /// The timestamp when the evidence of the equivocation was detected.
         timestamp: Timestamp,
     },
+    /// Consensus started a new era, which is now the current one.
+    EraValidators {
+        /// The ID of the era.
+        era_id: EraId,
+        /// The validators of the era.
+        validators: BTreeSet<PublicKey>,
+    },
 }
 
 impl Display for ConsensusAnnouncement {
@@ -205,6 +351,9 @@ impl Display for ConsensusAnnouncement {
             ConsensusAnnouncement::Finalized(block) => {
                 write!(formatter, "finalized block payload {}", block)
             }
+            ConsensusAnnouncement::ProposedBlock(proposed_block) => {
+                write!(formatter, "valid {}", proposed_block)
+            }
             ConsensusAnnouncement::CreatedFinalitySignature(fs) => {
                 write!(formatter, "signed an executed block: {}", fs)
             }
@@ -217,6 +366,12 @@ impl Display for ConsensusAnnouncement {
                 "Validator fault with public key: {} has been identified at time: {} in era: {}",
                 public_key, timestamp, era_id,
             ),
+            ConsensusAnnouncement::EraValidators { era_id, validators } => write!(
+                formatter,
+                "era {} started with {} validators",
+                era_id,
+                validators.len()
+            ),
         }
     }
 }
@@ -264,8 +419,9 @@ impl<T: Item> Display for GossiperAnnouncement<T> {
 pub(crate) enum LinearChainAnnouncement {
     /// A new block has been created and stored locally.
     BlockAdded(Box<Block>),
-    /// New finality signature received.
-    NewFinalitySignature(Box<FinalitySignature>),
+    /// New finality signature received, along with the cumulative weight of the block's known
+    /// signatures if the validators of the block's era are known.
+    NewFinalitySignature(Box<FinalitySignature>, Option<Box<SignedWeight>>),
 }
 
 impl Display for LinearChainAnnouncement {
@@ -274,7 +430,7 @@ impl Display for LinearChainAnnouncement {
             LinearChainAnnouncement::BlockAdded(block) => {
                 write!(f, "block added {}", block.hash())
             }
-            LinearChainAnnouncement::NewFinalitySignature(fs) => {
+            LinearChainAnnouncement::NewFinalitySignature(fs, _) => {
                 write!(f, "new finality signature {}", fs.block_hash)
             }
         }
@@ -308,7 +464,11 @@ pub(crate) enum ContractRuntimeAnnouncement {
         /// The results of executing the deploys in this block.
         // #[serde(skip_serializing)]
         execution_results: Vec<(DeployHash, DeployHeader, ExecutionResult)>,
+        /// The events emitted by contracts while executing the deploys, by deploy hash.
+        contract_events: HashMap<DeployHash, Vec<ContractEvent>>,
     },
+    /// Progress was made executing a deploy in a block.
+    DeployExecutionProgress(Box<DeployExecutionProgress>),
     /// A step was committed successfully and has altered global state.
     CommitStepSuccess {
         /// The era id in which the step was committed to global state.
@@ -323,6 +483,13 @@ pub(crate) enum ContractRuntimeAnnouncement {
         /// The validators for the eras after the `era_that_is_ending` era.
         upcoming_era_validators: BTreeMap<EraId, BTreeMap<PublicKey, U512>>,
     },
+    /// The deploy limits read from the on-chain configuration contract at an era boundary.
+    DeployConfigUpdated {
+        /// The era from which the deploy limits apply.
+        era_id: EraId,
+        /// The deploy config with the governed limits applied.
+        deploy_config: DeployConfig,
+    },
 }
 
 impl Display for ContractRuntimeAnnouncement {
@@ -331,6 +498,16 @@ impl Display for ContractRuntimeAnnouncement {
             ContractRuntimeAnnouncement::LinearChainBlock { block, .. } => {
                 write!(f, "created linear chain block {}", block.hash())
             }
+            ContractRuntimeAnnouncement::DeployExecutionProgress(progress) => {
+                match progress.as_ref() {
+                    DeployExecutionProgress::Started { deploy_hash, .. } => {
+                        write!(f, "started executing deploy {}", deploy_hash)
+                    }
+                    DeployExecutionProgress::Finished { deploy_hash, .. } => {
+                        write!(f, "finished executing deploy {}", deploy_hash)
+                    }
+                }
+            }
             ContractRuntimeAnnouncement::CommitStepSuccess { era_id, .. } => {
                 write!(f, "commit step completed for {}", era_id)
             }
@@ -343,6 +520,9 @@ impl Display for ContractRuntimeAnnouncement {
                     era_that_is_ending,
                 )
             }
+            ContractRuntimeAnnouncement::DeployConfigUpdated { era_id, .. } => {
+                write!(f, "deploy config updated for {}", era_id)
+            }
         }
     }
 }
@@ -366,3 +546,51 @@ impl Display for ChainSynchronizerAnnouncement {
         }
     }
 }
+
+/// A component supervisor announcement.
+#[derive(Debug, Serialize)]
+pub(crate) enum SupervisorAnnouncement {
+    /// A supervised component panicked, or failed to restart, and has been dropped.
+    ComponentCrashed {
+        /// The crashed component.
+        component: SupervisedComponent,
+        /// The panic message or restart error.
+        message: String,
+        /// The delay after which the component should be restarted.
+        restart_delay: Duration,
+    },
+    /// A task spawned by a supervised component panicked.
+    TaskPanicked {
+        /// The component which spawned the task.
+        component: SupervisedComponent,
+        /// The number of restarts of the component before it spawned the task.
+        generation: u64,
+        /// The panic message.
+        message: String,
+    },
+}
+
+impl Display for SupervisorAnnouncement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SupervisorAnnouncement::ComponentCrashed {
+                component,
+                message,
+                restart_delay,
+            } => write!(
+                f,
+                "{} crashed, restarting in {:?}: {}",
+                component, restart_delay, message
+            ),
+            SupervisorAnnouncement::TaskPanicked {
+                component,
+                generation,
+                message,
+            } => write!(
+                f,
+                "task of {} (generation {}) panicked: {}",
+                component, generation, message
+            ),
+        }
+    }
+}
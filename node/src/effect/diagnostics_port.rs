@@ -1,15 +1,20 @@
 use std::{
     borrow::Cow,
-    fmt::{Debug, Display},
+    collections::BTreeMap,
+    fmt::{self, Debug, Display, Formatter},
 };
 
-use casper_types::EraId;
-use datasize::DataSize;
+use casper_types::{EraId, TimeDiff, Timestamp};
+use datasize::{DataSize, MemUsageNode};
 use futures::Future;
 use serde::Serialize;
 
 use super::Responder;
-use crate::components::consensus::EraDump;
+use crate::{
+    components::consensus::EraDump,
+    reactor::{correlation::CorrelationId, QueueKind},
+    types::{DeployHash, NodeId, Tag},
+};
 
 /// A request to dump the internal consensus state of a specific era.
 #[derive(DataSize, Serialize)]
@@ -58,3 +63,211 @@ impl Debug for DumpConsensusStateRequest {
             .finish_non_exhaustive()
     }
 }
+
+/// The number of events waiting in each of the scheduler's queues.
+#[derive(Debug, Default, Serialize)]
+#[serde(transparent)]
+pub(crate) struct QueueDepths(pub(crate) BTreeMap<QueueKind, usize>);
+
+impl Display for QueueDepths {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        for (queue_kind, depth) in &self.0 {
+            writeln!(formatter, "{}: {}", queue_kind, depth)?;
+        }
+        write!(formatter, "total: {}", self.0.values().sum::<usize>())
+    }
+}
+
+/// The number of events dispatched to each component since the reactor started.
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(transparent)]
+pub(crate) struct EventCounts(pub(crate) BTreeMap<&'static str, u64>);
+
+impl Display for EventCounts {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        for (event_description, count) in &self.0 {
+            writeln!(formatter, "{}: {}", event_description, count)?;
+        }
+        write!(formatter, "total: {}", self.0.values().sum::<u64>())
+    }
+}
+
+/// A fetch of an item which is waiting for responses from peers.
+#[derive(Debug, Serialize)]
+pub(crate) struct InFlightFetch {
+    /// The type of the item.
+    pub(crate) tag: Tag,
+    /// The ID of the item.
+    pub(crate) id: String,
+    /// The peers the item has been requested from, with the number of requests waiting on each.
+    pub(crate) peers: BTreeMap<NodeId, usize>,
+}
+
+/// The fetches of all fetchers which are waiting for responses from peers.
+#[derive(Debug, Default, Serialize)]
+#[serde(transparent)]
+pub(crate) struct InFlightFetches(pub(crate) Vec<InFlightFetch>);
+
+impl Display for InFlightFetches {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        for fetch in &self.0 {
+            write!(formatter, "{} {} from", fetch.tag, fetch.id)?;
+            for (peer, requests) in &fetch.peers {
+                write!(formatter, " {} ({} requests)", peer, requests)?;
+            }
+            writeln!(formatter)?;
+        }
+        write!(formatter, "total: {}", self.0.len())
+    }
+}
+
+/// The estimated heap memory used by each component.
+#[derive(Debug, Default, Serialize)]
+#[serde(transparent)]
+pub(crate) struct MemoryUsage(pub(crate) BTreeMap<&'static str, ComponentMemoryUsage>);
+
+impl MemoryUsage {
+    /// Returns the estimated heap memory used by all components, in bytes.
+    pub(crate) fn total(&self) -> usize {
+        self.0.values().map(|usage| usage.total).sum()
+    }
+}
+
+impl Display for MemoryUsage {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        for (component, usage) in &self.0 {
+            writeln!(formatter, "{}: {} bytes", component, usage.total)?;
+            for (part, bytes) in &usage.parts {
+                writeln!(formatter, "  {}: {} bytes", part, bytes)?;
+            }
+        }
+        write!(formatter, "total: {} bytes", self.total())
+    }
+}
+
+/// The estimated heap memory used by a single component, in bytes.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct ComponentMemoryUsage {
+    /// The heap memory used by the whole component.
+    pub(crate) total: usize,
+    /// The heap memory used by each part of the component, e.g. its tables, caches and buffers.
+    pub(crate) parts: BTreeMap<String, usize>,
+}
+
+impl ComponentMemoryUsage {
+    /// Returns the heap memory used by the given component, broken down by its fields.
+    ///
+    /// Components implementing `DataSize` manually rather than deriving it are not broken down.
+    pub(crate) fn of<T: DataSize>(component: &T) -> Self {
+        let detailed = component.estimate_detailed_heap_size();
+        let total = detailed.total();
+        let parts = match detailed {
+            MemUsageNode::Size(_) => BTreeMap::new(),
+            MemUsageNode::Detailed(fields) => fields
+                .into_iter()
+                .map(|(field, usage)| (field.to_string(), usage.total()))
+                .collect(),
+        };
+        ComponentMemoryUsage { total, parts }
+    }
+}
+
+/// How fairly the scheduler's queues have been serviced since the reactor started.
+#[derive(Debug, Default, Serialize)]
+#[serde(transparent)]
+pub(crate) struct QueueFairness(pub(crate) BTreeMap<QueueKind, QueueFairnessStats>);
+
+/// How fairly one of the scheduler's queues has been serviced.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct QueueFairnessStats {
+    /// The number of events dispatched from the queue.
+    pub(crate) dispatched: u64,
+    /// The mean time dispatched events waited in the queue.
+    pub(crate) mean_wait: TimeDiff,
+    /// The longest time a dispatched event waited in the queue.
+    pub(crate) max_wait: TimeDiff,
+    /// How long the oldest event still in the queue has been waiting, if the queue is not empty.
+    pub(crate) oldest_waiting: Option<TimeDiff>,
+    /// The time since an event was last dispatched from the queue, if any has been.
+    pub(crate) since_last_dispatch: Option<TimeDiff>,
+    /// The number of times the queue was found to be starved.
+    pub(crate) starvations: u64,
+}
+
+impl Display for QueueFairness {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        for (index, (queue_kind, stats)) in self.0.iter().enumerate() {
+            if index > 0 {
+                writeln!(formatter)?;
+            }
+            write!(
+                formatter,
+                "{}: {} dispatched, mean wait {}, max wait {}",
+                queue_kind, stats.dispatched, stats.mean_wait, stats.max_wait
+            )?;
+            if let Some(oldest_waiting) = stats.oldest_waiting {
+                write!(formatter, ", oldest waiting {}", oldest_waiting)?;
+            }
+            if let Some(since_last_dispatch) = stats.since_last_dispatch {
+                write!(formatter, ", last dispatch {} ago", since_last_dispatch)?;
+            }
+            write!(formatter, ", {} starvations", stats.starvations)?;
+        }
+        Ok(())
+    }
+}
+
+/// The events relating to a traced deploy.
+#[derive(Debug, Serialize)]
+pub(crate) struct DeployTrace {
+    /// The traced deploy.
+    pub(crate) deploy_hash: DeployHash,
+    /// The time at which tracing started, if the deploy was being traced.
+    pub(crate) tracing_since: Option<Timestamp>,
+    /// The number of steps dropped as the trace grew too long.
+    pub(crate) dropped_steps: u64,
+    /// The recorded steps, oldest first.
+    pub(crate) steps: Vec<DeployTraceStep>,
+}
+
+/// An event relating to a traced deploy.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct DeployTraceStep {
+    /// The time at which the event was dispatched.
+    pub(crate) timestamp: Timestamp,
+    /// The ID of the event.
+    pub(crate) event_id: u64,
+    /// The correlation ID of the event.
+    pub(crate) correlation_id: CorrelationId,
+    /// The queue the event was scheduled on.
+    pub(crate) queue: QueueKind,
+    /// The description of the event, naming the component it was dispatched to.
+    pub(crate) component: &'static str,
+    /// The event.
+    pub(crate) event: String,
+}
+
+impl Display for DeployTrace {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self.tracing_since {
+            Some(since) => write!(formatter, "{} traced since {}", self.deploy_hash, since)?,
+            None => write!(formatter, "{} is not being traced", self.deploy_hash)?,
+        }
+        if self.dropped_steps > 0 {
+            write!(formatter, ", {} oldest steps dropped", self.dropped_steps)?;
+        }
+        for step in &self.steps {
+            write!(
+                formatter,
+                "\n{} ev={} cid={} [{}] {}: {}",
+                step.timestamp,
+                step.event_id,
+                step.correlation_id,
+                step.queue,
+                step.component,
+                step.event
+            )?;
+        }
+        Ok(())
+    }
+}
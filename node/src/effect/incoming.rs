@@ -11,7 +11,7 @@ use serde::Serialize;
 use crate::{
     components::{consensus, gossiper},
     protocol::Message,
-    types::{FinalitySignature, NodeId, Tag},
+    types::{DeployWithApprovals, FinalitySignature, NodeId, Tag},
 };
 
 use super::AutoClosingResponder;
@@ -76,6 +76,9 @@ pub(crate) type TrieResponseIncoming = MessageIncoming<TrieResponse>;
 /// A new finality signature arrived over the network.
 pub(crate) type FinalitySignatureIncoming = MessageIncoming<Box<FinalitySignature>>;
 
+/// Approvals of an already known deploy arrived over the network.
+pub(crate) type DeployApprovalsIncoming = MessageIncoming<Box<DeployWithApprovals>>;
+
 /// A request for an object out of storage arrived.
 ///
 /// Note: The variants here are grouped under a common enum, since they are usually handled by the
@@ -105,6 +108,8 @@ pub(crate) enum NetRequest {
     BlockHeadersBatch(Vec<u8>),
     /// Request for finality signatures for a block.
     FinalitySignatures(Vec<u8>),
+    /// Request for all finality signatures held for a block, to backfill those of a stored block.
+    BackfillFinalitySignatures(Vec<u8>),
 }
 
 impl Display for NetRequest {
@@ -124,6 +129,9 @@ impl Display for NetRequest {
             NetRequest::BlockAndDeploys(_) => f.write_str("request for a block and its deploys"),
             NetRequest::BlockHeadersBatch(_) => f.write_str("request for block headers batch"),
             NetRequest::FinalitySignatures(_) => f.write_str("request for finality signatures"),
+            NetRequest::BackfillFinalitySignatures(_) => {
+                f.write_str("request for finality signatures to backfill")
+            }
         }
     }
 }
@@ -146,6 +154,7 @@ impl NetRequest {
             NetRequest::BlockAndDeploys(ref id) => id,
             NetRequest::BlockHeadersBatch(ref id) => id,
             NetRequest::FinalitySignatures(ref id) => id,
+            NetRequest::BackfillFinalitySignatures(ref id) => id,
         };
         let mut unique_id = Vec::with_capacity(id.len() + 1);
         unique_id.push(self.tag() as u8);
@@ -169,6 +178,7 @@ impl NetRequest {
             NetRequest::BlockAndDeploys(_) => Tag::BlockAndDeploysByHash,
             NetRequest::BlockHeadersBatch(_) => Tag::BlockHeaderBatch,
             NetRequest::FinalitySignatures(_) => Tag::FinalitySignaturesByHash,
+            NetRequest::BackfillFinalitySignatures(_) => Tag::BackfillFinalitySignaturesByHash,
         }
     }
 }
@@ -208,6 +218,8 @@ pub(crate) enum NetResponse {
     BlockHeadersBatch(Arc<[u8]>),
     /// Response of finality signatures.
     FinalitySignatures(Arc<[u8]>),
+    /// Response of finality signatures to backfill.
+    BackfillFinalitySignatures(Arc<[u8]>),
 }
 
 // `NetResponse` uses `Arcs`, so we count all data as 0.
@@ -238,6 +250,9 @@ impl Display for NetResponse {
             NetResponse::BlockAndDeploys(_) => f.write_str("response, block and deploys"),
             NetResponse::BlockHeadersBatch(_) => f.write_str("response for block-headers-batch"),
             NetResponse::FinalitySignatures(_) => f.write_str("response for finality signatures"),
+            NetResponse::BackfillFinalitySignatures(_) => {
+                f.write_str("response for finality signatures to backfill")
+            }
         }
     }
 }
@@ -5,7 +5,7 @@
 
 use std::{
     borrow::Cow,
-    collections::{BTreeMap, HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fmt::{self, Debug, Display, Formatter},
     mem,
     sync::Arc,
@@ -22,37 +22,46 @@ use casper_execution_engine::{
         balance::{BalanceRequest, BalanceResult},
         era_validators::GetEraValidatorsError,
         genesis::GenesisSuccess,
+        get_balances::{BalanceIdentifier, GetBalancesRequest, GetBalancesResult},
         get_bids::{GetBidsRequest, GetBidsResult},
+        get_dictionary_items::{GetDictionaryItemsRequest, GetDictionaryItemsResult},
+        get_state_diff::{GetStateDiffRequest, GetStateDiffResult},
         query::{QueryRequest, QueryResult},
-        UpgradeConfig, UpgradeSuccess,
+        GasProfile, UpgradeConfig, UpgradeSuccess,
     },
     storage::trie::{TrieOrChunk, TrieOrChunkId},
 };
 use casper_hashing::Digest;
 use casper_types::{
-    bytesrepr::Bytes, system::auction::EraValidators, EraId, ExecutionResult, Key, ProtocolVersion,
-    PublicKey, TimeDiff, Transfer, URef,
+    account::AccountHash, bytesrepr::Bytes, system::auction::EraValidators, DictionaryAddr, EraId,
+    ExecutionResult, Key, ProtocolVersion, PublicKey, TimeDiff, Transfer, URef,
 };
 
 use crate::{
     components::{
         block_validator::ValidatingBlock,
+        chain_synchronizer::SyncProgress,
+        chainspec_loader::UpgradePointInfo,
         consensus::{BlockContext, ClContext, ValidatorChange},
         contract_runtime::{
             BlockAndExecutionEffects, BlockExecutionError, EraValidatorsRequest, ExecutionPreState,
+            RequestPriority,
         },
-        deploy_acceptor::Error,
+        deploy_acceptor::{BalanceProof, Error, PendingDeployStatus},
         fetcher::FetchResult,
+        rest_server::Readiness,
+        small_network::PeerDetails,
     },
     contract_runtime::SpeculativeExecutionState,
     effect::{AutoClosingResponder, Responder},
     rpcs::{chain::BlockIdentifier, docs::OpenRpcSchema},
     types::{
-        AvailableBlockRange, Block, BlockAndDeploys, BlockHash, BlockHeader,
-        BlockHeaderWithMetadata, BlockHeadersBatch, BlockHeadersBatchId, BlockPayload,
-        BlockSignatures, BlockWithMetadata, Chainspec, ChainspecInfo, ChainspecRawBytes, Deploy,
-        DeployHash, DeployMetadataExt, DeployWithFinalizedApprovals, FinalizedApprovals,
-        FinalizedBlock, Item, NodeId, NodeState, StatusFeed,
+        AccountDeploys, Approval, AvailableBlockRange, Block, BlockAndDeploys, BlockHash,
+        BlockHeader, BlockHeaderWithMetadata, BlockHeadersBatch, BlockHeadersBatchId, BlockHeight,
+        BlockPayload, BlockSignatures, BlockWithMetadata, Chainspec, ChainspecInfo,
+        ChainspecRawBytes, ContractEvent, Deploy, DeployHash, DeployMetadataExt,
+        DeployWithApprovals, DeployWithFinalizedApprovals, EraValidatorPerformance,
+        FinalizedApprovals, FinalizedBlock, Item, NodeId, NodeState, StatusFeed,
     },
     utils::{DisplayIter, Source},
 };
@@ -195,6 +204,11 @@ pub(crate) enum NetworkInfoRequest {
         /// Responds with a map from [NodeId]s to a socket address, represented as a string.
         responder: Responder<BTreeMap<NodeId, String>>,
     },
+    /// Get the details of all connected peers.
+    DetailedPeers {
+        /// Responder to be called with the details of all connected peers.
+        responder: Responder<Vec<PeerDetails>>,
+    },
     /// Get the peers in random order.
     FullyConnectedPeers {
         /// Responder to be called with all connected in random order peers.
@@ -205,6 +219,12 @@ pub(crate) enum NetworkInfoRequest {
         /// Responder to be called with all connected non-syncing peers in random order.
         responder: Responder<Vec<NodeId>>,
     },
+    /// Get only peers serving finality signatures to backfill, in random order.
+    FullyConnectedFinalitySignatureBackfillPeers {
+        /// Responder to be called with all connected peers serving finality signatures to
+        /// backfill, in random order.
+        responder: Responder<Vec<NodeId>>,
+    },
 }
 
 impl Display for NetworkInfoRequest {
@@ -213,12 +233,21 @@ impl Display for NetworkInfoRequest {
             NetworkInfoRequest::Peers { responder: _ } => {
                 write!(formatter, "get peers-to-socket-address map")
             }
+            NetworkInfoRequest::DetailedPeers { responder: _ } => {
+                write!(formatter, "get detailed peers")
+            }
             NetworkInfoRequest::FullyConnectedPeers { responder: _ } => {
                 write!(formatter, "get fully connected peers")
             }
             NetworkInfoRequest::FullyConnectedNonSyncingPeers { responder: _ } => {
                 write!(formatter, "get fully connected non-syncing peers")
             }
+            NetworkInfoRequest::FullyConnectedFinalitySignatureBackfillPeers { responder: _ } => {
+                write!(
+                    formatter,
+                    "get fully connected peers serving finality signatures to backfill"
+                )
+            }
         }
     }
 }
@@ -324,7 +353,7 @@ pub(crate) enum StorageRequest {
     },
     GetBlockHeaderByHeight {
         /// Height of block to get header of.
-        block_height: u64,
+        block_height: BlockHeight,
         /// Flag indicating whether storage should check the block availability before trying to
         /// retrieve it.
         only_from_available_block_range: bool,
@@ -335,7 +364,7 @@ pub(crate) enum StorageRequest {
     /// Checks if a block header at the given height exists in storage.
     CheckBlockHeaderExistence {
         /// Height of the block to check.
-        block_height: u64,
+        block_height: BlockHeight,
         /// Responder to call with the result.
         responder: Responder<bool>,
     },
@@ -355,6 +384,14 @@ pub(crate) enum StorageRequest {
         /// attempt or false if it was previously stored.
         responder: Responder<bool>,
     },
+    /// Merge the given approvals into the ones known for a stored deploy.
+    PutDeployApprovals {
+        /// The deploy hash and approvals to merge.
+        deploy_approvals: Box<DeployWithApprovals>,
+        /// Responder to call with the approvals which were not previously known, which is empty
+        /// if the deploy is not stored.
+        responder: Responder<BTreeSet<Approval>>,
+    },
     /// Retrieve deploys with given hashes.
     GetDeploys {
         /// Hashes of deploys to be retrieved.
@@ -385,6 +422,20 @@ pub(crate) enum StorageRequest {
         /// Responder to call when done storing.
         responder: Responder<()>,
     },
+    /// Store the events emitted by contracts while executing deploys.
+    PutContractEvents {
+        /// The events, in the order emitted, by the hash of the deploy emitting them.
+        contract_events: HashMap<DeployHash, Vec<ContractEvent>>,
+        /// Responder to call when done storing.
+        responder: Responder<()>,
+    },
+    /// Retrieve the events emitted by contracts while executing a deploy.
+    GetContractEvents {
+        /// Hash of the deploy.
+        deploy_hash: DeployHash,
+        /// Responder to call with the events, if any.
+        responder: Responder<Option<Vec<ContractEvent>>>,
+    },
     /// Retrieve deploy and its metadata.
     GetDeployAndMetadata {
         /// Hash of deploy to be retrieved.
@@ -392,6 +443,45 @@ pub(crate) enum StorageRequest {
         /// Responder to call with the results.
         responder: Responder<Option<(DeployWithFinalizedApprovals, DeployMetadataExt)>>,
     },
+    /// Retrieve the given deploys contained in a block, along with their execution results in that
+    /// block.
+    GetBlockDeploysWithExecutionResults {
+        /// Hash of the block containing the deploys.
+        block_hash: BlockHash,
+        /// Hashes of the deploys to be retrieved.
+        deploy_hashes: Vec<DeployHash>,
+        /// Responder to call with the results, `None` if any of the deploys is not stored.
+        responder: Responder<Option<Vec<(Deploy, Option<ExecutionResult>)>>>,
+    },
+    /// Retrieve deploys sent by the given account, most recent first.
+    GetAccountDeploys {
+        /// Hash of the account which sent the deploys.
+        account_hash: AccountHash,
+        /// If provided, only deploys after this position, as returned in
+        /// `AccountDeploys::next_page_after`, are retrieved.
+        after: Option<Vec<u8>>,
+        /// Maximum number of deploys to return.
+        limit: u32,
+        /// Responder to call with the results.
+        responder: Responder<AccountDeploys>,
+    },
+    /// Store the number of rounds each validator missed during the given era.
+    PutEraMissedRounds {
+        /// The era in which the rounds were missed.
+        era_id: EraId,
+        /// The number of rounds missed, by validator.
+        missed_rounds: BTreeMap<PublicKey, u64>,
+        /// Responder to call once the counts are stored.
+        responder: Responder<()>,
+    },
+    /// Retrieve how each validator performed during the given era.
+    GetEraValidatorPerformance {
+        /// The era to retrieve the performance for.
+        era_id: EraId,
+        /// Responder to call with the result.  Returns `None` if no performance is stored for the
+        /// era.
+        responder: Responder<Option<EraValidatorPerformance>>,
+    },
     /// Retrieve block and its metadata by its hash.
     GetBlockAndMetadataByHash {
         /// The hash of the block.
@@ -531,15 +621,52 @@ impl Display for StorageRequest {
                 write!(formatter, "get transfers for {}", block_hash)
             }
             StorageRequest::PutDeploy { deploy, .. } => write!(formatter, "put {}", deploy),
+            StorageRequest::PutDeployApprovals {
+                deploy_approvals, ..
+            } => write!(formatter, "put {}", deploy_approvals),
             StorageRequest::GetDeploys { deploy_hashes, .. } => {
                 write!(formatter, "get {}", DisplayIter::new(deploy_hashes.iter()))
             }
             StorageRequest::PutExecutionResults { block_hash, .. } => {
                 write!(formatter, "put execution results for {}", block_hash)
             }
+            StorageRequest::PutContractEvents {
+                contract_events, ..
+            } => {
+                write!(
+                    formatter,
+                    "put contract events for {} deploys",
+                    contract_events.len()
+                )
+            }
+            StorageRequest::GetContractEvents { deploy_hash, .. } => {
+                write!(formatter, "get contract events for {}", deploy_hash)
+            }
             StorageRequest::GetDeployAndMetadata { deploy_hash, .. } => {
                 write!(formatter, "get deploy and metadata for {}", deploy_hash)
             }
+            StorageRequest::GetBlockDeploysWithExecutionResults { block_hash, .. } => {
+                write!(
+                    formatter,
+                    "get deploys and execution results for {}",
+                    block_hash
+                )
+            }
+            StorageRequest::GetAccountDeploys {
+                account_hash,
+                limit,
+                ..
+            } => write!(
+                formatter,
+                "get up to {} deploys sent by {}",
+                limit, account_hash
+            ),
+            StorageRequest::PutEraMissedRounds { era_id, .. } => {
+                write!(formatter, "put missed rounds for era {}", era_id)
+            }
+            StorageRequest::GetEraValidatorPerformance { era_id, .. } => {
+                write!(formatter, "get validator performance for era {}", era_id)
+            }
             StorageRequest::GetBlockAndMetadataByHash { block_hash, .. } => {
                 write!(
                     formatter,
@@ -619,7 +746,7 @@ impl Display for StorageRequest {
 #[derive(Debug, Serialize)]
 pub(crate) struct MarkBlockCompletedRequest {
     /// Height of the block that was completed.
-    pub block_height: u64,
+    pub block_height: BlockHeight,
     /// Responder indicating that the change has been recorded.
     pub responder: Responder<()>,
 }
@@ -728,8 +855,24 @@ pub(crate) enum RpcRequest {
     SubmitDeploy {
         /// The deploy to be announced.
         deploy: Box<Deploy>,
+        /// Responder to call with the proof of the account's balance if the deploy is accepted.
+        responder: Responder<Result<Option<BalanceProof>, Error>>,
+    },
+    /// Submit a deploy which may lack approvals needed to meet its account's deployment threshold.
+    SubmitPendingDeploy {
+        /// The deploy to be announced.
+        deploy: Box<Deploy>,
+        /// Responder to call.
+        responder: Responder<Result<PendingDeployStatus, Error>>,
+    },
+    /// Add approvals to a deploy submitted via `SubmitPendingDeploy`.
+    AddDeployApprovals {
+        /// The hash of the pending deploy.
+        deploy_hash: DeployHash,
+        /// The approvals to be added.
+        approvals: BTreeSet<Approval>,
         /// Responder to call.
-        responder: Responder<Result<(), Error>>,
+        responder: Responder<Result<PendingDeployStatus, Error>>,
     },
     /// If `maybe_identifier` is `Some`, return the specified block if it exists, else `None`.  If
     /// `maybe_identifier` is `None`, return the latest block.
@@ -742,6 +885,22 @@ pub(crate) enum RpcRequest {
         /// Responder to call with the result.
         responder: Responder<Option<BlockWithMetadata>>,
     },
+    /// If `maybe_id` is `Some`, return the specified block header and its finality signatures if
+    /// they exist, else `None`.  If `maybe_id` is `None`, return the latest block header.
+    GetBlockHeader {
+        /// The identifier (can either be a hash or the height) of the block header to be
+        /// retrieved.
+        maybe_id: Option<BlockIdentifier>,
+        /// Responder to call with the result.
+        responder: Responder<Option<BlockHeaderWithMetadata>>,
+    },
+    /// Return the header of the switch block which ended the given era, if it is stored.
+    GetSwitchBlockHeader {
+        /// The ID of the era ended by the switch block.
+        era_id: EraId,
+        /// Responder to call with the result.
+        responder: Responder<Option<BlockHeader>>,
+    },
     /// Return transfers for block by hash (if any).
     GetBlockTransfers {
         /// The hash of the block to retrieve transfers for.
@@ -749,6 +908,16 @@ pub(crate) enum RpcRequest {
         /// Responder to call with the result.
         responder: Responder<Option<Vec<Transfer>>>,
     },
+    /// Return the given deploys contained in a block, along with their execution results in that
+    /// block, or `None` if any of the deploys is not stored.
+    GetBlockDeploys {
+        /// The hash of the block containing the deploys.
+        block_hash: BlockHash,
+        /// The hashes of the deploys to retrieve.
+        deploy_hashes: Vec<DeployHash>,
+        /// Responder to call with the result.
+        responder: Responder<Option<Vec<(Deploy, Option<ExecutionResult>)>>>,
+    },
     /// Query the global state at the given root hash.
     QueryGlobalState {
         /// The state root hash.
@@ -776,6 +945,42 @@ pub(crate) enum RpcRequest {
         /// Responder to call with the result.
         responder: Responder<Result<GetBidsResult, engine_state::Error>>,
     },
+    /// Get the balances of a number of purses at the given root hash.
+    GetBalances {
+        /// The global state hash.
+        state_root_hash: Digest,
+        /// The identifiers of the purses.
+        purses: Vec<BalanceIdentifier>,
+        /// Responder to call with the result.
+        responder: Responder<Result<GetBalancesResult, engine_state::Error>>,
+    },
+    /// Get a page of the items of a dictionary at the given root hash.
+    GetDictionaryItems {
+        /// The global state hash.
+        state_root_hash: Digest,
+        /// The dictionary's seed URef.
+        seed_uref: URef,
+        /// If provided, only items stored under a dictionary key ordered after this one are
+        /// returned.
+        after: Option<DictionaryAddr>,
+        /// The maximum number of items to return.
+        limit: usize,
+        /// Responder to call with the result.
+        responder: Responder<Result<GetDictionaryItemsResult, engine_state::Error>>,
+    },
+    /// Get a page of the keys whose values differ between two global states.
+    GetStateDiff {
+        /// The hash of the global state compared from.
+        old_state_root_hash: Digest,
+        /// The hash of the global state compared to.
+        new_state_root_hash: Digest,
+        /// If provided, only changes to keys ordered after this one are returned.
+        after: Option<Key>,
+        /// The maximum number of changes to return.
+        limit: usize,
+        /// Responder to call with the result.
+        responder: Responder<Result<GetStateDiffResult, engine_state::Error>>,
+    },
 
     /// Query the global state at the given root hash.
     GetBalance {
@@ -795,6 +1000,16 @@ pub(crate) enum RpcRequest {
         /// Responder to call with the result.
         responder: Responder<Option<(Deploy, DeployMetadataExt)>>,
     },
+    /// Wait for a deploy to be executed in a block added to the linear chain.
+    WaitForDeploy {
+        /// The hash of the deploy to wait for.
+        deploy_hash: DeployHash,
+        /// How long to wait at most.
+        timeout: TimeDiff,
+        /// Responder to call with the hash of the block in which the deploy was executed and its
+        /// execution result, or `None` if it wasn't executed before the timeout expired.
+        responder: Responder<Option<(BlockHash, ExecutionResult)>>,
+    },
     /// Return the connected peers.
     GetPeers {
         /// Responder to call with the result.
@@ -817,8 +1032,11 @@ pub(crate) enum RpcRequest {
         block_header: BlockHeader,
         /// Deploy to execute.
         deploy: Box<Deploy>,
+        /// Whether to also return a breakdown of the gas consumed by the deploy.
+        profile_gas: bool,
         /// Responder.
-        responder: Responder<Result<Option<ExecutionResult>, engine_state::Error>>,
+        responder:
+            Responder<Result<Option<(ExecutionResult, Option<GasProfile>)>, engine_state::Error>>,
     },
 }
 
@@ -826,6 +1044,19 @@ impl Display for RpcRequest {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
         match self {
             RpcRequest::SubmitDeploy { deploy, .. } => write!(formatter, "submit {}", *deploy),
+            RpcRequest::SubmitPendingDeploy { deploy, .. } => {
+                write!(formatter, "submit pending {}", *deploy)
+            }
+            RpcRequest::AddDeployApprovals {
+                deploy_hash,
+                approvals,
+                ..
+            } => write!(
+                formatter,
+                "add {} approvals to {}",
+                approvals.len(),
+                deploy_hash
+            ),
             RpcRequest::GetBlock {
                 maybe_id: Some(BlockIdentifier::Hash(hash)),
                 ..
@@ -835,9 +1066,26 @@ impl Display for RpcRequest {
                 ..
             } => write!(formatter, "get {}", height),
             RpcRequest::GetBlock { maybe_id: None, .. } => write!(formatter, "get latest block"),
+            RpcRequest::GetBlockHeader {
+                maybe_id: Some(BlockIdentifier::Hash(hash)),
+                ..
+            } => write!(formatter, "get header {}", hash),
+            RpcRequest::GetBlockHeader {
+                maybe_id: Some(BlockIdentifier::Height(height)),
+                ..
+            } => write!(formatter, "get header {}", height),
+            RpcRequest::GetBlockHeader { maybe_id: None, .. } => {
+                write!(formatter, "get latest block header")
+            }
+            RpcRequest::GetSwitchBlockHeader { era_id, .. } => {
+                write!(formatter, "get switch block header of {}", era_id)
+            }
             RpcRequest::GetBlockTransfers { block_hash, .. } => {
                 write!(formatter, "get transfers {}", block_hash)
             }
+            RpcRequest::GetBlockDeploys { block_hash, .. } => {
+                write!(formatter, "get deploys {}", block_hash)
+            }
 
             RpcRequest::QueryGlobalState {
                 state_root_hash,
@@ -857,6 +1105,34 @@ impl Display for RpcRequest {
             } => {
                 write!(formatter, "bids {}", state_root_hash)
             }
+            RpcRequest::GetBalances {
+                state_root_hash,
+                purses,
+                ..
+            } => write!(
+                formatter,
+                "balances {}, {} purses",
+                state_root_hash,
+                purses.len()
+            ),
+            RpcRequest::GetDictionaryItems {
+                state_root_hash,
+                seed_uref,
+                ..
+            } => write!(
+                formatter,
+                "dictionary items {}, seed_uref: {}",
+                state_root_hash, seed_uref
+            ),
+            RpcRequest::GetStateDiff {
+                old_state_root_hash,
+                new_state_root_hash,
+                ..
+            } => write!(
+                formatter,
+                "state diff from {} to {}",
+                old_state_root_hash, new_state_root_hash
+            ),
             RpcRequest::GetBalance {
                 state_root_hash,
                 purse_uref,
@@ -875,6 +1151,11 @@ impl Display for RpcRequest {
                 "get {} (finalized approvals: {})",
                 hash, finalized_approvals
             ),
+            RpcRequest::WaitForDeploy {
+                deploy_hash,
+                timeout,
+                ..
+            } => write!(formatter, "wait for {} (timeout: {})", deploy_hash, timeout),
             RpcRequest::GetPeers { .. } => write!(formatter, "get peers"),
             RpcRequest::GetStatus { .. } => write!(formatter, "get status"),
             RpcRequest::GetAvailableBlockRange { .. } => {
@@ -907,6 +1188,18 @@ pub(crate) enum RestRequest {
         /// Responder to call with the result
         responder: Responder<OpenRpcSchema>,
     },
+    /// Responds as soon as the request is handled, indicating that the node is live.
+    Liveness {
+        /// Responder to call once the request is handled.
+        responder: Responder<()>,
+    },
+    /// Returns the readiness of the node to serve client requests.
+    Readiness {
+        /// The minimum number of peers required for the node to be ready.
+        min_peer_count: usize,
+        /// Responder to call with the result.
+        responder: Responder<Readiness>,
+    },
 }
 
 impl Display for RestRequest {
@@ -915,6 +1208,8 @@ impl Display for RestRequest {
             RestRequest::Status { .. } => write!(formatter, "get status"),
             RestRequest::Metrics { .. } => write!(formatter, "get metrics"),
             RestRequest::RpcSchema { .. } => write!(formatter, "get openrpc"),
+            RestRequest::Liveness { .. } => write!(formatter, "get liveness"),
+            RestRequest::Readiness { .. } => write!(formatter, "get readiness"),
         }
     }
 }
@@ -954,6 +1249,8 @@ pub(crate) enum ContractRuntimeRequest {
         /// Query request.
         #[serde(skip_serializing)]
         query_request: QueryRequest,
+        /// The priority with which the query is run.
+        priority: RequestPriority,
         /// Responder to call with the query result.
         responder: Responder<Result<QueryResult, engine_state::Error>>,
     },
@@ -962,6 +1259,8 @@ pub(crate) enum ContractRuntimeRequest {
         /// Balance request.
         #[serde(skip_serializing)]
         balance_request: BalanceRequest,
+        /// The priority with which the balance request is run.
+        priority: RequestPriority,
         /// Responder to call with the balance result.
         responder: Responder<Result<BalanceResult, engine_state::Error>>,
     },
@@ -981,6 +1280,30 @@ pub(crate) enum ContractRuntimeRequest {
         /// Responder to call with the result.
         responder: Responder<Result<GetBidsResult, engine_state::Error>>,
     },
+    /// Return the balances of a number of purses at a given state root hash.
+    GetBalances {
+        /// Get balances request.
+        #[serde(skip_serializing)]
+        get_balances_request: GetBalancesRequest,
+        /// Responder to call with the result.
+        responder: Responder<Result<GetBalancesResult, engine_state::Error>>,
+    },
+    /// Return a page of the items of a dictionary at a given state root hash.
+    GetDictionaryItems {
+        /// Get dictionary items request.
+        #[serde(skip_serializing)]
+        get_dictionary_items_request: GetDictionaryItemsRequest,
+        /// Responder to call with the result.
+        responder: Responder<Result<GetDictionaryItemsResult, engine_state::Error>>,
+    },
+    /// Return a page of the keys whose values differ between two global states.
+    GetStateDiff {
+        /// Get state diff request.
+        #[serde(skip_serializing)]
+        get_state_diff_request: GetStateDiffRequest,
+        /// Responder to call with the result.
+        responder: Responder<Result<GetStateDiffResult, engine_state::Error>>,
+    },
     /// Check if validator is bonded in the future era (identified by `era_id`).
     IsBonded {
         /// State root hash of the LFB.
@@ -1050,6 +1373,34 @@ pub(crate) enum ContractRuntimeRequest {
         /// Results
         responder: Responder<Result<Option<ExecutionResult>, engine_state::Error>>,
     },
+    /// Execute a deploy without commiting results, recording where the gas it consumes is spent.
+    ProfileDeployGas {
+        /// Hash of a block on top of which to execute the deploy.
+        execution_prestate: SpeculativeExecutionState,
+        /// Deploy to execute.
+        deploy: Box<Deploy>,
+        /// Responder to call with the result of execution and its gas profile.
+        responder: Responder<Result<Option<(ExecutionResult, GasProfile)>, engine_state::Error>>,
+    },
+    /// Re-execute a stored block for diagnostic purposes, without recording block execution
+    /// metrics.
+    ReplayBlock {
+        /// The protocol version of the block to execute.
+        protocol_version: ProtocolVersion,
+        /// The state of the storage and blockchain prior to the block's execution.
+        execution_pre_state: ExecutionPreState,
+        /// The finalized block to execute; must have the same height as the child height specified
+        /// by the `execution_pre_state`.
+        finalized_block: FinalizedBlock,
+        /// The deploys for the block to execute; must correspond to the deploy hashes of the
+        /// `finalized_block` in that order.
+        deploys: Vec<Deploy>,
+        /// The transfers for the block to execute; must correspond to the transfer hashes of the
+        /// `finalized_block` in that order.
+        transfers: Vec<Deploy>,
+        /// Responder to call with the result.
+        responder: Responder<Result<BlockAndExecutionEffects, BlockExecutionError>>,
+    },
 }
 
 impl Display for ContractRuntimeRequest {
@@ -1093,6 +1444,33 @@ impl Display for ContractRuntimeRequest {
                 write!(formatter, "get bids request: {:?}", get_bids_request)
             }
 
+            ContractRuntimeRequest::GetBalances {
+                get_balances_request,
+                ..
+            } => write!(
+                formatter,
+                "get balances request: {:?}",
+                get_balances_request
+            ),
+
+            ContractRuntimeRequest::GetDictionaryItems {
+                get_dictionary_items_request,
+                ..
+            } => write!(
+                formatter,
+                "get dictionary items request: {:?}",
+                get_dictionary_items_request
+            ),
+
+            ContractRuntimeRequest::GetStateDiff {
+                get_state_diff_request,
+                ..
+            } => write!(
+                formatter,
+                "get state diff request: {:?}",
+                get_state_diff_request
+            ),
+
             ContractRuntimeRequest::IsBonded {
                 public_key, era_id, ..
             } => {
@@ -1129,6 +1507,23 @@ impl Display for ContractRuntimeRequest {
                     execution_prestate.state_root_hash
                 )
             }
+            ContractRuntimeRequest::ProfileDeployGas {
+                execution_prestate,
+                deploy,
+                ..
+            } => {
+                write!(
+                    formatter,
+                    "Profile gas of {} on {}",
+                    deploy.id(),
+                    execution_prestate.state_root_hash
+                )
+            }
+            ContractRuntimeRequest::ReplayBlock {
+                finalized_block, ..
+            } => {
+                write!(formatter, "Replay finalized block: {}", finalized_block)
+            }
         }
     }
 }
@@ -1158,6 +1553,8 @@ impl<T: Item> Display for FetcherRequest<T> {
 #[derive(Debug)]
 #[must_use]
 pub(crate) struct BlockValidationRequest {
+    /// The era of the block, which determines the deploy config it is validated against.
+    pub(crate) era_id: EraId,
     /// The block to be validated.
     pub(crate) block: ValidatingBlock,
     /// The sender of the block, which will be asked to provide all missing deploys.
@@ -1175,8 +1572,6 @@ impl Display for BlockValidationRequest {
     }
 }
 
-type BlockHeight = u64;
-
 #[derive(DataSize, Debug)]
 #[must_use]
 /// Consensus component requests.
@@ -1196,6 +1591,10 @@ pub(crate) enum ChainspecLoaderRequest {
     /// Request for the chainspec file bytes with the genesis_accounts and global_state bytes, if
     /// they are present.
     GetChainspecRawBytes(Responder<Arc<ChainspecRawBytes>>),
+    /// Request for the parsed chainspec.
+    GetChainspec(Responder<Arc<Chainspec>>),
+    /// Request for the upgrade points installed on the node.
+    GetUpgradePoints(Responder<Vec<UpgradePointInfo>>),
 }
 
 impl Display for ChainspecLoaderRequest {
@@ -1203,6 +1602,8 @@ impl Display for ChainspecLoaderRequest {
         match self {
             ChainspecLoaderRequest::GetChainspecInfo(_) => write!(f, "get chainspec info"),
             ChainspecLoaderRequest::GetChainspecRawBytes(_) => write!(f, "get chainspec raw bytes"),
+            ChainspecLoaderRequest::GetChainspec(_) => write!(f, "get chainspec"),
+            ChainspecLoaderRequest::GetUpgradePoints(_) => write!(f, "get upgrade points"),
         }
     }
 }
@@ -1216,3 +1617,13 @@ impl Display for NodeStateRequest {
         write!(f, "node state request")
     }
 }
+
+/// ChainSynchronizer component request for a summary of the chain synchronization progress.
+#[derive(Debug, Serialize)]
+pub(crate) struct SyncProgressRequest(pub(crate) Responder<SyncProgress>);
+
+impl Display for SyncProgressRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "sync progress request")
+    }
+}
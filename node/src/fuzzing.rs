@@ -0,0 +1,34 @@
+//! Entry points for fuzzing the decoders of network-facing types.
+//!
+//! Only available with the `fuzzing` feature enabled.  The fuzz targets in `node/fuzz` pass
+//! arbitrary input to these functions, none of which may panic or exhaust memory, whatever the
+//! input.
+
+use casper_types::bytesrepr;
+
+use crate::{
+    components::small_network,
+    protocol,
+    types::{Block, Deploy},
+};
+
+/// Decodes a network message from a single frame, as received from a peer.
+///
+/// This covers every payload a peer can send, including consensus messages and gossiped deploys.
+pub fn decode_network_message(frame: &[u8]) {
+    let _ = small_network::decode_message::<protocol::Message>(frame);
+}
+
+/// Decodes a deploy from its `bytesrepr` encoding and, if successful, validates it.
+pub fn decode_deploy(bytes: &[u8]) {
+    if let Ok(deploy) = bytesrepr::deserialize::<Deploy>(bytes.to_vec()) {
+        let _ = deploy.is_valid();
+    }
+}
+
+/// Decodes a block from its `bytesrepr` encoding and, if successful, validates it.
+pub fn decode_block(bytes: &[u8]) {
+    if let Ok(block) = bytesrepr::deserialize::<Block>(bytes.to_vec()) {
+        let _ = block.verify();
+    }
+}
@@ -21,11 +21,17 @@
     unused_qualifications
 )]
 
+pub(crate) mod alerts;
+mod chainspec_validation;
 pub(crate) mod components;
 mod config_migration;
+mod config_reload;
+pub(crate) mod crash_dump;
 mod data_migration;
 pub(crate) mod effect;
 pub(crate) mod logging;
+mod preflight;
+pub(crate) mod profiling;
 pub(crate) mod protocol;
 pub(crate) mod reactor;
 #[cfg(test)]
@@ -34,6 +40,8 @@ pub(crate) mod tls;
 
 // Public API
 pub mod cli;
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
 pub mod types;
 pub mod utils;
 pub use components::{
@@ -41,15 +49,22 @@ pub use components::{
     rpc_server::rpcs,
     storage::{self, Config as StorageConfig},
 };
+pub use reactor::supervision::in_supervised_component;
 pub use utils::WithDir;
 
-use std::sync::{atomic::AtomicUsize, Arc};
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize},
+    Arc,
+};
 
 use ansi_term::Color::Red;
 use once_cell::sync::Lazy;
 #[cfg(not(test))]
 use rand::SeedableRng;
-use signal_hook::{consts::TERM_SIGNALS, flag};
+use signal_hook::{
+    consts::{SIGHUP, TERM_SIGNALS},
+    flag,
+};
 
 pub(crate) use components::{
     block_proposer::Config as BlockProposerConfig,
@@ -100,6 +115,11 @@ pub(crate) static VERSION_STRING: Lazy<String> = Lazy::new(|| version_string(fal
 pub(crate) static TERMINATION_REQUESTED: Lazy<Arc<AtomicUsize>> =
     Lazy::new(|| Arc::new(AtomicUsize::new(0)));
 
+/// Global value that indicates the running reactor should reload the node-local settings which can
+/// be changed without restarting from the config file, set on receipt of a `SIGHUP` signal.
+pub(crate) static RELOAD_CONFIG_REQUESTED: Lazy<Arc<AtomicBool>> =
+    Lazy::new(|| Arc::new(AtomicBool::new(false)));
+
 /// Setup UNIX signal hooks for current application.
 pub(crate) fn setup_signal_hooks() {
     for signal in TERM_SIGNALS {
@@ -110,6 +130,8 @@ pub(crate) fn setup_signal_hooks() {
         )
         .unwrap_or_else(|error| panic!("failed to register signal {}: {}", signal, error));
     }
+    flag::register(SIGHUP, Arc::clone(&*RELOAD_CONFIG_REQUESTED))
+        .unwrap_or_else(|error| panic!("failed to register signal {}: {}", SIGHUP, error));
 }
 
 /// Constructs a new `NodeRng`.
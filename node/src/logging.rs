@@ -1,12 +1,21 @@
 //! Logging via the tracing crate.
 
-use std::{env, fmt, io};
+use std::{
+    collections::VecDeque,
+    env, fmt,
+    io::{self, Write},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, MutexGuard, PoisonError,
+    },
+};
 
 use ansi_term::{Color, Style};
 use anyhow::anyhow;
 use datasize::DataSize;
-use once_cell::sync::OnceCell;
+use once_cell::sync::{Lazy, OnceCell};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
 use smallvec::SmallVec;
 use tracing::{
     field::{Field, Visit},
@@ -14,7 +23,7 @@ use tracing::{
 };
 use tracing_subscriber::{
     fmt::{
-        format::{self, FieldFn, Format, Json, JsonFields, Writer},
+        format::{self, FieldFn, JsonFields, Writer},
         time::{FormatTime, SystemTime},
         FmtContext, FormatEvent, FormatFields, FormattedFields, Layer,
     },
@@ -32,11 +41,23 @@ const LOG_FIELD_MODULE: &str = "log.module_path";
 const LOG_FIELD_FILE: &str = "log.file";
 const LOG_FIELD_LINE: &str = "log.line";
 
+const JSON_FIELD_ERA: &str = "era";
+const JSON_FIELD_BLOCK_HASH: &str = "block_hash";
+const JSON_FIELD_DEPLOY_HASH: &str = "deploy_hash";
+const JSON_FIELD_PEER_ID: &str = "peer_id";
+const JSON_FIELD_CORRELATION_ID: &str = "correlation_id";
+
 /// Global reload handle.
 ///
 /// We use a static variable for the reload handle since our logger instance is also global.
 static RELOAD_HANDLE: OnceCell<ReloadHandle> = OnceCell::new();
 
+/// The most recently logged lines, oldest first, retained for inclusion in crash dumps.
+static RECENT_LOG_LINES: Lazy<Mutex<VecDeque<String>>> = Lazy::new(Default::default);
+
+/// The maximum number of recently logged lines to retain, zero if none are retained.
+static RECENT_LOG_LINES_CAPACITY: AtomicUsize = AtomicUsize::new(0);
+
 /// Logging configuration.
 #[derive(DataSize, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
@@ -77,7 +98,7 @@ impl LoggingConfig {
 pub enum LoggingFormat {
     /// Text format.
     Text,
-    /// JSON format.
+    /// JSON format, one object per line with stable field names (see [`JsonEvent`]).
     Json,
 }
 
@@ -260,6 +281,164 @@ where
     }
 }
 
+/// A single line of JSON log output.
+///
+/// The names of these fields are stable, so that log pipelines can index them without parsing
+/// messages.  Fields of the event and of its enclosing spans which are known under different names
+/// at different call sites are normalized, e.g. both `era_id` and `era` are output as `era`.
+#[derive(Serialize)]
+struct JsonLogLine<'a> {
+    /// The time of the event.
+    timestamp: String,
+    /// The log level.
+    level: &'a str,
+    /// The component or subsystem the event originated from, e.g. `small_network`.
+    component: &'a str,
+    /// The full module path of the source of the event.
+    target: &'a str,
+    /// The log message.
+    message: String,
+    /// The normalized `era`, `block_hash`, `deploy_hash`, `peer_id` and `correlation_id` fields,
+    /// where present.
+    #[serde(flatten)]
+    stable_fields: Map<String, JsonValue>,
+    /// All other fields of the event and its enclosing spans.
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    fields: Map<String, JsonValue>,
+}
+
+/// Returns the stable name of a field which is indexed in JSON log output, or `None` if the field
+/// is output under its own name in the `fields` object.
+fn stable_field_name(name: &str) -> Option<&'static str> {
+    match name {
+        "era" | "era_id" => Some(JSON_FIELD_ERA),
+        "block_hash" => Some(JSON_FIELD_BLOCK_HASH),
+        "deploy_hash" => Some(JSON_FIELD_DEPLOY_HASH),
+        "peer_id" | "peer" => Some(JSON_FIELD_PEER_ID),
+        "cid" | "correlation_id" => Some(JSON_FIELD_CORRELATION_ID),
+        _ => None,
+    }
+}
+
+/// Returns the component or subsystem the given module path belongs to.
+///
+/// `casper_node::components::small_network::tasks` yields `small_network`, other modules of the
+/// node yield their top-level module, e.g. `reactor`, and modules of other crates yield the crate.
+fn component_of(module_path: &str) -> &str {
+    let mut segments = module_path.split("::");
+    match (segments.next(), segments.next(), segments.next()) {
+        (Some("casper_node"), Some("components"), Some(component)) => component,
+        (Some("casper_node"), Some(module), _) => module,
+        (Some(krate), _, _) => krate,
+        (None, _, _) => module_path,
+    }
+}
+
+/// Collects the fields of an event or span for JSON output.
+#[derive(Default)]
+struct JsonFieldVisitor {
+    module: Option<String>,
+    message: Option<String>,
+    stable_fields: Map<String, JsonValue>,
+    fields: Map<String, JsonValue>,
+}
+
+impl JsonFieldVisitor {
+    fn record_value(&mut self, name: &str, value: JsonValue) {
+        match name {
+            LOG_FIELD_MESSAGE => {
+                self.message = Some(match value {
+                    JsonValue::String(message) => message,
+                    other => other.to_string(),
+                })
+            }
+            LOG_FIELD_MODULE => self.module = value.as_str().map(str::to_string),
+            LOG_FIELD_TARGET | LOG_FIELD_FILE | LOG_FIELD_LINE => {}
+            name => match stable_field_name(name) {
+                Some(stable_name) => {
+                    self.stable_fields.insert(stable_name.to_string(), value);
+                }
+                None => {
+                    self.fields.insert(name.to_string(), value);
+                }
+            },
+        }
+    }
+}
+
+impl Visit for JsonFieldVisitor {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record_value(field.name(), JsonValue::from(value))
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.record_value(field.name(), JsonValue::from(value))
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.record_value(field.name(), JsonValue::from(value))
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record_value(field.name(), JsonValue::from(value))
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record_value(field.name(), JsonValue::from(format!("{:?}", value)))
+    }
+}
+
+/// Formats tracing events as [`JsonLogLine`]s.
+pub struct JsonEvent;
+
+impl<S, N> FormatEvent<S, N> for JsonEvent
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let mut timestamp = String::new();
+        SystemTime.format_time(&mut Writer::new(&mut timestamp))?;
+
+        // Span fields are recorded by `JsonFields` as JSON objects.  Fields of inner spans take
+        // precedence over those of outer spans, and fields of the event over those of any span.
+        let mut visitor = JsonFieldVisitor::default();
+        ctx.visit_spans(|span| {
+            let ext = span.extensions();
+            let fields: Option<Map<String, JsonValue>> = ext
+                .get::<FormattedFields<N>>()
+                .and_then(|formatted| serde_json::from_str(&formatted.fields).ok());
+            for (name, value) in fields.into_iter().flatten() {
+                visitor.record_value(&name, value);
+            }
+            Ok::<(), fmt::Error>(())
+        })?;
+        event.record(&mut visitor);
+
+        let meta = event.metadata();
+        let target = meta
+            .module_path()
+            .or(visitor.module.as_deref())
+            .unwrap_or_else(|| meta.target());
+        let line = JsonLogLine {
+            timestamp,
+            level: meta.level().as_str(),
+            component: component_of(target),
+            target,
+            message: visitor.message.unwrap_or_default(),
+            stable_fields: visitor.stable_fields,
+            fields: visitor.fields,
+        };
+        let encoded = serde_json::to_string(&line).map_err(|_| fmt::Error)?;
+        writeln!(writer, "{}", encoded)
+    }
+}
+
 /// Initializes the logging system with the default parameters.
 ///
 /// See `init_params` for details.
@@ -272,9 +451,16 @@ pub fn init() -> anyhow::Result<()> {
 #[allow(clippy::type_complexity)] // Cannot be helped, unfortunately.
 pub enum ReloadHandle {
     /// Text-logger reload handle.
-    Text(Handle<EnvFilter, Layered<Layer<Registry, FieldFn<FormatDebugFn>, FmtEvent>, Registry>>),
+    Text(
+        Handle<
+            EnvFilter,
+            Layered<Layer<Registry, FieldFn<FormatDebugFn>, FmtEvent, MakeLogWriter>, Registry>,
+        >,
+    ),
     /// JSON-logger reload handle.
-    Json(Handle<EnvFilter, Layered<Layer<Registry, JsonFields, Format<Json>>, Registry>>),
+    Json(
+        Handle<EnvFilter, Layered<Layer<Registry, JsonFields, JsonEvent, MakeLogWriter>, Registry>>,
+    ),
 }
 
 impl ReloadHandle {
@@ -351,7 +537,7 @@ pub fn init_with_config(config: &LoggingConfig) -> anyhow::Result<()> {
         // Setup a new tracing-subscriber writing to `stdout` for logging.
         LoggingFormat::Text => {
             let builder = tracing_subscriber::fmt()
-                .with_writer(io::stdout as fn() -> std::io::Stdout)
+                .with_writer(LogWriter::new as MakeLogWriter)
                 .with_env_filter(filter)
                 .fmt_fields(formatter)
                 .event_format(FmtEvent::new(config.color, config.abbreviate_modules))
@@ -365,9 +551,10 @@ pub fn init_with_config(config: &LoggingConfig) -> anyhow::Result<()> {
         // JSON logging writes to `stdout` as well but uses the JSON format.
         LoggingFormat::Json => {
             let builder = tracing_subscriber::fmt()
-                .with_writer(io::stdout as fn() -> std::io::Stdout)
+                .with_writer(LogWriter::new as MakeLogWriter)
                 .with_env_filter(filter)
-                .json()
+                .fmt_fields(JsonFields::new())
+                .event_format(JsonEvent)
                 .with_filter_reloading();
             let handle = ReloadHandle::Json(builder.reload_handle());
             builder.try_init().map_err(|error| anyhow!(error))?;
@@ -376,3 +563,103 @@ pub fn init_with_config(config: &LoggingConfig) -> anyhow::Result<()> {
         }
     }
 }
+
+/// Constructor of the writer used by the logger.
+pub type MakeLogWriter = fn() -> LogWriter;
+
+/// Writer of log output to `stdout`, which also retains the most recently logged lines if enabled
+/// via [`retain_recent_log_lines`].
+pub struct LogWriter(io::Stdout);
+
+impl LogWriter {
+    fn new() -> Self {
+        LogWriter(io::stdout())
+    }
+}
+
+impl Write for LogWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.0.write(buf)?;
+        let capacity = RECENT_LOG_LINES_CAPACITY.load(Ordering::Relaxed);
+        if capacity > 0 {
+            // Each log event is written in a single call, so the written bytes are whole lines.
+            let mut recent_log_lines = lock_recent_log_lines();
+            for line in String::from_utf8_lossy(&buf[..written]).lines() {
+                if recent_log_lines.len() >= capacity {
+                    recent_log_lines.pop_front();
+                }
+                recent_log_lines.push_back(line.to_string());
+            }
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+/// Starts retaining up to `capacity` of the most recently logged lines.
+pub(crate) fn retain_recent_log_lines(capacity: usize) {
+    RECENT_LOG_LINES_CAPACITY.store(capacity, Ordering::Relaxed);
+}
+
+/// Returns the most recently logged lines, oldest first.
+pub(crate) fn recent_log_lines() -> Vec<String> {
+    lock_recent_log_lines().iter().cloned().collect()
+}
+
+/// Locks the recently logged lines, ignoring poisoning since they are only ever appended to.
+fn lock_recent_log_lines() -> MutexGuard<'static, VecDeque<String>> {
+    RECENT_LOG_LINES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_derive_component_from_module_path() {
+        assert_eq!(
+            component_of("casper_node::components::small_network::tasks"),
+            "small_network"
+        );
+        assert_eq!(
+            component_of("casper_node::reactor::participating"),
+            "reactor"
+        );
+        assert_eq!(component_of("casper_node"), "casper_node");
+        assert_eq!(component_of("hyper::proto::h1"), "hyper");
+    }
+
+    #[test]
+    fn should_normalize_stable_fields() {
+        let mut visitor = JsonFieldVisitor::default();
+        visitor.record_value("era_id", JsonValue::from(7));
+        visitor.record_value("peer", JsonValue::from("tls:0102..0304"));
+        visitor.record_value("block_hash", JsonValue::from("abcd"));
+        visitor.record_value("cid", JsonValue::from(42));
+        visitor.record_value("count", JsonValue::from(3));
+        visitor.record_value(LOG_FIELD_MESSAGE, JsonValue::from("block added"));
+        visitor.record_value(LOG_FIELD_LINE, JsonValue::from(12));
+
+        assert_eq!(visitor.message.as_deref(), Some("block added"));
+        assert_eq!(visitor.stable_fields[JSON_FIELD_ERA], JsonValue::from(7));
+        assert_eq!(
+            visitor.stable_fields[JSON_FIELD_PEER_ID],
+            JsonValue::from("tls:0102..0304")
+        );
+        assert_eq!(
+            visitor.stable_fields[JSON_FIELD_BLOCK_HASH],
+            JsonValue::from("abcd")
+        );
+        assert_eq!(
+            visitor.stable_fields[JSON_FIELD_CORRELATION_ID],
+            JsonValue::from(42)
+        );
+        assert_eq!(visitor.fields.len(), 1);
+        assert_eq!(visitor.fields["count"], JsonValue::from(3));
+    }
+}
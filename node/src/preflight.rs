@@ -0,0 +1,568 @@
+//! Checks of the host environment ahead of running the node.
+//!
+//! Covers the problems which commonly prevent a freshly set up validator from joining or keeping
+//! up with the network: too little disk space or an unsuitable filesystem for the storage, a
+//! drifting clock, a low open file limit, unavailable or unreachable ports, a world-readable
+//! secret key, and an inconsistent chainspec and config.
+
+use std::{
+    fmt::{self, Display, Formatter},
+    fs, io,
+    net::{TcpListener, TcpStream, ToSocketAddrs, UdpSocket},
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::Serialize;
+
+use crate::{
+    chainspec_validation::{self, Severity},
+    cli::{self, ConfigExt},
+    reactor::participating,
+    types::NodeMode,
+    utils::External,
+};
+
+/// Free disk space on the storage volume below which the check fails, in bytes.
+const MIN_FREE_DISK_SPACE: u64 = 10 * 1024 * 1024 * 1024;
+/// Free disk space on the storage volume below which the check warns, in bytes.
+const RECOMMENDED_FREE_DISK_SPACE: u64 = 100 * 1024 * 1024 * 1024;
+/// Clock offset above which the check fails.
+const MAX_CLOCK_OFFSET: Duration = Duration::from_secs(1);
+/// Clock offset above which the check warns.
+const RECOMMENDED_MAX_CLOCK_OFFSET: Duration = Duration::from_millis(200);
+/// Open file limit below which the check fails.
+const MIN_OPEN_FILES: u64 = 4_096;
+/// Open file limit below which the check warns.
+const RECOMMENDED_OPEN_FILES: u64 = 65_536;
+/// Timeout of network requests made by the checks.
+const NETWORK_TIMEOUT: Duration = Duration::from_secs(5);
+/// Seconds between the NTP epoch, 1900-01-01, and the Unix epoch.
+const NTP_UNIX_EPOCH_OFFSET: u64 = 2_208_988_800;
+
+/// The outcome of a check.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum CheckStatus {
+    /// Nothing to worry about.
+    Pass,
+    /// The node will run, but the setup is likely to cause problems.
+    Warning,
+    /// The node will not run, or not keep up with the network.
+    Fail,
+}
+
+impl Display for CheckStatus {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckStatus::Pass => write!(formatter, "pass"),
+            CheckStatus::Warning => write!(formatter, "warning"),
+            CheckStatus::Fail => write!(formatter, "fail"),
+        }
+    }
+}
+
+/// The result of a single check.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
+pub(crate) struct CheckResult {
+    /// The name of the check.
+    pub(crate) check: &'static str,
+    /// The outcome of the check.
+    pub(crate) status: CheckStatus,
+    /// A description of what was found.
+    pub(crate) message: String,
+}
+
+impl Display for CheckResult {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "{}: {}: {}",
+            self.status, self.check, self.message
+        )
+    }
+}
+
+/// The results of the checks so far.
+#[derive(Default)]
+struct CheckResults(Vec<CheckResult>);
+
+impl CheckResults {
+    fn push<M: Display>(&mut self, check: &'static str, status: CheckStatus, message: M) {
+        self.0.push(CheckResult {
+            check,
+            status,
+            message: message.to_string(),
+        })
+    }
+}
+
+/// Runs all checks for a node with the given config file, override file and command-line
+/// overrides, querying `ntp_server` for the clock offset.
+pub(crate) fn run(
+    config_path: &Path,
+    config_override: Option<&Path>,
+    config_ext: Vec<ConfigExt>,
+    ntp_server: &str,
+) -> Vec<CheckResult> {
+    let mut results = CheckResults::default();
+    let root = config_path.parent().unwrap_or_else(|| Path::new("/"));
+
+    for diagnostic in
+        chainspec_validation::validate(config_path, config_override, config_ext.clone())
+    {
+        let status = match diagnostic.severity {
+            Severity::Error => CheckStatus::Fail,
+            Severity::Warning => CheckStatus::Warning,
+        };
+        results.push("config", status, diagnostic);
+    }
+    if results.0.is_empty() {
+        results.push(
+            "config",
+            CheckStatus::Pass,
+            "chainspec and config are consistent",
+        );
+    }
+
+    // Problems loading the config were reported above.
+    if let Ok(config) = cli::load_config(config_path, config_override, config_ext) {
+        let storage_path = root.join(&config.storage.path);
+        check_disk_space(&storage_path, &mut results);
+        check_filesystem(&storage_path, &mut results);
+        check_secret_key_permissions(&config, root, &mut results);
+        check_ports(&config, &mut results);
+        check_known_addresses(&config, &mut results);
+    }
+    check_clock(ntp_server, &mut results);
+    check_open_files(&mut results);
+
+    results.0
+}
+
+/// Returns the closest existing ancestor of `path`, which may not have been created yet.
+fn existing_ancestor(path: &Path) -> &Path {
+    path.ancestors()
+        .find(|ancestor| ancestor.exists())
+        .unwrap_or_else(|| Path::new("/"))
+}
+
+/// Checks the free disk space on the storage volume.
+fn check_disk_space(storage_path: &Path, results: &mut CheckResults) {
+    let path = existing_ancestor(storage_path);
+    let available = match fs2::available_space(path) {
+        Ok(available) => available,
+        Err(error) => {
+            results.push(
+                "disk_space",
+                CheckStatus::Warning,
+                format!(
+                    "could not determine free space of {}: {}",
+                    path.display(),
+                    error
+                ),
+            );
+            return;
+        }
+    };
+    let status = if available < MIN_FREE_DISK_SPACE {
+        CheckStatus::Fail
+    } else if available < RECOMMENDED_FREE_DISK_SPACE {
+        CheckStatus::Warning
+    } else {
+        CheckStatus::Pass
+    };
+    results.push(
+        "disk_space",
+        status,
+        format!(
+            "{} GiB free on the volume of {}, recommended at least {} GiB",
+            available / (1024 * 1024 * 1024),
+            storage_path.display(),
+            RECOMMENDED_FREE_DISK_SPACE / (1024 * 1024 * 1024)
+        ),
+    );
+}
+
+/// Checks the type of the filesystem holding the storage.
+#[cfg(target_os = "linux")]
+fn check_filesystem(storage_path: &Path, results: &mut CheckResults) {
+    use std::{ffi::CString, mem::MaybeUninit, os::unix::ffi::OsStrExt};
+
+    let path = existing_ancestor(storage_path);
+    let c_path = match CString::new(path.as_os_str().as_bytes()) {
+        Ok(c_path) => c_path,
+        Err(error) => {
+            results.push("filesystem", CheckStatus::Warning, error);
+            return;
+        }
+    };
+    let mut stat = MaybeUninit::<libc::statfs>::uninit();
+    // SAFETY: `c_path` is a valid NUL-terminated string and `stat` is valid for writes.
+    if unsafe { libc::statfs(c_path.as_ptr(), stat.as_mut_ptr()) } != 0 {
+        results.push(
+            "filesystem",
+            CheckStatus::Warning,
+            format!(
+                "could not determine filesystem of {}: {}",
+                path.display(),
+                io::Error::last_os_error()
+            ),
+        );
+        return;
+    }
+    // SAFETY: `statfs` succeeded, so initialized `stat`.
+    let f_type = unsafe { stat.assume_init() }.f_type;
+    let (status, message) = classify_filesystem(f_type as u64 & 0xFFFF_FFFF);
+    results.push(
+        "filesystem",
+        status,
+        format!("{} holds {}", message, path.display()),
+    );
+}
+
+/// Checks the type of the filesystem holding the storage.
+#[cfg(not(target_os = "linux"))]
+fn check_filesystem(storage_path: &Path, results: &mut CheckResults) {
+    results.push(
+        "filesystem",
+        CheckStatus::Warning,
+        format!(
+            "filesystem of {} not checked on this platform",
+            storage_path.display()
+        ),
+    );
+}
+
+/// Returns the outcome of the filesystem check for the given filesystem magic number.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn classify_filesystem(magic: u64) -> (CheckStatus, String) {
+    match magic {
+        0xEF53 => (CheckStatus::Pass, "ext4".to_string()),
+        0x5846_5342 => (CheckStatus::Pass, "xfs".to_string()),
+        0x9123_683E => (CheckStatus::Pass, "btrfs".to_string()),
+        0x2FC1_2FC1 => (CheckStatus::Pass, "zfs".to_string()),
+        0x0102_1994 => (
+            CheckStatus::Fail,
+            "tmpfs, which does not persist across restarts,".to_string(),
+        ),
+        0x6969 => (
+            CheckStatus::Warning,
+            "nfs, on which the storage's memory-mapped database is unreliable,".to_string(),
+        ),
+        0x794C_7630 => (
+            CheckStatus::Warning,
+            "overlayfs, which is usually a container's ephemeral layer rather than a volume,"
+                .to_string(),
+        ),
+        other => (
+            CheckStatus::Warning,
+            format!("an unrecognized filesystem ({:#x})", other),
+        ),
+    }
+}
+
+/// Checks that the secret key file is not accessible by other users.
+fn check_secret_key_permissions(
+    config: &participating::Config,
+    root: &Path,
+    results: &mut CheckResults,
+) {
+    // No key is loaded in observer mode.
+    if config.node.node_mode == NodeMode::Observer {
+        return;
+    }
+    // A missing or unreadable key is reported by the config check.
+    let path = match &config.consensus.secret_key_path {
+        External::Path(path) => root.join(path),
+        External::Missing => return,
+    };
+    let metadata = match fs::metadata(&path) {
+        Ok(metadata) => metadata,
+        Err(_) => return,
+    };
+    let (status, message) = secret_key_permissions(&metadata);
+    results.push(
+        "secret_key_permissions",
+        status,
+        format!("{} {}", path.display(), message),
+    );
+}
+
+#[cfg(unix)]
+fn secret_key_permissions(metadata: &fs::Metadata) -> (CheckStatus, String) {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = metadata.permissions().mode() & 0o777;
+    if mode & 0o077 == 0 {
+        (CheckStatus::Pass, format!("has mode {:o}", mode))
+    } else {
+        (
+            CheckStatus::Fail,
+            format!(
+                "has mode {:o}, so is accessible by other users; restrict it to its owner",
+                mode
+            ),
+        )
+    }
+}
+
+#[cfg(not(unix))]
+fn secret_key_permissions(_metadata: &fs::Metadata) -> (CheckStatus, String) {
+    (
+        CheckStatus::Warning,
+        "permissions not checked on this platform".to_string(),
+    )
+}
+
+/// Checks that the ports the node listens on are available.
+fn check_ports(config: &participating::Config, results: &mut CheckResults) {
+    let mut listeners = vec![("network.bind_address", &config.network.bind_address)];
+    if config.rest_server.enable_server {
+        listeners.push(("rest_server.address", &config.rest_server.address));
+    }
+    if config.rpc_server.enable_server {
+        listeners.push(("rpc_server.address", &config.rpc_server.address));
+    }
+    if config.speculative_exec_server.enable_server {
+        listeners.push((
+            "speculative_exec_server.address",
+            &config.speculative_exec_server.address,
+        ));
+    }
+    if config.event_stream_server.enable_server {
+        listeners.push((
+            "event_stream_server.address",
+            &config.event_stream_server.address,
+        ));
+    }
+    for (setting, address) in listeners {
+        match TcpListener::bind(address.as_str()) {
+            Ok(_) => results.push(
+                "ports",
+                CheckStatus::Pass,
+                format!("{} {} is available", setting, address),
+            ),
+            Err(error) => results.push(
+                "ports",
+                CheckStatus::Fail,
+                format!("cannot listen on {} {}: {}", setting, address, error),
+            ),
+        }
+    }
+}
+
+/// Checks that at least one of the known addresses is reachable.
+fn check_known_addresses(config: &participating::Config, results: &mut CheckResults) {
+    // An empty list is reported by the config check.
+    if config.network.known_addresses.is_empty() {
+        return;
+    }
+    let mut unreachable = Vec::new();
+    for address in &config.network.known_addresses {
+        let reachable = address
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addresses| addresses.next())
+            .map_or(false, |address| {
+                TcpStream::connect_timeout(&address, NETWORK_TIMEOUT).is_ok()
+            });
+        if !reachable {
+            unreachable.push(address.as_str());
+        }
+    }
+    let total = config.network.known_addresses.len();
+    let (status, message) = if unreachable.is_empty() {
+        (
+            CheckStatus::Pass,
+            format!("all {} known addresses are reachable", total),
+        )
+    } else if unreachable.len() < total {
+        (
+            CheckStatus::Warning,
+            format!(
+                "{} of {} known addresses are unreachable: {}",
+                unreachable.len(),
+                total,
+                unreachable.join(", ")
+            ),
+        )
+    } else {
+        (
+            CheckStatus::Fail,
+            format!(
+                "none of the {} known addresses are reachable; check the outgoing firewall",
+                total
+            ),
+        )
+    };
+    results.push("known_addresses", status, message);
+}
+
+/// Checks the offset of the local clock from the given NTP server's.
+fn check_clock(ntp_server: &str, results: &mut CheckResults) {
+    let offset = match query_clock_offset(ntp_server) {
+        Ok(offset) => offset,
+        Err(error) => {
+            results.push(
+                "clock",
+                CheckStatus::Warning,
+                format!("could not query NTP server {}: {}", ntp_server, error),
+            );
+            return;
+        }
+    };
+    let magnitude = Duration::from_secs_f64(offset.abs());
+    let status = if magnitude > MAX_CLOCK_OFFSET {
+        CheckStatus::Fail
+    } else if magnitude > RECOMMENDED_MAX_CLOCK_OFFSET {
+        CheckStatus::Warning
+    } else {
+        CheckStatus::Pass
+    };
+    results.push(
+        "clock",
+        status,
+        format!(
+            "local clock is {:.3}s {} {}, recommended within {}ms",
+            offset.abs(),
+            if offset < 0.0 { "ahead of" } else { "behind" },
+            ntp_server,
+            RECOMMENDED_MAX_CLOCK_OFFSET.as_millis()
+        ),
+    );
+}
+
+/// Queries the given NTP server using SNTP, returning the offset of its clock from the local
+/// one in seconds.
+fn query_clock_offset(ntp_server: &str) -> io::Result<f64> {
+    let socket = UdpSocket::bind("0.0.0.0:0")?;
+    socket.set_read_timeout(Some(NETWORK_TIMEOUT))?;
+    socket.connect(ntp_server)?;
+
+    // Leap indicator 0, version 3, mode 3 (client).
+    let mut packet = [0u8; 48];
+    packet[0] = 0x1B;
+    let sent = unix_seconds(SystemTime::now());
+    socket.send(&packet)?;
+    let received = socket.recv(&mut packet)?;
+    let arrived = unix_seconds(SystemTime::now());
+    if received < 48 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "truncated NTP response",
+        ));
+    }
+
+    let server_received = ntp_seconds(&packet[32..40]);
+    let server_sent = ntp_seconds(&packet[40..48]);
+    Ok(clock_offset(sent, server_received, server_sent, arrived))
+}
+
+/// Returns the offset of the server's clock from the local one, given the local times the request
+/// was sent and the response arrived, and the server's times the request was received and the
+/// response sent.
+fn clock_offset(sent: f64, server_received: f64, server_sent: f64, arrived: f64) -> f64 {
+    ((server_received - sent) + (server_sent - arrived)) / 2.0
+}
+
+/// Converts the given NTP timestamp to seconds since the Unix epoch.
+fn ntp_seconds(timestamp: &[u8]) -> f64 {
+    let seconds = u32::from_be_bytes([timestamp[0], timestamp[1], timestamp[2], timestamp[3]]);
+    let fraction = u32::from_be_bytes([timestamp[4], timestamp[5], timestamp[6], timestamp[7]]);
+    (u64::from(seconds) as f64 - NTP_UNIX_EPOCH_OFFSET as f64)
+        + f64::from(fraction) / 4_294_967_296.0
+}
+
+/// Converts the given time to seconds since the Unix epoch.
+fn unix_seconds(time: SystemTime) -> f64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0.0, |duration| duration.as_secs_f64())
+}
+
+/// Checks the open file limit of the process.
+#[cfg(unix)]
+fn check_open_files(results: &mut CheckResults) {
+    let mut limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    // SAFETY: `limit` is a valid `rlimit` for `getrlimit` to write to.
+    if unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) } != 0 {
+        results.push(
+            "open_files",
+            CheckStatus::Warning,
+            format!(
+                "could not determine open file limit: {}",
+                io::Error::last_os_error()
+            ),
+        );
+        return;
+    }
+    let soft_limit = limit.rlim_cur as u64;
+    let status = if soft_limit < MIN_OPEN_FILES {
+        CheckStatus::Fail
+    } else if soft_limit < RECOMMENDED_OPEN_FILES {
+        CheckStatus::Warning
+    } else {
+        CheckStatus::Pass
+    };
+    results.push(
+        "open_files",
+        status,
+        format!(
+            "open file limit is {}, recommended at least {}",
+            soft_limit, RECOMMENDED_OPEN_FILES
+        ),
+    );
+}
+
+/// Checks the open file limit of the process.
+#[cfg(not(unix))]
+fn check_open_files(results: &mut CheckResults) {
+    results.push(
+        "open_files",
+        CheckStatus::Warning,
+        "open file limit not checked on this platform",
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_compute_clock_offset() {
+        // The server's clock is 2s ahead, with a round trip of 100ms.
+        let offset = clock_offset(100.0, 102.05, 102.06, 100.11);
+        assert!((offset - 2.0).abs() < 1e-9);
+
+        let mut timestamp = [0u8; 8];
+        timestamp[..4].copy_from_slice(&((NTP_UNIX_EPOCH_OFFSET + 10) as u32).to_be_bytes());
+        timestamp[4..].copy_from_slice(&(u32::MAX / 2).to_be_bytes());
+        assert!((ntp_seconds(&timestamp) - 10.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn should_reject_unsuitable_filesystems() {
+        assert_eq!(classify_filesystem(0xEF53).0, CheckStatus::Pass);
+        assert_eq!(classify_filesystem(0x0102_1994).0, CheckStatus::Fail);
+        assert_eq!(classify_filesystem(0x6969).0, CheckStatus::Warning);
+        assert_eq!(classify_filesystem(0x1234).0, CheckStatus::Warning);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn should_reject_shared_secret_key() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let file = tempfile::NamedTempFile::new().unwrap();
+        fs::set_permissions(file.path(), fs::Permissions::from_mode(0o600)).unwrap();
+        let metadata = fs::metadata(file.path()).unwrap();
+        assert_eq!(secret_key_permissions(&metadata).0, CheckStatus::Pass);
+
+        fs::set_permissions(file.path(), fs::Permissions::from_mode(0o644)).unwrap();
+        let metadata = fs::metadata(file.path()).unwrap();
+        assert_eq!(secret_key_permissions(&metadata).0, CheckStatus::Fail);
+    }
+}
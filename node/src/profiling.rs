@@ -0,0 +1,402 @@
+//! On-demand profiling of a running node.
+//!
+//! CPU profiles are sampled using `pprof` and written in the pprof protobuf format, which can be
+//! inspected using `go tool pprof` or converted into a flamegraph.  Task dumps list the effects
+//! spawned onto the async runtime which have not completed yet, grouped by the type of event whose
+//! handling created them, which helps locating effects stuck waiting for a response.
+//!
+//! Both are triggered via the diagnostics port or the REST server, and written to files in the
+//! configured output directory.  Profiling has to be enabled in the config, as tracking tasks adds
+//! some overhead to every effect, and CPU profiles additionally require the node to be built with
+//! the `cpu-profiling` feature.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    num::NonZeroU64,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, MutexGuard, PoisonError,
+    },
+    time::Instant,
+};
+#[cfg(feature = "cpu-profiling")]
+use std::{sync::atomic::AtomicBool, thread};
+
+use datasize::DataSize;
+use once_cell::sync::{Lazy, OnceCell};
+#[cfg(feature = "cpu-profiling")]
+use pprof::{protos::Message, ProfilerGuardBuilder};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::info;
+
+use casper_types::{TimeDiff, Timestamp};
+
+use crate::WithDir;
+
+/// Default directory to write profiles to, relative to the config file's directory.
+const DEFAULT_OUTPUT_DIR: &str = "profiles";
+/// Default maximum duration of a CPU profile.
+const DEFAULT_MAX_CPU_PROFILE_DURATION: &str = "2min";
+/// Default sampling frequency of CPU profiles, in Hz.
+const DEFAULT_CPU_PROFILE_FREQUENCY: u16 = 99;
+/// Libraries whose frames are excluded from CPU profiles, as unwinding through them is unreliable.
+#[cfg(feature = "cpu-profiling")]
+const CPU_PROFILE_BLOCKLIST: &[&str] = &["libc", "libgcc", "pthread", "vdso"];
+
+/// The profiler, initialized at startup if profiling is enabled.
+///
+/// We use a static variable since the CPU profiler and the tracking of tasks are process-wide.
+static PROFILER: OnceCell<Profiler> = OnceCell::new();
+
+/// The effects spawned onto the async runtime which have not completed yet, by task ID.
+static PENDING_TASKS: Lazy<Mutex<HashMap<u64, PendingTask>>> = Lazy::new(Default::default);
+
+/// The ID of the next task to be tracked.
+static NEXT_TASK_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Profiling configuration.
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct ProfilingConfig {
+    /// Whether profiling is enabled.
+    ///
+    /// As tracking pending tasks adds some overhead to spawning every effect, it is disabled by
+    /// default.
+    pub enabled: bool,
+    /// Directory to write profiles and task dumps to.
+    ///
+    /// If relative, it is relative to the directory of the config file.
+    pub output_dir: PathBuf,
+    /// Maximum duration of a CPU profile.
+    pub max_cpu_profile_duration: TimeDiff,
+    /// Sampling frequency of CPU profiles, in Hz.
+    pub cpu_profile_frequency: u16,
+}
+
+impl Default for ProfilingConfig {
+    fn default() -> Self {
+        ProfilingConfig {
+            enabled: false,
+            output_dir: DEFAULT_OUTPUT_DIR.into(),
+            max_cpu_profile_duration: DEFAULT_MAX_CPU_PROFILE_DURATION.parse().unwrap(),
+            cpu_profile_frequency: DEFAULT_CPU_PROFILE_FREQUENCY,
+        }
+    }
+}
+
+/// An error profiling the node.
+#[derive(Debug, Error)]
+pub(crate) enum ProfilingError {
+    /// Profiling is not enabled in the config.
+    #[error("profiling is not enabled")]
+    Disabled,
+    /// The node was built without the `cpu-profiling` feature.
+    #[error("CPU profiling is not supported by this build of the node")]
+    CpuProfilingUnsupported,
+    /// The requested CPU profile duration exceeds the configured maximum.
+    #[error("requested duration of {requested} exceeds the maximum of {maximum}")]
+    DurationTooLong {
+        /// The requested duration.
+        requested: TimeDiff,
+        /// The configured maximum duration.
+        maximum: TimeDiff,
+    },
+    /// A CPU profile is already being taken.
+    #[error("a CPU profile is already being taken")]
+    AlreadyRunning,
+    /// The CPU profiler failed.
+    #[cfg(feature = "cpu-profiling")]
+    #[error("CPU profiler failed: {0}")]
+    Pprof(#[from] pprof::Error),
+    /// The thread taking the CPU profile panicked.
+    #[cfg(feature = "cpu-profiling")]
+    #[error("CPU profiler panicked")]
+    Panicked,
+    /// The task dump couldn't be encoded.
+    #[error("failed to encode task dump: {0}")]
+    Encode(#[from] serde_json::Error),
+    /// The output file couldn't be written.
+    #[error("failed to write {}: {error}", path.display())]
+    Write {
+        /// The path of the output file.
+        path: PathBuf,
+        /// The underlying error.
+        error: io::Error,
+    },
+}
+
+/// Settings for writing profiles, taken from the config.
+#[derive(Debug)]
+struct Profiler {
+    /// The directory to write profiles and task dumps to.
+    output_dir: PathBuf,
+    /// The maximum duration of a CPU profile.
+    #[cfg(feature = "cpu-profiling")]
+    max_cpu_profile_duration: TimeDiff,
+    /// The sampling frequency of CPU profiles.
+    #[cfg(feature = "cpu-profiling")]
+    cpu_profile_frequency: u16,
+    /// Whether a CPU profile is currently being taken.
+    #[cfg(feature = "cpu-profiling")]
+    cpu_profile_running: AtomicBool,
+}
+
+impl Profiler {
+    /// Writes `contents` to a new file in the output directory, named after `kind`, the current
+    /// time and `extension`.
+    fn write(
+        &self,
+        kind: &str,
+        extension: &str,
+        contents: &[u8],
+    ) -> Result<PathBuf, ProfilingError> {
+        let path = self.output_dir.join(format!(
+            "{}-{}.{}",
+            kind,
+            Timestamp::now().millis(),
+            extension
+        ));
+        write_file(&path, contents).map_err(|error| ProfilingError::Write {
+            path: path.clone(),
+            error,
+        })?;
+        info!(path = %path.display(), "wrote {}", kind);
+        Ok(path)
+    }
+}
+
+/// Initializes profiling with the given config, if enabled.
+///
+/// Only the first call has any effect.
+pub(crate) fn init(config: &WithDir<ProfilingConfig>) {
+    let profiling_config = config.value();
+    if !profiling_config.enabled {
+        return;
+    }
+    let profiler = Profiler {
+        output_dir: config.with_dir(profiling_config.output_dir.clone()),
+        #[cfg(feature = "cpu-profiling")]
+        max_cpu_profile_duration: profiling_config.max_cpu_profile_duration,
+        #[cfg(feature = "cpu-profiling")]
+        cpu_profile_frequency: profiling_config.cpu_profile_frequency,
+        #[cfg(feature = "cpu-profiling")]
+        cpu_profile_running: AtomicBool::new(false),
+    };
+    drop(PROFILER.set(profiler));
+}
+
+/// Samples the CPU usage of the node for `duration`, and writes the profile in pprof format to the
+/// output directory, returning its path.
+#[cfg(feature = "cpu-profiling")]
+pub(crate) async fn profile_cpu(duration: TimeDiff) -> Result<PathBuf, ProfilingError> {
+    let profiler = PROFILER.get().ok_or(ProfilingError::Disabled)?;
+    if duration > profiler.max_cpu_profile_duration {
+        return Err(ProfilingError::DurationTooLong {
+            requested: duration,
+            maximum: profiler.max_cpu_profile_duration,
+        });
+    }
+    if profiler.cpu_profile_running.swap(true, Ordering::SeqCst) {
+        return Err(ProfilingError::AlreadyRunning);
+    }
+
+    // Sampling blocks the thread for the whole duration, so it runs on a blocking thread.
+    let frequency = i32::from(profiler.cpu_profile_frequency);
+    let result = tokio::task::spawn_blocking(move || -> Result<Vec<u8>, ProfilingError> {
+        let guard = ProfilerGuardBuilder::default()
+            .frequency(frequency)
+            .blocklist(CPU_PROFILE_BLOCKLIST)
+            .build()?;
+        thread::sleep(duration.into());
+        let profile = guard.report().build()?.pprof()?;
+        Ok(profile.encode_to_vec())
+    })
+    .await;
+    profiler.cpu_profile_running.store(false, Ordering::SeqCst);
+
+    let encoded = result.map_err(|_| ProfilingError::Panicked)??;
+    profiler.write("cpu", "pb", &encoded)
+}
+
+/// Returns an error, as the node was built without the `cpu-profiling` feature.
+#[cfg(not(feature = "cpu-profiling"))]
+pub(crate) async fn profile_cpu(_duration: TimeDiff) -> Result<PathBuf, ProfilingError> {
+    PROFILER.get().ok_or(ProfilingError::Disabled)?;
+    Err(ProfilingError::CpuProfilingUnsupported)
+}
+
+/// Writes a dump of the effects which have not completed yet to the output directory, returning
+/// its path.
+pub(crate) fn dump_tasks() -> Result<PathBuf, ProfilingError> {
+    let profiler = PROFILER.get().ok_or(ProfilingError::Disabled)?;
+    let task_dump = TaskDump::new(&lock_pending_tasks(), Instant::now());
+    let encoded = serde_json::to_vec_pretty(&task_dump)?;
+    profiler.write("tasks", "json", &encoded)
+}
+
+/// Writes `contents` to `path`, creating its parent directory if required.
+fn write_file(path: &Path, contents: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+/// An effect spawned onto the async runtime which has not completed yet.
+#[derive(Debug)]
+struct PendingTask {
+    /// The description of the event whose handling created the effect.
+    spawned_by: &'static str,
+    /// The ID of the event whose handling created the effect, if any.
+    ancestor: Option<NonZeroU64>,
+    /// The time the effect was spawned.
+    spawned_at: Instant,
+}
+
+/// Tracks a spawned task until dropped.
+#[derive(Debug)]
+pub(crate) struct TaskGuard(u64);
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        lock_pending_tasks().remove(&self.0);
+    }
+}
+
+/// Starts tracking an effect spawned while handling an event described by `spawned_by`, until the
+/// returned guard is dropped.
+///
+/// Returns `None` without tracking the effect if profiling is not enabled.
+pub(crate) fn track_task(
+    spawned_by: &'static str,
+    ancestor: Option<NonZeroU64>,
+) -> Option<TaskGuard> {
+    PROFILER.get()?;
+    Some(track_task_unconditionally(spawned_by, ancestor))
+}
+
+/// Starts tracking an effect regardless of whether profiling is enabled.
+fn track_task_unconditionally(spawned_by: &'static str, ancestor: Option<NonZeroU64>) -> TaskGuard {
+    let task_id = NEXT_TASK_ID.fetch_add(1, Ordering::Relaxed);
+    let pending_task = PendingTask {
+        spawned_by,
+        ancestor,
+        spawned_at: Instant::now(),
+    };
+    lock_pending_tasks().insert(task_id, pending_task);
+    TaskGuard(task_id)
+}
+
+/// Locks the pending tasks, ignoring poisoning since they are only ever inserted or removed.
+fn lock_pending_tasks() -> MutexGuard<'static, HashMap<u64, PendingTask>> {
+    PENDING_TASKS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The pending tasks spawned while handling one type of event.
+#[derive(Debug, PartialEq, Serialize)]
+struct TaskGroup {
+    /// The description of the events whose handling created the tasks.
+    spawned_by: &'static str,
+    /// The number of pending tasks.
+    count: usize,
+    /// The time since the oldest of the tasks was spawned.
+    oldest_age: TimeDiff,
+    /// The ID of the event whose handling created the oldest of the tasks, if any.
+    oldest_ancestor: Option<NonZeroU64>,
+}
+
+/// A dump of the pending tasks.
+#[derive(Debug, Serialize)]
+struct TaskDump {
+    /// The total number of pending tasks.
+    pending_tasks: usize,
+    /// The pending tasks grouped by the type of event whose handling created them, those with the
+    /// oldest tasks first.
+    groups: Vec<TaskGroup>,
+}
+
+impl TaskDump {
+    fn new(pending_tasks: &HashMap<u64, PendingTask>, now: Instant) -> Self {
+        let mut groups: BTreeMap<&'static str, TaskGroup> = BTreeMap::new();
+        for task in pending_tasks.values() {
+            let age = TimeDiff::from(now.saturating_duration_since(task.spawned_at));
+            let group = groups.entry(task.spawned_by).or_insert(TaskGroup {
+                spawned_by: task.spawned_by,
+                count: 0,
+                oldest_age: age,
+                oldest_ancestor: task.ancestor,
+            });
+            group.count += 1;
+            if age > group.oldest_age {
+                group.oldest_age = age;
+                group.oldest_ancestor = task.ancestor;
+            }
+        }
+        let mut groups: Vec<TaskGroup> = groups.into_values().collect();
+        groups.sort_by(|group1, group2| group2.oldest_age.cmp(&group1.oldest_age));
+        TaskDump {
+            pending_tasks: pending_tasks.len(),
+            groups,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn should_group_pending_tasks_oldest_first() {
+        let now = Instant::now();
+        let task = |spawned_by, ancestor, age_secs| PendingTask {
+            spawned_by,
+            ancestor: NonZeroU64::new(ancestor),
+            spawned_at: now - Duration::from_secs(age_secs),
+        };
+        let pending_tasks: HashMap<u64, PendingTask> = vec![
+            (0, task("SmallNetwork", 1, 1)),
+            (1, task("Consensus", 2, 30)),
+            (2, task("SmallNetwork", 3, 5)),
+        ]
+        .into_iter()
+        .collect();
+
+        let task_dump = TaskDump::new(&pending_tasks, now);
+        assert_eq!(task_dump.pending_tasks, 3);
+        assert_eq!(
+            task_dump.groups,
+            vec![
+                TaskGroup {
+                    spawned_by: "Consensus",
+                    count: 1,
+                    oldest_age: TimeDiff::from_seconds(30),
+                    oldest_ancestor: NonZeroU64::new(2),
+                },
+                TaskGroup {
+                    spawned_by: "SmallNetwork",
+                    count: 2,
+                    oldest_age: TimeDiff::from_seconds(5),
+                    oldest_ancestor: NonZeroU64::new(3),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_stop_tracking_dropped_tasks() {
+        // Profiling is not enabled in tests, so tasks aren't tracked by default.
+        assert!(track_task("test", None).is_none());
+
+        let guard = track_task_unconditionally("test", None);
+        assert!(lock_pending_tasks().contains_key(&guard.0));
+        let task_id = guard.0;
+        drop(guard);
+        assert!(!lock_pending_tasks().contains_key(&task_id));
+    }
+}
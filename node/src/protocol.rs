@@ -20,13 +20,13 @@ use crate::{
     },
     effect::{
         incoming::{
-            ConsensusMessageIncoming, FinalitySignatureIncoming, GossiperIncoming, NetRequest,
-            NetRequestIncoming, NetResponse, NetResponseIncoming, TrieDemand, TrieRequest,
-            TrieRequestIncoming, TrieResponse, TrieResponseIncoming,
+            ConsensusMessageIncoming, DeployApprovalsIncoming, FinalitySignatureIncoming,
+            GossiperIncoming, NetRequest, NetRequestIncoming, NetResponse, NetResponseIncoming,
+            TrieDemand, TrieRequest, TrieRequestIncoming, TrieResponse, TrieResponseIncoming,
         },
         AutoClosingResponder, EffectBuilder,
     },
-    types::{Deploy, FinalitySignature, Item, NodeId, Tag},
+    types::{Deploy, DeployWithApprovals, FinalitySignature, Item, NodeId, Tag},
 };
 
 /// Reactor message.
@@ -58,6 +58,9 @@ pub(crate) enum Message {
     /// Finality signature.
     #[from]
     FinalitySignature(Box<FinalitySignature>),
+    /// Approvals of an already known deploy.
+    #[from]
+    DeployApprovals(Box<DeployWithApprovals>),
 }
 
 impl Payload for Message {
@@ -81,9 +84,11 @@ impl Payload for Message {
                     Tag::BlockAndDeploysByHash => MessageKind::BlockTransfer,
                     Tag::BlockHeaderBatch => MessageKind::BlockTransfer,
                     Tag::FinalitySignaturesByHash => MessageKind::BlockTransfer,
+                    Tag::BackfillFinalitySignaturesByHash => MessageKind::BlockTransfer,
                 }
             }
             Message::FinalitySignature(_) => MessageKind::Consensus,
+            Message::DeployApprovals(_) => MessageKind::DeployGossip,
         }
     }
 
@@ -98,6 +103,20 @@ impl Payload for Message {
             Message::GetRequest { .. } => false,
             Message::GetResponse { .. } => false,
             Message::FinalitySignature(_) => false,
+            Message::DeployApprovals(_) => false,
+        }
+    }
+
+    fn is_droppable(&self) -> bool {
+        // Gossip is best-effort: the gossiping peer moves on to another peer if we don't respond.
+        match self {
+            Message::DeployGossiper(_)
+            | Message::AddressGossiper(_)
+            | Message::DeployApprovals(_) => true,
+            Message::Consensus(_)
+            | Message::GetRequest { .. }
+            | Message::GetResponse { .. }
+            | Message::FinalitySignature(_) => false,
         }
     }
 
@@ -119,6 +138,7 @@ impl Payload for Message {
                 Tag::BlockAndDeploysByHash => weights.block_requests,
                 Tag::BlockHeaderBatch => weights.block_requests,
                 Tag::FinalitySignaturesByHash => weights.block_requests,
+                Tag::BackfillFinalitySignaturesByHash => weights.block_requests,
             },
             Message::GetResponse { tag, .. } => match tag {
                 Tag::Deploy => weights.deploy_responses,
@@ -132,8 +152,10 @@ impl Payload for Message {
                 Tag::BlockAndDeploysByHash => weights.block_requests,
                 Tag::BlockHeaderBatch => weights.block_responses,
                 Tag::FinalitySignaturesByHash => weights.block_responses,
+                Tag::BackfillFinalitySignaturesByHash => weights.block_responses,
             },
             Message::FinalitySignature(_) => weights.finality_signatures,
+            Message::DeployApprovals(_) => weights.gossip,
         }
     }
 
@@ -147,6 +169,42 @@ impl Payload for Message {
             Message::GetRequest { .. } => false,
             Message::GetResponse { .. } => false,
             Message::FinalitySignature(_) => false,
+            Message::DeployApprovals(_) => false,
+        }
+    }
+
+    fn requires_envelope(&self) -> bool {
+        // Peers not accepting envelopes predate these payloads, and would drop the connection on
+        // failing to decode them.
+        match self {
+            Message::Consensus(_) => false,
+            Message::DeployGossiper(_) => false,
+            Message::AddressGossiper(_) => false,
+            Message::GetRequest { tag, .. } | Message::GetResponse { tag, .. } => {
+                *tag == Tag::BackfillFinalitySignaturesByHash
+            }
+            Message::FinalitySignature(_) => false,
+            Message::DeployApprovals(_) => true,
+        }
+    }
+
+    fn envelope_tag(&self) -> u16 {
+        // Tags of removed variants must not be reused.
+        match self {
+            Message::Consensus(_) => 0,
+            Message::DeployGossiper(_) => 1,
+            Message::AddressGossiper(_) => 2,
+            Message::GetRequest { .. } => 3,
+            Message::GetResponse { .. } => 4,
+            Message::FinalitySignature(_) => 5,
+            Message::DeployApprovals(_) => 6,
+        }
+    }
+
+    fn envelope_version(tag: u16) -> Option<u16> {
+        match tag {
+            0..=6 => Some(1),
+            _ => None,
         }
     }
 }
@@ -202,6 +260,9 @@ impl Debug for Message {
             Message::FinalitySignature(fs) => {
                 f.debug_tuple("FinalitySignature").field(&fs).finish()
             }
+            Message::DeployApprovals(approvals) => {
+                f.debug_tuple("DeployApprovals").field(&approvals).finish()
+            }
         }
     }
 }
@@ -224,6 +285,9 @@ impl Display for Message {
             Message::FinalitySignature(fs) => {
                 write!(f, "FinalitySignature::({})", fs)
             }
+            Message::DeployApprovals(approvals) => {
+                write!(f, "DeployApprovals::({})", approvals)
+            }
         }
     }
 }
@@ -238,7 +302,8 @@ where
         + From<TrieRequestIncoming>
         + From<TrieDemand>
         + From<TrieResponseIncoming>
-        + From<FinalitySignatureIncoming>,
+        + From<FinalitySignatureIncoming>
+        + From<DeployApprovalsIncoming>,
 {
     // fn from_incoming(sender: NodeId, payload: Message, effect_builder: EffectBuilder<REv>) ->
     // Self {
@@ -303,6 +368,11 @@ where
                     message: NetRequest::FinalitySignatures(serialized_id),
                 }
                 .into(),
+                Tag::BackfillFinalitySignaturesByHash => NetRequestIncoming {
+                    sender,
+                    message: NetRequest::BackfillFinalitySignatures(serialized_id),
+                }
+                .into(),
             },
             Message::GetResponse {
                 tag,
@@ -363,10 +433,16 @@ where
                     message: NetResponse::FinalitySignatures(serialized_item),
                 }
                 .into(),
+                Tag::BackfillFinalitySignaturesByHash => NetResponseIncoming {
+                    sender,
+                    message: NetResponse::BackfillFinalitySignatures(serialized_item),
+                }
+                .into(),
             },
             Message::FinalitySignature(message) => {
                 FinalitySignatureIncoming { sender, message }.into()
             }
+            Message::DeployApprovals(message) => DeployApprovalsIncoming { sender, message }.into(),
         }
     }
 
@@ -27,27 +27,33 @@
 //! With all these set up, a reactor can be executed using a [`Runner`], either in a step-wise
 //! manner using [`Runner::crank`] or indefinitely using [`Runner::run`].
 
+pub(crate) mod correlation;
+mod deploy_trace;
 mod event_queue_metrics;
+mod event_trace;
 pub(crate) mod initializer;
 pub(crate) mod joiner;
 pub(crate) mod participating;
+mod queue_fairness;
 mod queue_kind;
+pub(crate) mod supervision;
 
 #[cfg(test)]
 use std::sync::Arc;
 use std::{
     any,
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     env,
     fmt::{Debug, Display},
     io::Write,
     mem,
-    num::NonZeroU64,
+    num::{NonZeroU64, NonZeroUsize},
     str::FromStr,
     sync::atomic::Ordering,
 };
 
 use datasize::DataSize;
+use enum_iterator::IntoEnumIterator;
 use erased_serde::Serialize as ErasedSerialize;
 use futures::{future::BoxFuture, FutureExt};
 use once_cell::sync::Lazy;
@@ -61,30 +67,40 @@ use tracing::{debug, debug_span, error, info, instrument, trace, warn, Span};
 use tracing_futures::Instrument;
 
 use crate::{
+    alerts::{self, Alert},
     components::{deploy_acceptor, fetcher, fetcher::FetchedOrNotFound},
+    config_reload::{self, ReloadableConfig},
+    crash_dump::{self, Crash},
     effect::{
         announcements::{BlocklistAnnouncement, ControlAnnouncement, QueueDumpFormat},
+        diagnostics_port::{EventCounts, InFlightFetches, MemoryUsage},
         incoming::NetResponse,
         Effect, EffectBuilder, EffectExt, Effects,
     },
+    profiling,
     types::{
-        Block, BlockAndDeploys, BlockHeader, BlockHeaderWithMetadata, BlockHeadersBatch,
-        BlockSignatures, BlockWithMetadata, Deploy, DeployHash, ExitCode, FinalizedApprovalsWithId,
-        Item, NodeId,
+        BackfillBlockSignatures, Block, BlockAndDeploys, BlockHeader, BlockHeaderWithMetadata,
+        BlockHeadersBatch, BlockSignatures, BlockWithMetadata, Deploy, DeployHash, ExitCode,
+        FinalizedApprovalsWithId, Item, NodeId, NodeState,
     },
     unregister_metric,
     utils::{
         self,
         rlimit::{Limit, OpenFiles, ResourceLimit},
-        SharedFlag, Source, WeightedRoundRobin,
+        round_robin::QueueFull,
+        sd_notify, SharedFlag, Source, WeightedRoundRobin,
     },
-    NodeRng, TERMINATION_REQUESTED,
+    NodeRng, RELOAD_CONFIG_REQUESTED, TERMINATION_REQUESTED,
 };
 #[cfg(test)]
 use crate::{
     reactor::initializer::Reactor as InitializerReactor,
     types::{Chainspec, ChainspecRawBytes},
 };
+use correlation::CorrelationId;
+use deploy_trace::DeployTracer;
+use event_trace::EventRecorder;
+use queue_fairness::QueueFairnessTracker;
 pub(crate) use queue_kind::QueueKind;
 
 /// Default threshold for when an event is considered slow.  Can be overridden by setting the env
@@ -106,6 +122,80 @@ static DISPATCH_EVENT_THRESHOLD: Lazy<Duration> = Lazy::new(|| {
         .unwrap_or_else(|_| DEFAULT_DISPATCH_EVENT_THRESHOLD)
 });
 
+/// Default threshold for when an event queue whose oldest event is still waiting is considered
+/// starved.  Can be overridden by setting the env var `CL_QUEUE_STARVATION_MILLIS=<MILLISECONDS>`.
+const DEFAULT_QUEUE_STARVATION_THRESHOLD: Duration = Duration::from_secs(30);
+const QUEUE_STARVATION_THRESHOLD_ENV_VAR: &str = "CL_QUEUE_STARVATION_MILLIS";
+
+static QUEUE_STARVATION_THRESHOLD: Lazy<Duration> = Lazy::new(|| {
+    env::var(QUEUE_STARVATION_THRESHOLD_ENV_VAR)
+        .map(|threshold_str| {
+            let threshold_millis = u64::from_str(&threshold_str).unwrap_or_else(|error| {
+                panic!(
+                    "can't parse env var {}={} as a u64: {}",
+                    QUEUE_STARVATION_THRESHOLD_ENV_VAR, threshold_str, error
+                )
+            });
+            Duration::from_millis(threshold_millis)
+        })
+        .unwrap_or_else(|_| DEFAULT_QUEUE_STARVATION_THRESHOLD)
+});
+
+/// Default time an orderly shutdown may take before the node stops regardless.  Can be overridden
+/// by setting the env var `CL_SHUTDOWN_TIMEOUT_SECS=<SECONDS>`.
+const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(60);
+const SHUTDOWN_TIMEOUT_ENV_VAR: &str = "CL_SHUTDOWN_TIMEOUT_SECS";
+
+static SHUTDOWN_TIMEOUT: Lazy<Duration> = Lazy::new(|| {
+    env::var(SHUTDOWN_TIMEOUT_ENV_VAR)
+        .map(|timeout_str| {
+            let timeout_secs = u64::from_str(&timeout_str).unwrap_or_else(|error| {
+                panic!(
+                    "can't parse env var {}={} as a u64: {}",
+                    SHUTDOWN_TIMEOUT_ENV_VAR, timeout_str, error
+                )
+            });
+            Duration::from_secs(timeout_secs)
+        })
+        .unwrap_or_else(|_| DEFAULT_SHUTDOWN_TIMEOUT)
+});
+
+/// Prefix of the env vars overriding the capacity of an event queue.  Setting
+/// `CL_EVENT_QUEUE_CAPACITY_<QUEUE>=<EVENTS>`, e.g. `CL_EVENT_QUEUE_CAPACITY_NETWORK_INCOMING=1000`,
+/// limits the given queue to that many events, with `0` leaving it unbounded.
+const EVENT_QUEUE_CAPACITY_ENV_VAR_PREFIX: &str = "CL_EVENT_QUEUE_CAPACITY_";
+
+static EVENT_QUEUE_CAPACITIES: Lazy<HashMap<QueueKind, NonZeroUsize>> = Lazy::new(|| {
+    QueueKind::into_enum_iter()
+        .filter_map(|queue_kind| {
+            let env_var = format!(
+                "{}{}",
+                EVENT_QUEUE_CAPACITY_ENV_VAR_PREFIX,
+                queue_kind.metrics_name().to_uppercase()
+            );
+            let capacity = match env::var(&env_var) {
+                Ok(capacity_str) => {
+                    let capacity = usize::from_str(&capacity_str).unwrap_or_else(|error| {
+                        panic!(
+                            "can't parse env var {}={} as a usize: {}",
+                            env_var, capacity_str, error
+                        )
+                    });
+                    NonZeroUsize::new(capacity)
+                }
+                Err(_) => queue_kind.default_capacity(),
+            };
+            capacity.map(|capacity| (queue_kind, capacity))
+        })
+        .collect()
+});
+
+/// How often to check whether an orderly shutdown has completed while no events are queued.
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Describes the effects created when instantiating a reactor in task dumps.
+const INITIAL_EFFECTS: &str = "initial effects";
+
 /// The desired limit for open files.
 const TARGET_OPEN_FILES_LIMIT: Limit = 64_000;
 
@@ -163,9 +253,28 @@ pub(crate) enum ReactorExit {
 ///
 /// Components rarely use this, but use a bound `EventQueueHandle` instead.
 ///
-/// Schedule tuples contain an optional ancestor ID and the actual event. The ancestor ID indicates
-/// which potential previous event resulted in the event being created.
-pub(crate) type Scheduler<Ev> = WeightedRoundRobin<(Option<NonZeroU64>, Ev), QueueKind>;
+/// Schedule tuples contain the origin of the event and the actual event.
+pub(crate) type Scheduler<Ev> = WeightedRoundRobin<(EventOrigin, Ev), QueueKind>;
+
+/// The origin of a scheduled event.
+#[derive(Copy, Clone, Debug, Default, Serialize)]
+pub(crate) struct EventOrigin {
+    /// The ID of the event whose effects resulted in the event being created, if any.
+    ancestor: Option<NonZeroU64>,
+    /// The correlation ID of the trigger the event originates from, if the event was scheduled by
+    /// an effect or while dispatching an event.
+    correlation_id: Option<CorrelationId>,
+}
+
+impl EventOrigin {
+    /// Creates the origin of an event scheduled now, inheriting the current correlation ID.
+    fn new(ancestor: Option<NonZeroU64>) -> Self {
+        EventOrigin {
+            ancestor,
+            correlation_id: correlation::current(),
+        }
+    }
+}
 
 /// Event queue handle
 ///
@@ -231,7 +340,24 @@ impl<REv> EventQueueHandle<REv> {
         REv: From<Ev>,
     {
         self.scheduler
-            .push((ancestor, event.into()), queue_kind)
+            .push((EventOrigin::new(ancestor), event.into()), queue_kind)
+            .await
+    }
+
+    /// Schedule an event which may be dropped under load on a specific queue.
+    ///
+    /// Returns an error without scheduling the event if the queue is at its capacity, in which case
+    /// the caller should shed load.
+    pub(crate) async fn try_schedule<Ev>(
+        self,
+        event: Ev,
+        queue_kind: QueueKind,
+    ) -> Result<(), QueueFull>
+    where
+        REv: From<Ev>,
+    {
+        self.scheduler
+            .try_push((EventOrigin::new(None), event.into()), queue_kind)
             .await
     }
 
@@ -240,6 +366,12 @@ impl<REv> EventQueueHandle<REv> {
         self.scheduler.event_queues_counts()
     }
 
+    /// Returns the number of events dropped because they were scheduled on a full queue, for each
+    /// of the scheduler's queues.
+    pub(crate) fn dropped_event_counts(&self) -> HashMap<QueueKind, usize> {
+        self.scheduler.dropped_counts()
+    }
+
     /// Returns whether the associated reactor is currently shutting down.
     pub(crate) fn shutdown_flag(&self) -> SharedFlag {
         self.is_shutting_down
@@ -295,6 +427,53 @@ pub(crate) trait Reactor: Sized {
 
     /// Instructs the reactor to update performance metrics, if any.
     fn update_metrics(&mut self, _event_queue_handle: EventQueueHandle<Self::Event>) {}
+
+    /// Applies the node-local settings which can be changed without restarting, after they have
+    /// been reloaded from the config file.
+    ///
+    /// Returns an error if the reactor doesn't support changing them.
+    fn reload_config(&mut self, _reloadable_config: &ReloadableConfig) -> anyhow::Result<()> {
+        Err(anyhow::anyhow!(
+            "the config can only be reloaded once the node is participating"
+        ))
+    }
+
+    /// Returns the fetches of all fetchers which are waiting for responses from peers.
+    fn in_flight_fetches(&self) -> InFlightFetches {
+        InFlightFetches::default()
+    }
+
+    /// Returns the estimated heap memory used by each component.
+    fn estimate_memory_usage(&self) -> MemoryUsage {
+        MemoryUsage::default()
+    }
+
+    /// Returns the connected peers and their addresses.
+    fn peers(&self) -> BTreeMap<NodeId, String> {
+        BTreeMap::new()
+    }
+
+    /// Returns the node's syncing state, if known.
+    fn node_state(&self) -> Option<NodeState> {
+        None
+    }
+
+    /// Begins an orderly shutdown, in which the reactor stops taking on new work but keeps
+    /// dispatching events until the work in flight has been wound down.
+    ///
+    /// Returns `false` if the reactor does not support an orderly shutdown, in which case it is
+    /// stopped right away.
+    fn begin_shutdown(&mut self) -> bool {
+        false
+    }
+
+    /// Returns whether the work in flight when the orderly shutdown began has been wound down.
+    fn is_shut_down(&self) -> bool {
+        true
+    }
+
+    /// Completes an orderly shutdown once `is_shut_down` returns `true`, e.g. by persisting state.
+    fn finish_shutdown(&mut self) {}
 }
 
 /// A reactor event type.
@@ -358,6 +537,18 @@ where
     /// Counter for events, to aid tracing.
     current_event_id: u64,
 
+    /// The number of events dispatched, per event description.
+    event_counts: EventCounts,
+
+    /// How fairly the event queues are serviced.
+    queue_fairness: QueueFairnessTracker,
+
+    /// The events relating to traced deploys.
+    deploy_tracer: DeployTracer,
+
+    /// Records the dispatched events, if enabled.
+    event_recorder: Option<EventRecorder>,
+
     /// Timestamp of last reactor metrics update.
     last_metrics: Instant,
 
@@ -491,24 +682,43 @@ where
             );
         }
 
-        let scheduler = utils::leak(Scheduler::new(QueueKind::weights()));
+        let scheduler = utils::leak(Scheduler::with_capacities(
+            QueueKind::weights(),
+            EVENT_QUEUE_CAPACITIES.clone(),
+        ));
         let is_shutting_down = SharedFlag::new();
 
         let event_queue = EventQueueHandle::new(scheduler, is_shutting_down);
+        let event_recorder = EventRecorder::maybe_start::<R>(rng);
         let (reactor, initial_effects) = R::new(cfg, registry, event_queue, rng)?;
 
         // Run all effects from component instantiation.
-        process_effects(None, scheduler, initial_effects)
+        process_effects(None, None, INITIAL_EFFECTS, scheduler, initial_effects)
             .instrument(debug_span!("process initial effects"))
             .await;
 
         info!("reactor main loop is ready");
 
+        let metrics = RunnerMetrics::new(registry)?;
+        if let Some(timeout) = sd_notify::watchdog_timeout() {
+            debug!(?timeout, "pinging the systemd watchdog");
+            tokio::spawn(sd_notify::run_watchdog(
+                timeout,
+                metrics.events.clone(),
+                move || scheduler.item_count(),
+                is_shutting_down,
+            ));
+        }
+
         Ok(Runner {
             scheduler,
             reactor,
             current_event_id: 1,
-            metrics: RunnerMetrics::new(registry)?,
+            event_counts: EventCounts::default(),
+            queue_fairness: QueueFairnessTracker::new(registry, *QUEUE_STARVATION_THRESHOLD)?,
+            deploy_tracer: DeployTracer::default(),
+            event_recorder,
+            metrics,
             last_metrics: Instant::now(),
             event_metrics_min_delay: Duration::from_secs(30),
             event_metrics_threshold: 1000,
@@ -520,7 +730,12 @@ where
     /// Processes a single event on the event queue.
     ///
     /// Returns `false` if processing should stop.
-    #[instrument("dispatch", level = "debug", fields(a, ev = self.current_event_id), skip(self, rng))]
+    #[instrument(
+        "dispatch",
+        level = "debug",
+        fields(a, cid, ev = self.current_event_id),
+        skip(self, rng)
+    )]
     pub(crate) async fn crank(&mut self, rng: &mut NodeRng) -> bool {
         self.metrics.events.inc();
 
@@ -552,18 +767,49 @@ where
             }
         }
 
-        let ((ancestor, event), queue) = self.scheduler.pop().await;
+        let ((origin, event), queue, wait_time) = self.scheduler.pop_with_wait_time().await;
         trace!(%event, %queue, "current");
+        self.queue_fairness.record_dispatch(queue, wait_time);
+        if self.queue_fairness.starvation_check_due() {
+            self.queue_fairness
+                .check_starvation(&self.scheduler.oldest_item_ages());
+        }
         let event_desc = event.description();
+        *self.event_counts.0.entry(event_desc).or_default() += 1;
 
         // Create another span for tracing the processing of one event.
         Span::current().record("ev", &self.current_event_id);
 
         // If we know the ancestor of an event, record it.
-        if let Some(ancestor) = ancestor {
+        if let Some(ancestor) = origin.ancestor {
             Span::current().record("a", &ancestor.get());
         }
 
+        // An event without a correlation ID is a trigger in its own right, starting a new one.
+        let correlation_id = origin.correlation_id.unwrap_or_else(|| {
+            CorrelationId::new(
+                NonZeroU64::new(self.current_event_id).expect("event IDs should start at 1"),
+            )
+        });
+        Span::current().record("cid", &correlation_id.get());
+
+        if self.deploy_tracer.is_active() {
+            self.deploy_tracer.record(
+                self.current_event_id,
+                correlation_id,
+                queue,
+                event_desc,
+                &event,
+            );
+        }
+
+        if let Some(recorder) = self.event_recorder.as_mut() {
+            if let Err(error) = recorder.record(self.current_event_id, queue, &event) {
+                error!(%error, "failed to record event, stopping recording");
+                self.event_recorder = None;
+            }
+        }
+
         // Dispatch the event, then execute the resulting effect.
         let start = self.clock.start();
 
@@ -582,8 +828,22 @@ where
                 }
                 Some(ControlAnnouncement::FatalError { file, line, msg }) => {
                     error!(%file, %line, %msg, "fatal error via control announcement");
+                    self.write_crash_dump(format!("fatal error at {}:{}: {}", file, line, msg))
+                        .await;
+                    alerts::send(Alert::FatalError { file, line, msg }).await;
                     (Default::default(), false)
                 }
+                Some(ControlAnnouncement::ForkDetected(evidence)) => {
+                    error!(
+                        height = %evidence.height(),
+                        local_block_hash = %evidence.local.block_header.hash(),
+                        conflicting_block_hash = %evidence.conflicting.block_header.hash(),
+                        peer = %evidence.peer,
+                        "FORK DETECTED: the linear chain has forked past finalization; evidence \
+                        has been persisted and requires operator attention"
+                    );
+                    (Default::default(), true)
+                }
                 Some(ControlAnnouncement::QueueDumpRequest {
                     dump_format,
                     finished,
@@ -631,12 +891,48 @@ where
                     // Do nothing on queue dump otherwise.
                     (Default::default(), true)
                 }
+                Some(ControlAnnouncement::ReloadConfigRequest { responder }) => {
+                    let result = self.reload_config().map_err(|error| format!("{:#}", error));
+                    responder.respond(result).await;
+                    (Default::default(), true)
+                }
+                Some(ControlAnnouncement::EventCountsRequest { responder }) => {
+                    responder.respond(self.event_counts.clone()).await;
+                    (Default::default(), true)
+                }
+                Some(ControlAnnouncement::DeployTraceRequest {
+                    deploy_hash,
+                    stop,
+                    responder,
+                }) => {
+                    responder
+                        .respond(self.deploy_tracer.trace(deploy_hash, stop))
+                        .await;
+                    (Default::default(), true)
+                }
+                Some(ControlAnnouncement::QueueFairnessRequest { responder }) => {
+                    let oldest_item_ages = self.scheduler.oldest_item_ages();
+                    responder
+                        .respond(self.queue_fairness.snapshot(&oldest_item_ages))
+                        .await;
+                    (Default::default(), true)
+                }
+                Some(ControlAnnouncement::InFlightFetchesRequest { responder }) => {
+                    responder.respond(self.reactor.in_flight_fetches()).await;
+                    (Default::default(), true)
+                }
+                Some(ControlAnnouncement::MemoryUsageRequest { responder }) => {
+                    responder
+                        .respond(self.reactor.estimate_memory_usage())
+                        .await;
+                    (Default::default(), true)
+                }
             }
         } else {
-            (
-                self.reactor.dispatch_event(effect_builder, rng, event),
-                true,
-            )
+            let effects = correlation::with_dispatch(correlation_id, || {
+                self.reactor.dispatch_event(effect_builder, rng, event)
+            });
+            (effects, true)
         };
 
         let end = self.clock.end();
@@ -653,6 +949,8 @@ where
         // Run effects, with the current event ID as the ancestor for resulting set of events.
         process_effects(
             NonZeroU64::new(self.current_event_id),
+            Some(correlation_id),
+            event_desc,
             self.scheduler,
             effects,
         )
@@ -664,6 +962,15 @@ where
         keep_going
     }
 
+    /// Re-reads the node-local settings which can be changed without restarting from the config
+    /// file, and applies them to the reactor.
+    fn reload_config(&mut self) -> anyhow::Result<()> {
+        let reloadable_config = config_reload::load()?;
+        self.reactor.reload_config(&reloadable_config)?;
+        info!(?reloadable_config, "reloaded config");
+        Ok(())
+    }
+
     /// Gets both the allocated and total memory from sys-info + jemalloc
     fn get_allocated_memory() -> Option<AllocatedMem> {
         let mem_info = match sys_info::mem_info() {
@@ -694,12 +1001,67 @@ where
         })
     }
 
+    /// Writes a crash dump bundle for the node stopping due to `reason`, unless crash dumps are
+    /// disabled.
+    async fn write_crash_dump(&mut self, reason: String) {
+        if !crash_dump::is_enabled() {
+            return;
+        }
+        let mut crash = Crash {
+            reason,
+            node_state: self.reactor.node_state(),
+            peers: self.reactor.peers(),
+            queue_dump: None,
+        };
+        self.scheduler
+            .dump(|dump| match serde_json::to_value(dump) {
+                Ok(value) => crash.queue_dump = Some(value),
+                Err(err) => warn!(%err, "queue dump failed to serialize"),
+            })
+            .await;
+        crash_dump::write(crash);
+    }
+
+    /// Shuts the reactor down in an orderly fashion, dispatching events until the work in flight
+    /// has been wound down or `SHUTDOWN_TIMEOUT` has passed.
+    ///
+    /// Returns `false` if the reactor does not support an orderly shutdown, or did not complete it.
+    async fn shut_down_gracefully(&mut self, rng: &mut NodeRng) -> bool {
+        if !self.reactor.begin_shutdown() {
+            return false;
+        }
+        info!(timeout = ?*SHUTDOWN_TIMEOUT, "shutting down");
+
+        let deadline = Instant::now() + *SHUTDOWN_TIMEOUT;
+        while !self.reactor.is_shut_down() {
+            if Instant::now() >= deadline {
+                warn!("timed out winding down, stopping regardless");
+                return false;
+            }
+            // Waiting for an event could block past the deadline, so we only crank if one is due.
+            if self.scheduler.item_count() == 0 {
+                tokio::time::sleep(SHUTDOWN_POLL_INTERVAL).await;
+            } else if !self.crank(rng).await {
+                return false;
+            }
+        }
+
+        self.reactor.finish_shutdown();
+        info!("shut down");
+        true
+    }
+
     /// Runs the reactor until `maybe_exit()` returns `Some` or we get interrupted by a termination
     /// signal.
     pub(crate) async fn run(&mut self, rng: &mut NodeRng) -> ReactorExit {
         loop {
             match TERMINATION_REQUESTED.load(Ordering::SeqCst) as i32 {
                 0 => {
+                    if RELOAD_CONFIG_REQUESTED.swap(false, Ordering::SeqCst) {
+                        if let Err(error) = self.reload_config() {
+                            warn!(error = format!("{:#}", error), "failed to reload config");
+                        }
+                    }
                     if let Some(reactor_exit) = self.reactor.maybe_exit() {
                         self.is_shutting_down.set();
 
@@ -720,6 +1082,17 @@ where
                                 match ctrl_ann {
                                     ControlAnnouncement::FatalError { file, line, msg } => {
                                         warn!(%file, line=*line, %msg, "exiting due to fatal error scheduled before reactor completion");
+                                        self.write_crash_dump(format!(
+                                            "fatal error at {}:{}: {}",
+                                            file, line, msg
+                                        ))
+                                        .await;
+                                        alerts::send(Alert::FatalError {
+                                            file: *file,
+                                            line: *line,
+                                            msg: msg.clone(),
+                                        })
+                                        .await;
                                         return ReactorExit::ProcessShouldExit(ExitCode::Abort);
                                     }
                                     ControlAnnouncement::QueueDumpRequest { .. } => {
@@ -727,12 +1100,35 @@ where
                                         // Maybe return an error instead, something like "reactor is
                                         // shutting down"?
                                     }
+                                    ControlAnnouncement::ForkDetected(evidence) => {
+                                        error!(%evidence, "fork detected before reactor completion");
+                                    }
+                                    ControlAnnouncement::ReloadConfigRequest { .. }
+                                    | ControlAnnouncement::EventCountsRequest { .. }
+                                    | ControlAnnouncement::QueueFairnessRequest { .. }
+                                    | ControlAnnouncement::DeployTraceRequest { .. }
+                                    | ControlAnnouncement::InFlightFetchesRequest { .. }
+                                    | ControlAnnouncement::MemoryUsageRequest { .. } => {
+                                        // Config reloads and diagnostics requests are not handled
+                                        // when shutting down.
+                                    }
                                 }
                             } else {
                                 debug!(?ancestor, %event, "found non-control announcement while draining queue")
                             }
                         }
 
+                        // Stopping for an upgrade winds down like a requested shutdown, but keeps the
+                        // exit code telling the launcher how to proceed.
+                        if matches!(
+                            reactor_exit,
+                            ReactorExit::ProcessShouldExit(
+                                ExitCode::Success | ExitCode::UpgradeHandoff
+                            )
+                        ) {
+                            self.shut_down_gracefully(rng).await;
+                        }
+
                         break reactor_exit;
                     }
                     if !self.crank(rng).await {
@@ -749,8 +1145,13 @@ where
                     break ReactorExit::ProcessShouldExit(ExitCode::SigQuit);
                 }
                 SIGTERM => {
+                    let exit_code = if self.shut_down_gracefully(rng).await {
+                        ExitCode::CleanShutdown
+                    } else {
+                        ExitCode::SigTerm
+                    };
                     self.is_shutting_down.set();
-                    break ReactorExit::ProcessShouldExit(ExitCode::SigTerm);
+                    break ReactorExit::ProcessShouldExit(exit_code);
                 }
                 _ => error!("should be unreachable - bug in signal handler"),
             }
@@ -796,7 +1197,7 @@ where
 
         let effects = create_effects(effect_builder);
 
-        process_effects(None, self.scheduler, effects)
+        process_effects(None, None, "injected effects", self.scheduler, effects)
             .instrument(debug_span!(
                 "process injected effects",
                 ev = self.current_event_id
@@ -832,7 +1233,10 @@ impl Runner<InitializerReactor> {
         chainspec_raw_bytes: Arc<ChainspecRawBytes>,
     ) -> Result<Self, <InitializerReactor as Reactor>::Error> {
         let registry = Registry::new();
-        let scheduler = utils::leak(Scheduler::new(QueueKind::weights()));
+        let scheduler = utils::leak(Scheduler::with_capacities(
+            QueueKind::weights(),
+            EVENT_QUEUE_CAPACITIES.clone(),
+        ));
 
         let is_shutting_down = SharedFlag::new();
         let event_queue = EventQueueHandle::new(scheduler, is_shutting_down);
@@ -846,7 +1250,7 @@ impl Runner<InitializerReactor> {
 
         // Run all effects from component instantiation.
         let span = debug_span!("process initial effects");
-        process_effects(None, scheduler, initial_effects)
+        process_effects(None, None, INITIAL_EFFECTS, scheduler, initial_effects)
             .instrument(span)
             .await;
 
@@ -860,6 +1264,10 @@ impl Runner<InitializerReactor> {
             // It is important to initial event count to 1, as we use an ancestor event of 0
             // to mean "no ancestor".
             current_event_id: 1,
+            event_counts: EventCounts::default(),
+            queue_fairness: QueueFairnessTracker::new(&registry, *QUEUE_STARVATION_THRESHOLD)?,
+            deploy_tracer: DeployTracer::default(),
+            event_recorder: None,
             metrics: RunnerMetrics::new(&registry)?,
             // Calculate the `last_metrics` timestamp to be exactly one delay in the past. This will
             // cause the runner to collect metrics at the first opportunity.
@@ -874,9 +1282,12 @@ impl Runner<InitializerReactor> {
 
 /// Spawns tasks that will process the given effects.
 ///
-/// Result events from processing the events will be scheduled with the given ancestor.
+/// Result events from processing the events will be scheduled with the given ancestor, and the
+/// effects run with the given correlation ID.
 async fn process_effects<Ev>(
     ancestor: Option<NonZeroU64>,
+    correlation_id: Option<CorrelationId>,
+    spawned_by: &'static str,
     scheduler: &'static Scheduler<Ev>,
     effects: Effects<Ev>,
 ) where
@@ -886,11 +1297,17 @@ async fn process_effects<Ev>(
     let queue_kind = QueueKind::default();
 
     for effect in effects {
-        tokio::spawn(async move {
+        // If profiling is enabled, track the effect until it completes, so that it shows up in task
+        // dumps if it is stuck.
+        let task_guard = profiling::track_task(spawned_by, ancestor);
+        tokio::spawn(correlation::scope(correlation_id, async move {
             for event in effect.await {
-                scheduler.push((ancestor, event), queue_kind).await
+                scheduler
+                    .push((EventOrigin::new(ancestor), event), queue_kind)
+                    .await
             }
-        });
+            drop(task_guard);
+        }));
     }
 }
 
@@ -969,6 +1386,7 @@ where
         + From<fetcher::Event<BlockAndDeploys>>
         + From<fetcher::Event<BlockHeadersBatch>>
         + From<fetcher::Event<BlockSignatures>>
+        + From<fetcher::Event<BackfillBlockSignatures>>
         + From<fetcher::Event<Deploy>>
         + From<BlocklistAnnouncement>,
 {
@@ -1083,5 +1501,14 @@ where
                 serialized_item,
             )
         }
+        NetResponse::BackfillFinalitySignatures(ref serialized_item) => {
+            handle_fetch_response::<R, BackfillBlockSignatures>(
+                reactor,
+                effect_builder,
+                rng,
+                sender,
+                serialized_item,
+            )
+        }
     }
 }
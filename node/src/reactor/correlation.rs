@@ -0,0 +1,110 @@
+//! Correlation IDs.
+//!
+//! Every event is assigned a correlation ID identifying the trigger it ultimately originates from,
+//! e.g. an RPC request, a message from a peer or an expired timer.  An event scheduled from outside
+//! of the reactor starts a new correlation, using its own event ID as the correlation ID, while
+//! the events resulting from its effects, including any requests made by them, inherit its
+//! correlation ID.
+//!
+//! The correlation ID of the event being dispatched is recorded in the `dispatch` span, so it is
+//! included in all log messages emitted while handling the event.
+
+use std::{
+    cell::Cell,
+    fmt::{self, Display, Formatter},
+    future::Future,
+    num::NonZeroU64,
+};
+
+use datasize::DataSize;
+use serde::Serialize;
+
+/// The ID of the trigger an event ultimately originates from.
+#[derive(Copy, Clone, DataSize, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(transparent)]
+pub(crate) struct CorrelationId(NonZeroU64);
+
+impl CorrelationId {
+    /// Returns the correlation ID started by the event with the given ID.
+    pub(crate) fn new(event_id: NonZeroU64) -> Self {
+        CorrelationId(event_id)
+    }
+
+    /// Returns the correlation ID as a number.
+    pub(crate) fn get(self) -> u64 {
+        self.0.get()
+    }
+}
+
+impl Display for CorrelationId {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, formatter)
+    }
+}
+
+tokio::task_local! {
+    /// The correlation ID of the effect being run by the current task.
+    static EFFECT_CORRELATION_ID: Cell<Option<CorrelationId>>;
+}
+
+thread_local! {
+    /// The correlation ID of the event being dispatched on the current thread.
+    static DISPATCH_CORRELATION_ID: Cell<Option<CorrelationId>> = Cell::new(None);
+}
+
+/// Returns the correlation ID of the effect being run by the current task, or else of the event
+/// being dispatched on the current thread.
+pub(crate) fn current() -> Option<CorrelationId> {
+    EFFECT_CORRELATION_ID
+        .try_with(Cell::get)
+        .unwrap_or_else(|_| DISPATCH_CORRELATION_ID.with(Cell::get))
+}
+
+/// Runs `effect` with the given correlation ID, which is inherited by all events it schedules.
+pub(crate) fn scope<F: Future>(
+    correlation_id: Option<CorrelationId>,
+    effect: F,
+) -> impl Future<Output = F::Output> {
+    EFFECT_CORRELATION_ID.scope(Cell::new(correlation_id), effect)
+}
+
+/// Calls `dispatch` with the given correlation ID being the current one.
+pub(crate) fn with_dispatch<R>(correlation_id: CorrelationId, dispatch: impl FnOnce() -> R) -> R {
+    DISPATCH_CORRELATION_ID.with(|current| current.set(Some(correlation_id)));
+    let result = dispatch();
+    DISPATCH_CORRELATION_ID.with(|current| current.set(None));
+    result
+}
+
+/// Detaches the current task from its correlation, so that the events it schedules from now on
+/// start new correlations.
+///
+/// Used by timers, since an expired timer is a trigger in its own right: were it to inherit the
+/// correlation of whatever set it, all events of a component re-arming its timer would share a
+/// single correlation.
+pub(crate) fn detach() {
+    let _ = EFFECT_CORRELATION_ID.try_with(|current| current.set(None));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_inherit_correlation_id_within_scope() {
+        let correlation_id = CorrelationId::new(NonZeroU64::new(7).unwrap());
+        assert_eq!(current(), None);
+
+        let inherited = scope(Some(correlation_id), async {
+            let before = current();
+            detach();
+            (before, current())
+        })
+        .await;
+        assert_eq!(inherited, (Some(correlation_id), None));
+
+        let dispatched = with_dispatch(correlation_id, current);
+        assert_eq!(dispatched, Some(correlation_id));
+        assert_eq!(current(), None);
+    }
+}
@@ -0,0 +1,173 @@
+//! End-to-end tracing of deploys through the components.
+//!
+//! While a deploy is traced, every dispatched event whose description mentions the deploy's hash
+//! is recorded, along with every event sharing the correlation ID of a recorded event, i.e. every
+//! event resulting from the same trigger.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::Display,
+};
+
+use casper_types::Timestamp;
+
+use super::{correlation::CorrelationId, QueueKind};
+use crate::{
+    effect::diagnostics_port::{DeployTrace, DeployTraceStep},
+    types::DeployHash,
+};
+
+/// The maximum number of steps kept per traced deploy, after which the oldest are dropped.
+const MAX_STEPS: usize = 1_000;
+
+/// The maximum number of correlation IDs followed per traced deploy.
+const MAX_CORRELATION_IDS: usize = 10_000;
+
+/// The state of a single traced deploy.
+#[derive(Debug)]
+struct Trace {
+    /// The hex-encoded deploy hash, as it appears in event descriptions.
+    hex_hash: String,
+    /// The time at which tracing started.
+    since: Timestamp,
+    /// The correlation IDs of the recorded events.
+    correlation_ids: HashSet<CorrelationId>,
+    /// The recorded steps, oldest first.
+    steps: VecDeque<DeployTraceStep>,
+    /// The number of steps dropped because `MAX_STEPS` was exceeded.
+    dropped_steps: u64,
+}
+
+impl Trace {
+    fn new(deploy_hash: DeployHash) -> Self {
+        Trace {
+            hex_hash: format!("{:?}", deploy_hash.inner()),
+            since: Timestamp::now(),
+            correlation_ids: HashSet::new(),
+            steps: VecDeque::new(),
+            dropped_steps: 0,
+        }
+    }
+
+    /// Returns the steps recorded so far.
+    fn report(&self, deploy_hash: DeployHash) -> DeployTrace {
+        DeployTrace {
+            deploy_hash,
+            tracing_since: Some(self.since),
+            dropped_steps: self.dropped_steps,
+            steps: self.steps.iter().cloned().collect(),
+        }
+    }
+}
+
+/// Records the events relating to traced deploys.
+#[derive(Debug, Default)]
+pub(super) struct DeployTracer {
+    /// The traced deploys.
+    traces: HashMap<DeployHash, Trace>,
+}
+
+impl DeployTracer {
+    /// Returns whether any deploy is being traced.
+    pub(super) fn is_active(&self) -> bool {
+        !self.traces.is_empty()
+    }
+
+    /// Records the given event with each trace it relates to.
+    pub(super) fn record<Ev: Display>(
+        &mut self,
+        event_id: u64,
+        correlation_id: CorrelationId,
+        queue: QueueKind,
+        description: &'static str,
+        event: &Ev,
+    ) {
+        let event = event.to_string();
+        for trace in self.traces.values_mut() {
+            let mentions_deploy = event.contains(&trace.hex_hash);
+            if !mentions_deploy && !trace.correlation_ids.contains(&correlation_id) {
+                continue;
+            }
+            if mentions_deploy && trace.correlation_ids.len() < MAX_CORRELATION_IDS {
+                trace.correlation_ids.insert(correlation_id);
+            }
+            if trace.steps.len() == MAX_STEPS {
+                trace.steps.pop_front();
+                trace.dropped_steps += 1;
+            }
+            trace.steps.push_back(DeployTraceStep {
+                timestamp: Timestamp::now(),
+                event_id,
+                correlation_id,
+                queue,
+                component: description,
+                event: event.clone(),
+            });
+        }
+    }
+
+    /// Returns the steps recorded for the given deploy, starting to trace it if it isn't yet.
+    ///
+    /// If `stop` is true, the deploy is no longer traced afterwards.
+    pub(super) fn trace(&mut self, deploy_hash: DeployHash, stop: bool) -> DeployTrace {
+        if stop {
+            return match self.traces.remove(&deploy_hash) {
+                Some(trace) => trace.report(deploy_hash),
+                None => DeployTrace {
+                    deploy_hash,
+                    tracing_since: None,
+                    dropped_steps: 0,
+                    steps: Vec::new(),
+                },
+            };
+        }
+        self.traces
+            .entry(deploy_hash)
+            .or_insert_with(|| Trace::new(deploy_hash))
+            .report(deploy_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use casper_hashing::Digest;
+
+    use super::*;
+
+    #[test]
+    fn should_follow_correlated_events() {
+        let deploy_hash = DeployHash::new(Digest::hash(b"deploy"));
+        let correlation_id = |id| CorrelationId::new(NonZeroU64::new(id).unwrap());
+        let mut tracer = DeployTracer::default();
+        assert!(!tracer.is_active());
+
+        // Events before tracing started aren't recorded.
+        tracer.record(1, correlation_id(1), QueueKind::Api, "rpc", &deploy_hash);
+        assert!(tracer.trace(deploy_hash, false).steps.is_empty());
+        assert!(tracer.is_active());
+
+        tracer.record(2, correlation_id(2), QueueKind::Api, "rpc", &deploy_hash);
+        tracer.record(
+            3,
+            correlation_id(2),
+            QueueKind::Regular,
+            "storage",
+            &"put deploy",
+        );
+        tracer.record(
+            4,
+            correlation_id(4),
+            QueueKind::Regular,
+            "storage",
+            &"unrelated",
+        );
+
+        let trace = tracer.trace(deploy_hash, true);
+        assert!(trace.tracing_since.is_some());
+        let event_ids: Vec<_> = trace.steps.iter().map(|step| step.event_id).collect();
+        assert_eq!(event_ids, vec![2, 3]);
+        assert!(!tracer.is_active());
+    }
+}
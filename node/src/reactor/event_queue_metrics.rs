@@ -14,6 +14,8 @@ use crate::{
 pub(super) struct EventQueueMetrics {
     /// Per queue kind gauges that measure number of event in the queue.
     event_queue_gauges: HashMap<QueueKind, IntGauge>,
+    /// Per queue kind gauges that measure number of events dropped because the queue was full.
+    dropped_event_gauges: HashMap<QueueKind, IntGauge>,
     /// Total events count.
     event_total: IntGauge,
     /// Instance of registry to unregister from when being dropped.
@@ -27,6 +29,7 @@ impl EventQueueMetrics {
         event_queue_handle: EventQueueHandle<REv>,
     ) -> Result<Self, prometheus::Error> {
         let mut event_queue_gauges: HashMap<QueueKind, IntGauge> = HashMap::new();
+        let mut dropped_event_gauges: HashMap<QueueKind, IntGauge> = HashMap::new();
         for queue_kind in event_queue_handle.event_queues_counts().keys() {
             let key = format!("scheduler_queue_{}_count", queue_kind.metrics_name());
             let queue_event_counter = IntGauge::new(
@@ -39,6 +42,17 @@ impl EventQueueMetrics {
             registry.register(Box::new(queue_event_counter.clone()))?;
             let result = event_queue_gauges.insert(*queue_kind, queue_event_counter);
             assert!(result.is_none(), "Map keys should not be overwritten.");
+
+            let key = format!("scheduler_queue_{}_dropped", queue_kind.metrics_name());
+            let dropped_event_counter = IntGauge::new(
+                key,
+                format!(
+                    "total number of events dropped because the reactor {} queue was full",
+                    queue_kind.metrics_name()
+                ),
+            )?;
+            registry.register(Box::new(dropped_event_counter.clone()))?;
+            dropped_event_gauges.insert(*queue_kind, dropped_event_counter);
         }
 
         let event_total = IntGauge::new(
@@ -49,6 +63,7 @@ impl EventQueueMetrics {
 
         Ok(EventQueueMetrics {
             event_queue_gauges,
+            dropped_event_gauges,
             event_total,
             registry,
         })
@@ -78,6 +93,12 @@ impl EventQueueMetrics {
             })
             .join(",");
 
+        for (queue, dropped_count) in event_queue_handle.dropped_event_counts() {
+            if let Some(gauge) = self.dropped_event_gauges.get(&queue) {
+                gauge.set(dropped_count as i64);
+            }
+        }
+
         debug!(%total, %event_counts, "Collected new set of event queue sizes metrics.")
     }
 }
@@ -87,6 +108,7 @@ impl Drop for EventQueueMetrics {
         unregister_metric!(self.registry, self.event_total);
         self.event_queue_gauges
             .iter()
+            .chain(&self.dropped_event_gauges)
             .for_each(|(key, queue_gauge)| {
                 self.registry
                     .unregister(Box::new(queue_gauge.clone()))
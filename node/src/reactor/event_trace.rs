@@ -0,0 +1,431 @@
+//! Recording of the events dispatched by a reactor, for replaying them deterministically.
+//!
+//! If the env var `CL_EVENT_TRACE_DIR` is set, each reactor runner writes every event it
+//! dispatches to a trace file in that directory, one JSON object per line.  The first line is a
+//! header holding the seed the runner's RNG was reseeded with right before the reactor was created.
+//!
+//! A trace can be replayed from a test into a fresh reactor created with an RNG seeded the same
+//! way.  The recorded events are dispatched in order, while the resulting effects are dropped
+//! rather than executed, as the events they produced are part of the trace themselves.  Components
+//! doing IO, such as storage, need to be given a copy of the state they had when recording.
+//!
+//! Replaying requires the reactor's events to be deserializable.  The events of the node's main
+//! reactors carry responders, which can't be deserialized, so their traces can only be inspected
+//! for now.
+
+use std::{
+    any, env,
+    fs::{self, File},
+    io::{self, LineWriter, Write},
+    path::PathBuf,
+};
+#[cfg(test)]
+use std::{
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+use once_cell::sync::Lazy;
+use rand::{RngCore, SeedableRng};
+#[cfg(test)]
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+#[cfg(test)]
+use tracing::trace;
+use tracing::{error, info};
+
+use casper_types::Timestamp;
+
+use super::QueueKind;
+#[cfg(test)]
+use super::{EventQueueHandle, Reactor, ReactorEvent};
+#[cfg(test)]
+use crate::effect::EffectBuilder;
+use crate::NodeRng;
+
+/// Env var holding the directory to write event traces to.  Events are only recorded if it is set.
+const EVENT_TRACE_DIR_ENV_VAR: &str = "CL_EVENT_TRACE_DIR";
+
+static EVENT_TRACE_DIR: Lazy<Option<PathBuf>> =
+    Lazy::new(|| env::var_os(EVENT_TRACE_DIR_ENV_VAR).map(PathBuf::from));
+
+/// The seed of a `NodeRng`.
+type Seed = <NodeRng as SeedableRng>::Seed;
+
+/// Error recording or reading an event trace.
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    /// Failed to write or read the trace file.
+    #[error("failed to access event trace {path:?}: {source}")]
+    Io {
+        /// The path of the trace file.
+        path: PathBuf,
+        /// The underlying error.
+        #[source]
+        source: io::Error,
+    },
+    /// Failed to serialize an event.
+    #[error("failed to serialize event: {0}")]
+    Serialize(#[source] serde_json::Error),
+    /// A line of the trace file is not a valid header or event.
+    #[cfg(test)]
+    #[error("invalid line {line} in event trace: {source}")]
+    Deserialize {
+        /// The number of the invalid line, starting at 1.
+        line: usize,
+        /// The underlying error.
+        #[source]
+        source: serde_json::Error,
+    },
+    /// The trace file has no header.
+    #[cfg(test)]
+    #[error("event trace is empty")]
+    MissingHeader,
+    /// The seed in the header is not a valid hex-encoded `NodeRng` seed.
+    #[cfg(test)]
+    #[error("invalid seed in event trace: {0}")]
+    InvalidSeed(base16::DecodeError),
+}
+
+/// The first line of a trace file.
+#[derive(Debug, Serialize, Deserialize)]
+struct Header {
+    /// The type name of the recorded reactor.
+    reactor: String,
+    /// The hex-encoded seed of the RNG the reactor was created with.
+    seed: String,
+}
+
+/// An event dispatched by the recorded reactor.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RecordedEvent<Ev> {
+    /// The ID the runner assigned to the event.
+    pub(crate) id: u64,
+    /// The queue the event was taken from.
+    pub(crate) queue: QueueKind,
+    /// The event itself.
+    pub(crate) event: Ev,
+}
+
+/// Writes the events dispatched by a reactor to a trace file.
+#[derive(Debug)]
+pub(super) struct EventRecorder {
+    /// The path of the trace file.
+    path: PathBuf,
+    /// Writer flushing each event to the file, so the trace is complete even if the node aborts.
+    writer: LineWriter<File>,
+}
+
+impl EventRecorder {
+    /// Starts recording the events of a reactor of type `R` if `CL_EVENT_TRACE_DIR` is set.
+    ///
+    /// `rng` is reseeded with a seed drawn from it, which is written to the trace, so it must be
+    /// called right before the reactor is created.
+    pub(super) fn maybe_start<R>(rng: &mut NodeRng) -> Option<Self> {
+        let dir = EVENT_TRACE_DIR.as_ref()?;
+
+        // Name the trace after the module defining the reactor, e.g. `participating`.
+        let reactor = any::type_name::<R>();
+        let name = reactor.rsplit("::").nth(1).unwrap_or(reactor);
+        let path = dir.join(format!("{}-{}.trace", name, Timestamp::now().millis()));
+
+        let mut seed = Seed::default();
+        rng.fill_bytes(seed.as_mut());
+
+        match Self::create(path, reactor, &seed) {
+            Ok(recorder) => {
+                info!(path = %recorder.path.display(), "recording reactor events");
+                *rng = NodeRng::from_seed(seed);
+                Some(recorder)
+            }
+            Err(error) => {
+                error!(%error, "failed to start recording reactor events");
+                None
+            }
+        }
+    }
+
+    /// Creates a trace file at `path` for a reactor created with an RNG seeded with `seed`.
+    fn create(path: PathBuf, reactor: &str, seed: &Seed) -> Result<Self, Error> {
+        let file = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| File::create(&path))
+            .map_err(|source| Error::Io {
+                path: path.clone(),
+                source,
+            })?;
+
+        let mut recorder = EventRecorder {
+            path,
+            writer: LineWriter::new(file),
+        };
+        recorder.write_line(&Header {
+            reactor: reactor.to_string(),
+            seed: base16::encode_lower(seed),
+        })?;
+        Ok(recorder)
+    }
+
+    /// Records an event taken from `queue` and about to be dispatched.
+    pub(super) fn record<Ev: Serialize>(
+        &mut self,
+        id: u64,
+        queue: QueueKind,
+        event: &Ev,
+    ) -> Result<(), Error> {
+        self.write_line(&RecordedEvent { id, queue, event })
+    }
+
+    fn write_line<T: Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        let mut line = serde_json::to_vec(value).map_err(Error::Serialize)?;
+        line.push(b'\n');
+        self.writer.write_all(&line).map_err(|source| Error::Io {
+            path: self.path.clone(),
+            source,
+        })
+    }
+}
+
+/// The contents of a trace file.
+#[cfg(test)]
+#[derive(Debug)]
+pub(crate) struct EventTrace<Ev> {
+    /// The type name of the recorded reactor.
+    pub(crate) reactor: String,
+    /// The seed of the RNG the reactor was created with.
+    pub(crate) seed: Seed,
+    /// The dispatched events, in order.
+    pub(crate) events: Vec<RecordedEvent<Ev>>,
+}
+
+#[cfg(test)]
+impl<Ev: DeserializeOwned> EventTrace<Ev> {
+    /// Reads the trace file at `path`.
+    pub(crate) fn read(path: &Path) -> Result<Self, Error> {
+        let io_error = |source| Error::Io {
+            path: path.to_path_buf(),
+            source,
+        };
+        let mut lines = BufReader::new(File::open(path).map_err(io_error)?)
+            .lines()
+            .enumerate();
+
+        let header: Header = match lines.next() {
+            Some((_, line)) => serde_json::from_str(&line.map_err(io_error)?)
+                .map_err(|source| Error::Deserialize { line: 1, source })?,
+            None => return Err(Error::MissingHeader),
+        };
+        let mut seed = Seed::default();
+        base16::decode_slice(&header.seed, &mut seed).map_err(Error::InvalidSeed)?;
+
+        let events = lines
+            .map(|(index, line)| {
+                serde_json::from_str(&line.map_err(io_error)?).map_err(|source| {
+                    Error::Deserialize {
+                        line: index + 1,
+                        source,
+                    }
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(EventTrace {
+            reactor: header.reactor,
+            seed,
+            events,
+        })
+    }
+}
+
+#[cfg(test)]
+impl<Ev: ReactorEvent> EventTrace<Ev> {
+    /// Dispatches the recorded events to `reactor` in order, returning the number dispatched.
+    ///
+    /// `reactor` must have been created with an RNG seeded with the trace's seed, which must then
+    /// be passed as `rng`.  The effects of each event are dropped, as the events they produced
+    /// when recording are part of the trace.  Control events are skipped, as they are handled by
+    /// the runner rather than the reactor.
+    pub(crate) fn replay<R>(
+        self,
+        reactor: &mut R,
+        event_queue: EventQueueHandle<Ev>,
+        rng: &mut NodeRng,
+    ) -> usize
+    where
+        R: Reactor<Event = Ev>,
+    {
+        let effect_builder = EffectBuilder::new(event_queue);
+        let mut replayed = 0;
+        for RecordedEvent { id, event, .. } in self.events {
+            if event.as_control().is_some() {
+                continue;
+            }
+            trace!(%id, %event, "replaying event");
+            drop(reactor.dispatch_event(effect_builder, rng, event));
+            replayed += 1;
+        }
+        replayed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fmt::{self, Display, Formatter},
+        thread,
+    };
+
+    use derive_more::From;
+    use prometheus::Registry;
+    use rand::Rng;
+
+    use super::*;
+    use crate::{
+        effect::{announcements::ControlAnnouncement, Effects},
+        reactor::{ReactorExit, Scheduler},
+        utils,
+    };
+
+    #[derive(Debug, From, Serialize, Deserialize)]
+    enum Event {
+        Add(u64),
+        #[from]
+        #[serde(skip)]
+        Control(ControlAnnouncement),
+    }
+
+    impl Display for Event {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            match self {
+                Event::Add(value) => write!(f, "add {}", value),
+                Event::Control(ctrl_ann) => write!(f, "control: {}", ctrl_ann),
+            }
+        }
+    }
+
+    impl ReactorEvent for Event {
+        fn as_control(&self) -> Option<&ControlAnnouncement> {
+            if let Self::Control(ref ctrl_ann) = self {
+                Some(ctrl_ann)
+            } else {
+                None
+            }
+        }
+
+        fn try_into_control(self) -> Option<ControlAnnouncement> {
+            if let Self::Control(ctrl_ann) = self {
+                Some(ctrl_ann)
+            } else {
+                None
+            }
+        }
+    }
+
+    /// A reactor whose state depends on both the events and the RNG.
+    #[derive(Debug)]
+    struct TestReactor {
+        sums: Vec<u64>,
+    }
+
+    impl Reactor for TestReactor {
+        type Event = Event;
+        type Config = ();
+        type Error = prometheus::Error;
+
+        fn dispatch_event(
+            &mut self,
+            _effect_builder: EffectBuilder<Event>,
+            rng: &mut NodeRng,
+            event: Event,
+        ) -> Effects<Event> {
+            if let Event::Add(value) = event {
+                self.sums.push(value + rng.gen::<u8>() as u64);
+            }
+            Effects::new()
+        }
+
+        fn new(
+            _cfg: (),
+            _registry: &Registry,
+            _event_queue: EventQueueHandle<Event>,
+            rng: &mut NodeRng,
+        ) -> Result<(Self, Effects<Event>), prometheus::Error> {
+            let sums = vec![rng.gen()];
+            Ok((TestReactor { sums }, Effects::new()))
+        }
+
+        fn maybe_exit(&self) -> Option<ReactorExit> {
+            None
+        }
+    }
+
+    /// Creates a test reactor with an RNG seeded with `seed` and dispatches `values` to it,
+    /// recording them to `path` if given.
+    fn run(seed: Seed, values: Vec<u64>, path: Option<PathBuf>) -> Vec<u64> {
+        // Each `TestRng` needs its own thread.
+        thread::spawn(move || {
+            let mut rng = NodeRng::from_seed(seed);
+            let scheduler = utils::leak(Scheduler::new(QueueKind::weights()));
+            let event_queue = EventQueueHandle::without_shutdown(scheduler);
+            let (mut reactor, _) =
+                TestReactor::new((), &Registry::new(), event_queue, &mut rng).unwrap();
+            let mut recorder = path.map(|path| {
+                EventRecorder::create(path, any::type_name::<TestReactor>(), &seed).unwrap()
+            });
+            for (id, value) in values.into_iter().enumerate() {
+                let event = Event::Add(value);
+                if let Some(recorder) = recorder.as_mut() {
+                    recorder
+                        .record(id as u64 + 1, QueueKind::Regular, &event)
+                        .unwrap();
+                }
+                drop(reactor.dispatch_event(EffectBuilder::new(event_queue), &mut rng, event));
+            }
+            reactor.sums
+        })
+        .join()
+        .unwrap()
+    }
+
+    #[test]
+    fn should_replay_recorded_events_deterministically() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("test-0.trace");
+        let seed = Seed::default();
+        let recorded_sums = run(seed, vec![1, 2, 3], Some(path.clone()));
+
+        let replayed_sums = thread::spawn(move || {
+            let trace = EventTrace::<Event>::read(&path).unwrap();
+            assert_eq!(trace.reactor, any::type_name::<TestReactor>());
+            assert_eq!(trace.seed, seed);
+            let ids: Vec<u64> = trace.events.iter().map(|event| event.id).collect();
+            assert_eq!(ids, vec![1, 2, 3]);
+
+            let mut rng = NodeRng::from_seed(trace.seed);
+            let scheduler = utils::leak(Scheduler::new(QueueKind::weights()));
+            let event_queue = EventQueueHandle::without_shutdown(scheduler);
+            let (mut reactor, _) =
+                TestReactor::new((), &Registry::new(), event_queue, &mut rng).unwrap();
+            assert_eq!(trace.replay(&mut reactor, event_queue, &mut rng), 3);
+            reactor.sums
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(recorded_sums.len(), 4);
+        assert_eq!(replayed_sums, recorded_sums);
+    }
+
+    #[test]
+    fn should_reject_empty_trace() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("empty.trace");
+        File::create(&path).unwrap();
+        assert!(matches!(
+            EventTrace::<Event>::read(&path),
+            Err(Error::MissingHeader)
+        ));
+    }
+}
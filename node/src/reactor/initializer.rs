@@ -15,7 +15,7 @@ use casper_execution_engine::core::engine_state;
 use crate::{
     components::{
         chainspec_loader::{self, ChainspecLoader},
-        contract_runtime::{self, ContractRuntime},
+        contract_runtime::{self, CommitRules, ContractRuntime, Governance},
         small_network::{SmallNetworkIdentity, SmallNetworkIdentityError},
         storage::{self, Storage},
         Component,
@@ -159,6 +159,10 @@ pub(crate) enum Error {
     #[error("chainspec error: {0}")]
     Chainspec(#[from] chainspec::Error),
 
+    /// Error loading the chainspec in force before the most recent upgrade.
+    #[error(transparent)]
+    PreviousChainspec(#[from] chainspec_loader::PreviousChainspecError),
+
     /// `Storage` component error.
     #[error("storage error: {0}")]
     Storage(#[from] storage::FatalStorageError),
@@ -203,6 +207,14 @@ impl Reactor {
             &chainspec_loader.chainspec().network_config.name,
         )?;
 
+        let previous_engine_config = chainspec_loader.previous_chainspec()?.map(|chainspec| {
+            let max_query_depth = config.value().contract_runtime.max_query_depth();
+            (
+                chainspec.protocol_version(),
+                chainspec.engine_config(max_query_depth),
+                CommitRules::new(&chainspec.commit_rules_config),
+            )
+        });
         let contract_runtime = ContractRuntime::new(
             chainspec_loader.chainspec().protocol_config.version,
             storage.root_path(),
@@ -227,6 +239,15 @@ impl Reactor {
                 .core_config
                 .vesting_schedule_period
                 .millis(),
+            chainspec_loader
+                .chainspec()
+                .core_config
+                .allow_separate_reward_purses,
+            chainspec_loader.chainspec().core_config.refund_handling,
+            chainspec_loader.chainspec().core_config.fee_handling,
+            previous_engine_config,
+            CommitRules::new(&chainspec_loader.chainspec().commit_rules_config),
+            Governance::new(chainspec_loader.chainspec()),
             registry,
         )?;
 
@@ -274,7 +295,7 @@ impl reactor::Reactor for Reactor {
 
         // Construct the `ChainspecLoader` first so we fail fast if the chainspec is invalid.
         let (chainspec_loader, chainspec_effects) =
-            ChainspecLoader::new(config.dir(), effect_builder)?;
+            ChainspecLoader::new(config.dir(), &config.value().node, effect_builder)?;
         Self::new_with_chainspec_loader(config, registry, chainspec_loader, chainspec_effects)
     }
 
@@ -21,6 +21,8 @@ use tracing::{debug, error, warn};
 
 use casper_execution_engine::storage::trie::TrieOrChunk;
 
+#[cfg(test)]
+use crate::testing::network::NetworkedReactor;
 use crate::{
     components::{
         chain_synchronizer::{self, ChainSynchronizer, JoiningOutcome},
@@ -34,7 +36,8 @@ use crate::{
         gossiper::{self, Gossiper},
         metrics::Metrics,
         rest_server::{self, RestServer},
-        small_network::{self, GossipedAddress, SmallNetwork, SmallNetworkIdentity},
+        rpc_server::{self, RpcServer},
+        small_network::{self, GossipedAddress, SmallNetwork},
         storage::{self, Storage},
         Component,
     },
@@ -43,18 +46,21 @@ use crate::{
         announcements::{
             BlocklistAnnouncement, ChainSynchronizerAnnouncement, ChainspecLoaderAnnouncement,
             ContractRuntimeAnnouncement, ControlAnnouncement, DeployAcceptorAnnouncement,
-            GossiperAnnouncement, LinearChainAnnouncement,
+            GossiperAnnouncement, LinearChainAnnouncement, RpcServerAnnouncement,
+        },
+        diagnostics_port::{
+            ComponentMemoryUsage, DumpConsensusStateRequest, InFlightFetches, MemoryUsage,
         },
-        diagnostics_port::DumpConsensusStateRequest,
         incoming::{
-            ConsensusMessageIncoming, FinalitySignatureIncoming, GossiperIncoming,
-            NetRequestIncoming, NetResponseIncoming, TrieDemand, TrieRequestIncoming,
-            TrieResponseIncoming,
+            ConsensusMessageIncoming, DeployApprovalsIncoming, FinalitySignatureIncoming,
+            GossiperIncoming, NetRequestIncoming, NetResponseIncoming, TrieDemand,
+            TrieRequestIncoming, TrieResponseIncoming,
         },
         requests::{
             BeginGossipRequest, ChainspecLoaderRequest, ConsensusRequest, ContractRuntimeRequest,
             FetcherRequest, MarkBlockCompletedRequest, MetricsRequest, NetworkInfoRequest,
-            NetworkRequest, NodeStateRequest, RestRequest, StorageRequest,
+            NetworkRequest, NodeStateRequest, RestRequest, RpcRequest, StateStoreRequest,
+            StorageRequest, SyncProgressRequest,
         },
         EffectBuilder, EffectExt, Effects,
     },
@@ -67,14 +73,13 @@ use crate::{
         EventQueueHandle, Finalize, ReactorExit,
     },
     types::{
-        Block, BlockAndDeploys, BlockHeader, BlockHeaderWithMetadata, BlockHeadersBatch,
-        BlockSignatures, BlockWithMetadata, Deploy, ExitCode, FinalizedApprovalsWithId,
+        BackfillBlockSignatures, Block, BlockAndDeploys, BlockHeader, BlockHeaderWithMetadata,
+        BlockHeadersBatch, BlockSignatures, BlockWithMetadata, Deploy, FinalizedApprovalsWithId,
+        NodeId, NodeMode, NodeState,
     },
     utils::WithDir,
     NodeRng,
 };
-#[cfg(test)]
-use crate::{testing::network::NetworkedReactor, types::NodeId};
 
 /// Top-level event for the reactor.
 #[allow(clippy::large_enum_variant)]
@@ -88,6 +93,8 @@ pub(crate) enum JoinerEvent {
     #[from]
     Storage(storage::Event),
     #[from]
+    RpcServer(#[serde(skip_serializing)] rpc_server::Event),
+    #[from]
     RestServer(#[serde(skip_serializing)] rest_server::Event),
     #[from]
     EventStreamServer(#[serde(skip_serializing)] event_stream_server::Event),
@@ -100,6 +107,8 @@ pub(crate) enum JoinerEvent {
     #[from]
     ChainSynchronizerRequest(#[serde(skip_serializing)] NodeStateRequest),
     #[from]
+    SyncProgressRequest(#[serde(skip_serializing)] SyncProgressRequest),
+    #[from]
     NetworkInfoRequest(#[serde(skip_serializing)] NetworkInfoRequest),
     #[from]
     BlockFetcher(#[serde(skip_serializing)] fetcher::Event<Block>),
@@ -122,6 +131,10 @@ pub(crate) enum JoinerEvent {
     #[from]
     FinalitySignaturesFetcher(#[serde(skip_serializing)] fetcher::Event<BlockSignatures>),
     #[from]
+    BackfillFinalitySignaturesFetcher(
+        #[serde(skip_serializing)] fetcher::Event<BackfillBlockSignatures>,
+    ),
+    #[from]
     DeployAcceptor(#[serde(skip_serializing)] deploy_acceptor::Event),
     #[from]
     AddressGossiper(gossiper::Event<GossipedAddress>),
@@ -132,6 +145,8 @@ pub(crate) enum JoinerEvent {
     #[from]
     MarkBlockCompletedRequest(MarkBlockCompletedRequest),
     #[from]
+    StateStoreRequest(StateStoreRequest),
+    #[from]
     DiagnosticsPort(diagnostics_port::Event),
     #[from]
     ContractRuntime(contract_runtime::Event),
@@ -172,6 +187,8 @@ pub(crate) enum JoinerEvent {
     #[from]
     AddressGossiperAnnouncement(#[serde(skip_serializing)] GossiperAnnouncement<GossipedAddress>),
     #[from]
+    RpcServerAnnouncement(#[serde(skip_serializing)] RpcServerAnnouncement),
+    #[from]
     DeployAcceptorAnnouncement(#[serde(skip_serializing)] DeployAcceptorAnnouncement),
     #[from]
     DeployGossiperAnnouncement(#[serde(skip_serializing)] GossiperAnnouncement<Deploy>),
@@ -202,6 +219,8 @@ pub(crate) enum JoinerEvent {
     #[from]
     FinalitySignatureIncoming(FinalitySignatureIncoming),
     #[from]
+    DeployApprovalsIncoming(DeployApprovalsIncoming),
+    #[from]
     DumpConsensusStateRequest(DumpConsensusStateRequest),
 }
 
@@ -227,12 +246,14 @@ impl ReactorEvent for JoinerEvent {
             JoinerEvent::ChainSynchronizer(_) => "ChainSynchronizer",
             JoinerEvent::SmallNetwork(_) => "SmallNetwork",
             JoinerEvent::Storage(_) => "Storage",
+            JoinerEvent::RpcServer(_) => "RpcServer",
             JoinerEvent::RestServer(_) => "RestServer",
             JoinerEvent::EventStreamServer(_) => "EventStreamServer",
             JoinerEvent::MetricsRequest(_) => "MetricsRequest",
             JoinerEvent::ChainspecLoader(_) => "ChainspecLoader",
             JoinerEvent::ChainspecLoaderRequest(_) => "ChainspecLoaderRequest",
             JoinerEvent::ChainSynchronizerRequest(_) => "ChainSynchronizerRequest",
+            JoinerEvent::SyncProgressRequest(_) => "SyncProgressRequest",
             JoinerEvent::NetworkInfoRequest(_) => "NetworkInfoRequest",
             JoinerEvent::BlockFetcher(_) => "BlockFetcher",
             JoinerEvent::BlockByHeightFetcher(_) => "BlockByHeightFetcher",
@@ -241,6 +262,9 @@ impl ReactorEvent for JoinerEvent {
             JoinerEvent::TrieOrChunkFetcher(_) => "TrieOrChunkFetcher",
             JoinerEvent::BlockHeadersBatchFetcher(_) => "BlockHeadersBatchFetcher",
             JoinerEvent::FinalitySignaturesFetcher(_) => "FinalitySignaturesFetcher",
+            JoinerEvent::BackfillFinalitySignaturesFetcher(_) => {
+                "BackfillFinalitySignaturesFetcher"
+            }
             JoinerEvent::DeployAcceptor(_) => "DeployAcceptor",
             JoinerEvent::ContractRuntime(_) => "ContractRuntime",
             JoinerEvent::AddressGossiper(_) => "AddressGossiper",
@@ -254,6 +278,7 @@ impl ReactorEvent for JoinerEvent {
             JoinerEvent::ControlAnnouncement(_) => "ControlAnnouncement",
             JoinerEvent::ContractRuntimeAnnouncement(_) => "ContractRuntimeAnnouncement",
             JoinerEvent::AddressGossiperAnnouncement(_) => "AddressGossiperAnnouncement",
+            JoinerEvent::RpcServerAnnouncement(_) => "RpcServerAnnouncement",
             JoinerEvent::DeployAcceptorAnnouncement(_) => "DeployAcceptorAnnouncement",
             JoinerEvent::LinearChainAnnouncement(_) => "LinearChainAnnouncement",
             JoinerEvent::ChainspecLoaderAnnouncement(_) => "ChainspecLoaderAnnouncement",
@@ -269,6 +294,7 @@ impl ReactorEvent for JoinerEvent {
             JoinerEvent::BlocklistAnnouncement(_) => "BlocklistAnnouncement",
             JoinerEvent::StorageRequest(_) => "StorageRequest",
             JoinerEvent::MarkBlockCompletedRequest(_) => "MarkBlockCompletedRequest",
+            JoinerEvent::StateStoreRequest(_) => "StateStoreRequest",
             JoinerEvent::BeginAddressGossipRequest(_) => "BeginAddressGossipRequest",
             JoinerEvent::ConsensusMessageIncoming(_) => "ConsensusMessageIncoming",
             JoinerEvent::DeployGossiperIncoming(_) => "DeployGossiperIncoming",
@@ -279,6 +305,7 @@ impl ReactorEvent for JoinerEvent {
             JoinerEvent::TrieDemand(_) => "TrieDemand",
             JoinerEvent::TrieResponseIncoming(_) => "TrieResponseIncoming",
             JoinerEvent::FinalitySignatureIncoming(_) => "FinalitySignatureIncoming",
+            JoinerEvent::DeployApprovalsIncoming(_) => "DeployApprovalsIncoming",
             JoinerEvent::ContractRuntimeRequest(_) => "ContractRuntimeRequest",
             JoinerEvent::DeployGossiper(_) => "DeployGossiper",
             JoinerEvent::DeployGossiperAnnouncement(_) => "DeployGossiperAnnouncement",
@@ -311,6 +338,12 @@ impl From<NetworkRequest<gossiper::Message<Deploy>>> for JoinerEvent {
     }
 }
 
+impl From<RpcRequest> for JoinerEvent {
+    fn from(request: RpcRequest) -> Self {
+        JoinerEvent::RpcServer(rpc_server::Event::RpcRequest(request))
+    }
+}
+
 impl From<RestRequest> for JoinerEvent {
     fn from(request: RestRequest) -> Self {
         JoinerEvent::RestServer(rest_server::Event::RestRequest(request))
@@ -328,6 +361,7 @@ impl Display for JoinerEvent {
                 write!(f, "blocklist announcement: {}", event)
             }
             JoinerEvent::Storage(request) => write!(f, "storage: {}", request),
+            JoinerEvent::RpcServer(event) => write!(f, "rpc server: {}", event),
             JoinerEvent::RestServer(event) => write!(f, "rest server: {}", event),
             JoinerEvent::EventStreamServer(event) => write!(f, "event stream server: {}", event),
             JoinerEvent::MetricsRequest(req) => write!(f, "metrics request: {}", req),
@@ -338,10 +372,12 @@ impl Display for JoinerEvent {
             JoinerEvent::ChainSynchronizerRequest(req) => {
                 write!(f, "chain synchronizer request: {}", req)
             }
+            JoinerEvent::SyncProgressRequest(req) => write!(f, "sync progress request: {}", req),
             JoinerEvent::StorageRequest(req) => write!(f, "storage request: {}", req),
             JoinerEvent::MarkBlockCompletedRequest(req) => {
                 write!(f, "mark block as completed request: {}", req)
             }
+            JoinerEvent::StateStoreRequest(req) => write!(f, "state store request: {}", req),
             JoinerEvent::NetworkInfoRequest(req) => write!(f, "network info request: {}", req),
             JoinerEvent::BlockFetcherRequest(request) => {
                 write!(f, "block fetcher request: {}", request)
@@ -381,6 +417,9 @@ impl Display for JoinerEvent {
             JoinerEvent::BlockByHeightFetcher(event) => {
                 write!(f, "block by height fetcher event: {}", event)
             }
+            JoinerEvent::RpcServerAnnouncement(ann) => {
+                write!(f, "api server announcement: {}", ann)
+            }
             JoinerEvent::DeployAcceptorAnnouncement(ann) => {
                 write!(f, "deploy acceptor announcement: {}", ann)
             }
@@ -418,6 +457,7 @@ impl Display for JoinerEvent {
             JoinerEvent::TrieDemand(inner) => write!(f, "demand: {}", inner),
             JoinerEvent::TrieResponseIncoming(inner) => write!(f, "incoming: {}", inner),
             JoinerEvent::FinalitySignatureIncoming(inner) => write!(f, "incoming: {}", inner),
+            JoinerEvent::DeployApprovalsIncoming(inner) => write!(f, "incoming: {}", inner),
             JoinerEvent::ContractRuntimeRequest(req) => {
                 write!(f, "contract runtime request: {}", req)
             }
@@ -443,6 +483,9 @@ impl Display for JoinerEvent {
             JoinerEvent::FinalitySignaturesFetcher(inner) => {
                 write!(f, "finality signatures fetcher event: {}", inner)
             }
+            JoinerEvent::BackfillFinalitySignaturesFetcher(inner) => {
+                write!(f, "backfill finality signatures fetcher event: {}", inner)
+            }
             JoinerEvent::FinalitySignaturesFetcherRequest(inner) => {
                 write!(f, "finality signatures fetch request: {}", inner)
             }
@@ -476,10 +519,14 @@ pub(crate) struct Reactor {
     block_header_by_hash_fetcher: Fetcher<BlockHeader>,
     block_headers_batch_fetcher: Fetcher<BlockHeadersBatch>,
     finality_signatures_fetcher: Fetcher<BlockSignatures>,
+    backfill_finality_signatures_fetcher: Fetcher<BackfillBlockSignatures>,
     #[data_size(skip)]
     deploy_acceptor: DeployAcceptor,
     #[data_size(skip)]
     event_queue_metrics: EventQueueMetrics,
+    /// The JSON-RPC server, only run if the node is configured to run as a light node, which never
+    /// leaves the joiner reactor.
+    rpc_server: Option<RpcServer>,
     #[data_size(skip)]
     rest_server: RestServer,
     #[data_size(skip)]
@@ -536,7 +583,8 @@ impl reactor::Reactor for Reactor {
         let (small_network, small_network_effects) = SmallNetwork::new(
             event_queue,
             config.network.clone(),
-            Some(WithDir::new(&root, &config.consensus)),
+            (config.node.node_mode == NodeMode::Validator)
+                .then(|| WithDir::new(&root, &config.consensus)),
             registry,
             small_network_identity,
             chainspec,
@@ -566,17 +614,42 @@ impl reactor::Reactor for Reactor {
         ));
 
         let protocol_version = &chainspec_loader.chainspec().protocol_config.version;
+        // The validator key is only needed this early if status responses are to be signed.
+        let status_signing_key = if config.rest_server.sign_status_responses
+            || config.rpc_server.sign_status_responses
+        {
+            config
+                .load_validator_keys(&root)?
+                .map(|(secret_key, _)| secret_key)
+        } else {
+            None
+        };
+        let rpc_server = if config.node.light_sync {
+            Some(RpcServer::new(
+                WithDir::new(&root, config.rpc_server.clone()),
+                config.speculative_exec_server.clone(),
+                effect_builder,
+                *protocol_version,
+                node_startup_instant,
+                status_signing_key.clone(),
+                registry,
+            )?)
+        } else {
+            None
+        };
         let rest_server = RestServer::new(
-            config.rest_server.clone(),
+            WithDir::new(&root, config.rest_server.clone()),
             effect_builder,
             *protocol_version,
             node_startup_instant,
+            status_signing_key,
         )?;
 
         let event_stream_server = EventStreamServer::new(
             config.event_stream_server.clone(),
             storage.root_path().to_path_buf(),
             *protocol_version,
+            registry,
         )?;
 
         let fetcher_builder = FetcherBuilder::new(
@@ -595,6 +668,8 @@ impl reactor::Reactor for Reactor {
         let block_and_deploys_fetcher = fetcher_builder.build("block_and_deploys")?;
         let block_headers_batch_fetcher = fetcher_builder.build("block_headers_batch")?;
         let finality_signatures_fetcher = fetcher_builder.build("finality_signatures")?;
+        let backfill_finality_signatures_fetcher =
+            fetcher_builder.build("backfill_finality_signatures")?;
 
         let trie_or_chunk_fetcher = fetcher_builder.build("trie_or_chunk")?;
 
@@ -630,11 +705,13 @@ impl reactor::Reactor for Reactor {
                 block_header_by_hash_fetcher,
                 block_headers_batch_fetcher,
                 finality_signatures_fetcher,
+                backfill_finality_signatures_fetcher,
                 block_header_and_finality_signatures_by_height_fetcher,
                 block_and_deploys_fetcher,
                 trie_or_chunk_fetcher,
                 deploy_acceptor,
                 event_queue_metrics,
+                rpc_server,
                 rest_server,
                 event_stream_server,
                 memory_metrics,
@@ -711,6 +788,12 @@ impl reactor::Reactor for Reactor {
                 warn!(?deploy_hash, ?peer, "Invalid deploy received from a peer.");
                 Effects::new()
             }
+            JoinerEvent::DeployAcceptorAnnouncement(
+                DeployAcceptorAnnouncement::NewDeployApprovals { .. },
+            ) => {
+                // Approvals are only passed on to peers once the node is participating.
+                Effects::new()
+            }
             JoinerEvent::Storage(event) => reactor::wrap_effects(
                 JoinerEvent::Storage,
                 self.storage.handle_event(effect_builder, rng, event),
@@ -759,6 +842,11 @@ impl reactor::Reactor for Reactor {
                 self.finality_signatures_fetcher
                     .handle_event(effect_builder, rng, event),
             ),
+            JoinerEvent::BackfillFinalitySignaturesFetcher(event) => reactor::wrap_effects(
+                JoinerEvent::BackfillFinalitySignaturesFetcher,
+                self.backfill_finality_signatures_fetcher
+                    .handle_event(effect_builder, rng, event),
+            ),
             JoinerEvent::DeployFetcherRequest(request) => self.dispatch_event(
                 effect_builder,
                 rng,
@@ -777,6 +865,10 @@ impl reactor::Reactor for Reactor {
                 JoinerEvent::Storage,
                 self.storage.handle_event(effect_builder, rng, req.into()),
             ),
+            JoinerEvent::StateStoreRequest(req) => reactor::wrap_effects(
+                JoinerEvent::Storage,
+                self.storage.handle_event(effect_builder, rng, req.into()),
+            ),
             JoinerEvent::BeginAddressGossipRequest(req) => reactor::wrap_effects(
                 JoinerEvent::AddressGossiper,
                 self.address_gossiper
@@ -853,10 +945,10 @@ impl reactor::Reactor for Reactor {
                 )
             }
             JoinerEvent::LinearChainAnnouncement(
-                LinearChainAnnouncement::NewFinalitySignature(fs),
+                LinearChainAnnouncement::NewFinalitySignature(fs, signed_weight),
             ) => {
                 let reactor_event = JoinerEvent::EventStreamServer(
-                    event_stream_server::Event::FinalitySignature(fs),
+                    event_stream_server::Event::FinalitySignature(fs, signed_weight),
                 );
                 self.dispatch_event(effect_builder, rng, reactor_event)
             }
@@ -866,6 +958,20 @@ impl reactor::Reactor for Reactor {
                 warn!("unexpected sync finished announcement in the joiner");
                 Effects::new()
             }
+            JoinerEvent::RpcServer(event) => match self.rpc_server.as_mut() {
+                Some(rpc_server) => reactor::wrap_effects(
+                    JoinerEvent::RpcServer,
+                    rpc_server.handle_event(effect_builder, rng, event),
+                ),
+                None => {
+                    warn!(%event, "no rpc server running in the joiner");
+                    Effects::new()
+                }
+            },
+            JoinerEvent::RpcServerAnnouncement(ann) => {
+                let event = deploy_acceptor::Event::from(ann);
+                self.dispatch_event(effect_builder, rng, JoinerEvent::DeployAcceptor(event))
+            }
             JoinerEvent::RestServer(event) => reactor::wrap_effects(
                 JoinerEvent::RestServer,
                 self.rest_server.handle_event(effect_builder, rng, event),
@@ -894,6 +1000,11 @@ impl reactor::Reactor for Reactor {
                 rng,
                 JoinerEvent::ChainSynchronizer(req.into()),
             ),
+            JoinerEvent::SyncProgressRequest(req) => self.dispatch_event(
+                effect_builder,
+                rng,
+                JoinerEvent::ChainSynchronizer(req.into()),
+            ),
             JoinerEvent::NetworkInfoRequest(req) => {
                 let event = JoinerEvent::SmallNetwork(small_network::Event::from(req));
                 self.dispatch_event(effect_builder, rng, event)
@@ -980,6 +1091,10 @@ impl reactor::Reactor for Reactor {
                 debug!(%sender, "finality signatures not handled in joiner reactor");
                 Effects::new()
             }
+            JoinerEvent::DeployApprovalsIncoming(DeployApprovalsIncoming { sender, .. }) => {
+                debug!(%sender, "deploy approvals not handled in joiner reactor");
+                Effects::new()
+            }
             JoinerEvent::DumpConsensusStateRequest(req) => {
                 // We have no consensus running in the joiner, so we answer with `None`.
                 req.answer(Err(Cow::Borrowed("node is joining, no running consensus")))
@@ -1020,7 +1135,7 @@ impl reactor::Reactor for Reactor {
             .joining_outcome()
             .map(|outcome| match outcome {
                 JoiningOutcome::ShouldExitForUpgrade => {
-                    ReactorExit::ProcessShouldExit(ExitCode::Success)
+                    ReactorExit::ProcessShouldExit(self.chainspec_loader.exit_code_for_upgrade())
                 }
                 JoiningOutcome::Synced { .. } => ReactorExit::ProcessShouldContinue,
             })
@@ -1031,18 +1146,137 @@ impl reactor::Reactor for Reactor {
         self.event_queue_metrics
             .record_event_queue_counts(&event_queue_handle);
     }
+
+    fn in_flight_fetches(&self) -> InFlightFetches {
+        InFlightFetches(
+            self.deploy_fetcher
+                .in_flight()
+                .chain(self.finalized_approvals_fetcher.in_flight())
+                .chain(self.block_by_hash_fetcher.in_flight())
+                .chain(self.block_by_height_fetcher.in_flight())
+                .chain(
+                    self.block_header_and_finality_signatures_by_height_fetcher
+                        .in_flight(),
+                )
+                .chain(self.block_and_deploys_fetcher.in_flight())
+                .chain(self.trie_or_chunk_fetcher.in_flight())
+                .chain(self.block_header_by_hash_fetcher.in_flight())
+                .chain(self.block_headers_batch_fetcher.in_flight())
+                .chain(self.finality_signatures_fetcher.in_flight())
+                .chain(self.backfill_finality_signatures_fetcher.in_flight())
+                .collect(),
+        )
+    }
+
+    fn estimate_memory_usage(&self) -> MemoryUsage {
+        MemoryUsage(
+            vec![
+                ("metrics", ComponentMemoryUsage::of(&self.metrics)),
+                (
+                    "small_network",
+                    ComponentMemoryUsage::of(&self.small_network),
+                ),
+                (
+                    "address_gossiper",
+                    ComponentMemoryUsage::of(&self.address_gossiper),
+                ),
+                ("config", ComponentMemoryUsage::of(&self.config)),
+                (
+                    "chainspec_loader",
+                    ComponentMemoryUsage::of(&self.chainspec_loader),
+                ),
+                ("storage", ComponentMemoryUsage::of(&self.storage)),
+                (
+                    "contract_runtime",
+                    ComponentMemoryUsage::of(&self.contract_runtime),
+                ),
+                (
+                    "chain_synchronizer",
+                    ComponentMemoryUsage::of(&self.chain_synchronizer),
+                ),
+                (
+                    "diagnostics_port",
+                    ComponentMemoryUsage::of(&self.diagnostics_port),
+                ),
+                ("rpc_server", ComponentMemoryUsage::of(&self.rpc_server)),
+                (
+                    "deploy_gossiper",
+                    ComponentMemoryUsage::of(&self.deploy_gossiper),
+                ),
+                (
+                    "deploy_fetcher",
+                    ComponentMemoryUsage::of(&self.deploy_fetcher),
+                ),
+                (
+                    "finalized_approvals_fetcher",
+                    ComponentMemoryUsage::of(&self.finalized_approvals_fetcher),
+                ),
+                (
+                    "block_by_hash_fetcher",
+                    ComponentMemoryUsage::of(&self.block_by_hash_fetcher),
+                ),
+                (
+                    "block_by_height_fetcher",
+                    ComponentMemoryUsage::of(&self.block_by_height_fetcher),
+                ),
+                (
+                    "block_header_and_finality_signatures_by_height_fetcher",
+                    ComponentMemoryUsage::of(
+                        &self.block_header_and_finality_signatures_by_height_fetcher,
+                    ),
+                ),
+                (
+                    "block_and_deploys_fetcher",
+                    ComponentMemoryUsage::of(&self.block_and_deploys_fetcher),
+                ),
+                (
+                    "trie_or_chunk_fetcher",
+                    ComponentMemoryUsage::of(&self.trie_or_chunk_fetcher),
+                ),
+                (
+                    "block_header_by_hash_fetcher",
+                    ComponentMemoryUsage::of(&self.block_header_by_hash_fetcher),
+                ),
+                (
+                    "block_headers_batch_fetcher",
+                    ComponentMemoryUsage::of(&self.block_headers_batch_fetcher),
+                ),
+                (
+                    "finality_signatures_fetcher",
+                    ComponentMemoryUsage::of(&self.finality_signatures_fetcher),
+                ),
+                (
+                    "backfill_finality_signatures_fetcher",
+                    ComponentMemoryUsage::of(&self.backfill_finality_signatures_fetcher),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        )
+    }
+
+    fn peers(&self) -> BTreeMap<NodeId, String> {
+        self.small_network.peers()
+    }
+
+    fn node_state(&self) -> Option<NodeState> {
+        Some(self.chain_synchronizer.node_state())
+    }
 }
 
 impl Reactor {
-    /// Deconstructs the reactor into config useful for creating a Validator reactor. Shuts down
-    /// the network, closing all incoming and outgoing connections, and frees up the listening
-    /// socket.
+    /// Deconstructs the reactor into config useful for creating a Validator reactor. Hands over the
+    /// network, keeping its outgoing connections while closing the incoming ones and freeing up
+    /// the listening socket.
     pub(crate) async fn into_participating_config(self) -> Result<ParticipatingInitConfig, Error> {
         let chain_sync_metrics = self.chain_synchronizer.metrics();
         let joining_outcome = self
             .chain_synchronizer
             .into_joining_outcome()
             .ok_or(Error::InvalidJoiningOutcome)?;
+        self.rest_server.finalize().await;
+        // The network is handed over rather than finalized, to keep its outgoing connections.
+        let network_handover = self.small_network.into_handover().await;
         let config = ParticipatingInitConfig {
             root: self.root,
             chainspec_loader: self.chainspec_loader,
@@ -1052,11 +1286,9 @@ impl Reactor {
             joining_outcome,
             chain_sync_metrics,
             event_stream_server: self.event_stream_server,
-            small_network_identity: SmallNetworkIdentity::from(&self.small_network),
+            network_handover,
             node_startup_instant: self.node_startup_instant,
         };
-        self.small_network.finalize().await;
-        self.rest_server.finalize().await;
         Ok(config)
     }
 }
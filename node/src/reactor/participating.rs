@@ -3,12 +3,14 @@
 //! Participating nodes join the participating-only network upon startup.
 
 mod config;
+mod deploy_lifecycle_metrics;
 mod error;
 mod memory_metrics;
 #[cfg(test)]
 mod tests;
 
 use std::{
+    collections::BTreeMap,
     fmt::{self, Debug, Display, Formatter},
     path::PathBuf,
     sync::Arc,
@@ -23,11 +25,15 @@ use serde::Serialize;
 use tracing::{error, info};
 
 use casper_execution_engine::storage::trie::TrieOrChunk;
+use casper_types::EraId;
 
+#[cfg(test)]
+use crate::testing::network::NetworkedReactor;
 use crate::{
+    alerts::{self, Alert, HealthChecks},
     components::{
         block_proposer::{self, BlockProposer},
-        block_validator::{self, BlockValidator},
+        block_validator::{self, BlockValidator, ValidatingBlock},
         chain_synchronizer::{self, ChainSynchronizer, JoiningOutcome},
         chainspec_loader::{self, ChainspecLoader},
         consensus::{self, EraSupervisor, HighwayProtocol},
@@ -41,46 +47,55 @@ use crate::{
         metrics::Metrics,
         rest_server::{self, RestServer},
         rpc_server::{self, RpcServer},
-        small_network::{self, GossipedAddress, SmallNetwork, SmallNetworkIdentity},
+        small_network::{self, GossipedAddress, NetworkHandover, SmallNetwork},
         storage::{self, Storage},
         Component,
     },
+    config_reload::ReloadableConfig,
     contract_runtime,
     effect::{
         announcements::{
             BlockProposerAnnouncement, BlocklistAnnouncement, ChainSynchronizerAnnouncement,
             ChainspecLoaderAnnouncement, ConsensusAnnouncement, ContractRuntimeAnnouncement,
             ControlAnnouncement, DeployAcceptorAnnouncement, GossiperAnnouncement,
-            LinearChainAnnouncement, RpcServerAnnouncement,
+            LinearChainAnnouncement, RpcServerAnnouncement, SupervisorAnnouncement,
+        },
+        diagnostics_port::{
+            ComponentMemoryUsage, DumpConsensusStateRequest, InFlightFetches, MemoryUsage,
         },
-        diagnostics_port::DumpConsensusStateRequest,
         incoming::{
-            ConsensusMessageIncoming, FinalitySignatureIncoming, GossiperIncoming,
-            NetRequestIncoming, NetResponseIncoming, TrieDemand, TrieRequestIncoming,
-            TrieResponseIncoming,
+            ConsensusMessageIncoming, DeployApprovalsIncoming, FinalitySignatureIncoming,
+            GossiperIncoming, NetRequestIncoming, NetResponseIncoming, TrieDemand,
+            TrieRequestIncoming, TrieResponseIncoming,
         },
         requests::{
             BeginGossipRequest, BlockProposerRequest, BlockValidationRequest,
             ChainspecLoaderRequest, ConsensusRequest, ContractRuntimeRequest, FetcherRequest,
             MarkBlockCompletedRequest, MetricsRequest, NetworkInfoRequest, NetworkRequest,
             NodeStateRequest, RestRequest, RpcRequest, StateStoreRequest, StorageRequest,
+            SyncProgressRequest,
         },
         EffectBuilder, EffectExt, Effects,
     },
     fatal,
     protocol::Message,
-    reactor::{self, event_queue_metrics::EventQueueMetrics, EventQueueHandle, ReactorExit},
+    reactor::{
+        self,
+        event_queue_metrics::EventQueueMetrics,
+        supervision::{Supervised, SupervisedComponent},
+        EventQueueHandle, ReactorExit,
+    },
     types::{
-        Block, BlockAndDeploys, BlockHeader, BlockHeaderWithMetadata, BlockHeadersBatch,
-        BlockSignatures, BlockWithMetadata, Deploy, ExitCode, FinalitySignature,
-        FinalizedApprovalsWithId,
+        chainspec::DeployConfig, BackfillBlockSignatures, Block, BlockAndDeploys, BlockHeader,
+        BlockHeaderWithMetadata, BlockHeadersBatch, BlockSignatures, BlockWithMetadata, Deploy,
+        FinalitySignature, FinalizedApprovalsWithId, NodeFeatures, NodeFeaturesMetric, NodeId,
+        NodeState,
     },
-    utils::{Source, WithDir},
+    utils::WithDir,
     NodeRng,
 };
-#[cfg(test)]
-use crate::{testing::network::NetworkedReactor, types::NodeId};
 pub(crate) use config::Config;
+use deploy_lifecycle_metrics::DeployLifecycleMetrics;
 pub(crate) use error::Error;
 use memory_metrics::MemoryMetrics;
 
@@ -144,11 +159,17 @@ pub(crate) enum ParticipatingEvent {
     BlockHeadersBatchFetcher(#[serde(skip_serializing)] fetcher::Event<BlockHeadersBatch>),
     #[from]
     FinalitySignaturesFetcher(#[serde(skip_serializing)] fetcher::Event<BlockSignatures>),
+    #[from]
+    BackfillFinalitySignaturesFetcher(
+        #[serde(skip_serializing)] fetcher::Event<BackfillBlockSignatures>,
+    ),
 
     // Requests
     #[from]
     ChainSynchronizerRequest(#[serde(skip_serializing)] NodeStateRequest),
     #[from]
+    SyncProgressRequest(#[serde(skip_serializing)] SyncProgressRequest),
+    #[from]
     ContractRuntimeRequest(ContractRuntimeRequest),
     #[from]
     NetworkRequest(#[serde(skip_serializing)] NetworkRequest<Message>),
@@ -179,6 +200,10 @@ pub(crate) enum ParticipatingEvent {
     #[from]
     FinalitySignaturesFetcherRequest(#[serde(skip_serializing)] FetcherRequest<BlockSignatures>),
     #[from]
+    BackfillFinalitySignaturesFetcherRequest(
+        #[serde(skip_serializing)] FetcherRequest<BackfillBlockSignatures>,
+    ),
+    #[from]
     BlockProposerRequest(#[serde(skip_serializing)] BlockProposerRequest),
     #[from]
     BlockValidatorRequest(#[serde(skip_serializing)] BlockValidationRequest),
@@ -239,7 +264,14 @@ pub(crate) enum ParticipatingEvent {
     #[from]
     FinalitySignatureIncoming(FinalitySignatureIncoming),
     #[from]
+    DeployApprovalsIncoming(DeployApprovalsIncoming),
+    #[from]
     BlockProposerAnnouncement(#[serde(skip_serializing)] BlockProposerAnnouncement),
+    #[from]
+    SupervisorAnnouncement(SupervisorAnnouncement),
+
+    // Restarts of crashed supervised components
+    RestartComponent(SupervisedComponent),
 }
 
 impl ReactorEvent for ParticipatingEvent {
@@ -279,6 +311,7 @@ impl ReactorEvent for ParticipatingEvent {
             ParticipatingEvent::LinearChain(_) => "LinearChain",
             ParticipatingEvent::ContractRuntimeRequest(_) => "ContractRuntimeRequest",
             ParticipatingEvent::ChainSynchronizerRequest(_) => "ChainSynchronizerRequest",
+            ParticipatingEvent::SyncProgressRequest(_) => "SyncProgressRequest",
             ParticipatingEvent::BlockFetcher(_) => "BlockFetcher",
             ParticipatingEvent::BlockHeaderFetcher(_) => "BlockHeaderFetcher",
             ParticipatingEvent::TrieOrChunkFetcher(_) => "TrieOrChunkFetcher",
@@ -288,6 +321,9 @@ impl ReactorEvent for ParticipatingEvent {
             ParticipatingEvent::FinalizedApprovalsFetcher(_) => "FinalizedApprovalsFetcher",
             ParticipatingEvent::BlockHeadersBatchFetcher(_) => "BlockHeadersBatchFetcher",
             ParticipatingEvent::FinalitySignaturesFetcher(_) => "FinalitySignaturesFetcher",
+            ParticipatingEvent::BackfillFinalitySignaturesFetcher(_) => {
+                "BackfillFinalitySignaturesFetcher"
+            }
             ParticipatingEvent::DiagnosticsPort(_) => "DiagnosticsPort",
             ParticipatingEvent::NetworkRequest(_) => "NetworkRequest",
             ParticipatingEvent::NetworkInfoRequest(_) => "NetworkInfoRequest",
@@ -309,6 +345,9 @@ impl ReactorEvent for ParticipatingEvent {
             ParticipatingEvent::FinalitySignaturesFetcherRequest(_) => {
                 "FinalitySignaturesFetcherRequest"
             }
+            ParticipatingEvent::BackfillFinalitySignaturesFetcherRequest(_) => {
+                "BackfillFinalitySignaturesFetcherRequest"
+            }
             ParticipatingEvent::BlockProposerRequest(_) => "BlockProposerRequest",
             ParticipatingEvent::BlockValidatorRequest(_) => "BlockValidatorRequest",
             ParticipatingEvent::MetricsRequest(_) => "MetricsRequest",
@@ -338,8 +377,11 @@ impl ReactorEvent for ParticipatingEvent {
             ParticipatingEvent::TrieDemand(_) => "TrieDemand",
             ParticipatingEvent::TrieResponseIncoming(_) => "TrieResponseIncoming",
             ParticipatingEvent::FinalitySignatureIncoming(_) => "FinalitySignatureIncoming",
+            ParticipatingEvent::DeployApprovalsIncoming(_) => "DeployApprovalsIncoming",
             ParticipatingEvent::ContractRuntime(_) => "ContractRuntime",
             ParticipatingEvent::ChainSynchronizerAnnouncement(_) => "ChainSynchronizerAnnouncement",
+            ParticipatingEvent::SupervisorAnnouncement(_) => "SupervisorAnnouncement",
+            ParticipatingEvent::RestartComponent(_) => "RestartComponent",
         }
     }
 }
@@ -430,10 +472,16 @@ impl Display for ParticipatingEvent {
             ParticipatingEvent::FinalitySignaturesFetcher(event) => {
                 write!(f, "finality signatures fetcher: {}", event)
             }
+            ParticipatingEvent::BackfillFinalitySignaturesFetcher(event) => {
+                write!(f, "backfill finality signatures fetcher: {}", event)
+            }
             ParticipatingEvent::DiagnosticsPort(event) => write!(f, "diagnostics port: {}", event),
             ParticipatingEvent::ChainSynchronizerRequest(req) => {
                 write!(f, "chain synchronizer request: {}", req)
             }
+            ParticipatingEvent::SyncProgressRequest(req) => {
+                write!(f, "sync progress request: {}", req)
+            }
             ParticipatingEvent::NetworkRequest(req) => write!(f, "network request: {}", req),
             ParticipatingEvent::NetworkInfoRequest(req) => {
                 write!(f, "network info request: {}", req)
@@ -476,6 +524,13 @@ impl Display for ParticipatingEvent {
             ParticipatingEvent::FinalitySignaturesFetcherRequest(request) => {
                 write!(f, "finality signatures fetcher request: {}", request)
             }
+            ParticipatingEvent::BackfillFinalitySignaturesFetcherRequest(request) => {
+                write!(
+                    f,
+                    "backfill finality signatures fetcher request: {}",
+                    request
+                )
+            }
             ParticipatingEvent::BeginAddressGossipRequest(request) => {
                 write!(f, "begin address gossip request: {}", request)
             }
@@ -523,6 +578,12 @@ impl Display for ParticipatingEvent {
             ParticipatingEvent::ChainSynchronizerAnnouncement(ann) => {
                 write!(f, "chain synchronizer announcement: {}", ann)
             }
+            ParticipatingEvent::SupervisorAnnouncement(ann) => {
+                write!(f, "supervisor announcement: {}", ann)
+            }
+            ParticipatingEvent::RestartComponent(component) => {
+                write!(f, "restart component: {}", component)
+            }
             ParticipatingEvent::ConsensusMessageIncoming(inner) => Display::fmt(inner, f),
             ParticipatingEvent::DeployGossiperIncoming(inner) => Display::fmt(inner, f),
             ParticipatingEvent::AddressGossiperIncoming(inner) => Display::fmt(inner, f),
@@ -532,6 +593,7 @@ impl Display for ParticipatingEvent {
             ParticipatingEvent::TrieDemand(inner) => Display::fmt(inner, f),
             ParticipatingEvent::TrieResponseIncoming(inner) => Display::fmt(inner, f),
             ParticipatingEvent::FinalitySignatureIncoming(inner) => Display::fmt(inner, f),
+            ParticipatingEvent::DeployApprovalsIncoming(inner) => Display::fmt(inner, f),
             ParticipatingEvent::ContractRuntime(inner) => Display::fmt(inner, f),
         }
     }
@@ -547,7 +609,7 @@ pub(crate) struct ParticipatingInitConfig {
     pub(super) joining_outcome: JoiningOutcome,
     pub(super) chain_sync_metrics: chain_synchronizer::Metrics,
     pub(super) event_stream_server: EventStreamServer,
-    pub(super) small_network_identity: SmallNetworkIdentity,
+    pub(super) network_handover: NetworkHandover<Message>,
     pub(super) node_startup_instant: Instant,
 }
 
@@ -579,8 +641,8 @@ pub(crate) struct Reactor {
     storage: Storage,
     contract_runtime: ContractRuntime,
     rpc_server: RpcServer,
-    rest_server: RestServer,
-    event_stream_server: EventStreamServer,
+    rest_server: Supervised<RestServer>,
+    event_stream_server: Supervised<EventStreamServer>,
     chainspec_loader: ChainspecLoader,
     consensus: EraSupervisor,
     #[data_size(skip)]
@@ -600,12 +662,29 @@ pub(crate) struct Reactor {
     finalized_approvals_fetcher: Fetcher<FinalizedApprovalsWithId>,
     block_headers_batch_fetcher: Fetcher<BlockHeadersBatch>,
     finality_signatures_fetcher: Fetcher<BlockSignatures>,
+    backfill_finality_signatures_fetcher: Fetcher<BackfillBlockSignatures>,
     diagnostics_port: DiagnosticsPort,
     // Non-components.
     #[data_size(skip)] // Never allocates heap data.
     memory_metrics: MemoryMetrics,
     #[data_size(skip)]
     event_queue_metrics: EventQueueMetrics,
+    #[data_size(skip)]
+    deploy_lifecycle_metrics: DeployLifecycleMetrics,
+    #[data_size(skip)]
+    node_features_metric: NodeFeaturesMetric,
+    health_checks: HealthChecks,
+}
+
+impl Reactor {
+    /// Applies the given deploy config, in effect from the given era onwards, to the components
+    /// which accept, propose and validate deploys.
+    fn set_deploy_config(&mut self, era_id: EraId, deploy_config: DeployConfig) {
+        self.deploy_acceptor.set_deploy_config(deploy_config);
+        self.block_proposer.set_deploy_config(deploy_config);
+        self.block_validator
+            .set_deploy_config(era_id, deploy_config);
+    }
 }
 
 #[cfg(test)]
@@ -649,11 +728,12 @@ impl reactor::Reactor for Reactor {
             joining_outcome,
             chain_sync_metrics,
             event_stream_server,
-            small_network_identity,
+            network_handover,
             node_startup_instant,
         } = config;
 
-        let (our_secret_key, our_public_key) = config.consensus.load_keys(&root)?;
+        // In observer mode no validator key is loaded, so the node never signs anything.
+        let validator_keys = config.load_validator_keys(&root)?;
 
         let effect_builder = EffectBuilder::new(event_queue);
         let mut effects = Effects::new();
@@ -669,6 +749,7 @@ impl reactor::Reactor for Reactor {
                 if let Some(BlockAndExecutionEffects {
                     block,
                     execution_results,
+                    contract_events,
                     maybe_step_effect_and_upcoming_era_validators,
                 }) = chainspec_loader
                     .maybe_immediate_switch_block_data()
@@ -680,7 +761,11 @@ impl reactor::Reactor for Reactor {
                     // don't get dropped as the joining reactor shuts down.
                     effects.extend(
                         effect_builder
-                            .announce_new_linear_chain_block(block.clone(), execution_results)
+                            .announce_new_linear_chain_block(
+                                block.clone(),
+                                execution_results,
+                                contract_events,
+                            )
                             .ignore(),
                     );
 
@@ -706,11 +791,14 @@ impl reactor::Reactor for Reactor {
                         );
                     }
 
-                    let secret_key = our_secret_key.clone();
-                    let public_key = our_public_key.clone();
+                    let maybe_validator_keys = validator_keys.clone();
                     let block_hash = *block.hash();
                     effects.extend(
                         async move {
+                            let (secret_key, public_key) = match maybe_validator_keys {
+                                Some(keys) => keys,
+                                None => return,
+                            };
                             let validator_weights =
                                 match linear_chain::era_validator_weights_for_block(
                                     block.header(),
@@ -761,6 +849,10 @@ impl reactor::Reactor for Reactor {
 
         let memory_metrics = MemoryMetrics::new(registry.clone())?;
 
+        let deploy_lifecycle_metrics = DeployLifecycleMetrics::new(registry)?;
+
+        let node_features_metric = NodeFeaturesMetric::new(NodeFeatures::current(), registry)?;
+
         let event_queue_metrics = EventQueueMetrics::new(registry.clone(), event_queue)?;
 
         let metrics = Metrics::new(registry.clone());
@@ -779,18 +871,65 @@ impl reactor::Reactor for Reactor {
 
         let protocol_version = chainspec.protocol_config.version;
         let rpc_server = RpcServer::new(
-            config.rpc_server.clone(),
+            WithDir::new(&root, config.rpc_server.clone()),
             config.speculative_exec_server.clone(),
             effect_builder,
             protocol_version,
             node_startup_instant,
+            validator_keys
+                .as_ref()
+                .map(|(secret_key, _)| Arc::clone(secret_key)),
+            registry,
         )?;
-        let rest_server = RestServer::new(
-            config.rest_server.clone(),
-            effect_builder,
-            protocol_version,
-            node_startup_instant,
-        )?;
+        let mut rest_server = {
+            let rest_server_config = WithDir::new(&root, config.rest_server.clone());
+            let signing_key = validator_keys
+                .as_ref()
+                .map(|(secret_key, _)| Arc::clone(secret_key));
+            let create_rest_server = move || {
+                RestServer::new(
+                    rest_server_config.clone(),
+                    effect_builder,
+                    protocol_version,
+                    node_startup_instant,
+                    signing_key.clone(),
+                )
+            };
+            Supervised::new(
+                SupervisedComponent::RestServer,
+                create_rest_server()?,
+                move || create_rest_server().map_err(|error| error.to_string()),
+            )
+        };
+        let mut event_stream_server = {
+            let event_stream_server_config = config.event_stream_server.clone();
+            let storage_path = storage.root_path().to_path_buf();
+            let registry = registry.clone();
+            // The metrics of the crashed server may still be registered when restarting it, so
+            // they are reused rather than registered anew.
+            let metrics = event_stream_server.metrics();
+            Supervised::new(
+                SupervisedComponent::EventStreamServer,
+                event_stream_server,
+                move || {
+                    let event_stream_server = match &metrics {
+                        Some(metrics) => EventStreamServer::with_metrics(
+                            event_stream_server_config.clone(),
+                            storage_path.clone(),
+                            protocol_version,
+                            Arc::clone(metrics),
+                        ),
+                        None => EventStreamServer::new(
+                            event_stream_server_config.clone(),
+                            storage_path.clone(),
+                            protocol_version,
+                            &registry,
+                        ),
+                    };
+                    event_stream_server.map_err(|error| error.to_string())
+                },
+            )
+        };
 
         let fetcher_builder = FetcherBuilder::new(
             config.fetcher,
@@ -819,12 +958,14 @@ impl reactor::Reactor for Reactor {
             block_proposer_effects,
         ));
 
-        let (small_network, small_network_effects) = SmallNetwork::new(
+        // The network is taken over from the joiner, keeping its outgoing connections.
+        let (small_network, small_network_effects) = SmallNetwork::from_handover(
             event_queue,
             config.network.clone(),
-            Some(WithDir::new(&root, &config.consensus)),
-            registry,
-            small_network_identity,
+            validator_keys
+                .as_ref()
+                .map(|_| WithDir::new(&root, &config.consensus)),
+            network_handover,
             chainspec.as_ref(),
         )?;
 
@@ -837,8 +978,7 @@ impl reactor::Reactor for Reactor {
         let (consensus, init_consensus_effects) = EraSupervisor::new(
             highest_block_header.next_block_era_id(),
             storage.root_path(),
-            our_secret_key,
-            our_public_key,
+            validator_keys,
             config.consensus,
             effect_builder,
             chainspec.clone(),
@@ -890,6 +1030,8 @@ impl reactor::Reactor for Reactor {
         let finalized_approvals_fetcher = fetcher_builder.build("finalized_approvals")?;
         let block_headers_batch_fetcher = fetcher_builder.build("block_headers_batch")?;
         let finality_signatures_fetcher = fetcher_builder.build("finality_signatures")?;
+        let backfill_finality_signatures_fetcher =
+            fetcher_builder.build("backfill_finality_signatures")?;
 
         effects.extend(reactor::wrap_effects(
             ParticipatingEvent::SmallNetwork,
@@ -899,41 +1041,69 @@ impl reactor::Reactor for Reactor {
             ParticipatingEvent::ChainspecLoader,
             chainspec_loader.start_checking_for_upgrades(effect_builder),
         ));
+        effects.extend(rest_server.watch_tasks(effect_builder));
+        effects.extend(event_stream_server.watch_tasks(effect_builder));
 
-        Ok((
-            Reactor {
-                metrics,
-                small_network,
-                address_gossiper,
-                storage,
-                contract_runtime,
-                rpc_server,
-                rest_server,
-                event_stream_server,
-                chainspec_loader,
-                consensus,
-                deploy_acceptor,
-                deploy_fetcher,
-                deploy_gossiper,
-                block_proposer,
-                block_validator,
-                linear_chain,
-                chain_synchronizer,
-                block_by_hash_fetcher,
-                block_header_by_hash_fetcher,
-                trie_or_chunk_fetcher,
-                block_by_height_fetcher,
-                block_header_and_finality_signatures_by_height_fetcher,
-                block_and_deploys_fetcher,
-                finalized_approvals_fetcher,
-                block_headers_batch_fetcher,
-                finality_signatures_fetcher,
-                diagnostics_port,
-                memory_metrics,
-                event_queue_metrics,
-            },
-            effects,
-        ))
+        let mut reactor = Reactor {
+            metrics,
+            small_network,
+            address_gossiper,
+            storage,
+            contract_runtime,
+            rpc_server,
+            rest_server,
+            event_stream_server,
+            chainspec_loader,
+            consensus,
+            deploy_acceptor,
+            deploy_fetcher,
+            deploy_gossiper,
+            block_proposer,
+            block_validator,
+            linear_chain,
+            chain_synchronizer,
+            block_by_hash_fetcher,
+            block_header_by_hash_fetcher,
+            trie_or_chunk_fetcher,
+            block_by_height_fetcher,
+            block_header_and_finality_signatures_by_height_fetcher,
+            block_and_deploys_fetcher,
+            finalized_approvals_fetcher,
+            block_headers_batch_fetcher,
+            finality_signatures_fetcher,
+            backfill_finality_signatures_fetcher,
+            diagnostics_port,
+            memory_metrics,
+            event_queue_metrics,
+            deploy_lifecycle_metrics,
+            node_features_metric,
+            health_checks: HealthChecks::default(),
+        };
+
+        // If governance is enabled, apply the deploy limits read from the global state of the
+        // switch block preceding the current era.
+        if reactor
+            .chainspec_loader
+            .chainspec()
+            .governance_config
+            .enabled
+        {
+            let maybe_switch_block_header =
+                match highest_block_header.next_block_era_id().checked_sub(1) {
+                    Some(era_id) => reactor.storage.read_switch_block_header_by_era_id(era_id)?,
+                    None => None,
+                };
+            if let Some(header) = maybe_switch_block_header {
+                if let Some(deploy_config) = reactor
+                    .contract_runtime
+                    .governed_deploy_config(*header.state_root_hash())?
+                {
+                    reactor.set_deploy_config(header.next_block_era_id(), deploy_config);
+                }
+            }
+        }
+
+        Ok((reactor, effects))
     }
 
     fn dispatch_event(
@@ -973,10 +1143,19 @@ impl reactor::Reactor for Reactor {
                 self.chainspec_loader
                     .handle_event(effect_builder, rng, event),
             ),
-            ParticipatingEvent::Consensus(event) => reactor::wrap_effects(
-                ParticipatingEvent::Consensus,
-                self.consensus.handle_event(effect_builder, rng, event),
-            ),
+            ParticipatingEvent::Consensus(event) => {
+                if let consensus::Event::NewBlockPayload(new_block_payload) = &event {
+                    let block_payload = new_block_payload.block_payload();
+                    self.deploy_lifecycle_metrics
+                        .proposed(block_payload.deploy_hashes());
+                    self.deploy_lifecycle_metrics
+                        .proposed(block_payload.transfer_hashes());
+                }
+                reactor::wrap_effects(
+                    ParticipatingEvent::Consensus,
+                    self.consensus.handle_event(effect_builder, rng, event),
+                )
+            }
             ParticipatingEvent::DeployAcceptor(event) => reactor::wrap_effects(
                 ParticipatingEvent::DeployAcceptor,
                 self.deploy_acceptor
@@ -1060,6 +1239,11 @@ impl reactor::Reactor for Reactor {
                 self.finality_signatures_fetcher
                     .handle_event(effect_builder, rng, event),
             ),
+            ParticipatingEvent::BackfillFinalitySignaturesFetcher(event) => reactor::wrap_effects(
+                ParticipatingEvent::BackfillFinalitySignaturesFetcher,
+                self.backfill_finality_signatures_fetcher
+                    .handle_event(effect_builder, rng, event),
+            ),
             ParticipatingEvent::DiagnosticsPort(event) => reactor::wrap_effects(
                 ParticipatingEvent::DiagnosticsPort,
                 self.diagnostics_port
@@ -1072,6 +1256,11 @@ impl reactor::Reactor for Reactor {
                 self.chain_synchronizer
                     .handle_event(effect_builder, rng, request.into()),
             ),
+            ParticipatingEvent::SyncProgressRequest(request) => reactor::wrap_effects(
+                ParticipatingEvent::ChainSynchronizer,
+                self.chain_synchronizer
+                    .handle_event(effect_builder, rng, request.into()),
+            ),
             ParticipatingEvent::NetworkRequest(req) => {
                 let event = ParticipatingEvent::SmallNetwork(small_network::Event::from(req));
                 self.dispatch_event(effect_builder, rng, event)
@@ -1132,16 +1321,34 @@ impl reactor::Reactor for Reactor {
                 self.finality_signatures_fetcher
                     .handle_event(effect_builder, rng, request.into()),
             ),
+            ParticipatingEvent::BackfillFinalitySignaturesFetcherRequest(request) => {
+                reactor::wrap_effects(
+                    ParticipatingEvent::BackfillFinalitySignaturesFetcher,
+                    self.backfill_finality_signatures_fetcher.handle_event(
+                        effect_builder,
+                        rng,
+                        request.into(),
+                    ),
+                )
+            }
             ParticipatingEvent::BlockProposerRequest(req) => self.dispatch_event(
                 effect_builder,
                 rng,
                 ParticipatingEvent::BlockProposer(req.into()),
             ),
-            ParticipatingEvent::BlockValidatorRequest(req) => self.dispatch_event(
-                effect_builder,
-                rng,
-                ParticipatingEvent::BlockValidator(block_validator::Event::from(req)),
-            ),
+            ParticipatingEvent::BlockValidatorRequest(req) => {
+                if let ValidatingBlock::ProposedBlock(proposed_block) = &req.block {
+                    self.deploy_lifecycle_metrics
+                        .proposed(proposed_block.value().deploy_hashes());
+                    self.deploy_lifecycle_metrics
+                        .proposed(proposed_block.value().transfer_hashes());
+                }
+                self.dispatch_event(
+                    effect_builder,
+                    rng,
+                    ParticipatingEvent::BlockValidator(block_validator::Event::from(req)),
+                )
+            }
             ParticipatingEvent::MetricsRequest(req) => reactor::wrap_effects(
                 ParticipatingEvent::MetricsRequest,
                 self.metrics.handle_event(effect_builder, rng, req),
@@ -1177,15 +1384,8 @@ impl reactor::Reactor for Reactor {
             ParticipatingEvent::ControlAnnouncement(ctrl_ann) => {
                 unreachable!("unhandled control announcement: {}", ctrl_ann)
             }
-            ParticipatingEvent::RpcServerAnnouncement(RpcServerAnnouncement::DeployReceived {
-                deploy,
-                responder,
-            }) => {
-                let event = deploy_acceptor::Event::Accept {
-                    deploy,
-                    source: Source::Client,
-                    maybe_responder: responder,
-                };
+            ParticipatingEvent::RpcServerAnnouncement(ann) => {
+                let event = deploy_acceptor::Event::from(ann);
                 self.dispatch_event(
                     effect_builder,
                     rng,
@@ -1195,6 +1395,7 @@ impl reactor::Reactor for Reactor {
             ParticipatingEvent::DeployAcceptorAnnouncement(
                 DeployAcceptorAnnouncement::AcceptedNewDeploy { deploy, source },
             ) => {
+                self.deploy_lifecycle_metrics.accepted(*deploy.id());
                 let deploy_info = match deploy.deploy_info() {
                     Ok(deploy_info) => deploy_info,
                     Err(error) => {
@@ -1231,6 +1432,13 @@ impl reactor::Reactor for Reactor {
                     ParticipatingEvent::EventStreamServer(event),
                 ));
 
+                let event = rpc_server::Event::DeployAccepted(deploy.clone());
+                effects.extend(self.dispatch_event(
+                    effect_builder,
+                    rng,
+                    ParticipatingEvent::RpcServer(event),
+                ));
+
                 let event = fetcher::Event::GotRemotely {
                     item: deploy,
                     source,
@@ -1249,14 +1457,39 @@ impl reactor::Reactor for Reactor {
                     source: _,
                 },
             ) => Effects::new(),
+            ParticipatingEvent::DeployAcceptorAnnouncement(
+                DeployAcceptorAnnouncement::NewDeployApprovals {
+                    deploy_approvals,
+                    source,
+                },
+            ) => {
+                // Peers pass the approvals on in turn only if they learn any new ones from them, so
+                // the approvals known for the deploy converge across the network.
+                let exclude = source.node_id().into_iter().collect();
+                effect_builder
+                    .gossip_message(
+                        Message::DeployApprovals(deploy_approvals),
+                        self.deploy_gossiper.infection_target(),
+                        exclude,
+                    )
+                    .ignore()
+            }
             ParticipatingEvent::ConsensusAnnouncement(consensus_announcement) => {
                 match consensus_announcement {
                     ConsensusAnnouncement::Finalized(block) => {
+                        self.deploy_lifecycle_metrics
+                            .finalized(block.deploy_hashes().iter().chain(block.transfer_hashes()));
                         let reactor_event = ParticipatingEvent::BlockProposer(
                             block_proposer::Event::FinalizedBlock(block),
                         );
                         self.dispatch_event(effect_builder, rng, reactor_event)
                     }
+                    ConsensusAnnouncement::ProposedBlock(proposed_block) => {
+                        let reactor_event = ParticipatingEvent::BlockProposer(
+                            block_proposer::Event::ProposedBlock(proposed_block),
+                        );
+                        self.dispatch_event(effect_builder, rng, reactor_event)
+                    }
                     ConsensusAnnouncement::CreatedFinalitySignature(fs) => self.dispatch_event(
                         effect_builder,
                         rng,
@@ -1278,16 +1511,24 @@ impl reactor::Reactor for Reactor {
                         );
                         self.dispatch_event(effect_builder, rng, reactor_event)
                     }
+                    ann @ ConsensusAnnouncement::EraValidators { .. } => self.dispatch_event(
+                        effect_builder,
+                        rng,
+                        ParticipatingEvent::SmallNetwork(ann.into()),
+                    ),
                 }
             }
             ParticipatingEvent::ContractRuntimeAnnouncement(
                 ContractRuntimeAnnouncement::LinearChainBlock {
                     block,
                     execution_results,
+                    mut contract_events,
                 },
             ) => {
                 let mut effects = Effects::new();
                 let block_hash = *block.hash();
+                self.deploy_lifecycle_metrics
+                    .executed(execution_results.iter().map(|(hash, _, _)| hash));
 
                 // send to linear chain
                 let reactor_event =
@@ -1300,21 +1541,50 @@ impl reactor::Reactor for Reactor {
                     });
                 effects.extend(self.dispatch_event(effect_builder, rng, reactor_event));
 
-                // send to event stream
+                // store any events emitted by contracts
+                if !contract_events.is_empty() {
+                    effects.extend(
+                        effect_builder
+                            .put_contract_events_to_storage(contract_events.clone())
+                            .ignore(),
+                    );
+                }
+
+                // send to event stream and rpc server
                 for (deploy_hash, deploy_header, execution_result) in execution_results {
                     let reactor_event = ParticipatingEvent::EventStreamServer(
                         event_stream_server::Event::DeployProcessed {
                             deploy_hash,
-                            deploy_header: Box::new(deploy_header),
+                            deploy_header: Box::new(deploy_header.clone()),
                             block_hash,
-                            execution_result: Box::new(execution_result),
+                            execution_result: Box::new(execution_result.clone()),
+                            contract_events: contract_events
+                                .remove(&deploy_hash)
+                                .unwrap_or_default(),
                         },
                     );
                     effects.extend(self.dispatch_event(effect_builder, rng, reactor_event));
+
+                    let reactor_event =
+                        ParticipatingEvent::RpcServer(rpc_server::Event::DeployProcessed {
+                            deploy_hash,
+                            deploy_header: Box::new(deploy_header),
+                            block_hash,
+                            execution_result: Box::new(execution_result),
+                        });
+                    effects.extend(self.dispatch_event(effect_builder, rng, reactor_event));
                 }
 
                 effects
             }
+            ParticipatingEvent::ContractRuntimeAnnouncement(
+                ContractRuntimeAnnouncement::DeployExecutionProgress(progress),
+            ) => {
+                let reactor_event = ParticipatingEvent::EventStreamServer(
+                    event_stream_server::Event::DeployExecutionProgress(progress),
+                );
+                self.dispatch_event(effect_builder, rng, reactor_event)
+            }
             ParticipatingEvent::ContractRuntimeAnnouncement(
                 ContractRuntimeAnnouncement::CommitStepSuccess {
                     era_id,
@@ -1328,6 +1598,16 @@ impl reactor::Reactor for Reactor {
                     });
                 self.dispatch_event(effect_builder, rng, reactor_event)
             }
+            ParticipatingEvent::ContractRuntimeAnnouncement(
+                ContractRuntimeAnnouncement::DeployConfigUpdated {
+                    era_id,
+                    deploy_config,
+                },
+            ) => {
+                info!(%era_id, ?deploy_config, "applying deploy config read via governance");
+                self.set_deploy_config(era_id, deploy_config);
+                Effects::new()
+            }
             ParticipatingEvent::DeployGossiperAnnouncement(
                 GossiperAnnouncement::NewCompleteItem(gossiped_deploy_id),
             ) => {
@@ -1335,8 +1615,9 @@ impl reactor::Reactor for Reactor {
                 Effects::new()
             }
             ParticipatingEvent::DeployGossiperAnnouncement(
-                GossiperAnnouncement::FinishedGossiping(_gossiped_deploy_id),
+                GossiperAnnouncement::FinishedGossiping(gossiped_deploy_id),
             ) => {
+                self.deploy_lifecycle_metrics.gossiped(&gossiped_deploy_id);
                 // let reactor_event =
                 //     ParticipatingEvent::BlockProposer(block_proposer::Event::
                 // BufferDeploy(gossiped_deploy_id));
@@ -1365,10 +1646,13 @@ impl reactor::Reactor for Reactor {
                         header: Box::new(block.header().clone()),
                         header_hash: *block.hash(),
                     });
+                let reactor_event_rpc =
+                    ParticipatingEvent::RpcServer(rpc_server::Event::BlockAdded(block.clone()));
                 let reactor_event_es = ParticipatingEvent::EventStreamServer(
                     event_stream_server::Event::BlockAdded(block),
                 );
                 let mut effects = self.dispatch_event(effect_builder, rng, reactor_event_es);
+                effects.extend(self.dispatch_event(effect_builder, rng, reactor_event_rpc));
                 effects.extend(self.dispatch_event(effect_builder, rng, reactor_event_consensus));
 
                 effects
@@ -1376,18 +1660,24 @@ impl reactor::Reactor for Reactor {
             ParticipatingEvent::BlockProposerAnnouncement(
                 BlockProposerAnnouncement::DeploysExpired(hashes),
             ) => {
+                self.deploy_lifecycle_metrics.expired(&hashes);
                 let reactor_event = ParticipatingEvent::EventStreamServer(
                     event_stream_server::Event::DeploysExpired(hashes),
                 );
                 self.dispatch_event(effect_builder, rng, reactor_event)
             }
             ParticipatingEvent::LinearChainAnnouncement(
-                LinearChainAnnouncement::NewFinalitySignature(fs),
+                LinearChainAnnouncement::NewFinalitySignature(fs, signed_weight),
             ) => {
-                let reactor_event = ParticipatingEvent::EventStreamServer(
-                    event_stream_server::Event::FinalitySignature(fs),
+                let reactor_event_rpc = ParticipatingEvent::RpcServer(
+                    rpc_server::Event::FinalitySignature(fs.clone(), signed_weight.clone()),
                 );
-                self.dispatch_event(effect_builder, rng, reactor_event)
+                let reactor_event_es = ParticipatingEvent::EventStreamServer(
+                    event_stream_server::Event::FinalitySignature(fs, signed_weight),
+                );
+                let mut effects = self.dispatch_event(effect_builder, rng, reactor_event_es);
+                effects.extend(self.dispatch_event(effect_builder, rng, reactor_event_rpc));
+                effects
             }
             ParticipatingEvent::ChainSynchronizerAnnouncement(
                 ChainSynchronizerAnnouncement::SyncFinished,
@@ -1418,6 +1708,42 @@ impl reactor::Reactor for Reactor {
                 effects.extend(self.dispatch_event(effect_builder, rng, reactor_event));
                 effects
             }
+            ParticipatingEvent::SupervisorAnnouncement(
+                SupervisorAnnouncement::ComponentCrashed {
+                    component,
+                    message,
+                    restart_delay,
+                },
+            ) => {
+                let mut effects =
+                    alerts::send(Alert::ComponentCrashed { component, message }).ignore();
+                effects.extend(
+                    effect_builder
+                        .set_timeout(restart_delay)
+                        .event(move |_| ParticipatingEvent::RestartComponent(component)),
+                );
+                effects
+            }
+            ParticipatingEvent::SupervisorAnnouncement(SupervisorAnnouncement::TaskPanicked {
+                component,
+                generation,
+                message,
+            }) => match component {
+                SupervisedComponent::RestServer => {
+                    self.rest_server
+                        .task_panicked(effect_builder, generation, message)
+                }
+                SupervisedComponent::EventStreamServer => {
+                    self.event_stream_server
+                        .task_panicked(effect_builder, generation, message)
+                }
+            },
+            ParticipatingEvent::RestartComponent(component) => match component {
+                SupervisedComponent::RestServer => self.rest_server.restart(effect_builder),
+                SupervisedComponent::EventStreamServer => {
+                    self.event_stream_server.restart(effect_builder)
+                }
+            },
             ParticipatingEvent::BlocklistAnnouncement(ann) => self.dispatch_event(
                 effect_builder,
                 rng,
@@ -1470,6 +1796,11 @@ impl reactor::Reactor for Reactor {
                 self.linear_chain
                     .handle_event(effect_builder, rng, incoming.into()),
             ),
+            ParticipatingEvent::DeployApprovalsIncoming(incoming) => reactor::wrap_effects(
+                ParticipatingEvent::DeployAcceptor,
+                self.deploy_acceptor
+                    .handle_event(effect_builder, rng, incoming.into()),
+            ),
             ParticipatingEvent::ContractRuntimeAnnouncement(ann) => self.dispatch_event(
                 effect_builder,
                 rng,
@@ -1486,13 +1817,193 @@ impl reactor::Reactor for Reactor {
     fn update_metrics(&mut self, event_queue_handle: EventQueueHandle<Self::Event>) {
         self.memory_metrics.estimate(self);
         self.event_queue_metrics
-            .record_event_queue_counts(&event_queue_handle)
+            .record_event_queue_counts(&event_queue_handle);
+        self.health_checks
+            .check(self.small_network.peers().len(), self.storage.root_path());
     }
 
     fn maybe_exit(&self) -> Option<ReactorExit> {
         self.linear_chain
             .stop_for_upgrade()
-            .then(|| ReactorExit::ProcessShouldExit(ExitCode::Success))
+            .then(|| ReactorExit::ProcessShouldExit(self.chainspec_loader.exit_code_for_upgrade()))
+    }
+
+    fn reload_config(&mut self, reloadable_config: &ReloadableConfig) -> anyhow::Result<()> {
+        self.small_network.reload_config(reloadable_config);
+
+        let timeout = reloadable_config.get_from_peer_timeout;
+        self.deploy_fetcher.set_get_from_peer_timeout(timeout);
+        self.block_by_hash_fetcher
+            .set_get_from_peer_timeout(timeout);
+        self.block_header_by_hash_fetcher
+            .set_get_from_peer_timeout(timeout);
+        self.trie_or_chunk_fetcher
+            .set_get_from_peer_timeout(timeout);
+        self.block_by_height_fetcher
+            .set_get_from_peer_timeout(timeout);
+        self.block_header_and_finality_signatures_by_height_fetcher
+            .set_get_from_peer_timeout(timeout);
+        self.block_and_deploys_fetcher
+            .set_get_from_peer_timeout(timeout);
+        self.finalized_approvals_fetcher
+            .set_get_from_peer_timeout(timeout);
+        self.block_headers_batch_fetcher
+            .set_get_from_peer_timeout(timeout);
+        self.finality_signatures_fetcher
+            .set_get_from_peer_timeout(timeout);
+        self.backfill_finality_signatures_fetcher
+            .set_get_from_peer_timeout(timeout);
+        Ok(())
+    }
+
+    fn in_flight_fetches(&self) -> InFlightFetches {
+        InFlightFetches(
+            self.deploy_fetcher
+                .in_flight()
+                .chain(self.finalized_approvals_fetcher.in_flight())
+                .chain(self.block_by_hash_fetcher.in_flight())
+                .chain(self.block_by_height_fetcher.in_flight())
+                .chain(
+                    self.block_header_and_finality_signatures_by_height_fetcher
+                        .in_flight(),
+                )
+                .chain(self.block_and_deploys_fetcher.in_flight())
+                .chain(self.trie_or_chunk_fetcher.in_flight())
+                .chain(self.block_header_by_hash_fetcher.in_flight())
+                .chain(self.block_headers_batch_fetcher.in_flight())
+                .chain(self.finality_signatures_fetcher.in_flight())
+                .chain(self.backfill_finality_signatures_fetcher.in_flight())
+                .collect(),
+        )
+    }
+
+    fn estimate_memory_usage(&self) -> MemoryUsage {
+        MemoryUsage(
+            vec![
+                ("metrics", ComponentMemoryUsage::of(&self.metrics)),
+                (
+                    "small_network",
+                    ComponentMemoryUsage::of(&self.small_network),
+                ),
+                (
+                    "address_gossiper",
+                    ComponentMemoryUsage::of(&self.address_gossiper),
+                ),
+                ("storage", ComponentMemoryUsage::of(&self.storage)),
+                (
+                    "contract_runtime",
+                    ComponentMemoryUsage::of(&self.contract_runtime),
+                ),
+                ("rpc_server", ComponentMemoryUsage::of(&self.rpc_server)),
+                ("rest_server", ComponentMemoryUsage::of(&self.rest_server)),
+                (
+                    "event_stream_server",
+                    ComponentMemoryUsage::of(&self.event_stream_server),
+                ),
+                (
+                    "chainspec_loader",
+                    ComponentMemoryUsage::of(&self.chainspec_loader),
+                ),
+                ("consensus", self.consensus.estimate_memory_usage()),
+                (
+                    "deploy_gossiper",
+                    ComponentMemoryUsage::of(&self.deploy_gossiper),
+                ),
+                (
+                    "block_proposer",
+                    ComponentMemoryUsage::of(&self.block_proposer),
+                ),
+                (
+                    "block_validator",
+                    ComponentMemoryUsage::of(&self.block_validator),
+                ),
+                ("linear_chain", ComponentMemoryUsage::of(&self.linear_chain)),
+                (
+                    "chain_synchronizer",
+                    ComponentMemoryUsage::of(&self.chain_synchronizer),
+                ),
+                (
+                    "diagnostics_port",
+                    ComponentMemoryUsage::of(&self.diagnostics_port),
+                ),
+                (
+                    "deploy_fetcher",
+                    ComponentMemoryUsage::of(&self.deploy_fetcher),
+                ),
+                (
+                    "finalized_approvals_fetcher",
+                    ComponentMemoryUsage::of(&self.finalized_approvals_fetcher),
+                ),
+                (
+                    "block_by_hash_fetcher",
+                    ComponentMemoryUsage::of(&self.block_by_hash_fetcher),
+                ),
+                (
+                    "block_by_height_fetcher",
+                    ComponentMemoryUsage::of(&self.block_by_height_fetcher),
+                ),
+                (
+                    "block_header_and_finality_signatures_by_height_fetcher",
+                    ComponentMemoryUsage::of(
+                        &self.block_header_and_finality_signatures_by_height_fetcher,
+                    ),
+                ),
+                (
+                    "block_and_deploys_fetcher",
+                    ComponentMemoryUsage::of(&self.block_and_deploys_fetcher),
+                ),
+                (
+                    "trie_or_chunk_fetcher",
+                    ComponentMemoryUsage::of(&self.trie_or_chunk_fetcher),
+                ),
+                (
+                    "block_header_by_hash_fetcher",
+                    ComponentMemoryUsage::of(&self.block_header_by_hash_fetcher),
+                ),
+                (
+                    "block_headers_batch_fetcher",
+                    ComponentMemoryUsage::of(&self.block_headers_batch_fetcher),
+                ),
+                (
+                    "finality_signatures_fetcher",
+                    ComponentMemoryUsage::of(&self.finality_signatures_fetcher),
+                ),
+                (
+                    "backfill_finality_signatures_fetcher",
+                    ComponentMemoryUsage::of(&self.backfill_finality_signatures_fetcher),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        )
+    }
+
+    fn peers(&self) -> BTreeMap<NodeId, String> {
+        self.small_network.peers()
+    }
+
+    fn node_state(&self) -> Option<NodeState> {
+        Some(self.chain_synchronizer.node_state())
+    }
+
+    fn begin_shutdown(&mut self) -> bool {
+        self.deploy_acceptor.stop_accepting();
+        self.contract_runtime.stop_executing();
+        self.small_network.say_goodbye();
+        true
+    }
+
+    fn is_shut_down(&self) -> bool {
+        !self.contract_runtime.is_executing() && self.small_network.has_sent_queued_messages()
+    }
+
+    fn finish_shutdown(&mut self) {
+        if let Err(error) = self.storage.flush() {
+            error!(%error, "failed to flush storage");
+        }
+        if let Err(error) = self.contract_runtime.flush() {
+            error!(%error, "failed to flush global state");
+        }
     }
 }
 
@@ -1,11 +1,20 @@
+use std::{path::Path, sync::Arc};
+
 use datasize::DataSize;
 use serde::Deserialize;
 
+use casper_types::{PublicKey, SecretKey};
+
 use crate::{
-    logging::LoggingConfig, types::NodeConfig, BlockProposerConfig, ConsensusConfig,
-    ContractRuntimeConfig, DiagnosticsPortConfig, EventStreamServerConfig, FetcherConfig,
-    GossipConfig, RestServerConfig, RpcServerConfig, SmallNetworkConfig, SpeculativeExecConfig,
-    StorageConfig,
+    alerts::AlertsConfig,
+    crash_dump::CrashDumpConfig,
+    logging::LoggingConfig,
+    profiling::ProfilingConfig,
+    types::{NodeConfig, NodeMode},
+    utils::{key_file::KeyFileError, LoadError},
+    BlockProposerConfig, ConsensusConfig, ContractRuntimeConfig, DiagnosticsPortConfig,
+    EventStreamServerConfig, FetcherConfig, GossipConfig, RestServerConfig, RpcServerConfig,
+    SmallNetworkConfig, SpeculativeExecConfig, StorageConfig,
 };
 
 /// Root configuration.
@@ -42,4 +51,27 @@ pub(crate) struct Config {
     pub(crate) block_proposer: BlockProposerConfig,
     /// Diagnostics port configuration.
     pub(crate) diagnostics_port: DiagnosticsPortConfig,
+    /// Profiling configuration.
+    #[serde(default)]
+    pub(crate) profiling: ProfilingConfig,
+    /// Alerting configuration.
+    #[serde(default)]
+    pub(crate) alerts: AlertsConfig,
+    /// Crash dump configuration.
+    #[serde(default)]
+    pub(crate) crash_dump: CrashDumpConfig,
+}
+
+impl Config {
+    /// Loads the validator's secret key and derives its public key, unless the node runs in
+    /// observer mode, in which case `None` is returned without touching the key file.
+    pub(crate) fn load_validator_keys<P: AsRef<Path>>(
+        &self,
+        root: P,
+    ) -> Result<Option<(Arc<SecretKey>, PublicKey)>, LoadError<KeyFileError>> {
+        match self.node.node_mode {
+            NodeMode::Validator => self.consensus.load_keys(root).map(Some),
+            NodeMode::Observer => Ok(None),
+        }
+    }
 }
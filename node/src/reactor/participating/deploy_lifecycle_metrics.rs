@@ -0,0 +1,221 @@
+//! Metrics of the latency between the stages of a deploy's lifecycle.
+//!
+//! Every deploy accepted by the node is tracked until its execution results are available, and
+//! the time between consecutive stages is recorded in a histogram per pair of stages.  Deploys
+//! only seen as part of a block are not tracked, since the node doesn't know when they were
+//! submitted.
+
+use std::collections::HashMap;
+
+use prometheus::{self, Histogram, Registry};
+use tracing::debug;
+
+use casper_types::Timestamp;
+
+use crate::{types::DeployHash, unregister_metric, utils};
+
+/// The maximum number of deploys tracked at once; deploys accepted beyond this are not tracked.
+const MAX_TRACKED_DEPLOYS: usize = 100_000;
+
+/// Value of the upper bound of the first bucket, in seconds.
+const EXPONENTIAL_BUCKET_START: f64 = 0.1;
+
+/// Multiplier of previous upper bound for next bound.
+const EXPONENTIAL_BUCKET_FACTOR: f64 = 2.0;
+
+/// Bucket count, with the last bucket going to +Inf which will not be included in the results.
+const EXPONENTIAL_BUCKET_COUNT: usize = 14;
+
+/// The times at which a tracked deploy reached the stages of its lifecycle.
+#[derive(Debug)]
+struct DeployStages {
+    /// The time at which the deploy was accepted.
+    accepted: Timestamp,
+    /// The time at which the deploy was first included in a proposed block.
+    proposed: Option<Timestamp>,
+    /// The time at which a block including the deploy was first finalized.
+    finalized: Option<Timestamp>,
+}
+
+/// Metrics of the latency between the stages of a deploy's lifecycle.
+#[derive(Debug)]
+pub(super) struct DeployLifecycleMetrics {
+    /// The stages reached by each tracked deploy.
+    deploys: HashMap<DeployHash, DeployStages>,
+    /// Time from a deploy's acceptance until it finished being gossiped.
+    acceptance_to_gossip: Histogram,
+    /// Time from a deploy's acceptance until it was first included in a proposed block.
+    acceptance_to_proposal: Histogram,
+    /// Time from a deploy's first proposal until a block including it was finalized.
+    proposal_to_finalization: Histogram,
+    /// Time from a deploy's finalization until its execution results were available.
+    finalization_to_execution: Histogram,
+    /// Instance of registry to unregister from when being dropped.
+    registry: Registry,
+}
+
+impl DeployLifecycleMetrics {
+    /// Creates a new set of deploy lifecycle metrics, registering them.
+    pub(super) fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
+        let buckets = prometheus::exponential_buckets(
+            EXPONENTIAL_BUCKET_START,
+            EXPONENTIAL_BUCKET_FACTOR,
+            EXPONENTIAL_BUCKET_COUNT,
+        )?;
+        Ok(DeployLifecycleMetrics {
+            deploys: HashMap::new(),
+            acceptance_to_gossip: utils::register_histogram_metric(
+                registry,
+                "deploy_lifecycle_acceptance_to_gossip",
+                "time in seconds from a deploy's acceptance until it finished being gossiped",
+                buckets.clone(),
+            )?,
+            acceptance_to_proposal: utils::register_histogram_metric(
+                registry,
+                "deploy_lifecycle_acceptance_to_proposal",
+                "time in seconds from a deploy's acceptance until it was first proposed",
+                buckets.clone(),
+            )?,
+            proposal_to_finalization: utils::register_histogram_metric(
+                registry,
+                "deploy_lifecycle_proposal_to_finalization",
+                "time in seconds from a deploy's first proposal until it was finalized",
+                buckets.clone(),
+            )?,
+            finalization_to_execution: utils::register_histogram_metric(
+                registry,
+                "deploy_lifecycle_finalization_to_execution",
+                "time in seconds from a deploy's finalization until its execution results were \
+                 available",
+                buckets,
+            )?,
+            registry: registry.clone(),
+        })
+    }
+
+    /// Starts tracking the given newly accepted deploy.
+    pub(super) fn accepted(&mut self, deploy_hash: DeployHash) {
+        if self.deploys.len() >= MAX_TRACKED_DEPLOYS {
+            return;
+        }
+        self.deploys.entry(deploy_hash).or_insert(DeployStages {
+            accepted: Timestamp::now(),
+            proposed: None,
+            finalized: None,
+        });
+    }
+
+    /// Records that the given deploy finished being gossiped.
+    pub(super) fn gossiped(&self, deploy_hash: &DeployHash) {
+        if let Some(stages) = self.deploys.get(deploy_hash) {
+            observe(&self.acceptance_to_gossip, stages.accepted);
+        }
+    }
+
+    /// Records that the given deploys were included in a proposed block.
+    pub(super) fn proposed<'a>(&mut self, deploy_hashes: impl IntoIterator<Item = &'a DeployHash>) {
+        for deploy_hash in deploy_hashes {
+            if let Some(stages) = self.deploys.get_mut(deploy_hash) {
+                if stages.proposed.is_none() {
+                    observe(&self.acceptance_to_proposal, stages.accepted);
+                    stages.proposed = Some(Timestamp::now());
+                }
+            }
+        }
+    }
+
+    /// Records that a block including the given deploys was finalized.
+    pub(super) fn finalized<'a>(
+        &mut self,
+        deploy_hashes: impl IntoIterator<Item = &'a DeployHash>,
+    ) {
+        for deploy_hash in deploy_hashes {
+            if let Some(stages) = self.deploys.get_mut(deploy_hash) {
+                if stages.finalized.is_none() {
+                    if let Some(proposed) = stages.proposed {
+                        observe(&self.proposal_to_finalization, proposed);
+                    }
+                    stages.finalized = Some(Timestamp::now());
+                }
+            }
+        }
+    }
+
+    /// Records that the execution results of the given deploys are available, and stops tracking
+    /// them.
+    pub(super) fn executed<'a>(&mut self, deploy_hashes: impl IntoIterator<Item = &'a DeployHash>) {
+        for deploy_hash in deploy_hashes {
+            let stages = match self.deploys.remove(deploy_hash) {
+                Some(stages) => stages,
+                None => continue,
+            };
+            if let Some(finalized) = stages.finalized {
+                observe(&self.finalization_to_execution, finalized);
+            }
+            debug!(
+                %deploy_hash,
+                acceptance_to_proposal_ms = stages
+                    .proposed
+                    .map(|proposed| proposed.saturating_diff(stages.accepted).millis()),
+                proposal_to_finalization_ms = stages
+                    .proposed
+                    .zip(stages.finalized)
+                    .map(|(proposed, finalized)| finalized.saturating_diff(proposed).millis()),
+                acceptance_to_execution_ms = stages.accepted.elapsed().millis(),
+                "deploy lifecycle complete"
+            );
+        }
+    }
+
+    /// Stops tracking the given expired deploys.
+    pub(super) fn expired<'a>(&mut self, deploy_hashes: impl IntoIterator<Item = &'a DeployHash>) {
+        for deploy_hash in deploy_hashes {
+            self.deploys.remove(deploy_hash);
+        }
+    }
+}
+
+impl Drop for DeployLifecycleMetrics {
+    fn drop(&mut self) {
+        unregister_metric!(self.registry, self.acceptance_to_gossip);
+        unregister_metric!(self.registry, self.acceptance_to_proposal);
+        unregister_metric!(self.registry, self.proposal_to_finalization);
+        unregister_metric!(self.registry, self.finalization_to_execution);
+    }
+}
+
+/// Records the time elapsed since `since` in `histogram`, in seconds.
+fn observe(histogram: &Histogram, since: Timestamp) {
+    histogram.observe(since.elapsed().millis() as f64 / 1_000.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_hashing::Digest;
+
+    use super::*;
+
+    #[test]
+    fn should_record_each_stage_once() {
+        let registry = Registry::new();
+        let mut metrics = DeployLifecycleMetrics::new(&registry).unwrap();
+        let tracked = DeployHash::new(Digest::hash(b"tracked"));
+        let untracked = DeployHash::new(Digest::hash(b"untracked"));
+
+        metrics.accepted(tracked);
+        metrics.gossiped(&tracked);
+        metrics.proposed(&[tracked, untracked]);
+        metrics.proposed(&[tracked]);
+        metrics.finalized(&[tracked, untracked]);
+        metrics.executed(&[tracked, untracked]);
+        assert!(metrics.deploys.is_empty());
+
+        // Stages reached after execution are no longer recorded.
+        metrics.finalized(&[tracked]);
+
+        assert_eq!(metrics.acceptance_to_gossip.get_sample_count(), 1);
+        assert_eq!(metrics.acceptance_to_proposal.get_sample_count(), 1);
+        assert_eq!(metrics.proposal_to_finalization.get_sample_count(), 1);
+        assert_eq!(metrics.finalization_to_execution.get_sample_count(), 1);
+    }
+}
@@ -3,12 +3,12 @@ use thiserror::Error;
 use crate::{
     components::{
         chain_synchronizer, contract_runtime, contract_runtime::BlockExecutionError,
-        diagnostics_port, small_network, storage,
+        diagnostics_port, event_stream_server, rpc_server, small_network, storage,
     },
-    utils::{ListeningError, LoadError},
+    utils::{key_file::KeyFileError, ListeningError, LoadError},
 };
 use casper_execution_engine::core::engine_state;
-use casper_types::{bytesrepr, crypto::ErrorExt as CryptoError};
+use casper_types::bytesrepr;
 
 /// Error type returned by the validator reactor.
 #[derive(Debug, Error)]
@@ -25,6 +25,14 @@ pub(crate) enum Error {
     #[error("http server listening error: {0}")]
     HttpServerListening(#[from] ListeningError),
 
+    /// `RpcServer` component error.
+    #[error("rpc server error: {0}")]
+    RpcServer(#[from] rpc_server::Error),
+
+    /// `EventStreamServer` component error.
+    #[error("event stream server error: {0}")]
+    EventStreamServer(#[from] event_stream_server::Error),
+
     /// `Storage` component error.
     #[error("storage error: {0}")]
     Storage(#[from] storage::FatalStorageError),
@@ -37,6 +45,10 @@ pub(crate) enum Error {
     #[error("contract runtime config error: {0}")]
     ContractRuntime(#[from] contract_runtime::ConfigError),
 
+    /// Error reading the deploy limits governed by the on-chain configuration contract.
+    #[error(transparent)]
+    Governance(#[from] contract_runtime::GovernanceError),
+
     /// Block execution error.
     #[error(transparent)]
     BlockExecution(#[from] BlockExecutionError),
@@ -63,7 +75,7 @@ pub(crate) enum Error {
 
     /// Error while loading the signing key pair.
     #[error("signing key pair load error: {0}")]
-    LoadSigningKeyPair(#[from] LoadError<CryptoError>),
+    LoadSigningKeyPair(#[from] LoadError<KeyFileError>),
 }
 
 impl From<bytesrepr::Error> for Error {
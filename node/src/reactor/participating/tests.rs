@@ -542,7 +542,10 @@ async fn should_store_finalized_approvals() {
         .await;
 
     // Submit a deploy.
-    let mut deploy_alice_bob = Deploy::random_valid_native_transfer_without_deps(&mut rng);
+    let mut deploy_alice_bob = Deploy::builder()
+        .seed(rng.gen())
+        .timestamp(Timestamp::now())
+        .build();
     let mut deploy_alice_bob_charlie = deploy_alice_bob.clone();
     let mut deploy_bob_alice = deploy_alice_bob.clone();
 
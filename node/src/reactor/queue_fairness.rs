@@ -0,0 +1,256 @@
+//! Event queue fairness tracking.
+//!
+//! Records how long events wait in each of the scheduler's queues before being dispatched, and
+//! detects starved queues, i.e. queues whose oldest event has been waiting for longer than a
+//! threshold.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use enum_iterator::IntoEnumIterator;
+use prometheus::{self, Histogram, HistogramOpts, IntCounter, Registry};
+use tracing::warn;
+
+use crate::{
+    effect::diagnostics_port::{QueueFairness, QueueFairnessStats},
+    reactor::QueueKind,
+    unregister_metric,
+};
+
+/// Buckets of the queue wait time histograms, in nanoseconds, ranging from 10 us to 1 min.
+const WAIT_TIME_BUCKETS: &[f64] = &[
+    10_000.0,
+    100_000.0,
+    1_000_000.0,
+    10_000_000.0,
+    50_000_000.0,
+    100_000_000.0,
+    500_000_000.0,
+    1_000_000_000.0,
+    5_000_000_000.0,
+    10_000_000_000.0,
+    30_000_000_000.0,
+    60_000_000_000.0,
+];
+
+/// The statistics and metrics of a single queue.
+#[derive(Debug)]
+struct QueueStats {
+    /// The number of events dispatched from the queue.
+    dispatched: u64,
+    /// The combined time dispatched events waited in the queue.
+    total_wait: Duration,
+    /// The longest time a dispatched event waited in the queue.
+    max_wait: Duration,
+    /// The time at which an event was last dispatched from the queue.
+    last_dispatch: Option<Instant>,
+    /// The number of times the queue was found to be starved.
+    starvations: u64,
+    /// Histogram of how long events waited in the queue before being dispatched.
+    wait_time: Histogram,
+    /// Counter of the number of times the queue was found to be starved.
+    starvation_count: IntCounter,
+}
+
+impl QueueStats {
+    fn new(queue_kind: QueueKind, registry: &Registry) -> Result<Self, prometheus::Error> {
+        let wait_time = Histogram::with_opts(
+            HistogramOpts::new(
+                format!("scheduler_queue_{}_wait_time", queue_kind.metrics_name()),
+                format!(
+                    "time in nanoseconds events waited in the reactor {} queue before being \
+                     dispatched",
+                    queue_kind.metrics_name()
+                ),
+            )
+            .buckets(WAIT_TIME_BUCKETS.to_vec()),
+        )?;
+        let starvation_count = IntCounter::new(
+            format!("scheduler_queue_{}_starvations", queue_kind.metrics_name()),
+            format!(
+                "number of times the reactor {} queue was found to be starved",
+                queue_kind.metrics_name()
+            ),
+        )?;
+        registry.register(Box::new(wait_time.clone()))?;
+        registry.register(Box::new(starvation_count.clone()))?;
+
+        Ok(QueueStats {
+            dispatched: 0,
+            total_wait: Duration::ZERO,
+            max_wait: Duration::ZERO,
+            last_dispatch: None,
+            starvations: 0,
+            wait_time,
+            starvation_count,
+        })
+    }
+}
+
+/// Tracks how fairly the scheduler's queues are serviced.
+#[derive(Debug)]
+pub(super) struct QueueFairnessTracker {
+    /// The statistics of each queue.
+    queues: HashMap<QueueKind, QueueStats>,
+    /// The time after which a queue whose oldest event is still waiting is considered starved.
+    starvation_threshold: Duration,
+    /// The time at which the queues were last checked for starvation.
+    last_starvation_check: Instant,
+    /// Instance of registry to unregister from when being dropped.
+    registry: Registry,
+}
+
+impl QueueFairnessTracker {
+    /// Creates a new tracker, registering its metrics.
+    pub(super) fn new(
+        registry: &Registry,
+        starvation_threshold: Duration,
+    ) -> Result<Self, prometheus::Error> {
+        let queues = QueueKind::into_enum_iter()
+            .map(|queue_kind| Ok((queue_kind, QueueStats::new(queue_kind, registry)?)))
+            .collect::<Result<_, prometheus::Error>>()?;
+        Ok(QueueFairnessTracker {
+            queues,
+            starvation_threshold,
+            last_starvation_check: Instant::now(),
+            registry: registry.clone(),
+        })
+    }
+
+    /// Records that an event was dispatched from `queue_kind` after waiting for `wait_time`.
+    pub(super) fn record_dispatch(&mut self, queue_kind: QueueKind, wait_time: Duration) {
+        let stats = self.stats_mut(queue_kind);
+        stats.dispatched += 1;
+        stats.total_wait += wait_time;
+        stats.max_wait = stats.max_wait.max(wait_time);
+        stats.last_dispatch = Some(Instant::now());
+        stats.wait_time.observe(wait_time.as_nanos() as f64);
+    }
+
+    /// Returns whether the queues are due to be checked for starvation.
+    ///
+    /// The queues are checked at most once per starvation threshold, so each starved queue is
+    /// warned about at most once per threshold.
+    pub(super) fn starvation_check_due(&self) -> bool {
+        self.last_starvation_check.elapsed() >= self.starvation_threshold
+    }
+
+    /// Warns about each queue whose oldest event has been waiting for longer than the starvation
+    /// threshold, given the age of the oldest event in each of the non-empty queues.
+    pub(super) fn check_starvation(&mut self, oldest_item_ages: &HashMap<QueueKind, Duration>) {
+        self.last_starvation_check = Instant::now();
+        let starvation_threshold = self.starvation_threshold;
+        for (queue_kind, oldest_item_age) in oldest_item_ages {
+            if *oldest_item_age < starvation_threshold {
+                continue;
+            }
+            let stats = self.stats_mut(*queue_kind);
+            stats.starvations += 1;
+            stats.starvation_count.inc();
+            warn!(
+                queue = %queue_kind,
+                waited_ms = oldest_item_age.as_millis() as u64,
+                since_last_dispatch_ms = stats
+                    .last_dispatch
+                    .map(|last_dispatch| last_dispatch.elapsed().as_millis() as u64),
+                "event queue starved"
+            );
+        }
+    }
+
+    /// Returns the statistics of all queues, given the age of the oldest event in each of the
+    /// non-empty queues.
+    pub(super) fn snapshot(
+        &self,
+        oldest_item_ages: &HashMap<QueueKind, Duration>,
+    ) -> QueueFairness {
+        let queues = self
+            .queues
+            .iter()
+            .map(|(queue_kind, stats)| {
+                let mean_wait = match u128::from(stats.dispatched) {
+                    0 => Duration::ZERO,
+                    dispatched => {
+                        Duration::from_nanos((stats.total_wait.as_nanos() / dispatched) as u64)
+                    }
+                };
+                let snapshot = QueueFairnessStats {
+                    dispatched: stats.dispatched,
+                    mean_wait: mean_wait.into(),
+                    max_wait: stats.max_wait.into(),
+                    oldest_waiting: oldest_item_ages.get(queue_kind).map(|age| (*age).into()),
+                    since_last_dispatch: stats
+                        .last_dispatch
+                        .map(|last_dispatch| last_dispatch.elapsed().into()),
+                    starvations: stats.starvations,
+                };
+                (*queue_kind, snapshot)
+            })
+            .collect();
+        QueueFairness(queues)
+    }
+
+    fn stats_mut(&mut self, queue_kind: QueueKind) -> &mut QueueStats {
+        self.queues
+            .get_mut(&queue_kind)
+            .expect("should have stats for every queue kind")
+    }
+}
+
+impl Drop for QueueFairnessTracker {
+    fn drop(&mut self) {
+        for stats in self.queues.values() {
+            unregister_metric!(self.registry, stats.wait_time);
+            unregister_metric!(self.registry, stats.starvation_count);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_detect_starved_queues() {
+        let registry = Registry::new();
+        let mut tracker = QueueFairnessTracker::new(&registry, Duration::from_secs(1)).unwrap();
+
+        tracker.record_dispatch(QueueKind::Regular, Duration::from_millis(10));
+        tracker.record_dispatch(QueueKind::Regular, Duration::from_millis(30));
+
+        let oldest_item_ages: HashMap<_, _> = vec![
+            (QueueKind::Regular, Duration::from_millis(500)),
+            (QueueKind::NetworkLowPriority, Duration::from_secs(5)),
+        ]
+        .into_iter()
+        .collect();
+        tracker.check_starvation(&oldest_item_ages);
+        assert!(!tracker.starvation_check_due());
+
+        let snapshot = tracker.snapshot(&oldest_item_ages);
+        let regular = &snapshot.0[&QueueKind::Regular];
+        assert_eq!(regular.dispatched, 2);
+        assert_eq!(regular.mean_wait, Duration::from_millis(20).into());
+        assert_eq!(regular.max_wait, Duration::from_millis(30).into());
+        assert_eq!(regular.starvations, 0);
+        assert!(regular.since_last_dispatch.is_some());
+
+        let low_priority = &snapshot.0[&QueueKind::NetworkLowPriority];
+        assert_eq!(low_priority.dispatched, 0);
+        assert_eq!(low_priority.starvations, 1);
+        assert_eq!(
+            low_priority.oldest_waiting,
+            Some(Duration::from_secs(5).into())
+        );
+        assert!(low_priority.since_last_dispatch.is_none());
+        assert_eq!(
+            tracker
+                .stats_mut(QueueKind::NetworkLowPriority)
+                .starvation_count
+                .get(),
+            1
+        );
+    }
+}
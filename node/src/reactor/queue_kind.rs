@@ -3,16 +3,32 @@
 //! The reactor's event queue uses different queues to group events by priority and polls them in a
 //! round-robin manner. This way, events are only competing for time within one queue, non-congested
 //! queues can always assume to be speedily processed.
+//!
+//! Some queues have a capacity, which limits how many events that can be dropped under load, such
+//! as gossip messages, are accepted. Other events are always accepted, so critical queues and
+//! events are unbounded.
 
 use std::{fmt::Display, num::NonZeroUsize};
 
 use enum_iterator::IntoEnumIterator;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 /// Scheduling priority.
 ///
 /// Priorities are ordered from lowest to highest.
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, IntoEnumIterator, PartialOrd, Ord, Serialize)]
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    Hash,
+    IntoEnumIterator,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+)]
 pub enum QueueKind {
     /// Control messages for the runtime itself.
     Control,
@@ -77,6 +93,21 @@ impl QueueKind {
         .expect("weight must be positive")
     }
 
+    /// Returns the default capacity of a specific queue, or `None` if it is unbounded.
+    ///
+    /// Only producers of events which can be dropped under load are subject to the capacity.
+    pub(crate) fn default_capacity(self) -> Option<NonZeroUsize> {
+        match self {
+            QueueKind::NetworkIncoming => NonZeroUsize::new(50_000),
+            QueueKind::NetworkLowPriority => NonZeroUsize::new(50_000),
+            QueueKind::Control
+            | QueueKind::NetworkDemand
+            | QueueKind::Network
+            | QueueKind::Regular
+            | QueueKind::Api => None,
+        }
+    }
+
     /// Return weights of all possible `Queue`s.
     pub(crate) fn weights() -> Vec<(Self, NonZeroUsize)> {
         QueueKind::into_enum_iter()
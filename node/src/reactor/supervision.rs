@@ -0,0 +1,493 @@
+//! Supervision of components the node can keep running without.
+//!
+//! A panic while a supervised component handles an event is caught rather than aborting the node.
+//! So is a panic in any effect it returns or any task it spawns via its [`SupervisedTasks`],
+//! including the connection tasks of its server, which is reported via a
+//! [`SupervisorAnnouncement::TaskPanicked`].  The crashed component is dropped, shutting down any
+//! server it runs, and a [`SupervisorAnnouncement::ComponentCrashed`] is emitted.  The reactor then
+//! recreates the component after a delay which doubles with each consecutive crash.
+//!
+//! Only components serving clients, like the REST and event stream servers, are supervised.
+//! Components such as consensus and storage hold state the node can't safely continue without,
+//! so a panic in them still aborts the node.
+
+use std::{
+    any::Any,
+    cell::Cell,
+    fmt::{self, Debug, Display, Formatter},
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use datasize::DataSize;
+use futures::{
+    channel::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    FutureExt, StreamExt,
+};
+use serde::Serialize;
+use tokio::task::{self, JoinHandle};
+use tracing::{debug, error, info};
+
+use crate::{
+    components::Component,
+    effect::{announcements::SupervisorAnnouncement, EffectBuilder, EffectExt, Effects},
+    NodeRng,
+};
+
+/// Delay before restarting a component after its first crash.
+const INITIAL_RESTART_DELAY: Duration = Duration::from_secs(1);
+/// Maximum delay before restarting a crashed component.
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(300);
+/// How long a component needs to run without crashing for its restart delay to be reset.
+const STABLE_RUN_DURATION: Duration = Duration::from_secs(600);
+
+thread_local! {
+    /// Whether the current thread is running code of a supervised component.
+    static SUPERVISED: Cell<bool> = Cell::new(false);
+}
+
+/// Returns `true` if the current thread is running code of a supervised component, in which case
+/// a panic is caught and the component restarted, so the panic hook must not abort the process.
+pub fn in_supervised_component() -> bool {
+    SUPERVISED.with(Cell::get)
+}
+
+/// Marks the current thread as running code of a supervised component until dropped.
+struct SupervisedGuard {
+    /// Whether the thread was marked before.
+    was_supervised: bool,
+}
+
+impl SupervisedGuard {
+    fn enter() -> Self {
+        SupervisedGuard {
+            was_supervised: SUPERVISED.with(|supervised| supervised.replace(true)),
+        }
+    }
+}
+
+impl Drop for SupervisedGuard {
+    fn drop(&mut self) {
+        SUPERVISED.with(|supervised| supervised.set(self.was_supervised));
+    }
+}
+
+/// Runs `f` as code of a supervised component, catching any panic.
+fn supervise<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    let _guard = SupervisedGuard::enter();
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| panic_message(&*payload))
+}
+
+/// Extracts the message from a panic payload.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// Returns the delay before restarting a component which crashed `consecutive_crashes` times
+/// before.
+fn restart_delay(consecutive_crashes: u32) -> Duration {
+    INITIAL_RESTART_DELAY
+        .checked_mul(2u32.saturating_pow(consecutive_crashes))
+        .map_or(MAX_RESTART_DELAY, |delay| delay.min(MAX_RESTART_DELAY))
+}
+
+/// Identifies a supervised component.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub(crate) enum SupervisedComponent {
+    /// The REST server.
+    RestServer,
+    /// The event stream server.
+    EventStreamServer,
+}
+
+impl Display for SupervisedComponent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SupervisedComponent::RestServer => write!(f, "rest server"),
+            SupervisedComponent::EventStreamServer => write!(f, "event stream server"),
+        }
+    }
+}
+
+/// Spawns tasks on behalf of a supervised component, reporting their panics to the component's
+/// [`SupervisedTasks`].
+///
+/// Panics are only caught once the supervisor watches the component's tasks.  Until then, e.g.
+/// while the component is run by the joiner reactor, a panic aborts the node as usual.
+#[derive(Clone, Debug)]
+pub(crate) struct TaskSupervisor {
+    /// Reports the message of a caught panic.
+    panic_sender: UnboundedSender<String>,
+    /// Whether the supervisor watches for panics.
+    watched: Arc<AtomicBool>,
+}
+
+impl TaskSupervisor {
+    /// Wraps `future` so that a panic while polling it is reported rather than aborting the node,
+    /// in which case the future completes with the default output.
+    pub(crate) fn wrap<F>(&self, future: F) -> SupervisedFuture<F>
+    where
+        F: Future,
+        F::Output: Default,
+    {
+        SupervisedFuture {
+            future: Box::pin(future),
+            supervisor: self.clone(),
+        }
+    }
+
+    /// Spawns `future` as a supervised task on the tokio runtime.
+    pub(crate) fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Default + Send + 'static,
+    {
+        tokio::spawn(self.wrap(future))
+    }
+
+    /// Runs `f` as a supervised task on a thread where blocking is acceptable.
+    pub(crate) fn spawn_blocking<F>(&self, f: F) -> JoinHandle<()>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let supervisor = self.clone();
+        task::spawn_blocking(move || {
+            if !supervisor.is_watched() {
+                return f();
+            }
+            if let Err(message) = supervise(f) {
+                supervisor.report_panic(message);
+            }
+        })
+    }
+
+    fn is_watched(&self) -> bool {
+        self.watched.load(Ordering::SeqCst)
+    }
+
+    fn report_panic(&self, message: String) {
+        error!(%message, "supervised task panicked");
+        let _ = self.panic_sender.unbounded_send(message);
+    }
+}
+
+/// Spawns the connection tasks of a hyper server as supervised tasks.
+impl<F> hyper::rt::Executor<F> for TaskSupervisor
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    fn execute(&self, future: F) {
+        self.spawn(future);
+    }
+}
+
+/// A future whose panics are reported to a [`TaskSupervisor`].
+pub(crate) struct SupervisedFuture<F> {
+    future: Pin<Box<F>>,
+    supervisor: TaskSupervisor,
+}
+
+impl<F> Future for SupervisedFuture<F>
+where
+    F: Future,
+    F::Output: Default,
+{
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if !self.supervisor.is_watched() {
+            return self.future.as_mut().poll(cx);
+        }
+        let future = &mut self.future;
+        match supervise(|| future.as_mut().poll(cx)) {
+            Ok(poll) => poll,
+            Err(message) => {
+                self.supervisor.report_panic(message);
+                Poll::Ready(F::Output::default())
+            }
+        }
+    }
+}
+
+/// The tasks spawned by a supervised component.
+#[derive(Debug)]
+pub(crate) struct SupervisedTasks {
+    supervisor: TaskSupervisor,
+    /// Receives the messages of caught panics, until taken by the component's supervisor.
+    panic_receiver: Option<UnboundedReceiver<String>>,
+}
+
+impl SupervisedTasks {
+    pub(crate) fn new() -> Self {
+        let (panic_sender, panic_receiver) = mpsc::unbounded();
+        SupervisedTasks {
+            supervisor: TaskSupervisor {
+                panic_sender,
+                watched: Arc::new(AtomicBool::new(false)),
+            },
+            panic_receiver: Some(panic_receiver),
+        }
+    }
+
+    /// Returns the supervisor to spawn the component's tasks with.
+    pub(crate) fn supervisor(&self) -> &TaskSupervisor {
+        &self.supervisor
+    }
+
+    /// Starts catching panics of the tasks, returning the receiver of their messages unless
+    /// already taken.
+    fn watch(&mut self) -> Option<UnboundedReceiver<String>> {
+        self.supervisor.watched.store(true, Ordering::SeqCst);
+        self.panic_receiver.take()
+    }
+}
+
+/// A component which may spawn supervised tasks.
+pub(crate) trait SpawnsSupervisedTasks {
+    /// Returns the component's supervised tasks, or `None` if it doesn't run any.
+    fn supervised_tasks(&mut self) -> Option<&mut SupervisedTasks>;
+}
+
+/// Creates a new instance of a supervised component.
+type Constructor<C> = Box<dyn FnMut() -> Result<C, String> + Send>;
+
+/// A component which is restarted instead of aborting the node when it panics.
+pub(crate) struct Supervised<C> {
+    /// Which component this is.
+    id: SupervisedComponent,
+    /// The component, or `None` while it is awaiting a restart after crashing.
+    component: Option<C>,
+    /// Creates the component on restart.
+    constructor: Constructor<C>,
+    /// The number of crashes without the component running stably in between.
+    consecutive_crashes: u32,
+    /// When the component was last started or crashed.
+    since: Instant,
+    /// The number of times the component was restarted, to tell stale task panics apart.
+    generation: u64,
+}
+
+impl<C: SpawnsSupervisedTasks> Supervised<C> {
+    /// Supervises the running `component`, using `constructor` to recreate it after a crash.
+    pub(crate) fn new<F>(id: SupervisedComponent, component: C, constructor: F) -> Self
+    where
+        F: FnMut() -> Result<C, String> + Send + 'static,
+    {
+        Supervised {
+            id,
+            component: Some(component),
+            constructor: Box::new(constructor),
+            consecutive_crashes: 0,
+            since: Instant::now(),
+            generation: 0,
+        }
+    }
+
+    /// Starts catching panics of the component's tasks, returning an effect which announces the
+    /// first of them.
+    pub(crate) fn watch_tasks<Ev, REv>(&mut self, effect_builder: EffectBuilder<REv>) -> Effects<Ev>
+    where
+        REv: From<SupervisorAnnouncement> + Send,
+    {
+        let mut panic_receiver = match self
+            .component
+            .as_mut()
+            .and_then(|component| component.supervised_tasks())
+            .and_then(SupervisedTasks::watch)
+        {
+            Some(panic_receiver) => panic_receiver,
+            None => return Effects::new(),
+        };
+        let (component, generation) = (self.id, self.generation);
+        async move {
+            if let Some(message) = panic_receiver.next().await {
+                effect_builder
+                    .announce_task_panicked(component, generation, message)
+                    .await;
+            }
+        }
+        .ignore()
+    }
+
+    /// Passes `event` to the component.
+    ///
+    /// If handling the event panics, the component is dropped and a crash announced.  Events
+    /// received while the component is awaiting a restart are dropped.
+    pub(crate) fn handle_event<REv>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        rng: &mut NodeRng,
+        event: C::Event,
+    ) -> Effects<C::Event>
+    where
+        C: Component<REv>,
+        REv: From<SupervisorAnnouncement> + Send,
+    {
+        let component = match self.component.as_mut() {
+            Some(component) => component,
+            None => {
+                debug!(component = %self.id, "dropping event for crashed component");
+                return Effects::new();
+            }
+        };
+
+        match supervise(|| component.handle_event(effect_builder, rng, event)) {
+            Ok(effects) => match component.supervised_tasks() {
+                Some(tasks) => effects
+                    .into_iter()
+                    .map(|effect| tasks.supervisor().wrap(effect).boxed())
+                    .collect(),
+                None => effects,
+            },
+            Err(message) => self.crashed(effect_builder, message),
+        }
+    }
+
+    /// Handles the panic of a task spawned by the component, or by an earlier instance of it if
+    /// `generation` is outdated, in which case it is ignored.
+    pub(crate) fn task_panicked<Ev, REv>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        generation: u64,
+        message: String,
+    ) -> Effects<Ev>
+    where
+        REv: From<SupervisorAnnouncement> + Send,
+    {
+        if generation != self.generation || self.component.is_none() {
+            debug!(component = %self.id, %message, "ignoring panic of stale task");
+            return Effects::new();
+        }
+        self.crashed(effect_builder, format!("task panicked: {}", message))
+    }
+
+    /// Recreates the component if it has crashed.
+    ///
+    /// If the component can't be created, this counts as another crash.
+    pub(crate) fn restart<Ev, REv>(&mut self, effect_builder: EffectBuilder<REv>) -> Effects<Ev>
+    where
+        REv: From<SupervisorAnnouncement> + Send,
+    {
+        if self.component.is_some() {
+            return Effects::new();
+        }
+
+        let constructor = &mut self.constructor;
+        match supervise(constructor) {
+            Ok(Ok(component)) => {
+                info!(component = %self.id, "restarted component");
+                self.component = Some(component);
+                self.since = Instant::now();
+                self.generation = self.generation.wrapping_add(1);
+                self.watch_tasks(effect_builder)
+            }
+            Ok(Err(error)) => self.crashed(effect_builder, format!("failed to restart: {}", error)),
+            Err(message) => self.crashed(effect_builder, message),
+        }
+    }
+
+    /// Drops the crashed component and announces the crash.
+    fn crashed<Ev, REv>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        message: String,
+    ) -> Effects<Ev>
+    where
+        REv: From<SupervisorAnnouncement> + Send,
+    {
+        // Dropping the component shuts down its server, which may panic as well.
+        let component = self.component.take();
+        if let Err(drop_message) = supervise(|| drop(component)) {
+            error!(component = %self.id, message = %drop_message, "panicked dropping component");
+        }
+
+        if self.since.elapsed() >= STABLE_RUN_DURATION {
+            self.consecutive_crashes = 0;
+        }
+        let restart_delay = restart_delay(self.consecutive_crashes);
+        self.consecutive_crashes = self.consecutive_crashes.saturating_add(1);
+        self.since = Instant::now();
+
+        error!(
+            component = %self.id,
+            %message,
+            ?restart_delay,
+            "component crashed, restarting after delay"
+        );
+        effect_builder
+            .announce_component_crashed(self.id, message, restart_delay)
+            .ignore()
+    }
+}
+
+impl<C: Debug> Debug for Supervised<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Supervised")
+            .field("id", &self.id)
+            .field("component", &self.component)
+            .field("consecutive_crashes", &self.consecutive_crashes)
+            .finish()
+    }
+}
+
+impl<C: DataSize> DataSize for Supervised<C> {
+    const IS_DYNAMIC: bool = true;
+    const STATIC_HEAP_SIZE: usize = 0;
+
+    fn estimate_heap_size(&self) -> usize {
+        self.component.estimate_heap_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_double_restart_delay_up_to_maximum() {
+        assert_eq!(restart_delay(0), INITIAL_RESTART_DELAY);
+        assert_eq!(restart_delay(1), INITIAL_RESTART_DELAY * 2);
+        assert_eq!(restart_delay(3), INITIAL_RESTART_DELAY * 8);
+        assert_eq!(restart_delay(9), MAX_RESTART_DELAY);
+        assert_eq!(restart_delay(u32::MAX), MAX_RESTART_DELAY);
+    }
+
+    #[test]
+    fn should_catch_panic_and_restore_flag() {
+        assert!(!in_supervised_component());
+        let result = supervise(|| {
+            assert!(in_supervised_component());
+            panic!("boom")
+        });
+        assert_eq!(result, Err::<(), _>("boom".to_string()));
+        assert!(!in_supervised_component());
+    }
+
+    #[tokio::test]
+    async fn should_report_panic_of_watched_task() {
+        let mut tasks = SupervisedTasks::new();
+        let mut panic_receiver = tasks.watch().expect("should watch tasks");
+        tasks
+            .supervisor()
+            .spawn(async {
+                panic!("boom");
+            })
+            .await
+            .expect("should catch panic");
+        assert_eq!(panic_receiver.next().await, Some("boom".to_string()));
+        assert!(tasks.watch().is_none());
+    }
+}
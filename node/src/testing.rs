@@ -285,6 +285,19 @@ impl<REv: 'static> ComponentHarness<REv> {
                         ControlAnnouncement::QueueDumpRequest { .. } => {
                             panic!("queue dumps are not supported in the test harness")
                         }
+                        ControlAnnouncement::ForkDetected(_) => {
+                            debug!(?ev, "ignoring fork detection while looking for a fatal")
+                        }
+                        ControlAnnouncement::ReloadConfigRequest { .. } => {
+                            panic!("config reloads are not supported in the test harness")
+                        }
+                        ControlAnnouncement::EventCountsRequest { .. }
+                        | ControlAnnouncement::QueueFairnessRequest { .. }
+                        | ControlAnnouncement::DeployTraceRequest { .. }
+                        | ControlAnnouncement::InFlightFetchesRequest { .. }
+                        | ControlAnnouncement::MemoryUsageRequest { .. } => {
+                            panic!("diagnostics requests are not supported in the test harness")
+                        }
                     }
                 } else {
                     debug!(?ev, "ignoring event while looking for a fatal")
@@ -361,7 +374,11 @@ pub(crate) fn create_test_deploy(
     now: Timestamp,
     test_rng: &mut TestRng,
 ) -> Deploy {
-    Deploy::random_with_timestamp_and_ttl(test_rng, now - created_ago, ttl)
+    Deploy::builder()
+        .seed(test_rng.gen())
+        .timestamp(now - created_ago)
+        .ttl(ttl)
+        .build()
 }
 
 /// Creates a random deploy that is considered expired.
@@ -13,7 +13,10 @@ use casper_types::Timestamp;
 
 pub(crate) use crate::components::deploy_acceptor::{Error, Event};
 use crate::{
-    components::{deploy_acceptor::EventMetadata, Component},
+    components::{
+        deploy_acceptor::{BalanceProof, EventMetadata},
+        Component,
+    },
     effect::{
         announcements::DeployAcceptorAnnouncement,
         requests::{ContractRuntimeRequest, StorageRequest},
@@ -55,7 +58,7 @@ impl FakeDeployAcceptor {
         effect_builder: EffectBuilder<REv>,
         deploy: Box<Deploy>,
         source: Source,
-        maybe_responder: Option<Responder<Result<(), Error>>>,
+        maybe_responder: Option<Responder<Result<Option<BalanceProof>, Error>>>,
     ) -> Effects<Event> {
         let verification_start_timestamp = Timestamp::now();
         let event_metadata = EventMetadata::new(deploy.clone(), source, maybe_responder);
@@ -78,6 +81,7 @@ impl FakeDeployAcceptor {
             deploy,
             source,
             maybe_responder,
+            ..
         } = event_metadata;
         let mut effects = Effects::new();
         if is_new {
@@ -89,7 +93,7 @@ impl FakeDeployAcceptor {
         }
 
         if let Some(responder) = maybe_responder {
-            effects.extend(responder.respond(Ok(())).ignore());
+            effects.extend(responder.respond(Ok(None)).ignore());
         }
         effects
     }
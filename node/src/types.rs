@@ -3,18 +3,27 @@
 pub(crate) mod appendable_block;
 mod available_block_range;
 mod block;
+#[cfg(any(feature = "testing", test))]
+mod block_builder;
+mod block_height;
+mod canonical_json;
 pub mod chainspec;
+mod contract_event;
 mod deploy;
+#[cfg(any(feature = "testing", test))]
+mod deploy_builder;
 pub mod error;
 mod exit_code;
 mod item;
 pub mod json_compatibility;
 mod node_config;
+mod node_features;
 mod node_id;
 /// Peers map.
 pub mod peers_map;
 mod shared_object;
 mod status_feed;
+mod validator_performance;
 
 use rand::{CryptoRng, RngCore};
 #[cfg(not(test))]
@@ -22,30 +31,40 @@ use rand_chacha::ChaCha20Rng;
 
 pub use available_block_range::AvailableBlockRange;
 pub use block::{
-    json_compatibility::{JsonBlock, JsonBlockHeader},
+    json_compatibility::{JsonBlock, JsonBlockHeader, JsonEraEnd, JsonProof},
     Block, BlockAndDeploys, BlockBody, BlockHash, BlockHeader, BlockSignatures, FinalitySignature,
-    FinalizedBlock,
+    FinalizedBlock, SignedWeight,
 };
 pub(crate) use block::{
-    BlockHashAndHeight, BlockHeaderWithMetadata, BlockHeadersBatch, BlockHeadersBatchId,
-    BlockPayload, BlockWithMetadata,
+    BackfillBlockSignatures, BlockHashAndHeight, BlockHeaderWithMetadata, BlockHeadersBatch,
+    BlockHeadersBatchId, BlockPayload, BlockWithMetadata,
 };
+#[cfg(any(feature = "testing", test))]
+pub use block_builder::BlockBuilder;
+pub use block_height::BlockHeight;
+pub use canonical_json::{CanonicalJson, CANONICAL_JSON_VERSION};
 pub use chainspec::Chainspec;
 pub(crate) use chainspec::{ActivationPoint, ChainspecRawBytes};
+pub use contract_event::ContractEvent;
 pub use datasize::DataSize;
 pub use deploy::{
-    Approval, Deploy, DeployConfigurationFailure, DeployHash, DeployHeader, DeployMetadata,
-    DeployMetadataExt, DeployOrTransferHash, DeployWithApprovals, DeployWithFinalizedApprovals,
-    Error as DeployError, ExcessiveSizeError as ExcessiveSizeDeployError, FinalizedApprovals,
-    FinalizedApprovalsWithId,
+    AccountDeploy, AccountDeploys, Approval, Deploy, DeployConfigurationFailure, DeployHash,
+    DeployHeader, DeployMetadata, DeployMetadataExt, DeployOrTransferHash, DeployWithApprovals,
+    DeployWithFinalizedApprovals, Error as DeployError,
+    ExcessiveSizeError as ExcessiveSizeDeployError, FinalizedApprovals, FinalizedApprovalsWithId,
 };
+#[cfg(any(feature = "testing", test))]
+pub use deploy_builder::DeployBuilder;
 pub use error::BlockValidationError;
 pub use exit_code::ExitCode;
 pub(crate) use item::{Item, Tag};
-pub use node_config::NodeConfig;
+pub use node_config::{NodeConfig, NodeMode};
+pub use node_features::NodeFeatures;
+pub(crate) use node_features::NodeFeaturesMetric;
 pub(crate) use node_id::NodeId;
 pub use peers_map::PeersMap;
-pub use status_feed::{ChainspecInfo, GetStatusResult, NodeState, StatusFeed};
+pub use status_feed::{ChainspecInfo, GetStatusResult, NodeState, StatusAttestation, StatusFeed};
+pub use validator_performance::{EraValidatorPerformance, ValidatorPerformance};
 
 /// An object-safe RNG trait that requires a cryptographically strong random number generator.
 pub trait CryptoRngCore: CryptoRng + RngCore {}
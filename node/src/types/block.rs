@@ -483,36 +483,18 @@ impl FinalizedBlock {
     }
 
     /// Generates a random instance using a `TestRng`.
+    ///
+    /// For blocks with specific properties, use [`Block::builder`].
     #[cfg(any(feature = "testing", test))]
     pub fn random(rng: &mut TestRng) -> Self {
-        let era = rng.gen_range(0..5);
+        let era = rng.gen_range(0..MAX_ERA_FOR_RANDOM_BLOCK);
         let height = era * 10 + rng.gen_range(0..10);
         let is_switch = rng.gen_bool(0.1);
 
-        FinalizedBlock::random_with_specifics(rng, EraId::from(era), height, is_switch, None)
-    }
-
-    #[cfg(any(feature = "testing", test))]
-    /// Generates a random instance using a `TestRng`, but using the specified values.
-    /// If `deploy` is `None`, random deploys will be generated, otherwise, the provided `deploy`
-    /// will be used.
-    pub fn random_with_specifics<'a, I: IntoIterator<Item = &'a Deploy>>(
-        rng: &mut TestRng,
-        era_id: EraId,
-        height: u64,
-        is_switch: bool,
-        deploys_iter: I,
-    ) -> Self {
-        let mut deploys = deploys_iter
-            .into_iter()
-            .map(DeployWithApprovals::from)
-            .collect::<Vec<_>>();
-        if deploys.is_empty() {
-            let count = rng.gen_range(0..11);
-            deploys.extend(
-                iter::repeat_with(|| DeployWithApprovals::from(&Deploy::random(rng))).take(count),
-            );
-        }
+        let count = rng.gen_range(0..11);
+        let deploys = iter::repeat_with(|| DeployWithApprovals::from(&Deploy::random(rng)))
+            .take(count)
+            .collect();
         let random_bit = rng.gen();
         // TODO - make Timestamp deterministic.
         let timestamp = Timestamp::now();
@@ -553,7 +535,7 @@ impl FinalizedBlock {
             block_payload,
             era_report,
             timestamp,
-            era_id,
+            EraId::from(era),
             height,
             public_key,
         )
@@ -1370,6 +1352,45 @@ impl Item for BlockSignatures {
     }
 }
 
+/// All finality signatures held by a peer for a block, fetched to backfill the signatures of a
+/// block already in storage.
+///
+/// Unlike `BlockSignatures`, these are always fetched from the peer, even if the signatures held in
+/// local storage are sufficient for finality.
+#[derive(Clone, Debug, Serialize, Deserialize, DataSize, Eq, PartialEq)]
+pub(crate) struct BackfillBlockSignatures(BlockSignatures);
+
+impl BackfillBlockSignatures {
+    pub(crate) fn new(block_signatures: BlockSignatures) -> Self {
+        BackfillBlockSignatures(block_signatures)
+    }
+
+    pub(crate) fn into_inner(self) -> BlockSignatures {
+        self.0
+    }
+}
+
+impl Display for BackfillBlockSignatures {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "backfill {}", self.0)
+    }
+}
+
+impl Item for BackfillBlockSignatures {
+    type Id = BlockHash;
+    type ValidationError = crypto::Error;
+    const TAG: Tag = Tag::BackfillFinalitySignaturesByHash;
+    const ID_IS_COMPLETE_ITEM: bool = false;
+
+    fn validate(&self) -> Result<(), Self::ValidationError> {
+        self.0.verify()
+    }
+
+    fn id(&self) -> Self::Id {
+        self.0.block_hash
+    }
+}
+
 /// A proto-block after execution, with the resulting post-state-hash.  This is the core component
 /// of the Casper linear blockchain.
 #[derive(DataSize, Clone, Debug, PartialOrd, Ord, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -1529,36 +1550,13 @@ impl Block {
     }
 
     /// Generates a random instance using a `TestRng`.
+    ///
+    /// For blocks with specific properties, use [`Block::builder`].
     #[cfg(any(feature = "testing", test))]
     pub fn random(rng: &mut TestRng) -> Self {
-        let era = rng.gen_range(0..MAX_ERA_FOR_RANDOM_BLOCK);
-        let height = era * 10 + rng.gen_range(0..10);
-        let is_switch = rng.gen_bool(0.1);
-
-        Block::random_with_specifics(
-            rng,
-            EraId::from(era),
-            height,
-            ProtocolVersion::V1_0_0,
-            is_switch,
-            None,
-        )
-    }
-
-    /// Generates a random instance using a `TestRng`, but using the specified values.
-    #[cfg(any(feature = "testing", test))]
-    pub fn random_with_specifics<'a, I: IntoIterator<Item = &'a Deploy>>(
-        rng: &mut TestRng,
-        era_id: EraId,
-        height: u64,
-        protocol_version: ProtocolVersion,
-        is_switch: bool,
-        deploys_iter: I,
-    ) -> Self {
         let parent_hash = BlockHash::new(rng.gen::<[u8; Digest::LENGTH]>().into());
         let state_root_hash = rng.gen::<[u8; Digest::LENGTH]>().into();
-        let finalized_block =
-            FinalizedBlock::random_with_specifics(rng, era_id, height, is_switch, deploys_iter);
+        let finalized_block = FinalizedBlock::random(rng);
         let parent_seed = rng.gen::<[u8; Digest::LENGTH]>().into();
         let next_era_validator_weights = finalized_block
             .clone()
@@ -1571,9 +1569,9 @@ impl Block {
             state_root_hash,
             finalized_block,
             next_era_validator_weights,
-            protocol_version,
+            ProtocolVersion::V1_0_0,
         )
-        .expect("Could not create random block with specifics")
+        .expect("Could not create random block")
     }
 }
 
@@ -1839,6 +1837,7 @@ pub(crate) mod json_compatibility {
         }
     }
 
+    /// JSON representation of the end of an era, as recorded in its switch block.
     #[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq, DataSize)]
     #[serde(deny_unknown_fields)]
     pub struct JsonEraEnd {
@@ -2131,6 +2130,17 @@ impl Display for FinalitySignature {
     }
 }
 
+/// The combined weight of the validators known to have signed a block, along with the total weight
+/// of the validators of the block's era.  Clients can compare the two to decide whether the block
+/// is finalized with sufficient confidence.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, DataSize, PartialEq, Eq, JsonSchema)]
+pub struct SignedWeight {
+    /// The combined weight of the validators whose finality signatures for the block are known.
+    pub signed_weight: U512,
+    /// The total weight of the validators of the block's era.
+    pub total_weight: U512,
+}
+
 #[cfg(test)]
 mod tests {
     use std::rc::Rc;
@@ -2274,14 +2284,12 @@ mod tests {
         let deploys = iter::repeat_with(|| Deploy::random(&mut rng))
             .take(5)
             .collect::<Vec<_>>();
-        let block = Block::random_with_specifics(
-            &mut rng,
-            EraId::new(1),
-            2,
-            ProtocolVersion::V1_0_0,
-            false,
-            deploys.iter(),
-        );
+        let block = Block::builder()
+            .seed(rng.gen())
+            .era_id(EraId::new(1))
+            .height(2)
+            .deploys(deploys.iter())
+            .build();
         let block_and_deploys = BlockAndDeploys { block, deploys };
 
         block_and_deploys
@@ -2297,14 +2305,12 @@ mod tests {
         let deploys = iter::repeat_with(|| Deploy::random(&mut rng))
             .take(5)
             .collect::<Vec<_>>();
-        let block = Block::random_with_specifics(
-            &mut rng,
-            EraId::new(1),
-            2,
-            ProtocolVersion::V1_0_0,
-            false,
-            deploys.iter(),
-        );
+        let block = Block::builder()
+            .seed(rng.gen())
+            .era_id(EraId::new(1))
+            .height(2)
+            .deploys(deploys.iter())
+            .build();
 
         // Put both sets of deploys in `BlockAndDeploys`
         let extra_deploys = iter::repeat_with(|| Deploy::random(&mut rng))
@@ -2341,14 +2347,12 @@ mod tests {
         let deploys2 = iter::repeat_with(|| Deploy::random(&mut rng))
             .take(2)
             .collect::<Vec<_>>();
-        let block = Block::random_with_specifics(
-            &mut rng,
-            EraId::new(1),
-            2,
-            ProtocolVersion::V1_0_0,
-            false,
-            deploys1.iter().chain(deploys2.iter()),
-        );
+        let block = Block::builder()
+            .seed(rng.gen())
+            .era_id(EraId::new(1))
+            .height(2)
+            .deploys(deploys1.iter().chain(deploys2.iter()))
+            .build();
 
         // Only put first set of deploys in `BlockAndDeploys`
         let block_and_deploys = BlockAndDeploys {
@@ -2369,14 +2373,12 @@ mod tests {
         let mut rng = TestRng::new();
 
         let deploys = vec![Deploy::random(&mut rng)];
-        let mut block = Block::random_with_specifics(
-            &mut rng,
-            EraId::new(1),
-            2,
-            ProtocolVersion::V1_0_0,
-            false,
-            deploys.iter(),
-        );
+        let mut block = Block::builder()
+            .seed(rng.gen())
+            .era_id(EraId::new(1))
+            .height(2)
+            .deploys(deploys.iter())
+            .build();
 
         // Invalidate the block.
         block.hash = BlockHash::random(&mut rng);
@@ -2401,14 +2403,12 @@ mod tests {
             .chain(iter::once(bad_deploy.clone()))
             .collect::<Vec<_>>();
 
-        let block = Block::random_with_specifics(
-            &mut rng,
-            EraId::new(1),
-            2,
-            ProtocolVersion::V1_0_0,
-            false,
-            deploys.iter(),
-        );
+        let block = Block::builder()
+            .seed(rng.gen())
+            .era_id(EraId::new(1))
+            .height(2)
+            .deploys(deploys.iter())
+            .build();
 
         let block_and_deploys = BlockAndDeploys { block, deploys };
 
@@ -2444,14 +2444,11 @@ mod tests {
     #[test]
     fn block_headers_batch_id_from_known() {
         let mut rng = TestRng::new();
-        let trusted_block: Block = Block::random_with_specifics(
-            &mut rng,
-            EraId::new(1),
-            100,
-            ProtocolVersion::V1_0_0,
-            false,
-            iter::empty(),
-        );
+        let trusted_block: Block = Block::builder()
+            .seed(rng.gen())
+            .era_id(EraId::new(1))
+            .height(100)
+            .build();
         let trusted_header = trusted_block.take_header();
 
         let batch_size = 10;
@@ -2471,14 +2468,11 @@ mod tests {
             "expect batch towards Genesis"
         );
 
-        let trusted_last = Block::random_with_specifics(
-            &mut rng,
-            EraId::new(0),
-            1,
-            ProtocolVersion::V1_0_0,
-            false,
-            iter::empty(),
-        );
+        let trusted_last = Block::builder()
+            .seed(rng.gen())
+            .era_id(EraId::new(0))
+            .height(1)
+            .build();
 
         let trusted_last_header = trusted_last.take_header();
 
@@ -2540,7 +2534,15 @@ mod tests {
         type Item = Block;
 
         fn next(&mut self) -> Option<Self::Item> {
-            let (is_switch_block, validators) = match &self.switch_block_indices {
+            let builder = Block::builder()
+                .seed(self.rng.gen())
+                .era_id(self.block.header().era_id())
+                .height(self.block.header().height() + 1)
+                .protocol_version(self.block.header().protocol_version())
+                .parent_hash(self.block.id())
+                .parent_seed(self.block.header().accumulated_seed())
+                .state_root_hash(*self.block.header().state_root_hash());
+            let next = match &self.switch_block_indices {
                 Some(switch_block_indices)
                     if switch_block_indices.contains(&self.block.height()) =>
                 {
@@ -2557,26 +2559,10 @@ mod tests {
                         .map(|sk| (PublicKey::from(sk), 100.into()))
                         .collect();
 
-                    (true, Some(validators))
+                    builder.era_end(EraReport::default(), validators).build()
                 }
-                Some(_) | None => (false, None),
+                Some(_) | None => builder.build(),
             };
-
-            let next = Block::new(
-                self.block.id(),
-                self.block.header().accumulated_seed(),
-                *self.block.header().state_root_hash(),
-                FinalizedBlock::random_with_specifics(
-                    &mut self.rng,
-                    self.block.header().era_id(),
-                    self.block.header().height() + 1,
-                    is_switch_block,
-                    iter::empty(),
-                ),
-                validators,
-                self.block.header().protocol_version(),
-            )
-            .unwrap();
             self.block = next.clone();
             Some(next)
         }
@@ -0,0 +1,232 @@
+//! A builder of deterministic blocks for tests.
+
+use std::collections::BTreeMap;
+
+use casper_hashing::Digest;
+use casper_types::{EraId, ProtocolVersion, PublicKey, Timestamp, U512};
+
+use super::{
+    block::EraReport,
+    deploy_builder::{seeded_bytes, seeded_secret_key},
+    Block, BlockHash, BlockPayload, Deploy, DeployWithApprovals, FinalizedBlock,
+};
+
+/// The timestamp of built blocks, unless overridden.
+const DEFAULT_TIMESTAMP: u64 = 1_600_000_000_000;
+
+/// A builder of [`Block`]s.
+///
+/// Every property not explicitly set is either a fixed default or derived from the seed, so the
+/// same builder calls always produce the same block.  By default, the block is an empty,
+/// non-switch block of era 0 at height 0.
+pub struct BlockBuilder {
+    seed: u64,
+    era_id: EraId,
+    height: u64,
+    timestamp: Timestamp,
+    protocol_version: ProtocolVersion,
+    parent_hash: Option<BlockHash>,
+    parent_seed: Option<Digest>,
+    state_root_hash: Option<Digest>,
+    deploys: Vec<DeployWithApprovals>,
+    transfers: Vec<DeployWithApprovals>,
+    random_bit: bool,
+    switch: Option<(EraReport, BTreeMap<PublicKey, U512>)>,
+}
+
+impl Default for BlockBuilder {
+    fn default() -> Self {
+        BlockBuilder {
+            seed: 0,
+            era_id: EraId::from(0),
+            height: 0,
+            timestamp: Timestamp::from(DEFAULT_TIMESTAMP),
+            protocol_version: ProtocolVersion::V1_0_0,
+            parent_hash: None,
+            parent_seed: None,
+            state_root_hash: None,
+            deploys: vec![],
+            transfers: vec![],
+            random_bit: false,
+            switch: None,
+        }
+    }
+}
+
+impl BlockBuilder {
+    /// Sets the seed from which the properties not explicitly set are derived.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the era ID.
+    pub fn era_id(mut self, era_id: EraId) -> Self {
+        self.era_id = era_id;
+        self
+    }
+
+    /// Sets the height.
+    pub fn height(mut self, height: u64) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Sets the timestamp.
+    pub fn timestamp(mut self, timestamp: Timestamp) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Sets the protocol version.
+    pub fn protocol_version(mut self, protocol_version: ProtocolVersion) -> Self {
+        self.protocol_version = protocol_version;
+        self
+    }
+
+    /// Sets the parent block's hash.
+    pub fn parent_hash(mut self, parent_hash: BlockHash) -> Self {
+        self.parent_hash = Some(parent_hash);
+        self
+    }
+
+    /// Sets the parent block's accumulated seed.
+    pub fn parent_seed(mut self, parent_seed: Digest) -> Self {
+        self.parent_seed = Some(parent_seed);
+        self
+    }
+
+    /// Sets the global state root hash after executing the block.
+    pub fn state_root_hash(mut self, state_root_hash: Digest) -> Self {
+        self.state_root_hash = Some(state_root_hash);
+        self
+    }
+
+    /// Sets the deploys, other than native transfers, included in the block.
+    pub fn deploys<'a, I: IntoIterator<Item = &'a Deploy>>(mut self, deploys: I) -> Self {
+        self.deploys = deploys.into_iter().map(DeployWithApprovals::from).collect();
+        self
+    }
+
+    /// Sets the native transfers included in the block.
+    pub fn transfers<'a, I: IntoIterator<Item = &'a Deploy>>(mut self, transfers: I) -> Self {
+        self.transfers = transfers
+            .into_iter()
+            .map(DeployWithApprovals::from)
+            .collect();
+        self
+    }
+
+    /// Sets the random bit.
+    pub fn random_bit(mut self, random_bit: bool) -> Self {
+        self.random_bit = random_bit;
+        self
+    }
+
+    /// Makes the block a switch block with an empty era report and no validators for the next
+    /// era.
+    pub fn switch_block(self) -> Self {
+        self.era_end(EraReport::default(), BTreeMap::new())
+    }
+
+    /// Makes the block a switch block with the given era report and validators for the next era.
+    pub fn era_end(
+        mut self,
+        era_report: EraReport,
+        next_era_validator_weights: BTreeMap<PublicKey, U512>,
+    ) -> Self {
+        self.switch = Some((era_report, next_era_validator_weights));
+        self
+    }
+
+    /// Builds the block.
+    pub fn build(self) -> Block {
+        let BlockBuilder {
+            seed,
+            era_id,
+            height,
+            timestamp,
+            protocol_version,
+            parent_hash,
+            parent_seed,
+            state_root_hash,
+            deploys,
+            transfers,
+            random_bit,
+            switch,
+        } = self;
+        let parent_hash = parent_hash
+            .unwrap_or_else(|| BlockHash::new(Digest::from(seeded_bytes(seed, "parent_hash"))));
+        let state_root_hash =
+            state_root_hash.unwrap_or_else(|| Digest::from(seeded_bytes(seed, "state_root_hash")));
+        let parent_seed =
+            parent_seed.unwrap_or_else(|| Digest::from(seeded_bytes(seed, "parent_seed")));
+        let proposer = PublicKey::from(&seeded_secret_key(seed, "proposer"));
+        let (era_report, next_era_validator_weights) = match switch {
+            Some((era_report, next_era_validator_weights)) => {
+                (Some(era_report), Some(next_era_validator_weights))
+            }
+            None => (None, None),
+        };
+
+        let block_payload = BlockPayload::new(deploys, transfers, vec![], random_bit);
+        let finalized_block = FinalizedBlock::new(
+            block_payload,
+            era_report,
+            timestamp,
+            era_id,
+            height,
+            proposer,
+        );
+        Block::new(
+            parent_hash,
+            parent_seed,
+            state_root_hash,
+            finalized_block,
+            next_era_validator_weights,
+            protocol_version,
+        )
+        .expect("should build block")
+    }
+}
+
+impl Block {
+    /// Returns a builder of deterministic blocks.
+    pub fn builder() -> BlockBuilder {
+        BlockBuilder::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_build_same_block_from_same_seed() {
+        let deploy = Deploy::builder().build();
+        let build = |seed| {
+            Block::builder()
+                .seed(seed)
+                .era_id(EraId::from(2))
+                .height(21)
+                .transfers(&[deploy.clone()])
+                .build()
+        };
+
+        let block = build(3);
+        assert_eq!(block, build(3));
+        assert_ne!(block.hash(), build(4).hash());
+        assert!(block.verify().is_ok());
+        assert_eq!(block.height(), 21);
+        assert_eq!(block.header().era_id(), EraId::from(2));
+        assert_eq!(block.transfer_hashes(), &vec![*deploy.id()]);
+        assert!(!block.header().is_switch_block());
+    }
+
+    #[test]
+    fn should_build_switch_block() {
+        let block = Block::builder().switch_block().build();
+        assert!(block.header().is_switch_block());
+        assert!(block.verify().is_ok());
+    }
+}
@@ -0,0 +1,117 @@
+use std::fmt::{self, Display, Formatter};
+
+use datasize::DataSize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The height of a block, i.e. the number of its ancestors.
+///
+/// Used in place of a raw `u64` in component APIs so a height cannot be mixed up with another
+/// integer, e.g. an era ID or a count of blocks.
+#[derive(
+    Copy,
+    Clone,
+    Default,
+    PartialOrd,
+    Ord,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    DataSize,
+    JsonSchema,
+)]
+#[serde(transparent)]
+pub struct BlockHeight(u64);
+
+impl BlockHeight {
+    /// The height of the genesis block.
+    pub const GENESIS: BlockHeight = BlockHeight(0);
+
+    /// Constructs a new `BlockHeight`.
+    pub const fn new(height: u64) -> Self {
+        BlockHeight(height)
+    }
+
+    /// Returns the raw value.
+    pub fn value(self) -> u64 {
+        self.0
+    }
+
+    /// Returns the height of the next block.
+    ///
+    /// For `u64::MAX`, this returns `u64::MAX` again, a height which will never be reached in
+    /// practice.
+    #[must_use]
+    pub fn successor(self) -> Self {
+        BlockHeight(self.0.saturating_add(1))
+    }
+
+    /// Returns the height of the parent block, or `None` for the genesis block.
+    pub fn predecessor(self) -> Option<Self> {
+        self.0.checked_sub(1).map(BlockHeight)
+    }
+
+    /// Returns the height `count` blocks lower, or the genesis height if that would be less than
+    /// `0`.
+    #[must_use]
+    pub fn saturating_sub(self, count: u64) -> Self {
+        BlockHeight(self.0.saturating_sub(count))
+    }
+
+    /// Returns an iterator over the heights from `start` up to and including `end`.
+    ///
+    /// The iterator is empty if `end` is lower than `start`.
+    pub fn range_inclusive(start: BlockHeight, end: BlockHeight) -> impl Iterator<Item = Self> {
+        (start.0..=end.0).map(BlockHeight)
+    }
+}
+
+impl Display for BlockHeight {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, formatter)
+    }
+}
+
+impl From<u64> for BlockHeight {
+    fn from(height: u64) -> Self {
+        BlockHeight(height)
+    }
+}
+
+impl From<BlockHeight> for u64 {
+    fn from(height: BlockHeight) -> Self {
+        height.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_step_between_heights() {
+        let height = BlockHeight::new(7);
+        assert_eq!(height.successor(), BlockHeight::new(8));
+        assert_eq!(height.predecessor(), Some(BlockHeight::new(6)));
+        assert_eq!(BlockHeight::GENESIS.predecessor(), None);
+        assert_eq!(height.saturating_sub(10), BlockHeight::GENESIS);
+        assert_eq!(BlockHeight::new(u64::MAX).successor().value(), u64::MAX);
+
+        let heights: Vec<_> = BlockHeight::range_inclusive(height, height.successor()).collect();
+        assert_eq!(heights, vec![height, height.successor()]);
+        assert_eq!(
+            BlockHeight::range_inclusive(height.successor(), height).count(),
+            0
+        );
+    }
+
+    #[test]
+    fn should_serialize_as_raw_height() {
+        let height = BlockHeight::new(42);
+        assert_eq!(serde_json::to_string(&height).unwrap(), "42");
+        assert_eq!(height.to_string(), "42");
+    }
+}
@@ -0,0 +1,199 @@
+//! Canonical JSON encoding of the types whose hashes are part of the protocol.
+//!
+//! The canonical encoding of a value is its regular JSON representation, wrapped in an envelope
+//! naming its type and the version of the encoding:
+//!
+//! ```json
+//! {"type":"Deploy","value":{...},"version":1}
+//! ```
+//!
+//! It is fully determined by the value, so implementations in other languages can reproduce it
+//! byte-for-byte:
+//!
+//! * object keys are sorted by their UTF-8 bytes,
+//! * there is no whitespace outside of strings,
+//! * strings only escape `"`, `\` and control characters, all other characters are written as
+//!   UTF-8,
+//! * integers are written in decimal, while 128-bit and larger integers and byte arrays are
+//!   written as strings, as in the regular JSON representation.
+//!
+//! The hashes embedded in the values (e.g. a deploy's hash and body hash) are computed over their
+//! `bytesrepr` encoding, which can be reconstructed from the canonical JSON.  The golden vectors in
+//! `resources/test/canonical_json` pin both encodings.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::{Block, BlockBody, BlockHeader, Deploy, DeployHeader};
+
+/// The version of the canonical JSON encoding.
+///
+/// Must be incremented whenever the JSON representation of any of the types implementing
+/// [`CanonicalJson`] changes.
+pub const CANONICAL_JSON_VERSION: u32 = 1;
+
+/// A type with a canonical JSON encoding.
+pub trait CanonicalJson: Serialize {
+    /// The name of the type in the envelope of its canonical encoding.
+    const TYPE_NAME: &'static str;
+
+    /// Returns the canonical JSON encoding of `self`.
+    fn to_canonical_json(&self) -> Result<String, serde_json::Error> {
+        let envelope = Envelope {
+            r#type: Self::TYPE_NAME,
+            value: self,
+            version: CANONICAL_JSON_VERSION,
+        };
+        let mut output = String::new();
+        write_canonical(&serde_json::to_value(&envelope)?, &mut output);
+        Ok(output)
+    }
+}
+
+impl CanonicalJson for Deploy {
+    const TYPE_NAME: &'static str = "Deploy";
+}
+
+impl CanonicalJson for DeployHeader {
+    const TYPE_NAME: &'static str = "DeployHeader";
+}
+
+impl CanonicalJson for Block {
+    const TYPE_NAME: &'static str = "Block";
+}
+
+impl CanonicalJson for BlockHeader {
+    const TYPE_NAME: &'static str = "BlockHeader";
+}
+
+impl CanonicalJson for BlockBody {
+    const TYPE_NAME: &'static str = "BlockBody";
+}
+
+/// The envelope of a canonically encoded value.
+#[derive(Serialize)]
+struct Envelope<'a, T: ?Sized> {
+    r#type: &'static str,
+    value: &'a T,
+    version: u32,
+}
+
+/// Appends the canonical encoding of `value` to `output`.
+///
+/// Object keys are sorted explicitly, since the order of `serde_json`'s maps depends on whether
+/// any crate in the build enables its `preserve_order` feature.
+fn write_canonical(value: &Value, output: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_unstable_by(|(key1, _), (key2, _)| key1.cmp(key2));
+            output.push('{');
+            for (index, (key, value)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    output.push(',');
+                }
+                output.push_str(&Value::String(key.clone()).to_string());
+                output.push(':');
+                write_canonical(value, output);
+            }
+            output.push('}');
+        }
+        Value::Array(array) => {
+            output.push('[');
+            for (index, value) in array.iter().enumerate() {
+                if index > 0 {
+                    output.push(',');
+                }
+                write_canonical(value, output);
+            }
+            output.push(']');
+        }
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => {
+            output.push_str(&value.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use serde::de::DeserializeOwned;
+
+    use casper_hashing::Digest;
+
+    use super::*;
+    use crate::types::{BlockHash, DeployHash};
+
+    /// Reads the golden vector `name` and checks `T` decodes it to a value whose canonical
+    /// encoding is unchanged.
+    fn check_golden_vector<T: CanonicalJson + DeserializeOwned>(name: &str) -> T {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources/test/canonical_json")
+            .join(format!("{}.json", name));
+        let golden = fs::read_to_string(&path).unwrap();
+        let golden = golden.trim_end();
+
+        let mut envelope: Value = serde_json::from_str(golden).unwrap();
+        assert_eq!(envelope["type"], T::TYPE_NAME);
+        assert_eq!(envelope["version"], CANONICAL_JSON_VERSION);
+        let value: T = serde_json::from_value(envelope["value"].take()).unwrap();
+        assert_eq!(
+            value.to_canonical_json().unwrap(),
+            golden,
+            "canonical encoding of {} has drifted",
+            T::TYPE_NAME
+        );
+        value
+    }
+
+    fn digest(hex: &str) -> Digest {
+        Digest::from_hex(hex).unwrap()
+    }
+
+    #[test]
+    fn should_match_deploy_golden_vectors() {
+        let deploy: Deploy = check_golden_vector("deploy");
+        let header: DeployHeader = check_golden_vector("deploy_header");
+        assert_eq!(deploy.header(), &header);
+
+        assert_eq!(deploy.is_valid(), Ok(()));
+        assert_eq!(
+            *deploy.id(),
+            DeployHash::new(digest(
+                "97c03ada9259eff9c4a4419f36b665743fcee6a367a2a1584165bd2835c89954"
+            ))
+        );
+        assert_eq!(
+            *header.body_hash(),
+            digest("d8d9a8cb94258d57b89595e6d59ed1c19a62651b55cfde30c8d17398a054c091")
+        );
+    }
+
+    #[test]
+    fn should_match_block_golden_vectors() {
+        let block: Block = check_golden_vector("block");
+        let header: BlockHeader = check_golden_vector("block_header");
+        assert_eq!(block.header(), &header);
+
+        assert!(block.verify().is_ok());
+        assert_eq!(
+            *block.hash(),
+            BlockHash::new(digest(
+                "78e141751cd66373462dd30a8a56259978cc99dd9028008b891fcf7abde77271"
+            ))
+        );
+        assert_eq!(
+            *header.body_hash(),
+            digest("b0920fa6f576193a49e995ef6173b3a4329de43062b1de229ca724c3b3fb9059")
+        );
+    }
+
+    #[test]
+    fn should_sort_keys_and_omit_whitespace() {
+        let value = serde_json::json!({ "b": [1, { "d": null, "c": "\u{e9}\n" }], "a": true });
+        let mut output = String::new();
+        write_canonical(&value, &mut output);
+        assert_eq!(output, r#"{"a":true,"b":[1,{"c":"é\n","d":null}]}"#);
+    }
+}
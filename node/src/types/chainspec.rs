@@ -4,16 +4,20 @@
 mod accounts_config;
 mod activation_point;
 mod chainspec_raw_bytes;
+mod checkpoint;
+mod commit_rules_config;
 mod core_config;
 mod deploy_config;
+mod emergency_restart;
 mod error;
 mod global_state_update;
+mod governance_config;
 mod highway_config;
 mod network_config;
 mod parse_toml;
 mod protocol_config;
 
-use std::{fmt::Debug, path::Path};
+use std::{collections::BTreeSet, fmt::Debug, path::Path};
 
 use datasize::DataSize;
 #[cfg(test)]
@@ -22,8 +26,11 @@ use serde::Serialize;
 use tracing::{error, warn};
 
 use casper_execution_engine::{
-    core::engine_state::genesis::ExecConfig,
-    shared::{system_config::SystemConfig, wasm_config::WasmConfig},
+    core::engine_state::{genesis::ExecConfig, EngineConfig},
+    shared::{
+        system_config::SystemConfig,
+        wasm_config::{WasmConfig, MAX_WASM_MEMORY_PAGES},
+    },
 };
 use casper_hashing::{ChunkWithProof, Digest};
 #[cfg(test)]
@@ -38,11 +45,13 @@ pub(crate) use self::accounts_config::{AccountConfig, ValidatorConfig};
 pub use self::error::Error;
 pub(crate) use self::{
     accounts_config::AccountsConfig, activation_point::ActivationPoint,
-    chainspec_raw_bytes::ChainspecRawBytes, core_config::CoreConfig, deploy_config::DeployConfig,
-    global_state_update::GlobalStateUpdate, highway_config::HighwayConfig,
+    chainspec_raw_bytes::ChainspecRawBytes, checkpoint::Checkpoint,
+    commit_rules_config::CommitRulesConfig, core_config::CoreConfig, deploy_config::DeployConfig,
+    emergency_restart::EmergencyRestart, global_state_update::GlobalStateUpdate,
+    governance_config::GovernanceConfig, highway_config::HighwayConfig,
     network_config::NetworkConfig, protocol_config::ProtocolConfig,
 };
-use crate::utils::Loadable;
+use crate::{types::BlockHeader, utils::Loadable};
 
 /// The name of the chainspec file on disk.
 pub const CHAINSPEC_FILENAME: &str = "chainspec.toml";
@@ -65,6 +74,12 @@ pub struct Chainspec {
     pub(crate) wasm_config: WasmConfig,
     #[serde(rename = "system_costs")]
     pub(crate) system_costs_config: SystemConfig,
+    #[serde(rename = "commit_rules")]
+    pub(crate) commit_rules_config: CommitRulesConfig,
+    #[serde(rename = "governance")]
+    pub(crate) governance_config: GovernanceConfig,
+    /// Blocks through which any chain synchronized by the node must pass.
+    pub(crate) checkpoints: Vec<Checkpoint>,
 }
 
 impl Chainspec {
@@ -98,7 +113,63 @@ impl Chainspec {
             warn!("era duration is less than minimum era height * round length!");
         }
 
-        self.protocol_config.is_valid() && self.highway_config.is_valid()
+        self.protocol_config.is_valid()
+            && self.highway_config.is_valid()
+            && self.core_config.is_valid()
+            && self.is_wasm_config_valid()
+            && self.commit_rules_config.is_valid()
+            && self.governance_config.is_valid()
+            && self.are_checkpoints_valid()
+            && self
+                .protocol_config
+                .emergency_restart
+                .as_ref()
+                .map_or(true, |emergency_restart| {
+                    emergency_restart.is_valid(self.core_config.validator_slots)
+                })
+    }
+
+    /// Checks that no two checkpoints are at the same height.
+    fn are_checkpoints_valid(&self) -> bool {
+        let mut heights = BTreeSet::new();
+        for checkpoint in &self.checkpoints {
+            if !heights.insert(checkpoint.height) {
+                error!(
+                    height = checkpoint.height,
+                    "multiple checkpoints at the same height"
+                );
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Returns the checkpoint which `block_header` conflicts with, if any.
+    pub(crate) fn conflicting_checkpoint(&self, block_header: &BlockHeader) -> Option<&Checkpoint> {
+        self.checkpoints
+            .iter()
+            .find(|checkpoint| checkpoint.conflicts_with(block_header))
+    }
+
+    /// Checks that the execution limits set in the Wasm config can be enforced by the execution
+    /// engine.
+    fn is_wasm_config_valid(&self) -> bool {
+        let max_memory = self.wasm_config.max_memory;
+        if max_memory == 0 || max_memory > MAX_WASM_MEMORY_PAGES {
+            error!(
+                %max_memory,
+                "wasm max memory is not in the range [1, {}] pages", MAX_WASM_MEMORY_PAGES,
+            );
+            return false;
+        }
+
+        if self.wasm_config.max_stack_height == 0 {
+            error!("wasm max stack height must be greater than zero");
+            return false;
+        }
+
+        true
     }
 
     /// Serializes `self` and hashes the resulting bytes.
@@ -119,6 +190,24 @@ impl Chainspec {
     pub(crate) fn protocol_version(&self) -> ProtocolVersion {
         self.protocol_config.version
     }
+
+    /// Returns the execution engine config set by the chainspec, with the given maximum depth of
+    /// global state queries.
+    pub(crate) fn engine_config(&self, max_query_depth: u64) -> EngineConfig {
+        EngineConfig::new(
+            max_query_depth,
+            self.core_config.max_associated_keys,
+            self.core_config.max_runtime_call_stack_height,
+            self.core_config.minimum_delegation_amount,
+            self.core_config.strict_argument_checking,
+            self.core_config.vesting_schedule_period.millis(),
+            self.core_config.allow_separate_reward_purses,
+            self.core_config.refund_handling,
+            self.core_config.fee_handling,
+            self.wasm_config,
+            self.system_costs_config,
+        )
+    }
 }
 
 #[cfg(test)]
@@ -132,6 +221,11 @@ impl Chainspec {
         let deploy_config = DeployConfig::random(rng);
         let wasm_costs_config = rng.gen();
         let system_costs_config = rng.gen();
+        let commit_rules_config = CommitRulesConfig::random(rng);
+        let governance_config = GovernanceConfig::random(rng);
+        let checkpoints = (0..rng.gen_range(0..3))
+            .map(|_| Checkpoint::random(rng))
+            .collect();
 
         Chainspec {
             protocol_config,
@@ -141,6 +235,9 @@ impl Chainspec {
             deploy_config,
             wasm_config: wasm_costs_config,
             system_costs_config,
+            commit_rules_config,
+            governance_config,
+            checkpoints,
         }
     }
 }
@@ -155,6 +252,9 @@ impl ToBytes for Chainspec {
         buffer.extend(self.deploy_config.to_bytes()?);
         buffer.extend(self.wasm_config.to_bytes()?);
         buffer.extend(self.system_costs_config.to_bytes()?);
+        buffer.extend(self.commit_rules_config.to_bytes()?);
+        buffer.extend(self.governance_config.to_bytes()?);
+        buffer.extend(self.checkpoints.to_bytes()?);
         Ok(buffer)
     }
 
@@ -166,6 +266,9 @@ impl ToBytes for Chainspec {
             + self.deploy_config.serialized_length()
             + self.wasm_config.serialized_length()
             + self.system_costs_config.serialized_length()
+            + self.commit_rules_config.serialized_length()
+            + self.governance_config.serialized_length()
+            + self.checkpoints.serialized_length()
     }
 }
 
@@ -178,6 +281,9 @@ impl FromBytes for Chainspec {
         let (deploy_config, remainder) = DeployConfig::from_bytes(remainder)?;
         let (wasm_config, remainder) = WasmConfig::from_bytes(remainder)?;
         let (system_costs_config, remainder) = SystemConfig::from_bytes(remainder)?;
+        let (commit_rules_config, remainder) = CommitRulesConfig::from_bytes(remainder)?;
+        let (governance_config, remainder) = GovernanceConfig::from_bytes(remainder)?;
+        let (checkpoints, remainder) = Vec::<Checkpoint>::from_bytes(remainder)?;
         let chainspec = Chainspec {
             protocol_config,
             network_config,
@@ -186,6 +292,9 @@ impl FromBytes for Chainspec {
             deploy_config,
             wasm_config,
             system_costs_config,
+            commit_rules_config,
+            governance_config,
+            checkpoints,
         };
         Ok((chainspec, remainder))
     }
@@ -282,6 +391,8 @@ mod tests {
             print: HostFunction::new(123, [0, 1]),
             blake2b: HostFunction::new(133, [0, 1, 2, 3]),
             random_bytes: HostFunction::new(123, [0, 1]),
+            // Absent from the chainspecs under test, which predate it.
+            emit_event: HostFunctionCosts::default().emit_event,
         });
     static EXPECTED_GENESIS_WASM_COSTS: Lazy<WasmConfig> = Lazy::new(|| {
         WasmConfig::new(
@@ -359,6 +470,9 @@ mod tests {
         }
 
         assert_eq!(spec.network_config.name, "test-chain");
+        assert!(spec.commit_rules_config.reserved_key_prefixes.is_empty());
+        assert!(!spec.governance_config.enabled);
+        assert!(spec.checkpoints.is_empty());
 
         assert_eq!(spec.core_config.era_duration, TimeDiff::from(180000));
         assert_eq!(spec.core_config.minimum_era_height, 9);
@@ -402,6 +516,27 @@ mod tests {
         bytesrepr::test_serialization_roundtrip(&chainspec);
     }
 
+    #[test]
+    fn should_validate_execution_limits() {
+        let (mut chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_resources("local");
+        assert!(chainspec.is_valid());
+
+        chainspec.wasm_config.max_memory = MAX_WASM_MEMORY_PAGES;
+        assert!(chainspec.is_valid());
+        chainspec.wasm_config.max_memory = MAX_WASM_MEMORY_PAGES + 1;
+        assert!(!chainspec.is_valid());
+        chainspec.wasm_config.max_memory = 0;
+        assert!(!chainspec.is_valid());
+        chainspec.wasm_config.max_memory = 64;
+
+        chainspec.wasm_config.max_stack_height = 0;
+        assert!(!chainspec.is_valid());
+        chainspec.wasm_config.max_stack_height = 188;
+
+        chainspec.core_config.max_runtime_call_stack_height = 0;
+        assert!(!chainspec.is_valid());
+    }
+
     #[ignore = "We probably need to reconsider our approach here"]
     #[test]
     fn should_have_deterministic_chainspec_hash() {
@@ -0,0 +1,113 @@
+use std::fmt::{self, Display, Formatter};
+
+use datasize::DataSize;
+#[cfg(test)]
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use casper_types::bytesrepr::{self, FromBytes, ToBytes};
+#[cfg(test)]
+use casper_types::testing::TestRng;
+
+use crate::types::{BlockHash, BlockHeader};
+
+/// A block known to be part of the chain, through which any chain synchronized by the node must
+/// pass.
+#[derive(Copy, Clone, DataSize, PartialEq, Eq, Serialize, Deserialize, Debug)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub(crate) struct Checkpoint {
+    /// The height of the block.
+    pub(crate) height: u64,
+    /// The hash of the block.
+    pub(crate) block_hash: BlockHash,
+}
+
+impl Checkpoint {
+    /// Returns `true` if `block_header` is at the height of this checkpoint but has a different
+    /// hash, i.e. it is part of a chain which diverges from the checkpointed one.
+    pub(crate) fn conflicts_with(&self, block_header: &BlockHeader) -> bool {
+        block_header.height() == self.height && block_header.hash() != self.block_hash
+    }
+}
+
+impl Display for Checkpoint {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "checkpoint at height {}: {}",
+            self.height, self.block_hash
+        )
+    }
+}
+
+#[cfg(test)]
+impl Checkpoint {
+    /// Generates a random instance using a `TestRng`.
+    pub fn random(rng: &mut TestRng) -> Self {
+        Checkpoint {
+            height: rng.gen(),
+            block_hash: BlockHash::random(rng),
+        }
+    }
+}
+
+impl ToBytes for Checkpoint {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.extend(self.height.to_bytes()?);
+        buffer.extend(self.block_hash.to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.height.serialized_length() + self.block_hash.serialized_length()
+    }
+}
+
+impl FromBytes for Checkpoint {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (height, remainder) = u64::from_bytes(bytes)?;
+        let (block_hash, remainder) = BlockHash::from_bytes(remainder)?;
+        let checkpoint = Checkpoint { height, block_hash };
+        Ok((checkpoint, remainder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Block;
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        let mut rng = crate::new_rng();
+        let checkpoint = Checkpoint::random(&mut rng);
+        bytesrepr::test_serialization_roundtrip(&checkpoint);
+    }
+
+    #[test]
+    fn should_detect_conflicting_block_headers() {
+        let mut rng = crate::new_rng();
+        let block = Block::random(&mut rng);
+        let checkpoint = Checkpoint {
+            height: block.height(),
+            block_hash: *block.hash(),
+        };
+        assert!(!checkpoint.conflicts_with(block.header()));
+
+        let other_block = Block::random(&mut rng);
+        let conflicting_checkpoint = Checkpoint {
+            height: other_block.height(),
+            ..checkpoint
+        };
+        assert!(conflicting_checkpoint.conflicts_with(other_block.header()));
+
+        // Blocks at other heights don't conflict.
+        let other_height_checkpoint = Checkpoint {
+            height: other_block.height().wrapping_add(1),
+            ..checkpoint
+        };
+        assert!(!other_height_checkpoint.conflicts_with(other_block.header()));
+    }
+}
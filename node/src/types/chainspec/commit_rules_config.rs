@@ -0,0 +1,99 @@
+use datasize::DataSize;
+#[cfg(test)]
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+use casper_types::bytesrepr::{self, FromBytes, ToBytes};
+#[cfg(test)]
+use casper_types::testing::TestRng;
+
+/// The rules which the effects of each deploy's session code must satisfy before they are committed
+/// to global state.
+#[derive(Clone, Default, DataSize, PartialEq, Eq, Serialize, Deserialize, Debug)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub(crate) struct CommitRulesConfig {
+    /// Session code may not write to any key whose formatted string form starts with one of these
+    /// prefixes, e.g. "hash-ff" reserves all hash keys whose first byte is 0xff.  Keys only written
+    /// by the system contracts are exempt.
+    #[serde(default)]
+    pub(crate) reserved_key_prefixes: Vec<String>,
+}
+
+impl CommitRulesConfig {
+    /// Checks whether the values set in the config make sense and returns `false` if they don't.
+    pub(super) fn is_valid(&self) -> bool {
+        if self.reserved_key_prefixes.iter().any(String::is_empty) {
+            error!("reserved key prefixes must not be empty");
+            return false;
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+impl CommitRulesConfig {
+    /// Generates a random instance using a `TestRng`.
+    pub fn random(rng: &mut TestRng) -> Self {
+        let reserved_key_prefixes = (0..rng.gen_range(0..3))
+            .map(|_| format!("hash-{:02x}", rng.gen::<u8>()))
+            .collect();
+        CommitRulesConfig {
+            reserved_key_prefixes,
+        }
+    }
+}
+
+impl ToBytes for CommitRulesConfig {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.reserved_key_prefixes.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.reserved_key_prefixes.serialized_length()
+    }
+}
+
+impl FromBytes for CommitRulesConfig {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (reserved_key_prefixes, remainder) = Vec::<String>::from_bytes(bytes)?;
+        let config = CommitRulesConfig {
+            reserved_key_prefixes,
+        };
+        Ok((config, remainder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        let mut rng = crate::new_rng();
+        let config = CommitRulesConfig::random(&mut rng);
+        bytesrepr::test_serialization_roundtrip(&config);
+    }
+
+    #[test]
+    fn toml_roundtrip() {
+        let mut rng = crate::new_rng();
+        let config = CommitRulesConfig::random(&mut rng);
+        let encoded = toml::to_string_pretty(&config).unwrap();
+        let decoded = toml::from_str(&encoded).unwrap();
+        assert_eq!(config, decoded);
+    }
+
+    #[test]
+    fn should_reject_empty_reserved_key_prefix() {
+        let mut config = CommitRulesConfig {
+            reserved_key_prefixes: vec!["hash-ff".to_string()],
+        };
+        assert!(config.is_valid());
+
+        config.reserved_key_prefixes.push(String::new());
+        assert!(!config.is_valid());
+    }
+}
@@ -6,6 +6,9 @@ use serde::{Deserialize, Serialize};
 
 #[cfg(test)]
 use casper_types::testing::TestRng;
+use tracing::error;
+
+use casper_execution_engine::shared::fee_handling::{FeeHandling, RefundHandling};
 use casper_types::{
     bytesrepr::{self, FromBytes, ToBytes},
     TimeDiff,
@@ -39,6 +42,36 @@ pub struct CoreConfig {
     pub(crate) minimum_delegation_amount: u64,
     /// Enables strict arguments checking when calling a contract.
     pub(crate) strict_argument_checking: bool,
+    /// Allows validators to have their rewards paid into a purse designated under the
+    /// `reward_purse` named key of their account, rather than reinvested into their stake.
+    #[serde(default)]
+    pub(crate) allow_separate_reward_purses: bool,
+    /// How the unspent part of a deploy's payment is handled.
+    #[serde(default)]
+    pub(crate) refund_handling: RefundHandling,
+    /// How the fees paid for executing a deploy are handled.
+    #[serde(default)]
+    pub(crate) fee_handling: FeeHandling,
+}
+
+impl CoreConfig {
+    /// Checks whether the values set in the config make sense and returns `false` if they don't.
+    pub(super) fn is_valid(&self) -> bool {
+        if self.max_runtime_call_stack_height == 0 {
+            error!("max runtime call stack height must be greater than zero");
+            return false;
+        }
+
+        if !self.refund_handling.is_valid() {
+            error!(
+                refund_handling = ?self.refund_handling,
+                "refund ratio must have a non-zero denominator and must not exceed one"
+            );
+            return false;
+        }
+
+        true
+    }
 }
 
 #[cfg(test)]
@@ -60,6 +93,9 @@ impl CoreConfig {
         let max_runtime_call_stack_height = rng.gen();
         let minimum_delegation_amount = rng.gen::<u32>() as u64;
         let strict_argument_checking = rng.gen();
+        let allow_separate_reward_purses = rng.gen();
+        let refund_handling = rng.gen();
+        let fee_handling = rng.gen();
 
         CoreConfig {
             era_duration,
@@ -74,6 +110,9 @@ impl CoreConfig {
             max_runtime_call_stack_height,
             minimum_delegation_amount,
             strict_argument_checking,
+            allow_separate_reward_purses,
+            refund_handling,
+            fee_handling,
         }
     }
 }
@@ -93,6 +132,9 @@ impl ToBytes for CoreConfig {
         buffer.extend(self.max_runtime_call_stack_height.to_bytes()?);
         buffer.extend(self.minimum_delegation_amount.to_bytes()?);
         buffer.extend(self.strict_argument_checking.to_bytes()?);
+        buffer.extend(self.allow_separate_reward_purses.to_bytes()?);
+        buffer.extend(self.refund_handling.to_bytes()?);
+        buffer.extend(self.fee_handling.to_bytes()?);
         Ok(buffer)
     }
 
@@ -109,6 +151,9 @@ impl ToBytes for CoreConfig {
             + self.max_runtime_call_stack_height.serialized_length()
             + self.minimum_delegation_amount.serialized_length()
             + self.strict_argument_checking.serialized_length()
+            + self.allow_separate_reward_purses.serialized_length()
+            + self.refund_handling.serialized_length()
+            + self.fee_handling.serialized_length()
     }
 }
 
@@ -126,6 +171,9 @@ impl FromBytes for CoreConfig {
         let (max_runtime_call_stack_height, remainder) = u32::from_bytes(remainder)?;
         let (minimum_delegation_amount, remainder) = u64::from_bytes(remainder)?;
         let (strict_argument_checking, remainder) = bool::from_bytes(remainder)?;
+        let (allow_separate_reward_purses, remainder) = bool::from_bytes(remainder)?;
+        let (refund_handling, remainder) = RefundHandling::from_bytes(remainder)?;
+        let (fee_handling, remainder) = FeeHandling::from_bytes(remainder)?;
         let config = CoreConfig {
             era_duration,
             minimum_era_height,
@@ -139,6 +187,9 @@ impl FromBytes for CoreConfig {
             max_runtime_call_stack_height,
             minimum_delegation_amount,
             strict_argument_checking,
+            allow_separate_reward_purses,
+            refund_handling,
+            fee_handling,
         };
         Ok((config, remainder))
     }
@@ -163,4 +214,47 @@ mod tests {
         let decoded = toml::from_str(&encoded).unwrap();
         assert_eq!(config, decoded);
     }
+
+    #[test]
+    fn should_not_allow_separate_reward_purses_by_default() {
+        let mut rng = crate::new_rng();
+        let mut config = CoreConfig::random(&mut rng);
+        config.allow_separate_reward_purses = true;
+        let encoded = toml::to_string_pretty(&config).unwrap();
+        let without_option = encoded
+            .lines()
+            .filter(|line| !line.starts_with("allow_separate_reward_purses"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let decoded: CoreConfig = toml::from_str(&without_option).unwrap();
+        assert!(!decoded.allow_separate_reward_purses);
+    }
+
+    #[test]
+    fn should_validate_max_runtime_call_stack_height() {
+        let mut rng = crate::new_rng();
+        let mut config = CoreConfig::random(&mut rng);
+
+        config.max_runtime_call_stack_height = 1;
+        assert!(config.is_valid());
+
+        config.max_runtime_call_stack_height = 0;
+        assert!(!config.is_valid());
+    }
+
+    #[test]
+    fn should_validate_refund_ratio() {
+        let mut rng = crate::new_rng();
+        let mut config = CoreConfig::random(&mut rng);
+
+        config.refund_handling = RefundHandling::Refund {
+            refund_ratio: Ratio::new(1, 1),
+        };
+        assert!(config.is_valid());
+
+        config.refund_handling = RefundHandling::Refund {
+            refund_ratio: Ratio::new(3, 2),
+        };
+        assert!(!config.is_valid());
+    }
 }
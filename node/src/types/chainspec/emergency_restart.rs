@@ -0,0 +1,154 @@
+use std::collections::BTreeMap;
+
+use datasize::DataSize;
+#[cfg(test)]
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+use casper_hashing::Digest;
+#[cfg(test)]
+use casper_types::testing::TestRng;
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    PublicKey, U512,
+};
+
+/// A restart of the network agreed on outside of consensus, e.g. to recover from a catastrophic
+/// bug.
+///
+/// Rather than continuing from the global state of the last block before the activation point,
+/// the network continues from a designated global state, and the validators of the era following
+/// the immediate switch block must be exactly the designated ones.
+#[derive(Clone, DataSize, PartialEq, Eq, Serialize, Deserialize, Debug)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub(crate) struct EmergencyRestart {
+    /// The state root hash of the global state from which the network restarts.
+    pub(crate) state_root_hash: Digest,
+    /// The validators with which the network restarts, and their weights.
+    #[data_size(skip)]
+    pub(crate) validators: BTreeMap<PublicKey, U512>,
+}
+
+impl EmergencyRestart {
+    /// Returns `false` and logs errors if the values set in the config don't make sense, given
+    /// the maximum number of validators.
+    pub(super) fn is_valid(&self, validator_slots: u32) -> bool {
+        if self.validators.is_empty() {
+            error!("emergency restart must specify at least one validator");
+            return false;
+        }
+
+        if self.validators.len() > validator_slots as usize {
+            error!(
+                validator_count = self.validators.len(),
+                validator_slots, "emergency restart specifies more validators than there are slots"
+            );
+            return false;
+        }
+
+        if let Some((public_key, _)) = self
+            .validators
+            .iter()
+            .find(|(public_key, weight)| **public_key == PublicKey::System || weight.is_zero())
+        {
+            error!(
+                %public_key,
+                "emergency restart validator must be a non-system key with non-zero weight"
+            );
+            return false;
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+impl EmergencyRestart {
+    /// Generates a random instance using a `TestRng`.
+    pub fn random(rng: &mut TestRng) -> Self {
+        let validators = (0..rng.gen_range(1..5))
+            .map(|_| {
+                (
+                    PublicKey::random(rng),
+                    U512::from(rng.gen_range(1..1_000_000_u64)),
+                )
+            })
+            .collect();
+        EmergencyRestart {
+            state_root_hash: Digest::hash(rng.gen::<[u8; 32]>()),
+            validators,
+        }
+    }
+}
+
+impl ToBytes for EmergencyRestart {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.extend(self.state_root_hash.to_bytes()?);
+        buffer.extend(self.validators.to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.state_root_hash.serialized_length() + self.validators.serialized_length()
+    }
+}
+
+impl FromBytes for EmergencyRestart {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (state_root_hash, remainder) = Digest::from_bytes(bytes)?;
+        let (validators, remainder) = BTreeMap::<PublicKey, U512>::from_bytes(remainder)?;
+        let emergency_restart = EmergencyRestart {
+            state_root_hash,
+            validators,
+        };
+        Ok((emergency_restart, remainder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        let mut rng = crate::new_rng();
+        let emergency_restart = EmergencyRestart::random(&mut rng);
+        bytesrepr::test_serialization_roundtrip(&emergency_restart);
+    }
+
+    #[test]
+    fn toml_roundtrip() {
+        let mut rng = crate::new_rng();
+        let emergency_restart = EmergencyRestart::random(&mut rng);
+        let encoded = toml::to_string_pretty(&emergency_restart).unwrap();
+        let decoded = toml::from_str(&encoded).unwrap();
+        assert_eq!(emergency_restart, decoded);
+    }
+
+    #[test]
+    fn should_validate_validators() {
+        let mut rng = crate::new_rng();
+        let mut emergency_restart = EmergencyRestart::random(&mut rng);
+        let validator_count = emergency_restart.validators.len() as u32;
+        assert!(emergency_restart.is_valid(validator_count));
+        assert!(!emergency_restart.is_valid(validator_count - 1));
+
+        let public_key = PublicKey::random(&mut rng);
+        emergency_restart
+            .validators
+            .insert(public_key.clone(), U512::zero());
+        assert!(!emergency_restart.is_valid(validator_count + 1));
+
+        emergency_restart.validators.remove(&public_key);
+        emergency_restart
+            .validators
+            .insert(PublicKey::System, U512::one());
+        assert!(!emergency_restart.is_valid(validator_count + 1));
+
+        emergency_restart.validators.clear();
+        assert!(!emergency_restart.is_valid(validator_count));
+    }
+}
@@ -0,0 +1,131 @@
+use datasize::DataSize;
+#[cfg(test)]
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+#[cfg(test)]
+use casper_types::testing::TestRng;
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    ContractHash,
+};
+
+/// Settings for reading selected protocol parameters from an on-chain configuration contract.
+///
+/// When enabled, the deploy limits which apply during each era are read from the named keys of
+/// the configuration contract in the global state resulting from the preceding switch block.  The
+/// values set in the chainspec act as upper bounds and as defaults for keys which are missing.
+#[derive(Clone, Default, DataSize, PartialEq, Eq, Serialize, Deserialize, Debug)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub(crate) struct GovernanceConfig {
+    /// Whether protocol parameters are read from the configuration contract.
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    /// The hash of the configuration contract.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[data_size(skip)]
+    pub(crate) contract_hash: Option<ContractHash>,
+}
+
+impl GovernanceConfig {
+    /// Returns the hash of the configuration contract if governance is enabled.
+    pub(crate) fn enabled_contract_hash(&self) -> Option<ContractHash> {
+        if self.enabled {
+            self.contract_hash
+        } else {
+            None
+        }
+    }
+
+    /// Checks whether the values set in the config make sense and returns `false` if they don't.
+    pub(super) fn is_valid(&self) -> bool {
+        if self.enabled && self.contract_hash.is_none() {
+            error!("governance is enabled but no configuration contract hash is set");
+            return false;
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+impl GovernanceConfig {
+    /// Generates a random instance using a `TestRng`.
+    pub fn random(rng: &mut TestRng) -> Self {
+        let contract_hash = if rng.gen() {
+            Some(ContractHash::new(rng.gen()))
+        } else {
+            None
+        };
+        GovernanceConfig {
+            enabled: contract_hash.is_some() && rng.gen(),
+            contract_hash,
+        }
+    }
+}
+
+impl ToBytes for GovernanceConfig {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.extend(self.enabled.to_bytes()?);
+        buffer.extend(self.contract_hash.to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.enabled.serialized_length() + self.contract_hash.serialized_length()
+    }
+}
+
+impl FromBytes for GovernanceConfig {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (enabled, remainder) = bool::from_bytes(bytes)?;
+        let (contract_hash, remainder) = Option::<ContractHash>::from_bytes(remainder)?;
+        let config = GovernanceConfig {
+            enabled,
+            contract_hash,
+        };
+        Ok((config, remainder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        let mut rng = crate::new_rng();
+        let config = GovernanceConfig::random(&mut rng);
+        bytesrepr::test_serialization_roundtrip(&config);
+    }
+
+    #[test]
+    fn toml_roundtrip() {
+        let mut rng = crate::new_rng();
+        let config = GovernanceConfig::random(&mut rng);
+        let encoded = toml::to_string_pretty(&config).unwrap();
+        let decoded = toml::from_str(&encoded).unwrap();
+        assert_eq!(config, decoded);
+    }
+
+    #[test]
+    fn should_require_contract_hash_when_enabled() {
+        let mut config = GovernanceConfig {
+            enabled: true,
+            contract_hash: None,
+        };
+        assert!(!config.is_valid());
+        assert!(config.enabled_contract_hash().is_none());
+
+        config.contract_hash = Some(ContractHash::new([1; 32]));
+        assert!(config.is_valid());
+        assert_eq!(config.enabled_contract_hash(), config.contract_hash);
+
+        config.enabled = false;
+        assert!(config.is_valid());
+        assert!(config.enabled_contract_hash().is_none());
+    }
+}
@@ -15,8 +15,9 @@ use casper_types::{bytesrepr::Bytes, file_utils, ProtocolVersion};
 
 use super::{
     accounts_config::AccountsConfig, global_state_update::GlobalStateUpdateConfig, ActivationPoint,
-    Chainspec, ChainspecRawBytes, CoreConfig, DeployConfig, Error, GlobalStateUpdate,
-    HighwayConfig, NetworkConfig, ProtocolConfig,
+    Chainspec, ChainspecRawBytes, Checkpoint, CommitRulesConfig, CoreConfig, DeployConfig,
+    EmergencyRestart, Error, GlobalStateUpdate, GovernanceConfig, HighwayConfig, NetworkConfig,
+    ProtocolConfig,
 };
 
 #[derive(PartialEq, Eq, Serialize, Deserialize, Debug)]
@@ -34,6 +35,7 @@ struct TomlProtocol {
     version: ProtocolVersion,
     hard_reset: bool,
     activation_point: ActivationPoint,
+    emergency_restart: Option<EmergencyRestart>,
 }
 
 /// A chainspec configuration as laid out in the TOML-encoded configuration file.
@@ -48,6 +50,12 @@ pub(super) struct TomlChainspec {
     highway: HighwayConfig,
     wasm: WasmConfig,
     system_costs: SystemConfig,
+    #[serde(default)]
+    commit_rules: CommitRulesConfig,
+    #[serde(default)]
+    governance: GovernanceConfig,
+    #[serde(default)]
+    checkpoints: Vec<Checkpoint>,
 }
 
 impl From<&Chainspec> for TomlChainspec {
@@ -56,6 +64,7 @@ impl From<&Chainspec> for TomlChainspec {
             version: chainspec.protocol_config.version,
             hard_reset: chainspec.protocol_config.hard_reset,
             activation_point: chainspec.protocol_config.activation_point,
+            emergency_restart: chainspec.protocol_config.emergency_restart.clone(),
         };
         let network = TomlNetwork {
             name: chainspec.network_config.name.clone(),
@@ -66,6 +75,9 @@ impl From<&Chainspec> for TomlChainspec {
         let highway = chainspec.highway_config;
         let wasm = chainspec.wasm_config;
         let system_costs = chainspec.system_costs_config;
+        let commit_rules = chainspec.commit_rules_config.clone();
+        let governance = chainspec.governance_config.clone();
+        let checkpoints = chainspec.checkpoints.clone();
 
         TomlChainspec {
             protocol,
@@ -75,6 +87,9 @@ impl From<&Chainspec> for TomlChainspec {
             highway,
             wasm,
             system_costs,
+            commit_rules,
+            governance,
+            checkpoints,
         }
     }
 }
@@ -112,6 +127,7 @@ pub(super) fn parse_toml<P: AsRef<Path>>(
         hard_reset: toml_chainspec.protocol.hard_reset,
         activation_point: toml_chainspec.protocol.activation_point,
         global_state_update,
+        emergency_restart: toml_chainspec.protocol.emergency_restart,
     };
 
     let chainspec = Chainspec {
@@ -122,6 +138,9 @@ pub(super) fn parse_toml<P: AsRef<Path>>(
         highway_config: toml_chainspec.highway,
         wasm_config: toml_chainspec.wasm,
         system_costs_config: toml_chainspec.system_costs,
+        commit_rules_config: toml_chainspec.commit_rules,
+        governance_config: toml_chainspec.governance,
+        checkpoints: toml_chainspec.checkpoints,
     };
     let chainspec_raw_bytes = ChainspecRawBytes::new(
         Bytes::from(chainspec_bytes),
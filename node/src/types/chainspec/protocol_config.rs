@@ -7,6 +7,7 @@ use datasize::DataSize;
 #[cfg(test)]
 use rand::Rng;
 use serde::{Deserialize, Serialize};
+use tracing::error;
 
 #[cfg(test)]
 use casper_types::testing::TestRng;
@@ -15,7 +16,7 @@ use casper_types::{
     Key, ProtocolVersion, StoredValue,
 };
 
-use super::{ActivationPoint, GlobalStateUpdate};
+use super::{ActivationPoint, EmergencyRestart, GlobalStateUpdate};
 use crate::types::BlockHeader;
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, DataSize, Debug)]
@@ -30,6 +31,9 @@ pub struct ProtocolConfig {
     /// Any arbitrary updates we might want to make to the global state at the start of the era
     /// specified in the activation point.
     pub(crate) global_state_update: Option<GlobalStateUpdate>,
+    /// The designated global state and validators from which the network restarts at the
+    /// activation point, if this is an emergency restart.
+    pub(crate) emergency_restart: Option<EmergencyRestart>,
 }
 
 impl ProtocolConfig {
@@ -60,6 +64,17 @@ impl ProtocolConfig {
 
     /// Checks whether the values set in the config make sense and returns `false` if they don't.
     pub(super) fn is_valid(&self) -> bool {
+        if self.emergency_restart.is_some() {
+            if self.activation_point.era_id().is_genesis() {
+                error!("emergency restart cannot be activated at genesis");
+                return false;
+            }
+            if !self.hard_reset {
+                error!("emergency restart requires hard_reset to be enabled");
+                return false;
+            }
+        }
+
         true
     }
 
@@ -78,6 +93,7 @@ impl ProtocolConfig {
             hard_reset: rng.gen(),
             activation_point,
             global_state_update: None,
+            emergency_restart: None,
         }
     }
 }
@@ -89,6 +105,7 @@ impl ToBytes for ProtocolConfig {
         buffer.extend(self.hard_reset.to_bytes()?);
         buffer.extend(self.activation_point.to_bytes()?);
         buffer.extend(self.global_state_update.to_bytes()?);
+        buffer.extend(self.emergency_restart.to_bytes()?);
         Ok(buffer)
     }
 
@@ -97,6 +114,7 @@ impl ToBytes for ProtocolConfig {
             + self.hard_reset.serialized_length()
             + self.activation_point.serialized_length()
             + self.global_state_update.serialized_length()
+            + self.emergency_restart.serialized_length()
     }
 }
 
@@ -108,11 +126,13 @@ impl FromBytes for ProtocolConfig {
         let (hard_reset, remainder) = bool::from_bytes(remainder)?;
         let (activation_point, remainder) = ActivationPoint::from_bytes(remainder)?;
         let (global_state_update, remainder) = Option::<GlobalStateUpdate>::from_bytes(remainder)?;
+        let (emergency_restart, remainder) = Option::<EmergencyRestart>::from_bytes(remainder)?;
         let protocol_config = ProtocolConfig {
             version,
             hard_reset,
             activation_point,
             global_state_update,
+            emergency_restart,
         };
         Ok((protocol_config, remainder))
     }
@@ -171,6 +191,25 @@ mod tests {
         assert!(protocol_config.is_valid());
     }
 
+    #[test]
+    fn should_perform_checks_with_emergency_restart() {
+        let mut rng = crate::new_rng();
+        let mut protocol_config = ProtocolConfig::random(&mut rng);
+        protocol_config.hard_reset = true;
+        protocol_config.activation_point = ActivationPoint::EraId(EraId::from(5));
+        protocol_config.emergency_restart = Some(EmergencyRestart::random(&mut rng));
+        assert!(protocol_config.is_valid());
+
+        // The blocks created after the activation point must be discarded.
+        protocol_config.hard_reset = false;
+        assert!(!protocol_config.is_valid());
+
+        // There is no global state to restart from before genesis.
+        protocol_config.hard_reset = true;
+        protocol_config.activation_point = ActivationPoint::EraId(EraId::from(0));
+        assert!(!protocol_config.is_valid());
+    }
+
     #[test]
     fn should_recognize_blocks_before_activation_point() {
         let past_version = ProtocolVersion::from_parts(1, 0, 0);
@@ -180,35 +219,54 @@ mod tests {
         let upgrade_era = EraId::from(5);
         let previous_era = upgrade_era.saturating_sub(1);
 
-        let mut rng = crate::new_rng();
         let protocol_config = ProtocolConfig {
             version: current_version,
             hard_reset: false,
             activation_point: ActivationPoint::EraId(upgrade_era),
             global_state_update: None,
+            emergency_restart: None,
         };
 
         // The block before this protocol version: a switch block with previous era and version.
-        let block =
-            Block::random_with_specifics(&mut rng, previous_era, 100, past_version, true, None);
+        let block = Block::builder()
+            .era_id(previous_era)
+            .height(100)
+            .protocol_version(past_version)
+            .switch_block()
+            .build();
         assert!(protocol_config.is_last_block_before_activation(block.header()));
 
         // Not the activation point: wrong era.
-        let block =
-            Block::random_with_specifics(&mut rng, upgrade_era, 100, past_version, true, None);
+        let block = Block::builder()
+            .era_id(upgrade_era)
+            .height(100)
+            .protocol_version(past_version)
+            .switch_block()
+            .build();
         assert!(!protocol_config.is_last_block_before_activation(block.header()));
 
         // Not the activation point: wrong version.
-        let block =
-            Block::random_with_specifics(&mut rng, previous_era, 100, current_version, true, None);
+        let block = Block::builder()
+            .era_id(previous_era)
+            .height(100)
+            .protocol_version(current_version)
+            .switch_block()
+            .build();
         assert!(!protocol_config.is_last_block_before_activation(block.header()));
-        let block =
-            Block::random_with_specifics(&mut rng, previous_era, 100, future_version, true, None);
+        let block = Block::builder()
+            .era_id(previous_era)
+            .height(100)
+            .protocol_version(future_version)
+            .switch_block()
+            .build();
         assert!(!protocol_config.is_last_block_before_activation(block.header()));
 
         // Not the activation point: not a switch block.
-        let block =
-            Block::random_with_specifics(&mut rng, previous_era, 100, past_version, false, None);
+        let block = Block::builder()
+            .era_id(previous_era)
+            .height(100)
+            .protocol_version(past_version)
+            .build();
         assert!(!protocol_config.is_last_block_before_activation(block.header()));
     }
 }
@@ -0,0 +1,21 @@
+use once_cell::sync::Lazy;
+
+use casper_types::{CLValue, Key};
+
+use crate::components::rpc_server::rpcs::docs::DocExample;
+
+pub use casper_execution_engine::core::engine_state::ContractEvent;
+
+static CONTRACT_EVENT: Lazy<ContractEvent> = Lazy::new(|| {
+    ContractEvent::new(
+        Key::Hash([2; 32]),
+        "transfer".to_string(),
+        CLValue::from_t(1u64).unwrap(),
+    )
+});
+
+impl DocExample for ContractEvent {
+    fn doc_example() -> &'static Self {
+        &*CONTRACT_EVENT
+    }
+}
@@ -22,22 +22,19 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tracing::{info, warn};
 
-#[cfg(test)]
-use casper_execution_engine::core::engine_state::MAX_PAYMENT;
 use casper_execution_engine::core::engine_state::{
     executable_deploy_item::ExecutableDeployItem, DeployItem,
 };
 use casper_hashing::Digest;
-#[cfg(test)]
-use casper_types::bytesrepr::Bytes;
 #[cfg(any(feature = "testing", test))]
 use casper_types::testing::TestRng;
 use casper_types::{
+    account::AccountHash,
     bytesrepr::{self, FromBytes, ToBytes},
     crypto, runtime_args,
     system::standard_payment::ARG_AMOUNT,
-    ExecutionResult, Motes, PublicKey, RuntimeArgs, SecretKey, Signature, TimeDiff, Timestamp,
-    U512,
+    CLType, CLValue, ExecutionResult, Key, Motes, PublicKey, RuntimeArgs, SecretKey, Signature,
+    TimeDiff, Timestamp, U512,
 };
 
 use super::{BlockHash, BlockHashAndHeight, Item, Tag};
@@ -48,6 +45,9 @@ use crate::{
     utils::{ds, DisplayIter},
 };
 
+/// The name of the runtime argument of a native transfer naming its target.
+const ARG_TARGET: &str = "target";
+
 static DEPLOY: Lazy<Deploy> = Lazy::new(|| {
     let payment_args = runtime_args! {
         "amount" => 1000
@@ -206,6 +206,27 @@ pub enum DeployConfigurationFailure {
         /// The chainspec limit for max_associated_keys.
         max_associated_keys: u32,
     },
+
+    /// The deploy has expired.
+    #[error("deploy expired at {expiry_timestamp}, current time is {current_timestamp}")]
+    Expired {
+        /// The time at which the deploy expired.
+        expiry_timestamp: Timestamp,
+        /// The time the deploy was checked at.
+        current_timestamp: Timestamp,
+    },
+
+    /// The payment code is a native transfer.
+    #[error("transfer is not valid for payment code")]
+    InvalidPaymentVariant,
+
+    /// Missing transfer "target" runtime argument.
+    #[error("missing transfer 'target' runtime argument")]
+    MissingTransferTarget,
+
+    /// The session code is empty module bytes.
+    #[error("module bytes for session code cannot be empty")]
+    MissingModuleBytes,
 }
 
 /// Error returned when a Deploy is too large.
@@ -616,6 +637,35 @@ impl DeployWithApprovals {
     pub fn approvals(&self) -> &BTreeSet<Approval> {
         &self.approvals
     }
+
+    /// Consumes `self`, returning the approvals.
+    pub fn into_approvals(self) -> BTreeSet<Approval> {
+        self.approvals
+    }
+
+    /// Verifies that each of the approvals is a valid signature of the deploy hash.
+    pub fn verify_approvals(&self) -> Result<(), DeployConfigurationFailure> {
+        for (index, approval) in self.approvals.iter().enumerate() {
+            crypto::verify(&self.deploy_hash, approval.signature(), approval.signer()).map_err(
+                |error| DeployConfigurationFailure::InvalidApproval {
+                    index,
+                    error_msg: error.to_string(),
+                },
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for DeployWithApprovals {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "approvals for {}: {}",
+            self.deploy_hash,
+            DisplayIter::new(self.approvals.iter())
+        )
+    }
 }
 
 impl From<&Deploy> for DeployWithApprovals {
@@ -874,6 +924,7 @@ impl Deploy {
     /// Replaces the set of approvals attached to this deploy.
     pub fn replace_approvals(&mut self, approvals: BTreeSet<Approval>) {
         self.approvals = approvals;
+        self.is_valid = OnceCell::new();
     }
 
     /// Returns the hash of this deploy wrapped in `DeployOrTransferHash`.
@@ -888,27 +939,12 @@ impl Deploy {
     /// Returns the `DeployInfo`.
     pub fn deploy_info(&self) -> Result<DeployInfo, Error> {
         let header = self.header().clone();
-        let size = self.serialized_length();
-        let payment_amount = if self.session().is_transfer() {
+        let size = self.serialized_size();
+        let payment_amount = self
+            .payment_amount()
+            .map_err(|_| Error::InvalidPayment)?
             // TODO: we need a non-zero value constant for wasm-less transfer cost.
-            Motes::zero()
-        } else {
-            let payment_item = self.payment().clone();
-
-            // In the happy path for a payment we expect:
-            // - args to exist
-            // - contain "amount"
-            // - be a valid U512 value.
-            let value = payment_item
-                .args()
-                .get(ARG_AMOUNT)
-                .ok_or(Error::InvalidPayment)?;
-            let value = value
-                .clone()
-                .into_t::<U512>()
-                .map_err(|_| Error::InvalidPayment)?;
-            Motes::new(value)
-        };
+            .unwrap_or_else(Motes::zero);
         Ok(DeployInfo {
             header,
             payment_amount,
@@ -916,9 +952,53 @@ impl Deploy {
         })
     }
 
+    /// Returns the exact size of the deploy in bytes when serialized, which is the size limited by
+    /// the chainspec's `max_deploy_size`.
+    pub fn serialized_size(&self) -> usize {
+        self.serialized_length()
+    }
+
+    /// Returns the amount the deploy's payment code pays for its execution, taken from the
+    /// payment's "amount" runtime argument.
+    ///
+    /// Returns `Ok(None)` for native transfers, whose cost is fixed instead.
+    pub fn payment_amount(&self) -> Result<Option<Motes>, DeployConfigurationFailure> {
+        if self.session.is_transfer() {
+            return Ok(None);
+        }
+        let value = self
+            .payment
+            .args()
+            .get(ARG_AMOUNT)
+            .ok_or(DeployConfigurationFailure::MissingPaymentAmount)?;
+        let amount = value
+            .clone()
+            .into_t::<U512>()
+            .map_err(|_| DeployConfigurationFailure::FailedToParsePaymentAmount)?;
+        Ok(Some(Motes::new(amount)))
+    }
+
+    /// Returns the accounts a native transfer touches: the sending account, and the target
+    /// account unless the target is a purse.
+    ///
+    /// Returns `None` if the deploy is not a native transfer.  The target account is omitted if
+    /// the "target" runtime argument is missing or invalid.
+    pub fn transfer_accounts(&self) -> Option<BTreeSet<AccountHash>> {
+        let args = match &self.session {
+            ExecutableDeployItem::Transfer { args } => args,
+            _ => return None,
+        };
+        let mut accounts = BTreeSet::new();
+        accounts.insert(self.header.account.to_account_hash());
+        if let Some(target) = args.get(ARG_TARGET).and_then(transfer_target_account) {
+            accounts.insert(target);
+        }
+        Some(accounts)
+    }
+
     /// Returns true if the serialized size of the deploy is not greater than `max_deploy_size`.
     pub fn is_valid_size(&self, max_deploy_size: u32) -> Result<(), ExcessiveSizeError> {
-        let deploy_size = self.serialized_length();
+        let deploy_size = self.serialized_size();
         if deploy_size > max_deploy_size as usize {
             return Err(ExcessiveSizeError {
                 max_deploy_size,
@@ -1023,16 +1103,8 @@ impl Deploy {
         // Transfers have a fixed cost and won't blow the block gas limit.
         // Other deploys can, therefore, statically check the payment amount
         // associated with the deploy.
-        if !self.session().is_transfer() {
-            let value = self
-                .payment()
-                .args()
-                .get(ARG_AMOUNT)
-                .ok_or(DeployConfigurationFailure::MissingPaymentAmount)?;
-            let payment_amount = value
-                .clone()
-                .into_t::<U512>()
-                .map_err(|_| DeployConfigurationFailure::FailedToParsePaymentAmount)?;
+        if let Some(payment_amount) = self.payment_amount()? {
+            let payment_amount = payment_amount.value();
             if payment_amount > U512::from(config.block_gas_limit) {
                 info!(
                     amount = %payment_amount,
@@ -1102,6 +1174,59 @@ impl Deploy {
 
         Ok(())
     }
+
+    /// Checks everything the deploy acceptor checks which doesn't depend on global state: that
+    /// the deploy is config compliant, hasn't expired at `current_timestamp`, has payment and
+    /// session code of a valid kind, and is valid as per [`Deploy::is_valid`].
+    ///
+    /// A deploy passing these checks can still be rejected by the node, e.g. if its account
+    /// doesn't exist or has insufficient balance.
+    pub fn validate_structure(
+        &self,
+        chain_name: &str,
+        config: &DeployConfig,
+        max_associated_keys: u32,
+        current_timestamp: Timestamp,
+    ) -> Result<(), DeployConfigurationFailure> {
+        self.is_config_compliant(chain_name, config, max_associated_keys)?;
+
+        if self.header.expired(current_timestamp) {
+            return Err(DeployConfigurationFailure::Expired {
+                expiry_timestamp: self.header.expires(),
+                current_timestamp,
+            });
+        }
+
+        if self.payment.is_transfer() {
+            return Err(DeployConfigurationFailure::InvalidPaymentVariant);
+        }
+
+        match &self.session {
+            ExecutableDeployItem::Transfer { args } if args.get(ARG_TARGET).is_none() => {
+                return Err(DeployConfigurationFailure::MissingTransferTarget);
+            }
+            ExecutableDeployItem::ModuleBytes { module_bytes, .. } if module_bytes.is_empty() => {
+                return Err(DeployConfigurationFailure::MissingModuleBytes);
+            }
+            _ => (),
+        }
+
+        self.is_valid()
+    }
+}
+
+/// Returns the account a native transfer's "target" runtime argument names, if any.
+fn transfer_target_account(target: &CLValue) -> Option<AccountHash> {
+    match target.cl_type() {
+        CLType::ByteArray(32) => target.clone().into_t().ok(),
+        CLType::Key => target.clone().into_t::<Key>().ok()?.into_account(),
+        CLType::PublicKey => target
+            .clone()
+            .into_t::<PublicKey>()
+            .ok()
+            .map(|public_key| public_key.to_account_hash()),
+        _ => None,
+    }
 }
 
 /// A deploy combined with a potential set of finalized approvals.
@@ -1160,18 +1285,11 @@ impl DeployWithFinalizedApprovals {
 #[cfg(any(feature = "testing", test))]
 impl Deploy {
     /// Returns a random deploy.
+    ///
+    /// For deploys with specific properties, use [`Deploy::builder`].
     pub fn random(rng: &mut TestRng) -> Self {
         let timestamp = Timestamp::random(rng);
         let ttl = TimeDiff::from(rng.gen_range(60_000..3_600_000));
-        Deploy::random_with_timestamp_and_ttl(rng, timestamp, ttl)
-    }
-
-    /// Returns a random deploy but using the specified `timestamp` and `ttl`.
-    pub fn random_with_timestamp_and_ttl(
-        rng: &mut TestRng,
-        timestamp: Timestamp,
-        ttl: TimeDiff,
-    ) -> Self {
         let gas_price = rng.gen_range(1..100);
 
         let dependencies = vec![
@@ -1216,342 +1334,6 @@ impl Deploy {
     pub(crate) fn invalidate(&mut self) {
         self.header.chain_name.clear();
     }
-
-    /// Returns a random deploy for a native transfer.
-    pub(crate) fn random_valid_native_transfer(rng: &mut TestRng) -> Self {
-        let deploy = Self::random(rng);
-        let transfer_args = runtime_args! {
-            "amount" => *MAX_PAYMENT,
-            "source" => PublicKey::random(rng).to_account_hash(),
-            "target" => PublicKey::random(rng).to_account_hash(),
-        };
-        let payment_args = runtime_args! {
-            "amount" => U512::from(10),
-        };
-        let session = ExecutableDeployItem::Transfer {
-            args: transfer_args,
-        };
-        let payment = ExecutableDeployItem::ModuleBytes {
-            module_bytes: Bytes::new(),
-            args: payment_args,
-        };
-        let secret_key = SecretKey::random(rng);
-        Deploy::new(
-            Timestamp::now(),
-            deploy.header.ttl,
-            deploy.header.gas_price,
-            deploy.header.dependencies,
-            deploy.header.chain_name,
-            payment,
-            session,
-            &secret_key,
-            None,
-        )
-    }
-
-    /// Returns a random deploy for a native transfer with no dependencies.
-    pub(crate) fn random_valid_native_transfer_without_deps(rng: &mut TestRng) -> Self {
-        let deploy = Self::random(rng);
-        let transfer_args = runtime_args! {
-            "amount" => *MAX_PAYMENT,
-            "source" => PublicKey::random(rng).to_account_hash(),
-            "target" => PublicKey::random(rng).to_account_hash(),
-        };
-        let payment_args = runtime_args! {
-            "amount" => U512::from(10),
-        };
-        let session = ExecutableDeployItem::Transfer {
-            args: transfer_args,
-        };
-        let payment = ExecutableDeployItem::ModuleBytes {
-            module_bytes: Bytes::new(),
-            args: payment_args,
-        };
-        let secret_key = SecretKey::random(rng);
-        Deploy::new(
-            Timestamp::now(),
-            deploy.header.ttl,
-            deploy.header.gas_price,
-            vec![],
-            deploy.header.chain_name,
-            payment,
-            session,
-            &secret_key,
-            None,
-        )
-    }
-
-    /// Returns a random invalid deploy without a payment amount specified.
-    pub(crate) fn random_without_payment_amount(rng: &mut TestRng) -> Self {
-        let payment = ExecutableDeployItem::ModuleBytes {
-            module_bytes: Bytes::new(),
-            args: RuntimeArgs::default(),
-        };
-        Self::random_transfer_with_payment(rng, payment)
-    }
-
-    /// Returns a random invalid deploy with an invalid value for the payment amount.
-    pub(crate) fn random_with_mangled_payment_amount(rng: &mut TestRng) -> Self {
-        let payment_args = runtime_args! {
-            "amount" => "invalid-argument"
-        };
-        let payment = ExecutableDeployItem::ModuleBytes {
-            module_bytes: Bytes::new(),
-            args: payment_args,
-        };
-        Self::random_transfer_with_payment(rng, payment)
-    }
-
-    /// Returns a random deploy with custom payment specified as a stored contract by name.
-    pub(crate) fn random_with_valid_custom_payment_contract_by_name(rng: &mut TestRng) -> Self {
-        let payment = ExecutableDeployItem::StoredContractByName {
-            name: "Test".to_string(),
-            entry_point: "call".to_string(),
-            args: Default::default(),
-        };
-        Self::random_transfer_with_payment(rng, payment)
-    }
-
-    /// Returns a random invalid deploy with custom payment specified as a stored contract by hash,
-    /// but missing the runtime args.
-    pub(crate) fn random_with_missing_payment_contract_by_hash(rng: &mut TestRng) -> Self {
-        let payment = ExecutableDeployItem::StoredContractByHash {
-            hash: [19; 32].into(),
-            entry_point: "call".to_string(),
-            args: Default::default(),
-        };
-        Self::random_transfer_with_payment(rng, payment)
-    }
-
-    /// Returns a random invalid deploy with custom payment specified as a stored contract by hash,
-    /// but calling an invalid entry point.
-    pub(crate) fn random_with_missing_entry_point_in_payment_contract(rng: &mut TestRng) -> Self {
-        let payment = ExecutableDeployItem::StoredContractByHash {
-            hash: [19; 32].into(),
-            entry_point: "non-existent-entry-point".to_string(),
-            args: Default::default(),
-        };
-        Self::random_transfer_with_payment(rng, payment)
-    }
-
-    /// Returns a random deploy with custom payment specified as a stored versioned contract by
-    /// name.
-    pub(crate) fn random_with_valid_custom_payment_package_by_name(rng: &mut TestRng) -> Self {
-        let payment = ExecutableDeployItem::StoredVersionedContractByName {
-            name: "Test".to_string(),
-            version: None,
-            entry_point: "call".to_string(),
-            args: Default::default(),
-        };
-        Self::random_transfer_with_payment(rng, payment)
-    }
-
-    /// Returns a random invalid deploy with custom payment specified as a stored versioned contract
-    /// by hash, but missing the runtime args.
-    pub(crate) fn random_with_missing_payment_package_by_hash(rng: &mut TestRng) -> Self {
-        let payment = ExecutableDeployItem::StoredVersionedContractByHash {
-            hash: Default::default(),
-            version: None,
-            entry_point: "call".to_string(),
-            args: Default::default(),
-        };
-        Self::random_transfer_with_payment(rng, payment)
-    }
-
-    /// Returns a random invalid deploy with custom payment specified as a stored versioned contract
-    /// by hash, but calling an invalid entry point.
-    pub(crate) fn random_with_nonexistent_contract_version_in_payment_package(
-        rng: &mut TestRng,
-    ) -> Self {
-        let payment = ExecutableDeployItem::StoredVersionedContractByHash {
-            hash: [19; 32].into(),
-            version: Some(6u32),
-            entry_point: "non-existent-entry-point".to_string(),
-            args: Default::default(),
-        };
-        Self::random_transfer_with_payment(rng, payment)
-    }
-
-    /// Returns a random deploy with custom session specified as a stored contract by name.
-    pub(crate) fn random_with_valid_session_contract_by_name(rng: &mut TestRng) -> Self {
-        let session = ExecutableDeployItem::StoredContractByName {
-            name: "Test".to_string(),
-            entry_point: "call".to_string(),
-            args: Default::default(),
-        };
-        Self::random_transfer_with_session(rng, session)
-    }
-
-    /// Returns a random invalid deploy with custom session specified as a stored contract by hash,
-    /// but missing the runtime args.
-    pub(crate) fn random_with_missing_session_contract_by_hash(rng: &mut TestRng) -> Self {
-        let session = ExecutableDeployItem::StoredContractByHash {
-            hash: Default::default(),
-            entry_point: "call".to_string(),
-            args: Default::default(),
-        };
-        Self::random_transfer_with_session(rng, session)
-    }
-
-    /// Returns a random invalid deploy with custom session specified as a stored contract by hash,
-    /// but calling an invalid entry point.
-    pub(crate) fn random_with_missing_entry_point_in_session_contract(rng: &mut TestRng) -> Self {
-        let session = ExecutableDeployItem::StoredContractByHash {
-            hash: [19; 32].into(),
-            entry_point: "non-existent-entry-point".to_string(),
-            args: Default::default(),
-        };
-        Self::random_transfer_with_session(rng, session)
-    }
-
-    /// Returns a random deploy with custom session specified as a stored versioned contract by
-    /// name.
-    pub(crate) fn random_with_valid_session_package_by_name(rng: &mut TestRng) -> Self {
-        let session = ExecutableDeployItem::StoredVersionedContractByName {
-            name: "Test".to_string(),
-            version: None,
-            entry_point: "call".to_string(),
-            args: Default::default(),
-        };
-        Self::random_transfer_with_session(rng, session)
-    }
-
-    /// Returns a random invalid deploy with custom session specified as a stored versioned contract
-    /// by hash, but missing the runtime args.
-    pub(crate) fn random_with_missing_session_package_by_hash(rng: &mut TestRng) -> Self {
-        let session = ExecutableDeployItem::StoredVersionedContractByHash {
-            hash: Default::default(),
-            version: None,
-            entry_point: "call".to_string(),
-            args: Default::default(),
-        };
-        Self::random_transfer_with_session(rng, session)
-    }
-
-    /// Returns a random invalid deploy with custom session specified as a stored versioned contract
-    /// by hash, but calling an invalid entry point.
-    pub(crate) fn random_with_nonexistent_contract_version_in_session_package(
-        rng: &mut TestRng,
-    ) -> Self {
-        let session = ExecutableDeployItem::StoredVersionedContractByHash {
-            hash: [19; 32].into(),
-            version: Some(6u32),
-            entry_point: "non-existent-entry-point".to_string(),
-            args: Default::default(),
-        };
-        Self::random_transfer_with_session(rng, session)
-    }
-
-    /// Returns a random invalid transfer deploy with the "target" runtime arg missing.
-    pub(crate) fn random_without_transfer_target(rng: &mut TestRng) -> Self {
-        let transfer_args = runtime_args! {
-            "amount" => *MAX_PAYMENT,
-            "source" => PublicKey::random(rng).to_account_hash(),
-        };
-        let session = ExecutableDeployItem::Transfer {
-            args: transfer_args,
-        };
-        Self::random_transfer_with_session(rng, session)
-    }
-
-    /// Returns a random invalid transfer deploy with the "amount" runtime arg missing.
-    pub(crate) fn random_without_transfer_amount(rng: &mut TestRng) -> Self {
-        let transfer_args = runtime_args! {
-            "source" => PublicKey::random(rng).to_account_hash(),
-            "target" => PublicKey::random(rng).to_account_hash(),
-        };
-        let session = ExecutableDeployItem::Transfer {
-            args: transfer_args,
-        };
-        Self::random_transfer_with_session(rng, session)
-    }
-
-    /// Returns a random invalid transfer deploy with an invalid "amount" runtime arg.
-    pub(crate) fn random_with_mangled_transfer_amount(rng: &mut TestRng) -> Self {
-        let transfer_args = runtime_args! {
-            "amount" => "mangled-transfer-amount",
-            "source" => PublicKey::random(rng).to_account_hash(),
-            "target" => PublicKey::random(rng).to_account_hash(),
-        };
-        let session = ExecutableDeployItem::Transfer {
-            args: transfer_args,
-        };
-        Self::random_transfer_with_session(rng, session)
-    }
-
-    /// Returns a random invalid deploy with empty session bytes.
-    pub(crate) fn random_with_empty_session_module_bytes(rng: &mut TestRng) -> Self {
-        let session = ExecutableDeployItem::ModuleBytes {
-            module_bytes: Bytes::new(),
-            args: Default::default(),
-        };
-        Self::random_transfer_with_session(rng, session)
-    }
-
-    /// Returns a random invalid deploy with an expired TTL.
-    pub(crate) fn random_expired_deploy(rng: &mut TestRng) -> Self {
-        let deploy = Self::random_valid_native_transfer(rng);
-        let secret_key = SecretKey::random(rng);
-
-        Deploy::new(
-            Timestamp::zero(),
-            TimeDiff::from_seconds(1u32),
-            deploy.header.gas_price,
-            deploy.header.dependencies,
-            deploy.header.chain_name,
-            deploy.payment,
-            deploy.session,
-            &secret_key,
-            None,
-        )
-    }
-
-    /// Returns a random deploy with native transfer as payment code.
-    pub(crate) fn random_with_native_transfer_in_payment_logic(rng: &mut TestRng) -> Self {
-        let transfer_args = runtime_args! {
-            "amount" => *MAX_PAYMENT,
-            "source" => PublicKey::random(rng).to_account_hash(),
-            "target" => PublicKey::random(rng).to_account_hash(),
-        };
-        let payment = ExecutableDeployItem::Transfer {
-            args: transfer_args,
-        };
-        Self::random_transfer_with_payment(rng, payment)
-    }
-
-    fn random_transfer_with_payment(rng: &mut TestRng, payment: ExecutableDeployItem) -> Self {
-        let deploy = Self::random_valid_native_transfer(rng);
-        let secret_key = SecretKey::random(rng);
-
-        Deploy::new(
-            deploy.header.timestamp,
-            deploy.header.ttl,
-            deploy.header.gas_price,
-            deploy.header.dependencies,
-            deploy.header.chain_name,
-            payment,
-            deploy.session,
-            &secret_key,
-            None,
-        )
-    }
-
-    fn random_transfer_with_session(rng: &mut TestRng, session: ExecutableDeployItem) -> Self {
-        let deploy = Self::random_valid_native_transfer(rng);
-        let secret_key = SecretKey::random(rng);
-
-        Deploy::new(
-            deploy.header.timestamp,
-            deploy.header.ttl,
-            deploy.header.gas_price,
-            deploy.header.dependencies,
-            deploy.header.chain_name,
-            deploy.payment,
-            session,
-            &secret_key,
-            None,
-        )
-    }
 }
 
 impl DocExample for Deploy {
@@ -1710,6 +1492,27 @@ impl PartialEq<BlockHashAndHeight> for DeployMetadataExt {
     }
 }
 
+/// A deploy sent by a given account, along with what is known locally about its execution.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountDeploy {
+    /// The hash of the deploy.
+    pub deploy_hash: DeployHash,
+    /// The hash and height of the block containing the deploy, if known.
+    pub block_hash_and_height: Option<BlockHashAndHeight>,
+    /// The result of executing the deploy in the block containing it, if known.
+    pub execution_result: Option<ExecutionResult>,
+}
+
+/// A page of the deploys sent by a given account, most recent first.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccountDeploys {
+    /// The deploys of this page.
+    pub deploys: Vec<AccountDeploy>,
+    /// If more deploys follow, the opaque position of the last deploy of this page, after which
+    /// to continue retrieving them.
+    pub next_page_after: Option<Vec<u8>>,
+}
+
 impl ToBytes for Deploy {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut buffer = bytesrepr::allocate_buffer(self)?;
@@ -1754,7 +1557,7 @@ mod tests {
     use std::{iter, time::Duration};
 
     use casper_execution_engine::core::engine_state::MAX_PAYMENT_AMOUNT;
-    use casper_types::{bytesrepr::Bytes, CLValue};
+    use casper_types::{bytesrepr::Bytes, AccessRights, CLValue, URef};
 
     use super::*;
 
@@ -2322,4 +2125,120 @@ mod tests {
             deploy.is_config_compliant(chain_name, &deploy_config, DEFAULT_MAX_ASSOCIATED_KEYS)
         )
     }
+
+    #[test]
+    fn should_validate_structure_like_deploy_acceptor() {
+        let mut rng = crate::new_rng();
+        let chain_name = "net-1";
+        let deploy_config = DeployConfig::default();
+        let validate = |deploy: &Deploy, current_timestamp| {
+            deploy.validate_structure(
+                chain_name,
+                &deploy_config,
+                DEFAULT_MAX_ASSOCIATED_KEYS,
+                current_timestamp,
+            )
+        };
+        let transfer_args = runtime_args! {
+            "amount" => U512::from(MAX_PAYMENT_AMOUNT),
+            "target" => PublicKey::random(&mut rng),
+        };
+
+        let valid_deploy = Deploy::new(
+            Timestamp::now(),
+            deploy_config.max_ttl,
+            1,
+            vec![],
+            chain_name.to_string(),
+            ExecutableDeployItem::ModuleBytes {
+                module_bytes: Bytes::new(),
+                args: runtime_args! { "amount" => U512::from(10) },
+            },
+            ExecutableDeployItem::Transfer {
+                args: transfer_args.clone(),
+            },
+            &SecretKey::random(&mut rng),
+            None,
+        );
+        assert_eq!(validate(&valid_deploy, Timestamp::now()), Ok(()));
+
+        let expiry_timestamp = valid_deploy.header().expires();
+        let current_timestamp = expiry_timestamp + TimeDiff::from(1);
+        assert_eq!(
+            validate(&valid_deploy, current_timestamp),
+            Err(DeployConfigurationFailure::Expired {
+                expiry_timestamp,
+                current_timestamp,
+            })
+        );
+
+        let mut deploy = create_deploy(&mut rng, deploy_config.max_ttl, 0, chain_name);
+        assert_eq!(
+            validate(&deploy, Timestamp::now()),
+            Err(DeployConfigurationFailure::MissingTransferTarget)
+        );
+
+        deploy.session = ExecutableDeployItem::Transfer {
+            args: transfer_args,
+        };
+        deploy.payment = ExecutableDeployItem::Transfer {
+            args: RuntimeArgs::new(),
+        };
+        assert_eq!(
+            validate(&deploy, Timestamp::now()),
+            Err(DeployConfigurationFailure::InvalidPaymentVariant)
+        );
+    }
+
+    #[test]
+    fn should_return_accounts_touched_by_transfer() {
+        let mut rng = crate::new_rng();
+        let mut deploy = create_deploy(&mut rng, DeployConfig::default().max_ttl, 0, "net-1");
+        let source = deploy.header().account().to_account_hash();
+        let target = PublicKey::random(&mut rng);
+
+        deploy.session = ExecutableDeployItem::Transfer {
+            args: runtime_args! { "target" => target.clone() },
+        };
+        let expected: BTreeSet<_> = vec![source, target.to_account_hash()].into_iter().collect();
+        assert_eq!(deploy.transfer_accounts(), Some(expected));
+
+        // A purse is not an account.
+        deploy.session = ExecutableDeployItem::Transfer {
+            args: runtime_args! { "target" => URef::new([1; 32], AccessRights::ADD) },
+        };
+        let expected: BTreeSet<_> = iter::once(source).collect();
+        assert_eq!(deploy.transfer_accounts(), Some(expected));
+
+        deploy.session = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::from(vec![1]),
+            args: RuntimeArgs::new(),
+        };
+        assert_eq!(deploy.transfer_accounts(), None);
+    }
+
+    #[test]
+    fn should_interpret_payment_amount() {
+        let mut rng = crate::new_rng();
+        let mut deploy = create_deploy(&mut rng, DeployConfig::default().max_ttl, 0, "net-1");
+        assert_eq!(deploy.payment_amount(), Ok(None));
+
+        deploy.session = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::from(vec![1]),
+            args: RuntimeArgs::new(),
+        };
+        assert_eq!(
+            deploy.payment_amount(),
+            Err(DeployConfigurationFailure::MissingPaymentAmount)
+        );
+
+        deploy.payment = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::new(),
+            args: runtime_args! { "amount" => U512::from(10) },
+        };
+        assert_eq!(
+            deploy.payment_amount(),
+            Ok(Some(Motes::new(U512::from(10))))
+        );
+    }
 }
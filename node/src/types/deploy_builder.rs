@@ -0,0 +1,239 @@
+//! A builder of deterministic deploys for tests.
+
+use casper_execution_engine::core::engine_state::{
+    executable_deploy_item::ExecutableDeployItem, MAX_PAYMENT,
+};
+use casper_hashing::Digest;
+use casper_types::{
+    bytesrepr::Bytes, runtime_args, PublicKey, RuntimeArgs, SecretKey, TimeDiff, Timestamp, U512,
+};
+
+use super::{Deploy, DeployHash};
+
+/// The timestamp of built deploys, unless overridden.
+const DEFAULT_TIMESTAMP: u64 = 1_600_000_000_000;
+/// The chain name of built deploys, unless overridden.
+const DEFAULT_CHAIN_NAME: &str = "casper-example";
+/// The payment amount of built deploys, unless overridden.
+const DEFAULT_PAYMENT_AMOUNT: u64 = 10;
+
+/// Returns 32 bytes derived from `seed`, distinct for each `label`.
+pub(super) fn seeded_bytes(seed: u64, label: &str) -> [u8; Digest::LENGTH] {
+    Digest::hash([label.as_bytes(), &seed.to_le_bytes()].concat()).value()
+}
+
+/// Returns a secret key derived from `seed`, distinct for each `label`.
+pub(super) fn seeded_secret_key(seed: u64, label: &str) -> SecretKey {
+    SecretKey::ed25519_from_bytes(seeded_bytes(seed, label))
+        .expect("should create secret key from seeded bytes")
+}
+
+/// A builder of [`Deploy`]s.
+///
+/// Every property not explicitly set is either a fixed default or derived from the seed, so the
+/// same builder calls always produce the same deploy, including its hash and approval.  By default,
+/// the deploy is a valid native transfer of the maximum payment, paid for via standard payment.
+pub struct DeployBuilder {
+    seed: u64,
+    timestamp: Timestamp,
+    ttl: TimeDiff,
+    gas_price: u64,
+    dependencies: Vec<DeployHash>,
+    chain_name: String,
+    payment: Option<ExecutableDeployItem>,
+    session: Option<ExecutableDeployItem>,
+    account: Option<SecretKey>,
+}
+
+impl Default for DeployBuilder {
+    fn default() -> Self {
+        DeployBuilder {
+            seed: 0,
+            timestamp: Timestamp::from(DEFAULT_TIMESTAMP),
+            ttl: TimeDiff::from_seconds(30 * 60),
+            gas_price: 1,
+            dependencies: vec![],
+            chain_name: DEFAULT_CHAIN_NAME.to_string(),
+            payment: None,
+            session: None,
+            account: None,
+        }
+    }
+}
+
+impl DeployBuilder {
+    /// Sets the seed from which the properties not explicitly set are derived.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the timestamp.
+    pub fn timestamp(mut self, timestamp: Timestamp) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Sets the time to live.
+    pub fn ttl(mut self, ttl: TimeDiff) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sets the gas price.
+    pub fn gas_price(mut self, gas_price: u64) -> Self {
+        self.gas_price = gas_price;
+        self
+    }
+
+    /// Sets the dependencies.
+    pub fn dependencies(mut self, dependencies: Vec<DeployHash>) -> Self {
+        self.dependencies = dependencies;
+        self
+    }
+
+    /// Sets the chain name.
+    pub fn chain_name<S: Into<String>>(mut self, chain_name: S) -> Self {
+        self.chain_name = chain_name.into();
+        self
+    }
+
+    /// Sets the account sending and signing the deploy.
+    pub fn account(mut self, secret_key: SecretKey) -> Self {
+        self.account = Some(secret_key);
+        self
+    }
+
+    /// Sets the payment code.
+    pub fn payment(mut self, payment: ExecutableDeployItem) -> Self {
+        self.payment = Some(payment);
+        self
+    }
+
+    /// Sets the payment code to standard payment of `amount`.
+    pub fn payment_amount<T: Into<U512>>(self, amount: T) -> Self {
+        let args = runtime_args! {
+            "amount" => amount.into(),
+        };
+        self.payment(ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::new(),
+            args,
+        })
+    }
+
+    /// Sets the session code.
+    pub fn session(mut self, session: ExecutableDeployItem) -> Self {
+        self.session = Some(session);
+        self
+    }
+
+    /// Sets the session code to a native transfer with the given runtime args.
+    pub fn transfer_args(self, args: RuntimeArgs) -> Self {
+        self.session(ExecutableDeployItem::Transfer { args })
+    }
+
+    /// Returns the runtime args of a native transfer of `amount` between accounts derived from the
+    /// seed.
+    pub fn native_transfer_args<T: Into<U512>>(&self, amount: T) -> RuntimeArgs {
+        native_transfer_args(self.seed, amount.into())
+    }
+
+    /// Builds the deploy.
+    pub fn build(self) -> Deploy {
+        let DeployBuilder {
+            seed,
+            timestamp,
+            ttl,
+            gas_price,
+            dependencies,
+            chain_name,
+            payment,
+            session,
+            account,
+        } = self;
+        let payment = payment.unwrap_or_else(|| {
+            let args = runtime_args! {
+                "amount" => U512::from(DEFAULT_PAYMENT_AMOUNT),
+            };
+            ExecutableDeployItem::ModuleBytes {
+                module_bytes: Bytes::new(),
+                args,
+            }
+        });
+        let session = session.unwrap_or_else(|| ExecutableDeployItem::Transfer {
+            args: native_transfer_args(seed, *MAX_PAYMENT),
+        });
+        let secret_key = account.unwrap_or_else(|| seeded_secret_key(seed, "account"));
+
+        Deploy::new(
+            timestamp,
+            ttl,
+            gas_price,
+            dependencies,
+            chain_name,
+            payment,
+            session,
+            &secret_key,
+            None,
+        )
+    }
+}
+
+/// Returns the runtime args of a native transfer of `amount` between accounts derived from `seed`.
+fn native_transfer_args(seed: u64, amount: U512) -> RuntimeArgs {
+    let account_hash =
+        |label: &str| PublicKey::from(&seeded_secret_key(seed, label)).to_account_hash();
+    runtime_args! {
+        "amount" => amount,
+        "source" => account_hash("source"),
+        "target" => account_hash("target"),
+    }
+}
+
+impl Deploy {
+    /// Returns a builder of deterministic deploys.
+    pub fn builder() -> DeployBuilder {
+        DeployBuilder::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::CLValue;
+
+    use super::*;
+
+    #[test]
+    fn should_build_same_deploy_from_same_seed() {
+        let deploy = Deploy::builder().seed(7).build();
+        assert_eq!(deploy, Deploy::builder().seed(7).build());
+        assert_ne!(deploy.id(), Deploy::builder().seed(8).build().id());
+        assert_eq!(deploy.is_valid(), Ok(()));
+        assert!(deploy.session().is_transfer());
+        assert_eq!(
+            deploy.header().timestamp(),
+            Timestamp::from(DEFAULT_TIMESTAMP)
+        );
+    }
+
+    #[test]
+    fn should_apply_overrides() {
+        let secret_key = SecretKey::ed25519_from_bytes([3; 32]).unwrap();
+        let deploy = Deploy::builder()
+            .account(secret_key)
+            .payment_amount(25u64)
+            .chain_name("other-chain")
+            .gas_price(2)
+            .build();
+        assert_eq!(
+            deploy.header().account(),
+            &PublicKey::from(&SecretKey::ed25519_from_bytes([3; 32]).unwrap())
+        );
+        assert_eq!(deploy.header().chain_name(), "other-chain");
+        assert_eq!(deploy.header().gas_price(), 2);
+        assert_eq!(
+            deploy.payment().args().get("amount"),
+            Some(&CLValue::from_t(U512::from(25u64)).unwrap())
+        );
+    }
+}
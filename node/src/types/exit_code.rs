@@ -23,6 +23,14 @@ pub enum ExitCode {
     /// It is no longer used, but we keep it here to avoid it being reassigned to other features.
     #[doc(hidden)]
     DowngradeVersion = 102,
+    /// The process should exit with `103`.  The node has verified the next installed version and
+    /// recorded it in the upgrade handoff file.  The launcher should proceed to run the recorded
+    /// version of `casper-node`.
+    UpgradeHandoff = 103,
+    /// The process should exit with `104`.  The node was asked to stop via a `TERM` signal and
+    /// shut down in an orderly fashion.  The launcher should not proceed to run the next installed
+    /// version of `casper-node`.
+    CleanShutdown = 104,
     /// The exit code Rust uses by default when interrupted via an `INT` signal.
     SigInt = SIGNAL_OFFSET + SIGINT as u8,
     /// The exit code Rust uses by default when interrupted via a `QUIT` signal.
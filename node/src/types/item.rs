@@ -53,6 +53,9 @@ pub enum Tag {
     BlockHeaderBatch,
     /// Finality signatures for a block requested by the block's hash.
     FinalitySignaturesByHash,
+    /// All finality signatures held by a peer for a block requested by the block's hash, used to
+    /// backfill the signatures of a block already in storage.
+    BackfillFinalitySignaturesByHash,
 }
 
 /// A trait which allows an implementing type to be used by the gossiper and fetcher components, and
@@ -3,13 +3,15 @@
 mod account;
 mod auction_state;
 mod contracts;
+mod gas_profile;
 mod stored_value;
 
 use casper_types::{contracts::NamedKeys, NamedKey};
 
 pub use account::Account;
-pub use auction_state::AuctionState;
+pub use auction_state::{AuctionState, AuctionStatePage, AuctionSummary, JsonValidatorWeights};
 pub use contracts::{Contract, ContractPackage};
+pub use gas_profile::{GasProfile, HostFunctionGas};
 pub use stored_value::StoredValue;
 
 /// A helper function to change NamedKeys into a Vec<NamedKey>
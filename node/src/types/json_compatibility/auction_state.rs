@@ -1,7 +1,10 @@
 // TODO - remove once schemars stops causing warning.
 #![allow(clippy::field_reassign_with_default)]
 
-use std::collections::BTreeMap;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Bound,
+};
 
 use num_traits::Zero;
 use once_cell::sync::Lazy;
@@ -69,6 +72,29 @@ static AUCTION_INFO: Lazy<AuctionState> = Lazy::new(|| {
     AuctionState::new(state_root_hash, height, era_validators, bids)
 });
 
+static AUCTION_STATE_PAGE: Lazy<AuctionStatePage> = Lazy::new(|| {
+    let state_root_hash = Digest::from([11; Digest::LENGTH]);
+    let height: u64 = 10;
+    let era_validators = EraValidators::doc_example().clone();
+    let bids = Bids::doc_example().clone();
+    AuctionStatePage::new(
+        state_root_hash,
+        height,
+        &era_validators,
+        &bids,
+        None,
+        None,
+        bids.len(),
+    )
+});
+static AUCTION_SUMMARY: Lazy<AuctionSummary> = Lazy::new(|| {
+    let state_root_hash = Digest::from([11; Digest::LENGTH]);
+    let height: u64 = 10;
+    let era_validators = EraValidators::doc_example().clone();
+    let bids = Bids::doc_example().clone();
+    AuctionSummary::new(state_root_hash, height, &era_validators, &bids)
+});
+
 /// A validator's weight.
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -77,6 +103,12 @@ pub struct JsonValidatorWeights {
     weight: U512,
 }
 
+impl JsonValidatorWeights {
+    pub(crate) fn new(public_key: PublicKey, weight: U512) -> Self {
+        JsonValidatorWeights { public_key, weight }
+    }
+}
+
 /// The validators for the given era.
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -140,6 +172,46 @@ pub struct JsonBids {
     bid: JsonBid,
 }
 
+impl JsonBids {
+    fn new(public_key: &PublicKey, bid: &Bid) -> Self {
+        JsonBids {
+            public_key: public_key.clone(),
+            bid: JsonBid::from(bid.clone()),
+        }
+    }
+}
+
+/// The validators for the given era, along with their total weight.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct JsonEraValidatorsSummary {
+    era_id: EraId,
+    total_weight: U512,
+    validator_weights: Vec<JsonValidatorWeights>,
+}
+
+/// Converts the era validators into their JSON representation, keeping only the validators for
+/// which `include` returns `true`.
+fn json_era_validators<F>(era_validators: &EraValidators, include: F) -> Vec<JsonEraValidators>
+where
+    F: Fn(&PublicKey) -> bool,
+{
+    era_validators
+        .iter()
+        .map(|(era_id, validator_weights)| JsonEraValidators {
+            era_id: *era_id,
+            validator_weights: validator_weights
+                .iter()
+                .filter(|(public_key, _)| include(public_key))
+                .map(|(public_key, weight)| JsonValidatorWeights {
+                    public_key: public_key.clone(),
+                    weight: *weight,
+                })
+                .collect(),
+        })
+        .collect()
+}
+
 /// Data structure summarizing auction contract data.
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -162,20 +234,7 @@ impl AuctionState {
         era_validators: EraValidators,
         bids: Bids,
     ) -> Self {
-        let mut json_era_validators: Vec<JsonEraValidators> = Vec::new();
-        for (era_id, validator_weights) in era_validators.iter() {
-            let mut json_validator_weights: Vec<JsonValidatorWeights> = Vec::new();
-            for (public_key, weight) in validator_weights.iter() {
-                json_validator_weights.push(JsonValidatorWeights {
-                    public_key: public_key.clone(),
-                    weight: *weight,
-                });
-            }
-            json_era_validators.push(JsonEraValidators {
-                era_id: *era_id,
-                validator_weights: json_validator_weights,
-            });
-        }
+        let json_era_validators = json_era_validators(&era_validators, |_| true);
 
         let mut json_bids: Vec<JsonBids> = Vec::new();
         for (public_key, bid) in bids.iter() {
@@ -201,6 +260,141 @@ impl DocExample for AuctionState {
     }
 }
 
+/// Data structure containing a single page of the bids held by the auction contract.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AuctionStatePage {
+    /// Global state hash.
+    pub state_root_hash: Digest,
+    /// Block height.
+    pub block_height: u64,
+    /// Era validators, restricted to the requested validators if any were specified.
+    pub era_validators: Vec<JsonEraValidators>,
+    /// The bids contained in this page, ordered by public key.
+    bids: Vec<JsonBids>,
+    /// The public key of the last bid in this page if more bids are available, to be passed as
+    /// `after` to retrieve the next page.
+    pub next_page_after: Option<PublicKey>,
+}
+
+impl AuctionStatePage {
+    /// Creates a page holding at most `limit` bids.
+    ///
+    /// Only bids with public keys ordered after `after` are included, and if `validators` is
+    /// `Some`, only bids and weights belonging to those validators are included.
+    pub fn new(
+        state_root_hash: Digest,
+        block_height: u64,
+        era_validators: &EraValidators,
+        bids: &Bids,
+        validators: Option<&BTreeSet<PublicKey>>,
+        after: Option<&PublicKey>,
+        limit: usize,
+    ) -> Self {
+        let include = |public_key: &PublicKey| {
+            validators.map_or(true, |validators| validators.contains(public_key))
+        };
+
+        let lower_bound = after.map_or(Bound::Unbounded, Bound::Excluded);
+        let mut remaining_bids = bids
+            .range::<PublicKey, _>((lower_bound, Bound::Unbounded))
+            .filter(|(public_key, _)| include(public_key));
+        let json_bids: Vec<JsonBids> = remaining_bids
+            .by_ref()
+            .take(limit)
+            .map(|(public_key, bid)| JsonBids::new(public_key, bid))
+            .collect();
+        let next_page_after = if remaining_bids.next().is_some() {
+            json_bids
+                .last()
+                .map(|json_bids| json_bids.public_key.clone())
+        } else {
+            None
+        };
+
+        AuctionStatePage {
+            state_root_hash,
+            block_height,
+            era_validators: json_era_validators(era_validators, include),
+            bids: json_bids,
+            next_page_after,
+        }
+    }
+}
+
+impl DocExample for AuctionStatePage {
+    fn doc_example() -> &'static Self {
+        &*AUCTION_STATE_PAGE
+    }
+}
+
+/// Data structure summarizing the auction contract data without listing individual bids.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AuctionSummary {
+    /// Global state hash.
+    pub state_root_hash: Digest,
+    /// Block height.
+    pub block_height: u64,
+    /// Era validators along with the total weight per era.
+    pub era_validators: Vec<JsonEraValidatorsSummary>,
+    /// The number of bids, including inactive ones.
+    pub bid_count: u64,
+    /// The number of delegations across all bids.
+    pub delegation_count: u64,
+    /// The total amount staked by all bidders and their delegators.
+    pub total_staked: U512,
+}
+
+impl AuctionSummary {
+    /// Creates a new `AuctionSummary`.
+    pub fn new(
+        state_root_hash: Digest,
+        block_height: u64,
+        era_validators: &EraValidators,
+        bids: &Bids,
+    ) -> Self {
+        let era_validators = json_era_validators(era_validators, |_| true)
+            .into_iter()
+            .map(|json_era_validators| JsonEraValidatorsSummary {
+                era_id: json_era_validators.era_id,
+                total_weight: json_era_validators
+                    .validator_weights
+                    .iter()
+                    .fold(U512::zero(), |total, validator_weights| {
+                        total.saturating_add(validator_weights.weight)
+                    }),
+                validator_weights: json_era_validators.validator_weights,
+            })
+            .collect();
+
+        let mut delegation_count = 0;
+        let mut total_staked = U512::zero();
+        for bid in bids.values() {
+            delegation_count += bid.delegators().len() as u64;
+            total_staked = total_staked.saturating_add(*bid.staked_amount());
+            for delegator in bid.delegators().values() {
+                total_staked = total_staked.saturating_add(*delegator.staked_amount());
+            }
+        }
+
+        AuctionSummary {
+            state_root_hash,
+            block_height,
+            era_validators,
+            bid_count: bids.len() as u64,
+            delegation_count,
+            total_staked,
+        }
+    }
+}
+
+impl DocExample for AuctionSummary {
+    fn doc_example() -> &'static Self {
+        &*AUCTION_SUMMARY
+    }
+}
+
 impl DocExample for EraValidators {
     fn doc_example() -> &'static Self {
         &*ERA_VALIDATORS
@@ -212,3 +406,98 @@ impl DocExample for Bids {
         &*BIDS
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use super::*;
+
+    fn bids_and_validators(count: u8) -> (Bids, EraValidators) {
+        let mut bids = BTreeMap::new();
+        let mut validator_weights = BTreeMap::new();
+        for i in 1..=count {
+            let secret_key = SecretKey::ed25519_from_bytes([i; SecretKey::ED25519_LENGTH]).unwrap();
+            let public_key = PublicKey::from(&secret_key);
+            let bid = Bid::locked(
+                public_key.clone(),
+                URef::new([i; 32], AccessRights::READ_ADD_WRITE),
+                U512::from(i),
+                DelegationRate::zero(),
+                0,
+            );
+            bids.insert(public_key.clone(), bid);
+            validator_weights.insert(public_key, U512::from(i));
+        }
+        let mut era_validators = BTreeMap::new();
+        era_validators.insert(EraId::from(1u64), validator_weights);
+        (bids, era_validators)
+    }
+
+    #[test]
+    fn should_page_through_all_bids() {
+        let (bids, era_validators) = bids_and_validators(5);
+        let state_root_hash = Digest::from([1; Digest::LENGTH]);
+
+        let mut seen = vec![];
+        let mut after = None;
+        loop {
+            let page = AuctionStatePage::new(
+                state_root_hash,
+                1,
+                &era_validators,
+                &bids,
+                None,
+                after.as_ref(),
+                2,
+            );
+            assert!(page.bids.len() <= 2);
+            seen.extend(page.bids.iter().map(|bid| bid.public_key.clone()));
+            match page.next_page_after {
+                Some(public_key) => after = Some(public_key),
+                None => break,
+            }
+        }
+
+        assert_eq!(seen, bids.keys().cloned().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn should_filter_by_validator() {
+        let (bids, era_validators) = bids_and_validators(5);
+        let requested = bids.keys().nth(3).unwrap().clone();
+        let validators = iter::once(requested.clone()).collect();
+
+        let page = AuctionStatePage::new(
+            Digest::from([1; Digest::LENGTH]),
+            1,
+            &era_validators,
+            &bids,
+            Some(&validators),
+            None,
+            100,
+        );
+
+        assert_eq!(page.bids.len(), 1);
+        assert_eq!(page.bids[0].public_key, requested);
+        assert!(page.next_page_after.is_none());
+        assert_eq!(page.era_validators[0].validator_weights.len(), 1);
+        assert_eq!(
+            page.era_validators[0].validator_weights[0].public_key,
+            requested
+        );
+    }
+
+    #[test]
+    fn should_summarize_totals() {
+        let (bids, era_validators) = bids_and_validators(4);
+
+        let summary =
+            AuctionSummary::new(Digest::from([1; Digest::LENGTH]), 1, &era_validators, &bids);
+
+        assert_eq!(summary.bid_count, 4);
+        assert_eq!(summary.delegation_count, 0);
+        assert_eq!(summary.total_staked, U512::from(10));
+        assert_eq!(summary.era_validators[0].total_weight, U512::from(10));
+    }
+}
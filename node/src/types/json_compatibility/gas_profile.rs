@@ -0,0 +1,114 @@
+// TODO - remove once schemars stops causing warning.
+#![allow(clippy::field_reassign_with_default)]
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+};
+
+use once_cell::sync::Lazy;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use casper_execution_engine::core::engine_state::GasProfile as ExecutionEngineGasProfile;
+use casper_types::U512;
+
+use crate::rpcs::docs::DocExample;
+
+static GAS_PROFILE: Lazy<GasProfile> = Lazy::new(|| {
+    let mut host_functions = BTreeMap::new();
+    host_functions.insert(
+        "casper_write".to_string(),
+        HostFunctionGas {
+            calls: 2,
+            cost: U512::from(28_000),
+        },
+    );
+    let mut opcode_classes = BTreeMap::new();
+    opcode_classes.insert("local".to_string(), U512::from(7_800));
+    opcode_classes.insert("store".to_string(), U512::from(9_400));
+    GasProfile {
+        host_functions,
+        opcode_classes,
+        other: U512::zero(),
+        total: U512::from(45_200),
+    }
+});
+
+/// The gas consumed by calls to a single host function.
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct HostFunctionGas {
+    /// The number of times the host function was called.
+    pub calls: u64,
+    /// The total gas charged while executing the host function.
+    pub cost: U512,
+}
+
+/// A breakdown of the gas consumed executing a deploy.
+///
+/// The cost of each metered block of Wasm is split across the opcode classes it contains in
+/// proportion to their costs in the chainspec, so the per-class figures are estimates.
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GasProfile {
+    /// The gas consumed per host function, keyed by the host function's import name.
+    pub host_functions: BTreeMap<String, HostFunctionGas>,
+    /// The gas consumed per class of Wasm opcodes, keyed by the name of the class in the
+    /// chainspec's opcode cost table.
+    pub opcode_classes: BTreeMap<String, U512>,
+    /// The gas charged outside of any host function or Wasm opcode.
+    pub other: U512,
+    /// The total gas recorded in the profile.
+    pub total: U512,
+}
+
+impl From<&ExecutionEngineGasProfile> for GasProfile {
+    fn from(ee_gas_profile: &ExecutionEngineGasProfile) -> Self {
+        GasProfile {
+            host_functions: ee_gas_profile
+                .host_functions()
+                .iter()
+                .map(|(name, usage)| {
+                    let usage = HostFunctionGas {
+                        calls: usage.calls,
+                        cost: usage.cost.value(),
+                    };
+                    (name.clone(), usage)
+                })
+                .collect(),
+            opcode_classes: ee_gas_profile
+                .opcode_classes()
+                .iter()
+                .map(|(class, cost)| (class.to_string(), cost.value()))
+                .collect(),
+            other: ee_gas_profile.other().value(),
+            total: ee_gas_profile.total().value(),
+        }
+    }
+}
+
+impl Display for GasProfile {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(formatter, "host functions:")?;
+        for (name, usage) in &self.host_functions {
+            writeln!(
+                formatter,
+                "  {}: {} ({} calls)",
+                name, usage.cost, usage.calls
+            )?;
+        }
+        writeln!(formatter, "opcode classes:")?;
+        for (class, cost) in &self.opcode_classes {
+            writeln!(formatter, "  {}: {}", class, cost)?;
+        }
+        writeln!(formatter, "other: {}", self.other)?;
+        write!(formatter, "total: {}", self.total)
+    }
+}
+
+impl DocExample for GasProfile {
+    fn doc_example() -> &'static Self {
+        &*GAS_PROFILE
+    }
+}
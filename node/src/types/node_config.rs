@@ -1,8 +1,12 @@
+use std::path::PathBuf;
+
 use datasize::DataSize;
+use num::rational::Ratio;
 use serde::{Deserialize, Serialize};
 
 use crate::types::BlockHash;
 
+use casper_hashing::Digest;
 use casper_types::TimeDiff;
 
 /// Maximum number of fetch-deploy tasks to run in parallel during chain synchronization.
@@ -10,15 +14,42 @@ const DEFAULT_MAX_PARALLEL_DEPLOY_FETCHES: u32 = 5000;
 /// Maximum number of fetch-trie tasks to run in parallel during chain synchronization.
 const DEFAULT_MAX_PARALLEL_TRIE_FETCHES: u32 = 5000;
 const DEFAULT_MAX_PARALLEL_BLOCK_FETCHES: u32 = 50;
+/// Maximum number of blocks to fetch ahead of the block being executed during fast sync.
+const DEFAULT_MAX_BLOCKS_TO_PREFETCH: u32 = 10;
 const DEFAULT_MAX_SYNC_FETCH_ATTEMPTS: u32 = 5;
 const DEFAULT_PEER_REDEMPTION_INTERVAL: u32 = 10_000;
+const DEFAULT_PEER_LATENCY_WEIGHT: u32 = 1;
+const DEFAULT_PEER_THROUGHPUT_WEIGHT: u32 = 1;
 const DEFAULT_RETRY_INTERVAL: &str = "100ms";
+const DEFAULT_FINALITY_SIGNATURE_BACKFILL_INTERVAL: &str = "10min";
+
+/// The role the node plays in the network.
+#[derive(Clone, Copy, DataSize, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeMode {
+    /// The node loads its validator key and participates in consensus in every era in which the
+    /// key is one of the validators'.
+    Validator,
+    /// The node never loads a validator key nor participates in consensus, but still validates and
+    /// executes all blocks and their finality signatures.
+    Observer,
+}
+
+impl Default for NodeMode {
+    fn default() -> Self {
+        NodeMode::Validator
+    }
+}
 
 /// Node fast-sync configuration.
 #[derive(DataSize, Debug, Deserialize, Serialize, Clone)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
 #[serde(deny_unknown_fields)]
 pub struct NodeConfig {
+    /// Whether the node runs as a validator or an observer.
+    #[serde(default)]
+    pub node_mode: NodeMode,
+
     /// Hash used as a trust anchor when joining, if any.
     pub trusted_hash: Option<BlockHash>,
 
@@ -28,6 +59,10 @@ pub struct NodeConfig {
     /// Maximum number of blocks to fetch in parallel.
     pub max_parallel_block_fetches: u32,
 
+    /// Maximum number of blocks to fetch ahead of the block being executed during fast sync.
+    #[serde(default = "default_max_blocks_to_prefetch")]
+    pub max_blocks_to_prefetch: u32,
+
     /// Maximum number of trie nodes to fetch in parallel.
     pub max_parallel_trie_fetches: u32,
 
@@ -42,22 +77,105 @@ pub struct NodeConfig {
     /// How many items to fetch before redeeming a random peer.
     pub sync_peer_redemption_interval: u32,
 
+    /// The weight given to a peer's response time, relative to the fastest peer's, when choosing
+    /// which peers to sync from.
+    #[serde(default = "default_sync_peer_latency_weight")]
+    pub sync_peer_latency_weight: u32,
+
+    /// The weight given to a peer's throughput, relative to the fastest peer's, when choosing
+    /// which peers to sync from.  If both weights are zero, peers are chosen at random.
+    #[serde(default = "default_sync_peer_throughput_weight")]
+    pub sync_peer_throughput_weight: u32,
+
     /// Whether to run in sync-to-genesis mode which captures all data (blocks, deploys
     /// and global state) back to genesis.
     pub sync_to_genesis: bool,
+
+    /// Whether to run as a light node, which syncs and verifies only block headers and their
+    /// finality signatures, then keeps following the chain without executing blocks or
+    /// participating in consensus.
+    #[serde(default)]
+    pub light_sync: bool,
+
+    /// The state root hash designated by the emergency restart in the chainspec, if any.  The
+    /// node refuses to apply an emergency restart unless the operator confirms it by setting this.
+    pub confirm_emergency_restart: Option<Digest>,
+
+    /// The folder where the `casper-node` binaries are installed in subdirs named after their
+    /// protocol versions.  If set, the binary staged for the next upgrade is verified and hashed
+    /// along with its chainspec ahead of the upgrade's activation point.
+    pub upgrade_binary_dir: Option<PathBuf>,
+
+    /// If set, the node records the verified upgrade in this file when it exits to upgrade, and
+    /// exits with a distinct exit code for the launcher.
+    pub upgrade_handoff_file: Option<PathBuf>,
+
+    /// Whether to fetch further finality signatures from peers for each stored block whose
+    /// signatures' total weight doesn't exceed `finality_signature_backfill_threshold`.
+    #[serde(default)]
+    pub backfill_finality_signatures: bool,
+
+    /// The fraction of the total validator weight which the stored finality signatures of each
+    /// block should exceed, expressed as `[numerator, denominator]`.
+    #[data_size(skip)]
+    #[serde(default = "default_finality_signature_backfill_threshold")]
+    pub finality_signature_backfill_threshold: Ratio<u64>,
+
+    /// The duration for which to pause between passes over the stored blocks while backfilling
+    /// finality signatures.
+    #[serde(default = "default_finality_signature_backfill_interval")]
+    pub finality_signature_backfill_interval: TimeDiff,
 }
 
 impl Default for NodeConfig {
     fn default() -> NodeConfig {
         NodeConfig {
+            node_mode: NodeMode::default(),
             trusted_hash: None,
             max_parallel_deploy_fetches: DEFAULT_MAX_PARALLEL_DEPLOY_FETCHES,
             max_parallel_block_fetches: DEFAULT_MAX_PARALLEL_BLOCK_FETCHES,
+            max_blocks_to_prefetch: DEFAULT_MAX_BLOCKS_TO_PREFETCH,
             max_parallel_trie_fetches: DEFAULT_MAX_PARALLEL_TRIE_FETCHES,
             max_sync_fetch_attempts: DEFAULT_MAX_SYNC_FETCH_ATTEMPTS,
             retry_interval: DEFAULT_RETRY_INTERVAL.parse().unwrap(),
             sync_peer_redemption_interval: DEFAULT_PEER_REDEMPTION_INTERVAL,
+            sync_peer_latency_weight: DEFAULT_PEER_LATENCY_WEIGHT,
+            sync_peer_throughput_weight: DEFAULT_PEER_THROUGHPUT_WEIGHT,
             sync_to_genesis: false,
+            light_sync: false,
+            confirm_emergency_restart: None,
+            upgrade_binary_dir: None,
+            upgrade_handoff_file: None,
+            backfill_finality_signatures: false,
+            finality_signature_backfill_threshold: default_finality_signature_backfill_threshold(),
+            finality_signature_backfill_interval: default_finality_signature_backfill_interval(),
         }
     }
 }
+
+/// The default for `NodeConfig::max_blocks_to_prefetch`.
+fn default_max_blocks_to_prefetch() -> u32 {
+    DEFAULT_MAX_BLOCKS_TO_PREFETCH
+}
+
+/// The default for `NodeConfig::sync_peer_latency_weight`.
+fn default_sync_peer_latency_weight() -> u32 {
+    DEFAULT_PEER_LATENCY_WEIGHT
+}
+
+/// The default for `NodeConfig::sync_peer_throughput_weight`.
+fn default_sync_peer_throughput_weight() -> u32 {
+    DEFAULT_PEER_THROUGHPUT_WEIGHT
+}
+
+/// The default for `NodeConfig::finality_signature_backfill_threshold`.
+fn default_finality_signature_backfill_threshold() -> Ratio<u64> {
+    Ratio::new(2, 3)
+}
+
+/// The default for `NodeConfig::finality_signature_backfill_interval`.
+fn default_finality_signature_backfill_interval() -> TimeDiff {
+    DEFAULT_FINALITY_SIGNATURE_BACKFILL_INTERVAL
+        .parse()
+        .unwrap()
+}
@@ -0,0 +1,167 @@
+// TODO - remove once schemars stops causing warning.
+#![allow(clippy::field_reassign_with_default)]
+
+use std::fmt::{self, Display, Formatter};
+
+use datasize::DataSize;
+use once_cell::sync::OnceCell;
+use prometheus::{IntGauge, Opts, Registry};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{reactor::participating::Config, types::NodeMode, unregister_metric};
+
+/// The features the node was started with.
+///
+/// We use a static variable since the features are reported by several components and the
+/// networking handshake, none of which otherwise have access to the whole config.
+static NODE_FEATURES: OnceCell<NodeFeatures> = OnceCell::new();
+
+/// The optional features and modes of operation enabled on the node, as configured.
+#[derive(
+    Clone, Copy, DataSize, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema,
+)]
+// Missing features default to disabled, so that features can be added without breaking
+// handshakes with peers running older versions.
+#[serde(default)]
+pub struct NodeFeatures {
+    /// Whether the node syncs and retains the whole chain history back to genesis.
+    pub archival: bool,
+    /// Whether the node never participates in consensus, either as it runs in observer mode or as
+    /// it only follows the chain by syncing block headers and finality signatures.
+    pub observer: bool,
+    /// Whether the speculative execution server is enabled.
+    pub speculative_exec: bool,
+    /// Whether the JSON-RPC server is enabled.
+    pub rpc_server: bool,
+    /// Whether the REST server is enabled.
+    pub rest_server: bool,
+    /// Whether the SSE event stream server is enabled.
+    pub event_stream_server: bool,
+    /// Whether missing finality signatures of historical blocks are fetched from peers.
+    pub finality_signature_backfill: bool,
+    /// Whether the node answers peers' requests for the finality signatures of stored blocks to
+    /// backfill.  Always enabled, so that such requests are not sent to peers running older
+    /// versions, which can't answer them.
+    pub finality_signature_backfill_server: bool,
+}
+
+impl NodeFeatures {
+    /// Returns the features enabled by the given config.
+    pub(crate) fn from_config(config: &Config) -> Self {
+        NodeFeatures {
+            archival: config.node.sync_to_genesis,
+            observer: config.node.node_mode == NodeMode::Observer || config.node.light_sync,
+            speculative_exec: config.speculative_exec_server.enable_server,
+            rpc_server: config.rpc_server.enable_server,
+            rest_server: config.rest_server.enable_server,
+            event_stream_server: config.event_stream_server.enable_server,
+            finality_signature_backfill: config.node.backfill_finality_signatures,
+            finality_signature_backfill_server: true,
+        }
+    }
+
+    /// Registers these as the features the node was started with.
+    ///
+    /// Only the first call has any effect.
+    pub(crate) fn register(self) {
+        drop(NODE_FEATURES.set(self));
+    }
+
+    /// Returns the features the node was started with, or all features disabled if none were
+    /// registered.
+    pub(crate) fn current() -> Self {
+        NODE_FEATURES.get().copied().unwrap_or_default()
+    }
+
+    /// Returns the name of each feature along with whether it is enabled.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, bool)> {
+        vec![
+            ("archival", self.archival),
+            ("observer", self.observer),
+            ("speculative_exec", self.speculative_exec),
+            ("rpc_server", self.rpc_server),
+            ("rest_server", self.rest_server),
+            ("event_stream_server", self.event_stream_server),
+            (
+                "finality_signature_backfill",
+                self.finality_signature_backfill,
+            ),
+            (
+                "finality_signature_backfill_server",
+                self.finality_signature_backfill_server,
+            ),
+        ]
+        .into_iter()
+    }
+}
+
+impl Display for NodeFeatures {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        let mut enabled = self
+            .iter()
+            .filter(|(_, is_enabled)| *is_enabled)
+            .map(|(name, _)| name);
+        match enabled.next() {
+            Some(first) => {
+                write!(formatter, "{}", first)?;
+                enabled.try_for_each(|name| write!(formatter, ", {}", name))
+            }
+            None => write!(formatter, "none"),
+        }
+    }
+}
+
+/// Metric exposing the node's features as labels, each set to `true` or `false`.
+#[derive(Debug)]
+pub(crate) struct NodeFeaturesMetric {
+    node_features: IntGauge,
+    /// Instance of registry to unregister from when being dropped.
+    registry: Registry,
+}
+
+impl NodeFeaturesMetric {
+    /// Creates and registers the metric for the given features.
+    pub(crate) fn new(
+        features: NodeFeatures,
+        registry: &Registry,
+    ) -> Result<Self, prometheus::Error> {
+        let opts = features.iter().fold(
+            Opts::new(
+                "node_features",
+                "the optional features enabled on the node, as labels; always 1",
+            ),
+            |opts, (name, is_enabled)| opts.const_label(name, is_enabled.to_string()),
+        );
+        let node_features = IntGauge::with_opts(opts)?;
+        node_features.set(1);
+        registry.register(Box::new(node_features.clone()))?;
+        Ok(NodeFeaturesMetric {
+            node_features,
+            registry: registry.clone(),
+        })
+    }
+}
+
+impl Drop for NodeFeaturesMetric {
+    fn drop(&mut self) {
+        unregister_metric!(self.registry, self.node_features);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_display_enabled_features() {
+        assert_eq!(NodeFeatures::default().to_string(), "none");
+
+        let features = NodeFeatures {
+            archival: true,
+            rpc_server: true,
+            ..NodeFeatures::default()
+        };
+        assert_eq!(features.to_string(), "archival, rpc_server");
+    }
+}
@@ -142,7 +142,9 @@ mod tests {
             .expect("could not deserialize value");
         match msg {
             Message::Payload(payload) => payload,
-            Message::Handshake { .. } => panic!("expected payload"),
+            Message::Handshake { .. } | Message::Envelope(_) | Message::Goodbye => {
+                panic!("expected payload")
+            }
         }
     }
 
@@ -4,6 +4,7 @@
 use std::{
     collections::BTreeMap,
     net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::Arc,
     time::Duration,
 };
 
@@ -13,15 +14,17 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use casper_hashing::Digest;
-use casper_types::{EraId, ProtocolVersion, PublicKey, TimeDiff, Timestamp};
+use casper_types::{
+    crypto, EraId, ProtocolVersion, PublicKey, SecretKey, Signature, TimeDiff, Timestamp,
+};
 
 use crate::{
     components::{
-        chain_synchronizer::Progress,
+        chain_synchronizer::{Progress, SyncPhase, SyncProgress},
         chainspec_loader::NextUpgrade,
         rpc_server::rpcs::docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
     },
-    types::{ActivationPoint, Block, BlockHash, NodeId, PeersMap},
+    types::{ActivationPoint, Block, BlockHash, NodeFeatures, NodeId, PeersMap},
 };
 
 static CHAINSPEC_INFO: Lazy<ChainspecInfo> = Lazy::new(|| {
@@ -49,6 +52,19 @@ static GET_STATUS_RESULT: Lazy<GetStatusResult> = Lazy::new(|| {
         version: crate::VERSION_STRING.as_str(),
         node_uptime: Duration::from_secs(13),
         node_state: NodeState::Participating,
+        sync_progress: SyncProgress {
+            phase: SyncPhase::Finished,
+            blocks_remaining: None,
+            tries_fetched: 0,
+            estimated_time_remaining: None,
+        },
+        features: NodeFeatures {
+            rpc_server: true,
+            rest_server: true,
+            event_stream_server: true,
+            ..NodeFeatures::default()
+        },
+        status_signing_key: None,
     };
     GetStatusResult::new(status_feed, DOCS_EXAMPLE_PROTOCOL_VERSION)
 });
@@ -89,6 +105,9 @@ pub enum NodeState {
     },
     /// The node is currently in the participating state.
     Participating,
+    /// The node is running as a light node and has synced up to the tip of the chain, which it
+    /// keeps following by syncing block headers and finality signatures only.
+    Observing,
 }
 
 /// Data feed for client "info_get_status" endpoint.
@@ -110,6 +129,13 @@ pub struct StatusFeed {
     pub node_uptime: Duration,
     /// The current state of node.
     pub node_state: NodeState,
+    /// A summary of the progress of chain synchronization.
+    pub sync_progress: SyncProgress,
+    /// The optional features enabled on the node.
+    pub features: NodeFeatures,
+    /// The key to sign the status with, if status responses are to be signed.
+    #[serde(skip)]
+    pub status_signing_key: Option<Arc<SecretKey>>,
 }
 
 impl StatusFeed {
@@ -120,6 +146,8 @@ impl StatusFeed {
         consensus_status: Option<(PublicKey, Option<TimeDiff>)>,
         node_uptime: Duration,
         node_state: NodeState,
+        sync_progress: SyncProgress,
+        status_signing_key: Option<Arc<SecretKey>>,
     ) -> Self {
         let (our_public_signing_key, round_length) = match consensus_status {
             Some((public_key, round_length)) => (Some(public_key), round_length),
@@ -134,6 +162,9 @@ impl StatusFeed {
             version: crate::VERSION_STRING.as_str(),
             node_uptime,
             node_state,
+            sync_progress,
+            features: NodeFeatures::current(),
+            status_signing_key,
         }
     }
 }
@@ -163,6 +194,17 @@ impl From<Block> for MinimalBlockInfo {
     }
 }
 
+/// A signature of a [`GetStatusResult`] by the node which produced it.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct StatusAttestation {
+    /// The public key of the signing node.
+    pub public_key: PublicKey,
+    /// The signature of the compact JSON encoding of the response, excluding the `attestation`
+    /// field.
+    pub signature: Signature,
+}
+
 /// Result for "info_get_status" RPC response.
 #[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -191,12 +233,19 @@ pub struct GetStatusResult {
     pub uptime: TimeDiff,
     /// The current state of node.
     pub node_state: NodeState,
+    /// A summary of the progress of chain synchronization.
+    pub sync_progress: SyncProgress,
+    /// The optional features enabled on the node.
+    pub features: NodeFeatures,
+    /// The node's signature of this response, if status responses are signed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attestation: Option<StatusAttestation>,
 }
 
 impl GetStatusResult {
     #[allow(deprecated)]
     pub(crate) fn new(status_feed: StatusFeed, api_version: ProtocolVersion) -> Self {
-        GetStatusResult {
+        let mut result = GetStatusResult {
             api_version,
             chainspec_name: status_feed.chainspec_info.name,
             starting_state_root_hash: Digest::from([0u8; 32]),
@@ -207,13 +256,54 @@ impl GetStatusResult {
             next_upgrade: status_feed.chainspec_info.next_upgrade,
             uptime: status_feed.node_uptime.into(),
             node_state: status_feed.node_state,
+            sync_progress: status_feed.sync_progress,
+            features: status_feed.features,
             #[cfg(not(test))]
             build_version: crate::VERSION_STRING.clone(),
 
             //  Prevent these values from changing between test sessions
             #[cfg(test)]
             build_version: String::from("1.0.0-xxxxxxxxx@DEBUG"),
+            attestation: None,
+        };
+        if let Some(secret_key) = status_feed.status_signing_key {
+            result.attest(&secret_key);
         }
+        result
+    }
+
+    /// Returns the message signed by the attestation: the compact JSON encoding of `self` without
+    /// the `attestation` field.
+    fn attested_message(&self) -> Vec<u8> {
+        let mut value = serde_json::to_value(self).expect("should encode status as JSON");
+        if let Some(object) = value.as_object_mut() {
+            object.remove("attestation");
+        }
+        serde_json::to_vec(&value).expect("should encode status as JSON")
+    }
+
+    /// Signs the response with `secret_key`, replacing any previous attestation.
+    pub(crate) fn attest(&mut self, secret_key: &SecretKey) {
+        let public_key = PublicKey::from(secret_key);
+        let signature = crypto::sign(self.attested_message(), secret_key, &public_key);
+        self.attestation = Some(StatusAttestation {
+            public_key,
+            signature,
+        });
+    }
+
+    /// Verifies the attestation, returning the public key of the signing node.
+    ///
+    /// Returns `None` if there is no attestation, or it is invalid.
+    pub fn verify_attestation(&self) -> Option<&PublicKey> {
+        let attestation = self.attestation.as_ref()?;
+        crypto::verify(
+            self.attested_message(),
+            &attestation.signature,
+            &attestation.public_key,
+        )
+        .ok()?;
+        Some(&attestation.public_key)
     }
 }
 
@@ -222,3 +312,30 @@ impl DocExample for GetStatusResult {
         &*GET_STATUS_RESULT
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_verify_attested_status() {
+        let secret_key = SecretKey::ed25519_from_bytes([7; 32]).unwrap();
+        let json = serde_json::to_value(GetStatusResult::doc_example()).unwrap();
+        let mut status: GetStatusResult = serde_json::from_value(json).unwrap();
+        assert!(status.verify_attestation().is_none());
+
+        status.attest(&secret_key);
+        assert_eq!(
+            status.verify_attestation(),
+            Some(&PublicKey::from(&secret_key))
+        );
+
+        // The attestation survives a roundtrip through JSON.
+        let json = serde_json::to_string(&status).unwrap();
+        let mut status: GetStatusResult = serde_json::from_str(&json).unwrap();
+        assert!(status.verify_attestation().is_some());
+
+        status.uptime = TimeDiff::from_seconds(14);
+        assert!(status.verify_attestation().is_none());
+    }
+}
@@ -0,0 +1,29 @@
+use std::collections::BTreeMap;
+
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+use casper_types::PublicKey;
+
+/// How a validator performed during an era.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, DataSize)]
+pub struct ValidatorPerformance {
+    /// The number of the era's blocks proposed by the validator.
+    pub blocks_proposed: u64,
+    /// The number of rounds in which the validator was assigned but did not create any unit.
+    ///
+    /// Only known to nodes which took part in the era's consensus.
+    pub rounds_missed: Option<u64>,
+    /// The number of the era's blocks for which this node holds a finality signature by the
+    /// validator.
+    pub finality_signatures: u64,
+    /// Whether the validator was reported as an equivocator in the era's switch block.
+    pub equivocated: bool,
+}
+
+/// How each validator performed during an era.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, DataSize)]
+pub struct EraValidatorPerformance {
+    /// The performance of each validator, by public key.
+    pub validators: BTreeMap<PublicKey, ValidatorPerformance>,
+}
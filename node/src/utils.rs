@@ -5,9 +5,12 @@ mod display_error;
 pub(crate) mod ds;
 mod external;
 pub(crate) mod fmt_limit;
+pub(crate) mod key_file;
+pub(crate) mod listener;
 pub(crate) mod opt_display;
 pub(crate) mod rlimit;
 pub(crate) mod round_robin;
+pub(crate) mod sd_notify;
 pub(crate) mod umask;
 pub mod work_queue;
 
@@ -27,7 +30,7 @@ use std::{
 };
 
 use datasize::DataSize;
-use hyper::server::{conn::AddrIncoming, Builder, Server};
+use hyper::server::conn::AddrIncoming;
 #[cfg(test)]
 use once_cell::sync::Lazy;
 use prometheus::{self, Histogram, HistogramOpts, Registry};
@@ -131,15 +134,28 @@ pub(crate) enum ListeningError {
         /// The failure reason.
         error: Box<dyn std::error::Error + Send + Sync>,
     },
+
+    /// Failed to listen on a unix domain socket.
+    #[error("failed to listen on {}: {error}", .path.display())]
+    ListenUnix {
+        /// The path of the socket attempted to listen on.
+        path: PathBuf,
+        /// The failure reason.
+        error: io::Error,
+    },
+
+    /// Neither TCP nor a unix domain socket are enabled.
+    #[error("neither TCP nor a unix domain socket are enabled to listen on")]
+    NothingToListenOn,
 }
 
-pub(crate) fn start_listening(address: &str) -> Result<Builder<AddrIncoming>, ListeningError> {
+pub(crate) fn start_listening(address: &str) -> Result<AddrIncoming, ListeningError> {
     let address = resolve_address(address).map_err(|error| {
         warn!(%error, %address, "failed to start HTTP server, cannot parse address");
         ListeningError::ResolveAddress(error)
     })?;
 
-    Server::try_bind(&address).map_err(|error| {
+    AddrIncoming::bind(&address).map_err(|error| {
         warn!(%error, %address, "failed to start HTTP server");
         ListeningError::Listen {
             address,
@@ -60,6 +60,16 @@ impl External {
     where
         T: Loadable,
         P: AsRef<Path>,
+    {
+        self.load_with(root, |path| T::from_path(path))
+    }
+
+    /// Like `load`, but loads the value using `load_fn` rather than a `Loadable` implementation.
+    pub fn load_with<T, E, P, F>(self, root: P, load_fn: F) -> Result<T, LoadError<E>>
+    where
+        E: Debug + Display,
+        P: AsRef<Path>,
+        F: FnOnce(&Path) -> Result<T, E>,
     {
         match self {
             External::Path(path) => {
@@ -69,7 +79,7 @@ impl External {
                     path
                 };
 
-                T::from_path(&full_path).map_err(move |error| LoadError::Failed {
+                load_fn(&full_path).map_err(move |error| LoadError::Failed {
                     error,
                     // We canonicalize `full_path` here, with `ReadFileError` we get extra
                     // information about the absolute path this way if the latter is relative. It
@@ -0,0 +1,299 @@
+//! Listeners of the HTTP servers, accepting connections via TCP, a unix domain socket or both.
+
+use std::{
+    fmt::{self, Display, Formatter},
+    fs, io,
+    net::{IpAddr, Ipv4Addr},
+    path::{Path, PathBuf},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use hyper::server::{
+    accept::Accept,
+    conn::{AddrIncoming, AddrStream},
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    net::{UnixListener, UnixStream},
+};
+use tracing::{debug, warn};
+
+use super::{umask, ListeningError};
+
+/// Sets up a UNIX socket listener at the given path.
+///
+/// If the socket already exists, an attempt to delete it is made. Errors during deletion are
+/// ignored, but may cause the subsequent socket opening to fail.
+pub(crate) fn setup_unix_listener<P: AsRef<Path>>(
+    path: P,
+    socket_umask: umask::Mode,
+) -> io::Result<UnixListener> {
+    let socket_path = path.as_ref();
+
+    // This would be racy, but no one is racing us for the socket, so we'll just do a naive
+    // check-then-delete :).
+    if socket_path.exists() {
+        debug!(socket_path=%socket_path.display(), "found stale socket file, trying to remove");
+        match fs::remove_file(&socket_path) {
+            Ok(_) => {
+                debug!("stale socket file removed");
+            }
+            Err(err) => {
+                // This happens if a background program races us for the removal, as it usually
+                // means the file is already gone. We can ignore this, but make note of it in the
+                // log.
+                warn!(%err, "could not remove stale socket file, assuming race with other process");
+            }
+        }
+    }
+
+    // This is not thread-safe, as it will set the umask for the entire process, but we assume that
+    // initalization happens "sufficiently single-threaded".
+    let umask_guard = umask::temp_umask(socket_umask);
+    let listener = UnixListener::bind(socket_path)?;
+    drop(umask_guard);
+
+    Ok(listener)
+}
+
+/// Accepts the connections of an HTTP server via TCP, a unix domain socket or both.
+pub(crate) struct Listener {
+    /// The TCP listener, if listening via TCP.
+    tcp: Option<AddrIncoming>,
+    /// The unix domain socket listener and its path, if listening via a unix domain socket.
+    unix: Option<(UnixListener, PathBuf)>,
+}
+
+impl Listener {
+    /// Listens via TCP only.
+    pub(crate) fn tcp(tcp: AddrIncoming) -> Self {
+        Listener {
+            tcp: Some(tcp),
+            unix: None,
+        }
+    }
+
+    /// Listens via TCP, if `tcp` is given, and via the unix domain socket at `unix_socket_path`, if
+    /// given, which is created with the given `umask`.
+    pub(crate) fn bind(
+        tcp: Option<AddrIncoming>,
+        unix_socket_path: Option<PathBuf>,
+        socket_umask: umask::Mode,
+    ) -> Result<Self, ListeningError> {
+        let unix = match unix_socket_path {
+            Some(path) => {
+                let listener = setup_unix_listener(&path, socket_umask).map_err(|error| {
+                    warn!(%error, path = %path.display(), "failed to start HTTP server");
+                    ListeningError::ListenUnix {
+                        path: path.clone(),
+                        error,
+                    }
+                })?;
+                Some((listener, path))
+            }
+            None => None,
+        };
+
+        if tcp.is_none() && unix.is_none() {
+            return Err(ListeningError::NothingToListenOn);
+        }
+
+        Ok(Listener { tcp, unix })
+    }
+}
+
+impl Display for Listener {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match (&self.tcp, &self.unix) {
+            (Some(tcp), Some((_, path))) => {
+                write!(f, "{} and {}", tcp.local_addr(), path.display())
+            }
+            (Some(tcp), None) => write!(f, "{}", tcp.local_addr()),
+            (None, Some((_, path))) => write!(f, "{}", path.display()),
+            (None, None) => f.write_str("nothing"),
+        }
+    }
+}
+
+impl Accept for Listener {
+    type Conn = Connection;
+    type Error = io::Error;
+
+    fn poll_accept(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Conn, Self::Error>>> {
+        let this = self.get_mut();
+
+        if let Some(tcp) = this.tcp.as_mut() {
+            if let Poll::Ready(accepted) = Pin::new(tcp).poll_accept(cx) {
+                return Poll::Ready(accepted.map(|result| result.map(Connection::Tcp)));
+            }
+        }
+
+        if let Some((unix, _)) = this.unix.as_ref() {
+            if let Poll::Ready(accepted) = unix.poll_accept(cx) {
+                return Poll::Ready(Some(accepted.map(|(stream, _)| Connection::Unix(stream))));
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+/// A connection accepted by a [`Listener`].
+pub(crate) enum Connection {
+    /// A connection via TCP.
+    Tcp(AddrStream),
+    /// A connection via the unix domain socket.
+    Unix(UnixStream),
+}
+
+impl Connection {
+    /// Returns the IP address of the client.
+    ///
+    /// Clients connected via the unix domain socket are local, so the loopback address is returned
+    /// for them.
+    pub(crate) fn remote_ip(&self) -> IpAddr {
+        match self {
+            Connection::Tcp(stream) => stream.remote_addr().ip(),
+            Connection::Unix(_) => IpAddr::V4(Ipv4Addr::LOCALHOST),
+        }
+    }
+}
+
+impl AsyncRead for Connection {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Connection::Tcp(stream) => Pin::new(stream).poll_read(cx, buf),
+            Connection::Unix(stream) => Pin::new(stream).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for Connection {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            Connection::Tcp(stream) => Pin::new(stream).poll_write(cx, buf),
+            Connection::Unix(stream) => Pin::new(stream).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            Connection::Tcp(stream) => Pin::new(stream).poll_write_vectored(cx, bufs),
+            Connection::Unix(stream) => Pin::new(stream).poll_write_vectored(cx, bufs),
+        }
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        match self {
+            Connection::Tcp(stream) => stream.is_write_vectored(),
+            Connection::Unix(stream) => stream.is_write_vectored(),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Connection::Tcp(stream) => Pin::new(stream).poll_flush(cx),
+            Connection::Unix(stream) => Pin::new(stream).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Connection::Tcp(stream) => Pin::new(stream).poll_shutdown(cx),
+            Connection::Unix(stream) => Pin::new(stream).poll_shutdown(cx),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        os::unix::prelude::{FileTypeExt, PermissionsExt},
+    };
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::UnixStream,
+    };
+
+    use super::setup_unix_listener;
+
+    #[tokio::test]
+    async fn setup_listener_creates_listener() {
+        const TEST_MESSAGE: &[u8] = b"hello, world!";
+
+        let tmpdir = tempfile::tempdir().expect("could not create tempdir");
+        let socket_path = tmpdir.path().join("test.socket");
+
+        // We give it a strict umask to check.
+        let listener = setup_unix_listener(&socket_path, 0o077).expect("could not setup listener");
+
+        let meta = fs::metadata(&socket_path).expect("could not get metadata");
+        // With the given umask, world and group permissions should be 0.
+        assert_eq!(meta.permissions().mode() & 0o077, 0);
+
+        // Attempt to connect.
+        tokio::spawn(async move {
+            let mut stream = UnixStream::connect(socket_path)
+                .await
+                .expect("could not connect to listener");
+            stream
+                .write_all(TEST_MESSAGE)
+                .await
+                .expect("could not write to listener");
+        });
+
+        let (mut stream, _socket_addr) = listener
+            .accept()
+            .await
+            .expect("could not accept connection");
+
+        let mut buffer = Vec::new();
+        stream
+            .read_to_end(&mut buffer)
+            .await
+            .expect("failed to read to end");
+        assert_eq!(TEST_MESSAGE, buffer.as_slice());
+    }
+
+    #[tokio::test]
+    async fn setup_listener_removes_previous_listener() {
+        let tmpdir = tempfile::tempdir().expect("could not create tempdir");
+        let socket_path = tmpdir.path().join("overwrite-me.socket");
+
+        fs::write(&socket_path, b"this-file-should-be-deleted-soon")
+            .expect("could not write to socket-blocking temporary file");
+
+        let meta = fs::metadata(&socket_path).expect("could not get metadata");
+        assert!(
+            !meta.file_type().is_socket(),
+            "temporary file created should not be a socket"
+        );
+
+        // Creating the listener should remove the underlying file.
+        let _listener = setup_unix_listener(&socket_path, 0o022).expect("could not setup listener");
+
+        let meta = fs::metadata(&socket_path).expect("could not get metadata");
+        assert!(
+            meta.file_type().is_socket(),
+            "did not overwrite previous file"
+        );
+    }
+}
@@ -9,11 +9,16 @@ use std::{
     fmt::Debug,
     hash::Hash,
     num::NonZeroUsize,
-    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Mutex as SyncMutex, MutexGuard as SyncMutexGuard,
+    },
+    time::{Duration, Instant},
 };
 
 use enum_iterator::IntoEnumIterator;
 use serde::Serialize;
+use thiserror::Error;
 use tokio::sync::{Mutex, MutexGuard, Semaphore};
 use tracing::debug;
 
@@ -27,6 +32,10 @@ use tracing::debug;
 /// are passed to the constructor function.
 ///
 /// The scheduler keeps track internally which queue needs to be popped next.
+///
+/// Queues may be given a capacity, which is only enforced when pushing via `try_push`. Producers of
+/// events which can be dropped under load use it to shed load once a queue is full, while all
+/// other events are still accepted via `push`.
 #[derive(Debug)]
 pub struct WeightedRoundRobin<I, K> {
     /// Current iteration state.
@@ -45,6 +54,11 @@ pub struct WeightedRoundRobin<I, K> {
     sealed: AtomicBool,
 }
 
+/// Error returned when trying to push an item to a queue which is at its capacity.
+#[derive(Copy, Clone, Debug, Error, PartialEq, Eq)]
+#[error("queue is full")]
+pub(crate) struct QueueFull;
+
 /// State that wraps queue and its event count.
 #[derive(Debug)]
 struct QueueState<I> {
@@ -53,13 +67,24 @@ struct QueueState<I> {
     /// Do not modify this unless you are holding the `queue` lock.
     event_count: AtomicUsize,
     queue: Mutex<VecDeque<I>>,
+    /// The times at which the items in the queue were pushed, oldest first.
+    ///
+    /// Do not modify this unless you are holding the `queue` lock.
+    enqueued_at: SyncMutex<VecDeque<Instant>>,
+    /// The maximum number of items accepted by `try_push_back`, if limited.
+    capacity: Option<NonZeroUsize>,
+    /// The number of items rejected by `try_push_back` because the queue was full.
+    dropped_count: AtomicUsize,
 }
 
 impl<I> QueueState<I> {
-    fn new() -> Self {
+    fn new(capacity: Option<NonZeroUsize>) -> Self {
         QueueState {
             event_count: AtomicUsize::new(0),
             queue: Mutex::new(VecDeque::new()),
+            enqueued_at: SyncMutex::new(VecDeque::new()),
+            capacity,
+            dropped_count: AtomicUsize::new(0),
         }
     }
 
@@ -67,25 +92,64 @@ impl<I> QueueState<I> {
     async fn drain(&self) -> Vec<I> {
         let mut guard = self.queue.lock().await;
         let events: Vec<I> = guard.drain(..).collect();
+        self.enqueued_times().clear();
         self.event_count.fetch_sub(events.len(), Ordering::SeqCst);
         events
     }
 
     #[inline]
     async fn push_back(&self, element: I) {
-        self.queue.lock().await.push_back(element);
+        let mut guard = self.queue.lock().await;
+        guard.push_back(element);
+        self.enqueued_times().push_back(Instant::now());
         self.event_count.fetch_add(1, Ordering::SeqCst);
     }
 
+    /// Pushes an item unless the queue is at its capacity.
+    async fn try_push_back(&self, element: I) -> Result<(), QueueFull> {
+        let mut guard = self.queue.lock().await;
+        if let Some(capacity) = self.capacity {
+            if guard.len() >= capacity.get() {
+                self.dropped_count.fetch_add(1, Ordering::SeqCst);
+                return Err(QueueFull);
+            }
+        }
+        guard.push_back(element);
+        self.enqueued_times().push_back(Instant::now());
+        self.event_count.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Decrements the event count after an item has been popped, returning the time the item
+    /// spent in the queue.
     #[inline]
-    fn dec_count(&self) {
+    fn dec_count(&self) -> Duration {
+        let enqueued_at = self.enqueued_times().pop_front();
         self.event_count.fetch_sub(1, Ordering::SeqCst);
+        enqueued_at.map_or(Duration::ZERO, |enqueued_at| enqueued_at.elapsed())
     }
 
     #[inline]
     fn event_count(&self) -> usize {
         self.event_count.load(Ordering::SeqCst)
     }
+
+    /// Returns how long the oldest item in the queue has been waiting, if any.
+    fn oldest_item_age(&self) -> Option<Duration> {
+        self.enqueued_times()
+            .front()
+            .map(|enqueued_at| enqueued_at.elapsed())
+    }
+
+    /// Locks the enqueue times.
+    ///
+    /// The lock is only ever held briefly and never across an await point, so a poisoned lock can
+    /// only result from a panic in `VecDeque` itself, whose state is still consistent.
+    fn enqueued_times(&self) -> SyncMutexGuard<'_, VecDeque<Instant>> {
+        self.enqueued_at
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
 }
 
 /// The inner state of the queue iteration.
@@ -175,11 +239,21 @@ where
     /// Creates a queue for each pair given in `weights`. The second component of each `weight` is
     /// the number of times to return items from one queue before moving on to the next one.
     pub(crate) fn new(weights: Vec<(K, NonZeroUsize)>) -> Self {
+        Self::with_capacities(weights, HashMap::new())
+    }
+
+    /// Creates a new weighted round-robin scheduler whose queues have the given capacities.
+    ///
+    /// Queues not listed in `capacities` are unbounded.
+    pub(crate) fn with_capacities(
+        weights: Vec<(K, NonZeroUsize)>,
+        capacities: HashMap<K, NonZeroUsize>,
+    ) -> Self {
         assert!(!weights.is_empty(), "must provide at least one slot");
 
         let queues = weights
             .iter()
-            .map(|(idx, _)| (*idx, QueueState::new()))
+            .map(|(idx, _)| (*idx, QueueState::new(capacities.get(idx).copied())))
             .collect();
         let slots: Vec<Slot<K>> = weights
             .into_iter()
@@ -223,10 +297,43 @@ where
         self.total.add_permits(1);
     }
 
+    /// Pushes an item to a queue identified by key, unless the queue is at its capacity.
+    ///
+    /// Items pushed into a sealed queue are dropped, as with `push`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the queue identified by key `queue` does not exist.
+    pub(crate) async fn try_push(&self, item: I, queue: K) -> Result<(), QueueFull> {
+        if self.sealed.load(Ordering::SeqCst) {
+            debug!("queue sealed, dropping item");
+            return Ok(());
+        }
+
+        self.queues
+            .get(&queue)
+            .expect("tried to push to non-existent queue")
+            .try_push_back(item)
+            .await?;
+
+        // We increase the item count after we've put the item into the queue.
+        self.total.add_permits(1);
+        Ok(())
+    }
+
     /// Returns the next item from queue.
     ///
     /// Asynchronously waits until a queue is non-empty or panics if an internal error occurred.
+    #[cfg(test)]
     pub(crate) async fn pop(&self) -> (I, K) {
+        let (item, queue, _wait_time) = self.pop_with_wait_time().await;
+        (item, queue)
+    }
+
+    /// Returns the next item from queue, along with the time it spent waiting in the queue.
+    ///
+    /// Asynchronously waits until a queue is non-empty or panics if an internal error occurred.
+    pub(crate) async fn pop_with_wait_time(&self) -> (I, K, Duration) {
         // Safe to `expect` here as the only way for acquiring a permit to fail would be if the
         // `self.total` semaphore were closed.
         self.total.acquire().await.expect("should acquire").forget();
@@ -257,8 +364,8 @@ where
                 .pop_front()
                 // We hold the queue's lock and checked `is_empty` earlier.
                 .expect("item disappeared. this should not happen");
-            queue_state.dec_count();
-            break (item, inner.active_slot.key);
+            let wait_time = queue_state.dec_count();
+            break (item, inner.active_slot.key, wait_time);
         }
     }
 
@@ -312,11 +419,27 @@ where
             .map(|(key, queue)| (*key, queue.event_count()))
             .collect()
     }
+
+    /// Returns the number of items rejected by `try_push` in each of the queues.
+    pub(crate) fn dropped_counts(&self) -> HashMap<K, usize> {
+        self.queues
+            .iter()
+            .map(|(key, queue)| (*key, queue.dropped_count.load(Ordering::SeqCst)))
+            .collect()
+    }
+
+    /// Returns how long the oldest item in each of the non-empty queues has been waiting.
+    pub(crate) fn oldest_item_ages(&self) -> HashMap<K, Duration> {
+        self.queues
+            .iter()
+            .filter_map(|(key, queue)| Some((*key, queue.oldest_item_age()?)))
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::num::NonZeroUsize;
+    use std::{iter, num::NonZeroUsize};
 
     use futures::{future::FutureExt, join};
 
@@ -384,4 +507,53 @@ mod tests {
         assert_eq!(scheduler.item_count(), 0);
         assert!(scheduler.drain_queues().await.is_empty());
     }
+
+    #[tokio::test]
+    async fn should_track_wait_times() {
+        let scheduler = WeightedRoundRobin::<char, QueueKind>::new(weights());
+        assert!(scheduler.oldest_item_ages().is_empty());
+
+        scheduler.push('a', QueueKind::One).await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        scheduler.push('b', QueueKind::One).await;
+
+        let ages = scheduler.oldest_item_ages();
+        assert_eq!(ages.len(), 1);
+        assert!(ages[&QueueKind::One] >= Duration::from_millis(20));
+
+        let (item, queue, wait_time) = scheduler.pop_with_wait_time().await;
+        assert_eq!((item, queue), ('a', QueueKind::One));
+        assert!(wait_time >= Duration::from_millis(20));
+
+        let (item, _, _) = scheduler.pop_with_wait_time().await;
+        assert_eq!(item, 'b');
+        assert!(scheduler.oldest_item_ages().is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_reject_items_beyond_capacity() {
+        let capacities = iter::once((QueueKind::One, NonZeroUsize::new(2).unwrap())).collect();
+        let scheduler =
+            WeightedRoundRobin::<char, QueueKind>::with_capacities(weights(), capacities);
+
+        assert_eq!(scheduler.try_push('a', QueueKind::One).await, Ok(()));
+        assert_eq!(scheduler.try_push('b', QueueKind::One).await, Ok(()));
+        assert_eq!(
+            scheduler.try_push('c', QueueKind::One).await,
+            Err(QueueFull)
+        );
+        assert_eq!(scheduler.dropped_counts()[&QueueKind::One], 1);
+
+        // Unbounded pushes are still accepted, as are pushes to queues without a capacity.
+        scheduler.push('d', QueueKind::One).await;
+        assert_eq!(scheduler.try_push('e', QueueKind::Two).await, Ok(()));
+        assert_eq!(scheduler.item_count(), 4);
+        assert_eq!(scheduler.dropped_counts()[&QueueKind::Two], 0);
+
+        // Once items have been popped, there is room again.
+        assert_eq!(('a', QueueKind::One), scheduler.pop().await);
+        assert_eq!(('e', QueueKind::Two), scheduler.pop().await);
+        assert_eq!(('b', QueueKind::One), scheduler.pop().await);
+        assert_eq!(scheduler.try_push('f', QueueKind::One).await, Ok(()));
+    }
 }
@@ -0,0 +1,151 @@
+//! Notifications to the systemd service manager.
+//!
+//! When run as a systemd service of `Type=notify`, the node reports readiness once it has finished
+//! joining the network and, if the unit sets `WatchdogSec`, keeps pinging the watchdog for as long
+//! as the reactor makes progress.  If the node is not run by systemd, i.e. `NOTIFY_SOCKET` is not
+//! set, all notifications are no-ops.
+
+use std::{
+    env,
+    ffi::OsStr,
+    io,
+    os::unix::{ffi::OsStrExt, net::UnixDatagram},
+    process,
+    time::Duration,
+};
+
+use prometheus::IntCounter;
+use tracing::{debug, warn};
+
+use super::SharedFlag;
+
+/// Environment variable holding the path of the socket to send notifications to.
+const NOTIFY_SOCKET: &str = "NOTIFY_SOCKET";
+/// Environment variable holding the watchdog timeout in microseconds.
+const WATCHDOG_USEC: &str = "WATCHDOG_USEC";
+/// Environment variable holding the PID of the process expected to ping the watchdog.
+const WATCHDOG_PID: &str = "WATCHDOG_PID";
+
+/// Sends `state` to the notification socket at `socket_path`.
+fn send_to(socket_path: &OsStr, state: &str) -> io::Result<()> {
+    // Sockets in the abstract namespace cannot be addressed with the standard library yet.
+    if socket_path.as_bytes().starts_with(b"@") {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "abstract notification sockets are not supported",
+        ));
+    }
+    let socket = UnixDatagram::unbound()?;
+    socket.send_to(state.as_bytes(), socket_path)?;
+    Ok(())
+}
+
+/// Sends `state` to systemd, if the node is run by systemd.
+fn notify(state: &str) {
+    let socket_path = match env::var_os(NOTIFY_SOCKET) {
+        Some(socket_path) => socket_path,
+        None => return,
+    };
+    match send_to(&socket_path, state) {
+        Ok(()) => debug!(%state, "notified systemd"),
+        Err(error) => warn!(%error, %state, "failed to notify systemd"),
+    }
+}
+
+/// Notifies systemd that the node has finished starting up.
+pub(crate) fn ready() {
+    notify("READY=1");
+}
+
+/// Notifies systemd that the node is shutting down.
+pub(crate) fn stopping() {
+    notify("STOPPING=1");
+}
+
+/// Returns the watchdog timeout, if systemd expects this process to ping the watchdog.
+pub(crate) fn watchdog_timeout() -> Option<Duration> {
+    parse_watchdog_timeout(
+        env::var(WATCHDOG_USEC).ok().as_deref(),
+        env::var(WATCHDOG_PID).ok().as_deref(),
+        process::id(),
+    )
+}
+
+/// Parses the watchdog timeout from the values of `WATCHDOG_USEC` and `WATCHDOG_PID`.
+fn parse_watchdog_timeout(usec: Option<&str>, pid: Option<&str>, our_pid: u32) -> Option<Duration> {
+    let usec = usec?.parse::<u64>().ok().filter(|usec| *usec > 0)?;
+    // The watchdog may be meant for another process, e.g. if the node was started by a wrapper.
+    if let Some(pid) = pid {
+        if pid.parse::<u32>().ok()? != our_pid {
+            return None;
+        }
+    }
+    Some(Duration::from_micros(usec))
+}
+
+/// Pings the watchdog twice per `timeout` for as long as the reactor makes progress, until the
+/// reactor shuts down.
+///
+/// The reactor is considered live if it has dispatched an event since the last check, or if it is
+/// idle, i.e. `queued_events` returns zero.  A reactor stuck on an event while others are queued
+/// stops the pings, so systemd restarts the node once `timeout` has passed.
+pub(crate) async fn run_watchdog<F>(
+    timeout: Duration,
+    dispatched_events: IntCounter,
+    queued_events: F,
+    is_shutting_down: SharedFlag,
+) where
+    F: Fn() -> usize,
+{
+    let mut last_dispatched = dispatched_events.get();
+    loop {
+        tokio::time::sleep(timeout / 2).await;
+        if is_shutting_down.is_set() {
+            debug!("reactor is shutting down, no longer pinging the watchdog");
+            return;
+        }
+        let dispatched = dispatched_events.get();
+        if dispatched != last_dispatched || queued_events() == 0 {
+            notify("WATCHDOG=1");
+        } else {
+            warn!(%dispatched, "reactor made no progress, not pinging the watchdog");
+        }
+        last_dispatched = dispatched;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_watchdog_timeout() {
+        assert_eq!(
+            parse_watchdog_timeout(Some("30000000"), None, 7),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_watchdog_timeout(Some("30000000"), Some("7"), 7),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(parse_watchdog_timeout(Some("30000000"), Some("8"), 7), None);
+        assert_eq!(parse_watchdog_timeout(Some("0"), None, 7), None);
+        assert_eq!(parse_watchdog_timeout(Some("soon"), None, 7), None);
+        assert_eq!(parse_watchdog_timeout(None, Some("7"), 7), None);
+    }
+
+    #[test]
+    fn should_send_state_to_notification_socket() {
+        let tmpdir = tempfile::tempdir().expect("could not create tempdir");
+        let socket_path = tmpdir.path().join("notify.socket");
+        let socket = UnixDatagram::bind(&socket_path).expect("could not bind socket");
+
+        send_to(socket_path.as_os_str(), "READY=1").expect("could not notify");
+
+        let mut buffer = [0; 64];
+        let received = socket.recv(&mut buffer).expect("could not receive");
+        assert_eq!(&buffer[..received], b"READY=1");
+
+        assert!(send_to(OsStr::new("@abstract"), "READY=1").is_err());
+    }
+}
@@ -6,7 +6,7 @@ use core::mem::MaybeUninit;
 use casper_types::{
     account::AccountHash,
     api_error,
-    bytesrepr::{self, FromBytes},
+    bytesrepr::{self, FromBytes, ToBytes},
     contracts::{ContractVersion, NamedKeys},
     system::CallStackElement,
     ApiError, BlockTime, CLTyped, CLValue, ContractHash, ContractPackageHash, Key, Phase,
@@ -354,6 +354,22 @@ pub fn random_bytes() -> [u8; RANDOM_BYTES_COUNT] {
     ret
 }
 
+/// Emits an event with the given name and value.
+///
+/// The node publishes the events emitted during a deploy's execution, in the order in which they
+/// were emitted, but only if the deploy's session code executes successfully.  The name must be
+/// non-empty and at most 64 bytes long.
+///
+/// Only available from protocol version 1.5.0.  Wasm calling this function fails to execute on a
+/// network running an earlier protocol version.
+pub fn emit_event<T: CLTyped + ToBytes>(name: &str, value: T) {
+    let cl_value = CLValue::from_t(value).unwrap_or_revert();
+    let (value_ptr, value_size, _bytes) = contract_api::to_ptr(cl_value);
+    let result =
+        unsafe { ext_ffi::casper_emit_event(name.as_ptr(), name.len(), value_ptr, value_size) };
+    api_error::result_from(result).unwrap_or_revert()
+}
+
 fn read_host_buffer_into(dest: &mut [u8]) -> Result<usize, ApiError> {
     let mut bytes_written = MaybeUninit::uninit();
     let ret = unsafe {
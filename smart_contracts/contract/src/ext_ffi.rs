@@ -790,4 +790,22 @@ extern "C" {
     /// * `out_ptr` - pointer to the location where argument bytes will be copied from the host side
     /// * `out_size` - size of output pointer
     pub fn casper_random_bytes(out_ptr: *mut u8, out_size: usize) -> i32;
+    /// Emits an event with the given name and value, to be published by the node along with the
+    /// execution result of the deploy.
+    ///
+    /// Events are only recorded during the session phase, and only if the deploy's session code
+    /// executes successfully.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_ptr` - pointer to the UTF-8 bytes of the event's name
+    /// * `name_size` - size of the name (in bytes), at most 64
+    /// * `value_ptr` - pointer to bytes representing the serialized `CLValue` of the event
+    /// * `value_size` - size of the value (in bytes)
+    pub fn casper_emit_event(
+        name_ptr: *const u8,
+        name_size: usize,
+        value_ptr: *const u8,
+        value_size: usize,
+    ) -> i32;
 }
@@ -0,0 +1,49 @@
+#![no_std]
+#![no_main]
+
+#[macro_use]
+extern crate alloc;
+
+use alloc::string::ToString;
+
+use casper_contract::contract_api::{runtime, storage};
+use casper_types::{
+    contracts::{EntryPoint, EntryPoints},
+    ApiError, CLType, EntryPointAccess, EntryPointType, RuntimeArgs,
+};
+
+const ENTRY_POINT_EMIT: &str = "emit";
+const EVENT_NAME: &str = "counter";
+const CONTRACT_EVENT_NAME: &str = "from_contract";
+const ARG_REVERT: &str = "revert";
+
+#[no_mangle]
+pub extern "C" fn emit() {
+    runtime::emit_event(CONTRACT_EVENT_NAME, 2u64);
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let entry_points = {
+        let mut entry_points = EntryPoints::new();
+        let entry_point = EntryPoint::new(
+            ENTRY_POINT_EMIT.to_string(),
+            vec![],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        entry_points.add_entry_point(entry_point);
+        entry_points
+    };
+    let (contract_hash, _contract_version) = storage::new_contract(entry_points, None, None, None);
+
+    // Emit the same event twice, around one emitted by the stored contract.
+    runtime::emit_event(EVENT_NAME, 1u64);
+    runtime::call_contract::<()>(contract_hash, ENTRY_POINT_EMIT, RuntimeArgs::new());
+    runtime::emit_event(EVENT_NAME, 3u64);
+
+    if runtime::get_named_arg::<bool>(ARG_REVERT) {
+        runtime::revert(ApiError::User(0));
+    }
+}
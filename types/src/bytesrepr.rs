@@ -12,6 +12,7 @@ use alloc::{
 #[cfg(debug_assertions)]
 use core::any;
 use core::{
+    cmp,
     convert::TryInto,
     fmt::{self, Display, Formatter},
     mem,
@@ -483,7 +484,9 @@ impl<T: FromBytes> FromBytes for Vec<T> {
 
         let (count, mut stream) = u32::from_bytes(bytes)?;
 
-        let mut result = try_vec_with_capacity(count as usize)?;
+        // Every element other than a zero-sized one occupies at least one byte, so the count is not
+        // trusted beyond the remaining input when preallocating.
+        let mut result = try_vec_with_capacity(cmp::min(count as usize, stream.len()))?;
         for _ in 0..count {
             let (value, remainder) = T::from_bytes(stream)?;
             result.push(value);
@@ -1329,6 +1332,13 @@ mod tests {
         assert_eq!(result.unwrap_err(), Error::Formatting);
     }
 
+    #[test]
+    fn should_not_preallocate_beyond_input_for_vec() {
+        let malicious_bytes = u32::MAX.to_bytes().unwrap();
+        let result: Result<Vec<u64>, Error> = super::deserialize(malicious_bytes);
+        assert_eq!(result.unwrap_err(), Error::EarlyEndOfStream);
+    }
+
     #[cfg(debug_assertions)]
     #[test]
     #[should_panic(expected = "You should use Bytes newtype wrapper for efficiency")]
@@ -14,7 +14,7 @@ use datasize::DataSize;
 use num_rational::Ratio;
 #[cfg(feature = "json-schema")]
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::{
     bytesrepr::{self, FromBytes, ToBytes},
@@ -51,7 +51,10 @@ const CL_TYPE_TAG_PUBLIC_KEY: u8 = 22;
 /// Casper types, i.e. types which can be stored and manipulated by smart contracts.
 ///
 /// Provides a description of the underlying data type of a [`CLValue`](crate::CLValue).
-#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Serialize, Deserialize, Debug)]
+///
+/// Note that this type has a custom `Deserialize` implementation limiting the nesting depth, in
+/// order to avoid a stack overflow when deserializing crafted input.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Serialize, Debug)]
 #[cfg_attr(feature = "datasize", derive(DataSize))]
 #[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 #[serde(deny_unknown_fields)]
@@ -217,6 +220,117 @@ impl FromBytes for CLType {
     }
 }
 
+/// Mirror of `CLType` used to derive its `Deserialize` implementation.
+///
+/// Nested types are deserialized via `CLType`'s own implementation, which tracks the nesting
+/// depth.  The variants must be kept in the same order as those of `CLType`.
+#[derive(Deserialize)]
+#[serde(rename = "CLType", deny_unknown_fields)]
+enum CLTypeRepr {
+    Bool,
+    I32,
+    I64,
+    U8,
+    U32,
+    U64,
+    U128,
+    U256,
+    U512,
+    Unit,
+    String,
+    Key,
+    URef,
+    PublicKey,
+    Option(Box<CLType>),
+    List(Box<CLType>),
+    ByteArray(u32),
+    Result {
+        ok: Box<CLType>,
+        err: Box<CLType>,
+    },
+    Map {
+        key: Box<CLType>,
+        value: Box<CLType>,
+    },
+    Tuple1([Box<CLType>; 1]),
+    Tuple2([Box<CLType>; 2]),
+    Tuple3([Box<CLType>; 3]),
+    Any,
+}
+
+impl From<CLTypeRepr> for CLType {
+    fn from(repr: CLTypeRepr) -> Self {
+        match repr {
+            CLTypeRepr::Bool => CLType::Bool,
+            CLTypeRepr::I32 => CLType::I32,
+            CLTypeRepr::I64 => CLType::I64,
+            CLTypeRepr::U8 => CLType::U8,
+            CLTypeRepr::U32 => CLType::U32,
+            CLTypeRepr::U64 => CLType::U64,
+            CLTypeRepr::U128 => CLType::U128,
+            CLTypeRepr::U256 => CLType::U256,
+            CLTypeRepr::U512 => CLType::U512,
+            CLTypeRepr::Unit => CLType::Unit,
+            CLTypeRepr::String => CLType::String,
+            CLTypeRepr::Key => CLType::Key,
+            CLTypeRepr::URef => CLType::URef,
+            CLTypeRepr::PublicKey => CLType::PublicKey,
+            CLTypeRepr::Option(inner_type) => CLType::Option(inner_type),
+            CLTypeRepr::List(inner_type) => CLType::List(inner_type),
+            CLTypeRepr::ByteArray(len) => CLType::ByteArray(len),
+            CLTypeRepr::Result { ok, err } => CLType::Result { ok, err },
+            CLTypeRepr::Map { key, value } => CLType::Map { key, value },
+            CLTypeRepr::Tuple1(inner_types) => CLType::Tuple1(inner_types),
+            CLTypeRepr::Tuple2(inner_types) => CLType::Tuple2(inner_types),
+            CLTypeRepr::Tuple3(inner_types) => CLType::Tuple3(inner_types),
+            CLTypeRepr::Any => CLType::Any,
+        }
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+thread_local! {
+    /// The nesting depth of the `CLType` being deserialized on this thread.
+    static DESERIALIZATION_DEPTH: core::cell::Cell<u8> = core::cell::Cell::new(0);
+}
+
+/// Guard incrementing the deserialization nesting depth while alive.
+#[cfg(any(feature = "std", test))]
+struct DeserializationDepthGuard;
+
+#[cfg(any(feature = "std", test))]
+impl DeserializationDepthGuard {
+    /// Enters a nested `CLType`, returning `None` if the recursion depth limit is exceeded.
+    fn enter() -> Option<Self> {
+        DESERIALIZATION_DEPTH.with(|depth| {
+            if depth.get() >= CL_TYPE_RECURSION_DEPTH {
+                return None;
+            }
+            depth.set(depth.get() + 1);
+            Some(DeserializationDepthGuard)
+        })
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl Drop for DeserializationDepthGuard {
+    fn drop(&mut self) {
+        DESERIALIZATION_DEPTH.with(|depth| depth.set(depth.get() - 1));
+    }
+}
+
+impl<'de> Deserialize<'de> for CLType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Without `std` there is no thread-local storage to track the depth in, so it is not
+        // limited.  Nodes, which deserialize untrusted input, always enable `std`.
+        #[cfg(any(feature = "std", test))]
+        let _guard = DeserializationDepthGuard::enter().ok_or_else(|| {
+            <D::Error as serde::de::Error>::custom("exceeded recursion depth deserializing CLType")
+        })?;
+        CLTypeRepr::deserialize(deserializer).map(CLType::from)
+    }
+}
+
 fn depth_limited_from_bytes(depth: u8, bytes: &[u8]) -> Result<(CLType, &[u8]), bytesrepr::Error> {
     if depth >= CL_TYPE_RECURSION_DEPTH {
         return Err(bytesrepr::Error::ExceededRecursionDepth);
@@ -728,6 +842,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn deserializing_nested_option_cltype_should_not_stack_overflow() {
+        fn nested_option(depth: u8) -> CLType {
+            (1..depth).fold(CLType::Bool, |inner_type, _| {
+                CLType::Option(Box::new(inner_type))
+            })
+        }
+
+        let deepest = nested_option(CL_TYPE_RECURSION_DEPTH);
+        let bytes = bincode::serialize(&deepest).unwrap();
+        assert_eq!(bincode::deserialize::<CLType>(&bytes).unwrap(), deepest);
+        let json = serde_json::to_string(&deepest).unwrap();
+        assert_eq!(serde_json::from_str::<CLType>(&json).unwrap(), deepest);
+
+        let too_deep = nested_option(CL_TYPE_RECURSION_DEPTH + 1);
+        let bytes = bincode::serialize(&too_deep).unwrap();
+        assert!(bincode::deserialize::<CLType>(&bytes).is_err());
+        let json = serde_json::to_string(&too_deep).unwrap();
+        assert!(serde_json::from_str::<CLType>(&json).is_err());
+
+        // A failed attempt must not leave the depth counter raised.
+        let json = serde_json::to_string(&deepest).unwrap();
+        assert_eq!(serde_json::from_str::<CLType>(&json).unwrap(), deepest);
+    }
+
     #[test]
     fn any_should_work() {
         #[derive(PartialEq, Debug, Clone)]
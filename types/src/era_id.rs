@@ -55,6 +55,13 @@ impl EraId {
         (current_era_id..=current_era_id + num_eras).map(EraId)
     }
 
+    /// Returns an iterator over era IDs from `start` up to and including `end`.
+    ///
+    /// The iterator is empty if `end` is earlier than `start`.
+    pub fn range_inclusive(start: EraId, end: EraId) -> impl Iterator<Item = EraId> {
+        (start.0..=end.0).map(EraId)
+    }
+
     /// Returns a successor to current era.
     ///
     /// For `u64::MAX`, this returns `u64::MAX` again: We want to make sure this doesn't panic, and
@@ -218,6 +225,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_iterate_over_inclusive_range() {
+        let start = EraId::from(5);
+        let end = EraId::from(7);
+
+        let eras: Vec<EraId> = EraId::range_inclusive(start, end).collect();
+        assert_eq!(eras, vec![start, start.successor(), end]);
+        assert_eq!(EraId::range_inclusive(end, end).count(), 1);
+        assert_eq!(EraId::range_inclusive(end, start).count(), 0);
+        assert_eq!(EraId::range_inclusive(EraId::MAX, EraId::MAX).count(), 1);
+    }
+
     #[test]
     fn should_have_valid_genesis_era_id() {
         let expected_initial_era_id = EraId::from(0);
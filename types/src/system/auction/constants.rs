@@ -96,3 +96,6 @@ pub const AUCTION_DELAY_KEY: &str = "auction_delay";
 pub const LOCKED_FUNDS_PERIOD_KEY: &str = "locked_funds_period";
 /// Unbonding delay expressed in eras.
 pub const UNBONDING_DELAY_KEY: &str = "unbonding_delay";
+/// Named key under which a validator's account stores the purse its rewards are paid into, if
+/// separate reward purses are allowed by the chainspec.
+pub const REWARD_PURSE_KEY: &str = "reward_purse";
@@ -11,11 +11,12 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     bytesrepr::{self, FromBytes, ToBytes},
-    CLType, CLTyped, PublicKey, U512,
+    CLType, CLTyped, PublicKey, URef, U512,
 };
 
 const SEIGNIORAGE_ALLOCATION_VALIDATOR_TAG: u8 = 0;
 const SEIGNIORAGE_ALLOCATION_DELEGATOR_TAG: u8 = 1;
+const SEIGNIORAGE_ALLOCATION_VALIDATOR_REWARD_PURSE_TAG: u8 = 2;
 
 /// Information about a seigniorage allocation
 #[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
@@ -39,6 +40,16 @@ pub enum SeigniorageAllocation {
         /// Allocated amount
         amount: U512,
     },
+    /// Info about a seigniorage allocation for a validator, paid into its designated reward purse
+    /// instead of increasing its stake
+    ValidatorRewardPurse {
+        /// Validator's public key
+        validator_public_key: PublicKey,
+        /// Purse the allocated amount was paid into
+        reward_purse: URef,
+        /// Allocated amount
+        amount: U512,
+    },
 }
 
 impl SeigniorageAllocation {
@@ -63,11 +74,25 @@ impl SeigniorageAllocation {
         }
     }
 
+    /// Constructs a [`SeigniorageAllocation::ValidatorRewardPurse`]
+    pub const fn validator_reward_purse(
+        validator_public_key: PublicKey,
+        reward_purse: URef,
+        amount: U512,
+    ) -> Self {
+        SeigniorageAllocation::ValidatorRewardPurse {
+            validator_public_key,
+            reward_purse,
+            amount,
+        }
+    }
+
     /// Returns the amount for a given seigniorage allocation
     pub fn amount(&self) -> &U512 {
         match self {
             SeigniorageAllocation::Validator { amount, .. } => amount,
             SeigniorageAllocation::Delegator { amount, .. } => amount,
+            SeigniorageAllocation::ValidatorRewardPurse { amount, .. } => amount,
         }
     }
 
@@ -75,6 +100,9 @@ impl SeigniorageAllocation {
         match self {
             SeigniorageAllocation::Validator { .. } => SEIGNIORAGE_ALLOCATION_VALIDATOR_TAG,
             SeigniorageAllocation::Delegator { .. } => SEIGNIORAGE_ALLOCATION_DELEGATOR_TAG,
+            SeigniorageAllocation::ValidatorRewardPurse { .. } => {
+                SEIGNIORAGE_ALLOCATION_VALIDATOR_REWARD_PURSE_TAG
+            }
         }
     }
 }
@@ -102,6 +130,15 @@ impl ToBytes for SeigniorageAllocation {
                         + validator_public_key.serialized_length()
                         + amount.serialized_length()
                 }
+                SeigniorageAllocation::ValidatorRewardPurse {
+                    validator_public_key,
+                    reward_purse,
+                    amount,
+                } => {
+                    validator_public_key.serialized_length()
+                        + reward_purse.serialized_length()
+                        + amount.serialized_length()
+                }
             }
     }
 
@@ -124,6 +161,15 @@ impl ToBytes for SeigniorageAllocation {
                 validator_public_key.write_bytes(writer)?;
                 amount.write_bytes(writer)?;
             }
+            SeigniorageAllocation::ValidatorRewardPurse {
+                validator_public_key,
+                reward_purse,
+                amount,
+            } => {
+                validator_public_key.write_bytes(writer)?;
+                reward_purse.write_bytes(writer)?;
+                amount.write_bytes(writer)?;
+            }
         }
         Ok(())
     }
@@ -154,6 +200,19 @@ impl FromBytes for SeigniorageAllocation {
                     rem,
                 ))
             }
+            SEIGNIORAGE_ALLOCATION_VALIDATOR_REWARD_PURSE_TAG => {
+                let (validator_public_key, rem) = PublicKey::from_bytes(rem)?;
+                let (reward_purse, rem) = URef::from_bytes(rem)?;
+                let (amount, rem) = U512::from_bytes(rem)?;
+                Ok((
+                    SeigniorageAllocation::validator_reward_purse(
+                        validator_public_key,
+                        reward_purse,
+                        amount,
+                    ),
+                    rem,
+                ))
+            }
             _ => Err(bytesrepr::Error::Formatting),
         }
     }
@@ -195,8 +254,8 @@ impl EraInfo {
 
     /// Returns all seigniorage allocations that match the provided public key
     /// using the following criteria:
-    /// * If the match candidate is a validator allocation, the provided public key is matched
-    ///   against the validator public key.
+    /// * If the match candidate is a validator allocation, including one paid into a reward purse,
+    ///   the provided public key is matched against the validator public key.
     /// * If the match candidate is a delegator allocation, the provided public key is matched
     ///   against the delegator public key.
     pub fn select(&self, public_key: PublicKey) -> impl Iterator<Item = &SeigniorageAllocation> {
@@ -206,6 +265,10 @@ impl EraInfo {
                 SeigniorageAllocation::Validator {
                     validator_public_key,
                     ..
+                }
+                | SeigniorageAllocation::ValidatorRewardPurse {
+                    validator_public_key,
+                    ..
                 } => public_key == *validator_public_key,
                 SeigniorageAllocation::Delegator {
                     delegator_public_key,
@@ -261,7 +324,7 @@ pub mod gens {
 
     use crate::{
         crypto::gens::public_key_arb,
-        gens::u512_arb,
+        gens::{u512_arb, uref_arb},
         system::auction::{EraInfo, SeigniorageAllocation},
     };
 
@@ -279,11 +342,25 @@ pub mod gens {
         )
     }
 
+    fn seigniorage_allocation_validator_reward_purse_arb(
+    ) -> impl Strategy<Value = SeigniorageAllocation> {
+        (public_key_arb(), uref_arb(), u512_arb()).prop_map(
+            |(validator_public_key, reward_purse, amount)| {
+                SeigniorageAllocation::validator_reward_purse(
+                    validator_public_key,
+                    reward_purse,
+                    amount,
+                )
+            },
+        )
+    }
+
     /// Creates an arbitrary [`SeignorageAllocation`](crate::system::auction::SeigniorageAllocation)
     pub fn seigniorage_allocation_arb() -> impl Strategy<Value = SeigniorageAllocation> {
         prop_oneof![
             seigniorage_allocation_validator_arb(),
-            seigniorage_allocation_delegator_arb()
+            seigniorage_allocation_delegator_arb(),
+            seigniorage_allocation_validator_reward_purse_arb()
         ]
     }
 
@@ -81,6 +81,17 @@ impl Timestamp {
         Timestamp(self.0.saturating_add(other.0))
     }
 
+    /// Returns the sum of `self` and `other`, or `None` if that would overflow.
+    pub fn checked_add(self, other: TimeDiff) -> Option<Timestamp> {
+        self.0.checked_add(other.0).map(Timestamp)
+    }
+
+    /// Returns the difference between `self` and `other`, or `None` if that would be before the
+    /// epoch.
+    pub fn checked_sub(self, other: TimeDiff) -> Option<Timestamp> {
+        self.0.checked_sub(other.0).map(Timestamp)
+    }
+
     /// Returns the number of trailing zeros in the number of milliseconds since the epoch.
     pub fn trailing_zeros(&self) -> u8 {
         self.0.trailing_zeros() as u8
@@ -93,11 +104,6 @@ impl Timestamp {
     pub fn random(rng: &mut TestRng) -> Self {
         Timestamp(1_596_763_000_000 + rng.gen_range(200_000..1_000_000))
     }
-
-    /// Checked subtraction for timestamps
-    pub fn checked_sub(self, other: TimeDiff) -> Option<Timestamp> {
-        self.0.checked_sub(other.0).map(Timestamp)
-    }
 }
 
 #[cfg(any(feature = "std", test))]
@@ -266,6 +272,23 @@ impl TimeDiff {
     pub fn saturating_mul(self, rhs: u64) -> Self {
         TimeDiff(self.0.saturating_mul(rhs))
     }
+
+    /// Returns the sum, or `TimeDiff(u64::MAX)` if it would overflow.
+    #[must_use]
+    pub fn saturating_add(self, rhs: TimeDiff) -> Self {
+        TimeDiff(self.0.saturating_add(rhs.0))
+    }
+
+    /// Returns the difference, or `TimeDiff(0)` if `rhs` is greater than `self`.
+    #[must_use]
+    pub fn saturating_sub(self, rhs: TimeDiff) -> Self {
+        TimeDiff(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Returns the difference, or `None` if `rhs` is greater than `self`.
+    pub fn checked_sub(self, rhs: TimeDiff) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(TimeDiff)
+    }
 }
 
 impl Add<TimeDiff> for TimeDiff {
@@ -420,4 +443,31 @@ mod tests {
 
         bytesrepr::test_serialization_roundtrip(&timediff);
     }
+
+    #[test]
+    fn checked_and_saturating_arithmetic() {
+        let timestamp = Timestamp::from(100);
+        let max = Timestamp::from(u64::MAX);
+
+        assert_eq!(
+            timestamp.checked_add(TimeDiff::from(5)),
+            Some(Timestamp::from(105))
+        );
+        assert_eq!(max.checked_add(TimeDiff::from(1)), None);
+        assert_eq!(max.saturating_add(TimeDiff::from(1)), max);
+        assert_eq!(timestamp.checked_sub(TimeDiff::from(101)), None);
+        assert_eq!(
+            timestamp.saturating_sub(TimeDiff::from(101)),
+            Timestamp::zero()
+        );
+
+        let diff = TimeDiff::from(10);
+        assert_eq!(diff.saturating_sub(TimeDiff::from(11)), TimeDiff::from(0));
+        assert_eq!(diff.checked_sub(TimeDiff::from(11)), None);
+        assert_eq!(diff.checked_sub(TimeDiff::from(4)), Some(TimeDiff::from(6)));
+        assert_eq!(
+            TimeDiff::from(u64::MAX).saturating_add(diff),
+            TimeDiff::from(u64::MAX)
+        );
+    }
 }
@@ -129,6 +129,7 @@ async fn main() -> Result<(), anyhow::Error> {
         let block_and_execution_effects = execute_finalized_block(
             &engine_state,
             None,
+            None,
             protocol_version,
             execution_pre_state,
             finalized_block,